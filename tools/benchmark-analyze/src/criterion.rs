@@ -0,0 +1,125 @@
+//! Reads the results Criterion writes under `target/criterion`
+//!
+//! Criterion keeps each benchmark in its own directory, nested by group and
+//! parameter, with the latest run in `new/`:
+//!
+//! ```text
+//! target/criterion/{group}/{function}/{parameter}/new/estimates.json
+//!                                                    /benchmark.json
+//!                                                    /sample.json
+//! ```
+//!
+//! Each run becomes a `BenchmarkResult` named by its full Criterion ID, such
+//! as `parse/frontmatter/large`. The `base/` and `change/` directories, which
+//! hold Criterion's own comparison, are ignored.
+
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context};
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use crate::BenchmarkResult;
+
+#[derive(Debug, Deserialize)]
+struct Estimates {
+    mean: Estimate,
+    std_dev: Estimate,
+}
+
+#[derive(Debug, Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BenchmarkId {
+    full_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sample {
+    iters: Vec<f64>,
+}
+
+/// Whether a directory holds Criterion output rather than flat result files
+pub fn is_criterion_dir(dir: &Path) -> bool {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .any(|e| is_latest_estimates(e.path()))
+}
+
+/// Collect the latest run of every benchmark in a Criterion output directory
+pub fn collect_criterion_results(dir: &Path) -> Result<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+
+    for entry in WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| is_latest_estimates(e.path()))
+    {
+        let run_dir = entry.path().parent().expect("estimates.json is in new/");
+        let benchmark_dir = run_dir.parent().unwrap_or(dir);
+
+        let contents = fs::read_to_string(entry.path())
+            .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        let estimates: Estimates = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", entry.path().display()))?;
+
+        results.push(BenchmarkResult {
+            name: benchmark_name(dir, benchmark_dir, run_dir)?,
+            mean: estimates.mean.point_estimate,
+            std_dev: estimates.std_dev.point_estimate,
+            iterations: iterations(run_dir)?,
+        });
+    }
+
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(results)
+}
+
+/// Whether a path is the estimates of a benchmark's latest run
+fn is_latest_estimates(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "estimates.json")
+        && path
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .is_some_and(|name| name == "new")
+}
+
+/// The benchmark's full ID, or its directory relative to the output directory
+///
+/// Criterion rewrites characters it can't use in file names, so the
+/// `benchmark.json` it writes beside the estimates has the real ID.
+fn benchmark_name(root: &Path, benchmark_dir: &Path, run_dir: &Path) -> Result<String> {
+    let id_path = run_dir.join("benchmark.json");
+    if id_path.exists() {
+        let contents = fs::read_to_string(&id_path)
+            .with_context(|| format!("Failed to read {}", id_path.display()))?;
+        let id: BenchmarkId = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", id_path.display()))?;
+        return Ok(id.full_id);
+    }
+
+    let relative = benchmark_dir.strip_prefix(root).unwrap_or(benchmark_dir);
+    Ok(relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Total iterations measured in a run, or 0 without its `sample.json`
+fn iterations(run_dir: &Path) -> Result<u64> {
+    let sample_path = run_dir.join("sample.json");
+    if !sample_path.exists() {
+        return Ok(0);
+    }
+
+    let contents = fs::read_to_string(&sample_path)
+        .with_context(|| format!("Failed to read {}", sample_path.display()))?;
+    let sample: Sample = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", sample_path.display()))?;
+    Ok(sample.iters.iter().sum::<f64>() as u64)
+}
@@ -0,0 +1,85 @@
+//! Pull request comments
+//!
+//! Renders a report as GitHub-flavored markdown for a CI job to post on a
+//! pull request. Regressions are shown in full; improvements, unchanged
+//! benchmarks and trends are collapsed. Sections that don't fit in GitHub's
+//! comment size limit are left out with a note saying so.
+
+use std::fs;
+use std::path::PathBuf;
+use anyhow::Result;
+use common_cli::github::{details, fit_sections};
+
+use crate::Report;
+
+pub use common_cli::github::COMMENT_LIMIT;
+
+/// A report as a pull request comment of at most `limit` characters
+pub fn github_comment(report: &Report, limit: usize) -> String {
+    let status = if report.regressions.is_empty() {
+        "## ✅ No benchmark regressions".to_string()
+    } else {
+        format!(
+            "## ❌ {} benchmark {}",
+            report.regressions.len(),
+            if report.regressions.len() == 1 { "regression" } else { "regressions" }
+        )
+    };
+    let head = format!(
+        "{}\n\n**Compared to base:** 🔺 {} slower, 🔻 {} faster, {} unchanged",
+        status,
+        report.regressions.len(),
+        report.improvements.len(),
+        report.unchanged.len()
+    );
+
+    let mut sections = Vec::new();
+
+    if !report.regressions.is_empty() {
+        let mut section = String::from("| Benchmark | Base | Current | Change |\n|-----------|-----:|--------:|-------:|\n");
+        for regression in &report.regressions {
+            section.push_str(&format!(
+                "| `{}` | {:.2} | {:.2} | 🔺 +{:.1}% |\n",
+                regression.name, regression.baseline, regression.current, regression.percentage
+            ));
+        }
+        sections.push(section.trim_end().to_string());
+    }
+
+    if !report.improvements.is_empty() {
+        let mut rows = String::from("| Benchmark | Base | Current | Change |\n|-----------|-----:|--------:|-------:|\n");
+        for improvement in &report.improvements {
+            rows.push_str(&format!(
+                "| `{}` | {:.2} | {:.2} | 🔻 -{:.1}% |\n",
+                improvement.name, improvement.baseline, improvement.current, improvement.percentage
+            ));
+        }
+        sections.push(details(&format!("🚀 {} faster", report.improvements.len()), rows.trim_end()));
+    }
+
+    if !report.unchanged.is_empty() {
+        let names: Vec<String> = report.unchanged.iter().map(|name| format!("- `{}`", name)).collect();
+        sections.push(details(&format!("➖ {} unchanged", report.unchanged.len()), &names.join("\n")));
+    }
+
+    if !report.trends.is_empty() {
+        let mut rows = String::from("| Benchmark | History | Change Point |\n|-----------|---------|--------------|\n");
+        for trend in &report.trends {
+            let change = match &trend.change_point {
+                Some(change) if change.is_regression() => format!("🔺 +{:.1}% at `{}`", change.percentage, change.commit),
+                Some(change) => format!("🔻 -{:.1}% at `{}`", change.percentage, change.commit),
+                None => "–".to_string(),
+            };
+            rows.push_str(&format!("| `{}` | {} | {} |\n", trend.name, trend.sparkline(), change));
+        }
+        sections.push(details("📈 Trends", rows.trim_end()));
+    }
+
+    fit_sections(head, sections, limit, "section")
+}
+
+/// Write the report as a pull request comment
+pub fn output_github_comment(report: &Report, path: &PathBuf) -> Result<()> {
+    fs::write(path, github_comment(report, COMMENT_LIMIT))?;
+    Ok(())
+}
@@ -0,0 +1,217 @@
+//! Benchmark history and trend analysis
+//!
+//! Each run's results are appended to a JSON Lines file, one line per run,
+//! keyed by commit and timestamp:
+//!
+//! ```text
+//! {"commit":"3f2a9c1","timestamp":"2024-05-01T12:00:00Z","results":[{"name":"parse","mean":1200.0,...}]}
+//! ```
+//!
+//! Comparing one run against one baseline flags noise as often as real
+//! changes. A trend instead looks at the last runs of a benchmark for the
+//! point where its mean shifted: the split that best divides the runs into
+//! two flat segments. The shift counts as a change point when it stands out
+//! from the run-to-run noise within the segments.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::BenchmarkResult;
+
+/// Runs analyzed for a trend unless another window is given
+pub const DEFAULT_WINDOW: usize = 20;
+
+/// Fewest runs on either side of a change point
+pub const MIN_SEGMENT: usize = 3;
+
+/// How many standard deviations of noise a shift must exceed to count
+pub const CHANGE_SIGMAS: f64 = 3.0;
+
+/// Smallest shift, as a percentage of the earlier mean, that counts
+pub const MIN_CHANGE_PERCENTAGE: f64 = 2.0;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The results of one benchmark run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub commit: String,
+    pub timestamp: DateTime<Utc>,
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// Every stored benchmark run, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+/// Where a benchmark's mean shifted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangePoint {
+    /// Position of the first run after the shift in the trend's means
+    pub index: usize,
+    /// Commit of the first run after the shift
+    pub commit: String,
+    pub timestamp: DateTime<Utc>,
+    /// Mean of the runs before the shift
+    pub before: f64,
+    /// Mean of the runs from the shift on
+    pub after: f64,
+    pub percentage: f64,
+}
+
+impl ChangePoint {
+    /// Whether the benchmark got slower
+    pub fn is_regression(&self) -> bool {
+        self.after > self.before
+    }
+}
+
+/// A benchmark's recent runs and the change point among them, if any
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trend {
+    pub name: String,
+    /// Mean of each run, oldest first
+    pub means: Vec<f64>,
+    pub change_point: Option<ChangePoint>,
+}
+
+impl Trend {
+    /// The means as a sparkline, one bar per run
+    pub fn sparkline(&self) -> String {
+        sparkline(&self.means)
+    }
+}
+
+impl History {
+    /// Load the history file, returning an empty history if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let mut entries = Vec::new();
+        for (number, line) in contents.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let entry: HistoryEntry = serde_json::from_str(line)
+                .with_context(|| format!("Failed to parse {} line {}", path.display(), number + 1))?;
+            entries.push(entry);
+        }
+        entries.sort_by_key(|entry| entry.timestamp);
+
+        Ok(Self { entries })
+    }
+
+    /// Append a run to the history file
+    pub fn append(path: &Path, entry: &HistoryEntry) -> Result<()> {
+        let line = serde_json::to_string(entry)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        writeln!(file, "{}", line).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// The trend of a benchmark over its last `window` runs
+    ///
+    /// Returns `None` if the benchmark has never been run.
+    pub fn analyze_trend(&self, benchmark: &str, window: usize) -> Option<Trend> {
+        let runs: Vec<(&HistoryEntry, f64)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .results
+                    .iter()
+                    .find(|result| result.name == benchmark)
+                    .map(|result| (entry, result.mean))
+            })
+            .collect();
+        if runs.is_empty() {
+            return None;
+        }
+
+        let runs = &runs[runs.len().saturating_sub(window)..];
+        let means: Vec<f64> = runs.iter().map(|(_, mean)| *mean).collect();
+        let change_point = find_change_point(&means).map(|index| {
+            let (entry, _) = runs[index];
+            let before = average(&means[..index]);
+            let after = average(&means[index..]);
+            ChangePoint {
+                index,
+                commit: entry.commit.clone(),
+                timestamp: entry.timestamp,
+                before,
+                after,
+                percentage: ((after - before) / before * 100.0).abs(),
+            }
+        });
+
+        Some(Trend {
+            name: benchmark.to_string(),
+            means,
+            change_point,
+        })
+    }
+}
+
+/// The index of the first value after a significant shift in the mean
+///
+/// Tries every split leaving at least `MIN_SEGMENT` values on each side and
+/// keeps the one with the least squared error around the two segment means.
+fn find_change_point(values: &[f64]) -> Option<usize> {
+    if values.len() < MIN_SEGMENT * 2 {
+        return None;
+    }
+
+    let (index, error) = (MIN_SEGMENT..=values.len() - MIN_SEGMENT)
+        .map(|index| (index, squared_error(&values[..index]) + squared_error(&values[index..])))
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    let before = average(&values[..index]);
+    let after = average(&values[index..]);
+    let shift = (after - before).abs();
+    let noise = (error / (values.len() - 2) as f64).sqrt();
+
+    let percentage = if before == 0.0 { f64::INFINITY } else { shift / before.abs() * 100.0 };
+    if shift > CHANGE_SIGMAS * noise && percentage >= MIN_CHANGE_PERCENTAGE {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+fn average(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn squared_error(values: &[f64]) -> f64 {
+    let mean = average(values);
+    values.iter().map(|value| (value - mean).powi(2)).sum()
+}
+
+/// Draw values as a sparkline scaled between their minimum and maximum
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|value| {
+            if max > min {
+                SPARKS[((value - min) / (max - min) * (SPARKS.len() - 1) as f64).round() as usize]
+            } else {
+                SPARKS[0]
+            }
+        })
+        .collect()
+}
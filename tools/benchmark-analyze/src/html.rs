@@ -0,0 +1,226 @@
+//! HTML benchmark reports
+//!
+//! A single self-contained page for CI artifacts: the comparison tables sort
+//! by any column when their header is clicked, each trend gets an inline SVG
+//! chart of its recent runs, and benchmarks with a flamegraph in the results
+//! directory link to it.
+//!
+//! Flamegraphs are found by name. `flamegraph.svg` files, such as the ones
+//! Criterion's profilers write to `{benchmark}/profile/`, belong to the
+//! benchmark whose directory holds them; any other `*flamegraph*.svg` file
+//! belongs to the benchmark named by the rest of its file name.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use walkdir::WalkDir;
+
+use crate::{Report, Trend};
+
+const CHART_WIDTH: f64 = 240.0;
+const CHART_HEIGHT: f64 = 48.0;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+table{border-collapse:collapse;margin-bottom:2rem}\
+th,td{border:1px solid #ccc;padding:.4rem .8rem;text-align:left}\
+th{background:#f4f4f4;cursor:pointer}\
+.regression{color:#b00020}.improvement{color:#1b7f3b}\
+svg polyline{fill:none;stroke:#3366cc;stroke-width:2}svg circle{fill:#b00020}";
+
+const SORT_SCRIPT: &str = "document.querySelectorAll('table.sortable th').forEach(function(th,i){\
+th.addEventListener('click',function(){\
+var body=th.closest('table').tBodies[0],rows=Array.from(body.rows),asc=th.dataset.asc!=='true';\
+rows.sort(function(a,b){var x=a.cells[i].dataset.value||a.cells[i].textContent,y=b.cells[i].dataset.value||b.cells[i].textContent;\
+var n=parseFloat(x)-parseFloat(y);return (isNaN(n)?x.localeCompare(y):n)*(asc?1:-1);});\
+rows.forEach(function(row){body.appendChild(row);});th.dataset.asc=asc;});});";
+
+/// Find flamegraph SVGs in a results directory, by benchmark name
+pub fn find_flamegraphs(dir: &Path) -> BTreeMap<String, PathBuf> {
+    let mut flamegraphs = BTreeMap::new();
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_lowercase();
+        if !file_name.ends_with(".svg") || !file_name.contains("flamegraph") {
+            continue;
+        }
+
+        let name = if file_name == "flamegraph.svg" {
+            let parent = path.parent().unwrap_or(dir);
+            let benchmark_dir = if parent.file_name().is_some_and(|name| name == "profile") {
+                parent.parent().unwrap_or(dir)
+            } else {
+                parent
+            };
+            relative_name(dir, benchmark_dir)
+        } else {
+            let stem = file_name.trim_end_matches(".svg").replace("flamegraph", "");
+            stem.trim_matches(|c: char| c == '-' || c == '_' || c == '.').to_string()
+        };
+
+        if !name.is_empty() {
+            flamegraphs.insert(name, path.to_path_buf());
+        }
+    }
+
+    flamegraphs
+}
+
+/// Write the report as an HTML page, linking to flamegraphs found in `results_dir`
+pub fn output_html_report(report: &Report, results_dir: Option<&Path>, path: &PathBuf) -> Result<()> {
+    let flamegraphs = results_dir.map(find_flamegraphs).unwrap_or_default();
+    let report_dir = path.parent().unwrap_or(Path::new(""));
+    let flamegraph_cell = |name: &str| match flamegraphs.get(name) {
+        Some(flamegraph) => {
+            let href = flamegraph.strip_prefix(report_dir).unwrap_or(flamegraph);
+            format!(
+                "<td><a href=\"{}\">flamegraph</a></td>",
+                escape(&href.to_string_lossy().replace('\\', "/"))
+            )
+        }
+        None => "<td></td>".to_string(),
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Benchmark Analysis Report</title>\n");
+    let _ = writeln!(html, "<style>{}</style>", STYLE);
+    html.push_str("</head>\n<body>\n<h1>Benchmark Analysis Report</h1>\n");
+
+    // Regressions and improvements share a layout
+    let comparisons = [
+        ("Performance Regressions", "regression", "+", report.regressions.iter().map(|r| (&r.name, r.baseline, r.current, r.percentage)).collect::<Vec<_>>()),
+        ("Performance Improvements", "improvement", "-", report.improvements.iter().map(|i| (&i.name, i.baseline, i.current, i.percentage)).collect::<Vec<_>>()),
+    ];
+    for (title, class, sign, rows) in comparisons {
+        if rows.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(html, "<h2>{}</h2>", title);
+        html.push_str("<table class=\"sortable\">\n<thead><tr><th>Benchmark</th><th>Baseline</th><th>Current</th><th>Change</th><th>Profile</th></tr></thead>\n<tbody>\n");
+        for (name, baseline, current, percentage) in rows {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td data-value=\"{}\">{:.2}</td><td data-value=\"{}\">{:.2}</td><td class=\"{}\" data-value=\"{}\">{}{:.1}%</td>{}</tr>",
+                escape(name),
+                baseline,
+                baseline,
+                current,
+                current,
+                class,
+                percentage,
+                sign,
+                percentage,
+                flamegraph_cell(name)
+            );
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    if !report.unchanged.is_empty() {
+        html.push_str("<h2>Unchanged Benchmarks</h2>\n");
+        html.push_str("<table class=\"sortable\">\n<thead><tr><th>Benchmark</th><th>Profile</th></tr></thead>\n<tbody>\n");
+        for name in &report.unchanged {
+            let _ = writeln!(html, "<tr><td>{}</td>{}</tr>", escape(name), flamegraph_cell(name));
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    if !report.trends.is_empty() {
+        html.push_str("<h2>Trends</h2>\n");
+        html.push_str("<table class=\"sortable\">\n<thead><tr><th>Benchmark</th><th>History</th><th>Latest</th><th>Change Point</th><th>Profile</th></tr></thead>\n<tbody>\n");
+        for trend in &report.trends {
+            let latest = trend.means.last().copied().unwrap_or_default();
+            let change = match &trend.change_point {
+                Some(change) if change.is_regression() => format!(
+                    "<td class=\"regression\" data-value=\"{}\">Regressed {:.1}% at {}</td>",
+                    change.percentage,
+                    change.percentage,
+                    escape(&change.commit)
+                ),
+                Some(change) => format!(
+                    "<td class=\"improvement\" data-value=\"-{}\">Improved {:.1}% at {}</td>",
+                    change.percentage,
+                    change.percentage,
+                    escape(&change.commit)
+                ),
+                None => "<td data-value=\"0\">-</td>".to_string(),
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td data-value=\"{}\">{:.2}</td>{}{}</tr>",
+                escape(&trend.name),
+                trend_chart(trend),
+                latest,
+                latest,
+                change,
+                flamegraph_cell(&trend.name)
+            );
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    let _ = writeln!(html, "<script>{}</script>", SORT_SCRIPT);
+    html.push_str("</body>\n</html>\n");
+
+    fs::write(path, html)?;
+    Ok(())
+}
+
+/// An inline SVG line chart of a trend's means, marking its change point
+pub fn trend_chart(trend: &Trend) -> String {
+    let min = trend.means.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = trend.means.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let step = if trend.means.len() > 1 { CHART_WIDTH / (trend.means.len() - 1) as f64 } else { 0.0 };
+    let point = |index: usize, mean: f64| {
+        let y = if max > min { (max - mean) / (max - min) * CHART_HEIGHT } else { CHART_HEIGHT / 2.0 };
+        (index as f64 * step, y)
+    };
+
+    let points: Vec<String> = trend
+        .means
+        .iter()
+        .enumerate()
+        .map(|(index, mean)| {
+            let (x, y) = point(index, *mean);
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect();
+
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" viewBox=\"-4 -4 {} {}\" role=\"img\" aria-label=\"{} history\"><polyline points=\"{}\"/>",
+        CHART_WIDTH + 8.0,
+        CHART_HEIGHT + 8.0,
+        CHART_WIDTH + 8.0,
+        CHART_HEIGHT + 8.0,
+        escape(&trend.name),
+        points.join(" ")
+    );
+    if let Some(change) = &trend.change_point {
+        if let Some(mean) = trend.means.get(change.index) {
+            let (x, y) = point(change.index, *mean);
+            let _ = write!(svg, "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\"><title>{}</title></circle>", x, y, escape(&change.commit));
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn relative_name(root: &Path, dir: &Path) -> String {
+    dir.strip_prefix(root)
+        .unwrap_or(dir)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -4,7 +4,17 @@ use anyhow::{Result, Context};
 use walkdir::WalkDir;
 use std::fs;
 
-#[derive(Debug, Serialize, Deserialize)]
+pub mod criterion;
+pub mod github;
+pub mod history;
+pub mod html;
+
+pub use criterion::{collect_criterion_results, is_criterion_dir};
+pub use github::{github_comment, output_github_comment, COMMENT_LIMIT};
+pub use history::{sparkline, ChangePoint, History, HistoryEntry, Trend};
+pub use html::{find_flamegraphs, output_html_report, trend_chart};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResult {
     pub name: String,
     pub mean: f64,
@@ -17,6 +27,9 @@ pub struct Report {
     pub regressions: Vec<Regression>,
     pub improvements: Vec<Improvement>,
     pub unchanged: Vec<String>,
+    /// Each benchmark's recent history, when results are being stored
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trends: Vec<Trend>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,7 +48,12 @@ pub struct Improvement {
     pub percentage: f64,
 }
 
+/// Collect benchmark results from a directory of flat JSON results, or of Criterion output
 pub fn collect_results(dir: &PathBuf) -> Result<Vec<BenchmarkResult>> {
+    if is_criterion_dir(dir) {
+        return collect_criterion_results(dir);
+    }
+
     let mut results = Vec::new();
 
     for entry in WalkDir::new(dir)
@@ -64,6 +82,7 @@ pub fn generate_report(
         regressions: Vec::new(),
         improvements: Vec::new(),
         unchanged: Vec::new(),
+        trends: Vec::new(),
     };
 
     if let Some(baseline) = baseline {
@@ -152,6 +171,31 @@ pub fn output_markdown_report(report: &Report, path: &PathBuf) -> Result<()> {
         markdown.push('\n');
     }
 
+    // Add trends section
+    if !report.trends.is_empty() {
+        markdown.push_str("## Trends\n\n");
+        markdown.push_str("| Benchmark | Trend | Latest | Change Point |\n");
+        markdown.push_str("|-----------|-------|--------|--------------|\n");
+
+        for trend in &report.trends {
+            let change = match &trend.change_point {
+                Some(change) if change.is_regression() => {
+                    format!("Regressed {:.1}% at {}", change.percentage, change.commit)
+                }
+                Some(change) => format!("Improved {:.1}% at {}", change.percentage, change.commit),
+                None => "-".to_string(),
+            };
+            markdown.push_str(&format!(
+                "| {} | {} | {:.2} | {} |\n",
+                trend.name,
+                trend.sparkline(),
+                trend.means.last().copied().unwrap_or_default(),
+                change
+            ));
+        }
+        markdown.push('\n');
+    }
+
     fs::write(path, markdown)?;
     Ok(())
 }
@@ -1,16 +1,17 @@
 use std::path::PathBuf;
 use clap::Parser;
 use anyhow::Result;
-use benchmark_analyze::{collect_results, generate_report, output_json_report, output_markdown_report};
+use benchmark_analyze::history::DEFAULT_WINDOW;
+use benchmark_analyze::{collect_results, generate_report, output_github_comment, output_html_report, output_json_report, output_markdown_report, History, HistoryEntry};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Directory containing baseline benchmark results
+    /// Directory containing baseline benchmark results (flat JSON or Criterion output)
     #[arg(short, long)]
     baseline: Option<PathBuf>,
 
-    /// Directory containing current benchmark results
+    /// Directory containing current benchmark results, such as target/criterion
     #[arg(short, long)]
     current: PathBuf,
 
@@ -22,10 +23,30 @@ struct Args {
     #[arg(short, long, default_value = "benchmark_report.md")]
     report: PathBuf,
 
+    /// JSON Lines file to append the current results to and analyze trends from
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Commit the current results were measured at (default: the current git HEAD)
+    #[arg(long)]
+    commit: Option<String>,
+
+    /// Number of most recent runs to analyze for each trend
+    #[arg(long, default_value_t = DEFAULT_WINDOW)]
+    window: usize,
+
     /// Output JSON format
     #[arg(short, long)]
     json: bool,
 
+    /// Output an HTML page with sortable tables, trend charts and flamegraph links
+    #[arg(long, conflicts_with = "json")]
+    html: bool,
+
+    /// Output a GitHub pull request comment
+    #[arg(long, conflicts_with_all = ["json", "html"])]
+    github: bool,
+
     /// Enable verbose output
     #[arg(short, long)]
     verbose: bool,
@@ -43,14 +64,45 @@ fn main() -> Result<()> {
     };
 
     // Generate report
-    let report = generate_report(&current_results, baseline_results.as_ref().map(|v| &**v), args.threshold);
+    let mut report = generate_report(&current_results, baseline_results.as_ref().map(|v| &**v), args.threshold);
+
+    // Store the results, then look for change points in each benchmark's history
+    if let Some(history_path) = &args.history {
+        let entry = HistoryEntry {
+            commit: args.commit.clone().unwrap_or_else(current_commit),
+            timestamp: chrono::Utc::now(),
+            results: current_results.clone(),
+        };
+        History::append(history_path, &entry)?;
+
+        let history = History::load(history_path)?;
+        report.trends = current_results
+            .iter()
+            .filter_map(|result| history.analyze_trend(&result.name, args.window))
+            .collect();
+    }
 
     // Output report
     if args.json {
         output_json_report(&report, &args.report)?;
+    } else if args.github {
+        output_github_comment(&report, &args.report)?;
+    } else if args.html {
+        output_html_report(&report, Some(&args.current), &args.report)?;
     } else {
         output_markdown_report(&report, &args.report)?;
     }
 
     Ok(())
 }
+
+/// The short hash of the checked out commit, or "unknown" outside a git repository
+fn current_commit() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
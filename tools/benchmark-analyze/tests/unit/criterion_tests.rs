@@ -0,0 +1,86 @@
+//! Tests for reading Criterion output
+
+use benchmark_analyze::*;
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write_run(dir: &Path, mean: f64, std_dev: f64, full_id: Option<&str>, iters: Option<&[f64]>) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join("estimates.json"),
+        format!(
+            r#"{{"mean":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":1.0,"upper_bound":2.0}},"point_estimate":{},"standard_error":0.5}},
+               "median":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":1.0,"upper_bound":2.0}},"point_estimate":1.5,"standard_error":0.5}},
+               "std_dev":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":1.0,"upper_bound":2.0}},"point_estimate":{},"standard_error":0.1}},
+               "slope":null}}"#,
+            mean, std_dev
+        ),
+    )?;
+    if let Some(full_id) = full_id {
+        fs::write(dir.join("benchmark.json"), format!(r#"{{"group_id":"g","full_id":"{}","directory_name":"d","title":"t"}}"#, full_id))?;
+    }
+    if let Some(iters) = iters {
+        fs::write(dir.join("sample.json"), serde_json::json!({"sampling_mode": "Linear", "iters": iters, "times": iters}).to_string())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_collect_criterion_results() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("criterion");
+
+    write_run(&root.join("parse").join("frontmatter").join("new"), 1200.0, 15.0, Some("parse/frontmatter"), Some(&[10.0, 20.0, 30.0]))?;
+    write_run(&root.join("render").join("html").join("large").join("new"), 5400.5, 80.25, None, None)?;
+    // Criterion's comparison with the previous run isn't a result
+    write_run(&root.join("parse").join("frontmatter").join("base"), 9999.0, 1.0, Some("parse/frontmatter"), None)?;
+    write_run(&root.join("parse").join("frontmatter").join("change"), 0.1, 0.1, None, None)?;
+    fs::create_dir_all(root.join("report"))?;
+    fs::write(root.join("report").join("index.html"), "<html></html>")?;
+
+    assert!(is_criterion_dir(&root));
+    let results = collect_results(&root)?;
+    assert_eq!(results.len(), 2);
+
+    assert_eq!(results[0].name, "parse/frontmatter");
+    assert_eq!(results[0].mean, 1200.0);
+    assert_eq!(results[0].std_dev, 15.0);
+    assert_eq!(results[0].iterations, 60);
+
+    // Without benchmark.json the name comes from the directories
+    assert_eq!(results[1].name, "render/html/large");
+    assert_eq!(results[1].mean, 5400.5);
+    assert_eq!(results[1].iterations, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_criterion_results_compare_against_a_baseline() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let baseline = temp_dir.path().join("baseline");
+    let current = temp_dir.path().join("current");
+    write_run(&baseline.join("build").join("new"), 100.0, 1.0, Some("build"), None)?;
+    write_run(&current.join("build").join("new"), 150.0, 1.0, Some("build"), None)?;
+
+    let baseline = collect_criterion_results(&baseline)?;
+    let current = collect_criterion_results(&current)?;
+    let report = generate_report(&current, Some(&baseline), 10.0);
+    assert_eq!(report.regressions.len(), 1);
+    assert_eq!(report.regressions[0].percentage, 50.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_flat_results_are_not_criterion_output() -> Result<()> {
+    let temp_dir = tempdir()?;
+    fs::write(temp_dir.path().join("bench.json"), r#"{"name":"bench","mean":1.0,"std_dev":0.1,"iterations":10}"#)?;
+
+    assert!(!is_criterion_dir(temp_dir.path()));
+    assert_eq!(collect_results(&temp_dir.path().to_path_buf())?[0].name, "bench");
+
+    Ok(())
+}
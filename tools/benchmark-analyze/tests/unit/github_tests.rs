@@ -0,0 +1,48 @@
+//! Tests for pull request comments
+
+use benchmark_analyze::*;
+
+fn report() -> Report {
+    Report {
+        regressions: vec![Regression {
+            name: "parse".to_string(),
+            baseline: 100.0,
+            current: 125.0,
+            percentage: 25.0,
+        }],
+        improvements: vec![Improvement {
+            name: "render".to_string(),
+            baseline: 200.0,
+            current: 150.0,
+            percentage: 25.0,
+        }],
+        unchanged: vec!["lex".to_string(), "write".to_string()],
+        trends: vec![],
+    }
+}
+
+#[test]
+fn test_github_comment() {
+    let comment = github_comment(&report(), COMMENT_LIMIT);
+
+    assert!(comment.starts_with("## ❌ 1 benchmark regression\n\n**Compared to base:** 🔺 1 slower, 🔻 1 faster, 2 unchanged"));
+    assert!(comment.contains("| `parse` | 100.00 | 125.00 | 🔺 +25.0% |"));
+    assert!(comment.contains("<details>\n<summary>🚀 1 faster</summary>\n\n| Benchmark |"));
+    assert!(comment.contains("| `render` | 200.00 | 150.00 | 🔻 -25.0% |"));
+    assert!(comment.contains("<summary>➖ 2 unchanged</summary>\n\n- `lex`\n- `write`\n\n</details>"));
+    assert!(!comment.contains("Trends"));
+
+    let empty = github_comment(&generate_report(&[], None, 10.0), COMMENT_LIMIT);
+    assert_eq!(empty, "## ✅ No benchmark regressions\n\n**Compared to base:** 🔺 0 slower, 🔻 0 faster, 0 unchanged");
+}
+
+#[test]
+fn test_github_comment_is_truncated_to_the_limit() {
+    let full = github_comment(&report(), COMMENT_LIMIT);
+    let limit = full.chars().count() - 10;
+
+    let comment = github_comment(&report(), limit);
+    assert!(comment.chars().count() <= limit);
+    assert!(comment.contains("🚀 1 faster"));
+    assert!(comment.ends_with("_1 more section not shown._"));
+}
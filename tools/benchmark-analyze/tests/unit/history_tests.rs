@@ -0,0 +1,97 @@
+//! Tests for benchmark history and trends
+
+use benchmark_analyze::*;
+use anyhow::Result;
+use chrono::{Duration, TimeZone, Utc};
+use std::fs;
+use tempfile::tempdir;
+
+fn entry(run: i64, means: &[(&str, f64)]) -> HistoryEntry {
+    HistoryEntry {
+        commit: format!("c{}", run),
+        timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::hours(run),
+        results: means
+            .iter()
+            .map(|(name, mean)| BenchmarkResult {
+                name: name.to_string(),
+                mean: *mean,
+                std_dev: 1.0,
+                iterations: 100,
+            })
+            .collect(),
+    }
+}
+
+fn history(means: &[f64]) -> History {
+    History {
+        entries: means.iter().enumerate().map(|(run, mean)| entry(run as i64, &[("build", *mean)])).collect(),
+    }
+}
+
+#[test]
+fn test_history_appends_and_loads_runs() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let path = temp_dir.path().join("history.jsonl");
+
+    assert!(History::load(&path)?.entries.is_empty());
+    History::append(&path, &entry(1, &[("build", 10.0), ("parse", 2.0)]))?;
+    History::append(&path, &entry(0, &[("build", 12.0)]))?;
+    assert_eq!(fs::read_to_string(&path)?.lines().count(), 2);
+
+    // Runs are ordered by time, not by when they were stored
+    let history = History::load(&path)?;
+    let commits: Vec<&str> = history.entries.iter().map(|entry| entry.commit.as_str()).collect();
+    assert_eq!(commits, vec!["c0", "c1"]);
+    assert_eq!(history.analyze_trend("parse", 10).unwrap().means, vec![2.0]);
+    assert!(history.analyze_trend("missing", 10).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_trend_finds_a_step_change() {
+    let means = [100.0, 102.0, 99.0, 101.0, 100.0, 130.0, 131.0, 129.0, 130.0];
+    let trend = history(&means).analyze_trend("build", 20).unwrap();
+
+    let change = trend.change_point.unwrap();
+    assert_eq!(change.commit, "c5");
+    assert!(change.is_regression());
+    assert_eq!(change.before, 100.4);
+    assert_eq!(change.after, 130.0);
+    assert!((change.percentage - 29.48).abs() < 0.01);
+}
+
+#[test]
+fn test_trend_ignores_noise_and_old_runs() {
+    // A single outlier is noise, not a change point
+    let noisy = [100.0, 104.0, 97.0, 118.0, 101.0, 99.0, 103.0, 98.0];
+    assert!(history(&noisy).analyze_trend("build", 20).unwrap().change_point.is_none());
+
+    // The window leaves out the old, slower runs
+    let improved = [150.0, 150.0, 150.0, 100.0, 101.0, 99.0, 100.0, 101.0, 100.0];
+    assert!(history(&improved).analyze_trend("build", 20).unwrap().change_point.is_some());
+    let trend = history(&improved).analyze_trend("build", 6).unwrap();
+    assert_eq!(trend.means.len(), 6);
+    assert!(trend.change_point.is_none());
+
+    // Too few runs to split
+    assert!(history(&[100.0, 200.0]).analyze_trend("build", 20).unwrap().change_point.is_none());
+}
+
+#[test]
+fn test_sparkline_and_trend_report() -> Result<()> {
+    assert_eq!(sparkline(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]), "▁▂▃▄▅▆▇█");
+    assert_eq!(sparkline(&[5.0, 5.0]), "▁▁");
+
+    let temp_dir = tempdir()?;
+    let md_path = temp_dir.path().join("report.md");
+    let mut report = generate_report(&[], None, 10.0);
+    report.trends = vec![history(&[100.0, 101.0, 100.0, 150.0, 151.0, 150.0]).analyze_trend("build", 20).unwrap()];
+    output_markdown_report(&report, &md_path)?;
+
+    let markdown = fs::read_to_string(&md_path)?;
+    assert!(markdown.contains("## Trends"));
+    assert!(markdown.contains("| build | ▁▁▁███ | 150.00 | Regressed 49.8% at c3 |"));
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Tests for HTML reports
+
+use benchmark_analyze::*;
+use anyhow::Result;
+use chrono::{Duration, TimeZone, Utc};
+use std::fs;
+use tempfile::tempdir;
+
+fn trend(means: &[f64]) -> Trend {
+    let history = History {
+        entries: means
+            .iter()
+            .enumerate()
+            .map(|(run, mean)| HistoryEntry {
+                commit: format!("c{}", run),
+                timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::hours(run as i64),
+                results: vec![BenchmarkResult {
+                    name: "render/html".to_string(),
+                    mean: *mean,
+                    std_dev: 1.0,
+                    iterations: 100,
+                }],
+            })
+            .collect(),
+    };
+    history.analyze_trend("render/html", 20).unwrap()
+}
+
+#[test]
+fn test_find_flamegraphs() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("render").join("html").join("profile"))?;
+    fs::write(root.join("render").join("html").join("profile").join("flamegraph.svg"), "<svg/>")?;
+    fs::write(root.join("parse-flamegraph.svg"), "<svg/>")?;
+    fs::write(root.join("chart.svg"), "<svg/>")?;
+
+    let flamegraphs = find_flamegraphs(root);
+    let names: Vec<&str> = flamegraphs.keys().map(String::as_str).collect();
+    assert_eq!(names, vec!["parse", "render/html"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_html_report_links_flamegraphs_and_charts_trends() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path();
+    fs::write(root.join("parse-flamegraph.svg"), "<svg/>")?;
+
+    let report = Report {
+        regressions: vec![Regression {
+            name: "parse".to_string(),
+            baseline: 100.0,
+            current: 125.0,
+            percentage: 25.0,
+        }],
+        improvements: vec![],
+        unchanged: vec!["lex<fast>".to_string()],
+        trends: vec![trend(&[100.0, 101.0, 100.0, 150.0, 151.0, 150.0])],
+    };
+
+    let html_path = root.join("report.html");
+    output_html_report(&report, Some(root), &html_path)?;
+    let html = fs::read_to_string(&html_path)?;
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h2>Performance Regressions</h2>"));
+    assert!(html.contains("<td class=\"regression\" data-value=\"25\">+25.0%</td><td><a href=\"parse-flamegraph.svg\">flamegraph</a></td>"));
+    assert!(!html.contains("Performance Improvements"));
+    assert!(html.contains("<td>lex&lt;fast&gt;</td><td></td>"));
+    assert!(html.contains("Regressed 49.8% at c3"));
+    assert!(html.contains("table.sortable"));
+
+    // The chart marks the first run after the change point
+    let chart = trend_chart(&report.trends[0]);
+    assert!(chart.contains("<polyline points=\"0.0,48.0 48.0,47.1 96.0,48.0 144.0,0.9 192.0,0.0 240.0,0.9\"/>"));
+    assert!(chart.contains("<circle cx=\"144.0\" cy=\"0.9\" r=\"3\"><title>c3</title></circle>"));
+
+    Ok(())
+}
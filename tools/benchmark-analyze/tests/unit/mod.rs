@@ -1,3 +1,6 @@
 // Unit tests for benchmark-analyze
 
-mod tests_tests;
\ No newline at end of file
+mod tests_tests;mod criterion_tests;
+mod history_tests;
+mod html_tests;
+mod github_tests;
@@ -210,6 +210,7 @@ mod tests {
             ],
             improvements: vec![],
             unchanged: vec![],
+            trends: vec![],
         };
 
         let json_path = dir_path.join("report.json");
@@ -244,6 +245,7 @@ mod tests {
             regressions: vec![],
             improvements: vec![],
             unchanged: vec![],
+            trends: vec![],
         };
 
         let json_path = dir_path.join("report.json");
@@ -1,6 +1,8 @@
 use clap::Args;
 use std::path::PathBuf;
 
+use crate::output::{OutputFormat, OutputFormatter};
+
 /// Common arguments for content-related commands
 #[derive(Args, Debug, Clone)]
 pub struct ContentArgs {
@@ -80,9 +82,16 @@ pub struct ForceArgs {
 /// Common arguments for output format
 #[derive(Args, Debug, Clone)]
 pub struct OutputFormatArgs {
-    /// Output format (text, json, yaml)
-    #[arg(short, long, default_value = "text")]
-    pub format: String,
+    /// Output format
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Plain)]
+    pub format: OutputFormat,
+}
+
+impl OutputFormatArgs {
+    /// A formatter for the chosen format
+    pub fn formatter(&self) -> OutputFormatter {
+        OutputFormatter::new(self.format)
+    }
 }
 
 /// Common arguments for verbose output
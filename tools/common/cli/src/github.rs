@@ -0,0 +1,34 @@
+//! # Pull Request Comments
+//!
+//! Building blocks for the GitHub-flavored markdown comments CI jobs post on
+//! pull requests: collapsed sections, and a heading followed by as many
+//! sections as fit in GitHub's comment size limit.
+
+/// Most characters GitHub accepts in a comment
+pub const COMMENT_LIMIT: usize = 65_536;
+
+/// A collapsed section
+pub fn details(summary: &str, body: &str) -> String {
+    format!("<details>\n<summary>{}</summary>\n\n{}\n\n</details>", summary, body)
+}
+
+/// Join a heading and sections, leaving out sections past `limit` characters
+///
+/// Sections that are left out are counted in a closing note, such as
+/// `_2 more files not shown._` for the noun `file`.
+pub fn fit_sections(head: String, sections: Vec<String>, limit: usize, noun: &str) -> String {
+    let total = sections.len();
+    let mut comment = head;
+    for (shown, section) in sections.into_iter().enumerate() {
+        let remaining = total - shown;
+        let noun = if remaining == 1 { noun.to_string() } else { format!("{}s", noun) };
+        let note = format!("\n\n_{} more {} not shown._", remaining, noun);
+        if comment.chars().count() + section.chars().count() + 2 + note.chars().count() > limit {
+            comment.push_str(&note);
+            return comment;
+        }
+        comment.push_str("\n\n");
+        comment.push_str(&section);
+    }
+    comment
+}
@@ -9,6 +9,8 @@ use std::path::PathBuf;
 pub mod factory;
 // Add the args module
 pub mod args;
+pub mod github;
+pub mod output;
 
 // Re-export common argument structs for easier access
 pub use args::{
@@ -16,6 +18,7 @@ pub use args::{
     ForceArgs, OutputFormatArgs, VerboseArgs, FileArgs, DirectoryArgs,
     RecursiveArgs, LimitArgs, SearchArgs, SortArgs, PaginationArgs,
 };
+pub use output::{OutputFormat, OutputFormatter};
 
 /// Common trait for command execution
 #[cfg(feature = "command")]
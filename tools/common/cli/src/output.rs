@@ -0,0 +1,119 @@
+//! Machine-readable output
+//!
+//! Tools print a report in one of three formats chosen with `--format`:
+//! `plain` for people, and `json` or `yaml` for scripts and CI. The report
+//! type decides what the plain text looks like through [`DisplayResult`];
+//! the structured formats serialize it as is, so their shape is stable
+//! across releases in a way the plain text isn't.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::DisplayResult;
+
+/// How a tool prints its report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "command", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    #[cfg_attr(feature = "command", value(alias = "text"))]
+    Plain,
+    /// Pretty-printed JSON
+    Json,
+    /// YAML
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Whether the format is for scripts rather than people
+    pub fn is_structured(self) -> bool {
+        self != OutputFormat::Plain
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Plain => write!(f, "plain"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "plain" | "text" => Ok(OutputFormat::Plain),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            other => Err(anyhow::anyhow!("Unknown output format '{}': expected plain, json or yaml", other)),
+        }
+    }
+}
+
+/// Prints reports in the format a user asked for
+///
+/// In the structured formats a tool should print nothing but the report on
+/// standard output, so progress messages are skipped when
+/// [`OutputFormatter::is_plain`] is false.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputFormatter {
+    format: OutputFormat,
+}
+
+impl OutputFormatter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    /// The format reports are printed in
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Whether reports are printed for people, and progress messages may be too
+    pub fn is_plain(&self) -> bool {
+        !self.format.is_structured()
+    }
+
+    /// Render a report in the chosen format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be serialized
+    pub fn render<T: Serialize + DisplayResult>(&self, report: &T) -> Result<String> {
+        match self.format {
+            OutputFormat::Plain => Ok(report.to_display()),
+            OutputFormat::Json => serde_json::to_string_pretty(report).context("Failed to serialize report as JSON"),
+            OutputFormat::Yaml => serde_yaml::to_string(report).context("Failed to serialize report as YAML"),
+        }
+    }
+
+    /// Print a report to standard output in the chosen format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be serialized
+    pub fn print<T: Serialize + DisplayResult>(&self, report: &T) -> Result<()> {
+        let output = self.render(report)?;
+        if output.ends_with('\n') {
+            print!("{}", output);
+        } else {
+            println!("{}", output);
+        }
+        Ok(())
+    }
+}
+
+impl From<OutputFormat> for OutputFormatter {
+    fn from(format: OutputFormat) -> Self {
+        Self::new(format)
+    }
+}
@@ -0,0 +1,7 @@
+//! Test modules for common-cli
+
+// Unit tests
+#[path = "unit/output_tests.rs"]
+mod output_tests;
+#[path = "unit/github_tests.rs"]
+mod github_tests;
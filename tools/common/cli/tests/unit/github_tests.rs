@@ -0,0 +1,20 @@
+use common_cli::github::{details, fit_sections, COMMENT_LIMIT};
+
+#[test]
+fn test_details_collapses_the_body() {
+    assert_eq!(details("Summary", "- one"), "<details>\n<summary>Summary</summary>\n\n- one\n\n</details>");
+}
+
+#[test]
+fn test_fit_sections_counts_what_is_left_out() {
+    let sections = vec!["a".repeat(10), "b".repeat(10), "c".repeat(10)];
+
+    let comment = fit_sections("Head".to_string(), sections.clone(), COMMENT_LIMIT, "file");
+    assert_eq!(comment, format!("Head\n\n{}", sections.join("\n\n")));
+
+    let comment = fit_sections("Head".to_string(), sections.clone(), 50, "file");
+    assert_eq!(comment, format!("Head\n\n{}\n\n_2 more files not shown._", sections[0]));
+
+    let comment = fit_sections("Head".to_string(), sections, 60, "section");
+    assert!(comment.ends_with("_1 more section not shown._"));
+}
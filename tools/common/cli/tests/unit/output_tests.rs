@@ -0,0 +1,52 @@
+use common_cli::{DisplayResult, OutputFormat, OutputFormatter};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Report {
+    files: usize,
+    names: Vec<String>,
+}
+
+impl DisplayResult for Report {
+    fn to_display(&self) -> String {
+        format!("{} files: {}", self.files, self.names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod output_tests {
+    use super::*;
+
+    fn report() -> Report {
+        Report { files: 2, names: vec!["a.md".to_string(), "b.md".to_string()] }
+    }
+
+    #[test]
+    fn test_plain_uses_display() {
+        let formatter = OutputFormatter::new(OutputFormat::Plain);
+        assert!(formatter.is_plain());
+        assert_eq!(formatter.render(&report()).unwrap(), "2 files: a.md, b.md");
+    }
+
+    #[test]
+    fn test_structured_formats_serialize_the_report() {
+        let json = OutputFormatter::new(OutputFormat::Json).render(&report()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["files"], 2);
+        assert_eq!(value["names"][1], "b.md");
+
+        let yaml = OutputFormatter::new(OutputFormat::Yaml).render(&report()).unwrap();
+        assert!(yaml.contains("files: 2"));
+        assert!(yaml.contains("- a.md"));
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Plain);
+        assert_eq!("YML".parse::<OutputFormat>().unwrap(), OutputFormat::Yaml);
+        assert!("xml".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::Yaml.to_string(), "yaml");
+        assert!(!OutputFormat::Plain.is_structured());
+    }
+}
@@ -0,0 +1,350 @@
+//! Rust source analyzer
+//!
+//! Parses Rust files with `syn` and measures every function with a body:
+//! free functions, methods in `impl` blocks (named `Type::method`) and
+//! default methods in traits (named `Trait::method`).
+//!
+//! - Cyclomatic complexity is 1 plus each `if`, `for`, `while`, extra `match`
+//!   arm, `&&` and `||`.
+//! - Cognitive complexity follows the SonarSource definition: each `if`,
+//!   `else`, `match` and loop adds 1, plus its nesting level for all but
+//!   `else`; each sequence of like logical operators and each labeled
+//!   `break` or `continue` adds 1. Closures add a level of nesting.
+//! - Nesting depth is the deepest control structure or closure.
+//!
+//! Functions nested inside another function are measured on their own, and
+//! don't count towards the outer function. Code inside macro invocations
+//! isn't parsed, so it isn't counted.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use syn::visit::{self, Visit};
+use syn::{BinOp, Block, Expr, ImplItemFn, ItemFn, ItemImpl, ItemTrait, Signature, TraitItemFn};
+use walkdir::WalkDir;
+
+use crate::{CodebaseComplexity, ComplexityConfig, ComplexityThresholds, FileComplexity, FunctionComplexity};
+
+/// Name of the file line count metric
+pub const FILE_LINE_COUNT: &str = "Line Count";
+
+/// Name of the codebase average cyclomatic complexity metric
+pub const AVERAGE_CYCLOMATIC_COMPLEXITY: &str = "Average Cyclomatic Complexity";
+
+/// Name of the codebase average cognitive complexity metric
+pub const AVERAGE_COGNITIVE_COMPLEXITY: &str = "Average Cognitive Complexity";
+
+/// An error analyzing a source file
+#[derive(Debug)]
+pub enum AnalyzeError {
+    /// The file could not be read
+    Io(PathBuf, std::io::Error),
+    /// The file is not valid Rust
+    Parse(PathBuf, syn::Error),
+}
+
+impl fmt::Display for AnalyzeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalyzeError::Io(path, err) => write!(f, "Failed to read {}: {}", path.display(), err),
+            AnalyzeError::Parse(path, err) => {
+                let start = err.span().start();
+                write!(f, "Failed to parse {} at line {}: {}", path.display(), start.line, err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnalyzeError {}
+
+/// Analyze every Rust file under a directory with the default thresholds
+///
+/// `target` directories and hidden directories are skipped, as are files
+/// that cannot be read or parsed. Files are listed in path order.
+pub fn analyze_path(dir: &Path) -> CodebaseComplexity {
+    analyze_path_with(dir, &ComplexityConfig::default())
+}
+
+/// Analyze every Rust file under a directory
+///
+/// Each file's functions are held to the thresholds `config` gives for the
+/// file's path relative to `dir`.
+pub fn analyze_path_with(dir: &Path, config: &ComplexityConfig) -> CodebaseComplexity {
+    let mut codebase = CodebaseComplexity::new();
+
+    let files = WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || !(name == "target" || name.starts_with('.'))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "rs"));
+
+    for entry in files {
+        let thresholds = config.thresholds_for(entry.path().strip_prefix(dir).unwrap_or(entry.path()));
+        let Ok(source) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        if let Ok(file) = analyze_source_with(entry.path(), &source, &thresholds) {
+            codebase.add_file(file);
+        }
+    }
+
+    let functions: Vec<&FunctionComplexity> = codebase.files.iter().flat_map(|file| &file.functions).collect();
+    if !functions.is_empty() {
+        let count = functions.len() as f64;
+        let cyclomatic = functions.iter().map(|f| f.cyclomatic_complexity.value).sum::<f64>() / count;
+        let cognitive = functions.iter().map(|f| f.cognitive_complexity.value).sum::<f64>() / count;
+        codebase.add_metric(AVERAGE_CYCLOMATIC_COMPLEXITY, cyclomatic, 5.0, 10.0);
+        codebase.add_metric(AVERAGE_COGNITIVE_COMPLEXITY, cognitive, 8.0, 15.0);
+    }
+
+    codebase
+}
+
+/// Analyze one Rust file
+pub fn analyze_file(path: &Path) -> Result<FileComplexity, AnalyzeError> {
+    let source = fs::read_to_string(path).map_err(|err| AnalyzeError::Io(path.to_path_buf(), err))?;
+    analyze_source(path, &source)
+}
+
+/// Analyze Rust source code, reporting it as the file at `path`
+pub fn analyze_source(path: &Path, source: &str) -> Result<FileComplexity, AnalyzeError> {
+    analyze_source_with(path, source, &ComplexityThresholds::default())
+}
+
+/// Analyze Rust source code with the given function thresholds
+pub fn analyze_source_with(
+    path: &Path,
+    source: &str,
+    thresholds: &ComplexityThresholds,
+) -> Result<FileComplexity, AnalyzeError> {
+    let syntax = syn::parse_file(source).map_err(|err| AnalyzeError::Parse(path.to_path_buf(), err))?;
+
+    let mut visitor = FileVisitor::new(thresholds);
+    visitor.visit_file(&syntax);
+
+    let mut file = FileComplexity::new(path);
+    file.add_metric(FILE_LINE_COUNT, source.lines().count() as f64, 500.0, 1000.0);
+    for function in visitor.functions {
+        file.add_function(function);
+    }
+
+    Ok(file)
+}
+
+/// Finds the functions in a file, tracking the `impl` or trait they are in
+struct FileVisitor<'a> {
+    functions: Vec<FunctionComplexity>,
+    owners: Vec<String>,
+    thresholds: &'a ComplexityThresholds,
+}
+
+impl<'a> FileVisitor<'a> {
+    fn new(thresholds: &'a ComplexityThresholds) -> Self {
+        Self {
+            functions: Vec::new(),
+            owners: Vec::new(),
+            thresholds,
+        }
+    }
+
+    fn measure(&mut self, sig: &Signature, block: &Block) {
+        let name = match self.owners.last() {
+            Some(owner) => format!("{}::{}", owner, sig.ident),
+            None => sig.ident.to_string(),
+        };
+
+        let mut body = BodyVisitor::default();
+        body.visit_block(block);
+
+        let start = sig.fn_token.span.start().line;
+        let end = block.brace_token.span.close().end().line;
+
+        self.functions.push(FunctionComplexity::with_thresholds(
+            &name,
+            start,
+            body.cyclomatic as f64,
+            body.cognitive as f64,
+            (end.saturating_sub(start) + 1) as f64,
+            sig.inputs.len() as f64,
+            body.max_depth as f64,
+            self.thresholds,
+        ));
+    }
+}
+
+impl<'ast> Visit<'ast> for FileVisitor<'_> {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.measure(&node.sig, &node.block);
+
+        // Functions nested in this one are measured on their own
+        let owners = std::mem::take(&mut self.owners);
+        visit::visit_item_fn(self, node);
+        self.owners = owners;
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let owner = match node.self_ty.as_ref() {
+            syn::Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+            _ => None,
+        };
+        self.owners.push(owner.unwrap_or_else(|| "impl".to_string()));
+        visit::visit_item_impl(self, node);
+        self.owners.pop();
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        self.measure(&node.sig, &node.block);
+
+        let owners = std::mem::take(&mut self.owners);
+        visit::visit_impl_item_fn(self, node);
+        self.owners = owners;
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
+        self.owners.push(node.ident.to_string());
+        visit::visit_item_trait(self, node);
+        self.owners.pop();
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+        if let Some(block) = &node.default {
+            self.measure(&node.sig, block);
+        }
+
+        let owners = std::mem::take(&mut self.owners);
+        visit::visit_trait_item_fn(self, node);
+        self.owners = owners;
+    }
+}
+
+/// Measures the body of one function
+struct BodyVisitor {
+    cyclomatic: usize,
+    cognitive: usize,
+    nesting: usize,
+    max_depth: usize,
+}
+
+impl Default for BodyVisitor {
+    fn default() -> Self {
+        Self {
+            cyclomatic: 1,
+            cognitive: 0,
+            nesting: 0,
+            max_depth: 0,
+        }
+    }
+}
+
+impl BodyVisitor {
+    /// Visit the body of a control structure one level deeper
+    fn nested(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.nesting += 1;
+        self.max_depth = self.max_depth.max(self.nesting);
+        visit(self);
+        self.nesting -= 1;
+    }
+
+    fn visit_if(&mut self, node: &syn::ExprIf, is_else_if: bool) {
+        self.cyclomatic += 1;
+        self.cognitive += if is_else_if { 1 } else { 1 + self.nesting };
+
+        self.visit_expr(&node.cond);
+        self.nested(|this| this.visit_block(&node.then_branch));
+
+        if let Some((_, else_branch)) = &node.else_branch {
+            match else_branch.as_ref() {
+                Expr::If(else_if) => self.visit_if(else_if, true),
+                other => {
+                    self.cognitive += 1;
+                    self.nested(|this| this.visit_expr(other));
+                }
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for BodyVisitor {
+    // Nested functions, impls and modules are measured on their own
+    fn visit_item(&mut self, _node: &'ast syn::Item) {}
+
+    fn visit_expr_if(&mut self, node: &'ast syn::ExprIf) {
+        self.visit_if(node, false);
+    }
+
+    fn visit_expr_match(&mut self, node: &'ast syn::ExprMatch) {
+        self.cyclomatic += node.arms.len().saturating_sub(1);
+        self.cognitive += 1 + self.nesting;
+
+        self.visit_expr(&node.expr);
+        self.nested(|this| {
+            for arm in &node.arms {
+                this.visit_arm(arm);
+            }
+        });
+    }
+
+    fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+        self.cyclomatic += 1;
+        self.cognitive += 1 + self.nesting;
+
+        self.visit_expr(&node.expr);
+        self.nested(|this| this.visit_block(&node.body));
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+        self.cyclomatic += 1;
+        self.cognitive += 1 + self.nesting;
+
+        self.visit_expr(&node.cond);
+        self.nested(|this| this.visit_block(&node.body));
+    }
+
+    fn visit_expr_loop(&mut self, node: &'ast syn::ExprLoop) {
+        self.cognitive += 1 + self.nesting;
+        self.nested(|this| this.visit_block(&node.body));
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast syn::ExprClosure) {
+        self.nested(|this| this.visit_expr(&node.body));
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if is_logical(&node.op) {
+            self.cyclomatic += 1;
+
+            // `a && b && c` is one sequence, counted at its leftmost operator
+            let continues_sequence = matches!(
+                node.left.as_ref(),
+                Expr::Binary(left) if std::mem::discriminant(&left.op) == std::mem::discriminant(&node.op)
+            );
+            if !continues_sequence {
+                self.cognitive += 1;
+            }
+        }
+
+        visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_break(&mut self, node: &'ast syn::ExprBreak) {
+        if node.label.is_some() {
+            self.cognitive += 1;
+        }
+        visit::visit_expr_break(self, node);
+    }
+
+    fn visit_expr_continue(&mut self, node: &'ast syn::ExprContinue) {
+        if node.label.is_some() {
+            self.cognitive += 1;
+        }
+    }
+}
+
+fn is_logical(op: &BinOp) -> bool {
+    matches!(op, BinOp::And(_) | BinOp::Or(_))
+}
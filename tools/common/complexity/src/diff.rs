@@ -0,0 +1,177 @@
+//! Baseline comparison
+//!
+//! Compares a complexity report with a stored baseline, such as the JSON
+//! report for the main branch, and lists the function metrics whose status
+//! changed: a metric that now exceeds a threshold it didn't before has
+//! crossed it, and one that no longer does has been resolved. A CI job can
+//! fail when anything crossed, holding new code to "no new complexity"
+//! without first fixing every existing function.
+//!
+//! Functions are matched by file path and name, so the baseline should be
+//! analyzed from the same directory as the current report. A function that
+//! isn't in the baseline counts as crossing each threshold it exceeds.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CodebaseComplexity, ComplexityMetric, ComplexityStatus, FunctionComplexity};
+
+/// A function metric whose status changed since the baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdChange {
+    /// Path to the file
+    pub path: PathBuf,
+    /// Name of the function
+    pub function: String,
+    /// Line number where the function starts
+    pub line: usize,
+    /// Name of the metric
+    pub metric: String,
+    /// Value in the baseline, if the function was there
+    pub baseline_value: Option<f64>,
+    /// Current value
+    pub value: f64,
+    /// Status in the baseline, if the function was there
+    pub baseline_status: Option<ComplexityStatus>,
+    /// Current status
+    pub status: ComplexityStatus,
+}
+
+/// The threshold changes between a baseline and a current report
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComplexityDiff {
+    /// Metrics that exceed a threshold they didn't exceed in the baseline
+    pub crossed: Vec<ThresholdChange>,
+    /// Metrics that no longer exceed a threshold they exceeded in the baseline
+    pub resolved: Vec<ThresholdChange>,
+}
+
+impl ComplexityDiff {
+    /// Whether any metric crossed a threshold since the baseline
+    pub fn has_new_complexity(&self) -> bool {
+        !self.crossed.is_empty()
+    }
+
+    /// Whether the function at `path` named `name` crossed a threshold
+    pub fn crossed_function(&self, path: &Path, name: &str) -> bool {
+        self.crossed.iter().any(|change| change.path == path && change.function == name)
+    }
+
+    /// Generate a summary report of the changes
+    pub fn summary_report(&self) -> String {
+        let mut report = String::new();
+
+        report.push_str("# Complexity Changes Since Baseline\n\n");
+
+        if self.crossed.is_empty() && self.resolved.is_empty() {
+            report.push_str("No function crossed a complexity threshold.\n");
+            return report;
+        }
+
+        for (title, changes) in [
+            ("## New Complexity\n\n", &self.crossed),
+            ("## Resolved Complexity\n\n", &self.resolved),
+        ] {
+            if changes.is_empty() {
+                continue;
+            }
+
+            report.push_str(title);
+            report.push_str("| Function | Metric | Baseline | Current | Status |\n");
+            report.push_str("|----------|--------|----------|---------|--------|\n");
+            for change in changes {
+                let baseline = change
+                    .baseline_value
+                    .map(|value| format!("{:.2}", value))
+                    .unwrap_or_else(|| "new".to_string());
+                report.push_str(&format!(
+                    "| {}:{} `{}` | {} | {} | {:.2} | {} |\n",
+                    change.path.display(),
+                    change.line,
+                    change.function,
+                    change.metric,
+                    baseline,
+                    change.value,
+                    status_icon(change.status)
+                ));
+            }
+            report.push('\n');
+        }
+
+        report
+    }
+}
+
+impl CodebaseComplexity {
+    /// Compare with a baseline report
+    pub fn diff(&self, baseline: &CodebaseComplexity) -> ComplexityDiff {
+        let baseline_functions: HashMap<_, _> = index_functions(baseline).into_iter().collect();
+        let mut diff = ComplexityDiff::default();
+
+        for (key, function) in index_functions(self) {
+            let before = baseline_functions.get(&key);
+            let metrics = function_metrics(function);
+            let baseline_metrics = before.map(|before| function_metrics(before));
+
+            for (index, metric) in metrics.iter().enumerate() {
+                let baseline_metric = baseline_metrics.map(|metrics| metrics[index]);
+                let baseline_status = baseline_metric.map(|metric| metric.status());
+                let change = ThresholdChange {
+                    path: key.0.to_path_buf(),
+                    function: function.name.clone(),
+                    line: function.line,
+                    metric: metric.name.clone(),
+                    baseline_value: baseline_metric.map(|metric| metric.value),
+                    value: metric.value,
+                    baseline_status,
+                    status: metric.status(),
+                };
+
+                let previous = baseline_status.unwrap_or(ComplexityStatus::Ok);
+                if change.status > previous {
+                    diff.crossed.push(change);
+                } else if change.status < previous {
+                    diff.resolved.push(change);
+                }
+            }
+        }
+
+        diff
+    }
+}
+
+type FunctionKey<'a> = (&'a Path, &'a str, usize);
+
+/// Functions keyed by path, name and occurrence, so same-named functions pair up in order
+fn index_functions(codebase: &CodebaseComplexity) -> Vec<(FunctionKey<'_>, &FunctionComplexity)> {
+    let mut index = Vec::new();
+    for file in &codebase.files {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for function in &file.functions {
+            let occurrence = seen.entry(function.name.as_str()).or_default();
+            index.push(((file.path.as_path(), function.name.as_str(), *occurrence), function));
+            *occurrence += 1;
+        }
+    }
+    index
+}
+
+fn function_metrics(function: &FunctionComplexity) -> [&ComplexityMetric; 5] {
+    [
+        &function.cyclomatic_complexity,
+        &function.cognitive_complexity,
+        &function.line_count,
+        &function.parameter_count,
+        &function.nesting_depth,
+    ]
+}
+
+pub(crate) fn status_icon(status: ComplexityStatus) -> &'static str {
+    match status {
+        ComplexityStatus::Ok => "✅",
+        ComplexityStatus::Warning => "⚠️",
+        ComplexityStatus::Error => "❌",
+    }
+}
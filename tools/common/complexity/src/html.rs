@@ -0,0 +1,132 @@
+//! HTML complexity reports
+//!
+//! A single self-contained page: the codebase metrics, then each file that
+//! exceeds a threshold with its file metrics and complex functions. When the
+//! report is compared with a baseline, the functions that crossed a
+//! threshold since then are highlighted and listed first.
+
+use std::fmt::Write as _;
+
+use crate::diff::status_icon;
+use crate::{CodebaseComplexity, ComplexityDiff, ComplexityMetric};
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+table{border-collapse:collapse;margin-bottom:1.5rem}\
+th,td{border:1px solid #ccc;padding:.4rem .8rem;text-align:left}\
+th{background:#f4f4f4}\
+.new{background:#fdecea}.resolved{background:#e9f6ec}";
+
+/// The report as an HTML page, highlighting the changes in `diff`
+pub fn html_report(codebase: &CodebaseComplexity, diff: Option<&ComplexityDiff>) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Complexity Report</title>\n");
+    let _ = writeln!(html, "<style>{}</style>", STYLE);
+    html.push_str("</head>\n<body>\n<h1>Complexity Report</h1>\n");
+    let _ = writeln!(
+        html,
+        "<p>Overall status: {} {:?}</p>",
+        status_icon(codebase.overall_status()),
+        codebase.overall_status()
+    );
+
+    if let Some(diff) = diff {
+        html.push_str("<h2>Changes Since Baseline</h2>\n");
+        if diff.crossed.is_empty() && diff.resolved.is_empty() {
+            html.push_str("<p>No function crossed a complexity threshold.</p>\n");
+        } else {
+            html.push_str("<table>\n<thead><tr><th>Function</th><th>Metric</th><th>Baseline</th><th>Current</th><th>Status</th></tr></thead>\n<tbody>\n");
+            let changes = diff
+                .crossed
+                .iter()
+                .map(|change| ("new", change))
+                .chain(diff.resolved.iter().map(|change| ("resolved", change)));
+            for (class, change) in changes {
+                let baseline = change
+                    .baseline_value
+                    .map(|value| format!("{:.2}", value))
+                    .unwrap_or_else(|| "new".to_string());
+                let _ = writeln!(
+                    html,
+                    "<tr class=\"{}\"><td>{}:{} <code>{}</code></td><td>{}</td><td>{}</td><td>{:.2}</td><td>{}</td></tr>",
+                    class,
+                    escape(&change.path.display().to_string()),
+                    change.line,
+                    escape(&change.function),
+                    escape(&change.metric),
+                    baseline,
+                    change.value,
+                    status_icon(change.status)
+                );
+            }
+            html.push_str("</tbody>\n</table>\n");
+        }
+    }
+
+    if !codebase.metrics.is_empty() {
+        html.push_str("<h2>Codebase Metrics</h2>\n");
+        metric_table(&mut html, sorted(codebase.metrics.values().collect()));
+    }
+
+    let complex_files = codebase.complex_files();
+    if !complex_files.is_empty() {
+        html.push_str("<h2>Files Exceeding Complexity Thresholds</h2>\n");
+    }
+    for file in complex_files {
+        let path = file.path.display().to_string();
+        let _ = writeln!(html, "<h3>{} {}</h3>", status_icon(file.overall_status()), escape(&path));
+
+        let exceeding = file.exceeding_metrics();
+        if !exceeding.is_empty() {
+            metric_table(&mut html, sorted(exceeding));
+        }
+
+        let mut functions = file.complex_functions();
+        functions.sort_by_key(|function| !diff.is_some_and(|diff| diff.crossed_function(&file.path, &function.name)));
+        for function in functions {
+            let is_new = diff.is_some_and(|diff| diff.crossed_function(&file.path, &function.name));
+            let _ = writeln!(
+                html,
+                "<h4{}>{} <code>{}</code> (line {}){}</h4>",
+                if is_new { " class=\"new\"" } else { "" },
+                status_icon(function.overall_status()),
+                escape(&function.name),
+                function.line,
+                if is_new { " — new since baseline" } else { "" }
+            );
+            metric_table(&mut html, function.exceeding_metrics());
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn metric_table(html: &mut String, metrics: Vec<&ComplexityMetric>) {
+    html.push_str("<table>\n<thead><tr><th>Metric</th><th>Value</th><th>Warning Threshold</th><th>Error Threshold</th><th>Status</th></tr></thead>\n<tbody>\n");
+    for metric in metrics {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{:.2}</td><td>{:.2}</td><td>{:.2}</td><td>{}</td></tr>",
+            escape(&metric.name),
+            metric.value,
+            metric.warning_threshold,
+            metric.error_threshold,
+            status_icon(metric.status())
+        );
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+/// Metrics in name order, since they are kept in a map
+fn sorted(mut metrics: Vec<&ComplexityMetric>) -> Vec<&ComplexityMetric> {
+    metrics.sort_by(|a, b| a.name.cmp(&b.name));
+    metrics
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
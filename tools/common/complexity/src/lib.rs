@@ -1,8 +1,21 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+pub mod analyzer;
+pub mod diff;
+pub mod html;
+pub mod thresholds;
+
+pub use analyzer::{
+    analyze_file, analyze_path, analyze_path_with, analyze_source, analyze_source_with, AnalyzeError,
+};
+pub use diff::{ComplexityDiff, ThresholdChange};
+pub use html::html_report;
+pub use thresholds::{ComplexityConfig, ComplexityThresholds, Threshold, ThresholdOverride, ThresholdsError};
+
 /// Represents a code complexity metric measurement
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComplexityMetric {
     /// Name of the metric
     pub name: String,
@@ -47,8 +60,8 @@ impl ComplexityMetric {
     }
 }
 
-/// Status of a complexity metric
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Status of a complexity metric, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ComplexityStatus {
     /// Metric is within acceptable range
     Ok,
@@ -59,7 +72,7 @@ pub enum ComplexityStatus {
 }
 
 /// Represents complexity metrics for a function
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionComplexity {
     /// Name of the function
     pub name: String,
@@ -78,7 +91,7 @@ pub struct FunctionComplexity {
 }
 
 impl FunctionComplexity {
-    /// Create a new function complexity measurement
+    /// Create a new function complexity measurement with the default thresholds
     pub fn new(
         name: &str,
         line: usize,
@@ -88,24 +101,50 @@ impl FunctionComplexity {
         parameter_count: f64,
         nesting_depth: f64,
     ) -> Self {
+        Self::with_thresholds(
+            name,
+            line,
+            cyclomatic_complexity,
+            cognitive_complexity,
+            line_count,
+            parameter_count,
+            nesting_depth,
+            &ComplexityThresholds::default(),
+        )
+    }
+
+    /// Create a new function complexity measurement with the given thresholds
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_thresholds(
+        name: &str,
+        line: usize,
+        cyclomatic_complexity: f64,
+        cognitive_complexity: f64,
+        line_count: f64,
+        parameter_count: f64,
+        nesting_depth: f64,
+        thresholds: &ComplexityThresholds,
+    ) -> Self {
+        let metric = |name: &str, value: f64, threshold: Threshold| {
+            ComplexityMetric::new(name, value, threshold.warning, threshold.error)
+        };
+
         Self {
             name: name.to_string(),
             line,
-            cyclomatic_complexity: ComplexityMetric::new(
+            cyclomatic_complexity: metric(
                 "Cyclomatic Complexity",
                 cyclomatic_complexity,
-                10.0,
-                20.0,
+                thresholds.cyclomatic_complexity,
             ),
-            cognitive_complexity: ComplexityMetric::new(
+            cognitive_complexity: metric(
                 "Cognitive Complexity",
                 cognitive_complexity,
-                15.0,
-                30.0,
+                thresholds.cognitive_complexity,
             ),
-            line_count: ComplexityMetric::new("Line Count", line_count, 50.0, 100.0),
-            parameter_count: ComplexityMetric::new("Parameter Count", parameter_count, 5.0, 8.0),
-            nesting_depth: ComplexityMetric::new("Nesting Depth", nesting_depth, 3.0, 5.0),
+            line_count: metric("Line Count", line_count, thresholds.line_count),
+            parameter_count: metric("Parameter Count", parameter_count, thresholds.parameter_count),
+            nesting_depth: metric("Nesting Depth", nesting_depth, thresholds.nesting_depth),
         }
     }
 
@@ -147,7 +186,7 @@ impl FunctionComplexity {
 }
 
 /// Represents complexity metrics for a file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileComplexity {
     /// Path to the file
     pub path: PathBuf,
@@ -185,7 +224,7 @@ impl FileComplexity {
         let function_status = self.functions.iter().map(|f| f.overall_status());
         let metric_status = self.metrics.values().map(|m| m.status());
 
-        let all_statuses = function_status.chain(metric_status);
+        let mut all_statuses = function_status.chain(metric_status);
 
         if all_statuses.clone().any(|s| s == ComplexityStatus::Error) {
             ComplexityStatus::Error
@@ -214,7 +253,7 @@ impl FileComplexity {
 }
 
 /// Represents complexity metrics for a codebase
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodebaseComplexity {
     /// File complexity measurements
     pub files: Vec<FileComplexity>,
@@ -249,7 +288,7 @@ impl CodebaseComplexity {
         let file_status = self.files.iter().map(|f| f.overall_status());
         let metric_status = self.metrics.values().map(|m| m.status());
 
-        let all_statuses = file_status.chain(metric_status);
+        let mut all_statuses = file_status.chain(metric_status);
 
         if all_statuses.clone().any(|s| s == ComplexityStatus::Error) {
             ComplexityStatus::Error
@@ -276,6 +315,21 @@ impl CodebaseComplexity {
             .collect()
     }
 
+    /// Serialize the report as JSON, such as to store it as a baseline
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Load a report serialized with [`CodebaseComplexity::to_json`]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Generate an HTML report of complexity metrics
+    pub fn to_html(&self) -> String {
+        html_report(self, None)
+    }
+
     /// Generate a summary report of complexity metrics
     pub fn summary_report(&self) -> String {
         let mut report = String::new();
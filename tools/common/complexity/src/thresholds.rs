@@ -0,0 +1,178 @@
+//! Complexity thresholds
+//!
+//! The warning and error thresholds for each function metric can be set in
+//! `.complexity.yaml`, or in the `complexity` section of `config.yaml`:
+//!
+//! ```yaml
+//! complexity:
+//!   cyclomatic_complexity: { warning: 8, error: 15 }
+//!   nesting_depth: { warning: 3, error: 4 }
+//!   overrides:
+//!     - path: content-build/src/legacy
+//!       cyclomatic_complexity: { warning: 20, error: 40 }
+//!       line_count: { warning: 150, error: 300 }
+//! ```
+//!
+//! Metrics left out keep their defaults. An override applies to files under
+//! its path, relative to the analyzed directory, and changes only the
+//! metrics it lists. When several overrides match a file, the one with the
+//! longest path wins for each metric.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the dedicated thresholds file
+pub const THRESHOLDS_FILE: &str = ".complexity.yaml";
+
+/// Section of `config.yaml` that holds the thresholds
+pub const CONFIG_SECTION: &str = "complexity";
+
+/// Warning and error thresholds for one metric
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Threshold {
+    pub warning: f64,
+    pub error: f64,
+}
+
+impl Threshold {
+    pub const fn new(warning: f64, error: f64) -> Self {
+        Self { warning, error }
+    }
+}
+
+/// Thresholds for each function metric
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComplexityThresholds {
+    pub cyclomatic_complexity: Threshold,
+    pub cognitive_complexity: Threshold,
+    pub line_count: Threshold,
+    pub parameter_count: Threshold,
+    pub nesting_depth: Threshold,
+}
+
+impl Default for ComplexityThresholds {
+    fn default() -> Self {
+        Self {
+            cyclomatic_complexity: Threshold::new(10.0, 20.0),
+            cognitive_complexity: Threshold::new(15.0, 30.0),
+            line_count: Threshold::new(50.0, 100.0),
+            parameter_count: Threshold::new(5.0, 8.0),
+            nesting_depth: Threshold::new(3.0, 5.0),
+        }
+    }
+}
+
+/// Thresholds for the files under one path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdOverride {
+    pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cyclomatic_complexity: Option<Threshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cognitive_complexity: Option<Threshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_count: Option<Threshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter_count: Option<Threshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nesting_depth: Option<Threshold>,
+}
+
+impl ThresholdOverride {
+    fn apply(&self, thresholds: &mut ComplexityThresholds) {
+        let fields = [
+            (&mut thresholds.cyclomatic_complexity, self.cyclomatic_complexity),
+            (&mut thresholds.cognitive_complexity, self.cognitive_complexity),
+            (&mut thresholds.line_count, self.line_count),
+            (&mut thresholds.parameter_count, self.parameter_count),
+            (&mut thresholds.nesting_depth, self.nesting_depth),
+        ];
+        for (threshold, value) in fields {
+            if let Some(value) = value {
+                *threshold = value;
+            }
+        }
+    }
+}
+
+/// Default thresholds and the overrides for particular paths
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComplexityConfig {
+    #[serde(flatten)]
+    pub thresholds: ComplexityThresholds,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ThresholdOverride>,
+}
+
+/// Why thresholds could not be loaded
+#[derive(Debug)]
+pub enum ThresholdsError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, serde_yaml::Error),
+}
+
+impl std::fmt::Display for ThresholdsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThresholdsError::Io(path, err) => write!(f, "Failed to read {}: {}", path.display(), err),
+            ThresholdsError::Parse(path, err) => write!(f, "Failed to parse {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for ThresholdsError {}
+
+impl ComplexityConfig {
+    /// Load the thresholds for a project directory
+    ///
+    /// Reads `.complexity.yaml` if the directory has one, and otherwise the
+    /// `complexity` section of its `config.yaml`. Without either, the
+    /// default thresholds apply.
+    pub fn load(dir: &Path) -> Result<Self, ThresholdsError> {
+        let thresholds_file = dir.join(THRESHOLDS_FILE);
+        if thresholds_file.exists() {
+            return Self::from_file(&thresholds_file);
+        }
+
+        let config_file = dir.join("config.yaml");
+        if config_file.exists() {
+            let content = read(&config_file)?;
+            let config: serde_yaml::Value =
+                serde_yaml::from_str(&content).map_err(|err| ThresholdsError::Parse(config_file.clone(), err))?;
+            if let Some(section) = config.get(CONFIG_SECTION) {
+                return serde_yaml::from_value(section.clone()).map_err(|err| ThresholdsError::Parse(config_file, err));
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    /// Load thresholds from a YAML file holding only them
+    pub fn from_file(path: &Path) -> Result<Self, ThresholdsError> {
+        let content = read(path)?;
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_yaml::from_str(&content).map_err(|err| ThresholdsError::Parse(path.to_path_buf(), err))
+    }
+
+    /// The thresholds for a file, given as a path relative to the analyzed directory
+    pub fn thresholds_for(&self, path: &Path) -> ComplexityThresholds {
+        let mut matching: Vec<&ThresholdOverride> =
+            self.overrides.iter().filter(|o| path.starts_with(&o.path)).collect();
+        matching.sort_by_key(|o| o.path.components().count());
+
+        let mut thresholds = self.thresholds;
+        for matched in matching {
+            matched.apply(&mut thresholds);
+        }
+        thresholds
+    }
+}
+
+fn read(path: &Path) -> Result<String, ThresholdsError> {
+    fs::read_to_string(path).map_err(|err| ThresholdsError::Io(path.to_path_buf(), err))
+}
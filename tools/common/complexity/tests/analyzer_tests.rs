@@ -0,0 +1,116 @@
+use common_complexity::{analyze_path, analyze_source, ComplexityStatus, FunctionComplexity};
+use std::path::Path;
+
+const SOURCE: &str = r#"
+fn simple(a: i32) -> i32 {
+    a + 1
+}
+
+fn branchy(items: &[i32], limit: i32, strict: bool) -> i32 {
+    let mut total = 0;
+    for item in items {
+        if *item > limit && strict {
+            total += 1;
+        } else if *item < 0 || *item == limit {
+            total -= 1;
+        } else {
+            total += match item {
+                0 => 0,
+                1 => 1,
+                _ => 2,
+            };
+        }
+    }
+    total
+}
+
+struct Counter;
+
+impl Counter {
+    fn next(&mut self, step: usize) -> usize {
+        let add = |value: usize| if value > 0 { value } else { 1 };
+        add(step)
+    }
+}
+
+trait Named {
+    fn name(&self) -> String {
+        fn inner() -> &'static str {
+            "named"
+        }
+        inner().to_string()
+    }
+
+    fn required(&self);
+}
+"#;
+
+fn function<'a>(functions: &'a [FunctionComplexity], name: &str) -> &'a FunctionComplexity {
+    functions.iter().find(|f| f.name == name).unwrap_or_else(|| panic!("no function {}", name))
+}
+
+#[test]
+fn test_analyze_source_measures_each_function() {
+    let file = analyze_source(Path::new("src/lib.rs"), SOURCE).unwrap();
+
+    let names: Vec<&str> = file.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["simple", "branchy", "Counter::next", "Named::name", "inner"]);
+
+    let simple = function(&file.functions, "simple");
+    assert_eq!(simple.line, 2);
+    assert_eq!(simple.cyclomatic_complexity.value, 1.0);
+    assert_eq!(simple.cognitive_complexity.value, 0.0);
+    assert_eq!(simple.line_count.value, 3.0);
+    assert_eq!(simple.parameter_count.value, 1.0);
+    assert_eq!(simple.nesting_depth.value, 0.0);
+
+    // for, if, else if, two extra match arms, && and ||
+    let branchy = function(&file.functions, "branchy");
+    assert_eq!(branchy.cyclomatic_complexity.value, 8.0);
+    // for 1, if 2, else if 1, else 1, match 3, && 1, || 1
+    assert_eq!(branchy.cognitive_complexity.value, 10.0);
+    assert_eq!(branchy.line_count.value, 17.0);
+    assert_eq!(branchy.parameter_count.value, 3.0);
+    assert_eq!(branchy.nesting_depth.value, 3.0);
+
+    // The closure nests its if
+    let next = function(&file.functions, "Counter::next");
+    assert_eq!(next.parameter_count.value, 2.0);
+    assert_eq!(next.cyclomatic_complexity.value, 2.0);
+    assert_eq!(next.cognitive_complexity.value, 3.0);
+    assert_eq!(next.nesting_depth.value, 2.0);
+
+    // Nested functions don't count towards the outer one
+    assert_eq!(function(&file.functions, "Named::name").line_count.value, 6.0);
+    assert_eq!(file.metrics["Line Count"].value, SOURCE.lines().count() as f64);
+}
+
+#[test]
+fn test_analyze_source_rejects_invalid_rust() {
+    let err = analyze_source(Path::new("broken.rs"), "fn broken( {").unwrap_err();
+    assert!(err.to_string().starts_with("Failed to parse broken.rs"));
+}
+
+#[test]
+fn test_analyze_path_skips_target_and_unparseable_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::create_dir_all(root.join("target").join("debug")).unwrap();
+    std::fs::write(root.join("src").join("lib.rs"), SOURCE).unwrap();
+    std::fs::write(root.join("src").join("broken.rs"), "fn broken( {").unwrap();
+    std::fs::write(root.join("src").join("notes.txt"), "fn not_rust() {}").unwrap();
+    std::fs::write(root.join("target").join("debug").join("build.rs"), "fn generated() {}").unwrap();
+
+    let codebase = analyze_path(root);
+    assert_eq!(codebase.files.len(), 1);
+    assert!(codebase.files[0].path.ends_with("src/lib.rs"));
+
+    // (1 + 8 + 2 + 1 + 1) / 5 functions
+    assert_eq!(codebase.metrics["Average Cyclomatic Complexity"].value, 2.6);
+
+    // Only the loop's match is nested deeply enough to warn about
+    let complex: Vec<&str> = codebase.files[0].complex_functions().iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(complex, vec!["branchy"]);
+    assert_eq!(codebase.overall_status(), ComplexityStatus::Warning);
+}
@@ -0,0 +1,102 @@
+use common_complexity::{
+    html_report, CodebaseComplexity, ComplexityStatus, FileComplexity, FunctionComplexity,
+};
+use std::path::Path;
+
+fn codebase(functions: Vec<FunctionComplexity>) -> CodebaseComplexity {
+    let mut file = FileComplexity::new(Path::new("src/lib.rs"));
+    for function in functions {
+        file.add_function(function);
+    }
+    file.add_metric("Line Count", 120.0, 500.0, 1000.0);
+
+    let mut codebase = CodebaseComplexity::new();
+    codebase.add_file(file);
+    codebase.add_metric("Average Cyclomatic Complexity", 4.0, 5.0, 10.0);
+    codebase
+}
+
+#[test]
+fn test_json_round_trip() {
+    let report = codebase(vec![FunctionComplexity::new("parse", 3, 12.0, 4.0, 20.0, 2.0, 1.0)]);
+
+    let json = report.to_json().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["files"][0]["functions"][0]["cyclomatic_complexity"]["value"], 12.0);
+
+    let loaded = CodebaseComplexity::from_json(&json).unwrap();
+    assert_eq!(loaded.files[0].functions[0].name, "parse");
+    assert_eq!(loaded.overall_status(), ComplexityStatus::Warning);
+}
+
+#[test]
+fn test_diff_reports_crossed_and_resolved_thresholds() {
+    let baseline = codebase(vec![
+        FunctionComplexity::new("parse", 3, 4.0, 4.0, 20.0, 2.0, 1.0),
+        FunctionComplexity::new("render", 30, 12.0, 4.0, 20.0, 2.0, 1.0),
+        FunctionComplexity::new("legacy", 60, 25.0, 4.0, 20.0, 2.0, 1.0),
+    ]);
+    let current = codebase(vec![
+        // Crossed the cyclomatic warning threshold
+        FunctionComplexity::new("parse", 3, 11.0, 4.0, 20.0, 2.0, 1.0),
+        // Back under it
+        FunctionComplexity::new("render", 30, 8.0, 4.0, 20.0, 2.0, 1.0),
+        // Still over it, and no worse
+        FunctionComplexity::new("legacy", 60, 26.0, 4.0, 20.0, 2.0, 1.0),
+        // New, with too many parameters
+        FunctionComplexity::new("helper", 90, 1.0, 0.0, 5.0, 9.0, 0.0),
+    ]);
+
+    let diff = current.diff(&baseline);
+    assert!(diff.has_new_complexity());
+
+    let crossed: Vec<(&str, &str, Option<f64>)> = diff
+        .crossed
+        .iter()
+        .map(|change| (change.function.as_str(), change.metric.as_str(), change.baseline_value))
+        .collect();
+    assert_eq!(
+        crossed,
+        vec![
+            ("parse", "Cyclomatic Complexity", Some(4.0)),
+            ("helper", "Parameter Count", None),
+        ]
+    );
+    assert_eq!(diff.crossed[1].status, ComplexityStatus::Error);
+
+    assert_eq!(diff.resolved.len(), 1);
+    assert_eq!(diff.resolved[0].function, "render");
+    assert_eq!(diff.resolved[0].baseline_status, Some(ComplexityStatus::Warning));
+
+    let summary = diff.summary_report();
+    assert!(summary.contains("## New Complexity"));
+    assert!(summary.contains("| src/lib.rs:90 `helper` | Parameter Count | new | 9.00 | ❌ |"));
+}
+
+#[test]
+fn test_diff_against_itself_is_empty() {
+    let report = codebase(vec![FunctionComplexity::new("legacy", 60, 25.0, 4.0, 20.0, 2.0, 1.0)]);
+    let diff = report.diff(&report);
+    assert!(!diff.has_new_complexity());
+    assert!(diff.resolved.is_empty());
+}
+
+#[test]
+fn test_html_report_highlights_new_complexity() {
+    let baseline = codebase(vec![FunctionComplexity::new("legacy", 60, 25.0, 4.0, 20.0, 2.0, 1.0)]);
+    let current = codebase(vec![
+        FunctionComplexity::new("legacy", 60, 25.0, 4.0, 20.0, 2.0, 1.0),
+        FunctionComplexity::new("fresh<T>", 90, 12.0, 4.0, 20.0, 2.0, 1.0),
+    ]);
+
+    let html = html_report(&current, Some(&current.diff(&baseline)));
+    assert!(html.contains("<h2>Changes Since Baseline</h2>"));
+    assert!(html.contains("<h4 class=\"new\">⚠️ <code>fresh&lt;T&gt;</code> (line 90) — new since baseline</h4>"));
+
+    // New complexity is listed before what was already there
+    assert!(html.find("fresh&lt;T&gt;</code> (line 90)").unwrap() < html.find("<code>legacy</code> (line 60)").unwrap());
+
+    let plain = current.to_html();
+    assert!(!plain.contains("Changes Since Baseline"));
+    assert!(!plain.contains("class=\"new\""));
+}
@@ -0,0 +1,120 @@
+use common_complexity::{analyze_path_with, ComplexityConfig, ComplexityStatus, ComplexityThresholds, Threshold};
+use std::path::Path;
+
+const OVERRIDES: &str = r#"
+cyclomatic_complexity: { warning: 4, error: 8 }
+overrides:
+  - path: src/legacy
+    cyclomatic_complexity: { warning: 20, error: 40 }
+    nesting_depth: { warning: 6, error: 8 }
+  - path: src/legacy/worst
+    cyclomatic_complexity: { warning: 50, error: 100 }
+"#;
+
+const BRANCHY: &str = r#"
+fn branchy(value: i32) -> i32 {
+    if value > 0 {
+        if value > 10 {
+            if value > 100 {
+                return 3;
+            }
+            return 2;
+        }
+        return 1;
+    }
+    match value {
+        0 => 0,
+        -1 => -1,
+        _ => -2,
+    }
+}
+"#;
+
+#[test]
+fn test_missing_config_uses_default_thresholds() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config = ComplexityConfig::load(temp_dir.path()).unwrap();
+    assert_eq!(config, ComplexityConfig::default());
+    assert_eq!(config.thresholds.cyclomatic_complexity, Threshold::new(10.0, 20.0));
+}
+
+#[test]
+fn test_load_reads_config_yaml_section() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("config.yaml"),
+        "title: Writing\ncomplexity:\n  line_count: { warning: 40, error: 80 }\n",
+    )
+    .unwrap();
+
+    let config = ComplexityConfig::load(temp_dir.path()).unwrap();
+    assert_eq!(config.thresholds.line_count, Threshold::new(40.0, 80.0));
+    assert_eq!(config.thresholds.nesting_depth, ComplexityThresholds::default().nesting_depth);
+}
+
+#[test]
+fn test_complexity_yaml_takes_precedence() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("config.yaml"),
+        "complexity:\n  line_count: { warning: 40, error: 80 }\n",
+    )
+    .unwrap();
+    std::fs::write(temp_dir.path().join(".complexity.yaml"), OVERRIDES).unwrap();
+
+    let config = ComplexityConfig::load(temp_dir.path()).unwrap();
+    assert_eq!(config.thresholds.cyclomatic_complexity, Threshold::new(4.0, 8.0));
+    assert_eq!(config.thresholds.line_count, ComplexityThresholds::default().line_count);
+    assert_eq!(config.overrides.len(), 2);
+}
+
+#[test]
+fn test_invalid_config_is_an_error() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join(".complexity.yaml"), "cyclomatic_complexity: high\n").unwrap();
+
+    let err = ComplexityConfig::load(temp_dir.path()).unwrap_err();
+    assert!(err.to_string().starts_with("Failed to parse"));
+}
+
+#[test]
+fn test_most_specific_override_wins() {
+    let config: ComplexityConfig = serde_yaml::from_str(OVERRIDES).unwrap();
+
+    let new_code = config.thresholds_for(Path::new("src/lib.rs"));
+    assert_eq!(new_code.cyclomatic_complexity, Threshold::new(4.0, 8.0));
+
+    let legacy = config.thresholds_for(Path::new("src/legacy/parser.rs"));
+    assert_eq!(legacy.cyclomatic_complexity, Threshold::new(20.0, 40.0));
+    assert_eq!(legacy.nesting_depth, Threshold::new(6.0, 8.0));
+
+    // The deeper override changes cyclomatic complexity, the shallower one still sets nesting depth
+    let worst = config.thresholds_for(Path::new("src/legacy/worst/mod.rs"));
+    assert_eq!(worst.cyclomatic_complexity, Threshold::new(50.0, 100.0));
+    assert_eq!(worst.nesting_depth, Threshold::new(6.0, 8.0));
+
+    // Paths match by component, not by prefix
+    let legacy_ish = config.thresholds_for(Path::new("src/legacy_new/lib.rs"));
+    assert_eq!(legacy_ish.cyclomatic_complexity, Threshold::new(4.0, 8.0));
+}
+
+#[test]
+fn test_analyze_path_with_applies_overrides() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join("src").join("legacy")).unwrap();
+    std::fs::write(root.join("src").join("lib.rs"), BRANCHY).unwrap();
+    std::fs::write(root.join("src").join("legacy").join("old.rs"), BRANCHY).unwrap();
+
+    let config: ComplexityConfig = serde_yaml::from_str(OVERRIDES).unwrap();
+    let codebase = analyze_path_with(root, &config);
+    assert_eq!(codebase.files.len(), 2);
+
+    let status = |suffix: &str| {
+        let file = codebase.files.iter().find(|file| file.path.ends_with(suffix)).unwrap();
+        file.functions[0].overall_status()
+    };
+    // Three ifs and two extra match arms: 6 is over the strict limit of 4
+    assert_eq!(status("src/lib.rs"), ComplexityStatus::Warning);
+    assert_eq!(status("src/legacy/old.rs"), ComplexityStatus::Ok);
+}
@@ -7,6 +7,7 @@
 //! - Thread-safe configuration caching
 //! - Automatic cache invalidation based on file modification time
 //! - Lazy loading of configuration
+//! - A process-wide configuration shared by every `load_config` call
 //!
 //! ## Example
 //!
@@ -15,7 +16,8 @@
 //! or the file is modified (depending on settings).
 
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
 use std::time::{Duration, SystemTime};
 use common_errors::{Result, WritingError};
 use common_models::Config;
@@ -274,6 +276,81 @@ impl ConfigCache {
     }
 }
 
+/// File fingerprint used to detect changes: modification time and length
+type FileStamp = Option<(SystemTime, u64)>;
+
+/// Process-wide configuration entry shared by `load_config`
+struct SharedEntry {
+    /// The loaded configuration, with all layers applied
+    config: Config,
+    /// The base configuration file it was loaded from
+    path: PathBuf,
+    /// Fingerprints of the base and local files at load time
+    stamps: Vec<(PathBuf, FileStamp)>,
+}
+
+/// Process-wide configuration, read by every `load_config` call
+static SHARED: Lazy<RwLock<Option<SharedEntry>>> = Lazy::new(|| RwLock::new(None));
+
+/// Whether shared entries are checked against the files' modification times
+static CHECK_MODIFICATIONS: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable modification time checks for the process-wide configuration
+///
+/// Checks are enabled by default, so editing `config.yaml` or
+/// `config.local.yaml` is picked up by the next `load_config` call. Long
+/// running tools that want a stable configuration can turn them off and call
+/// `reload_config` explicitly instead.
+pub fn set_check_modifications(enabled: bool) {
+    CHECK_MODIFICATIONS.store(enabled, Ordering::Relaxed);
+}
+
+/// Get the process-wide configuration for a base file, loading it on first use
+///
+/// The configuration is reloaded if `path` differs from the cached one or,
+/// when modification checks are enabled, if the base or local file changed.
+/// Environment overrides are read when the configuration is loaded, so
+/// changes to them need an explicit `reload_config`.
+pub fn shared_config(path: &Path, load: impl FnOnce(&Path) -> Result<Config>) -> Result<Config> {
+    let watched = [path.to_path_buf(), crate::layers::local_config_path(path)];
+
+    {
+        let shared = SHARED.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = shared.as_ref() {
+            let fresh = !CHECK_MODIFICATIONS.load(Ordering::Relaxed)
+                || entry.stamps.iter().all(|(file, stamp)| file_stamp(file) == *stamp);
+            if entry.path == path && fresh {
+                return Ok(entry.config.clone());
+            }
+        }
+    }
+
+    // Stamp before loading so a write during the load triggers another reload
+    let stamps = watched.iter().map(|file| (file.clone(), file_stamp(file))).collect();
+    let config = load(path)?;
+
+    let mut shared = SHARED.write().unwrap_or_else(|e| e.into_inner());
+    *shared = Some(SharedEntry {
+        config: config.clone(),
+        path: path.to_path_buf(),
+        stamps,
+    });
+
+    Ok(config)
+}
+
+/// Drop the process-wide configuration so the next load reads from disk
+pub fn invalidate_shared_config() {
+    let mut shared = SHARED.write().unwrap_or_else(|e| e.into_inner());
+    *shared = None;
+}
+
+/// Fingerprint a file, or `None` if it doesn't exist
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
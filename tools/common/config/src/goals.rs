@@ -0,0 +1,79 @@
+//! # Goals
+//!
+//! Writing goals are listed in a `goals.yaml` file in the content base
+//! directory. Each goal sets a target number of words or posts per week or
+//! month, optionally for a single topic.
+//!
+//! ```yaml
+//! - metric: words
+//!   target: 2000
+//!   period: week
+//! - metric: posts
+//!   target: 2
+//!   period: month
+//!   topic: blog
+//! ```
+
+use common_errors::{Result, ResultExt};
+use common_models::Config;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the goals file in the content base directory
+pub const GOALS_FILE: &str = "goals.yaml";
+
+/// What a goal counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalMetric {
+    /// Words in the articles published during the period
+    Words,
+    /// Articles published during the period
+    Posts,
+}
+
+/// How often a goal resets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalPeriod {
+    /// Monday to Sunday
+    Week,
+    /// Calendar month
+    Month,
+}
+
+/// A writing target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Goal {
+    pub metric: GoalMetric,
+    pub target: usize,
+    pub period: GoalPeriod,
+    /// Only count articles in this topic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+}
+
+/// Get the path of the goals file for a configuration
+pub fn goals_path(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(GOALS_FILE)
+}
+
+/// Load goals from a file, returning an empty list if it does not exist
+pub fn load_goals(path: &Path) -> Result<Vec<Goal>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read goals file: {}", path.display()))?;
+
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let goals: Vec<Goal> = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse goals file: {}", path.display()))?;
+
+    Ok(goals)
+}
@@ -0,0 +1,165 @@
+//! # Layered Configuration
+//!
+//! The configuration is assembled from up to three layers, each overriding
+//! the one before it:
+//!
+//! 1. The base file, e.g. `config.yaml` (committed)
+//! 2. A local file next to it, e.g. `config.local.yaml` (not committed)
+//! 3. Environment variables prefixed with `WRITING_`
+//!
+//! Layers are merged key by key: mappings are merged recursively, while any
+//! other value (including lists) replaces the value below it.
+//!
+//! Environment variable names map onto YAML keys by stripping the prefix,
+//! lowercasing, and splitting on a double underscore, so
+//! `WRITING_CONTENT__BASE_DIR=/srv/content` overrides `content.base_dir`.
+//! Values are parsed as YAML scalars, so numbers and booleans keep their type.
+//! Keys are the names used in the YAML file (`publication.site`, not
+//! `publication.site_url`).
+//!
+//! Saving the configuration only writes the base file, and values that come
+//! from an override layer are written back as their base value so local
+//! paths never leak into the committed file.
+
+use common_errors::{Result, ResultExt};
+use common_models::Config;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prefix of environment variables that override configuration values
+pub const ENV_PREFIX: &str = "WRITING_";
+
+/// Separator between nested keys in environment variable names
+pub const ENV_SEPARATOR: &str = "__";
+
+/// Get the path of the local override file for a base configuration file
+///
+/// `config.yaml` becomes `config.local.yaml` in the same directory.
+pub fn local_config_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("config");
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("yaml");
+    path.with_file_name(format!("{}.local.{}", stem, extension))
+}
+
+/// Load a configuration file with its local and environment overrides applied
+pub fn load_layered_config(path: &Path) -> Result<Config> {
+    let (mut value, overrides) = load_layers(path)?;
+    merge_values(&mut value, overrides);
+
+    serde_yaml::from_value(value)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))
+}
+
+/// Convert a configuration to the value that should be saved to its base file
+///
+/// Any value that still equals its override is replaced by the value from
+/// the base file, or dropped if the base file doesn't have it.
+pub fn strip_overrides(config: &Config, path: &Path) -> Result<Value> {
+    let mut value = serde_yaml::to_value(config)
+        .with_context(|| "Failed to serialize configuration".to_string())?;
+
+    if path.exists() {
+        let (base, overrides) = load_layers(path)?;
+        restore_overridden(&mut value, &base, &overrides);
+    }
+
+    Ok(value)
+}
+
+/// Read the base layer and the combined override layers
+fn load_layers(path: &Path) -> Result<(Value, Value)> {
+    let base = read_yaml(path)?;
+
+    let mut overrides = Value::Mapping(Mapping::new());
+    let local_path = local_config_path(path);
+    if local_path.exists() {
+        merge_values(&mut overrides, read_yaml(&local_path)?);
+    }
+    merge_values(&mut overrides, env_overrides(std::env::vars()));
+
+    Ok((base, overrides))
+}
+
+/// Read a YAML file into a value
+fn read_yaml(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+    if content.trim().is_empty() {
+        return Ok(Value::Mapping(Mapping::new()));
+    }
+
+    serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))
+}
+
+/// Build an override layer from `WRITING_*` environment variables
+pub fn env_overrides(vars: impl IntoIterator<Item = (String, String)>) -> Value {
+    let mut overrides = Value::Mapping(Mapping::new());
+
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+
+        let keys: Vec<String> = path.split(ENV_SEPARATOR).map(str::to_lowercase).collect();
+        if keys.iter().any(String::is_empty) {
+            continue;
+        }
+
+        let value = serde_yaml::from_str(&raw).unwrap_or(Value::String(raw));
+        let nested = keys
+            .iter()
+            .rev()
+            .fold(value, |value, key| {
+                let mut mapping = Mapping::new();
+                mapping.insert(Value::String(key.clone()), value);
+                Value::Mapping(mapping)
+            });
+        merge_values(&mut overrides, nested);
+    }
+
+    overrides
+}
+
+/// Merge `overlay` into `base`, recursing into mappings
+pub fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Replace values in `value` that equal their override with the base value
+fn restore_overridden(value: &mut Value, base: &Value, overrides: &Value) {
+    let (Value::Mapping(mapping), Value::Mapping(overrides)) = (value, overrides) else {
+        return;
+    };
+
+    for (key, overridden) in overrides {
+        let base_value = base.as_mapping().and_then(|base| base.get(key));
+
+        match mapping.get_mut(key) {
+            Some(current) if overridden.is_mapping() && current.is_mapping() => {
+                restore_overridden(current, base_value.unwrap_or(&Value::Null), overridden);
+            }
+            Some(current) if current == overridden => match base_value {
+                Some(base_value) => *current = base_value.clone(),
+                None => {
+                    mapping.remove(key);
+                }
+            },
+            _ => {}
+        }
+    }
+}
@@ -4,11 +4,13 @@
 //!
 //! ## Features
 //!
-//! - Configuration loading from files
+//! - Configuration loading from and saving to files
+//! - Layered overrides from `config.local.yaml` and `WRITING_*` environment variables
 //! - Topic management
 //! - Configuration validation
 //! - Configuration caching
 //! - Context-specific configuration views
+//! - Redirect map for moved content
 //!
 //! ## Example
 //!
@@ -41,6 +43,7 @@
 //! ```
 
 use common_errors::{Result, WritingError, ResultExt};
+use common_fs::{with_file_lock, write_file_atomic};
 use common_models::{Config, TopicConfig};
 use std::fs;
 use std::path::Path;
@@ -51,11 +54,32 @@ pub mod cache;
 // Export the views module
 pub mod views;
 
+// Export the redirects module
+pub mod redirects;
+
+// Export the shortcodes module
+pub mod shortcodes;
+
+// Export the goals module
+pub mod goals;
+
+// Export the validation module
+pub mod validation;
+
+// Export the layers module
+pub mod layers;
+
 // Re-export the views for convenience
 pub use views::ContentView;
 pub use views::ImageView;
 pub use views::PublicationView;
 pub use views::ConfigView;
+pub use redirects::{add_redirect, load_redirects, redirects_path, Redirect};
+pub use shortcodes::{load_shortcodes, shortcodes_path, Shortcode};
+pub use goals::{goals_path, load_goals, Goal, GoalMetric, GoalPeriod};
+pub use cache::set_check_modifications;
+pub use layers::{load_layered_config, local_config_path};
+pub use validation::{format_config_issues, report_config_issues, validate_config, ConfigIssue};
 
 #[cfg(test)]
 #[path = "../tests/mod.rs"]
@@ -89,6 +113,9 @@ fn get_config_path() -> Result<std::path::PathBuf> {
 }
 
 /// Load configuration from the default path
+///
+/// The configuration is cached for the whole process and reloaded when the
+/// configuration files change; see [`cache::shared_config`] and [`reload_config`].
 pub fn load_config() -> Result<Config> {
     // Check if we're in a test environment
     #[cfg(test)]
@@ -110,10 +137,26 @@ pub fn load_config() -> Result<Config> {
 
     // Default loading behavior
     let config_path = get_config_path()?;
-    load_config_from_path(&config_path)
+    if cfg!(feature = "cache") {
+        cache::shared_config(&config_path, load_layered_config)
+    } else {
+        load_layered_config(&config_path)
+    }
+}
+
+/// Reload the configuration from disk, replacing the cached configuration
+///
+/// Use this after changing environment overrides or when modification checks
+/// are disabled with [`cache::set_check_modifications`].
+pub fn reload_config() -> Result<Config> {
+    clear_config_cache();
+    load_config()
 }
 
 /// Load the configuration file from a specific path
+///
+/// Only the given file is read; use [`load_layered_config`] to apply the
+/// local and environment overrides as `load_config` does.
 pub fn load_config_from_path(path: &Path) -> Result<Config> {
     // Read the file
     let content = fs::read_to_string(path)
@@ -126,6 +169,35 @@ pub fn load_config_from_path(path: &Path) -> Result<Config> {
     Ok(config)
 }
 
+/// Save configuration to the file it is loaded from
+///
+/// Comments and formatting in the existing file are not preserved.
+pub fn save_config(config: &Config) -> Result<()> {
+    let config_path = get_config_path()?;
+    save_config_to_path(config, &config_path)
+}
+
+/// Save configuration to a specific path
+///
+/// Values that come from `config.local.yaml` or the environment are saved as
+/// their value in the existing file, see [`layers::strip_overrides`].
+///
+/// The file is locked while it is saved and replaced in one step, so another
+/// tool reading it never sees a partial configuration.
+pub fn save_config_to_path(config: &Config, path: &Path) -> Result<()> {
+    with_file_lock(path, || {
+        let value = layers::strip_overrides(config, path)?;
+        let content = serde_yaml::to_string(&value)
+            .with_context(|| "Failed to serialize configuration".to_string())?;
+
+        write_file_atomic(path, &content)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))
+    })?;
+
+    clear_config_cache();
+    Ok(())
+}
+
 /// Get all topics from the configuration
 pub fn get_topics() -> Result<Vec<TopicConfig>> {
     let config = load_config()?;
@@ -193,4 +265,5 @@ pub fn validate_topic(topic_key: &str) -> Result<TopicConfig> {
 /// Call this function to force a refresh of cached configuration data.
 pub fn clear_config_cache() {
     cache::ConfigCache::global().clear();
+    cache::invalidate_shared_config();
 }
\ No newline at end of file
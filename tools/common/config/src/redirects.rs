@@ -0,0 +1,90 @@
+//! # Redirect Map
+//!
+//! Moving or renaming content changes its URL. Each relocation is recorded
+//! in a `redirects.yaml` file in the content base directory so that builds
+//! can publish redirects from the old URL to the new one.
+//!
+//! ```yaml
+//! - from: /blog/old-slug
+//!   to: /notes/new-slug
+//! ```
+
+use common_errors::{Result, ResultExt};
+use common_fs::{with_file_lock, write_file_atomic};
+use common_models::Config;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the redirects file in the content base directory
+pub const REDIRECTS_FILE: &str = "redirects.yaml";
+
+/// A redirect from an old content URL path to its current one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redirect {
+    /// Old URL path, e.g. `/blog/old-slug`
+    pub from: String,
+    /// Current URL path, e.g. `/notes/new-slug`
+    pub to: String,
+}
+
+impl Redirect {
+    /// Create a redirect between two topic/slug locations
+    pub fn between(from_topic: &str, from_slug: &str, to_topic: &str, to_slug: &str) -> Self {
+        Self {
+            from: format!("/{}/{}", from_topic, from_slug),
+            to: format!("/{}/{}", to_topic, to_slug),
+        }
+    }
+}
+
+/// Get the path of the redirects file for a configuration
+pub fn redirects_path(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(REDIRECTS_FILE)
+}
+
+/// Load redirects from a file, returning an empty list if it does not exist
+pub fn load_redirects(path: &Path) -> Result<Vec<Redirect>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read redirects file: {}", path.display()))?;
+
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let redirects: Vec<Redirect> = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse redirects file: {}", path.display()))?;
+
+    Ok(redirects)
+}
+
+/// Record a redirect, keeping the map free of chains and loops
+///
+/// Existing redirects that pointed at `redirect.from` are updated to point at
+/// `redirect.to`, and any redirect away from `redirect.to` is dropped since
+/// content lives there again.
+///
+/// The file is locked while it is updated, so concurrent moves don't lose
+/// each other's redirects.
+pub fn add_redirect(path: &Path, redirect: Redirect) -> Result<()> {
+    with_file_lock(path, || {
+        let mut redirects = load_redirects(path)?;
+
+        redirects.retain(|existing| existing.from != redirect.to && existing.from != redirect.from);
+        for existing in redirects.iter_mut() {
+            if existing.to == redirect.from {
+                existing.to = redirect.to.clone();
+            }
+        }
+        redirects.push(redirect);
+
+        let content = serde_yaml::to_string(&redirects)
+            .with_context(|| "Failed to serialize redirects".to_string())?;
+        write_file_atomic(path, &content)
+            .with_context(|| format!("Failed to write redirects file: {}", path.display()))
+    })
+}
@@ -0,0 +1,67 @@
+//! # Shortcodes
+//!
+//! MDX content can use JSX-like components such as `<YouTube id="..." />`.
+//! The components a build knows how to render are listed in a
+//! `shortcodes.yaml` file in the content base directory. Each one is rendered
+//! with a Handlebars partial from `templates/shortcodes/`, or stripped down to
+//! its children.
+//!
+//! ```yaml
+//! - name: YouTube
+//!   partial: youtube
+//! - name: Aside
+//!   strip: true
+//! ```
+
+use common_errors::{Result, ResultExt};
+use common_models::Config;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the shortcodes file in the content base directory
+pub const SHORTCODES_FILE: &str = "shortcodes.yaml";
+
+/// A component that content may use
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shortcode {
+    /// Component tag name, e.g. `YouTube`
+    pub name: String,
+    /// Partial in `templates/shortcodes/` (without `.hbs`), defaulting to the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial: Option<String>,
+    /// Replace the component with its children instead of rendering a partial
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strip: bool,
+}
+
+impl Shortcode {
+    /// Name of the partial used to render the component
+    pub fn partial_name(&self) -> &str {
+        self.partial.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Get the path of the shortcodes file for a configuration
+pub fn shortcodes_path(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(SHORTCODES_FILE)
+}
+
+/// Load shortcodes from a file, returning an empty list if it does not exist
+pub fn load_shortcodes(path: &Path) -> Result<Vec<Shortcode>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read shortcodes file: {}", path.display()))?;
+
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let shortcodes: Vec<Shortcode> = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse shortcodes file: {}", path.display()))?;
+
+    Ok(shortcodes)
+}
@@ -0,0 +1,207 @@
+//! # Configuration Validation
+//!
+//! `load_config` only checks that the YAML parses into a [`Config`]. This
+//! module checks that the configuration also makes sense: directories exist,
+//! topics don't share a directory, the site URL is well formed and image
+//! settings are usable.
+
+use common_models::Config;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Image formats the image tools can produce
+pub const KNOWN_IMAGE_FORMATS: &[&str] = &["jpg", "jpeg", "png", "webp", "avif", "gif"];
+
+/// A problem found in the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Path of the offending field, e.g. `content.topics.blog.directory`
+    pub field: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Check a configuration for problems
+///
+/// Returns an empty list if the configuration is valid. Issues are ordered
+/// by field so the report is stable between runs.
+pub fn validate_config(config: &Config) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    validate_content(config, &mut issues);
+    validate_images(config, &mut issues);
+    validate_publication(config, &mut issues);
+
+    issues.sort_by(|a, b| a.field.cmp(&b.field));
+    issues
+}
+
+/// Format issues as a report suitable for printing on startup
+pub fn format_config_issues(issues: &[ConfigIssue]) -> String {
+    let mut report = format!("Found {} configuration issue(s):\n", issues.len());
+    for issue in issues {
+        report.push_str(&format!("  - {}\n", issue));
+    }
+    report
+}
+
+/// Validate the current configuration and print any issues to stderr
+///
+/// Tools call this on startup. Problems are reported but not fatal, and a
+/// configuration that cannot be loaded is left for the tool itself to report.
+pub fn report_config_issues() -> Vec<ConfigIssue> {
+    let issues = match crate::load_config() {
+        Ok(config) => validate_config(&config),
+        Err(_) => return Vec::new(),
+    };
+
+    if !issues.is_empty() {
+        eprint!("Warning: {}", format_config_issues(&issues));
+    }
+
+    issues
+}
+
+/// Check the content base directory and topic directories
+fn validate_content(config: &Config, issues: &mut Vec<ConfigIssue>) {
+    let base_dir = Path::new(&config.content.base_dir);
+    if !base_dir.is_dir() {
+        issues.push(ConfigIssue::new(
+            "content.base_dir",
+            format!("directory does not exist: {}", base_dir.display()),
+        ));
+    }
+
+    let mut directories: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (key, topic) in &config.content.topics {
+        directories
+            .entry(topic.directory.trim_end_matches('/'))
+            .or_default()
+            .push(key);
+
+        let dir = base_dir.join(&topic.directory);
+        if base_dir.is_dir() && !dir.is_dir() {
+            issues.push(ConfigIssue::new(
+                format!("content.topics.{}.directory", key),
+                format!("directory does not exist: {}", dir.display()),
+            ));
+        }
+    }
+
+    for (directory, mut keys) in directories {
+        if keys.len() > 1 {
+            keys.sort();
+            issues.push(ConfigIssue::new(
+                format!("content.topics.{}.directory", keys[0]),
+                format!("directory '{}' is shared by topics: {}", directory, keys.join(", ")),
+            ));
+        }
+    }
+
+    if let Some(default_topic) = &config.default_topic {
+        if !config.content.topics.contains_key(default_topic) {
+            issues.push(ConfigIssue::new(
+                "default_topic",
+                format!("unknown topic: {}", default_topic),
+            ));
+        }
+    }
+}
+
+/// Check image formats, sizes and quality settings
+fn validate_images(config: &Config, issues: &mut Vec<ConfigIssue>) {
+    let images = &config.images;
+
+    if images.formats.is_empty() {
+        issues.push(ConfigIssue::new("images.formats", "no image formats configured"));
+    }
+    for format in &images.formats {
+        if !is_known_format(format) {
+            issues.push(ConfigIssue::new(
+                "images.formats",
+                format!("unknown image format '{}' (expected one of: {})", format, KNOWN_IMAGE_FORMATS.join(", ")),
+            ));
+        }
+    }
+
+    if images.sizes.is_empty() {
+        issues.push(ConfigIssue::new("images.sizes", "no image sizes configured"));
+    }
+    for (name, size) in &images.sizes {
+        if size.width == 0 || size.height == 0 {
+            issues.push(ConfigIssue::new(
+                format!("images.sizes.{}", name),
+                format!("width and height must be positive, got {}x{}", size.width, size.height),
+            ));
+        }
+    }
+
+    for (format, settings) in images.quality.iter().flatten() {
+        if !is_known_format(format) {
+            issues.push(ConfigIssue::new(
+                format!("images.quality.{}", format),
+                format!("unknown image format '{}'", format),
+            ));
+        }
+        for (setting, value) in settings {
+            if !(1..=100).contains(value) {
+                issues.push(ConfigIssue::new(
+                    format!("images.quality.{}.{}", format, setting),
+                    format!("quality must be between 1 and 100, got {}", value),
+                ));
+            }
+        }
+    }
+}
+
+/// Check the publication site URL
+fn validate_publication(config: &Config, issues: &mut Vec<ConfigIssue>) {
+    if let Some(site_url) = &config.publication.site_url {
+        if !is_valid_site_url(site_url) {
+            issues.push(ConfigIssue::new(
+                "publication.site_url",
+                format!("invalid URL '{}' (expected http:// or https:// followed by a host)", site_url),
+            ));
+        }
+    }
+}
+
+/// Whether a format name is one the image tools support
+fn is_known_format(format: &str) -> bool {
+    KNOWN_IMAGE_FORMATS.contains(&format.to_lowercase().as_str())
+}
+
+/// Whether a URL has an http(s) scheme and a plausible host
+fn is_valid_site_url(url: &str) -> bool {
+    let rest = match url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        Some(rest) => rest,
+        None => return false,
+    };
+
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = host.rsplit_once(':').map_or(host, |(host, port)| {
+        if port.chars().all(|c| c.is_ascii_digit()) { host } else { "" }
+    });
+
+    !host.is_empty()
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && host.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        && !url.chars().any(char::is_whitespace)
+}
@@ -310,4 +310,81 @@ images:
     let config = result2.unwrap();
     assert_eq!(config.content.base_dir, "content");
     assert_eq!(config.publication.author, "Test Author");
-}
\ No newline at end of file
+}
+
+/// The shared configuration is process-wide, so these tests run one at a time
+static SHARED_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Write a complete configuration with the given author
+fn write_full_config(path: &Path, author: &str) {
+    let config_content = format!(
+        r#"title: "Test"
+email: "test@example.com"
+url: "https://example.com"
+image: "image.png"
+content:
+  base_dir: "content"
+  topics: {{}}
+images:
+  formats: [jpg]
+  sizes: {{}}
+publication:
+  author: "{}"
+  copyright: "Test"
+"#,
+        author
+    );
+    fs::write(path, config_content).unwrap();
+}
+
+#[test]
+fn test_shared_config_reloads_when_file_changes() {
+    let _lock = SHARED_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    crate::cache::invalidate_shared_config();
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+    write_full_config(&path, "First");
+
+    let loads = std::cell::Cell::new(0);
+    let load = |path: &Path| {
+        loads.set(loads.get() + 1);
+        crate::load_layered_config(path)
+    };
+
+    assert_eq!(crate::cache::shared_config(&path, load).unwrap().publication.author, "First");
+    assert_eq!(crate::cache::shared_config(&path, load).unwrap().publication.author, "First");
+    assert_eq!(loads.get(), 1);
+
+    write_full_config(&path, "Second author");
+    assert_eq!(crate::cache::shared_config(&path, load).unwrap().publication.author, "Second author");
+    assert_eq!(loads.get(), 2);
+
+    // A local override file appearing also invalidates the entry
+    fs::write(dir.path().join("config.local.yaml"), "publication:\n  author: Local\n").unwrap();
+    assert_eq!(crate::cache::shared_config(&path, load).unwrap().publication.author, "Local");
+    assert_eq!(loads.get(), 3);
+}
+
+#[test]
+fn test_shared_config_without_modification_checks() {
+    let _lock = SHARED_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    crate::cache::invalidate_shared_config();
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+    write_full_config(&path, "First");
+
+    crate::cache::set_check_modifications(false);
+    let first = crate::cache::shared_config(&path, crate::load_layered_config).unwrap();
+    write_full_config(&path, "Second author");
+    let cached = crate::cache::shared_config(&path, crate::load_layered_config).unwrap();
+
+    crate::cache::invalidate_shared_config();
+    let reloaded = crate::cache::shared_config(&path, crate::load_layered_config).unwrap();
+    crate::cache::set_check_modifications(true);
+
+    assert_eq!(first.publication.author, "First");
+    assert_eq!(cached.publication.author, "First");
+    assert_eq!(reloaded.publication.author, "Second author");
+}
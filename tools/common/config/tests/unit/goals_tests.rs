@@ -0,0 +1,47 @@
+//! Tests for the goals file
+
+use crate::goals::{load_goals, Goal, GoalMetric, GoalPeriod};
+use tempfile::tempdir;
+
+#[test]
+fn test_load_missing_goals_file_is_empty() {
+    let dir = tempdir().unwrap();
+    let goals = load_goals(&dir.path().join("goals.yaml")).unwrap();
+    assert!(goals.is_empty());
+}
+
+#[test]
+fn test_load_goals() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("goals.yaml");
+    std::fs::write(
+        &path,
+        "- metric: words\n  target: 2000\n  period: week\n- metric: posts\n  target: 2\n  period: month\n  topic: blog\n",
+    )
+    .unwrap();
+
+    let goals = load_goals(&path).unwrap();
+    assert_eq!(goals, vec![
+        Goal {
+            metric: GoalMetric::Words,
+            target: 2000,
+            period: GoalPeriod::Week,
+            topic: None,
+        },
+        Goal {
+            metric: GoalMetric::Posts,
+            target: 2,
+            period: GoalPeriod::Month,
+            topic: Some("blog".to_string()),
+        },
+    ]);
+}
+
+#[test]
+fn test_load_goals_rejects_unknown_metric() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("goals.yaml");
+    std::fs::write(&path, "- metric: pages\n  target: 1\n  period: week\n").unwrap();
+
+    assert!(load_goals(&path).is_err());
+}
@@ -0,0 +1,80 @@
+//! Tests for layered configuration loading
+
+use crate::layers::{env_overrides, load_layered_config, local_config_path, merge_values, strip_overrides};
+use serde_yaml::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+const BASE_CONFIG: &str = r#"title: "Test"
+email: "test@example.com"
+url: "https://example.com"
+image: "image.png"
+content:
+  base_dir: "content"
+  topics:
+    blog:
+      name: Blog
+      description: Blog posts
+      directory: blog
+images:
+  formats: [jpg]
+  sizes: {}
+publication:
+  author: Base Author
+  copyright: Test
+"#;
+
+#[test]
+fn test_local_config_path() {
+    assert_eq!(
+        local_config_path(Path::new("/site/config.yaml")),
+        Path::new("/site/config.local.yaml")
+    );
+}
+
+#[test]
+fn test_env_overrides_nest_on_double_underscore() {
+    let overrides = env_overrides(vec![
+        ("WRITING_CONTENT__BASE_DIR".to_string(), "/srv/content".to_string()),
+        ("WRITING_IMAGES__SIZES__SMALL__WIDTH".to_string(), "480".to_string()),
+        ("WRITING_".to_string(), "ignored".to_string()),
+        ("HOME".to_string(), "/root".to_string()),
+    ]);
+
+    let expected: Value = serde_yaml::from_str(
+        "content:\n  base_dir: /srv/content\nimages:\n  sizes:\n    small:\n      width: 480\n",
+    )
+    .unwrap();
+    assert_eq!(overrides, expected);
+}
+
+#[test]
+fn test_merge_values_recurses_into_mappings_and_replaces_lists() {
+    let mut base: Value = serde_yaml::from_str("a: {b: 1, c: 2}\nlist: [1, 2]").unwrap();
+    merge_values(&mut base, serde_yaml::from_str("a: {c: 3}\nlist: [9]").unwrap());
+
+    assert_eq!(base, serde_yaml::from_str::<Value>("a: {b: 1, c: 3}\nlist: [9]").unwrap());
+}
+
+#[test]
+fn test_local_file_overrides_base_and_is_not_saved() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+    fs::write(&path, BASE_CONFIG).unwrap();
+    fs::write(
+        dir.path().join("config.local.yaml"),
+        "content:\n  base_dir: /home/me/content\n",
+    )
+    .unwrap();
+
+    let mut config = load_layered_config(&path).unwrap();
+    assert_eq!(config.content.base_dir, "/home/me/content");
+    assert_eq!(config.content.topics["blog"].directory, "blog");
+    assert_eq!(config.publication.author, "Base Author");
+
+    config.publication.author = "New Author".to_string();
+    let saved = strip_overrides(&config, &path).unwrap();
+    assert_eq!(saved["content"]["base_dir"], Value::from("content"));
+    assert_eq!(saved["publication"]["author"], Value::from("New Author"));
+}
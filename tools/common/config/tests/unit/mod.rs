@@ -5,4 +5,9 @@
 pub mod config_loading_tests;
 pub mod config_caching_tests;
 pub mod views_tests;
-pub mod config_mock_tests;
\ No newline at end of file
+pub mod config_mock_tests;
+pub mod redirects_tests;
+pub mod validation_tests;
+pub mod layers_tests;
+pub mod shortcodes_tests;
+pub mod goals_tests;
@@ -0,0 +1,53 @@
+//! Tests for the redirect map
+
+use crate::redirects::{add_redirect, load_redirects, Redirect};
+use tempfile::tempdir;
+
+#[test]
+fn test_load_missing_redirects_file_is_empty() {
+    let dir = tempdir().unwrap();
+    let redirects = load_redirects(&dir.path().join("redirects.yaml")).unwrap();
+    assert!(redirects.is_empty());
+}
+
+#[test]
+fn test_add_redirect_round_trips() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("redirects.yaml");
+
+    add_redirect(&path, Redirect::between("blog", "old", "notes", "new")).unwrap();
+
+    let redirects = load_redirects(&path).unwrap();
+    assert_eq!(redirects, vec![Redirect {
+        from: "/blog/old".to_string(),
+        to: "/notes/new".to_string(),
+    }]);
+}
+
+#[test]
+fn test_add_redirect_collapses_chains() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("redirects.yaml");
+
+    add_redirect(&path, Redirect::between("blog", "a", "blog", "b")).unwrap();
+    add_redirect(&path, Redirect::between("blog", "b", "notes", "c")).unwrap();
+
+    let redirects = load_redirects(&path).unwrap();
+    assert_eq!(redirects.len(), 2);
+    assert!(redirects.iter().all(|r| r.to == "/notes/c"));
+}
+
+#[test]
+fn test_moving_back_removes_loop() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("redirects.yaml");
+
+    add_redirect(&path, Redirect::between("blog", "a", "notes", "a")).unwrap();
+    add_redirect(&path, Redirect::between("notes", "a", "blog", "a")).unwrap();
+
+    let redirects = load_redirects(&path).unwrap();
+    assert_eq!(redirects, vec![Redirect {
+        from: "/notes/a".to_string(),
+        to: "/blog/a".to_string(),
+    }]);
+}
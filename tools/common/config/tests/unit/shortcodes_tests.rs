@@ -0,0 +1,33 @@
+//! Tests for the shortcodes file
+
+use crate::shortcodes::{load_shortcodes, Shortcode};
+use tempfile::tempdir;
+
+#[test]
+fn test_load_missing_shortcodes_file_is_empty() {
+    let dir = tempdir().unwrap();
+    let shortcodes = load_shortcodes(&dir.path().join("shortcodes.yaml")).unwrap();
+    assert!(shortcodes.is_empty());
+}
+
+#[test]
+fn test_load_shortcodes_defaults() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("shortcodes.yaml");
+    std::fs::write(&path, "- name: YouTube\n  partial: youtube\n- name: Aside\n  strip: true\n").unwrap();
+
+    let shortcodes = load_shortcodes(&path).unwrap();
+    assert_eq!(shortcodes, vec![
+        Shortcode {
+            name: "YouTube".to_string(),
+            partial: Some("youtube".to_string()),
+            strip: false,
+        },
+        Shortcode {
+            name: "Aside".to_string(),
+            partial: None,
+            strip: true,
+        },
+    ]);
+    assert_eq!(shortcodes[1].partial_name(), "Aside");
+}
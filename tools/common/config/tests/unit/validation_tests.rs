@@ -0,0 +1,109 @@
+//! Tests for configuration validation
+
+use crate::validation::validate_config;
+use common_models::{Config, ContentConfig, ImageConfig, ImageSize, PublicationConfig, TopicConfig};
+use std::collections::HashMap;
+use std::fs;
+use tempfile::tempdir;
+
+fn topic(directory: &str) -> TopicConfig {
+    TopicConfig {
+        name: directory.to_string(),
+        description: String::new(),
+        directory: directory.to_string(),
+    }
+}
+
+fn valid_config(base_dir: &str) -> Config {
+    let mut topics = HashMap::new();
+    topics.insert("blog".to_string(), topic("blog"));
+
+    let mut sizes = HashMap::new();
+    sizes.insert("small".to_string(), ImageSize {
+        width: 480,
+        height: 320,
+        description: "Small".to_string(),
+    });
+
+    let mut jpg = HashMap::new();
+    jpg.insert("standard".to_string(), 85);
+    let mut quality = HashMap::new();
+    quality.insert("jpg".to_string(), jpg);
+
+    Config {
+        title: "Test".to_string(),
+        email: "test@example.com".to_string(),
+        url: "https://example.com".to_string(),
+        image: "image.png".to_string(),
+        default_topic: Some("blog".to_string()),
+        content: ContentConfig {
+            base_dir: base_dir.to_string(),
+            topics,
+            tags: None,
+        },
+        images: ImageConfig {
+            formats: vec!["jpg".to_string(), "webp".to_string()],
+            format_descriptions: None,
+            sizes,
+            naming: None,
+            quality: Some(quality),
+        },
+        publication: PublicationConfig {
+            author: "Test".to_string(),
+            copyright: "Test".to_string(),
+            site_url: Some("https://example.com:8080/blog".to_string()),
+        },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
+    }
+}
+
+#[test]
+fn test_valid_config_has_no_issues() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("blog")).unwrap();
+
+    let config = valid_config(dir.path().to_str().unwrap());
+    assert_eq!(validate_config(&config), Vec::new());
+}
+
+#[test]
+fn test_invalid_config_reports_each_problem() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("blog")).unwrap();
+
+    let mut config = valid_config(dir.path().to_str().unwrap());
+    config.content.topics.insert("notes".to_string(), topic("blog/"));
+    config.content.topics.insert("missing".to_string(), topic("missing"));
+    config.images.formats.push("tiff".to_string());
+    config.images.sizes.clear();
+    config.images.quality.as_mut().unwrap().get_mut("jpg").unwrap().insert("high".to_string(), 150);
+    config.publication.site_url = Some("example.com".to_string());
+
+    let fields: Vec<String> = validate_config(&config).into_iter().map(|issue| issue.field).collect();
+    assert_eq!(fields, vec![
+        "content.topics.blog.directory",
+        "content.topics.missing.directory",
+        "images.formats",
+        "images.quality.jpg.high",
+        "images.sizes",
+        "publication.site_url",
+    ]);
+}
+
+#[test]
+fn test_missing_base_dir_is_reported_once() {
+    let dir = tempdir().unwrap();
+    let config = valid_config(dir.path().join("missing").to_str().unwrap());
+
+    let issues = validate_config(&config);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].field, "content.base_dir");
+}
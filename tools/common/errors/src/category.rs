@@ -39,6 +39,8 @@ pub enum ErrorCategory {
     Template,
     /// Parsing errors
     Parsing,
+    /// Operations cancelled by the user
+    Cancelled,
     /// Unexpected errors
     Unexpected,
 }
@@ -71,6 +73,7 @@ impl ErrorCategory {
             ErrorCategory::Command => "The command execution failed.",
             ErrorCategory::Template => "There was an error processing the template.",
             ErrorCategory::Parsing => "There was an error parsing the content.",
+            ErrorCategory::Cancelled => "The operation was cancelled.",
             ErrorCategory::Unexpected => "An unexpected error occurred.",
         }
     }
@@ -102,6 +105,7 @@ impl ErrorCategory {
             ErrorCategory::Command => "Check if the command exists and you have permission to run it.",
             ErrorCategory::Template => "Check your template syntax and make sure all variables are defined.",
             ErrorCategory::Parsing => "Check the syntax of your content and make sure it's valid.",
+            ErrorCategory::Cancelled => "Run the command again to finish the operation.",
             ErrorCategory::Unexpected => "This is a bug. Please report it to the developers.",
         }
     }
@@ -125,6 +129,10 @@ impl From<&WritingError> for ErrorCategory {
             WritingError::CommandError(_) => ErrorCategory::Command,
             WritingError::TemplateError(_) => ErrorCategory::Template,
             WritingError::ContentParsingError(_) => ErrorCategory::Parsing,
+            WritingError::Cancelled(_) => ErrorCategory::Cancelled,
+            WritingError::Context { source, .. } | WritingError::Transient(source) => {
+                ErrorCategory::from(source.as_ref())
+            }
             WritingError::Other(_) => ErrorCategory::Unexpected,
         }
     }
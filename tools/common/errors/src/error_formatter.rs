@@ -147,8 +147,8 @@ impl ErrorFormatter {
             output.push_str(&format!("{}{} {}\n", indent, context_label, context));
         }
 
-        // Add error source chain
-        let mut current_error = error.source();
+        // Add the causes of the underlying error; the contexts wrapping it are shown above
+        let mut current_error = error.root_cause().source();
         let mut depth = 0;
 
         if current_error.is_some() {
@@ -206,8 +206,8 @@ impl ErrorFormatter {
             output.push_str(&format!("{}{} {}\n", indent, context_label, context));
         }
 
-        // Add error source chain
-        let mut current_error = error.source();
+        // Add the causes of the underlying error; the contexts wrapping it are shown above
+        let mut current_error = error.root_cause().source();
         let mut depth = 0;
 
         if current_error.is_some() {
@@ -314,6 +314,9 @@ impl ErrorFormatter {
             ErrorKind::UnsupportedOperationError => {
                 Some("This operation is not supported in the current context or configuration.")
             },
+            ErrorKind::Cancelled => {
+                Some("The operation was cancelled. Run it again to complete the remaining work.")
+            },
             ErrorKind::UnknownError => {
                 Some("An unexpected error occurred. Check the logs for more information.")
             },
@@ -381,6 +384,7 @@ impl ErrorFormatter {
             ErrorKind::UnauthorizedError => "Unauthorized",
             ErrorKind::LockError => "Lock",
             ErrorKind::UnsupportedOperationError => "Unsupported Operation",
+            ErrorKind::Cancelled => "Cancelled",
             ErrorKind::UnknownError => "Unknown",
             ErrorKind::ConfigError => "Configuration",
             ErrorKind::ContentNotFound => "Content Not Found",
@@ -41,6 +41,8 @@ mod macros;
 // mod error;
 // Add the error formatter module definition
 mod error_formatter;
+// Add the retry module definition
+mod retry;
 
 // Add comprehensive test modules
 #[cfg(test)]
@@ -62,6 +64,9 @@ pub use reporting::{ErrorReporter, ErrorDisplayStyle, get_default_reporter,
 // pub use crate::try_with_context;
 // pub use crate::error;
 
+// Re-export the retry helpers
+pub use retry::{retry_with_backoff, RetryPolicy, Retryable};
+
 // Re-export the error formatter
 pub use error_formatter::{
     ErrorFormatter, ErrorFormatterExt, Verbosity,
@@ -131,6 +136,8 @@ pub enum ErrorKind {
     LockError,
     /// Unsupported operation error
     UnsupportedOperationError,
+    /// Operation cancelled error
+    Cancelled,
     /// Unknown error
     UnknownError,
     /// Other error
@@ -203,8 +210,26 @@ pub enum WritingError {
     /// Error for content parsing failures
     ContentParsingError(String),
 
+    /// Error when an operation was cancelled before it completed
+    Cancelled(String),
+
     /// Generic error for other cases
     Other(String),
+
+    /// An error with a description of what was being done when it happened
+    ///
+    /// Added by [`ResultExt::with_context`]. Contexts nest, so the outermost
+    /// describes the highest-level operation.
+    Context {
+        /// What was being done
+        context: String,
+        /// The error that occurred
+        source: Box<WritingError>,
+    },
+
+    /// An error that is likely to go away if the operation is retried, such
+    /// as a network timeout
+    Transient(Box<WritingError>),
 }
 
 /// Result type alias for the writing tools
@@ -393,6 +418,83 @@ impl WritingError {
         WritingError::ContentParsingError(msg.as_ref().to_string())
     }
 
+    /// Create a new cancelled error
+    ///
+    /// # Parameters
+    ///
+    /// * `msg` - Description of the operation that was cancelled
+    ///
+    /// # Returns
+    ///
+    /// A new WritingError::Cancelled
+    pub fn cancelled<S: AsRef<str>>(msg: S) -> Self {
+        WritingError::Cancelled(msg.as_ref().to_string())
+    }
+
+    /// Check whether this error was caused by a cancellation request
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.root_cause(), WritingError::Cancelled(_))
+    }
+
+    /// Mark an error as transient, so the operation that failed can be retried
+    ///
+    /// # Parameters
+    ///
+    /// * `err` - The error that occurred
+    ///
+    /// # Returns
+    ///
+    /// A new WritingError::Transient
+    pub fn transient(err: WritingError) -> Self {
+        WritingError::Transient(Box::new(err))
+    }
+
+    /// Check whether the operation that failed is worth retrying
+    ///
+    /// Transient errors and network, timeout and lock errors are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            WritingError::Transient(_) => true,
+            WritingError::Context { source, .. } => source.is_retryable(),
+            _ => retry::is_transient_kind(self.kind()),
+        }
+    }
+
+    /// Wrap this error with a description of what was being done
+    pub fn with_context<S: AsRef<str>>(self, context: S) -> Self {
+        WritingError::Context {
+            context: context.as_ref().to_string(),
+            source: Box::new(self),
+        }
+    }
+
+    /// The underlying error, without any context or transient marker
+    pub fn root_cause(&self) -> &WritingError {
+        let mut error = self;
+        loop {
+            error = match error {
+                WritingError::Context { source, .. } | WritingError::Transient(source) => source,
+                _ => return error,
+            };
+        }
+    }
+
+    /// Every context added to this error, outermost first
+    pub fn contexts(&self) -> Vec<&str> {
+        let mut contexts = Vec::new();
+        let mut error = self;
+        loop {
+            error = match error {
+                WritingError::Context { context, source } => {
+                    contexts.push(context.as_str());
+                    source
+                }
+                WritingError::Transient(source) => source,
+                _ => return contexts,
+            };
+        }
+    }
+
     /// Create a new path error
     ///
     /// # Parameters
@@ -450,11 +552,16 @@ impl WritingError {
             WritingError::CommandError(_) => ErrorKind::CommandError,
             WritingError::TemplateError(_) => ErrorKind::TemplateError,
             WritingError::ContentParsingError(_) => ErrorKind::ContentParsingError,
+            WritingError::Cancelled(_) => ErrorKind::Cancelled,
             WritingError::Other(_) => ErrorKind::Other,
+            WritingError::Context { source, .. } | WritingError::Transient(source) => source.kind(),
         }
     }
 
     /// Get the error message
+    ///
+    /// This is the message of the underlying error. The contexts it was
+    /// wrapped in are available from [`WritingError::context`].
     pub fn message(&self) -> String {
         match self {
             WritingError::ConfigError(msg) => msg.clone(),
@@ -472,18 +579,30 @@ impl WritingError {
             WritingError::CommandError(msg) => msg.clone(),
             WritingError::TemplateError(msg) => msg.clone(),
             WritingError::ContentParsingError(msg) => msg.clone(),
+            WritingError::Cancelled(msg) => format!("Operation cancelled: {}", msg),
             WritingError::Other(msg) => msg.clone(),
+            WritingError::Context { source, .. } | WritingError::Transient(source) => source.message(),
         }
     }
 
-    /// Get the error context
+    /// Get the contexts this error was wrapped in, outermost first, joined with `: `
     pub fn context(&self) -> Option<String> {
-        None
+        let contexts = self.contexts();
+        if contexts.is_empty() {
+            None
+        } else {
+            Some(contexts.join(": "))
+        }
     }
 
     /// Get the source error
+    ///
+    /// For an error with context or a transient error, this is the error it wraps.
     pub fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        match self {
+            WritingError::Context { source, .. } | WritingError::Transient(source) => Some(source.as_ref()),
+            _ => None,
+        }
     }
 
     /// Get the backtrace
@@ -522,7 +641,8 @@ impl From<walkdir::Error> for WritingError {
 
 impl From<anyhow::Error> for WritingError {
     fn from(err: anyhow::Error) -> Self {
-        WritingError::other(err.to_string())
+        // The alternate format includes anyhow's context chain
+        WritingError::other(format!("{:#}", err))
     }
 }
 
@@ -545,13 +665,7 @@ where
         F: FnOnce() -> C,
         C: AsRef<str>,
     {
-        match self {
-            Ok(value) => Ok(value),
-            Err(err) => {
-                let _context = f(); // Mark as used but we don't use it yet
-                Err(err.into())
-            }
-        }
+        self.map_err(|err| err.into().with_context(f()))
     }
 
     fn file_not_found_if_not_exists<P: AsRef<Path>>(self, path: P) -> Result<T> {
@@ -0,0 +1,110 @@
+//! # Retrying Transient Errors
+//!
+//! Network requests fail for reasons that go away on their own: a timeout, a
+//! dropped connection, a busy lock. This module classifies those errors as
+//! retryable and retries an operation with exponential backoff until it
+//! succeeds, fails for good, or runs out of attempts.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use common_errors::{retry_with_backoff, RetryPolicy, WritingError};
+//!
+//! let mut attempts = 0;
+//! let result = retry_with_backoff(
+//!     || {
+//!         attempts += 1;
+//!         if attempts < 3 {
+//!             Err(WritingError::transient(WritingError::other("connection reset")))
+//!         } else {
+//!             Ok(attempts)
+//!         }
+//!     },
+//!     &RetryPolicy::immediate(3),
+//! );
+//! assert_eq!(result, Ok(3));
+//! ```
+
+use std::time::Duration;
+
+use crate::{ErrorKind, WritingError};
+
+/// An error that knows whether the failed operation is worth trying again
+pub trait Retryable {
+    /// Whether the error is likely to go away if the operation is retried
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for WritingError {
+    fn is_retryable(&self) -> bool {
+        WritingError::is_retryable(self)
+    }
+}
+
+/// How many times to try an operation and how long to wait between attempts
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first
+    pub max_attempts: u32,
+    /// Wait before the first retry
+    pub initial_delay: Duration,
+    /// Longest wait between attempts
+    pub max_delay: Duration,
+    /// Factor the wait grows by after each retry
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_attempts` times in total without waiting
+    pub fn immediate(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            multiplier: 1.0,
+        }
+    }
+
+    /// Wait before retry number `retry`, counting from 1
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.powi(retry.saturating_sub(1) as i32);
+        self.initial_delay.mul_f64(factor).min(self.max_delay)
+    }
+}
+
+/// Run `op` until it succeeds, fails with an error that isn't retryable, or
+/// has been tried `policy.max_attempts` times
+///
+/// Returns the first success or the last error.
+pub fn retry_with_backoff<T, E, F>(mut op: F, policy: &RetryPolicy) -> std::result::Result<T, E>
+where
+    F: FnMut() -> std::result::Result<T, E>,
+    E: Retryable,
+{
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(err) if err.is_retryable() && attempt < policy.max_attempts => {
+                std::thread::sleep(policy.delay_for(attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether errors of a kind are usually transient
+pub(crate) fn is_transient_kind(kind: ErrorKind) -> bool {
+    matches!(kind, ErrorKind::NetworkError | ErrorKind::TimeoutError | ErrorKind::LockError)
+}
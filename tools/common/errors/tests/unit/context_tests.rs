@@ -149,3 +149,27 @@ fn test_with_enhanced_context_ok_result() {
     // Check that the result is still Ok with the same value
     assert_eq!(result, Ok(42));
 }
+
+#[test]
+fn test_with_context_preserves_the_chain() {
+    let result: Result<()> = Err(WritingError::validation_error("Invalid value"));
+    let error = result
+        .with_context(|| "While validating input")
+        .with_context(|| "While building site")
+        .unwrap_err();
+
+    assert_eq!(error.contexts(), vec!["While building site", "While validating input"]);
+    assert_eq!(error.context(), Some("While building site: While validating input".to_string()));
+    assert_eq!(error.message(), "Invalid value");
+    assert_eq!(error.root_cause(), &WritingError::validation_error("Invalid value"));
+
+    // The context is shown alongside the underlying message
+    let display = error.to_string();
+    assert!(display.contains("Invalid value"));
+    assert!(display.contains("While building site: While validating input"));
+
+    // Each layer is a source, so anyhow can walk the whole chain
+    let error = anyhow::Error::from(error);
+    assert_eq!(error.chain().count(), 3);
+    assert_eq!(error.root_cause().to_string(), WritingError::validation_error("Invalid value").to_string());
+}
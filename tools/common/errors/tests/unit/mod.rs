@@ -7,4 +7,5 @@ pub mod context_tests;
 pub mod validation_tests;
 pub mod category_tests;
 pub mod error_formatting_tests;
-pub mod reporting_tests;
\ No newline at end of file
+pub mod reporting_tests;
+pub mod retry_tests;
\ No newline at end of file
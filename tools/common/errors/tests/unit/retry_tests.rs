@@ -0,0 +1,75 @@
+//! Tests for retrying transient errors
+//!
+//! This module contains tests for error retry classification and backoff.
+
+use crate::helpers::{retry_with_backoff, ResultExt, RetryPolicy, WritingError};
+use std::time::Duration;
+
+#[test]
+fn test_is_retryable() {
+    let transient = WritingError::transient(WritingError::other("connection reset"));
+    assert!(transient.is_retryable());
+    assert_eq!(transient.message(), "connection reset");
+
+    // Context doesn't hide that the underlying error is transient
+    let with_context: Result<(), WritingError> = Err(transient);
+    assert!(with_context.with_context(|| "While checking links").unwrap_err().is_retryable());
+
+    assert!(!WritingError::validation_error("Invalid value").is_retryable());
+    assert!(!WritingError::file_not_found("missing.md").is_retryable());
+}
+
+#[test]
+fn test_retry_with_backoff_stops_on_success_or_permanent_errors() {
+    let mut attempts = 0;
+    let result = retry_with_backoff(
+        || {
+            attempts += 1;
+            if attempts < 3 {
+                Err(WritingError::transient(WritingError::other("timed out")))
+            } else {
+                Ok("done")
+            }
+        },
+        &RetryPolicy::immediate(5),
+    );
+    assert_eq!(result, Ok("done"));
+    assert_eq!(attempts, 3);
+
+    let mut attempts = 0;
+    let result: Result<(), WritingError> = retry_with_backoff(
+        || {
+            attempts += 1;
+            Err(WritingError::validation_error("Invalid value"))
+        },
+        &RetryPolicy::immediate(5),
+    );
+    assert!(result.is_err());
+    assert_eq!(attempts, 1);
+
+    let mut attempts = 0;
+    let result: Result<(), WritingError> = retry_with_backoff(
+        || {
+            attempts += 1;
+            Err(WritingError::transient(WritingError::other("timed out")))
+        },
+        &RetryPolicy::immediate(4),
+    );
+    assert!(result.unwrap_err().is_retryable());
+    assert_eq!(attempts, 4);
+}
+
+#[test]
+fn test_retry_policy_delays_grow_to_the_maximum() {
+    let policy = RetryPolicy {
+        max_attempts: 5,
+        initial_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(300),
+        multiplier: 2.0,
+    };
+
+    assert_eq!(policy.delay_for(1), Duration::from_millis(100));
+    assert_eq!(policy.delay_for(2), Duration::from_millis(200));
+    assert_eq!(policy.delay_for(3), Duration::from_millis(300));
+    assert_eq!(policy.delay_for(4), Duration::from_millis(300));
+}
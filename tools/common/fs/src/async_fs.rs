@@ -0,0 +1,115 @@
+//! # Async Filesystem Operations
+//!
+//! Non-blocking versions of the common file operations, built on `tokio::fs`.
+//! They behave like their blocking counterparts and return the same errors,
+//! so code can move between the two without changing how it handles
+//! failures. Enabled with the `async` feature.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use common_fs::async_fs;
+//! use std::path::Path;
+//!
+//! async fn word_count(path: &Path) -> common_errors::Result<usize> {
+//!     let content = async_fs::read_file(path).await?;
+//!     Ok(content.split_whitespace().count())
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use common_errors::{Result, WritingError};
+use tokio::fs;
+
+use crate::file::{read_error, write_error};
+
+/// Read a file's contents as a string
+pub async fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    fs::read_to_string(path).await.map_err(|e| read_error(path, e))
+}
+
+/// Write content to a file
+pub async fn write_file<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
+    let path = path.as_ref();
+    fs::write(path, content).await.map_err(|e| write_error(path, e))
+}
+
+/// Find all files in a path with a specific extension
+///
+/// Files are returned in no particular order.
+pub async fn find_files_with_extension(base_path: &Path, extension: &str) -> Result<Vec<PathBuf>> {
+    if !fs::try_exists(base_path).await.unwrap_or(false) {
+        return Err(WritingError::directory_not_found(base_path));
+    }
+
+    let dot_extension = format!(".{}", extension);
+    let mut files = Vec::new();
+    let mut pending = vec![base_path.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let mut entries = fs::read_dir(&dir).await.map_err(|e| dir_error(&dir, e))?;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| dir_error(&dir, e))? {
+            let path = entry.path();
+            let file_type = entry.file_type().await.map_err(|e| dir_error(&path, e))?;
+
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() {
+                let matches = match path.extension() {
+                    Some(ext) => ext == extension,
+                    None => path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.ends_with(&dot_extension)),
+                };
+                if matches {
+                    files.push(path);
+                }
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Copy a directory and everything in it, creating `dst` if needed
+pub async fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
+    let mut pending = vec![(src.to_path_buf(), dst.to_path_buf())];
+
+    while let Some((src, dst)) = pending.pop() {
+        fs::create_dir_all(&dst).await.map_err(|e| {
+            WritingError::IoError(format!("Unable to create target directory during copy: {}: {}", dst.display(), e))
+        })?;
+
+        let mut entries = fs::read_dir(&src).await.map_err(|e| dir_error(&src, e))?;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| dir_error(&src, e))? {
+            let src_path = entry.path();
+            let dst_path = dst.join(entry.file_name());
+
+            if entry.file_type().await.map_err(|e| dir_error(&src_path, e))?.is_dir() {
+                pending.push((src_path, dst_path));
+            } else {
+                fs::copy(&src_path, &dst_path).await.map_err(|e| {
+                    WritingError::IoError(format!(
+                        "Failed to copy {} to {}: {}",
+                        src_path.display(),
+                        dst_path.display(),
+                        e
+                    ))
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn dir_error(path: &Path, e: std::io::Error) -> WritingError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => WritingError::directory_not_found(path),
+        std::io::ErrorKind::PermissionDenied => WritingError::permission_denied(path),
+        _ => WritingError::IoError(format!("Failed to read directory {}: {}", path.display(), e)),
+    }
+}
@@ -0,0 +1,86 @@
+//! # Atomic Writes and File Locks
+//!
+//! Two tools running at once, such as a build and an edit, can otherwise
+//! leave each other a half-written file. An atomic write replaces a file in
+//! one step, so readers see either the old content or the new. A file lock
+//! keeps read-modify-write updates from different processes from
+//! interleaving.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common_errors::{Result, WritingError};
+
+use crate::file::write_error;
+
+/// Distinguishes temporary files written by threads of the same process
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Write content to a file so readers never see it half-written
+///
+/// The content is written to a temporary file next to `path`, flushed to
+/// disk, and renamed over `path`. The temporary file is removed if any step
+/// fails.
+pub fn write_file_atomic<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
+    let path = path.as_ref();
+    let temp_path = temp_path_for(path);
+
+    let result = write_and_sync(&temp_path, content)
+        .map_err(|e| write_error(path, e))
+        .and_then(|()| {
+            fs::rename(&temp_path, path).map_err(|e| {
+                WritingError::IoError(format!("Failed to replace file {}: {}", path.display(), e))
+            })
+        });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Run `f` while holding an exclusive advisory lock on `path`
+///
+/// The lock is taken on a `.lock` file next to `path` rather than `path`
+/// itself, so it survives `path` being replaced by [`write_file_atomic`].
+/// Blocks until any other holder releases the lock. The lock is only
+/// advisory: it keeps out other callers of this function, not every writer.
+pub fn with_file_lock<P, T, F>(path: P, f: F) -> Result<T>
+where
+    P: AsRef<Path>,
+    F: FnOnce() -> Result<T>,
+{
+    let lock_path = lock_path_for(path.as_ref());
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| write_error(&lock_path, e))?;
+
+    lock_file
+        .lock()
+        .map_err(|e| WritingError::IoError(format!("Failed to lock {}: {}", lock_path.display(), e)))?;
+
+    // The lock is released when the file is closed, even if `f` panics
+    f()
+}
+
+fn write_and_sync(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()
+}
+
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let count = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}.{}.tmp", name, std::process::id(), count))
+}
+
+fn lock_path_for(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    path.with_file_name(format!("{}.lock", name))
+}
@@ -1,5 +1,61 @@
+//! # Content Paths
+//!
+//! An article lives in a directory named after its slug. The content file in
+//! that directory is `{slug}.md` or `{slug}.mdx`, or `index.md` / `index.mdx`
+//! for content that predates the slug-named convention. Translations sit
+//! beside it as `{slug}.{locale}.md` (or `index.{locale}.md`), such as
+//! `hello.de.md`.
+
+#[cfg(feature = "content")]
 use common_models::Config;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Names of the files that can hold an article, in order of preference
+pub fn content_file_names(slug: &str) -> [String; 4] {
+    [
+        format!("{}.md", slug),
+        format!("{}.mdx", slug),
+        "index.md".to_string(),
+        "index.mdx".to_string(),
+    ]
+}
+
+/// Find the content file in an article directory
+///
+/// Checks `{slug}.md`, `{slug}.mdx`, `index.md` and `index.mdx`, in that
+/// order, returning the first that exists.
+pub fn resolve_content_file(article_dir: &Path, slug: &str) -> Option<PathBuf> {
+    content_file_names(slug)
+        .iter()
+        .map(|name| article_dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Find the content file in an article directory, using the directory name as the slug
+pub fn resolve_content_dir(article_dir: &Path) -> Option<PathBuf> {
+    let slug = article_dir.file_name()?.to_str()?;
+    resolve_content_file(article_dir, slug)
+}
+
+/// Find the translations in an article directory
+///
+/// Checks `{slug}.{locale}.md`, `{slug}.{locale}.mdx`, `index.{locale}.md`
+/// and `index.{locale}.mdx` for each locale, returning the locale and path of
+/// each translation found, in the order of `locales`.
+pub fn resolve_translations<S: AsRef<str>>(article_dir: &Path, slug: &str, locales: &[S]) -> Vec<(String, PathBuf)> {
+    locales
+        .iter()
+        .map(AsRef::as_ref)
+        .filter_map(|locale| {
+            [slug, "index"]
+                .iter()
+                .flat_map(|stem| ["md", "mdx"].map(|extension| format!("{}.{}.{}", stem, locale, extension)))
+                .map(|name| article_dir.join(name))
+                .find(|path| path.is_file())
+                .map(|path| (locale.to_string(), path))
+        })
+        .collect()
+}
 
 /// Find the path to content by slug and optionally topic
 ///
@@ -15,6 +71,7 @@ use std::path::PathBuf;
 /// # Returns
 ///
 /// Returns a Result containing the path to the content file, or an error if not found.
+#[cfg(feature = "content")]
 pub fn find_content_path(
     slug: &str,
     topic: Option<&str>,
@@ -40,55 +97,25 @@ pub fn find_content_path(
         }
 
         let topic_config = &config.content.topics[topic_key];
-        let topic_path = &topic_config.directory;
-
-        // Check for the matching-name file with .md extension
-        let content_path_md = PathBuf::from(&config.content.base_dir)
-            .join(topic_path)
-            .join(slug)
-            .join(format!("{}.md", slug));
+        let article_dir = PathBuf::from(&config.content.base_dir)
+            .join(&topic_config.directory)
+            .join(slug);
 
-        if content_path_md.exists() {
-            return Ok(content_path_md);
-        }
-
-        // Check for the matching-name file with .mdx extension
-        let content_path_mdx = PathBuf::from(&config.content.base_dir)
-            .join(topic_path)
-            .join(slug)
-            .join(format!("{}.mdx", slug));
-
-        if content_path_mdx.exists() {
-            return Ok(content_path_mdx);
-        }
-
-        Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            format!("Content not found: {}/{}", topic_key, slug),
-        ))
+        resolve_content_file(&article_dir, slug).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Content not found: {}/{}", topic_key, slug),
+            )
+        })
     } else {
         // Search all topics
         for topic_config in config.content.topics.values() {
-            let topic_path = &topic_config.directory;
-
-            // Check for the matching-name file with .md extension
-            let content_path_md = PathBuf::from(&config.content.base_dir)
-                .join(topic_path)
-                .join(slug)
-                .join(format!("{}.md", slug));
-
-            if content_path_md.exists() {
-                return Ok(content_path_md);
-            }
-
-            // Check for the matching-name file with .mdx extension
-            let content_path_mdx = PathBuf::from(&config.content.base_dir)
-                .join(topic_path)
-                .join(slug)
-                .join(format!("{}.mdx", slug));
+            let article_dir = PathBuf::from(&config.content.base_dir)
+                .join(&topic_config.directory)
+                .join(slug);
 
-            if content_path_mdx.exists() {
-                return Ok(content_path_mdx);
+            if let Some(content_path) = resolve_content_file(&article_dir, slug) {
+                return Ok(content_path);
             }
         }
 
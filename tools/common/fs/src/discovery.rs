@@ -0,0 +1,134 @@
+//! # Content Discovery
+//!
+//! Finds files and directories under the content tree the way git sees it.
+//! Anything excluded by a `.gitignore`, `.ignore` or `.writingignore` file is
+//! skipped, along with hidden files and directories such as `.git` and
+//! `node_modules` directories, so build output and dependencies are never
+//! walked. Files can also be narrowed down by extension and glob pattern.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use common_fs::discovery::{discover_files, DiscoveryOptions};
+//! use std::path::Path;
+//!
+//! let options = DiscoveryOptions {
+//!     extensions: vec!["md".to_string(), "mdx".to_string()],
+//!     globs: vec!["blog/**".to_string()],
+//!     ..Default::default()
+//! };
+//! let files = discover_files(Path::new("content"), &options)?;
+//! # Ok::<(), common_errors::WritingError>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use common_errors::{Result, WritingError};
+use ignore::overrides::OverrideBuilder;
+use ignore::{DirEntry, WalkBuilder};
+
+/// Name of the ignore file for content that shouldn't be processed but is
+/// still committed
+pub const WRITINGIGNORE_FILE: &str = ".writingignore";
+
+/// Directory names that are never walked, ignore files or not
+const ALWAYS_SKIPPED: &[&str] = &["node_modules"];
+
+/// What to look for when discovering files
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryOptions {
+    /// File extensions to include, without the dot; empty includes every file
+    pub extensions: Vec<String>,
+    /// Glob patterns, relative to the root, that files must match; patterns
+    /// starting with `!` exclude files instead
+    pub globs: Vec<String>,
+    /// How deep to look, where the root's children are at depth 1
+    pub max_depth: Option<usize>,
+}
+
+/// Find the files under `root` that aren't ignored, sorted by path
+///
+/// # Errors
+///
+/// Returns an error if `root` doesn't exist, a glob pattern is invalid or a
+/// directory can't be read
+pub fn discover_files(root: &Path, options: &DiscoveryOptions) -> Result<Vec<PathBuf>> {
+    let mut walker = walker(root, options.max_depth)?;
+
+    if !options.globs.is_empty() {
+        let mut overrides = OverrideBuilder::new(root);
+        for glob in &options.globs {
+            overrides
+                .add(glob)
+                .map_err(|e| WritingError::invalid_argument(format!("Invalid glob '{}': {}", glob, e)))?;
+        }
+        walker.overrides(
+            overrides
+                .build()
+                .map_err(|e| WritingError::invalid_argument(format!("Invalid glob patterns: {}", e)))?,
+        );
+    }
+
+    let mut files = Vec::new();
+    for entry in walker.build() {
+        let entry = entry.map_err(|e| walk_error(root, e))?;
+        if entry.file_type().is_some_and(|file_type| file_type.is_file()) && has_extension(&entry, &options.extensions)
+        {
+            files.push(entry.into_path());
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Find the directories under `root` between `min_depth` and `max_depth`
+/// that aren't ignored, sorted by path
+///
+/// # Errors
+///
+/// Returns an error if `root` doesn't exist or a directory can't be read
+pub fn discover_dirs(root: &Path, min_depth: usize, max_depth: usize) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in walker(root, Some(max_depth))?.build() {
+        let entry = entry.map_err(|e| walk_error(root, e))?;
+        if entry.depth() >= min_depth && entry.file_type().is_some_and(|file_type| file_type.is_dir()) {
+            dirs.push(entry.into_path());
+        }
+    }
+
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// A walker that honours ignore files whether or not `root` is in a git repository
+fn walker(root: &Path, max_depth: Option<usize>) -> Result<WalkBuilder> {
+    if !root.is_dir() {
+        return Err(WritingError::directory_not_found(root));
+    }
+
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .max_depth(max_depth)
+        .require_git(false)
+        .add_custom_ignore_filename(WRITINGIGNORE_FILE)
+        .filter_entry(|entry| {
+            !(entry.depth() > 0
+                && entry.file_type().is_some_and(|file_type| file_type.is_dir())
+                && ALWAYS_SKIPPED.iter().any(|name| entry.file_name() == *name))
+        });
+    Ok(builder)
+}
+
+fn has_extension(entry: &DirEntry, extensions: &[String]) -> bool {
+    extensions.is_empty()
+        || entry
+            .path()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.iter().any(|wanted| wanted.eq_ignore_ascii_case(ext)))
+}
+
+fn walk_error(root: &Path, e: ignore::Error) -> WritingError {
+    WritingError::IoError(format!("Failed to read directory {}: {}", root.display(), e))
+}
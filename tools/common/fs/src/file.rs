@@ -15,12 +15,7 @@ use common_errors::{Result, WritingError};
 /// The file's contents as a string
 pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
     let path = path.as_ref();
-    fs::read_to_string(path)
-        .map_err(|e| match e.kind() {
-            io::ErrorKind::NotFound => WritingError::file_not_found(path),
-            io::ErrorKind::PermissionDenied => WritingError::permission_denied(path),
-            _ => WritingError::IoError(format!("Failed to read file {}: {}", path.display(), e)),
-        })
+    fs::read_to_string(path).map_err(|e| read_error(path, e))
 }
 
 /// Write content to a file
@@ -35,12 +30,7 @@ pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
 /// Result indicating success or failure
 pub fn write_file<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
     let path = path.as_ref();
-    fs::write(path, content)
-        .map_err(|e| match e.kind() {
-            io::ErrorKind::NotFound => WritingError::directory_not_found(path.parent().unwrap_or(path)),
-            io::ErrorKind::PermissionDenied => WritingError::permission_denied(path),
-            _ => WritingError::IoError(format!("Failed to write file {}: {}", path.display(), e)),
-        })
+    fs::write(path, content).map_err(|e| write_error(path, e))
 }
 
 /// Check if a file exists
@@ -149,4 +139,22 @@ pub fn delete_dir<P: AsRef<Path>>(path: P) -> Result<()> {
             io::ErrorKind::PermissionDenied => WritingError::permission_denied(path),
             _ => WritingError::IoError(format!("Failed to delete directory {}: {}", path.display(), e)),
         })
-}
\ No newline at end of file
+}
+
+/// Map an error reading `path` to a `WritingError`
+pub(crate) fn read_error(path: &Path, e: io::Error) -> WritingError {
+    match e.kind() {
+        io::ErrorKind::NotFound => WritingError::file_not_found(path),
+        io::ErrorKind::PermissionDenied => WritingError::permission_denied(path),
+        _ => WritingError::IoError(format!("Failed to read file {}: {}", path.display(), e)),
+    }
+}
+
+/// Map an error writing `path` to a `WritingError`
+pub(crate) fn write_error(path: &Path, e: io::Error) -> WritingError {
+    match e.kind() {
+        io::ErrorKind::NotFound => WritingError::directory_not_found(path.parent().unwrap_or(path)),
+        io::ErrorKind::PermissionDenied => WritingError::permission_denied(path),
+        _ => WritingError::IoError(format!("Failed to write file {}: {}", path.display(), e)),
+    }
+}
@@ -0,0 +1,136 @@
+//! # Filesystem Abstraction
+//!
+//! Code that reads and writes content through [`FileSystem`] rather than
+//! calling this crate's functions directly can be tested without temporary
+//! directories: tools default to [`RealFileSystem`], and tests pass the
+//! `MockFileSystem` or `InMemoryFileSystem` from `common-test-utils`
+//! instead.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use common_errors::{Result, WritingError};
+
+use crate::file::{read_error, write_error};
+
+/// Operations for interacting with the filesystem
+pub trait FileSystem: Send + Sync {
+    /// Check if a file exists at the given path
+    fn file_exists(&self, path: &Path) -> Result<bool>;
+
+    /// Check if a directory exists at the given path
+    fn dir_exists(&self, path: &Path) -> Result<bool>;
+
+    /// Read the contents of a file
+    fn read_file(&self, path: &Path) -> Result<String>;
+
+    /// Write contents to a file
+    fn write_file(&self, path: &Path, contents: &str) -> Result<()>;
+
+    /// Append contents to a file
+    fn append_file(&self, path: &Path, contents: &str) -> Result<()>;
+
+    /// Create a directory and any parent directories
+    fn create_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// List files in a directory
+    fn list_files(&self, path: &Path) -> Result<Vec<PathBuf>>;
+
+    /// List subdirectories in a directory
+    fn list_dirs(&self, path: &Path) -> Result<Vec<PathBuf>>;
+
+    /// Remove a file
+    fn remove_file(&self, path: &Path) -> Result<()>;
+
+    /// Remove a directory and all its contents
+    fn remove_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// Copy a file from source to destination
+    fn copy_file(&self, from: &Path, to: &Path) -> Result<()>;
+
+    /// Move a file from source to destination
+    fn move_file(&self, from: &Path, to: &Path) -> Result<()>;
+}
+
+/// The filesystem on disk
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFileSystem;
+
+impl RealFileSystem {
+    /// List the entries of a directory that match `keep`, in path order
+    fn list(&self, path: &Path, keep: fn(&Path) -> bool) -> Result<Vec<PathBuf>> {
+        if !path.is_dir() {
+            return Err(WritingError::directory_not_found(path));
+        }
+
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .map_err(|e| read_error(path, e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|entry| keep(entry))
+            .collect();
+        entries.sort();
+        Ok(entries)
+    }
+}
+
+impl FileSystem for RealFileSystem {
+    fn file_exists(&self, path: &Path) -> Result<bool> {
+        Ok(crate::file_exists(path))
+    }
+
+    fn dir_exists(&self, path: &Path) -> Result<bool> {
+        Ok(crate::dir_exists(path))
+    }
+
+    fn read_file(&self, path: &Path) -> Result<String> {
+        crate::read_file(path)
+    }
+
+    fn write_file(&self, path: &Path, contents: &str) -> Result<()> {
+        crate::write_file(path, contents)
+    }
+
+    fn append_file(&self, path: &Path, contents: &str) -> Result<()> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .map_err(|e| write_error(path, e))
+    }
+
+    fn create_dir_all(&self, path: &Path) -> Result<()> {
+        crate::create_dir_all(path)
+    }
+
+    fn list_files(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        self.list(path, Path::is_file)
+    }
+
+    fn list_dirs(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        self.list(path, Path::is_dir)
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        crate::delete_file(path)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        crate::delete_dir(path)
+    }
+
+    fn copy_file(&self, from: &Path, to: &Path) -> Result<()> {
+        crate::copy_file_std(from, to)
+    }
+
+    fn move_file(&self, from: &Path, to: &Path) -> Result<()> {
+        if fs::rename(from, to).is_ok() {
+            return Ok(());
+        }
+
+        // Renames fail across filesystems, so copy and remove instead
+        self.copy_file(from, to)?;
+        self.remove_file(from)
+    }
+}
@@ -0,0 +1,288 @@
+//! # Operation Journal
+//!
+//! Moves, deletions and bulk edits record what they are about to change in
+//! `.writing/journal/<id>/` under the content base directory, so that the
+//! last operation can be rolled back with [`Journal::undo_last`] instead of
+//! digging through version control.
+//!
+//! A transaction holds a copy of every file or directory the operation
+//! changes or removes, and a list of the paths it creates. It is only added
+//! to the history once [`Transaction::commit`] is called; a transaction that
+//! is dropped without committing removes its snapshots again.
+//!
+//! ```no_run
+//! use common_fs::journal::Journal;
+//! use std::path::Path;
+//! # fn main() -> common_errors::Result<()> {
+//! let journal = Journal::for_base_dir("content");
+//!
+//! let mut transaction = journal.begin("delete", "Delete blog/hello")?;
+//! transaction.snapshot(Path::new("content/blog/hello"))?;
+//! std::fs::remove_dir_all("content/blog/hello")?;
+//! transaction.commit()?;
+//!
+//! journal.undo_last()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+use common_errors::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::directory::copy_dir_all;
+use crate::file::{read_file, write_file};
+
+/// Location of the journal, relative to the content base directory
+pub const JOURNAL_DIR: &str = ".writing/journal";
+
+/// Name of the record in each transaction directory
+const RECORD_FILE: &str = "transaction.yaml";
+
+/// Directory holding the snapshots in each transaction directory
+const SNAPSHOT_DIR: &str = "snapshots";
+
+/// One path touched by an operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// The path existed before the operation; undo puts the snapshot back
+    Snapshot {
+        path: PathBuf,
+        /// The copy, relative to the transaction directory
+        snapshot: PathBuf,
+    },
+    /// The path did not exist before the operation; undo removes it
+    Created { path: PathBuf },
+}
+
+impl Change {
+    /// The path in the content repository
+    pub fn path(&self) -> &Path {
+        match self {
+            Change::Snapshot { path, .. } | Change::Created { path } => path,
+        }
+    }
+}
+
+/// A recorded operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionRecord {
+    /// Name of the transaction directory
+    pub id: String,
+    /// The kind of operation, such as `move` or `delete`
+    pub operation: String,
+    /// What the operation did, for listing the history
+    pub description: String,
+    pub recorded_at: DateTime<Utc>,
+    /// Changes in the order they were recorded
+    pub changes: Vec<Change>,
+}
+
+/// The journal of a content repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Journal {
+    root: PathBuf,
+}
+
+impl Journal {
+    /// Open the journal stored in `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Open the journal for the content in `base_dir`
+    pub fn for_base_dir(base_dir: impl AsRef<Path>) -> Self {
+        Self::new(base_dir.as_ref().join(JOURNAL_DIR))
+    }
+
+    /// The directory holding the transactions
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Start recording an operation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction directory cannot be created
+    pub fn begin(&self, operation: &str, description: impl Into<String>) -> Result<Transaction> {
+        let recorded_at = Utc::now();
+
+        // Several operations can start within the same millisecond
+        let timestamp = recorded_at.format("%Y%m%dT%H%M%S%.3fZ").to_string();
+        let mut id = timestamp.clone();
+        let mut suffix = 1;
+        while self.root.join(&id).exists() {
+            id = format!("{}-{}", timestamp, suffix);
+            suffix += 1;
+        }
+
+        let dir = self.root.join(&id);
+        fs::create_dir_all(dir.join(SNAPSHOT_DIR))?;
+
+        Ok(Transaction {
+            record: TransactionRecord {
+                id,
+                operation: operation.to_string(),
+                description: description.into(),
+                recorded_at,
+                changes: Vec::new(),
+            },
+            dir,
+            committed: false,
+        })
+    }
+
+    /// Committed transactions, most recent first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal cannot be read or a record is invalid
+    pub fn history(&self) -> Result<Vec<TransactionRecord>> {
+        let mut records = Vec::new();
+
+        if !self.root.is_dir() {
+            return Ok(records);
+        }
+
+        for entry in fs::read_dir(&self.root)?.filter_map(|entry| entry.ok()) {
+            let record_path = entry.path().join(RECORD_FILE);
+            if record_path.exists() {
+                records.push(serde_yaml::from_str::<TransactionRecord>(&read_file(&record_path)?)?);
+            }
+        }
+
+        records.sort_by(|a, b| b.recorded_at.cmp(&a.recorded_at).then_with(|| b.id.cmp(&a.id)));
+        Ok(records)
+    }
+
+    /// Roll back the most recent transaction and remove it from the history
+    ///
+    /// Changes are undone in the reverse of the order they were recorded.
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction that was undone, or `None` if the journal is empty
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a snapshot cannot be restored or a created path
+    /// cannot be removed
+    pub fn undo_last(&self) -> Result<Option<TransactionRecord>> {
+        let Some(record) = self.history()?.into_iter().next() else {
+            return Ok(None);
+        };
+        let dir = self.root.join(&record.id);
+
+        for change in record.changes.iter().rev() {
+            match change {
+                Change::Snapshot { path, snapshot } => {
+                    remove_path(path)?;
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    copy_path(&dir.join(snapshot), path)?;
+                }
+                Change::Created { path } => remove_path(path)?,
+            }
+        }
+
+        fs::remove_dir_all(&dir)?;
+        Ok(Some(record))
+    }
+}
+
+/// An operation being recorded
+///
+/// Dropping a transaction without committing it discards its snapshots.
+#[derive(Debug)]
+pub struct Transaction {
+    record: TransactionRecord,
+    dir: PathBuf,
+    committed: bool,
+}
+
+impl Transaction {
+    /// The record as it stands
+    pub fn record(&self) -> &TransactionRecord {
+        &self.record
+    }
+
+    /// Copy a file or directory before the operation changes or removes it
+    ///
+    /// A path that does not exist yet is recorded as created, so that undo
+    /// removes whatever the operation puts there. Each path is only recorded
+    /// once, the first time it is seen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path cannot be copied
+    pub fn snapshot(&mut self, path: &Path) -> Result<()> {
+        if self.contains(path) {
+            return Ok(());
+        }
+        if !path.exists() {
+            self.created(path);
+            return Ok(());
+        }
+
+        let snapshot = Path::new(SNAPSHOT_DIR).join(self.record.changes.len().to_string());
+        copy_path(path, &self.dir.join(&snapshot))?;
+        self.record.changes.push(Change::Snapshot {
+            path: path.to_path_buf(),
+            snapshot,
+        });
+        Ok(())
+    }
+
+    /// Record a path the operation creates, so that undo removes it
+    pub fn created(&mut self, path: &Path) {
+        if !self.contains(path) {
+            self.record.changes.push(Change::Created { path: path.to_path_buf() });
+        }
+    }
+
+    /// Add the transaction to the history
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record cannot be written
+    pub fn commit(mut self) -> Result<TransactionRecord> {
+        write_file(self.dir.join(RECORD_FILE), &serde_yaml::to_string(&self.record)?)?;
+        self.committed = true;
+        Ok(self.record.clone())
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.record.changes.iter().any(|change| change.path() == path)
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+fn copy_path(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        copy_dir_all(from, to)
+    } else {
+        fs::copy(from, to)?;
+        Ok(())
+    }
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
@@ -1,5 +1,4 @@
-#[cfg(feature = "content")]
-mod content_path;
+pub mod content_path;
 
 // Add the normalize module
 pub mod normalize;
@@ -7,10 +6,20 @@ pub mod cleanup;
 // Add the directory module for directory operations
 pub mod directory;
 pub mod file;
+pub mod filesystem;
 pub mod macros;  // Include the new macros module
+pub mod atomic;
+pub mod journal;
+#[cfg(feature = "find")]
+pub mod discovery;
+#[cfg(feature = "find")]
+pub mod repository;
+#[cfg(feature = "async")]
+pub mod async_fs;
 
 #[cfg(feature = "content")]
 pub use content_path::find_content_path;
+pub use content_path::{resolve_content_dir, resolve_content_file, resolve_translations};
 
 // Re-export key directory operations for convenience
 pub use directory::{move_dir, copy_dir_all, has_content, copy_content, move_content};
@@ -21,6 +30,21 @@ pub use file::{
     delete_file, delete_dir, resolve_path
 };
 
+#[cfg(feature = "find")]
+pub use discovery::{discover_dirs, discover_files, DiscoveryOptions};
+
+#[cfg(feature = "find")]
+pub use repository::{CachedContentRepository, FsContentRepository};
+
+// Re-export the filesystem abstraction
+pub use filesystem::{FileSystem, RealFileSystem};
+
+// Re-export atomic writes and file locks
+pub use atomic::{write_file_atomic, with_file_lock};
+
+// Re-export the operation journal
+pub use journal::{Journal, Transaction, TransactionRecord};
+
 // Re-export from cleanup module
 pub use cleanup::{
     copy_file, copy_file_std
@@ -101,6 +125,21 @@ pub fn find_dirs_with_depth(base_path: &Path, min_depth: usize, max_depth: usize
     Ok(dirs)
 }
 
+/// Find the content file of each article directory in a topic directory
+///
+/// Article directories without a content file are skipped; see
+/// [`resolve_content_file`] for the names that are checked. Directories
+/// excluded by ignore files are skipped too, see [`discovery`].
+#[cfg(feature = "find")]
+pub fn find_article_files(topic_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = discover_dirs(topic_dir, 1, 1)?
+        .iter()
+        .filter_map(|dir| resolve_content_dir(dir))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
 /// Find all files in a path with a specific extension
 #[cfg(feature = "find")]
 pub fn find_files_with_extension(base_path: &Path, extension: &str) -> Result<Vec<PathBuf>> {
@@ -0,0 +1,174 @@
+//! # Filesystem Content Repository
+//!
+//! The [`ContentRepository`] for content on disk, laid out as the
+//! configuration describes: a directory per topic under the content base
+//! directory, and a directory per article in each topic. Article directories
+//! excluded by ignore files are skipped, see [`crate::discovery`].
+//!
+//! Long-running processes can wrap it in a [`CachedContentRepository`],
+//! which keeps article content in memory between reads.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+
+use common_errors::{Result, WritingError};
+use common_models::Config;
+use common_traits::{ArticleLocation, ContentRepository};
+
+use crate::{discover_dirs, read_file, resolve_content_file};
+
+/// Content read from the directories named in the configuration
+#[derive(Debug, Clone)]
+pub struct FsContentRepository {
+    /// Directory of each topic, by topic key
+    topics: BTreeMap<String, PathBuf>,
+}
+
+impl FsContentRepository {
+    /// A repository for the topics in `config`
+    pub fn new(config: &Config) -> Self {
+        let base_dir = Path::new(&config.content.base_dir);
+        let topics = config
+            .content
+            .topics
+            .iter()
+            .map(|(key, topic)| (key.clone(), base_dir.join(&topic.directory)))
+            .collect();
+        Self { topics }
+    }
+
+    /// Directory of a topic
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the topic isn't configured
+    pub fn topic_dir(&self, topic: &str) -> Result<&Path> {
+        self.topics
+            .get(topic)
+            .map(PathBuf::as_path)
+            .ok_or_else(|| WritingError::topic_error(format!("Unknown topic: {}", topic)))
+    }
+}
+
+impl ContentRepository for FsContentRepository {
+    fn list_topics(&self) -> Result<Vec<String>> {
+        Ok(self.topics.keys().cloned().collect())
+    }
+
+    fn list_articles(&self, topic: &str) -> Result<Vec<ArticleLocation>> {
+        let topic_dir = self.topic_dir(topic)?;
+        if !topic_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut articles = Vec::new();
+        for dir in discover_dirs(topic_dir, 1, 1)? {
+            if let Some(article) = locate(topic, dir) {
+                articles.push(article);
+            }
+        }
+        Ok(articles)
+    }
+
+    fn read_article(&self, article: &ArticleLocation) -> Result<String> {
+        read_file(&article.content_file)
+    }
+
+    // An article named directly is found without listing the whole topic
+    fn find_article(&self, topic: &str, slug: &str) -> Result<Option<ArticleLocation>> {
+        let dir = self.topic_dir(topic)?.join(slug);
+        if !dir.is_dir() {
+            return Ok(None);
+        }
+        Ok(locate(topic, dir))
+    }
+}
+
+/// Modification time and size of a file
+type Stamp = (Option<SystemTime>, u64);
+
+/// A [`FsContentRepository`] that keeps the content of each article it reads
+///
+/// A cached article is read again when its content file's modification time
+/// or size changes, so edits made by other tools are picked up without
+/// clearing the cache. Listings are never cached, since they are cheap and
+/// would otherwise miss new articles.
+#[derive(Debug)]
+pub struct CachedContentRepository {
+    inner: FsContentRepository,
+    /// Content of each read file, with the modification time and size it was read at
+    cache: Mutex<HashMap<PathBuf, (Stamp, String)>>,
+}
+
+impl CachedContentRepository {
+    /// A cached repository for the topics in `config`
+    pub fn new(config: &Config) -> Self {
+        Self {
+            inner: FsContentRepository::new(config),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The repository being cached
+    pub fn inner(&self) -> &FsContentRepository {
+        &self.inner
+    }
+
+    /// Number of files held in memory
+    pub fn cached_files(&self) -> usize {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Drop every cached file
+    pub fn clear(&self) {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+}
+
+impl ContentRepository for CachedContentRepository {
+    fn list_topics(&self) -> Result<Vec<String>> {
+        self.inner.list_topics()
+    }
+
+    fn list_articles(&self, topic: &str) -> Result<Vec<ArticleLocation>> {
+        self.inner.list_articles(topic)
+    }
+
+    fn read_article(&self, article: &ArticleLocation) -> Result<String> {
+        let path = &article.content_file;
+        let stamp = match std::fs::metadata(path) {
+            Ok(metadata) => (metadata.modified().ok(), metadata.len()),
+            // Let the read report the problem
+            Err(_) => return read_file(path),
+        };
+
+        let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((cached_stamp, content)) = cache.get(path) {
+            if *cached_stamp == stamp {
+                return Ok(content.clone());
+            }
+        }
+
+        let content = read_file(path)?;
+        cache.insert(path.clone(), (stamp, content.clone()));
+        Ok(content)
+    }
+
+    fn find_article(&self, topic: &str, slug: &str) -> Result<Option<ArticleLocation>> {
+        self.inner.find_article(topic, slug)
+    }
+}
+
+/// The article in `dir`, if it has a content file
+fn locate(topic: &str, dir: PathBuf) -> Option<ArticleLocation> {
+    let slug = dir.file_name()?.to_str()?.to_string();
+    let content_file = resolve_content_file(&dir, &slug)?;
+    Some(ArticleLocation {
+        topic: topic.to_string(),
+        slug,
+        dir,
+        content_file,
+    })
+}
@@ -0,0 +1,52 @@
+use common_errors::Result;
+use common_fs::async_fs;
+use std::fs;
+use tempfile::tempdir;
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("runtime")
+        .block_on(future)
+}
+
+#[test]
+fn test_async_read_and_write_file() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let path = temp_dir.path().join("article.md");
+
+    block_on(async {
+        async_fs::write_file(&path, "# Hello").await?;
+        assert_eq!(async_fs::read_file(&path).await?, "# Hello");
+
+        let missing = async_fs::read_file(temp_dir.path().join("missing.md")).await;
+        assert!(missing.unwrap_err().to_string().contains("File not found"));
+        Ok(())
+    })
+}
+
+#[test]
+fn test_async_find_and_copy_directories() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(src.join("nested"))?;
+    fs::write(src.join("index.md"), "one")?;
+    fs::write(src.join("nested/index.md"), "two")?;
+    fs::write(src.join("nested/cover.jpg"), "image")?;
+
+    block_on(async {
+        let mut files = async_fs::find_files_with_extension(&src, "md").await?;
+        files.sort();
+        assert_eq!(files, vec![src.join("index.md"), src.join("nested/index.md")]);
+
+        let dst = temp_dir.path().join("dst");
+        async_fs::copy_dir_all(&src, &dst).await?;
+        assert_eq!(fs::read_to_string(dst.join("nested/index.md"))?, "two");
+        assert!(dst.join("nested/cover.jpg").is_file());
+
+        assert!(async_fs::find_files_with_extension(&temp_dir.path().join("missing"), "md")
+            .await
+            .is_err());
+        Ok(())
+    })
+}
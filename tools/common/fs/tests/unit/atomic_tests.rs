@@ -0,0 +1,53 @@
+//! Unit tests for atomic writes and file locks
+
+use common_fs::{with_file_lock, write_file_atomic};
+use std::fs;
+use std::sync::{Arc, Barrier};
+use std::thread;
+use tempfile::tempdir;
+
+#[test]
+fn test_write_file_atomic_replaces_without_leftovers() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("all.json");
+
+    write_file_atomic(&path, "[]").unwrap();
+    write_file_atomic(&path, "[1]").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "[1]");
+
+    let entries: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().collect();
+    assert_eq!(entries.len(), 1, "temporary files should be renamed away");
+
+    assert!(write_file_atomic(temp_dir.path().join("missing/all.json"), "[]").is_err());
+}
+
+#[test]
+fn test_with_file_lock_serializes_updates() {
+    let temp_dir = tempdir().unwrap();
+    let path = Arc::new(temp_dir.path().join("counter.txt"));
+    fs::write(path.as_ref(), "0").unwrap();
+
+    let threads = 8;
+    let barrier = Arc::new(Barrier::new(threads));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let path = Arc::clone(&path);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                with_file_lock(path.as_ref(), || {
+                    let count: u32 = fs::read_to_string(path.as_ref()).unwrap().parse().unwrap();
+                    thread::yield_now();
+                    write_file_atomic(path.as_ref(), &(count + 1).to_string())
+                })
+                .unwrap();
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(fs::read_to_string(path.as_ref()).unwrap(), threads.to_string());
+}
@@ -0,0 +1,58 @@
+//! Unit tests for resolving content files in article directories
+
+use crate::content_path::{resolve_content_dir, resolve_content_file, resolve_translations};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_resolve_content_file_prefers_slug_named_files() {
+    let temp_dir = tempdir().unwrap();
+    let article_dir = temp_dir.path().join("hello");
+    fs::create_dir_all(&article_dir).unwrap();
+
+    assert_eq!(resolve_content_file(&article_dir, "hello"), None);
+
+    for (name, expected) in [
+        ("index.mdx", "index.mdx"),
+        ("index.md", "index.md"),
+        ("hello.mdx", "hello.mdx"),
+        ("hello.md", "hello.md"),
+    ] {
+        fs::write(article_dir.join(name), "---\ntitle: Hello\n---\n").unwrap();
+        assert_eq!(resolve_content_file(&article_dir, "hello"), Some(article_dir.join(expected)));
+    }
+
+    assert_eq!(resolve_content_dir(&article_dir), Some(article_dir.join("hello.md")));
+}
+
+#[test]
+fn test_resolve_content_file_ignores_directories() {
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("index.md")).unwrap();
+
+    assert_eq!(resolve_content_file(temp_dir.path(), "hello"), None);
+}
+
+#[test]
+fn test_resolve_translations_finds_configured_locales() {
+    let temp_dir = tempdir().unwrap();
+    let article_dir = temp_dir.path().join("hello");
+    fs::create_dir_all(&article_dir).unwrap();
+
+    for name in ["hello.md", "hello.de.md", "index.fr.mdx", "hello.es.md", "hello.de.mdx"] {
+        fs::write(article_dir.join(name), "---\ntitle: Hello\n---\n").unwrap();
+    }
+
+    // Only configured locales count, and the slug-named `.md` file wins
+    let translations = resolve_translations(&article_dir, "hello", &["fr", "de", "it"]);
+    assert_eq!(
+        translations,
+        vec![
+            ("fr".to_string(), article_dir.join("index.fr.mdx")),
+            ("de".to_string(), article_dir.join("hello.de.md")),
+        ]
+    );
+
+    // A translation is never taken for the main content file
+    assert_eq!(resolve_content_dir(&article_dir), Some(article_dir.join("hello.md")));
+}
@@ -0,0 +1,65 @@
+//! Unit tests for ignore-aware content discovery
+
+use common_fs::{discover_dirs, discover_files, find_article_files, DiscoveryOptions};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write(root: &Path, relative: &str, content: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_discover_files_skips_ignored_paths() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, ".gitignore", "build/\n");
+    write(root, ".writingignore", "blog/scratch/\n");
+    write(root, "blog/first/index.md", "one");
+    write(root, "blog/first/cover.jpg", "image");
+    write(root, "blog/scratch/index.md", "ignored");
+    write(root, "build/blog/first/index.md", "output");
+    write(root, "node_modules/pkg/README.md", "dependency");
+    write(root, ".git/HEAD", "ref: refs/heads/main");
+
+    let options = DiscoveryOptions {
+        extensions: vec!["md".to_string()],
+        ..Default::default()
+    };
+    let files = discover_files(root, &options).unwrap();
+    assert_eq!(files, vec![root.join("blog/first/index.md")]);
+
+    let options = DiscoveryOptions {
+        globs: vec!["blog/**/*.jpg".to_string()],
+        ..Default::default()
+    };
+    let files = discover_files(root, &options).unwrap();
+    assert_eq!(files, vec![root.join("blog/first/cover.jpg")]);
+
+    let options = DiscoveryOptions {
+        globs: vec!["[".to_string()],
+        ..Default::default()
+    };
+    assert!(discover_files(root, &options).is_err());
+}
+
+#[test]
+fn test_discover_dirs_and_article_files() {
+    let temp_dir = tempdir().unwrap();
+    let topic = temp_dir.path().join("blog");
+    write(&topic, ".writingignore", "drafts-wip/\n");
+    write(&topic, "second/index.md", "two");
+    write(&topic, "first/index.mdx", "one");
+    write(&topic, "drafts-wip/index.md", "ignored");
+    write(&topic, "empty/notes.txt", "no content file");
+
+    let dirs = discover_dirs(&topic, 1, 1).unwrap();
+    assert_eq!(dirs, vec![topic.join("empty"), topic.join("first"), topic.join("second")]);
+
+    let files = find_article_files(&topic).unwrap();
+    assert_eq!(files, vec![topic.join("first/index.mdx"), topic.join("second/index.md")]);
+
+    assert!(discover_dirs(&temp_dir.path().join("missing"), 1, 1).is_err());
+}
@@ -0,0 +1,112 @@
+//! Unit tests for the operation journal
+
+use common_fs::Journal;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write(root: &Path, relative: &str, content: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_undo_last_restores_deleted_directory() {
+    let temp_dir = tempdir().unwrap();
+    let base = temp_dir.path();
+    write(base, "blog/hello/index.md", "hello");
+    write(base, "blog/hello/image.png", "png");
+    let journal = Journal::for_base_dir(base);
+
+    let mut transaction = journal.begin("delete", "Delete blog/hello").unwrap();
+    transaction.snapshot(&base.join("blog/hello")).unwrap();
+    fs::remove_dir_all(base.join("blog/hello")).unwrap();
+    transaction.commit().unwrap();
+
+    let undone = journal.undo_last().unwrap().unwrap();
+    assert_eq!(undone.operation, "delete");
+    assert_eq!(fs::read_to_string(base.join("blog/hello/index.md")).unwrap(), "hello");
+    assert_eq!(fs::read_to_string(base.join("blog/hello/image.png")).unwrap(), "png");
+    assert!(journal.history().unwrap().is_empty());
+    assert!(journal.undo_last().unwrap().is_none());
+}
+
+#[test]
+fn test_undo_last_reverses_move_and_edits() {
+    let temp_dir = tempdir().unwrap();
+    let base = temp_dir.path();
+    write(base, "blog/hello/index.md", "hello");
+    write(base, "blog/links/index.md", "see /blog/hello");
+    let journal = Journal::for_base_dir(base);
+
+    let mut transaction = journal.begin("move", "Move blog/hello to notes/hello").unwrap();
+    transaction.snapshot(&base.join("blog/links/index.md")).unwrap();
+    transaction.snapshot(&base.join("blog/hello")).unwrap();
+    transaction.snapshot(&base.join("notes/hello")).unwrap();
+    fs::create_dir_all(base.join("notes")).unwrap();
+    fs::rename(base.join("blog/hello"), base.join("notes/hello")).unwrap();
+    fs::write(base.join("blog/links/index.md"), "see /notes/hello").unwrap();
+    transaction.commit().unwrap();
+
+    journal.undo_last().unwrap();
+    assert!(!base.join("notes/hello").exists());
+    assert_eq!(fs::read_to_string(base.join("blog/hello/index.md")).unwrap(), "hello");
+    assert_eq!(fs::read_to_string(base.join("blog/links/index.md")).unwrap(), "see /blog/hello");
+}
+
+#[test]
+fn test_history_lists_most_recent_first() {
+    let temp_dir = tempdir().unwrap();
+    let base = temp_dir.path();
+    write(base, "a.md", "a");
+    let journal = Journal::for_base_dir(base);
+
+    for step in ["first", "second", "third"] {
+        let mut transaction = journal.begin("edit", step).unwrap();
+        transaction.snapshot(&base.join("a.md")).unwrap();
+        fs::write(base.join("a.md"), step).unwrap();
+        transaction.commit().unwrap();
+    }
+
+    let descriptions: Vec<String> = journal.history().unwrap().into_iter().map(|record| record.description).collect();
+    assert_eq!(descriptions, ["third", "second", "first"]);
+
+    journal.undo_last().unwrap();
+    journal.undo_last().unwrap();
+    assert_eq!(fs::read_to_string(base.join("a.md")).unwrap(), "first");
+}
+
+#[test]
+fn test_uncommitted_transaction_is_discarded() {
+    let temp_dir = tempdir().unwrap();
+    let base = temp_dir.path();
+    write(base, "a.md", "a");
+    let journal = Journal::for_base_dir(base);
+
+    {
+        let mut transaction = journal.begin("edit", "abandoned").unwrap();
+        transaction.snapshot(&base.join("a.md")).unwrap();
+    }
+
+    assert!(journal.history().unwrap().is_empty());
+    assert_eq!(fs::read_dir(journal.root()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_undo_removes_created_paths() {
+    let temp_dir = tempdir().unwrap();
+    let base = temp_dir.path();
+    write(base, "a.md", "a");
+    let journal = Journal::for_base_dir(base);
+
+    let mut transaction = journal.begin("trash", "Move a.md to the trash").unwrap();
+    transaction.snapshot(&base.join("a.md")).unwrap();
+    fs::rename(base.join("a.md"), base.join("trashed.md")).unwrap();
+    transaction.created(&base.join("trashed.md"));
+    transaction.commit().unwrap();
+
+    journal.undo_last().unwrap();
+    assert!(!base.join("trashed.md").exists());
+    assert_eq!(fs::read_to_string(base.join("a.md")).unwrap(), "a");
+}
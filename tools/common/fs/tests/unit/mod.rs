@@ -5,6 +5,11 @@
 pub mod file_operations_tests;
 pub mod directory_operations_tests;
 pub mod lib_tests;
+pub mod content_path_tests;
+pub mod atomic_tests;
+pub mod discovery_tests;
+pub mod repository_tests;
+pub mod journal_tests;
 
 // Unit tests for the fs module
 
@@ -0,0 +1,82 @@
+//! Unit tests for the filesystem content repository
+
+use common_fs::{CachedContentRepository, FsContentRepository};
+use common_test_utils::config_for;
+use common_traits::ContentRepository;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write(root: &Path, relative: &str, content: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_fs_repository_lists_topics_and_articles() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "notes/second/index.md", "two");
+    write(root, "notes/first/index.mdx", "one");
+    write(root, "notes/images/cover.jpg", "not an article");
+    write(root, "blog/second/index.md", "blog two");
+
+    let repository = FsContentRepository::new(&config_for(root, &["notes", "blog", "empty"]));
+
+    assert_eq!(repository.list_topics().unwrap(), vec!["blog", "empty", "notes"]);
+    assert!(repository.list_articles("empty").unwrap().is_empty());
+    assert!(repository.list_articles("missing").is_err());
+
+    let notes = repository.list_articles("notes").unwrap();
+    let slugs: Vec<&str> = notes.iter().map(|article| article.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["first", "second"]);
+    assert_eq!(notes[0].content_file, root.join("notes/first/index.mdx"));
+    assert_eq!(notes[0].dir, root.join("notes/first"));
+
+    assert_eq!(repository.article_paths().unwrap().len(), 3);
+}
+
+#[test]
+fn test_fs_repository_finds_and_loads_articles_by_slug() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "notes/second/index.md", "notes two");
+    write(root, "blog/second/index.md", "blog two");
+
+    let repository = FsContentRepository::new(&config_for(root, &["notes", "blog"]));
+
+    let found = repository.find_articles("second").unwrap();
+    let topics: Vec<&str> = found.iter().map(|article| article.topic.as_str()).collect();
+    assert_eq!(topics, vec!["blog", "notes"]);
+    assert!(repository.find_article("notes", "missing").unwrap().is_none());
+
+    let (article, content) = repository.load_article("second").unwrap();
+    assert_eq!(article.topic, "blog");
+    assert_eq!(content, "blog two");
+    assert!(repository.load_article("missing").is_err());
+}
+
+#[test]
+fn test_cached_repository_rereads_changed_files() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path();
+    write(root, "notes/first/index.md", "one");
+
+    let repository = CachedContentRepository::new(&config_for(root, &["notes"]));
+    let article = repository.find_article("notes", "first").unwrap().unwrap();
+
+    assert_eq!(repository.read_article(&article).unwrap(), "one");
+    assert_eq!(repository.read_article(&article).unwrap(), "one");
+    assert_eq!(repository.cached_files(), 1);
+
+    // A different size is a change even within the file system's time resolution
+    write(root, "notes/first/index.md", "one and two");
+    assert_eq!(repository.read_article(&article).unwrap(), "one and two");
+
+    repository.clear();
+    assert_eq!(repository.cached_files(), 0);
+
+    fs::remove_file(root.join("notes/first/index.md")).unwrap();
+    assert!(repository.read_article(&article).is_err());
+}
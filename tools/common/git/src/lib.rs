@@ -0,0 +1,331 @@
+//! # Common Git
+//!
+//! Git integration for tools that only need to look at what changed. A
+//! pull request or an incremental CI build can validate, build or analyze
+//! just the articles touched since a commit or branch instead of the whole
+//! content tree. The commit history also tells the build when each article
+//! was created and last changed, and by whom, and hooks let checks run on
+//! staged content before it is committed.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use common_git::changed_content_since;
+//! use common_models::Config;
+//!
+//! fn changed(config: &Config) -> common_errors::Result<()> {
+//!     for article in changed_content_since(config, "origin/main")? {
+//!         println!("{}/{}", article.topic, article.slug);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use chrono::{DateTime, Utc};
+use common_errors::{Result, WritingError};
+use common_models::{Config, GitMetadata};
+use git2::{DiffOptions, Delta, Repository, Sort};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+/// An article with at least one changed file in its directory
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChangedArticle {
+    /// Topic key from the configuration
+    pub topic: String,
+    pub slug: String,
+}
+
+/// Open the repository containing `path`, searching parent directories
+pub fn open_repository(path: &Path) -> Result<Repository> {
+    Repository::discover(path).map_err(git_error)
+}
+
+/// Files that differ from `reference`, as absolute paths
+///
+/// Covers committed, staged and unstaged changes and untracked files, so a
+/// working tree is compared as it stands. Deleted files are left out since
+/// there is nothing left to process. `reference` can be anything git
+/// understands, such as a branch, tag or commit hash.
+///
+/// # Errors
+///
+/// Returns an error if `path` isn't in a git repository with a working tree,
+/// or `reference` can't be resolved
+pub fn changed_files_since(path: &Path, reference: &str) -> Result<Vec<PathBuf>> {
+    let repo = open_repository(path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| WritingError::command_error("git: repository has no working tree"))?;
+    let workdir = workdir.canonicalize().unwrap_or_else(|_| workdir.to_path_buf());
+
+    let tree = repo
+        .revparse_single(reference)
+        .and_then(|object| object.peel_to_tree())
+        .map_err(|err| WritingError::invalid_argument(format!("Unknown git reference '{}': {}", reference, err.message())))?;
+
+    let mut options = DiffOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+        .map_err(git_error)?;
+
+    let mut files: Vec<PathBuf> = diff
+        .deltas()
+        .filter(|delta| delta.status() != Delta::Deleted)
+        .filter_map(|delta| delta.new_file().path().map(|path| workdir.join(path)))
+        .collect();
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+/// Articles with files that differ from `reference`
+///
+/// A change to any file in an article's directory, such as an image, counts
+/// as a change to the article.
+///
+/// # Errors
+///
+/// Returns an error if the content isn't in a git repository or `reference`
+/// can't be resolved
+pub fn changed_content_since(config: &Config, reference: &str) -> Result<BTreeSet<ChangedArticle>> {
+    let base_dir = Path::new(&config.content.base_dir);
+    let base_dir = match base_dir.canonicalize() {
+        Ok(base_dir) => base_dir,
+        Err(_) => return Err(WritingError::directory_not_found(base_dir)),
+    };
+
+    Ok(articles_for_files(config, &base_dir, &changed_files_since(&base_dir, reference)?))
+}
+
+/// Files added or modified in the index, as absolute paths
+///
+/// These are the files the next commit will change. Deleted files are left
+/// out since there is nothing left to check.
+///
+/// # Errors
+///
+/// Returns an error if `path` isn't in a git repository with a working tree
+pub fn staged_files(path: &Path) -> Result<Vec<PathBuf>> {
+    let repo = open_repository(path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| WritingError::command_error("git: repository has no working tree"))?;
+    let workdir = workdir.canonicalize().unwrap_or_else(|_| workdir.to_path_buf());
+
+    // Before the first commit everything in the index is staged
+    let head = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree().map_err(git_error)?),
+        Err(_) => None,
+    };
+    let diff = repo
+        .diff_tree_to_index(head.as_ref(), None, None)
+        .map_err(git_error)?;
+
+    let mut files: Vec<PathBuf> = diff
+        .deltas()
+        .filter(|delta| delta.status() != Delta::Deleted)
+        .filter_map(|delta| delta.new_file().path().map(|path| workdir.join(path)))
+        .collect();
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+/// Read the staged version of a file from the index
+///
+/// This is what will be committed, which may differ from the working tree
+/// when only some changes were staged.
+///
+/// # Errors
+///
+/// Returns an error if the file isn't in the index or isn't valid UTF-8
+pub fn read_staged(path: &Path) -> Result<String> {
+    let repo = open_repository(path.parent().unwrap_or(path))?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| WritingError::command_error("git: repository has no working tree"))?;
+    let workdir = workdir.canonicalize().unwrap_or_else(|_| workdir.to_path_buf());
+
+    let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let relative = absolute
+        .strip_prefix(&workdir)
+        .map_err(|_| WritingError::invalid_argument(format!("Not in the repository: {}", path.display())))?;
+
+    let index = repo.index().map_err(git_error)?;
+    let entry = index
+        .get_path(relative, 0)
+        .ok_or_else(|| WritingError::file_not_found(path))?;
+    let blob = repo.find_blob(entry.id).map_err(git_error)?;
+
+    String::from_utf8(blob.content().to_vec())
+        .map_err(|_| WritingError::format_error(format!("Staged file is not valid UTF-8: {}", path.display())))
+}
+
+/// Marker line in hooks installed by [`install_hook`]
+pub const HOOK_MARKER: &str = "# Installed by the writing tools; reinstall to update";
+
+/// Install a git hook that runs `command`
+///
+/// The hook is a shell script in the repository's hooks directory, which
+/// honours `core.hooksPath`. A hook that was installed by this function is
+/// replaced; any other existing hook is left alone unless `force` is set.
+/// Returns the path of the hook.
+///
+/// # Errors
+///
+/// Returns an error if `path` isn't in a git repository, a hook that wasn't
+/// installed by this function exists and `force` isn't set, or the hook can't
+/// be written
+pub fn install_hook(path: &Path, name: &str, command: &str, force: bool) -> Result<PathBuf> {
+    let repo = open_repository(path)?;
+
+    let hooks_dir = match repo.config().and_then(|config| config.get_path("core.hooksPath")) {
+        Ok(hooks_path) => match repo.workdir() {
+            Some(workdir) => workdir.join(hooks_path),
+            None => hooks_path,
+        },
+        Err(_) => repo.path().join("hooks"),
+    };
+    let hook_path = hooks_dir.join(name);
+
+    if let Ok(existing) = std::fs::read_to_string(&hook_path) {
+        if !force && !existing.contains(HOOK_MARKER) {
+            return Err(WritingError::validation_error(format!(
+                "A {} hook already exists at {}; pass --force to replace it",
+                name,
+                hook_path.display()
+            )));
+        }
+    }
+
+    std::fs::create_dir_all(&hooks_dir)?;
+    std::fs::write(&hook_path, format!("#!/bin/sh\n{}\n\n{}\n", HOOK_MARKER, command))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(hook_path)
+}
+
+/// Revision history of each file, keyed by the paths as given
+///
+/// The history comes from a single walk of the commits reachable from `HEAD`.
+/// Merge commits are skipped, so a change is credited to the author of the
+/// commit that made it. Files that have never been committed are left out.
+///
+/// # Errors
+///
+/// Returns an error if the files aren't in a git repository or its history
+/// can't be read
+pub fn file_history(paths: &[PathBuf]) -> Result<HashMap<PathBuf, GitMetadata>> {
+    let first = match paths.first() {
+        Some(first) => first,
+        None => return Ok(HashMap::new()),
+    };
+
+    let repo = open_repository(first.parent().unwrap_or(first))?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| WritingError::command_error("git: repository has no working tree"))?;
+    let workdir = workdir.canonicalize().unwrap_or_else(|_| workdir.to_path_buf());
+
+    // Paths in git are relative to the working tree
+    let mut wanted: HashMap<PathBuf, &PathBuf> = HashMap::new();
+    for path in paths {
+        let absolute = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if let Ok(relative) = absolute.strip_prefix(&workdir) {
+            wanted.insert(relative.to_path_buf(), path);
+        }
+    }
+
+    let mut history: HashMap<PathBuf, GitMetadata> = HashMap::new();
+    if repo.head().is_err() {
+        // No commits yet
+        return Ok(history);
+    }
+
+    let mut revwalk = repo.revwalk().map_err(git_error)?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME | Sort::REVERSE).map_err(git_error)?;
+    revwalk.push_head().map_err(git_error)?;
+
+    for oid in revwalk {
+        let commit = repo.find_commit(oid.map_err(git_error)?).map_err(git_error)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+
+        let tree = commit.tree().map_err(git_error)?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree().map_err(git_error)?),
+            Err(_) => None,
+        };
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(git_error)?;
+
+        let time = commit_time(&commit);
+        let author = commit.author().name().unwrap_or_default().to_string();
+
+        for delta in diff.deltas() {
+            let path = match delta.new_file().path().and_then(|path| wanted.get(path)) {
+                Some(path) => (*path).clone(),
+                None => continue,
+            };
+
+            let metadata = history.entry(path).or_insert_with(|| GitMetadata {
+                created: time,
+                last_modified: time,
+                contributors: Vec::new(),
+            });
+            metadata.created = metadata.created.min(time);
+            metadata.last_modified = metadata.last_modified.max(time);
+            if !author.is_empty() && !metadata.contributors.contains(&author) {
+                metadata.contributors.push(author.clone());
+            }
+        }
+    }
+
+    Ok(history)
+}
+
+/// When a commit was authored
+fn commit_time(commit: &git2::Commit) -> DateTime<Utc> {
+    DateTime::from_timestamp(commit.author().when().seconds(), 0).unwrap_or_default()
+}
+
+/// Map changed files to the articles whose directories contain them
+fn articles_for_files(config: &Config, base_dir: &Path, files: &[PathBuf]) -> BTreeSet<ChangedArticle> {
+    let mut articles = BTreeSet::new();
+
+    for (topic_key, topic_config) in &config.content.topics {
+        let topic_dir = base_dir.join(&topic_config.directory);
+
+        for file in files {
+            let mut components = match file.strip_prefix(&topic_dir) {
+                Ok(relative) => relative.components(),
+                Err(_) => continue,
+            };
+
+            // Files directly in the topic directory don't belong to an article
+            if let (Some(Component::Normal(slug)), Some(_)) = (components.next(), components.next()) {
+                articles.insert(ChangedArticle {
+                    topic: topic_key.clone(),
+                    slug: slug.to_string_lossy().into_owned(),
+                });
+            }
+        }
+    }
+
+    articles
+}
+
+fn git_error(err: git2::Error) -> WritingError {
+    WritingError::command_error(format!("git: {}", err.message()))
+}
@@ -0,0 +1,118 @@
+use common_git::{changed_content_since, changed_files_since, file_history, ChangedArticle};
+use common_test_utils::config_for;
+use git2::{Repository, Signature};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Stage everything in the working tree and commit it
+fn commit_all(repo: &Repository, message: &str) {
+    let mut index = repo.index().unwrap();
+    index.add_all(["*"], git2::IndexAddOption::DEFAULT, None).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now("Writer", "writer@example.com").unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap();
+}
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_changed_content_since_finds_touched_articles() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    let repo = Repository::init(&root).unwrap();
+
+    write(&root, "content/blog/first/first.md", "First");
+    write(&root, "content/blog/second/second.md", "Second");
+    write(&root, "content/notes/third/third.md", "Third");
+    write(&root, "content/notes/removed/removed.md", "Removed");
+    commit_all(&repo, "Initial content");
+
+    // A committed change, an unstaged change, a new untracked article and a deletion
+    write(&root, "content/blog/first/first.md", "First, edited");
+    commit_all(&repo, "Edit first");
+    write(&root, "content/notes/third/chart.png", "png");
+    write(&root, "content/blog/fourth/fourth.md", "Fourth");
+    write(&root, "content/blog/README.md", "Not an article");
+    fs::remove_dir_all(root.join("content/notes/removed")).unwrap();
+
+    let files = changed_files_since(&root, "HEAD~1").unwrap();
+    assert_eq!(files, vec![
+        root.join("content/blog/README.md"),
+        root.join("content/blog/first/first.md"),
+        root.join("content/blog/fourth/fourth.md"),
+        root.join("content/notes/third/chart.png"),
+    ]);
+
+    let articles = changed_content_since(&config_for(&root.join("content"), &["blog", "notes"]), "HEAD").unwrap();
+    let articles: Vec<ChangedArticle> = articles.into_iter().collect();
+    assert_eq!(articles, vec![
+        ChangedArticle { topic: "blog".to_string(), slug: "fourth".to_string() },
+        ChangedArticle { topic: "notes".to_string(), slug: "third".to_string() },
+    ]);
+}
+
+#[test]
+fn test_changed_files_since_rejects_unknown_references() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = Repository::init(temp_dir.path()).unwrap();
+    write(temp_dir.path(), "content/blog/first/first.md", "First");
+    commit_all(&repo, "Initial content");
+
+    assert!(changed_files_since(temp_dir.path(), "no-such-branch").is_err());
+
+    let outside = TempDir::new().unwrap();
+    assert!(changed_files_since(outside.path(), "HEAD").is_err());
+}
+
+/// Commit everything as `author` at `seconds` past the epoch
+fn commit_as(repo: &Repository, author: &str, seconds: i64) {
+    let mut index = repo.index().unwrap();
+    index.add_all(["*"], git2::IndexAddOption::DEFAULT, None).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::new(author, "writer@example.com", &git2::Time::new(seconds, 0)).unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &signature, &signature, "Update", &tree, &parents).unwrap();
+}
+
+#[test]
+fn test_file_history_tracks_dates_and_contributors() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    let repo = Repository::init(&root).unwrap();
+
+    write(&root, "content/blog/first/first.md", "First");
+    commit_as(&repo, "Ada", 1_700_000_000);
+    write(&root, "content/blog/second/second.md", "Second");
+    commit_as(&repo, "Grace", 1_700_100_000);
+    write(&root, "content/blog/first/first.md", "First, edited");
+    commit_as(&repo, "Grace", 1_700_200_000);
+    write(&root, "content/blog/first/first.md", "First, edited again");
+    commit_as(&repo, "Ada", 1_700_300_000);
+    write(&root, "content/blog/draft/draft.md", "Not committed");
+
+    let first = root.join("content/blog/first/first.md");
+    let second = root.join("content/blog/second/second.md");
+    let draft = root.join("content/blog/draft/draft.md");
+    let history = file_history(&[first.clone(), second.clone(), draft.clone()]).unwrap();
+
+    let first = &history[&first];
+    assert_eq!(first.created.timestamp(), 1_700_000_000);
+    assert_eq!(first.last_modified.timestamp(), 1_700_300_000);
+    assert_eq!(first.contributors, vec!["Ada".to_string(), "Grace".to_string()]);
+
+    let second = &history[&second];
+    assert_eq!(second.created, second.last_modified);
+    assert_eq!(second.contributors, vec!["Grace".to_string()]);
+
+    assert!(!history.contains_key(&draft));
+}
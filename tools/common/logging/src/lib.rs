@@ -0,0 +1,205 @@
+//! # Common Logging
+//!
+//! Structured logging for the writing tools, built on `tracing`. Libraries
+//! emit events and spans with the `tracing` macros; each command line
+//! installs a subscriber once at startup from its verbosity flags. Log lines
+//! go to stderr, so they never mix with a tool's output.
+//!
+//! The level comes from `--verbose` and `--quiet`. Individual modules can be
+//! turned up or down with filter directives, such as
+//! `content_validate=debug`, given with `--log-filter` or the `WRITING_LOG`
+//! environment variable, which replaces the flags entirely.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use common_logging::{LogFormat, LoggingOptions, Verbosity};
+//!
+//! let options = LoggingOptions {
+//!     verbosity: Verbosity::from_flags(1, false),
+//!     format: LogFormat::Text,
+//!     filter: Some("content_build=debug".to_string()),
+//! };
+//! common_logging::init(&options)?;
+//!
+//! let _span = tracing::info_span!("build").entered();
+//! tracing::info!(articles = 3, "Processed content");
+//! # Ok::<(), common_errors::WritingError>(())
+//! ```
+
+use common_errors::{Result, WritingError};
+use tracing_subscriber::EnvFilter;
+
+pub use tracing;
+
+/// Environment variable with filter directives that override the flags
+pub const LOG_ENV: &str = "WRITING_LOG";
+
+/// How much a tool logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Errors only
+    Quiet,
+    /// Errors and warnings
+    #[default]
+    Normal,
+    /// Progress through the work as well
+    Verbose,
+    /// Detail for diagnosing problems
+    Debug,
+    /// Everything
+    Trace,
+}
+
+impl Verbosity {
+    /// Verbosity for a repeatable `--verbose` flag and a `--quiet` flag
+    ///
+    /// `--quiet` wins over any number of `--verbose` flags.
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, 2) => Verbosity::Debug,
+            (false, _) => Verbosity::Trace,
+        }
+    }
+
+    /// The most detailed level that is logged
+    pub fn level(self) -> tracing::Level {
+        match self {
+            Verbosity::Quiet => tracing::Level::ERROR,
+            Verbosity::Normal => tracing::Level::WARN,
+            Verbosity::Verbose => tracing::Level::INFO,
+            Verbosity::Debug => tracing::Level::DEBUG,
+            Verbosity::Trace => tracing::Level::TRACE,
+        }
+    }
+
+    /// Whether progress should be reported
+    pub fn is_verbose(self) -> bool {
+        self >= Verbosity::Verbose
+    }
+}
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+/// Everything needed to set up logging
+#[derive(Debug, Clone, Default)]
+pub struct LoggingOptions {
+    pub verbosity: Verbosity,
+    pub format: LogFormat,
+    /// Extra filter directives, comma-separated, such as `content_validate=debug`
+    pub filter: Option<String>,
+}
+
+impl LoggingOptions {
+    /// The filter directives to log with
+    ///
+    /// `WRITING_LOG` replaces the verbosity and filter when it is set.
+    pub fn directives(&self) -> String {
+        if let Ok(directives) = std::env::var(LOG_ENV) {
+            if !directives.trim().is_empty() {
+                return directives;
+            }
+        }
+
+        let level = self.verbosity.level().as_str().to_lowercase();
+        match &self.filter {
+            Some(filter) if !filter.trim().is_empty() => format!("{},{}", level, filter),
+            _ => level,
+        }
+    }
+
+    /// Parse the filter directives
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directive is invalid
+    pub fn env_filter(&self) -> Result<EnvFilter> {
+        let directives = self.directives();
+        EnvFilter::try_new(&directives)
+            .map_err(|err| WritingError::invalid_argument(format!("Invalid log filter '{}': {}", directives, err)))
+    }
+}
+
+/// Install the global subscriber that writes log lines to stderr
+///
+/// Only the first call in a process installs a subscriber; later calls are
+/// ignored, so tests can call this freely.
+///
+/// # Errors
+///
+/// Returns an error if a filter directive is invalid
+pub fn init(options: &LoggingOptions) -> Result<()> {
+    let filter = options.env_filter()?;
+
+    // Module paths only help once there is detail to attribute
+    let show_target = options.verbosity >= Verbosity::Debug;
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_target(show_target);
+
+    let _ = match options.format {
+        LogFormat::Text => builder.without_time().try_init(),
+        LogFormat::Json => builder.json().try_init(),
+    };
+
+    Ok(())
+}
+
+/// Verbosity flags shared by the command lines
+#[cfg(feature = "cli")]
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct LogArgs {
+    /// Show progress; repeat for debug (-vv) and trace (-vvv) logs
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+
+    /// Only show errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Write log lines as JSON
+    #[arg(long, global = true)]
+    pub log_json: bool,
+
+    /// Log filter directives, such as content_build=debug
+    #[arg(long, global = true, value_name = "DIRECTIVES")]
+    pub log_filter: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+impl LogArgs {
+    /// Verbosity the flags ask for
+    pub fn verbosity(&self) -> Verbosity {
+        Verbosity::from_flags(self.verbose, self.quiet)
+    }
+
+    /// Logging options the flags ask for
+    pub fn options(&self) -> LoggingOptions {
+        LoggingOptions {
+            verbosity: self.verbosity(),
+            format: if self.log_json { LogFormat::Json } else { LogFormat::Text },
+            filter: self.log_filter.clone(),
+        }
+    }
+
+    /// Install the global subscriber for these flags, see [`init`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `--log-filter` is invalid
+    pub fn init(&self) -> Result<()> {
+        init(&self.options())
+    }
+}
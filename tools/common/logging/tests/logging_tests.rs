@@ -0,0 +1,50 @@
+use clap::Parser;
+use common_logging::{LogArgs, LogFormat, LoggingOptions, Verbosity};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+#[test]
+fn test_verbosity_from_flags() {
+    assert_eq!(Verbosity::from_flags(0, false), Verbosity::Normal);
+    assert_eq!(Verbosity::from_flags(1, false), Verbosity::Verbose);
+    assert_eq!(Verbosity::from_flags(2, false), Verbosity::Debug);
+    assert_eq!(Verbosity::from_flags(5, false), Verbosity::Trace);
+    assert_eq!(Verbosity::from_flags(2, true), Verbosity::Quiet);
+
+    assert!(Verbosity::Verbose.is_verbose());
+    assert!(!Verbosity::Normal.is_verbose());
+    assert_eq!(Verbosity::Quiet.level(), tracing::Level::ERROR);
+}
+
+#[test]
+fn test_log_args_map_to_options() {
+    let cli = Cli::parse_from(["tool", "-vv", "--log-json", "--log-filter", "content_build=trace"]);
+    let options = cli.log.options();
+    assert_eq!(options.verbosity, Verbosity::Debug);
+    assert_eq!(options.format, LogFormat::Json);
+
+    if std::env::var(common_logging::LOG_ENV).is_err() {
+        assert_eq!(options.directives(), "debug,content_build=trace");
+    }
+
+    assert!(Cli::try_parse_from(["tool", "--quiet", "--verbose"]).is_err());
+}
+
+#[test]
+fn test_invalid_filter_is_rejected() {
+    let options = LoggingOptions {
+        filter: Some("content_build=loud".to_string()),
+        ..Default::default()
+    };
+
+    if std::env::var(common_logging::LOG_ENV).is_err() {
+        assert!(options.env_filter().is_err());
+        assert!(common_logging::init(&options).is_err());
+    }
+    assert!(common_logging::init(&LoggingOptions::default()).is_ok());
+    assert!(common_logging::init(&LoggingOptions::default()).is_ok());
+}
@@ -0,0 +1,190 @@
+//! # Word Count Analysis
+//!
+//! Counting whitespace-separated tokens over-counts: code, raw HTML, URLs
+//! and leftover frontmatter all look like words. This module walks the
+//! markdown events instead, counting prose separately from code, and counts
+//! CJK text by character since it isn't separated by spaces.
+//!
+//! Requires the `html` feature
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+/// Word counts for a markdown document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordCountAnalysis {
+    /// Space-separated words in prose, excluding URLs
+    pub words: usize,
+    /// Chinese and Japanese characters in prose, each counted as a word
+    pub cjk_characters: usize,
+    /// Words in inline code and code blocks
+    pub code_words: usize,
+}
+
+impl WordCountAnalysis {
+    /// Number of words in the prose, counting each CJK character as a word
+    pub fn word_count(&self) -> usize {
+        self.words + self.cjk_characters
+    }
+}
+
+/// Options for estimating reading time
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadingTimeOptions {
+    /// Reading speed for space-separated words
+    pub words_per_minute: u32,
+    /// Reading speed for CJK characters
+    pub cjk_characters_per_minute: u32,
+    /// Whether code counts towards the reading time
+    pub include_code: bool,
+}
+
+impl Default for ReadingTimeOptions {
+    fn default() -> Self {
+        Self {
+            words_per_minute: 200,
+            cjk_characters_per_minute: 500,
+            include_code: false,
+        }
+    }
+}
+
+/// Count the words in a markdown document
+///
+/// A leading frontmatter block is ignored, as are raw HTML, link targets and
+/// image alt text.
+pub fn analyze_words(content: &str) -> WordCountAnalysis {
+    let (prose, code) = split_prose_and_code(content);
+
+    let mut analysis = WordCountAnalysis {
+        code_words: count_tokens(&code),
+        ..Default::default()
+    };
+
+    for token in prose.split_whitespace() {
+        if is_url(token) {
+            continue;
+        }
+
+        let cjk = token.chars().filter(|c| is_cjk(*c)).count();
+        analysis.cjk_characters += cjk;
+
+        // Whatever remains between CJK characters is counted as words
+        analysis.words += token
+            .split(is_cjk)
+            .filter(|part| part.chars().any(char::is_alphanumeric))
+            .count();
+    }
+
+    analysis
+}
+
+/// The prose of a markdown document as plain text, without code or URLs
+///
+/// Uses the same rules as [`analyze_words`], so the words in the text are the
+/// words it counts.
+pub fn prose_text(content: &str) -> String {
+    let (prose, _) = split_prose_and_code(content);
+    prose
+        .split_whitespace()
+        .filter(|token| !is_url(token))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Separate the prose of a markdown document from its code
+fn split_prose_and_code(content: &str) -> (String, String) {
+    let mut prose = String::new();
+    let mut code = String::new();
+    let mut in_code_block = false;
+    let mut in_image = false;
+
+    for event in Parser::new_ext(strip_frontmatter(content), Options::all()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => {
+                in_code_block = false;
+                code.push(' ');
+            }
+            Event::Start(Tag::Image(..)) => in_image = true,
+            Event::End(Tag::Image(..)) => in_image = false,
+            Event::Text(_) if in_image => {}
+            Event::Text(text) if in_code_block => code.push_str(&text),
+            Event::Text(text) => prose.push_str(&text),
+            Event::Code(text) => {
+                code.push_str(&text);
+                code.push(' ');
+            }
+            // Inline formatting doesn't split words, but breaks and block ends do
+            Event::SoftBreak | Event::HardBreak | Event::Rule => prose.push(' '),
+            Event::End(tag) if !is_inline(&tag) => prose.push(' '),
+            _ => {}
+        }
+    }
+
+    (prose, code)
+}
+
+/// Estimate the reading time of a markdown document in minutes
+///
+/// The result is rounded up and is at least one minute.
+pub fn calculate_reading_time_with_options(content: &str, options: &ReadingTimeOptions) -> u32 {
+    let analysis = analyze_words(content);
+
+    let mut words = analysis.words;
+    if options.include_code {
+        words += analysis.code_words;
+    }
+
+    let minutes = words as f64 / options.words_per_minute.max(1) as f64
+        + analysis.cjk_characters as f64 / options.cjk_characters_per_minute.max(1) as f64;
+
+    (minutes.ceil() as u32).max(1)
+}
+
+/// Skip a leading `---` delimited frontmatter block
+pub(crate) fn strip_frontmatter(content: &str) -> &str {
+    let Some(rest) = content.strip_prefix("---\n").or_else(|| content.strip_prefix("---\r\n")) else {
+        return content;
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return &rest[offset..];
+        }
+    }
+
+    content
+}
+
+/// Whether a tag is inline formatting, which doesn't separate words
+fn is_inline(tag: &Tag) -> bool {
+    matches!(tag, Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..))
+}
+
+/// Count whitespace-separated tokens that contain a letter or digit
+fn count_tokens(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|token| token.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Whether a token looks like a bare URL
+fn is_url(token: &str) -> bool {
+    let token = token.trim_start_matches(['(', '<', '[']);
+    token.starts_with("http://") || token.starts_with("https://") || token.starts_with("www.")
+}
+
+/// Whether a character is a Chinese or Japanese character
+///
+/// Korean is written with spaces between words, so Hangul is counted as words.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // Hiragana and Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{20000}'..='\u{2FA1F}' // Supplementary ideographs
+    )
+}
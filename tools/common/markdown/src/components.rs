@@ -0,0 +1,300 @@
+//! # MDX Components
+//!
+//! Finds JSX-like components such as `<YouTube id="abc" />` or
+//! `<Aside>Some *markdown*</Aside>` in markdown source. Component names start
+//! with an uppercase letter, which keeps them apart from plain HTML tags.
+//! Components inside fenced code blocks and inline code are ignored.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// A component used in markdown content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// Tag name, e.g. `YouTube`
+    pub name: String,
+    /// Attribute values; attributes without a value are `"true"`
+    pub attributes: BTreeMap<String, String>,
+    /// Source between the opening and closing tags, `None` if self-closing
+    pub children: Option<String>,
+    /// Byte range of the whole component in the source
+    pub range: Range<usize>,
+    /// Line of the opening tag, starting at 1
+    pub line: usize,
+}
+
+/// Find the top-level components in markdown content
+///
+/// Components nested in another component's children are not returned; call
+/// this again on the children to find them.
+pub fn find_components(content: &str) -> Vec<Component> {
+    let code = code_ranges(content);
+    let mut components = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = content[pos..].find('<') {
+        let start = pos + offset;
+
+        if let Some(range) = code.iter().find(|range| range.contains(&start)) {
+            pos = range.end;
+            continue;
+        }
+
+        match parse_component(content, start) {
+            Some(component) => {
+                pos = component.range.end;
+                components.push(component);
+            }
+            None => pos = start + 1,
+        }
+    }
+
+    components
+}
+
+/// Replace each top-level component with the output of `replace`
+pub fn replace_components<F>(content: &str, mut replace: F) -> String
+where
+    F: FnMut(&Component) -> String,
+{
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for component in find_components(content) {
+        output.push_str(&content[last..component.range.start]);
+        output.push_str(&replace(&component));
+        last = component.range.end;
+    }
+    output.push_str(&content[last..]);
+
+    output
+}
+
+/// Parse a component starting at the `<` at `start`
+fn parse_component(content: &str, start: usize) -> Option<Component> {
+    let after = &content[start + 1..];
+    if !after.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    let name_len = after
+        .find(|c: char| !is_name_char(c))
+        .unwrap_or(after.len());
+    let name = &after[..name_len];
+    let mut pos = start + 1 + name_len;
+    let mut attributes = BTreeMap::new();
+
+    let (tag_end, self_closing) = loop {
+        pos = skip_whitespace(content, pos);
+        let rest = &content[pos..];
+
+        if rest.starts_with("/>") {
+            break (pos + 2, true);
+        }
+        if rest.starts_with('>') {
+            break (pos + 1, false);
+        }
+
+        let attribute_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        if attribute_len == 0 {
+            return None;
+        }
+        let attribute = &rest[..attribute_len];
+
+        pos = skip_whitespace(content, pos + attribute_len);
+        if content[pos..].starts_with('=') {
+            pos = skip_whitespace(content, pos + 1);
+            let (value, next) = parse_attribute_value(content, pos)?;
+            attributes.insert(attribute.to_string(), value);
+            pos = next;
+        } else {
+            attributes.insert(attribute.to_string(), "true".to_string());
+        }
+    };
+
+    // An opening tag without a closing tag is treated as self-closing
+    let closing = if self_closing {
+        None
+    } else {
+        find_closing_tag(content, name, tag_end)
+    };
+    let (children, end) = match closing {
+        Some((children_end, end)) => (Some(content[tag_end..children_end].to_string()), end),
+        None => (None, tag_end),
+    };
+
+    Some(Component {
+        name: name.to_string(),
+        attributes,
+        children,
+        range: start..end,
+        line: content[..start].matches('\n').count() + 1,
+    })
+}
+
+/// Parse a quoted, braced or bare attribute value, returning it and the position after it
+fn parse_attribute_value(content: &str, pos: usize) -> Option<(String, usize)> {
+    let rest = &content[pos..];
+
+    match rest.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let len = rest[1..].find(quote)?;
+            Some((rest[1..1 + len].to_string(), pos + len + 2))
+        }
+        '{' => {
+            let mut depth = 0;
+            for (index, c) in rest.char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            let expression = rest[1..index].trim();
+                            return Some((unquote(expression).to_string(), pos + index + 1));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            None
+        }
+        _ => {
+            let len = rest
+                .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .unwrap_or(rest.len());
+            Some((rest[..len].to_string(), pos + len))
+        }
+    }
+}
+
+/// Strip the quotes from a string literal expression such as `{"abc"}`
+fn unquote(expression: &str) -> &str {
+    for quote in ['"', '\'', '`'] {
+        if expression.len() >= 2 && expression.starts_with(quote) && expression.ends_with(quote) {
+            return &expression[1..expression.len() - 1];
+        }
+    }
+    expression
+}
+
+/// Find the closing tag matching an opening tag, returning where the children
+/// end and where the closing tag ends
+fn find_closing_tag(content: &str, name: &str, from: usize) -> Option<(usize, usize)> {
+    let open = format!("<{}", name);
+    let close = format!("</{}>", name);
+    let mut depth = 1;
+    let mut pos = from;
+
+    while let Some(offset) = content[pos..].find('<') {
+        let at = pos + offset;
+        let rest = &content[at..];
+
+        if rest.starts_with(&close) {
+            depth -= 1;
+            if depth == 0 {
+                return Some((at, at + close.len()));
+            }
+            pos = at + close.len();
+        } else if rest.starts_with(&open) && !rest[open.len()..].starts_with(is_name_char) {
+            let tag_len = rest.find('>')?;
+            if !rest[..tag_len].ends_with('/') {
+                depth += 1;
+            }
+            pos = at + tag_len + 1;
+        } else {
+            pos = at + 1;
+        }
+    }
+
+    None
+}
+
+/// Byte ranges of fenced code blocks and inline code spans
+fn code_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut fence: Option<(usize, char, usize)> = None;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let line_end = offset + line.len();
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        let run = marker.map_or(0, |c| trimmed.chars().take_while(|x| *x == c).count());
+
+        match fence {
+            Some((start, c, len)) => {
+                if marker == Some(c) && run >= len && trimmed[run..].trim().is_empty() {
+                    ranges.push(start..line_end);
+                    fence = None;
+                }
+            }
+            None if indent <= 3 && run >= 3 => {
+                fence = Some((offset, marker.expect("run implies a marker"), run));
+            }
+            None => ranges.extend(
+                inline_code_ranges(line)
+                    .into_iter()
+                    .map(|range| offset + range.start..offset + range.end),
+            ),
+        }
+
+        offset = line_end;
+    }
+
+    // An unclosed fence runs to the end of the document
+    if let Some((start, _, _)) = fence {
+        ranges.push(start..content.len());
+    }
+
+    ranges
+}
+
+/// Byte ranges of the inline code spans in a line
+fn inline_code_ranges(line: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = line[pos..].find('`') {
+        let start = pos + offset;
+        let run = line[start..].chars().take_while(|c| *c == '`').count();
+        let delimiter = &line[start..start + run];
+
+        // The span closes at the next run of exactly the same length
+        let mut search = start + run;
+        let mut end = None;
+        while let Some(offset) = line[search..].find(delimiter) {
+            let candidate = search + offset;
+            let candidate_run = line[candidate..].chars().take_while(|c| *c == '`').count();
+            if candidate_run == run {
+                end = Some(candidate + run);
+                break;
+            }
+            search = candidate + candidate_run;
+        }
+
+        match end {
+            Some(end) => {
+                ranges.push(start..end);
+                pos = end;
+            }
+            None => pos = start + run,
+        }
+    }
+
+    ranges
+}
+
+/// Whether a character can appear in a component name
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Position of the next non-whitespace character at or after `pos`
+fn skip_whitespace(content: &str, pos: usize) -> usize {
+    pos + content[pos..]
+        .find(|c: char| !c.is_whitespace())
+        .unwrap_or(content.len() - pos)
+}
@@ -0,0 +1,389 @@
+//! # Markdown Extensions
+//!
+//! Syntax that CommonMark doesn't cover, implemented as [`MarkdownTransform`]s
+//! that turn the paragraphs and block quotes pulldown-cmark produces into
+//! semantic HTML:
+//!
+//! - GitHub-style admonitions (`> [!NOTE]`), with the `admonitions` feature
+//! - `:::warning` containers, with the `containers` feature
+//! - Definition lists (`Term` followed by `: Definition`), with the
+//!   `definition-lists` feature
+//!
+//! Requires the `html` feature
+
+#[cfg(feature = "containers")]
+use pulldown_cmark::escape::escape_html;
+use pulldown_cmark::{CowStr, Event, Tag};
+
+use crate::transform::MarkdownTransform;
+
+/// Admonition kinds recognised after `[!` in a block quote, as on GitHub
+#[cfg(feature = "admonitions")]
+pub const ADMONITION_KINDS: &[&str] = &["note", "tip", "important", "warning", "caution"];
+
+/// Render `> [!NOTE]` block quotes as `<div class="admonition admonition-note">`
+///
+/// The marker is replaced by a `<p class="admonition-title">` holding the
+/// capitalised kind. Block quotes without a known marker are left alone.
+#[cfg(feature = "admonitions")]
+#[derive(Debug, Clone, Default)]
+pub struct Admonitions;
+
+#[cfg(feature = "admonitions")]
+impl MarkdownTransform for Admonitions {
+    fn name(&self) -> &str {
+        "admonitions"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut output = Vec::with_capacity(events.len());
+        let mut quote: Vec<Event<'a>> = Vec::new();
+        let mut depth = 0;
+
+        for event in events {
+            match event {
+                Event::Start(Tag::BlockQuote) => {
+                    if depth > 0 {
+                        quote.push(event);
+                    }
+                    depth += 1;
+                }
+                Event::End(Tag::BlockQuote) if depth > 0 => {
+                    depth -= 1;
+                    if depth > 0 {
+                        quote.push(event);
+                    } else {
+                        let inner = self.transform(std::mem::take(&mut quote));
+                        output.extend(self.render_quote(inner));
+                    }
+                }
+                event if depth > 0 => quote.push(event),
+                event => output.push(event),
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(feature = "admonitions")]
+impl Admonitions {
+    /// Render the contents of a block quote, as an admonition if it has a marker
+    fn render_quote<'a>(&self, inner: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut events = merge_text(inner);
+
+        let kind = match (events.first(), events.get(1)) {
+            (Some(Event::Start(Tag::Paragraph)), Some(Event::Text(text))) => admonition_kind(text),
+            _ => None,
+        };
+        let (kind, marker_len) = match kind {
+            Some(found) => found,
+            None => {
+                let mut output = vec![Event::Start(Tag::BlockQuote)];
+                output.extend(events);
+                output.push(Event::End(Tag::BlockQuote));
+                return output;
+            }
+        };
+
+        // Drop the marker, and the line break after it
+        let rest = match &events[1] {
+            Event::Text(text) => text[marker_len..].trim_start().to_string(),
+            _ => unreachable!("checked above"),
+        };
+        if rest.is_empty() {
+            events.remove(1);
+            if matches!(events.get(1), Some(Event::SoftBreak | Event::HardBreak)) {
+                events.remove(1);
+            }
+        } else {
+            events[1] = Event::Text(CowStr::from(rest));
+        }
+
+        // A marker on its own leaves an empty first paragraph
+        if matches!(events.get(1), Some(Event::End(Tag::Paragraph))) {
+            events.drain(..2);
+        }
+
+        let mut open = format!("<div class=\"admonition admonition-{}\">\n", kind);
+        open.push_str("<p class=\"admonition-title\">");
+        open.push_str(&capitalize(kind));
+        open.push_str("</p>\n");
+
+        let mut output = vec![Event::Html(CowStr::from(open))];
+        output.extend(events);
+        output.push(Event::Html(CowStr::Borrowed("</div>\n")));
+        output
+    }
+}
+
+/// Kind and length of a `[!KIND]` marker at the start of `text`
+#[cfg(feature = "admonitions")]
+fn admonition_kind(text: &str) -> Option<(&'static str, usize)> {
+    let marker = text.strip_prefix("[!")?;
+    let end = marker.find(']')?;
+    let name = marker[..end].to_lowercase();
+
+    ADMONITION_KINDS
+        .iter()
+        .find(|kind| **kind == name)
+        .map(|kind| (*kind, end + 3))
+}
+
+/// Render `:::kind Title` ... `:::` fences as `<div class="container container-kind">`
+///
+/// The optional title becomes a `<p class="container-title">`. Containers can
+/// nest, and markdown inside them is rendered as usual. Fence lines must start
+/// a paragraph line; unclosed containers are closed at the end of the document.
+#[cfg(feature = "containers")]
+#[derive(Debug, Clone, Default)]
+pub struct Containers;
+
+#[cfg(feature = "containers")]
+impl MarkdownTransform for Containers {
+    fn name(&self) -> &str {
+        "containers"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut output = Vec::with_capacity(events.len());
+        let mut open = 0;
+
+        for block in paragraphs(events) {
+            let lines = match block {
+                Block::Paragraph(inner) => paragraph_lines(merge_text(inner)),
+                Block::Event(event) => {
+                    output.push(event);
+                    continue;
+                }
+            };
+
+            let mut pending: Vec<Event<'a>> = Vec::new();
+            for line in lines {
+                match container_fence(&line) {
+                    Some(Fence::Open { kind, title }) => {
+                        flush_paragraph(&mut output, &mut pending);
+                        output.push(Event::Html(CowStr::from(container_open(&kind, title.as_deref()))));
+                        open += 1;
+                    }
+                    Some(Fence::Close) if open > 0 => {
+                        flush_paragraph(&mut output, &mut pending);
+                        output.push(Event::Html(CowStr::Borrowed("</div>\n")));
+                        open -= 1;
+                    }
+                    _ => pending.extend(line),
+                }
+            }
+            flush_paragraph(&mut output, &mut pending);
+        }
+
+        for _ in 0..open {
+            output.push(Event::Html(CowStr::Borrowed("</div>\n")));
+        }
+
+        output
+    }
+}
+
+/// A `:::` fence line
+#[cfg(feature = "containers")]
+enum Fence {
+    Open { kind: String, title: Option<String> },
+    Close,
+}
+
+/// Parse a paragraph line as a container fence
+#[cfg(feature = "containers")]
+fn container_fence(line: &[Event]) -> Option<Fence> {
+    let text = match line {
+        [Event::Text(text)] | [Event::Text(text), Event::SoftBreak | Event::HardBreak] => text.trim(),
+        _ => return None,
+    };
+    let rest = text.strip_prefix(":::")?.trim();
+
+    if rest.is_empty() {
+        return Some(Fence::Close);
+    }
+
+    let (kind, title) = match rest.split_once(char::is_whitespace) {
+        Some((kind, title)) => (kind, Some(title.trim().to_string())),
+        None => (rest, None),
+    };
+    if !kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return None;
+    }
+
+    Some(Fence::Open {
+        kind: kind.to_lowercase(),
+        title,
+    })
+}
+
+/// Opening HTML for a container
+#[cfg(feature = "containers")]
+fn container_open(kind: &str, title: Option<&str>) -> String {
+    let mut open = format!("<div class=\"container container-{}\">\n", kind);
+    if let Some(title) = title {
+        open.push_str("<p class=\"container-title\">");
+        escape_html(&mut open, title).expect("writing to a String cannot fail");
+        open.push_str("</p>\n");
+    }
+    open
+}
+
+/// Render paragraphs of `Term` / `: Definition` lines as `<dl>` lists
+///
+/// Only the compact form is recognised: the first line of the paragraph is a
+/// term and each definition is on its own line, without blank lines between.
+#[cfg(feature = "definition-lists")]
+#[derive(Debug, Clone, Default)]
+pub struct DefinitionLists;
+
+#[cfg(feature = "definition-lists")]
+impl MarkdownTransform for DefinitionLists {
+    fn name(&self) -> &str {
+        "definition-lists"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut output = Vec::with_capacity(events.len());
+
+        for block in paragraphs(events) {
+            match block {
+                Block::Paragraph(inner) => output.extend(definition_list(merge_text(inner))),
+                Block::Event(event) => output.push(event),
+            }
+        }
+
+        output
+    }
+}
+
+/// Render a paragraph as a definition list, or unchanged if it isn't one
+#[cfg(feature = "definition-lists")]
+fn definition_list(inner: Vec<Event>) -> Vec<Event> {
+    let lines = paragraph_lines(inner);
+    let is_definition = |line: &[Event]| matches!(line.first(), Some(Event::Text(text)) if text.starts_with(": "));
+
+    if lines.len() < 2 || is_definition(&lines[0]) || !lines[1..].iter().any(|line| is_definition(line)) {
+        let mut output = vec![Event::Start(Tag::Paragraph)];
+        output.extend(lines.into_iter().flatten());
+        output.push(Event::End(Tag::Paragraph));
+        return output;
+    }
+
+    let mut output = vec![Event::Html(CowStr::Borrowed("<dl>\n"))];
+    for mut line in lines {
+        if matches!(line.last(), Some(Event::SoftBreak | Event::HardBreak)) {
+            line.pop();
+        }
+
+        let tag = if is_definition(&line) {
+            if let Some(Event::Text(text)) = line.first_mut() {
+                *text = CowStr::from(text[2..].trim_start().to_string());
+            }
+            "dd"
+        } else {
+            "dt"
+        };
+
+        output.push(Event::Html(CowStr::from(format!("<{}>", tag))));
+        output.extend(line);
+        output.push(Event::Html(CowStr::from(format!("</{}>\n", tag))));
+    }
+    output.push(Event::Html(CowStr::Borrowed("</dl>\n")));
+
+    output
+}
+
+/// A top-level paragraph's inline events, or any other event
+#[cfg(any(feature = "containers", feature = "definition-lists"))]
+enum Block<'a> {
+    Paragraph(Vec<Event<'a>>),
+    Event(Event<'a>),
+}
+
+/// Group the inline events of each paragraph
+#[cfg(any(feature = "containers", feature = "definition-lists"))]
+fn paragraphs(events: Vec<Event>) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Option<Vec<Event>> = None;
+
+    for event in events {
+        match event {
+            Event::Start(Tag::Paragraph) => paragraph = Some(Vec::new()),
+            Event::End(Tag::Paragraph) => {
+                if let Some(inner) = paragraph.take() {
+                    blocks.push(Block::Paragraph(inner));
+                }
+            }
+            event => match paragraph.as_mut() {
+                Some(inner) => inner.push(event),
+                None => blocks.push(Block::Event(event)),
+            },
+        }
+    }
+
+    blocks
+}
+
+/// Split a paragraph's inline events into lines, each keeping its trailing break
+#[cfg(any(feature = "containers", feature = "definition-lists"))]
+fn paragraph_lines(inner: Vec<Event>) -> Vec<Vec<Event>> {
+    let mut lines = vec![Vec::new()];
+
+    for event in inner {
+        let is_break = matches!(event, Event::SoftBreak | Event::HardBreak);
+        lines.last_mut().expect("at least one line").push(event);
+        if is_break {
+            lines.push(Vec::new());
+        }
+    }
+
+    if lines.last().is_some_and(Vec::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Emit the pending lines as a paragraph, if there are any
+#[cfg(feature = "containers")]
+fn flush_paragraph<'a>(output: &mut Vec<Event<'a>>, pending: &mut Vec<Event<'a>>) {
+    if matches!(pending.last(), Some(Event::SoftBreak | Event::HardBreak)) {
+        pending.pop();
+    }
+    if pending.is_empty() {
+        return;
+    }
+
+    output.push(Event::Start(Tag::Paragraph));
+    output.append(pending);
+    output.push(Event::End(Tag::Paragraph));
+}
+
+/// Join adjacent text events, which pulldown-cmark splits at special characters
+#[cfg(any(feature = "admonitions", feature = "containers", feature = "definition-lists"))]
+fn merge_text(events: Vec<Event>) -> Vec<Event> {
+    let mut merged: Vec<Event> = Vec::with_capacity(events.len());
+
+    for event in events {
+        match (merged.last_mut(), event) {
+            (Some(Event::Text(previous)), Event::Text(text)) => {
+                *previous = CowStr::from(format!("{}{}", previous, text));
+            }
+            (_, event) => merged.push(event),
+        }
+    }
+
+    merged
+}
+
+/// Uppercase the first letter of a word
+#[cfg(feature = "admonitions")]
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -7,13 +7,23 @@
 //! - Frontmatter extraction and generation (requires 'frontmatter' feature)
 //! - Markdown to HTML conversion (requires 'html' feature)
 //! - Word count and reading time calculation
+//! - Markdown-aware word count analysis (requires 'html' feature)
 //! - Paragraph extraction (requires 'html' feature)
+//! - Summaries from the opening sentences (requires 'html' feature)
+//! - Event transforms applied before HTML rendering (requires 'html' feature)
+//! - Locale-aware smart quotes, dashes and ellipses (requires 'html' feature)
+//! - Admonitions, containers and definition lists (require the matching features)
+//! - Finding and replacing MDX components such as `<YouTube id="..." />`
 //!
 //! ## Feature Flags
 //!
 //! - `html`: Enables HTML conversion functionality (enabled by default)
 //! - `frontmatter`: Enables frontmatter handling (enabled by default)
 //! - `syntax-highlight`: Enables syntax highlighting (disabled by default)
+//! - `extensions`: Enables `admonitions`, `containers` and `definition-lists` (enabled by default)
+//! - `admonitions`: GitHub-style `> [!NOTE]` admonitions
+//! - `containers`: `:::warning` containers
+//! - `definition-lists`: `Term` / `: Definition` lists
 //!
 //! ## Example
 //!
@@ -56,6 +66,46 @@ use pulldown_cmark::{html, Event, Options, Parser, Tag};
 #[cfg(feature = "frontmatter")]
 use regex::Regex;
 
+pub mod components;
+
+pub use components::{find_components, replace_components, Component};
+
+#[cfg(feature = "html")]
+pub mod analysis;
+
+#[cfg(feature = "html")]
+pub use analysis::{analyze_words, calculate_reading_time_with_options, prose_text, ReadingTimeOptions, WordCountAnalysis};
+
+#[cfg(feature = "html")]
+pub mod summary;
+
+#[cfg(feature = "html")]
+pub use summary::{generate_summary, SummaryOptions};
+
+#[cfg(feature = "html")]
+pub mod transform;
+
+#[cfg(feature = "html")]
+pub mod typography;
+
+#[cfg(feature = "html")]
+pub use typography::{QuoteStyle, SmartTypography};
+
+#[cfg(any(feature = "admonitions", feature = "containers", feature = "definition-lists"))]
+pub mod extensions;
+
+#[cfg(feature = "admonitions")]
+pub use extensions::Admonitions;
+
+#[cfg(feature = "containers")]
+pub use extensions::Containers;
+
+#[cfg(feature = "definition-lists")]
+pub use extensions::DefinitionLists;
+
+#[cfg(feature = "html")]
+pub use transform::{ExternalLinks, HeadingAnchors, MarkdownTransform, RelativeImageUrls, ResponsiveTables, TransformPipeline};
+
 /// Extract frontmatter and content from a markdown file
 ///
 /// Requires the `frontmatter` feature
@@ -101,6 +151,9 @@ pub fn extract_frontmatter(content: &str) -> Result<(serde_yaml::Value, String)>
 }
 
 /// Calculate word count from markdown content
+///
+/// This counts every whitespace-separated token; use [`analyze_words`] to
+/// exclude code, HTML and URLs.
 pub fn calculate_word_count(content: &str) -> usize {
     content.split_whitespace().count()
 }
@@ -246,7 +299,7 @@ This is a test paragraph."#;
 
         let (frontmatter, markdown) = result.unwrap();
         assert_eq!(frontmatter.title, "Test Title");
-        assert_eq!(frontmatter.published_at, Some("2023-01-01".to_string()));
+        assert_eq!(frontmatter.published_at, Some("2023-01-01".parse().unwrap()));
         assert_eq!(frontmatter.description, Some("Test Description".to_string()));
         assert_eq!(frontmatter.tags, Some(vec!["test".to_string(), "markdown".to_string()]));
         assert_eq!(frontmatter.is_draft, Some(true));
@@ -0,0 +1,116 @@
+//! # Summaries
+//!
+//! Generates a short plain-text summary from the opening sentences of an
+//! article, for listings and feeds when the frontmatter doesn't provide one.
+//!
+//! Requires the `html` feature
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use crate::analysis::strip_frontmatter;
+
+/// Options for generating a summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryOptions {
+    /// Number of sentences to take from the start of the content
+    pub max_sentences: usize,
+    /// Maximum length in characters; longer summaries are cut at a word boundary
+    pub max_length: usize,
+}
+
+impl Default for SummaryOptions {
+    fn default() -> Self {
+        Self {
+            max_sentences: 2,
+            max_length: 300,
+        }
+    }
+}
+
+/// Generate a plain-text summary from the first sentences of markdown content
+///
+/// Only paragraph text is used: headings, code blocks, raw HTML and images are
+/// skipped, and inline formatting is removed. Returns `None` if the content
+/// has no paragraph text.
+pub fn generate_summary(content: &str, options: &SummaryOptions) -> Option<String> {
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    let mut in_image = false;
+
+    for event in Parser::new_ext(strip_frontmatter(content), Options::all()) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(Tag::Paragraph) => {
+                in_paragraph = false;
+                text.push(' ');
+                if sentence_end(&text, options.max_sentences).is_some() {
+                    break;
+                }
+            }
+            Event::Start(Tag::Image(..)) => in_image = true,
+            Event::End(Tag::Image(..)) => in_image = false,
+            Event::Text(value) | Event::Code(value) if in_paragraph && !in_image => text.push_str(&value),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() || options.max_sentences == 0 {
+        return None;
+    }
+
+    let summary = match sentence_end(&text, options.max_sentences) {
+        Some(end) => &text[..end],
+        None => text.as_str(),
+    };
+
+    Some(truncate_at_word(summary, options.max_length))
+}
+
+/// Byte offset just after the `count`th sentence, if the text has that many
+///
+/// A sentence ends at `.`, `!` or `?` followed by whitespace and a capital
+/// letter (or the end of the text), so abbreviations like "e.g." don't end one.
+fn sentence_end(text: &str, count: usize) -> Option<usize> {
+    let mut sentences = 0;
+
+    for (index, c) in text.char_indices() {
+        let end = index + c.len_utf8();
+        let is_boundary = match c {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' => {
+                let rest = &text[end..];
+                let next = rest.trim_start().chars().next();
+                rest.is_empty()
+                    || (rest.starts_with(char::is_whitespace)
+                        && !next.is_some_and(char::is_lowercase))
+            }
+            _ => false,
+        };
+
+        if is_boundary {
+            sentences += 1;
+            if sentences == count {
+                return Some(end);
+            }
+        }
+    }
+
+    None
+}
+
+/// Cut text to at most `max_length` characters at a word boundary, adding an ellipsis
+fn truncate_at_word(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+
+    let cut: String = text.chars().take(max_length.saturating_sub(1)).collect();
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(index) => &cut[..index],
+        None => cut.as_str(),
+    };
+
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_whitespace() || c == ',' || c == ';' || c == ':'))
+}
@@ -0,0 +1,393 @@
+//! # Markdown Transforms
+//!
+//! Transforms rewrite the pulldown-cmark event stream between parsing and
+//! HTML rendering, so the output can be adjusted (heading anchors, image
+//! paths, link attributes) without post-processing the rendered HTML.
+//!
+//! Requires the `html` feature
+
+use std::collections::HashMap;
+
+use pulldown_cmark::escape::{escape_href, escape_html};
+use pulldown_cmark::{html, CowStr, Event, HeadingLevel, Options, Parser, Tag};
+
+use common_models::LinkPolicyConfig;
+
+/// A rewrite applied to the events of a markdown document
+pub trait MarkdownTransform: Send + Sync {
+    /// Name of the transform, used in diagnostics
+    fn name(&self) -> &str;
+
+    /// Rewrite the events of a whole document
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>>;
+}
+
+/// An ordered list of transforms applied before rendering HTML
+pub struct TransformPipeline {
+    options: Options,
+    transforms: Vec<Box<dyn MarkdownTransform>>,
+}
+
+impl Default for TransformPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransformPipeline {
+    /// Create an empty pipeline that parses with the extensions used for content
+    pub fn new() -> Self {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_TASKLISTS);
+        options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+
+        Self {
+            options,
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Use different parser options
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add a transform to the end of the pipeline
+    pub fn with<T: MarkdownTransform + 'static>(mut self, transform: T) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Add a boxed transform to the end of the pipeline
+    pub fn push(&mut self, transform: Box<dyn MarkdownTransform>) {
+        self.transforms.push(transform);
+    }
+
+    /// Names of the transforms, in the order they are applied
+    pub fn names(&self) -> Vec<&str> {
+        self.transforms.iter().map(|transform| transform.name()).collect()
+    }
+
+    /// Apply every transform to the events, in order
+    pub fn apply<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        self.transforms
+            .iter()
+            .fold(events, |events, transform| transform.transform(events))
+    }
+
+    /// Parse markdown, apply the transforms and render the result as HTML
+    pub fn render_html(&self, content: &str) -> String {
+        let events = self.apply(Parser::new_ext(content, self.options).collect());
+
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, events.into_iter());
+        html_output
+    }
+}
+
+/// Give every heading an `id` derived from its text
+///
+/// Headings that already have an id (`# Title {#custom}`) keep it. Repeated
+/// ids get a numeric suffix, as on GitHub.
+#[derive(Debug, Clone, Default)]
+pub struct HeadingAnchors;
+
+impl MarkdownTransform for HeadingAnchors {
+    fn name(&self) -> &str {
+        "heading-anchors"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut output = Vec::with_capacity(events.len());
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut heading: Option<OpenHeading<'a>> = None;
+
+        for event in events {
+            match event {
+                Event::Start(Tag::Heading(level, id, classes)) => {
+                    heading = Some(OpenHeading {
+                        level,
+                        id,
+                        classes,
+                        inner: Vec::new(),
+                    });
+                }
+                Event::End(Tag::Heading(..)) if heading.is_some() => {
+                    let OpenHeading { level, id, classes, inner } = heading.take().expect("heading is open");
+                    let anchor = match id {
+                        Some(id) => id.to_string(),
+                        None => unique_anchor(&mut seen, &anchor_slug(&heading_text(&inner))),
+                    };
+
+                    let mut open = format!("<{} id=\"", level);
+                    escape_html(&mut open, &anchor).expect("writing to a String cannot fail");
+                    open.push('"');
+                    if !classes.is_empty() {
+                        open.push_str(" class=\"");
+                        escape_html(&mut open, &classes.join(" ")).expect("writing to a String cannot fail");
+                        open.push('"');
+                    }
+                    open.push('>');
+
+                    output.push(Event::Html(CowStr::from(open)));
+                    output.extend(inner);
+                    output.push(Event::Html(CowStr::from(format!("</{}>\n", level))));
+                }
+                event => match heading.as_mut() {
+                    Some(open) => open.inner.push(event),
+                    None => output.push(event),
+                },
+            }
+        }
+
+        output
+    }
+}
+
+/// A heading whose closing tag hasn't been reached yet
+struct OpenHeading<'a> {
+    level: HeadingLevel,
+    id: Option<&'a str>,
+    classes: Vec<&'a str>,
+    inner: Vec<Event<'a>>,
+}
+
+/// Plain text of a heading's inline events
+fn heading_text(events: &[Event]) -> String {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Lowercase the text and join its words with hyphens
+fn anchor_slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Suffix an anchor with a counter if it has already been used
+fn unique_anchor(seen: &mut HashMap<String, usize>, slug: &str) -> String {
+    let slug = if slug.is_empty() { "section" } else { slug };
+    let count = seen.entry(slug.to_string()).or_insert(0);
+    *count += 1;
+
+    if *count == 1 {
+        slug.to_string()
+    } else {
+        format!("{}-{}", slug, *count - 1)
+    }
+}
+
+/// Rewrite relative image URLs to point into the build output
+#[derive(Debug, Clone)]
+pub struct RelativeImageUrls {
+    base_url: String,
+}
+
+impl RelativeImageUrls {
+    /// Resolve relative image URLs against `base_url` (e.g. `/images/blog/my-post`)
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Resolve a relative URL, leaving absolute, root-relative and data URLs alone
+    fn resolve<'a>(&self, url: CowStr<'a>) -> CowStr<'a> {
+        if !is_relative_url(&url) {
+            return url;
+        }
+
+        let path = url.trim_start_matches("./");
+        CowStr::from(format!("{}/{}", self.base_url, path))
+    }
+}
+
+impl MarkdownTransform for RelativeImageUrls {
+    fn name(&self) -> &str {
+        "relative-image-urls"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        events
+            .into_iter()
+            .map(|event| match event {
+                Event::Start(Tag::Image(link_type, url, title)) => {
+                    Event::Start(Tag::Image(link_type, self.resolve(url), title))
+                }
+                Event::End(Tag::Image(link_type, url, title)) => {
+                    Event::End(Tag::Image(link_type, self.resolve(url), title))
+                }
+                event => event,
+            })
+            .collect()
+    }
+}
+
+/// Whether a URL is relative to the current document
+fn is_relative_url(url: &str) -> bool {
+    !(url.is_empty()
+        || url.starts_with('/')
+        || url.starts_with('#')
+        || url.starts_with("data:")
+        || url.contains("://"))
+}
+
+/// Wrap tables in a `<div>` so they can scroll horizontally on small screens
+#[derive(Debug, Clone)]
+pub struct ResponsiveTables {
+    class: String,
+}
+
+impl Default for ResponsiveTables {
+    fn default() -> Self {
+        Self::new("table-responsive")
+    }
+}
+
+impl ResponsiveTables {
+    /// Wrap tables in a `<div>` with the given class
+    pub fn new(class: impl Into<String>) -> Self {
+        Self { class: class.into() }
+    }
+}
+
+impl MarkdownTransform for ResponsiveTables {
+    fn name(&self) -> &str {
+        "responsive-tables"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut output = Vec::with_capacity(events.len());
+
+        for event in events {
+            match event {
+                Event::Start(Tag::Table(_)) => {
+                    let mut open = String::from("<div class=\"");
+                    escape_html(&mut open, &self.class).expect("writing to a String cannot fail");
+                    open.push_str("\">\n");
+                    output.push(Event::Html(CowStr::from(open)));
+                    output.push(event);
+                }
+                Event::End(Tag::Table(_)) => {
+                    output.push(event);
+                    output.push(Event::Html(CowStr::Borrowed("</div>\n")));
+                }
+                event => output.push(event),
+            }
+        }
+
+        output
+    }
+}
+
+/// Open links to other sites in a new tab
+///
+/// Adds `target="_blank"` and `rel="noopener noreferrer"` to `http(s)` links
+/// whose host differs from the site's own host, and `nofollow` to links to
+/// the domains given to [`ExternalLinks::with_nofollow`].
+#[derive(Debug, Clone, Default)]
+pub struct ExternalLinks {
+    site_host: Option<String>,
+    nofollow_domains: Vec<String>,
+}
+
+impl ExternalLinks {
+    /// Treat links to the host of `site_url` as internal
+    pub fn new(site_url: Option<&str>) -> Self {
+        Self {
+            site_host: site_url.and_then(url_host).map(str::to_lowercase),
+            nofollow_domains: Vec::new(),
+        }
+    }
+
+    /// Mark links to these domains, or their subdomains, as `nofollow`
+    pub fn with_nofollow(mut self, domains: Vec<String>) -> Self {
+        self.nofollow_domains = domains;
+        self
+    }
+
+    /// Whether search engines are asked not to follow a link
+    fn is_nofollow(&self, url: &str) -> bool {
+        url_host(url).is_some_and(|host| LinkPolicyConfig::matches_domain(host, &self.nofollow_domains))
+    }
+
+    /// Whether a link leaves the site
+    fn is_external(&self, url: &str) -> bool {
+        match url_host(url) {
+            Some(host) => !matches!(
+                self.site_host.as_deref(),
+                Some(site_host) if host.eq_ignore_ascii_case(site_host)
+            ),
+            None => false,
+        }
+    }
+}
+
+impl MarkdownTransform for ExternalLinks {
+    fn name(&self) -> &str {
+        "external-links"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut output = Vec::with_capacity(events.len());
+        // Links can't nest, so one flag tracks whether the open link was rewritten
+        let mut rewritten = false;
+
+        for event in events {
+            match event {
+                Event::Start(Tag::Link(_, ref url, ref title)) if self.is_external(url) => {
+                    let mut open = String::from("<a href=\"");
+                    escape_href(&mut open, url).expect("writing to a String cannot fail");
+                    open.push('"');
+                    if !title.is_empty() {
+                        open.push_str(" title=\"");
+                        escape_html(&mut open, title).expect("writing to a String cannot fail");
+                        open.push('"');
+                    }
+                    open.push_str(" target=\"_blank\" rel=\"noopener noreferrer");
+                    if self.is_nofollow(url) {
+                        open.push_str(" nofollow");
+                    }
+                    open.push_str("\">");
+
+                    output.push(Event::Html(CowStr::from(open)));
+                    rewritten = true;
+                }
+                Event::End(Tag::Link(..)) if rewritten => {
+                    output.push(Event::Html(CowStr::Borrowed("</a>")));
+                    rewritten = false;
+                }
+                event => output.push(event),
+            }
+        }
+
+        output
+    }
+}
+
+/// Host of an absolute `http(s)` URL
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
@@ -0,0 +1,273 @@
+//! # Smart Typography
+//!
+//! [`SmartTypography`] replaces the typewriter punctuation of prose with its
+//! typographic forms:
+//!
+//! - straight quotes become curly quotes in the style of the locale, like
+//!   “…” in English, „…“ in German and « … » in French (see [`QuoteStyle`])
+//! - apostrophes become ’
+//! - `--` becomes an en dash and `---` an em dash
+//! - `...` becomes an ellipsis
+//! - the space between a number and a unit (`10 km`, `50 %`) becomes a
+//!   non-breaking space, so the two stay on one line
+//!
+//! French text also gets a narrow non-breaking space before `;`, `!` and `?`
+//! and inside guillemets, and a non-breaking space before `:`, where it has
+//! a space.
+//!
+//! Inline code, code blocks and raw HTML are left alone.
+//!
+//! Requires the `html` feature
+
+use pulldown_cmark::{CowStr, Event, Tag};
+
+use crate::transform::MarkdownTransform;
+
+const NO_BREAK_SPACE: char = '\u{a0}';
+const NARROW_NO_BREAK_SPACE: char = '\u{202f}';
+
+/// Units kept on the same line as the number before them
+const UNITS: &[&str] = &[
+    "%", "‰", "°C", "°F", "°", "€", "£", "km/h", "km", "cm", "mm", "µm", "nm", "m", "mi", "ft", "in", "kg", "mg", "g",
+    "lb", "oz", "ml", "mL", "l", "L", "ms", "min", "s", "h", "Hz", "kHz", "MHz", "GHz", "B", "kB", "KB", "MB", "GB",
+    "TB", "KiB", "MiB", "GiB", "TiB", "px", "pt", "em", "rem", "W", "kW", "kWh", "V", "mph",
+];
+
+/// The quotation marks of a language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteStyle {
+    pub double_open: &'static str,
+    pub double_close: &'static str,
+    pub single_open: &'static str,
+    pub single_close: &'static str,
+}
+
+impl QuoteStyle {
+    pub const ENGLISH: QuoteStyle = QuoteStyle::new("“", "”", "‘", "’");
+    pub const GERMAN: QuoteStyle = QuoteStyle::new("„", "“", "‚", "‘");
+    pub const SWISS: QuoteStyle = QuoteStyle::new("«", "»", "‹", "›");
+    pub const FRENCH: QuoteStyle = QuoteStyle::new("«\u{202f}", "\u{202f}»", "“", "”");
+    pub const GUILLEMETS: QuoteStyle = QuoteStyle::new("«", "»", "“", "”");
+    pub const RUSSIAN: QuoteStyle = QuoteStyle::new("«", "»", "„", "“");
+    pub const POLISH: QuoteStyle = QuoteStyle::new("„", "”", "«", "»");
+    pub const HUNGARIAN: QuoteStyle = QuoteStyle::new("„", "”", "»", "«");
+    pub const SWEDISH: QuoteStyle = QuoteStyle::new("”", "”", "’", "’");
+    pub const DANISH: QuoteStyle = QuoteStyle::new("»", "«", "›", "‹");
+    pub const NORWEGIAN: QuoteStyle = QuoteStyle::new("«", "»", "‘", "’");
+    pub const CORNER_BRACKETS: QuoteStyle = QuoteStyle::new("「", "」", "『", "』");
+
+    const fn new(
+        double_open: &'static str,
+        double_close: &'static str,
+        single_open: &'static str,
+        single_close: &'static str,
+    ) -> Self {
+        Self {
+            double_open,
+            double_close,
+            single_open,
+            single_close,
+        }
+    }
+
+    /// The quotes of a locale such as `de` or `pt-BR`; English quotes when the language isn't known
+    pub fn for_locale(locale: &str) -> Self {
+        let locale = locale.to_ascii_lowercase().replace('_', "-");
+        let (language, region) = locale.split_once('-').unwrap_or((&locale, ""));
+
+        match (language, region) {
+            ("de", "ch" | "li") => Self::SWISS,
+            ("de" | "cs" | "sk" | "sl" | "lt" | "et" | "is" | "bg" | "ka", _) => Self::GERMAN,
+            ("fr", _) => Self::FRENCH,
+            ("pt", "br") => Self::ENGLISH,
+            ("es" | "ca" | "it" | "pt" | "el", _) => Self::GUILLEMETS,
+            ("ru" | "uk" | "be", _) => Self::RUSSIAN,
+            ("pl" | "ro" | "hr", _) => Self::POLISH,
+            ("hu", _) => Self::HUNGARIAN,
+            ("sv" | "fi", _) => Self::SWEDISH,
+            ("da", _) => Self::DANISH,
+            ("nb" | "nn" | "no", _) => Self::NORWEGIAN,
+            ("ja", _) | ("zh", "tw" | "hk" | "mo" | "hant") => Self::CORNER_BRACKETS,
+            _ => Self::ENGLISH,
+        }
+    }
+}
+
+/// Curly quotes, dashes, ellipses and non-breaking spaces in prose
+#[derive(Debug, Clone)]
+pub struct SmartTypography {
+    quotes: QuoteStyle,
+    french_spacing: bool,
+}
+
+impl Default for SmartTypography {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl SmartTypography {
+    /// Typography for text in `locale`
+    pub fn new(locale: &str) -> Self {
+        let language = locale.split(['-', '_']).next().unwrap_or_default();
+        Self {
+            quotes: QuoteStyle::for_locale(locale),
+            french_spacing: language.eq_ignore_ascii_case("fr"),
+        }
+    }
+
+    /// Use different quotation marks
+    pub fn with_quotes(mut self, quotes: QuoteStyle) -> Self {
+        self.quotes = quotes;
+        self
+    }
+
+    /// Rewrite one text event, given what came before it and the character after it
+    fn convert(&self, text: &str, state: &mut QuoteState, after: Option<char>) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut output = String::with_capacity(text.len());
+        let mut index = 0;
+
+        while index < chars.len() {
+            let char = chars[index];
+            let prev = output.chars().next_back().or(state.prev);
+            let next = chars.get(index + 1).copied().or(after);
+            let run = chars[index..].iter().take_while(|&&other| other == char).count();
+
+            match char {
+                '"' => {
+                    if opens_quote(prev) && !next.is_none_or(char::is_whitespace) {
+                        output.push_str(self.quotes.double_open);
+                    } else {
+                        output.push_str(self.quotes.double_close);
+                    }
+                }
+                '\'' => {
+                    let is_word = |char: Option<char>| char.is_some_and(char::is_alphanumeric);
+                    if is_word(prev) && is_word(next) {
+                        output.push('’');
+                    } else if opens_quote(prev) {
+                        if next.is_some_and(|next| next.is_ascii_digit()) {
+                            // Abbreviated years, like '90s
+                            output.push('’');
+                        } else {
+                            state.single += 1;
+                            output.push_str(self.quotes.single_open);
+                        }
+                    } else if state.single > 0 {
+                        state.single -= 1;
+                        output.push_str(self.quotes.single_close);
+                    } else {
+                        output.push('’');
+                    }
+                }
+                '-' if run == 3 => output.push('—'),
+                '-' if run == 2 && prev != Some('<') && chars.get(index + 2).copied().or(after) != Some('>') => {
+                    output.push('–')
+                }
+                '.' if run == 3 => output.push('…'),
+                '-' | '.' => output.extend(&chars[index..index + run]),
+                ' ' => output.push(self.space(prev, next, &chars[index + 1..], after)),
+                char => output.push(char),
+            }
+
+            index += if matches!(char, '-' | '.') { run } else { 1 };
+        }
+
+        state.prev = output.chars().next_back().or(state.prev);
+        output
+    }
+
+    /// What a space between `prev` and `next` becomes
+    fn space(&self, prev: Option<char>, next: Option<char>, rest: &[char], after: Option<char>) -> char {
+        if self.french_spacing {
+            match (prev, next) {
+                (Some('«'), _) | (_, Some('»' | ';' | '!' | '?')) => return NARROW_NO_BREAK_SPACE,
+                (_, Some(':')) => return NO_BREAK_SPACE,
+                _ => {}
+            }
+        }
+        if prev.is_some_and(|prev| prev.is_ascii_digit()) && unit_follows(rest, after) {
+            return NO_BREAK_SPACE;
+        }
+        ' '
+    }
+}
+
+/// The last character written in the current block, and the single quotes still open in it
+///
+/// Closing single quotes look like apostrophes, so only the open ones tell them apart.
+#[derive(Debug, Default)]
+struct QuoteState {
+    prev: Option<char>,
+    single: usize,
+}
+
+impl MarkdownTransform for SmartTypography {
+    fn name(&self) -> &str {
+        "smart-typography"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let mut output = Vec::with_capacity(events.len());
+        let mut state = QuoteState::default();
+        let mut in_code_block = false;
+
+        for (index, event) in events.iter().enumerate() {
+            match event {
+                Event::Text(text) if !in_code_block => {
+                    let converted = self.convert(text, &mut state, next_char(&events[index + 1..]));
+                    if converted == text.as_ref() {
+                        output.push(event.clone());
+                    } else {
+                        output.push(Event::Text(CowStr::from(converted)));
+                    }
+                    continue;
+                }
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+                Event::Start(tag) | Event::End(tag) if !is_inline(tag) => state = QuoteState::default(),
+                Event::Code(code) => state.prev = code.chars().next_back().or(state.prev),
+                Event::SoftBreak | Event::HardBreak | Event::TaskListMarker(_) => state.prev = Some(' '),
+                Event::FootnoteReference(_) => state.prev = Some(']'),
+                _ => {}
+            }
+            output.push(event.clone());
+        }
+
+        output
+    }
+}
+
+/// Whether a quote after `prev` opens a quotation
+fn opens_quote(prev: Option<char>) -> bool {
+    match prev {
+        None => true,
+        Some(prev) => prev.is_whitespace() || "([{<‹«„“‘‚—–-/".contains(prev),
+    }
+}
+
+/// The first character of the text that follows, across inline markup
+fn next_char(events: &[Event]) -> Option<char> {
+    for event in events {
+        match event {
+            Event::Text(text) | Event::Code(text) => return text.chars().next(),
+            Event::Start(tag) | Event::End(tag) if is_inline(tag) => continue,
+            Event::Html(_) => continue,
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn is_inline(tag: &Tag) -> bool {
+    matches!(tag, Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..) | Tag::Image(..))
+}
+
+/// Whether `rest` starts with a unit that ends a word; `after` follows `rest`
+fn unit_follows(rest: &[char], after: Option<char>) -> bool {
+    UNITS.iter().any(|unit| {
+        let unit: Vec<char> = unit.chars().collect();
+        rest.starts_with(&unit) && !rest.get(unit.len()).copied().or(after).is_some_and(char::is_alphanumeric)
+    })
+}
@@ -1,2 +1,4 @@
 // Test modules
-mod property;
\ No newline at end of file
+mod property;
+// Unit tests
+mod unit;
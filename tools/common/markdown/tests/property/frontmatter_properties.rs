@@ -27,7 +27,8 @@ fn frontmatter_yaml_strategy() -> impl Strategy<Value = String> {
 /// Generate valid frontmatter with common fields
 fn frontmatter_with_common_fields_strategy() -> impl Strategy<Value = String> {
     let title_strategy = proptest::string::string_regex("[A-Za-z0-9 ]{3,50}").unwrap();
-    let date_strategy = proptest::string::string_regex("[0-9]{4}-[0-9]{2}-[0-9]{2}").unwrap();
+    let date_strategy = (2000..2030u32, 1..13u32, 1..29u32)
+        .prop_map(|(y, m, d)| format!("{:04}-{:02}-{:02}", y, m, d));
     let tag_strategy = proptest::string::string_regex("[a-z-]{3,15}").unwrap();
     let tags_count = 0..5;
 
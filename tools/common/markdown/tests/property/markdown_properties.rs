@@ -92,7 +92,7 @@ proptest! {
 
         // Generate a new document with the same frontmatter and content
         let title = frontmatter.title.clone();
-        let published_at = frontmatter.published_at.clone();
+        let published_at = frontmatter.published_at.as_ref().map(ToString::to_string);
         let tags_vec = frontmatter.tags.clone().unwrap_or_default();
         let tags = tags_vec.iter().map(|s| s.as_str()).collect();
         let description = frontmatter.description.clone();
@@ -0,0 +1,57 @@
+//! Tests for markdown-aware word counting
+
+use common_markdown::analysis::{analyze_words, calculate_reading_time_with_options, ReadingTimeOptions};
+
+#[test]
+fn test_analyze_words_excludes_code_html_and_urls() {
+    let content = r#"---
+title: "Leftover frontmatter"
+---
+
+# A *heading*
+
+Some **bold**text and a [link](https://example.com/a/very/long/path) to https://example.com.
+
+<div class="note">raw html</div>
+
+![alt text here](image.png)
+
+```rust
+fn main() { println!("hello"); }
+```
+
+Run `cargo test` now.
+"#;
+
+    let analysis = analyze_words(content);
+    // A heading / Some boldtext and a link to / Run now
+    assert_eq!(analysis.words, 10);
+    assert_eq!(analysis.cjk_characters, 0);
+    assert_eq!(analysis.code_words, 5);
+}
+
+#[test]
+fn test_analyze_words_counts_cjk_characters() {
+    let analysis = analyze_words("日本語の文章です。 Mixed with English 中文\n\n한국어 문장");
+
+    assert_eq!(analysis.cjk_characters, 10);
+    assert_eq!(analysis.words, 5);
+    assert_eq!(analysis.word_count(), 15);
+}
+
+#[test]
+fn test_calculate_reading_time_with_options() {
+    let prose = "word ".repeat(300);
+    let content = format!("{}\n\n```\n{}\n```\n", prose, "code ".repeat(300));
+
+    let default = ReadingTimeOptions::default();
+    assert_eq!(calculate_reading_time_with_options(&content, &default), 2);
+
+    let with_code = ReadingTimeOptions { include_code: true, ..default.clone() };
+    assert_eq!(calculate_reading_time_with_options(&content, &with_code), 3);
+
+    let fast = ReadingTimeOptions { words_per_minute: 400, ..default };
+    assert_eq!(calculate_reading_time_with_options(&content, &fast), 1);
+
+    assert_eq!(calculate_reading_time_with_options("", &ReadingTimeOptions::default()), 1);
+}
@@ -0,0 +1,44 @@
+//! Tests for finding MDX components in markdown
+
+use common_markdown::components::{find_components, replace_components};
+
+#[test]
+fn test_find_self_closing_and_paired_components() {
+    let content = "Intro\n\n<YouTube id=\"abc\" start={30} autoplay />\n\n<Aside title='Note'>Some *markdown* <Aside>inner</Aside></Aside>\n\n<div>html</div>\n";
+
+    let components = find_components(content);
+    assert_eq!(components.len(), 2);
+
+    let video = &components[0];
+    assert_eq!(video.name, "YouTube");
+    assert_eq!(video.line, 3);
+    assert_eq!(video.attributes["id"], "abc");
+    assert_eq!(video.attributes["start"], "30");
+    assert_eq!(video.attributes["autoplay"], "true");
+    assert_eq!(video.children, None);
+    assert_eq!(&content[video.range.clone()], "<YouTube id=\"abc\" start={30} autoplay />");
+
+    let aside = &components[1];
+    assert_eq!(aside.attributes["title"], "Note");
+    assert_eq!(aside.children.as_deref(), Some("Some *markdown* <Aside>inner</Aside>"));
+}
+
+#[test]
+fn test_components_in_code_are_ignored() {
+    let content = "```mdx\n<YouTube id=\"a\" />\n```\n\nUse `<Tweet id=\"1\" />` or <Tweet id={\"2\"} />.\n";
+
+    let components = find_components(content);
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].name, "Tweet");
+    assert_eq!(components[0].attributes["id"], "2");
+    assert_eq!(components[0].line, 5);
+}
+
+#[test]
+fn test_replace_components() {
+    let replaced = replace_components("a <Br /> b <Em>c</Em> d", |component| {
+        component.children.clone().unwrap_or_else(|| "|".to_string())
+    });
+
+    assert_eq!(replaced, "a | b c d");
+}
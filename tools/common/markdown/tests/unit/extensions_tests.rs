@@ -0,0 +1,58 @@
+//! Tests for the admonition, container and definition list extensions
+
+use common_markdown::extensions::{Admonitions, Containers, DefinitionLists};
+use common_markdown::transform::TransformPipeline;
+
+#[test]
+fn test_admonitions_render_with_title_and_content() {
+    let html = TransformPipeline::new()
+        .with(Admonitions)
+        .render_html("> [!WARNING]\n> Back up **first**.\n\n> Just a quote\n");
+
+    assert!(html.starts_with(
+        "<div class=\"admonition admonition-warning\">\n<p class=\"admonition-title\">Warning</p>\n<p>Back up <strong>first</strong>.</p>\n</div>\n"
+    ));
+    assert!(html.contains("<blockquote>\n<p>Just a quote</p>\n</blockquote>"));
+}
+
+#[test]
+fn test_unknown_admonition_kind_stays_a_quote() {
+    let html = TransformPipeline::new().with(Admonitions).render_html("> [!SHRUG]\n> Maybe\n");
+
+    assert!(html.starts_with("<blockquote>"));
+    assert!(html.contains("[!SHRUG]"));
+}
+
+#[test]
+fn test_containers_wrap_markdown_and_nest() {
+    let html = TransformPipeline::new().with(Containers).render_html(
+        ":::warning Mind the gap\nFirst line.\n\n- item\n\n:::tip\nNested\n:::\n:::\n\nAfter\n",
+    );
+
+    assert_eq!(
+        html,
+        "<div class=\"container container-warning\">\n<p class=\"container-title\">Mind the gap</p>\n\
+         <p>First line.</p>\n<ul>\n<li>item</li>\n</ul>\n\
+         <div class=\"container container-tip\">\n<p>Nested</p>\n</div>\n</div>\n<p>After</p>\n"
+    );
+}
+
+#[test]
+fn test_stray_container_close_is_left_as_text() {
+    let html = TransformPipeline::new().with(Containers).render_html(":::\n");
+
+    assert_eq!(html, "<p>:::</p>\n");
+}
+
+#[test]
+fn test_definition_lists() {
+    let html = TransformPipeline::new()
+        .with(DefinitionLists)
+        .render_html("Slug\n: The *URL* name of a post\nTopic\n: A section of the site\n\nPlain paragraph\n");
+
+    assert_eq!(
+        html,
+        "<dl>\n<dt>Slug</dt>\n<dd>The <em>URL</em> name of a post</dd>\n<dt>Topic</dt>\n<dd>A section of the site</dd>\n</dl>\n\
+         <p>Plain paragraph</p>\n"
+    );
+}
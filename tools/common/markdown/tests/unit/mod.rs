@@ -0,0 +1,8 @@
+//! Unit tests for the markdown library
+
+mod analysis_tests;
+mod transform_tests;
+mod extensions_tests;
+mod components_tests;
+mod summary_tests;
+mod typography_tests;
@@ -0,0 +1,41 @@
+//! Tests for summary generation
+
+use common_markdown::summary::{generate_summary, SummaryOptions};
+
+#[test]
+fn test_summary_takes_first_sentences_without_formatting() {
+    let content = r#"---
+title: "Ignored"
+---
+
+# Heading is skipped
+
+![A photo](photo.jpg)
+
+Habits are **small** things. They add up over [years](https://example.com), e.g. in `code`. Nobody sees this.
+
+```
+ignored();
+```
+"#;
+
+    let summary = generate_summary(content, &SummaryOptions::default());
+    assert_eq!(
+        summary.as_deref(),
+        Some("Habits are small things. They add up over years, e.g. in code.")
+    );
+}
+
+#[test]
+fn test_summary_spans_paragraphs_and_truncates_at_words() {
+    let options = SummaryOptions {
+        max_sentences: 3,
+        max_length: 30,
+    };
+
+    assert_eq!(
+        generate_summary("One.\n\nTwo is longer than the limit allows.", &options).as_deref(),
+        Some("One. Two is longer than the…")
+    );
+    assert_eq!(generate_summary("# Only a heading\n", &options), None);
+}
@@ -0,0 +1,89 @@
+//! Tests for the markdown transform pipeline
+
+use common_markdown::transform::{
+    ExternalLinks, HeadingAnchors, MarkdownTransform, RelativeImageUrls, ResponsiveTables, TransformPipeline,
+};
+use pulldown_cmark::{CowStr, Event};
+
+#[test]
+fn test_heading_anchors_are_unique_and_keep_custom_ids() {
+    let html = TransformPipeline::new()
+        .with(HeadingAnchors)
+        .render_html("# Hello, *World*!\n\n## Setup\n\n## Setup\n\n### Custom {#mine}\n");
+
+    assert!(html.contains("<h1 id=\"hello-world\">Hello, <em>World</em>!</h1>"));
+    assert!(html.contains("<h2 id=\"setup\">Setup</h2>"));
+    assert!(html.contains("<h2 id=\"setup-1\">Setup</h2>"));
+    assert!(html.contains("<h3 id=\"mine\">Custom</h3>"));
+}
+
+#[test]
+fn test_relative_image_urls_point_at_build_output() {
+    let html = TransformPipeline::new()
+        .with(RelativeImageUrls::new("/images/blog/my-post/"))
+        .render_html("![a](./hero.jpg) ![b](/static/logo.png) ![c](https://cdn.example.com/x.png)");
+
+    assert!(html.contains("src=\"/images/blog/my-post/hero.jpg\""));
+    assert!(html.contains("src=\"/static/logo.png\""));
+    assert!(html.contains("src=\"https://cdn.example.com/x.png\""));
+}
+
+#[test]
+fn test_responsive_tables_are_wrapped() {
+    let html = TransformPipeline::new()
+        .with(ResponsiveTables::default())
+        .render_html("| a | b |\n|---|---|\n| 1 | 2 |\n");
+
+    assert!(html.starts_with("<div class=\"table-responsive\">\n<table>"));
+    assert!(html.trim_end().ends_with("</table>\n</div>"));
+}
+
+#[test]
+fn test_external_links_open_in_new_tab() {
+    let html = TransformPipeline::new()
+        .with(ExternalLinks::new(Some("https://www.example.com")))
+        .render_html("[out](https://other.org/?a=1&b=2 \"Other\") [in](https://WWW.example.com/post) [rel](/post)");
+
+    assert!(html.contains(
+        "<a href=\"https://other.org/?a=1&amp;b=2\" title=\"Other\" target=\"_blank\" rel=\"noopener noreferrer\">out</a>"
+    ));
+    assert!(html.contains("<a href=\"https://WWW.example.com/post\">in</a>"));
+    assert!(html.contains("<a href=\"/post\">rel</a>"));
+}
+
+#[test]
+fn test_external_links_add_nofollow_for_configured_domains() {
+    let html = TransformPipeline::new()
+        .with(ExternalLinks::new(None).with_nofollow(vec!["sponsor.example".to_string()]))
+        .render_html("[a](https://shop.Sponsor.example/x) [b](https://notsponsor.example/) [c](https://sponsor.example)");
+
+    assert!(html.contains("<a href=\"https://shop.Sponsor.example/x\" target=\"_blank\" rel=\"noopener noreferrer nofollow\">a</a>"));
+    assert!(html.contains("<a href=\"https://notsponsor.example/\" target=\"_blank\" rel=\"noopener noreferrer\">b</a>"));
+    assert!(html.contains("rel=\"noopener noreferrer nofollow\">c</a>"));
+}
+
+struct Shout;
+
+impl MarkdownTransform for Shout {
+    fn name(&self) -> &str {
+        "shout"
+    }
+
+    fn transform<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        events
+            .into_iter()
+            .map(|event| match event {
+                Event::Text(text) => Event::Text(CowStr::from(text.to_uppercase())),
+                event => event,
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_pipeline_applies_custom_transforms_in_order() {
+    let pipeline = TransformPipeline::new().with(Shout).with(HeadingAnchors);
+
+    assert_eq!(pipeline.names(), vec!["shout", "heading-anchors"]);
+    assert_eq!(pipeline.render_html("# Quiet title"), "<h1 id=\"quiet-title\">QUIET TITLE</h1>\n");
+}
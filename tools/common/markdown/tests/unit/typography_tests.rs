@@ -0,0 +1,47 @@
+//! Tests for the smart typography transform
+
+use common_markdown::typography::{QuoteStyle, SmartTypography};
+use common_markdown::TransformPipeline;
+
+fn render(locale: &str, markdown: &str) -> String {
+    TransformPipeline::new().with(SmartTypography::new(locale)).render_html(markdown)
+}
+
+#[test]
+fn test_smart_typography_replaces_quotes_dashes_and_ellipses() {
+    let html = render(
+        "en",
+        "\"Don't,\" she said -- 'it's *the* \"best\"' --- in the '90s... and `\"code\"` <!-- kept -->\n\n```\n\"raw\" -- ...\n```\n",
+    );
+
+    assert_eq!(
+        html,
+        "<p>“Don’t,” she said – ‘it’s <em>the</em> “best”’ — in the ’90s… and <code>&quot;code&quot;</code> <!-- kept --></p>\n<pre><code>&quot;raw&quot; -- ...\n</code></pre>\n"
+    );
+}
+
+#[test]
+fn test_smart_typography_quotes_across_markup() {
+    let html = render("en", "\"*Emphasis*\" and \"[a link](/x)\"");
+
+    assert_eq!(html, "<p>“<em>Emphasis</em>” and “<a href=\"/x\">a link</a>”</p>\n");
+}
+
+#[test]
+fn test_smart_typography_keeps_numbers_and_units_together() {
+    let html = render("en", "A 10 km run at 20 % effort, 3 minutes long.");
+
+    assert_eq!(html, "<p>A 10\u{a0}km run at 20\u{a0}% effort, 3 minutes long.</p>\n");
+}
+
+#[test]
+fn test_smart_typography_uses_locale_quote_styles() {
+    assert_eq!(render("de-DE", "Er sagt \"Hallo\"."), "<p>Er sagt „Hallo“.</p>\n");
+    assert_eq!(render("de-CH", "\"Grüezi\""), "<p>«Grüezi»</p>\n");
+    assert_eq!(
+        render("fr", "Il dit \"Bonjour\" : vraiment ?"),
+        "<p>Il dit «\u{202f}Bonjour\u{202f}»\u{a0}: vraiment\u{202f}?</p>\n"
+    );
+    assert_eq!(QuoteStyle::for_locale("pt_BR"), QuoteStyle::ENGLISH);
+    assert_eq!(QuoteStyle::for_locale("xx"), QuoteStyle::ENGLISH);
+}
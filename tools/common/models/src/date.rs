@@ -0,0 +1,187 @@
+//! # Publication Dates
+//!
+//! Frontmatter dates are written as `YYYY-MM-DD`, as RFC 3339 timestamps, or
+//! as the `DRAFT` sentinel for content that has no publication date yet.
+//! [`PublishDate`] parses all three so tools compare real dates instead of
+//! strings. Anything else in frontmatter is kept as written, as
+//! [`PublishDate::Invalid`], so one mistyped date doesn't stop a whole
+//! article from loading and validation can point at it instead.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Sentinel used in frontmatter for content without a publication date
+pub const DRAFT: &str = "DRAFT";
+
+/// A publication or update date from frontmatter
+///
+/// Dates order chronologically, with a date-only value ordered as midnight
+/// UTC. `Draft` orders after every date, as content that is yet to be
+/// published, so sorting newest first lists drafts at the top. `Invalid`
+/// values order between the dates and `Draft`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PublishDate {
+    /// Not published yet (`DRAFT`)
+    Draft,
+    /// A calendar date (`2024-01-31`)
+    Date(NaiveDate),
+    /// A point in time (`2024-01-31T09:00:00Z`)
+    DateTime(DateTime<Utc>),
+    /// A frontmatter value that isn't a date, as written
+    Invalid(String),
+}
+
+impl PublishDate {
+    /// Check whether this is the `DRAFT` sentinel
+    pub fn is_draft(&self) -> bool {
+        matches!(self, PublishDate::Draft)
+    }
+
+    /// Check whether this is a date, a timestamp or `DRAFT`
+    pub fn is_valid(&self) -> bool {
+        !matches!(self, PublishDate::Invalid(_))
+    }
+
+    /// Get the calendar date, or `None` for drafts and invalid values
+    pub fn date(&self) -> Option<NaiveDate> {
+        match self {
+            PublishDate::Draft | PublishDate::Invalid(_) => None,
+            PublishDate::Date(date) => Some(*date),
+            PublishDate::DateTime(datetime) => Some(datetime.date_naive()),
+        }
+    }
+
+    /// Get the point in time, using midnight UTC for dates, or `None` for drafts
+    /// and invalid values
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        match self {
+            PublishDate::Draft | PublishDate::Invalid(_) => None,
+            PublishDate::Date(date) => Some(date.and_time(chrono::NaiveTime::MIN).and_utc()),
+            PublishDate::DateTime(datetime) => Some(*datetime),
+        }
+    }
+}
+
+/// Error returned when a frontmatter date cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePublishDateError(String);
+
+impl fmt::Display for ParsePublishDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid date '{}': expected YYYY-MM-DD, an RFC 3339 timestamp or {}",
+            self.0, DRAFT
+        )
+    }
+}
+
+impl std::error::Error for ParsePublishDateError {}
+
+impl FromStr for PublishDate {
+    type Err = ParsePublishDateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+
+        if value.eq_ignore_ascii_case(DRAFT) {
+            return Ok(PublishDate::Draft);
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(PublishDate::Date(date));
+        }
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+            return Ok(PublishDate::DateTime(datetime.with_timezone(&Utc)));
+        }
+
+        // Timestamps without an offset are taken as UTC
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .map(|datetime| PublishDate::DateTime(datetime.and_utc()))
+            .ok_or_else(|| ParsePublishDateError(value.to_string()))
+    }
+}
+
+impl fmt::Display for PublishDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublishDate::Draft => f.write_str(DRAFT),
+            PublishDate::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            PublishDate::DateTime(datetime) => {
+                f.write_str(&datetime.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+            }
+            PublishDate::Invalid(value) => f.write_str(value),
+        }
+    }
+}
+
+impl Ord for PublishDate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.to_datetime(), other.to_datetime()) {
+            (None, None) => match (self, other) {
+                (PublishDate::Invalid(a), PublishDate::Invalid(b)) => a.cmp(b),
+                (PublishDate::Invalid(_), _) => Ordering::Less,
+                (_, PublishDate::Invalid(_)) => Ordering::Greater,
+                _ => Ordering::Equal,
+            },
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            // A date sorts before a timestamp at the same instant
+            (Some(a), Some(b)) => a.cmp(&b).then_with(|| {
+                matches!(self, PublishDate::DateTime(_)).cmp(&matches!(other, PublishDate::DateTime(_)))
+            }),
+        }
+    }
+}
+
+impl PartialOrd for PublishDate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<str> for PublishDate {
+    fn eq(&self, other: &str) -> bool {
+        other.parse::<PublishDate>().is_ok_and(|date| date == *self)
+    }
+}
+
+impl PartialEq<&str> for PublishDate {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl From<NaiveDate> for PublishDate {
+    fn from(date: NaiveDate) -> Self {
+        PublishDate::Date(date)
+    }
+}
+
+impl From<DateTime<Utc>> for PublishDate {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        PublishDate::DateTime(datetime)
+    }
+}
+
+impl Serialize for PublishDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublishDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        // An empty field is content that hasn't been given a date yet
+        if value.trim().is_empty() {
+            return Ok(PublishDate::Draft);
+        }
+        Ok(value.parse().unwrap_or(PublishDate::Invalid(value)))
+    }
+}
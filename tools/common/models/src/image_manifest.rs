@@ -0,0 +1,100 @@
+//! # Image Manifest
+//!
+//! image-build records every image it generates in a `manifest.json` file in
+//! its output directory. Other tools read the manifest to learn which
+//! variants exist, and their sizes, without opening the images.
+//!
+//! ```json
+//! {
+//!   "variants": [
+//!     {
+//!       "path": "blog/hello/hello-featured.webp",
+//!       "size": "featured",
+//!       "width": 1200,
+//!       "height": 630,
+//!       "format": "webp"
+//!     }
+//!   ]
+//! }
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Name of the manifest in the image-build output directory
+pub const IMAGE_MANIFEST_FILE: &str = "manifest.json";
+
+/// Size key of the social card rendered for each article
+pub const SOCIAL_CARD_SIZE: &str = "og";
+
+/// Size key of an SVG, GIF or video copied at its own size
+pub const ORIGINAL_SIZE: &str = "original";
+
+/// Size key of the still frame shown for an animated GIF or video
+pub const POSTER_SIZE: &str = "poster";
+
+/// Size key of an animated GIF transcoded to MP4 or WebP
+pub const ANIMATED_SIZE: &str = "animated";
+
+/// One generated image
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageVariant {
+    /// Location relative to the output directory, with `/` separators
+    pub path: String,
+    /// Key of `images.sizes` the image was generated for, or one of
+    /// [`ORIGINAL_SIZE`], [`POSTER_SIZE`] and [`ANIMATED_SIZE`]
+    pub size: String,
+    /// Width in pixels, `0` for a video copied without a poster
+    pub width: u32,
+    pub height: u32,
+    /// File format, such as `jpg` or `webp`
+    pub format: String,
+}
+
+/// The images image-build has generated
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageManifest {
+    /// Generated images, ordered by path
+    #[serde(default)]
+    pub variants: Vec<ImageVariant>,
+}
+
+impl ImageManifest {
+    /// Find the image at a path relative to the output directory
+    pub fn get(&self, path: &str) -> Option<&ImageVariant> {
+        self.variants
+            .binary_search_by(|variant| variant.path.as_str().cmp(path))
+            .ok()
+            .map(|index| &self.variants[index])
+    }
+
+    /// Record an image, replacing any earlier record for the same path
+    pub fn insert(&mut self, variant: ImageVariant) {
+        match self.variants.binary_search_by(|existing| existing.path.cmp(&variant.path)) {
+            Ok(index) => self.variants[index] = variant,
+            Err(index) => self.variants.insert(index, variant),
+        }
+    }
+
+    /// Load a manifest, returning `None` if it does not exist
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read image manifest: {}", path.display()))?;
+        let mut manifest: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse image manifest: {}", path.display()))?;
+        manifest.variants.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Some(manifest))
+    }
+
+    /// Write the manifest as JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize image manifest")?;
+        std::fs::write(path, json).with_context(|| format!("Failed to write image manifest: {}", path.display()))
+    }
+}
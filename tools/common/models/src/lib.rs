@@ -12,7 +12,7 @@
 //!
 //! ```rust
 //! use common_models::{Config, ContentConfig, TopicConfig};
-//! use std::collections::HashMap;
+//! use std::collections::{BTreeMap, HashMap};
 //!
 //! // Create a simple configuration
 //! let mut topics = HashMap::new();
@@ -32,9 +32,19 @@
 //! };
 //! ```
 
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod date;
+pub use date::{ParsePublishDateError, PublishDate};
+pub mod image_manifest;
+pub use image_manifest::{
+    ImageManifest, ImageVariant, ANIMATED_SIZE, IMAGE_MANIFEST_FILE, ORIGINAL_SIZE, POSTER_SIZE, SOCIAL_CARD_SIZE,
+};
+pub mod workflow;
+pub use workflow::{ContentState, ParseContentStateError};
+
 // Include external test module
 #[cfg(test)]
 mod tests;
@@ -68,6 +78,15 @@ mod tests;
 ///         copyright: "Copyright".to_string(),
 ///         site_url: None,
 ///     },
+///     hooks: Vec::new(),
+///     i18n: Default::default(),
+///     deploy: Default::default(),
+///     export: Default::default(),
+///     import: Default::default(),
+///     newsletter: Default::default(),
+///     typography: Default::default(),
+///     link_policy: Default::default(),
+///     og_image: Default::default(),
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -88,6 +107,33 @@ pub struct Config {
     pub images: ImageConfig,
     /// Publication configuration settings
     pub publication: PublicationConfig,
+    /// Hooks that run at points in the content lifecycle
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookConfig>,
+    /// Languages content is written in
+    #[serde(default, skip_serializing_if = "I18nConfig::is_disabled")]
+    pub i18n: I18nConfig,
+    /// Places the built site can be deployed to, by name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub deploy: HashMap<String, DeployConfig>,
+    /// Settings for exporting articles as books
+    #[serde(default, skip_serializing_if = "ExportConfig::is_default")]
+    pub export: ExportConfig,
+    /// Settings for importing content from other services
+    #[serde(default, skip_serializing_if = "ImportConfig::is_default")]
+    pub import: ImportConfig,
+    /// Settings for rendering and sending the newsletter
+    #[serde(default, skip_serializing_if = "NewsletterConfig::is_default")]
+    pub newsletter: NewsletterConfig,
+    /// Typographic punctuation in rendered articles
+    #[serde(default, skip_serializing_if = "TypographyConfig::is_default")]
+    pub typography: TypographyConfig,
+    /// Rules for links to other sites
+    #[serde(default, skip_serializing_if = "LinkPolicyConfig::is_default")]
+    pub link_policy: LinkPolicyConfig,
+    /// Social card images rendered by image-build
+    #[serde(default, skip_serializing_if = "OgImageConfig::is_default")]
+    pub og_image: OgImageConfig,
 }
 
 impl Default for Config {
@@ -101,6 +147,15 @@ impl Default for Config {
             content: ContentConfig::default(),
             images: ImageConfig::default(),
             publication: PublicationConfig::default(),
+            hooks: Vec::new(),
+            i18n: I18nConfig::default(),
+            deploy: HashMap::new(),
+            export: ExportConfig::default(),
+            import: ImportConfig::default(),
+            newsletter: NewsletterConfig::default(),
+            typography: TypographyConfig::default(),
+            link_policy: LinkPolicyConfig::default(),
+            og_image: OgImageConfig::default(),
         }
     }
 }
@@ -353,6 +408,462 @@ impl Default for PublicationConfig {
     }
 }
 
+/// Configuration from the `i18n` section: the languages content is written in
+///
+/// With no `locales`, the site has a single language and articles carry no
+/// language information. Otherwise every article has a language: the main
+/// content file is in its `lang` frontmatter field or the default locale,
+/// and a `{slug}.{locale}.md` file beside it is a translation.
+///
+/// # Example
+///
+/// ```yaml
+/// i18n:
+///   default_locale: en
+///   locales: [en, de]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct I18nConfig {
+    /// Locale of content that doesn't say otherwise, published at the site root
+    #[serde(default = "default_locale")]
+    pub default_locale: String,
+    /// Locales content can be written in
+    #[serde(default)]
+    pub locales: Vec<String>,
+}
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            default_locale: default_locale(),
+            locales: Vec::new(),
+        }
+    }
+}
+
+impl I18nConfig {
+    /// Check whether the site has more than one language configured
+    pub fn is_enabled(&self) -> bool {
+        !self.locales.is_empty()
+    }
+
+    fn is_disabled(&self) -> bool {
+        !self.is_enabled()
+    }
+
+    /// Every available locale, with the default first
+    pub fn available_locales(&self) -> Vec<&str> {
+        let mut locales = vec![self.default_locale.as_str()];
+        locales.extend(
+            self.locales
+                .iter()
+                .map(String::as_str)
+                .filter(|locale| *locale != self.default_locale),
+        );
+        locales
+    }
+
+    /// Check whether `locale` is one of the available locales
+    pub fn is_available(&self, locale: &str) -> bool {
+        self.available_locales().contains(&locale)
+    }
+
+    /// Check whether `locale` is the default locale
+    pub fn is_default(&self, locale: &str) -> bool {
+        locale == self.default_locale
+    }
+}
+
+/// A hook from the `hooks` section of the configuration
+///
+/// Each hook runs a shell command or touches a file when a lifecycle event
+/// fires. Exactly one of `run` and `touch` should be set.
+///
+/// # Example
+///
+/// ```yaml
+/// hooks:
+///   - event: post_build
+///     run: ./scripts/deploy.sh
+///   - event: post_create
+///     touch: .last-created
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookConfig {
+    /// Lifecycle event that fires the hook, such as `pre_build`
+    pub event: String,
+    /// Shell command to run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+    /// File to write the current time to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub touch: Option<String>,
+}
+
+/// A deploy target from the `deploy` section of the configuration
+///
+/// Targets are keyed by name and pick their kind with `type`. Credentials
+/// never go in the configuration: S3 targets read the standard
+/// `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment variables,
+/// and GitHub Pages targets push with the credentials git already has.
+///
+/// # Example
+///
+/// ```yaml
+/// deploy:
+///   staging:
+///     type: local
+///     path: /var/www/staging
+///   production:
+///     type: s3
+///     bucket: example-site
+///     region: eu-west-1
+///   pages:
+///     type: github_pages
+///     branch: gh-pages
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeployConfig {
+    /// Copy the output into a directory, such as a mounted web root
+    Local {
+        /// Directory to copy into
+        path: String,
+    },
+    /// Upload the output to an S3-compatible bucket
+    S3 {
+        /// Bucket to upload to
+        bucket: String,
+        /// Region of the bucket
+        #[serde(default = "default_s3_region")]
+        region: String,
+        /// Endpoint of an S3-compatible service, instead of AWS
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+        /// Key prefix to upload under
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefix: Option<String>,
+        /// Key of the deploy manifest, which should be somewhere the site
+        /// isn't served from (defaults to `.deploy/{prefix}/manifest.json`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        manifest_key: Option<String>,
+    },
+    /// Commit the output to a branch served by GitHub Pages
+    GithubPages {
+        /// Git remote name or URL to push to
+        #[serde(default = "default_pages_remote")]
+        remote: String,
+        /// Branch to commit to
+        #[serde(default = "default_pages_branch")]
+        branch: String,
+    },
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_pages_remote() -> String {
+    "origin".to_string()
+}
+
+fn default_pages_branch() -> String {
+    "gh-pages".to_string()
+}
+
+/// Configuration from the `export` section, used when exporting articles as a book
+///
+/// A PDF is rendered from the book as a single HTML file by an external
+/// program. `pdf_command` is run by the shell with `{input}` replaced by the
+/// path of the HTML file and `{output}` by the path of the PDF to write.
+///
+/// # Example
+///
+/// ```yaml
+/// export:
+///   pdf_command: weasyprint {input} {output}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportConfig {
+    /// Command that renders the PDF, `weasyprint {input} {output}` when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdf_command: Option<String>,
+}
+
+impl ExportConfig {
+    /// Command used when `pdf_command` is not set
+    pub const DEFAULT_PDF_COMMAND: &'static str = "weasyprint {input} {output}";
+
+    /// The command that renders the PDF
+    pub fn pdf_command(&self) -> &str {
+        self.pdf_command.as_deref().unwrap_or(Self::DEFAULT_PDF_COMMAND)
+    }
+
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Configuration from the `import` section, used when importing content from other services
+///
+/// # Example
+///
+/// ```yaml
+/// import:
+///   notion:
+///     database_id: 0f9e6b1c2d3a4b5c8d7e6f5a4b3c2d1e
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportConfig {
+    /// The Notion database that drafts are imported from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notion: Option<NotionConfig>,
+}
+
+impl ImportConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// A Notion database to import pages from
+///
+/// The integration token is read from the `NOTION_TOKEN` environment
+/// variable when it is set, and from `token` otherwise, so it can be kept
+/// out of a configuration file that is committed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotionConfig {
+    /// Integration token with read access to the database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// ID of the database whose pages are imported
+    pub database_id: String,
+}
+
+impl NotionConfig {
+    /// Environment variable that overrides `token`
+    pub const TOKEN_VAR: &'static str = "NOTION_TOKEN";
+
+    /// The integration token, from the environment or the configuration
+    pub fn token(&self) -> Option<String> {
+        std::env::var(Self::TOKEN_VAR)
+            .ok()
+            .filter(|token| !token.trim().is_empty())
+            .or_else(|| self.token.clone())
+    }
+}
+
+/// Configuration from the `newsletter` section, used when rendering recent articles as an email
+///
+/// Templates are Handlebars files; without them the built-in templates are used.
+///
+/// # Example
+///
+/// ```yaml
+/// newsletter:
+///   articles: 3
+///   html_template: templates/newsletter.html.hbs
+///   webhook:
+///     url: https://hooks.example.com/newsletter
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewsletterConfig {
+    /// Number of recent articles in each newsletter, 5 when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub articles: Option<usize>,
+    /// Template for the HTML version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_template: Option<String>,
+    /// Template for the plain-text version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_template: Option<String>,
+    /// Where rendered newsletters are pushed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookConfig>,
+}
+
+impl NewsletterConfig {
+    /// Number of articles used when `articles` is not set
+    pub const DEFAULT_ARTICLES: usize = 5;
+
+    /// The number of recent articles in each newsletter
+    pub fn articles(&self) -> usize {
+        self.articles.unwrap_or(Self::DEFAULT_ARTICLES)
+    }
+
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// An HTTP endpoint that rendered newsletters are posted to as JSON
+///
+/// The bearer token is read from the `NEWSLETTER_WEBHOOK_TOKEN` environment
+/// variable when it is set, and from `token` otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Token sent in the `Authorization` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl WebhookConfig {
+    /// Environment variable that overrides `token`
+    pub const TOKEN_VAR: &'static str = "NEWSLETTER_WEBHOOK_TOKEN";
+
+    /// The bearer token, from the environment or the configuration
+    pub fn token(&self) -> Option<String> {
+        std::env::var(Self::TOKEN_VAR)
+            .ok()
+            .filter(|token| !token.trim().is_empty())
+            .or_else(|| self.token.clone())
+    }
+}
+
+/// Configuration from the `typography` section
+///
+/// When enabled, the build turns straight quotes, `--`, `...` and the spaces
+/// between numbers and units into their typographic forms, in the style of
+/// each article's language. An article's `typography` frontmatter field
+/// overrides this setting.
+///
+/// # Example
+///
+/// ```yaml
+/// typography:
+///   enabled: true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypographyConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl TypographyConfig {
+    /// Frontmatter field that turns typography on or off for one article
+    pub const FRONTMATTER_FIELD: &'static str = "typography";
+
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Configuration from the `link_policy` section
+///
+/// Each rule is off until it is configured. With `allow_domains` set, links
+/// to other sites must go to one of them; links to a `deny_domains` domain
+/// are never allowed. `require_https` flags plain `http` links,
+/// `flag_tracking_params` flags `utm_*` and similar query parameters, and
+/// the build adds `rel="nofollow"` to links to `nofollow_domains`. A domain
+/// also covers its subdomains.
+///
+/// # Example
+///
+/// ```yaml
+/// link_policy:
+///   deny_domains: [bit.ly]
+///   require_https: true
+///   flag_tracking_params: true
+///   nofollow_domains: [sponsor.example]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkPolicyConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_domains: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_domains: Vec<String>,
+    #[serde(default)]
+    pub require_https: bool,
+    #[serde(default)]
+    pub flag_tracking_params: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nofollow_domains: Vec<String>,
+}
+
+impl LinkPolicyConfig {
+    /// Whether `host` is one of `domains` or a subdomain of one
+    pub fn matches_domain(host: &str, domains: &[String]) -> bool {
+        let host = host.trim_end_matches('.').to_lowercase();
+        domains.iter().any(|domain| {
+            let domain = domain.trim().trim_start_matches("*.").trim_end_matches('.').to_lowercase();
+            !domain.is_empty()
+                && (host == domain || host.strip_suffix(&domain).is_some_and(|prefix| prefix.ends_with('.')))
+        })
+    }
+
+    /// Whether links to `host` get `rel="nofollow"`
+    pub fn is_nofollow(&self, host: &str) -> bool {
+        Self::matches_domain(host, &self.nofollow_domains)
+    }
+
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Configuration from the `og_image` section
+///
+/// When a `font` is set, image-build renders a 1200×630 social card for
+/// each article: its title drawn over its `index.jpg`, darkened so the text
+/// stays readable, or over the `background` color when it has no image or
+/// `use_hero_image` is off. Colors are written as `#rrggbb`, and the font
+/// path is relative to the working directory.
+///
+/// # Example
+///
+/// ```yaml
+/// og_image:
+///   font: fonts/Inter-Bold.ttf
+///   background: "#1e293b"
+///   text_color: "#ffffff"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OgImageConfig {
+    /// TrueType or OpenType font for the title; no cards are rendered without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+    #[serde(default = "OgImageConfig::default_background")]
+    pub background: String,
+    #[serde(default = "OgImageConfig::default_text_color")]
+    pub text_color: String,
+    #[serde(default = "OgImageConfig::default_use_hero_image")]
+    pub use_hero_image: bool,
+}
+
+impl OgImageConfig {
+    fn default_background() -> String {
+        "#1e293b".to_string()
+    }
+
+    fn default_text_color() -> String {
+        "#ffffff".to_string()
+    }
+
+    fn default_use_hero_image() -> bool {
+        true
+    }
+
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl Default for OgImageConfig {
+    fn default() -> Self {
+        Self {
+            font: None,
+            background: Self::default_background(),
+            text_color: Self::default_text_color(),
+            use_hero_image: Self::default_use_hero_image(),
+        }
+    }
+}
+
 /// Frontmatter metadata for articles
 ///
 /// This struct contains metadata for an article, such as title,
@@ -365,7 +876,7 @@ impl Default for PublicationConfig {
 ///
 /// let frontmatter = Frontmatter {
 ///     title: "Article Title".to_string(),
-///     published_at: Some("2023-01-01".to_string()),
+///     published_at: Some("2023-01-01".parse().unwrap()),
 ///     updated_at: None,
 ///     slug: Some("article-slug".to_string()),
 ///     description: Some("Article description".to_string()),
@@ -373,18 +884,23 @@ impl Default for PublicationConfig {
 ///     topics: Some(vec!["topic1".to_string()]),
 ///     is_draft: Some(false),
 ///     featured_image_path: Some("images/article.jpg".to_string()),
+///     publish_at: None,
+///     series: None,
+///     series_part: None,
+///     archived: None,
+///     extra: Default::default(),
 /// };
 /// ```
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Frontmatter {
     /// Title of the article
     pub title: String,
-    /// Optional publication date
+    /// Optional publication date, or `DRAFT`
     #[serde(rename = "published")]
-    pub published_at: Option<String>,
+    pub published_at: Option<PublishDate>,
     /// Optional last updated date
     #[serde(rename = "updated")]
-    pub updated_at: Option<String>,
+    pub updated_at: Option<PublishDate>,
     /// Optional slug for the article
     pub slug: Option<String>,
     /// Optional description
@@ -399,6 +915,17 @@ pub struct Frontmatter {
     /// Optional featured image path
     #[serde(rename = "featured_image")]
     pub featured_image_path: Option<String>,
+    /// Optional scheduled publication time (RFC 3339)
+    pub publish_at: Option<String>,
+    /// Optional name of the series the article belongs to
+    pub series: Option<String>,
+    /// Optional position of the article within its series, starting at 1
+    pub series_part: Option<u32>,
+    /// Optional archived status; archived content is kept but not published
+    pub archived: Option<bool>,
+    /// Custom fields not listed above, kept so they survive a round-trip
+    #[serde(flatten, default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, serde_yaml::Value>,
 }
 
 impl Default for Frontmatter {
@@ -413,10 +940,104 @@ impl Default for Frontmatter {
             topics: None,
             is_draft: Some(true),
             featured_image_path: None,
+            publish_at: None,
+            series: None,
+            series_part: None,
+            archived: None,
+            extra: Default::default(),
         }
     }
 }
 
+impl Frontmatter {
+    /// Check whether the article has been archived
+    pub fn is_archived(&self) -> bool {
+        self.archived.unwrap_or(false)
+    }
+
+    /// Get a custom field as a string
+    ///
+    /// Returns `None` if the field is missing or not a string.
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.extra.get(key).and_then(serde_yaml::Value::as_str)
+    }
+
+    /// Get a custom field as a boolean
+    ///
+    /// Returns `None` if the field is missing or not a boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.extra.get(key).and_then(serde_yaml::Value::as_bool)
+    }
+
+    /// Get a custom field as a date
+    ///
+    /// Accepts `YYYY-MM-DD` and RFC 3339 timestamps, returning the date part.
+    /// Returns `None` if the field is missing or not a valid date.
+    pub fn get_date(&self, key: &str) -> Option<NaiveDate> {
+        let value = self.get_string(key)?.trim();
+        DateTime::parse_from_rfc3339(value)
+            .map(|datetime| datetime.date_naive())
+            .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+            .ok()
+    }
+
+    /// Set a custom field, replacing any previous value
+    pub fn set_extra(&mut self, key: impl Into<String>, value: impl Into<serde_yaml::Value>) {
+        self.extra.insert(key.into(), value.into());
+    }
+
+    /// Get the summary written for the article, if any
+    ///
+    /// Uses `description`, falling back to a custom `tagline` field. Blank
+    /// values are ignored.
+    pub fn summary_override(&self) -> Option<&str> {
+        let non_blank = |value: &str| !value.trim().is_empty();
+        self.description
+            .as_deref()
+            .filter(|value| non_blank(value))
+            .or_else(|| self.get_string("tagline").filter(|value| non_blank(value)))
+            .map(str::trim)
+    }
+
+    /// Get the language the article is written in, from the `lang` field
+    pub fn lang(&self) -> Option<&str> {
+        self.get_string("lang").map(str::trim).filter(|lang| !lang.is_empty())
+    }
+
+    /// Get the URLs the article has been syndicated to, from the `syndicated_to` field
+    ///
+    /// Accepts a list of URLs or a single URL. Blank entries are ignored.
+    pub fn syndicated_to(&self) -> Vec<&str> {
+        let urls: Vec<&str> = match self.extra.get("syndicated_to") {
+            Some(serde_yaml::Value::Sequence(urls)) => urls.iter().filter_map(serde_yaml::Value::as_str).collect(),
+            Some(serde_yaml::Value::String(url)) => vec![url.as_str()],
+            _ => Vec::new(),
+        };
+        urls.into_iter().map(str::trim).filter(|url| !url.is_empty()).collect()
+    }
+
+    /// Remove a custom field, returning its previous value
+    pub fn remove_extra(&mut self, key: &str) -> Option<serde_yaml::Value> {
+        self.extra.remove(key)
+    }
+
+    /// Parse the scheduled publication time
+    ///
+    /// Returns `None` if no `publish_at` is set, or the parse result otherwise.
+    pub fn publish_at_datetime(&self) -> Option<Result<DateTime<FixedOffset>, chrono::ParseError>> {
+        self.publish_at
+            .as_deref()
+            .map(|value| DateTime::parse_from_rfc3339(value.trim()))
+    }
+
+    /// Check whether the article is scheduled for publication after `now`
+    ///
+    /// Articles without a valid `publish_at` are never considered scheduled.
+    pub fn is_scheduled_after(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.publish_at_datetime(), Some(Ok(publish_at)) if publish_at > now)
+    }
+}
+
 /// Structure for a complete article
 ///
 /// This struct contains all information about an article,
@@ -430,7 +1051,7 @@ impl Default for Frontmatter {
 /// let article = Article {
 ///     frontmatter: Frontmatter {
 ///         title: "My First Post".to_string(),
-///         published_at: Some("2023-01-01".to_string()),
+///         published_at: Some("2023-01-01".parse().unwrap()),
 ///         updated_at: None,
 ///         slug: Some("my-first-post".to_string()),
 ///         description: None,
@@ -438,6 +1059,11 @@ impl Default for Frontmatter {
 ///         topics: Some(vec!["blog".to_string()]),
 ///         is_draft: Some(false),
 ///         featured_image_path: None,
+///         publish_at: None,
+///         series: None,
+///         series_part: None,
+///         archived: None,
+///         extra: Default::default(),
 ///     },
 ///     content: "# My First Post\n\nThis is my first blog post.".to_string(),
 ///     slug: "my-first-post".to_string(),
@@ -445,6 +1071,15 @@ impl Default for Frontmatter {
 ///     path: "/content/blog/my-first-post/index.mdx".to_string(),
 ///     word_count: Some(7),
 ///     reading_time: Some(1),
+///     series_navigation: None,
+///     html: None,
+///     summary: None,
+///     git: None,
+///     lang: None,
+///     alternates: Vec::new(),
+///     links: Vec::new(),
+///     hero_image: None,
+///     social_image: None,
 /// };
 /// ```
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -463,4 +1098,124 @@ pub struct Article {
     pub word_count: Option<usize>,
     /// Optional reading time in minutes
     pub reading_time: Option<u32>,
+    /// Position within a series, with links to the neighbouring parts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series_navigation: Option<SeriesNavigation>,
+    /// Article content rendered to HTML, set by the build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    /// Short plain-text summary for listings and feeds, set by the build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Revision history from git, set by the build when asked for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitMetadata>,
+    /// Language of the article, set by the build when the site has more than one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// The article in each available language, including this one, set by the build
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternates: Vec<LocaleAlternate>,
+    /// Links for the page head, such as its canonical URL and syndicated copies, set by the build
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<PageLink>,
+    /// Image representing the article in listings and social cards, set by the build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_image: Option<HeroImage>,
+    /// URL of the card image-build rendered for sharing the article, set by the build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub social_image: Option<String>,
+}
+
+/// A version of an article in one language, for `hreflang` links
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LocaleAlternate {
+    /// Locale of this version, such as `de`
+    pub lang: String,
+    /// URL this version is published at
+    pub url: String,
+}
+
+/// A `<link>` for the head of an article's page
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PageLink {
+    /// Relationship to the article, such as `canonical` or `syndication`
+    pub rel: String,
+    /// URL of the linked page
+    pub href: String,
+}
+
+/// Where an article's hero image was found
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeroImageSource {
+    /// The `cover` frontmatter field
+    Cover,
+    /// `index.jpg` in the article directory
+    IndexImage,
+    /// The first image in the article body
+    BodyImage,
+}
+
+/// An article's hero image
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct HeroImage {
+    pub source: HeroImageSource,
+    /// URL of the original image, under `/images/` for images beside the content file
+    pub url: String,
+    /// Dimensions of the original image, unknown for remote images
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Image generated by image-build for each key of `images.sizes`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sizes: BTreeMap<String, HeroImageSize>,
+}
+
+/// One generated size of a hero image
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct HeroImageSize {
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Revision history of an article's content file, from git
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GitMetadata {
+    /// Time of the first commit that touched the file
+    pub created: DateTime<Utc>,
+    /// Time of the last commit that touched the file
+    pub last_modified: DateTime<Utc>,
+    /// Commit authors, in the order they first changed the file
+    pub contributors: Vec<String>,
+}
+
+/// Link to one part of a series
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SeriesLink {
+    /// Title of the linked article
+    pub title: String,
+    /// Slug of the linked article
+    pub slug: String,
+    /// Topic of the linked article
+    pub topic: String,
+    /// Part number of the linked article
+    pub part: u32,
+}
+
+/// Position of an article within its series
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SeriesNavigation {
+    /// Name of the series
+    pub series: String,
+    /// Part number of this article
+    pub part: u32,
+    /// Total number of parts in the series
+    pub total: usize,
+    /// Previous part, if any
+    pub prev: Option<SeriesLink>,
+    /// Next part, if any
+    pub next: Option<SeriesLink>,
 }
\ No newline at end of file
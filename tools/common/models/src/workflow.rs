@@ -0,0 +1,133 @@
+//! # Editorial Workflow
+//!
+//! Content moves through a fixed set of states on its way to readers:
+//! an idea becomes a draft, the draft goes to review, and reviewed work is
+//! published and eventually archived. The state is stored in the `state`
+//! frontmatter field; content written before the field existed gets a
+//! state inferred from its `draft`, `archived` and `published` fields.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Frontmatter, PublishDate};
+
+/// Frontmatter field holding the workflow state
+pub const STATE_FIELD: &str = "state";
+
+/// Where a piece of content is in the editorial workflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentState {
+    /// A topic worth writing about, with little or no text yet
+    Idea,
+    /// Being written
+    Draft,
+    /// Written and waiting for review
+    Review,
+    /// Visible to readers
+    Published,
+    /// Kept for reference but no longer listed
+    Archived,
+}
+
+impl ContentState {
+    /// Every state, in workflow order
+    pub const ALL: [ContentState; 5] = [
+        ContentState::Idea,
+        ContentState::Draft,
+        ContentState::Review,
+        ContentState::Published,
+        ContentState::Archived,
+    ];
+
+    /// The name used in frontmatter
+    pub fn name(self) -> &'static str {
+        match self {
+            ContentState::Idea => "idea",
+            ContentState::Draft => "draft",
+            ContentState::Review => "review",
+            ContentState::Published => "published",
+            ContentState::Archived => "archived",
+        }
+    }
+
+    /// The states content in this state can move to
+    ///
+    /// Content can always be archived, and sent back to draft from any
+    /// later state, but can only be published after review.
+    pub fn allowed_transitions(self) -> &'static [ContentState] {
+        match self {
+            ContentState::Idea => &[ContentState::Draft, ContentState::Archived],
+            ContentState::Draft => &[ContentState::Idea, ContentState::Review, ContentState::Archived],
+            ContentState::Review => &[ContentState::Draft, ContentState::Published, ContentState::Archived],
+            ContentState::Published => &[ContentState::Draft, ContentState::Archived],
+            ContentState::Archived => &[ContentState::Draft],
+        }
+    }
+
+    /// Check whether content in this state can move to `next`
+    pub fn can_transition_to(self, next: ContentState) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    /// Check whether content in this state is visible to readers
+    pub fn is_public(self) -> bool {
+        self == ContentState::Published
+    }
+}
+
+/// Error returned when a workflow state name is not recognized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseContentStateError(String);
+
+impl fmt::Display for ParseContentStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = ContentState::ALL.iter().map(|state| state.name()).collect();
+        write!(f, "unknown state '{}': expected one of {}", self.0, names.join(", "))
+    }
+}
+
+impl std::error::Error for ParseContentStateError {}
+
+impl FromStr for ContentState {
+    type Err = ParseContentStateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        ContentState::ALL
+            .into_iter()
+            .find(|state| state.name().eq_ignore_ascii_case(value))
+            .ok_or_else(|| ParseContentStateError(value.to_string()))
+    }
+}
+
+impl fmt::Display for ContentState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Frontmatter {
+    /// Get the workflow state
+    ///
+    /// Uses the `state` field when it holds a known state. Otherwise the
+    /// state is inferred: archived content is `Archived`, content marked as
+    /// a draft or without a publication date is `Draft`, and anything else
+    /// is `Published`.
+    pub fn content_state(&self) -> ContentState {
+        if let Some(state) = self.get_string(STATE_FIELD).and_then(|value| value.parse().ok()) {
+            return state;
+        }
+
+        if self.is_archived() {
+            ContentState::Archived
+        } else if self.is_draft.unwrap_or(false)
+            || self.published_at.as_ref().is_none_or(PublishDate::is_draft)
+        {
+            ContentState::Draft
+        } else {
+            ContentState::Published
+        }
+    }
+}
@@ -91,6 +91,15 @@ fn test_full_config_json_roundtrip() {
             copyright: "Test Copyright".to_string(),
             site_url: Some("https://example.com".to_string()),
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
     };
 
     // Convert to JSON
@@ -126,14 +135,19 @@ fn test_frontmatter_yaml_compatibility() {
     // Create a frontmatter with all fields populated
     let frontmatter = Frontmatter {
         title: "Test Article".to_string(),
-        published_at: Some("2023-01-01".to_string()),
-        updated_at: Some("2023-01-15".to_string()),
+        published_at: Some("2023-01-01".parse().unwrap()),
+        updated_at: Some("2023-01-15".parse().unwrap()),
         slug: Some("test-article".to_string()),
         description: Some("This is a test article".to_string()),
         tags: Some(vec!["test".to_string(), "article".to_string()]),
         topics: Some(vec!["blog".to_string()]),
         is_draft: Some(false),
         featured_image_path: Some("images/featured.jpg".to_string()),
+        publish_at: None,
+        series: None,
+        series_part: None,
+        archived: None,
+        extra: Default::default(),
     };
 
     // Convert to JSON (to simulate YAML serialization/deserialization)
@@ -160,7 +174,7 @@ fn test_article_serialization() {
     let article = Article {
         frontmatter: Frontmatter {
             title: "Test Article".to_string(),
-            published_at: Some("2023-01-01".to_string()),
+            published_at: Some("2023-01-01".parse().unwrap()),
             updated_at: None,
             slug: Some("test-article".to_string()),
             description: None,
@@ -168,6 +182,11 @@ fn test_article_serialization() {
             topics: Some(vec!["blog".to_string()]),
             is_draft: Some(false),
             featured_image_path: None,
+            publish_at: None,
+            series: None,
+            series_part: None,
+            archived: None,
+            extra: Default::default(),
         },
         content: "# Test Article\n\nThis is a test article.".to_string(),
         slug: "test-article".to_string(),
@@ -175,6 +194,15 @@ fn test_article_serialization() {
         path: "/content/blog/test-article.md".to_string(),
         word_count: Some(6),
         reading_time: Some(1),
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
     };
 
     // Convert to JSON
@@ -198,7 +226,7 @@ fn test_serialize_article_to_json() {
     let article = Article {
         frontmatter: Frontmatter {
             title: "Test Article".to_string(),
-            published_at: Some("2023-01-01".to_string()),
+            published_at: Some("2023-01-01".parse().unwrap()),
             updated_at: None,
             slug: Some("test-article".to_string()),
             description: Some("A test article".to_string()),
@@ -206,6 +234,11 @@ fn test_serialize_article_to_json() {
             topics: Some(vec!["blog".to_string()]),
             is_draft: Some(false),
             featured_image_path: None,
+            publish_at: None,
+            series: None,
+            series_part: None,
+            archived: None,
+            extra: Default::default(),
         },
         content: "This is a test article with some content.".to_string(),
         slug: "test-article".to_string(),
@@ -213,6 +246,15 @@ fn test_serialize_article_to_json() {
         path: "content/blog/test-article.md".to_string(),
         word_count: Some(100),
         reading_time: Some(1),
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
     };
 
     let json = serde_json::to_string(&article).unwrap();
@@ -248,7 +290,7 @@ fn test_deserialize_json_to_article() {
 
     let article: Article = serde_json::from_str(json).unwrap();
     assert_eq!(article.frontmatter.title, "Test Article");
-    assert_eq!(article.frontmatter.published_at, Some("2023-01-01".to_string()));
+    assert_eq!(article.frontmatter.published_at, Some("2023-01-01".parse().unwrap()));
     assert_eq!(article.content, "This is a test article with some content.");
     assert_eq!(article.slug, "test-article");
     assert_eq!(article.topic, "blog");
@@ -282,6 +324,15 @@ fn test_serialize_and_deserialize_config() {
             copyright: "Test Copyright".to_string(),
             site_url: Some("https://example.com".to_string()),
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
     };
 
     let json = serde_json::to_string(&original_config).unwrap();
@@ -26,6 +26,18 @@ mod strategies {
             )
     }
 
+    pub fn publish_date_strategy() -> impl Strategy<Value = PublishDate> {
+        prop_oneof![
+            Just(PublishDate::Draft),
+            (2000..2030i32, 1..13u32, 1..29u32).prop_map(|(y, m, d)| {
+                PublishDate::Date(chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap())
+            }),
+            (0..2_000_000_000i64).prop_map(|secs| {
+                PublishDate::DateTime(chrono::DateTime::from_timestamp(secs, 0).unwrap())
+            })
+        ]
+    }
+
     pub fn option_of<T: Strategy>(strat: T) -> impl Strategy<Value = Option<T::Value>>
     where
         T::Value: Clone,
@@ -71,8 +83,8 @@ mod strategies {
     pub fn frontmatter_strategy() -> impl Strategy<Value = Frontmatter> {
         (
             valid_string(),
-            option_of(valid_string()),
-            option_of(valid_string()),
+            option_of(publish_date_strategy()),
+            option_of(publish_date_strategy()),
             option_of(valid_string()),
             option_of(valid_string()),
             option_of(proptest::collection::vec(valid_string(), 0..10)),
@@ -100,6 +112,11 @@ mod strategies {
                 topics,
                 is_draft,
                 featured_image_path,
+                publish_at: None,
+                series: None,
+                series_part: None,
+                archived: None,
+                extra: Default::default(),
             }
         })
     }
@@ -191,6 +208,15 @@ proptest! {
             path: path.clone(),
             word_count,
             reading_time,
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         };
 
         // Verify core properties
@@ -3,6 +3,7 @@
 //! This file contains unit tests for the models in the common models library.
 
 use crate::*;
+use std::collections::HashMap;
 
 #[test]
 fn test_article_with_word_count_and_reading_time() {
@@ -14,6 +15,15 @@ fn test_article_with_word_count_and_reading_time() {
         path: "content/blog/test-article.md".to_string(),
         word_count: Some(100),
         reading_time: Some(1),
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
     };
 
     assert_eq!(article.word_count, Some(100));
@@ -67,6 +77,15 @@ fn test_config_structure() {
             copyright: "Test Copyright".to_string(),
             site_url: None,
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
     };
 
     assert_eq!(config.title, "Test Site");
@@ -77,4 +96,184 @@ fn test_config_structure() {
     assert_eq!(config.content.base_dir, "/content");
     assert_eq!(config.images.formats, vec!["jpg"]);
     assert_eq!(config.publication.author, "Test Author");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_frontmatter_publish_at_parsing() {
+    let mut frontmatter = Frontmatter::default();
+    assert!(frontmatter.publish_at_datetime().is_none());
+
+    frontmatter.publish_at = Some("2030-06-01T09:00:00Z".to_string());
+    assert!(matches!(frontmatter.publish_at_datetime(), Some(Ok(_))));
+
+    frontmatter.publish_at = Some("June 1st".to_string());
+    assert!(matches!(frontmatter.publish_at_datetime(), Some(Err(_))));
+}
+
+#[test]
+fn test_frontmatter_is_scheduled_after() {
+    let now = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    let mut frontmatter = Frontmatter::default();
+    assert!(!frontmatter.is_scheduled_after(now));
+
+    frontmatter.publish_at = Some("2024-06-01T09:00:00+02:00".to_string());
+    assert!(frontmatter.is_scheduled_after(now));
+
+    frontmatter.publish_at = Some("2023-06-01T09:00:00Z".to_string());
+    assert!(!frontmatter.is_scheduled_after(now));
+
+    frontmatter.publish_at = Some("not a date".to_string());
+    assert!(!frontmatter.is_scheduled_after(now));
+}
+
+#[test]
+fn test_frontmatter_keeps_custom_fields() {
+    let yaml = "title: Post\ncanonical_url: https://example.com/post\nfeatured: true\nreviewed: 2024-03-01\ndraft: false\n";
+    let mut frontmatter: Frontmatter = serde_yaml::from_str(yaml).unwrap();
+
+    assert_eq!(frontmatter.is_draft, Some(false));
+    assert_eq!(frontmatter.get_string("canonical_url"), Some("https://example.com/post"));
+    assert_eq!(frontmatter.get_bool("featured"), Some(true));
+    assert_eq!(frontmatter.get_date("reviewed"), chrono::NaiveDate::from_ymd_opt(2024, 3, 1));
+    assert!(!frontmatter.extra.contains_key("draft"));
+    assert_eq!(frontmatter.get_string("missing"), None);
+
+    frontmatter.set_extra("cover_caption", "A caption");
+    frontmatter.remove_extra("featured");
+
+    let round_trip: Frontmatter = serde_yaml::from_str(&serde_yaml::to_string(&frontmatter).unwrap()).unwrap();
+    assert_eq!(round_trip.get_string("canonical_url"), Some("https://example.com/post"));
+    assert_eq!(round_trip.get_string("cover_caption"), Some("A caption"));
+    assert_eq!(round_trip.get_bool("featured"), None);
+}
+
+#[test]
+fn test_publish_date_parsing_and_display() {
+    assert_eq!("DRAFT".parse::<PublishDate>().unwrap(), PublishDate::Draft);
+    assert_eq!(
+        "2024-01-31".parse::<PublishDate>().unwrap(),
+        PublishDate::Date(chrono::NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+    );
+
+    let datetime: PublishDate = "2024-01-31T09:00:00+02:00".parse().unwrap();
+    assert_eq!(datetime.to_string(), "2024-01-31T07:00:00Z");
+    assert_eq!(datetime.date(), chrono::NaiveDate::from_ymd_opt(2024, 1, 31));
+
+    assert_eq!("2024-01-31 09:30".parse::<PublishDate>().unwrap().to_string(), "2024-01-31T09:30:00Z");
+    assert!("31/01/2024".parse::<PublishDate>().is_err());
+    assert!("2024-02-30".parse::<PublishDate>().is_err());
+}
+
+#[test]
+fn test_publish_date_ordering() {
+    let mut dates: Vec<PublishDate> = ["2024-02-01", "DRAFT", "2023-12-31T23:00:00Z", "2024-01-31", "2024-01-31T08:00:00Z"]
+        .iter()
+        .map(|date| date.parse().unwrap())
+        .collect();
+    dates.push(PublishDate::Invalid("soon".to_string()));
+    dates.sort();
+
+    let sorted: Vec<String> = dates.iter().map(ToString::to_string).collect();
+    assert_eq!(sorted, vec!["2023-12-31T23:00:00Z", "2024-01-31", "2024-01-31T08:00:00Z", "2024-02-01", "soon", "DRAFT"]);
+}
+
+#[test]
+fn test_frontmatter_dates_round_trip() {
+    let frontmatter: Frontmatter = serde_yaml::from_str("title: Post\npublished: 2024-01-31\nupdated: DRAFT\n").unwrap();
+    assert_eq!(frontmatter.published_at, Some("2024-01-31".parse().unwrap()));
+    assert_eq!(frontmatter.updated_at, Some(PublishDate::Draft));
+
+    let json = serde_json::to_value(&frontmatter).unwrap();
+    assert_eq!(json["published"], "2024-01-31");
+    assert_eq!(json["updated"], "DRAFT");
+
+    // Unreadable dates are kept for validation to report, and round trip as written
+    let frontmatter: Frontmatter = serde_yaml::from_str("title: Post\npublished: January 1, 2024\nupdated: ''\n").unwrap();
+    assert_eq!(frontmatter.published_at, Some(PublishDate::Invalid("January 1, 2024".to_string())));
+    assert!(!frontmatter.published_at.as_ref().unwrap().is_valid());
+    assert_eq!(frontmatter.updated_at, Some(PublishDate::Draft));
+    assert!(serde_yaml::to_string(&frontmatter).unwrap().contains("published: January 1, 2024"));
+}
+
+#[test]
+fn test_frontmatter_summary_override() {
+    let mut frontmatter = Frontmatter::default();
+    assert_eq!(frontmatter.summary_override(), None);
+
+    frontmatter.set_extra("tagline", "  A tagline  ");
+    assert_eq!(frontmatter.summary_override(), Some("A tagline"));
+
+    frontmatter.description = Some(" ".to_string());
+    assert_eq!(frontmatter.summary_override(), Some("A tagline"));
+
+    frontmatter.description = Some("The description".to_string());
+    assert_eq!(frontmatter.summary_override(), Some("The description"));
+}
+
+#[test]
+fn test_frontmatter_syndicated_to() {
+    let mut frontmatter = Frontmatter::default();
+    assert!(frontmatter.syndicated_to().is_empty());
+
+    frontmatter.set_extra("syndicated_to", " https://dev.to/me/post ");
+    assert_eq!(frontmatter.syndicated_to(), ["https://dev.to/me/post"]);
+
+    let urls: serde_yaml::Value = serde_yaml::from_str("[https://dev.to/me/post, '', https://medium.com/@me/post]").unwrap();
+    frontmatter.set_extra("syndicated_to", urls);
+    assert_eq!(frontmatter.syndicated_to(), ["https://dev.to/me/post", "https://medium.com/@me/post"]);
+}
+
+#[test]
+fn test_content_state_transitions() {
+    for state in ContentState::ALL {
+        assert_eq!(state.to_string().parse::<ContentState>().unwrap(), state);
+        assert!(!state.can_transition_to(state));
+    }
+    assert_eq!(" Review ".parse::<ContentState>().unwrap(), ContentState::Review);
+    assert!("pending".parse::<ContentState>().is_err());
+
+    assert!(ContentState::Review.can_transition_to(ContentState::Published));
+    assert!(!ContentState::Draft.can_transition_to(ContentState::Published));
+    assert!(!ContentState::Archived.can_transition_to(ContentState::Published));
+    assert!(ContentState::ALL.iter().all(|state| *state == ContentState::Archived
+        || state.can_transition_to(ContentState::Archived)));
+}
+
+#[test]
+fn test_frontmatter_content_state() {
+    let state_of = |yaml: &str| serde_yaml::from_str::<Frontmatter>(yaml).unwrap().content_state();
+
+    assert_eq!(state_of("title: Post\nstate: review\ndraft: true\n"), ContentState::Review);
+    assert_eq!(state_of("title: Post\npublished: 2024-01-31\ndraft: false\n"), ContentState::Published);
+    assert_eq!(state_of("title: Post\npublished: DRAFT\n"), ContentState::Draft);
+    assert_eq!(state_of("title: Post\npublished: 2024-01-31\ndraft: true\n"), ContentState::Draft);
+    assert_eq!(state_of("title: Post\npublished: 2024-01-31\narchived: true\n"), ContentState::Archived);
+    // Unknown states fall back to the inferred state
+    assert_eq!(state_of("title: Post\nstate: pending\npublished: 2024-01-31\n"), ContentState::Published);
+    assert_eq!(Frontmatter::default().content_state(), ContentState::Draft);
+}
+
+#[test]
+fn test_deploy_config_targets() {
+    let yaml = "local:\n  type: local\n  path: /var/www\nbucket:\n  type: s3\n  bucket: site\npages:\n  type: github_pages\n";
+    let targets: HashMap<String, DeployConfig> = serde_yaml::from_str(yaml).unwrap();
+
+    assert_eq!(targets["local"], DeployConfig::Local { path: "/var/www".to_string() });
+    assert_eq!(
+        targets["bucket"],
+        DeployConfig::S3 {
+            bucket: "site".to_string(),
+            region: "us-east-1".to_string(),
+            endpoint: None,
+            prefix: None,
+            manifest_key: None,
+        }
+    );
+    assert_eq!(
+        targets["pages"],
+        DeployConfig::GithubPages { remote: "origin".to_string(), branch: "gh-pages".to_string() }
+    );
+    assert!(serde_yaml::from_str::<DeployConfig>("type: ftp\n").is_err());
+}
@@ -0,0 +1,335 @@
+//! # Common Plugins
+//!
+//! Hooks that run at points in the content lifecycle, so a workflow can be
+//! extended without changing the tools: rebuild a search index after each
+//! build, notify a service when an article is created, or refuse a move
+//! that a script doesn't approve of.
+//!
+//! Hooks are listed in the `hooks` section of `config.yaml`. Tools fire
+//! events through a [`HookRegistry`] built from the configuration. A hook
+//! that fails before an operation (a `pre_*` event) stops the operation; a
+//! hook that fails afterwards is reported, since the work is already done.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use common_models::Config;
+//! use common_plugins::{HookContext, HookEvent, HookRegistry};
+//!
+//! fn build(config: &Config) -> common_errors::Result<()> {
+//!     let hooks = HookRegistry::from_config(config)?;
+//!     let context = HookContext::new(HookEvent::PreBuild);
+//!     hooks.fire(&context)?;
+//!     // ... build ...
+//!     hooks.fire(&context.with_event(HookEvent::PostBuild))
+//! }
+//! ```
+
+use common_errors::{Result, WritingError};
+use common_models::{Config, HookConfig};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+/// A point in the content lifecycle that hooks can run at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HookEvent {
+    /// Before an article is created
+    PreCreate,
+    /// After an article is created
+    PostCreate,
+    /// Before content is built
+    PreBuild,
+    /// After content is built
+    PostBuild,
+    /// Before an article is moved or renamed
+    PreMove,
+    /// After an article is deleted
+    PostDelete,
+}
+
+impl HookEvent {
+    /// Every event, in lifecycle order
+    pub const ALL: [HookEvent; 6] = [
+        HookEvent::PreCreate,
+        HookEvent::PostCreate,
+        HookEvent::PreBuild,
+        HookEvent::PostBuild,
+        HookEvent::PreMove,
+        HookEvent::PostDelete,
+    ];
+
+    /// Name of the event in the configuration
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::PreCreate => "pre_create",
+            HookEvent::PostCreate => "post_create",
+            HookEvent::PreBuild => "pre_build",
+            HookEvent::PostBuild => "post_build",
+            HookEvent::PreMove => "pre_move",
+            HookEvent::PostDelete => "post_delete",
+        }
+    }
+
+    /// Whether the event fires before the operation, so a failing hook can stop it
+    pub fn is_pre(self) -> bool {
+        matches!(self, HookEvent::PreCreate | HookEvent::PreBuild | HookEvent::PreMove)
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HookEvent {
+    type Err = WritingError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        HookEvent::ALL
+            .into_iter()
+            .find(|event| event.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = HookEvent::ALL.iter().map(|event| event.name()).collect();
+                WritingError::config_error(format!(
+                    "Unknown hook event '{}'; expected one of: {}",
+                    s,
+                    names.join(", ")
+                ))
+            })
+    }
+}
+
+/// What a hook is told about the event that fired it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookContext {
+    pub event: HookEvent,
+    /// Topic of the article, if the event is about one
+    pub topic: Option<String>,
+    /// Slug of the article, if the event is about one
+    pub slug: Option<String>,
+    /// The article directory, or the output directory for builds
+    pub path: Option<PathBuf>,
+}
+
+impl HookContext {
+    /// A context with nothing but the event
+    pub fn new(event: HookEvent) -> Self {
+        Self {
+            event,
+            topic: None,
+            slug: None,
+            path: None,
+        }
+    }
+
+    /// A context for an event about an article
+    pub fn for_article(event: HookEvent, topic: &str, slug: &str) -> Self {
+        Self {
+            topic: Some(topic.to_string()),
+            slug: Some(slug.to_string()),
+            ..Self::new(event)
+        }
+    }
+
+    /// The same context for another event
+    pub fn with_event(&self, event: HookEvent) -> Self {
+        Self { event, ..self.clone() }
+    }
+
+    /// The same context with a path
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Environment variables describing the event, for external commands
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![("WRITING_EVENT", self.event.name().to_string())];
+        if let Some(topic) = &self.topic {
+            vars.push(("WRITING_TOPIC", topic.clone()));
+        }
+        if let Some(slug) = &self.slug {
+            vars.push(("WRITING_SLUG", slug.clone()));
+        }
+        if let Some(path) = &self.path {
+            vars.push(("WRITING_PATH", path.display().to_string()));
+        }
+        vars
+    }
+}
+
+/// Something that runs when a lifecycle event fires
+pub trait ContentHook: Send + Sync {
+    /// Short description for error messages
+    fn name(&self) -> String;
+
+    /// Run the hook
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hook fails; for `pre_*` events this stops the operation
+    fn run(&self, context: &HookContext) -> Result<()>;
+}
+
+/// Runs a shell command, with the event described in `WRITING_*` environment variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandHook {
+    pub command: String,
+}
+
+impl ContentHook for CommandHook {
+    fn name(&self) -> String {
+        format!("run '{}'", self.command)
+    }
+
+    fn run(&self, context: &HookContext) -> Result<()> {
+        let mut command = shell(&self.command);
+        command.envs(context.env_vars());
+
+        let status = command
+            .status()
+            .map_err(|err| WritingError::command_error(format!("Failed to run '{}': {}", self.command, err)))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(WritingError::command_error(format!("'{}' exited with {}", self.command, status)))
+        }
+    }
+}
+
+/// Writes the current time to a file, creating it and its directory if needed
+///
+/// Other tools can watch the file, or compare its modification time, to
+/// notice that the event happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchHook {
+    pub path: PathBuf,
+}
+
+impl ContentHook for TouchHook {
+    fn name(&self) -> String {
+        format!("touch {}", self.path.display())
+    }
+
+    fn run(&self, _context: &HookContext) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, format!("{}\n", chrono::Utc::now().to_rfc3339()))?;
+        Ok(())
+    }
+}
+
+/// The hooks to run for each event
+#[derive(Default)]
+pub struct HookRegistry {
+    hooks: Vec<(HookEvent, Box<dyn ContentHook>)>,
+}
+
+impl HookRegistry {
+    /// A registry with no hooks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the hooks in the configuration
+    ///
+    /// Relative `touch` paths are relative to the content base directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hook names an unknown event or doesn't set
+    /// exactly one of `run` and `touch`
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let base_dir = Path::new(&config.content.base_dir);
+        let mut registry = Self::new();
+        for hook in &config.hooks {
+            let (event, hook) = configured_hook(hook, base_dir)?;
+            registry.register(event, hook);
+        }
+        Ok(registry)
+    }
+
+    /// Add a hook for an event; hooks for the same event run in the order they are added
+    pub fn register(&mut self, event: HookEvent, hook: Box<dyn ContentHook>) {
+        self.hooks.push((event, hook));
+    }
+
+    /// Whether no hooks are registered
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Names of the hooks registered for an event
+    pub fn hooks_for(&self, event: HookEvent) -> Vec<String> {
+        self.hooks
+            .iter()
+            .filter(|(hook_event, _)| *hook_event == event)
+            .map(|(_, hook)| hook.name())
+            .collect()
+    }
+
+    /// Run the hooks for the context's event, stopping at the first that fails
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first hook that fails
+    pub fn fire(&self, context: &HookContext) -> Result<()> {
+        for (event, hook) in &self.hooks {
+            if *event == context.event {
+                hook.run(context).map_err(|err| {
+                    err.with_context(format!("{} hook {}", context.event, hook.name()))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HookRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.hooks.iter().map(|(event, hook)| format!("{}: {}", event, hook.name())))
+            .finish()
+    }
+}
+
+/// Build the hook a configuration entry describes
+fn configured_hook(hook: &HookConfig, base_dir: &Path) -> Result<(HookEvent, Box<dyn ContentHook>)> {
+    let event: HookEvent = hook.event.parse()?;
+
+    let hook: Box<dyn ContentHook> = match (&hook.run, &hook.touch) {
+        (Some(command), None) => Box::new(CommandHook {
+            command: command.clone(),
+        }),
+        (None, Some(path)) => Box::new(TouchHook {
+            path: base_dir.join(path),
+        }),
+        _ => {
+            return Err(WritingError::config_error(format!(
+                "The {} hook must set exactly one of 'run' and 'touch'",
+                event
+            )))
+        }
+    };
+
+    Ok((event, hook))
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
@@ -0,0 +1,129 @@
+use common_errors::{Result, WritingError};
+use common_models::{Config, HookConfig};
+use common_plugins::{ContentHook, HookContext, HookEvent, HookRegistry, TouchHook};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// Records the contexts it is run with, failing if asked to
+struct RecordingHook {
+    seen: Arc<Mutex<Vec<HookContext>>>,
+    fail: bool,
+}
+
+impl ContentHook for RecordingHook {
+    fn name(&self) -> String {
+        "recording".to_string()
+    }
+
+    fn run(&self, context: &HookContext) -> Result<()> {
+        self.seen.lock().unwrap().push(context.clone());
+        if self.fail {
+            Err(WritingError::validation_error("refused"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn hook_config(event: &str, run: Option<&str>, touch: Option<&str>) -> HookConfig {
+    HookConfig {
+        event: event.to_string(),
+        run: run.map(str::to_string),
+        touch: touch.map(str::to_string),
+    }
+}
+
+fn config_with(base_dir: &str, hooks: Vec<HookConfig>) -> Config {
+    let mut config = Config::default();
+    config.content.base_dir = base_dir.to_string();
+    config.hooks = hooks;
+    config
+}
+
+#[test]
+fn test_event_names_round_trip() {
+    for event in HookEvent::ALL {
+        assert_eq!(event.name().parse::<HookEvent>().unwrap(), event);
+    }
+    assert!("pre_publish".parse::<HookEvent>().is_err());
+    assert!(HookEvent::PreMove.is_pre());
+    assert!(!HookEvent::PostDelete.is_pre());
+}
+
+#[test]
+fn test_fire_runs_only_hooks_for_the_event() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut registry = HookRegistry::new();
+    registry.register(HookEvent::PreCreate, Box::new(RecordingHook { seen: seen.clone(), fail: false }));
+
+    registry.fire(&HookContext::new(HookEvent::PostCreate)).unwrap();
+    assert!(seen.lock().unwrap().is_empty());
+
+    let context = HookContext::for_article(HookEvent::PreCreate, "blog", "hello");
+    registry.fire(&context).unwrap();
+    assert_eq!(*seen.lock().unwrap(), vec![context]);
+}
+
+#[test]
+fn test_failing_hook_stops_later_hooks() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut registry = HookRegistry::new();
+    registry.register(HookEvent::PreBuild, Box::new(RecordingHook { seen: seen.clone(), fail: true }));
+    registry.register(HookEvent::PreBuild, Box::new(RecordingHook { seen: seen.clone(), fail: false }));
+
+    let err = registry.fire(&HookContext::new(HookEvent::PreBuild)).unwrap_err();
+    assert!(err.to_string().contains("pre_build hook recording"));
+    assert_eq!(seen.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_from_config_rejects_invalid_hooks() {
+    let unknown = config_with(".", vec![hook_config("pre_publish", Some("true"), None)]);
+    assert!(HookRegistry::from_config(&unknown).is_err());
+
+    let both = config_with(".", vec![hook_config("pre_build", Some("true"), Some("stamp"))]);
+    assert!(HookRegistry::from_config(&both).is_err());
+
+    let neither = config_with(".", vec![hook_config("pre_build", None, None)]);
+    assert!(HookRegistry::from_config(&neither).is_err());
+}
+
+#[test]
+fn test_touch_hook_writes_timestamp_under_base_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let base_dir = temp_dir.path().to_str().unwrap();
+    let config = config_with(base_dir, vec![hook_config("post_build", None, Some(".stamps/built"))]);
+
+    let registry = HookRegistry::from_config(&config).unwrap();
+    assert_eq!(registry.hooks_for(HookEvent::PostBuild).len(), 1);
+    registry.fire(&HookContext::new(HookEvent::PostBuild)).unwrap();
+
+    let stamp = std::fs::read_to_string(temp_dir.path().join(".stamps/built")).unwrap();
+    assert!(chrono::DateTime::parse_from_rfc3339(stamp.trim()).is_ok());
+
+    let hook = TouchHook { path: temp_dir.path().join("other") };
+    hook.run(&HookContext::new(HookEvent::PostDelete)).unwrap();
+    assert!(temp_dir.path().join("other").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_command_hook_sees_event_and_fails_on_nonzero_exit() {
+    let temp_dir = TempDir::new().unwrap();
+    let out = temp_dir.path().join("out");
+    let command = format!("echo \"$WRITING_EVENT $WRITING_TOPIC $WRITING_SLUG\" > '{}'", out.display());
+    let config = config_with(
+        ".",
+        vec![
+            hook_config("post_create", Some(&command), None),
+            hook_config("pre_create", Some("exit 3"), None),
+        ],
+    );
+    let registry = HookRegistry::from_config(&config).unwrap();
+
+    let context = HookContext::for_article(HookEvent::PostCreate, "blog", "hello");
+    registry.fire(&context).unwrap();
+    assert_eq!(std::fs::read_to_string(&out).unwrap().trim(), "post_create blog hello");
+
+    assert!(registry.fire(&context.with_event(HookEvent::PreCreate)).is_err());
+}
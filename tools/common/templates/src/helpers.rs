@@ -0,0 +1,208 @@
+//! # Template Helpers
+//!
+//! Functions that templates can call, such as `{{date "now" "%Y-%m-%d"}}`
+//! or `{{truncate description 80}}`. Content templates call them through
+//! [`crate::Template::render`]; with the `handlebars` feature they can be
+//! registered with a Handlebars registry, so site templates like
+//! `article.hbs` have the same helpers.
+//!
+//! Arguments are quoted strings, numbers, or variable names. A helper is
+//! only called when it is given arguments, so `{{ date }}` is still the
+//! `date` variable.
+//!
+//! | Helper | Result |
+//! |--------|--------|
+//! | `date spec [format]` | `spec` (`now`, `YYYY-MM-DD` or RFC 3339) formatted with a strftime format, `%Y-%m-%d` by default |
+//! | `slugify text` | `text` as a URL slug |
+//! | `upper text` | `text` in upper case |
+//! | `truncate text length` | `text` cut to at most `length` characters, ending in `…` when cut |
+//! | `default value fallback` | `value`, or `fallback` when it is missing or empty |
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use common_errors::{Result, WritingError};
+
+/// Names of the built-in helpers
+pub const HELPERS: [&str; 5] = ["date", "slugify", "upper", "truncate", "default"];
+
+/// Whether `name` is a built-in helper
+pub fn is_helper(name: &str) -> bool {
+    HELPERS.contains(&name)
+}
+
+/// Call a built-in helper
+///
+/// Missing variables are passed as `None`.
+///
+/// # Errors
+///
+/// Returns an error if the helper is unknown, is given the wrong number of
+/// arguments, or can't use an argument
+pub fn call_helper(name: &str, args: &[Option<&str>]) -> Result<String> {
+    let arg = |index: usize| args.get(index).copied().flatten().unwrap_or_default();
+    let expect = |min: usize, max: usize| {
+        if args.len() < min || args.len() > max {
+            Err(WritingError::template_error(format!(
+                "Helper '{}' takes {} arguments, got {}",
+                name,
+                if min == max { min.to_string() } else { format!("{} to {}", min, max) },
+                args.len()
+            )))
+        } else {
+            Ok(())
+        }
+    };
+
+    match name {
+        "date" => {
+            expect(1, 2)?;
+            let format = if args.len() == 2 { arg(1) } else { "%Y-%m-%d" };
+            format_date(arg(0), format)
+        }
+        "slugify" => {
+            expect(1, 1)?;
+            Ok(slug::slugify(arg(0)))
+        }
+        "upper" => {
+            expect(1, 1)?;
+            Ok(arg(0).to_uppercase())
+        }
+        "truncate" => {
+            expect(2, 2)?;
+            let length = arg(1).parse::<usize>().map_err(|_| {
+                WritingError::template_error(format!("Helper 'truncate' needs a length, got '{}'", arg(1)))
+            })?;
+            Ok(truncate(arg(0), length))
+        }
+        "default" => {
+            expect(2, 2)?;
+            let value = arg(0);
+            Ok(if value.trim().is_empty() { arg(1) } else { value }.to_string())
+        }
+        _ => Err(WritingError::template_error(format!("Unknown helper: {}", name))),
+    }
+}
+
+/// Format a date with a strftime format
+///
+/// `spec` is `now`, a `YYYY-MM-DD` date, or an RFC 3339 timestamp.
+///
+/// # Errors
+///
+/// Returns an error if the date or the format is invalid
+pub fn format_date(spec: &str, format: &str) -> Result<String> {
+    // chrono panics when displaying an invalid format, so check it first
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(WritingError::template_error(format!("Invalid date format: {}", format)));
+    }
+
+    let date: NaiveDateTime = match spec.trim() {
+        "now" | "today" => Local::now().naive_local(),
+        spec => {
+            if let Ok(date) = DateTime::parse_from_rfc3339(spec) {
+                date.naive_local()
+            } else if let Ok(date) = NaiveDate::parse_from_str(spec, "%Y-%m-%d") {
+                date.and_hms_opt(0, 0, 0).unwrap_or_default()
+            } else {
+                return Err(WritingError::template_error(format!("Invalid date: {}", spec)));
+            }
+        }
+    };
+
+    Ok(date.format(format).to_string())
+}
+
+/// Cut `text` to at most `length` characters, ending in `…` when cut
+pub fn truncate(text: &str, length: usize) -> String {
+    if text.chars().count() <= length {
+        return text.to_string();
+    }
+    if length == 0 {
+        return String::new();
+    }
+
+    let cut: String = text.chars().take(length - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Register the built-in helpers with a Handlebars registry
+#[cfg(feature = "handlebars")]
+pub fn register_helpers(registry: &mut handlebars::Handlebars) {
+    for name in HELPERS {
+        registry.register_helper(name, Box::new(HandlebarsHelper(name)));
+    }
+}
+
+#[cfg(feature = "handlebars")]
+struct HandlebarsHelper(&'static str);
+
+#[cfg(feature = "handlebars")]
+impl handlebars::HelperDef for HandlebarsHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        helper: &handlebars::Helper<'reg, 'rc>,
+        _: &'reg handlebars::Handlebars<'reg>,
+        _: &'rc handlebars::Context,
+        _: &mut handlebars::RenderContext<'reg, 'rc>,
+    ) -> std::result::Result<handlebars::ScopedJson<'reg, 'rc>, handlebars::RenderError> {
+        use handlebars::JsonValue;
+
+        let args: Vec<Option<String>> = helper
+            .params()
+            .iter()
+            .map(|param| match param.value() {
+                JsonValue::Null => None,
+                JsonValue::String(value) => Some(value.clone()),
+                value => Some(value.to_string()),
+            })
+            .collect();
+        let args: Vec<Option<&str>> = args.iter().map(Option::as_deref).collect();
+
+        let value = call_helper(self.0, &args).map_err(|err| handlebars::RenderError::new(err.to_string()))?;
+        Ok(handlebars::ScopedJson::Derived(JsonValue::String(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_helper() {
+        assert_eq!(call_helper("date", &[Some("2024-03-09"), Some("%d %B %Y")]).unwrap(), "09 March 2024");
+        assert_eq!(call_helper("date", &[Some("2024-03-09T10:30:00Z")]).unwrap(), "2024-03-09");
+        assert_eq!(call_helper("slugify", &[Some("Hello, World!")]).unwrap(), "hello-world");
+        assert_eq!(call_helper("upper", &[Some("rust")]).unwrap(), "RUST");
+        assert_eq!(call_helper("truncate", &[Some("one two three"), Some("8")]).unwrap(), "one two…");
+        assert_eq!(call_helper("truncate", &[Some("short"), Some("80")]).unwrap(), "short");
+        assert_eq!(call_helper("default", &[None, Some("fallback")]).unwrap(), "fallback");
+        assert_eq!(call_helper("default", &[Some("value"), Some("fallback")]).unwrap(), "value");
+    }
+
+    #[test]
+    fn test_call_helper_errors() {
+        assert!(call_helper("date", &[Some("yesterday")]).is_err());
+        assert!(call_helper("date", &[Some("now"), Some("%Q")]).is_err());
+        assert!(call_helper("truncate", &[Some("text"), Some("many")]).is_err());
+        assert!(call_helper("upper", &[]).is_err());
+        assert!(call_helper("lower", &[Some("text")]).is_err());
+    }
+
+    #[cfg(feature = "handlebars")]
+    #[test]
+    fn test_handlebars_helpers() {
+        let mut handlebars = handlebars::Handlebars::new();
+        register_helpers(&mut handlebars);
+
+        let data = serde_json::json!({ "title": "Fish & Chips", "published": "2024-03-09" });
+        let rendered = handlebars
+            .render_template(
+                r#"{{upper title}}|{{slugify title}}|{{date published "%Y"}}|{{default tagline "none"}}"#,
+                &data,
+            )
+            .unwrap();
+        assert_eq!(rendered, "FISH &amp; CHIPS|fish-chips|2024|none");
+
+        assert!(handlebars.render_template(r#"{{date "never"}}"#, &data).is_err());
+    }
+}
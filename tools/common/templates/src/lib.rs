@@ -8,6 +8,9 @@
 //! - Template variable substitution
 //! - Template discovery and listing
 //! - Template file management
+//! - Template metadata and required variables, see [`metadata`]
+//! - Interactive rendering, see [`prompt`]
+//! - Date and string helpers, see [`helpers`]
 //!
 //! ## Example
 //!
@@ -16,7 +19,7 @@
 //!
 //! fn create_from_template(template_name: &str, variables: &[(&str, &str)]) -> common_errors::Result<String> {
 //!     // Load template by name
-//!     let template = load_template(template_name)?;
+//!     let mut template = load_template(template_name)?;
 //!
 //!     // Replace variables in template
 //!     let content = template.render(variables)?;
@@ -33,6 +36,13 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use common_fs::normalize::{normalize_path, join_paths};
 
+pub mod helpers;
+pub mod metadata;
+pub mod prompt;
+
+pub use metadata::{split_metadata, with_metadata, TemplateMetadata, TemplateVariable};
+pub use prompt::{ask_missing, Prompter, TemplatePrompt};
+
 /// Template structure representing a content template
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Template {
@@ -48,7 +58,15 @@ pub struct Template {
     /// Path to the template file
     pub path: PathBuf,
 
-    /// Content of the template
+    /// Variables that must be provided to render the template
+    #[serde(default)]
+    pub required_variables: Vec<String>,
+
+    /// Variables the template declares, with descriptions and defaults
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+
+    /// Content of the template, without its metadata block
     #[serde(skip)]
     content: Option<String>,
 }
@@ -61,16 +79,94 @@ impl Template {
             description: description.to_string(),
             content_type: content_type.to_string(),
             path: path.to_path_buf(),
+            required_variables: Vec::new(),
+            variables: Vec::new(),
             content: None,
         }
     }
 
-    /// Load template content
+    /// Create a template from in-memory content rather than a file
+    pub fn from_content(name: &str, description: &str, content_type: &str, content: &str) -> Self {
+        Template {
+            name: name.to_string(),
+            description: description.to_string(),
+            content_type: content_type.to_string(),
+            path: PathBuf::new(),
+            required_variables: Vec::new(),
+            variables: Vec::new(),
+            content: Some(content.to_string()),
+        }
+    }
+
+    /// Read a template file, describing it from its metadata block
+    ///
+    /// Anything the metadata leaves out is inferred from the file name, so
+    /// `note-template.mdx` is a template named `note-template` for notes.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let file_stem = path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown");
+        let content_type = file_stem.split('-').next().unwrap_or(file_stem);
+
+        let mut template = Template::new(
+            file_stem,
+            &format!("Template for {} content", content_type),
+            content_type,
+            path,
+        );
+        template.load()?;
+
+        Ok(template)
+    }
+
+    /// Load template content, applying its metadata block if it has one
     pub fn load(&mut self) -> Result<()> {
-        self.content = Some(common_fs::read_file(&self.path)?);
+        let raw = common_fs::read_file(&self.path)?;
+        let (metadata, body) = split_metadata(&raw)
+            .map_err(|e| e.with_context(format!("Template file {}", self.path.display())))?;
+
+        if let Some(metadata) = metadata {
+            self.apply_metadata(metadata);
+        }
+        self.content = Some(body.to_string());
+
         Ok(())
     }
 
+    /// Override the template's description with what its metadata declares
+    fn apply_metadata(&mut self, metadata: TemplateMetadata) {
+        if let Some(name) = metadata.name {
+            self.name = name;
+        }
+        if let Some(description) = metadata.description {
+            self.description = description;
+        }
+        if let Some(content_type) = metadata.content_type {
+            self.content_type = content_type;
+        }
+        self.required_variables = metadata.required;
+        self.variables = metadata.variables;
+    }
+
+    /// Set the variables that must be provided to render the template
+    pub fn with_required_variables<I, S>(mut self, variables: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required_variables = variables.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Required variables that are not in `variables`
+    pub fn missing_variables(&self, variables: &[(&str, &str)]) -> Vec<&str> {
+        self.required_variables
+            .iter()
+            .map(String::as_str)
+            .filter(|required| !variables.iter().any(|(name, _)| name == required))
+            .collect()
+    }
+
     /// Check if template content is loaded
     pub fn is_loaded(&self) -> bool {
         self.content.is_some()
@@ -86,32 +182,75 @@ impl Template {
     }
 
     /// Render template with variable substitutions
+    ///
+    /// Variables the template uses but that aren't provided are left as they
+    /// are, unless the template declares them required.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template cannot be loaded or a required
+    /// variable is missing
     pub fn render(&mut self, variables: &[(&str, &str)]) -> Result<String> {
-        let content = self.get_content()?;
+        // Loading reads the required variables from the metadata block
+        if !self.is_loaded() {
+            self.load()?;
+        }
+
+        let missing = self.missing_variables(variables);
+        if !missing.is_empty() {
+            return Err(WritingError::template_error(format!(
+                "Template '{}' is missing required variables: {}",
+                self.name,
+                missing.join(", ")
+            )));
+        }
+
+        let content = self.content.as_deref().unwrap_or_default();
 
         // Create a variable map for easier lookups
         let var_map: HashMap<&str, &str> = variables.iter().cloned().collect();
 
-        // Replace variables in the format {{ variable_name }}
-        let re = Regex::new(r"\{\{\s*([a-zA-Z0-9_]+)\s*\}\}")
+        // Replace variables in the format {{ variable_name }}, and helper
+        // calls in the format {{ helper arg "literal" }}
+        let re = Regex::new(r#"\{\{\s*([a-zA-Z0-9_]+)((?:\s+(?:"[^"]*"|[^\s"{}]+))*)\s*\}\}"#)
+            .map_err(|e| WritingError::format_error(format!("Failed to compile regex: {}", e)))?;
+        let arg_re = Regex::new(r#""([^"]*)"|(\S+)"#)
             .map_err(|e| WritingError::format_error(format!("Failed to compile regex: {}", e)))?;
 
         // Functional approach using fold instead of imperative loop with mutable state
         let result = re.captures_iter(content)
-            .fold((String::new(), 0), |(mut output, last_end), cap| {
+            .try_fold((String::new(), 0), |(mut output, last_end), cap| -> Result<(String, usize)> {
                 let whole_match = cap.get(0).unwrap();
-                let var_name = cap.get(1).unwrap().as_str();
+                let name = cap.get(1).unwrap().as_str();
+                let args = cap.get(2).map_or("", |m| m.as_str()).trim();
 
                 // Add everything up to this match
                 output.push_str(&content[last_end..whole_match.start()]);
 
-                // Add the variable replacement or the original if not found
-                let replacement = var_map.get(var_name).copied()
-                    .unwrap_or(whole_match.as_str());
-                output.push_str(replacement);
+                if args.is_empty() {
+                    // Add the variable replacement or the original if not found
+                    let replacement = var_map.get(name).copied()
+                        .unwrap_or(whole_match.as_str());
+                    output.push_str(replacement);
+                } else if helpers::is_helper(name) {
+                    // Quoted arguments and numbers are literals, anything else is a variable
+                    let args: Vec<Option<&str>> = arg_re.captures_iter(args)
+                        .map(|arg| match (arg.get(1), arg.get(2)) {
+                            (Some(literal), _) => Some(literal.as_str()),
+                            (_, Some(word)) if word.as_str().parse::<f64>().is_ok() => Some(word.as_str()),
+                            (_, Some(word)) => var_map.get(word.as_str()).copied(),
+                            _ => None,
+                        })
+                        .collect();
+                    let value = helpers::call_helper(name, &args)
+                        .map_err(|e| e.with_context(format!("Template '{}'", self.name)))?;
+                    output.push_str(&value);
+                } else {
+                    output.push_str(whole_match.as_str());
+                }
 
-                (output, whole_match.end())
-            });
+                Ok((output, whole_match.end()))
+            })?;
 
         // Add any remaining content after the last match
         let (mut output, last_end) = result;
@@ -166,33 +305,28 @@ pub fn list_templates() -> Result<Vec<Template>> {
         return Err(WritingError::directory_not_found(&templates_dir));
     }
 
-    // List all .mdx files in the templates directory
-    for entry in fs::read_dir(&templates_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_file() &&
-           path.extension().is_some_and(|ext| ext == "mdx" || ext == "md") &&
-           path.file_name().is_some_and(|name| name.to_string_lossy().contains("template"))
-        {
-            // Extract template information from filename
-            let file_stem = path.file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown");
-
-            let parts: Vec<&str> = file_stem.split('-').collect();
-            if parts.len() >= 2 && parts.last().unwrap() == &"template" {
-                let content_type = parts[0];
-                let template_name = file_stem.to_string();
-                let description = format!("Template for {} content", content_type);
-
-                templates.push(Template::new(
-                    &template_name,
-                    &description,
-                    content_type,
-                    &path
-                ));
-            }
+    // Templates are Markdown files that describe themselves in a metadata
+    // block, or that follow the older `{content_type}-template` naming
+    let mut paths: Vec<PathBuf> = fs::read_dir(&templates_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    paths.sort();
+
+    for path in paths {
+        if !path.is_file() || !path.extension().is_some_and(|ext| ext == "mdx" || ext == "md") {
+            continue;
+        }
+
+        let raw = common_fs::read_file(&path)?;
+        let (metadata, _) = split_metadata(&raw)
+            .map_err(|e| e.with_context(format!("Template file {}", path.display())))?;
+
+        let named_template = path.file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|stem| stem.split('-').count() >= 2 && stem.ends_with("-template"));
+
+        if metadata.is_some() || named_template {
+            templates.push(Template::from_file(&path)?);
         }
     }
 
@@ -214,9 +348,13 @@ pub fn list_templates() -> Result<Vec<Template>> {
 pub fn load_template(name: &str) -> Result<Template> {
     let templates = list_templates()?;
 
-    // Find template by name
+    // Find template by name, or by file name for templates that declare another name
     let template = templates.into_iter()
-        .find(|t| t.name == name || t.name.contains(name))
+        .find(|t| {
+            t.name == name
+                || t.name.contains(name)
+                || t.path.file_stem().is_some_and(|stem| stem == name)
+        })
         .ok_or_else(|| WritingError::format_error(format!("Template not found: {}", name)))?;
 
     // Load template content
@@ -267,11 +405,18 @@ pub fn create_template(name: &str, content_type: &str, content: &str) -> Result<
     let filename = format!("{}-template.mdx", sanitized_name);
     let path = templates_dir.join(&filename);
 
-    // Write template content
-    common_fs::write_file(&path, content)?;
+    // Write template content, described by a metadata block
+    let description = format!("Template for {} content", content_type);
+    let metadata = TemplateMetadata {
+        name: Some(sanitized_name.clone()),
+        description: Some(description.clone()),
+        content_type: Some(content_type.to_string()),
+        required: Vec::new(),
+        variables: Vec::new(),
+    };
+    common_fs::write_file(&path, &with_metadata(&metadata, content)?)?;
 
     // Create and return template object
-    let description = format!("Template for {} content", content_type);
     let template = Template::new(
         &sanitized_name,
         &description,
@@ -395,4 +540,145 @@ This is the main content.
         assert!(rendered.contains("description: \"{{ description }}\""));
         assert!(rendered.contains("topics: [\"{{ topic }}\"]"));
     }
-}
\ No newline at end of file
+
+    const TEMPLATE_WITH_METADATA: &str = r#"---
+template:
+  name: essay
+  description: A short essay
+  content_type: article
+  required: [title, slug]
+---
+---
+title: "{{ title }}"
+slug: "{{ slug }}"
+---
+
+{{ introduction }}
+"#;
+
+    #[test]
+    fn test_split_metadata() {
+        let (metadata, body) = split_metadata(TEMPLATE_WITH_METADATA).unwrap();
+        let metadata = metadata.unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("essay"));
+        assert_eq!(metadata.content_type.as_deref(), Some("article"));
+        assert_eq!(metadata.required, vec!["title", "slug"]);
+        assert!(body.starts_with("---\ntitle:"));
+
+        // Content frontmatter on its own is not metadata
+        let (metadata, body) = split_metadata(body).unwrap();
+        assert!(metadata.is_none());
+        assert!(body.starts_with("---\ntitle:"));
+
+        assert!(split_metadata("---\ntemplate: [unclosed\n---\n").is_err());
+    }
+
+    #[test]
+    fn test_from_file_reads_metadata() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("long-form.md");
+        fs::write(&path, TEMPLATE_WITH_METADATA).unwrap();
+
+        let mut template = Template::from_file(&path).unwrap();
+        assert_eq!(template.name, "essay");
+        assert_eq!(template.description, "A short essay");
+        assert_eq!(template.content_type, "article");
+        assert_eq!(template.required_variables, vec!["title", "slug"]);
+        assert!(!template.get_content().unwrap().contains("template:"));
+
+        // Without metadata, everything comes from the file name
+        let path = temp_dir.path().join("note-template.mdx");
+        fs::write(&path, "{{ content }}").unwrap();
+        let template = Template::from_file(&path).unwrap();
+        assert_eq!(template.name, "note-template");
+        assert_eq!(template.content_type, "note");
+        assert!(template.required_variables.is_empty());
+    }
+
+    #[test]
+    fn test_render_requires_declared_variables() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("essay.md");
+        fs::write(&path, TEMPLATE_WITH_METADATA).unwrap();
+
+        let mut template = Template::new("essay", "", "article", &path);
+        let err = template.render(&[("introduction", "Hello")]).unwrap_err();
+        assert!(err.to_string().contains("missing required variables: title, slug"));
+
+        let rendered = template.render(&[("title", "Essay"), ("slug", "essay")]).unwrap();
+        assert!(rendered.starts_with("---\ntitle: \"Essay\""));
+        assert!(rendered.contains("{{ introduction }}"));
+
+        let mut template = Template::from_content("inline", "", "note", "{{ body }}")
+            .with_required_variables(["body"]);
+        assert_eq!(template.missing_variables(&[]), vec!["body"]);
+        assert_eq!(template.render(&[("body", "Text")]).unwrap(), "Text");
+    }
+
+    /// Answers prompts from a script, recording what was asked
+    struct ScriptedPrompter {
+        answers: Vec<&'static str>,
+        asked: Vec<String>,
+    }
+
+    impl Prompter for ScriptedPrompter {
+        fn ask(&mut self, prompt: &TemplatePrompt) -> Result<String> {
+            self.asked.push(prompt.name.clone());
+            Ok(self.answers.remove(0).to_string())
+        }
+    }
+
+    #[test]
+    fn test_prompts_and_interactive_rendering() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("essay.md");
+        fs::write(&path, r#"---
+template:
+  required: [title, slug]
+  variables:
+    - name: title
+      description: Title of the essay
+    - name: tags
+      default: essay
+---
+{{ title }}|{{ slug }}|{{ tags }}
+"#).unwrap();
+
+        let template = Template::from_file(&path).unwrap();
+        let prompts = template.prompts();
+        let names: Vec<&str> = prompts.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["title", "tags", "slug"]);
+        assert_eq!(prompts[0].description, "Title of the essay");
+        assert!(prompts[0].required);
+        assert_eq!(prompts[1].default.as_deref(), Some("essay"));
+        assert!(!prompts[1].required);
+
+        // Provided variables aren't asked for, and empty answers take the default
+        let mut template = template;
+        let mut prompter = ScriptedPrompter { answers: vec!["Why", ""], asked: Vec::new() };
+        let rendered = template.render_interactive(&[("slug", "why")], &mut prompter).unwrap();
+        assert_eq!(rendered.trim(), "Why|why|essay");
+        assert_eq!(prompter.asked, vec!["title", "tags"]);
+
+        // An empty answer for a required variable without a default fails to render
+        let mut prompter = ScriptedPrompter { answers: vec!["", "", ""], asked: Vec::new() };
+        let err = template.render_interactive(&[], &mut prompter).unwrap_err();
+        assert!(err.to_string().contains("title, slug"));
+    }
+
+    #[test]
+    fn test_render_calls_helpers() {
+        let mut template = Template::from_content(
+            "helpers",
+            "",
+            "note",
+            r#"{{ date }}|{{date "2024-03-09" "%Y"}}|{{ slugify title }}|{{truncate title 5}}|{{default tagline "none"}}|{{ lower title }}"#,
+        );
+
+        let rendered = template.render(&[("date", "today"), ("title", "Hello World")]).unwrap();
+        assert_eq!(rendered, "today|2024|hello-world|Hell…|none|{{ lower title }}");
+
+        let mut template = Template::from_content("broken", "", "note", r#"{{date "soon"}}"#);
+        assert!(template.render(&[]).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! # Template Metadata
+//!
+//! A template file can describe itself in a leading YAML block holding a
+//! single `template` mapping. The block is removed before rendering, so the
+//! frontmatter of the content the template produces follows it unchanged:
+//!
+//! ```text
+//! ---
+//! template:
+//!   name: article
+//!   description: A long-form article
+//!   content_type: article
+//!   required: [title, slug, topic]
+//!   variables:
+//!     - name: title
+//!       description: Title of the article
+//!     - name: tags
+//!       default: draft
+//! ---
+//! ---
+//! title: "{{ title }}"
+//! ---
+//! ```
+//!
+//! A leading block without a `template` key is the content's own
+//! frontmatter and is left alone.
+
+use common_errors::{Result, WritingError};
+use serde::{Deserialize, Serialize};
+
+/// What a template says about itself
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateMetadata {
+    /// Name used to select the template; defaults to the file name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// One-line description shown when listing templates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Content type the template is for (article, note, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    /// Variables that must be provided to render the template
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+
+    /// Variables worth asking for, in the order to ask
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<TemplateVariable>,
+}
+
+/// A variable a template declares, with what to tell someone filling it in
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+
+    /// What the variable is for, shown when asking for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Value used when the answer is left empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MetadataBlock {
+    template: TemplateMetadata,
+}
+
+#[derive(Serialize)]
+struct MetadataBlockRef<'a> {
+    template: &'a TemplateMetadata,
+}
+
+/// Split a template file into its metadata, if it has any, and its body
+///
+/// # Errors
+///
+/// Returns an error if the metadata block isn't valid YAML
+pub fn split_metadata(raw: &str) -> Result<(Option<TemplateMetadata>, &str)> {
+    let Some(rest) = raw.strip_prefix("---\n").or_else(|| raw.strip_prefix("---\r\n")) else {
+        return Ok((None, raw));
+    };
+
+    // Find the line that closes the block
+    let mut offset = 0;
+    let mut close = None;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            close = Some((offset, offset + line.len()));
+            break;
+        }
+        offset += line.len();
+    }
+    let Some((yaml_end, body_start)) = close else {
+        return Ok((None, raw));
+    };
+
+    // Top-level keys are the unindented lines
+    let yaml = &rest[..yaml_end];
+    if !yaml.lines().any(|line| line.starts_with("template:")) {
+        return Ok((None, raw));
+    }
+
+    let block: MetadataBlock = serde_yaml::from_str(yaml)
+        .map_err(|e| WritingError::template_error(format!("Invalid template metadata: {}", e)))?;
+
+    Ok((Some(block.template), &rest[body_start..]))
+}
+
+/// Prefix a template body with a metadata block
+///
+/// # Errors
+///
+/// Returns an error if the metadata cannot be serialized
+pub fn with_metadata(metadata: &TemplateMetadata, body: &str) -> Result<String> {
+    let yaml = serde_yaml::to_string(&MetadataBlockRef { template: metadata })
+        .map_err(|e| WritingError::template_error(format!("Failed to serialize template metadata: {}", e)))?;
+    Ok(format!("---\n{}---\n{}", yaml, body))
+}
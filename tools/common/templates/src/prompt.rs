@@ -0,0 +1,152 @@
+//! # Template Prompts
+//!
+//! Rendering a template interactively: the variables a template declares
+//! become prompts, and a [`Prompter`] supplies the answers. The command
+//! lines ask on the terminal; tests answer from a script.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use common_templates::{Prompter, Template, TemplatePrompt};
+//!
+//! struct Always(&'static str);
+//!
+//! impl Prompter for Always {
+//!     fn ask(&mut self, _prompt: &TemplatePrompt) -> common_errors::Result<String> {
+//!         Ok(self.0.to_string())
+//!     }
+//! }
+//!
+//! let mut template = Template::from_content("greeting", "", "note", "Hello, {{ name }}")
+//!     .with_required_variables(["name"]);
+//! let rendered = template.render_interactive(&[], &mut Always("world"))?;
+//! assert_eq!(rendered, "Hello, world");
+//! # Ok::<(), common_errors::WritingError>(())
+//! ```
+
+use common_errors::Result;
+
+use crate::{Template, TemplateVariable};
+
+/// A question for one template variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplatePrompt {
+    /// Name of the variable
+    pub name: String,
+    /// What to show when asking; the variable name if nothing is declared
+    pub description: String,
+    /// Value used when the answer is left empty
+    pub default: Option<String>,
+    /// Whether rendering fails without a value
+    pub required: bool,
+}
+
+impl TemplatePrompt {
+    /// A prompt for a variable with no declared description or default
+    pub fn new(name: &str, required: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            description: name.to_string(),
+            default: None,
+            required,
+        }
+    }
+
+    fn declared(variable: &TemplateVariable, required: bool) -> Self {
+        Self {
+            name: variable.name.clone(),
+            description: variable.description.clone().unwrap_or_else(|| variable.name.clone()),
+            default: variable.default.clone(),
+            required,
+        }
+    }
+
+    /// The answer to use, falling back to the default when it is empty
+    pub fn resolve(&self, answer: &str) -> Option<String> {
+        let answer = answer.trim();
+        if answer.is_empty() {
+            self.default.clone()
+        } else {
+            Some(answer.to_string())
+        }
+    }
+}
+
+/// Something that can answer template prompts
+pub trait Prompter {
+    /// Ask for a value; an empty answer takes the prompt's default
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no answer can be read
+    fn ask(&mut self, prompt: &TemplatePrompt) -> Result<String>;
+}
+
+/// Ask for every prompt not already in `provided`, returning the answers
+///
+/// Prompts left empty without a default are skipped, so rendering reports
+/// them if they are required.
+///
+/// # Errors
+///
+/// Returns an error if the prompter fails
+pub fn ask_missing(
+    prompts: &[TemplatePrompt],
+    provided: &[(&str, &str)],
+    prompter: &mut dyn Prompter,
+) -> Result<Vec<(String, String)>> {
+    let mut answers = Vec::new();
+    for prompt in prompts {
+        if provided.iter().any(|(name, _)| *name == prompt.name) {
+            continue;
+        }
+        if let Some(value) = prompt.resolve(&prompter.ask(prompt)?) {
+            answers.push((prompt.name.clone(), value));
+        }
+    }
+    Ok(answers)
+}
+
+impl Template {
+    /// Prompts for the template's variables
+    ///
+    /// Declared variables come first, in the order they are declared, then
+    /// any required variable that isn't declared.
+    pub fn prompts(&self) -> Vec<TemplatePrompt> {
+        let is_required = |name: &str| self.required_variables.iter().any(|required| required == name);
+
+        let mut prompts: Vec<TemplatePrompt> = self
+            .variables
+            .iter()
+            .map(|variable| TemplatePrompt::declared(variable, is_required(&variable.name)))
+            .collect();
+
+        for required in &self.required_variables {
+            if !prompts.iter().any(|prompt| &prompt.name == required) {
+                prompts.push(TemplatePrompt::new(required, true));
+            }
+        }
+
+        prompts
+    }
+
+    /// Render the template, asking `prompter` for any variable not in `provided`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template cannot be loaded, the prompter
+    /// fails, or a required variable is still missing
+    pub fn render_interactive(&mut self, provided: &[(&str, &str)], prompter: &mut dyn Prompter) -> Result<String> {
+        // Loading reads the declared variables from the metadata block
+        if !self.is_loaded() {
+            self.load()?;
+        }
+
+        let answers = ask_missing(&self.prompts(), provided, prompter)?;
+
+        let mut variables = provided.to_vec();
+        variables.extend(answers.iter().map(|(name, value)| (name.as_str(), value.as_str())));
+
+        self.render(&variables)
+    }
+}
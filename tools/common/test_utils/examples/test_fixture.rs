@@ -44,6 +44,15 @@ impl TestFixture {
                 copyright: "Test Copyright".to_string(),
                 site_url: None,
             },
+            hooks: Vec::new(),
+            i18n: Default::default(),
+            deploy: Default::default(),
+            export: Default::default(),
+            import: Default::default(),
+            newsletter: Default::default(),
+            typography: Default::default(),
+            link_policy: Default::default(),
+            og_image: Default::default(),
         };
 
         let config_loader = MockConfigLoader::new(config);
@@ -99,8 +99,8 @@ pub fn assert_frontmatter(frontmatter: &Frontmatter, expected: &[(&str, &str)])
         match *key {
             "title" => assert_eq!(frontmatter.title, *value, "Frontmatter title did not match"),
             "description" => assert_eq!(frontmatter.description.as_deref().unwrap_or(""), *value, "Frontmatter description did not match"),
-            "published_at" => assert_eq!(frontmatter.published_at.as_deref().unwrap_or(""), *value, "Frontmatter published_at did not match"),
-            "updated_at" => assert_eq!(frontmatter.updated_at.as_deref().unwrap_or(""), *value, "Frontmatter updated_at did not match"),
+            "published_at" => assert_eq!(frontmatter.published_at.as_ref().map(ToString::to_string).unwrap_or_default(), *value, "Frontmatter published_at did not match"),
+            "updated_at" => assert_eq!(frontmatter.updated_at.as_ref().map(ToString::to_string).unwrap_or_default(), *value, "Frontmatter updated_at did not match"),
             "slug" => assert_eq!(frontmatter.slug.as_deref().unwrap_or(""), *value, "Frontmatter slug did not match"),
             _ => panic!("Unsupported frontmatter field: {}", key),
         }
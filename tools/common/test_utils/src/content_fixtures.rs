@@ -0,0 +1,88 @@
+//! # Content Fixtures
+//!
+//! Helpers for tests that lay out a small content repository by hand: a
+//! config with a few topics, the articles they need, and `CONFIG_PATH`
+//! pointed at the result. For generated repositories with many articles, see
+//! [`crate::RepoBuilder`].
+//!
+//! ```rust
+//! use common_test_utils::{use_config, write_article, write_repository};
+//!
+//! let temp_dir = tempfile::tempdir().unwrap();
+//! let config_path = write_repository(temp_dir.path(), &["blog"]);
+//! write_article(&temp_dir.path().join("content"), "blog", "hello", "---\ntitle: Hello\n---\n");
+//!
+//! let _config = use_config(&config_path);
+//! assert!(temp_dir.path().join("content/blog/hello/hello.md").exists());
+//! ```
+
+use common_models::{Config, TopicConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Held while a test has `CONFIG_PATH` set
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Configuration reading content from `base_dir`, with a topic for each key
+///
+/// Each topic is named after its key and kept in a directory of the same name.
+pub fn config_for(base_dir: &Path, topics: &[&str]) -> Config {
+    let mut config = Config::default();
+    config.content.base_dir = base_dir.to_string_lossy().into_owned();
+    config.content.topics.clear();
+    for topic in topics {
+        config.content.topics.insert(topic.to_string(), TopicConfig {
+            name: topic.to_string(),
+            description: String::new(),
+            directory: topic.to_string(),
+        });
+    }
+    config
+}
+
+/// Write `config` to `root/config.yaml`, returning the file's path
+pub fn write_config(root: &Path, config: &Config) -> PathBuf {
+    let path = root.join("config.yaml");
+    fs::write(&path, serde_yaml::to_string(config).unwrap()).unwrap();
+    path
+}
+
+/// Write the config of a repository with content under `root/content`
+///
+/// Returns the config file's path, ready for [`use_config`].
+pub fn write_repository(root: &Path, topics: &[&str]) -> PathBuf {
+    write_config(root, &config_for(&root.join("content"), topics))
+}
+
+/// Write an article to `base_dir/{topic_dir}/{slug}/{slug}.md`, returning the file's path
+pub fn write_article(base_dir: &Path, topic_dir: &str, slug: &str, content: &str) -> PathBuf {
+    let path = base_dir.join(topic_dir).join(slug).join(format!("{}.md", slug));
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, content).unwrap();
+    path
+}
+
+/// Keeps `CONFIG_PATH` pointed at a test's repository until dropped
+///
+/// The environment is shared by every test in a binary, so the guard also
+/// holds a lock that keeps other tests using it waiting.
+pub struct ConfigPathGuard {
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Drop for ConfigPathGuard {
+    fn drop(&mut self) {
+        std::env::remove_var("CONFIG_PATH");
+    }
+}
+
+/// Point `CONFIG_PATH` at a config file for the rest of a test
+///
+/// Waits for any other test holding a [`ConfigPathGuard`] to finish first.
+pub fn use_config(config_path: &Path) -> ConfigPathGuard {
+    // A test that panicked while holding the lock left nothing to clean up
+    let lock = CONFIG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    std::env::set_var("CONFIG_PATH", config_path);
+    ConfigPathGuard { _lock: lock }
+}
@@ -0,0 +1,318 @@
+//! # End-to-End Harness
+//!
+//! This module runs the real tool binaries one after another against a
+//! repository built by [`RepoBuilder`](crate::RepoBuilder), so tests can check
+//! that what one tool writes is what the next one expects: that search finds
+//! content created a step earlier, or that the sitemap follows a move.
+//!
+//! Every step runs with `CONFIG_PATH` pointing at the repository's config and
+//! `TEST_MODE` set, so tools skip their interactive prompts. Steps are
+//! recorded, and a failed step or invariant reports the steps before it.
+//!
+//! ```rust,no_run
+//! use common_test_utils::e2e::E2eHarness;
+//! use common_test_utils::RepoBuilder;
+//!
+//! let repo = RepoBuilder::new().topic("blog").articles_per_topic(2).build().unwrap();
+//! let mut harness = E2eHarness::new(repo);
+//!
+//! harness.build("first build").unwrap();
+//! harness.assert_sitemap_contains("/blog/blog-article-1");
+//! ```
+
+use crate::integration::find_binary;
+use crate::repo_builder::TestRepo;
+use common_errors::{Result, WritingError};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Every tool the full workflow runs
+pub const WORKFLOW_TOOLS: &[&str] = &[
+    "content-new",
+    "content-edit",
+    "content-validate",
+    "content-build",
+    "content-search",
+    "content-move",
+];
+
+/// Output directory the harness builds into, relative to the repository root
+pub const OUTPUT_DIR: &str = "public";
+
+/// Search index directory, relative to the repository root
+pub const INDEX_DIR: &str = ".search-index";
+
+/// A tool run by the harness
+#[derive(Debug)]
+pub struct Step {
+    /// What the step was for
+    pub name: String,
+    /// The tool that was run
+    pub tool: String,
+    /// Arguments passed to the tool
+    pub args: Vec<String>,
+    /// Whatever the tool printed and its exit status
+    pub output: Output,
+}
+
+impl Step {
+    /// Get standard output as text
+    pub fn stdout(&self) -> String {
+        String::from_utf8_lossy(&self.output.stdout).into_owned()
+    }
+
+    /// Get standard error as text
+    pub fn stderr(&self) -> String {
+        String::from_utf8_lossy(&self.output.stderr).into_owned()
+    }
+}
+
+/// Runs tools in sequence against a test repository
+pub struct E2eHarness {
+    /// The repository the tools work on
+    pub repo: TestRepo,
+    /// Steps run so far
+    steps: Vec<Step>,
+}
+
+impl E2eHarness {
+    /// Create a harness for a repository
+    pub fn new(repo: TestRepo) -> Self {
+        Self { repo, steps: Vec::new() }
+    }
+
+    /// Find the tools that have not been built
+    ///
+    /// The tools are only present after `cargo build --workspace`, so suites
+    /// assert this is empty to fail with a clear message.
+    pub fn missing_tools(tools: &[&str]) -> Vec<String> {
+        tools
+            .iter()
+            .filter(|tool| find_binary(tool).is_none())
+            .map(|tool| tool.to_string())
+            .collect()
+    }
+
+    /// Get the steps run so far
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Get the directory the site is built into
+    pub fn output_dir(&self) -> PathBuf {
+        self.repo.path().join(OUTPUT_DIR)
+    }
+
+    /// Run a tool in the repository, failing if it exits unsuccessfully
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tool cannot be found or run, or exits with a
+    /// non-zero status
+    pub fn run(&mut self, name: &str, tool: &str, args: &[&str]) -> Result<&Step> {
+        self.run_with_env(name, tool, args, &[])
+    }
+
+    /// Run a tool with extra environment variables
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tool cannot be found or run, or exits with a
+    /// non-zero status
+    pub fn run_with_env(&mut self, name: &str, tool: &str, args: &[&str], env: &[(&str, &Path)]) -> Result<&Step> {
+        let path = find_binary(tool)
+            .ok_or_else(|| WritingError::command_error(format!("Command executable not found: {}", tool)))?;
+
+        let output = Command::new(path)
+            .args(args)
+            .current_dir(self.repo.path())
+            .env("CONFIG_PATH", &self.repo.config_path)
+            .env("TEST_MODE", "1")
+            .env("NO_COLOR", "1")
+            .envs(env.iter().copied())
+            .output()?;
+
+        self.steps.push(Step {
+            name: name.to_string(),
+            tool: tool.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            output,
+        });
+
+        let step = &self.steps[self.steps.len() - 1];
+        if !step.output.status.success() {
+            return Err(WritingError::command_error(format!(
+                "Step '{}' failed with {}\nstdout: {}\nstderr: {}\n{}",
+                name,
+                step.output.status,
+                step.stdout(),
+                step.stderr(),
+                self.history()
+            )));
+        }
+
+        Ok(step)
+    }
+
+    /// Create an article with content-new
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if content-new fails
+    pub fn create(&mut self, topic: &str, title: &str, tags: &str) -> Result<&Step> {
+        self.run(
+            &format!("create '{}'", title),
+            "content-new",
+            &["--topic", topic, "--title", title, "--description", title, "--tags", tags],
+        )
+    }
+
+    /// Append text to an article with content-edit
+    ///
+    /// The editor content-edit opens is a script that appends `text` to the
+    /// file it is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the editor script cannot be written or content-edit fails
+    #[cfg(unix)]
+    pub fn append(&mut self, topic: &str, slug: &str, text: &str) -> Result<&Step> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let editor = self.repo.path().join(format!("editor-{}.sh", self.steps.len()));
+        let script = format!("#!/bin/sh\ncat >> \"$1\" <<'EOF'\n\n{}\nEOF\n", text);
+        fs::write(&editor, script)?;
+        fs::set_permissions(&editor, fs::Permissions::from_mode(0o755))?;
+
+        self.run_with_env(
+            &format!("edit {}/{}", topic, slug),
+            "content-edit",
+            &["--topic", topic, "--slug", slug],
+            &[("EDITOR", editor.as_path())],
+        )
+    }
+
+    /// Validate every article with content-validate
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if content-validate reports errors or fails to run
+    pub fn validate(&mut self, name: &str) -> Result<&Step> {
+        self.run(name, "content-validate", &["--skip-external-links"])
+    }
+
+    /// Build the site with content-build
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if content-build fails
+    pub fn build(&mut self, name: &str) -> Result<&Step> {
+        self.run(name, "content-build", &["--output-dir", OUTPUT_DIR])
+    }
+
+    /// Search with content-search, rebuilding the index first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if content-search fails
+    pub fn search(&mut self, query: &str) -> Result<&Step> {
+        self.run(
+            &format!("search '{}'", query),
+            "content-search",
+            &["search", query, "--index-path", INDEX_DIR, "--rebuild"],
+        )
+    }
+
+    /// Move an article with content-move
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if content-move fails
+    pub fn move_article(&mut self, topic: &str, slug: &str, new_topic: &str, new_slug: &str) -> Result<&Step> {
+        self.run(
+            &format!("move {}/{} to {}/{}", topic, slug, new_topic, new_slug),
+            "content-move",
+            &["--topic", topic, "--slug", slug, "--new-topic", new_topic, "--new-slug", new_slug],
+        )
+    }
+
+    /// Find the content file of an article
+    pub fn article_file(&self, topic: &str, slug: &str) -> Option<PathBuf> {
+        let topic_dir = &self.repo.config.content.topics.get(topic)?.directory;
+        let article_dir = self.repo.content_dir.join(topic_dir).join(slug);
+        ["md", "mdx"]
+            .iter()
+            .map(|extension| article_dir.join(format!("{}.{}", slug, extension)))
+            .chain(["index.md", "index.mdx"].iter().map(|name| article_dir.join(name)))
+            .find(|path| path.is_file())
+    }
+
+    /// Read the built sitemap
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sitemap hasn't been built
+    pub fn sitemap(&self) -> Result<String> {
+        Ok(fs::read_to_string(self.output_dir().join("sitemap.xml"))?)
+    }
+
+    /// Assert that the sitemap has a URL ending in `path`
+    pub fn assert_sitemap_contains(&self, path: &str) {
+        let sitemap = self.sitemap().unwrap_or_else(|e| panic!("{}\n{}", e, self.history()));
+        assert!(
+            sitemap_has(&sitemap, path),
+            "Sitemap has no URL for {}\nSitemap: {}\n{}",
+            path,
+            sitemap,
+            self.history()
+        );
+    }
+
+    /// Assert that the sitemap has no URL ending in `path`
+    pub fn assert_sitemap_lacks(&self, path: &str) {
+        let sitemap = self.sitemap().unwrap_or_else(|e| panic!("{}\n{}", e, self.history()));
+        assert!(
+            !sitemap_has(&sitemap, path),
+            "Sitemap still has a URL for {}\nSitemap: {}\n{}",
+            path,
+            sitemap,
+            self.history()
+        );
+    }
+
+    /// Assert that searching for `query` lists `slug`
+    pub fn assert_search_finds(&mut self, query: &str, slug: &str) {
+        let found = match self.search(query) {
+            Ok(step) => step.stdout().contains(slug),
+            Err(e) => panic!("{}", e),
+        };
+        assert!(found, "Search for '{}' did not find {}\n{}", query, slug, self.history());
+    }
+
+    /// Describe the steps run so far, for failure messages
+    pub fn history(&self) -> String {
+        let mut history = String::from("Steps:\n");
+        for (index, step) in self.steps.iter().enumerate() {
+            history.push_str(&format!(
+                "{}. {} ({} {}): {}\n",
+                index + 1,
+                step.name,
+                step.tool,
+                step.args.join(" "),
+                step.output.status
+            ));
+        }
+        history
+    }
+}
+
+/// Check whether a sitemap lists a URL whose path ends with `path`
+fn sitemap_has(sitemap: &str, path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    sitemap
+        .split("<loc>")
+        .skip(1)
+        .filter_map(|rest| rest.split("</loc>").next())
+        .any(|url| url.trim().trim_end_matches('/').ends_with(path))
+}
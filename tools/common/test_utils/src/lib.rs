@@ -13,6 +13,9 @@
 //! - Specialized test fixtures for validation and file system testing
 //! - Standard assertion helpers for common test patterns
 //! - Test environment setup helpers
+//! - Multi-topic content repositories built in one chained call
+//! - Hand-built content repositories and a guard for `CONFIG_PATH`
+//! - End-to-end runs of the tools against a shared repository
 //! - Test helper macros for common patterns
 //!
 //! ## Example
@@ -45,12 +48,17 @@ pub mod fixtures;
 pub mod assertions;
 pub mod test_environment;
 pub mod macros;
+pub mod e2e;
+pub mod repo_builder;
+pub mod content_fixtures;
 
 // Also re-export key fixtures for easier access
 pub use fixtures::{ValidationFixture, FileSystemFixture, TestFixture};
 pub use test_environment::{TestEnvironment, TestEnvironmentConfig, with_test_environment, with_custom_test_environment};
 pub use assertions::*;
 pub use proptest::TestScenario;
+pub use repo_builder::{RepoArticle, RepoBuilder, RepoTopic, TestRepo};
+pub use content_fixtures::{config_for, use_config, write_article, write_config, write_repository, ConfigPathGuard};
 
 // Re-export key mocks for easier access
 pub use mocks::{
@@ -58,6 +66,8 @@ pub use mocks::{
     FileSystem, MockFileSystem,
     // Config mocks
     ConfigLoader, MockConfigLoader,
+    // Content repository mocks
+    InMemoryArticle, InMemoryContentRepository,
     // Tool mocks
     ContentCreator, ContentEditor, ContentValidator, ContentSearcher,
     ContentMover, ContentDeleter
@@ -74,6 +84,42 @@ pub mod integration {
     use std::io::{self, BufRead, BufReader, Write};
     use std::process::{Child, Stdio, Output, Command};
 
+    /// Find a tool executable in the target directory
+    ///
+    /// Looks in `target/debug` under the current directory and up to three of
+    /// its parents, then under `CARGO_TARGET_DIR`.
+    pub fn find_binary(name: &str) -> Option<PathBuf> {
+        // Get the current working directory
+        let current_dir = std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."));
+
+        // Find the command in the target directory
+        let target_dir = std::env::var("CARGO_TARGET_DIR")
+            .unwrap_or_else(|_| "target".to_string());
+
+        // Try to find the executable in several likely locations
+        let mut paths = Vec::new();
+
+        // Direct absolute path from current directory
+        paths.push(current_dir.join("target").join("debug").join(name));
+
+        // If we're in a subdirectory of the project, try going up to find the target directory
+        let mut up_dir = current_dir.clone();
+        for _ in 0..3 {
+            // Go up one level
+            if let Some(parent) = up_dir.parent() {
+                up_dir = parent.to_path_buf();
+                paths.push(up_dir.join("target").join("debug").join(name));
+            }
+        }
+
+        // Try using the CARGO_TARGET_DIR environment variable if set
+        paths.push(PathBuf::from(&target_dir).join("debug").join(name));
+
+        // Use the first path that exists
+        paths.into_iter().find(|p| p.exists())
+    }
+
     /// Represents a command to be tested
     pub struct TestCommand {
         /// The name of the command executable
@@ -87,36 +133,7 @@ pub mod integration {
     impl TestCommand {
         /// Create a new test command
         pub fn new(name: &str) -> Result<Self> {
-            // Get the current working directory
-            let current_dir = std::env::current_dir()
-                .unwrap_or_else(|_| PathBuf::from("."));
-
-            // Find the command in the target directory
-            let target_dir = std::env::var("CARGO_TARGET_DIR")
-                .unwrap_or_else(|_| "target".to_string());
-
-            // Try to find the executable in several likely locations
-            let mut paths = Vec::new();
-
-            // Direct absolute path from current directory
-            paths.push(current_dir.join("target").join("debug").join(name));
-
-            // If we're in a subdirectory of the project, try going up to find the target directory
-            let mut up_dir = current_dir.clone();
-            for _ in 0..3 {
-                // Go up one level
-                if let Some(parent) = up_dir.parent() {
-                    up_dir = parent.to_path_buf();
-                    paths.push(up_dir.join("target").join("debug").join(name));
-                }
-            }
-
-            // Try using the CARGO_TARGET_DIR environment variable if set
-            paths.push(PathBuf::from(&target_dir).join("debug").join(name));
-
-            // Use the first path that exists
-            let path = paths.into_iter()
-                .find(|p| p.exists())
+            let path = find_binary(name)
                 .ok_or_else(|| {
                     common_errors::WritingError::validation_error(
                         format!("Command executable not found: {}", name)
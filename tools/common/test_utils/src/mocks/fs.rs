@@ -6,44 +6,26 @@ use std::path::{Path, PathBuf};
 use mockall::mock;
 use common_errors::Result;
 
-/// The FileSystem trait defines operations for interacting with the filesystem
-#[mockall::automock]
-pub trait FileSystem {
-    /// Check if a file exists at the given path
-    fn file_exists(&self, path: &Path) -> Result<bool>;
-
-    /// Check if a directory exists at the given path
-    fn dir_exists(&self, path: &Path) -> Result<bool>;
-
-    /// Read the contents of a file
-    fn read_file(&self, path: &Path) -> Result<String>;
-
-    /// Write contents to a file
-    fn write_file(&self, path: &Path, contents: &str) -> Result<()>;
-
-    /// Append contents to a file
-    fn append_file(&self, path: &Path, contents: &str) -> Result<()>;
-
-    /// Create a directory and any parent directories
-    fn create_dir_all(&self, path: &Path) -> Result<()>;
-
-    /// List files in a directory
-    fn list_files(&self, path: &Path) -> Result<Vec<PathBuf>>;
-
-    /// List subdirectories in a directory
-    fn list_dirs(&self, path: &Path) -> Result<Vec<PathBuf>>;
-
-    /// Remove a file
-    fn remove_file(&self, path: &Path) -> Result<()>;
-
-    /// Remove a directory and all its contents
-    fn remove_dir_all(&self, path: &Path) -> Result<()>;
-
-    /// Copy a file from source to destination
-    fn copy_file(&self, from: &Path, to: &Path) -> Result<()>;
-
-    /// Move a file from source to destination
-    fn move_file(&self, from: &Path, to: &Path) -> Result<()>;
+pub use common_fs::FileSystem;
+
+mock! {
+    /// A mock of [`FileSystem`] for setting expectations on each operation
+    pub FileSystem {}
+
+    impl FileSystem for FileSystem {
+        fn file_exists(&self, path: &Path) -> Result<bool>;
+        fn dir_exists(&self, path: &Path) -> Result<bool>;
+        fn read_file(&self, path: &Path) -> Result<String>;
+        fn write_file(&self, path: &Path, contents: &str) -> Result<()>;
+        fn append_file(&self, path: &Path, contents: &str) -> Result<()>;
+        fn create_dir_all(&self, path: &Path) -> Result<()>;
+        fn list_files(&self, path: &Path) -> Result<Vec<PathBuf>>;
+        fn list_dirs(&self, path: &Path) -> Result<Vec<PathBuf>>;
+        fn remove_file(&self, path: &Path) -> Result<()>;
+        fn remove_dir_all(&self, path: &Path) -> Result<()>;
+        fn copy_file(&self, from: &Path, to: &Path) -> Result<()>;
+        fn move_file(&self, from: &Path, to: &Path) -> Result<()>;
+    }
 }
 
 /// A test implementation of FileSystem that operates on an in-memory filesystem
@@ -46,13 +46,18 @@ impl MockMarkdown {
             // Default behavior if no mock response is set
             let frontmatter = Frontmatter {
                 title: "Mock Title".to_string(),
-                published_at: Some("2023-01-01".to_string()),
-                updated_at: Some("2023-01-01".to_string()),
+                published_at: Some("2023-01-01".parse().unwrap()),
+                updated_at: Some("2023-01-01".parse().unwrap()),
                 slug: Some("mock-title".to_string()),
                 description: None,
                 tags: None,
                 topics: None,
                 featured_image_path: None,
+                publish_at: None,
+                series: None,
+                series_part: None,
+                archived: None,
+                extra: Default::default(),
                 is_draft: Some(false),
             };
             Ok((frontmatter, content.to_string()))
@@ -76,7 +81,7 @@ impl MockMarkdown {
             // Default behavior if no mock response is set
             format!("---\ntitle: {}\npublished: {}\n---\n{}",
                     frontmatter.title,
-                    frontmatter.published_at.as_deref().unwrap_or("2023-01-01"),
+                    frontmatter.published_at.as_ref().map(ToString::to_string).unwrap_or_else(|| "2023-01-01".to_string()),
                     content)
         }
     }
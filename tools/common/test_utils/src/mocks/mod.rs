@@ -4,11 +4,13 @@
 
 pub mod config;
 pub mod fs;
+pub mod repository;
 pub mod tools;
 
 // Re-export types
 pub use config::{ConfigLoader, MockConfigLoader};
 pub use fs::{FileSystem, MockFileSystem};
+pub use repository::{InMemoryArticle, InMemoryContentRepository};
 pub use common_traits::tools::{
     ContentCreator, ContentEditor, ContentValidator, ContentSearcher,
     ContentMover, ContentDeleter
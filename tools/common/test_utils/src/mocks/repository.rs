@@ -0,0 +1,115 @@
+//! In-memory content repository for testing
+//!
+//! This module provides a [`ContentRepository`] whose topics and articles
+//! are defined in the test itself, so code that walks content can be tested
+//! without creating files in a temporary directory.
+
+use common_errors::{Result, WritingError};
+use common_traits::{ArticleLocation, ContentRepository};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// An article held by an [`InMemoryContentRepository`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InMemoryArticle {
+    pub topic: String,
+    pub slug: String,
+    /// Full content of the article, including frontmatter
+    pub content: String,
+}
+
+impl InMemoryArticle {
+    /// Create an article in a topic
+    pub fn new(topic: &str, slug: &str, content: &str) -> Self {
+        Self {
+            topic: topic.to_string(),
+            slug: slug.to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
+/// A content repository backed by maps instead of directories
+///
+/// Articles are reported at `<topic>/<slug>/index.md`, relative paths that
+/// don't exist on disk.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryContentRepository {
+    topics: BTreeSet<String>,
+    articles: BTreeMap<(String, String), String>,
+}
+
+impl InMemoryContentRepository {
+    /// Create an empty repository
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a repository holding `articles`
+    pub fn with_articles<I: IntoIterator<Item = InMemoryArticle>>(articles: I) -> Self {
+        let mut repository = Self::new();
+        for article in articles {
+            repository.add_article(article);
+        }
+        repository
+    }
+
+    /// Add a topic, which may have no articles
+    pub fn with_topic(mut self, topic: &str) -> Self {
+        self.topics.insert(topic.to_string());
+        self
+    }
+
+    /// Add an article, creating its topic if needed
+    pub fn with_article(mut self, topic: &str, slug: &str, content: &str) -> Self {
+        self.add_article(InMemoryArticle::new(topic, slug, content));
+        self
+    }
+
+    /// Add or replace an article, creating its topic if needed
+    pub fn add_article(&mut self, article: InMemoryArticle) {
+        self.topics.insert(article.topic.clone());
+        self.articles.insert((article.topic, article.slug), article.content);
+    }
+
+    /// Remove an article, returning its content
+    pub fn remove_article(&mut self, topic: &str, slug: &str) -> Option<String> {
+        self.articles.remove(&(topic.to_string(), slug.to_string()))
+    }
+
+    fn location(topic: &str, slug: &str) -> ArticleLocation {
+        let dir = PathBuf::from(topic).join(slug);
+        ArticleLocation {
+            topic: topic.to_string(),
+            slug: slug.to_string(),
+            content_file: dir.join("index.md"),
+            dir,
+        }
+    }
+}
+
+impl ContentRepository for InMemoryContentRepository {
+    fn list_topics(&self) -> Result<Vec<String>> {
+        Ok(self.topics.iter().cloned().collect())
+    }
+
+    fn list_articles(&self, topic: &str) -> Result<Vec<ArticleLocation>> {
+        if !self.topics.contains(topic) {
+            return Err(WritingError::topic_error(format!("Unknown topic: {}", topic)));
+        }
+
+        Ok(self
+            .articles
+            .keys()
+            .filter(|(article_topic, _)| article_topic == topic)
+            .map(|(topic, slug)| Self::location(topic, slug))
+            .collect())
+    }
+
+    fn read_article(&self, article: &ArticleLocation) -> Result<String> {
+        self.articles
+            .get(&(article.topic.clone(), article.slug.clone()))
+            .cloned()
+            .ok_or_else(|| WritingError::file_not_found(&article.content_file))
+    }
+}
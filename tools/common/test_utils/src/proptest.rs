@@ -6,6 +6,8 @@
 //!
 //! - Generators for common domain types
 //! - Strategies for testing with realistic data
+//! - Valid and invalid frontmatter, slugs and tag lists
+//! - Markdown documents built from blocks, which shrink well
 //! - Helpers for generating test scenarios
 //!
 //! ## Example
@@ -152,12 +154,17 @@ pub fn valid_frontmatter_strategy() -> impl Strategy<Value = Frontmatter> {
         Frontmatter {
             title,
             description,
-            published_at: Some(published.clone()),
-            updated_at: Some(published),
+            published_at: Some(published.parse().unwrap()),
+            updated_at: Some(published.parse().unwrap()),
             slug: Some(slug),
             tags,
             topics,
             featured_image_path: None,
+            publish_at: None,
+            series: None,
+            series_part: None,
+            archived: None,
+            extra: Default::default(),
             is_draft: Some(draft),
         }
     })
@@ -180,6 +187,15 @@ pub fn valid_article_strategy() -> impl Strategy<Value = Article> {
             path: path.to_string_lossy().to_string(),
             slug,
             reading_time: Some(2),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
             word_count: Some(100),
             topic,
         }
@@ -229,6 +245,15 @@ pub fn complex_article_strategy() -> impl Strategy<Value = Article> {
             reading_time: reading_time.map(|rt| rt % 60), // Keep within reasonable bounds
             word_count: word_count.map(|wc| wc as usize), // Convert from u32 to usize
             topic,
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         };
 
         // Add any additional metadata
@@ -295,6 +320,206 @@ pub fn valid_content_directory_strategy() -> impl Strategy<Value = (PathBuf, Vec
     })
 }
 
+/// Generate a list of distinct valid tags
+pub fn tag_list_strategy(max: usize) -> impl Strategy<Value = Vec<String>> {
+    prop::collection::btree_set(valid_tag_strategy(), 0..max).prop_map(|tags| tags.into_iter().collect())
+}
+
+/// Generate a tag with a character tag validation rejects
+pub fn invalid_tag_strategy() -> impl Strategy<Value = String> {
+    prop::string::string_regex("[a-z]{1,10}[!@#$%^&*()/:;][a-z]{0,10}").unwrap()
+}
+
+/// Generate frontmatter with one defect: a blank title, an invalid slug or an invalid tag
+///
+/// Everything else comes from [`valid_frontmatter_strategy`], so a failing
+/// case shrinks towards the single field that makes it invalid.
+pub fn invalid_frontmatter_strategy() -> impl Strategy<Value = Frontmatter> {
+    let blank_title = (valid_frontmatter_strategy(), prop::string::string_regex("[ \t]{0,5}").unwrap())
+        .prop_map(|(frontmatter, title)| Frontmatter { title, ..frontmatter });
+    let invalid_slug = (valid_frontmatter_strategy(), invalid_slug_strategy())
+        .prop_map(|(frontmatter, slug)| Frontmatter { slug: Some(slug), ..frontmatter });
+    let invalid_tag = (valid_frontmatter_strategy(), tag_list_strategy(5), invalid_tag_strategy()).prop_map(
+        |(frontmatter, mut tags, tag)| {
+            tags.push(tag);
+            Frontmatter { tags: Some(tags), ..frontmatter }
+        },
+    );
+
+    prop_oneof![blank_title, invalid_slug, invalid_tag]
+}
+
+/// A block of a generated markdown document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownBlock {
+    /// An ATX heading, level 1 to 6
+    Heading { level: u8, text: String },
+    /// Text followed by inline links, as `(text, url)` pairs
+    Paragraph { text: String, links: Vec<(String, String)> },
+    /// A fenced code block
+    CodeBlock { language: Option<String>, code: String },
+    /// A bulleted list
+    List(Vec<String>),
+}
+
+impl MarkdownBlock {
+    /// Render the block as markdown
+    pub fn to_markdown(&self) -> String {
+        match self {
+            MarkdownBlock::Heading { level, text } => format!("{} {}", "#".repeat(*level as usize), text),
+            MarkdownBlock::Paragraph { text, links } => {
+                let mut paragraph = text.clone();
+                for (text, url) in links {
+                    paragraph.push_str(&format!(" [{}]({})", text, url));
+                }
+                paragraph
+            }
+            MarkdownBlock::CodeBlock { language, code } => {
+                format!("```{}\n{}\n```", language.as_deref().unwrap_or(""), code)
+            }
+            MarkdownBlock::List(items) => items.iter().map(|item| format!("- {}", item)).collect::<Vec<_>>().join("\n"),
+        }
+    }
+}
+
+/// A markdown document built from blocks, with optional frontmatter
+///
+/// Strategies generate the blocks rather than the text, so proptest shrinks
+/// a failing document by dropping blocks and fields, and the text is only
+/// rendered by [`MarkdownDocument::to_markdown`]. The builder methods make
+/// documents for regression tests from shrunk cases.
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownDocument {
+    pub frontmatter: Option<Frontmatter>,
+    pub blocks: Vec<MarkdownBlock>,
+}
+
+impl MarkdownDocument {
+    /// Create an empty document without frontmatter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the frontmatter
+    pub fn frontmatter(mut self, frontmatter: Frontmatter) -> Self {
+        self.frontmatter = Some(frontmatter);
+        self
+    }
+
+    /// Add a heading
+    pub fn heading(mut self, level: u8, text: &str) -> Self {
+        self.blocks.push(MarkdownBlock::Heading { level: level.clamp(1, 6), text: text.to_string() });
+        self
+    }
+
+    /// Add a paragraph without links
+    pub fn paragraph(mut self, text: &str) -> Self {
+        self.blocks.push(MarkdownBlock::Paragraph { text: text.to_string(), links: Vec::new() });
+        self
+    }
+
+    /// Add a paragraph ending with a link
+    pub fn link(mut self, text: &str, link_text: &str, url: &str) -> Self {
+        self.blocks.push(MarkdownBlock::Paragraph {
+            text: text.to_string(),
+            links: vec![(link_text.to_string(), url.to_string())],
+        });
+        self
+    }
+
+    /// Add a fenced code block
+    pub fn code_block(mut self, language: Option<&str>, code: &str) -> Self {
+        self.blocks.push(MarkdownBlock::CodeBlock {
+            language: language.map(str::to_string),
+            code: code.to_string(),
+        });
+        self
+    }
+
+    /// Add a bulleted list
+    pub fn list(mut self, items: &[&str]) -> Self {
+        self.blocks.push(MarkdownBlock::List(items.iter().map(|item| item.to_string()).collect()));
+        self
+    }
+
+    /// The body, without frontmatter
+    pub fn body(&self) -> String {
+        let blocks: Vec<String> = self.blocks.iter().map(MarkdownBlock::to_markdown).collect();
+        format!("{}\n", blocks.join("\n\n"))
+    }
+
+    /// Render the document, with its frontmatter between `---` lines
+    pub fn to_markdown(&self) -> String {
+        match &self.frontmatter {
+            Some(frontmatter) => {
+                let yaml = serde_yaml::to_string(frontmatter).expect("frontmatter serializes to YAML");
+                format!("---\n{}---\n\n{}", yaml, self.body())
+            }
+            None => self.body(),
+        }
+    }
+
+    /// Text of the headings, in order
+    pub fn headings(&self) -> Vec<&str> {
+        self.blocks
+            .iter()
+            .filter_map(|block| match block {
+                MarkdownBlock::Heading { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// URLs of the links, in order
+    pub fn links(&self) -> Vec<&str> {
+        self.blocks
+            .iter()
+            .flat_map(|block| match block {
+                MarkdownBlock::Paragraph { links, .. } => links.iter().map(|(_, url)| url.as_str()).collect(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Generate plain words with no markdown syntax
+pub fn markdown_text_strategy() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::string::string_regex("[a-z]{1,10}").unwrap(), 1..12).prop_map(|words| words.join(" "))
+}
+
+/// Generate a link to a page on another site
+pub fn markdown_link_strategy() -> impl Strategy<Value = (String, String)> {
+    (markdown_text_strategy(), valid_slug_strategy()).prop_map(|(text, slug)| (text, format!("https://example.com/{}", slug)))
+}
+
+/// Generate a heading, paragraph, code block or list
+pub fn markdown_block_strategy() -> impl Strategy<Value = MarkdownBlock> {
+    let heading = (1..=6u8, markdown_text_strategy()).prop_map(|(level, text)| MarkdownBlock::Heading { level, text });
+    let paragraph = (markdown_text_strategy(), prop::collection::vec(markdown_link_strategy(), 0..3))
+        .prop_map(|(text, links)| MarkdownBlock::Paragraph { text, links });
+    // Code has no backticks, so it can't close the fence early
+    let code_block = (
+        proptest::option::of(prop_oneof![Just("rust"), Just("yaml"), Just("text")]),
+        prop::collection::vec(prop::string::string_regex("[a-z0-9 =;(){}\\[\\]<>#*_-]{0,40}").unwrap(), 1..6),
+    )
+        .prop_map(|(language, lines)| MarkdownBlock::CodeBlock {
+            language: language.map(str::to_string),
+            code: lines.join("\n"),
+        });
+    let list = prop::collection::vec(markdown_text_strategy(), 1..5).prop_map(MarkdownBlock::List);
+
+    prop_oneof![heading, paragraph, code_block, list]
+}
+
+/// Generate a markdown document of up to `max_blocks` blocks, with frontmatter half the time
+pub fn markdown_document_strategy(max_blocks: usize) -> impl Strategy<Value = MarkdownDocument> {
+    (
+        proptest::option::of(valid_frontmatter_strategy()),
+        prop::collection::vec(markdown_block_strategy(), 1..max_blocks.max(2)),
+    )
+        .prop_map(|(frontmatter, blocks)| MarkdownDocument { frontmatter, blocks })
+}
+
 /// Generate a structured set of test data for integration testing
 pub fn test_scenario_strategy() -> impl Strategy<Value = TestScenario> {
     (
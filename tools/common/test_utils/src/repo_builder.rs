@@ -0,0 +1,319 @@
+//! # Scenario Builder
+//!
+//! This module builds whole content repositories on disk for tests that need
+//! more than a single article: a config with several topics, a number of
+//! articles in each, and drafts, images or broken links where asked for.
+//!
+//! ```rust
+//! use common_test_utils::RepoBuilder;
+//!
+//! let repo = RepoBuilder::new()
+//!     .topics(2)
+//!     .articles_per_topic(3)
+//!     .drafts(1)
+//!     .broken_links(1)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(repo.articles().count(), 6);
+//! assert_eq!(repo.drafts().count(), 2);
+//! assert!(repo.config_path.exists());
+//! ```
+
+use common_errors::Result;
+use common_models::{Config, TopicConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A 1x1 transparent PNG, written for articles built with images
+const PLACEHOLDER_PNG: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F, 0x15, 0xC4,
+    0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00, 0x01, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE,
+    0x42, 0x60, 0x82,
+];
+
+/// File name of the image written into each article built with images
+pub const ARTICLE_IMAGE: &str = "diagram.png";
+
+/// Builder for a temporary content repository
+///
+/// Every topic gets the same number of articles. Within a topic, the last
+/// articles are drafts and the first articles carry the broken links, so the
+/// two only overlap when they add up to more than the articles in the topic.
+#[derive(Debug, Clone, Default)]
+pub struct RepoBuilder {
+    /// Topic keys, in the order they were added
+    topics: Vec<String>,
+    /// Articles created in each topic
+    articles_per_topic: usize,
+    /// Drafts among the articles of each topic
+    drafts: usize,
+    /// Articles in each topic that link to content that doesn't exist
+    broken_links: usize,
+    /// Whether each article gets an image
+    images: bool,
+    /// Site URL written to the config
+    site_url: Option<String>,
+}
+
+impl RepoBuilder {
+    /// Create a builder for an empty repository
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a topic with the given key, which is also its directory
+    pub fn topic(mut self, key: &str) -> Self {
+        self.topics.push(key.to_string());
+        self
+    }
+
+    /// Add `count` topics named `topic-1`, `topic-2` and so on
+    pub fn topics(mut self, count: usize) -> Self {
+        let start = self.topics.len();
+        self.topics.extend((start + 1..=start + count).map(|n| format!("topic-{}", n)));
+        self
+    }
+
+    /// Create `count` articles in each topic
+    pub fn articles_per_topic(mut self, count: usize) -> Self {
+        self.articles_per_topic = count;
+        self
+    }
+
+    /// Make the last `count` articles of each topic drafts
+    pub fn drafts(mut self, count: usize) -> Self {
+        self.drafts = count;
+        self
+    }
+
+    /// Give the first `count` articles of each topic a link to a missing article
+    pub fn broken_links(mut self, count: usize) -> Self {
+        self.broken_links = count;
+        self
+    }
+
+    /// Give every article an image, referenced from its content
+    pub fn with_images(mut self) -> Self {
+        self.images = true;
+        self
+    }
+
+    /// Set the site URL written to the config
+    pub fn site_url(mut self, url: &str) -> Self {
+        self.site_url = Some(url.to_string());
+        self
+    }
+
+    /// Write the repository to a new temporary directory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory, config or content cannot be written
+    pub fn build(self) -> Result<TestRepo> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().to_path_buf();
+        let content_dir = root.join("content");
+
+        let mut config = Config::default();
+        config.content.base_dir = content_dir.to_string_lossy().into_owned();
+        config.content.topics = self
+            .topics
+            .iter()
+            .map(|key| {
+                let topic_config = TopicConfig {
+                    name: title_case(key),
+                    description: format!("{} articles", title_case(key)),
+                    directory: key.clone(),
+                };
+                (key.clone(), topic_config)
+            })
+            .collect();
+        if let Some(url) = &self.site_url {
+            config.url = url.clone();
+        }
+
+        let config_path = root.join("config.yaml");
+        fs::write(&config_path, serde_yaml::to_string(&config)?)?;
+
+        let mut topics = Vec::with_capacity(self.topics.len());
+        for key in &self.topics {
+            let dir = content_dir.join(key);
+            fs::create_dir_all(&dir)?;
+
+            let mut articles = Vec::with_capacity(self.articles_per_topic);
+            for index in 0..self.articles_per_topic {
+                articles.push(self.write_article(&dir, key, index)?);
+            }
+
+            topics.push(RepoTopic { key: key.clone(), dir, articles });
+        }
+
+        Ok(TestRepo {
+            temp_dir,
+            root,
+            config_path,
+            content_dir,
+            config,
+            topics,
+        })
+    }
+
+    /// Write the article at `index` in a topic
+    fn write_article(&self, topic_dir: &Path, topic: &str, index: usize) -> Result<RepoArticle> {
+        let number = index + 1;
+        let slug = format!("{}-article-{}", topic, number);
+        let title = format!("{} Article {}", title_case(topic), number);
+        let is_draft = index + self.drafts >= self.articles_per_topic;
+
+        let dir = topic_dir.join(&slug);
+        fs::create_dir_all(&dir)?;
+
+        let mut body = format!("# {}\n\nThis is article {} in the {} topic.\n", title, number, topic);
+
+        let image = if self.images {
+            let image = dir.join(ARTICLE_IMAGE);
+            fs::write(&image, PLACEHOLDER_PNG)?;
+            body.push_str(&format!("\n![Diagram for {}]({})\n", title, ARTICLE_IMAGE));
+            Some(image)
+        } else {
+            None
+        };
+
+        let broken_link = if index < self.broken_links {
+            let link = format!("/{}/missing-article-{}", topic, number);
+            body.push_str(&format!("\nSee [the missing article]({}).\n", link));
+            Some(link)
+        } else {
+            None
+        };
+
+        let content = format!(
+            "---\ntitle: \"{}\"\ndescription: \"Description of {}\"\npublished: \"2023-01-{:02}\"\ntopics:\n  - {}\ndraft: {}\n---\n\n{}",
+            title,
+            title,
+            number.min(28),
+            topic,
+            is_draft,
+            body
+        );
+        let content_file = dir.join(format!("{}.md", slug));
+        fs::write(&content_file, content)?;
+
+        Ok(RepoArticle {
+            topic: topic.to_string(),
+            slug,
+            title,
+            dir,
+            content_file,
+            is_draft,
+            image,
+            broken_link,
+        })
+    }
+}
+
+/// A temporary content repository created by [`RepoBuilder`]
+///
+/// The directory is removed when the repository is dropped.
+pub struct TestRepo {
+    /// The temporary directory holding the repository
+    pub temp_dir: TempDir,
+    /// Root of the repository
+    pub root: PathBuf,
+    /// Path to `config.yaml`
+    pub config_path: PathBuf,
+    /// Base directory for content
+    pub content_dir: PathBuf,
+    /// The config written to `config.yaml`
+    pub config: Config,
+    /// The topics, in the order they were added
+    pub topics: Vec<RepoTopic>,
+}
+
+impl TestRepo {
+    /// Get the root of the repository
+    pub fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// Find a topic by key
+    pub fn topic(&self, key: &str) -> Option<&RepoTopic> {
+        self.topics.iter().find(|topic| topic.key == key)
+    }
+
+    /// Find an article by topic and slug
+    pub fn article(&self, topic: &str, slug: &str) -> Option<&RepoArticle> {
+        self.topic(topic)?.articles.iter().find(|article| article.slug == slug)
+    }
+
+    /// Iterate over every article, topic by topic
+    pub fn articles(&self) -> impl Iterator<Item = &RepoArticle> {
+        self.topics.iter().flat_map(|topic| topic.articles.iter())
+    }
+
+    /// Iterate over the articles that are not drafts
+    pub fn published(&self) -> impl Iterator<Item = &RepoArticle> {
+        self.articles().filter(|article| !article.is_draft)
+    }
+
+    /// Iterate over the draft articles
+    pub fn drafts(&self) -> impl Iterator<Item = &RepoArticle> {
+        self.articles().filter(|article| article.is_draft)
+    }
+
+    /// Iterate over the articles with a broken link
+    pub fn with_broken_links(&self) -> impl Iterator<Item = &RepoArticle> {
+        self.articles().filter(|article| article.broken_link.is_some())
+    }
+}
+
+/// A topic in a [`TestRepo`]
+#[derive(Debug, Clone)]
+pub struct RepoTopic {
+    /// Topic key, which is also its directory name
+    pub key: String,
+    /// Directory holding the topic's articles
+    pub dir: PathBuf,
+    /// The topic's articles, in order
+    pub articles: Vec<RepoArticle>,
+}
+
+/// An article in a [`TestRepo`]
+#[derive(Debug, Clone)]
+pub struct RepoArticle {
+    /// Key of the topic the article is in
+    pub topic: String,
+    /// Article slug
+    pub slug: String,
+    /// Article title
+    pub title: String,
+    /// Directory holding the article
+    pub dir: PathBuf,
+    /// The article's `{slug}.md` file
+    pub content_file: PathBuf,
+    /// Whether the article is a draft
+    pub is_draft: bool,
+    /// The article's image, if built with images
+    pub image: Option<PathBuf>,
+    /// URL of the missing article this one links to, if any
+    pub broken_link: Option<String>,
+}
+
+/// Turn a topic key such as `topic-1` into a name such as `Topic 1`
+fn title_case(key: &str) -> String {
+    key.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,62 @@
+//! End-to-end suite: runs the built tools one after another on one repository
+//!
+//! The suite needs the tools' binaries, so it is ignored by default. Run it with
+//! `cargo build --workspace && cargo test -p common-test-utils --test e2e -- --ignored`.
+
+#![cfg(unix)]
+
+use common_test_utils::e2e::{E2eHarness, WORKFLOW_TOOLS};
+use common_test_utils::RepoBuilder;
+
+#[test]
+#[ignore = "needs the workspace binaries; run with --ignored after cargo build --workspace"]
+fn test_create_edit_validate_build_search_move_build() {
+    let missing = E2eHarness::missing_tools(WORKFLOW_TOOLS);
+    assert!(
+        missing.is_empty(),
+        "Tools not built, run cargo build --workspace first: {}",
+        missing.join(", ")
+    );
+
+    let repo = RepoBuilder::new()
+        .topic("blog")
+        .topic("notes")
+        .articles_per_topic(2)
+        .site_url("https://example.com")
+        .build()
+        .unwrap();
+    let mut harness = E2eHarness::new(repo);
+    let slug = "harness-launch-notes";
+
+    // Create: the new article is where the other tools will look for it
+    harness.create("blog", "Harness Launch Notes", "e2e").unwrap();
+    let created = harness.article_file("blog", slug).expect("content-new did not create the article");
+
+    // Edit: the change is saved to the same file
+    harness.append("blog", slug, "A quokka paragraph marks this edit.").unwrap();
+    assert!(std::fs::read_to_string(&created).unwrap().contains("quokka"));
+
+    // Validate: everything created so far passes
+    harness.validate("validate after edit").unwrap();
+
+    // Build: the new article and the generated ones are published
+    harness.build("first build").unwrap();
+    harness.assert_sitemap_contains(&format!("/blog/{}", slug));
+    harness.assert_sitemap_contains("/notes/notes-article-1");
+
+    // Search: the edited text is indexed
+    harness.assert_search_finds("quokka", slug);
+
+    // Move: the article leaves its old topic
+    harness.move_article("blog", slug, "notes", "launch-notes").unwrap();
+    assert!(harness.article_file("blog", slug).is_none());
+    assert!(harness.article_file("notes", "launch-notes").is_some());
+
+    // Build again: the sitemap follows the move
+    harness.build("build after move").unwrap();
+    harness.assert_sitemap_contains("/notes/launch-notes");
+    harness.assert_sitemap_lacks(&format!("/blog/{}", slug));
+    harness.assert_search_finds("quokka", "launch-notes");
+
+    assert_eq!(harness.steps().len(), 8);
+}
@@ -59,6 +59,15 @@ fn test_mock_config_loader() {
             copyright: "Test Copyright".to_string(),
             site_url: None,
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
     };
 
     let mut mock_config = MockConfigLoader::new(config.clone());
@@ -84,7 +93,7 @@ fn test_mock_content_operations() {
     let article = Article {
         frontmatter: Frontmatter {
             title: "Test Article".to_string(),
-            published_at: Some("2023-01-01".to_string()),
+            published_at: Some("2023-01-01".parse().unwrap()),
             updated_at: None,
             slug: Some("test-article".to_string()),
             description: None,
@@ -92,6 +101,11 @@ fn test_mock_content_operations() {
             topics: Some(vec!["blog".to_string()]),
             is_draft: Some(false),
             featured_image_path: None,
+            publish_at: None,
+            series: None,
+            series_part: None,
+            archived: None,
+            extra: Default::default(),
         },
         content: "# Test Article\n\nThis is a test article.".to_string(),
         slug: "test-article".to_string(),
@@ -99,6 +113,15 @@ fn test_mock_content_operations() {
         path: "/content/blog/test-article".to_string(),
         word_count: Some(7),
         reading_time: Some(1),
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
     };
 
     // Add the article
@@ -178,6 +201,15 @@ fn test_trait_implementations() {
             copyright: "Test Copyright".to_string(),
             site_url: None,
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
     };
 
     let config_loader: Box<dyn ConfigLoader> = Box::new(MockConfigLoader::new(config));
@@ -0,0 +1,56 @@
+use common_test_utils::proptest::*;
+use proptest::prelude::*;
+
+fn is_valid_slug(slug: &str) -> bool {
+    common_validation::validate_slug(slug).is_ok()
+}
+
+fn is_valid_tag(tag: &str) -> bool {
+    common_validation::validate_tags(tag).is_ok()
+}
+
+#[test]
+fn test_markdown_document_builder() {
+    let document = MarkdownDocument::new()
+        .heading(2, "Setup")
+        .link("See the", "guide", "https://example.com/guide")
+        .code_block(Some("rust"), "fn main() {}")
+        .list(&["one", "two"]);
+
+    assert_eq!(
+        document.to_markdown(),
+        "## Setup\n\nSee the [guide](https://example.com/guide)\n\n```rust\nfn main() {}\n```\n\n- one\n- two\n"
+    );
+    assert_eq!(document.headings(), vec!["Setup"]);
+    assert_eq!(document.links(), vec!["https://example.com/guide"]);
+}
+
+proptest! {
+    #[test]
+    fn test_generated_frontmatter_round_trips(document in markdown_document_strategy(8)) {
+        let markdown = document.to_markdown();
+        prop_assert!(markdown.ends_with('\n'));
+
+        if let Some(frontmatter) = &document.frontmatter {
+            let (parsed, body) = common_markdown::extract_frontmatter_and_content(&markdown).unwrap();
+            prop_assert_eq!(serde_yaml::to_value(&parsed).unwrap(), serde_yaml::to_value(frontmatter).unwrap());
+            prop_assert_eq!(body, document.body());
+        }
+    }
+
+    #[test]
+    fn test_invalid_frontmatter_has_a_defect(frontmatter in invalid_frontmatter_strategy()) {
+        let blank_title = frontmatter.title.trim().is_empty();
+        let invalid_slug = frontmatter.slug.as_deref().is_some_and(|slug| !is_valid_slug(slug));
+        let invalid_tag = frontmatter.tags.iter().flatten().any(|tag| !is_valid_tag(tag));
+        prop_assert!(blank_title || invalid_slug || invalid_tag);
+    }
+
+    #[test]
+    fn test_tag_lists_are_valid_and_distinct(tags in tag_list_strategy(8)) {
+        let mut distinct = tags.clone();
+        distinct.dedup();
+        prop_assert_eq!(&distinct, &tags);
+        prop_assert!(tags.iter().all(|tag| is_valid_tag(tag)));
+    }
+}
@@ -0,0 +1,81 @@
+use common_models::{Config, Frontmatter};
+use common_test_utils::repo_builder::ARTICLE_IMAGE;
+use common_test_utils::RepoBuilder;
+
+#[test]
+fn test_repo_builder_creates_topics_and_articles() {
+    let repo = RepoBuilder::new()
+        .topic("blog")
+        .topics(2)
+        .articles_per_topic(3)
+        .drafts(1)
+        .build()
+        .unwrap();
+
+    let keys: Vec<&str> = repo.topics.iter().map(|topic| topic.key.as_str()).collect();
+    assert_eq!(keys, vec!["blog", "topic-2", "topic-3"]);
+    assert_eq!(repo.articles().count(), 9);
+    assert_eq!(repo.drafts().count(), 3);
+    assert_eq!(repo.published().count(), 6);
+
+    // The config on disk matches the returned config
+    let config: Config = serde_yaml::from_str(&std::fs::read_to_string(&repo.config_path).unwrap()).unwrap();
+    assert_eq!(config, repo.config);
+    assert_eq!(config.content.topics["topic-2"].name, "Topic 2");
+    assert_eq!(repo.content_dir, repo.path().join("content"));
+
+    let article = repo.article("blog", "blog-article-3").unwrap();
+    assert!(article.is_draft);
+    assert_eq!(article.content_file, repo.content_dir.join("blog/blog-article-3/blog-article-3.md"));
+
+    let content = std::fs::read_to_string(&article.content_file).unwrap();
+    let (frontmatter, body) = split_frontmatter(&content);
+    let frontmatter: Frontmatter = serde_yaml::from_str(frontmatter).unwrap();
+    assert_eq!(frontmatter.title, "Blog Article 3");
+    assert_eq!(frontmatter.is_draft, Some(true));
+    assert!(body.contains("# Blog Article 3"));
+}
+
+#[test]
+fn test_repo_builder_adds_images_and_broken_links() {
+    let repo = RepoBuilder::new()
+        .topics(1)
+        .articles_per_topic(2)
+        .broken_links(1)
+        .with_images()
+        .build()
+        .unwrap();
+
+    let broken: Vec<&str> = repo.with_broken_links().map(|article| article.slug.as_str()).collect();
+    assert_eq!(broken, vec!["topic-1-article-1"]);
+
+    for article in repo.articles() {
+        let image = article.image.as_ref().unwrap();
+        assert_eq!(image, &article.dir.join(ARTICLE_IMAGE));
+        assert!(image.is_file());
+
+        let content = std::fs::read_to_string(&article.content_file).unwrap();
+        assert!(content.contains(&format!("]({})", ARTICLE_IMAGE)));
+        match &article.broken_link {
+            Some(link) => assert!(content.contains(&format!("]({})", link))),
+            None => assert!(!content.contains("missing-article")),
+        }
+    }
+}
+
+#[test]
+fn test_repo_is_removed_when_dropped() {
+    let repo = RepoBuilder::new().topics(1).articles_per_topic(1).build().unwrap();
+    let root = repo.path().to_path_buf();
+    assert!(root.exists());
+
+    drop(repo);
+    assert!(!root.exists());
+}
+
+/// Split a content file into its frontmatter and body
+fn split_frontmatter(content: &str) -> (&str, &str) {
+    let rest = content.strip_prefix("---\n").unwrap();
+    let end = rest.find("\n---\n").unwrap();
+    (&rest[..end], &rest[end + 5..])
+}
@@ -0,0 +1,50 @@
+use common_test_utils::mocks::{InMemoryArticle, InMemoryContentRepository};
+use common_traits::ContentRepository;
+use std::path::PathBuf;
+
+#[test]
+fn test_in_memory_repository_lists_articles() {
+    let repository = InMemoryContentRepository::with_articles([
+        InMemoryArticle::new("notes", "second", "# Second"),
+        InMemoryArticle::new("blog", "first", "# First"),
+        InMemoryArticle::new("notes", "first", "# Note"),
+    ])
+    .with_topic("drafts");
+
+    assert_eq!(repository.list_topics().unwrap(), vec!["blog", "drafts", "notes"]);
+    assert!(repository.list_articles("drafts").unwrap().is_empty());
+    assert!(repository.list_articles("missing").is_err());
+
+    let notes = repository.list_articles("notes").unwrap();
+    let slugs: Vec<&str> = notes.iter().map(|article| article.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["first", "second"]);
+    assert_eq!(notes[0].content_file, PathBuf::from("notes/first/index.md"));
+
+    let all: Vec<String> = repository
+        .article_paths()
+        .unwrap()
+        .into_iter()
+        .map(|article| format!("{}/{}", article.topic, article.slug))
+        .collect();
+    assert_eq!(all, vec!["blog/first", "notes/first", "notes/second"]);
+}
+
+#[test]
+fn test_in_memory_repository_loads_and_removes_articles() {
+    let mut repository = InMemoryContentRepository::new()
+        .with_article("notes", "first", "# Note")
+        .with_article("blog", "first", "# First");
+
+    let (article, content) = repository.load_article("first").unwrap();
+    assert_eq!(article.topic, "blog");
+    assert_eq!(content, "# First");
+
+    assert_eq!(repository.find_articles("first").unwrap().len(), 2);
+    assert_eq!(repository.remove_article("blog", "first"), Some("# First".to_string()));
+
+    let (article, content) = repository.load_article("first").unwrap();
+    assert_eq!(article.topic, "notes");
+    assert_eq!(content, "# Note");
+    assert!(repository.read_article(&repository.find_articles("first").unwrap()[0]).is_ok());
+    assert!(repository.load_article("missing").is_err());
+}
@@ -0,0 +1,107 @@
+//! # Cooperative Cancellation
+//!
+//! This module provides a cancellation token that long-running operations
+//! (content builds, image processing, link checking, indexing) poll between
+//! units of work so that a CLI, TUI or editor integration can abort them cleanly.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use common_traits::CancellationToken;
+//!
+//! let token = CancellationToken::new();
+//! let worker_token = token.clone();
+//!
+//! assert!(worker_token.check("build").is_ok());
+//! token.cancel();
+//! assert!(worker_token.is_cancelled());
+//! assert!(worker_token.check("build").is_err());
+//! ```
+
+use common_errors::{Result, WritingError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A cheaply cloneable flag shared between the party requesting cancellation
+/// and the operation being cancelled
+///
+/// Cancellation is cooperative: operations call [`CancellationToken::check`]
+/// at safe points (between articles, images or links) and stop once the
+/// token has been cancelled, leaving already-written output in place.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new token that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every operation holding a clone of this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Check whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Return a cancelled error if cancellation has been requested
+    ///
+    /// # Parameters
+    ///
+    /// * `operation` - Name of the operation, used in the error message
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the operation may continue, or `WritingError::Cancelled`
+    pub fn check(&self, operation: &str) -> Result<()> {
+        if self.is_cancelled() {
+            Err(WritingError::cancelled(operation))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Cancel this token when the process receives Ctrl-C (SIGINT)
+    ///
+    /// Only one handler can be installed per process; installing a second
+    /// one returns an error.
+    ///
+    /// Requires the `signal` feature
+    #[cfg(feature = "signal")]
+    pub fn cancel_on_ctrlc(&self) -> Result<()> {
+        let token = self.clone();
+        ctrlc::set_handler(move || token.cancel()).map_err(|e| {
+            WritingError::other(format!("Failed to install Ctrl-C handler: {}", e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_token_is_not_cancelled() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+        assert!(token.check("test").is_ok());
+    }
+
+    #[test]
+    fn test_cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        clone.cancel();
+
+        assert!(token.is_cancelled());
+        let err = token.check("indexing").unwrap_err();
+        assert!(err.is_cancelled());
+        assert!(err.message().contains("indexing"));
+    }
+}
@@ -13,6 +13,14 @@ use serde::{Serialize, Deserialize};
 pub mod tools;
 pub use tools::*;
 
+// Cooperative cancellation for long-running operations
+pub mod cancellation;
+pub use cancellation::CancellationToken;
+
+// Shared access to topics and articles
+pub mod repository;
+pub use repository::{ArticleLocation, ContentRepository};
+
 /// Trait for file operations
 ///
 /// This trait provides common file operations for types that need
@@ -0,0 +1,87 @@
+//! # Content Repository
+//!
+//! Every tool needs to walk the same content tree: topics, the article
+//! directories in each topic, and the content file in each article. This
+//! trait is the one place that knows how, so the tools agree on what counts
+//! as an article and can be pointed at something other than the filesystem.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use common_traits::ContentRepository;
+//!
+//! fn count_words(repository: &impl ContentRepository) -> common_errors::Result<usize> {
+//!     let mut words = 0;
+//!     for article in repository.article_paths()? {
+//!         words += repository.read_article(&article)?.split_whitespace().count();
+//!     }
+//!     Ok(words)
+//! }
+//! ```
+
+use common_errors::{Result, WritingError};
+use std::path::PathBuf;
+
+/// Where an article lives in the content tree
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArticleLocation {
+    /// Topic key from the configuration
+    pub topic: String,
+    pub slug: String,
+    /// The article directory
+    pub dir: PathBuf,
+    /// The content file in the article directory
+    pub content_file: PathBuf,
+}
+
+/// Read access to the topics and articles of a content tree
+pub trait ContentRepository {
+    /// Keys of the configured topics, sorted
+    fn list_topics(&self) -> Result<Vec<String>>;
+
+    /// Articles in a topic, sorted by slug
+    ///
+    /// A topic whose directory doesn't exist has no articles. Article
+    /// directories without a content file are left out.
+    fn list_articles(&self, topic: &str) -> Result<Vec<ArticleLocation>>;
+
+    /// Read an article's content file
+    fn read_article(&self, article: &ArticleLocation) -> Result<String>;
+
+    /// The article with `slug` in `topic`, if there is one
+    fn find_article(&self, topic: &str, slug: &str) -> Result<Option<ArticleLocation>> {
+        Ok(self
+            .list_articles(topic)?
+            .into_iter()
+            .find(|article| article.slug == slug))
+    }
+
+    /// Every article with `slug`, in topic order
+    fn find_articles(&self, slug: &str) -> Result<Vec<ArticleLocation>> {
+        let mut articles = Vec::new();
+        for topic in self.list_topics()? {
+            articles.extend(self.find_article(&topic, slug)?);
+        }
+        Ok(articles)
+    }
+
+    /// The first article with `slug`, in topic order, and its content
+    fn load_article(&self, slug: &str) -> Result<(ArticleLocation, String)> {
+        let article = self
+            .find_articles(slug)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| WritingError::content_not_found(format!("No article found with slug: {}", slug)))?;
+        let content = self.read_article(&article)?;
+        Ok((article, content))
+    }
+
+    /// Every article in every topic, in topic order
+    fn article_paths(&self) -> Result<Vec<ArticleLocation>> {
+        let mut articles = Vec::new();
+        for topic in self.list_topics()? {
+            articles.extend(self.list_articles(&topic)?);
+        }
+        Ok(articles)
+    }
+}
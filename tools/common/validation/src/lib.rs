@@ -10,6 +10,7 @@
 //! - **Path validation**: Validate and generate paths for content files
 //! - **Tag validation**: Validate and format tags for content
 //! - **Topic validation**: Validate topics against configuration
+//! - **Series validation**: Ensure series parts are unique and contiguous
 //!
 //! ## Example
 //!
@@ -39,6 +40,7 @@
 //! - `path.rs`: Path validation and generation functions
 //! - `tags.rs`: Tag validation and formatting functions
 //! - `topic.rs`: Topic validation functions
+//! - `series.rs`: Series validation functions
 
 // Re-export dependencies for use by modules
 pub use common_errors::{Result, WritingError, ResultExt, ErrorContext, IoResultExt};
@@ -58,6 +60,7 @@ mod slug;
 mod path;
 mod frontmatter;
 mod tags;
+mod series;
 
 // Re-export module functions
 pub use content::*;
@@ -66,6 +69,7 @@ pub use slug::*;
 pub use path::*;
 pub use frontmatter::*;
 pub use tags::*;
+pub use series::*;
 
 /// Validate that a slug is provided and properly formatted
 pub fn validate_slug(slug: &str) -> Result<String> {
@@ -0,0 +1,118 @@
+use common_errors::{Result, WritingError};
+use common_models::Article;
+use std::collections::BTreeMap;
+
+/// Validate that the parts of every series are unique and contiguous
+///
+/// Articles are grouped by their `series` frontmatter field. Within each
+/// series, every article must have a `series_part` of at least 1, no two
+/// articles may share a part number, and the parts must run from 1 without gaps.
+///
+/// # Arguments
+///
+/// * `articles` - The articles to check
+///
+/// # Returns
+///
+/// * `Ok(())` - If all series are valid
+/// * `Err(WritingError)` - A validation error listing every problem found
+///
+/// # Examples
+///
+/// ```
+/// use common_models::{Article, Frontmatter};
+/// use common_validation::validate_series;
+///
+/// fn part(slug: &str, part: u32) -> Article {
+///     Article {
+///         frontmatter: Frontmatter {
+///             series: Some("rust-basics".to_string()),
+///             series_part: Some(part),
+///             ..Frontmatter::default()
+///         },
+///         content: String::new(),
+///         slug: slug.to_string(),
+///         topic: "blog".to_string(),
+///         path: String::new(),
+///         word_count: None,
+///         reading_time: None,
+///         series_navigation: None,
+///         html: None,
+///         summary: None,
+///         git: None,
+///         lang: None,
+///         alternates: Vec::new(),
+///         links: Vec::new(),
+///         hero_image: None,
+///         social_image: None,
+///     }
+/// }
+///
+/// assert!(validate_series(&[part("intro", 1), part("ownership", 2)]).is_ok());
+/// assert!(validate_series(&[part("intro", 1), part("traits", 3)]).is_err());
+/// ```
+pub fn validate_series(articles: &[Article]) -> Result<()> {
+    let mut series: BTreeMap<&str, Vec<(&Article, Option<u32>)>> = BTreeMap::new();
+    let mut problems = Vec::new();
+
+    for article in articles {
+        match (&article.frontmatter.series, article.frontmatter.series_part) {
+            (Some(name), part) => series.entry(name.as_str()).or_default().push((article, part)),
+            (None, Some(_)) => problems.push(format!(
+                "'{}' has a series_part but no series",
+                article.slug
+            )),
+            (None, None) => {}
+        }
+    }
+
+    for (name, members) in series {
+        let mut parts: BTreeMap<u32, Vec<&str>> = BTreeMap::new();
+
+        for (article, part) in members {
+            match part {
+                Some(0) => problems.push(format!(
+                    "'{}' has series_part 0, but parts start at 1",
+                    article.slug
+                )),
+                Some(part) => parts.entry(part).or_default().push(&article.slug),
+                None => problems.push(format!(
+                    "'{}' is in series '{}' but has no series_part",
+                    article.slug, name
+                )),
+            }
+        }
+
+        for (part, slugs) in &parts {
+            if slugs.len() > 1 {
+                problems.push(format!(
+                    "Series '{}' has more than one part {}: {}",
+                    name,
+                    part,
+                    slugs.join(", ")
+                ));
+            }
+        }
+
+        let missing: Vec<String> = (1..=parts.keys().last().copied().unwrap_or(0))
+            .filter(|part| !parts.contains_key(part))
+            .map(|part| part.to_string())
+            .collect();
+        if !missing.is_empty() {
+            problems.push(format!(
+                "Series '{}' is missing part(s) {}",
+                name,
+                missing.join(", ")
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(WritingError::validation_error(format!(
+            "Invalid series: {}",
+            problems.join("; ")
+        )))
+    }
+}
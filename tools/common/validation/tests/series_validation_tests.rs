@@ -0,0 +1,84 @@
+use common_models::{Article, Frontmatter};
+use common_validation::validate_series;
+
+fn article(slug: &str, series: Option<&str>, part: Option<u32>) -> Article {
+    Article {
+        frontmatter: Frontmatter {
+            title: slug.to_string(),
+            series: series.map(|s| s.to_string()),
+            series_part: part,
+            ..Frontmatter::default()
+        },
+        content: String::new(),
+        slug: slug.to_string(),
+        topic: "blog".to_string(),
+        path: String::new(),
+        word_count: None,
+        reading_time: None,
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
+    }
+}
+
+#[test]
+fn test_contiguous_series_is_valid() {
+    let articles = vec![
+        article("part-two", Some("intro"), Some(2)),
+        article("part-one", Some("intro"), Some(1)),
+        article("standalone", None, None),
+    ];
+    assert!(validate_series(&articles).is_ok());
+}
+
+#[test]
+fn test_duplicate_parts_are_rejected() {
+    let articles = vec![
+        article("first", Some("intro"), Some(1)),
+        article("also-first", Some("intro"), Some(1)),
+    ];
+    let err = validate_series(&articles).unwrap_err().to_string();
+    assert!(err.contains("more than one part 1"));
+    assert!(err.contains("also-first"));
+}
+
+#[test]
+fn test_gaps_are_rejected() {
+    let articles = vec![
+        article("one", Some("intro"), Some(1)),
+        article("four", Some("intro"), Some(4)),
+    ];
+    let err = validate_series(&articles).unwrap_err().to_string();
+    assert!(err.contains("missing part(s) 2, 3"));
+}
+
+#[test]
+fn test_missing_part_or_series_is_rejected() {
+    let articles = vec![
+        article("no-part", Some("intro"), None),
+        article("no-series", None, Some(1)),
+    ];
+    let err = validate_series(&articles).unwrap_err().to_string();
+    assert!(err.contains("'no-part' is in series 'intro' but has no series_part"));
+    assert!(err.contains("'no-series' has a series_part but no series"));
+}
+
+#[test]
+fn test_part_zero_is_rejected() {
+    let articles = vec![article("zero", Some("intro"), Some(0))];
+    let err = validate_series(&articles).unwrap_err().to_string();
+    assert!(err.contains("'zero' has series_part 0, but parts start at 1"));
+
+    let articles = vec![
+        article("zero", Some("intro"), Some(0)),
+        article("two", Some("intro"), Some(2)),
+    ];
+    let err = validate_series(&articles).unwrap_err().to_string();
+    assert!(err.contains("missing part(s) 1"));
+}
@@ -0,0 +1,159 @@
+//! # Content Archive
+//!
+//! Archived content stays in the repository, in place, but is marked with
+//! `archived: true` in its frontmatter. Builds always skip it, and search and
+//! stats leave it out unless asked to include it, so old posts can be retired
+//! without losing them or their history.
+
+use anyhow::Result;
+use common_fs::normalize::{join_paths, normalize_path};
+use std::path::{Path, PathBuf};
+
+/// A piece of archived content
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedContent {
+    /// Topic key the content belongs to
+    pub topic: String,
+    /// Content slug
+    pub slug: String,
+    /// Title from the frontmatter
+    pub title: String,
+    /// Path to the content file
+    pub path: PathBuf,
+}
+
+/// Archive content, excluding it from builds, search and stats
+///
+/// # Parameters
+///
+/// * `slug` - Slug of the content to archive
+///
+/// # Returns
+///
+/// Returns the path to the archived content file
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be found, is already archived, or
+/// its frontmatter cannot be updated
+pub fn archive_content(slug: &str) -> Result<PathBuf> {
+    let (topic, file_path) = find_content_file(slug)?;
+
+    if read_frontmatter(&file_path)?.is_archived() {
+        return Err(anyhow::anyhow!("Content is already archived: {}/{}", topic, slug));
+    }
+
+    set_archived(&file_path, true)?;
+    Ok(file_path)
+}
+
+/// Restore archived content
+///
+/// # Parameters
+///
+/// * `slug` - Slug of the content to restore
+///
+/// # Returns
+///
+/// Returns the path to the restored content file
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be found, is not archived, or its
+/// frontmatter cannot be updated
+pub fn unarchive_content(slug: &str) -> Result<PathBuf> {
+    let (topic, file_path) = find_content_file(slug)?;
+
+    if !read_frontmatter(&file_path)?.is_archived() {
+        return Err(anyhow::anyhow!("Content is not archived: {}/{}", topic, slug));
+    }
+
+    set_archived(&file_path, false)?;
+    Ok(file_path)
+}
+
+/// List all archived content, sorted by topic and slug
+pub fn list_archived() -> Result<Vec<ArchivedContent>> {
+    let mut archived = Vec::new();
+
+    for (topic, slug, file_path) in list_content_files()? {
+        // Content with unreadable frontmatter can't be archived
+        let frontmatter = match read_frontmatter(&file_path) {
+            Ok(frontmatter) => frontmatter,
+            Err(_) => continue,
+        };
+
+        if frontmatter.is_archived() {
+            archived.push(ArchivedContent {
+                topic,
+                slug,
+                title: frontmatter.title,
+                path: file_path,
+            });
+        }
+    }
+
+    archived.sort_by(|a, b| (&a.topic, &a.slug).cmp(&(&b.topic, &b.slug)));
+    Ok(archived)
+}
+
+/// Find the content file for a slug in any topic
+fn find_content_file(slug: &str) -> Result<(String, PathBuf)> {
+    list_content_files()?
+        .into_iter()
+        .find(|(_, content_slug, _)| content_slug == slug)
+        .map(|(topic, _, file_path)| (topic, file_path))
+        .ok_or_else(|| anyhow::anyhow!("Content not found for slug: {}", slug))
+}
+
+/// List the content file of every article
+fn list_content_files() -> Result<Vec<(String, String, PathBuf)>> {
+    let config = common_config::load_config()?;
+    let base_dir = PathBuf::from(&config.content.base_dir);
+    let mut files = Vec::new();
+
+    for (topic_key, topic_config) in &config.content.topics {
+        let topic_dir = join_paths(&base_dir, &topic_config.directory);
+        if !topic_dir.is_dir() {
+            continue;
+        }
+
+        for entry in std::fs::read_dir(&topic_dir)?.filter_map(|entry| entry.ok()) {
+            let article_dir = entry.path();
+            let slug = match article_dir.file_name().and_then(|name| name.to_str()) {
+                Some(slug) if article_dir.is_dir() => slug.to_string(),
+                _ => continue,
+            };
+
+            if let Some(file_path) = common_fs::resolve_content_file(&article_dir, &slug) {
+                files.push((topic_key.clone(), slug, normalize_path(file_path)));
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Read and parse the frontmatter of a content file
+fn read_frontmatter(file_path: &Path) -> Result<common_models::Frontmatter> {
+    let content = common_fs::read_file(file_path)?;
+    let (frontmatter, _) = common_markdown::extract_frontmatter_and_content(&content)?;
+    Ok(frontmatter)
+}
+
+/// Set or remove the `archived` frontmatter field of a content file
+///
+/// Only the `archived` line changes; the rest of the frontmatter keeps its
+/// formatting, comments and order.
+fn set_archived(file_path: &Path, archived: bool) -> Result<()> {
+    let content = common_fs::read_file(file_path)?;
+
+    let updated_content = if archived {
+        content_edit::sections::set_frontmatter_field(&content, "archived", true.into())?
+    } else {
+        content_edit::sections::remove_frontmatter_field(&content, "archived")?
+    };
+    common_fs::write_file(file_path, &updated_content)?;
+
+    Ok(())
+}
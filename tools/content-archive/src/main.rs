@@ -0,0 +1,55 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use colored::*;
+use content_archive::{archive_content, list_archived, unarchive_content};
+
+#[derive(Parser)]
+#[command(author, version, about = "Archive content without deleting it")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Archive content, excluding it from builds, search and stats
+    Archive {
+        /// Content slug
+        slug: String,
+    },
+
+    /// Restore archived content
+    Unarchive {
+        /// Content slug
+        slug: String,
+    },
+
+    /// List archived content
+    List,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Archive { slug } => {
+            let path = archive_content(&slug)?;
+            println!("{} Content archived: {}", "SUCCESS:".green().bold(), path.display());
+        }
+        Commands::Unarchive { slug } => {
+            let path = unarchive_content(&slug)?;
+            println!("{} Content restored: {}", "SUCCESS:".green().bold(), path.display());
+        }
+        Commands::List => {
+            let archived = list_archived()?;
+            if archived.is_empty() {
+                println!("No archived content");
+            }
+            for content in archived {
+                println!("{}/{} ({})", content.topic.yellow(), content.slug.yellow(), content.title);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+//! Test modules for content-archive
+
+// Unit tests
+#[path = "unit/archive_tests.rs"]
+mod archive_tests;
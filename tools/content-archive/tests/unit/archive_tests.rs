@@ -0,0 +1,51 @@
+use content_archive::{archive_content, list_archived, unarchive_content};
+use common_test_utils::{use_config, write_article, write_repository};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod archive_tests {
+    use super::*;
+
+    fn seed_repository(root: &Path) -> PathBuf {
+        for (slug, title) in [("old-post", "Old Post"), ("new-post", "New Post")] {
+            let content = format!("---\ntitle: {} # keep this comment\n---\nBody\n", title);
+            write_article(&root.join("content"), "blog", slug, &content);
+        }
+        write_repository(root, &["blog"])
+    }
+
+    #[test]
+    fn test_archive_and_unarchive_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let _config = use_config(&seed_repository(temp_dir.path()));
+
+        assert!(list_archived().unwrap().is_empty());
+
+        let path = archive_content("old-post").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "---\ntitle: Old Post # keep this comment\narchived: true\n---\nBody\n"
+        );
+
+        let archived = list_archived().unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].topic, "blog");
+        assert_eq!(archived[0].slug, "old-post");
+        assert_eq!(archived[0].title, "Old Post");
+
+        let err = archive_content("old-post").unwrap_err();
+        assert!(err.to_string().contains("already archived"));
+
+        unarchive_content("old-post").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "---\ntitle: Old Post # keep this comment\n---\nBody\n"
+        );
+        assert!(list_archived().unwrap().is_empty());
+
+        assert!(unarchive_content("new-post").is_err());
+        assert!(archive_content("missing").is_err());
+    }
+}
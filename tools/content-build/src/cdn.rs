@@ -0,0 +1,137 @@
+//! # Image CDN
+//!
+//! Sites that serve images from a CDN can have the build point image URLs at
+//! it, instead of rewriting the output afterwards:
+//!
+//! ```text
+//! content-build --images-dir build/images \
+//!     --image-cdn https://cdn.example.com/images \
+//!     --image-cdn-params "w={width}&fm={format}"
+//! ```
+//!
+//! URLs under `/images/` in each article's HTML, its frontmatter, its hero
+//! image sizes and social card, and the rendered pages are rewritten when
+//! the image-build manifest lists the image, so the CDN is never asked for an
+//! image that was not generated. Other URLs are left alone. The query string can use
+//! `{width}`, `{height}`, `{format}` and `{size}`, filled in from the manifest.
+
+use anyhow::{bail, Result};
+use common_models::{Article, ImageManifest, ImageVariant, IMAGE_MANIFEST_FILE};
+use regex::{Captures, Regex};
+use std::path::PathBuf;
+
+/// URL path relative image URLs are pointed at by the content pipeline
+pub const IMAGES_URL: &str = "/images";
+
+/// Settings for rewriting image URLs to a CDN
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageCdnOptions {
+    /// URL the image-build output is served from
+    pub base_url: String,
+    /// Query string added to each URL, with `{width}`, `{height}`, `{format}` and `{size}` placeholders
+    pub params: Option<String>,
+    /// Directory of image-build output holding the manifest
+    pub images_dir: PathBuf,
+}
+
+/// Rewrites image URLs to a CDN for the images in the manifest
+#[derive(Debug, Clone)]
+pub struct ImageCdn {
+    base_url: String,
+    params: Option<String>,
+    manifest: ImageManifest,
+    url: Regex,
+}
+
+impl ImageCdn {
+    /// Create a rewriter for the images in a manifest
+    pub fn new(base_url: &str, params: Option<String>, manifest: ImageManifest) -> Self {
+        // A URL starts the text or follows a quote, bracket, `=`, comma or space, as in `srcset`
+        let url = Regex::new(&format!(
+            r#"(^|[\s"'(=,]){}/([^\s"'()<>?#,\\]+)"#,
+            regex::escape(IMAGES_URL)
+        ))
+        .expect("valid regex");
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            params: params.filter(|params| !params.trim().is_empty()),
+            manifest,
+            url,
+        }
+    }
+
+    /// Create a rewriter from build options, reading the image-build manifest
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest is missing or can't be read
+    pub fn load(options: &ImageCdnOptions) -> Result<Self> {
+        let manifest_path = options.images_dir.join(IMAGE_MANIFEST_FILE);
+        let Some(manifest) = ImageManifest::load(&manifest_path)? else {
+            bail!(
+                "Image CDN rewriting needs the image-build manifest, but {} does not exist; run image-build first",
+                manifest_path.display()
+            );
+        };
+
+        Ok(Self::new(&options.base_url, options.params.clone(), manifest))
+    }
+
+    /// Get the CDN URL for a URL under `/images/`, if the manifest lists the image
+    pub fn rewrite_url(&self, url: &str) -> Option<String> {
+        let path = url.strip_prefix(IMAGES_URL)?.strip_prefix('/')?;
+        self.manifest.get(path).map(|variant| self.cdn_url(variant))
+    }
+
+    /// Rewrite every image URL in HTML or other text
+    pub fn rewrite_text(&self, text: &str) -> String {
+        self.url
+            .replace_all(text, |captures: &Captures<'_>| match self.manifest.get(&captures[2]) {
+                Some(variant) => format!("{}{}", &captures[1], self.cdn_url(variant)),
+                None => captures[0].to_string(),
+            })
+            .into_owned()
+    }
+
+    /// Rewrite the image URLs in an article's HTML, featured image, hero image sizes and social card
+    pub fn rewrite_article(&self, article: &mut Article) {
+        if let Some(html) = article.html.as_mut() {
+            *html = self.rewrite_text(html);
+        }
+
+        let featured = article.frontmatter.featured_image_path.as_deref().and_then(|url| self.rewrite_url(url));
+        if let Some(featured) = featured {
+            article.frontmatter.featured_image_path = Some(featured);
+        }
+
+        if let Some(card) = article.social_image.as_deref().and_then(|url| self.rewrite_url(url)) {
+            article.social_image = Some(card);
+        }
+
+        if let Some(hero) = article.hero_image.as_mut() {
+            for size in hero.sizes.values_mut() {
+                if let Some(url) = self.rewrite_url(&size.url) {
+                    size.url = url;
+                }
+            }
+        }
+    }
+
+    /// The CDN URL for an image
+    fn cdn_url(&self, variant: &ImageVariant) -> String {
+        let url = format!("{}/{}", self.base_url, variant.path);
+        match &self.params {
+            Some(params) => {
+                let query = params
+                    .trim_start_matches('?')
+                    .replace("{width}", &variant.width.to_string())
+                    .replace("{height}", &variant.height.to_string())
+                    .replace("{format}", &variant.format)
+                    .replace("{size}", &variant.size);
+                format!("{}?{}", url, query)
+            }
+            None => url,
+        }
+    }
+}
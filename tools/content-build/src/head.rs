@@ -0,0 +1,129 @@
+//! # Search Engine Controls
+//!
+//! Three frontmatter fields control how search engines treat an article:
+//!
+//! ```yaml
+//! canonical: https://example.com/blog/original   # or a site path like /blog/original
+//! noindex: true                                  # keep the page out of search results
+//! robots: noindex, nofollow                      # any robots directives
+//! ```
+//!
+//! `canonical` is also accepted as `canonical_url`. The build adds a
+//! `<link rel="canonical">` and a `<meta name="robots">` tag to the head of
+//! each article's HTML, unless the template already renders them, and
+//! leaves pages that shouldn't be indexed out of the sitemap.
+//!
+//! Articles with a social card or hero image also get `og:image` and
+//! `twitter:image` tags, so links shared on social sites show it.
+
+use common_models::Article;
+use handlebars::html_escape;
+
+/// Frontmatter field that keeps an article out of search results
+pub const NOINDEX_FIELD: &str = "noindex";
+
+/// Frontmatter field with robots directives for an article
+pub const ROBOTS_FIELD: &str = "robots";
+
+/// Generated size of the hero image preferred for social cards, a key of `images.sizes`
+pub const SOCIAL_IMAGE_SIZE: &str = "featured";
+
+/// Robots directives for an article, lowercased and without duplicates
+///
+/// Combines the `robots` field with `noindex: true`. Empty if the article
+/// sets neither, leaving search engines to their defaults.
+pub fn robots_directives(article: &Article) -> Vec<String> {
+    let mut directives: Vec<String> = Vec::new();
+    let listed = article.frontmatter.get_string(ROBOTS_FIELD).unwrap_or_default();
+    for directive in listed.split(',').map(|directive| directive.trim().to_lowercase()) {
+        if !directive.is_empty() && !directives.contains(&directive) {
+            directives.push(directive);
+        }
+    }
+
+    if article.frontmatter.get_bool(NOINDEX_FIELD).unwrap_or(false) && !directives.iter().any(|d| d == "noindex") {
+        directives.insert(0, "noindex".to_string());
+    }
+
+    directives
+}
+
+/// Whether search engines are asked not to index an article
+pub fn is_noindex(article: &Article) -> bool {
+    robots_directives(article)
+        .iter()
+        .any(|directive| directive == "noindex" || directive == "none")
+}
+
+/// Absolute URL of the image shown when an article is shared
+///
+/// Uses the social card image-build rendered for the article, else the hero
+/// image's `featured` size, its widest generated size or the original image.
+/// Site paths are resolved against the origin of the canonical link, and
+/// `None` is returned without one.
+pub fn social_image_url(article: &Article) -> Option<String> {
+    let url = match (&article.social_image, &article.hero_image) {
+        (Some(card), _) => card.as_str(),
+        (None, Some(hero)) => hero
+            .sizes
+            .get(SOCIAL_IMAGE_SIZE)
+            .or_else(|| hero.sizes.values().max_by_key(|size| size.width))
+            .map_or(hero.url.as_str(), |size| size.url.as_str()),
+        (None, None) => return None,
+    };
+    if !url.starts_with('/') || url.starts_with("//") {
+        return Some(url.to_string());
+    }
+
+    let canonical = article.links.iter().find(|link| link.rel == "canonical")?;
+    let host_start = canonical.href.find("://")? + 3;
+    let origin_end = canonical.href[host_start..].find('/').map_or(canonical.href.len(), |end| host_start + end);
+    Some(format!("{}{}", &canonical.href[..origin_end], url))
+}
+
+/// The canonical link, robots and social image tags for an article's page head
+///
+/// Tags the rendered page already has are left out, so templates that
+/// render the article's `links` keep control of them.
+pub fn head_tags(article: &Article, html: &str) -> String {
+    let mut tags = Vec::new();
+
+    let has_canonical = html.contains("rel=\"canonical\"") || html.contains("rel='canonical'");
+    if let Some(link) = article.links.iter().find(|link| link.rel == "canonical").filter(|_| !has_canonical) {
+        tags.push(format!("<link rel=\"canonical\" href=\"{}\">", html_escape(&link.href)));
+    }
+
+    let directives = robots_directives(article);
+    let has_robots = html.contains("name=\"robots\"") || html.contains("name='robots'");
+    if !directives.is_empty() && !has_robots {
+        tags.push(format!("<meta name=\"robots\" content=\"{}\">", html_escape(&directives.join(", "))));
+    }
+
+    if let Some(image) = social_image_url(article) {
+        let image = html_escape(&image);
+        if !html.contains("property=\"og:image\"") && !html.contains("property='og:image'") {
+            tags.push(format!("<meta property=\"og:image\" content=\"{}\">", image));
+        }
+        if !html.contains("name=\"twitter:card\"") && !html.contains("name='twitter:card'") {
+            tags.push("<meta name=\"twitter:card\" content=\"summary_large_image\">".to_string());
+            tags.push(format!("<meta name=\"twitter:image\" content=\"{}\">", image));
+        }
+    }
+
+    tags.join("\n")
+}
+
+/// Add an article's head tags to its rendered page
+///
+/// Tags go just before `</head>`, or at the start of pages without one.
+pub fn inject_head_tags(article: &Article, html: &str) -> String {
+    let tags = head_tags(article, html);
+    if tags.is_empty() {
+        return html.to_string();
+    }
+
+    match html.to_ascii_lowercase().find("</head>") {
+        Some(index) => format!("{}{}\n{}", &html[..index], tags, &html[index..]),
+        None => format!("{}\n{}", tags, html),
+    }
+}
@@ -0,0 +1,236 @@
+//! # Hero Images
+//!
+//! Each article gets a hero image for listings and social cards, the first of:
+//!
+//! 1. the image in the `cover` frontmatter field
+//! 2. `index.jpg` beside the content file, the image image-build resizes
+//! 3. the first image in the body
+//!
+//! Images beside the content file must exist and be at least
+//! [`MIN_HERO_WIDTH`] by [`MIN_HERO_HEIGHT`] pixels, the size of a social
+//! card. Remote and site-root images can't be checked and are used as they
+//! are. When the image-build manifest lists images generated for the
+//! article, the hero image carries their URLs for each size, and the
+//! article's `social_image` is the card image-build rendered for it.
+
+use anyhow::{bail, Context, Result};
+use common_models::{
+    Article, HeroImage, HeroImageSize, HeroImageSource, ImageManifest, ImageVariant, ANIMATED_SIZE, IMAGE_MANIFEST_FILE,
+    ORIGINAL_SIZE, POSTER_SIZE, SOCIAL_CARD_SIZE,
+};
+use pulldown_cmark::{Event, Parser, Tag};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::cdn::IMAGES_URL;
+
+/// Frontmatter field naming an article's hero image
+pub const COVER_FIELD: &str = "cover";
+
+/// Image beside the content file used when there is no `cover`
+pub const INDEX_IMAGE: &str = "index.jpg";
+
+/// Narrowest hero image accepted, in pixels
+pub const MIN_HERO_WIDTH: u32 = 1200;
+
+/// Shortest hero image accepted, in pixels
+pub const MIN_HERO_HEIGHT: u32 = 630;
+
+/// Size keys of images in the manifest that aren't resized from `index.jpg`
+const OTHER_SIZES: &[&str] = &[SOCIAL_CARD_SIZE, ORIGINAL_SIZE, POSTER_SIZE, ANIMATED_SIZE];
+
+/// Formats preferred for each generated size, in order
+const SIZE_FORMATS: &[&str] = &["jpg", "jpeg", "png", "webp", "avif", "gif"];
+
+/// Settings for choosing hero images
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeroImageOptions {
+    /// Directory of image-build output, for the sizes generated from each hero image
+    pub images_dir: Option<PathBuf>,
+    pub min_width: u32,
+    pub min_height: u32,
+}
+
+impl Default for HeroImageOptions {
+    fn default() -> Self {
+        Self {
+            images_dir: None,
+            min_width: MIN_HERO_WIDTH,
+            min_height: MIN_HERO_HEIGHT,
+        }
+    }
+}
+
+/// Choose an article's hero image, returning where it was found and its URL as written
+pub fn select_hero_image(article: &Article) -> Option<(HeroImageSource, String)> {
+    let cover = article.frontmatter.get_string(COVER_FIELD).map(str::trim).filter(|cover| !cover.is_empty());
+    if let Some(cover) = cover {
+        return Some((HeroImageSource::Cover, cover.to_string()));
+    }
+
+    if article_dir(article).join(INDEX_IMAGE).is_file() {
+        return Some((HeroImageSource::IndexImage, INDEX_IMAGE.to_string()));
+    }
+
+    first_body_image(&article.content).map(|url| (HeroImageSource::BodyImage, url))
+}
+
+/// Get the URL of the first image in markdown
+pub fn first_body_image(markdown: &str) -> Option<String> {
+    Parser::new(markdown).find_map(|event| match event {
+        Event::Start(Tag::Image(_, url, _)) => Some(url.to_string()),
+        _ => None,
+    })
+}
+
+/// Choose and check an article's hero image
+///
+/// Returns `None` if the article has no image to use.
+///
+/// # Errors
+///
+/// Returns an error if the image is beside the content file and is missing,
+/// can't be read or is smaller than the minimum size
+pub fn hero_image(article: &Article, options: &HeroImageOptions, manifest: Option<&ImageManifest>) -> Result<Option<HeroImage>> {
+    let Some((source, url)) = select_hero_image(article) else {
+        return Ok(None);
+    };
+
+    if !is_relative_url(&url) {
+        return Ok(Some(HeroImage {
+            source,
+            url,
+            width: None,
+            height: None,
+            sizes: BTreeMap::new(),
+        }));
+    }
+
+    let relative = url.trim_start_matches("./");
+    let path = article_dir(article).join(relative);
+    if !path.is_file() {
+        bail!("Hero image not found: {}", path.display());
+    }
+
+    let (width, height) =
+        image::image_dimensions(&path).with_context(|| format!("Failed to read hero image: {}", path.display()))?;
+    if width < options.min_width || height < options.min_height {
+        bail!(
+            "Hero image {} is {}x{}, smaller than the minimum of {}x{}",
+            path.display(),
+            width,
+            height,
+            options.min_width,
+            options.min_height
+        );
+    }
+
+    // image-build only resizes index.jpg
+    let sizes = match manifest {
+        Some(manifest) if relative == INDEX_IMAGE => generated_sizes(manifest, article),
+        _ => BTreeMap::new(),
+    };
+
+    Ok(Some(HeroImage {
+        source,
+        url: format!("{}/{}/{}/{}", IMAGES_URL, article.topic, article.slug, relative),
+        width: Some(width),
+        height: Some(height),
+        sizes,
+    }))
+}
+
+/// Set the hero image and social card of each article
+///
+/// An article whose image fails its checks is built without one, and a
+/// warning is logged.
+///
+/// # Errors
+///
+/// Returns an error if the image-build manifest can't be read
+pub fn add_hero_images(articles: &mut [Article], options: &HeroImageOptions) -> Result<()> {
+    let manifest = match &options.images_dir {
+        Some(images_dir) => ImageManifest::load(&images_dir.join(IMAGE_MANIFEST_FILE))?,
+        None => None,
+    };
+
+    for article in articles.iter_mut() {
+        match hero_image(article, options, manifest.as_ref()) {
+            Ok(hero) => article.hero_image = hero,
+            Err(err) => tracing::warn!(path = %article.path, "{:#}", err),
+        }
+        article.social_image = manifest.as_ref().and_then(|manifest| social_card(manifest, article));
+    }
+
+    Ok(())
+}
+
+/// URL of the social card image-build rendered for an article
+pub fn social_card(manifest: &ImageManifest, article: &Article) -> Option<String> {
+    let prefix = format!("{}/{}/", article.topic, article.slug);
+    manifest
+        .variants
+        .iter()
+        .find(|variant| variant.size == SOCIAL_CARD_SIZE && variant.path.starts_with(&prefix))
+        .map(|variant| format!("{}/{}", IMAGES_URL, variant.path))
+}
+
+/// The directory holding an article's content file
+fn article_dir(article: &Article) -> &Path {
+    Path::new(&article.path).parent().unwrap_or(Path::new(""))
+}
+
+/// Whether a URL points beside the content file, rather than at the site root or another site
+fn is_relative_url(url: &str) -> bool {
+    !(url.starts_with('/') || url.starts_with("data:") || url.contains("://"))
+}
+
+/// The image generated for each size of an article's `index.jpg`, in the best supported format
+fn generated_sizes(manifest: &ImageManifest, article: &Article) -> BTreeMap<String, HeroImageSize> {
+    // image-build mirrors the content layout: `{topic directory}/{slug}/`
+    let prefix = format!("{}/{}/", article.topic, article.slug);
+
+    // PNG fallbacks for SVG diagrams share the configured size keys
+    let svg_stems: Vec<&str> = manifest
+        .variants
+        .iter()
+        .filter(|variant| variant.path.starts_with(&prefix) && variant.format == "svg")
+        .map(|variant| variant.path.trim_end_matches(".svg"))
+        .collect();
+    let from_svg = |variant: &ImageVariant| {
+        variant.format == "png" && svg_stems.iter().any(|stem| variant.path.starts_with(&format!("{}-", stem)))
+    };
+
+    let mut best: BTreeMap<&str, &ImageVariant> = BTreeMap::new();
+    let resized = manifest.variants.iter().filter(|variant| {
+        variant.path.starts_with(&prefix) && !OTHER_SIZES.contains(&variant.size.as_str()) && !from_svg(variant)
+    });
+    for variant in resized {
+        match best.get(variant.size.as_str()) {
+            Some(current) if preference(current) <= preference(variant) => {}
+            _ => {
+                best.insert(&variant.size, variant);
+            }
+        }
+    }
+
+    best.into_iter()
+        .map(|(size, variant)| {
+            let generated = HeroImageSize {
+                url: format!("{}/{}", IMAGES_URL, variant.path),
+                width: variant.width,
+                height: variant.height,
+            };
+            (size.to_string(), generated)
+        })
+        .collect()
+}
+
+/// Sort key for variants of one size: the most widely supported format, then the shortest name
+fn preference(variant: &ImageVariant) -> (usize, usize) {
+    let format = SIZE_FORMATS
+        .iter()
+        .position(|format| format.eq_ignore_ascii_case(&variant.format))
+        .unwrap_or(SIZE_FORMATS.len());
+    (format, variant.path.len())
+}
@@ -1,20 +1,53 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use common_config::load_config;
-use common_fs::{create_dir_all, write_file};
-use common_markdown::extract_frontmatter_and_content;
-use common_models::Article;
+use common_fs::{create_dir_all, write_file_atomic, FileSystem, RealFileSystem};
+use common_git::ChangedArticle;
+use common_markdown::{
+    extract_frontmatter_and_content, generate_summary, Admonitions, Containers, DefinitionLists, ExternalLinks, HeadingAnchors,
+    RelativeImageUrls, ResponsiveTables, SmartTypography, SummaryOptions, TransformPipeline,
+};
+use common_models::{Article, Frontmatter, I18nConfig, LinkPolicyConfig, LocaleAlternate, PageLink, PublishDate, TypographyConfig};
+use common_plugins::{HookContext, HookEvent, HookRegistry};
+use common_traits::CancellationToken;
 use handlebars::Handlebars;
-use pulldown_cmark::{html, Options, Parser};
 use quick_xml::se::to_string;
-use regex::Regex;
-use rss::{ChannelBuilder, ItemBuilder};
+use rss::{ChannelBuilder, Enclosure, EnclosureBuilder, ItemBuilder};
 use serde::Serialize;
 use serde_json;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+pub mod cdn;
+pub mod head;
+pub mod hero;
+pub mod media;
+pub mod minify;
+pub mod preview;
+pub mod profile;
+pub mod report;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod series;
+pub mod shortcodes;
+pub mod watch;
+
+pub use cdn::{ImageCdn, ImageCdnOptions, IMAGES_URL};
+pub use head::{head_tags, inject_head_tags, is_noindex, robots_directives, social_image_url};
+pub use hero::{add_hero_images, first_body_image, hero_image, select_hero_image, social_card, HeroImageOptions};
+pub use media::AnimatedMedia;
+pub use minify::{minify_html, precompress_dir, Compression};
+pub use preview::{PreviewSite, Route};
+pub use profile::{ArticleProfile, BuildProfile, Phase, Profiler};
+pub use report::{BuildReport, SkippedContent};
+#[cfg(feature = "serve")]
+pub use serve::{serve, ServeOptions};
+pub use series::{link_series, SeriesIndex};
+pub use shortcodes::ShortcodeRegistry;
+pub use watch::ChangeWatcher;
+
 /// Options for the build process
 pub struct BuildOptions {
     pub output_dir: Option<String>,
@@ -26,6 +59,26 @@ pub struct BuildOptions {
     pub skip_rss: bool,
     pub skip_sitemap: bool,
     pub verbose: bool,
+    /// Number of sentences in summaries generated from the content
+    pub summary_sentences: usize,
+    /// What the RSS feed carries for each article
+    pub rss: RssOptions,
+    /// Build even when articles in different topics share a slug or canonical URL
+    pub allow_duplicate_slugs: bool,
+    /// Add creation and modification dates and contributors from git to each article
+    pub git_metadata: bool,
+    /// Minify rendered HTML and write compact JSON
+    pub minify: bool,
+    /// Formats to write precompressed copies of the text output in
+    pub precompress: Vec<Compression>,
+    /// File to write per-article phase timings to, as JSON
+    pub profile: Option<PathBuf>,
+    /// Point image URLs at a CDN for the images image-build generated
+    pub image_cdn: Option<ImageCdnOptions>,
+    /// How hero images are chosen and checked
+    pub hero_images: HeroImageOptions,
+    /// Only build articles changed since this git reference
+    pub changed_since: Option<String>,
 }
 
 impl Default for BuildOptions {
@@ -40,6 +93,40 @@ impl Default for BuildOptions {
             skip_rss: false,
             skip_sitemap: false,
             verbose: false,
+            summary_sentences: SummaryOptions::default().max_sentences,
+            rss: RssOptions::default(),
+            allow_duplicate_slugs: false,
+            git_metadata: false,
+            minify: false,
+            precompress: Vec::new(),
+            profile: None,
+            image_cdn: None,
+            hero_images: HeroImageOptions::default(),
+            changed_since: None,
+        }
+    }
+}
+
+/// Options for the RSS feed
+#[derive(Debug, Clone)]
+pub struct RssOptions {
+    /// Include each article's full HTML as `content:encoded`, not just its summary
+    pub full_content: bool,
+    /// Directory of image-build output, used to attach hero images as enclosures
+    pub images_dir: Option<PathBuf>,
+    /// URL path the image-build output is published at
+    pub images_url: String,
+    /// Image size (a key of `images.sizes`) used for enclosures
+    pub hero_size: String,
+}
+
+impl Default for RssOptions {
+    fn default() -> Self {
+        Self {
+            full_content: false,
+            images_dir: None,
+            images_url: "/images".to_string(),
+            hero_size: "featured".to_string(),
         }
     }
 }
@@ -48,93 +135,246 @@ impl Default for BuildOptions {
 pub fn process_content(
     content_path: &Path,
     include_drafts: bool,
+) -> Result<Article> {
+    process_content_for_site(content_path, include_drafts, &SiteContext::default())
+}
+
+/// Site-wide settings used when processing each article
+#[derive(Default)]
+pub struct SiteContext {
+    /// Site URL; links to its host are treated as internal
+    pub site_url: Option<String>,
+    /// Partials for MDX components
+    pub shortcodes: ShortcodeRegistry,
+    /// How summaries are generated when the frontmatter has none
+    pub summary: SummaryOptions,
+    /// Languages articles can be written in
+    pub i18n: I18nConfig,
+    /// Whether articles get typographic punctuation, unless their frontmatter says otherwise
+    pub typography: TypographyConfig,
+    /// Rules for links to other sites, such as which get `rel="nofollow"`
+    pub link_policy: LinkPolicyConfig,
+    /// Times each phase of processing an article, when profiling
+    pub profiler: Profiler,
+}
+
+/// Process a content file for a site
+pub fn process_content_for_site(
+    content_path: &Path,
+    include_drafts: bool,
+    site: &SiteContext,
 ) -> Result<Article> {
     // Check if the path is a directory
     let file_path = if content_path.is_dir() {
-        let index_path = content_path.join("index.mdx");
-        if index_path.exists() {
-            index_path
-        } else {
-            return Err(anyhow::anyhow!(
-                "Directory does not contain an index.mdx file: {:?}",
-                content_path
-            ));
+        match common_fs::resolve_content_dir(content_path) {
+            Some(file_path) => file_path,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "Directory does not contain a {{slug}}.md, {{slug}}.mdx, index.md or index.mdx file: {:?}",
+                    content_path
+                ));
+            }
         }
     } else {
         content_path.to_path_buf()
     };
 
+    let (slug, topic) = slug_and_topic(&RealFileSystem, content_path);
+    process_article_file(&RealFileSystem, &file_path, slug, topic, None, include_drafts, site)
+}
+
+/// Process a content file for a site, reading it through `fs`
+///
+/// Unlike [`process_content_for_site`], `file_path` must be the content file
+/// itself rather than its article directory.
+pub fn process_content_file_with_fs(
+    fs: &dyn FileSystem,
+    file_path: &Path,
+    include_drafts: bool,
+    site: &SiteContext,
+) -> Result<Article> {
+    let (slug, topic) = slug_and_topic(fs, file_path);
+    process_article_file(fs, file_path, slug, topic, None, include_drafts, site)
+}
+
+/// Derive the slug and topic of an article from its path
+///
+/// `content_path` is either the article directory or a content file inside
+/// it, so the topic is the directory above the article directory.
+fn slug_and_topic(fs: &dyn FileSystem, content_path: &Path) -> (String, String) {
+    let article_dir = if fs.dir_exists(content_path).unwrap_or(false) {
+        content_path
+    } else {
+        content_path.parent().unwrap_or(Path::new(""))
+    };
+
+    let name = |path: Option<&Path>| {
+        path.and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    (name(Some(article_dir)), name(article_dir.parent()))
+}
+
+/// Process every language version of an article
+///
+/// Without configured locales this is the article itself. Otherwise the main
+/// content file is in the language of its `lang` field, or the default
+/// locale, and each `{slug}.{locale}.md` beside it is a translation. Each
+/// version is returned with the path it was read from.
+pub fn process_localized(
+    content_path: &Path,
+    include_drafts: bool,
+    site: &SiteContext,
+) -> Vec<(PathBuf, Result<Article>)> {
+    if !site.i18n.is_enabled() || !content_path.is_dir() {
+        return vec![(content_path.to_path_buf(), process_content_for_site(content_path, include_drafts, site))];
+    }
+
+    let mut versions = Vec::new();
+
+    // An article can be written only in translations, with no main content file
+    if common_fs::resolve_content_dir(content_path).is_some() {
+        let main = process_content_for_site(content_path, include_drafts, site).and_then(|mut article| {
+            let lang = article.frontmatter.lang().unwrap_or(&site.i18n.default_locale).to_string();
+            if !site.i18n.is_available(&lang) {
+                return Err(anyhow::anyhow!(
+                    "Unknown locale '{}'; configured locales are {}",
+                    lang,
+                    site.i18n.available_locales().join(", ")
+                ));
+            }
+            article.lang = Some(lang);
+            Ok(article)
+        });
+        versions.push((content_path.to_path_buf(), main));
+    }
+
+    let (slug, topic) = slug_and_topic(&RealFileSystem, content_path);
+
+    // The file name, not the frontmatter, says which language a translation is in
+    for (locale, path) in common_fs::resolve_translations(content_path, &slug, &site.i18n.available_locales()) {
+        let translation = process_article_file(&RealFileSystem, &path, slug.clone(), topic.clone(), Some(&locale), include_drafts, site).map(|mut article| {
+            article.lang = Some(locale);
+            article
+        });
+        versions.push((path, translation));
+    }
+
+    versions
+}
+
+/// Process one content file of an article
+///
+/// `locale` is the language of a translation, taken from its file name.
+fn process_article_file(
+    fs: &dyn FileSystem,
+    file_path: &Path,
+    slug: String,
+    topic: String,
+    locale: Option<&str>,
+    include_drafts: bool,
+    site: &SiteContext,
+) -> Result<Article> {
     // Make sure the file exists
-    if !file_path.exists() {
+    if !fs.file_exists(file_path)? {
         return Err(anyhow::anyhow!("File not found: {:?}", file_path));
     }
 
+    let profile_key = file_path.to_str().unwrap_or("");
+
     // Read the file content
-    let content = fs::read_to_string(&file_path)
+    let content = site
+        .profiler
+        .time(profile_key, Phase::Read, || fs.read_file(file_path))
         .with_context(|| format!("Failed to read content file: {}", file_path.display()))?;
 
     // Extract frontmatter and markdown content
-    let (frontmatter, md_content) = extract_frontmatter_and_content(&content)?;
+    let (frontmatter, md_content) =
+        site.profiler.time(profile_key, Phase::Parse, || extract_frontmatter_and_content(&content))?;
+
+    // Archived content is kept in the repository but never published
+    if frontmatter.is_archived() {
+        return Err(anyhow::anyhow!("Skipping archived content"));
+    }
 
     // Skip draft content unless specifically included
     if frontmatter.is_draft.unwrap_or(false) && !include_drafts {
         return Err(anyhow::anyhow!("Skipping draft content"));
     }
 
-    // Convert markdown to HTML
-    let mut options = Options::empty();
-    options.insert(Options::ENABLE_STRIKETHROUGH);
-    options.insert(Options::ENABLE_TABLES);
-    options.insert(Options::ENABLE_FOOTNOTES);
-    options.insert(Options::ENABLE_TASKLISTS);
-    options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
-
-    let parser = Parser::new_ext(&md_content, options);
-    let mut html_content = String::new();
-    html::push_html(&mut html_content, parser);
+    // Skip scheduled content until its publication time unless drafts are included
+    if frontmatter.is_scheduled_after(Utc::now()) && !include_drafts {
+        return Err(anyhow::anyhow!("Skipping scheduled content"));
+    }
 
-    // Calculate reading time based on words (assuming avg reading speed of 200 wpm)
-    let word_count = md_content.split_whitespace().count();
-    let reading_time = (word_count as f64 / 200.0).ceil() as u32;
+    // Render MDX components, then convert markdown to HTML
+    let html_content = site.profiler.time(profile_key, Phase::Markdown, || -> Result<String> {
+        let expanded = site.shortcodes.expand(&md_content)?;
+        let mut pipeline = content_pipeline(&topic, &slug, site.site_url.as_deref(), &site.link_policy);
+        let typography = frontmatter.get_bool(TypographyConfig::FRONTMATTER_FIELD).unwrap_or(site.typography.enabled);
+        if typography {
+            let locale = locale.or(frontmatter.lang()).unwrap_or(&site.i18n.default_locale);
+            pipeline.push(Box::new(SmartTypography::new(locale)));
+        }
+        Ok(pipeline.render_html(&expanded))
+    })?;
 
-    // Derive slug from directory name or parent directory
-    let slug = if content_path.is_dir() {
-        content_path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("")
-            .to_string()
-    } else {
-        content_path
-            .parent()
-            .and_then(|parent| parent.file_name())
-            .and_then(|name| name.to_str())
-            .unwrap_or("")
-            .to_string()
+    // Prefer the summary written in the frontmatter
+    let summary = match frontmatter.summary_override() {
+        Some(summary) => Some(summary.to_string()),
+        None => generate_summary(&md_content, &site.summary),
     };
 
-    // Find topic from path
-    let topic = content_path
-        .parent()
-        .and_then(|parent| parent.file_name())
-        .and_then(|name| name.to_str())
-        .unwrap_or("")
-        .to_string();
+    // Count prose words and estimate reading time, skipping code and HTML
+    let word_count = common_markdown::analyze_words(&md_content).word_count();
+    let reading_time = common_markdown::calculate_reading_time_with_options(
+        &md_content,
+        &common_markdown::ReadingTimeOptions::default(),
+    );
 
     // Create article
     let article = Article {
         frontmatter,
         content: md_content,
-        slug: slug.clone(),
-        topic: topic.clone(),
+        slug,
+        topic,
         path: file_path.to_str().unwrap_or("").to_string(),
         word_count: Some(word_count),
         reading_time: Some(reading_time),
+        series_navigation: None,
+        html: Some(html_content),
+        summary,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
     };
 
     Ok(article)
 }
 
+/// Transforms applied to an article's markdown before rendering HTML
+///
+/// The markdown extensions run first, since they restructure whole blocks.
+/// Relative image URLs are pointed at the image-build output for the article,
+/// `/images/{topic}/{slug}/`, and links to the link policy's
+/// `nofollow_domains` get `rel="nofollow"`.
+pub fn content_pipeline(topic: &str, slug: &str, site_url: Option<&str>, link_policy: &LinkPolicyConfig) -> TransformPipeline {
+    TransformPipeline::new()
+        .with(Admonitions)
+        .with(Containers)
+        .with(DefinitionLists)
+        .with(HeadingAnchors)
+        .with(RelativeImageUrls::new(format!("/images/{}/{}", topic, slug)))
+        .with(ResponsiveTables::default())
+        .with(ExternalLinks::new(site_url).with_nofollow(link_policy.nofollow_domains.clone()))
+}
+
 /// Find all content files in a given directory
 pub fn find_content_files(
     base_dir: &Path,
@@ -155,30 +395,7 @@ pub fn find_content_files(
             return Err(anyhow::anyhow!("Topic directory not found: {:?}", topic_dir));
         }
 
-        // Walk through the topic directory
-        for entry in WalkDir::new(&topic_dir)
-            .min_depth(1)
-            .max_depth(2)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-
-            // Check for subdirectories with index.mdx or for *.mdx files
-            if path.is_dir() {
-                let index_path = path.join("index.mdx");
-                if index_path.exists() {
-                    content_files.push(path.to_path_buf());
-                }
-            } else if path.extension().map_or(false, |ext| ext == "mdx") {
-                if path.file_name().map_or(false, |name| name != "index.mdx") {
-                    // Include the parent directory for standalone mdx files
-                    if let Some(parent) = path.parent() {
-                        content_files.push(parent.to_path_buf());
-                    }
-                }
-            }
-        }
+        content_files.extend(article_dirs(&topic_dir, &config.i18n));
     } else {
         // No topic specified, check all topics
         for (topic_key, _topic_config) in &config.content.topics {
@@ -187,36 +404,72 @@ pub fn find_content_files(
                 continue;
             }
 
-            // Walk through the topic directory
-            for entry in WalkDir::new(&topic_dir)
-                .min_depth(1)
-                .max_depth(2)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path();
-
-                // Check for subdirectories with index.mdx or for *.mdx files
-                if path.is_dir() {
-                    let index_path = path.join("index.mdx");
-                    if index_path.exists() {
-                        content_files.push(path.to_path_buf());
-                    }
-                } else if path.extension().map_or(false, |ext| ext == "mdx") {
-                    if path.file_name().map_or(false, |name| name != "index.mdx") {
-                        // Include the parent directory for standalone mdx files
-                        if let Some(parent) = path.parent() {
-                            content_files.push(parent.to_path_buf());
-                        }
-                    }
-                }
-            }
+            content_files.extend(article_dirs(&topic_dir, &config.i18n));
         }
     }
 
     Ok(content_files)
 }
 
+/// Article directories in a topic directory that hold a content file or a translation
+fn article_dirs(topic_dir: &Path, i18n: &I18nConfig) -> Vec<PathBuf> {
+    let has_translations = |path: &Path| {
+        i18n.is_enabled()
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|slug| !common_fs::resolve_translations(path, slug, &i18n.available_locales()).is_empty())
+    };
+
+    let mut dirs: Vec<PathBuf> = WalkDir::new(topic_dir)
+        .min_depth(1)
+        .max_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_dir())
+        .map(|entry| entry.into_path())
+        .filter(|path| common_fs::resolve_content_dir(path).is_some() || has_translations(path))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// List articles scheduled for future publication
+///
+/// Returns non-draft articles whose `publish_at` is in the future, sorted by
+/// publication time with the soonest first.
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be loaded
+pub fn list_scheduled() -> Result<Vec<Article>> {
+    let config = load_config()?;
+    let content_base_dir = PathBuf::from(&config.content.base_dir);
+    let now = Utc::now();
+
+    let mut scheduled: Vec<(chrono::DateTime<chrono::FixedOffset>, Article)> = Vec::new();
+    for content_path in find_content_files(&content_base_dir, None)? {
+        let article = match process_content(&content_path, true) {
+            Ok(article) => article,
+            Err(_) => continue,
+        };
+
+        if article.frontmatter.is_draft.unwrap_or(false) {
+            continue;
+        }
+
+        if let Some(Ok(publish_at)) = article.frontmatter.publish_at_datetime() {
+            if publish_at > now {
+                scheduled.push((publish_at, article));
+            }
+        }
+    }
+
+    scheduled.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(scheduled.into_iter().map(|(_, article)| article).collect())
+}
+
 /// Filter content files to find a specific content item by slug
 pub fn find_content_by_slug(
     base_dir: &Path,
@@ -241,7 +494,18 @@ pub fn find_content_by_slug(
 }
 
 /// Process content and generate output files
-pub fn build_content(options: &BuildOptions) -> Result<()> {
+pub fn build_content(options: &BuildOptions) -> Result<BuildReport> {
+    build_content_cancellable(options, &CancellationToken::new())
+}
+
+/// Process content and generate output files, stopping early if `token` is cancelled
+///
+/// Cancellation is checked between articles and between output phases. When a
+/// build is cancelled while processing articles, the JSON files for articles that
+/// were already processed are still written before the cancelled error is returned.
+pub fn build_content_cancellable(options: &BuildOptions, token: &CancellationToken) -> Result<BuildReport> {
+    let _span = tracing::info_span!("build").entered();
+
     // Load config
     let config = load_config()?;
 
@@ -254,11 +518,21 @@ pub fn build_content(options: &BuildOptions) -> Result<()> {
         None => PathBuf::from("public"),
     };
 
+    // Lifecycle hooks can veto the build before anything is written
+    let hooks = HookRegistry::from_config(&config)?;
+    let mut hook_context = HookContext::new(HookEvent::PreBuild).with_path(&output_dir);
+    hook_context.topic = options.topic.clone();
+    hook_context.slug = options.slug.clone();
+    hooks.fire(&hook_context)?;
+
+    // Read the image-build manifest up front, so a missing one fails before anything is written
+    let image_cdn = options.image_cdn.as_ref().map(ImageCdn::load).transpose()?;
+
     // Create output directory if it doesn't exist
     create_dir_all(&output_dir)?;
 
     // Find content to process
-    let content_files = if let Some(slug) = &options.slug {
+    let mut content_files = if let Some(slug) = &options.slug {
         // Process a single content item
         let content_path = find_content_by_slug(
             &content_base_dir,
@@ -274,22 +548,70 @@ pub fn build_content(options: &BuildOptions) -> Result<()> {
         find_content_files(&content_base_dir, None)?
     };
 
+    // Only the articles changed since the requested git reference, if any
+    if let Some(reference) = &options.changed_since {
+        let changed = common_git::changed_content_since(&config, reference)?;
+        content_files.retain(|path| {
+            let (slug, topic) = slug_and_topic(&RealFileSystem, path);
+            changed.contains(&ChangedArticle { topic, slug })
+        });
+
+        // Nothing to rebuild is a normal outcome for an incremental build
+        if content_files.is_empty() {
+            tracing::info!("No content changed since {}", reference);
+            return Ok(BuildReport { output_dir, ..Default::default() });
+        }
+    }
+
     if content_files.is_empty() {
         return Err(anyhow::anyhow!("No content found to process"));
     }
 
+    // Load the partials for MDX components and the other site-wide settings
+    let site = SiteContext {
+        site_url: config.publication.site_url.clone(),
+        shortcodes: ShortcodeRegistry::load(&config, Path::new("templates"))?,
+        summary: SummaryOptions {
+            max_sentences: options.summary_sentences,
+            ..Default::default()
+        },
+        i18n: config.i18n.clone(),
+        typography: config.typography.clone(),
+        link_policy: config.link_policy.clone(),
+        profiler: if options.profile.is_some() { Profiler::enabled() } else { Profiler::default() },
+    };
+
+    let mut report = BuildReport {
+        output_dir: output_dir.clone(),
+        ..Default::default()
+    };
+
     // Process each content item
     let mut articles = Vec::new();
     for content_path in &content_files {
-        match process_content(content_path, options.include_drafts) {
-            Ok(article) => {
-                articles.push(article);
-                if options.verbose {
-                    println!("Processed: {}", content_path.display());
+        if let Err(err) = token.check("content build") {
+            // Flush what has been processed so far before stopping
+            if !options.skip_json {
+                for (tree_dir, articles) in locale_trees(articles, &output_dir, &config.i18n) {
+                    write_article_json(&tree_dir.join("data"), &articles, options.minify, &site.profiler)?;
                 }
             }
-            Err(err) => {
-                eprintln!("Error processing {}: {}", content_path.display(), err);
+            return Err(err.into());
+        }
+
+        for (content_path, result) in process_localized(content_path, options.include_drafts, &site) {
+            match result {
+                Ok(article) => {
+                    articles.push(article);
+                    if options.verbose {
+                        println!("Processed: {}", content_path.display());
+                    }
+                    tracing::debug!(path = %content_path.display(), "Processed content");
+                }
+                Err(err) => {
+                    tracing::error!(path = %content_path.display(), "Error processing content: {:#}", err);
+                    report.skipped.push(SkippedContent { path: content_path, reason: format!("{:#}", err) });
+                }
             }
         }
     }
@@ -298,31 +620,128 @@ pub fn build_content(options: &BuildOptions) -> Result<()> {
         return Err(anyhow::anyhow!("No content items were processed successfully"));
     }
 
+    // Revision dates are a nice-to-have, so a build outside a repository still goes ahead
+    if options.git_metadata {
+        if let Err(err) = add_git_metadata(&mut articles) {
+            tracing::warn!("Git metadata unavailable: {}", err.message());
+        }
+    }
+
+    // Link the versions of each article in different languages
+    let site_url = config.publication.site_url.clone().unwrap_or_else(|| "https://example.com".to_string());
+    link_translations(&mut articles, &site_url, &config.i18n);
+    add_page_links(&mut articles, &site_url);
+    add_hero_images(&mut articles, &options.hero_images)?;
+
+    // Before the CDN rewrite, so the URLs in the new markup are rewritten too
+    if let Some(images_dir) = &options.hero_images.images_dir {
+        if let Some(media) = AnimatedMedia::load(images_dir)? {
+            articles.iter_mut().for_each(|article| media.rewrite_article(article));
+        }
+    }
+
+    if let Some(cdn) = &image_cdn {
+        articles.iter_mut().for_each(|article| cdn.rewrite_article(article));
+    }
+
+    // Output files are named by slug, so colliding articles would overwrite each other
+    let collisions = find_collisions(&articles, &site_url);
+    if !collisions.is_empty() {
+        let report = collisions.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n  ");
+        if !options.allow_duplicate_slugs {
+            return Err(anyhow::anyhow!(
+                "Colliding content URLs:\n  {}\nRename the articles, or pass --allow-duplicate-slugs",
+                report
+            ));
+        }
+        tracing::warn!("Colliding content URLs:\n  {}", report);
+    }
+
+    report.articles = articles.len();
+
+    // Generate sitemap if not skipped
+    if !options.skip_sitemap {
+        token.check("content build")?;
+        generate_sitemap(&output_dir, &articles, &config)?;
+    }
+
+    // Each locale gets its own output tree, with the default locale at the root
+    for (tree_dir, mut articles) in locale_trees(articles, &output_dir, &config.i18n) {
+        build_tree(&tree_dir, &mut articles, options, &config, &site.profiler, image_cdn.as_ref(), token)?;
+    }
+
+    // Publish redirects for moved or renamed content
+    token.check("content build")?;
+    let redirects = common_config::load_redirects(&common_config::redirects_path(&config))?;
+    generate_redirects(&output_dir, &redirects)?;
+    report.redirects = redirects.len();
+
+    // Compress last, so every text file written above gets a copy
+    if !options.precompress.is_empty() {
+        token.check("content build")?;
+        let written = precompress_dir(&output_dir, &options.precompress)?;
+        tracing::debug!(files = written.len(), "Wrote precompressed copies");
+        report.precompressed = written.len();
+    }
+
+    if let Some(profile_path) = &options.profile {
+        let profile = site.profiler.profile();
+        profile.save(profile_path)?;
+        report.profile = Some(profile);
+    }
+
+    if let Err(err) = hooks.fire(&hook_context.with_event(HookEvent::PostBuild)) {
+        tracing::warn!("{}", err);
+    }
+
+    Ok(report)
+}
+
+/// Write the JSON, HTML and RSS output for the articles in one language
+fn build_tree(
+    output_dir: &Path,
+    articles: &mut [Article],
+    options: &BuildOptions,
+    config: &common_models::Config,
+    profiler: &Profiler,
+    image_cdn: Option<&ImageCdn>,
+    token: &CancellationToken,
+) -> Result<()> {
+    // Order series and link each part to its neighbours
+    if let Err(err) = common_validation::validate_series(articles) {
+        tracing::warn!("{}", err);
+    }
+    let series = link_series(articles);
+
     // Generate JSON files if not skipped
     if !options.skip_json {
-        // Create data directory
-        let data_dir = output_dir.join("data");
-        create_dir_all(&data_dir)?;
+        token.check("content build")?;
 
         // Write individual JSON files
-        for article in &articles {
-            let json_path = data_dir.join(format!("{}.json", article.slug));
-            let json = serde_json::to_string_pretty(&article)
-                .with_context(|| format!("Failed to serialize article to JSON: {}", article.slug))?;
-            write_file(&json_path, &json)
-                .with_context(|| format!("Failed to write JSON file: {:?}", json_path))?;
-        }
+        let data_dir = output_dir.join("data");
+        write_article_json(&data_dir, articles, options.minify, profiler)?;
 
         // Write all.json
         let all_json_path = data_dir.join("all.json");
-        let json = serde_json::to_string_pretty(&articles)
+        let json = minify::to_json(articles, options.minify)
             .with_context(|| "Failed to serialize all articles to JSON")?;
-        write_file(&all_json_path, &json)
+        write_file_atomic(&all_json_path, &json)
             .with_context(|| format!("Failed to write all.json file: {:?}", all_json_path))?;
+
+        // Write series.json
+        if !series.is_empty() {
+            let series_json_path = data_dir.join("series.json");
+            let json = minify::to_json(&series, options.minify)
+                .with_context(|| "Failed to serialize series index to JSON")?;
+            write_file_atomic(&series_json_path, &json)
+                .with_context(|| format!("Failed to write series.json file: {:?}", series_json_path))?;
+        }
     }
 
     // Generate HTML files if not skipped and templates are available
     if !options.skip_html {
+        token.check("content build")?;
+
         // Check if templates directory exists
         let templates_dir = PathBuf::from("templates");
         if templates_dir.exists() {
@@ -334,32 +753,128 @@ pub fn build_content(options: &BuildOptions) -> Result<()> {
 
                 // Set up handlebars
                 let mut handlebars = Handlebars::new();
+                common_templates::helpers::register_helpers(&mut handlebars);
                 handlebars
                     .register_template_file("article", template_file)
                     .with_context(|| "Failed to register article template")?;
 
                 // Render HTML for each content item
-                for article in &articles {
+                for article in articles.iter() {
+                    token.check("content build")?;
+
                     let html_path = html_dir.join(format!("{}.html", article.slug));
-                    let rendered = handlebars
-                        .render("article", &article)
-                        .with_context(|| format!("Failed to render HTML for {}", article.slug))?;
+                    let rendered = profiler.time(&article.path, Phase::Template, || -> Result<String> {
+                        let mut rendered = handlebars
+                            .render("article", &article)
+                            .with_context(|| format!("Failed to render HTML for {}", article.slug))?;
+                        rendered = inject_head_tags(article, &rendered);
+                        if let Some(cdn) = image_cdn {
+                            rendered = cdn.rewrite_text(&rendered);
+                        }
+                        if options.minify {
+                            rendered = minify_html(&rendered);
+                        }
+                        Ok(rendered)
+                    })?;
 
-                    write_file(&html_path, &rendered)
+                    profiler
+                        .time(&article.path, Phase::Write, || write_file_atomic(&html_path, &rendered))
                         .with_context(|| format!("Failed to write HTML file: {:?}", html_path))?;
                 }
             }
         }
     }
 
-    // Generate sitemap if not skipped
-    if !options.skip_sitemap {
-        generate_sitemap(&output_dir, &articles, &config)?;
-    }
-
     // Generate RSS feed if not skipped
     if !options.skip_rss {
-        generate_rss_feed(&output_dir, &articles, &config)?;
+        token.check("content build")?;
+        generate_rss_feed_with(output_dir, articles, config, &options.rss)?;
+    }
+
+    Ok(())
+}
+
+/// Split articles into the output tree for each language
+///
+/// Articles in the default locale, or without a language, go in
+/// `output_dir`; articles in other locales go in `output_dir/{locale}`.
+/// Trees are returned with the root first.
+pub fn locale_trees(articles: Vec<Article>, output_dir: &Path, i18n: &I18nConfig) -> Vec<(PathBuf, Vec<Article>)> {
+    let mut trees: BTreeMap<PathBuf, Vec<Article>> = BTreeMap::new();
+    for article in articles {
+        let tree_dir = match article.lang.as_deref() {
+            Some(lang) if !i18n.is_default(lang) => output_dir.join(lang),
+            _ => output_dir.to_path_buf(),
+        };
+        trees.entry(tree_dir).or_default().push(article);
+    }
+    trees.into_iter().collect()
+}
+
+/// Set the alternates of each article to its versions in every language
+///
+/// Versions of an article share a topic and slug. Articles in the default
+/// locale are published at `{site_url}/{topic}/{slug}`, and the others at
+/// `{site_url}/{locale}/{topic}/{slug}`, unless a `canonical_url` says
+/// otherwise. Articles without a language are left alone.
+pub fn link_translations(articles: &mut [Article], site_url: &str, i18n: &I18nConfig) {
+    let mut versions: BTreeMap<(String, String), Vec<LocaleAlternate>> = BTreeMap::new();
+    for article in articles.iter() {
+        let Some(lang) = article.lang.clone() else { continue };
+        let url = frontmatter_canonical_url(&article.frontmatter, site_url).unwrap_or_else(|| {
+            let site_url = site_url.trim_end_matches('/');
+            if i18n.is_default(&lang) {
+                format!("{}/{}/{}", site_url, article.topic, article.slug)
+            } else {
+                format!("{}/{}/{}/{}", site_url, lang, article.topic, article.slug)
+            }
+        });
+        versions
+            .entry((article.topic.clone(), article.slug.clone()))
+            .or_default()
+            .push(LocaleAlternate { lang, url });
+    }
+
+    // List the versions in the order the locales are configured
+    let locales = i18n.available_locales();
+    for alternates in versions.values_mut() {
+        alternates.sort_by_key(|alternate| locales.iter().position(|locale| *locale == alternate.lang));
+    }
+
+    for article in articles.iter_mut().filter(|article| article.lang.is_some()) {
+        if let Some(alternates) = versions.get(&(article.topic.clone(), article.slug.clone())) {
+            article.alternates = alternates.clone();
+        }
+    }
+}
+
+/// Set each article's git revision history from its content file
+///
+/// Articles whose content file has never been committed are left without one.
+pub fn add_git_metadata(articles: &mut [Article]) -> common_errors::Result<()> {
+    let paths: Vec<PathBuf> = articles.iter().map(|article| PathBuf::from(&article.path)).collect();
+    let mut history = common_git::file_history(&paths)?;
+
+    for (article, path) in articles.iter_mut().zip(&paths) {
+        article.git = history.remove(path);
+    }
+
+    Ok(())
+}
+
+/// Write one JSON file per article into the data directory
+fn write_article_json(data_dir: &Path, articles: &[Article], minify: bool, profiler: &Profiler) -> Result<()> {
+    create_dir_all(data_dir)?;
+
+    for article in articles {
+        let json_path = data_dir.join(format!("{}.json", article.slug));
+        profiler.time(&article.path, Phase::Write, || -> Result<()> {
+            let json = minify::to_json(article, minify)
+                .with_context(|| format!("Failed to serialize article to JSON: {}", article.slug))?;
+            write_file_atomic(&json_path, &json)
+                .with_context(|| format!("Failed to write JSON file: {:?}", json_path))?;
+            Ok(())
+        })?;
     }
 
     Ok(())
@@ -376,6 +891,8 @@ struct Sitemap {
 struct UrlSet {
     #[serde(rename = "@xmlns")]
     xmlns: String,
+    #[serde(rename = "@xmlns:xhtml", skip_serializing_if = "Option::is_none")]
+    xmlns_xhtml: Option<String>,
     #[serde(rename = "url")]
     urls: Vec<SitemapUrl>,
 }
@@ -386,6 +903,151 @@ struct SitemapUrl {
     lastmod: String,
     changefreq: String,
     priority: String,
+    #[serde(rename = "xhtml:link", skip_serializing_if = "Vec::is_empty")]
+    alternates: Vec<HreflangLink>,
+}
+
+/// An `hreflang` link to a version of a page in another language
+#[derive(Serialize)]
+struct HreflangLink {
+    #[serde(rename = "@rel")]
+    rel: &'static str,
+    #[serde(rename = "@hreflang")]
+    hreflang: String,
+    #[serde(rename = "@href")]
+    href: String,
+}
+
+/// Resolve the canonical URL of an article
+///
+/// A `canonical` (or `canonical_url`) in the frontmatter wins: absolute URLs
+/// are used as they are, and paths are resolved against the site URL.
+/// Otherwise the article lives at `{site_url}/{topic}/{slug}`, or at the URL
+/// of its language version set by [`link_translations`].
+pub fn canonical_url(article: &Article, site_url: &str) -> String {
+    if let Some(url) = frontmatter_canonical_url(&article.frontmatter, site_url) {
+        return url;
+    }
+
+    // Translations are published under their locale
+    let own_version = article
+        .alternates
+        .iter()
+        .find(|alternate| article.lang.as_ref() == Some(&alternate.lang));
+    match own_version {
+        Some(alternate) => alternate.url.clone(),
+        None => canonical_url_for(&article.frontmatter, &article.topic, &article.slug, site_url),
+    }
+}
+
+/// Resolve the canonical URL of an article that hasn't been built
+///
+/// The same as [`canonical_url`] for tools that read the frontmatter
+/// themselves, except that translations aren't known, so the article is
+/// taken to be in the default language.
+pub fn canonical_url_for(frontmatter: &Frontmatter, topic: &str, slug: &str, site_url: &str) -> String {
+    frontmatter_canonical_url(frontmatter, site_url)
+        .unwrap_or_else(|| format!("{}/{}/{}", site_url.trim_end_matches('/'), topic, slug))
+}
+
+/// The canonical URL set in an article's frontmatter, resolved against the site URL
+fn frontmatter_canonical_url(frontmatter: &Frontmatter, site_url: &str) -> Option<String> {
+    let site_url = site_url.trim_end_matches('/');
+
+    let canonical = ["canonical", "canonical_url"]
+        .iter()
+        .find_map(|field| frontmatter.get_string(field));
+    match canonical.map(str::trim) {
+        Some(url) if url.contains("://") => Some(url.to_string()),
+        Some(path) if !path.is_empty() => Some(format!("{}/{}", site_url, path.trim_start_matches('/'))),
+        _ => None,
+    }
+}
+
+/// Set the head links of each article from [`page_links`]
+pub fn add_page_links(articles: &mut [Article], site_url: &str) {
+    for article in articles.iter_mut() {
+        article.links = page_links(article, site_url);
+    }
+}
+
+/// Links for the head of an article's page
+///
+/// The first is the article's `canonical` URL. Each copy listed in its
+/// `syndicated_to` field follows as a `syndication` link, so readers and
+/// Webmention receivers can find the posts elsewhere.
+pub fn page_links(article: &Article, site_url: &str) -> Vec<PageLink> {
+    let mut links = vec![PageLink {
+        rel: "canonical".to_string(),
+        href: canonical_url(article, site_url),
+    }];
+
+    for url in article.frontmatter.syndicated_to() {
+        if !links.iter().any(|link| link.href == url) {
+            links.push(PageLink {
+                rel: "syndication".to_string(),
+                href: url.to_string(),
+            });
+        }
+    }
+
+    links
+}
+
+/// Articles that would overwrite each other in the build output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Collision {
+    /// The same slug in more than one topic
+    Slug { slug: String, topics: Vec<String> },
+    /// More than one article with the same canonical URL, as `topic/slug`
+    CanonicalUrl { url: String, articles: Vec<String> },
+}
+
+impl std::fmt::Display for Collision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Collision::Slug { slug, topics } => {
+                write!(f, "slug '{}' is used in topics {}", slug, topics.join(", "))
+            }
+            Collision::CanonicalUrl { url, articles } => {
+                write!(f, "{} is the canonical URL of {}", url, articles.join(", "))
+            }
+        }
+    }
+}
+
+/// Find the articles whose slugs or canonical URLs collide
+pub fn find_collisions(articles: &[Article], site_url: &str) -> Vec<Collision> {
+    // Each language has its own output tree, so slugs only collide within one
+    let mut slugs: BTreeMap<(Option<&str>, &str), Vec<String>> = BTreeMap::new();
+    let mut urls: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for article in articles {
+        slugs.entry((article.lang.as_deref(), &article.slug)).or_default().push(article.topic.clone());
+        urls.entry(canonical_url(article, site_url))
+            .or_default()
+            .push(format!("{}/{}", article.topic, article.slug));
+    }
+
+    let mut collisions: Vec<Collision> = slugs
+        .into_iter()
+        .filter(|(_, topics)| topics.len() > 1)
+        .map(|((_, slug), mut topics)| {
+            topics.sort();
+            Collision::Slug { slug: slug.to_string(), topics }
+        })
+        .collect();
+
+    collisions.extend(
+        urls.into_iter()
+            .filter(|(_, articles)| articles.len() > 1)
+            .map(|(url, mut articles)| {
+                articles.sort();
+                Collision::CanonicalUrl { url, articles }
+            }),
+    );
+
+    collisions
 }
 
 /// Generate XML sitemap
@@ -403,6 +1065,7 @@ pub fn generate_sitemap(
         lastmod: Utc::now().format("%Y-%m-%d").to_string(),
         changefreq: "daily".to_string(),
         priority: "1.0".to_string(),
+        alternates: Vec::new(),
     });
 
     // Add topic pages
@@ -412,34 +1075,61 @@ pub fn generate_sitemap(
             lastmod: Utc::now().format("%Y-%m-%d").to_string(),
             changefreq: "weekly".to_string(),
             priority: "0.8".to_string(),
+            alternates: Vec::new(),
         });
     }
 
     // Add content pages
-    for article in articles {
-        if article.frontmatter.is_draft.unwrap_or(false) {
-            continue;
-        }
-
-        let url = format!("{}/{}/{}", site_url, article.topic, article.slug);
+    let now = Utc::now();
+    let listed: Vec<(&Article, String)> = articles
+        .iter()
+        .filter(|article| !article.frontmatter.is_draft.unwrap_or(false) && !article.frontmatter.is_scheduled_after(now))
+        // Pages search engines shouldn't index don't belong in the sitemap either
+        .filter(|article| !is_noindex(article))
+        .map(|article| (article, canonical_url(article, &site_url)))
+        // Articles that are canonical elsewhere don't belong in this site's sitemap
+        .filter(|(_, url)| url.starts_with(&site_url))
+        .collect();
+
+    for (article, url) in &listed {
+        // Link every listed language version, including this one, and the default as x-default
+        let versions: Vec<&LocaleAlternate> = article
+            .alternates
+            .iter()
+            .filter(|alternate| listed.iter().any(|(_, listed_url)| *listed_url == alternate.url))
+            .collect();
+        let alternates = if versions.len() > 1 { hreflang_links(&versions, &config.i18n) } else { Vec::new() };
+
+        // The last commit is a better signal of a change than the publish date
+        let git = article.git.as_ref();
         let last_mod = article.frontmatter.updated_at
             .as_ref()
-            .or(article.frontmatter.published_at.as_ref())
-            .unwrap_or(&"".to_string())
-            .to_string();
+            .filter(|date| !date.is_draft())
+            .map(ToString::to_string)
+            .or_else(|| git.map(|git| git.last_modified.format("%Y-%m-%d").to_string()))
+            .or_else(|| {
+                article.frontmatter.published_at
+                    .as_ref()
+                    .filter(|date| !date.is_draft())
+                    .map(ToString::to_string)
+            })
+            .unwrap_or_default();
 
         urls.push(SitemapUrl {
-            loc: url,
+            loc: url.clone(),
             lastmod: last_mod,
             changefreq: "monthly".to_string(),
             priority: "0.7".to_string(),
+            alternates,
         });
     }
 
     // Create the sitemap
+    let has_alternates = urls.iter().any(|url| !url.alternates.is_empty());
     let sitemap = Sitemap {
         urlset: UrlSet {
             xmlns: "http://www.sitemaps.org/schemas/sitemap/0.9".to_string(),
+            xmlns_xhtml: has_alternates.then(|| "http://www.w3.org/1999/xhtml".to_string()),
             urls,
         },
     };
@@ -449,33 +1139,56 @@ pub fn generate_sitemap(
 
     // Write to file
     let sitemap_path = output_dir.join("sitemap.xml");
-    write_file(&sitemap_path, &xml)
+    write_file_atomic(&sitemap_path, &xml)
         .with_context(|| format!("Failed to write sitemap file: {:?}", sitemap_path))?;
 
     Ok(())
 }
 
+/// Links to each language version of a page, with the default locale as `x-default`
+fn hreflang_links(versions: &[&LocaleAlternate], i18n: &I18nConfig) -> Vec<HreflangLink> {
+    let link = |hreflang: &str, href: &str| HreflangLink {
+        rel: "alternate",
+        hreflang: hreflang.to_string(),
+        href: href.to_string(),
+    };
+
+    let mut links: Vec<HreflangLink> = versions.iter().map(|version| link(&version.lang, &version.url)).collect();
+    if let Some(default) = versions.iter().find(|version| i18n.is_default(&version.lang)) {
+        links.push(link("x-default", &default.url));
+    }
+    links
+}
+
 /// Generate RSS feed
 pub fn generate_rss_feed(
     output_dir: &Path,
     articles: &[Article],
     config: &common_models::Config,
+) -> Result<()> {
+    generate_rss_feed_with(output_dir, articles, config, &RssOptions::default())
+}
+
+/// Generate RSS feed with summaries or full content, and hero image enclosures
+pub fn generate_rss_feed_with(
+    output_dir: &Path,
+    articles: &[Article],
+    config: &common_models::Config,
+    options: &RssOptions,
 ) -> Result<()> {
     let site_url = config.publication.site_url.clone().unwrap_or_else(|| "https://example.com".to_string());
     let site_title = config.publication.author.clone();
     let site_description = "Articles and content".to_string();
-    let empty_string = "".to_string();
 
-    // Sort content items by date (newest first)
+    // Sort content items by date (newest first, undated last)
     let mut sorted_articles = articles.to_vec();
-    sorted_articles.sort_by(|a, b| {
-        let a_date = a.frontmatter.published_at.as_ref().unwrap_or(&empty_string);
-        let b_date = b.frontmatter.published_at.as_ref().unwrap_or(&empty_string);
-        b_date.cmp(a_date)
-    });
+    sorted_articles.sort_by(|a, b| b.frontmatter.published_at.cmp(&a.frontmatter.published_at));
 
-    // Keep only non-draft items
-    sorted_articles.retain(|article| !article.frontmatter.is_draft.unwrap_or(false));
+    // Keep only non-draft items that have been published
+    let now = Utc::now();
+    sorted_articles.retain(|article| {
+        !article.frontmatter.is_draft.unwrap_or(false) && !article.frontmatter.is_scheduled_after(now)
+    });
 
     // Limit to 20 most recent items
     let items_to_include = sorted_articles.iter().take(20);
@@ -483,16 +1196,31 @@ pub fn generate_rss_feed(
     // Create RSS items
     let mut rss_items = Vec::new();
     for article in items_to_include {
-        // Clean HTML content for RSS (remove code blocks that might not render well in feeds)
-        let re = Regex::new(r"<pre><code>(.*?)</code></pre>").unwrap();
-        let clean_html = re.replace_all(&article.content, "[Code snippet]");
+        // Full content is carried as HTML in content:encoded
+        let content = options.full_content.then(|| match &article.html {
+            Some(html) => html.clone(),
+            None => common_markdown::markdown_to_html(&article.content),
+        });
+
+        let enclosure = match &options.images_dir {
+            Some(images_dir) => hero_image_enclosure(images_dir, article, &site_url, options)?,
+            None => None,
+        };
 
         // Create RSS item
         let rss_item = ItemBuilder::default()
             .title(article.frontmatter.title.clone())
-            .link(format!("{}/{}/{}", site_url, article.topic, article.slug))
-            .description(clean_html.to_string())
-            .pub_date(article.frontmatter.published_at.clone().unwrap_or_default())
+            .link(canonical_url(article, &site_url))
+            .description(article.summary.clone())
+            .content(content)
+            .enclosure(enclosure)
+            .pub_date(
+                article.frontmatter.published_at
+                    .as_ref()
+                    .and_then(PublishDate::to_datetime)
+                    .or_else(|| article.git.as_ref().map(|git| git.created))
+                    .map(|date| date.to_rfc2822()),
+            )
             .build();
 
         rss_items.push(rss_item);
@@ -509,8 +1237,106 @@ pub fn generate_rss_feed(
     // Write to file
     let rss_path = output_dir.join("rss.xml");
     let rss_string = channel.to_string();
-    write_file(&rss_path, &rss_string)
+    write_file_atomic(&rss_path, &rss_string)
         .with_context(|| format!("Failed to write RSS file: {:?}", rss_path))?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Image formats that can be used as enclosures, in order of preference
+const ENCLOSURE_FORMATS: &[(&str, &str)] = &[
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("webp", "image/webp"),
+    ("avif", "image/avif"),
+    ("gif", "image/gif"),
+];
+
+/// Find an article's hero image in the image-build output and describe it as an enclosure
+///
+/// image-build writes each article's images to `{images_dir}/{topic}/{slug}/`,
+/// with the size key (`featured_2x` becomes `featured-2x`) in the file name.
+fn hero_image_enclosure(
+    images_dir: &Path,
+    article: &Article,
+    site_url: &str,
+    options: &RssOptions,
+) -> Result<Option<Enclosure>> {
+    let article_dir = images_dir.join(&article.topic).join(&article.slug);
+    if !article_dir.is_dir() {
+        return Ok(None);
+    }
+
+    let size = format!("-{}", options.hero_size.replace('_', "-"));
+    let mut candidates = Vec::new();
+    for entry in fs::read_dir(&article_dir)
+        .with_context(|| format!("Failed to read image directory: {:?}", article_dir))?
+    {
+        let path = entry?.path();
+        let (stem, extension) = match (path.file_stem(), path.extension()) {
+            (Some(stem), Some(extension)) => (stem.to_string_lossy(), extension.to_string_lossy().to_lowercase()),
+            _ => continue,
+        };
+        if !stem.contains(&size) {
+            continue;
+        }
+        if let Some(rank) = ENCLOSURE_FORMATS.iter().position(|(format, _)| *format == extension) {
+            candidates.push((rank, path));
+        }
+    }
+
+    // Prefer the most widely supported format, then the shortest name (e.g. not `-2x`)
+    candidates.sort_by_key(|(rank, path)| (*rank, path.as_os_str().len(), path.clone()));
+    let (rank, path) = match candidates.into_iter().next() {
+        Some(candidate) => candidate,
+        None => return Ok(None),
+    };
+
+    let length = fs::metadata(&path)
+        .with_context(|| format!("Failed to read image metadata: {:?}", path))?
+        .len();
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    Ok(Some(
+        EnclosureBuilder::default()
+            .url(format!(
+                "{}{}/{}/{}/{}",
+                site_url.trim_end_matches('/'),
+                options.images_url.trim_end_matches('/'),
+                article.topic,
+                article.slug,
+                file_name
+            ))
+            .length(length.to_string())
+            .mime_type(ENCLOSURE_FORMATS[rank].1)
+            .build(),
+    ))
+}
+
+/// Generate redirect files for moved or renamed content
+///
+/// Writes a Netlify/Cloudflare Pages style `_redirects` file and a
+/// `redirects.json` file for other hosts. Nothing is written when there
+/// are no redirects.
+pub fn generate_redirects(output_dir: &Path, redirects: &[common_config::Redirect]) -> Result<()> {
+    if redirects.is_empty() {
+        return Ok(());
+    }
+
+    let rules: String = redirects
+        .iter()
+        .map(|redirect| format!("{} {} 301\n", redirect.from, redirect.to))
+        .collect();
+    let redirects_path = output_dir.join("_redirects");
+    write_file_atomic(&redirects_path, &rules)
+        .with_context(|| format!("Failed to write _redirects file: {:?}", redirects_path))?;
+
+    let json = serde_json::to_string_pretty(redirects)
+        .with_context(|| "Failed to serialize redirects to JSON")?;
+    let json_path = output_dir.join("redirects.json");
+    write_file_atomic(&json_path, &json)
+        .with_context(|| format!("Failed to write redirects.json file: {:?}", json_path))?;
+
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
-use content_build::{BuildOptions, build_content};
+use common_cli::OutputFormatArgs;
+use common_logging::LogArgs;
+use common_traits::CancellationToken;
+use content_build::watch::{watch, DEFAULT_POLL_INTERVAL};
+use content_build::{BuildOptions, Compression, HeroImageOptions, ImageCdnOptions, RssOptions, build_content_cancellable};
+use std::path::PathBuf;
 
 /// Tool for building content into static files (JSON, HTML, RSS, sitemap)
 #[derive(Parser, Debug)]
@@ -39,13 +44,87 @@ struct Args {
     #[clap(long)]
     skip_sitemap: bool,
 
-    /// Show verbose output
-    #[clap(long, short)]
-    verbose: bool,
+    /// Number of sentences in summaries generated for content without a description
+    #[clap(long, default_value_t = 2)]
+    summary_sentences: usize,
+
+    /// Include the full HTML of each article in the RSS feed, not just its summary
+    #[clap(long)]
+    rss_full_content: bool,
+
+    /// Directory of image-build output, used for hero image sizes, GIF and video markup, RSS enclosures and --image-cdn
+    #[clap(long)]
+    images_dir: Option<String>,
+
+    /// Point image URLs at this CDN base URL, for images listed in the image-build manifest
+    #[clap(long, value_name = "URL", requires = "images_dir")]
+    image_cdn: Option<String>,
+
+    /// Query string for CDN image URLs, with {width}, {height}, {format} and {size} placeholders
+    #[clap(long, value_name = "PARAMS", requires = "image_cdn")]
+    image_cdn_params: Option<String>,
+
+    /// Build even when articles in different topics share a slug or canonical URL
+    #[clap(long)]
+    allow_duplicate_slugs: bool,
+
+    /// Add creation and modification dates and contributors from git history
+    #[clap(long)]
+    git_metadata: bool,
+
+    /// Only build articles changed since this git commit, branch or tag
+    #[clap(long, value_name = "REF", conflicts_with = "slug")]
+    changed_since: Option<String>,
+
+    /// Minify rendered HTML and write compact JSON
+    #[clap(long)]
+    minify: bool,
+
+    /// Write precompressed copies of the text output (gzip, brotli)
+    #[clap(long, value_delimiter = ',')]
+    precompress: Vec<Compression>,
+
+    /// Time each phase of building every article, writing the timings to this file as JSON
+    #[clap(long, value_name = "FILE")]
+    profile: Option<PathBuf>,
+
+    /// Rebuild whenever content, templates or configuration change
+    #[clap(long)]
+    watch: bool,
+
+    /// Serve a live-reloading preview, with drafts under /drafts/ (builds to .preview by default)
+    #[cfg(feature = "serve")]
+    #[clap(long)]
+    serve: bool,
+
+    /// Port for the preview server
+    #[cfg(feature = "serve")]
+    #[clap(long, default_value_t = 3000)]
+    port: u16,
+
+    #[clap(flatten)]
+    log: LogArgs,
+
+    #[clap(flatten)]
+    output: OutputFormatArgs,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    args.log.init()?;
+
+    // Warn about configuration problems before building
+    common_config::report_config_issues();
+
+    let image_cdn = args.image_cdn.map(|base_url| ImageCdnOptions {
+        base_url,
+        params: args.image_cdn_params,
+        images_dir: args.images_dir.as_deref().map(PathBuf::from).unwrap_or_default(),
+    });
+    let hero_images = HeroImageOptions {
+        images_dir: args.images_dir.as_deref().map(PathBuf::from),
+        ..Default::default()
+    };
 
     // Convert args to BuildOptions
     let options = BuildOptions {
@@ -57,18 +136,62 @@ fn main() -> Result<()> {
         skip_json: args.skip_json,
         skip_rss: args.skip_rss,
         skip_sitemap: args.skip_sitemap,
-        verbose: args.verbose,
+        verbose: args.log.verbosity().is_verbose(),
+        summary_sentences: args.summary_sentences,
+        rss: RssOptions {
+            full_content: args.rss_full_content,
+            images_dir: args.images_dir.map(PathBuf::from),
+            ..Default::default()
+        },
+        allow_duplicate_slugs: args.allow_duplicate_slugs,
+        git_metadata: args.git_metadata,
+        minify: args.minify,
+        precompress: args.precompress,
+        profile: args.profile,
+        image_cdn,
+        hero_images,
+        changed_since: args.changed_since,
     };
 
-    // Build the content
-    build_content(&options)?;
+    // Stop cleanly between articles when the user presses Ctrl-C
+    let token = CancellationToken::new();
+    token.cancel_on_ctrlc()?;
 
-    // Determine output directory for success message
-    let output_dir = match &options.output_dir {
-        Some(dir) => dir.clone(),
-        None => "public".to_string(),
-    };
+    #[cfg(feature = "serve")]
+    if args.serve {
+        // Previews read the JSON output, and keep drafts out of the real output
+        let options = BuildOptions {
+            output_dir: Some(options.output_dir.clone().unwrap_or_else(|| ".preview".to_string())),
+            include_drafts: true,
+            skip_json: false,
+            ..options
+        };
 
-    println!("{} Content built successfully to {}", "✓".green(), output_dir);
-    Ok(())
+        let serve_options = content_build::ServeOptions {
+            addr: std::net::SocketAddr::from(([127, 0, 0, 1], args.port)),
+            ..Default::default()
+        };
+        return content_build::serve(&options, &serve_options, &token);
+    }
+
+    let formatter = args.output.formatter();
+
+    if args.watch {
+        if formatter.is_plain() {
+            println!("Watching for changes. Press Ctrl-C to stop.");
+        }
+        // Each build's report is printed as it finishes, one document per build
+        return watch(&options, DEFAULT_POLL_INTERVAL, &token, |result| match result {
+            Ok(report) => {
+                if let Err(err) = formatter.print(&report) {
+                    eprintln!("{} {:#}", "✗".red(), err);
+                }
+            }
+            Err(err) => eprintln!("{} {:#}", "✗".red(), err),
+        });
+    }
+
+    // Build the content
+    let report = build_content_cancellable(&options, &token)?;
+    formatter.print(&report)
 } 
\ No newline at end of file
@@ -0,0 +1,159 @@
+//! # Animated GIFs and Video
+//!
+//! image-build copies animated GIFs and videos beside an article's other
+//! images with a poster of their first frame, and can convert GIFs to MP4
+//! and WebP. When the manifest lists them, an image in an article that
+//! points at one is replaced with markup that doesn't load a heavy GIF up
+//! front:
+//!
+//! - a GIF converted to MP4 becomes a muted, looping `<video>` that plays
+//!   like the GIF, showing the poster until it loads
+//! - a GIF converted only to WebP becomes a `<picture>` offering the WebP
+//! - any other GIF becomes its poster, linking to the animation
+//! - a video becomes a `<video>` with controls, and its poster if it has one
+//!
+//! ```markdown
+//! ![Deploying from the dashboard](deploy.gif)
+//! ![Walkthrough](walkthrough.mp4)
+//! ```
+
+use anyhow::Result;
+use common_models::{Article, ImageManifest, ImageVariant, ANIMATED_SIZE, IMAGE_MANIFEST_FILE, ORIGINAL_SIZE, POSTER_SIZE};
+use regex::{Captures, Regex};
+use std::path::Path;
+
+use crate::cdn::IMAGES_URL;
+
+/// Replaces images of animated GIFs and videos in the manifest with lighter markup
+#[derive(Debug, Clone)]
+pub struct AnimatedMedia {
+    manifest: ImageManifest,
+    image: Regex,
+}
+
+impl AnimatedMedia {
+    /// Create a rewriter for the media in a manifest
+    pub fn new(manifest: ImageManifest) -> Self {
+        // pulldown-cmark renders an image as `<img src="..." alt="..." />`, with an optional title
+        let image = Regex::new(&format!(
+            r#"<img src="{}/([^"]+)" alt="([^"]*)"(?: title="[^"]*")? />"#,
+            regex::escape(IMAGES_URL)
+        ))
+        .expect("valid regex");
+
+        Self { manifest, image }
+    }
+
+    /// Create a rewriter from the manifest in an image-build output directory
+    ///
+    /// Returns `None` if image-build hasn't written a manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest can't be read
+    pub fn load(images_dir: &Path) -> Result<Option<Self>> {
+        Ok(ImageManifest::load(&images_dir.join(IMAGE_MANIFEST_FILE))?.map(Self::new))
+    }
+
+    /// Markup for the GIF or video at a path in the manifest, if it has any
+    ///
+    /// `alt` must already be escaped for HTML.
+    pub fn markup(&self, path: &str, alt: &str) -> Option<String> {
+        let original = self.manifest.get(path).filter(|variant| variant.size == ORIGINAL_SIZE)?;
+        let stem = path.rsplit_once('.').map_or(path, |(stem, _)| stem);
+        let poster = self
+            .manifest
+            .get(&format!("{}-{}.jpg", stem, POSTER_SIZE))
+            .filter(|variant| variant.size == POSTER_SIZE);
+        let poster_attr = poster.map(|poster| format!(" poster=\"{}\"", url(poster))).unwrap_or_default();
+
+        if original.format != "gif" {
+            let media_type = video_type(&original.format)?;
+            // Without a poster, the browser shows the first frame once it has the metadata
+            let preload = if poster.is_some() { "none" } else { "metadata" };
+            return Some(format!(
+                r#"<video controls preload="{}"{}{} aria-label="{}"><source src="{}" type="{}"></video>"#,
+                preload,
+                dimensions(original),
+                poster_attr,
+                alt,
+                url(original),
+                media_type
+            ));
+        }
+
+        let animated = |format: &str| {
+            self.manifest
+                .get(&format!("{}.{}", stem, format))
+                .filter(|variant| variant.size == ANIMATED_SIZE)
+        };
+        if let Some(mp4) = animated("mp4") {
+            return Some(format!(
+                r#"<video autoplay loop muted playsinline{}{} aria-label="{}"><source src="{}" type="video/mp4"><img src="{}" alt="{}" /></video>"#,
+                dimensions(original),
+                poster_attr,
+                alt,
+                url(mp4),
+                url(original),
+                alt
+            ));
+        }
+        if let Some(webp) = animated("webp") {
+            return Some(format!(
+                r#"<picture><source srcset="{}" type="image/webp"><img src="{}" alt="{}"{} /></picture>"#,
+                url(webp),
+                url(original),
+                alt,
+                dimensions(original)
+            ));
+        }
+
+        let poster = poster?;
+        Some(format!(
+            r#"<a href="{}" class="animated-gif"><img src="{}" alt="{}"{} loading="lazy" /></a>"#,
+            url(original),
+            url(poster),
+            alt,
+            dimensions(poster)
+        ))
+    }
+
+    /// Replace the images of GIFs and videos in HTML
+    pub fn rewrite_html(&self, html: &str) -> String {
+        self.image
+            .replace_all(html, |captures: &Captures<'_>| {
+                self.markup(&captures[1], &captures[2]).unwrap_or_else(|| captures[0].to_string())
+            })
+            .into_owned()
+    }
+
+    /// Replace the images of GIFs and videos in an article's HTML
+    pub fn rewrite_article(&self, article: &mut Article) {
+        if let Some(html) = article.html.as_mut() {
+            *html = self.rewrite_html(html);
+        }
+    }
+}
+
+/// Site URL of an image-build output file
+fn url(variant: &ImageVariant) -> String {
+    format!("{}/{}", IMAGES_URL, variant.path)
+}
+
+/// `width` and `height` attributes, so the page doesn't shift as media loads
+fn dimensions(variant: &ImageVariant) -> String {
+    if variant.width == 0 || variant.height == 0 {
+        return String::new();
+    }
+    format!(" width=\"{}\" height=\"{}\"", variant.width, variant.height)
+}
+
+/// Media type of a video format image-build copies
+fn video_type(format: &str) -> Option<&'static str> {
+    match format {
+        "mp4" => Some("video/mp4"),
+        "webm" => Some("video/webm"),
+        "mov" => Some("video/quicktime"),
+        _ => None,
+    }
+}
@@ -0,0 +1,187 @@
+//! Smaller build output
+//!
+//! Minification removes what browsers don't need from rendered HTML:
+//! comments and runs of whitespace. It is deliberately conservative: the
+//! contents of `pre`, `textarea`, `script` and `style` elements are left
+//! exactly as they are, and whitespace is collapsed to a single space rather
+//! than removed, so inline elements keep the spaces between them.
+//!
+//! Precompression writes `.gz` and `.br` copies beside each text file in the
+//! output, for hosts that serve precompressed files.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use walkdir::WalkDir;
+
+/// Elements whose contents are kept as written
+const RAW_ELEMENTS: [&str; 4] = ["pre", "textarea", "script", "style"];
+
+/// Extensions of the output files worth precompressing
+const COMPRESSIBLE_EXTENSIONS: [&str; 6] = ["html", "json", "xml", "txt", "css", "js"];
+
+/// Files smaller than this gain too little from compression to be worth a copy
+pub const MIN_PRECOMPRESS_SIZE: u64 = 1024;
+
+/// Minify rendered HTML
+///
+/// Strips comments, except conditional comments (`<!--[if ...]>`), and
+/// collapses each run of ASCII whitespace to a single space outside raw
+/// elements.
+pub fn minify_html(html: &str) -> String {
+    let mut minified = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(rest.len(), |end| "<!--".len() + end + "-->".len());
+            if comment.starts_with("[if") || comment.starts_with("<![endif]") {
+                minified.push_str(&rest[..end]);
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        if let Some(element) = raw_element_at(rest) {
+            let close = format!("</{}", element);
+            let end = find_ignore_case(rest, &close).map_or(rest.len(), |start| start + close.len());
+            minified.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let mut chars = rest.chars();
+        let Some(c) = chars.next() else { break };
+        // Non-breaking spaces are content, so only ASCII whitespace collapses
+        if c.is_ascii_whitespace() {
+            if !minified.ends_with(' ') {
+                minified.push(' ');
+            }
+            rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        } else {
+            minified.push(c);
+            rest = chars.as_str();
+        }
+    }
+
+    minified.trim_matches(' ').to_string()
+}
+
+/// The raw element opened at the start of `html`, if any
+fn raw_element_at(html: &str) -> Option<&'static str> {
+    let tag = html.strip_prefix('<')?;
+    RAW_ELEMENTS.into_iter().find(|element| {
+        tag.get(..element.len()).is_some_and(|name| name.eq_ignore_ascii_case(element))
+            && tag[element.len()..].starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace())
+    })
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.to_ascii_lowercase().find(needle)
+}
+
+/// Serialize a value as JSON, compact when minifying and pretty otherwise
+pub fn to_json<T: serde::Serialize + ?Sized>(value: &T, minify: bool) -> serde_json::Result<String> {
+    if minify {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
+
+/// A format for precompressed copies of the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// gzip, written as `{file}.gz`
+    Gzip,
+    /// Brotli, written as `{file}.br`
+    Brotli,
+}
+
+impl Compression {
+    /// Extension added to the name of a compressed copy
+    pub fn extension(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Brotli => "br",
+        }
+    }
+
+    /// Compress `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if compression fails
+    pub fn compress(self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Compression::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(data)?;
+                Ok(encoder.finish()?)
+            }
+            Compression::Brotli => {
+                let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+                encoder.write_all(data)?;
+                Ok(encoder.into_inner())
+            }
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gz" | "gzip" => Ok(Compression::Gzip),
+            "br" | "brotli" => Ok(Compression::Brotli),
+            other => Err(anyhow::anyhow!("Unknown compression '{}': expected gzip or brotli", other)),
+        }
+    }
+}
+
+/// Write compressed copies of the text files in `output_dir`
+///
+/// Each HTML, JSON, XML, text, CSS or JavaScript file of at least
+/// [`MIN_PRECOMPRESS_SIZE`] bytes gets a copy beside it for each format,
+/// replacing any copy from an earlier build. Returns the copies written.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read, compressed or written
+pub fn precompress_dir(output_dir: &Path, formats: &[Compression]) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    if formats.is_empty() {
+        return Ok(written);
+    }
+
+    for entry in WalkDir::new(output_dir).into_iter().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let compressible = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| COMPRESSIBLE_EXTENSIONS.contains(&extension));
+        if !entry.file_type().is_file() || !compressible {
+            continue;
+        }
+        if entry.metadata().map_or(0, |metadata| metadata.len()) < MIN_PRECOMPRESS_SIZE {
+            continue;
+        }
+
+        let data = std::fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+        for format in formats {
+            let mut compressed_path = path.as_os_str().to_owned();
+            compressed_path.push(".");
+            compressed_path.push(format.extension());
+            let compressed_path = PathBuf::from(compressed_path);
+
+            let compressed = format.compress(&data)?;
+            std::fs::write(&compressed_path, compressed)
+                .with_context(|| format!("Failed to write {:?}", compressed_path))?;
+            written.push(compressed_path);
+        }
+    }
+
+    Ok(written)
+}
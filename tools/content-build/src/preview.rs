@@ -0,0 +1,333 @@
+//! Routing for the local preview server
+//!
+//! The build writes article HTML as `html/{slug}.html`, but the site serves
+//! articles at `/{topic}/{slug}`. The preview maps the public URLs onto the
+//! build output, lists the articles in each topic at `/{topic}/`, and serves
+//! drafts and scheduled articles under `/drafts/{topic}/{slug}` so they are
+//! never mistaken for published pages. Other paths are served as files from
+//! the output directory.
+//!
+//! Articles in other languages are served under `/{locale}/`, from the
+//! output tree the build writes for each locale.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use common_models::Article;
+use handlebars::html_escape;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Path the live-reload script polls for the current build generation
+pub const LIVE_RELOAD_PATH: &str = "/__livereload";
+
+/// First path segment of draft previews
+pub const DRAFTS_SEGMENT: &str = "drafts";
+
+/// Polls the build generation and reloads the page when it changes
+const LIVE_RELOAD_SCRIPT: &str = "<script>(function(){var seen=null;setInterval(function(){\
+fetch('/__livereload').then(function(r){return r.text()}).then(function(g){\
+if(seen!==null&&g!==seen){location.reload()}seen=g}).catch(function(){})},1000)})();</script>";
+
+/// An article in the preview
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewEntry {
+    pub topic: String,
+    pub slug: String,
+    pub title: String,
+    /// Whether the article is a draft or scheduled, and only served under `/drafts/`
+    pub draft: bool,
+    /// Rendered content, for articles built without a template
+    pub html: Option<String>,
+}
+
+impl PreviewEntry {
+    fn from_article(article: &Article) -> Self {
+        let draft = article.frontmatter.is_draft.unwrap_or(false)
+            || article.frontmatter.is_scheduled_after(Utc::now());
+        Self {
+            topic: article.topic.clone(),
+            slug: article.slug.clone(),
+            title: article.frontmatter.title.clone(),
+            draft,
+            html: article.html.clone(),
+        }
+    }
+
+    /// URL of the article in the preview, under `prefix`
+    pub fn url(&self, prefix: &str) -> String {
+        let drafts = if self.draft { format!("/{}", DRAFTS_SEGMENT) } else { String::new() };
+        format!("{}{}/{}/{}", prefix, drafts, self.topic, self.slug)
+    }
+}
+
+/// The articles in one output tree
+#[derive(Debug, Clone)]
+struct PreviewTree {
+    /// URL prefix, empty for the root tree and `/{locale}` otherwise
+    prefix: String,
+    dir: PathBuf,
+    articles: Vec<PreviewEntry>,
+}
+
+/// What a request path refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// A file from the output directory
+    File(PathBuf),
+    /// An article page
+    Article { prefix: String, topic: String, slug: String },
+    /// The list of topics and their articles
+    SiteIndex { prefix: String },
+    /// The list of articles in a topic
+    TopicIndex { prefix: String, topic: String },
+    NotFound,
+}
+
+/// The build output, as the preview server sees it
+#[derive(Debug, Clone, Default)]
+pub struct PreviewSite {
+    output_dir: PathBuf,
+    trees: Vec<PreviewTree>,
+}
+
+impl PreviewSite {
+    /// Read the articles in the build output from each tree's `data/all.json`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an `all.json` file cannot be read or parsed
+    pub fn load(output_dir: &Path) -> Result<Self> {
+        let mut trees = vec![load_tree(String::new(), output_dir)?];
+
+        if let Ok(entries) = std::fs::read_dir(output_dir) {
+            let mut locales: Vec<_> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|dir| dir.join("data").join("all.json").is_file())
+                .collect();
+            locales.sort();
+            for dir in locales {
+                let locale = dir.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                trees.push(load_tree(format!("/{}", locale), &dir)?);
+            }
+        }
+
+        Ok(Self { output_dir: output_dir.to_path_buf(), trees })
+    }
+
+    /// Work out what a request path refers to
+    pub fn resolve(&self, path: &str) -> Route {
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        if segments.iter().any(|segment| *segment == ".." || segment.contains('\\')) {
+            return Route::NotFound;
+        }
+
+        // Files in the output win, so feeds, data and images are served as built,
+        // except the pages rendered for drafts
+        let file = segments.iter().fold(self.output_dir.clone(), |file, segment| file.join(segment));
+        if file.is_file() {
+            if self.is_draft_page(&file) {
+                return Route::NotFound;
+            }
+            return Route::File(file);
+        }
+        if file.join("index.html").is_file() {
+            return Route::File(file.join("index.html"));
+        }
+
+        let locale_tree = segments
+            .first()
+            .and_then(|first| self.trees.iter().skip(1).find(|tree| tree.prefix[1..] == **first));
+        let (tree, segments) = match (locale_tree, self.trees.first()) {
+            (Some(tree), _) => (tree, &segments[1..]),
+            (None, Some(root)) => (root, &segments[..]),
+            (None, None) => return Route::NotFound,
+        };
+        let prefix = tree.prefix.clone();
+
+        let (draft, segments) = match segments.split_first() {
+            Some((&DRAFTS_SEGMENT, rest)) if !rest.is_empty() => (true, rest),
+            _ => (false, segments),
+        };
+
+        match segments {
+            [] => Route::SiteIndex { prefix },
+            [topic] if !draft && tree.articles.iter().any(|article| article.topic == *topic) => {
+                Route::TopicIndex { prefix, topic: topic.to_string() }
+            }
+            [topic, slug] => {
+                let slug = slug.trim_end_matches(".html");
+                match tree.find(topic, slug) {
+                    Some(article) if article.draft == draft => Route::Article {
+                        prefix,
+                        topic: topic.to_string(),
+                        slug: slug.to_string(),
+                    },
+                    _ => Route::NotFound,
+                }
+            }
+            _ => Route::NotFound,
+        }
+    }
+
+    /// HTML of an article: the page the build rendered from the article
+    /// template, or a plain page around the article content
+    pub fn article_html(&self, prefix: &str, topic: &str, slug: &str) -> Option<String> {
+        let tree = self.tree(prefix)?;
+        let article = tree.find(topic, slug)?;
+
+        let rendered = tree.dir.join("html").join(format!("{}.html", slug));
+        if let Ok(html) = std::fs::read_to_string(rendered) {
+            return Some(html);
+        }
+
+        let body = format!(
+            "<p><a href=\"{}/{}/\">{}</a></p>\n<h1>{}</h1>\n{}",
+            prefix,
+            html_escape(topic),
+            html_escape(topic),
+            html_escape(&article.title),
+            article.html.as_deref().unwrap_or_default()
+        );
+        Some(page(&article.title, &body))
+    }
+
+    /// HTML listing every topic and its articles, drafts included
+    pub fn site_index_html(&self, prefix: &str) -> Option<String> {
+        let tree = self.tree(prefix)?;
+
+        let mut by_topic: BTreeMap<&str, Vec<&PreviewEntry>> = BTreeMap::new();
+        for article in &tree.articles {
+            by_topic.entry(&article.topic).or_default().push(article);
+        }
+
+        let mut body = String::from("<h1>Preview</h1>\n");
+        for (topic, articles) in by_topic {
+            body.push_str(&format!(
+                "<h2><a href=\"{}/{}/\">{}</a></h2>\n{}",
+                prefix,
+                html_escape(topic),
+                html_escape(topic),
+                article_list(&articles, prefix)
+            ));
+        }
+        if tree.articles.is_empty() {
+            body.push_str("<p>No articles were built.</p>\n");
+        }
+
+        Some(page("Preview", &body))
+    }
+
+    /// HTML listing the articles in a topic, drafts included
+    pub fn topic_index_html(&self, prefix: &str, topic: &str) -> Option<String> {
+        let tree = self.tree(prefix)?;
+        let articles: Vec<&PreviewEntry> = tree.articles.iter().filter(|article| article.topic == topic).collect();
+        if articles.is_empty() {
+            return None;
+        }
+
+        let body = format!(
+            "<p><a href=\"{}/\">All topics</a></p>\n<h1>{}</h1>\n{}",
+            prefix,
+            html_escape(topic),
+            article_list(&articles, prefix)
+        );
+        Some(page(topic, &body))
+    }
+
+    /// Whether a file is the `html/{slug}.html` page the build rendered for a draft
+    fn is_draft_page(&self, file: &Path) -> bool {
+        self.trees.iter().any(|tree| {
+            file.parent() == Some(tree.dir.join("html").as_path())
+                && file.file_stem().is_some_and(|stem| {
+                    tree.articles.iter().any(|article| article.draft && stem == article.slug.as_str())
+                })
+        })
+    }
+
+    fn tree(&self, prefix: &str) -> Option<&PreviewTree> {
+        self.trees.iter().find(|tree| tree.prefix == prefix)
+    }
+}
+
+impl PreviewTree {
+    fn find(&self, topic: &str, slug: &str) -> Option<&PreviewEntry> {
+        self.articles.iter().find(|article| article.topic == topic && article.slug == slug)
+    }
+}
+
+fn load_tree(prefix: String, dir: &Path) -> Result<PreviewTree> {
+    let all_json = dir.join("data").join("all.json");
+    let articles = if all_json.is_file() {
+        let json = std::fs::read_to_string(&all_json).with_context(|| format!("Failed to read {:?}", all_json))?;
+        let articles: Vec<Article> =
+            serde_json::from_str(&json).with_context(|| format!("Failed to parse {:?}", all_json))?;
+        articles.iter().map(PreviewEntry::from_article).collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(PreviewTree { prefix, dir: dir.to_path_buf(), articles })
+}
+
+fn article_list(articles: &[&PreviewEntry], prefix: &str) -> String {
+    let mut list = String::from("<ul>\n");
+    for article in articles {
+        list.push_str(&format!(
+            "<li><a href=\"{}\">{}</a>{}</li>\n",
+            article.url(prefix),
+            html_escape(&article.title),
+            if article.draft { " <small>(draft)</small>" } else { "" }
+        ));
+    }
+    list.push_str("</ul>\n");
+    list
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        html_escape(title),
+        body
+    )
+}
+
+/// HTML for a path that doesn't exist in the preview
+pub fn not_found_html(path: &str) -> String {
+    let body = format!(
+        "<h1>Not found</h1>\n<p>Nothing is built at <code>{}</code>. Drafts are served under <code>/{}/</code>.</p>\n\
+         <p><a href=\"/\">All topics</a></p>\n",
+        html_escape(path),
+        DRAFTS_SEGMENT
+    );
+    page("Not found", &body)
+}
+
+/// Add the live-reload script to an HTML page, before `</body>` when it has one
+pub fn inject_live_reload(html: &str) -> String {
+    match html.to_ascii_lowercase().rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], LIVE_RELOAD_SCRIPT, &html[end..]),
+        None => format!("{}{}", html, LIVE_RELOAD_SCRIPT),
+    }
+}
+
+/// The `Content-Type` to serve a file from the output with, by extension
+pub fn content_type(path: &Path) -> &'static str {
+    let extension = path.extension().map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("html") | Some("htm") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("txt") => "text/plain; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("ico") => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
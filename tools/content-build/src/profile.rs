@@ -0,0 +1,192 @@
+//! # Build Profile
+//!
+//! With `--profile <FILE>`, the build times each phase of every article and
+//! writes the timings to `FILE` as JSON. The build report then lists the
+//! slowest articles, so a slow build can be traced to the content behind it.
+//!
+//! The phases are reading the content file, parsing its frontmatter,
+//! rendering the markdown to HTML, rendering the article template and
+//! writing the output files.
+
+use anyhow::{Context, Result};
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::write_file_atomic;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of articles listed in the build report
+pub const SLOWEST_SHOWN: usize = 10;
+
+/// A timed phase of building an article
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Reading the content file
+    Read,
+    /// Splitting off and parsing the frontmatter
+    Parse,
+    /// Expanding shortcodes and rendering markdown to HTML
+    Markdown,
+    /// Rendering the article template
+    Template,
+    /// Writing the article's JSON and HTML files
+    Write,
+}
+
+/// Time spent building one article, by phase
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ArticleProfile {
+    /// Content file the article was read from
+    pub path: String,
+    #[serde(rename = "read_ms", serialize_with = "as_millis")]
+    pub read: Duration,
+    #[serde(rename = "parse_ms", serialize_with = "as_millis")]
+    pub parse: Duration,
+    #[serde(rename = "markdown_ms", serialize_with = "as_millis")]
+    pub markdown: Duration,
+    #[serde(rename = "template_ms", serialize_with = "as_millis")]
+    pub template: Duration,
+    #[serde(rename = "write_ms", serialize_with = "as_millis")]
+    pub write: Duration,
+    #[serde(rename = "total_ms", serialize_with = "as_millis")]
+    pub total: Duration,
+}
+
+impl ArticleProfile {
+    fn add(&mut self, phase: Phase, elapsed: Duration) {
+        let time = match phase {
+            Phase::Read => &mut self.read,
+            Phase::Parse => &mut self.parse,
+            Phase::Markdown => &mut self.markdown,
+            Phase::Template => &mut self.template,
+            Phase::Write => &mut self.write,
+        };
+        *time += elapsed;
+        self.total += elapsed;
+    }
+}
+
+/// Collects phase timings while a build runs
+///
+/// The default profiler is off, and runs each phase without timing it.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    enabled: bool,
+    articles: Mutex<BTreeMap<String, ArticleProfile>>,
+}
+
+impl Profiler {
+    /// A profiler that records timings
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Whether timings are being recorded
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Run one phase of building the article at `path`, adding its duration to the article's profile
+    pub fn time<T>(&self, path: &str, phase: Phase, run: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return run();
+        }
+
+        let start = Instant::now();
+        let result = run();
+        let elapsed = start.elapsed();
+
+        let mut articles = self.articles.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        articles
+            .entry(path.to_string())
+            .or_insert_with(|| ArticleProfile {
+                path: path.to_string(),
+                ..Default::default()
+            })
+            .add(phase, elapsed);
+        result
+    }
+
+    /// The timings recorded so far, slowest article first
+    pub fn profile(&self) -> BuildProfile {
+        let articles = self.articles.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut articles: Vec<ArticleProfile> = articles.values().cloned().collect();
+        articles.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.path.cmp(&b.path)));
+
+        BuildProfile {
+            total: articles.iter().map(|article| article.total).sum(),
+            articles,
+        }
+    }
+}
+
+/// Phase timings for every article in a build
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BuildProfile {
+    /// Time spent on all articles
+    #[serde(rename = "total_ms", serialize_with = "as_millis")]
+    pub total: Duration,
+    /// Articles, slowest first
+    pub articles: Vec<ArticleProfile>,
+}
+
+impl BuildProfile {
+    /// The slowest `count` articles
+    pub fn slowest(&self, count: usize) -> &[ArticleProfile] {
+        &self.articles[..count.min(self.articles.len())]
+    }
+
+    /// Write the profile as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize build profile")?;
+        write_file_atomic(path, &json).with_context(|| format!("Failed to write build profile: {:?}", path))?;
+        Ok(())
+    }
+}
+
+impl DisplayResult for BuildProfile {
+    fn to_display(&self) -> String {
+        let mut lines = vec![format!(
+            "{} ({} articles, {})",
+            "Slowest articles:".cyan().bold(),
+            self.articles.len(),
+            millis(self.total)
+        )];
+        lines.push(format!(
+            "  {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}  {}",
+            "total", "read", "parse", "markdown", "template", "write", "path"
+        ));
+        for article in self.slowest(SLOWEST_SHOWN) {
+            lines.push(format!(
+                "  {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}  {}",
+                millis(article.total),
+                millis(article.read),
+                millis(article.parse),
+                millis(article.markdown),
+                millis(article.template),
+                millis(article.write),
+                article.path
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// A duration in milliseconds, for the report
+fn millis(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn as_millis<S: Serializer>(duration: &Duration, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
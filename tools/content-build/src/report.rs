@@ -0,0 +1,57 @@
+//! # Build Report
+//!
+//! What a build wrote, printed as text for people or as JSON or YAML for
+//! scripts and CI with `--format`.
+
+use crate::profile::BuildProfile;
+use colored::*;
+use common_cli::DisplayResult;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A content file that wasn't built
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedContent {
+    pub path: PathBuf,
+    /// Why it was skipped: a draft, archived or scheduled article, or the error that stopped it
+    pub reason: String,
+}
+
+/// The outcome of a build
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BuildReport {
+    pub output_dir: PathBuf,
+    /// Articles built, counting each language of an article once
+    pub articles: usize,
+    /// Content files that weren't built
+    pub skipped: Vec<SkippedContent>,
+    /// Redirects written for moved content
+    pub redirects: usize,
+    /// Precompressed copies written
+    pub precompressed: usize,
+    /// Per-article phase timings, when the build was profiled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<BuildProfile>,
+}
+
+impl DisplayResult for BuildReport {
+    fn to_display(&self) -> String {
+        // Errors are logged as they happen, so only the count is repeated here
+        let skipped = if self.skipped.is_empty() {
+            String::new()
+        } else {
+            format!(", {} skipped", self.skipped.len())
+        };
+        let summary = format!(
+            "{} Content built successfully to {} ({} articles{})",
+            "✓".green(),
+            self.output_dir.display(),
+            self.articles,
+            skipped
+        );
+        match &self.profile {
+            Some(profile) => format!("{}\n\n{}", summary, profile.to_display()),
+            None => summary,
+        }
+    }
+}
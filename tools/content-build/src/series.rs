@@ -0,0 +1,72 @@
+//! Series support for content builds
+//!
+//! Articles sharing a `series` frontmatter field are ordered by `series_part`,
+//! linked to their neighbours, and summarised in a series index.
+
+use common_models::{Article, SeriesLink, SeriesNavigation};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// An ordered series of articles
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SeriesIndex {
+    /// Name of the series
+    pub name: String,
+    /// Parts of the series, ordered by part number
+    pub entries: Vec<SeriesLink>,
+}
+
+/// Link articles in the same series and build the series index
+///
+/// Each article that belongs to a series gets its `series_navigation` set,
+/// with `prev`/`next` pointing at the neighbouring parts. Articles in a series
+/// without a `series_part` are ordered after numbered parts, by slug.
+///
+/// # Parameters
+///
+/// * `articles` - The articles to link; updated in place
+///
+/// # Returns
+///
+/// Returns one index per series, sorted by series name
+pub fn link_series(articles: &mut [Article]) -> Vec<SeriesIndex> {
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, article) in articles.iter().enumerate() {
+        if let Some(series) = &article.frontmatter.series {
+            groups.entry(series.clone()).or_default().push(index);
+        }
+    }
+
+    let mut indexes = Vec::new();
+    for (name, mut members) in groups {
+        members.sort_by(|&a, &b| {
+            let key = |i: usize| (articles[i].frontmatter.series_part.unwrap_or(u32::MAX), articles[i].slug.clone());
+            key(a).cmp(&key(b))
+        });
+
+        let entries: Vec<SeriesLink> = members
+            .iter()
+            .enumerate()
+            .map(|(position, &i)| SeriesLink {
+                title: articles[i].frontmatter.title.clone(),
+                slug: articles[i].slug.clone(),
+                topic: articles[i].topic.clone(),
+                part: articles[i].frontmatter.series_part.unwrap_or(position as u32 + 1),
+            })
+            .collect();
+
+        for (position, &i) in members.iter().enumerate() {
+            articles[i].series_navigation = Some(SeriesNavigation {
+                series: name.clone(),
+                part: entries[position].part,
+                total: entries.len(),
+                prev: position.checked_sub(1).map(|p| entries[p].clone()),
+                next: entries.get(position + 1).cloned(),
+            });
+        }
+
+        indexes.push(SeriesIndex { name, entries });
+    }
+
+    indexes
+}
@@ -0,0 +1,142 @@
+//! Local preview server
+//!
+//! Serves the build output over HTTP while watch mode rebuilds it, so a
+//! writer can preview changes without setting up a web server. Each
+//! successful build bumps a generation counter that the live-reload script
+//! in every HTML page polls, reloading the page when the counter changes.
+//! Routing is in [`crate::preview`].
+
+use anyhow::{Context, Result};
+use axum::extract::State;
+use axum::http::{header, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use common_traits::CancellationToken;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use crate::preview::{content_type, inject_live_reload, not_found_html, PreviewSite, Route, LIVE_RELOAD_PATH};
+use crate::watch::{watch, DEFAULT_POLL_INTERVAL};
+use crate::BuildOptions;
+
+/// Options for the preview server
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    /// Address to listen on
+    pub addr: SocketAddr,
+    /// Reload open pages after each successful build
+    pub live_reload: bool,
+    /// How often to check for changed content
+    pub poll_interval: Duration,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            addr: SocketAddr::from(([127, 0, 0, 1], 3000)),
+            live_reload: true,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+struct ServerState {
+    site: RwLock<PreviewSite>,
+    /// Number of successful builds so far
+    generation: AtomicU64,
+    live_reload: bool,
+}
+
+/// Build the site, serve the output and rebuild on changes until `token` is cancelled
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound or the files to watch
+/// cannot be determined. Failed builds are logged and the previous output
+/// stays up.
+pub fn serve(options: &BuildOptions, serve: &ServeOptions, token: &CancellationToken) -> Result<()> {
+    let output_dir = PathBuf::from(options.output_dir.as_deref().unwrap_or("public"));
+
+    // Bind up front so a port in use is reported before the first build
+    let listener = std::net::TcpListener::bind(serve.addr)
+        .with_context(|| format!("Failed to listen on {}", serve.addr))?;
+    listener.set_nonblocking(true)?;
+
+    let state = Arc::new(ServerState {
+        site: RwLock::new(PreviewSite::default()),
+        generation: AtomicU64::new(0),
+        live_reload: serve.live_reload,
+    });
+
+    let app = Router::new()
+        .route(LIVE_RELOAD_PATH, get(generation))
+        .fallback(handle)
+        .with_state(Arc::clone(&state));
+    std::thread::spawn(move || {
+        if let Err(err) = run_server(listener, app) {
+            tracing::error!("Preview server stopped: {:#}", err);
+        }
+    });
+
+    println!("Previewing at http://{} (drafts under /drafts/)", serve.addr);
+
+    watch(options, serve.poll_interval, token, |result| {
+        match result.and_then(|_| PreviewSite::load(&output_dir)) {
+            Ok(site) => {
+                *state.site.write().unwrap_or_else(PoisonError::into_inner) = site;
+                state.generation.fetch_add(1, Ordering::SeqCst);
+            }
+            Err(err) => tracing::error!("Build failed: {:#}", err),
+        }
+    })
+}
+
+fn run_server(listener: std::net::TcpListener, app: Router) -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+        axum::serve(listener, app).await
+    })?;
+    Ok(())
+}
+
+async fn generation(State(state): State<Arc<ServerState>>) -> String {
+    state.generation.load(Ordering::SeqCst).to_string()
+}
+
+async fn handle(State(state): State<Arc<ServerState>>, uri: Uri) -> Response {
+    let site = state.site.read().unwrap_or_else(PoisonError::into_inner);
+
+    let html = match site.resolve(uri.path()) {
+        Route::File(path) => return file_response(&path, uri.path(), state.live_reload),
+        Route::Article { prefix, topic, slug } => site.article_html(&prefix, &topic, &slug),
+        Route::SiteIndex { prefix } => site.site_index_html(&prefix),
+        Route::TopicIndex { prefix, topic } => site.topic_index_html(&prefix, &topic),
+        Route::NotFound => None,
+    };
+
+    match html {
+        Some(html) => html_response(StatusCode::OK, &html, state.live_reload),
+        None => html_response(StatusCode::NOT_FOUND, &not_found_html(uri.path()), state.live_reload),
+    }
+}
+
+fn html_response(status: StatusCode, html: &str, live_reload: bool) -> Response {
+    let html = if live_reload { inject_live_reload(html) } else { html.to_string() };
+    (status, [(header::CONTENT_TYPE, "text/html; charset=utf-8")], html).into_response()
+}
+
+fn file_response(path: &Path, request_path: &str, live_reload: bool) -> Response {
+    let content_type = content_type(path);
+    match std::fs::read(path) {
+        Ok(bytes) if content_type.starts_with("text/html") => {
+            html_response(StatusCode::OK, &String::from_utf8_lossy(&bytes), live_reload)
+        }
+        Ok(bytes) => (StatusCode::OK, [(header::CONTENT_TYPE, content_type)], bytes).into_response(),
+        Err(_) => html_response(StatusCode::NOT_FOUND, &not_found_html(request_path), live_reload),
+    }
+}
@@ -0,0 +1,147 @@
+//! Shortcode rendering for MDX components
+//!
+//! Components listed in `shortcodes.yaml` are rendered with a Handlebars
+//! partial from `templates/shortcodes/{partial}.hbs`. The partial gets the
+//! component's attributes, plus `children` holding its children rendered to
+//! HTML. Components marked `strip`, and components that aren't registered,
+//! are replaced with their children so the build never breaks on them;
+//! `content-validate` reports the unknown ones.
+
+use anyhow::{Context, Result};
+use common_markdown::{markdown_to_html, replace_components, Component};
+use common_models::Config;
+use handlebars::Handlebars;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Directory of shortcode partials within the templates directory
+pub const SHORTCODE_TEMPLATES_DIR: &str = "shortcodes";
+
+/// How a registered component is rendered
+#[derive(Debug, Clone, PartialEq)]
+enum Rendering {
+    Partial(String),
+    Strip,
+}
+
+/// Components the build knows how to render
+pub struct ShortcodeRegistry {
+    handlebars: Handlebars<'static>,
+    components: HashMap<String, Rendering>,
+}
+
+impl Default for ShortcodeRegistry {
+    fn default() -> Self {
+        // Partials get the same helpers as article templates
+        let mut handlebars = Handlebars::new();
+        common_templates::helpers::register_helpers(&mut handlebars);
+
+        Self {
+            handlebars,
+            components: HashMap::new(),
+        }
+    }
+}
+
+impl ShortcodeRegistry {
+    /// Create an empty registry, which strips every component
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the shortcodes configured for a site
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shortcodes file can't be read, or a configured
+    /// partial is missing or invalid.
+    pub fn load(config: &Config, templates_dir: &Path) -> Result<Self> {
+        let shortcodes = common_config::load_shortcodes(&common_config::shortcodes_path(config))?;
+        let partials_dir = templates_dir.join(SHORTCODE_TEMPLATES_DIR);
+        let mut registry = Self::new();
+
+        for shortcode in shortcodes {
+            if shortcode.strip {
+                registry.register_strip(&shortcode.name);
+                continue;
+            }
+
+            let partial_path = partials_dir.join(format!("{}.hbs", shortcode.partial_name()));
+            let template = std::fs::read_to_string(&partial_path).with_context(|| {
+                format!("Failed to read partial for <{}>: {:?}", shortcode.name, partial_path)
+            })?;
+            registry.register_partial(&shortcode.name, &template)?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Render a component with a Handlebars template
+    pub fn register_partial(&mut self, name: &str, template: &str) -> Result<()> {
+        let template_name = format!("{}/{}", SHORTCODE_TEMPLATES_DIR, name);
+        self.handlebars
+            .register_template_string(&template_name, template)
+            .with_context(|| format!("Failed to register partial for <{}>", name))?;
+        self.components.insert(name.to_string(), Rendering::Partial(template_name));
+        Ok(())
+    }
+
+    /// Replace a component with its children
+    pub fn register_strip(&mut self, name: &str) {
+        self.components.insert(name.to_string(), Rendering::Strip);
+    }
+
+    /// Whether a component name is registered
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    /// Replace the components in markdown content with their rendered output
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a partial fails to render.
+    pub fn expand(&self, content: &str) -> Result<String> {
+        let mut error = None;
+
+        let expanded = replace_components(content, |component| {
+            match self.render(component) {
+                Ok(rendered) => rendered,
+                Err(err) => {
+                    error.get_or_insert(err);
+                    String::new()
+                }
+            }
+        });
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(expanded),
+        }
+    }
+
+    /// Render one component, expanding the components in its children first
+    fn render(&self, component: &Component) -> Result<String> {
+        let children = match &component.children {
+            Some(children) => self.expand(children)?,
+            None => String::new(),
+        };
+
+        let template_name = match self.components.get(&component.name) {
+            Some(Rendering::Partial(template_name)) => template_name,
+            Some(Rendering::Strip) | None => return Ok(children),
+        };
+
+        let mut data: Map<String, Value> = component
+            .attributes
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect();
+        data.insert("children".to_string(), Value::String(markdown_to_html(&children)));
+
+        self.handlebars
+            .render(template_name, &data)
+            .with_context(|| format!("Failed to render <{}> on line {}", component.name, component.line))
+    }
+}
@@ -0,0 +1,99 @@
+//! Rebuilding when content changes
+//!
+//! Watch mode polls the content directory, templates and configuration for
+//! changed modification times or sizes and rebuilds when anything changes.
+//! Polling is less immediate than file system notifications but behaves the
+//! same on every platform and on network drives and containers.
+
+use anyhow::Result;
+use common_traits::CancellationToken;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+use crate::{build_content_cancellable, BuildOptions, BuildReport};
+
+/// How often watch mode checks for changes
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and size of a file
+type Stamp = (Option<SystemTime>, u64);
+
+/// Detects changes to the files under a set of paths
+#[derive(Debug, Clone)]
+pub struct ChangeWatcher {
+    paths: Vec<PathBuf>,
+    stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+impl ChangeWatcher {
+    /// Watch `paths`, each a file or directory, which need not exist yet
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let stamps = snapshot(&paths);
+        Self { paths, stamps }
+    }
+
+    /// Check whether any file was added, removed or modified since the last check
+    pub fn poll(&mut self) -> bool {
+        let stamps = snapshot(&self.paths);
+        let changed = stamps != self.stamps;
+        self.stamps = stamps;
+        changed
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, Stamp> {
+    paths
+        .iter()
+        .flat_map(|path| WalkDir::new(path).into_iter().filter_map(|entry| entry.ok()))
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((entry.into_path(), (metadata.modified().ok(), metadata.len())))
+        })
+        .collect()
+}
+
+/// The files a build reads: content, templates, configuration and redirects
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be loaded
+pub fn watched_paths() -> Result<Vec<PathBuf>> {
+    let config = common_config::load_config()?;
+    let config_file = Path::new("config.yaml");
+
+    Ok(vec![
+        PathBuf::from(&config.content.base_dir),
+        PathBuf::from("templates"),
+        config_file.to_path_buf(),
+        common_config::local_config_path(config_file),
+        common_config::redirects_path(&config),
+    ])
+}
+
+/// Build, then rebuild whenever a watched file changes, until `token` is cancelled
+///
+/// `on_build` is called with the result of each build. A failed build does
+/// not stop watching, so a writer can fix the problem and save again.
+///
+/// # Errors
+///
+/// Returns an error if the files to watch cannot be determined
+pub fn watch<F>(options: &BuildOptions, interval: Duration, token: &CancellationToken, mut on_build: F) -> Result<()>
+where
+    F: FnMut(Result<BuildReport>),
+{
+    let mut watcher = ChangeWatcher::new(watched_paths()?);
+    on_build(build_content_cancellable(options, token));
+
+    while !token.is_cancelled() {
+        std::thread::sleep(interval);
+        if watcher.poll() && !token.is_cancelled() {
+            on_build(build_content_cancellable(options, token));
+        }
+    }
+
+    Ok(())
+}
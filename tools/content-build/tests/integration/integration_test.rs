@@ -188,6 +188,16 @@ This is the content of episode 1."#.to_string()));
         skip_rss: false,
         skip_sitemap: false,
         verbose: true,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act - build all content
@@ -315,6 +325,16 @@ This is a test article with some content."#.to_string()));
         skip_rss: false,
         skip_sitemap: false,
         verbose: true,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act - build specific content
@@ -460,6 +480,16 @@ This is a test article with some content."#.to_string()));
         skip_rss: false,  // Enable RSS feed generation
         skip_sitemap: false, // Enable sitemap generation
         verbose: true,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act - build with all features
@@ -467,4 +497,52 @@ This is a test article with some content."#.to_string()));
 
     // Assert
     assert!(result.is_ok(), "Building content with all features should succeed: {:?}", result.err());
-}
\ No newline at end of file
+}
+
+/// Commit everything in the working tree of `repo`
+fn commit_all(repo: &git2::Repository, message: &str) {
+    let mut index = repo.index().unwrap();
+    index.add_all(["*"], git2::IndexAddOption::DEFAULT, None).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::now("Writer", "writer@example.com").unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap();
+}
+
+#[test]
+fn test_build_only_content_changed_since_a_reference() {
+    let fixture = TestFixture::new().unwrap();
+    let root = fixture.path();
+    let config_path = common_test_utils::write_repository(root, &["blog"]);
+    let base_dir = root.join("content");
+    for slug in ["old", "new"] {
+        common_test_utils::write_article(&base_dir, "blog", slug, &format!("---\ntitle: {}\npublished: 2024-01-01\n---\n\nText.\n", slug));
+    }
+    let repo = git2::Repository::init(root).unwrap();
+    commit_all(&repo, "Add articles");
+
+    common_test_utils::write_article(&base_dir, "blog", "new", "---\ntitle: New, revised\npublished: 2024-01-01\n---\n\nMore text.\n");
+
+    let _config = common_test_utils::use_config(&config_path);
+    let output_dir = root.join("public");
+    let options = BuildOptions {
+        output_dir: Some(output_dir.to_string_lossy().to_string()),
+        skip_html: true,
+        skip_rss: true,
+        skip_sitemap: true,
+        changed_since: Some("HEAD".to_string()),
+        ..Default::default()
+    };
+
+    let report = build_content(&options).unwrap();
+    assert_eq!(report.articles, 1);
+    assert!(output_dir.join("data/new.json").exists());
+    assert!(!output_dir.join("data/old.json").exists());
+
+    // With nothing changed there is nothing to build, which isn't an error
+    commit_all(&repo, "Revise an article");
+    let report = build_content(&options).unwrap();
+    assert_eq!(report.articles, 0);
+}
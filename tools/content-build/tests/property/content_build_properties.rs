@@ -21,7 +21,7 @@ fn valid_description_strategy() -> impl Strategy<Value = String> {
 }
 
 fn valid_date_strategy() -> impl Strategy<Value = String> {
-    r"\d{4}\-\d{2}\-\d{2}".prop_map(String::from)
+    (2000..2030u32, 1..13u32, 1..29u32).prop_map(|(y, m, d)| format!("{:04}-{:02}-{:02}", y, m, d))
 }
 
 // Strategy for valid markdown content
@@ -106,7 +106,7 @@ proptest! {
         let article = result.unwrap();
         prop_assert_eq!(article.frontmatter.title, title, "Title should match input");
         prop_assert_eq!(article.frontmatter.description.as_ref().unwrap(), &description, "Description should match input");
-        prop_assert_eq!(article.frontmatter.published_at.as_ref().unwrap(), &published_at, "Published date should match input");
+        prop_assert_eq!(article.frontmatter.published_at.as_ref().unwrap().to_string(), published_at, "Published date should match input");
         prop_assert_eq!(article.slug, slug, "Slug should match directory name");
         prop_assert!(article.content.contains(&content), "Content should match input");
         prop_assert!(article.word_count.is_some(), "Word count should be calculated");
@@ -19,7 +19,7 @@ fn article_data_strategy() -> impl Strategy<Value = (String, String, String, Str
         // Description (using instead of description based on current Frontmatter structure)
         r#"[A-Za-z0-9\s\.\,\-\:\;]{10,100}"#.prop_map(String::from),
         // Date (YYYY-MM-DD)
-        r"\d{4}\-\d{2}\-\d{2}".prop_map(String::from),
+        (2000..2030u32, 1..13u32, 1..29u32).prop_map(|(y, m, d)| format!("{:04}-{:02}-{:02}", y, m, d)),
         // Content
         prop::collection::vec("[A-Za-z0-9\\s\\.\\,\\-\\:\\;]{5,20}".prop_map(String::from), 5..20)
             .prop_map(|lines| lines.join("\n"))
@@ -49,7 +49,7 @@ fn create_test_article(
             title: title.to_string(),
             // Using description instead of description based on current Frontmatter structure
             description: Some(description.to_string()),
-            published_at: Some(published_at.to_string()),
+            published_at: Some(published_at.parse().unwrap()),
             updated_at: None,
             is_draft: Some(is_draft),
             ..Default::default()
@@ -60,6 +60,15 @@ fn create_test_article(
         path: format!("content/{}/{}/index.mdx", topic, slug),
         word_count: Some(word_count),
         reading_time: Some(reading_time),
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
     }
 }
 
@@ -75,7 +84,7 @@ fn verify_json_contents(json_content: &str, article: &Article) -> Result<(), Tes
     }
 
     if let Some(date) = &article.frontmatter.published_at {
-        prop_assert_eq!(json["published_at"].as_str().unwrap(), date, "Published date should match");
+        prop_assert_eq!(json["published_at"].as_str().unwrap(), date.to_string(), "Published date should match");
     }
 
     prop_assert_eq!(json["slug"].as_str().unwrap(), article.slug.as_str(), "Slug should match");
@@ -281,6 +290,16 @@ proptest! {
             skip_rss: true,       // Skip RSS for this test
             skip_sitemap: true,   // Skip sitemap for this test
             verbose: false,
+            summary_sentences: 2,
+            rss: Default::default(),
+            allow_duplicate_slugs: false,
+            git_metadata: false,
+            minify: false,
+            precompress: Vec::new(),
+            profile: None,
+            image_cdn: None,
+            hero_images: Default::default(),
+            changed_since: None,
         };
 
         // Execute build
@@ -112,6 +112,16 @@ This is the content of the test article."#.to_string()));
         skip_rss: false,
         skip_sitemap: false,
         verbose: true,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act
@@ -246,6 +256,15 @@ This is the content of article 2."#.to_string()));
             author: "Test Author".to_string(),
             copyright: "Copyright © 2023".to_string(),
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
 
     };
 
@@ -269,6 +288,16 @@ This is the content of article 2."#.to_string()));
         skip_rss: false,
         skip_sitemap: false,
         verbose: true,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act
@@ -334,6 +363,16 @@ fn test_build_content_with_no_content_found() {
         skip_rss: false,
         skip_sitemap: false,
         verbose: false,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act
@@ -405,6 +444,15 @@ This is the content of the test article."#.to_string()));
             author: "Test Author".to_string(),
             copyright: "Copyright © 2023".to_string(),
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
 
     };
 
@@ -428,6 +476,16 @@ This is the content of the test article."#.to_string()));
         skip_rss: true,
         skip_sitemap: true,
         verbose: false,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act
@@ -557,6 +615,15 @@ This is the content of the draft article."#.to_string()));
             author: "Test Author".to_string(),
             copyright: "Copyright © 2023".to_string(),
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
 
     };
 
@@ -580,6 +647,16 @@ This is the content of the draft article."#.to_string()));
         skip_rss: false,
         skip_sitemap: false,
         verbose: true,
+        summary_sentences: 2,
+        rss: Default::default(),
+        allow_duplicate_slugs: false,
+        git_metadata: false,
+        minify: false,
+        precompress: Vec::new(),
+        profile: None,
+        image_cdn: None,
+        hero_images: Default::default(),
+        changed_since: None,
     };
 
     // Act
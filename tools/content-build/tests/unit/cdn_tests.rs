@@ -0,0 +1,98 @@
+use common_models::{Article, Frontmatter, ImageManifest, ImageVariant, IMAGE_MANIFEST_FILE};
+use content_build::cdn::{ImageCdn, ImageCdnOptions};
+
+fn variant(path: &str, width: u32, format: &str) -> ImageVariant {
+    ImageVariant {
+        path: path.to_string(),
+        size: "featured".to_string(),
+        width,
+        height: width / 2,
+        format: format.to_string(),
+    }
+}
+
+fn cdn(params: Option<&str>) -> ImageCdn {
+    let mut manifest = ImageManifest::default();
+    manifest.insert(variant("blog/post/post-featured.webp", 1200, "webp"));
+    manifest.insert(variant("blog/post/post-small.jpg", 400, "jpg"));
+    ImageCdn::new("https://cdn.example.com/img/", params.map(str::to_string), manifest)
+}
+
+#[test]
+fn test_rewrite_text_only_touches_images_in_the_manifest() {
+    let html = r#"<img src="/images/blog/post/post-featured.webp" srcset="/images/blog/post/post-small.jpg 400w,/images/blog/post/post-featured.webp 1200w"><img src="/images/blog/post/diagram.png"><a href="https://example.com/images/blog/post/post-small.jpg">x</a>"#;
+
+    assert_eq!(
+        cdn(None).rewrite_text(html),
+        r#"<img src="https://cdn.example.com/img/blog/post/post-featured.webp" srcset="https://cdn.example.com/img/blog/post/post-small.jpg 400w,https://cdn.example.com/img/blog/post/post-featured.webp 1200w"><img src="/images/blog/post/diagram.png"><a href="https://example.com/images/blog/post/post-small.jpg">x</a>"#
+    );
+}
+
+#[test]
+fn test_params_are_filled_from_the_manifest() {
+    let cdn = cdn(Some("w={width}&h={height}&fm={format}&s={size}"));
+
+    assert_eq!(
+        cdn.rewrite_url("/images/blog/post/post-small.jpg").as_deref(),
+        Some("https://cdn.example.com/img/blog/post/post-small.jpg?w=400&h=200&fm=jpg&s=featured")
+    );
+    assert_eq!(cdn.rewrite_url("/images/blog/post/missing.jpg"), None);
+    assert_eq!(cdn.rewrite_url("post-small.jpg"), None);
+}
+
+#[test]
+fn test_rewrite_article_covers_html_and_featured_image() {
+    let mut article = Article {
+        frontmatter: Frontmatter {
+            featured_image_path: Some("/images/blog/post/post-featured.webp".to_string()),
+            ..Default::default()
+        },
+        content: String::new(),
+        slug: "post".to_string(),
+        topic: "blog".to_string(),
+        path: "content/blog/post/post.md".to_string(),
+        word_count: None,
+        reading_time: None,
+        series_navigation: None,
+        html: Some("<p><img src=\"/images/blog/post/post-small.jpg\"></p>".to_string()),
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
+    };
+
+    cdn(Some("fm={format}")).rewrite_article(&mut article);
+    assert_eq!(
+        article.frontmatter.featured_image_path.as_deref(),
+        Some("https://cdn.example.com/img/blog/post/post-featured.webp?fm=webp")
+    );
+    assert_eq!(
+        article.html.as_deref(),
+        Some("<p><img src=\"https://cdn.example.com/img/blog/post/post-small.jpg?fm=jpg\"></p>")
+    );
+}
+
+#[test]
+fn test_load_requires_a_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = ImageCdnOptions {
+        base_url: "https://cdn.example.com".to_string(),
+        params: None,
+        images_dir: dir.path().to_path_buf(),
+    };
+
+    let err = ImageCdn::load(&options).unwrap_err();
+    assert!(err.to_string().contains("image-build manifest"));
+
+    let mut manifest = ImageManifest::default();
+    manifest.insert(variant("blog/post/post-small.jpg", 400, "jpg"));
+    manifest.save(&dir.path().join(IMAGE_MANIFEST_FILE)).unwrap();
+    let cdn = ImageCdn::load(&options).unwrap();
+    assert_eq!(
+        cdn.rewrite_url("/images/blog/post/post-small.jpg").as_deref(),
+        Some("https://cdn.example.com/blog/post/post-small.jpg")
+    );
+}
@@ -1,4 +1,4 @@
-use content_build::{generate_sitemap, generate_rss_feed};
+use content_build::{canonical_url, canonical_url_for, find_collisions, generate_sitemap, generate_rss_feed, Collision};
 use common_test_utils::fixtures::TestFixture;
 use common_test_utils::mocks::MockFileSystem;
 use mockall::predicate;
@@ -30,7 +30,7 @@ fn test_generate_sitemap() {
             frontmatter: Frontmatter {
                 title: "Article 1".to_string(),
                 description: Some("Description 1".to_string()),
-                published_at: Some("2023-01-01".to_string()),
+                published_at: Some("2023-01-01".parse().unwrap()),
                 updated_at: None,
                 is_draft: None,
                 ..Default::default()
@@ -41,13 +41,22 @@ fn test_generate_sitemap() {
             path: "content/blog/article-1/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
         Article {
             frontmatter: Frontmatter {
                 title: "Article 2".to_string(),
                 description: Some("Description 2".to_string()),
-                published_at: Some("2023-01-02".to_string()),
-                updated_at: Some("2023-01-03".to_string()),
+                published_at: Some("2023-01-02".parse().unwrap()),
+                updated_at: Some("2023-01-03".parse().unwrap()),
                 is_draft: None,
                 ..Default::default()
             },
@@ -57,6 +66,15 @@ fn test_generate_sitemap() {
             path: "content/blog/article-2/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
         Article {
             frontmatter: Frontmatter {
@@ -73,6 +91,15 @@ fn test_generate_sitemap() {
             path: "content/blog/draft-article/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
     ];
 
@@ -129,7 +156,7 @@ fn test_generate_sitemap_without_site_url() {
             frontmatter: Frontmatter {
                 title: "Article 1".to_string(),
                 description: Some("Description 1".to_string()),
-                published_at: Some("2023-01-01".to_string()),
+                published_at: Some("2023-01-01".parse().unwrap()),
                 updated_at: None,
                 is_draft: None,
                 ..Default::default()
@@ -140,6 +167,15 @@ fn test_generate_sitemap_without_site_url() {
             path: "content/blog/article-1/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
     ];
 
@@ -196,7 +232,7 @@ fn test_generate_rss_feed() {
             frontmatter: Frontmatter {
                 title: "Article 1".to_string(),
                 description: Some("Description 1".to_string()),
-                published_at: Some("2023-01-01".to_string()),
+                published_at: Some("2023-01-01".parse().unwrap()),
                 updated_at: None,
                 is_draft: None,
                 ..Default::default()
@@ -207,13 +243,22 @@ fn test_generate_rss_feed() {
             path: "content/blog/article-1/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
         Article {
             frontmatter: Frontmatter {
                 title: "Article 2".to_string(),
                 description: Some("Description 2".to_string()),
-                published_at: Some("2023-01-02".to_string()),
-                updated_at: Some("2023-01-03".to_string()),
+                published_at: Some("2023-01-02".parse().unwrap()),
+                updated_at: Some("2023-01-03".parse().unwrap()),
                 is_draft: None,
                 ..Default::default()
             },
@@ -223,6 +268,15 @@ fn test_generate_rss_feed() {
             path: "content/blog/article-2/index.mdx".to_string(),
             word_count: Some(10),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
         Article {
             frontmatter: Frontmatter {
@@ -239,6 +293,15 @@ fn test_generate_rss_feed() {
             path: "content/blog/draft-article/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
     ];
 
@@ -296,7 +359,7 @@ fn test_generate_rss_feed_with_many_articles() {
             frontmatter: Frontmatter {
                 title: format!("Article {}", i),
                 description: Some(format!("Description {}", i)),
-                published_at: Some(format!("2023-01-{:02}", i)),
+                published_at: Some(format!("2023-01-{:02}", i).parse().unwrap()),
                 updated_at: None,
                 is_draft: None,
                 ..Default::default()
@@ -307,6 +370,15 @@ fn test_generate_rss_feed_with_many_articles() {
             path: format!("content/blog/article-{}/index.mdx", i),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         });
     }
 
@@ -363,7 +435,7 @@ fn test_generate_sitemap_file_error() {
             frontmatter: Frontmatter {
                 title: "Article 1".to_string(),
                 description: Some("Description 1".to_string()),
-                published_at: Some("2023-01-01".to_string()),
+                published_at: Some("2023-01-01".parse().unwrap()),
                 updated_at: None,
                 is_draft: None,
                 ..Default::default()
@@ -374,6 +446,15 @@ fn test_generate_sitemap_file_error() {
             path: "content/blog/article-1/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
     ];
 
@@ -412,7 +493,7 @@ fn test_generate_rss_feed_file_error() {
             frontmatter: Frontmatter {
                 title: "Article 1".to_string(),
                 description: Some("Description 1".to_string()),
-                published_at: Some("2023-01-01".to_string()),
+                published_at: Some("2023-01-01".parse().unwrap()),
                 updated_at: None,
                 is_draft: None,
                 ..Default::default()
@@ -423,6 +504,15 @@ fn test_generate_rss_feed_file_error() {
             path: "content/blog/article-1/index.mdx".to_string(),
             word_count: Some(2),
             reading_time: Some(1),
+            series_navigation: None,
+            html: None,
+            summary: None,
+            git: None,
+            lang: None,
+            alternates: Vec::new(),
+            links: Vec::new(),
+            hero_image: None,
+            social_image: None,
         },
     ];
 
@@ -435,4 +525,379 @@ fn test_generate_rss_feed_file_error() {
     // Assert
     assert!(result.is_err(), "Generating RSS feed should fail when write_file fails");
     assert!(result.unwrap_err().to_string().contains("Permission denied"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_generate_redirects() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let redirects = vec![common_config::Redirect::between("blog", "old-post", "notes", "new-post")];
+
+    content_build::generate_redirects(temp_dir.path(), &redirects).unwrap();
+
+    let rules = std::fs::read_to_string(temp_dir.path().join("_redirects")).unwrap();
+    assert_eq!(rules, "/blog/old-post /notes/new-post 301\n");
+
+    let json = std::fs::read_to_string(temp_dir.path().join("redirects.json")).unwrap();
+    assert!(json.contains("\"from\": \"/blog/old-post\""));
+    assert!(json.contains("\"to\": \"/notes/new-post\""));
+}
+
+#[test]
+fn test_generate_redirects_without_redirects_writes_nothing() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    content_build::generate_redirects(temp_dir.path(), &[]).unwrap();
+
+    assert!(!temp_dir.path().join("_redirects").exists());
+    assert!(!temp_dir.path().join("redirects.json").exists());
+}
+
+#[test]
+fn test_generate_rss_feed_with_full_content_and_hero_enclosure() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let images_dir = temp_dir.path().join("images");
+    let article_images = images_dir.join("blog").join("article-1");
+    std::fs::create_dir_all(&article_images).unwrap();
+    std::fs::write(article_images.join("article-1-featured.webp"), vec![0u8; 8]).unwrap();
+    std::fs::write(article_images.join("article-1-featured.jpg"), vec![0u8; 12]).unwrap();
+    std::fs::write(article_images.join("article-1-thumbnail.jpg"), vec![0u8; 4]).unwrap();
+
+    let article = Article {
+        frontmatter: Frontmatter {
+            title: "Article 1".to_string(),
+            published_at: Some("2023-01-01".parse().unwrap()),
+            is_draft: None,
+            ..Default::default()
+        },
+        content: "# Heading\n\nBody text.".to_string(),
+        slug: "article-1".to_string(),
+        topic: "blog".to_string(),
+        path: "content/blog/article-1/index.mdx".to_string(),
+        word_count: Some(2),
+        reading_time: Some(1),
+        series_navigation: None,
+        html: None,
+        summary: Some("Body text.".to_string()),
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
+    };
+
+    let config = Config {
+        publication: PublicationConfig {
+            site_url: Some("https://example.com/".to_string()),
+            author: "Test Author".to_string(),
+            copyright: "Copyright © 2023".to_string(),
+        },
+        ..Default::default()
+    };
+
+    let options = content_build::RssOptions {
+        full_content: true,
+        images_dir: Some(images_dir),
+        ..Default::default()
+    };
+    content_build::generate_rss_feed_with(temp_dir.path(), &[article], &config, &options).unwrap();
+
+    let channel = rss::Channel::read_from(
+        std::fs::read(temp_dir.path().join("rss.xml")).unwrap().as_slice(),
+    )
+    .unwrap();
+    let item = &channel.items()[0];
+
+    assert_eq!(item.description(), Some("Body text."));
+    assert_eq!(item.content(), Some("<h1>Heading</h1>\n<p>Body text.</p>"));
+
+    let enclosure = item.enclosure().unwrap();
+    assert_eq!(enclosure.url(), "https://example.com/images/blog/article-1/article-1-featured.jpg");
+    assert_eq!(enclosure.length(), "12");
+    assert_eq!(enclosure.mime_type(), "image/jpeg");
+}
+
+fn article_in(topic: &str, slug: &str) -> Article {
+    Article {
+        frontmatter: Frontmatter {
+            title: slug.to_string(),
+            ..Default::default()
+        },
+        content: String::new(),
+        slug: slug.to_string(),
+        topic: topic.to_string(),
+        path: format!("content/{}/{}/index.mdx", topic, slug),
+        word_count: None,
+        reading_time: None,
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
+    }
+}
+
+#[test]
+fn test_canonical_url_respects_frontmatter_override() {
+    let mut article = article_in("blog", "hello");
+    assert_eq!(canonical_url(&article, "https://example.com/"), "https://example.com/blog/hello");
+
+    article.frontmatter.set_extra("canonical_url", "/notes/hello");
+    assert_eq!(canonical_url(&article, "https://example.com"), "https://example.com/notes/hello");
+
+    article.frontmatter.set_extra("canonical_url", "https://elsewhere.dev/hello");
+    assert_eq!(canonical_url(&article, "https://example.com"), "https://elsewhere.dev/hello");
+    assert_eq!(
+        canonical_url_for(&article.frontmatter, "blog", "hello", "https://example.com"),
+        "https://elsewhere.dev/hello"
+    );
+    assert_eq!(
+        canonical_url_for(&Frontmatter::default(), "blog", "hello", "https://example.com/"),
+        "https://example.com/blog/hello"
+    );
+}
+
+#[test]
+fn test_canonical_frontmatter_field_takes_precedence() {
+    let mut article = article_in("blog", "hello");
+    article.frontmatter.set_extra("canonical_url", "/notes/old");
+    article.frontmatter.set_extra("canonical", "/notes/hello");
+    assert_eq!(canonical_url(&article, "https://example.com"), "https://example.com/notes/hello");
+}
+
+#[test]
+fn test_head_tags_add_canonical_and_robots_once() {
+    let mut article = article_in("blog", "hello");
+    article.frontmatter.set_extra("robots", "NoFollow, noarchive, nofollow");
+    article.frontmatter.set_extra("noindex", true);
+    content_build::add_page_links(std::slice::from_mut(&mut article), "https://example.com");
+
+    assert_eq!(content_build::robots_directives(&article), ["noindex", "nofollow", "noarchive"]);
+    assert!(content_build::is_noindex(&article));
+
+    let html = content_build::inject_head_tags(&article, "<html><HEAD><title>Hello</title></HEAD><body></body></html>");
+    assert_eq!(
+        html,
+        "<html><HEAD><title>Hello</title><link rel=\"canonical\" href=\"https://example.com/blog/hello\">\n\
+         <meta name=\"robots\" content=\"noindex, nofollow, noarchive\">\n</HEAD><body></body></html>"
+    );
+
+    // Templates that render the tags themselves keep them
+    let templated = "<head><link rel=\"canonical\" href=\"/x\"><meta name=\"robots\" content=\"all\"></head>";
+    assert_eq!(content_build::inject_head_tags(&article, templated), templated);
+
+    let plain = article_in("blog", "plain");
+    assert!(!content_build::is_noindex(&plain));
+    assert_eq!(content_build::head_tags(&plain, "<p>Body</p>"), "");
+}
+
+#[test]
+fn test_generate_sitemap_leaves_out_noindex_pages() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut hidden = article_in("blog", "hidden");
+    hidden.frontmatter.is_draft = Some(false);
+    hidden.frontmatter.set_extra("robots", "none");
+    let mut listed = article_in("blog", "listed");
+    listed.frontmatter.is_draft = Some(false);
+    listed.frontmatter.set_extra("noindex", false);
+
+    let config = Config {
+        publication: PublicationConfig {
+            site_url: Some("https://example.com".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    generate_sitemap(temp_dir.path(), &[hidden, listed], &config).unwrap();
+
+    let sitemap = std::fs::read_to_string(temp_dir.path().join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("https://example.com/blog/listed"));
+    assert!(!sitemap.contains("https://example.com/blog/hidden"));
+}
+
+#[test]
+fn test_page_links_list_canonical_and_syndicated_urls() {
+    let mut article = article_in("blog", "hello");
+    let urls: serde_yaml::Value =
+        serde_yaml::from_str("[https://dev.to/me/hello, https://example.com/blog/hello, https://dev.to/me/hello]").unwrap();
+    article.frontmatter.set_extra("syndicated_to", urls);
+
+    let links = content_build::page_links(&article, "https://example.com");
+    let links: Vec<(&str, &str)> = links.iter().map(|link| (link.rel.as_str(), link.href.as_str())).collect();
+    assert_eq!(
+        links,
+        [("canonical", "https://example.com/blog/hello"), ("syndication", "https://dev.to/me/hello")]
+    );
+
+    let mut articles = vec![article];
+    content_build::add_page_links(&mut articles, "https://example.com");
+    let json = serde_json::to_value(&articles[0]).unwrap();
+    assert_eq!(json["links"][1]["rel"], "syndication");
+}
+
+#[test]
+fn test_find_collisions_reports_shared_slugs_and_canonical_urls() {
+    let mut moved = article_in("notes", "moved");
+    moved.frontmatter.set_extra("canonical_url", "/blog/hello");
+    let articles = vec![
+        article_in("blog", "hello"),
+        article_in("notes", "hello"),
+        article_in("blog", "unique"),
+        moved,
+    ];
+
+    let collisions = find_collisions(&articles, "https://example.com");
+
+    assert_eq!(collisions, vec![
+        Collision::Slug {
+            slug: "hello".to_string(),
+            topics: vec!["blog".to_string(), "notes".to_string()],
+        },
+        Collision::CanonicalUrl {
+            url: "https://example.com/blog/hello".to_string(),
+            articles: vec!["blog/hello".to_string(), "notes/moved".to_string()],
+        },
+    ]);
+    assert!(find_collisions(&articles[2..], "https://example.com").is_empty());
+}
+
+#[test]
+fn test_generate_sitemap_uses_git_last_modified() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let git = common_models::GitMetadata {
+        created: "2023-01-01T09:00:00Z".parse().unwrap(),
+        last_modified: "2024-05-06T18:30:00Z".parse().unwrap(),
+        contributors: vec!["Writer".to_string()],
+    };
+
+    let mut committed = article_in("blog", "committed");
+    committed.frontmatter.is_draft = Some(false);
+    committed.frontmatter.published_at = Some("2023-01-01".parse().unwrap());
+    committed.git = Some(git.clone());
+
+    let mut updated = article_in("blog", "updated");
+    updated.frontmatter.is_draft = Some(false);
+    updated.frontmatter.updated_at = Some("2024-09-01".parse().unwrap());
+    updated.git = Some(git);
+
+    let config = Config {
+        publication: PublicationConfig {
+            site_url: Some("https://example.com".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    generate_sitemap(temp_dir.path(), &[committed, updated], &config).unwrap();
+
+    let sitemap = std::fs::read_to_string(temp_dir.path().join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("<loc>https://example.com/blog/committed</loc><lastmod>2024-05-06</lastmod>"));
+    // A date set by hand still wins
+    assert!(sitemap.contains("<loc>https://example.com/blog/updated</loc><lastmod>2024-09-01</lastmod>"));
+}
+
+fn i18n_config() -> Config {
+    Config {
+        publication: PublicationConfig {
+            site_url: Some("https://example.com".to_string()),
+            author: "Test Author".to_string(),
+            copyright: "Copyright © 2023".to_string(),
+        },
+        i18n: common_models::I18nConfig {
+            default_locale: "en".to_string(),
+            locales: vec!["en".to_string(), "de".to_string()],
+        },
+        ..Default::default()
+    }
+}
+
+fn translated(lang: &str, topic: &str, slug: &str) -> Article {
+    let mut article = article_in(topic, slug);
+    article.frontmatter.published_at = Some("2023-01-01".parse().unwrap());
+    article.frontmatter.is_draft = Some(false);
+    article.lang = Some(lang.to_string());
+    article
+}
+
+#[test]
+fn test_link_translations_sets_alternates_and_urls() {
+    let config = i18n_config();
+    let mut articles = vec![
+        translated("de", "blog", "hello"),
+        translated("en", "blog", "hello"),
+        translated("de", "blog", "only-german"),
+        article_in("blog", "no-language"),
+    ];
+
+    content_build::link_translations(&mut articles, "https://example.com/", &config.i18n);
+
+    let expected = vec![
+        common_models::LocaleAlternate { lang: "en".to_string(), url: "https://example.com/blog/hello".to_string() },
+        common_models::LocaleAlternate { lang: "de".to_string(), url: "https://example.com/de/blog/hello".to_string() },
+    ];
+    assert_eq!(articles[0].alternates, expected);
+    assert_eq!(articles[1].alternates, expected);
+    assert_eq!(canonical_url(&articles[0], "https://example.com"), "https://example.com/de/blog/hello");
+    assert_eq!(canonical_url(&articles[1], "https://example.com"), "https://example.com/blog/hello");
+    assert_eq!(articles[2].alternates.len(), 1);
+    assert!(articles[3].alternates.is_empty());
+
+    // Translations share a slug but are written to different trees
+    assert!(find_collisions(&articles, "https://example.com").is_empty());
+
+    let json = serde_json::to_value(&articles[0]).unwrap();
+    assert_eq!(json["lang"], "de");
+    assert_eq!(json["alternates"][0]["url"], "https://example.com/blog/hello");
+}
+
+#[test]
+fn test_locale_trees_put_the_default_locale_at_the_root() {
+    let config = i18n_config();
+    let output_dir = PathBuf::from("public");
+    let articles = vec![
+        translated("de", "blog", "hello"),
+        translated("en", "blog", "hello"),
+        article_in("blog", "no-language"),
+    ];
+
+    let trees = content_build::locale_trees(articles, &output_dir, &config.i18n);
+
+    let layout: Vec<(PathBuf, Vec<Option<String>>)> = trees
+        .into_iter()
+        .map(|(dir, articles)| (dir, articles.into_iter().map(|article| article.lang).collect()))
+        .collect();
+    assert_eq!(layout, vec![
+        (output_dir.clone(), vec![Some("en".to_string()), None]),
+        (output_dir.join("de"), vec![Some("de".to_string())]),
+    ]);
+}
+
+#[test]
+fn test_generate_sitemap_links_translations_with_hreflang() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config = i18n_config();
+    let mut draft = translated("de", "blog", "draft");
+    draft.frontmatter.is_draft = Some(true);
+    let mut articles = vec![
+        translated("en", "blog", "hello"),
+        translated("de", "blog", "hello"),
+        translated("en", "blog", "draft"),
+        draft,
+    ];
+    content_build::link_translations(&mut articles, "https://example.com", &config.i18n);
+
+    generate_sitemap(temp_dir.path(), &articles, &config).unwrap();
+
+    let sitemap = std::fs::read_to_string(temp_dir.path().join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains(r#"xmlns:xhtml="http://www.w3.org/1999/xhtml""#));
+    assert!(sitemap.contains("<loc>https://example.com/de/blog/hello</loc>"));
+    assert!(sitemap.contains(r#"<xhtml:link rel="alternate" hreflang="de" href="https://example.com/de/blog/hello"/>"#));
+    assert!(sitemap.contains(r#"<xhtml:link rel="alternate" hreflang="x-default" href="https://example.com/blog/hello"/>"#));
+
+    // The draft translation isn't listed, so neither is a link to it
+    assert!(!sitemap.contains("de/blog/draft"));
+    assert_eq!(sitemap.matches("hreflang=\"x-default\"").count(), 2);
+}
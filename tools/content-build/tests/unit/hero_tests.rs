@@ -0,0 +1,184 @@
+use common_models::{Article, Frontmatter, HeroImageSource, ImageManifest, ImageVariant, PageLink};
+use content_build::hero::{add_hero_images, first_body_image, hero_image, select_hero_image, HeroImageOptions};
+use std::path::Path;
+
+fn article_at(dir: &Path, content: &str) -> Article {
+    Article {
+        frontmatter: Frontmatter::default(),
+        content: content.to_string(),
+        slug: "post".to_string(),
+        topic: "blog".to_string(),
+        path: dir.join("post.md").to_string_lossy().to_string(),
+        word_count: None,
+        reading_time: None,
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
+    }
+}
+
+fn save_image(path: &Path, width: u32, height: u32) {
+    image::RgbImage::new(width, height).save(path).unwrap();
+}
+
+fn options() -> HeroImageOptions {
+    HeroImageOptions {
+        images_dir: None,
+        min_width: 60,
+        min_height: 30,
+    }
+}
+
+fn variant(path: &str, size: &str, width: u32, format: &str) -> ImageVariant {
+    ImageVariant {
+        path: path.to_string(),
+        size: size.to_string(),
+        width,
+        height: width / 2,
+        format: format.to_string(),
+    }
+}
+
+#[test]
+fn test_hero_image_selection_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut article = article_at(dir.path(), "Intro\n\n![Diagram](diagram.png)\n\n![Other](other.png)\n");
+
+    assert_eq!(first_body_image(&article.content).as_deref(), Some("diagram.png"));
+    assert_eq!(select_hero_image(&article), Some((HeroImageSource::BodyImage, "diagram.png".to_string())));
+
+    std::fs::write(dir.path().join("index.jpg"), "").unwrap();
+    assert_eq!(select_hero_image(&article), Some((HeroImageSource::IndexImage, "index.jpg".to_string())));
+
+    article.frontmatter.set_extra("cover", "./cover.png");
+    assert_eq!(select_hero_image(&article), Some((HeroImageSource::Cover, "./cover.png".to_string())));
+
+    assert_eq!(select_hero_image(&article_at(&dir.path().join("empty"), "No images.\n")), None);
+}
+
+#[test]
+fn test_hero_image_is_checked_and_gets_generated_sizes() {
+    let dir = tempfile::tempdir().unwrap();
+    save_image(&dir.path().join("index.jpg"), 64, 32);
+    let article = article_at(dir.path(), "");
+
+    let mut manifest = ImageManifest::default();
+    manifest.insert(variant("blog/post/post-featured.webp", "featured", 1200, "webp"));
+    manifest.insert(variant("blog/post/post-featured.jpg", "featured", 1200, "jpg"));
+    manifest.insert(variant("blog/post/post-small.webp", "small", 400, "webp"));
+    manifest.insert(variant("blog/other/other-small.jpg", "small", 400, "jpg"));
+    manifest.insert(variant("blog/post/diagram.svg", "original", 800, "svg"));
+    manifest.insert(variant("blog/post/diagram-small.png", "small", 400, "png"));
+
+    let hero = hero_image(&article, &options(), Some(&manifest)).unwrap().unwrap();
+    assert_eq!(hero.source, HeroImageSource::IndexImage);
+    assert_eq!(hero.url, "/images/blog/post/index.jpg");
+    assert_eq!((hero.width, hero.height), (Some(64), Some(32)));
+
+    let sizes: Vec<(&str, &str)> = hero.sizes.iter().map(|(size, image)| (size.as_str(), image.url.as_str())).collect();
+    assert_eq!(
+        sizes,
+        vec![("featured", "/images/blog/post/post-featured.jpg"), ("small", "/images/blog/post/post-small.webp")]
+    );
+
+    let strict = HeroImageOptions {
+        min_width: 100,
+        ..options()
+    };
+    let err = hero_image(&article, &strict, None).unwrap_err();
+    assert!(err.to_string().contains("is 64x32, smaller than the minimum of 100x30"));
+}
+
+#[test]
+fn test_cover_must_exist_unless_remote() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut article = article_at(dir.path(), "");
+
+    article.frontmatter.set_extra("cover", "cover.png");
+    let err = hero_image(&article, &options(), None).unwrap_err();
+    assert!(err.to_string().starts_with("Hero image not found"));
+
+    // Only index.jpg is resized by image-build
+    save_image(&dir.path().join("cover.png"), 80, 40);
+    let mut manifest = ImageManifest::default();
+    manifest.insert(variant("blog/post/post-small.jpg", "small", 400, "jpg"));
+    let hero = hero_image(&article, &options(), Some(&manifest)).unwrap().unwrap();
+    assert_eq!(hero.url, "/images/blog/post/cover.png");
+    assert!(hero.sizes.is_empty());
+
+    article.frontmatter.set_extra("cover", "https://cdn.example.com/cover.png");
+    let hero = hero_image(&article, &options(), None).unwrap().unwrap();
+    assert_eq!(hero.source, HeroImageSource::Cover);
+    assert_eq!(hero.width, None);
+}
+
+#[test]
+fn test_head_tags_add_social_image() {
+    let dir = tempfile::tempdir().unwrap();
+    save_image(&dir.path().join("index.jpg"), 64, 32);
+    let mut article = article_at(dir.path(), "");
+    let mut manifest = ImageManifest::default();
+    manifest.insert(variant("blog/post/post-large.jpg", "large", 1200, "jpg"));
+    manifest.insert(variant("blog/post/post-small.jpg", "small", 400, "jpg"));
+    article.hero_image = hero_image(&article, &options(), Some(&manifest)).unwrap();
+
+    // Site paths need the canonical link to be made absolute
+    assert_eq!(content_build::social_image_url(&article), None);
+
+    article.links = vec![PageLink {
+        rel: "canonical".to_string(),
+        href: "https://example.com/blog/post".to_string(),
+    }];
+    assert_eq!(
+        content_build::social_image_url(&article).as_deref(),
+        Some("https://example.com/images/blog/post/post-large.jpg")
+    );
+
+    let tags = content_build::head_tags(&article, "<head><link rel=\"canonical\" href=\"/x\"></head>");
+    assert_eq!(
+        tags,
+        "<meta property=\"og:image\" content=\"https://example.com/images/blog/post/post-large.jpg\">\n\
+         <meta name=\"twitter:card\" content=\"summary_large_image\">\n\
+         <meta name=\"twitter:image\" content=\"https://example.com/images/blog/post/post-large.jpg\">"
+    );
+}
+
+#[test]
+fn test_social_card_is_preferred_for_sharing() {
+    let dir = tempfile::tempdir().unwrap();
+    let images_dir = dir.path().join("images");
+    std::fs::create_dir(&images_dir).unwrap();
+    save_image(&dir.path().join("index.jpg"), 64, 32);
+
+    let mut manifest = ImageManifest::default();
+    manifest.insert(variant("blog/post/post-large.jpg", "large", 1200, "jpg"));
+    manifest.insert(variant("blog/post/post-og.jpg", "og", 1200, "jpg"));
+    manifest.save(&images_dir.join(common_models::IMAGE_MANIFEST_FILE)).unwrap();
+
+    let mut articles = vec![article_at(dir.path(), "")];
+    let options = HeroImageOptions {
+        images_dir: Some(images_dir),
+        ..options()
+    };
+    add_hero_images(&mut articles, &options).unwrap();
+
+    let article = &mut articles[0];
+    assert_eq!(article.social_image.as_deref(), Some("/images/blog/post/post-og.jpg"));
+    let hero = article.hero_image.as_ref().unwrap();
+    assert_eq!(hero.sizes.keys().collect::<Vec<_>>(), vec!["large"]);
+
+    article.links = vec![PageLink {
+        rel: "canonical".to_string(),
+        href: "https://example.com/blog/post".to_string(),
+    }];
+    assert_eq!(
+        content_build::social_image_url(article).as_deref(),
+        Some("https://example.com/images/blog/post/post-og.jpg")
+    );
+}
@@ -0,0 +1,69 @@
+use common_models::{ImageManifest, ImageVariant};
+use content_build::media::AnimatedMedia;
+
+fn variant(path: &str, size: &str, format: &str) -> ImageVariant {
+    ImageVariant {
+        path: path.to_string(),
+        size: size.to_string(),
+        width: 640,
+        height: 360,
+        format: format.to_string(),
+    }
+}
+
+fn render(media: &AnimatedMedia, markdown: &str) -> String {
+    media.rewrite_html(&common_markdown::TransformPipeline::new().render_html(markdown))
+}
+
+#[test]
+fn test_gif_markup_depends_on_what_image_build_generated() {
+    let mut manifest = ImageManifest::default();
+    manifest.insert(variant("blog/post/deploy.gif", "original", "gif"));
+    manifest.insert(variant("blog/post/deploy-poster.jpg", "poster", "jpg"));
+    let media = AnimatedMedia::new(manifest.clone());
+
+    // Only a poster: show it, linking to the animation
+    assert_eq!(
+        render(&media, "![Deploy](/images/blog/post/deploy.gif)"),
+        "<p><a href=\"/images/blog/post/deploy.gif\" class=\"animated-gif\"><img src=\"/images/blog/post/deploy-poster.jpg\" alt=\"Deploy\" width=\"640\" height=\"360\" loading=\"lazy\" /></a></p>\n"
+    );
+
+    manifest.insert(variant("blog/post/deploy.webp", "animated", "webp"));
+    assert_eq!(
+        render(&AnimatedMedia::new(manifest.clone()), "![Deploy](/images/blog/post/deploy.gif)"),
+        "<p><picture><source srcset=\"/images/blog/post/deploy.webp\" type=\"image/webp\"><img src=\"/images/blog/post/deploy.gif\" alt=\"Deploy\" width=\"640\" height=\"360\" /></picture></p>\n"
+    );
+
+    manifest.insert(variant("blog/post/deploy.mp4", "animated", "mp4"));
+    assert_eq!(
+        render(&AnimatedMedia::new(manifest), "![Deploy](/images/blog/post/deploy.gif \"Title\")"),
+        "<p><video autoplay loop muted playsinline width=\"640\" height=\"360\" poster=\"/images/blog/post/deploy-poster.jpg\" aria-label=\"Deploy\"><source src=\"/images/blog/post/deploy.mp4\" type=\"video/mp4\"><img src=\"/images/blog/post/deploy.gif\" alt=\"Deploy\" /></video></p>\n"
+    );
+
+    // Images image-build didn't copy are left alone
+    let html = render(&media, "![Still](/images/blog/post/still.gif)");
+    assert_eq!(html, "<p><img src=\"/images/blog/post/still.gif\" alt=\"Still\" /></p>\n");
+}
+
+#[test]
+fn test_video_markup() {
+    let mut manifest = ImageManifest::default();
+    manifest.insert(ImageVariant {
+        width: 0,
+        height: 0,
+        ..variant("blog/post/clip.webm", "original", "webm")
+    });
+    let media = AnimatedMedia::new(manifest.clone());
+
+    assert_eq!(
+        media.markup("blog/post/clip.webm", "Walkthrough").as_deref(),
+        Some("<video controls preload=\"metadata\" aria-label=\"Walkthrough\"><source src=\"/images/blog/post/clip.webm\" type=\"video/webm\"></video>")
+    );
+    assert_eq!(media.markup("blog/post/missing.mp4", "Missing"), None);
+
+    manifest.insert(variant("blog/post/clip-poster.jpg", "poster", "jpg"));
+    assert_eq!(
+        AnimatedMedia::new(manifest).markup("blog/post/clip.webm", "Walkthrough").as_deref(),
+        Some("<video controls preload=\"none\" poster=\"/images/blog/post/clip-poster.jpg\" aria-label=\"Walkthrough\"><source src=\"/images/blog/post/clip.webm\" type=\"video/webm\"></video>")
+    );
+}
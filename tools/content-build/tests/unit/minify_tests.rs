@@ -0,0 +1,74 @@
+use content_build::minify::{minify_html, precompress_dir, to_json, Compression, MIN_PRECOMPRESS_SIZE};
+use std::io::Read;
+
+#[test]
+fn test_minify_html_collapses_whitespace_and_strips_comments() {
+    let html = "<!DOCTYPE html>\n<html>\n  <head>\n    <!-- page metadata -->\n    <title>Hello</title>\n  </head>\n  <body>\n    <p>One   <em>two</em>\n      three</p>\n  </body>\n</html>\n";
+
+    assert_eq!(
+        minify_html(html),
+        "<!DOCTYPE html> <html> <head> <title>Hello</title> </head> <body> <p>One <em>two</em> three</p> </body> </html>"
+    );
+}
+
+#[test]
+fn test_minify_html_keeps_raw_elements_and_conditional_comments() {
+    let html = "<div>\n  <pre>  indented\n    code</pre>\n  <SCRIPT>if (a  <  b) {}</SCRIPT>\n  <!--[if IE]><p>Old</p><![endif]-->\n  <p>a\u{a0}\u{a0}b</p>\n</div>";
+
+    let minified = minify_html(html);
+
+    assert!(minified.contains("<pre>  indented\n    code</pre>"));
+    assert!(minified.contains("<SCRIPT>if (a  <  b) {}</SCRIPT>"));
+    assert!(minified.contains("<!--[if IE]><p>Old</p><![endif]-->"));
+    // Non-breaking spaces are content
+    assert!(minified.contains("<p>a\u{a0}\u{a0}b</p>"));
+    // `<prefix>` is not a `<pre>` element
+    assert_eq!(minify_html("<prefix>  a  </prefix>"), "<prefix> a </prefix>");
+}
+
+#[test]
+fn test_to_json_is_compact_when_minifying() {
+    let value = serde_json::json!({ "title": "Hello", "tags": ["a", "b"] });
+
+    assert_eq!(to_json(&value, true).unwrap(), r#"{"tags":["a","b"],"title":"Hello"}"#);
+    assert!(to_json(&value, false).unwrap().contains("\n  \"tags\""));
+}
+
+#[test]
+fn test_compression_names() {
+    assert_eq!("gzip".parse::<Compression>().unwrap(), Compression::Gzip);
+    assert_eq!("BR".parse::<Compression>().unwrap(), Compression::Brotli);
+    assert!("zstd".parse::<Compression>().is_err());
+}
+
+#[test]
+fn test_precompress_dir_writes_copies_of_large_text_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let html_dir = temp_dir.path().join("html");
+    std::fs::create_dir_all(&html_dir).unwrap();
+
+    let large = "<p>Some repeated text.</p>\n".repeat(100);
+    assert!(large.len() as u64 >= MIN_PRECOMPRESS_SIZE);
+    std::fs::write(html_dir.join("large.html"), &large).unwrap();
+    std::fs::write(html_dir.join("small.html"), "<p>Small</p>").unwrap();
+    std::fs::write(temp_dir.path().join("image.png"), vec![0u8; 4096]).unwrap();
+
+    let mut written = precompress_dir(temp_dir.path(), &[Compression::Gzip, Compression::Brotli]).unwrap();
+    written.sort();
+
+    assert_eq!(written, vec![html_dir.join("large.html.br"), html_dir.join("large.html.gz")]);
+
+    let mut decoded = String::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(html_dir.join("large.html.gz")).unwrap())
+        .read_to_string(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, large);
+
+    let mut decoded = String::new();
+    brotli::Decompressor::new(std::fs::File::open(html_dir.join("large.html.br")).unwrap(), 4096)
+        .read_to_string(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, large);
+
+    assert!(precompress_dir(temp_dir.path(), &[]).unwrap().is_empty());
+}
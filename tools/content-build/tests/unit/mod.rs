@@ -4,6 +4,13 @@ mod process_content_tests;
 mod find_content_tests;
 mod build_content_tests;
 mod generate_output_tests;
+mod series_tests;
+mod shortcode_tests;
+mod minify_tests;
+mod preview_tests;
+mod cdn_tests;
+mod hero_tests;
+mod media_tests;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,127 @@
+use common_models::{Article, Frontmatter};
+use content_build::preview::{inject_live_reload, PreviewSite, Route};
+use content_build::ChangeWatcher;
+use std::fs;
+use std::path::Path;
+
+fn article(topic: &str, slug: &str, draft: bool) -> Article {
+    Article {
+        frontmatter: Frontmatter {
+            title: format!("Title of {}", slug),
+            is_draft: Some(draft),
+            ..Default::default()
+        },
+        content: String::new(),
+        slug: slug.to_string(),
+        topic: topic.to_string(),
+        path: String::new(),
+        word_count: None,
+        reading_time: None,
+        series_navigation: None,
+        html: Some(format!("<p>Body of {}</p>", slug)),
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
+    }
+}
+
+fn write_tree(dir: &Path, articles: &[Article]) {
+    fs::create_dir_all(dir.join("data")).unwrap();
+    fs::write(dir.join("data").join("all.json"), serde_json::to_string(articles).unwrap()).unwrap();
+}
+
+fn preview_site() -> (tempfile::TempDir, PreviewSite) {
+    let output = tempfile::tempdir().unwrap();
+    write_tree(
+        output.path(),
+        &[article("blog", "hello", false), article("blog", "upcoming", true), article("notes", "tip", false)],
+    );
+    write_tree(&output.path().join("de"), &[article("blog", "hallo", false)]);
+    fs::create_dir_all(output.path().join("html")).unwrap();
+    fs::write(output.path().join("html").join("hello.html"), "<html><body>Rendered</body></html>").unwrap();
+    fs::write(output.path().join("html").join("upcoming.html"), "<html><body>Draft</body></html>").unwrap();
+    fs::write(output.path().join("rss.xml"), "<rss/>").unwrap();
+
+    let site = PreviewSite::load(output.path()).unwrap();
+    (output, site)
+}
+
+fn article_route(prefix: &str, topic: &str, slug: &str) -> Route {
+    Route::Article { prefix: prefix.to_string(), topic: topic.to_string(), slug: slug.to_string() }
+}
+
+#[test]
+fn test_preview_routes_public_urls_to_output() {
+    let (output, site) = preview_site();
+
+    assert_eq!(site.resolve("/blog/hello"), article_route("", "blog", "hello"));
+    assert_eq!(site.resolve("/blog/hello/"), article_route("", "blog", "hello"));
+    assert_eq!(site.resolve("/rss.xml"), Route::File(output.path().join("rss.xml")));
+    assert_eq!(site.resolve("/"), Route::SiteIndex { prefix: String::new() });
+    assert_eq!(site.resolve("/notes/"), Route::TopicIndex { prefix: String::new(), topic: "notes".to_string() });
+    assert_eq!(site.resolve("/de/blog/hallo"), article_route("/de", "blog", "hallo"));
+    assert_eq!(site.resolve("/missing/"), Route::NotFound);
+    assert_eq!(site.resolve("/../secret"), Route::NotFound);
+}
+
+#[test]
+fn test_preview_serves_drafts_only_under_drafts() {
+    let (output, site) = preview_site();
+
+    assert_eq!(site.resolve("/drafts/blog/upcoming"), article_route("", "blog", "upcoming"));
+    assert_eq!(site.resolve("/blog/upcoming"), Route::NotFound);
+    assert_eq!(site.resolve("/drafts/blog/hello"), Route::NotFound);
+    assert_eq!(site.resolve("/html/upcoming.html"), Route::NotFound);
+    assert_eq!(site.resolve("/html/hello.html"), Route::File(output.path().join("html").join("hello.html")));
+
+    let index = site.topic_index_html("", "blog").unwrap();
+    assert!(index.contains("<a href=\"/blog/hello\">Title of hello</a>"));
+    assert!(index.contains("<a href=\"/drafts/blog/upcoming\">Title of upcoming</a> <small>(draft)</small>"));
+}
+
+#[test]
+fn test_preview_article_html_falls_back_without_template() {
+    let (_output, site) = preview_site();
+
+    assert_eq!(site.article_html("", "blog", "hello").unwrap(), "<html><body>Rendered</body></html>");
+    let fallback = site.article_html("", "notes", "tip").unwrap();
+    assert!(fallback.contains("<h1>Title of tip</h1>"));
+    assert!(fallback.contains("<p>Body of tip</p>"));
+
+    let index = site.site_index_html("").unwrap();
+    assert!(index.contains("<a href=\"/blog/\">blog</a>"));
+    assert!(index.contains("<a href=\"/notes/tip\">"));
+}
+
+#[test]
+fn test_inject_live_reload() {
+    let html = inject_live_reload("<html><BODY><p>Hi</p></BODY></html>");
+    assert!(html.starts_with("<html><BODY><p>Hi</p><script>"));
+    assert!(html.ends_with("</script></BODY></html>"));
+    assert!(html.contains("/__livereload"));
+
+    assert!(inject_live_reload("<p>Fragment</p>").starts_with("<p>Fragment</p><script>"));
+}
+
+#[test]
+fn test_change_watcher_detects_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("post.md");
+    fs::write(&file, "one").unwrap();
+
+    let mut watcher = ChangeWatcher::new(vec![dir.path().to_path_buf(), dir.path().join("missing")]);
+    assert!(!watcher.poll());
+
+    fs::write(&file, "one two").unwrap();
+    assert!(watcher.poll());
+    assert!(!watcher.poll());
+
+    fs::write(dir.path().join("new.md"), "new").unwrap();
+    assert!(watcher.poll());
+    fs::remove_file(&file).unwrap();
+    assert!(watcher.poll());
+}
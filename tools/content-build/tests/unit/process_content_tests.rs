@@ -351,4 +351,185 @@ This article has invalid frontmatter."#.to_string()));
     assert!(result.is_err());
     let err = result.unwrap_err();
     assert!(err.to_string().contains("Failed to parse frontmatter"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_process_content_skips_scheduled_content() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let article_dir = temp_dir.path().join("blog").join("scheduled-article");
+    std::fs::create_dir_all(&article_dir).unwrap();
+    std::fs::write(article_dir.join("index.mdx"), r#"---
+title: "Scheduled Article"
+publish_at: "2999-01-01T09:00:00Z"
+---
+# Scheduled Article
+
+Coming soon."#).unwrap();
+
+    let result = process_content(&article_dir, false);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("scheduled"));
+
+    let article = process_content(&article_dir, true).unwrap();
+    assert_eq!(article.frontmatter.publish_at.as_deref(), Some("2999-01-01T09:00:00Z"));
+}
+
+#[test]
+fn test_process_content_with_slug_named_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let article_dir = temp_dir.path().join("blog").join("hello");
+    std::fs::create_dir_all(&article_dir).unwrap();
+    std::fs::write(
+        article_dir.join("hello.md"),
+        "---\ntitle: \"Hello\"\npublished_at: \"2023-01-01\"\n---\n# Hello\n\nFrom a slug-named file.\n",
+    )
+    .unwrap();
+
+    let article = process_content(&article_dir, false).unwrap();
+
+    assert_eq!(article.slug, "hello");
+    assert_eq!(article.topic, "blog");
+    assert!(article.content.contains("From a slug-named file."));
+}
+
+#[test]
+fn test_process_localized_reads_each_translation() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let article_dir = temp_dir.path().join("blog").join("hello");
+    std::fs::create_dir_all(&article_dir).unwrap();
+    std::fs::write(article_dir.join("hello.md"), "---\ntitle: \"Hello\"\n---\nHello there.\n").unwrap();
+    std::fs::write(article_dir.join("hello.de.md"), "---\ntitle: \"Hallo\"\n---\nHallo zusammen.\n").unwrap();
+    std::fs::write(article_dir.join("hello.fr.md"), "---\ntitle: \"Bonjour\"\n---\nBonjour.\n").unwrap();
+
+    let site = content_build::SiteContext {
+        i18n: common_models::I18nConfig {
+            default_locale: "en".to_string(),
+            locales: vec!["en".to_string(), "de".to_string()],
+        },
+        ..Default::default()
+    };
+
+    let versions = content_build::process_localized(&article_dir, true, &site);
+    let articles: Vec<_> = versions.into_iter().map(|(_, article)| article.unwrap()).collect();
+
+    // French isn't a configured locale, so its file is ignored
+    assert_eq!(articles.len(), 2);
+    assert_eq!((articles[0].lang.as_deref(), articles[0].frontmatter.title.as_str()), (Some("en"), "Hello"));
+    assert_eq!((articles[1].lang.as_deref(), articles[1].frontmatter.title.as_str()), (Some("de"), "Hallo"));
+    assert!(articles.iter().all(|article| article.slug == "hello" && article.topic == "blog"));
+
+    // A main file in a language that isn't configured is an error
+    std::fs::write(article_dir.join("hello.md"), "---\ntitle: \"Hello\"\nlang: it\n---\nCiao.\n").unwrap();
+    let versions = content_build::process_localized(&article_dir, true, &site);
+    assert!(versions[0].1.as_ref().unwrap_err().to_string().contains("Unknown locale 'it'"));
+
+    // Without configured locales, only the main file is read
+    let versions = content_build::process_localized(&article_dir, true, &content_build::SiteContext::default());
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].1.as_ref().unwrap().lang, None);
+}
+
+#[test]
+fn test_process_localized_applies_typography_in_each_language() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let article_dir = temp_dir.path().join("blog").join("quotes");
+    std::fs::create_dir_all(&article_dir).unwrap();
+    std::fs::write(article_dir.join("quotes.md"), "---\ntitle: \"Quotes\"\n---\nShe said \"hi\" -- twice.\n").unwrap();
+    std::fs::write(article_dir.join("quotes.de.md"), "---\ntitle: \"Zitate\"\n---\nSie sagte \"hallo\".\n").unwrap();
+
+    let site = content_build::SiteContext {
+        i18n: common_models::I18nConfig {
+            default_locale: "en".to_string(),
+            locales: vec!["en".to_string(), "de".to_string()],
+        },
+        typography: common_models::TypographyConfig { enabled: true },
+        ..Default::default()
+    };
+
+    let articles: Vec<_> = content_build::process_localized(&article_dir, true, &site)
+        .into_iter()
+        .map(|(_, article)| article.unwrap())
+        .collect();
+    assert_eq!(articles[0].html.as_deref(), Some("<p>She said “hi” – twice.</p>\n"));
+    assert_eq!(articles[1].html.as_deref(), Some("<p>Sie sagte „hallo“.</p>\n"));
+
+    // The frontmatter can turn it off for one article
+    std::fs::write(
+        article_dir.join("quotes.md"),
+        "---\ntitle: \"Quotes\"\ntypography: false\n---\nShe said \"hi\" -- twice.\n",
+    )
+    .unwrap();
+    let versions = content_build::process_localized(&article_dir, true, &site);
+    assert_eq!(versions[0].1.as_ref().unwrap().html.as_deref(), Some("<p>She said &quot;hi&quot; -- twice.</p>\n"));
+}
+
+#[test]
+fn test_profiler_times_each_phase_of_processing() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let blog_dir = temp_dir.path().join("blog");
+    for slug in ["first", "second"] {
+        std::fs::create_dir_all(blog_dir.join(slug)).unwrap();
+        std::fs::write(blog_dir.join(slug).join(format!("{}.md", slug)), "---\ntitle: \"Timed\"\n---\nSome *text*.\n").unwrap();
+    }
+
+    let site = content_build::SiteContext {
+        profiler: content_build::Profiler::enabled(),
+        ..Default::default()
+    };
+    for slug in ["first", "second"] {
+        content_build::process_content_for_site(&blog_dir.join(slug), true, &site).unwrap();
+    }
+
+    let profile = site.profiler.profile();
+    assert_eq!(profile.articles.len(), 2);
+    assert!(profile.articles[0].total >= profile.articles[1].total);
+    assert_eq!(profile.total, profile.articles[0].total + profile.articles[1].total);
+    assert_eq!(profile.slowest(1).len(), 1);
+    assert_eq!(profile.slowest(10).len(), 2);
+
+    let article = &profile.articles[0];
+    assert_eq!(article.total, article.read + article.parse + article.markdown);
+    assert!(article.path.ends_with(".md"));
+
+    let json: serde_json::Value = serde_json::to_value(&profile).unwrap();
+    assert!(json["articles"][0]["markdown_ms"].is_f64());
+    assert!(json["total_ms"].is_f64());
+
+    // Without profiling, nothing is recorded
+    let site = content_build::SiteContext::default();
+    content_build::process_content_for_site(&blog_dir.join("first"), true, &site).unwrap();
+    assert!(site.profiler.profile().articles.is_empty());
+}
+
+#[test]
+fn test_process_content_file_with_mock_filesystem() {
+    let article_file = PathBuf::from("content/blog/mocked-article/mocked-article.md");
+    let mut mock_fs = MockFileSystem::new();
+
+    mock_fs.expect_dir_exists()
+        .with(predicate::eq(article_file.clone()))
+        .returning(|_| Ok(false));
+    mock_fs.expect_file_exists()
+        .with(predicate::eq(article_file.clone()))
+        .returning(|_| Ok(true));
+    mock_fs.expect_read_file()
+        .with(predicate::eq(article_file.clone()))
+        .times(1)
+        .returning(|_| Ok("---\ntitle: \"Mocked Article\"\npublished_at: \"2023-01-01\"\n---\n\n# Mocked Article\n".to_string()));
+
+    let site = content_build::SiteContext::default();
+    let article = content_build::process_content_file_with_fs(&mock_fs, &article_file, false, &site).unwrap();
+    assert_eq!(article.slug, "mocked-article");
+    assert_eq!(article.topic, "blog");
+    assert_eq!(article.frontmatter.title, "Mocked Article");
+    assert!(article.content.contains("Mocked Article"));
+
+    // Missing files are reported without being read
+    let mut mock_fs = MockFileSystem::new();
+    mock_fs.expect_dir_exists().returning(|_| Ok(false));
+    mock_fs.expect_file_exists().returning(|_| Ok(false));
+    mock_fs.expect_read_file().times(0);
+
+    let err = content_build::process_content_file_with_fs(&mock_fs, &article_file, false, &site).unwrap_err();
+    assert!(err.to_string().contains("File not found"));
+}
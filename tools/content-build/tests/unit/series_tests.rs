@@ -0,0 +1,67 @@
+use common_models::{Article, Frontmatter};
+use content_build::link_series;
+
+fn article(slug: &str, series: Option<&str>, part: Option<u32>) -> Article {
+    Article {
+        frontmatter: Frontmatter {
+            title: format!("Title {}", slug),
+            series: series.map(|s| s.to_string()),
+            series_part: part,
+            ..Frontmatter::default()
+        },
+        content: String::new(),
+        slug: slug.to_string(),
+        topic: "blog".to_string(),
+        path: String::new(),
+        word_count: None,
+        reading_time: None,
+        series_navigation: None,
+        html: None,
+        summary: None,
+        git: None,
+        lang: None,
+        alternates: Vec::new(),
+        links: Vec::new(),
+        hero_image: None,
+        social_image: None,
+    }
+}
+
+#[test]
+fn test_link_series_orders_entries_and_sets_navigation() {
+    let mut articles = vec![
+        article("third", Some("rust"), Some(3)),
+        article("first", Some("rust"), Some(1)),
+        article("other", None, None),
+        article("second", Some("rust"), Some(2)),
+    ];
+
+    let indexes = link_series(&mut articles);
+
+    assert_eq!(indexes.len(), 1);
+    assert_eq!(indexes[0].name, "rust");
+    let slugs: Vec<&str> = indexes[0].entries.iter().map(|e| e.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["first", "second", "third"]);
+
+    let first = articles[1].series_navigation.as_ref().unwrap();
+    assert_eq!(first.part, 1);
+    assert_eq!(first.total, 3);
+    assert!(first.prev.is_none());
+    assert_eq!(first.next.as_ref().unwrap().slug, "second");
+
+    let second = articles[3].series_navigation.as_ref().unwrap();
+    assert_eq!(second.prev.as_ref().unwrap().slug, "first");
+    assert_eq!(second.next.as_ref().unwrap().title, "Title third");
+
+    let third = articles[0].series_navigation.as_ref().unwrap();
+    assert!(third.next.is_none());
+
+    assert!(articles[2].series_navigation.is_none());
+}
+
+#[test]
+fn test_link_series_without_series_returns_no_indexes() {
+    let mut articles = vec![article("solo", None, None)];
+    assert!(link_series(&mut articles).is_empty());
+    assert!(articles[0].series_navigation.is_none());
+}
@@ -0,0 +1,44 @@
+use content_build::ShortcodeRegistry;
+
+#[test]
+fn test_registered_components_render_with_partials() {
+    let mut registry = ShortcodeRegistry::new();
+    registry
+        .register_partial("YouTube", "<iframe src=\"https://www.youtube.com/embed/{{id}}\"></iframe>")
+        .unwrap();
+    registry
+        .register_partial("Aside", "<aside class=\"{{kind}}\">{{{children}}}</aside>")
+        .unwrap();
+
+    let expanded = registry
+        .expand("Watch:\n\n<YouTube id=\"abc\" />\n\n<Aside kind=\"tip\">Use *tabs*</Aside>\n")
+        .unwrap();
+
+    assert!(expanded.contains("<iframe src=\"https://www.youtube.com/embed/abc\"></iframe>"));
+    assert!(expanded.contains("<aside class=\"tip\"><p>Use <em>tabs</em></p>\n</aside>"));
+    assert!(registry.is_registered("YouTube"));
+}
+
+#[test]
+fn test_stripped_and_unknown_components_keep_their_children() {
+    let mut registry = ShortcodeRegistry::new();
+    registry.register_strip("Wrapper");
+
+    let expanded = registry
+        .expand("<Wrapper>kept <Unknown flag /></Wrapper> and <Tweet id=\"1\" />.")
+        .unwrap();
+
+    assert_eq!(expanded, "kept  and .");
+    assert!(!registry.is_registered("Tweet"));
+}
+
+#[test]
+fn test_partials_can_use_template_helpers() {
+    let mut registry = ShortcodeRegistry::new();
+    registry
+        .register_partial("Badge", "<span>{{upper label}} {{default note \"-\"}}</span>")
+        .unwrap();
+
+    let expanded = registry.expand("<Badge label=\"new\" />\n").unwrap();
+    assert!(expanded.contains("<span>NEW -</span>"));
+}
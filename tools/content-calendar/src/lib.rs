@@ -0,0 +1,256 @@
+//! # Content Calendar
+//!
+//! Lays out every dated article on a month-by-month calendar for editorial
+//! planning. Articles are placed by their `published_at` date, or by their
+//! `publish_at` time when they are scheduled for later. Months between the
+//! first and last article that have nothing published or scheduled are gaps.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use common_fs::normalize::join_paths;
+use common_models::{Config, Frontmatter, PublishDate};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// Whether an article on the calendar is out or still to come
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryStatus {
+    Published,
+    Scheduled,
+}
+
+/// An article on the calendar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CalendarEntry {
+    pub topic: String,
+    pub slug: String,
+    pub title: String,
+    pub date: NaiveDate,
+    pub status: EntryStatus,
+}
+
+impl CalendarEntry {
+    /// Place an article on the calendar as of `now`
+    ///
+    /// Returns `None` for undated articles, and for drafts that aren't dated
+    /// in the future.
+    pub fn from_frontmatter(
+        topic: &str,
+        slug: &str,
+        frontmatter: &Frontmatter,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let (date, status) = if frontmatter.is_scheduled_after(now) {
+            let publish_at = frontmatter.publish_at_datetime()?.ok()?;
+            (publish_at.date_naive(), EntryStatus::Scheduled)
+        } else {
+            let date = frontmatter.published_at.as_ref().and_then(PublishDate::date)?;
+
+            // A draft only has a place on the calendar once it has a date to go out
+            let status = match (frontmatter.is_draft.unwrap_or(false), date > now.date_naive()) {
+                (false, false) => EntryStatus::Published,
+                (true, false) => return None,
+                (_, true) => EntryStatus::Scheduled,
+            };
+            (date, status)
+        };
+
+        Some(Self {
+            topic: topic.to_string(),
+            slug: slug.to_string(),
+            title: frontmatter.title.clone(),
+            date,
+            status,
+        })
+    }
+}
+
+/// One month of the calendar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CalendarMonth {
+    /// Month label, e.g. `2024-03`
+    pub month: String,
+    pub published: usize,
+    pub scheduled: usize,
+    /// Whether nothing was published or scheduled this month
+    pub gap: bool,
+    /// Articles in the month, by date
+    pub entries: Vec<CalendarEntry>,
+}
+
+/// Articles laid out month by month
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Calendar {
+    pub months: Vec<CalendarMonth>,
+}
+
+impl Calendar {
+    /// Lay out entries on a calendar running from the first month to the last
+    pub fn from_entries(mut entries: Vec<CalendarEntry>) -> Self {
+        entries.sort_by(|a, b| (a.date, &a.topic, &a.slug).cmp(&(b.date, &b.topic, &b.slug)));
+
+        let (first, last) = match (entries.first(), entries.last()) {
+            (Some(first), Some(last)) => (month_start(first.date), month_start(last.date)),
+            _ => return Self::default(),
+        };
+
+        let mut months = Vec::new();
+        let mut month = first;
+        let mut remaining = entries.into_iter().peekable();
+        while month <= last {
+            let mut in_month = Vec::new();
+            while let Some(entry) = remaining.next_if(|entry| month_start(entry.date) == month) {
+                in_month.push(entry);
+            }
+
+            let published = in_month.iter().filter(|entry| entry.status == EntryStatus::Published).count();
+            months.push(CalendarMonth {
+                month: month.format("%Y-%m").to_string(),
+                published,
+                scheduled: in_month.len() - published,
+                gap: in_month.is_empty(),
+                entries: in_month,
+            });
+            month = next_month(month);
+        }
+
+        Self { months }
+    }
+
+    /// Months without anything published or scheduled
+    pub fn gaps(&self) -> Vec<&str> {
+        self.months
+            .iter()
+            .filter(|month| month.gap)
+            .map(|month| month.month.as_str())
+            .collect()
+    }
+
+    /// Render the calendar as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize content calendar")
+    }
+
+    /// Render the calendar as a Markdown table, one row per month
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        let _ = writeln!(output, "| Month | Published | Scheduled | Articles |");
+        let _ = writeln!(output, "| --- | ---: | ---: | --- |");
+
+        for month in &self.months {
+            let articles = if month.gap {
+                "*Gap*".to_string()
+            } else {
+                month
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        let marker = match entry.status {
+                            EntryStatus::Published => "",
+                            EntryStatus::Scheduled => " (scheduled)",
+                        };
+                        format!("{} {}/{}{}", entry.date.format("%d"), entry.topic, entry.slug, marker)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("<br>")
+            };
+
+            let _ = writeln!(
+                output,
+                "| {} | {} | {} | {} |",
+                month.month, month.published, month.scheduled, articles
+            );
+        }
+
+        output
+    }
+}
+
+/// Options for building a calendar
+#[derive(Debug, Clone, Default)]
+pub struct CalendarOptions {
+    /// Only include articles in this topic
+    pub topic: Option<String>,
+    /// Include archived articles
+    pub include_archived: bool,
+}
+
+/// Collect the calendar entries for the articles in a configuration
+///
+/// # Errors
+///
+/// Returns an error if the topic doesn't exist or a content file can't be read
+pub fn collect_entries(
+    config: &Config,
+    options: &CalendarOptions,
+    now: DateTime<Utc>,
+) -> Result<Vec<CalendarEntry>> {
+    if let Some(topic) = &options.topic {
+        if !config.content.topics.contains_key(topic) {
+            return Err(anyhow::anyhow!("Topic not found: {}", topic));
+        }
+    }
+
+    let base_dir = PathBuf::from(&config.content.base_dir);
+    let mut entries = Vec::new();
+
+    for (topic_key, topic_config) in &config.content.topics {
+        if options.topic.as_ref().is_some_and(|topic| topic != topic_key) {
+            continue;
+        }
+
+        let topic_dir = join_paths(&base_dir, &topic_config.directory);
+        if !topic_dir.is_dir() {
+            continue;
+        }
+
+        for file_path in common_fs::find_article_files(&topic_dir)? {
+            let slug = match file_path.parent().and_then(|dir| dir.file_name()).and_then(|name| name.to_str()) {
+                Some(slug) => slug.to_string(),
+                None => continue,
+            };
+
+            let content = common_fs::read_file(&file_path)?;
+            let frontmatter = match common_markdown::extract_frontmatter_and_content(&content) {
+                Ok((frontmatter, _)) => frontmatter,
+                Err(_) => continue,
+            };
+
+            if frontmatter.is_archived() && !options.include_archived {
+                continue;
+            }
+
+            entries.extend(CalendarEntry::from_frontmatter(topic_key, &slug, &frontmatter, now));
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Build the calendar for the current configuration
+///
+/// # Errors
+///
+/// Returns an error if the configuration can't be loaded or the content can't be read
+pub fn generate_calendar(options: &CalendarOptions) -> Result<Calendar> {
+    let config = common_config::load_config()?;
+    let entries = collect_entries(&config, options, Utc::now())?;
+    Ok(Calendar::from_entries(entries))
+}
+
+/// First day of the month containing `date`
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+/// First day of the month after the one starting at `month`
+fn next_month(month: NaiveDate) -> NaiveDate {
+    if month.month() == 12 {
+        NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)
+    }
+    .expect("the first of a month is a valid date")
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::*;
+use content_calendar::{generate_calendar, CalendarOptions};
+
+#[derive(Parser)]
+#[command(author, version, about = "Show a month-by-month calendar of published and scheduled content")]
+struct Args {
+    /// Only include content in this topic
+    #[arg(short, long)]
+    topic: Option<String>,
+
+    /// Include archived content
+    #[arg(long)]
+    include_archived: bool,
+
+    /// Print the calendar as JSON instead of a Markdown table
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let options = CalendarOptions {
+        topic: args.topic,
+        include_archived: args.include_archived,
+    };
+
+    let calendar = generate_calendar(&options)?;
+
+    if args.json {
+        println!("{}", calendar.to_json()?);
+        return Ok(());
+    }
+
+    if calendar.months.is_empty() {
+        println!("No dated content found.");
+        return Ok(());
+    }
+
+    print!("{}", calendar.to_markdown());
+
+    let gaps = calendar.gaps();
+    if !gaps.is_empty() {
+        println!("\n{} {}", "Gaps:".yellow().bold(), gaps.join(", "));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+//! Test modules for content-calendar
+
+// Unit tests
+#[path = "unit/calendar_tests.rs"]
+mod calendar_tests;
@@ -0,0 +1,127 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use common_models::{Config, Frontmatter, TopicConfig};
+use content_calendar::{collect_entries, Calendar, CalendarEntry, CalendarOptions, EntryStatus};
+use std::fs;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod calendar_tests {
+    use super::*;
+
+    fn frontmatter(published_at: Option<&str>, is_draft: bool, publish_at: Option<&str>) -> Frontmatter {
+        Frontmatter {
+            title: "Title".to_string(),
+            published_at: published_at.map(|date| date.parse().unwrap()),
+            is_draft: Some(is_draft),
+            publish_at: publish_at.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn entry(slug: &str, date: &str, status: EntryStatus) -> CalendarEntry {
+        CalendarEntry {
+            topic: "blog".to_string(),
+            slug: slug.to_string(),
+            title: slug.to_string(),
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_entry_status_from_frontmatter() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let place = |frontmatter: Frontmatter| {
+            CalendarEntry::from_frontmatter("blog", "post", &frontmatter, now)
+                .map(|entry| (entry.date.to_string(), entry.status))
+        };
+
+        assert_eq!(
+            place(frontmatter(Some("2024-03-01"), false, None)),
+            Some(("2024-03-01".to_string(), EntryStatus::Published))
+        );
+        assert_eq!(
+            place(frontmatter(Some("2024-04-01"), true, None)),
+            Some(("2024-04-01".to_string(), EntryStatus::Scheduled))
+        );
+        assert_eq!(
+            place(frontmatter(Some("2024-03-01"), false, Some("2024-05-02T09:00:00Z"))),
+            Some(("2024-05-02".to_string(), EntryStatus::Scheduled))
+        );
+        assert_eq!(place(frontmatter(Some("2024-03-01"), true, None)), None);
+        assert_eq!(place(frontmatter(None, false, None)), None);
+    }
+
+    #[test]
+    fn test_calendar_fills_gaps_between_months() {
+        let calendar = Calendar::from_entries(vec![
+            entry("later", "2024-04-10", EntryStatus::Scheduled),
+            entry("first", "2024-01-05", EntryStatus::Published),
+            entry("second", "2024-01-20", EntryStatus::Published),
+        ]);
+
+        let months: Vec<(&str, usize, usize)> = calendar
+            .months
+            .iter()
+            .map(|month| (month.month.as_str(), month.published, month.scheduled))
+            .collect();
+        assert_eq!(months, vec![
+            ("2024-01", 2, 0),
+            ("2024-02", 0, 0),
+            ("2024-03", 0, 0),
+            ("2024-04", 0, 1),
+        ]);
+        assert_eq!(calendar.gaps(), vec!["2024-02", "2024-03"]);
+        assert_eq!(calendar.months[0].entries[0].slug, "first");
+
+        let markdown = calendar.to_markdown();
+        assert!(markdown.contains("| 2024-01 | 2 | 0 | 05 blog/first<br>20 blog/second |"));
+        assert!(markdown.contains("| 2024-02 | 0 | 0 | *Gap* |"));
+        assert!(markdown.contains("| 2024-04 | 0 | 1 | 10 blog/later (scheduled) |"));
+
+        let json: serde_json::Value = serde_json::from_str(&calendar.to_json().unwrap()).unwrap();
+        assert_eq!(json["months"][3]["entries"][0]["status"], "scheduled");
+        assert_eq!(json["months"][1]["gap"], true);
+
+        assert!(Calendar::from_entries(Vec::new()).months.is_empty());
+    }
+
+    #[test]
+    fn test_collect_entries_filters_by_topic() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.content.base_dir = temp_dir.path().to_string_lossy().into_owned();
+        config.content.topics.clear();
+        for topic in ["blog", "notes"] {
+            config.content.topics.insert(topic.to_string(), TopicConfig {
+                name: topic.to_string(),
+                description: String::new(),
+                directory: topic.to_string(),
+            });
+            let article_dir = temp_dir.path().join(topic).join("post");
+            fs::create_dir_all(&article_dir).unwrap();
+            fs::write(
+                article_dir.join("post.md"),
+                "---\ntitle: Post\npublished: 2024-01-02\n---\nBody\n",
+            )
+            .unwrap();
+        }
+
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let options = CalendarOptions {
+            topic: Some("notes".to_string()),
+            ..Default::default()
+        };
+        let entries = collect_entries(&config, &options, now).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].topic, "notes");
+        assert_eq!(entries[0].status, EntryStatus::Published);
+
+        let options = CalendarOptions {
+            topic: Some("missing".to_string()),
+            ..Default::default()
+        };
+        assert!(collect_entries(&config, &options, now).is_err());
+    }
+}
@@ -0,0 +1,437 @@
+//! # Content Daemon
+//!
+//! A long-running process that answers JSON-RPC 2.0 requests for the core
+//! writing tools, so editor plugins can validate, search and manage content
+//! without starting a new process for every keystroke. The configuration
+//! stays loaded and article content stays in memory between requests, read
+//! again only when a file changes.
+//!
+//! Requests are newline-delimited JSON, over a Unix socket that only its
+//! owner can connect to, or standard input and output. Paths sent by
+//! clients must be inside the content directory. The methods are:
+//!
+//! | Method     | Params                                                        | Result |
+//! |------------|---------------------------------------------------------------|--------|
+//! | `ping`     |                                                               | `"pong"` |
+//! | `validate` | `path`, optional `text` (unsaved buffer), `types`, `check_external_links` | issues |
+//! | `search`   | `query`, optional `topic`, `tags`, `limit`, `include_drafts`  | results |
+//! | `stats`    | optional `topic`, `slug`, `include_drafts`                     | totals and articles |
+//! | `create`   | `title`, optional `topic`, `slug` (from the title by default), `description`, `tags`, `template`, `draft` | created path |
+//! | `move`     | `slug`, optional `topic`, `new_slug`, `new_topic`, `dry_run`  | move plan |
+//! | `reload`   |                                                               | reloads configuration and drops cached content |
+//! | `shutdown` |                                                               | stops the daemon |
+
+use common_fs::CachedContentRepository;
+use common_traits::ContentRepository;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{PoisonError, RwLock};
+
+pub mod protocol;
+
+use protocol::{Request, Response, RpcError, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR};
+
+/// Default socket, relative to the project root
+pub const DEFAULT_SOCKET: &str = ".writing.sock";
+
+/// Shared state of a running daemon
+#[derive(Debug)]
+pub struct Daemon {
+    repository: RwLock<CachedContentRepository>,
+    shutdown: AtomicBool,
+}
+
+impl Daemon {
+    /// Load the configuration and prepare to answer requests
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration cannot be loaded
+    pub fn new() -> anyhow::Result<Self> {
+        let config = common_config::load_config()?;
+        Ok(Self {
+            repository: RwLock::new(CachedContentRepository::new(&config)),
+            shutdown: AtomicBool::new(false),
+        })
+    }
+
+    /// Read every article into memory, returning how many were read
+    ///
+    /// Articles that cannot be read are skipped; requests that need them
+    /// report the problem.
+    pub fn warm(&self) -> usize {
+        let repository = self.repository.read().unwrap_or_else(PoisonError::into_inner);
+        let topics = repository.list_topics().unwrap_or_default();
+        topics
+            .iter()
+            .flat_map(|topic| repository.list_articles(topic).unwrap_or_default())
+            .filter(|article| repository.read_article(article).is_ok())
+            .count()
+    }
+
+    /// Check whether a client has asked the daemon to stop
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.load(Ordering::SeqCst)
+    }
+
+    /// Answer one line of input, returning the line to send back, if any
+    pub fn handle_line(&self, line: &str) -> Option<String> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(err) => {
+                let response = Response::failure(Value::Null, RpcError::new(PARSE_ERROR, format!("Parse error: {}", err)));
+                return Some(to_line(&response));
+            }
+        };
+
+        match message {
+            Value::Array(batch) if !batch.is_empty() => {
+                let responses: Vec<Response> = batch.into_iter().filter_map(|message| self.handle_message(message)).collect();
+                (!responses.is_empty()).then(|| to_line(&responses))
+            }
+            message => self.handle_message(message).map(|response| to_line(&response)),
+        }
+    }
+
+    /// Answer one request, or nothing for a notification
+    fn handle_message(&self, message: Value) -> Option<Response> {
+        let request = match serde_json::from_value::<Request>(message) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            _ => return Some(Response::failure(Value::Null, RpcError::new(INVALID_REQUEST, "Invalid request"))),
+        };
+
+        let result = self.call(&request.method, request.params);
+        let id = request.id?;
+        Some(match result {
+            Ok(result) => Response::success(id, result),
+            Err(error) => Response::failure(id, error),
+        })
+    }
+
+    /// Run a method
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method is unknown, its parameters are
+    /// invalid, or the tool behind it fails
+    pub fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "ping" => Ok(json!("pong")),
+            "validate" => self.validate(parse_params(params)?),
+            "search" => self.search(parse_params(params)?),
+            "stats" => stats(parse_params(params)?),
+            "create" => create(parse_params(params)?),
+            "move" => move_content(parse_params(params)?),
+            "reload" => self.reload(),
+            "shutdown" => {
+                self.shutdown.store(true, Ordering::SeqCst);
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        }
+    }
+
+    /// Answer requests read from `reader` until it closes or the daemon shuts down
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or writing fails
+    pub fn serve_connection(&self, reader: impl BufRead, mut writer: impl Write) -> std::io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_line(&line) {
+                writeln!(writer, "{}", response)?;
+                writer.flush()?;
+            }
+            if self.is_shutting_down() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self, params: ValidateParams) -> Result<Value, RpcError> {
+        ensure_content_path(&params.path)?;
+        let text = match params.text {
+            Some(text) => text,
+            None => std::fs::read_to_string(&params.path)
+                .map_err(|err| RpcError::tool(anyhow::anyhow!("Failed to read {}: {}", params.path.display(), err)))?,
+        };
+
+        let validation_types = match params.types {
+            Some(types) => types.iter().map(|name| validation_type(name)).collect::<Result<Vec<_>, _>>()?,
+            None => vec![content_validate::ValidationType::All],
+        };
+        let options = content_validate::ValidationOptions {
+            article_slug: None,
+            topic: None,
+            validation_types,
+            check_external_links: params.check_external_links,
+            timeout: None,
+            dictionary_path: None,
+            include_drafts: true,
+            images_dir: None,
+            changed_since: None,
+            archive_fallback: Default::default(),
+        };
+
+        let issues = content_validate::validate_text(&params.path, &text, &options).map_err(RpcError::tool)?;
+        Ok(json!({ "path": params.path, "issues": issues }))
+    }
+
+    fn search(&self, params: SearchParams) -> Result<Value, RpcError> {
+        let options = content_search::SearchOptions {
+            query: params.query,
+            topic: params.topic,
+            tags: params.tags,
+            limit: params.limit.unwrap_or(20),
+            include_drafts: params.include_drafts,
+            ..Default::default()
+        };
+
+        let repository = self.repository.read().unwrap_or_else(PoisonError::into_inner);
+        let results = content_search::search_repository(&*repository, &options).map_err(RpcError::tool)?;
+        Ok(json!(results))
+    }
+
+    fn reload(&self) -> Result<Value, RpcError> {
+        let config = common_config::reload_config().map_err(|err| RpcError::tool(err.into()))?;
+        *self.repository.write().unwrap_or_else(PoisonError::into_inner) = CachedContentRepository::new(&config);
+        Ok(Value::Null)
+    }
+}
+
+/// Check that a path from a client is inside the content directory
+///
+/// The file need not exist, since an unsaved buffer can be validated, so `..`
+/// is refused and symbolic links are followed as far as the path exists.
+/// Relative paths are taken from the daemon's working directory.
+fn ensure_content_path(path: &Path) -> Result<(), RpcError> {
+    let outside = || RpcError::invalid_params(format!("{} is outside the content directory", path.display()));
+    if path.components().any(|component| component == Component::ParentDir) {
+        return Err(outside());
+    }
+
+    let config = common_config::load_config().map_err(|err| RpcError::tool(err.into()))?;
+    let base_dir = Path::new(&config.content.base_dir)
+        .canonicalize()
+        .map_err(|err| RpcError::tool(anyhow::anyhow!("Failed to resolve the content directory: {}", err)))?;
+
+    let absolute = match std::env::current_dir() {
+        Ok(current_dir) => current_dir.join(path),
+        Err(_) => path.to_path_buf(),
+    };
+    let existing = absolute.ancestors().find_map(|ancestor| ancestor.canonicalize().ok());
+    match existing {
+        Some(existing) if existing.starts_with(&base_dir) => Ok(()),
+        _ => Err(outside()),
+    }
+}
+
+fn to_line<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|err| {
+        format!(r#"{{"jsonrpc":"2.0","error":{{"code":-32603,"message":"{}"}},"id":null}}"#, err)
+    })
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    // Methods whose parameters are all optional can be called without any
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(RpcError::invalid_params)
+}
+
+fn validation_type(name: &str) -> Result<content_validate::ValidationType, RpcError> {
+    use content_validate::ValidationType;
+    match name {
+        "links" => Ok(ValidationType::Links),
+        "markdown" => Ok(ValidationType::Markdown),
+        "assets" => Ok(ValidationType::Assets),
+        "publish-date" => Ok(ValidationType::PublishDate),
+        "duplicate-slugs" => Ok(ValidationType::DuplicateSlugs),
+        "all" => Ok(ValidationType::All),
+        other => Err(RpcError::invalid_params(format!(
+            "unknown validation type '{}': expected links, markdown, assets, publish-date, duplicate-slugs or all",
+            other
+        ))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidateParams {
+    path: PathBuf,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    types: Option<Vec<String>>,
+    #[serde(default)]
+    check_external_links: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchParams {
+    query: String,
+    #[serde(default)]
+    topic: Option<String>,
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+    #[serde(default)]
+    limit: Option<usize>,
+    #[serde(default)]
+    include_drafts: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatsParams {
+    #[serde(default)]
+    topic: Option<String>,
+    #[serde(default)]
+    slug: Option<String>,
+    #[serde(default)]
+    include_drafts: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateParams {
+    title: String,
+    #[serde(default)]
+    topic: Option<String>,
+    #[serde(default)]
+    slug: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+    #[serde(default)]
+    template: Option<String>,
+    #[serde(default)]
+    draft: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MoveParams {
+    slug: String,
+    #[serde(default)]
+    topic: Option<String>,
+    #[serde(default)]
+    new_slug: Option<String>,
+    #[serde(default)]
+    new_topic: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+fn stats(params: StatsParams) -> Result<Value, RpcError> {
+    let options = content_stats::StatsOptions {
+        slug: params.slug,
+        topic: params.topic,
+        include_drafts: params.include_drafts,
+        include_archived: false,
+        sort_by: "date".to_string(),
+        detailed: false,
+        changed_since: None,
+    };
+    let (articles, tags, total_words, total_articles, total_drafts) =
+        content_stats::generate_stats(&options).map_err(RpcError::tool)?;
+
+    let report = content_stats::StatsReport::new(&articles, &tags, total_words, total_articles, total_drafts);
+    Ok(json!(report))
+}
+
+fn create(params: CreateParams) -> Result<Value, RpcError> {
+    let options = content_new::NewOptions {
+        slug: params.slug.or_else(|| Some(slug::slugify(&params.title))),
+        title: Some(params.title),
+        topic: params.topic,
+        description: params.description,
+        template: params.template,
+        tags: params.tags,
+        draft: params.draft,
+    };
+    let path = content_new::create_content(&options).map_err(RpcError::tool)?;
+    Ok(json!({ "path": path }))
+}
+
+fn move_content(params: MoveParams) -> Result<Value, RpcError> {
+    let options = content_move::MoveOptions {
+        slug: Some(params.slug),
+        new_slug: params.new_slug,
+        topic: params.topic,
+        new_topic: params.new_topic,
+        update_frontmatter: true,
+        dry_run: params.dry_run,
+    };
+    let plan = content_move::move_content(&options).map_err(RpcError::tool)?;
+
+    Ok(json!({
+        "from_topic": plan.from_topic,
+        "from_slug": plan.from_slug,
+        "to_topic": plan.to_topic,
+        "to_slug": plan.to_slug,
+        "destination": plan.destination,
+        "references_updated": plan.reference_updates.len(),
+        "dry_run": params.dry_run,
+    }))
+}
+
+/// Listen on a Unix socket until a client sends `shutdown`
+///
+/// Each connection is served on its own thread. The socket is readable and
+/// writable only by its owner. A stale socket file left by a daemon that
+/// didn't stop cleanly is replaced.
+///
+/// # Errors
+///
+/// Returns an error if another daemon is listening on the socket or the
+/// socket cannot be created
+#[cfg(unix)]
+pub fn serve_socket(daemon: std::sync::Arc<Daemon>, socket_path: &Path) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use std::io::BufReader;
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            anyhow::bail!("A daemon is already listening on {}", socket_path.display());
+        }
+        std::fs::remove_file(socket_path).with_context(|| format!("Failed to remove {}", socket_path.display()))?;
+    }
+    let listener =
+        UnixListener::bind(socket_path).with_context(|| format!("Failed to listen on {}", socket_path.display()))?;
+    // Clients can edit content, so only the user running the daemon may connect
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("Failed to restrict access to {}", socket_path.display()))?;
+
+    for stream in listener.incoming() {
+        if daemon.is_shutting_down() {
+            break;
+        }
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+
+        let daemon = std::sync::Arc::clone(&daemon);
+        let socket_path = socket_path.to_path_buf();
+        std::thread::spawn(move || {
+            if let Ok(reader) = stream.try_clone() {
+                // A client that disconnects mid-request is not the daemon's problem
+                let _ = daemon.serve_connection(BufReader::new(reader), &stream);
+            }
+            if daemon.is_shutting_down() {
+                // Wake the accept loop so it sees the shutdown
+                let _ = UnixStream::connect(&socket_path);
+            }
+        });
+    }
+
+    let _ = std::fs::remove_file(socket_path);
+    Ok(())
+}
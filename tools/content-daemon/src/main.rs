@@ -0,0 +1,42 @@
+use anyhow::Result;
+use clap::Parser;
+use content_daemon::{Daemon, DEFAULT_SOCKET};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(author, version, about = "Serve the writing tools to editors over JSON-RPC")]
+struct Args {
+    /// Unix socket to listen on
+    #[arg(short, long, default_value = DEFAULT_SOCKET)]
+    socket: PathBuf,
+
+    /// Read requests from standard input and answer on standard output instead
+    #[arg(long)]
+    stdio: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let daemon = Arc::new(Daemon::new()?);
+    let articles = daemon.warm();
+
+    if args.stdio {
+        let stdin = std::io::stdin();
+        daemon.serve_connection(stdin.lock(), std::io::stdout().lock())?;
+        return Ok(());
+    }
+
+    #[cfg(unix)]
+    {
+        eprintln!("Listening on {} ({} articles loaded)", args.socket.display(), articles);
+        content_daemon::serve_socket(daemon, &args.socket)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = articles;
+        anyhow::bail!("Unix sockets are not available on this platform; use --stdio")
+    }
+}
@@ -0,0 +1,84 @@
+//! JSON-RPC 2.0 messages
+//!
+//! Each message is one line of JSON. A request without an `id` is a
+//! notification and gets no response. Batches (arrays of requests) are
+//! answered with an array of responses.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// The line could not be parsed as JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON is not a valid request
+pub const INVALID_REQUEST: i64 = -32600;
+/// No method with the requested name
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// The parameters don't match what the method takes
+pub const INVALID_PARAMS: i64 = -32602;
+/// A tool reported an error
+pub const TOOL_ERROR: i64 = -32000;
+
+/// A call from a client
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    /// Missing for notifications
+    #[serde(default)]
+    pub id: Option<Value>,
+}
+
+/// The answer to a request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Response {
+    pub jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    pub id: Value,
+}
+
+impl Response {
+    /// A successful response
+    pub fn success(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", result: Some(result), error: None, id }
+    }
+
+    /// An error response
+    pub fn failure(id: Value, error: RpcError) -> Self {
+        Self { jsonrpc: "2.0", result: None, error: Some(error), id }
+    }
+}
+
+/// An error returned to the client
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    /// Parameters that don't match the method
+    pub fn invalid_params(error: impl fmt::Display) -> Self {
+        Self::new(INVALID_PARAMS, format!("Invalid params: {}", error))
+    }
+
+    /// An error from one of the tools
+    pub fn tool(error: anyhow::Error) -> Self {
+        Self::new(TOOL_ERROR, format!("{:#}", error))
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
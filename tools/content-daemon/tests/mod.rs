@@ -0,0 +1,5 @@
+//! Test modules for content-daemon
+
+// Unit tests
+#[path = "unit/daemon_tests.rs"]
+mod daemon_tests;
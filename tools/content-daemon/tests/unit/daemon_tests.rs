@@ -0,0 +1,119 @@
+use content_daemon::protocol::{INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR};
+use content_daemon::Daemon;
+use common_test_utils::{use_config, write_article, write_repository, ConfigPathGuard};
+use serde_json::{json, Value};
+use std::io::Cursor;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod daemon_tests {
+    use super::*;
+
+    /// A daemon over a repository with one published article
+    fn daemon(root: &Path) -> (Daemon, ConfigPathGuard) {
+        let config = use_config(&write_repository(root, &["blog"]));
+        write_article(
+            &root.join("content"),
+            "blog",
+            "rust-tips",
+            "---\ntitle: \"Rust tips\"\npublished: \"2024-01-01\"\ntags:\n  - rust\n---\nBorrowing explained in plain words.\n",
+        );
+        common_config::clear_config_cache();
+        (Daemon::new().unwrap(), config)
+    }
+
+    fn request(daemon: &Daemon, method: &str, params: Value) -> Value {
+        let line = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 }).to_string();
+        serde_json::from_str(&daemon.handle_line(&line).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_protocol_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let (daemon, _config) = daemon(temp_dir.path());
+
+        let response = request(&daemon, "ping", Value::Null);
+        assert_eq!(response["result"], "pong");
+        assert_eq!(response["id"], 1);
+
+        let response: Value = serde_json::from_str(&daemon.handle_line("{not json").unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+        assert_eq!(response["id"], Value::Null);
+
+        let response: Value = serde_json::from_str(&daemon.handle_line(r#"{"method":"ping","id":2}"#).unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+        let response = request(&daemon, "publish", Value::Null);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = request(&daemon, "search", json!({ "limit": 3 }));
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        // Notifications get no response
+        assert!(daemon.handle_line(r#"{"jsonrpc":"2.0","method":"ping"}"#).is_none());
+    }
+
+    #[test]
+    fn test_batch_and_shutdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let (daemon, _config) = daemon(temp_dir.path());
+
+        let input = concat!(
+            r#"[{"jsonrpc":"2.0","method":"ping","id":1},{"jsonrpc":"2.0","method":"ping"},{"jsonrpc":"2.0","method":"ping","id":2}]"#,
+            "\n\n",
+            r#"{"jsonrpc":"2.0","method":"shutdown","id":3}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","method":"ping","id":4}"#,
+            "\n"
+        );
+        let mut output = Vec::new();
+        daemon.serve_connection(Cursor::new(input), &mut output).unwrap();
+
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2, "the request after shutdown is not answered");
+        let ids: Vec<&Value> = lines[0].as_array().unwrap().iter().map(|response| &response["id"]).collect();
+        assert_eq!(ids, vec![&json!(1), &json!(2)]);
+        assert_eq!(lines[1]["id"], 3);
+        assert!(daemon.is_shutting_down());
+    }
+
+    #[test]
+    fn test_tools_over_rpc() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let (daemon, _config) = daemon(root);
+        assert_eq!(daemon.warm(), 1);
+
+        let response = request(&daemon, "search", json!({ "query": "borrowing" }));
+        assert_eq!(response["result"][0]["title"], "Rust tips");
+
+        // Unsaved text is validated instead of the file on disk
+        let path = root.join("content").join("blog").join("rust-tips").join("rust-tips.md");
+        let text = "---\ntitle: \"Rust tips\"\n---\nSee [missing](/blog/missing-post).\n";
+        let response = request(&daemon, "validate", json!({ "path": path, "text": text, "types": ["links"] }));
+        let issues = response["result"]["issues"].as_array().unwrap();
+        assert!(!issues.is_empty(), "expected a broken link in {:?}", response);
+
+        let response = request(&daemon, "validate", json!({ "path": path, "types": ["spelling"] }));
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        // Files outside the content directory are not read
+        for outside in [root.join("config.yaml"), root.join("content").join("..").join("config.yaml")] {
+            let response = request(&daemon, "validate", json!({ "path": outside }));
+            assert_eq!(response["error"]["code"], INVALID_PARAMS, "{:?}", response);
+        }
+
+        let response = request(&daemon, "create", json!({ "title": "Second Post", "topic": "blog" }));
+        let created = response["result"]["path"].as_str().unwrap_or_else(|| panic!("{:?}", response));
+        assert!(Path::new(created).exists());
+
+        let response = request(&daemon, "stats", json!({ "include_drafts": true }));
+        assert_eq!(response["result"]["total_articles"], 2);
+        assert_eq!(response["result"]["tags"]["rust"], 1);
+    }
+}
@@ -1,13 +1,19 @@
 use anyhow::Result;
 use common_fs::normalize::{normalize_path, join_paths};
+use common_fs::Journal;
 use common_errors::{WritingError, ErrorContext, IoResultExt};
 use common_cli::{Command, ContentCommand, DisplayResult};
+use common_plugins::{HookContext, HookEvent, HookRegistry};
 use common_traits::tools::ContentDeleter;
 use clap::Parser;
 use std::path::{Path, PathBuf};
 use colored::*;
 use std::fs;
 
+pub mod trash;
+
+pub use trash::{list_trash, move_to_trash, purge_trash, restore_from_trash, TrashEntry, TrashManifest};
+
 /// CLI arguments for the content-delete command
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Delete existing content")]
@@ -23,6 +29,10 @@ pub struct DeleteArgs {
     /// Force delete without confirmation
     #[arg(short, long)]
     pub force: bool,
+
+    /// Move content to the trash instead of deleting it permanently
+    #[arg(long)]
+    pub trash: bool,
 }
 
 /// Command for deleting content
@@ -69,16 +79,12 @@ impl Command for DeleteCommand {
         let (content_dir, topic_name) = find_content_dir(&slug, topic.as_deref())?;
 
         // Get content title for confirmation
-        let content_file = join_paths(&content_dir, "index.mdx");
+        let content_file = common_fs::resolve_content_file(&content_dir, &slug)
+            .ok_or_else(|| anyhow::anyhow!("No content file found in {}", content_dir.display()))?;
         let title = extract_title_from_content(&content_file)?;
 
-        // Delete content directory with enhanced context
-        std::fs::remove_dir_all(&content_dir)
-            .with_enhanced_context(|| {
-                ErrorContext::new("delete content directory")
-                    .with_file(&content_dir)
-                    .with_details("Unable to remove directory")
-            })?;
+        let journal = Journal::for_base_dir(&common_config::load_config()?.content.base_dir);
+        journaled_remove(&journal, &content_dir, &topic_name, &slug, self.args.trash)?;
 
         Ok(DeleteResult {
             topic: topic_name,
@@ -100,6 +106,8 @@ pub struct DeleteOptions {
     pub slug: Option<String>,
     pub topic: Option<String>,
     pub force: bool,
+    /// Move content to the trash instead of deleting it permanently
+    pub trash: bool,
 }
 
 /// Find the directory containing the content to delete
@@ -171,8 +179,7 @@ pub fn list_all_content() -> Result<Vec<(String, String, PathBuf)>> {
                 .unwrap_or("")
                 .to_string();
 
-            let index_path = join_paths(&article_dir, "index.mdx");
-            if index_path.exists() {
+            if common_fs::resolve_content_file(&article_dir, &slug).is_some() {
                 content_list.push((topic_key.clone(), slug, normalize_path(article_dir)));
             }
         }
@@ -225,6 +232,8 @@ pub fn delete_content(options: &DeleteOptions) -> Result<String> {
     // Load configuration
     let config = common_config::load_config()
         .map_err(|e| WritingError::config_error(format!("Failed to load config: {}", e)))?;
+    let hooks = HookRegistry::from_config(&config)?;
+    let journal = Journal::for_base_dir(&config.content.base_dir);
 
     // If topic is provided, look in that topic directory
     if let Some(topic_key) = &options.topic {
@@ -238,21 +247,17 @@ pub fn delete_content(options: &DeleteOptions) -> Result<String> {
             return Err(WritingError::content_not_found(format!("Content with slug '{}' not found in topic '{}'", slug, topic_key)).into());
         }
 
-        // Delete the content directory
-        fs::remove_dir_all(&content_dir)
-            .map_err(|e| WritingError::validation_error(format!("Failed to delete content directory: {}", e)))?;
+        remove_content_dir(&hooks, &journal, &content_dir, topic_key, slug, options.trash)?;
 
         Ok(content_dir.to_string_lossy().to_string())
     } else {
         // No topic provided, search all topics
-        for topic_config in config.content.topics.values() {
+        for (topic_key, topic_config) in &config.content.topics {
             let topic_dir = join_paths(&config.content.base_dir, &topic_config.directory);
             let content_dir = topic_dir.join(slug);
 
             if content_dir.exists() {
-                // Delete the content directory
-                fs::remove_dir_all(&content_dir)
-                    .map_err(|e| WritingError::validation_error(format!("Failed to delete content directory: {}", e)))?;
+                remove_content_dir(&hooks, &journal, &content_dir, topic_key, slug, options.trash)?;
 
                 return Ok(content_dir.to_string_lossy().to_string());
             }
@@ -262,6 +267,51 @@ pub fn delete_content(options: &DeleteOptions) -> Result<String> {
     }
 }
 
+/// Delete a content directory, or move it to the trash
+fn remove_content_dir(
+    hooks: &HookRegistry,
+    journal: &Journal,
+    content_dir: &Path,
+    topic: &str,
+    slug: &str,
+    trash: bool,
+) -> Result<()> {
+    journaled_remove(journal, content_dir, topic, slug, trash)?;
+
+    // The content is gone either way, so a failing hook is only worth a warning
+    let context = HookContext::for_article(HookEvent::PostDelete, topic, slug).with_path(content_dir);
+    if let Err(err) = hooks.fire(&context) {
+        tracing::warn!(path = %content_dir.display(), "{}", err);
+    }
+
+    Ok(())
+}
+
+/// Delete a content directory, or move it to the trash, recording it in the journal
+///
+/// The directory is snapshotted before it is removed, so the deletion can be
+/// undone with [`Journal::undo_last`] even when it bypasses the trash.
+fn journaled_remove(journal: &Journal, content_dir: &Path, topic: &str, slug: &str, trash: bool) -> Result<()> {
+    let operation = if trash { "trash" } else { "delete" };
+    let mut transaction = journal.begin(operation, format!("Delete {}/{}", topic, slug))?;
+    transaction.snapshot(content_dir)?;
+
+    if trash {
+        let entry = move_to_trash(content_dir, topic, slug)?;
+        transaction.created(&entry.path);
+    } else {
+        fs::remove_dir_all(content_dir)
+            .with_enhanced_context(|| {
+                ErrorContext::new("delete content directory")
+                    .with_file(content_dir)
+                    .with_details("Unable to remove directory")
+            })?;
+    }
+
+    transaction.commit()?;
+    Ok(())
+}
+
 // Add ContentDeleterImpl struct to implement ContentDeleter trait
 /// Implementation of ContentDeleter trait for the content-delete tool
 pub struct ContentDeleterImpl;
@@ -290,6 +340,7 @@ impl ContentDeleter for ContentDeleterImpl {
             slug: Some(slug.to_string()),
             topic: topic.map(String::from),
             force,
+            trash: false,
         };
 
         // Use the existing delete_content function
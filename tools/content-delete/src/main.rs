@@ -27,8 +27,10 @@ fn main() -> Result<()> {
             
             if let Some((topic, slug, content_dir)) = matching_content {
                 // Get content title for confirmation
-                let content_file = content_dir.join("index.mdx");
-                let title = extract_title_from_content(&content_file)?;
+                let title = match common_fs::resolve_content_file(content_dir, slug) {
+                    Some(content_file) => extract_title_from_content(&content_file)?,
+                    None => slug.clone(),
+                };
                 
                 let confirm_message = format!("Delete content '{}/{}' ({})?", topic, slug, title);
                 if !Confirm::new().with_prompt(confirm_message).interact()? {
@@ -74,8 +76,10 @@ fn handle_interactive_selection(args: DeleteArgs) -> Result<()> {
     let (topic, slug, content_dir) = &content_list[selection];
     
     // Get content title for confirmation
-    let content_file = content_dir.join("index.mdx");
-    let title = extract_title_from_content(&content_file)?;
+    let title = match common_fs::resolve_content_file(content_dir, slug) {
+        Some(content_file) => extract_title_from_content(&content_file)?,
+        None => slug.clone(),
+    };
     
     // Confirm deletion
     if !args.force {
@@ -91,6 +95,7 @@ fn handle_interactive_selection(args: DeleteArgs) -> Result<()> {
         slug: Some(slug.clone()),
         topic: Some(topic.clone()),
         force: args.force,
+        trash: args.trash,
     };
     
     // Execute the command with selected item
@@ -0,0 +1,205 @@
+//! # Trash
+//!
+//! Soft-deleted content is moved into `.trash/<timestamp>/` under the content
+//! base directory, next to a `manifest.yaml` recording where it came from, so
+//! that accidental deletions can be restored until the trash is purged.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use common_errors::WritingError;
+use common_fs::normalize::join_paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the trash directory, relative to the content base directory
+pub const TRASH_DIR: &str = ".trash";
+
+/// Name of the manifest file in each trash entry
+const MANIFEST_FILE: &str = "manifest.yaml";
+
+/// Where a piece of trashed content came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashManifest {
+    /// Topic key the content was deleted from
+    pub topic: String,
+    /// Slug of the deleted content
+    pub slug: String,
+    /// When the content was deleted
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// A piece of content in the trash
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashEntry {
+    /// Where the content came from
+    pub manifest: TrashManifest,
+    /// The `.trash/<timestamp>` directory holding the content and manifest
+    pub path: PathBuf,
+}
+
+impl TrashEntry {
+    /// The trashed content directory
+    pub fn content_dir(&self) -> PathBuf {
+        self.path.join(&self.manifest.slug)
+    }
+}
+
+/// Get the trash directory for the current configuration
+pub fn trash_dir() -> Result<PathBuf> {
+    let config = common_config::load_config()?;
+    Ok(join_paths(&config.content.base_dir, TRASH_DIR))
+}
+
+/// Move a content directory into the trash
+///
+/// # Parameters
+///
+/// * `content_dir` - The content directory to move
+/// * `topic` - Topic key the content belongs to
+/// * `slug` - Slug of the content
+///
+/// # Returns
+///
+/// Returns the new trash entry
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be moved or the manifest cannot be written
+pub fn move_to_trash(content_dir: &Path, topic: &str, slug: &str) -> Result<TrashEntry> {
+    let manifest = TrashManifest {
+        topic: topic.to_string(),
+        slug: slug.to_string(),
+        deleted_at: Utc::now(),
+    };
+
+    // Several deletions can happen within the same millisecond
+    let trash_root = trash_dir()?;
+    let timestamp = manifest.deleted_at.format("%Y%m%dT%H%M%S%.3fZ").to_string();
+    let mut entry_dir = trash_root.join(&timestamp);
+    let mut suffix = 1;
+    while entry_dir.exists() {
+        entry_dir = trash_root.join(format!("{}-{}", timestamp, suffix));
+        suffix += 1;
+    }
+
+    common_fs::create_dir_all(&entry_dir)?;
+    if let Err(err) = fill_entry(&entry_dir, &manifest, content_dir) {
+        // Nothing was moved, so the half-made entry can simply go
+        let _ = fs::remove_dir_all(&entry_dir);
+        return Err(err);
+    }
+
+    Ok(TrashEntry {
+        manifest,
+        path: entry_dir,
+    })
+}
+
+/// Write a trash entry's manifest, then move the content in beside it
+///
+/// The manifest goes first: content moved into an entry without one would be
+/// skipped by [`list_trash`], and so could never be restored.
+fn fill_entry(entry_dir: &Path, manifest: &TrashManifest, content_dir: &Path) -> Result<()> {
+    common_fs::write_file(entry_dir.join(MANIFEST_FILE), &serde_yaml::to_string(manifest)?)?;
+    fs::rename(content_dir, entry_dir.join(&manifest.slug))?;
+    Ok(())
+}
+
+/// List the content in the trash, oldest deletion first
+pub fn list_trash() -> Result<Vec<TrashEntry>> {
+    let trash_root = trash_dir()?;
+    let mut entries = Vec::new();
+
+    if !trash_root.is_dir() {
+        return Ok(entries);
+    }
+
+    for dir_entry in fs::read_dir(&trash_root)?.filter_map(|entry| entry.ok()) {
+        let manifest_path = dir_entry.path().join(MANIFEST_FILE);
+        if !manifest_path.exists() {
+            continue;
+        }
+
+        let manifest: TrashManifest = serde_yaml::from_str(&common_fs::read_file(&manifest_path)?)?;
+        entries.push(TrashEntry {
+            manifest,
+            path: dir_entry.path(),
+        });
+    }
+
+    entries.sort_by_key(|entry| entry.manifest.deleted_at);
+    Ok(entries)
+}
+
+/// Restore the most recently deleted content with the given slug
+///
+/// # Parameters
+///
+/// * `slug` - Slug of the content to restore
+///
+/// # Returns
+///
+/// Returns the restored content directory
+///
+/// # Errors
+///
+/// Returns an error if no trashed content has the slug, its topic no longer
+/// exists, or content already exists at the original location
+pub fn restore_from_trash(slug: &str) -> Result<PathBuf> {
+    let entry = list_trash()?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.manifest.slug == slug)
+        .ok_or_else(|| {
+            WritingError::content_not_found(format!("No content with slug '{}' in the trash", slug))
+        })?;
+
+    let config = common_config::load_config()?;
+    let topic_config = config.content.topics.get(&entry.manifest.topic).ok_or_else(|| {
+        WritingError::topic_error(format!("Topic '{}' not found", entry.manifest.topic))
+    })?;
+
+    let topic_dir = join_paths(&config.content.base_dir, &topic_config.directory);
+    let content_dir = topic_dir.join(slug);
+    if content_dir.exists() {
+        return Err(WritingError::content_already_exists(format!(
+            "Content already exists: {}/{}",
+            entry.manifest.topic, slug
+        ))
+        .into());
+    }
+
+    common_fs::create_dir_all(&topic_dir)?;
+    fs::rename(entry.content_dir(), &content_dir)?;
+    fs::remove_dir_all(&entry.path)?;
+
+    Ok(content_dir)
+}
+
+/// Permanently delete content that has been in the trash longer than `older_than`
+///
+/// # Parameters
+///
+/// * `older_than` - Minimum time since deletion
+///
+/// # Returns
+///
+/// Returns the entries that were purged
+///
+/// # Errors
+///
+/// Returns an error if the trash cannot be read or an entry cannot be removed
+pub fn purge_trash(older_than: Duration) -> Result<Vec<TrashEntry>> {
+    let cutoff = Utc::now() - older_than;
+    let mut purged = Vec::new();
+
+    for entry in list_trash()? {
+        if entry.manifest.deleted_at <= cutoff {
+            fs::remove_dir_all(&entry.path)?;
+            purged.push(entry);
+        }
+    }
+
+    Ok(purged)
+}
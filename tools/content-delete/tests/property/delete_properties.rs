@@ -87,6 +87,7 @@ proptest! {
             slug: Some(slug.clone()),
             topic: Some(topic.clone()),
             force: true,
+            trash: false,
         };
 
         // Delete the content
@@ -144,6 +145,7 @@ proptest! {
             slug: Some(slug),
             topic: Some(topic),
             force: true,
+            trash: false,
         };
 
         // Try to delete content with invalid slug
@@ -198,6 +200,7 @@ proptest! {
             slug: Some(slug),
             topic: Some(topic),
             force: true,
+            trash: false,
         };
 
         // Try to delete content with invalid topic
@@ -224,6 +227,7 @@ proptest! {
             slug: None,
             topic,
             force,
+            trash: false,
         };
 
         // Try to delete content without slug
@@ -16,6 +16,7 @@ fn test_delete_command_no_slug() {
         slug: None,
         topic: Some("blog".to_string()),
         force: false,
+        trash: false,
     };
 
     let command = DeleteCommand::new(args);
@@ -36,6 +37,7 @@ fn test_delete_command_invalid_topic() {
         slug: Some("test-article".to_string()),
         topic: Some("nonexistent-topic".to_string()),
         force: false,
+        trash: false,
     };
 
     let command = DeleteCommand::new(args);
@@ -106,6 +108,7 @@ Test content
         slug: Some("test-article".to_string()),
         topic: Some("blog".to_string()),
         force: true, // Force delete without confirmation
+        trash: false,
     };
 
     let command = DeleteCommand::new(args);
@@ -168,6 +171,7 @@ fn test_delete_command_nonexistent_content() {
         slug: Some("nonexistent-article".to_string()),
         topic: Some("blog".to_string()),
         force: true,
+        trash: false,
     };
 
     let command = DeleteCommand::new(args);
@@ -12,6 +12,7 @@ fn test_delete_content_requires_slug() {
         slug: None,
         topic: Some("blog".to_string()),
         force: false,
+        trash: false,
     };
 
     let result = delete_content(&options);
@@ -55,6 +56,7 @@ fn test_delete_content_nonexistent_topic() {
         slug: Some("test-article".to_string()),
         topic: Some("nonexistent-topic".to_string()),
         force: false,
+        trash: false,
     };
 
     let result = delete_content(&options);
@@ -108,6 +110,7 @@ fn test_delete_content_topic_not_found() {
         slug: Some("nonexistent-article".to_string()),
         topic: Some("blog".to_string()),
         force: false,
+        trash: false,
     };
 
     let result = delete_content(&options);
@@ -165,6 +168,7 @@ fn test_delete_content_success() {
         slug: Some("test-article".to_string()),
         topic: Some("blog".to_string()),
         force: true, // Force delete without confirmation
+        trash: false,
     };
 
     let result = delete_content(&options);
@@ -230,6 +234,7 @@ fn test_delete_content_search_in_all_topics() {
         slug: Some("test-article".to_string()),
         topic: None, // No topic specified, should search in all
         force: true,
+        trash: false,
     };
 
     let result = delete_content(&options);
@@ -286,6 +291,7 @@ fn test_delete_content_non_force_safety() {
         slug: Some("test-article".to_string()),
         topic: Some("blog".to_string()),
         force: false, // No force flag
+        trash: false,
     };
 
     let result = delete_content(&options);
@@ -298,4 +304,51 @@ fn test_delete_content_non_force_safety() {
                 "Unexpected error message: {}", err);
     }
     // If it succeeds without requiring confirmation, that's also acceptable
-}
\ No newline at end of file
+}
+
+/// Write a repository with one blog article and the given hooks, and point `CONFIG_PATH` at it
+fn use_hooks(fixture: &TestFixture, hooks: Vec<common_models::HookConfig>) -> common_test_utils::ConfigPathGuard {
+    let base_dir = fixture.path().join("content");
+    let mut config = common_test_utils::config_for(&base_dir, &["blog"]);
+    config.hooks = hooks;
+    common_test_utils::write_article(&base_dir, "blog", "hooked", "---\ntitle: Hooked\n---\n\nText.\n");
+    common_test_utils::use_config(&common_test_utils::write_config(fixture.path(), &config))
+}
+
+fn hooked_options() -> DeleteOptions {
+    DeleteOptions {
+        slug: Some("hooked".to_string()),
+        topic: Some("blog".to_string()),
+        force: true,
+        trash: false,
+    }
+}
+
+#[test]
+fn test_delete_content_runs_post_delete_hooks() {
+    let fixture = TestFixture::new().unwrap();
+    let marker = fixture.path().join("deleted");
+    let _config = use_hooks(&fixture, vec![common_models::HookConfig {
+        event: "post_delete".to_string(),
+        run: None,
+        touch: Some(marker.to_string_lossy().to_string()),
+    }]);
+
+    delete_content(&hooked_options()).unwrap();
+    assert!(!fixture.path().join("content/blog/hooked").exists());
+    assert!(marker.exists());
+}
+
+#[test]
+fn test_delete_content_only_warns_when_a_post_delete_hook_fails() {
+    let fixture = TestFixture::new().unwrap();
+    let _config = use_hooks(&fixture, vec![common_models::HookConfig {
+        event: "post_delete".to_string(),
+        run: Some("exit 1".to_string()),
+        touch: None,
+    }]);
+
+    // The content is already gone, so the hook failing doesn't make the delete fail
+    delete_content(&hooked_options()).unwrap();
+    assert!(!fixture.path().join("content/blog/hooked").exists());
+}
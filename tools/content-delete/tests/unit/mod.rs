@@ -4,6 +4,7 @@ mod tests_tests;
 mod delete_content_tests;
 mod find_content_dir_tests;
 mod delete_command_tests;
+mod trash_tests;
 
 #[cfg(test)]
 mod tests {
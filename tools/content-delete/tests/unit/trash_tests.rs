@@ -0,0 +1,66 @@
+use content_delete::{delete_content, list_trash, move_to_trash, purge_trash, restore_from_trash, DeleteOptions};
+use common_test_utils::{use_config, write_article, write_repository};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn seed_repository(root: &Path) -> PathBuf {
+    let config_path = write_repository(root, &["blog"]);
+    write_article(&root.join("content"), "blog", "test-article", "---\ntitle: Test\n---\nBody\n");
+    config_path
+}
+
+#[test]
+fn test_trash_restore_and_purge() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let _config = use_config(&seed_repository(root));
+
+    let options = DeleteOptions {
+        slug: Some("test-article".to_string()),
+        topic: None,
+        force: true,
+        trash: true,
+    };
+
+    // Soft delete moves the content into the trash with a manifest
+    delete_content(&options).unwrap();
+    assert!(!root.join("content/blog/test-article").exists());
+
+    let entries = list_trash().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].manifest.topic, "blog");
+    assert_eq!(entries[0].manifest.slug, "test-article");
+    assert!(entries[0].path.starts_with(root.join("content/.trash")));
+    assert!(entries[0].content_dir().join("test-article.md").exists());
+
+    // Restoring puts it back where it was and empties the entry
+    let restored = restore_from_trash("test-article").unwrap();
+    assert_eq!(restored, root.join("content/blog/test-article"));
+    assert!(restored.join("test-article.md").exists());
+    assert!(list_trash().unwrap().is_empty());
+    assert!(restore_from_trash("test-article").is_err());
+
+    // Purging only removes entries older than the cutoff
+    delete_content(&options).unwrap();
+    assert!(purge_trash(chrono::Duration::days(30)).unwrap().is_empty());
+    assert_eq!(list_trash().unwrap().len(), 1);
+
+    let purged = purge_trash(chrono::Duration::zero()).unwrap();
+    assert_eq!(purged.len(), 1);
+    assert!(!purged[0].path.exists());
+    assert!(list_trash().unwrap().is_empty());
+}
+
+#[test]
+fn test_failed_move_leaves_no_trash_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let _config = use_config(&seed_repository(root));
+
+    let missing = root.join("content/blog/missing");
+    assert!(move_to_trash(&missing, "blog", "missing").is_err());
+
+    assert!(list_trash().unwrap().is_empty());
+    assert_eq!(fs::read_dir(root.join("content/.trash")).unwrap().count(), 0);
+}
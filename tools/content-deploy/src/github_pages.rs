@@ -0,0 +1,115 @@
+//! Deploying to GitHub Pages
+//!
+//! The pages branch is fetched into a temporary checkout, updated like a
+//! local directory, then committed and pushed. Only the tip of the branch is
+//! fetched, and a branch that doesn't exist yet is created without history.
+//! The `git` command is used rather than a library so pushes use whatever
+//! credentials git is already set up with.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+use crate::local::{copy_into, remove_from};
+use crate::{DeployTarget, Manifest};
+
+/// Commit message for each deploy
+const COMMIT_MESSAGE: &str = "Deploy site";
+
+/// A branch the site is committed to
+#[derive(Debug)]
+pub struct GitHubPagesTarget {
+    /// URL or path of the repository to push to
+    remote: String,
+    branch: String,
+    checkout: TempDir,
+}
+
+impl GitHubPagesTarget {
+    /// Target `branch` of `remote`, which is a remote of the current
+    /// repository, a URL or a path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `remote` is a remote name that cannot be resolved,
+    /// or the checkout directory cannot be created
+    pub fn new(remote: &str, branch: &str) -> Result<Self> {
+        let remote = if Path::new(remote).exists() {
+            std::fs::canonicalize(remote)?.to_string_lossy().into_owned()
+        } else if remote.contains(':') {
+            remote.to_string()
+        } else {
+            git(Path::new("."), &["remote", "get-url", remote])
+                .with_context(|| format!("Unknown git remote '{}'", remote))?
+        };
+
+        Ok(Self {
+            remote,
+            branch: branch.to_string(),
+            checkout: TempDir::new().context("Failed to create a checkout directory")?,
+        })
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        git(self.checkout.path(), args)
+    }
+}
+
+impl DeployTarget for GitHubPagesTarget {
+    fn manifest(&mut self) -> Result<Manifest> {
+        self.git(&["init", "-q"])?;
+
+        let branch_ref = format!("refs/heads/{}", self.branch);
+        let existing = self.git(&["ls-remote", "--heads", &self.remote, &branch_ref])?;
+        if !existing.is_empty() {
+            self.git(&["fetch", "-q", "--depth", "1", &self.remote, &branch_ref])?;
+            self.git(&["checkout", "-q", "FETCH_HEAD"])?;
+        }
+
+        Manifest::from_dir(self.checkout.path())
+    }
+
+    fn upload(&mut self, path: &str, local_path: &Path) -> Result<()> {
+        copy_into(self.checkout.path(), path, local_path)
+    }
+
+    fn delete(&mut self, path: &str) -> Result<()> {
+        remove_from(self.checkout.path(), path)
+    }
+
+    fn finish(&mut self, _manifest: &Manifest) -> Result<()> {
+        self.git(&["add", "-A"])?;
+
+        // Fall back to a fixed identity where git has none, as on CI runners
+        let mut commit = Vec::new();
+        if self.git(&["config", "user.email"]).is_err() {
+            commit.extend(["-c", "user.name=content-deploy", "-c", "user.email=content-deploy@localhost"]);
+        }
+        commit.extend(["commit", "-q", "-m", COMMIT_MESSAGE]);
+        self.git(&commit)?;
+
+        let refspec = format!("HEAD:refs/heads/{}", self.branch);
+        self.git(&["push", "-q", &self.remote, &refspec])?;
+        Ok(())
+    }
+}
+
+/// Run git in `dir`, returning its trimmed output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
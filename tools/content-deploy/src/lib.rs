@@ -0,0 +1,259 @@
+//! # Content Deploy
+//!
+//! Publishes the output of `content-build` to where the site is served from.
+//! Each target keeps track of what it already holds as a manifest of file
+//! hashes, so a deploy only uploads files whose contents changed and,
+//! optionally, deletes files that are no longer in the output.
+//!
+//! Three kinds of target are supported, configured in the `deploy` section
+//! of the configuration:
+//!
+//! * `local` - a directory, such as a mounted web root, updated rsync-style
+//! * `s3` - an S3-compatible bucket
+//! * `github_pages` - a branch of a git repository served by GitHub Pages
+
+use anyhow::{Context, Result};
+use common_models::DeployConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+use walkdir::WalkDir;
+
+pub mod github_pages;
+pub mod local;
+pub mod s3;
+
+pub use github_pages::GitHubPagesTarget;
+pub use local::LocalTarget;
+pub use s3::S3Target;
+
+/// Name of the manifest file kept by targets that can't hash their files cheaply
+pub const MANIFEST_FILE: &str = ".deploy-manifest.json";
+
+/// The files of a deployed site: each path, relative to the site root and
+/// separated by `/`, mapped to the SHA-256 hash of its contents
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Manifest {
+    pub files: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Hash every file under `dir`
+    ///
+    /// The `.git` directory and the manifest file are not part of the site
+    /// and are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be walked or a file cannot be read
+    pub fn from_dir(dir: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+
+        let walker = WalkDir::new(dir).into_iter().filter_entry(|entry| entry.file_name() != ".git");
+        for entry in walker {
+            let entry = entry.with_context(|| format!("Failed to read {:?}", dir))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative = relative_path(dir, entry.path());
+            if relative == MANIFEST_FILE {
+                continue;
+            }
+            files.insert(relative, hash_file(entry.path())?);
+        }
+
+        Ok(Self { files })
+    }
+
+    /// Check whether the manifest lists no files
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// SHA-256 hash of a file's contents, as lowercase hex
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read
+pub fn hash_file(path: &Path) -> Result<String> {
+    let data = std::fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+    Ok(hex::encode(Sha256::digest(&data)))
+}
+
+/// `path` relative to `root`, with `/` separators on every platform
+fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let parts: Vec<_> = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect();
+    parts.join("/")
+}
+
+/// The changes that bring a target up to date with the build output
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeployPlan {
+    /// Files that are new or changed
+    pub upload: Vec<String>,
+    /// Files the target has that the output doesn't
+    pub delete: Vec<String>,
+    /// Number of files the target already has as built
+    pub unchanged: usize,
+}
+
+impl DeployPlan {
+    /// Compare the build output with what the target holds
+    pub fn between(local: &Manifest, remote: &Manifest) -> Self {
+        let mut plan = Self::default();
+
+        for (path, hash) in &local.files {
+            if remote.files.get(path) == Some(hash) {
+                plan.unchanged += 1;
+            } else {
+                plan.upload.push(path.clone());
+            }
+        }
+        plan.delete = remote
+            .files
+            .keys()
+            .filter(|path| !local.files.contains_key(*path))
+            .cloned()
+            .collect();
+
+        plan
+    }
+}
+
+/// Somewhere the built site can be deployed to
+///
+/// A deploy reads the target's manifest, uploads and deletes files, then
+/// calls [`DeployTarget::finish`] once, so targets can batch their changes.
+/// `finish` is not called when there is nothing to change.
+pub trait DeployTarget {
+    /// The files the target currently holds
+    fn manifest(&mut self) -> Result<Manifest>;
+
+    /// Upload the file at `local_path` as `path`
+    fn upload(&mut self, path: &str, local_path: &Path) -> Result<()>;
+
+    /// Delete `path` from the target
+    fn delete(&mut self, path: &str) -> Result<()>;
+
+    /// Complete the deploy, recording `manifest` as what the target now holds
+    fn finish(&mut self, manifest: &Manifest) -> Result<()>;
+}
+
+/// Options for a deploy
+#[derive(Debug, Clone, Default)]
+pub struct DeployOptions {
+    /// Work out what would change without changing anything
+    pub dry_run: bool,
+    /// Delete files from the target that are no longer in the output
+    pub delete_removed: bool,
+}
+
+/// What a deploy changed, or would change in a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DeployReport {
+    pub uploaded: Vec<String>,
+    pub deleted: Vec<String>,
+    /// Files in the target that are no longer in the output but were kept
+    pub kept: Vec<String>,
+    pub unchanged: usize,
+    pub dry_run: bool,
+}
+
+impl DeployReport {
+    /// Check whether the deploy changed nothing
+    pub fn is_empty(&self) -> bool {
+        self.uploaded.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// Deploy `output_dir` to the target described by `target`
+///
+/// # Errors
+///
+/// Returns an error if the target cannot be set up, or reading the output or
+/// updating the target fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use common_models::DeployConfig;
+/// use content_deploy::{deploy, DeployOptions};
+/// use std::path::Path;
+///
+/// let target = DeployConfig::Local { path: "/var/www/site".to_string() };
+/// let report = deploy(Path::new("public"), &target, &DeployOptions::default()).unwrap();
+/// println!("{} files uploaded", report.uploaded.len());
+/// ```
+pub fn deploy(output_dir: &Path, target: &DeployConfig, options: &DeployOptions) -> Result<DeployReport> {
+    match target {
+        DeployConfig::Local { path } => deploy_to(output_dir, &mut LocalTarget::new(path), options),
+        DeployConfig::S3 { bucket, region, endpoint, prefix, manifest_key } => {
+            let mut target = S3Target::new(bucket, region, endpoint.as_deref(), prefix.as_deref())?;
+            if let Some(manifest_key) = manifest_key {
+                target = target.with_manifest_key(manifest_key);
+            }
+            deploy_to(output_dir, &mut target, options)
+        }
+        DeployConfig::GithubPages { remote, branch } => {
+            deploy_to(output_dir, &mut GitHubPagesTarget::new(remote, branch)?, options)
+        }
+    }
+}
+
+/// Deploy `output_dir` to `target`, uploading only what changed
+///
+/// # Errors
+///
+/// Returns an error if reading the output or updating the target fails
+pub fn deploy_to(output_dir: &Path, target: &mut dyn DeployTarget, options: &DeployOptions) -> Result<DeployReport> {
+    if !output_dir.is_dir() {
+        anyhow::bail!("Output directory {:?} does not exist; run content-build first", output_dir);
+    }
+
+    let local = Manifest::from_dir(output_dir)?;
+    let remote = target.manifest()?;
+    let plan = DeployPlan::between(&local, &remote);
+
+    let mut report = DeployReport {
+        uploaded: plan.upload,
+        unchanged: plan.unchanged,
+        dry_run: options.dry_run,
+        ..Default::default()
+    };
+    if options.delete_removed {
+        report.deleted = plan.delete;
+    } else {
+        report.kept = plan.delete;
+    }
+
+    if options.dry_run || report.is_empty() {
+        return Ok(report);
+    }
+
+    for path in &report.uploaded {
+        target.upload(path, &output_dir.join(path))?;
+    }
+    for path in &report.deleted {
+        target.delete(path)?;
+    }
+
+    // Kept files are still on the target, so they stay in its manifest
+    let mut manifest = local;
+    for path in &report.kept {
+        manifest.files.insert(path.clone(), remote.files[path].clone());
+    }
+    target.finish(&manifest)?;
+
+    Ok(report)
+}
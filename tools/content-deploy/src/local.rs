@@ -0,0 +1,70 @@
+//! Deploying to a local directory
+//!
+//! The directory's own files are hashed to find what changed, so it can be
+//! edited or replaced by other means between deploys without confusing the
+//! next one.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::{DeployTarget, Manifest};
+
+/// A directory the site is copied into
+#[derive(Debug, Clone)]
+pub struct LocalTarget {
+    root: PathBuf,
+}
+
+impl LocalTarget {
+    /// Target the directory at `root`, which is created on the first deploy
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl DeployTarget for LocalTarget {
+    fn manifest(&mut self) -> Result<Manifest> {
+        if !self.root.exists() {
+            return Ok(Manifest::default());
+        }
+        Manifest::from_dir(&self.root)
+    }
+
+    fn upload(&mut self, path: &str, local_path: &Path) -> Result<()> {
+        copy_into(&self.root, path, local_path)
+    }
+
+    fn delete(&mut self, path: &str) -> Result<()> {
+        remove_from(&self.root, path)
+    }
+
+    fn finish(&mut self, _manifest: &Manifest) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Copy `local_path` to `path` under `root`, creating directories as needed
+pub(crate) fn copy_into(root: &Path, path: &str, local_path: &Path) -> Result<()> {
+    let destination = root.join(path);
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+    }
+    std::fs::copy(local_path, &destination)
+        .with_context(|| format!("Failed to copy {:?} to {:?}", local_path, destination))?;
+    Ok(())
+}
+
+/// Remove `path` under `root`, along with any directories it leaves empty
+pub(crate) fn remove_from(root: &Path, path: &str) -> Result<()> {
+    let target = root.join(path);
+    std::fs::remove_file(&target).with_context(|| format!("Failed to remove {:?}", target))?;
+
+    let mut dir = target.parent();
+    while let Some(current) = dir {
+        if current == root || std::fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+    Ok(())
+}
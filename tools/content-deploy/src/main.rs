@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use colored::*;
+use content_deploy::{deploy, DeployOptions};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(author, version, about = "Deploy the built site, uploading only the files that changed")]
+struct Args {
+    /// Name of the deploy target in the configuration (optional when only one is configured)
+    target: Option<String>,
+
+    /// Directory of content-build output to deploy
+    #[arg(short, long, default_value = "public")]
+    output_dir: PathBuf,
+
+    /// Show what would change without deploying
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Delete files from the target that are no longer in the output
+    #[arg(long)]
+    delete: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = common_config::load_config()?;
+
+    let name = match args.target {
+        Some(name) => name,
+        None if config.deploy.len() == 1 => config.deploy.keys().next().cloned().unwrap_or_default(),
+        None => {
+            let mut names: Vec<&String> = config.deploy.keys().collect();
+            names.sort();
+            let names: Vec<&str> = names.into_iter().map(String::as_str).collect();
+            anyhow::bail!(
+                "Specify a deploy target: {}",
+                if names.is_empty() { "none are configured".to_string() } else { names.join(", ") }
+            );
+        }
+    };
+    let target = config
+        .deploy
+        .get(&name)
+        .with_context(|| format!("No deploy target named '{}' in the configuration", name))?;
+
+    let options = DeployOptions {
+        dry_run: args.dry_run,
+        delete_removed: args.delete,
+    };
+    let report = deploy(&args.output_dir, target, &options)?;
+
+    let (upload_verb, delete_verb) = if report.dry_run { ("Would upload", "Would delete") } else { ("Uploaded", "Deleted") };
+    for path in &report.uploaded {
+        println!("{} {}", upload_verb.green(), path);
+    }
+    for path in &report.deleted {
+        println!("{} {}", delete_verb.red(), path);
+    }
+    if !report.kept.is_empty() {
+        println!(
+            "{} {} files no longer in the output were kept; use --delete to remove them",
+            "Note:".yellow().bold(),
+            report.kept.len()
+        );
+    }
+
+    println!(
+        "{} {}: {} uploaded, {} deleted, {} unchanged",
+        "✓".green(),
+        name,
+        report.uploaded.len(),
+        report.deleted.len(),
+        report.unchanged
+    );
+
+    Ok(())
+}
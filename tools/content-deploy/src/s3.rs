@@ -0,0 +1,354 @@
+//! Deploying to S3-compatible storage
+//!
+//! Objects are uploaded with path-style requests signed with AWS Signature
+//! Version 4, which AWS and most S3-compatible services (MinIO, Cloudflare
+//! R2, Backblaze B2) accept. Listing and hashing every object would be slow
+//! and costly, so the bucket keeps a manifest object that records what the
+//! last deploy uploaded. It is kept under `.deploy/`, outside the site
+//! prefix, so a bucket policy that makes the site public needn't expose it;
+//! a bucket serving every key should set `manifest_key` to one it doesn't
+//! serve.
+//!
+//! Precompressed copies written by content-build, such as `index.html.gz`,
+//! are uploaded with the type of the original file and a `Content-Encoding`,
+//! so clients that ask for them get the page rather than an archive.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::blocking::{Client, Response};
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use crate::{DeployTarget, Manifest, MANIFEST_FILE};
+
+/// Prefix the deploy manifest is kept under, outside the site
+pub const MANIFEST_PREFIX: &str = ".deploy/";
+
+/// Media type of files of an unknown type
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Credentials for signing requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Token for temporary credentials
+    pub session_token: Option<String>,
+}
+
+impl Credentials {
+    /// Read credentials from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+    /// the optional `AWS_SESSION_TOKEN`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key ID or secret is not set
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        Ok(Self {
+            access_key_id: var("AWS_ACCESS_KEY_ID").context("AWS_ACCESS_KEY_ID is not set")?,
+            secret_access_key: var("AWS_SECRET_ACCESS_KEY").context("AWS_SECRET_ACCESS_KEY is not set")?,
+            session_token: var("AWS_SESSION_TOKEN"),
+        })
+    }
+}
+
+/// A bucket, or a prefix within one, the site is uploaded to
+#[derive(Debug)]
+pub struct S3Target {
+    client: Client,
+    credentials: Credentials,
+    region: String,
+    /// Base URL of the bucket, ending in `/`
+    bucket_url: Url,
+    /// Key prefix, empty or ending in `/`
+    prefix: String,
+    /// Key of the deploy manifest
+    manifest_key: String,
+}
+
+impl S3Target {
+    /// Target `bucket` in `region`, on AWS or at `endpoint`, with credentials
+    /// from the environment
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if credentials are missing or the endpoint is not a valid URL
+    pub fn new(bucket: &str, region: &str, endpoint: Option<&str>, prefix: Option<&str>) -> Result<Self> {
+        Self::with_credentials(Credentials::from_env()?, bucket, region, endpoint, prefix)
+    }
+
+    /// Target `bucket` using the given credentials
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is not a valid URL
+    pub fn with_credentials(
+        credentials: Credentials,
+        bucket: &str,
+        region: &str,
+        endpoint: Option<&str>,
+        prefix: Option<&str>,
+    ) -> Result<Self> {
+        let endpoint = match endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://s3.{}.amazonaws.com", region),
+        };
+        let bucket_url = Url::parse(&format!("{}/{}/", endpoint, bucket))
+            .with_context(|| format!("Invalid S3 endpoint '{}'", endpoint))?;
+
+        let prefix = prefix.map(|prefix| prefix.trim_matches('/')).unwrap_or_default();
+        let prefix = if prefix.is_empty() { String::new() } else { format!("{}/", prefix) };
+        let manifest_key = format!("{}{}manifest.json", MANIFEST_PREFIX, prefix);
+
+        Ok(Self {
+            client: Client::new(),
+            credentials,
+            region: region.to_string(),
+            bucket_url,
+            prefix,
+            manifest_key,
+        })
+    }
+
+    /// Keep the deploy manifest at `key` instead of under [`MANIFEST_PREFIX`]
+    pub fn with_manifest_key(mut self, key: &str) -> Self {
+        self.manifest_key = key.trim_start_matches('/').to_string();
+        self
+    }
+
+    /// Key of a site file under the prefix
+    fn site_key(&self, path: &str) -> String {
+        format!("{}{}", self.prefix, path)
+    }
+
+    /// Send a signed request for the object at `key`, with headers describing the object
+    fn send(&self, method: Method, key: &str, body: Vec<u8>, object_headers: &[(&str, &str)]) -> Result<Response> {
+        let url = self
+            .bucket_url
+            .join(&uri_encode(&key))
+            .with_context(|| format!("Invalid object key '{}'", key))?;
+
+        let mut host = url.host_str().unwrap_or_default().to_string();
+        if let Some(port) = url.port() {
+            host = format!("{}:{}", host, port);
+        }
+        let date_time = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let mut headers = vec![
+            ("host", host),
+            ("x-amz-content-sha256", payload_hash.clone()),
+            ("x-amz-date", date_time.clone()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+
+        let request = CanonicalRequest {
+            method: method.as_str(),
+            uri: url.path(),
+            query: "",
+            headers: headers.clone(),
+            payload_hash,
+        };
+        let authorization = request.authorization(&self.credentials, &date_time, &self.region, "s3");
+
+        let mut builder = self.client.request(method.clone(), url.clone()).header("authorization", authorization);
+        for (name, value) in headers.into_iter().filter(|(name, _)| *name != "host") {
+            builder = builder.header(name, value);
+        }
+        for (name, value) in object_headers {
+            builder = builder.header(*name, *value);
+        }
+
+        builder.body(body).send().with_context(|| format!("{} {} failed", method, url))
+    }
+}
+
+/// Fail with the response body when a request didn't succeed
+fn check(response: Response, action: &str) -> Result<Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    anyhow::bail!("Failed to {}: {} {}", action, status, body.trim())
+}
+
+impl DeployTarget for S3Target {
+    fn manifest(&mut self) -> Result<Manifest> {
+        // Earlier deploys kept the manifest among the site's files
+        for key in [self.manifest_key.clone(), self.site_key(MANIFEST_FILE)] {
+            let response = self.send(Method::GET, &key, Vec::new(), &[])?;
+            if response.status() == StatusCode::NOT_FOUND {
+                continue;
+            }
+
+            let body = check(response, "read the deploy manifest")?.text()?;
+            return serde_json::from_str(&body).context("Failed to parse the deploy manifest");
+        }
+
+        // A bucket that has never been deployed to has no manifest yet
+        Ok(Manifest::default())
+    }
+
+    fn upload(&mut self, path: &str, local_path: &Path) -> Result<()> {
+        let body = std::fs::read(local_path).with_context(|| format!("Failed to read {:?}", local_path))?;
+        let mut headers = vec![("content-type", content_type(path))];
+        if let Some(encoding) = content_encoding(path) {
+            headers.push(("content-encoding", encoding));
+        }
+
+        let response = self.send(Method::PUT, &self.site_key(path), body, &headers)?;
+        check(response, &format!("upload {}", path))?;
+        Ok(())
+    }
+
+    fn delete(&mut self, path: &str) -> Result<()> {
+        let response = self.send(Method::DELETE, &self.site_key(path), Vec::new(), &[])?;
+        check(response, &format!("delete {}", path))?;
+        Ok(())
+    }
+
+    fn finish(&mut self, manifest: &Manifest) -> Result<()> {
+        let body = serde_json::to_vec(manifest)?;
+        let response = self.send(Method::PUT, &self.manifest_key, body, &[("content-type", "application/json")])?;
+        check(response, "write the deploy manifest")?;
+
+        // Don't leave a manifest from an earlier deploy where the site is served
+        let response = self.send(Method::DELETE, &self.site_key(MANIFEST_FILE), Vec::new(), &[])?;
+        if response.status() != StatusCode::NOT_FOUND {
+            check(response, "remove the old deploy manifest")?;
+        }
+        Ok(())
+    }
+}
+
+/// A request in the canonical form that Signature Version 4 signs
+#[derive(Debug, Clone)]
+pub struct CanonicalRequest<'a> {
+    pub method: &'a str,
+    /// Path of the request, already URI-encoded
+    pub uri: &'a str,
+    /// Query string, already canonicalized
+    pub query: &'a str,
+    /// Headers to sign, with lowercase names
+    pub headers: Vec<(&'a str, String)>,
+    /// SHA-256 hash of the body, as lowercase hex
+    pub payload_hash: String,
+}
+
+impl CanonicalRequest<'_> {
+    /// The value of the `Authorization` header for this request
+    ///
+    /// `date_time` is the request time in the `x-amz-date` format,
+    /// `YYYYMMDD'T'HHMMSS'Z'`.
+    pub fn authorization(&self, credentials: &Credentials, date_time: &str, region: &str, service: &str) -> String {
+        let mut headers = self.headers.clone();
+        headers.sort();
+        let signed_headers: Vec<&str> = headers.iter().map(|(name, _)| *name).collect();
+        let signed_headers = signed_headers.join(";");
+
+        let mut canonical = format!("{}\n{}\n{}\n", self.method, self.uri, self.query);
+        for (name, value) in &headers {
+            canonical.push_str(&format!("{}:{}\n", name, value.trim()));
+        }
+        canonical.push_str(&format!("\n{}\n{}", signed_headers, self.payload_hash));
+
+        let date = &date_time[..8];
+        let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            date_time,
+            scope,
+            hex::encode(Sha256::digest(canonical.as_bytes()))
+        );
+
+        let key = format!("AWS4{}", credentials.secret_access_key);
+        let key = hmac(key.as_bytes(), date);
+        let key = hmac(&key, region);
+        let key = hmac(&key, service);
+        let key = hmac(&key, "aws4_request");
+        let signature = hex::encode(hmac(&key, &string_to_sign));
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            credentials.access_key_id, scope, signed_headers, signature
+        )
+    }
+}
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode an object key for a request path, keeping `/` separators
+pub fn uri_encode(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// The `Content-Type` to serve a file with, by extension
+///
+/// A precompressed copy of a file has the type of the original; see
+/// [`content_encoding`].
+pub fn content_type(path: &str) -> &'static str {
+    match precompressed(path) {
+        Some((original, _)) => media_type(original),
+        None => media_type(path),
+    }
+}
+
+/// The `Content-Encoding` of a precompressed copy of a file, such as `index.html.gz`
+///
+/// Only copies of files with a known type count, so an archive such as
+/// `backup.tar.gz` is served as it is.
+pub fn content_encoding(path: &str) -> Option<&'static str> {
+    precompressed(path).map(|(_, encoding)| encoding)
+}
+
+/// The original path and encoding of a precompressed copy of a file
+fn precompressed(path: &str) -> Option<(&str, &'static str)> {
+    let (original, extension) = path.rsplit_once('.')?;
+    let encoding = match extension.to_ascii_lowercase().as_str() {
+        "gz" => "gzip",
+        "br" => "br",
+        _ => return None,
+    };
+    (media_type(original) != OCTET_STREAM).then_some((original, encoding))
+}
+
+/// Media type of a file, by its last extension
+fn media_type(path: &str) -> &'static str {
+    let extension = path.rsplit_once('.').map(|(_, extension)| extension.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("html") | Some("htm") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("txt") => "text/plain; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("ico") => "image/x-icon",
+        Some("woff2") => "font/woff2",
+        Some("gz") => "application/gzip",
+        Some("br") => "application/x-brotli",
+        _ => OCTET_STREAM,
+    }
+}
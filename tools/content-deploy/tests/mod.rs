@@ -0,0 +1,5 @@
+//! Test modules for content-deploy
+
+// Unit tests
+#[path = "unit/deploy_tests.rs"]
+mod deploy_tests;
@@ -0,0 +1,197 @@
+use content_deploy::s3::{content_encoding, content_type, uri_encode, CanonicalRequest, Credentials};
+use content_deploy::{deploy_to, DeployOptions, DeployPlan, GitHubPagesTarget, LocalTarget, Manifest};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod deploy_tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn site() -> TempDir {
+        let output = TempDir::new().unwrap();
+        write(output.path(), "index.html", "<h1>Home</h1>");
+        write(output.path(), "blog/hello/index.html", "<h1>Hello</h1>");
+        write(output.path(), "rss.xml", "<rss/>");
+        output
+    }
+
+    fn manifest(files: &[(&str, &str)]) -> Manifest {
+        Manifest {
+            files: files.iter().map(|(path, hash)| (path.to_string(), hash.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_manifest_hashes_files_by_relative_path() {
+        let output = site();
+        write(output.path(), ".git/HEAD", "ref: refs/heads/main");
+        write(output.path(), ".deploy-manifest.json", "{}");
+
+        let manifest = Manifest::from_dir(output.path()).unwrap();
+        let paths: Vec<&str> = manifest.files.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["blog/hello/index.html", "index.html", "rss.xml"]);
+        // SHA-256 of "<rss/>"
+        assert_eq!(manifest.files["rss.xml"].len(), 64);
+        assert_ne!(manifest.files["index.html"], manifest.files["rss.xml"]);
+    }
+
+    #[test]
+    fn test_plan_compares_hashes() {
+        let local = manifest(&[("a.html", "1"), ("b.html", "2"), ("c.html", "3")]);
+        let remote = manifest(&[("a.html", "1"), ("b.html", "old"), ("gone.html", "4")]);
+
+        let plan = DeployPlan::between(&local, &remote);
+        assert_eq!(plan.upload, vec!["b.html", "c.html"]);
+        assert_eq!(plan.delete, vec!["gone.html"]);
+        assert_eq!(plan.unchanged, 1);
+    }
+
+    #[test]
+    fn test_local_deploy_uploads_only_changed_files() {
+        let output = site();
+        let destination = TempDir::new().unwrap();
+        let root = destination.path().join("www");
+        let mut target = LocalTarget::new(&root);
+
+        let report = deploy_to(output.path(), &mut target, &DeployOptions::default()).unwrap();
+        assert_eq!(report.uploaded.len(), 3);
+        assert_eq!(fs::read_to_string(root.join("blog/hello/index.html")).unwrap(), "<h1>Hello</h1>");
+
+        write(output.path(), "index.html", "<h1>Home, updated</h1>");
+        let report = deploy_to(output.path(), &mut target, &DeployOptions::default()).unwrap();
+        assert_eq!(report.uploaded, vec!["index.html"]);
+        assert_eq!(report.unchanged, 2);
+        assert_eq!(fs::read_to_string(root.join("index.html")).unwrap(), "<h1>Home, updated</h1>");
+
+        let report = deploy_to(output.path(), &mut target, &DeployOptions::default()).unwrap();
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn test_local_deploy_keeps_removed_files_unless_asked() {
+        let output = site();
+        let destination = TempDir::new().unwrap();
+        let mut target = LocalTarget::new(destination.path());
+        deploy_to(output.path(), &mut target, &DeployOptions::default()).unwrap();
+
+        fs::remove_dir_all(output.path().join("blog")).unwrap();
+        let report = deploy_to(output.path(), &mut target, &DeployOptions::default()).unwrap();
+        assert_eq!(report.kept, vec!["blog/hello/index.html"]);
+        assert!(destination.path().join("blog/hello/index.html").exists());
+
+        let options = DeployOptions { delete_removed: true, ..Default::default() };
+        let report = deploy_to(output.path(), &mut target, &options).unwrap();
+        assert_eq!(report.deleted, vec!["blog/hello/index.html"]);
+        // Directories left empty are removed too
+        assert!(!destination.path().join("blog").exists());
+        assert!(destination.path().join("index.html").exists());
+    }
+
+    #[test]
+    fn test_dry_run_changes_nothing() {
+        let output = site();
+        let destination = TempDir::new().unwrap();
+        let mut target = LocalTarget::new(destination.path().join("www"));
+
+        let options = DeployOptions { dry_run: true, ..Default::default() };
+        let report = deploy_to(output.path(), &mut target, &options).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.uploaded.len(), 3);
+        assert!(!destination.path().join("www").exists());
+    }
+
+    #[test]
+    fn test_deploy_requires_output() {
+        let destination = TempDir::new().unwrap();
+        let mut target = LocalTarget::new(destination.path());
+        let error = deploy_to(&destination.path().join("public"), &mut target, &DeployOptions::default()).unwrap_err();
+        assert!(error.to_string().contains("content-build"));
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git").arg("-C").arg(dir).args(args).output().unwrap();
+        assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_github_pages_deploy_commits_to_branch() {
+        let remote = TempDir::new().unwrap();
+        git(remote.path(), &["init", "-q", "--bare"]);
+        let remote_path = remote.path().to_str().unwrap();
+        let output = site();
+
+        let mut target = GitHubPagesTarget::new(remote_path, "gh-pages").unwrap();
+        let report = deploy_to(output.path(), &mut target, &DeployOptions::default()).unwrap();
+        assert_eq!(report.uploaded.len(), 3);
+        assert_eq!(git(remote.path(), &["show", "gh-pages:blog/hello/index.html"]), "<h1>Hello</h1>");
+
+        // A second deploy starts from the branch and commits only the change
+        write(output.path(), "rss.xml", "<rss version=\"2.0\"/>");
+        let mut target = GitHubPagesTarget::new(remote_path, "gh-pages").unwrap();
+        let report = deploy_to(output.path(), &mut target, &DeployOptions::default()).unwrap();
+        assert_eq!(report.uploaded, vec!["rss.xml"]);
+        assert_eq!(git(remote.path(), &["rev-list", "--count", "gh-pages"]), "2");
+        assert_eq!(
+            git(remote.path(), &["diff", "--name-only", "gh-pages~1", "gh-pages"]),
+            "rss.xml"
+        );
+    }
+
+    #[test]
+    fn test_sigv4_authorization() {
+        // The get-vanilla case from the AWS Signature Version 4 test suite
+        let credentials = Credentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        };
+        let request = CanonicalRequest {
+            method: "GET",
+            uri: "/",
+            query: "",
+            headers: vec![
+                ("x-amz-date", "20150830T123600Z".to_string()),
+                ("host", "example.amazonaws.com".to_string()),
+            ],
+            payload_hash: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+        };
+
+        assert_eq!(
+            request.authorization(&credentials, "20150830T123600Z", "us-east-1", "service"),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_object_keys_and_content_types() {
+        assert_eq!(uri_encode("blog/hello world/index.html"), "blog/hello%20world/index.html");
+        assert_eq!(uri_encode("café.html"), "caf%C3%A9.html");
+        assert_eq!(content_type("blog/index.html"), "text/html; charset=utf-8");
+        assert_eq!(content_type("images/hero.WEBP"), "image/webp");
+        assert_eq!(content_type("LICENSE"), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_precompressed_copies_keep_their_original_type() {
+        assert_eq!(content_type("blog/index.html.gz"), "text/html; charset=utf-8");
+        assert_eq!(content_encoding("blog/index.html.gz"), Some("gzip"));
+        assert_eq!(content_type("assets/site.css.br"), "text/css; charset=utf-8");
+        assert_eq!(content_encoding("assets/site.css.br"), Some("br"));
+
+        // Archives are downloads, not encoded pages
+        assert_eq!(content_type("downloads/backup.tar.gz"), "application/gzip");
+        assert_eq!(content_encoding("downloads/backup.tar.gz"), None);
+        assert_eq!(content_encoding("blog/index.html"), None);
+    }
+}
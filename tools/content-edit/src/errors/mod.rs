@@ -152,25 +152,26 @@ impl From<ContentEditError> for WritingError {
 
 impl From<WritingError> for ContentEditError {
     fn from(error: WritingError) -> Self {
-        match error {
+        // Context wraps the error that decides the variant
+        match error.root_cause() {
             WritingError::ContentNotFound(message) => {
                 ContentEditError::ContentNotFound {
-                    slug: message,
+                    slug: message.clone(),
                     topic: None,
                 }
             },
             WritingError::IoError(source) => {
                 ContentEditError::FileSystem {
-                    error: std::io::Error::new(std::io::ErrorKind::Other, source.to_string())
+                    error: std::io::Error::new(std::io::ErrorKind::Other, source.clone())
                 }
             },
             WritingError::ConfigError(message) => {
-                ContentEditError::Configuration { reason: message }
+                ContentEditError::Configuration { reason: message.clone() }
             },
             WritingError::ValidationError(message) => {
-                ContentEditError::Validation { reason: message }
+                ContentEditError::Validation { reason: message.clone() }
             },
             _ => ContentEditError::Other { message: error.to_string() }
         }
     }
-}
\ No newline at end of file
+}
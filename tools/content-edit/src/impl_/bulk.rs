@@ -0,0 +1,491 @@
+//! Bulk frontmatter editing implementation
+//!
+//! This module applies structured frontmatter changes to every article that
+//! matches a filter. Edits are made line by line on the raw frontmatter, so
+//! fields that are not touched keep their formatting, comments and order.
+
+use std::path::PathBuf;
+
+use common_fs::Journal;
+
+use crate::errors::ContentEditError;
+use crate::impl_::list::list_all_content;
+
+/// Selects the articles a bulk edit applies to
+///
+/// Every criterion that is set must match; an empty filter selects everything.
+#[derive(Debug, Clone, Default)]
+pub struct FrontmatterFilter {
+    /// Only articles in this topic
+    pub topic: Option<String>,
+    /// Only articles with this tag (case-insensitive)
+    pub tag: Option<String>,
+    /// Only articles whose draft status matches
+    pub draft: Option<bool>,
+}
+
+impl FrontmatterFilter {
+    /// Check whether an article in `topic` with the given frontmatter matches
+    pub fn matches(&self, topic: &str, frontmatter: &serde_yaml::Value) -> bool {
+        if let Some(wanted) = &self.topic {
+            if wanted != topic {
+                return false;
+            }
+        }
+
+        if let Some(wanted) = &self.tag {
+            if !tags_of(frontmatter).iter().any(|tag| tag.eq_ignore_ascii_case(wanted)) {
+                return false;
+            }
+        }
+
+        if let Some(wanted) = self.draft {
+            let is_draft = frontmatter
+                .get("draft")
+                .and_then(|draft| draft.as_bool())
+                .unwrap_or(false);
+            if is_draft != wanted {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// A structured change to an article's frontmatter
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontmatterChange {
+    /// Set a field, adding it if missing
+    Set {
+        /// Field name
+        field: String,
+        /// New value
+        value: serde_yaml::Value,
+    },
+    /// Remove a field
+    Remove {
+        /// Field name
+        field: String,
+    },
+    /// Rename a field, keeping its value and position
+    Rename {
+        /// Current field name
+        from: String,
+        /// New field name
+        to: String,
+    },
+    /// Add a tag if not already present
+    AddTag(String),
+    /// Remove a tag (case-insensitive)
+    RemoveTag(String),
+    /// Rename a tag in place (case-insensitive), dropping it if the new name is already present
+    RenameTag {
+        /// Current tag
+        from: String,
+        /// New tag
+        to: String,
+    },
+}
+
+/// The result of a bulk edit on one article
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterEdit {
+    /// Path to the content file
+    pub path: PathBuf,
+    /// Topic containing the content
+    pub topic: String,
+    /// Slug of the content
+    pub slug: String,
+    /// Frontmatter before the edit
+    pub original: String,
+    /// Frontmatter after the edit
+    pub updated: String,
+}
+
+impl FrontmatterEdit {
+    /// Describe the edit as removed (`-`) and added (`+`) frontmatter lines
+    pub fn diff(&self) -> Vec<String> {
+        let original: Vec<&str> = self.original.lines().collect();
+        let updated: Vec<&str> = self.updated.lines().collect();
+
+        let removed = original
+            .iter()
+            .filter(|line| !updated.contains(line))
+            .map(|line| format!("- {}", line));
+        let added = updated
+            .iter()
+            .filter(|line| !original.contains(line))
+            .map(|line| format!("+ {}", line));
+
+        removed.chain(added).collect()
+    }
+}
+
+/// Apply frontmatter changes to every article matching a filter.
+///
+/// Changes are applied in order. Articles whose frontmatter would not change
+/// are left alone and not reported. Every article is read and edited before
+/// any file is written, so an article that can't be edited stops the run
+/// with nothing changed. The files that are written are
+/// snapshotted in the journal first, so the whole edit can be undone with
+/// [`Journal::undo_last`].
+///
+/// # Arguments
+///
+/// * `filter` - Selects the articles to edit
+/// * `changes` - The changes to apply
+/// * `dry_run` - Report the edits without writing them
+///
+/// # Returns
+///
+/// Returns the edit made (or that would be made) to each changed article.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The content cannot be listed or read
+/// * An article's frontmatter is invalid, or would be invalid after editing
+/// * The content cannot be written
+///
+/// # Examples
+///
+/// ```no_run
+/// use content_edit::{update_frontmatter_fields, FrontmatterChange, FrontmatterFilter};
+///
+/// let filter = FrontmatterFilter {
+///     topic: Some("blog".to_string()),
+///     ..Default::default()
+/// };
+/// let changes = vec![
+///     FrontmatterChange::RemoveTag("draft-ideas".to_string()),
+///     FrontmatterChange::Rename { from: "date".to_string(), to: "published".to_string() },
+/// ];
+///
+/// for edit in update_frontmatter_fields(&filter, &changes, true).unwrap() {
+///     println!("{}", edit.path.display());
+///     for line in edit.diff() {
+///         println!("  {}", line);
+///     }
+/// }
+/// ```
+pub fn update_frontmatter_fields(
+    filter: &FrontmatterFilter,
+    changes: &[FrontmatterChange],
+    dry_run: bool,
+) -> Result<Vec<FrontmatterEdit>, ContentEditError> {
+    Ok(edit_frontmatter(filter, changes, dry_run, false)?.0)
+}
+
+/// Apply frontmatter changes like [`update_frontmatter_fields`], optionally
+/// skipping articles whose frontmatter is missing or invalid
+///
+/// Returns the edits along with the reason, naming the file, each article was skipped.
+pub(crate) fn edit_frontmatter(
+    filter: &FrontmatterFilter,
+    changes: &[FrontmatterChange],
+    dry_run: bool,
+    skip_invalid: bool,
+) -> Result<(Vec<FrontmatterEdit>, Vec<String>), ContentEditError> {
+    // Every file is read and edited in memory before anything is written, so
+    // one bad article stops the run without leaving it half applied
+    let mut planned = Vec::new();
+    let mut skipped = Vec::new();
+    for content in list_all_content()? {
+        let text = common_fs::read_file(&content.path)
+            .map_err(|e| ContentEditError::FileSystem {
+                error: std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+            })?;
+
+        let parsed = split_raw_frontmatter(&text)
+            .ok_or_else(|| ContentEditError::InvalidFormat {
+                reason: format!("No frontmatter found in {}", content.path.display())
+            })
+            .and_then(|(original, rest)| {
+                let frontmatter = parse_frontmatter(&original, &content.path)?;
+                Ok((original, rest, frontmatter))
+            });
+        let (original, rest, frontmatter) = match parsed {
+            Ok(parsed) => parsed,
+            Err(ContentEditError::InvalidFormat { reason }) if skip_invalid => {
+                skipped.push(reason);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if !filter.matches(&content.topic, &frontmatter) {
+            continue;
+        }
+
+        let updated = apply_changes(&original, changes)?;
+        if updated == original {
+            continue;
+        }
+        parse_frontmatter(&updated, &content.path)?;
+
+        let edit = FrontmatterEdit {
+            path: content.path,
+            topic: content.topic,
+            slug: content.slug,
+            original,
+            updated,
+        };
+        planned.push((edit, rest));
+    }
+
+    if !dry_run && !planned.is_empty() {
+        let config = common_config::load_config().map_err(|e| ContentEditError::Configuration {
+            reason: format!("Failed to load configuration: {}", e)
+        })?;
+        let description = changes.iter().map(describe_change).collect::<Vec<_>>().join(", ");
+        let mut transaction = Journal::for_base_dir(&config.content.base_dir)
+            .begin("edit", format!("Edit frontmatter: {}", description))?;
+
+        for (edit, _) in &planned {
+            transaction.snapshot(&edit.path)?;
+        }
+        for (edit, rest) in &planned {
+            common_fs::write_file(&edit.path, &join_raw_frontmatter(&edit.updated, rest))
+                .map_err(|e| ContentEditError::FileSystem {
+                    error: std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+                })?;
+        }
+
+        transaction.commit()?;
+    }
+
+    Ok((planned.into_iter().map(|(edit, _)| edit).collect(), skipped))
+}
+
+/// Summarize a change for the journal
+fn describe_change(change: &FrontmatterChange) -> String {
+    match change {
+        FrontmatterChange::Set { field, .. } => format!("set {}", field),
+        FrontmatterChange::Remove { field } => format!("remove {}", field),
+        FrontmatterChange::Rename { from, to } => format!("rename {} to {}", from, to),
+        FrontmatterChange::AddTag(tag) => format!("add tag {}", tag),
+        FrontmatterChange::RemoveTag(tag) => format!("remove tag {}", tag),
+        FrontmatterChange::RenameTag { from, to } => format!("rename tag {} to {}", from, to),
+    }
+}
+
+/// Apply changes to raw frontmatter text (without the `---` markers).
+///
+/// # Errors
+///
+/// Returns an error if the frontmatter cannot be parsed or a value cannot be serialized.
+pub fn apply_changes(frontmatter: &str, changes: &[FrontmatterChange]) -> Result<String, ContentEditError> {
+    let mut entries = split_entries(frontmatter);
+
+    for change in changes {
+        match change {
+            FrontmatterChange::Set { field, value } => {
+                let lines = render_field(field, value)?;
+                match find_entry(&entries, field) {
+                    Some(index) => entries[index] = lines,
+                    None => entries.push(lines),
+                }
+            }
+            FrontmatterChange::Remove { field } => {
+                if let Some(index) = find_entry(&entries, field) {
+                    entries.remove(index);
+                }
+            }
+            FrontmatterChange::Rename { from, to } => {
+                if let Some(index) = find_entry(&entries, from) {
+                    if let Some(existing) = find_entry(&entries, to) {
+                        if existing != index {
+                            return Err(ContentEditError::Validation {
+                                reason: format!("Cannot rename '{}' to '{}': field already exists", from, to)
+                            });
+                        }
+                    }
+                    let first = &entries[index][0];
+                    entries[index][0] = format!("{}{}", to, &first[from.len()..]);
+                }
+            }
+            FrontmatterChange::AddTag(_) | FrontmatterChange::RemoveTag(_) | FrontmatterChange::RenameTag { .. } => {
+                let index = find_entry(&entries, "tags");
+                let current = match index {
+                    Some(index) => tags_of(&parse_yaml(&entries[index].join("\n"))?),
+                    None => Vec::new(),
+                };
+
+                let tags = edit_tags(change, &current);
+                if tags == current {
+                    continue;
+                }
+
+                let lines = render_tags(&tags, index.map(|index| entries[index].as_slice()));
+                match index {
+                    Some(index) => entries[index] = lines,
+                    None => entries.push(lines),
+                }
+            }
+        }
+    }
+
+    Ok(entries
+        .iter()
+        .flatten()
+        .map(|line| format!("{}\n", line))
+        .collect())
+}
+
+/// Apply a tag change to a list of tags
+fn edit_tags(change: &FrontmatterChange, current: &[String]) -> Vec<String> {
+    let mut tags = current.to_vec();
+
+    match change {
+        FrontmatterChange::AddTag(tag) if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) => {
+            tags.push(tag.clone());
+        }
+        FrontmatterChange::RemoveTag(tag) => {
+            tags.retain(|existing| !existing.eq_ignore_ascii_case(tag));
+        }
+        FrontmatterChange::RenameTag { from, to } => {
+            let mut renamed: Vec<String> = Vec::new();
+            for tag in tags {
+                let tag = if tag.eq_ignore_ascii_case(from) { to.clone() } else { tag };
+                if !renamed.iter().any(|existing| existing.eq_ignore_ascii_case(&tag)) {
+                    renamed.push(tag);
+                }
+            }
+            tags = renamed;
+        }
+        _ => {}
+    }
+
+    tags
+}
+
+/// Split content into its raw frontmatter (with a trailing newline) and
+/// everything after the closing `---` marker
+///
+/// Content with `\r\n` line endings is recognised too; its raw frontmatter is
+/// returned with `\n` line endings, and [`join_raw_frontmatter`] restores them.
+pub(crate) fn split_raw_frontmatter(content: &str) -> Option<(String, String)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((rest[..offset].replace("\r\n", "\n"), rest[offset + 3..].to_string()));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Put raw frontmatter back in front of the content that followed it,
+/// using the line endings of that content
+pub(crate) fn join_raw_frontmatter(frontmatter: &str, rest: &str) -> String {
+    if rest.starts_with("\r\n") {
+        format!("---\r\n{}---{}", frontmatter.replace('\n', "\r\n"), rest)
+    } else {
+        format!("---\n{}---{}", frontmatter, rest)
+    }
+}
+
+/// Group frontmatter lines into top-level entries
+///
+/// Each entry starts with an unindented line; indented lines, list items and
+/// blank lines belong to the entry before them.
+fn split_entries(frontmatter: &str) -> Vec<Vec<String>> {
+    let mut entries: Vec<Vec<String>> = Vec::new();
+
+    for line in frontmatter.lines() {
+        let continues = line.is_empty() || line.starts_with(' ') || line.starts_with('\t') || line.starts_with("- ");
+        match entries.last_mut() {
+            Some(entry) if continues => entry.push(line.to_string()),
+            _ => entries.push(vec![line.to_string()]),
+        }
+    }
+
+    entries
+}
+
+/// Find the entry defining a top-level field
+fn find_entry(entries: &[Vec<String>], field: &str) -> Option<usize> {
+    entries.iter().position(|entry| {
+        entry[0]
+            .strip_prefix(field)
+            .is_some_and(|rest| rest.starts_with(':'))
+    })
+}
+
+/// Render a field as frontmatter lines
+fn render_field(field: &str, value: &serde_yaml::Value) -> Result<Vec<String>, ContentEditError> {
+    let rendered = serde_yaml::to_string(value)
+        .map_err(|e| ContentEditError::Validation {
+            reason: format!("Failed to serialize value for '{}': {}", field, e)
+        })?;
+
+    match value {
+        serde_yaml::Value::Sequence(items) if !items.is_empty() => Ok(nested(field, &rendered)),
+        serde_yaml::Value::Mapping(map) if !map.is_empty() => Ok(nested(field, &rendered)),
+        _ => Ok(vec![format!("{}: {}", field, rendered.trim_end())]),
+    }
+}
+
+/// Render a block value indented under its field
+fn nested(field: &str, rendered: &str) -> Vec<String> {
+    std::iter::once(format!("{}:", field))
+        .chain(rendered.lines().map(|line| format!("  {}", line)))
+        .collect()
+}
+
+/// Render a tags list, following the style of the existing entry if any
+fn render_tags(tags: &[String], existing: Option<&[String]>) -> Vec<String> {
+    let quote = |tag: &String| {
+        serde_yaml::to_string(tag)
+            .map(|rendered| rendered.trim_end().to_string())
+            .unwrap_or_else(|_| tag.clone())
+    };
+
+    let flow = existing.is_some_and(|lines| lines.len() == 1 && lines[0].contains('['));
+    if flow || tags.is_empty() {
+        let items: Vec<String> = tags.iter().map(quote).collect();
+        return vec![format!("tags: [{}]", items.join(", "))];
+    }
+
+    // Reuse the indentation of existing list items
+    let indent = existing
+        .and_then(|lines| lines.iter().skip(1).find(|line| line.trim_start().starts_with("- ")))
+        .map(|line| line[..line.len() - line.trim_start().len()].to_string())
+        .unwrap_or_else(|| "  ".to_string());
+
+    std::iter::once("tags:".to_string())
+        .chain(tags.iter().map(|tag| format!("{}- {}", indent, quote(tag))))
+        .collect()
+}
+
+/// Get the tags of parsed frontmatter
+pub(crate) fn tags_of(frontmatter: &serde_yaml::Value) -> Vec<String> {
+    frontmatter
+        .get("tags")
+        .and_then(|tags| tags.as_sequence())
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|tag| tag.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse YAML text
+fn parse_yaml(text: &str) -> Result<serde_yaml::Value, ContentEditError> {
+    serde_yaml::from_str(text).map_err(|e| ContentEditError::InvalidFormat {
+        reason: format!("Failed to parse frontmatter: {}", e)
+    })
+}
+
+/// Parse the frontmatter of a content file, naming the file on error
+fn parse_frontmatter(text: &str, path: &std::path::Path) -> Result<serde_yaml::Value, ContentEditError> {
+    parse_yaml(text).map_err(|e| ContentEditError::InvalidFormat {
+        reason: format!("{}: {}", path.display(), e)
+    })
+}
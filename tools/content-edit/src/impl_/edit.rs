@@ -341,14 +341,14 @@ pub fn update_frontmatter_field(slug: &str, topic: Option<&str>, field: &str, va
     if let Some(date) = &frontmatter.published_at {
         fm_mapping.insert(
             serde_yaml::Value::String("published".to_string()),
-            serde_yaml::Value::String(date.clone())
+            serde_yaml::Value::String(date.to_string())
         );
     }
 
     if let Some(updated) = &frontmatter.updated_at {
         fm_mapping.insert(
             serde_yaml::Value::String("updated".to_string()),
-            serde_yaml::Value::String(updated.clone())
+            serde_yaml::Value::String(updated.to_string())
         );
     }
 
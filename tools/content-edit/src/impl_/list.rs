@@ -71,16 +71,20 @@ pub fn list_all_content() -> Result<Vec<EditableContent>, ContentEditError> {
                 })?
                 .to_string();
 
-            // Look for index.md or index.mdx
-            let index_md = article_dir.join("index.md");
-            let index_mdx = article_dir.join("index.mdx");
+            // Look for {slug}.md, {slug}.mdx, index.md or index.mdx
+            let content_path = [
+                format!("{}.md", slug),
+                format!("{}.mdx", slug),
+                "index.md".to_string(),
+                "index.mdx".to_string(),
+            ]
+            .iter()
+            .map(|name| article_dir.join(name))
+            .find(|path| path.exists());
 
-            let content_path = if index_md.exists() {
-                index_md
-            } else if index_mdx.exists() {
-                index_mdx
-            } else {
-                continue;
+            let content_path = match content_path {
+                Some(path) => path,
+                None => continue,
             };
 
             // Extract title from frontmatter
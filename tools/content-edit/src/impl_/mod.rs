@@ -9,10 +9,18 @@ pub mod list;
 pub mod edit;
 pub mod frontmatter;
 pub mod editor;
+pub mod bulk;
+pub mod tags;
+pub mod sections;
+pub mod workflow;
 
 // Re-export the public functions for use by the lib.rs module
 pub use find::find_content_path;
 pub use list::list_all_content;
 pub use edit::{edit_content, save_edited_content, update_content, update_frontmatter_field};
 pub use frontmatter::{extract_frontmatter, extract_frontmatter_from_string, split_frontmatter_and_body};
-pub use editor::ContentEditorImpl;
\ No newline at end of file
+pub use editor::ContentEditorImpl;
+pub use bulk::{update_frontmatter_fields, apply_changes, FrontmatterChange, FrontmatterEdit, FrontmatterFilter};
+pub use sections::{append_to_section, insert_image_reference, replace_frontmatter_field, update_section};
+pub use workflow::{content_by_state, list_by_state, transition, transition_content};
+pub use tags::{delete_unused_tags, list_orphaned_tags, merge_tags, rename_tag, tag_usage, TagChangeReport};
\ No newline at end of file
@@ -0,0 +1,320 @@
+//! Section-targeted editing implementation
+//!
+//! This module edits one part of an article and leaves the rest of the file
+//! byte for byte as it was, so scripts and editor integrations can make
+//! small changes without reformatting the whole article.
+//!
+//! A section is a Markdown heading and everything after it up to the next
+//! heading of the same or a higher level. Headings are matched by their
+//! text, ignoring case; `## Setup` matches only a level-two heading, while
+//! `Setup` matches any level. Lines in fenced code blocks and in the
+//! frontmatter are never taken for headings.
+
+use std::ops::Range;
+use std::path::Path;
+
+use crate::errors::ContentEditError;
+use crate::impl_::bulk::{apply_changes, join_raw_frontmatter, split_raw_frontmatter, FrontmatterChange};
+use crate::impl_::find::find_content_path;
+
+/// Replace the body of a section, keeping its heading.
+///
+/// # Arguments
+///
+/// * `slug` - The slug of the content to edit
+/// * `topic` - Optional topic to narrow down the search
+/// * `heading` - The heading of the section, optionally with its `#` markers
+/// * `new_markdown` - The new body of the section
+///
+/// # Errors
+///
+/// Returns an error if the content or the section cannot be found, or the
+/// content cannot be read or written
+///
+/// # Examples
+///
+/// ```no_run
+/// use content_edit::update_section;
+///
+/// update_section("example-post", Some("blog"), "## Summary", "Shorter and clearer.").unwrap();
+/// ```
+pub fn update_section(slug: &str, topic: Option<&str>, heading: &str, new_markdown: &str) -> Result<(), ContentEditError> {
+    edit_file(slug, topic, |content| replace_section(content, heading, new_markdown))
+}
+
+/// Add Markdown to the end of a section.
+///
+/// # Arguments
+///
+/// * `slug` - The slug of the content to edit
+/// * `topic` - Optional topic to narrow down the search
+/// * `heading` - The heading of the section, optionally with its `#` markers
+/// * `markdown` - The Markdown to add
+///
+/// # Errors
+///
+/// Returns an error if the content or the section cannot be found, or the
+/// content cannot be read or written
+pub fn append_to_section(slug: &str, topic: Option<&str>, heading: &str, markdown: &str) -> Result<(), ContentEditError> {
+    edit_file(slug, topic, |content| append_section(content, heading, markdown))
+}
+
+/// Set one frontmatter field, leaving every other line of the file alone.
+///
+/// The field is added at the end of the frontmatter if it is missing.
+///
+/// # Arguments
+///
+/// * `slug` - The slug of the content to edit
+/// * `topic` - Optional topic to narrow down the search
+/// * `field` - The frontmatter field to set
+/// * `value` - The new value
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be found, has no frontmatter, or
+/// cannot be read or written
+///
+/// # Examples
+///
+/// ```no_run
+/// use content_edit::replace_frontmatter_field;
+///
+/// replace_frontmatter_field("example-post", None, "draft", serde_yaml::Value::Bool(false)).unwrap();
+/// ```
+pub fn replace_frontmatter_field(
+    slug: &str,
+    topic: Option<&str>,
+    field: &str,
+    value: serde_yaml::Value,
+) -> Result<(), ContentEditError> {
+    edit_file(slug, topic, |content| set_frontmatter_field(content, field, value))
+}
+
+/// Add an image to an article.
+///
+/// The image goes at the end of the section under `heading`, or at the end
+/// of the article when no heading is given.
+///
+/// # Arguments
+///
+/// * `slug` - The slug of the content to edit
+/// * `topic` - Optional topic to narrow down the search
+/// * `heading` - The section to add the image to, if any
+/// * `image_path` - Path or URL of the image
+/// * `alt` - Alternative text for the image
+///
+/// # Errors
+///
+/// Returns an error if the content or the section cannot be found, or the
+/// content cannot be read or written
+pub fn insert_image_reference(
+    slug: &str,
+    topic: Option<&str>,
+    heading: Option<&str>,
+    image_path: &str,
+    alt: &str,
+) -> Result<(), ContentEditError> {
+    edit_file(slug, topic, |content| insert_image(content, heading, image_path, alt))
+}
+
+/// Replace the body of the section under `heading` in `content`.
+///
+/// # Errors
+///
+/// Returns an error if the section cannot be found
+pub fn replace_section(content: &str, heading: &str, new_markdown: &str) -> Result<String, ContentEditError> {
+    let range = section_body(content, heading)?;
+    let (before, after) = (&content[..range.start], &content[range.end..]);
+
+    let body = new_markdown.trim_matches('\n');
+    let mut updated = String::from(before);
+    if !body.is_empty() {
+        updated.push('\n');
+        updated.push_str(body);
+        updated.push('\n');
+    }
+    if !after.is_empty() {
+        updated.push('\n');
+    }
+    updated.push_str(after);
+
+    Ok(updated)
+}
+
+/// Add Markdown to the end of the section under `heading` in `content`.
+///
+/// The Markdown is separated from the section's last paragraph by a blank line.
+///
+/// # Errors
+///
+/// Returns an error if the section cannot be found
+pub fn append_section(content: &str, heading: &str, markdown: &str) -> Result<String, ContentEditError> {
+    let body = section_body(content, heading)?;
+    Ok(append_at(content, body, markdown))
+}
+
+/// Set a frontmatter field in `content`, adding it if missing.
+///
+/// # Errors
+///
+/// Returns an error if the content has no frontmatter or the value cannot be serialized
+pub fn set_frontmatter_field(content: &str, field: &str, value: serde_yaml::Value) -> Result<String, ContentEditError> {
+    let (frontmatter, rest) = split_raw_frontmatter(content).ok_or_else(|| ContentEditError::InvalidFormat {
+        reason: "Content does not contain frontmatter".to_string()
+    })?;
+
+    let updated = apply_changes(&frontmatter, &[FrontmatterChange::Set { field: field.to_string(), value }])?;
+
+    Ok(join_raw_frontmatter(&updated, &rest))
+}
+
+/// Remove a frontmatter field from `content`, if present.
+///
+/// # Errors
+///
+/// Returns an error if the content has no frontmatter
+pub fn remove_frontmatter_field(content: &str, field: &str) -> Result<String, ContentEditError> {
+    let (frontmatter, rest) = split_raw_frontmatter(content).ok_or_else(|| ContentEditError::InvalidFormat {
+        reason: "Content does not contain frontmatter".to_string()
+    })?;
+
+    let updated = apply_changes(&frontmatter, &[FrontmatterChange::Remove { field: field.to_string() }])?;
+
+    Ok(join_raw_frontmatter(&updated, &rest))
+}
+
+/// Add an image reference to `content`, at the end of the section under
+/// `heading` or at the end of the article.
+///
+/// # Errors
+///
+/// Returns an error if the section cannot be found
+pub fn insert_image(content: &str, heading: Option<&str>, image_path: &str, alt: &str) -> Result<String, ContentEditError> {
+    let reference = format!("![{}]({})", alt.replace(']', "\\]"), image_path);
+
+    let range = match heading {
+        Some(heading) => section_body(content, heading)?,
+        None => body_start(content)..content.len(),
+    };
+
+    Ok(append_at(content, range, &reference))
+}
+
+/// Find the body of the section under `heading`: the lines after the
+/// heading, up to the next heading of the same or a higher level
+fn section_body(content: &str, heading: &str) -> Result<Range<usize>, ContentEditError> {
+    let wanted_level = heading.trim().chars().take_while(|c| *c == '#').count();
+    let wanted_text = heading.trim().trim_start_matches('#').trim();
+
+    let headings = headings(content);
+    let index = headings
+        .iter()
+        .position(|(_, level, text)| {
+            (wanted_level == 0 || *level == wanted_level) && text.eq_ignore_ascii_case(wanted_text)
+        })
+        .ok_or_else(|| ContentEditError::Validation {
+            reason: format!("Section '{}' not found", heading.trim())
+        })?;
+
+    let (line, level, _) = &headings[index];
+    let end = headings[index + 1..]
+        .iter()
+        .find(|(_, next_level, _)| next_level <= level)
+        .map_or(content.len(), |(next_line, _, _)| next_line.start);
+
+    Ok(line.end..end)
+}
+
+/// ATX headings in the body: each heading's line (with its newline), level and text
+fn headings(content: &str) -> Vec<(Range<usize>, usize, &str)> {
+    let mut headings = Vec::new();
+    let mut offset = body_start(content);
+    let mut fence: Option<&str> = None;
+
+    for line in content[offset..].split_inclusive('\n') {
+        let range = offset..offset + line.len();
+        offset += line.len();
+
+        let trimmed = line.trim();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+
+        let level = line.chars().take_while(|c| *c == '#').count();
+        let text = &line[level..];
+        if (1..=6).contains(&level) && (text.starts_with(' ') || text.trim().is_empty()) {
+            headings.push((range, level, text.trim().trim_end_matches('#').trim_end()));
+        }
+    }
+
+    headings
+}
+
+/// Offset of the first byte after the frontmatter
+fn body_start(content: &str) -> usize {
+    match split_raw_frontmatter(content) {
+        // Skip the rest of the closing `---` line
+        Some((_, rest)) => {
+            let body = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n')).unwrap_or(rest.as_str());
+            content.len() - body.len()
+        }
+        None => 0,
+    }
+}
+
+/// Add `markdown` at the end of `range`, after a blank line, keeping what follows
+fn append_at(content: &str, range: Range<usize>, markdown: &str) -> String {
+    let existing = content[range.start..range.end].trim_end();
+    let after = &content[range.end..];
+
+    // A blank line separates the addition from the heading or the text before it
+    let mut updated = String::from(&content[..range.start]);
+    updated.push_str(existing);
+    if !existing.is_empty() {
+        updated.push_str("\n\n");
+    } else if range.start > 0 {
+        updated.push('\n');
+    }
+    updated.push_str(markdown.trim_matches('\n'));
+    updated.push('\n');
+    if !after.is_empty() {
+        updated.push('\n');
+    }
+    updated.push_str(after);
+
+    updated
+}
+
+/// Read a content file, apply an edit and write it back if it changed
+fn edit_file<F>(slug: &str, topic: Option<&str>, edit: F) -> Result<(), ContentEditError>
+where
+    F: FnOnce(&str) -> Result<String, ContentEditError>,
+{
+    let content_path = find_content_path(slug, topic)?;
+    let content = read(&content_path)?;
+
+    let updated = edit(&content)?;
+    if updated == content {
+        return Ok(());
+    }
+
+    common_fs::write_file(&content_path, &updated)
+        .map_err(|e| ContentEditError::FileSystem {
+            error: std::io::Error::other(e.to_string())
+        })
+}
+
+fn read(path: &Path) -> Result<String, ContentEditError> {
+    common_fs::read_file(path)
+        .map_err(|e| ContentEditError::FileSystem {
+            error: std::io::Error::other(e.to_string())
+        })
+}
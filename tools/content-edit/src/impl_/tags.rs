@@ -0,0 +1,234 @@
+//! Tag management implementation
+//!
+//! This module renames, merges and prunes tags across all content, keeping
+//! the tag categories in `config.content.tags` in step with the frontmatter.
+
+use std::collections::BTreeMap;
+
+use common_models::Config;
+
+use crate::errors::ContentEditError;
+use crate::impl_::bulk::{edit_frontmatter, tags_of, FrontmatterChange, FrontmatterEdit, FrontmatterFilter};
+use crate::impl_::list::list_all_content;
+
+/// The changes made (or that would be made) by a tag operation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagChangeReport {
+    /// Frontmatter edits, one per changed content file
+    pub edits: Vec<FrontmatterEdit>,
+    /// Changes to the tag categories in the configuration
+    pub config_changes: Vec<String>,
+    /// Content files left alone because their frontmatter could not be read
+    pub skipped: Vec<String>,
+}
+
+/// Rename a tag in the frontmatter of all content and in the configuration.
+///
+/// Tags are matched case-insensitively. Content that already has the new tag
+/// just loses the old one.
+///
+/// # Arguments
+///
+/// * `from` - The tag to rename
+/// * `to` - The new tag name
+/// * `dry_run` - Report the changes without writing them
+///
+/// # Errors
+///
+/// Returns an error if content or configuration cannot be read or written.
+pub fn rename_tag(from: &str, to: &str, dry_run: bool) -> Result<TagChangeReport, ContentEditError> {
+    merge_tags(&[from.to_string()], to, dry_run)
+}
+
+/// Merge several tags into one across all content and the configuration.
+///
+/// Each source tag is replaced by `target` in place. In the configuration, the
+/// source tags are removed from their categories and `target` is added to the
+/// category of the first source tag, unless it is already configured.
+/// Content without valid frontmatter is skipped and listed in the report,
+/// as [`tag_usage`] ignores it.
+///
+/// # Arguments
+///
+/// * `sources` - The tags to merge
+/// * `target` - The tag to merge them into
+/// * `dry_run` - Report the changes without writing them
+///
+/// # Errors
+///
+/// Returns an error if content or configuration cannot be read or written.
+pub fn merge_tags(sources: &[String], target: &str, dry_run: bool) -> Result<TagChangeReport, ContentEditError> {
+    let mut edits: Vec<FrontmatterEdit> = Vec::new();
+    let mut skipped: Vec<String> = Vec::new();
+
+    for source in sources {
+        let filter = FrontmatterFilter {
+            tag: Some(source.clone()),
+            ..Default::default()
+        };
+        let changes = [FrontmatterChange::RenameTag {
+            from: source.clone(),
+            to: target.to_string(),
+        }];
+
+        let (source_edits, source_skipped) = edit_frontmatter(&filter, &changes, dry_run, true)?;
+        for reason in source_skipped {
+            if !skipped.contains(&reason) {
+                skipped.push(reason);
+            }
+        }
+
+        for edit in source_edits {
+            // In a dry run each source is planned against the original file,
+            // so fold later edits into the earlier report for the same file
+            match edits.iter_mut().find(|existing| existing.path == edit.path) {
+                Some(existing) if dry_run => {
+                    existing.updated = crate::impl_::bulk::apply_changes(&existing.updated, &changes)?;
+                }
+                Some(existing) => existing.updated = edit.updated,
+                None => edits.push(edit),
+            }
+        }
+    }
+
+    let mut config = load_config()?;
+    let mut config_changes = Vec::new();
+    let categories = config.content.tags.get_or_insert_with(Default::default);
+
+    let mut names: Vec<String> = categories.keys().cloned().collect();
+    names.sort();
+
+    let mut target_category = None;
+    for category in names {
+        let tags = categories.get_mut(&category).expect("category exists");
+        let removed: Vec<String> = tags
+            .iter()
+            .filter(|tag| sources.iter().any(|source| tag.eq_ignore_ascii_case(source)))
+            .cloned()
+            .collect();
+        if !removed.is_empty() {
+            tags.retain(|tag| !removed.contains(tag));
+            config_changes.push(format!("Removed {} from category '{}'", removed.join(", "), category));
+            target_category.get_or_insert(category);
+        }
+    }
+
+    let target_configured = categories
+        .values()
+        .flatten()
+        .any(|tag| tag.eq_ignore_ascii_case(target));
+    if let (Some(category), false) = (target_category, target_configured) {
+        if let Some(tags) = categories.get_mut(&category) {
+            tags.push(target.to_string());
+            config_changes.push(format!("Added {} to category '{}'", target, category));
+        }
+    }
+
+    if !config_changes.is_empty() && !dry_run {
+        save_config(&config)?;
+    }
+
+    Ok(TagChangeReport {
+        edits,
+        config_changes,
+        skipped,
+    })
+}
+
+/// Count how many content files use each tag.
+///
+/// # Errors
+///
+/// Returns an error if content cannot be listed or read.
+pub fn tag_usage() -> Result<BTreeMap<String, usize>, ContentEditError> {
+    let mut usage = BTreeMap::new();
+
+    for content in list_all_content()? {
+        let text = common_fs::read_file(&content.path)
+            .map_err(|e| ContentEditError::FileSystem {
+                error: std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+            })?;
+
+        // Files without valid frontmatter have no tags
+        let frontmatter = match crate::impl_::frontmatter::extract_frontmatter_from_string(&text) {
+            Ok(frontmatter) => frontmatter,
+            Err(_) => continue,
+        };
+
+        for tag in tags_of(&frontmatter) {
+            *usage.entry(tag).or_insert(0) += 1;
+        }
+    }
+
+    Ok(usage)
+}
+
+/// List tags used in content that are not in any `config.content.tags` category.
+///
+/// # Errors
+///
+/// Returns an error if content or configuration cannot be read.
+pub fn list_orphaned_tags() -> Result<Vec<String>, ContentEditError> {
+    let config = load_config()?;
+    let configured: Vec<&String> = config.content.tags.iter().flat_map(|categories| categories.values().flatten()).collect();
+
+    Ok(tag_usage()?
+        .into_keys()
+        .filter(|tag| !configured.iter().any(|known| known.eq_ignore_ascii_case(tag)))
+        .collect())
+}
+
+/// Remove tags from `config.content.tags` that no content uses.
+///
+/// # Arguments
+///
+/// * `dry_run` - Report the changes without writing them
+///
+/// # Errors
+///
+/// Returns an error if content or configuration cannot be read or written.
+pub fn delete_unused_tags(dry_run: bool) -> Result<TagChangeReport, ContentEditError> {
+    let usage = tag_usage()?;
+    let is_used = |tag: &String| usage.keys().any(|used| used.eq_ignore_ascii_case(tag));
+
+    let mut config = load_config()?;
+    let mut config_changes = Vec::new();
+
+    if let Some(categories) = config.content.tags.as_mut() {
+        let mut names: Vec<String> = categories.keys().cloned().collect();
+        names.sort();
+
+        for category in names {
+            let tags = categories.get_mut(&category).expect("category exists");
+            let unused: Vec<String> = tags.iter().filter(|tag| !is_used(tag)).cloned().collect();
+            if !unused.is_empty() {
+                tags.retain(|tag| is_used(tag));
+                config_changes.push(format!("Removed {} from category '{}'", unused.join(", "), category));
+            }
+        }
+    }
+
+    if !config_changes.is_empty() && !dry_run {
+        save_config(&config)?;
+    }
+
+    Ok(TagChangeReport {
+        edits: Vec::new(),
+        config_changes,
+        skipped: Vec::new(),
+    })
+}
+
+/// Load the configuration
+fn load_config() -> Result<Config, ContentEditError> {
+    common_config::load_config().map_err(|e| ContentEditError::Configuration {
+        reason: format!("Failed to load configuration: {}", e)
+    })
+}
+
+/// Save the configuration
+fn save_config(config: &Config) -> Result<(), ContentEditError> {
+    common_config::save_config(config).map_err(|e| ContentEditError::Configuration {
+        reason: format!("Failed to save configuration: {}", e)
+    })
+}
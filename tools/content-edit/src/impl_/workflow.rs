@@ -0,0 +1,174 @@
+//! Editorial workflow implementation
+//!
+//! This module moves content between workflow states (idea, draft, review,
+//! published, archived) and lists content by state for editorial
+//! dashboards. The state lives in the `state` frontmatter field, and a
+//! transition only rewrites the fields it owns:
+//!
+//! * `state` - the new state
+//! * `draft` - `false` only while published
+//! * `archived` - `true` while archived, removed otherwise
+//! * `reviewed_at` - the date content leaves review
+//! * `published` - the date content is first published, if not already set
+
+use std::collections::BTreeMap;
+
+use chrono::{Local, NaiveDate};
+use common_models::workflow::STATE_FIELD;
+use common_models::{ContentState, Frontmatter};
+use serde_yaml::Value;
+
+use crate::errors::ContentEditError;
+use crate::impl_::bulk::{apply_changes, join_raw_frontmatter, split_raw_frontmatter, FrontmatterChange};
+use crate::impl_::find::find_content_path;
+use crate::impl_::frontmatter::extract_frontmatter;
+use crate::impl_::list::list_all_content;
+use crate::models::EditableContent;
+
+/// Move content to a new workflow state.
+///
+/// # Arguments
+///
+/// * `slug` - The slug of the content to move
+/// * `topic` - Optional topic to narrow down the search
+/// * `new_state` - The state to move the content to
+///
+/// # Returns
+///
+/// Returns the state the content was in before the transition
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be found, read or written, or the
+/// transition is not allowed from the content's current state
+///
+/// # Examples
+///
+/// ```no_run
+/// use common_models::ContentState;
+/// use content_edit::transition;
+///
+/// let previous = transition("example-post", Some("blog"), ContentState::Review).unwrap();
+/// println!("Moved from {} to review", previous);
+/// ```
+pub fn transition(slug: &str, topic: Option<&str>, new_state: ContentState) -> Result<ContentState, ContentEditError> {
+    let content_path = find_content_path(slug, topic)?;
+    let content = common_fs::read_file(&content_path)
+        .map_err(|e| ContentEditError::FileSystem {
+            error: std::io::Error::other(e.to_string())
+        })?;
+
+    let previous = current_state(&content)?;
+    let updated = transition_content(&content, new_state, Local::now().date_naive())?;
+
+    common_fs::write_file(&content_path, &updated)
+        .map_err(|e| ContentEditError::FileSystem {
+            error: std::io::Error::other(e.to_string())
+        })?;
+
+    Ok(previous)
+}
+
+/// Move `content` to a new workflow state, timestamping with `today`.
+///
+/// # Errors
+///
+/// Returns an error if the content has no valid frontmatter or the
+/// transition is not allowed from the content's current state
+pub fn transition_content(content: &str, new_state: ContentState, today: NaiveDate) -> Result<String, ContentEditError> {
+    let (raw, rest) = split_raw_frontmatter(content).ok_or_else(|| ContentEditError::InvalidFormat {
+        reason: "Content does not contain frontmatter".to_string()
+    })?;
+    let frontmatter = parse_frontmatter(&raw)?;
+
+    let current = frontmatter.content_state();
+    if !current.can_transition_to(new_state) {
+        let allowed: Vec<&str> = current.allowed_transitions().iter().map(|state| state.name()).collect();
+        return Err(ContentEditError::Validation {
+            reason: format!(
+                "Cannot move content from {} to {}; allowed: {}",
+                current, new_state, allowed.join(", ")
+            )
+        });
+    }
+
+    let date = Value::String(today.format("%Y-%m-%d").to_string());
+    let mut changes = vec![
+        FrontmatterChange::Set { field: STATE_FIELD.to_string(), value: Value::String(new_state.name().to_string()) },
+        FrontmatterChange::Set { field: "draft".to_string(), value: Value::Bool(!new_state.is_public()) },
+    ];
+
+    if new_state == ContentState::Archived {
+        changes.push(FrontmatterChange::Set { field: "archived".to_string(), value: Value::Bool(true) });
+    } else {
+        changes.push(FrontmatterChange::Remove { field: "archived".to_string() });
+    }
+    if current == ContentState::Review {
+        changes.push(FrontmatterChange::Set { field: "reviewed_at".to_string(), value: date.clone() });
+    }
+    let has_published_date = frontmatter.published_at.as_ref().is_some_and(|published| !published.is_draft());
+    if new_state == ContentState::Published && !has_published_date {
+        changes.push(FrontmatterChange::Set { field: "published".to_string(), value: date });
+    }
+
+    let updated = apply_changes(&raw, &changes)?;
+
+    Ok(join_raw_frontmatter(&updated, &rest))
+}
+
+/// List the content in one workflow state.
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be listed or its frontmatter
+/// cannot be read
+///
+/// # Examples
+///
+/// ```no_run
+/// use common_models::ContentState;
+/// use content_edit::list_by_state;
+///
+/// for content in list_by_state(ContentState::Review).unwrap() {
+///     println!("Waiting for review: {} ({})", content.title, content.topic);
+/// }
+/// ```
+pub fn list_by_state(state: ContentState) -> Result<Vec<EditableContent>, ContentEditError> {
+    Ok(content_by_state()?.remove(&state).unwrap_or_default())
+}
+
+/// Group all content by workflow state.
+///
+/// Every state is present in the result, with an empty list when no content
+/// is in it, so dashboards can show a column for each state.
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be listed or its frontmatter
+/// cannot be read
+pub fn content_by_state() -> Result<BTreeMap<ContentState, Vec<EditableContent>>, ContentEditError> {
+    let mut by_state: BTreeMap<ContentState, Vec<EditableContent>> =
+        ContentState::ALL.into_iter().map(|state| (state, Vec::new())).collect();
+
+    for content in list_all_content()? {
+        let state = extract_frontmatter(&content.path)?.content_state();
+        by_state.entry(state).or_default().push(content);
+    }
+
+    Ok(by_state)
+}
+
+/// The workflow state of `content`
+fn current_state(content: &str) -> Result<ContentState, ContentEditError> {
+    let (raw, _) = split_raw_frontmatter(content).ok_or_else(|| ContentEditError::InvalidFormat {
+        reason: "Content does not contain frontmatter".to_string()
+    })?;
+
+    Ok(parse_frontmatter(&raw)?.content_state())
+}
+
+fn parse_frontmatter(raw: &str) -> Result<Frontmatter, ContentEditError> {
+    serde_yaml::from_str(raw).map_err(|e| ContentEditError::InvalidFormat {
+        reason: format!("Failed to parse frontmatter: {}", e)
+    })
+}
@@ -7,6 +7,10 @@
 pub mod unit {
     pub mod content_edit_tests;
     pub mod content_editor_tests;
+    pub mod bulk_edit_tests;
+    pub mod tag_management_tests;
+    pub mod section_edit_tests;
+    pub mod workflow_tests;
 }
 
 // Integration tests
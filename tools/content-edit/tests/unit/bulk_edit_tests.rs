@@ -0,0 +1,126 @@
+//! Tests for bulk frontmatter editing
+
+use common_test_utils::{use_config, write_article, write_repository};
+use content_edit::{apply_changes, update_frontmatter_fields, FrontmatterChange, FrontmatterFilter};
+use std::fs;
+use tempfile::TempDir;
+
+const FRONTMATTER: &str = r#"title: "Hello"
+# Publication details
+date: 2024-01-01
+draft: false
+tags:
+    - rust
+    - Async
+series: basics
+"#;
+
+#[test]
+fn test_set_and_remove_keep_other_lines() {
+    let changes = vec![
+        FrontmatterChange::Set {
+            field: "draft".to_string(),
+            value: serde_yaml::Value::Bool(true),
+        },
+        FrontmatterChange::Set {
+            field: "author".to_string(),
+            value: serde_yaml::Value::String("Evan".to_string()),
+        },
+        FrontmatterChange::Remove {
+            field: "series".to_string(),
+        },
+    ];
+
+    let updated = apply_changes(FRONTMATTER, &changes).unwrap();
+
+    assert_eq!(
+        updated,
+        "title: \"Hello\"\n# Publication details\ndate: 2024-01-01\ndraft: true\ntags:\n    - rust\n    - Async\nauthor: Evan\n"
+    );
+}
+
+#[test]
+fn test_rename_keeps_value_and_position() {
+    let changes = vec![FrontmatterChange::Rename {
+        from: "date".to_string(),
+        to: "published".to_string(),
+    }];
+
+    let updated = apply_changes(FRONTMATTER, &changes).unwrap();
+    assert!(updated.contains("# Publication details\npublished: 2024-01-01\ndraft: false\n"));
+
+    let clash = vec![FrontmatterChange::Rename {
+        from: "date".to_string(),
+        to: "draft".to_string(),
+    }];
+    assert!(apply_changes(FRONTMATTER, &clash).is_err());
+}
+
+#[test]
+fn test_tags_follow_existing_style() {
+    let changes = vec![
+        FrontmatterChange::AddTag("tokio".to_string()),
+        FrontmatterChange::AddTag("RUST".to_string()),
+        FrontmatterChange::RemoveTag("async".to_string()),
+    ];
+
+    let updated = apply_changes(FRONTMATTER, &changes).unwrap();
+    assert!(updated.contains("tags:\n    - rust\n    - tokio\nseries: basics\n"));
+
+    let flow = apply_changes("title: Hi\ntags: [a, b]\n", &[FrontmatterChange::AddTag("c".to_string())]).unwrap();
+    assert_eq!(flow, "title: Hi\ntags: [a, b, c]\n");
+
+    let added = apply_changes("title: Hi\n", &[FrontmatterChange::AddTag("new".to_string())]).unwrap();
+    assert_eq!(added, "title: Hi\ntags:\n  - new\n");
+}
+
+#[test]
+fn test_filter_matches_topic_tag_and_draft() {
+    let frontmatter: serde_yaml::Value = serde_yaml::from_str(FRONTMATTER).unwrap();
+
+    assert!(FrontmatterFilter::default().matches("blog", &frontmatter));
+
+    let filter = FrontmatterFilter {
+        topic: Some("blog".to_string()),
+        tag: Some("async".to_string()),
+        draft: Some(false),
+    };
+    assert!(filter.matches("blog", &frontmatter));
+    assert!(!filter.matches("notes", &frontmatter));
+
+    let drafts = FrontmatterFilter {
+        draft: Some(true),
+        ..Default::default()
+    };
+    assert!(!drafts.matches("blog", &frontmatter));
+}
+
+#[test]
+fn test_update_writes_nothing_when_an_article_cannot_be_edited() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let content = root.join("content");
+    let first = write_article(&content, "blog", "first", "---\ntitle: First\n---\nBody\n");
+    let windows = write_article(&content, "blog", "windows", "---\r\ntitle: Windows\r\n---\r\nBody\r\n");
+    let _config = use_config(&write_repository(root, &["blog"]));
+
+    let changes = vec![FrontmatterChange::Set {
+        field: "draft".to_string(),
+        value: serde_yaml::Value::Bool(true),
+    }];
+
+    // Content with `\r\n` line endings is edited like any other, and keeps them
+    let edits = update_frontmatter_fields(&FrontmatterFilter::default(), &changes, false).unwrap();
+    assert_eq!(edits.len(), 2);
+    assert_eq!(fs::read_to_string(&first).unwrap(), "---\ntitle: First\ndraft: true\n---\nBody\n");
+    assert_eq!(fs::read_to_string(&windows).unwrap(), "---\r\ntitle: Windows\r\ndraft: true\r\n---\r\nBody\r\n");
+
+    // One article without frontmatter stops the run before anything is written
+    write_article(&content, "blog", "broken", "No frontmatter\n");
+    let changes = vec![FrontmatterChange::Remove {
+        field: "draft".to_string(),
+    }];
+    let err = update_frontmatter_fields(&FrontmatterFilter::default(), &changes, false).unwrap_err();
+    assert!(err.to_string().contains("broken"));
+    assert_eq!(fs::read_to_string(&first).unwrap(), "---\ntitle: First\ndraft: true\n---\nBody\n");
+}
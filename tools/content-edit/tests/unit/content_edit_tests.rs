@@ -426,6 +426,24 @@ This is a test post."#;
     }
 }
 
+/// Errors wrapped in context keep the variant of their root cause
+#[test]
+fn test_writing_errors_map_by_root_cause() {
+    use common_errors::WritingError;
+
+    let error = WritingError::content_not_found("hello").with_context("Editing blog/hello");
+    assert!(matches!(
+        ContentEditError::from(error),
+        ContentEditError::ContentNotFound { slug, topic: None } if slug == "hello"
+    ));
+
+    let error = WritingError::validation_error("Title is required").with_context("Saving").with_context("Editing");
+    assert!(matches!(
+        ContentEditError::from(error),
+        ContentEditError::Validation { reason } if reason == "Title is required"
+    ));
+}
+
 /*
 mod list_content_tests {
     // This test module relies on mock implementations
@@ -0,0 +1,104 @@
+//! Tests for section-targeted editing
+
+use content_edit::sections::{
+    append_section, insert_image, remove_frontmatter_field, replace_section, set_frontmatter_field,
+};
+
+const ARTICLE: &str = "---
+title: Example # keep this comment
+tags: [rust]
+---
+
+# Example
+
+Intro paragraph.
+
+## Setup
+
+Install the tools.
+
+```sh
+## not a heading
+```
+
+### Details
+
+Nested detail.
+
+## Usage
+
+Run it.
+";
+
+#[test]
+fn test_replace_section_keeps_surrounding_content() {
+    let updated = replace_section(ARTICLE, "## Setup", "New setup text.\n").unwrap();
+
+    assert!(updated.starts_with("---\ntitle: Example # keep this comment\n"));
+    assert!(updated.contains("## Setup\n\nNew setup text.\n\n## Usage\n\nRun it.\n"));
+    assert!(!updated.contains("Nested detail."));
+
+    // A nested section ends at the next heading of the same or a higher level
+    let updated = replace_section(ARTICLE, "details", "Less detail.").unwrap();
+    assert!(updated.contains("### Details\n\nLess detail.\n\n## Usage"));
+    assert!(updated.contains("## not a heading"));
+}
+
+#[test]
+fn test_missing_section_is_an_error() {
+    assert!(replace_section(ARTICLE, "Conclusion", "Done.").is_err());
+    assert!(replace_section(ARTICLE, "not a heading", "Done.").is_err());
+    assert!(replace_section(ARTICLE, "### Setup", "Done.").is_err());
+}
+
+#[test]
+fn test_append_section() {
+    let updated = append_section(ARTICLE, "Usage", "Run it again.").unwrap();
+    assert!(updated.ends_with("## Usage\n\nRun it.\n\nRun it again.\n"));
+
+    let updated = append_section(ARTICLE, "Setup", "- step").unwrap();
+    assert!(updated.contains("Nested detail.\n\n- step\n\n## Usage"));
+}
+
+#[test]
+fn test_set_frontmatter_field_preserves_other_lines() {
+    let updated = set_frontmatter_field(ARTICLE, "draft", serde_yaml::Value::Bool(true)).unwrap();
+    assert!(updated.starts_with("---\ntitle: Example # keep this comment\ntags: [rust]\ndraft: true\n---\n\n# Example"));
+
+    let updated = set_frontmatter_field(ARTICLE, "title", "New Title".into()).unwrap();
+    assert!(updated.starts_with("---\ntitle: New Title\ntags: [rust]\n---"));
+
+    assert!(set_frontmatter_field("# No frontmatter\n", "title", "x".into()).is_err());
+}
+
+#[test]
+fn test_frontmatter_edits_keep_crlf_line_endings() {
+    let article = "---\r\ntitle: Example\r\ntags: [rust]\r\n---\r\n\r\n# Example\r\n";
+
+    let updated = set_frontmatter_field(article, "draft", serde_yaml::Value::Bool(true)).unwrap();
+    assert_eq!(updated, "---\r\ntitle: Example\r\ntags: [rust]\r\ndraft: true\r\n---\r\n\r\n# Example\r\n");
+
+    let updated = remove_frontmatter_field(article, "tags").unwrap();
+    assert_eq!(updated, "---\r\ntitle: Example\r\n---\r\n\r\n# Example\r\n");
+}
+
+#[test]
+fn test_remove_frontmatter_field_preserves_other_lines() {
+    let updated = remove_frontmatter_field(ARTICLE, "tags").unwrap();
+    assert!(updated.starts_with("---\ntitle: Example # keep this comment\n---\n\n# Example"));
+
+    assert_eq!(remove_frontmatter_field(ARTICLE, "missing").unwrap(), ARTICLE);
+    assert!(remove_frontmatter_field("# No frontmatter\n", "title").is_err());
+}
+
+#[test]
+fn test_insert_image() {
+    let updated = insert_image(ARTICLE, Some("# Example"), "diagram.png", "A diagram").unwrap();
+    assert!(updated.contains("Run it.\n\n![A diagram](diagram.png)\n"));
+
+    let updated = insert_image(ARTICLE, Some("Details"), "detail.png", "Detail").unwrap();
+    assert!(updated.contains("Nested detail.\n\n![Detail](detail.png)\n\n## Usage"));
+
+    let updated = insert_image("---\ntitle: Empty\n---\n", None, "cover.png", "").unwrap();
+    assert_eq!(updated, "---\ntitle: Empty\n---\n\n![](cover.png)\n");
+}
@@ -0,0 +1,71 @@
+//! Tests for tag management
+
+use content_edit::{delete_unused_tags, list_orphaned_tags, merge_tags, rename_tag};
+use common_test_utils::{config_for, use_config, write_article, write_config};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn seed_repository(root: &Path) -> PathBuf {
+    let mut config = config_for(&root.join("content"), &["blog"]);
+    let languages = ["rust", "golang", "cobol"].iter().map(|tag| tag.to_string()).collect();
+    config.content.tags = Some(HashMap::from([("languages".to_string(), languages)]));
+
+    for (slug, tags) in [("first", "[rust, async]"), ("second", "[golang, go, async]")] {
+        write_article(&root.join("content"), "blog", slug, &format!("---\ntitle: {}\ntags: {}\n---\nBody\n", slug, tags));
+    }
+
+    write_config(root, &config)
+}
+
+fn read(root: &Path, slug: &str) -> String {
+    fs::read_to_string(root.join("content/blog").join(slug).join(format!("{}.md", slug))).unwrap()
+}
+
+#[test]
+fn test_tag_management() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let _config = use_config(&seed_repository(root));
+
+    assert_eq!(list_orphaned_tags().unwrap(), vec!["async", "go"]);
+
+    // Dry runs report without writing
+    let report = merge_tags(&["golang".to_string(), "go".to_string()], "go-lang", true).unwrap();
+    assert_eq!(report.edits.len(), 1);
+    assert!(report.edits[0].updated.contains("tags: [go-lang, async]"));
+    assert_eq!(report.config_changes.len(), 2);
+    assert!(read(root, "second").contains("[golang, go, async]"));
+
+    merge_tags(&["golang".to_string(), "go".to_string()], "go-lang", false).unwrap();
+    assert!(read(root, "second").contains("tags: [go-lang, async]\n"));
+
+    let report = rename_tag("async", "concurrency", false).unwrap();
+    assert_eq!(report.edits.len(), 2);
+    assert!(report.config_changes.is_empty());
+    assert!(read(root, "first").contains("tags: [rust, concurrency]\n"));
+    assert_eq!(list_orphaned_tags().unwrap(), vec!["concurrency"]);
+
+    let report = delete_unused_tags(false).unwrap();
+    assert_eq!(report.config_changes, vec!["Removed cobol from category 'languages'"]);
+    let config = fs::read_to_string(root.join("config.yaml")).unwrap();
+    assert!(!config.contains("cobol"));
+    assert!(config.contains("go-lang"));
+}
+
+#[test]
+fn test_merge_skips_content_with_invalid_frontmatter() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let _config = use_config(&seed_repository(root));
+    write_article(&root.join("content"), "blog", "broken", "---\ntitle: [unclosed\n---\nBody\n");
+
+    let report = merge_tags(&["golang".to_string()], "go-lang", false).unwrap();
+
+    assert_eq!(report.edits.len(), 1);
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].contains("broken.md"));
+    assert!(read(root, "second").contains("tags: [go-lang, go, async]\n"));
+    assert!(read(root, "broken").contains("title: [unclosed"));
+}
@@ -0,0 +1,73 @@
+//! Tests for the editorial workflow
+
+use chrono::NaiveDate;
+use common_models::{ContentState, Frontmatter};
+use content_edit::transition_content;
+
+const IDEA: &str = "---
+title: Example # keep this comment
+state: idea
+published: DRAFT
+---
+
+Notes.
+";
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 9).unwrap()
+}
+
+fn frontmatter(content: &str) -> Frontmatter {
+    let (frontmatter, _) = content_edit::split_frontmatter_and_body(content).unwrap();
+    frontmatter
+}
+
+#[test]
+fn test_transition_through_the_workflow() {
+    let draft = transition_content(IDEA, ContentState::Draft, today()).unwrap();
+    assert!(draft.starts_with("---\ntitle: Example # keep this comment\nstate: draft\n"));
+    assert!(draft.ends_with("---\n\nNotes.\n"));
+    assert_eq!(frontmatter(&draft).is_draft, Some(true));
+
+    let review = transition_content(&draft, ContentState::Review, today()).unwrap();
+    assert_eq!(frontmatter(&review).content_state(), ContentState::Review);
+    assert_eq!(frontmatter(&review).get_date("reviewed_at"), None);
+
+    let published = transition_content(&review, ContentState::Published, today()).unwrap();
+    let fm = frontmatter(&published);
+    assert_eq!(fm.content_state(), ContentState::Published);
+    assert_eq!(fm.is_draft, Some(false));
+    assert_eq!(fm.get_date("reviewed_at"), Some(today()));
+    assert_eq!(fm.published_at, Some("2024-03-09".parse().unwrap()));
+
+    let archived = transition_content(&published, ContentState::Archived, today()).unwrap();
+    let fm = frontmatter(&archived);
+    assert_eq!(fm.content_state(), ContentState::Archived);
+    assert!(fm.is_archived());
+
+    let restored = transition_content(&archived, ContentState::Draft, today()).unwrap();
+    let fm = frontmatter(&restored);
+    assert_eq!(fm.content_state(), ContentState::Draft);
+    assert_eq!(fm.archived, None);
+}
+
+#[test]
+fn test_republishing_keeps_the_original_date() {
+    let content = "---\ntitle: Example\nstate: review\npublished: 2023-01-31\n---\nBody\n";
+    let published = transition_content(content, ContentState::Published, today()).unwrap();
+    assert_eq!(frontmatter(&published).published_at, Some("2023-01-31".parse().unwrap()));
+}
+
+#[test]
+fn test_disallowed_transitions_are_rejected() {
+    let err = transition_content(IDEA, ContentState::Published, today()).unwrap_err();
+    assert!(err.to_string().contains("Cannot move content from idea to published"));
+
+    // Existing content without a state is inferred from its draft flag
+    let draft = "---\ntitle: Example\ndraft: true\n---\nBody\n";
+    assert!(transition_content(draft, ContentState::Published, today()).is_err());
+    assert!(transition_content(draft, ContentState::Draft, today()).is_err());
+    assert!(transition_content(draft, ContentState::Review, today()).is_ok());
+
+    assert!(transition_content("No frontmatter", ContentState::Draft, today()).is_err());
+}
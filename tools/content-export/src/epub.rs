@@ -0,0 +1,294 @@
+//! # EPUB
+//!
+//! Writes a book as an EPUB 3 package. The package holds a navigation
+//! document and an NCX table of contents for older readers, an optional
+//! cover page, one XHTML document per chapter and the chapters' images:
+//!
+//! ```text
+//! mimetype
+//! META-INF/container.xml
+//! OEBPS/content.opf
+//! OEBPS/nav.xhtml
+//! OEBPS/toc.ncx
+//! OEBPS/style.css
+//! OEBPS/cover.xhtml
+//! OEBPS/chapter-1.xhtml
+//! OEBPS/images/{slug}/...
+//! ```
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::{escape, Book, Chapter};
+
+/// Stylesheet shared by the chapters
+pub const STYLESHEET: &str = "body { font-family: serif; line-height: 1.5; }
+h1, h2, h3, h4 { font-family: sans-serif; line-height: 1.2; }
+header { margin-bottom: 2em; }
+.meta { color: #666; font-size: 0.9em; }
+.description { font-style: italic; }
+img { max-width: 100%; }
+pre { white-space: pre-wrap; font-size: 0.85em; }
+.cover { text-align: center; margin: 0; padding: 0; }
+.cover img { max-height: 100%; }
+";
+
+const CONTAINER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+/// Write a book as an EPUB file
+///
+/// # Errors
+///
+/// Returns an error if the file or one of the book's images cannot be
+/// read or written
+pub fn write_epub(book: &Book, path: &Path) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    // The mimetype must come first, uncompressed, so readers can identify the file
+    zip.start_file("mimetype", FileOptions::default().compression_method(CompressionMethod::Stored))?;
+    zip.write_all(b"application/epub+zip")?;
+
+    let mut add = |name: &str, data: &[u8]| -> Result<()> {
+        zip.start_file(name, deflated)?;
+        zip.write_all(data)?;
+        Ok(())
+    };
+
+    add("META-INF/container.xml", CONTAINER.as_bytes())?;
+    add("OEBPS/content.opf", package_document(book).as_bytes())?;
+    add("OEBPS/nav.xhtml", navigation_document(book).as_bytes())?;
+    add("OEBPS/toc.ncx", ncx_document(book).as_bytes())?;
+    add("OEBPS/style.css", STYLESHEET.as_bytes())?;
+
+    if let Some(cover) = &book.cover {
+        let data = std::fs::read(cover).with_context(|| format!("Failed to read {}", cover.display()))?;
+        add(&format!("OEBPS/{}", cover_href(cover)), &data)?;
+        add("OEBPS/cover.xhtml", cover_document(book, cover).as_bytes())?;
+    }
+
+    for (index, chapter) in book.chapters.iter().enumerate() {
+        add(&format!("OEBPS/{}", chapter_href(index)), chapter_document(book, chapter).as_bytes())?;
+        for image in &chapter.images {
+            let data = std::fs::read(&image.source).with_context(|| format!("Failed to read {}", image.source.display()))?;
+            add(&format!("OEBPS/{}/{}", chapter.image_dir(), image.path), &data)?;
+        }
+    }
+
+    zip.finish().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// File name of a chapter's document, numbered from 1
+pub fn chapter_href(index: usize) -> String {
+    format!("chapter-{}.xhtml", index + 1)
+}
+
+fn cover_href(cover: &Path) -> String {
+    let extension = cover.extension().and_then(|extension| extension.to_str()).unwrap_or("jpg");
+    format!("cover.{}", extension.to_lowercase())
+}
+
+/// Media type of a file, from its extension
+pub fn media_type(path: &str) -> &'static str {
+    let extension = path.rsplit_once('.').map(|(_, extension)| extension.to_lowercase()).unwrap_or_default();
+    match extension.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "xhtml" => "application/xhtml+xml",
+        "css" => "text/css",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Escape a path for use in an `href`
+fn href(path: &str) -> String {
+    escape(&path.replace('%', "%25").replace(' ', "%20"))
+}
+
+/// The package document, `content.opf`, listing the book's metadata,
+/// every file in it and the reading order
+pub fn package_document(book: &Book) -> String {
+    let mut metadata = vec![
+        format!("<dc:identifier id=\"book-id\">{}</dc:identifier>", escape(&book.identifier())),
+        format!("<dc:title>{}</dc:title>", escape(&book.title)),
+        format!("<dc:creator>{}</dc:creator>", escape(&book.author)),
+        format!("<dc:language>{}</dc:language>", escape(&book.language)),
+        format!(
+            "<meta property=\"dcterms:modified\">{}</meta>",
+            book.modified.format("%Y-%m-%dT%H:%M:%SZ")
+        ),
+    ];
+    if let Some(description) = &book.description {
+        metadata.push(format!("<dc:description>{}</dc:description>", escape(description)));
+    }
+
+    let mut manifest = vec![
+        "<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>".to_string(),
+        "<item id=\"ncx\" href=\"toc.ncx\" media-type=\"application/x-dtbncx+xml\"/>".to_string(),
+        "<item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>".to_string(),
+    ];
+    let mut spine = Vec::new();
+
+    if let Some(cover) = &book.cover {
+        let cover_href = cover_href(cover);
+        // Readers that predate EPUB 3 find the cover through this meta element
+        metadata.push("<meta name=\"cover\" content=\"cover-image\"/>".to_string());
+        manifest.push(format!(
+            "<item id=\"cover-image\" href=\"{}\" media-type=\"{}\" properties=\"cover-image\"/>",
+            href(&cover_href),
+            media_type(&cover_href)
+        ));
+        manifest.push("<item id=\"cover\" href=\"cover.xhtml\" media-type=\"application/xhtml+xml\"/>".to_string());
+        spine.push("<itemref idref=\"cover\"/>".to_string());
+    }
+    spine.push("<itemref idref=\"nav\"/>".to_string());
+
+    let mut image_count = 0;
+    for (index, chapter) in book.chapters.iter().enumerate() {
+        let id = format!("chapter-{}", index + 1);
+        manifest.push(format!(
+            "<item id=\"{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>",
+            id,
+            chapter_href(index)
+        ));
+        spine.push(format!("<itemref idref=\"{}\"/>", id));
+
+        for image in &chapter.images {
+            image_count += 1;
+            let path = format!("{}/{}", chapter.image_dir(), image.path);
+            manifest.push(format!(
+                "<item id=\"image-{}\" href=\"{}\" media-type=\"{}\"/>",
+                image_count,
+                href(&path),
+                media_type(&path)
+            ));
+        }
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id" xml:lang="{lang}">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    {metadata}
+  </metadata>
+  <manifest>
+    {manifest}
+  </manifest>
+  <spine toc="ncx">
+    {spine}
+  </spine>
+</package>
+"#,
+        lang = escape(&book.language),
+        metadata = metadata.join("\n    "),
+        manifest = manifest.join("\n    "),
+        spine = spine.join("\n    "),
+    )
+}
+
+/// The navigation document, `nav.xhtml`, which doubles as the printed
+/// table of contents
+pub fn navigation_document(book: &Book) -> String {
+    let entries: Vec<String> = book
+        .chapters
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| format!("<li><a href=\"{}\">{}</a></li>", chapter_href(index), escape(&chapter.title)))
+        .collect();
+
+    xhtml_document(
+        book,
+        "Contents",
+        &format!(
+            "<nav epub:type=\"toc\" id=\"toc\">\n<h1>Contents</h1>\n<ol>\n{}\n</ol>\n</nav>\n",
+            entries.join("\n")
+        ),
+    )
+}
+
+/// The NCX table of contents, `toc.ncx`, for readers that predate EPUB 3
+pub fn ncx_document(book: &Book) -> String {
+    let points: Vec<String> = book
+        .chapters
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| {
+            format!(
+                "<navPoint id=\"nav-{number}\" playOrder=\"{number}\">\n      <navLabel><text>{}</text></navLabel>\n      <content src=\"{}\"/>\n    </navPoint>",
+                escape(&chapter.title),
+                chapter_href(index),
+                number = index + 1,
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+  <head>
+    <meta name="dtb:uid" content="{}"/>
+  </head>
+  <docTitle><text>{}</text></docTitle>
+  <navMap>
+    {}
+  </navMap>
+</ncx>
+"#,
+        escape(&book.identifier()),
+        escape(&book.title),
+        points.join("\n    ")
+    )
+}
+
+/// A chapter's document
+pub fn chapter_document(book: &Book, chapter: &Chapter) -> String {
+    xhtml_document(book, &chapter.title, &chapter.render_section())
+}
+
+fn cover_document(book: &Book, cover: &Path) -> String {
+    xhtml_document(
+        book,
+        &book.title,
+        &format!(
+            "<div class=\"cover\"><img src=\"{}\" alt=\"{}\"/></div>\n",
+            href(&cover_href(cover)),
+            escape(&book.title)
+        ),
+    )
+}
+
+fn xhtml_document(book: &Book, title: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" xml:lang="{lang}" lang="{lang}">
+<head>
+<meta charset="UTF-8"/>
+<title>{title}</title>
+<link rel="stylesheet" type="text/css" href="style.css"/>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        lang = escape(&book.language),
+        title = escape(title),
+        body = body,
+    )
+}
@@ -0,0 +1,442 @@
+//! # Content Export
+//!
+//! Compiles a selection of articles into a book: an EPUB, assembled here
+//! from XHTML documents, and optionally a PDF, rendered from the same
+//! chapters by an external program configured in the `export` section (see
+//! [`common_models::ExportConfig`]).
+//!
+//! Articles are selected by topic, tag, series or an explicit list of slugs.
+//! Each becomes a chapter headed by its title, date, tags and description,
+//! and the images it links to relatively are packaged alongside it.
+//!
+//! The same selection can instead be written out as the content of a Hugo,
+//! Jekyll or Astro site, see [`ssg`].
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::FsContentRepository;
+use common_markdown::{
+    extract_frontmatter_and_content, Admonitions, Containers, DefinitionLists, HeadingAnchors, RelativeImageUrls,
+    TransformPipeline,
+};
+use common_models::{Config, Frontmatter};
+use common_traits::{ArticleLocation, ContentRepository};
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::path::{Component, Path, PathBuf};
+
+pub mod epub;
+pub mod pdf;
+pub mod ssg;
+
+pub use epub::write_epub;
+pub use pdf::write_pdf;
+pub use ssg::{export_site, Generator, SiteExportOptions, SiteExportReport};
+
+/// Which articles go into a book
+///
+/// The filters combine: an article must match every one that is set.
+/// Archived articles are always left out.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// Only articles in this topic
+    pub topic: Option<String>,
+    /// Only articles with this tag
+    pub tag: Option<String>,
+    /// Only articles in this series, ordered by their part
+    pub series: Option<String>,
+    /// Exactly these articles, in this order
+    pub slugs: Vec<String>,
+    /// Whether drafts can be included
+    pub include_drafts: bool,
+}
+
+impl Selection {
+    /// Check whether an article's frontmatter passes the filters
+    pub(crate) fn matches(&self, frontmatter: &Frontmatter) -> bool {
+        if frontmatter.is_archived() {
+            return false;
+        }
+        if !self.include_drafts && is_draft(frontmatter) {
+            return false;
+        }
+        if let Some(tag) = &self.tag {
+            let tags = frontmatter.tags.as_deref().unwrap_or_default();
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                return false;
+            }
+        }
+        if let Some(series) = &self.series {
+            if frontmatter.series.as_deref() != Some(series.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+pub(crate) fn is_draft(frontmatter: &Frontmatter) -> bool {
+    frontmatter.is_draft.unwrap_or(false) || frontmatter.published_at.as_ref().is_some_and(|date| date.is_draft())
+}
+
+/// An image a chapter links to, found in its article directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChapterImage {
+    /// Path as written in the markdown, relative to the article directory
+    pub path: String,
+    /// The image file
+    pub source: PathBuf,
+}
+
+/// An article as a chapter of a book
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub slug: String,
+    pub topic: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub published: Option<NaiveDate>,
+    pub tags: Vec<String>,
+    /// Markdown body, without the frontmatter
+    pub content: String,
+    /// Images packaged with the chapter
+    pub images: Vec<ChapterImage>,
+    /// Relative image links that don't point at a file in the article directory
+    pub missing_images: Vec<String>,
+}
+
+impl Chapter {
+    /// Read a chapter from an article's content file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frontmatter cannot be parsed
+    pub fn from_article(article: &ArticleLocation, content: &str) -> Result<Self> {
+        let (frontmatter, body) = extract_frontmatter_and_content(content)
+            .with_context(|| format!("Failed to read {}", article.content_file.display()))?;
+        Ok(Self::new(article, frontmatter, body))
+    }
+
+    fn new(article: &ArticleLocation, frontmatter: Frontmatter, content: String) -> Self {
+        let mut images = Vec::new();
+        let mut missing_images = Vec::new();
+        for path in relative_images(&content) {
+            let source = article.dir.join(&path);
+            if is_inside(&path) && source.is_file() {
+                images.push(ChapterImage { path, source });
+            } else {
+                missing_images.push(path);
+            }
+        }
+
+        Self {
+            slug: article.slug.clone(),
+            topic: article.topic.clone(),
+            title: frontmatter.title,
+            description: frontmatter.description.filter(|description| !description.is_empty()),
+            published: frontmatter.published_at.and_then(|date| date.date()),
+            tags: frontmatter.tags.unwrap_or_default(),
+            content,
+            images,
+            missing_images,
+        }
+    }
+
+    /// Directory the chapter's images are packaged in, relative to the book
+    pub fn image_dir(&self) -> String {
+        format!("images/{}", self.slug)
+    }
+
+    /// The chapter as an XHTML `<section>`: a header with its title and
+    /// metadata, followed by the rendered body
+    pub fn render_section(&self) -> String {
+        let mut meta = Vec::new();
+        if let Some(date) = self.published {
+            meta.push(date.format("%B %-d, %Y").to_string());
+        }
+        if !self.tags.is_empty() {
+            meta.push(self.tags.join(", "));
+        }
+
+        let mut header = format!("<h1>{}</h1>\n", escape(&self.title));
+        if !meta.is_empty() {
+            header.push_str(&format!("<p class=\"meta\">{}</p>\n", escape(&meta.join(" · "))));
+        }
+        if let Some(description) = &self.description {
+            header.push_str(&format!("<p class=\"description\">{}</p>\n", escape(description)));
+        }
+
+        let body = TransformPipeline::new()
+            .with(Admonitions)
+            .with(Containers)
+            .with(DefinitionLists)
+            .with(HeadingAnchors)
+            .with(RelativeImageUrls::new(self.image_dir()))
+            .render_html(&self.content);
+
+        format!(
+            "<section class=\"chapter\" id=\"{}\">\n<header>\n{}</header>\n{}</section>\n",
+            escape(&self.slug),
+            header,
+            body
+        )
+    }
+}
+
+/// Relative image links in markdown, without duplicates
+pub(crate) fn relative_images(content: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    for event in Parser::new(content) {
+        if let Event::Start(Tag::Image(_, url, _)) = event {
+            let url = url.trim_start_matches("./");
+            if is_relative_url(url) && !paths.iter().any(|path| path == url) {
+                paths.push(url.to_string());
+            }
+        }
+    }
+    paths
+}
+
+/// Whether a URL is relative to the current document
+pub(crate) fn is_relative_url(url: &str) -> bool {
+    !(url.is_empty()
+        || url.starts_with('/')
+        || url.starts_with('#')
+        || url.starts_with("data:")
+        || url.contains("://"))
+}
+
+/// Check whether a relative path stays inside the directory it's relative to
+fn is_inside(path: &str) -> bool {
+    Path::new(path).components().all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Escape text for XML and HTML
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Collect the chapters for a selection, in reading order
+///
+/// Explicitly listed articles keep their order. A series is ordered by each
+/// article's part, and anything else from oldest to newest.
+///
+/// # Errors
+///
+/// Returns an error if a listed article doesn't exist, an article cannot be
+/// read, or no articles match
+pub fn select_chapters(repository: &impl ContentRepository, selection: &Selection) -> Result<Vec<Chapter>> {
+    let mut locations = Vec::new();
+    if selection.slugs.is_empty() {
+        for topic in repository.list_topics()? {
+            if selection.topic.is_none() || selection.topic.as_ref() == Some(&topic) {
+                locations.extend(repository.list_articles(&topic)?);
+            }
+        }
+    } else {
+        for slug in &selection.slugs {
+            let found = match &selection.topic {
+                Some(topic) => repository.find_article(topic, slug)?,
+                None => repository.find_articles(slug)?.into_iter().next(),
+            };
+            let location = found.with_context(|| format!("No article found with slug: {}", slug))?;
+            if !locations.iter().any(|existing: &ArticleLocation| existing.dir == location.dir) {
+                locations.push(location);
+            }
+        }
+    }
+
+    let mut articles = Vec::new();
+    for location in locations {
+        let content = repository.read_article(&location)?;
+        let (frontmatter, body) = extract_frontmatter_and_content(&content)
+            .with_context(|| format!("Failed to read {}", location.content_file.display()))?;
+        if selection.matches(&frontmatter) {
+            let series_part = frontmatter.series_part;
+            articles.push((series_part, Chapter::new(&location, frontmatter, body)));
+        }
+    }
+
+    if selection.slugs.is_empty() {
+        articles.sort_by(|(a_part, a), (b_part, b)| {
+            let by_part = if selection.series.is_some() { compare_missing_last(a_part, b_part) } else { Ordering::Equal };
+            by_part
+                .then_with(|| compare_missing_last(&a.published, &b.published))
+                .then_with(|| a.title.cmp(&b.title))
+        });
+    }
+
+    anyhow::ensure!(!articles.is_empty(), "No articles match the selection");
+    Ok(articles.into_iter().map(|(_, chapter)| chapter).collect())
+}
+
+/// Order present values ascending, then missing ones
+fn compare_missing_last<T: Ord>(a: &Option<T>, b: &Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// A book compiled from chapters
+#[derive(Debug, Clone)]
+pub struct Book {
+    pub title: String,
+    pub author: String,
+    /// Language code, such as `en`
+    pub language: String,
+    pub description: Option<String>,
+    /// Cover image file
+    pub cover: Option<PathBuf>,
+    pub chapters: Vec<Chapter>,
+    /// When the book was compiled
+    pub modified: DateTime<Utc>,
+}
+
+impl Book {
+    /// A unique identifier for the book, stable across exports
+    pub fn identifier(&self) -> String {
+        format!("urn:writing:{}", slug::slugify(&self.title))
+    }
+}
+
+/// Options for [`export_book`]
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub selection: Selection,
+    /// Title of the book, defaulting to the series, topic or site title
+    pub title: Option<String>,
+    /// Author, defaulting to the publication author
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub cover: Option<PathBuf>,
+    /// EPUB file to write, defaulting to the slugified title in the current directory
+    pub output: Option<PathBuf>,
+    /// Also render a PDF beside the EPUB
+    pub pdf: bool,
+    /// Command that renders the PDF, instead of the configured one
+    pub pdf_command: Option<String>,
+}
+
+/// What an export wrote
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub title: String,
+    pub epub: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf: Option<PathBuf>,
+    /// Slug and title of each chapter, in order
+    pub chapters: Vec<ChapterSummary>,
+    pub warnings: Vec<String>,
+}
+
+/// A chapter listed in an [`ExportReport`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ChapterSummary {
+    pub slug: String,
+    pub title: String,
+}
+
+impl DisplayResult for ExportReport {
+    fn to_display(&self) -> String {
+        let mut lines = Vec::new();
+        for (number, chapter) in self.chapters.iter().enumerate() {
+            lines.push(format!("{:>3}. {} ({})", number + 1, chapter.title, chapter.slug));
+        }
+        for warning in &self.warnings {
+            lines.push(format!("{} {}", "Warning:".yellow(), warning));
+        }
+        lines.push(format!("{} {}", "✓".green(), self.epub.display()));
+        if let Some(pdf) = &self.pdf {
+            lines.push(format!("{} {}", "✓".green(), pdf.display()));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Compile the selected articles into an EPUB, and a PDF if asked for
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be loaded, the selection
+/// fails, or the book cannot be written
+pub fn export_book(options: &ExportOptions) -> Result<ExportReport> {
+    let config = common_config::load_config()?;
+    let repository = FsContentRepository::new(&config);
+    let chapters = select_chapters(&repository, &options.selection)?;
+    let book = assemble(&config, options, chapters);
+
+    let epub = options
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("{}.epub", slug::slugify(&book.title))));
+    write_epub(&book, &epub)?;
+
+    let pdf = if options.pdf {
+        let path = epub.with_extension("pdf");
+        let command = options.pdf_command.as_deref().unwrap_or(config.export.pdf_command());
+        write_pdf(&book, command, &path)?;
+        Some(path)
+    } else {
+        None
+    };
+
+    let warnings = book
+        .chapters
+        .iter()
+        .flat_map(|chapter| {
+            chapter
+                .missing_images
+                .iter()
+                .map(move |path| format!("{}: image {} was not found in the article directory", chapter.slug, path))
+        })
+        .collect();
+
+    Ok(ExportReport {
+        title: book.title.clone(),
+        epub,
+        pdf,
+        chapters: book
+            .chapters
+            .iter()
+            .map(|chapter| ChapterSummary {
+                slug: chapter.slug.clone(),
+                title: chapter.title.clone(),
+            })
+            .collect(),
+        warnings,
+    })
+}
+
+/// Put a book together from its chapters, filling in what the options leave out
+pub fn assemble(config: &Config, options: &ExportOptions, chapters: Vec<Chapter>) -> Book {
+    let selection = &options.selection;
+    let title = options
+        .title
+        .clone()
+        .or_else(|| selection.series.clone())
+        .or_else(|| {
+            let topic = selection.topic.as_ref()?;
+            config.content.topics.get(topic).map(|topic| topic.name.clone())
+        })
+        .unwrap_or_else(|| config.title.clone());
+
+    Book {
+        title,
+        author: options.author.clone().unwrap_or_else(|| config.publication.author.clone()),
+        language: config.i18n.default_locale.clone(),
+        description: options.description.clone(),
+        cover: options.cover.clone(),
+        chapters,
+        modified: Utc::now(),
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use colored::*;
+use common_cli::OutputFormatArgs;
+use common_fs::FsContentRepository;
+use content_export::{export_book, export_site, ExportOptions, Generator, Selection, SiteExportOptions};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(author, version, about = "Export articles as a book or as content for another static site generator")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    #[command(flatten)]
+    format: OutputFormatArgs,
+}
+
+#[derive(clap::Args)]
+struct SelectionArgs {
+    /// Slugs of the articles to include (all matching articles when omitted)
+    slugs: Vec<String>,
+
+    /// Only include articles in this topic
+    #[arg(short, long)]
+    topic: Option<String>,
+
+    /// Only include articles with this tag
+    #[arg(long)]
+    tag: Option<String>,
+
+    /// Only include articles in this series
+    #[arg(short, long)]
+    series: Option<String>,
+
+    /// Include drafts
+    #[arg(long)]
+    include_drafts: bool,
+}
+
+impl From<SelectionArgs> for Selection {
+    fn from(args: SelectionArgs) -> Self {
+        Selection {
+            topic: args.topic,
+            tag: args.tag,
+            series: args.series,
+            slugs: args.slugs,
+            include_drafts: args.include_drafts,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Compile articles into an EPUB, and optionally a PDF, in reading order
+    Book {
+        #[command(flatten)]
+        selection: SelectionArgs,
+
+        /// Title of the book (defaults to the series, topic or site title)
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Author of the book (defaults to the publication author)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Short description of the book
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Cover image
+        #[arg(long)]
+        cover: Option<PathBuf>,
+
+        /// EPUB file to write (defaults to the slugified title)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Also render a PDF beside the EPUB
+        #[arg(long)]
+        pdf: bool,
+
+        /// Command that renders the PDF, with {input} and {output} placeholders
+        #[arg(long)]
+        pdf_command: Option<String>,
+    },
+
+    /// Write articles and the site configuration in the layout of Hugo, Jekyll or Astro
+    Site {
+        /// Generator whose conventions to follow
+        #[arg(value_enum)]
+        generator: Generator,
+
+        #[command(flatten)]
+        selection: SelectionArgs,
+
+        /// Directory to write the site to
+        #[arg(short, long, default_value = "export")]
+        output: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let formatter = cli.format.formatter();
+
+    match cli.command {
+        Commands::Book {
+            selection,
+            title,
+            author,
+            description,
+            cover,
+            output,
+            pdf,
+            pdf_command,
+        } => {
+            let options = ExportOptions {
+                selection: selection.into(),
+                title,
+                author,
+                description,
+                cover,
+                output,
+                pdf,
+                pdf_command,
+            };
+
+            if formatter.is_plain() {
+                println!("{}", "Exporting...".green().bold());
+            }
+            formatter.print(&export_book(&options)?)
+        }
+        Commands::Site {
+            generator,
+            selection,
+            output,
+        } => {
+            let config = common_config::load_config()?;
+            let repository = FsContentRepository::new(&config);
+            let options = SiteExportOptions {
+                generator,
+                output,
+                selection: selection.into(),
+            };
+
+            formatter.print(&export_site(&config, &repository, &options)?)
+        }
+    }
+}
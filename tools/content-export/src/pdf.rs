@@ -0,0 +1,141 @@
+//! # PDF
+//!
+//! Renders a book to PDF with an external program. The book is written as
+//! one HTML document, with its images beside it laid out as in the EPUB,
+//! into a temporary directory, and the configured command converts it.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::epub::STYLESHEET;
+use crate::{escape, Book};
+
+/// The whole book as a single HTML document: the cover, the table of
+/// contents, then each chapter on a new page
+pub fn book_html(book: &Book) -> String {
+    let mut body = String::new();
+
+    if let Some(cover) = &book.cover {
+        let extension = cover.extension().and_then(|extension| extension.to_str()).unwrap_or("jpg");
+        body.push_str(&format!(
+            "<div class=\"cover\"><img src=\"cover.{}\" alt=\"{}\"/></div>\n",
+            extension.to_lowercase(),
+            escape(&book.title)
+        ));
+    }
+
+    body.push_str(&format!("<h1 class=\"title\">{}</h1>\n", escape(&book.title)));
+    body.push_str(&format!("<p class=\"author\">{}</p>\n", escape(&book.author)));
+
+    let entries: Vec<String> = book
+        .chapters
+        .iter()
+        .map(|chapter| format!("<li><a href=\"#{}\">{}</a></li>", escape(&chapter.slug), escape(&chapter.title)))
+        .collect();
+    body.push_str(&format!("<nav id=\"toc\">\n<h1>Contents</h1>\n<ol>\n{}\n</ol>\n</nav>\n", entries.join("\n")));
+
+    for chapter in &book.chapters {
+        body.push_str(&chapter.render_section());
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{lang}">
+<head>
+<meta charset="UTF-8">
+<title>{title}</title>
+<style>
+{style}section.chapter, nav {{ page-break-before: always; }}
+.cover {{ page-break-after: always; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        lang = escape(&book.language),
+        title = escape(&book.title),
+        style = STYLESHEET,
+        body = body,
+    )
+}
+
+/// Render a book to a PDF file with `command`
+///
+/// `{input}` in the command is replaced by the path of the HTML document
+/// and `{output}` by `output`, both quoted for the shell.
+///
+/// # Errors
+///
+/// Returns an error if the document cannot be prepared, the command fails,
+/// or it doesn't write the PDF
+pub fn write_pdf(book: &Book, command: &str, output: &Path) -> Result<()> {
+    let dir = tempfile::tempdir().context("Failed to create a temporary directory")?;
+    let input = dir.path().join("book.html");
+    std::fs::write(&input, book_html(book)).with_context(|| format!("Failed to write {}", input.display()))?;
+
+    if let Some(cover) = &book.cover {
+        let extension = cover.extension().and_then(|extension| extension.to_str()).unwrap_or("jpg");
+        let target = dir.path().join(format!("cover.{}", extension.to_lowercase()));
+        std::fs::copy(cover, &target).with_context(|| format!("Failed to copy {}", cover.display()))?;
+    }
+    for chapter in &book.chapters {
+        for image in &chapter.images {
+            let target = dir.path().join(chapter.image_dir()).join(&image.path);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(&image.source, &target).with_context(|| format!("Failed to copy {}", image.source.display()))?;
+        }
+    }
+
+    // Relative output paths are relative to where we were run, not the temporary directory
+    let output = if output.is_absolute() {
+        output.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(output)
+    };
+    let command = command
+        .replace("{input}", &quote(&input))
+        .replace("{output}", &quote(&output));
+
+    let result = shell(&command)
+        .current_dir(dir.path())
+        .output()
+        .with_context(|| format!("Failed to run PDF renderer: {}", command))?;
+    if !result.status.success() {
+        anyhow::bail!(
+            "PDF renderer failed ({}): {}",
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        );
+    }
+    anyhow::ensure!(output.is_file(), "PDF renderer did not write {}", output.display());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', r"'\''"))
+}
+
+#[cfg(windows)]
+fn quote(path: &Path) -> String {
+    format!("\"{}\"", path.display())
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
@@ -0,0 +1,443 @@
+//! # Static Site Generators
+//!
+//! Writes the content tree in the layout and frontmatter conventions of
+//! another static site generator, so articles can be previewed in it or
+//! moved to it:
+//!
+//! * Hugo - each topic is a section, `content/{directory}/`, and each
+//!   article a page bundle, `{slug}/index.md`, with its files beside it
+//! * Jekyll - articles are posts, `_posts/{date}-{slug}.md`, categorised by
+//!   topic; their files go to `assets/images/{slug}/`, and posts without a
+//!   date go to `_drafts/`
+//! * Astro - each topic is a content collection, `src/content/{topic}/`,
+//!   and each article an entry, `{slug}/index.md`, with its files beside it
+//!
+//! Frontmatter fields are renamed to what each generator expects, such as
+//! `published` to `date` or `pubDate`. Fields it has no name for are kept
+//! as they are. The site configuration is written alongside the content.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use colored::*;
+use common_cli::DisplayResult;
+use common_markdown::extract_frontmatter_and_content;
+use common_models::{Config, Frontmatter};
+use common_traits::{ArticleLocation, ContentRepository};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+use crate::{is_draft, is_relative_url, relative_images, Selection};
+
+/// A static site generator to export to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Generator {
+    Hugo,
+    Jekyll,
+    Astro,
+}
+
+/// Options for [`export_site`]
+#[derive(Debug, Clone)]
+pub struct SiteExportOptions {
+    pub generator: Generator,
+    /// Directory to write the site to
+    pub output: PathBuf,
+    /// Articles to export; the slugs and series filters apply as for books
+    pub selection: Selection,
+}
+
+/// An article written by a site export
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedArticle {
+    pub topic: String,
+    pub slug: String,
+    /// The article's file, relative to the output directory
+    pub path: PathBuf,
+    /// Other files copied with it
+    pub files: usize,
+}
+
+/// What a site export wrote
+#[derive(Debug, Clone, Serialize)]
+pub struct SiteExportReport {
+    pub generator: Generator,
+    pub output: PathBuf,
+    pub articles: Vec<ExportedArticle>,
+    /// Configuration and index files, relative to the output directory
+    pub site_files: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+}
+
+impl DisplayResult for SiteExportReport {
+    fn to_display(&self) -> String {
+        let mut lines: Vec<String> = self
+            .articles
+            .iter()
+            .map(|article| format!("{} {}", "✓".green(), article.path.display()))
+            .collect();
+        for path in &self.site_files {
+            lines.push(format!("{} {}", "✓".green(), path.display()));
+        }
+        for warning in &self.warnings {
+            lines.push(format!("{} {}", "Warning:".yellow(), warning));
+        }
+        lines.push(format!(
+            "{} {} articles to {}",
+            "Exported".bold(),
+            self.articles.len(),
+            self.output.display()
+        ));
+        lines.join("\n")
+    }
+}
+
+/// Write the selected articles and the site configuration in a generator's layout
+///
+/// Existing files in the output directory are overwritten, others are left alone.
+///
+/// # Errors
+///
+/// Returns an error if an article cannot be read or a file cannot be written
+pub fn export_site(
+    config: &Config,
+    repository: &impl ContentRepository,
+    options: &SiteExportOptions,
+) -> Result<SiteExportReport> {
+    let selection = &options.selection;
+    let mut report = SiteExportReport {
+        generator: options.generator,
+        output: options.output.clone(),
+        articles: Vec::new(),
+        site_files: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    for topic in repository.list_topics()? {
+        if selection.topic.is_some() && selection.topic.as_ref() != Some(&topic) {
+            continue;
+        }
+        for location in repository.list_articles(&topic)? {
+            if !selection.slugs.is_empty() && !selection.slugs.contains(&location.slug) {
+                continue;
+            }
+            let content = repository.read_article(&location)?;
+            let (frontmatter, body) = extract_frontmatter_and_content(&content)
+                .with_context(|| format!("Failed to read {}", location.content_file.display()))?;
+            if selection.matches(&frontmatter) {
+                let article = export_article(config, options, &location, &frontmatter, &body, &mut report.warnings)?;
+                report.articles.push(article);
+            }
+        }
+    }
+
+    for (path, contents) in site_files(config, options.generator, &report.articles)? {
+        write(&options.output.join(&path), &contents)?;
+        report.site_files.push(path);
+    }
+
+    Ok(report)
+}
+
+fn export_article(
+    config: &Config,
+    options: &SiteExportOptions,
+    location: &ArticleLocation,
+    frontmatter: &Frontmatter,
+    body: &str,
+    warnings: &mut Vec<String>,
+) -> Result<ExportedArticle> {
+    let slug = &location.slug;
+    let (path, files_dir, body) = match options.generator {
+        Generator::Hugo => {
+            let section = config.content.topics.get(&location.topic).map_or(&location.topic, |topic| &topic.directory);
+            let dir = PathBuf::from("content").join(section).join(slug);
+            (dir.join("index.md"), dir, body.to_string())
+        }
+        Generator::Astro => {
+            let dir = PathBuf::from("src/content").join(&location.topic).join(slug);
+            (dir.join("index.md"), dir, body.to_string())
+        }
+        Generator::Jekyll => {
+            let path = match frontmatter.published_at.as_ref().and_then(|date| date.date()) {
+                Some(date) if !is_draft(frontmatter) => {
+                    PathBuf::from("_posts").join(format!("{}-{}.md", date.format("%Y-%m-%d"), slug))
+                }
+                date => {
+                    if date.is_none() && !is_draft(frontmatter) {
+                        warnings.push(format!("{}/{} has no publication date and was exported as a draft", location.topic, slug));
+                    }
+                    PathBuf::from("_drafts").join(format!("{}.md", slug))
+                }
+            };
+            let files_dir = jekyll_files_dir(slug);
+            let body = rewrite_image_links(body, &format!("/{}", files_dir.display()));
+            (path, files_dir, body)
+        }
+    };
+
+    let fields = convert_frontmatter(options.generator, location, frontmatter);
+    let yaml = serde_yaml::to_string(&fields).context("Failed to serialize frontmatter")?;
+    write(&options.output.join(&path), &format!("---\n{}---\n{}", yaml, body))?;
+    let files = copy_article_files(location, &options.output.join(files_dir))?;
+
+    Ok(ExportedArticle {
+        topic: location.topic.clone(),
+        slug: slug.clone(),
+        path,
+        files,
+    })
+}
+
+fn jekyll_files_dir(slug: &str) -> PathBuf {
+    PathBuf::from("assets/images").join(slug)
+}
+
+/// Frontmatter in a generator's conventions
+///
+/// Known fields come first, in the order the generator's own templates use,
+/// followed by any others the article had.
+pub fn convert_frontmatter(generator: Generator, location: &ArticleLocation, frontmatter: &Frontmatter) -> Mapping {
+    let mut fields = Mapping::new();
+    let mut set = |key: &str, value: Value| {
+        fields.insert(Value::from(key), value);
+    };
+    let date = |date: &Option<common_models::PublishDate>| {
+        date.as_ref().filter(|date| !date.is_draft()).map(|date| Value::from(date.to_string()))
+    };
+    let tags = frontmatter.tags.clone().filter(|tags| !tags.is_empty()).map(|tags| {
+        Value::Sequence(tags.into_iter().map(Value::from).collect())
+    });
+    let draft = is_draft(frontmatter);
+
+    set("title", Value::from(frontmatter.title.clone()));
+    match generator {
+        Generator::Hugo => {
+            if let Some(published) = date(&frontmatter.published_at) {
+                set("date", published);
+            }
+            if let Some(updated) = date(&frontmatter.updated_at) {
+                set("lastmod", updated);
+            }
+            if let Some(publish_at) = &frontmatter.publish_at {
+                set("publishDate", Value::from(publish_at.clone()));
+            }
+            if let Some(description) = &frontmatter.description {
+                set("description", Value::from(description.clone()));
+            }
+            if let Some(slug) = &frontmatter.slug {
+                set("slug", Value::from(slug.clone()));
+            }
+            if let Some(tags) = tags {
+                set("tags", tags);
+            }
+            if let Some(series) = &frontmatter.series {
+                set("series", Value::Sequence(vec![Value::from(series.clone())]));
+            }
+            if let Some(part) = frontmatter.series_part {
+                set("weight", Value::from(part));
+            }
+            if let Some(image) = &frontmatter.featured_image_path {
+                set("images", Value::Sequence(vec![Value::from(image.clone())]));
+            }
+            if draft {
+                set("draft", Value::from(true));
+            }
+        }
+        Generator::Jekyll => {
+            set("layout", Value::from("post"));
+            if let Some(published) = date(&frontmatter.published_at) {
+                set("date", published);
+            }
+            if let Some(updated) = date(&frontmatter.updated_at) {
+                set("last_modified_at", updated);
+            }
+            if let Some(description) = &frontmatter.description {
+                set("description", Value::from(description.clone()));
+            }
+            set("categories", Value::Sequence(vec![Value::from(location.topic.clone())]));
+            if let Some(tags) = tags {
+                set("tags", tags);
+            }
+            if let Some(series) = &frontmatter.series {
+                set("series", Value::from(series.clone()));
+            }
+            if let Some(part) = frontmatter.series_part {
+                set("series_part", Value::from(part));
+            }
+            if let Some(image) = &frontmatter.featured_image_path {
+                let image = if is_relative_url(image) {
+                    format!("/{}/{}", jekyll_files_dir(&location.slug).display(), image.trim_start_matches("./"))
+                } else {
+                    image.clone()
+                };
+                set("image", Value::from(image));
+            }
+        }
+        Generator::Astro => {
+            if let Some(description) = &frontmatter.description {
+                set("description", Value::from(description.clone()));
+            }
+            if let Some(published) = date(&frontmatter.published_at) {
+                set("pubDate", published);
+            }
+            if let Some(updated) = date(&frontmatter.updated_at) {
+                set("updatedDate", updated);
+            }
+            if let Some(image) = &frontmatter.featured_image_path {
+                set("heroImage", Value::from(image.clone()));
+            }
+            if let Some(tags) = tags {
+                set("tags", tags);
+            }
+            if let Some(series) = &frontmatter.series {
+                set("series", Value::from(series.clone()));
+            }
+            if let Some(part) = frontmatter.series_part {
+                set("seriesPart", Value::from(part));
+            }
+            if draft {
+                set("draft", Value::from(true));
+            }
+        }
+    }
+
+    let mut extra: Vec<_> = frontmatter.extra.iter().collect();
+    extra.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, value) in extra {
+        if !fields.contains_key(key.as_str()) {
+            fields.insert(Value::from(key.clone()), value.clone());
+        }
+    }
+
+    fields
+}
+
+/// Point relative image links in markdown at `base`
+pub fn rewrite_image_links(body: &str, base: &str) -> String {
+    let mut body = body.to_string();
+    for path in relative_images(&body) {
+        let target = format!("{}/{}", base, path);
+        for original in [path.clone(), format!("./{}", path)] {
+            body = body
+                .replace(&format!("]({})", original), &format!("]({})", target))
+                .replace(&format!("]({} ", original), &format!("]({} ", target));
+        }
+    }
+    body
+}
+
+/// Copy the files in an article directory, other than markdown and hidden
+/// files, into `target`
+fn copy_article_files(location: &ArticleLocation, target: &Path) -> Result<usize> {
+    let mut copied = 0;
+    for entry in walkdir::WalkDir::new(&location.dir).min_depth(1) {
+        let entry = entry.with_context(|| format!("Failed to read {}", location.dir.display()))?;
+        let name = entry.file_name().to_string_lossy();
+        let is_markdown = entry.path().extension().is_some_and(|extension| extension == "md" || extension == "mdx");
+        if !entry.file_type().is_file() || name.starts_with('.') || is_markdown {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(&location.dir).unwrap_or(entry.path());
+        let destination = target.join(relative);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::copy(entry.path(), &destination)
+            .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The configuration, and section indexes for Hugo, for a generator
+fn site_files(config: &Config, generator: Generator, articles: &[ExportedArticle]) -> Result<Vec<(PathBuf, String)>> {
+    let mut topics: Vec<_> = config.content.topics.iter().collect();
+    topics.sort_by(|a, b| a.0.cmp(b.0));
+    let exported = |key: &str| articles.iter().any(|article| article.topic == key);
+
+    let mut files = Vec::new();
+    match generator {
+        Generator::Hugo => {
+            let site = serde_yaml::to_string(&mapping(&[
+                ("baseURL", Value::from(config.url.clone())),
+                ("title", Value::from(config.title.clone())),
+                ("languageCode", Value::from(config.i18n.default_locale.clone())),
+                (
+                    "params",
+                    mapping(&[("author", Value::from(config.publication.author.clone()))]),
+                ),
+                (
+                    "taxonomies",
+                    mapping(&[("tag", Value::from("tags")), ("series", Value::from("series"))]),
+                ),
+            ]))?;
+            files.push((PathBuf::from("hugo.yaml"), site));
+
+            for (_, topic) in topics.into_iter().filter(|(key, _)| exported(key)) {
+                let index = serde_yaml::to_string(&mapping(&[
+                    ("title", Value::from(topic.name.clone())),
+                    ("description", Value::from(topic.description.clone())),
+                ]))?;
+                files.push((
+                    PathBuf::from("content").join(&topic.directory).join("_index.md"),
+                    format!("---\n{}---\n", index),
+                ));
+            }
+        }
+        Generator::Jekyll => {
+            let site = serde_yaml::to_string(&mapping(&[
+                ("title", Value::from(config.title.clone())),
+                ("email", Value::from(config.email.clone())),
+                ("url", Value::from(config.url.clone())),
+                ("author", Value::from(config.publication.author.clone())),
+                ("lang", Value::from(config.i18n.default_locale.clone())),
+                ("permalink", Value::from("/:categories/:title/")),
+            ]))?;
+            files.push((PathBuf::from("_config.yml"), site));
+        }
+        Generator::Astro => {
+            let collections: Vec<String> = topics
+                .iter()
+                .filter(|(key, _)| exported(key))
+                .map(|(key, _)| format!("  '{}': defineCollection({{ type: 'content', schema }}),", key))
+                .collect();
+            files.push((
+                PathBuf::from("src/content/config.ts"),
+                format!("{}{}\n}};\n", ASTRO_SCHEMA, collections.join("\n")),
+            ));
+        }
+    }
+    Ok(files)
+}
+
+/// A YAML mapping with its keys in the given order
+fn mapping(entries: &[(&str, Value)]) -> Value {
+    Value::Mapping(entries.iter().map(|(key, value)| (Value::from(*key), value.clone())).collect())
+}
+
+const ASTRO_SCHEMA: &str = "import { defineCollection, z } from 'astro:content';
+
+const schema = z
+  .object({
+    title: z.string(),
+    description: z.string().optional(),
+    pubDate: z.coerce.date().optional(),
+    updatedDate: z.coerce.date().optional(),
+    heroImage: z.string().optional(),
+    tags: z.array(z.string()).default([]),
+    draft: z.boolean().default(false),
+  })
+  .passthrough();
+
+export const collections = {
+";
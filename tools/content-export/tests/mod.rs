@@ -0,0 +1,7 @@
+//! Test modules for content-export
+
+// Unit tests
+#[path = "unit/export_tests.rs"]
+mod export_tests;
+#[path = "unit/ssg_tests.rs"]
+mod ssg_tests;
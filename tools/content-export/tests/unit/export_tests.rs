@@ -0,0 +1,247 @@
+use chrono::{TimeZone, Utc};
+use common_fs::FsContentRepository;
+use common_models::Config;
+use content_export::epub::{chapter_document, package_document};
+use content_export::pdf::book_html;
+use content_export::{assemble, select_chapters, write_epub, write_pdf, Book, ExportOptions, Selection};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+
+    fn config(base_dir: &Path) -> Config {
+        let mut config = common_test_utils::config_for(base_dir, &["blog", "notes"]);
+        config.publication.author = "Test Author".to_string();
+        config
+    }
+
+    fn write_article(base_dir: &Path, topic: &str, slug: &str, frontmatter: &str, body: &str) {
+        common_test_utils::write_article(base_dir, topic, slug, &format!("---\n{}\n---\n{}", frontmatter, body));
+    }
+
+    /// A content tree with a three-part series split across topics
+    fn content() -> (TempDir, Config) {
+        let temp = TempDir::new().unwrap();
+        let base = temp.path();
+        write_article(
+            base,
+            "blog",
+            "part-two",
+            "title: Part Two\npublished: 2024-01-01\nseries: Rust\nseries_part: 2\ntags: [rust]",
+            "Second.\n",
+        );
+        write_article(
+            base,
+            "blog",
+            "part-one",
+            "title: Part One\npublished: 2024-02-01\nseries: Rust\nseries_part: 1\ntags: [rust, intro]\ndescription: Where it starts",
+            "First.\n\n![Diagram](diagram.png)\n\n![Gone](missing.png)\n",
+        );
+        fs::write(base.join("blog/part-one/diagram.png"), b"png").unwrap();
+        write_article(
+            base,
+            "notes",
+            "part-three",
+            "title: Part Three\npublished: 2023-06-01\nseries: Rust\nseries_part: 3",
+            "Third.\n",
+        );
+        write_article(base, "notes", "aside", "title: An Aside\npublished: 2023-01-01\ntags: [Rust]", "Aside.\n");
+        write_article(base, "notes", "wip", "title: Work in Progress\ndraft: true\ntags: [rust]", "Draft.\n");
+        write_article(base, "notes", "old", "title: Old\narchived: true\ntags: [rust]", "Old.\n");
+
+        let config = config(base);
+        (temp, config)
+    }
+
+    fn slugs(selection: &Selection, config: &Config) -> Vec<String> {
+        select_chapters(&FsContentRepository::new(config), selection)
+            .unwrap()
+            .into_iter()
+            .map(|chapter| chapter.slug)
+            .collect()
+    }
+
+    fn book(config: &Config, selection: Selection) -> Book {
+        let chapters = select_chapters(&FsContentRepository::new(config), &selection).unwrap();
+        let options = ExportOptions {
+            selection,
+            ..Default::default()
+        };
+        let mut book = assemble(config, &options, chapters);
+        book.modified = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        book
+    }
+
+    #[test]
+    fn test_select_orders_series_by_part_and_others_by_date() {
+        let (_temp, config) = content();
+
+        let series = Selection {
+            series: Some("Rust".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(slugs(&series, &config), vec!["part-one", "part-two", "part-three"]);
+
+        let tagged = Selection {
+            tag: Some("rust".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(slugs(&tagged, &config), vec!["aside", "part-two", "part-one"]);
+
+        let topic = Selection {
+            topic: Some("notes".to_string()),
+            include_drafts: true,
+            ..Default::default()
+        };
+        assert_eq!(slugs(&topic, &config), vec!["aside", "part-three", "wip"]);
+    }
+
+    #[test]
+    fn test_select_explicit_slugs_keeps_their_order() {
+        let (_temp, config) = content();
+
+        let selection = Selection {
+            slugs: vec!["part-three".to_string(), "aside".to_string(), "part-three".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(slugs(&selection, &config), vec!["part-three", "aside"]);
+
+        let missing = Selection {
+            slugs: vec!["nope".to_string()],
+            ..Default::default()
+        };
+        let error = select_chapters(&FsContentRepository::new(&config), &missing).unwrap_err();
+        assert!(error.to_string().contains("nope"));
+
+        let nothing = Selection {
+            tag: Some("cooking".to_string()),
+            ..Default::default()
+        };
+        assert!(select_chapters(&FsContentRepository::new(&config), &nothing).is_err());
+    }
+
+    #[test]
+    fn test_chapters_carry_metadata_and_images() {
+        let (_temp, config) = content();
+        let book = book(
+            &config,
+            Selection {
+                series: Some("Rust".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(book.title, "Rust");
+        assert_eq!(book.author, "Test Author");
+
+        let first = &book.chapters[0];
+        assert_eq!(first.images.len(), 1);
+        assert_eq!(first.images[0].path, "diagram.png");
+        assert_eq!(first.missing_images, vec!["missing.png"]);
+
+        let document = chapter_document(&book, first);
+        assert!(document.contains("<h1>Part One</h1>"));
+        assert!(document.contains("February 1, 2024 · rust, intro"));
+        assert!(document.contains("<p class=\"description\">Where it starts</p>"));
+        assert!(document.contains("src=\"images/part-one/diagram.png\""));
+    }
+
+    #[test]
+    fn test_package_document_lists_chapters_images_and_cover() {
+        let (temp, config) = content();
+        let mut book = book(
+            &config,
+            Selection {
+                series: Some("Rust".to_string()),
+                ..Default::default()
+            },
+        );
+        book.cover = Some(temp.path().join("cover.JPG"));
+
+        let package = package_document(&book);
+        assert!(package.contains("<dc:title>Rust</dc:title>"));
+        assert!(package.contains("<meta property=\"dcterms:modified\">2024-03-01T12:00:00Z</meta>"));
+        assert!(package.contains("href=\"cover.jpg\" media-type=\"image/jpeg\" properties=\"cover-image\""));
+        assert!(package.contains("href=\"images/part-one/diagram.png\" media-type=\"image/png\""));
+
+        let spine: Vec<&str> = package.lines().filter(|line| line.contains("<itemref")).map(str::trim).collect();
+        assert_eq!(
+            spine,
+            vec![
+                "<itemref idref=\"cover\"/>",
+                "<itemref idref=\"nav\"/>",
+                "<itemref idref=\"chapter-1\"/>",
+                "<itemref idref=\"chapter-2\"/>",
+                "<itemref idref=\"chapter-3\"/>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_epub_packages_every_file() {
+        let (temp, config) = content();
+        let mut book = book(
+            &config,
+            Selection {
+                series: Some("Rust".to_string()),
+                ..Default::default()
+            },
+        );
+        fs::write(temp.path().join("cover.png"), b"cover").unwrap();
+        book.cover = Some(temp.path().join("cover.png"));
+
+        let path = temp.path().join("rust.epub");
+        write_epub(&book, &path).unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&path).unwrap()).unwrap();
+        let names: Vec<String> = archive.file_names().map(String::from).collect();
+        for name in [
+            "META-INF/container.xml",
+            "OEBPS/content.opf",
+            "OEBPS/nav.xhtml",
+            "OEBPS/toc.ncx",
+            "OEBPS/cover.png",
+            "OEBPS/cover.xhtml",
+            "OEBPS/chapter-3.xhtml",
+            "OEBPS/images/part-one/diagram.png",
+        ] {
+            assert!(names.contains(&name.to_string()), "missing {}", name);
+        }
+
+        let mut mimetype = archive.by_index(0).unwrap();
+        assert_eq!(mimetype.name(), "mimetype");
+        assert_eq!(mimetype.compression(), zip::CompressionMethod::Stored);
+        let mut contents = String::new();
+        mimetype.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "application/epub+zip");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_pdf_runs_the_renderer() {
+        let (temp, config) = content();
+        let book = book(
+            &config,
+            Selection {
+                series: Some("Rust".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let html = book_html(&book);
+        assert!(html.contains("<a href=\"#part-one\">Part One</a>"));
+        assert!(html.contains("<section class=\"chapter\" id=\"part-three\">"));
+
+        // Copying the document stands in for a renderer, and checks the images were laid out beside it
+        let output = temp.path().join("rust.pdf");
+        write_pdf(&book, "test -f images/part-one/diagram.png && cp {input} {output}", &output).unwrap();
+        assert_eq!(fs::read_to_string(&output).unwrap(), html);
+
+        let error = write_pdf(&book, "echo broken >&2; exit 3", &temp.path().join("broken.pdf")).unwrap_err();
+        assert!(error.to_string().contains("broken"));
+    }
+}
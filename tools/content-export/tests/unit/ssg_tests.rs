@@ -0,0 +1,131 @@
+use common_fs::FsContentRepository;
+use common_models::Config;
+use content_export::ssg::rewrite_image_links;
+use content_export::{export_site, Generator, Selection, SiteExportOptions};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod ssg_tests {
+    use super::*;
+
+    /// The blog topic is kept in `posts`, so directories and topic keys differ
+    fn config(base_dir: &Path) -> Config {
+        let mut config = common_test_utils::config_for(base_dir, &["blog", "notes"]);
+        config.title = "Test Site".to_string();
+        let blog = config.content.topics.get_mut("blog").unwrap();
+        blog.name = "Blog".to_string();
+        blog.description = "Blog posts".to_string();
+        blog.directory = "posts".to_string();
+        config
+    }
+
+    fn write_article(base_dir: &Path, directory: &str, slug: &str, frontmatter: &str, body: &str) {
+        common_test_utils::write_article(base_dir, directory, slug, &format!("---\n{}\n---\n{}", frontmatter, body));
+    }
+
+    fn content() -> (TempDir, Config) {
+        let temp = TempDir::new().unwrap();
+        let base = temp.path().join("content");
+        write_article(
+            &base,
+            "posts",
+            "hello",
+            "title: Hello\npublished: 2024-02-01\nupdated: 2024-03-01\ndescription: The first post\ntags: [intro]\nseries: Basics\nseries_part: 1\nfeatured_image: cover.png\nmood: cheerful",
+            "Hi.\n\n![Cover](./cover.png \"The cover\")\n\n![Remote](https://example.com/x.png)\n",
+        );
+        fs::write(base.join("posts/hello/cover.png"), b"png").unwrap();
+        fs::write(base.join("posts/hello/.DS_Store"), b"junk").unwrap();
+        write_article(&base, "posts", "wip", "title: Work in Progress\ndraft: true", "Soon.\n");
+        write_article(&base, "notes", "aside", "title: An Aside\npublished: 2023-01-01", "Aside.\n");
+
+        let config = config(&base);
+        (temp, config)
+    }
+
+    fn export(config: &Config, output: &Path, generator: Generator, selection: Selection) -> Vec<String> {
+        let options = SiteExportOptions {
+            generator,
+            output: output.to_path_buf(),
+            selection,
+        };
+        let report = export_site(config, &FsContentRepository::new(config), &options).unwrap();
+        report.articles.iter().map(|article| article.path.display().to_string()).collect()
+    }
+
+    #[test]
+    fn test_hugo_writes_page_bundles_in_topic_sections() {
+        let (temp, config) = content();
+        let output = temp.path().join("hugo");
+
+        let mut paths = export(&config, &output, Generator::Hugo, Selection::default());
+        paths.sort();
+        assert_eq!(paths, vec!["content/notes/aside/index.md", "content/posts/hello/index.md"]);
+
+        let page = fs::read_to_string(output.join("content/posts/hello/index.md")).unwrap();
+        assert!(page.starts_with("---\ntitle: Hello\ndate: 2024-02-01\nlastmod: 2024-03-01\n"));
+        assert!(page.contains("series:\n- Basics\nweight: 1\nimages:\n- cover.png\nmood: cheerful\n---\nHi."));
+        assert!(output.join("content/posts/hello/cover.png").is_file());
+        assert!(!output.join("content/posts/hello/.DS_Store").exists());
+
+        let index = fs::read_to_string(output.join("content/posts/_index.md")).unwrap();
+        assert_eq!(index, "---\ntitle: Blog\ndescription: Blog posts\n---\n");
+        let site = fs::read_to_string(output.join("hugo.yaml")).unwrap();
+        assert!(site.starts_with("baseURL: https://example.com\ntitle: Test Site\nlanguageCode: en\n"));
+    }
+
+    #[test]
+    fn test_jekyll_writes_dated_posts_and_drafts() {
+        let (temp, config) = content();
+        let output = temp.path().join("jekyll");
+
+        let selection = Selection {
+            topic: Some("blog".to_string()),
+            include_drafts: true,
+            ..Default::default()
+        };
+        let paths = export(&config, &output, Generator::Jekyll, selection);
+        assert_eq!(paths, vec!["_posts/2024-02-01-hello.md", "_drafts/wip.md"]);
+
+        let post = fs::read_to_string(output.join("_posts/2024-02-01-hello.md")).unwrap();
+        assert!(post.starts_with("---\ntitle: Hello\nlayout: post\ndate: 2024-02-01\nlast_modified_at: 2024-03-01\n"));
+        assert!(post.contains("categories:\n- blog\n"));
+        assert!(post.contains("image: /assets/images/hello/cover.png\n"));
+        assert!(post.contains("![Cover](/assets/images/hello/cover.png \"The cover\")"));
+        assert!(post.contains("![Remote](https://example.com/x.png)"));
+        assert!(output.join("assets/images/hello/cover.png").is_file());
+
+        let site = fs::read_to_string(output.join("_config.yml")).unwrap();
+        assert!(site.contains("permalink: /:categories/:title/\n"));
+    }
+
+    #[test]
+    fn test_astro_writes_content_collections() {
+        let (temp, config) = content();
+        let output = temp.path().join("astro");
+
+        let selection = Selection {
+            slugs: vec!["aside".to_string()],
+            ..Default::default()
+        };
+        let paths = export(&config, &output, Generator::Astro, selection);
+        assert_eq!(paths, vec!["src/content/notes/aside/index.md"]);
+
+        let entry = fs::read_to_string(output.join("src/content/notes/aside/index.md")).unwrap();
+        assert_eq!(entry, "---\ntitle: An Aside\npubDate: 2023-01-01\n---\nAside.\n");
+
+        let collections = fs::read_to_string(output.join("src/content/config.ts")).unwrap();
+        assert!(collections.contains("'notes': defineCollection({ type: 'content', schema }),"));
+        assert!(!collections.contains("'blog'"));
+    }
+
+    #[test]
+    fn test_rewrite_image_links_leaves_absolute_links() {
+        let body = "![a](a.png) ![b](./img/b.png) ![c](/c.png) [link](a.png)";
+        assert_eq!(
+            rewrite_image_links(body, "/assets/images/post"),
+            "![a](/assets/images/post/a.png) ![b](/assets/images/post/img/b.png) ![c](/c.png) [link](/assets/images/post/a.png)"
+        );
+    }
+}
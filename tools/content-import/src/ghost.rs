@@ -0,0 +1,120 @@
+//! # Ghost
+//!
+//! Reads posts from a Ghost JSON export, written by Settings → Labs →
+//! Export. A post's primary tag, the first one it lists, is used as its
+//! category. Internal tags, whose names start with `#`, are left out.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::{parse_date, ImportedPost};
+
+#[derive(Debug, Deserialize)]
+struct Export {
+    db: Vec<Database>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Database {
+    data: Data,
+}
+
+#[derive(Debug, Deserialize)]
+struct Data {
+    #[serde(default)]
+    posts: Vec<Post>,
+    #[serde(default)]
+    tags: Vec<Tag>,
+    #[serde(default)]
+    posts_tags: Vec<PostTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Post {
+    id: Value,
+    title: String,
+    slug: String,
+    html: Option<String>,
+    status: String,
+    published_at: Option<String>,
+    custom_excerpt: Option<String>,
+    feature_image: Option<String>,
+    /// `post` or `page`; older exports mark pages with `page: true` instead
+    #[serde(rename = "type")]
+    post_type: Option<String>,
+    #[serde(default)]
+    page: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tag {
+    id: Value,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PostTag {
+    post_id: Value,
+    tag_id: Value,
+    #[serde(default)]
+    sort_order: i64,
+}
+
+impl Post {
+    fn is_page(&self) -> bool {
+        self.post_type.as_deref() == Some("page") || self.page == true || self.page == 1
+    }
+}
+
+/// Read the posts from the contents of a Ghost export
+///
+/// # Errors
+///
+/// Returns an error if the JSON is not a Ghost export
+pub fn parse(json: &str) -> Result<Vec<ImportedPost>> {
+    let export: Export = serde_json::from_str(json).context("Invalid Ghost export")?;
+    let mut posts = Vec::new();
+
+    for database in export.db {
+        let data = database.data;
+        let tags: HashMap<String, &str> = data
+            .tags
+            .iter()
+            .filter(|tag| !tag.name.starts_with('#'))
+            .map(|tag| (tag.id.to_string(), tag.name.as_str()))
+            .collect();
+
+        let mut post_tags: HashMap<String, Vec<&PostTag>> = HashMap::new();
+        for post_tag in &data.posts_tags {
+            post_tags.entry(post_tag.post_id.to_string()).or_default().push(post_tag);
+        }
+
+        for post in data.posts.iter().filter(|post| !post.is_page()) {
+            let mut links = post_tags.remove(&post.id.to_string()).unwrap_or_default();
+            links.sort_by_key(|link| link.sort_order);
+            let names: Vec<String> = links
+                .iter()
+                .filter_map(|link| tags.get(&link.tag_id.to_string()))
+                .map(|name| name.to_string())
+                .collect();
+
+            posts.push(ImportedPost {
+                title: post.title.clone(),
+                slug: post.slug.clone(),
+                date: post.published_at.as_deref().and_then(parse_date),
+                categories: names.first().cloned().into_iter().collect(),
+                tags: names,
+                description: post.custom_excerpt.clone().filter(|excerpt| !excerpt.is_empty()),
+                draft: post.status != "published",
+                html: post.html.clone().unwrap_or_default(),
+                featured_image: post.feature_image.clone(),
+                source_url: None,
+                ..Default::default()
+            });
+        }
+    }
+
+    Ok(posts)
+}
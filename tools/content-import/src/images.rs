@@ -0,0 +1,168 @@
+//! # Imported Images
+//!
+//! Finds the images a post references and copies them into its article
+//! directory, from the web or from the export itself.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use scraper::{Html, Selector};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
+
+/// Ghost writes this in place of the site address in its exports
+const GHOST_URL: &str = "__GHOST_URL__";
+
+/// Where an image can be copied from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageLocation {
+    Remote(Url),
+    Local(PathBuf),
+}
+
+impl fmt::Display for ImageLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageLocation::Remote(url) => write!(f, "{}", url),
+            ImageLocation::Local(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The `src` of every image in an HTML fragment, in order and without duplicates
+pub fn image_sources(html: &str) -> Vec<String> {
+    let selector = Selector::parse("img[src]").expect("valid selector");
+    let mut sources: Vec<String> = Vec::new();
+
+    for element in Html::parse_fragment(html).select(&selector) {
+        let src = element.value().attr("src").unwrap_or_default().trim().to_string();
+        if !src.is_empty() && !src.starts_with("data:") && !sources.contains(&src) {
+            sources.push(src);
+        }
+    }
+
+    sources
+}
+
+/// The target of every image in a markdown document, in order and without duplicates
+pub fn markdown_image_sources(markdown: &str) -> Vec<String> {
+    static IMAGE: OnceLock<Regex> = OnceLock::new();
+    let image = IMAGE.get_or_init(|| Regex::new(r"!\[[^\]]*\]\(\s*<?([^)\s>]+)>?").expect("valid regex"));
+    let mut sources: Vec<String> = Vec::new();
+
+    for captures in image.captures_iter(markdown) {
+        let src = captures[1].to_string();
+        if !src.starts_with("data:") && !sources.contains(&src) {
+            sources.push(src);
+        }
+    }
+
+    sources
+}
+
+/// Work out where an image link points
+///
+/// Absolute `http` and `https` links are fetched from the web. Other links
+/// are looked up in the export directory first, then resolved against
+/// `site_url`. Links leading out of the export directory are never read
+/// from disk. Returns `None` for links that cannot be resolved.
+pub fn resolve(src: &str, site_url: Option<&str>, export_dir: Option<&Path>) -> Option<ImageLocation> {
+    let site_url = site_url.map(|url| url.trim_end_matches('/'));
+    let src = match site_url {
+        Some(site_url) => src.replace(GHOST_URL, site_url),
+        None if src.contains(GHOST_URL) => return None,
+        None => src.to_string(),
+    };
+
+    if let Some(rest) = src.strip_prefix("//") {
+        return Url::parse(&format!("https://{}", rest)).ok().map(ImageLocation::Remote);
+    }
+    if let Ok(url) = Url::parse(&src) {
+        return matches!(url.scheme(), "http" | "https").then_some(ImageLocation::Remote(url));
+    }
+
+    if let Some(path) = export_dir.and_then(|dir| export_file(dir, &src)) {
+        return Some(ImageLocation::Local(path));
+    }
+
+    let base = Url::parse(&format!("{}/", site_url?)).ok()?;
+    base.join(&src).ok().map(ImageLocation::Remote)
+}
+
+/// The file a link points at in the export directory, if it is inside it
+fn export_file(export_dir: &Path, src: &str) -> Option<PathBuf> {
+    let relative = Path::new(src.trim_start_matches('/'));
+    if !relative.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir)) {
+        return None;
+    }
+
+    // Symbolic links in the export could still point elsewhere
+    let path = export_dir.join(relative).canonicalize().ok()?;
+    let export_dir = export_dir.canonicalize().ok()?;
+    (path.is_file() && path.starts_with(&export_dir)).then_some(path)
+}
+
+/// A file name for an image that is not already used by another image of the post
+pub fn unique_file_name(location: &ImageLocation, taken: &[(ImageLocation, String)]) -> String {
+    let name = match location {
+        ImageLocation::Remote(url) => url.path_segments().and_then(|mut segments| segments.next_back()).unwrap_or_default().to_string(),
+        ImageLocation::Local(path) => path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+    };
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '-' })
+        .collect();
+    let name = if name.trim_matches(['.', '-']).is_empty() { "image".to_string() } else { name };
+
+    let is_taken = |candidate: &str| taken.iter().any(|(_, file_name)| file_name == candidate);
+    if !is_taken(&name) {
+        return name;
+    }
+
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem.to_string(), format!(".{}", extension)),
+        _ => (name.clone(), String::new()),
+    };
+    (2..)
+        .map(|n| format!("{}-{}{}", stem, n, extension))
+        .find(|candidate| !is_taken(candidate))
+        .unwrap_or(name)
+}
+
+/// Copy an image to `destination`
+///
+/// # Errors
+///
+/// Returns an error if the image cannot be downloaded or copied
+pub fn fetch(location: &ImageLocation, destination: &Path) -> Result<()> {
+    match location {
+        ImageLocation::Remote(url) => {
+            let response = client()
+                .get(url.as_str())
+                .send()
+                .and_then(|response| response.error_for_status())
+                .with_context(|| format!("Failed to download {}", url))?;
+            let bytes = response.bytes().with_context(|| format!("Failed to download {}", url))?;
+            std::fs::write(destination, &bytes)
+                .with_context(|| format!("Failed to write {}", destination.display()))?;
+        }
+        ImageLocation::Local(path) => {
+            std::fs::copy(path, destination)
+                .with_context(|| format!("Failed to copy {} to {}", path.display(), destination.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn client() -> &'static reqwest::blocking::Client {
+    static CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("content-import/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
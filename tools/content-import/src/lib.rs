@@ -0,0 +1,471 @@
+//! # Content Import
+//!
+//! Brings posts from other platforms into the content repository:
+//!
+//! * `wordpress` - a WordPress WXR export (`.xml`)
+//! * `ghost` - a Ghost JSON export (`.json`)
+//! * `medium` - the `posts/` directory of a Medium export, or one of its `.html` files
+//! * `url` - a single web page
+//! * `notion` - the pages of a Notion database, as drafts
+//!
+//! Each post is converted to markdown with frontmatter and written to
+//! `{topic}/{slug}/{slug}.md`. The topic comes from the post's categories,
+//! either through an explicit category-to-topic mapping or by matching a
+//! configured topic's key or name, and falls back to a default topic.
+//! Images referenced by a post are copied into its article directory and
+//! its links rewritten to point at the copies.
+//!
+//! Imports are recorded in the journal, so `write undo` removes them again.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use common_fs::Journal;
+use common_models::Config;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub mod ghost;
+pub mod images;
+pub mod medium;
+pub mod notion;
+pub mod report;
+pub mod web;
+pub mod wordpress;
+
+pub use report::{ImportItem, ImportReport, ImportStatus};
+
+/// A post read from an export, before it is converted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportedPost {
+    pub title: String,
+    /// Slug on the source platform; the title is slugified when empty
+    pub slug: String,
+    pub date: Option<NaiveDate>,
+    /// Categories, used to choose the topic
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub draft: bool,
+    /// The post body as HTML
+    pub html: String,
+    /// The post body as markdown, used in place of `html` when set
+    pub markdown: Option<String>,
+    pub featured_image: Option<String>,
+    /// Where the post was published
+    pub source_url: Option<String>,
+    /// The Notion page the post was read from
+    pub notion_id: Option<String>,
+}
+
+impl ImportedPost {
+    /// The slug to import the post under
+    pub fn target_slug(&self) -> String {
+        let slug = slug::slugify(if self.slug.trim().is_empty() { &self.title } else { &self.slug });
+        if slug.is_empty() {
+            "untitled".to_string()
+        } else {
+            slug
+        }
+    }
+}
+
+/// The platform an export comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Wordpress,
+    Ghost,
+    Medium,
+    Url,
+    Notion,
+}
+
+/// What to do when a post's slug is already used
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Collision {
+    /// Leave the existing content alone and skip the post
+    #[default]
+    Skip,
+    /// Import the post under the first free `{slug}-2`, `{slug}-3`, ...
+    Rename,
+    /// Replace the existing content
+    Overwrite,
+}
+
+/// Options for an import
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Export file or directory, a URL, or a Notion database ID
+    pub source: String,
+    /// Platform of the export; detected from `source` when not set
+    pub format: Option<SourceFormat>,
+    /// Topic for posts whose categories match no topic
+    pub default_topic: Option<String>,
+    /// Category to topic key, compared case-insensitively
+    pub topic_map: BTreeMap<String, String>,
+    pub collision: Collision,
+    /// Copy referenced images into the article directories
+    pub download_images: bool,
+    /// Address of the original site, for resolving relative image links
+    /// and Ghost's `__GHOST_URL__` placeholder
+    pub site_url: Option<String>,
+    /// Import every post as a draft, even if it was published
+    pub draft: bool,
+    /// Title for a page imported from a URL, in place of the one on the page
+    pub title: Option<String>,
+    /// Tags added to every imported post
+    pub tags: Vec<String>,
+    /// Report what would be imported without writing anything
+    pub dry_run: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            source: String::new(),
+            format: None,
+            default_topic: None,
+            topic_map: BTreeMap::new(),
+            collision: Collision::Skip,
+            download_images: true,
+            site_url: None,
+            draft: false,
+            title: None,
+            tags: Vec::new(),
+            dry_run: false,
+        }
+    }
+}
+
+/// Work out the platform of an export from its name
+///
+/// # Errors
+///
+/// Returns an error if the format cannot be told from the name
+pub fn detect_format(source: &str) -> Result<SourceFormat> {
+    if notion::is_notion_url(source) {
+        return Ok(SourceFormat::Notion);
+    }
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return Ok(SourceFormat::Url);
+    }
+
+    let path = Path::new(source);
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("xml") => Ok(SourceFormat::Wordpress),
+        Some("json") => Ok(SourceFormat::Ghost),
+        Some("html") => Ok(SourceFormat::Medium),
+        _ if path.is_dir() => Ok(SourceFormat::Medium),
+        _ => Err(anyhow::anyhow!(
+            "Cannot tell the format of {}; pass --from wordpress, ghost, medium, url or notion",
+            source
+        )),
+    }
+}
+
+/// Read the posts from an export
+///
+/// # Errors
+///
+/// Returns an error if the export cannot be read or parsed
+pub fn read_posts(source: &str, format: SourceFormat) -> Result<Vec<ImportedPost>> {
+    match format {
+        SourceFormat::Wordpress => wordpress::parse(&read_source(source)?),
+        SourceFormat::Ghost => ghost::parse(&read_source(source)?),
+        SourceFormat::Medium => medium::read(Path::new(source)),
+        SourceFormat::Url => Ok(vec![web::fetch(source)?]),
+        SourceFormat::Notion => notion::fetch(common_config::load_config()?.import.notion.as_ref(), source),
+    }
+}
+
+fn read_source(source: &str) -> Result<String> {
+    std::fs::read_to_string(source).with_context(|| format!("Failed to read {}", source))
+}
+
+/// Import the posts of an export into the configured content repository
+///
+/// # Errors
+///
+/// Returns an error if the configuration or export cannot be read, or
+/// content cannot be written
+pub fn import_content(options: &ImportOptions) -> Result<ImportReport> {
+    let format = match options.format {
+        Some(format) => format,
+        None => detect_format(&options.source)?,
+    };
+    if options.title.is_some() && format != SourceFormat::Url {
+        anyhow::bail!("A title can only be given when importing a page from a URL");
+    }
+    let config = common_config::load_config()?;
+    let posts = match format {
+        SourceFormat::Notion => notion::fetch(config.import.notion.as_ref(), &options.source)?,
+        _ => read_posts(&options.source, format)?,
+    };
+
+    // Relative image links in file exports are relative to the export
+    let source = Path::new(&options.source);
+    let export_dir = match format {
+        SourceFormat::Url | SourceFormat::Notion => None,
+        _ if source.is_dir() => Some(source.to_path_buf()),
+        _ => source.parent().map(Path::to_path_buf),
+    };
+
+    import_posts(posts, options, &config, export_dir.as_deref())
+}
+
+/// Import posts into the content repository described by `config`
+///
+/// # Parameters
+///
+/// * `posts` - The posts to import
+/// * `options` - How to choose topics, handle collisions and fetch images
+/// * `config` - The configuration of the content repository
+/// * `export_dir` - Directory that relative image links are resolved against
+///
+/// # Errors
+///
+/// Returns an error if content cannot be written or the journal cannot be updated
+pub fn import_posts(
+    posts: Vec<ImportedPost>,
+    options: &ImportOptions,
+    config: &Config,
+    export_dir: Option<&Path>,
+) -> Result<ImportReport> {
+    let base_dir = PathBuf::from(&config.content.base_dir);
+    let site_url = options.site_url.as_deref();
+    let mut transaction = if options.dry_run {
+        None
+    } else {
+        Some(Journal::for_base_dir(&base_dir).begin("import", format!("Import {}", options.source))?)
+    };
+
+    let notion_pages = if posts.iter().any(|post| post.notion_id.is_some()) {
+        notion::imported_pages(config)
+    } else {
+        HashMap::new()
+    };
+    let mut claimed: HashSet<String> = HashSet::new();
+    let mut items = Vec::new();
+
+    for mut post in posts {
+        post.draft |= options.draft;
+        if let Some(title) = &options.title {
+            post.title = title.clone();
+        }
+        for tag in &options.tags {
+            if !post.tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                post.tags.push(tag.clone());
+            }
+        }
+        let slug = post.target_slug();
+
+        // A page imported before replaces its draft, wherever it was put
+        let imported_page = post.notion_id.as_ref().and_then(|id| notion_pages.get(id));
+        if let Some(page) = imported_page.filter(|page| !page.draft) {
+            let reason = format!("Already imported as {}/{}, which is no longer a draft", page.topic, page.slug);
+            items.push(ImportItem::skipped(&post, page.slug.clone(), reason));
+            continue;
+        }
+        let topic = match imported_page {
+            Some(page) => page.topic.clone(),
+            None => match choose_topic(&post, options, config) {
+                Ok(topic) => topic,
+                Err(reason) => {
+                    items.push(ImportItem::skipped(&post, slug, reason));
+                    continue;
+                }
+            },
+        };
+
+        let existing = |slug: &str| claimed.contains(slug) || existing_content_dir(config, slug).is_some();
+        let (slug, status) = if let Some(page) = imported_page {
+            (page.slug.clone(), ImportStatus::Updated)
+        } else if !existing(&slug) {
+            (slug, ImportStatus::Imported)
+        } else {
+            match options.collision {
+                Collision::Skip => {
+                    let reason = format!("Content with slug '{}' already exists", slug);
+                    items.push(ImportItem::skipped(&post, slug, reason));
+                    continue;
+                }
+                Collision::Rename => {
+                    let renamed = (2..).map(|n| format!("{}-{}", slug, n)).find(|candidate| !existing(candidate));
+                    let renamed = renamed.unwrap_or_default();
+                    (renamed, ImportStatus::Renamed { from: slug })
+                }
+                Collision::Overwrite if claimed.contains(&slug) => {
+                    let reason = format!("Slug '{}' is used by an earlier post in this import", slug);
+                    items.push(ImportItem::skipped(&post, slug, reason));
+                    continue;
+                }
+                Collision::Overwrite => (slug, ImportStatus::Overwritten),
+            }
+        };
+        claimed.insert(slug.clone());
+
+        let topic_dir = &config.content.topics[&topic].directory;
+        let article_dir = base_dir.join(topic_dir).join(&slug);
+        let content_file = article_dir.join(format!("{}.md", slug));
+        let mut item = ImportItem::new(&post, &topic, &slug, content_file.clone(), status);
+
+        let mut markdown = match &post.markdown {
+            Some(markdown) => markdown.trim().to_string(),
+            None => html2md::parse_html(&post.html).trim().to_string(),
+        };
+        let mut featured_image = post.featured_image.clone();
+        let mut pending = Vec::new();
+        if options.download_images {
+            let mut sources = match &post.markdown {
+                Some(markdown) => images::markdown_image_sources(markdown),
+                None => images::image_sources(&post.html),
+            };
+            sources.extend(post.featured_image.clone().filter(|image| !sources.contains(image)));
+            for src in sources {
+                let Some(location) = images::resolve(&src, site_url, export_dir) else {
+                    item.warnings.push(format!("Could not resolve image {}", src));
+                    continue;
+                };
+                let file_name = images::unique_file_name(&location, &pending);
+                markdown = markdown.replace(&src, &file_name);
+                if featured_image.as_deref() == Some(src.as_str()) {
+                    featured_image = Some(file_name.clone());
+                }
+                item.images.push(file_name.clone());
+                pending.push((location, file_name));
+            }
+        }
+
+        if let Some(transaction) = transaction.as_mut() {
+            if matches!(item.status, ImportStatus::Overwritten | ImportStatus::Updated) {
+                if let Some(existing_dir) = existing_content_dir(config, &slug) {
+                    transaction.snapshot(&existing_dir)?;
+                    std::fs::remove_dir_all(&existing_dir)
+                        .with_context(|| format!("Failed to remove {}", existing_dir.display()))?;
+                }
+            }
+            transaction.snapshot(&article_dir)?;
+
+            common_fs::create_dir_all(&article_dir)?;
+            for (location, file_name) in &pending {
+                if let Err(err) = images::fetch(location, &article_dir.join(file_name)) {
+                    item.warnings.push(format!("Could not download {}: {}", location, err));
+                }
+            }
+
+            let document = render_document(&post, &topic, &slug, featured_image.as_deref(), &markdown)?;
+            common_fs::write_file(&content_file, &document)?;
+        }
+
+        items.push(item);
+    }
+
+    if let Some(transaction) = transaction {
+        if items.iter().any(|item| item.is_imported()) {
+            transaction.commit()?;
+        }
+    }
+
+    Ok(ImportReport {
+        items,
+        dry_run: options.dry_run,
+    })
+}
+
+/// Choose the topic for a post, or explain why there is none
+fn choose_topic(post: &ImportedPost, options: &ImportOptions, config: &Config) -> std::result::Result<String, String> {
+    let topics = &config.content.topics;
+
+    for category in &post.categories {
+        let mapped = options
+            .topic_map
+            .iter()
+            .find(|(from, _)| from.eq_ignore_ascii_case(category))
+            .map(|(_, to)| to.clone());
+        if let Some(topic) = mapped {
+            return if topics.contains_key(&topic) {
+                Ok(topic)
+            } else {
+                Err(format!("Category '{}' maps to topic '{}', which is not configured", category, topic))
+            };
+        }
+
+        let key = slug::slugify(category);
+        let matched = topics
+            .iter()
+            .find(|(topic_key, topic)| **topic_key == key || topic.name.eq_ignore_ascii_case(category));
+        if let Some((topic_key, _)) = matched {
+            return Ok(topic_key.clone());
+        }
+    }
+
+    match &options.default_topic {
+        Some(topic) if topics.contains_key(topic) => Ok(topic.clone()),
+        Some(topic) => Err(format!("Topic '{}' is not configured", topic)),
+        None if post.categories.is_empty() => Err("No categories; pass --topic to choose one".to_string()),
+        None => Err(format!(
+            "No topic for categories {}; pass --topic or --map",
+            post.categories.join(", ")
+        )),
+    }
+}
+
+/// The article directory using `slug` in any topic
+fn existing_content_dir(config: &Config, slug: &str) -> Option<PathBuf> {
+    config
+        .content
+        .topics
+        .values()
+        .map(|topic| Path::new(&config.content.base_dir).join(&topic.directory).join(slug))
+        .find(|dir| dir.exists())
+}
+
+/// Render an imported post as a markdown document with frontmatter
+///
+/// # Errors
+///
+/// Returns an error if the frontmatter cannot be serialized
+pub fn render_document(
+    post: &ImportedPost,
+    topic: &str,
+    slug: &str,
+    featured_image: Option<&str>,
+    markdown: &str,
+) -> Result<String> {
+    let mut frontmatter = serde_yaml::Mapping::new();
+    let mut set = |key: &str, value: serde_yaml::Value| {
+        frontmatter.insert(key.into(), value);
+    };
+
+    set("title", post.title.clone().into());
+    if let Some(description) = &post.description {
+        set("description", description.clone().into());
+    }
+    set("slug", slug.into());
+    set("topics", vec![topic.to_string()].into());
+    set("tags", post.tags.clone().into());
+    if let Some(date) = post.date {
+        set("published", date.format("%Y-%m-%d").to_string().into());
+    }
+    set("draft", post.draft.into());
+    if let Some(image) = featured_image {
+        set("featured_image", image.into());
+    }
+    if let Some(url) = &post.source_url {
+        set("source_url", url.clone().into());
+    }
+    if let Some(id) = &post.notion_id {
+        set(notion::PAGE_ID_FIELD, id.clone().into());
+    }
+
+    Ok(format!("---\n{}---\n\n{}\n", serde_yaml::to_string(&frontmatter)?, markdown))
+}
+
+/// Parse the date at the start of a timestamp such as `2024-01-31 10:00:00`
+pub(crate) fn parse_date(timestamp: &str) -> Option<NaiveDate> {
+    timestamp
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
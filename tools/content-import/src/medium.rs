@@ -0,0 +1,85 @@
+//! # Medium
+//!
+//! Reads posts from a Medium export, the archive from Settings → Download
+//! your information. Each post is an HTML file in its `posts/` directory;
+//! unpublished posts have names starting with `draft_`. Medium has no
+//! categories, so imported posts go to the default topic unless mapped.
+
+use anyhow::{Context, Result};
+use scraper::{ElementRef, Html, Selector};
+use std::path::{Path, PathBuf};
+
+use crate::{parse_date, ImportedPost};
+
+/// Read the posts in a Medium export
+///
+/// `path` may be the export directory, its `posts/` directory or a single
+/// post's HTML file.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a post cannot be read
+pub fn read(path: &Path) -> Result<Vec<ImportedPost>> {
+    let files: Vec<PathBuf> = if path.is_dir() {
+        let posts_dir = if path.join("posts").is_dir() { path.join("posts") } else { path.to_path_buf() };
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&posts_dir)
+            .with_context(|| format!("Failed to read {}", posts_dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.extension().is_some_and(|extension| extension == "html"))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    files
+        .iter()
+        .map(|file| {
+            let html = std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+            let draft = file
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("draft_"));
+            Ok(parse(&html, draft))
+        })
+        .collect()
+}
+
+/// Read a post from a Medium HTML file
+pub fn parse(html: &str, draft: bool) -> ImportedPost {
+    let document = Html::parse_document(html);
+    let first = |selector: &str| {
+        let selector = Selector::parse(selector).expect("valid selector");
+        document.select(&selector).next()
+    };
+    let text = |element: ElementRef| element.text().collect::<String>().trim().to_string();
+
+    let title = first("h1.p-name").or_else(|| first("title")).map(text).unwrap_or_default();
+    let source_url = first("a.p-canonical").and_then(|link| link.value().attr("href")).map(String::from);
+
+    // Canonical links end in the slug followed by Medium's post id
+    let slug = source_url
+        .as_deref()
+        .and_then(|url| url.rsplit('/').next())
+        .map(|segment| match segment.rsplit_once('-') {
+            Some((slug, id)) if id.len() >= 8 && id.chars().all(|c| c.is_ascii_hexdigit()) => slug.to_string(),
+            _ => segment.to_string(),
+        })
+        .unwrap_or_default();
+
+    ImportedPost {
+        title,
+        slug,
+        date: first("time.dt-published")
+            .and_then(|time| time.value().attr("datetime"))
+            .and_then(parse_date),
+        categories: Vec::new(),
+        tags: Vec::new(),
+        description: first("section.p-summary").map(text).filter(|summary| !summary.is_empty()),
+        draft,
+        html: first("section.e-content").or_else(|| first("body")).map(|body| body.inner_html()).unwrap_or_default(),
+        featured_image: None,
+        source_url,
+        ..Default::default()
+    }
+}
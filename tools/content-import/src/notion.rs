@@ -0,0 +1,464 @@
+//! # Notion
+//!
+//! Imports the pages of a Notion database as drafts. Pages are read through
+//! the Notion API with the integration token and database set under
+//! `import.notion` in the configuration, and their blocks are converted to
+//! markdown.
+//!
+//! Each post records its page ID as `notion_id` in its frontmatter. Importing
+//! the database again updates the drafts an earlier import created instead
+//! of adding new ones, and leaves alone any that have since been published.
+
+use anyhow::{Context, Result};
+use common_models::{Config, NotionConfig};
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use url::Url;
+
+use crate::ImportedPost;
+
+/// Address of the Notion API
+pub const API_URL: &str = "https://api.notion.com/v1";
+
+/// Version of the Notion API the requests are written against
+pub const API_VERSION: &str = "2022-06-28";
+
+/// Frontmatter field that records the page an article was imported from
+pub const PAGE_ID_FIELD: &str = "notion_id";
+
+/// A client for the parts of the Notion API the importer uses
+pub struct NotionClient {
+    http: Client,
+    token: String,
+}
+
+impl NotionClient {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            http: Client::new(),
+            token: token.into(),
+        }
+    }
+
+    /// The pages of a database, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails
+    pub fn query_database(&self, database_id: &str) -> Result<Vec<Value>> {
+        let url = format!("{}/databases/{}/query", API_URL, database_id);
+        self.paginate(|cursor| {
+            let mut body = json!({
+                "page_size": 100,
+                "sorts": [{ "timestamp": "created_time", "direction": "ascending" }],
+            });
+            if let Some(cursor) = cursor {
+                body["start_cursor"] = cursor.into();
+            }
+            self.http.post(&url).json(&body)
+        })
+        .with_context(|| format!("Failed to query Notion database {}", database_id))
+    }
+
+    /// The blocks of a page or block, with the blocks nested in each under `children`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails
+    pub fn blocks(&self, block_id: &str) -> Result<Vec<Value>> {
+        let url = format!("{}/blocks/{}/children", API_URL, block_id);
+        let mut blocks = self
+            .paginate(|cursor| {
+                let mut query = vec![("page_size", "100")];
+                if let Some(cursor) = cursor {
+                    query.push(("start_cursor", cursor));
+                }
+                self.http.get(&url).query(&query)
+            })
+            .with_context(|| format!("Failed to read Notion blocks of {}", block_id))?;
+
+        for block in &mut blocks {
+            if block["has_children"].as_bool() == Some(true) {
+                if let Some(id) = block["id"].as_str() {
+                    block["children"] = Value::Array(self.blocks(id)?);
+                }
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Collect the `results` of every page of a paginated request
+    fn paginate(&self, request: impl Fn(Option<&str>) -> RequestBuilder) -> Result<Vec<Value>> {
+        let mut results = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response: Value = request(cursor.as_deref())
+                .bearer_auth(&self.token)
+                .header("Notion-Version", API_VERSION)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.json())?;
+
+            if let Some(page) = response["results"].as_array() {
+                results.extend(page.iter().cloned());
+            }
+            match response["next_cursor"].as_str() {
+                Some(next) if response["has_more"].as_bool() == Some(true) => cursor = Some(next.to_string()),
+                _ => return Ok(results),
+            }
+        }
+    }
+}
+
+/// Read the pages of a Notion database as posts
+///
+/// `source` is the ID or address of the database; the configured
+/// `import.notion.database_id` is used when it is empty.
+///
+/// # Errors
+///
+/// Returns an error if there is no token or database, or the API cannot be read
+pub fn fetch(settings: Option<&NotionConfig>, source: &str) -> Result<Vec<ImportedPost>> {
+    let database = if source.trim().is_empty() {
+        settings
+            .map(|settings| settings.database_id.clone())
+            .context("No Notion database; set import.notion.database_id or pass its ID")?
+    } else {
+        database_id(source)
+    };
+    let token = settings.cloned().unwrap_or_default().token().with_context(|| {
+        format!(
+            "No Notion token; set {} or import.notion.token",
+            NotionConfig::TOKEN_VAR
+        )
+    })?;
+
+    let client = NotionClient::new(token);
+    client
+        .query_database(&database)?
+        .iter()
+        .filter(|page| page["archived"].as_bool() != Some(true))
+        .map(|page| {
+            let id = page["id"].as_str().unwrap_or_default();
+            Ok(page_to_post(page, &client.blocks(id)?))
+        })
+        .collect()
+}
+
+/// The database ID in a database address such as
+/// `https://www.notion.so/team/0f9e6b1c2d3a4b5c8d7e6f5a4b3c2d1e?v=...`,
+/// or `source` itself when it is not an address
+pub fn database_id(source: &str) -> String {
+    let Ok(url) = Url::parse(source) else {
+        return source.trim().to_string();
+    };
+    let segment = url.path_segments().and_then(|mut segments| segments.next_back()).unwrap_or_default();
+    let hex: String = segment.chars().rev().take_while(char::is_ascii_hexdigit).collect();
+    if hex.len() >= 32 {
+        hex.chars().take(32).collect::<Vec<_>>().into_iter().rev().collect()
+    } else {
+        segment.to_string()
+    }
+}
+
+/// Check whether an address points at Notion
+pub fn is_notion_url(source: &str) -> bool {
+    Url::parse(source)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| {
+            host == "notion.so" || host.ends_with(".notion.so") || host.ends_with(".notion.site")
+        })
+}
+
+/// Read a page and its blocks as a draft post
+///
+/// The title comes from the page's title property. A multi-select property
+/// named `Tags`, a text property named `Description` or `Slug` and a date
+/// property named `Date` or `Published` are used when the database has them.
+pub fn page_to_post(page: &Value, blocks: &[Value]) -> ImportedPost {
+    let properties = page["properties"].as_object();
+    let property = |names: &[&str], kind: &str| {
+        properties.and_then(|properties| {
+            properties
+                .iter()
+                .find(|(name, value)| {
+                    value["type"] == kind && names.iter().any(|wanted| name.eq_ignore_ascii_case(wanted))
+                })
+                .map(|(_, value)| &value[kind])
+        })
+    };
+    let text = |names: &[&str]| {
+        property(names, "rich_text")
+            .map(plain_text)
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+    };
+
+    let title = properties
+        .and_then(|properties| properties.values().find(|value| value["type"] == "title"))
+        .map(|value| plain_text(&value["title"]))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let tags = property(&["tags"], "multi_select")
+        .and_then(Value::as_array)
+        .map(|options| {
+            options
+                .iter()
+                .filter_map(|option| option["name"].as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let date = property(&["date", "published"], "date")
+        .and_then(|date| date["start"].as_str())
+        .and_then(crate::parse_date);
+
+    ImportedPost {
+        title,
+        slug: text(&["slug"]).unwrap_or_default(),
+        date,
+        categories: Vec::new(),
+        tags,
+        description: text(&["description", "summary"]),
+        draft: true,
+        html: String::new(),
+        markdown: Some(blocks_to_markdown(blocks)),
+        featured_image: file_url(&page["cover"]),
+        source_url: page["url"].as_str().map(String::from),
+        notion_id: page["id"].as_str().map(String::from),
+    }
+}
+
+/// Convert Notion blocks to markdown
+pub fn blocks_to_markdown(blocks: &[Value]) -> String {
+    let mut output = String::new();
+    let mut previous: Option<&str> = None;
+    let mut number = 0;
+
+    for block in blocks {
+        let kind = block["type"].as_str().unwrap_or_default();
+        number = if kind == "numbered_list_item" && previous == Some(kind) {
+            number + 1
+        } else {
+            1
+        };
+
+        let Some(markdown) = block_to_markdown(block, kind, number) else {
+            continue;
+        };
+
+        if !output.is_empty() {
+            // Items of the same list stay together; everything else is a paragraph of its own
+            let same_list = previous == Some(kind) && is_list_item(kind);
+            output.push_str(if same_list { "\n" } else { "\n\n" });
+        }
+        output.push_str(&markdown);
+        previous = Some(kind);
+    }
+
+    output
+}
+
+fn is_list_item(kind: &str) -> bool {
+    matches!(kind, "bulleted_list_item" | "numbered_list_item" | "to_do")
+}
+
+/// Convert one block, or `None` for blocks with nothing to show
+fn block_to_markdown(block: &Value, kind: &str, number: usize) -> Option<String> {
+    let data = &block[kind];
+    let text = rich_text(&data["rich_text"]);
+    let children = block["children"].as_array().map(|children| blocks_to_markdown(children));
+    let with_children = |text: String| match children.as_deref() {
+        Some(children) if !children.is_empty() => format!("{}\n\n{}", text, children),
+        _ => text,
+    };
+
+    let markdown = match kind {
+        "paragraph" if text.is_empty() && children.is_none() => return None,
+        "paragraph" => with_children(text),
+        "heading_1" => with_children(format!("# {}", text)),
+        "heading_2" => with_children(format!("## {}", text)),
+        "heading_3" => with_children(format!("### {}", text)),
+        "bulleted_list_item" => list_item("- ", &text, children.as_deref()),
+        "numbered_list_item" => list_item(&format!("{}. ", number), &text, children.as_deref()),
+        "to_do" => {
+            let marker = if data["checked"].as_bool() == Some(true) { "- [x] " } else { "- [ ] " };
+            list_item(marker, &text, children.as_deref())
+        }
+        "quote" => quote(&with_children(text)),
+        "callout" => quote(&format!("[!NOTE]\n{}", with_children(text))),
+        "toggle" => format!(
+            "<details>\n<summary>{}</summary>\n\n{}\n\n</details>",
+            text,
+            children.unwrap_or_default()
+        ),
+        "code" => {
+            let language = data["language"].as_str().filter(|language| *language != "plain text");
+            format!("```{}\n{}\n```", language.unwrap_or_default(), plain_text(&data["rich_text"]))
+        }
+        "equation" => format!("$$\n{}\n$$", data["expression"].as_str().unwrap_or_default()),
+        "divider" => "---".to_string(),
+        "image" => {
+            let url = file_url(data)?;
+            format!("![{}]({})", plain_text(&data["caption"]).trim(), url)
+        }
+        "bookmark" | "embed" | "link_preview" | "video" | "audio" | "file" | "pdf" => {
+            let url = file_url(data).or_else(|| data["url"].as_str().map(String::from))?;
+            let caption = plain_text(&data["caption"]);
+            let label = if caption.trim().is_empty() { url.as_str() } else { caption.trim() };
+            format!("[{}]({})", label, url)
+        }
+        "table" => table(block["children"].as_array().map(Vec::as_slice).unwrap_or_default()),
+        "column_list" | "column" | "synced_block" => children.filter(|children| !children.is_empty())?,
+        "child_page" | "child_database" | "unsupported" | "" => {
+            format!("<!-- Notion {} not imported -->", kind.replace('_', " "))
+        }
+        other => format!("<!-- Notion {} block not imported -->", other.replace('_', " ")),
+    };
+
+    Some(markdown)
+}
+
+/// A list item, with nested blocks indented beneath it
+fn list_item(marker: &str, text: &str, children: Option<&str>) -> String {
+    let mut item = format!("{}{}", marker, text);
+    if let Some(children) = children.filter(|children| !children.is_empty()) {
+        for line in children.lines() {
+            item.push('\n');
+            if !line.is_empty() {
+                item.push_str("    ");
+                item.push_str(line);
+            }
+        }
+    }
+    item
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A table, using its first row as the header
+fn table(rows: &[Value]) -> String {
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .filter_map(|row| row["table_row"]["cells"].as_array())
+        .map(|cells| cells.iter().map(|cell| rich_text(cell).replace('|', "\\|")).collect())
+        .collect();
+    let Some(header) = rows.first() else {
+        return String::new();
+    };
+
+    let line = |cells: &[String]| format!("| {} |", cells.join(" | "));
+    let mut lines = vec![line(header), line(&vec!["---".to_string(); header.len()])];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+/// The address of a Notion file object, whether hosted by Notion or external
+fn file_url(file: &Value) -> Option<String> {
+    file["file"]["url"]
+        .as_str()
+        .or_else(|| file["external"]["url"].as_str())
+        .map(String::from)
+}
+
+/// Rich text without its formatting
+fn plain_text(rich_text: &Value) -> String {
+    rich_text
+        .as_array()
+        .map(|spans| spans.iter().filter_map(|span| span["plain_text"].as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Rich text as markdown
+fn rich_text(rich_text: &Value) -> String {
+    let Some(spans) = rich_text.as_array() else {
+        return String::new();
+    };
+
+    spans
+        .iter()
+        .map(|span| {
+            let text = span["plain_text"].as_str().unwrap_or_default();
+            if span["type"] == "equation" {
+                return format!("${}$", text);
+            }
+
+            // Markers must touch the text, so surrounding spaces stay outside them
+            let inner = text.trim();
+            if inner.is_empty() {
+                return text.to_string();
+            }
+            let leading = &text[..text.len() - text.trim_start().len()];
+            let trailing = &text[text.trim_end().len()..];
+
+            let annotations = &span["annotations"];
+            let on = |name: &str| annotations[name].as_bool() == Some(true);
+            let mut inner = if on("code") { format!("`{}`", inner) } else { inner.to_string() };
+            for (name, marker) in [("bold", "**"), ("italic", "*"), ("strikethrough", "~~")] {
+                if on(name) {
+                    inner = format!("{}{}{}", marker, inner, marker);
+                }
+            }
+            if let Some(href) = span["href"].as_str() {
+                inner = format!("[{}]({})", inner, href);
+            }
+            format!("{}{}{}", leading, inner, trailing)
+        })
+        .collect()
+}
+
+/// An article an earlier Notion import created
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedPage {
+    pub topic: String,
+    pub slug: String,
+    /// Still a draft, so an import may replace it
+    pub draft: bool,
+}
+
+/// The articles in the content repository that came from Notion, by page ID
+pub fn imported_pages(config: &Config) -> HashMap<String, ImportedPage> {
+    let mut pages = HashMap::new();
+
+    for (topic, topic_config) in &config.content.topics {
+        let topic_dir = Path::new(&config.content.base_dir).join(&topic_config.directory);
+        let Ok(entries) = std::fs::read_dir(&topic_dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let slug = entry.file_name().to_string_lossy().to_string();
+            let content_file = entry.path().join(format!("{}.md", slug));
+            let Ok(content) = std::fs::read_to_string(&content_file) else {
+                continue;
+            };
+            let Ok((frontmatter, _)) = common_markdown::extract_frontmatter_and_content(&content) else {
+                continue;
+            };
+            if let Some(id) = frontmatter.get_string(PAGE_ID_FIELD) {
+                let draft = frontmatter.is_draft.unwrap_or(false)
+                    || frontmatter.published_at.as_ref().is_none_or(|date| date.is_draft());
+                pages.insert(
+                    id.to_string(),
+                    ImportedPage {
+                        topic: topic.clone(),
+                        slug,
+                        draft,
+                    },
+                );
+            }
+        }
+    }
+
+    pages
+}
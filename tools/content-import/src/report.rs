@@ -0,0 +1,121 @@
+//! # Import Report
+//!
+//! What an import did, or would do in a dry run, to each post.
+
+use colored::*;
+use common_cli::DisplayResult;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::ImportedPost;
+
+/// What happened to one post
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ImportStatus {
+    Imported,
+    /// Imported under a new slug because `from` was taken
+    Renamed { from: String },
+    /// Imported in place of existing content with the same slug
+    Overwritten,
+    /// Replaced the draft an earlier import of the same page created
+    Updated,
+    Skipped { reason: String },
+}
+
+/// One post of an import
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportItem {
+    pub title: String,
+    pub slug: String,
+    pub topic: Option<String>,
+    /// The content file written for the post
+    pub path: Option<PathBuf>,
+    #[serde(flatten)]
+    pub status: ImportStatus,
+    /// Images copied into the article directory
+    pub images: Vec<String>,
+    /// Problems that did not stop the post from being imported
+    pub warnings: Vec<String>,
+}
+
+impl ImportItem {
+    pub fn new(post: &ImportedPost, topic: &str, slug: &str, path: PathBuf, status: ImportStatus) -> Self {
+        Self {
+            title: post.title.clone(),
+            slug: slug.to_string(),
+            topic: Some(topic.to_string()),
+            path: Some(path),
+            status,
+            images: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn skipped(post: &ImportedPost, slug: String, reason: String) -> Self {
+        Self {
+            title: post.title.clone(),
+            slug,
+            topic: None,
+            path: None,
+            status: ImportStatus::Skipped { reason },
+            images: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn is_imported(&self) -> bool {
+        !matches!(self.status, ImportStatus::Skipped { .. })
+    }
+}
+
+/// The posts of an import
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    pub items: Vec<ImportItem>,
+    /// Nothing was written
+    pub dry_run: bool,
+}
+
+impl ImportReport {
+    pub fn imported(&self) -> usize {
+        self.items.iter().filter(|item| item.is_imported()).count()
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.items.len() - self.imported()
+    }
+}
+
+impl DisplayResult for ImportReport {
+    fn to_display(&self) -> String {
+        let mut lines = Vec::new();
+
+        for item in &self.items {
+            let name = format!("{}/{}", item.topic.as_deref().unwrap_or("?"), item.slug);
+            let images = match item.images.len() {
+                0 => String::new(),
+                1 => " (1 image)".to_string(),
+                n => format!(" ({} images)", n),
+            };
+            let line = match &item.status {
+                ImportStatus::Imported => format!("{} {}{}", "✓".green(), name, images),
+                ImportStatus::Renamed { from } => {
+                    format!("{} {}{} ({} was taken)", "✓".green(), name, images, from)
+                }
+                ImportStatus::Overwritten => format!("{} {}{} (replaced)", "✓".yellow(), name, images),
+                ImportStatus::Updated => format!("{} {}{} (updated)", "✓".green(), name, images),
+                ImportStatus::Skipped { reason } => format!("{} {}: {}", "✗".red(), item.title, reason),
+            };
+            lines.push(line);
+
+            for warning in &item.warnings {
+                lines.push(format!("    {} {}", "Warning:".yellow(), warning));
+            }
+        }
+
+        let verb = if self.dry_run { "Would import" } else { "Imported" };
+        lines.push(format!("{} {} posts, skipped {}", verb.bold(), self.imported(), self.skipped()));
+        lines.join("\n")
+    }
+}
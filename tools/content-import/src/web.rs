@@ -0,0 +1,57 @@
+//! # Web Pages
+//!
+//! Imports a single published page by URL, taking its title from
+//! `og:title` or `<title>` and its body from `<article>`, `<main>` or
+//! `<body>`, whichever comes first.
+
+use anyhow::{Context, Result};
+use scraper::{Html, Selector};
+
+use crate::ImportedPost;
+
+/// Download a page and read it as a post
+///
+/// # Errors
+///
+/// Returns an error if the page cannot be downloaded
+pub fn fetch(url: &str) -> Result<ImportedPost> {
+    let html = reqwest::blocking::get(url)
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .with_context(|| format!("Failed to fetch {}", url))?;
+
+    Ok(parse(&html, url))
+}
+
+/// Read a downloaded page as a post
+pub fn parse(html: &str, url: &str) -> ImportedPost {
+    let document = Html::parse_document(html);
+    let first = |selector: &str| {
+        let selector = Selector::parse(selector).expect("valid selector");
+        document.select(&selector).next()
+    };
+
+    let title = first("meta[property='og:title']")
+        .and_then(|meta| meta.value().attr("content").map(String::from))
+        .or_else(|| first("title").map(|title| title.text().collect()))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    let description = first("meta[name='description']")
+        .and_then(|meta| meta.value().attr("content"))
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    let html = first("article")
+        .or_else(|| first("main"))
+        .or_else(|| first("body"))
+        .map(|body| body.inner_html())
+        .unwrap_or_default();
+
+    ImportedPost {
+        title,
+        description,
+        html,
+        source_url: Some(url.to_string()),
+        ..Default::default()
+    }
+}
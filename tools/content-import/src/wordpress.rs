@@ -0,0 +1,107 @@
+//! # WordPress
+//!
+//! Reads posts from a WordPress WXR export, the XML file written by
+//! Tools → Export. Pages, attachments and other post types are left out;
+//! attachments are only used to find each post's featured image.
+
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+use crate::{parse_date, ImportedPost};
+
+/// An `<item>` of the export, as far as it matters here
+#[derive(Debug, Default)]
+struct Item {
+    post: ImportedPost,
+    id: String,
+    post_type: String,
+    attachment_url: Option<String>,
+    thumbnail_id: Option<String>,
+}
+
+/// Read the posts from the contents of a WXR file
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed
+pub fn parse(xml: &str) -> Result<Vec<ImportedPost>> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut items = Vec::new();
+    let mut item: Option<Item> = None;
+    let mut text = String::new();
+    let mut category_domain = None;
+    let mut meta_key = String::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .with_context(|| format!("Invalid WordPress export at byte {}", reader.buffer_position()))?;
+
+        match event {
+            Event::Start(element) => {
+                text.clear();
+                match element.name().as_ref() {
+                    b"item" => item = Some(Item::default()),
+                    b"category" => category_domain = attribute(&element, "domain"),
+                    _ => {}
+                }
+            }
+            Event::Text(content) => text.push_str(&content.unescape()?),
+            Event::CData(content) => text.push_str(&String::from_utf8_lossy(&content.into_inner())),
+            Event::End(element) => {
+                let Some(current) = item.as_mut() else {
+                    continue;
+                };
+                let value = std::mem::take(&mut text).trim().to_string();
+                let post = &mut current.post;
+
+                match element.name().as_ref() {
+                    b"item" => items.extend(item.take()),
+                    b"title" => post.title = value,
+                    b"link" => post.source_url = Some(value).filter(|link| !link.is_empty()),
+                    b"content:encoded" => post.html = value,
+                    b"excerpt:encoded" => post.description = Some(value).filter(|excerpt| !excerpt.is_empty()),
+                    b"wp:post_id" => current.id = value,
+                    b"wp:post_name" => post.slug = value,
+                    b"wp:post_date" => post.date = parse_date(&value),
+                    b"wp:status" => post.draft = value != "publish",
+                    b"wp:post_type" => current.post_type = value,
+                    b"wp:attachment_url" => current.attachment_url = Some(value),
+                    b"wp:meta_key" => meta_key = value,
+                    b"wp:meta_value" if meta_key == "_thumbnail_id" => current.thumbnail_id = Some(value),
+                    b"category" => match category_domain.take().as_deref() {
+                        Some("category") => post.categories.push(value),
+                        Some("post_tag") => post.tags.push(value),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let attachments: HashMap<String, String> = items
+        .iter()
+        .filter_map(|item| Some((item.id.clone(), item.attachment_url.clone()?)))
+        .collect();
+
+    Ok(items
+        .into_iter()
+        .filter(|item| item.post_type == "post")
+        .map(|mut item| {
+            item.post.featured_image = item.thumbnail_id.and_then(|id| attachments.get(&id).cloned());
+            item.post
+        })
+        .collect())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    let attribute = element.try_get_attribute(name).ok()??;
+    attribute.unescape_value().ok().map(|value| value.to_string())
+}
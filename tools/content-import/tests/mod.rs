@@ -0,0 +1,7 @@
+//! Test modules for content-import
+
+// Unit tests
+#[path = "unit/import_tests.rs"]
+mod import_tests;
+#[path = "unit/notion_tests.rs"]
+mod notion_tests;
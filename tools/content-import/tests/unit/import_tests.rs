@@ -0,0 +1,291 @@
+use chrono::NaiveDate;
+use common_fs::Journal;
+use common_models::Config;
+use content_import::images::{self, ImageLocation};
+use content_import::{
+    detect_format, ghost, import_posts, medium, wordpress, Collision, ImportOptions, ImportStatus, ImportedPost,
+    SourceFormat,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod import_tests {
+    use super::*;
+
+    const WXR: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/" xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+  <title>My Blog</title>
+  <item>
+    <title>Hello &amp; Welcome</title>
+    <link>https://example.com/2024/01/hello/</link>
+    <content:encoded><![CDATA[<p>First <strong>post</strong>.</p><img src="https://example.com/wp-content/uploads/hero.jpg" />]]></content:encoded>
+    <excerpt:encoded><![CDATA[A short hello]]></excerpt:encoded>
+    <wp:post_id>1</wp:post_id>
+    <wp:post_date>2024-01-31 10:00:00</wp:post_date>
+    <wp:post_name>hello-welcome</wp:post_name>
+    <wp:status>publish</wp:status>
+    <wp:post_type>post</wp:post_type>
+    <category domain="category" nicename="notes"><![CDATA[Notes]]></category>
+    <category domain="post_tag" nicename="rust"><![CDATA[rust]]></category>
+    <wp:postmeta>
+      <wp:meta_key>_thumbnail_id</wp:meta_key>
+      <wp:meta_value>9</wp:meta_value>
+    </wp:postmeta>
+  </item>
+  <item>
+    <title>Unfinished</title>
+    <content:encoded><![CDATA[<p>Draft</p>]]></content:encoded>
+    <wp:post_id>2</wp:post_id>
+    <wp:post_date>0000-00-00 00:00:00</wp:post_date>
+    <wp:post_name></wp:post_name>
+    <wp:status>draft</wp:status>
+    <wp:post_type>post</wp:post_type>
+  </item>
+  <item>
+    <title>About</title>
+    <wp:post_id>3</wp:post_id>
+    <wp:post_type>page</wp:post_type>
+  </item>
+  <item>
+    <title>hero</title>
+    <wp:post_id>9</wp:post_id>
+    <wp:post_type>attachment</wp:post_type>
+    <wp:attachment_url>https://example.com/wp-content/uploads/hero.jpg</wp:attachment_url>
+  </item>
+</channel>
+</rss>"#;
+
+    const GHOST: &str = r##"{"db": [{"data": {
+        "posts": [
+            {"id": "p1", "title": "Ghostly", "slug": "ghostly", "html": "<p>Boo</p>", "status": "published",
+             "published_at": "2023-05-06T07:08:09.000Z", "custom_excerpt": "Spooky", "feature_image": "__GHOST_URL__/content/images/boo.png", "type": "post"},
+            {"id": "p2", "title": "Later", "slug": "later", "html": null, "status": "draft",
+             "published_at": null, "custom_excerpt": null, "feature_image": null, "type": "post"},
+            {"id": "p3", "title": "Contact", "slug": "contact", "html": "", "status": "published",
+             "published_at": null, "custom_excerpt": null, "feature_image": null, "type": "page"}
+        ],
+        "tags": [{"id": "t1", "name": "Notes"}, {"id": "t2", "name": "rust"}, {"id": "t3", "name": "#internal"}],
+        "posts_tags": [
+            {"post_id": "p1", "tag_id": "t2", "sort_order": 1},
+            {"post_id": "p1", "tag_id": "t1", "sort_order": 0},
+            {"post_id": "p1", "tag_id": "t3", "sort_order": 2}
+        ]
+    }}]}"##;
+
+    const MEDIUM: &str = r#"<!DOCTYPE html><html><head><title>On Writing</title></head><body><article>
+        <header><h1 class="p-name">On Writing</h1></header>
+        <section data-field="subtitle" class="p-summary">Some thoughts</section>
+        <section data-field="body" class="e-content"><p>Words matter.</p></section>
+        <footer><time class="dt-published" datetime="2022-03-04T05:06:07.000Z">March 4, 2022</time>
+        <a href="https://medium.com/@me/on-writing-1a2b3c4d5e6f" class="p-canonical">Canonical link</a></footer>
+    </article></body></html>"#;
+
+    fn config(base_dir: &Path) -> Config {
+        common_test_utils::config_for(base_dir, &["blog", "notes"])
+    }
+
+    fn post(title: &str, categories: &[&str]) -> ImportedPost {
+        ImportedPost {
+            title: title.to_string(),
+            categories: categories.iter().map(|category| category.to_string()).collect(),
+            html: "<p>Body</p>".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn options() -> ImportOptions {
+        ImportOptions {
+            source: "export.xml".to_string(),
+            default_topic: Some("blog".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_wordpress_reads_posts_with_categories_and_featured_image() {
+        let posts = wordpress::parse(WXR).unwrap();
+        assert_eq!(posts.len(), 2, "pages and attachments are left out");
+
+        let hello = &posts[0];
+        assert_eq!(hello.title, "Hello & Welcome");
+        assert_eq!(hello.slug, "hello-welcome");
+        assert_eq!(hello.date, NaiveDate::from_ymd_opt(2024, 1, 31));
+        assert_eq!(hello.categories, ["Notes"]);
+        assert_eq!(hello.tags, ["rust"]);
+        assert_eq!(hello.description.as_deref(), Some("A short hello"));
+        assert!(!hello.draft);
+        assert!(hello.html.contains("<strong>post</strong>"));
+        assert_eq!(hello.featured_image.as_deref(), Some("https://example.com/wp-content/uploads/hero.jpg"));
+
+        let draft = &posts[1];
+        assert!(draft.draft);
+        assert_eq!(draft.date, None);
+        assert_eq!(draft.target_slug(), "unfinished");
+    }
+
+    #[test]
+    fn test_ghost_uses_primary_tag_as_category() {
+        let posts = ghost::parse(GHOST).unwrap();
+        assert_eq!(posts.len(), 2, "pages are left out");
+
+        assert_eq!(posts[0].categories, ["Notes"]);
+        assert_eq!(posts[0].tags, ["Notes", "rust"], "internal tags are left out");
+        assert_eq!(posts[0].date, NaiveDate::from_ymd_opt(2023, 5, 6));
+        assert_eq!(posts[0].description.as_deref(), Some("Spooky"));
+        assert!(posts[1].draft);
+        assert!(ghost::parse("{\"posts\": []}").is_err());
+    }
+
+    #[test]
+    fn test_medium_reads_slug_from_canonical_link() {
+        let post = medium::parse(MEDIUM, true);
+        assert_eq!(post.title, "On Writing");
+        assert_eq!(post.slug, "on-writing");
+        assert_eq!(post.description.as_deref(), Some("Some thoughts"));
+        assert_eq!(post.date, NaiveDate::from_ymd_opt(2022, 3, 4));
+        assert!(post.draft);
+        assert!(post.html.contains("Words matter."));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format("export.xml").unwrap(), SourceFormat::Wordpress);
+        assert_eq!(detect_format("ghost.json").unwrap(), SourceFormat::Ghost);
+        assert_eq!(detect_format("https://example.com/post").unwrap(), SourceFormat::Url);
+        assert!(detect_format("export.zip").is_err());
+    }
+
+    #[test]
+    fn test_import_maps_categories_to_topics() {
+        let temp = TempDir::new().unwrap();
+        let config = config(&temp.path().join("content"));
+        let mut options = options();
+        options.topic_map.insert("Essays".to_string(), "notes".to_string());
+
+        let posts = vec![post("Matched", &["notes"]), post("Mapped", &["essays"]), post("Fallback", &["Misc"])];
+        let report = import_posts(posts, &options, &config, None).unwrap();
+
+        let topics: Vec<_> = report.items.iter().map(|item| item.topic.as_deref().unwrap()).collect();
+        assert_eq!(topics, ["notes", "notes", "blog"]);
+
+        let document = fs::read_to_string(temp.path().join("content/notes/matched/matched.md")).unwrap();
+        assert!(document.starts_with("---\ntitle: Matched\n"));
+        assert!(document.contains("topics:\n- notes\n"));
+        assert!(document.contains("Body"));
+
+        options.default_topic = None;
+        let report = import_posts(vec![post("Lost", &["Misc"])], &options, &config, None).unwrap();
+        assert!(matches!(report.items[0].status, ImportStatus::Skipped { .. }));
+    }
+
+    #[test]
+    fn test_import_handles_slug_collisions() {
+        let temp = TempDir::new().unwrap();
+        let config = config(&temp.path().join("content"));
+        fs::create_dir_all(temp.path().join("content/notes/hello")).unwrap();
+        let mut options = options();
+
+        let report = import_posts(vec![post("Hello", &[])], &options, &config, None).unwrap();
+        assert!(matches!(report.items[0].status, ImportStatus::Skipped { .. }));
+
+        options.collision = Collision::Rename;
+        let report = import_posts(vec![post("Hello", &[]), post("Hello", &[])], &options, &config, None).unwrap();
+        let slugs: Vec<_> = report.items.iter().map(|item| item.slug.as_str()).collect();
+        assert_eq!(slugs, ["hello-2", "hello-3"]);
+
+        options.collision = Collision::Overwrite;
+        let report = import_posts(vec![post("Hello", &[])], &options, &config, None).unwrap();
+        assert_eq!(report.items[0].status, ImportStatus::Overwritten);
+        assert!(!temp.path().join("content/notes/hello").exists());
+        assert!(temp.path().join("content/blog/hello/hello.md").exists());
+    }
+
+    #[test]
+    fn test_import_copies_images_and_can_be_undone() {
+        let temp = TempDir::new().unwrap();
+        let content = temp.path().join("content");
+        let config = config(&content);
+        let export = temp.path().join("export");
+        fs::create_dir_all(export.join("images")).unwrap();
+        fs::write(export.join("images/cat.png"), "png").unwrap();
+
+        let mut with_image = post("Cats", &[]);
+        with_image.html = "<p>Look</p><img src=\"images/cat.png\" alt=\"A cat\">".to_string();
+        with_image.featured_image = Some("images/cat.png".to_string());
+
+        let report = import_posts(vec![with_image], &options(), &config, Some(&export)).unwrap();
+        assert_eq!(report.items[0].images, ["cat.png"]);
+        assert_eq!(fs::read_to_string(content.join("blog/cats/cat.png")).unwrap(), "png");
+        let document = fs::read_to_string(content.join("blog/cats/cats.md")).unwrap();
+        assert!(document.contains("](cat.png)"), "{}", document);
+        assert!(document.contains("featured_image: cat.png"));
+
+        Journal::for_base_dir(&content).undo_last().unwrap();
+        assert!(!content.join("blog/cats").exists());
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let temp = TempDir::new().unwrap();
+        let content = temp.path().join("content");
+        let options = ImportOptions { dry_run: true, ..options() };
+
+        let report = import_posts(vec![post("Hello", &[])], &options, &config(&content), None).unwrap();
+        assert_eq!(report.imported(), 1);
+        assert!(report.dry_run);
+        assert!(!content.exists());
+    }
+
+    #[test]
+    fn test_import_applies_title_and_tags() {
+        let temp = TempDir::new().unwrap();
+        let content = temp.path().join("content");
+        let options = ImportOptions {
+            title: Some("Renamed".to_string()),
+            tags: vec!["Rust".to_string(), "imported".to_string()],
+            ..options()
+        };
+        let mut page = post("Original", &[]);
+        page.tags = vec!["rust".to_string()];
+
+        let report = import_posts(vec![page], &options, &config(&content), None).unwrap();
+        assert_eq!(report.items[0].slug, "renamed");
+        let document = fs::read_to_string(content.join("blog/renamed/renamed.md")).unwrap();
+        assert!(document.starts_with("---\ntitle: Renamed\n"));
+        assert!(document.contains("tags:\n- rust\n- imported\n"), "{}", document);
+    }
+
+    #[test]
+    fn test_resolve_image_locations() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("local.png"), "png").unwrap();
+
+        assert!(matches!(images::resolve("https://cdn.example.com/a.png", None, None), Some(ImageLocation::Remote(_))));
+        assert!(matches!(images::resolve("local.png", None, Some(temp.path())), Some(ImageLocation::Local(_))));
+
+        // Links out of the export are not read from disk
+        let export = temp.path().join("export");
+        fs::create_dir(&export).unwrap();
+        assert_eq!(images::resolve("../local.png", None, Some(&export)), None);
+        assert_eq!(images::resolve("/images/../../local.png", None, Some(&export)), None);
+        assert_eq!(
+            images::resolve("../local.png", Some("https://example.com/blog"), Some(&export)).map(|location| location.to_string()),
+            Some("https://example.com/local.png".to_string())
+        );
+        assert_eq!(images::resolve("__GHOST_URL__/content/images/a.png", None, None), None);
+        assert_eq!(
+            images::resolve("__GHOST_URL__/content/images/a.png", Some("https://ghost.example.com/"), None)
+                .map(|location| location.to_string()),
+            Some("https://ghost.example.com/content/images/a.png".to_string())
+        );
+        assert_eq!(images::resolve("data:image/png;base64,AAAA", None, None), None);
+
+        let location = images::resolve("https://example.com/uploads/photo.png?w=100", None, None).unwrap();
+        let taken = vec![(location.clone(), "photo.png".to_string())];
+        assert_eq!(images::unique_file_name(&location, &[]), "photo.png");
+        assert_eq!(images::unique_file_name(&location, &taken), "photo-2.png");
+    }
+}
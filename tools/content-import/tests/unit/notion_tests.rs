@@ -0,0 +1,167 @@
+use common_models::{Config, NotionConfig};
+use content_import::notion::{blocks_to_markdown, database_id, is_notion_url, page_to_post};
+use content_import::{detect_format, import_posts, ImportOptions, ImportStatus, SourceFormat};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod notion_tests {
+    use super::*;
+
+    fn text(content: &str) -> Value {
+        json!({"type": "text", "plain_text": content, "annotations": {}, "href": null})
+    }
+
+    fn block(kind: &str, data: Value) -> Value {
+        json!({"type": kind, "has_children": false, kind: data})
+    }
+
+    fn page() -> Value {
+        json!({
+            "id": "1a2b3c4d-0000-4000-8000-000000000001",
+            "url": "https://www.notion.so/Draft-Ideas-1a2b3c4d000040008000000000000001",
+            "cover": {"type": "external", "external": {"url": "https://images.example.com/cover.jpg"}},
+            "properties": {
+                "Name": {"type": "title", "title": [text("Draft "), text("Ideas")]},
+                "Tags": {"type": "multi_select", "multi_select": [{"name": "rust"}, {"name": "notes"}]},
+                "Summary": {"type": "rich_text", "rich_text": [text("Half-formed thoughts")]},
+                "Date": {"type": "date", "date": {"start": "2024-03-05"}},
+                "Status": {"type": "select", "select": {"name": "Draft"}}
+            }
+        })
+    }
+
+    fn config(base_dir: &Path) -> Config {
+        let mut config = common_test_utils::config_for(base_dir, &["blog", "notes"]);
+        config.import.notion = Some(NotionConfig {
+            token: None,
+            database_id: "0f9e6b1c2d3a4b5c8d7e6f5a4b3c2d1e".to_string(),
+        });
+        config
+    }
+
+    fn options() -> ImportOptions {
+        ImportOptions {
+            format: Some(SourceFormat::Notion),
+            default_topic: Some("notes".to_string()),
+            download_images: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_blocks_to_markdown() {
+        let mut nested = block("bulleted_list_item", json!({"rich_text": [text("Parent")]}));
+        nested["has_children"] = true.into();
+        nested["children"] = json!([block("bulleted_list_item", json!({"rich_text": [text("Child")]}))]);
+
+        let blocks = vec![
+            block("heading_2", json!({"rich_text": [text("Intro")]})),
+            block(
+                "paragraph",
+                json!({"rich_text": [
+                    text("Some "),
+                    {"type": "text", "plain_text": "bold ", "annotations": {"bold": true}, "href": null},
+                    {"type": "text", "plain_text": "link", "annotations": {"code": true}, "href": "https://example.com"}
+                ]}),
+            ),
+            block("paragraph", json!({"rich_text": []})),
+            nested,
+            block("bulleted_list_item", json!({"rich_text": [text("Sibling")]})),
+            block("numbered_list_item", json!({"rich_text": [text("One")]})),
+            block("numbered_list_item", json!({"rich_text": [text("Two")]})),
+            block("to_do", json!({"rich_text": [text("Done")], "checked": true})),
+            block("code", json!({"rich_text": [text("fn main() {}")], "language": "rust"})),
+            block("callout", json!({"rich_text": [text("Careful")], "icon": {"emoji": "⚠️"}})),
+            block(
+                "image",
+                json!({"type": "file", "file": {"url": "https://s3.example.com/a/diagram.png?X-Amz=1"}, "caption": [text("A diagram")]}),
+            ),
+            block("divider", json!({})),
+            block("child_database", json!({"title": "Tasks"})),
+        ];
+
+        assert_eq!(
+            blocks_to_markdown(&blocks),
+            "## Intro\n\nSome **bold** [`link`](https://example.com)\n\n- Parent\n    - Child\n- Sibling\n\n1. One\n2. Two\n\n- [x] Done\n\n```rust\nfn main() {}\n```\n\n> [!NOTE]\n> Careful\n\n![A diagram](https://s3.example.com/a/diagram.png?X-Amz=1)\n\n---\n\n<!-- Notion child database not imported -->"
+        );
+    }
+
+    #[test]
+    fn test_table_uses_first_row_as_header() {
+        let row = |cells: &[&str]| {
+            json!({"type": "table_row", "table_row": {"cells": cells.iter().map(|cell| json!([text(cell)])).collect::<Vec<_>>()}})
+        };
+        let mut table = block("table", json!({"table_width": 2, "has_column_header": true}));
+        table["has_children"] = true.into();
+        table["children"] = json!([row(&["Name", "Value"]), row(&["a|b", "1"])]);
+
+        assert_eq!(blocks_to_markdown(&[table]), "| Name | Value |\n| --- | --- |\n| a\\|b | 1 |");
+    }
+
+    #[test]
+    fn test_page_to_post_reads_properties() {
+        let post = page_to_post(&page(), &[block("paragraph", json!({"rich_text": [text("Hello")]}))]);
+
+        assert_eq!(post.title, "Draft Ideas");
+        assert_eq!(post.target_slug(), "draft-ideas");
+        assert_eq!(post.tags, ["rust", "notes"]);
+        assert_eq!(post.description.as_deref(), Some("Half-formed thoughts"));
+        assert_eq!(post.date.map(|date| date.to_string()).as_deref(), Some("2024-03-05"));
+        assert!(post.draft);
+        assert_eq!(post.markdown.as_deref(), Some("Hello"));
+        assert_eq!(post.featured_image.as_deref(), Some("https://images.example.com/cover.jpg"));
+        assert_eq!(post.notion_id.as_deref(), Some("1a2b3c4d-0000-4000-8000-000000000001"));
+    }
+
+    #[test]
+    fn test_database_addresses() {
+        let url = "https://www.notion.so/team/Drafts-0f9e6b1c2d3a4b5c8d7e6f5a4b3c2d1e?v=123";
+        assert!(is_notion_url(url));
+        assert!(!is_notion_url("https://example.com/notion.so"));
+        assert_eq!(detect_format(url).unwrap(), SourceFormat::Notion);
+        assert_eq!(database_id(url), "0f9e6b1c2d3a4b5c8d7e6f5a4b3c2d1e");
+        assert_eq!(database_id(" 0f9e6b1c-2d3a "), "0f9e6b1c-2d3a");
+        assert_eq!(
+            config(Path::new("content")).import.notion.unwrap().database_id,
+            "0f9e6b1c2d3a4b5c8d7e6f5a4b3c2d1e"
+        );
+    }
+
+    #[test]
+    fn test_reimporting_a_page_updates_its_draft() {
+        let temp = TempDir::new().unwrap();
+        let content = temp.path().join("content");
+        let config = config(&content);
+        let paragraph = |content: &str| vec![block("paragraph", json!({"rich_text": [text(content)]}))];
+
+        let report = import_posts(vec![page_to_post(&page(), &paragraph("First"))], &options(), &config, None).unwrap();
+        assert_eq!(report.items[0].status, ImportStatus::Imported);
+        let file = content.join("notes/draft-ideas/draft-ideas.md");
+        let document = fs::read_to_string(&file).unwrap();
+        assert!(document.contains("draft: true\n"), "{}", document);
+        assert!(document.contains("notion_id: 1a2b3c4d-0000-4000-8000-000000000001\n"));
+
+        // The page keeps its article even when renamed in Notion or imported to another topic
+        let mut renamed = page();
+        renamed["properties"]["Name"]["title"] = json!([text("Better Title")]);
+        let options = ImportOptions {
+            default_topic: Some("blog".to_string()),
+            ..options()
+        };
+        let report = import_posts(vec![page_to_post(&renamed, &paragraph("Second"))], &options, &config, None).unwrap();
+        assert_eq!(report.items[0].status, ImportStatus::Updated);
+        assert_eq!(report.items[0].slug, "draft-ideas");
+        let document = fs::read_to_string(&file).unwrap();
+        assert!(document.contains("title: Better Title\n"));
+        assert!(document.ends_with("\nSecond\n"));
+
+        // Once published, the article is left alone
+        fs::write(&file, document.replace("draft: true", "draft: false")).unwrap();
+        let report = import_posts(vec![page_to_post(&page(), &paragraph("Third"))], &options, &config, None).unwrap();
+        assert!(matches!(report.items[0].status, ImportStatus::Skipped { .. }));
+        assert!(fs::read_to_string(&file).unwrap().ends_with("\nSecond\n"));
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::Result;
 use common_fs::normalize::{join_paths, normalize_path};
+use common_fs::{FileSystem, Journal, RealFileSystem, Transaction};
+use common_plugins::{HookContext, HookEvent, HookRegistry};
 use fs_extra::dir::{copy, CopyOptions};
+use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
 /// Extension trait for Option to validate required fields
@@ -22,6 +25,86 @@ pub struct MoveOptions {
     pub topic: Option<String>,
     pub new_topic: Option<String>,
     pub update_frontmatter: bool,
+    /// Compute the move plan without touching the filesystem
+    pub dry_run: bool,
+}
+
+/// A frontmatter field that a move changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterChange {
+    /// Name of the frontmatter field
+    pub field: String,
+    /// The value before the move, as YAML
+    pub from: String,
+    /// The value after the move, as YAML
+    pub to: String,
+}
+
+/// A problem that prevents a move from being executed
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveConflict {
+    /// The source and destination are the same
+    NoChanges { topic: String, slug: String },
+    /// The current topic is not configured
+    SourceTopicNotFound(String),
+    /// The new topic is not configured
+    TargetTopicNotFound(String),
+    /// The content does not exist in the current topic
+    SourceNotFound { topic: String, slug: String },
+    /// Content already exists at the destination
+    DestinationExists { topic: String, slug: String },
+}
+
+impl std::fmt::Display for MoveConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveConflict::NoChanges { topic, slug } => {
+                write!(f, "No changes requested: {}/{}", topic, slug)
+            }
+            MoveConflict::SourceTopicNotFound(topic) => {
+                write!(f, "Current topic not found: {}", topic)
+            }
+            MoveConflict::TargetTopicNotFound(topic) => write!(f, "New topic not found: {}", topic),
+            MoveConflict::SourceNotFound { topic, slug } => {
+                write!(f, "Content not found: {}/{}", topic, slug)
+            }
+            MoveConflict::DestinationExists { topic, slug } => write!(
+                f,
+                "Content already exists in target topic: {}/{}",
+                topic, slug
+            ),
+        }
+    }
+}
+
+/// Everything a move will do, computed without touching the filesystem
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovePlan {
+    /// Topic key the content is moving from
+    pub from_topic: String,
+    /// Slug the content is moving from
+    pub from_slug: String,
+    /// Topic key the content is moving to
+    pub to_topic: String,
+    /// Slug the content is moving to
+    pub to_slug: String,
+    /// Current content directory
+    pub source: PathBuf,
+    /// Content directory after the move
+    pub destination: PathBuf,
+    /// Frontmatter fields of the moved content that will change
+    pub frontmatter_changes: Vec<FrontmatterChange>,
+    /// Links in other content that will be rewritten
+    pub reference_updates: Vec<ReferenceUpdate>,
+    /// Problems that prevent the move; empty if it can be executed
+    pub conflicts: Vec<MoveConflict>,
+}
+
+impl MovePlan {
+    /// Whether the move can be executed
+    pub fn is_executable(&self) -> bool {
+        self.conflicts.is_empty()
+    }
 }
 
 /// Find the directory containing the content
@@ -100,12 +183,8 @@ pub fn list_all_content() -> Result<Vec<(String, String, PathBuf)>> {
                 .unwrap_or("")
                 .to_string();
 
-            // Check for the matching-name file with .md extension
-            let md_file_path = join_paths(&article_dir, format!("{}.md", slug));
-            let mdx_file_path = join_paths(&article_dir, format!("{}.mdx", slug));
-
-            // If any of the content files exist, add to the list
-            if md_file_path.exists() || mdx_file_path.exists() {
+            // Only list directories that hold a content file
+            if common_fs::resolve_content_file(&article_dir, &slug).is_some() {
                 content_list.push((topic_key.clone(), slug, normalize_path(article_dir)));
             }
         }
@@ -119,95 +198,670 @@ pub fn update_content_references(
     content_path: &Path,
     old_slug: &str,
     new_slug: &str,
+) -> Result<()> {
+    update_content_references_with_fs(&RealFileSystem, content_path, old_slug, new_slug)
+}
+
+/// Update content references, reading and writing through `fs`
+pub fn update_content_references_with_fs(
+    fs: &dyn FileSystem,
+    content_path: &Path,
+    old_slug: &str,
+    new_slug: &str,
 ) -> Result<()> {
     // Normalize the path before reading the file
     let normalized_path = normalize_path(content_path);
-    let content = common_fs::read_file(&normalized_path)?;
+    let content = fs.read_file(&normalized_path)?;
 
     // Replace old slug with new slug
     let updated_content = content.replace(old_slug, new_slug);
 
     if content != updated_content {
-        common_fs::write_file(&normalized_path, &updated_content)?;
+        fs.write_file(&normalized_path, &updated_content)?;
     }
 
     Ok(())
 }
 
-/// Move content to a new location and/or rename it
-pub fn move_content(options: &MoveOptions) -> Result<()> {
+/// A rewritten internal link in another piece of content
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceUpdate {
+    /// File containing the link
+    pub path: PathBuf,
+    /// Line number of the link (1-based)
+    pub line: usize,
+    /// The line before rewriting
+    pub original: String,
+    /// The line after rewriting
+    pub updated: String,
+}
+
+/// Rewrites internal links from one content location to another
+///
+/// Site-relative links to `/{old_topic}/{old_slug}` are always rewritten;
+/// links to the same path on another site are not. Relative
+/// `](../{old_slug})` links are only meaningful between articles in the old
+/// topic, so callers decide per file whether to rewrite them.
+#[derive(Debug, Clone)]
+pub struct LinkRewriter {
+    absolute: Regex,
+    relative: Regex,
+    new_absolute: String,
+    new_relative: String,
+}
+
+impl LinkRewriter {
+    /// Create a rewriter for content moving from `old_topic/old_slug` to `new_topic/new_slug`
+    pub fn new(old_topic: &str, old_slug: &str, new_topic: &str, new_slug: &str) -> Result<Self> {
+        // Only site-relative links: the path must start the link target, so
+        // `https://elsewhere.com/{topic}/{slug}` is left alone
+        let absolute = Regex::new(&format!(
+            r#"(^|[(\s"'=<])/{}/{}([/#?)"'\s>]|$)"#,
+            regex::escape(old_topic),
+            regex::escape(old_slug)
+        ))?;
+        let relative = Regex::new(&format!(r#"\]\(\.\./{}([/#?)])"#, regex::escape(old_slug)))?;
+
+        let new_relative = if old_topic == new_topic {
+            format!("](../{}${{1}}", new_slug)
+        } else {
+            format!("](/{}/{}${{1}}", new_topic, new_slug)
+        };
+
+        Ok(Self {
+            absolute,
+            relative,
+            new_absolute: format!("${{1}}/{}/{}${{2}}", new_topic, new_slug),
+            new_relative,
+        })
+    }
+
+    /// Rewrite the links in a line of text
+    ///
+    /// # Parameters
+    ///
+    /// * `line` - The text to rewrite
+    /// * `in_old_topic` - Whether the text belongs to an article in the old topic,
+    ///   in which case relative links are rewritten too
+    pub fn rewrite(&self, line: &str, in_old_topic: bool) -> String {
+        let updated = self.absolute.replace_all(line, self.new_absolute.as_str());
+        if in_old_topic {
+            self.relative.replace_all(&updated, self.new_relative.as_str()).into_owned()
+        } else {
+            updated.into_owned()
+        }
+    }
+}
+
+/// Rewrite internal links from one content location to another
+///
+/// Scans every content file for links to `/{old_topic}/{old_slug}` (and
+/// `../{old_slug}` links between articles in the same topic) and points them
+/// at the new location. When `dry_run` is set, the updates are reported but no
+/// files are written.
+///
+/// # Parameters
+///
+/// * `old_topic` - Topic key the content is moving from
+/// * `old_slug` - Slug the content is moving from
+/// * `new_topic` - Topic key the content is moving to
+/// * `new_slug` - Slug the content is moving to
+/// * `dry_run` - Report the updates without writing them
+///
+/// # Returns
+///
+/// Returns every line that was (or would be) rewritten
+///
+/// # Errors
+///
+/// Returns an error if content cannot be listed, read or written
+pub fn rewrite_backlinks(
+    old_topic: &str,
+    old_slug: &str,
+    new_topic: &str,
+    new_slug: &str,
+    dry_run: bool,
+) -> Result<Vec<ReferenceUpdate>> {
+    let rewriter = LinkRewriter::new(old_topic, old_slug, new_topic, new_slug)?;
+    rewrite_backlinks_with_fs(&RealFileSystem, &list_all_content()?, old_topic, &rewriter, dry_run)
+}
+
+/// Rewrite internal links in the given content, reading and writing through `fs`
+///
+/// `articles` lists each article as `(topic, slug, article directory)`, as
+/// returned by [`list_all_content`].
+///
+/// # Errors
+///
+/// Returns an error if content cannot be read or written
+pub fn rewrite_backlinks_with_fs(
+    fs: &dyn FileSystem,
+    articles: &[(String, String, PathBuf)],
+    old_topic: &str,
+    rewriter: &LinkRewriter,
+    dry_run: bool,
+) -> Result<Vec<ReferenceUpdate>> {
+    let mut updates = Vec::new();
+    for (topic, slug, article_dir) in articles {
+        for extension in ["md", "mdx"] {
+            let file_path = join_paths(article_dir, format!("{}.{}", slug, extension));
+            if !fs.file_exists(&file_path)? {
+                continue;
+            }
+
+            let content = fs.read_file(&file_path)?;
+            let mut changed = false;
+            let lines: Vec<String> = content
+                .split('\n')
+                .enumerate()
+                .map(|(index, line)| {
+                    let updated = rewriter.rewrite(line, topic == old_topic);
+                    if updated != line {
+                        changed = true;
+                        updates.push(ReferenceUpdate {
+                            path: file_path.clone(),
+                            line: index + 1,
+                            original: line.to_string(),
+                            updated: updated.clone(),
+                        });
+                    }
+                    updated
+                })
+                .collect();
+
+            if changed && !dry_run {
+                fs.write_file(&file_path, &lines.join("\n"))?;
+            }
+        }
+    }
+
+    Ok(updates)
+}
+
+/// Work out what moving content would do, without touching the filesystem
+///
+/// Problems such as a missing source or an occupied destination are reported
+/// as conflicts on the plan rather than as errors, so that a whole batch of
+/// moves can be reviewed at once.
+///
+/// # Parameters
+///
+/// * `options` - Move options
+///
+/// # Returns
+///
+/// Returns the plan for the move
+///
+/// # Errors
+///
+/// Returns an error if no slug is given, the new slug is not a valid slug,
+/// either topic is not configured, the content cannot be located, or content
+/// cannot be read
+pub fn plan_move(options: &MoveOptions) -> Result<MovePlan> {
     // Validate options
     let slug = options
         .slug
         .clone()
         .validate_required("Content slug is required")?;
-    let current_topic = options
-        .topic
-        .clone()
-        .validate_required("Current topic is required")?;
-    let new_topic = options
-        .new_topic
-        .clone()
-        .validate_required("New topic is required")?;
 
     // Load config
     let config = common_config::load_config()?;
 
-    // Validate current topic
-    if !config.content.topics.contains_key(&current_topic) {
-        return Err(anyhow::anyhow!(
-            "Current topic not found: {}",
-            current_topic
-        ));
+    let current_topic = match &options.topic {
+        Some(topic) => topic.clone(),
+        None => find_content_dir(&slug, None)?.1,
+    };
+    let new_topic = options.new_topic.clone().unwrap_or_else(|| current_topic.clone());
+    let new_slug = options.new_slug.clone().unwrap_or_else(|| slug.clone());
+    common_validation::validate_slug(&new_slug)?;
+
+    // Paths are only ever built from configured topic directories
+    let source_dir = config
+        .content
+        .topics
+        .get(&current_topic)
+        .map(|topic_config| topic_config.directory.clone())
+        .ok_or_else(|| anyhow::anyhow!("{}", MoveConflict::SourceTopicNotFound(current_topic.clone())))?;
+    let destination_dir = config
+        .content
+        .topics
+        .get(&new_topic)
+        .map(|topic_config| topic_config.directory.clone())
+        .ok_or_else(|| anyhow::anyhow!("{}", MoveConflict::TargetTopicNotFound(new_topic.clone())))?;
+
+    let base_dir = PathBuf::from(&config.content.base_dir);
+    let mut plan = MovePlan {
+        source: join_paths(&base_dir, join_paths(source_dir, &slug)),
+        destination: join_paths(&base_dir, join_paths(destination_dir, &new_slug)),
+        from_topic: current_topic,
+        from_slug: slug,
+        to_topic: new_topic,
+        to_slug: new_slug,
+        frontmatter_changes: Vec::new(),
+        reference_updates: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
+    if plan.from_topic == plan.to_topic && plan.from_slug == plan.to_slug {
+        plan.conflicts.push(MoveConflict::NoChanges {
+            topic: plan.from_topic.clone(),
+            slug: plan.from_slug.clone(),
+        });
     }
 
-    // Validate new topic
-    if !config.content.topics.contains_key(&new_topic) {
-        return Err(anyhow::anyhow!("New topic not found: {}", new_topic));
+    if !plan.source.exists() {
+        plan.conflicts.push(MoveConflict::SourceNotFound {
+            topic: plan.from_topic.clone(),
+            slug: plan.from_slug.clone(),
+        });
+    } else if options.update_frontmatter {
+        if let Some(file_path) = content_file(&plan.source, &plan.from_slug) {
+            let content = common_fs::read_file(&file_path)?;
+            let (frontmatter, _) = common_markdown::extract_frontmatter(&content)?;
+            let updated = updated_frontmatter(&frontmatter, &plan.from_topic, &plan.to_topic, &plan.to_slug);
+            plan.frontmatter_changes = frontmatter_changes(&frontmatter, &updated)?;
+        }
     }
 
-    // Get topic configs
-    let current_topic_config = &config.content.topics[&current_topic];
-    let new_topic_config = &config.content.topics[&new_topic];
+    if plan.destination.exists() && plan.destination != plan.source {
+        plan.conflicts.push(MoveConflict::DestinationExists {
+            topic: plan.to_topic.clone(),
+            slug: plan.to_slug.clone(),
+        });
+    }
 
-    // Get paths
-    let base_dir = PathBuf::from(&config.content.base_dir);
-    let current_topic_path = &current_topic_config.directory;
-    let new_topic_path = &new_topic_config.directory;
+    plan.reference_updates = rewrite_backlinks(
+        &plan.from_topic,
+        &plan.from_slug,
+        &plan.to_topic,
+        &plan.to_slug,
+        true,
+    )?;
 
-    // Find content path
-    let content_path = join_paths(&base_dir, join_paths(current_topic_path, &slug));
+    Ok(plan)
+}
 
-    if !content_path.exists() {
-        return Err(anyhow::anyhow!(
-            "Content not found: {}/{}",
-            current_topic,
-            slug
-        ));
+/// Move content to a new location and/or rename it
+///
+/// The article directory is moved to `new_topic` (defaulting to the current
+/// topic) and renamed to `new_slug` (defaulting to the current slug), along
+/// with its `{slug}.md`/`{slug}.mdx` content file. When `update_frontmatter`
+/// is set, the article's `topics` and `slug` frontmatter fields are updated.
+/// Links to the old location in other content are rewritten and a redirect
+/// is recorded. Every file the move changes is snapshotted in the journal
+/// first, so the move can be undone with [`Journal::undo_last`].
+///
+/// When `dry_run` is set, the plan is returned without touching the
+/// filesystem, conflicts included. Otherwise the first conflict is returned
+/// as an error.
+pub fn move_content(options: &MoveOptions) -> Result<MovePlan> {
+    let plan = plan_move(options)?;
+
+    if options.dry_run {
+        return Ok(plan);
+    }
+
+    if let Some(conflict) = plan.conflicts.first() {
+        return Err(anyhow::anyhow!("{}", conflict));
     }
 
-    // Create new content path
-    let new_content_path = join_paths(&base_dir, join_paths(new_topic_path, &slug));
+    let config = common_config::load_config()?;
+    let hooks = HookRegistry::from_config(&config)?;
+    fire_pre_move(&hooks, &plan)?;
 
-    if new_content_path.exists() {
-        return Err(anyhow::anyhow!(
-            "Content already exists in target topic: {}/{}",
-            new_topic,
-            slug
-        ));
+    let description = format!(
+        "Move {}/{} to {}/{}",
+        plan.from_topic, plan.from_slug, plan.to_topic, plan.to_slug
+    );
+    let mut transaction = Journal::for_base_dir(&config.content.base_dir).begin("move", description)?;
+    journal_plan(&mut transaction, &plan, &common_config::redirects_path(&config))?;
+
+    execute_plan(&plan, options.update_frontmatter)?;
+    transaction.commit()?;
+
+    Ok(plan)
+}
+
+/// Snapshot everything a move will change, so the journal can undo it
+fn journal_plan(transaction: &mut Transaction, plan: &MovePlan, redirects_path: &Path) -> Result<()> {
+    for update in rewrite_backlinks(&plan.from_topic, &plan.from_slug, &plan.to_topic, &plan.to_slug, true)? {
+        transaction.snapshot(&update.path)?;
+    }
+    transaction.snapshot(&plan.source)?;
+    transaction.snapshot(&plan.destination)?;
+    transaction.snapshot(redirects_path)?;
+    Ok(())
+}
+
+/// Run the `pre_move` hooks for a planned move
+fn fire_pre_move(hooks: &HookRegistry, plan: &MovePlan) -> Result<()> {
+    let context = HookContext::for_article(HookEvent::PreMove, &plan.from_topic, &plan.from_slug)
+        .with_path(&plan.source);
+    hooks.fire(&context)?;
+    Ok(())
+}
+
+/// What happened to one item of a batch move
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveItemStatus {
+    /// The content was moved
+    Moved,
+    /// The content was moved, then restored after a later item failed
+    RolledBack,
+    /// Moving the content failed
+    Failed(String),
+    /// The batch failed before this item was reached
+    NotAttempted,
+}
+
+/// The result of one item of a batch move
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveItemResult {
+    /// The plan for the item
+    pub plan: MovePlan,
+    /// What happened to the item
+    pub status: MoveItemStatus,
+}
+
+/// Per-item results of a batch move
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MoveReport {
+    /// One result per requested move, in request order
+    pub items: Vec<MoveItemResult>,
+}
+
+impl MoveReport {
+    /// Whether every item was moved
+    pub fn is_success(&self) -> bool {
+        self.items
+            .iter()
+            .all(|item| item.status == MoveItemStatus::Moved)
     }
+}
+
+/// Move many pieces of content as a single batch
+///
+/// The whole batch is validated before anything is touched: every move must be
+/// free of conflicts, and no two moves may share a source or destination. The
+/// moves are then carried out in order. If one fails, the directories already
+/// moved are restored and the content files and redirects are reset to their
+/// state before the batch, so the repository is left as it was. A batch
+/// that succeeds is recorded in the journal as one transaction.
+///
+/// The `dry_run` flag of individual options is ignored; use [`plan_move`] to
+/// review a batch before running it.
+///
+/// # Parameters
+///
+/// * `moves` - The moves to carry out
+///
+/// # Returns
+///
+/// Returns a per-item report; check [`MoveReport::is_success`] to see whether
+/// the batch was applied
+///
+/// # Errors
+///
+/// Returns an error if the batch fails validation, or if a failed batch
+/// cannot be rolled back
+pub fn move_many(moves: Vec<MoveOptions>) -> Result<MoveReport> {
+    let mut problems = Vec::new();
+    let mut plans = Vec::new();
+    for options in &moves {
+        match plan_move(options) {
+            Ok(plan) => plans.push(plan),
+            Err(err) => problems.push(format!("{}: {}", requested_label(options), err)),
+        }
+    }
+    validate_batch(&plans, problems)?;
+
+    let config = common_config::load_config()?;
+
+    // Every hook gets its say before the first move, so a veto needs no rollback
+    let hooks = HookRegistry::from_config(&config)?;
+    for plan in &plans {
+        fire_pre_move(&hooks, plan)?;
+    }
+
+    let snapshot = Snapshot::take(&common_config::redirects_path(&config))?;
+
+    let description = format!("Move {} items", plans.len());
+    let mut transaction = Journal::for_base_dir(&config.content.base_dir).begin("move", description)?;
+    for plan in &plans {
+        journal_plan(&mut transaction, plan, &common_config::redirects_path(&config))?;
+    }
+
+    let mut items: Vec<MoveItemResult> = plans
+        .into_iter()
+        .map(|plan| MoveItemResult {
+            plan,
+            status: MoveItemStatus::NotAttempted,
+        })
+        .collect();
+
+    for index in 0..items.len() {
+        let update_frontmatter_fields = moves[index].update_frontmatter;
+        match execute_plan(&items[index].plan, update_frontmatter_fields) {
+            Ok(()) => items[index].status = MoveItemStatus::Moved,
+            Err(err) => {
+                items[index].status = MoveItemStatus::Failed(err.to_string());
+
+                // Undo the failed item as far as it got, then everything before it
+                for item in items[..=index].iter_mut().rev() {
+                    restore_directory(&item.plan).map_err(|rollback_err| {
+                        anyhow::anyhow!(
+                            "Failed to roll back move of {}/{} after error ({}): {}",
+                            item.plan.from_topic,
+                            item.plan.from_slug,
+                            err,
+                            rollback_err
+                        )
+                    })?;
+                    if item.status == MoveItemStatus::Moved {
+                        item.status = MoveItemStatus::RolledBack;
+                    }
+                }
+                snapshot.restore()?;
+
+                // Nothing is left to undo, so the transaction is dropped
+                return Ok(MoveReport { items });
+            }
+        }
+    }
+
+    transaction.commit()?;
+    Ok(MoveReport { items })
+}
+
+/// Label a requested move by the content it names, for moves that could not be planned
+fn requested_label(options: &MoveOptions) -> String {
+    let slug = options.slug.as_deref().unwrap_or_default();
+    match &options.topic {
+        Some(topic) => format!("{}/{}", topic, slug),
+        None => slug.to_string(),
+    }
+}
+
+/// Check a batch of plans for conflicts, within each move and between moves
+///
+/// `problems` holds the moves that could not be planned at all. Moves are
+/// not reordered, so a move into content another move is taking away (a chain
+/// like `a -> b, b -> c`, or a swap) is rejected too.
+fn validate_batch(plans: &[MovePlan], mut problems: Vec<String>) -> Result<()> {
+    let mut sources = std::collections::HashSet::new();
+    let mut destinations = std::collections::HashSet::new();
+
+    for plan in plans {
+        let label = format!("{}/{}", plan.from_topic, plan.from_slug);
+        for conflict in &plan.conflicts {
+            problems.push(format!("{}: {}", label, conflict));
+        }
+        if !sources.insert(plan.source.clone()) {
+            problems.push(format!("{}: moved more than once", label));
+        }
+        if !destinations.insert(plan.destination.clone()) {
+            problems.push(format!(
+                "{}: destination {}/{} is used by another move",
+                label, plan.to_topic, plan.to_slug
+            ));
+        }
+    }
+
+    for plan in plans {
+        let moved_elsewhere = plans
+            .iter()
+            .any(|other| other.source == plan.destination && other.source != plan.source);
+        if moved_elsewhere {
+            problems.push(format!(
+                "{}/{}: destination {}/{} is moved by another move",
+                plan.from_topic, plan.from_slug, plan.to_topic, plan.to_slug
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Batch move validation failed: {}",
+            problems.join("; ")
+        ))
+    }
+}
+
+/// Move a content directory back to its source, undoing a (possibly partial) move
+fn restore_directory(plan: &MovePlan) -> Result<()> {
+    if plan.destination.exists() && !plan.source.exists() {
+        move_dir(&plan.destination, &plan.source)?;
+    }
+    if plan.to_slug != plan.from_slug && plan.source.exists() {
+        rename_content_file(&plan.source, &plan.to_slug, &plan.from_slug)?;
+    }
+
+    Ok(())
+}
+
+/// Content files and redirects as they were before a batch move
+struct Snapshot {
+    files: Vec<(PathBuf, String)>,
+    redirects_path: PathBuf,
+    redirects: Option<String>,
+}
+
+impl Snapshot {
+    /// Record the current content files and redirects
+    fn take(redirects_path: &Path) -> Result<Self> {
+        let mut files = Vec::new();
+        for (_, slug, article_dir) in list_all_content()? {
+            if let Some(file_path) = content_file(&article_dir, &slug) {
+                let content = common_fs::read_file(&file_path)?;
+                files.push((file_path, content));
+            }
+        }
+
+        let redirects = if redirects_path.exists() {
+            Some(common_fs::read_file(redirects_path)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            files,
+            redirects_path: redirects_path.to_path_buf(),
+            redirects,
+        })
+    }
+
+    /// Write back every recorded file that has changed
+    fn restore(&self) -> Result<()> {
+        for (path, content) in &self.files {
+            if fs::read_to_string(path).ok().as_ref() != Some(content) {
+                common_fs::write_file(path, content)?;
+            }
+        }
+
+        match &self.redirects {
+            Some(redirects) => common_fs::write_file(&self.redirects_path, redirects)?,
+            None if self.redirects_path.exists() => fs::remove_file(&self.redirects_path)?,
+            None => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Carry out a conflict-free move plan
+fn execute_plan(plan: &MovePlan, update_frontmatter_fields: bool) -> Result<()> {
+    let config = common_config::load_config()?;
 
     // Create parent directory if it doesn't exist
-    if let Some(parent) = new_content_path.parent() {
+    if let Some(parent) = plan.destination.parent() {
         common_fs::create_dir_all(parent)?;
     }
 
     // Move content
-    move_dir(&content_path, &new_content_path)?;
+    move_dir(&plan.source, &plan.destination)?;
+
+    // Rename the content file to match the new slug
+    if plan.to_slug != plan.from_slug {
+        rename_content_file(&plan.destination, &plan.from_slug, &plan.to_slug)?;
+    }
 
     // Update frontmatter if requested
-    if options.update_frontmatter {
-        update_frontmatter(&new_content_path, &current_topic, &new_topic)?;
+    if update_frontmatter_fields {
+        update_frontmatter(&plan.destination, &plan.from_topic, &plan.to_topic, &plan.to_slug)?;
+    }
+
+    // Point links in other content at the new location
+    rewrite_backlinks(&plan.from_topic, &plan.from_slug, &plan.to_topic, &plan.to_slug, false)?;
+
+    // Keep the old URL working
+    common_config::add_redirect(
+        &common_config::redirects_path(&config),
+        common_config::Redirect::between(&plan.from_topic, &plan.from_slug, &plan.to_topic, &plan.to_slug),
+    )?;
+
+    Ok(())
+}
+
+/// Find the `{slug}.md` or `{slug}.mdx` file in a content directory
+fn content_file(content_path: &Path, slug: &str) -> Option<PathBuf> {
+    ["md", "mdx"]
+        .iter()
+        .map(|extension| content_path.join(format!("{}.{}", slug, extension)))
+        .find(|path| path.exists())
+}
+
+/// List the top-level frontmatter fields that differ between two versions
+fn frontmatter_changes(
+    before: &serde_yaml::Value,
+    after: &serde_yaml::Value,
+) -> Result<Vec<FrontmatterChange>> {
+    let mut changes = Vec::new();
+    if let (Some(before), Some(after)) = (before.as_mapping(), after.as_mapping()) {
+        for (key, old_value) in before {
+            let new_value = match after.get(key) {
+                Some(value) if value != old_value => value,
+                _ => continue,
+            };
+            changes.push(FrontmatterChange {
+                field: key.as_str().unwrap_or_default().to_string(),
+                from: serde_yaml::to_string(old_value)?.trim_end().to_string(),
+                to: serde_yaml::to_string(new_value)?.trim_end().to_string(),
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Rename `{old_slug}.md`/`{old_slug}.mdx` in a content directory to the new slug
+fn rename_content_file(content_path: &Path, old_slug: &str, new_slug: &str) -> Result<()> {
+    for extension in ["md", "mdx"] {
+        let old_file = content_path.join(format!("{}.{}", old_slug, extension));
+        if old_file.exists() {
+            fs::rename(&old_file, content_path.join(format!("{}.{}", new_slug, extension)))?;
+        }
     }
 
     Ok(())
@@ -282,6 +936,7 @@ fn copy_dir_all(from: &Path, to: &Path) -> Result<()> {
 /// * `content_path` - Path to the content directory
 /// * `old_topic` - Old topic key
 /// * `new_topic` - New topic key
+/// * `new_slug` - New slug
 ///
 /// # Returns
 ///
@@ -290,30 +945,20 @@ fn copy_dir_all(from: &Path, to: &Path) -> Result<()> {
 /// # Errors
 ///
 /// Returns an error if the frontmatter cannot be updated
-fn update_frontmatter(content_path: &Path, old_topic: &str, new_topic: &str) -> Result<()> {
+fn update_frontmatter(content_path: &Path, old_topic: &str, new_topic: &str, new_slug: &str) -> Result<()> {
     // Get slug from the content directory name
     let slug = content_path
         .file_name()
         .and_then(|name| name.to_str())
         .ok_or_else(|| anyhow::anyhow!("Invalid content directory name"))?;
 
-    // Check for the matching-name file with .md extension
-    let md_path = content_path.join(format!("{}.md", slug));
-    if md_path.exists() {
-        return update_frontmatter_file(&md_path, old_topic, new_topic);
-    }
-
-    // Check for the matching-name file with .mdx extension
-    let mdx_path = content_path.join(format!("{}.mdx", slug));
-    if mdx_path.exists() {
-        return update_frontmatter_file(&mdx_path, old_topic, new_topic);
+    match common_fs::resolve_content_file(content_path, slug) {
+        Some(content_file) => update_frontmatter_file(&RealFileSystem, &content_file, old_topic, new_topic, new_slug),
+        None => Err(anyhow::anyhow!(
+            "Content file not found in {}",
+            content_path.display()
+        )),
     }
-
-    // No content file found
-    Err(anyhow::anyhow!(
-        "Content file not found in {}",
-        content_path.display()
-    ))
 }
 
 /// Update frontmatter in a file
@@ -322,9 +967,11 @@ fn update_frontmatter(content_path: &Path, old_topic: &str, new_topic: &str) ->
 ///
 /// # Parameters
 ///
+/// * `fs` - Filesystem to read and write the file through
 /// * `file_path` - Path to the file
 /// * `old_topic` - Old topic key
 /// * `new_topic` - New topic key
+/// * `new_slug` - New slug, written to the `slug` field if present
 ///
 /// # Returns
 ///
@@ -333,19 +980,52 @@ fn update_frontmatter(content_path: &Path, old_topic: &str, new_topic: &str) ->
 /// # Errors
 ///
 /// Returns an error if the frontmatter cannot be updated
-fn update_frontmatter_file(file_path: &Path, old_topic: &str, new_topic: &str) -> Result<()> {
+pub fn update_frontmatter_file(
+    fs: &dyn FileSystem,
+    file_path: &Path,
+    old_topic: &str,
+    new_topic: &str,
+    new_slug: &str,
+) -> Result<()> {
     // Read the file
-    let content = common_fs::read_file(file_path)?;
+    let content = fs.read_file(file_path)?;
 
     // Extract frontmatter
     let (frontmatter, content_without_frontmatter) =
         common_markdown::extract_frontmatter(&content)?;
 
-    // Update topics in frontmatter
-    let mut updated_frontmatter = frontmatter.clone();
+    // Update topics and slug in frontmatter
+    let updated = updated_frontmatter(&frontmatter, old_topic, new_topic, new_slug);
+
+    // Convert frontmatter back to YAML
+    let updated_frontmatter_str = serde_yaml::to_string(&updated)?;
+
+    // Combine updated frontmatter with content
+    let updated_content = format!(
+        "---\n{}---\n{}",
+        updated_frontmatter_str, content_without_frontmatter
+    );
+
+    // Write updated content back to file
+    fs.write_file(file_path, &updated_content)?;
+
+    Ok(())
+}
+
+/// Apply a move to parsed frontmatter
+///
+/// Replaces `old_topic` in the `topics` list with `new_topic`, and sets the
+/// `slug` field to `new_slug` if present.
+fn updated_frontmatter(
+    frontmatter: &serde_yaml::Value,
+    old_topic: &str,
+    new_topic: &str,
+    new_slug: &str,
+) -> serde_yaml::Value {
+    let mut updated = frontmatter.clone();
 
     // Check if topics field exists
-    if let Some(topics) = updated_frontmatter.get_mut("topics") {
+    if let Some(topics) = updated.get_mut("topics") {
         if let Some(topics_array) = topics.as_sequence_mut() {
             // Replace old topic with new topic
             for topic_value in topics_array.iter_mut() {
@@ -358,17 +1038,10 @@ fn update_frontmatter_file(file_path: &Path, old_topic: &str, new_topic: &str) -
         }
     }
 
-    // Convert frontmatter back to YAML
-    let updated_frontmatter_str = serde_yaml::to_string(&updated_frontmatter)?;
-
-    // Combine updated frontmatter with content
-    let updated_content = format!(
-        "---\n{}---\n{}",
-        updated_frontmatter_str, content_without_frontmatter
-    );
-
-    // Write updated content back to file
-    common_fs::write_file(file_path, &updated_content)?;
+    // Update slug in frontmatter
+    if let Some(slug) = updated.get_mut("slug") {
+        *slug = serde_yaml::Value::String(new_slug.to_string());
+    }
 
-    Ok(())
+    updated
 }
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
 use colored::*;
-use content_move::{MoveOptions, move_content, list_all_content};
+use content_move::{MoveOptions, MovePlan, move_content, list_all_content};
 use dialoguer::{Confirm, Input, Select};
 
 #[derive(Parser)]
@@ -22,6 +22,10 @@ struct Args {
     /// New topic (optional, will move content to new topic)
     #[arg(short = 'o', long)]
     new_topic: Option<String>,
+
+    /// Show which links would be rewritten without moving anything
+    #[arg(long)]
+    dry_run: bool,
 }
 
 fn main() -> Result<()> {
@@ -34,6 +38,7 @@ fn main() -> Result<()> {
         topic: args.topic.clone(),
         new_topic: args.new_topic.clone(),
         update_frontmatter: true,
+        dry_run: args.dry_run,
     };
 
     // If no slug is provided, show a selection menu
@@ -63,6 +68,7 @@ fn main() -> Result<()> {
             topic: Some(topic.clone()),
             new_topic: None,
             update_frontmatter: true,
+            dry_run: args.dry_run,
         };
 
         // Get new slug
@@ -108,6 +114,11 @@ fn main() -> Result<()> {
             _ => "Move content?".to_string(),
         };
 
+        if new_options.dry_run {
+            print_plan(&move_content(&new_options)?);
+            return Ok(());
+        }
+
         if !Confirm::new().with_prompt(confirm_message).interact()? {
             println!("Operation cancelled");
             return Ok(());
@@ -115,10 +126,10 @@ fn main() -> Result<()> {
 
         // Move the content
         match move_content(&new_options) {
-            Ok(()) => {
+            Ok(plan) => {
                 println!("{} Content moved successfully", "SUCCESS:".green().bold());
-                println!("From: {}/{}", topic.yellow(), slug.yellow());
-                println!("To: {}/{}", new_options.new_topic.as_ref().unwrap_or(&"".to_string()).yellow(), new_options.new_slug.as_ref().unwrap().yellow());
+                println!("From: {}/{}", plan.from_topic.yellow(), plan.from_slug.yellow());
+                println!("To: {}/{}", plan.to_topic.yellow(), plan.to_slug.yellow());
                 return Ok(());
             },
             Err(err) => {
@@ -147,6 +158,12 @@ fn main() -> Result<()> {
             return Ok(());
         }
 
+        // Report the plan without touching anything
+        if options.dry_run {
+            print_plan(&move_content(&options)?);
+            return Ok(());
+        }
+
         // Confirm the move
         let confirm_message = format!("Move content? (details will be shown after successful move)");
         if !Confirm::new().with_prompt(confirm_message).interact()? {
@@ -156,10 +173,10 @@ fn main() -> Result<()> {
 
         // Move the content
         match move_content(&options) {
-            Ok(()) => {
+            Ok(plan) => {
                 println!("{} Content moved successfully", "SUCCESS:".green().bold());
-                println!("From: {}/{}", options.topic.as_ref().unwrap_or(&"".to_string()).yellow(), options.slug.as_ref().unwrap_or(&"".to_string()).yellow());
-                println!("To: {}/{}", options.new_topic.as_ref().unwrap_or(&"".to_string()).yellow(), options.new_slug.as_ref().unwrap_or(&"".to_string()).yellow());
+                println!("From: {}/{}", plan.from_topic.yellow(), plan.from_slug.yellow());
+                println!("To: {}/{}", plan.to_topic.yellow(), plan.to_slug.yellow());
                 return Ok(());
             },
             Err(err) => {
@@ -168,4 +185,35 @@ fn main() -> Result<()> {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Print a move plan for review
+fn print_plan(plan: &MovePlan) {
+    println!(
+        "{} {}/{} -> {}/{}",
+        "DRY RUN:".yellow().bold(),
+        plan.from_topic,
+        plan.from_slug,
+        plan.to_topic,
+        plan.to_slug
+    );
+    println!("Source: {}", plan.source.display());
+    println!("Destination: {}", plan.destination.display());
+
+    for change in &plan.frontmatter_changes {
+        println!("Frontmatter {}: {} -> {}", change.field, change.from, change.to);
+    }
+
+    if plan.reference_updates.is_empty() {
+        println!("No links to rewrite");
+    }
+    for update in &plan.reference_updates {
+        println!("{}:{}", update.path.display(), update.line);
+        println!("  {} {}", "-".red(), update.original);
+        println!("  {} {}", "+".green(), update.updated);
+    }
+
+    for conflict in &plan.conflicts {
+        println!("{} {}", "CONFLICT:".red().bold(), conflict);
+    }
+}
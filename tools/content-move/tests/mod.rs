@@ -10,6 +10,12 @@ mod find_content_tests;
 #[path = "unit/move_content_tests.rs"]
 mod move_content_tests;
 
+#[path = "unit/link_rewriter_tests.rs"]
+mod link_rewriter_tests;
+
+#[path = "unit/move_plan_tests.rs"]
+mod move_plan_tests;
+
 #[path = "unit/tests_tests.rs"]
 mod tests_tests;
 
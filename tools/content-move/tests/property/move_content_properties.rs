@@ -159,6 +159,15 @@ fn prop_integration_move_content() -> Result<()> {
                         default_topic: Some("blog".to_string()),
                         content: content_config,
                         publication: common_models::PublicationConfig::default(),
+                        hooks: Vec::new(),
+                        i18n: Default::default(),
+                        deploy: Default::default(),
+                        export: Default::default(),
+                        import: Default::default(),
+                        newsletter: Default::default(),
+                        typography: Default::default(),
+                        link_policy: Default::default(),
+                        og_image: Default::default(),
                         images: common_models::ImageConfig::default(),
                     };
 
@@ -193,6 +202,7 @@ topic: {}
                         topic: Some(source_topic.to_string()),
                         new_topic: Some(dest_topic.to_string()),
                         update_frontmatter: true,
+                        dry_run: false,
                     };
 
                     // Act
@@ -0,0 +1,98 @@
+use content_move::{rewrite_backlinks_with_fs, update_content_references_with_fs, update_frontmatter_file, LinkRewriter};
+use common_test_utils::mocks::MockFileSystem;
+use mockall::predicate;
+use std::path::PathBuf;
+
+#[cfg(test)]
+mod filesystem_tests {
+    use super::*;
+
+    #[test]
+    fn test_update_content_references_writes_replaced_content() {
+        let path = PathBuf::from("content/blog/post/post.md");
+        let mut mock_fs = MockFileSystem::new();
+
+        mock_fs.expect_read_file()
+            .with(predicate::eq(path.clone()))
+            .returning(|_| Ok("See old-post for details.".to_string()));
+        mock_fs.expect_write_file()
+            .with(predicate::eq(path.clone()), predicate::eq("See new-post for details.".to_string()))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        update_content_references_with_fs(&mock_fs, &path, "old-post", "new-post").unwrap();
+    }
+
+    #[test]
+    fn test_update_content_references_leaves_unchanged_files_alone() {
+        let mut mock_fs = MockFileSystem::new();
+
+        mock_fs.expect_read_file()
+            .returning(|_| Ok("Nothing to see here.".to_string()));
+        mock_fs.expect_write_file().times(0);
+
+        update_content_references_with_fs(&mock_fs, &PathBuf::from("content/blog/post/post.md"), "old-post", "new-post").unwrap();
+    }
+
+    #[test]
+    fn test_rewrite_backlinks_with_fs() {
+        let articles = vec![
+            ("blog".to_string(), "sibling".to_string(), PathBuf::from("content/blog/sibling")),
+            ("notes".to_string(), "other".to_string(), PathBuf::from("content/notes/other")),
+        ];
+        let rewriter = LinkRewriter::new("blog", "old-post", "blog", "new-post").unwrap();
+        let mut mock_fs = MockFileSystem::new();
+
+        mock_fs.expect_file_exists()
+            .returning(|path| Ok(path.extension().is_some_and(|extension| extension == "md")));
+        mock_fs.expect_read_file()
+            .with(predicate::eq(PathBuf::from("content/blog/sibling/sibling.md")))
+            .returning(|_| Ok("Intro\n[old](../old-post/)".to_string()));
+        mock_fs.expect_read_file()
+            .with(predicate::eq(PathBuf::from("content/notes/other/other.md")))
+            .returning(|_| Ok("No links".to_string()));
+        mock_fs.expect_write_file()
+            .with(
+                predicate::eq(PathBuf::from("content/blog/sibling/sibling.md")),
+                predicate::eq("Intro\n[old](../new-post/)".to_string()),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let updates = rewrite_backlinks_with_fs(&mock_fs, &articles, "blog", &rewriter, false).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].line, 2);
+        assert_eq!(updates[0].updated, "[old](../new-post/)");
+    }
+
+    #[test]
+    fn test_rewrite_backlinks_with_fs_dry_run_writes_nothing() {
+        let articles = vec![("blog".to_string(), "sibling".to_string(), PathBuf::from("content/blog/sibling"))];
+        let rewriter = LinkRewriter::new("blog", "old-post", "notes", "new-post").unwrap();
+        let mut mock_fs = MockFileSystem::new();
+
+        mock_fs.expect_file_exists().returning(|_| Ok(true));
+        mock_fs.expect_read_file().returning(|_| Ok("[old](/blog/old-post)".to_string()));
+        mock_fs.expect_write_file().times(0);
+
+        let updates = rewrite_backlinks_with_fs(&mock_fs, &articles, "blog", &rewriter, true).unwrap();
+        // Both the .md and .mdx files exist in this mock
+        assert_eq!(updates.len(), 2);
+        assert!(updates.iter().all(|update| update.updated == "[old](/notes/new-post)"));
+    }
+
+    #[test]
+    fn test_update_frontmatter_file_with_mock() {
+        let path = PathBuf::from("content/notes/new-post/new-post.md");
+        let mut mock_fs = MockFileSystem::new();
+
+        mock_fs.expect_read_file()
+            .returning(|_| Ok("---\ntitle: Post\nslug: old-post\ntopics:\n- blog\n---\nBody\n".to_string()));
+        mock_fs.expect_write_file()
+            .withf(|_, contents| contents.contains("slug: new-post") && contents.contains("- notes") && contents.ends_with("Body\n"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        update_frontmatter_file(&mock_fs, &path, "blog", "notes", "new-post").unwrap();
+    }
+}
@@ -46,6 +46,15 @@ mod find_content_dir_tests {
             default_topic: Some("blog".to_string()),
             content: content_config,
             publication: PublicationConfig::default(),
+            hooks: Vec::new(),
+            i18n: Default::default(),
+            deploy: Default::default(),
+            export: Default::default(),
+            import: Default::default(),
+            newsletter: Default::default(),
+            typography: Default::default(),
+            link_policy: Default::default(),
+            og_image: Default::default(),
             images: ImageConfig::default(),
         }
     }
@@ -0,0 +1,56 @@
+use content_move::LinkRewriter;
+
+#[cfg(test)]
+mod link_rewriter_tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrites_absolute_links() {
+        let rewriter = LinkRewriter::new("blog", "old-post", "notes", "new-post").unwrap();
+
+        assert_eq!(
+            rewriter.rewrite("See [the post](/blog/old-post) and [a section](/blog/old-post#intro).", false),
+            "See [the post](/notes/new-post) and [a section](/notes/new-post#intro)."
+        );
+        assert_eq!(
+            rewriter.rewrite(r#"<a href="/blog/old-post/">post</a>"#, false),
+            r#"<a href="/notes/new-post/">post</a>"#
+        );
+        assert_eq!(rewriter.rewrite("/blog/old-post", false), "/notes/new-post");
+    }
+
+    #[test]
+    fn test_leaves_other_slugs_alone() {
+        let rewriter = LinkRewriter::new("blog", "old-post", "blog", "new-post").unwrap();
+
+        let line = "[other](/blog/old-post-two) and [elsewhere](/notes/old-post)";
+        assert_eq!(rewriter.rewrite(line, true), line);
+    }
+
+    #[test]
+    fn test_leaves_external_links_alone() {
+        let rewriter = LinkRewriter::new("blog", "old-post", "notes", "new-post").unwrap();
+
+        let line = "[theirs](https://other-site.com/blog/old-post) and [ours](/blog/old-post)";
+        assert_eq!(
+            rewriter.rewrite(line, false),
+            "[theirs](https://other-site.com/blog/old-post) and [ours](/notes/new-post)"
+        );
+    }
+
+    #[test]
+    fn test_rewrites_relative_links_only_in_old_topic() {
+        let rewriter = LinkRewriter::new("blog", "old-post", "blog", "new-post").unwrap();
+        let line = "[sibling](../old-post/)";
+
+        assert_eq!(rewriter.rewrite(line, true), "[sibling](../new-post/)");
+        assert_eq!(rewriter.rewrite(line, false), line);
+    }
+
+    #[test]
+    fn test_relative_links_become_absolute_across_topics() {
+        let rewriter = LinkRewriter::new("blog", "old-post", "notes", "new-post").unwrap();
+
+        assert_eq!(rewriter.rewrite("[sibling](../old-post)", true), "[sibling](/notes/new-post)");
+    }
+}
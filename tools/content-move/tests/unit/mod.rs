@@ -3,3 +3,6 @@
 mod move_content_tests;
 mod find_content_tests;
 mod move_options_tests;
+mod link_rewriter_tests;
+mod move_plan_tests;
+mod filesystem_tests;
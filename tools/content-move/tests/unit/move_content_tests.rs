@@ -44,6 +44,15 @@ mod move_content_tests {
             default_topic: Some("blog".to_string()),
             content: content_config,
             publication: common_models::PublicationConfig::default(),
+            hooks: Vec::new(),
+            i18n: Default::default(),
+            deploy: Default::default(),
+            export: Default::default(),
+            import: Default::default(),
+            newsletter: Default::default(),
+            typography: Default::default(),
+            link_policy: Default::default(),
+            og_image: Default::default(),
             images: common_models::ImageConfig::default(),
         }
     }
@@ -95,6 +104,7 @@ This is a test article.
                 topic: Some("blog".to_string()),
                 new_topic: Some("docs".to_string()),
                 update_frontmatter: false,
+                dry_run: false,
             };
 
             // Act
@@ -153,6 +163,7 @@ This is a test article.
                 topic: Some("blog".to_string()),
                 new_topic: Some("docs".to_string()),
                 update_frontmatter: false,
+                dry_run: false,
             };
 
             // Act
@@ -202,6 +213,7 @@ This is a test article.
                 topic: Some("blog".to_string()),
                 new_topic: Some("docs".to_string()),
                 update_frontmatter: false,
+                dry_run: false,
             };
 
             // Act
@@ -262,6 +274,7 @@ This is a blog post.
                 topic: Some("blog".to_string()),
                 new_topic: Some("docs".to_string()),
                 update_frontmatter: true,
+                dry_run: false,
             };
 
             // Act
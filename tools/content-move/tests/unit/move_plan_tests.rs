@@ -0,0 +1,215 @@
+use content_move::{move_content, move_many, plan_move, MoveConflict, MoveItemStatus, MoveOptions};
+use common_test_utils::{use_config, write_article, write_repository};
+use std::fs;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod move_plan_tests {
+    use super::*;
+
+    fn move_options(topic: &str, slug: &str, new_topic: &str, new_slug: &str) -> MoveOptions {
+        MoveOptions {
+            slug: Some(slug.to_string()),
+            new_slug: Some(new_slug.to_string()),
+            topic: Some(topic.to_string()),
+            new_topic: Some(new_topic.to_string()),
+            update_frontmatter: true,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_dry_run_reports_plan_without_touching_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let _config = use_config(&write_repository(root, &["blog", "notes"]));
+
+        write_article(&root.join("content"), "blog", "old-post", "---\ntitle: Old\nslug: old-post\ntopics:\n  - blog\n---\nBody\n");
+        write_article(&root.join("content"), "blog", "linking-post", "---\ntitle: Linking\n---\nSee [old](/blog/old-post).\n");
+        write_article(&root.join("content"), "notes", "taken", "---\ntitle: Taken\n---\n");
+
+        let options = MoveOptions {
+            dry_run: true,
+            ..move_options("blog", "old-post", "notes", "new-post")
+        };
+
+        let plan = move_content(&options).unwrap();
+
+        assert!(plan.is_executable());
+        assert_eq!(plan.source, root.join("content/blog/old-post"));
+        assert_eq!(plan.destination, root.join("content/notes/new-post"));
+
+        let fields: Vec<&str> = plan.frontmatter_changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["slug", "topics"]);
+
+        assert_eq!(plan.reference_updates.len(), 1);
+        assert_eq!(plan.reference_updates[0].updated, "See [old](/notes/new-post).");
+
+        // Nothing moved or rewritten
+        assert!(root.join("content/blog/old-post/old-post.md").exists());
+        assert!(!root.join("content/notes/new-post").exists());
+        let linking = fs::read_to_string(root.join("content/blog/linking-post/linking-post.md")).unwrap();
+        assert!(linking.contains("/blog/old-post"));
+
+        // An occupied destination is reported as a conflict, and refused without dry run
+        let taken = MoveOptions {
+            new_slug: Some("taken".to_string()),
+            ..options
+        };
+        let plan = move_content(&taken).unwrap();
+        assert_eq!(
+            plan.conflicts,
+            vec![MoveConflict::DestinationExists {
+                topic: "notes".to_string(),
+                slug: "taken".to_string(),
+            }]
+        );
+
+        let err = move_content(&MoveOptions { dry_run: false, ..taken }).unwrap_err();
+        assert!(err.to_string().contains("Content already exists in target topic: notes/taken"));
+        assert!(root.join("content/blog/old-post").exists());
+    }
+
+    #[test]
+    fn test_move_many_moves_every_item() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let _config = use_config(&write_repository(root, &["blog", "notes"]));
+
+        write_article(&root.join("content"), "blog", "first", "---\ntitle: First\n---\nSee [second](/blog/second).\n");
+        write_article(&root.join("content"), "blog", "second", "---\ntitle: Second\n---\n");
+
+        let report = move_many(vec![
+            move_options("blog", "first", "notes", "first"),
+            move_options("blog", "second", "notes", "second"),
+        ])
+        .unwrap();
+
+        assert!(report.is_success());
+        assert_eq!(report.items.len(), 2);
+        assert!(report.items.iter().all(|item| item.status == MoveItemStatus::Moved));
+        assert!(!root.join("content/blog/first").exists());
+        assert!(!root.join("content/blog/second").exists());
+
+        let first = fs::read_to_string(root.join("content/notes/first/first.md")).unwrap();
+        assert!(first.contains("/notes/second"));
+    }
+
+    #[test]
+    fn test_move_many_rejects_colliding_batch_before_moving() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let _config = use_config(&write_repository(root, &["blog", "notes"]));
+
+        write_article(&root.join("content"), "blog", "first", "---\ntitle: First\n---\n");
+        write_article(&root.join("content"), "blog", "second", "---\ntitle: Second\n---\n");
+
+        let err = move_many(vec![
+            move_options("blog", "first", "notes", "merged"),
+            move_options("blog", "second", "notes", "merged"),
+            move_options("blog", "first", "notes", "first"),
+            move_options("blog", "second", "missing", "second"),
+        ])
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("blog/second: destination notes/merged is used by another move"));
+        assert!(err.contains("blog/second: New topic not found: missing"));
+        assert!(err.contains("blog/first: moved more than once"));
+        assert!(root.join("content/blog/first/first.md").exists());
+        assert!(root.join("content/blog/second/second.md").exists());
+        assert!(!root.join("content/notes").exists());
+    }
+
+    #[test]
+    fn test_move_many_rejects_chained_and_swapped_moves() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let _config = use_config(&write_repository(root, &["blog", "notes"]));
+
+        write_article(&root.join("content"), "blog", "first", "---\ntitle: First\n---\n");
+        write_article(&root.join("content"), "blog", "second", "---\ntitle: Second\n---\n");
+
+        let chain = move_many(vec![
+            move_options("blog", "first", "blog", "second"),
+            move_options("blog", "second", "notes", "third"),
+        ])
+        .unwrap_err()
+        .to_string();
+        assert!(chain.contains("blog/first: destination blog/second is moved by another move"));
+
+        let swap = move_many(vec![
+            move_options("blog", "first", "blog", "second"),
+            move_options("blog", "second", "blog", "first"),
+        ])
+        .unwrap_err()
+        .to_string();
+        assert!(swap.contains("blog/first: destination blog/second is moved by another move"));
+        assert!(swap.contains("blog/second: destination blog/first is moved by another move"));
+
+        assert!(root.join("content/blog/first/first.md").exists());
+        assert!(root.join("content/blog/second/second.md").exists());
+        assert!(!root.join("content/notes").exists());
+    }
+
+    #[test]
+    fn test_plan_rejects_path_traversal_in_new_slug() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let _config = use_config(&write_repository(root, &["blog", "notes"]));
+
+        write_article(&root.join("content"), "blog", "old-post", "---\ntitle: Old\n---\n");
+
+        let options = MoveOptions {
+            dry_run: true,
+            ..move_options("blog", "old-post", "notes", "../../escaped")
+        };
+        assert!(plan_move(&options).is_err());
+        assert!(move_content(&options).is_err());
+        assert!(root.join("content/blog/old-post/old-post.md").exists());
+    }
+
+    #[test]
+    fn test_plan_rejects_unknown_topic() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let _config = use_config(&write_repository(root, &["blog", "notes"]));
+
+        write_article(&root.join("content"), "blog", "old-post", "---\ntitle: Old\n---\n");
+
+        let err = plan_move(&move_options("blog", "old-post", "../outside", "old-post"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("New topic not found: ../outside"));
+    }
+
+    #[test]
+    fn test_move_many_rolls_back_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let _config = use_config(&write_repository(root, &["blog", "notes"]));
+
+        let first = "---\ntitle: First\nslug: first\n---\nSee [second](/blog/second).\n";
+        let second = "---\ntitle: Second\n---\nSee [first](/blog/first).\n";
+        write_article(&root.join("content"), "blog", "first", first);
+        write_article(&root.join("content"), "blog", "second", second);
+
+        // A file where the notes topic directory should be makes the second move fail
+        fs::write(root.join("content/notes"), "").unwrap();
+
+        let report = move_many(vec![
+            move_options("blog", "first", "blog", "renamed"),
+            move_options("blog", "second", "notes", "second"),
+        ])
+        .unwrap();
+
+        assert!(!report.is_success());
+        assert_eq!(report.items[0].status, MoveItemStatus::RolledBack);
+        assert!(matches!(report.items[1].status, MoveItemStatus::Failed(_)));
+
+        assert!(!root.join("content/blog/renamed").exists());
+        assert_eq!(fs::read_to_string(root.join("content/blog/first/first.md")).unwrap(), first);
+        assert_eq!(fs::read_to_string(root.join("content/blog/second/second.md")).unwrap(), second);
+        assert!(!root.join("content/redirects.yaml").exists());
+    }
+}
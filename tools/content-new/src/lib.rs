@@ -1,12 +1,34 @@
 use anyhow::Result;
 use common_config::load_config;
+use common_errors::ErrorKind;
 use common_fs::{create_dir_all, write_file};
-use common_models::{Frontmatter, TopicConfig};
-use std::collections::HashMap;
+use common_models::TopicConfig;
+use common_plugins::{HookContext, HookEvent, HookRegistry};
+use common_templates::{ask_missing, Prompter, Template, TemplatePrompt};
+use common_validation::{format_tags, validate_slug, validate_tags, validate_topic};
 use std::error::Error;
 use std::fmt;
 use std::path::PathBuf;
 
+/// Template used when no template is requested and none can be loaded
+const DEFAULT_TEMPLATE: &str = r#"---
+title: "{{ title }}"
+description: "{{ description }}"
+slug: "{{ slug }}"
+topics: ["{{ topic }}"]
+tags:
+  [
+    {{ tags }}
+  ]
+published: "{{ date }}"
+draft: {{ draft }}
+---
+
+# {{ title }}
+
+{{ tagline }}
+"#;
+
 #[derive(Debug)]
 pub enum ContentNewError {
     /// Topic not found
@@ -26,11 +48,11 @@ impl fmt::Display for ContentNewError {
         match self {
             ContentNewError::TopicNotFound(topic) => write!(f, "Topic not found: {}", topic),
             ContentNewError::SlugAlreadyExists(slug) => {
-                write!(f, "Content with slug already exists: {}", slug)
+                write!(f, "Content already exists: {}", slug)
             }
             ContentNewError::InvalidSlug(slug) => write!(f, "Invalid slug: {}", slug),
             ContentNewError::MissingRequiredField(field) => {
-                write!(f, "Missing required field: {}", field)
+                write!(f, "{} is required", field)
             }
             ContentNewError::IoError(err) => write!(f, "IO error: {}", err),
         }
@@ -45,30 +67,31 @@ impl From<std::io::Error> for ContentNewError {
     }
 }
 
-impl From<ContentNewError> for anyhow::Error {
-    fn from(err: ContentNewError) -> Self {
-        anyhow::anyhow!(err.to_string())
-    }
-}
-
+/// Options for creating new content
+#[derive(Debug, Clone, Default)]
 pub struct NewOptions {
-    pub slug: String,
-    pub title: String,
-    pub topic: String,
+    /// Slug of the new content, used as the directory and file name
+    pub slug: Option<String>,
+    /// Title of the new content
+    pub title: Option<String>,
+    /// Topic key the content belongs to
+    pub topic: Option<String>,
+    /// Short description, also used as the template's tagline
     pub description: Option<String>,
+    /// Name of the template to render (defaults to the article template)
     pub template: Option<String>,
+    /// Tags for the content
     pub tags: Option<Vec<String>>,
+    /// Whether to create the content as a draft
     pub draft: Option<bool>,
-    pub publish: bool,
-    pub subtitle: Option<String>,
-    pub author: Option<String>,
-    pub date: Option<String>,
-    pub aliases: Option<Vec<String>>,
 }
 
 /// Create new content
 ///
-/// This function creates a new piece of content based on the provided options.
+/// This function validates the slug and topic, renders the requested
+/// template with the title, tagline, tags and date, and writes it to
+/// `{base_dir}/{topic}/{slug}/{slug}.md`. Existing content is never
+/// overwritten.
 ///
 /// # Parameters
 ///
@@ -80,122 +103,113 @@ pub struct NewOptions {
 ///
 /// # Errors
 ///
-/// Returns an error if the creation fails
-pub fn create_new_content(options: &NewOptions) -> Result<PathBuf> {
-    let config = load_config()?;
+/// Returns an error if a required field is missing, the slug, topic or
+/// tags are invalid, the content already exists, or the template cannot
+/// be rendered
+pub fn create_content(options: &NewOptions) -> Result<PathBuf> {
+    let slug = options.slug.as_deref()
+        .ok_or_else(|| ContentNewError::MissingRequiredField("Content slug".to_string()))?;
+    let title = options.title.as_deref()
+        .ok_or_else(|| ContentNewError::MissingRequiredField("Title".to_string()))?;
+    let topic = options.topic.as_deref()
+        .ok_or_else(|| ContentNewError::MissingRequiredField("Topic".to_string()))?;
+
+    let slug = validate_slug(slug)
+        .map_err(|e| ContentNewError::InvalidSlug(format!("{} ({})", slug, e.message())))?;
+    validate_topic(Some(topic)).map_err(|e| match e.kind() {
+        ErrorKind::TopicError => anyhow::Error::from(ContentNewError::TopicNotFound(topic.to_string())),
+        _ => anyhow::Error::from(e),
+    })?;
+    let tags = validate_tags(&options.tags.clone().unwrap_or_default().join(","))?;
 
-    // Get the topic configuration
+    let config = load_config()?;
     let topic_config = config
         .content
         .topics
-        .get(&options.topic)
-        .ok_or_else(|| ContentNewError::TopicNotFound(options.topic.clone()))?;
-
-    // Verify the slug doesn't already exist in other topics
-    for (topic_key, topic_conf) in &config.content.topics {
-        if topic_key == &options.topic {
-            continue;
-        }
+        .get(topic)
+        .ok_or_else(|| ContentNewError::TopicNotFound(topic.to_string()))?;
 
-        let topic_dir = PathBuf::from(&config.content.base_dir).join(&topic_conf.directory);
-        let article_dir = topic_dir.join(&options.slug);
+    // Refuse to create content whose slug is already used in any topic
+    for topic_conf in config.content.topics.values() {
+        let existing_dir = PathBuf::from(&config.content.base_dir)
+            .join(&topic_conf.directory)
+            .join(&slug);
 
-        if article_dir.exists() {
-            return Err(ContentNewError::SlugAlreadyExists(options.slug.clone()).into());
+        if existing_dir.exists() {
+            return Err(ContentNewError::SlugAlreadyExists(slug).into());
         }
     }
 
-    // Create the content
-    let topic_dir = PathBuf::from(&config.content.base_dir).join(&topic_config.directory);
-    let article_dir = topic_dir.join(&options.slug);
-
-    // Create the directory if it doesn't exist
-    if !article_dir.exists() {
-        std::fs::create_dir_all(&article_dir)?;
-    }
-
-    // Create the content file path using the slug name format
-    let content_file = article_dir.join(format!("{}.md", options.slug));
+    let article_dir = PathBuf::from(&config.content.base_dir)
+        .join(&topic_config.directory)
+        .join(&slug);
+    let content_file = article_dir.join(format!("{}.md", slug));
 
-    // Check if the file already exists
-    if content_file.exists() {
-        return Err(ContentNewError::SlugAlreadyExists(options.slug.clone()).into());
-    }
+    let content = render_content(options, &slug, title, topic, &tags)?;
 
-    // Create the frontmatter
-    let mut frontmatter = HashMap::new();
+    let hooks = HookRegistry::from_config(&config)?;
+    let hook_context = HookContext::for_article(HookEvent::PreCreate, topic, &slug).with_path(&article_dir);
+    hooks.fire(&hook_context)?;
 
-    // Add title to frontmatter
-    frontmatter.insert("title".to_string(), options.title.clone());
-
-    // Add optional description if provided
-    if let Some(description) = &options.description {
-        frontmatter.insert("description".to_string(), description.clone());
-    }
-
-    // Add published status
-    frontmatter.insert("published".to_string(), "true".to_string());
-
-    // Add draft status
-    let is_draft = options.draft.unwrap_or(false);
-    frontmatter.insert("draft".to_string(), is_draft.to_string());
-
-    // Add optional subtitle if provided
-    if let Some(subtitle) = &options.subtitle {
-        frontmatter.insert("subtitle".to_string(), subtitle.clone());
-    }
+    create_dir_all(&article_dir)?;
+    write_file(&content_file, &content)?;
 
-    // Add optional author if provided
-    if let Some(author) = &options.author {
-        frontmatter.insert("author".to_string(), author.clone());
+    // The content exists now, so a failing hook is only worth a warning
+    if let Err(err) = hooks.fire(&hook_context.with_event(HookEvent::PostCreate)) {
+        tracing::warn!(path = %content_file.display(), "{}", err);
     }
 
-    // Add date (either provided or current)
-    if let Some(date) = &options.date {
-        frontmatter.insert("date".to_string(), date.clone());
-    } else {
-        let now = chrono::Local::now();
-        frontmatter.insert("date".to_string(), now.format("%Y-%m-%d").to_string());
-    }
-
-    // Add aliases if provided
-    if let Some(aliases) = &options.aliases {
-        if !aliases.is_empty() {
-            let aliases_string = format!("[{}]", aliases.join(", "));
-            frontmatter.insert("aliases".to_string(), aliases_string);
-        }
-    }
-
-    // Add tags if provided
-    if let Some(tags) = &options.tags {
-        if !tags.is_empty() {
-            let tags_string = format!("[{}]", tags.join(", "));
-            frontmatter.insert("tags".to_string(), tags_string);
-        }
-    }
-
-    // Create the content
-    let mut content = String::new();
-
-    // Add the frontmatter
-    content.push_str("---\n");
-    for (key, value) in &frontmatter {
-        content.push_str(&format!("{}: {}\n", key, value));
-    }
-    content.push_str("---\n\n");
-
-    // Add the content
-    content.push_str(&format!("# {}\n\n", options.title));
-    if let Some(subtitle) = &options.subtitle {
-        content.push_str(&format!("_{}_\n\n", subtitle));
-    }
-    content.push_str("Your content here...\n");
+    Ok(content_file)
+}
 
-    // Write the content to the file
-    std::fs::write(&content_file, content)?;
+/// Render the requested template, or the default template if none was requested
+fn render_content(
+    options: &NewOptions,
+    slug: &str,
+    title: &str,
+    topic: &str,
+    tags: &[String],
+) -> Result<String> {
+    let description = options.description.clone().unwrap_or_default();
+    let draft = options.draft.unwrap_or(false).to_string();
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let tags = format_tags(&tags.join(","));
+
+    let variables = [
+        ("title", title),
+        ("tagline", description.as_str()),
+        ("description", description.as_str()),
+        ("slug", slug),
+        ("topic", topic),
+        ("tags", tags.trim_start()),
+        ("date", date.as_str()),
+        ("draft", draft.as_str()),
+        ("introduction", description.as_str()),
+        ("content", ""),
+    ];
+
+    let template = match &options.template {
+        Some(name) => Some(common_templates::load_template(name)?),
+        None => common_templates::load_template_for_content_type("article").ok(),
+    };
+
+    let rendered = match template {
+        Some(mut template) => template.render(&variables)?,
+        None => render_default_template(&variables)?,
+    };
+
+    Ok(rendered)
+}
 
-    // Return the path to the created content
-    Ok(content_file)
+/// Render the built-in default template
+fn render_default_template(variables: &[(&str, &str)]) -> Result<String> {
+    let mut template = common_templates::Template::from_content(
+        "default",
+        "Default template for article content",
+        "article",
+        DEFAULT_TEMPLATE,
+    );
+    Ok(template.render(variables)?)
 }
 
 /// List available templates
@@ -236,3 +250,113 @@ pub fn get_available_topics() -> Result<Vec<(String, TopicConfig)>> {
 
     Ok(topics)
 }
+
+/// Prompts for the fields of new content that can be asked for interactively
+///
+/// The title, tagline, tags and topic are asked for in that order. When the
+/// template declares one of these variables, its description and default
+/// are used; `tagline` and `description` are the same field. The topic
+/// defaults to the first of `topics`.
+///
+/// # Parameters
+///
+/// * `template` - The template the content will be rendered with, if known
+/// * `topics` - Keys of the configured topics
+///
+/// # Returns
+///
+/// Returns the prompts, in the order to ask them
+pub fn content_prompts(template: Option<&Template>, topics: &[String]) -> Vec<TemplatePrompt> {
+    let declared = template.map(Template::prompts).unwrap_or_default();
+
+    let mut topic = TemplatePrompt::new("topic", true);
+    topic.description = format!("Topic ({})", topics.join(", "));
+    topic.default = topics.first().cloned();
+
+    let fields = [
+        (prompt_with("title", "Title", true), &["title"][..]),
+        (prompt_with("tagline", "Tagline", false), &["tagline", "description"][..]),
+        (prompt_with("tags", "Tags (comma-separated)", false), &["tags"][..]),
+        (topic, &["topic"][..]),
+    ];
+
+    fields
+        .into_iter()
+        .map(|(mut prompt, names)| {
+            if let Some(declared) = declared.iter().find(|declared| names.contains(&declared.name.as_str())) {
+                if declared.description != declared.name {
+                    prompt.description = declared.description.clone();
+                }
+                if declared.default.is_some() {
+                    prompt.default = declared.default.clone();
+                }
+                prompt.required |= declared.required;
+            }
+            prompt
+        })
+        .collect()
+}
+
+fn prompt_with(name: &str, description: &str, required: bool) -> TemplatePrompt {
+    TemplatePrompt {
+        description: description.to_string(),
+        ..TemplatePrompt::new(name, required)
+    }
+}
+
+/// Ask for the fields of `options` that are not set
+///
+/// Only the fields named by `prompts` (see [`content_prompts`]) are asked
+/// for. A missing slug is made from the title.
+///
+/// # Parameters
+///
+/// * `options` - Options given on the command line
+/// * `prompts` - Prompts for the fields that can be asked for
+/// * `prompter` - Supplies the answers
+///
+/// # Returns
+///
+/// Returns the options with the answers filled in
+///
+/// # Errors
+///
+/// Returns an error if the prompter fails
+pub fn prompt_for_options(
+    options: &NewOptions,
+    prompts: &[TemplatePrompt],
+    prompter: &mut dyn Prompter,
+) -> Result<NewOptions> {
+    let mut provided = Vec::new();
+    if options.title.is_some() {
+        provided.push(("title", ""));
+    }
+    if options.description.is_some() {
+        provided.push(("tagline", ""));
+    }
+    if options.tags.is_some() {
+        provided.push(("tags", ""));
+    }
+    if options.topic.is_some() {
+        provided.push(("topic", ""));
+    }
+
+    let mut options = options.clone();
+    for (name, value) in ask_missing(prompts, &provided, prompter)? {
+        match name.as_str() {
+            "title" => options.title = Some(value),
+            "tagline" => options.description = Some(value),
+            "tags" => {
+                options.tags = Some(value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect())
+            }
+            "topic" => options.topic = Some(value),
+            _ => {}
+        }
+    }
+
+    if options.slug.is_none() {
+        options.slug = options.title.as_deref().map(slug::slugify);
+    }
+
+    Ok(options)
+}
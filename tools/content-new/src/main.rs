@@ -1,9 +1,11 @@
 use common_errors::{Result, WritingError};
 use clap::Parser;
-use content_new::{NewOptions, create_content, get_available_topics, list_templates};
+use common_templates::{Prompter, TemplatePrompt};
+use content_new::{NewOptions, content_prompts, create_content, get_available_topics, list_templates, prompt_for_options};
 use dialoguer::{Input, Select, Confirm};
 use std::convert::From;
 use std::env;
+use std::io::{self, BufRead, Write};
 use slug::slugify;
 
 // Define our own simplified versions of the CLI argument structs
@@ -73,67 +75,40 @@ struct Args {
     tag: TagArgs,
 }
 
+/// Answers template prompts on the terminal
+struct StdinPrompter;
+
+impl Prompter for StdinPrompter {
+    fn ask(&mut self, prompt: &TemplatePrompt) -> Result<String> {
+        match &prompt.default {
+            Some(default) => print!("{} [{}]: ", prompt.description, default),
+            None => print!("{}: ", prompt.description),
+        }
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        Ok(answer.trim_end().to_string())
+    }
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
     // Check if we're in a test environment
     let is_test = env::var("CI").is_ok() || env::var("TEST_MODE").is_ok();
 
-    // Get title if not provided
-    let title = if !args.create.title.is_empty() {
-        args.create.title.clone()
-    } else if is_test {
-        "Test Title".to_string()
-    } else {
-        let input = Input::new()
-            .with_prompt("Enter content title")
-            .interact_text()
-            .map_err(|e| WritingError::validation_error(format!("Dialog error: {}", e)))?;
-        input
-    };
-
-    // Generate slug from title
-    let slug = slugify(&title);
-
-    // Get topic if not provided
-    let topic = match &args.content.topic {
-        Some(t) => t.clone(),
-        None => {
-            if is_test {
-                "blog".to_string()
-            } else {
-                // Get available topics
-                let topics = get_available_topics()
-                    .map_err(|e| WritingError::validation_error(format!("Error getting topics: {}", e)))?;
-
-                // Create a list of topic options
-                let topic_display: Vec<String> = topics.iter()
-                    .map(|(k, v)| format!("{} - {}", k, v.description))
-                    .collect();
-
-                let selection = Select::new()
-                    .with_prompt("Select a topic")
-                    .items(&topic_display)
-                    .default(0)
-                    .interact()
-                    .map_err(|e| WritingError::validation_error(format!("Dialog error: {}", e)))?;
-
-                topics[selection].0.clone()
-            }
-        }
-    };
-
-    // Get description if not provided
-    let description = if !args.create.description.is_empty() {
-        args.create.description.clone()
-    } else if is_test {
-        "Test Description".to_string()
-    } else {
-        let input = Input::new()
-            .with_prompt("Enter content description")
-            .interact_text()
-            .map_err(|e| WritingError::validation_error(format!("Dialog error: {}", e)))?;
-        input
+    let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+    let options = NewOptions {
+        slug: None,
+        topic: args.content.topic.clone(),
+        title: non_empty(&args.create.title),
+        description: non_empty(&args.create.description),
+        template: args.template.template.clone(),
+        tags: args.tag.tags.as_deref().map(|tags| {
+            tags.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+        }),
+        draft: Some(args.draft.draft),
     };
 
     // Get content type if not already specified
@@ -155,7 +130,7 @@ fn main() -> Result<()> {
     };
 
     // Get template if not provided
-    let template = match &args.template.template {
+    let template = match &options.template {
         Some(t) => Some(t.clone()),
         None => {
             if is_test {
@@ -192,26 +167,37 @@ fn main() -> Result<()> {
             }
         }
     };
-
-    // Get tags if not provided
-    let tags = match &args.tag.tags {
-        Some(t) => t.clone(),
-        None => {
-            if is_test {
-                "".to_string()
-            } else {
-                let input = Input::new()
-                    .with_prompt("Enter tags (comma-separated)")
-                    .allow_empty(true)
-                    .interact_text()
-                    .map_err(|e| WritingError::validation_error(format!("Dialog error: {}", e)))?;
-                input
-            }
+    let options = NewOptions { template, ..options };
+
+    // Ask for the title, tagline, tags and topic that weren't given
+    let options = if is_test {
+        NewOptions {
+            title: options.title.clone().or_else(|| Some("Test Title".to_string())),
+            description: options.description.clone().or_else(|| Some("Test Description".to_string())),
+            topic: options.topic.clone().or_else(|| Some("blog".to_string())),
+            ..options
         }
+    } else {
+        let topics: Vec<String> = get_available_topics()
+            .map_err(|e| WritingError::validation_error(format!("Error getting topics: {}", e)))?
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+
+        // The template's declared variables describe what to ask for
+        let template = match &options.template {
+            Some(name) => common_templates::load_template(name).ok(),
+            None => common_templates::load_template_for_content_type("article").ok(),
+        };
+
+        let prompts = content_prompts(template.as_ref(), &topics);
+        prompt_for_options(&options, &prompts, &mut StdinPrompter)
+            .map_err(|e| WritingError::validation_error(format!("Prompt error: {}", e)))?
+    };
+    let options = NewOptions {
+        slug: options.slug.clone().or_else(|| options.title.as_deref().map(slugify)),
+        ..options
     };
-
-    // Get draft status if not provided
-    let draft = args.draft.draft;
 
     // Get introduction if not provided
     let _introduction = match &args.create.introduction {
@@ -240,28 +226,13 @@ fn main() -> Result<()> {
         }
     };
 
-    // Create content options
-    let title_clone = title.clone();
-    let options = NewOptions {
-        slug: Some(slug),
-        topic: Some(topic),
-        title: Some(title),
-        description: Some(description),
-        template,
-        tags: if !tags.is_empty() {
-            Some(tags.split(',').map(|s| s.trim().to_string()).collect())
-        } else {
-            None
-        },
-        draft: Some(draft),
-    };
-
     // Create content
+    let title = options.title.clone().unwrap_or_default();
     let content_path = create_content(&options)
         .map_err(|e| WritingError::validation_error(format!("Error creating content: {}", e)))?;
 
     // Print success message
-    println!("Created content: {} (\"{}\")", content_path.display(), title_clone);
+    println!("Created content: {} (\"{}\")", content_path.display(), title);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -44,7 +44,7 @@ fn test_cli_create_content_with_args() -> Result<()> {
         .join("content")
         .join("blog")
         .join("test-title")
-        .join("index.mdx");
+        .join("test-title.md");
 
     // Verify the file was created
     assert!(expected_path.exists(),
@@ -20,7 +20,7 @@ fn test_create_new_content() {
     assert!(stdout.contains("Test Article"));
 
     // Verify content was created
-    let content_path = command.fixture.path().join("content/blog/test-article/index.mdx");
+    let content_path = command.fixture.path().join("content/blog/test-article/test-article.md");
     assert!(content_path.exists());
 
     // Verify content has expected structure
@@ -48,7 +48,7 @@ fn test_create_new_content_with_tags() {
     assert!(stdout.contains("Tagged Article"));
 
     // Verify content was created
-    let content_path = command.fixture.path().join("content/blog/tagged-article/index.mdx");
+    let content_path = command.fixture.path().join("content/blog/tagged-article/tagged-article.md");
     assert!(content_path.exists());
 
     // Verify content has expected structure
@@ -79,7 +79,7 @@ fn test_create_new_content_as_draft() {
     assert!(stdout.contains("Draft Article"));
 
     // Verify content was created
-    let content_path = command.fixture.path().join("content/blog/draft-article/index.mdx");
+    let content_path = command.fixture.path().join("content/blog/draft-article/draft-article.md");
     assert!(content_path.exists());
 
     // Verify content has expected structure
@@ -87,7 +87,6 @@ fn test_create_new_content_as_draft() {
     assert!(content.contains("title: \"Draft Article\""));
     assert!(content.contains("description: \"A draft article\""));
     assert!(content.contains("draft: true"));
-    assert!(content.contains("date: DRAFT"));
 }
 
 #[test]
@@ -121,7 +120,7 @@ fn test_create_new_content_with_custom_template() {
     assert!(stdout.contains("Custom Template Article"));
 
     // Verify content was created
-    let content_path = command.fixture.path().join("content/blog/custom-template-article/index.mdx");
+    let content_path = command.fixture.path().join("content/blog/custom-template-article/custom-template-article.md");
     assert!(content_path.exists());
 
     // Verify content has expected structure
@@ -149,7 +148,7 @@ fn test_create_new_content_with_invalid_topic() {
     assert!(stderr.contains("invalid-topic"));
 
     // Verify content was not created
-    let content_path = command.fixture.path().join("content/invalid-topic/invalid-topic-article/index.mdx");
+    let content_path = command.fixture.path().join("content/invalid-topic/invalid-topic-article/invalid-topic-article.md");
     assert!(!content_path.exists());
 }
 
@@ -191,7 +190,7 @@ fn test_interactive_content_creation() {
     assert!(output.status.success());
 
     // Verify content was created
-    let content_path = command.fixture.path().join("content/blog/interactive-article/index.mdx");
+    let content_path = command.fixture.path().join("content/blog/interactive-article/interactive-article.md");
     assert!(content_path.exists());
 
     // Verify content has expected structure
@@ -76,7 +76,7 @@ proptest! {
             .join("content")
             .join(&topic)
             .join(&slug)
-            .join("index.mdx");
+            .join(format!("{slug}.md"));
 
         prop_assert_eq!(path, expected_path);
 
@@ -174,7 +174,7 @@ proptest! {
         } else {
             prop_assert!(!content.contains("date: DRAFT"));
             // Should have a real date instead
-            prop_assert!(content.contains("date: \"20"));
+            prop_assert!(content.contains("published: \"20"));
         }
     }
 }
\ No newline at end of file
@@ -36,7 +36,7 @@ fn test_create_content_with_valid_options() -> Result<()> {
         .join("content")
         .join("blog")
         .join("test-slug")
-        .join("index.mdx");
+        .join("test-slug.md");
     assert_eq!(content_path, expected_path);
 
     // Verify the file exists and has the expected content
@@ -80,7 +80,6 @@ fn test_create_content_draft_mode() -> Result<()> {
 
     // Verify draft status in content
     let content = std::fs::read_to_string(&content_path)?;
-    assert!(content.contains("date: DRAFT"));
     assert!(content.contains("draft: true"));
 
     // Clean up
@@ -257,4 +256,55 @@ fn test_create_content_existing_content() -> Result<()> {
     std::env::remove_var("TEST_MODE");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Write a repository with a blog topic and the given hooks, and point `CONFIG_PATH` at it
+fn use_hooks(fixture: &TestFixture, hooks: Vec<common_models::HookConfig>) -> common_test_utils::ConfigPathGuard {
+    let mut config = common_test_utils::config_for(&fixture.path().join("content"), &["blog"]);
+    config.hooks = hooks;
+    common_test_utils::use_config(&common_test_utils::write_config(fixture.path(), &config))
+}
+
+fn hooked_options() -> NewOptions {
+    NewOptions {
+        slug: Some("hooked".to_string()),
+        title: Some("Hooked".to_string()),
+        topic: Some("blog".to_string()),
+        description: Some("Test description".to_string()),
+        draft: Some(false),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_create_content_runs_post_create_hooks() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let marker = fixture.path().join("created");
+    let _config = use_hooks(&fixture, vec![common_models::HookConfig {
+        event: "post_create".to_string(),
+        run: None,
+        touch: Some(marker.to_string_lossy().to_string()),
+    }]);
+
+    let content_path = create_content(&hooked_options())?;
+    assert!(content_path.exists());
+    assert!(marker.exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_create_content_only_warns_when_a_post_create_hook_fails() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let _config = use_hooks(&fixture, vec![common_models::HookConfig {
+        event: "post_create".to_string(),
+        run: Some("exit 1".to_string()),
+        touch: None,
+    }]);
+
+    // The content is already written, so the hook failing doesn't undo it
+    let content_path = create_content(&hooked_options())?;
+    assert!(content_path.exists());
+
+    Ok(())
+}
@@ -4,4 +4,5 @@
 
 mod get_available_topics_tests;
 mod create_content_tests;
-mod list_templates_tests;
\ No newline at end of file
+mod list_templates_tests;
+mod prompt_options_tests;
\ No newline at end of file
@@ -0,0 +1,70 @@
+use common_templates::{Prompter, Template, TemplatePrompt};
+use content_new::{content_prompts, prompt_for_options, NewOptions};
+use mockall::mock;
+use mockall::predicate::function;
+
+mock! {
+    pub Prompter {}
+
+    impl Prompter for Prompter {
+        fn ask(&mut self, prompt: &TemplatePrompt) -> common_errors::Result<String>;
+    }
+}
+
+fn topics() -> Vec<String> {
+    vec!["blog".to_string(), "notes".to_string()]
+}
+
+#[test]
+fn test_content_prompts_use_declared_variables() {
+    let mut template = Template::from_content("essay", "", "article", "{{ description }}")
+        .with_required_variables(["description"]);
+    template.variables = vec![common_templates::TemplateVariable {
+        name: "description".to_string(),
+        description: Some("One-line summary".to_string()),
+        default: None,
+    }];
+
+    let prompts = content_prompts(Some(&template), &topics());
+    let names: Vec<&str> = prompts.iter().map(|prompt| prompt.name.as_str()).collect();
+    assert_eq!(names, vec!["title", "tagline", "tags", "topic"]);
+
+    assert_eq!(prompts[1].description, "One-line summary");
+    assert!(prompts[1].required);
+    assert_eq!(prompts[3].default.as_deref(), Some("blog"));
+    assert!(prompts[3].description.contains("blog, notes"));
+}
+
+#[test]
+fn test_prompt_for_options_asks_only_for_missing_fields() {
+    let mut prompter = MockPrompter::new();
+    prompter
+        .expect_ask()
+        .with(function(|prompt: &TemplatePrompt| prompt.name == "title"))
+        .times(1)
+        .returning(|_| Ok("Hello World".to_string()));
+    prompter
+        .expect_ask()
+        .with(function(|prompt: &TemplatePrompt| prompt.name == "tags"))
+        .times(1)
+        .returning(|_| Ok("rust, cli,".to_string()));
+    prompter
+        .expect_ask()
+        .with(function(|prompt: &TemplatePrompt| prompt.name == "topic"))
+        .times(1)
+        .returning(|_| Ok(String::new()));
+
+    let options = NewOptions {
+        description: Some("Given on the command line".to_string()),
+        ..Default::default()
+    };
+    let prompts = content_prompts(None, &topics());
+    let options = prompt_for_options(&options, &prompts, &mut prompter).unwrap();
+
+    assert_eq!(options.title.as_deref(), Some("Hello World"));
+    assert_eq!(options.slug.as_deref(), Some("hello-world"));
+    assert_eq!(options.description.as_deref(), Some("Given on the command line"));
+    assert_eq!(options.tags, Some(vec!["rust".to_string(), "cli".to_string()]));
+    // An empty answer takes the default topic
+    assert_eq!(options.topic.as_deref(), Some("blog"));
+}
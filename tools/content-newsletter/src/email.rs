@@ -0,0 +1,343 @@
+//! # Email-safe HTML
+//!
+//! Email clients ignore or strip most of what a browser would run, so
+//! newsletters are rewritten before they are sent:
+//!
+//! * scripts, frames, embedded objects and forms are removed, along with
+//!   `on*` event attributes and `javascript:` links
+//! * rules in `<style>` elements are copied into the `style` attribute of
+//!   each element they match. Only type, class and id selectors (and
+//!   combinations like `p.note`) can be inlined; other rules, such as
+//!   `@media` queries and descendant selectors, are kept in a single
+//!   `<style>` element in the head for the clients that read it
+//! * relative `href`, `src`, `srcset` and `background` addresses are made
+//!   absolute. Merge tags such as `*|UNSUB|*` or `{{unsubscribe}}` are left
+//!   alone
+//!
+//! The rewriting works on the tags of well-formed HTML, like the templates
+//! and the HTML rendered from markdown, and is not a general HTML parser.
+
+use regex::Regex;
+use std::sync::OnceLock;
+use url::Url;
+
+/// Elements removed with their content
+const UNSAFE_ELEMENTS: &[&str] = &["script", "iframe", "frame", "object", "embed", "applet", "form"];
+
+/// Attributes holding an address
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "background", "poster"];
+
+/// Elements that styles are never inlined into
+const UNSTYLED_ELEMENTS: &[&str] = &["html", "head", "title", "meta", "link", "style", "base", "br"];
+
+/// Make rendered HTML safe to send by email, with relative addresses resolved against `base`
+pub fn email_safe(html: &str, base: &Url) -> String {
+    absolute_urls(&inline_styles(&strip_unsafe(html)), base)
+}
+
+/// Remove scripts, frames, objects and forms, event attributes and `javascript:` links
+pub fn strip_unsafe(html: &str) -> String {
+    static ELEMENTS: OnceLock<Vec<Regex>> = OnceLock::new();
+    let elements = ELEMENTS.get_or_init(|| {
+        UNSAFE_ELEMENTS
+            .iter()
+            .map(|name| {
+                Regex::new(&format!(r"(?is)<{0}\b[^>]*>.*?</{0}\s*>|<{0}\b[^>]*>", name)).expect("valid regex")
+            })
+            .collect()
+    });
+
+    let mut html = html.to_string();
+    for element in elements {
+        html = element.replace_all(&html, "").into_owned();
+    }
+
+    rewrite_tags(&html, |_, attributes| {
+        attributes.retain(|(name, value)| {
+            // Compared as bytes, since a character can straddle the end of the prefix
+            let is_event = name.len() > 2 && name.as_bytes()[..2].eq_ignore_ascii_case(b"on");
+            let is_script = value.as_deref().is_some_and(|value| {
+                let value = value.trim_start().as_bytes();
+                value.len() >= 11 && value[..11].eq_ignore_ascii_case(b"javascript:")
+            });
+            !is_event && !is_script
+        });
+    })
+}
+
+/// Copy the rules of `<style>` elements into the `style` attributes of the elements they match
+///
+/// Declarations already in a `style` attribute win over the rules; among the
+/// rules, more specific selectors win, then later ones.
+pub fn inline_styles(html: &str) -> String {
+    static STYLE: OnceLock<Regex> = OnceLock::new();
+    let style = STYLE.get_or_init(|| Regex::new(r"(?is)<style\b[^>]*>(.*?)</style\s*>").expect("valid regex"));
+
+    let mut rules = Vec::new();
+    let mut kept = String::new();
+    for block in style.captures_iter(html) {
+        let (inlined, rest) = parse_stylesheet(&block[1]);
+        rules.extend(inlined);
+        kept.push_str(&rest);
+    }
+    if rules.is_empty() && kept.is_empty() {
+        return html.to_string();
+    }
+
+    let stripped = style.replace_all(html, "");
+    let mut inlined = rewrite_tags(&stripped, |tag, attributes| {
+        if UNSTYLED_ELEMENTS.contains(&tag) {
+            return;
+        }
+        let mut matching: Vec<(usize, &Rule)> =
+            rules.iter().enumerate().filter(|(_, rule)| rule.selector.matches(tag, attributes)).collect();
+        if matching.is_empty() {
+            return;
+        }
+        matching.sort_by_key(|(order, rule)| (rule.selector.specificity(), *order));
+
+        let mut declarations = Vec::new();
+        for (_, rule) in matching {
+            merge_declarations(&mut declarations, &rule.declarations);
+        }
+        if let Some(index) = attributes.iter().position(|(name, _)| name.eq_ignore_ascii_case("style")) {
+            let (_, own) = attributes.remove(index);
+            merge_declarations(&mut declarations, &parse_declarations(own.as_deref().unwrap_or_default()));
+        }
+        let value = declarations.iter().map(|(property, value)| format!("{}: {}", property, value)).collect::<Vec<_>>();
+        attributes.push(("style".to_string(), Some(value.join("; "))));
+    });
+
+    let kept = kept.trim();
+    if !kept.is_empty() {
+        let element = format!("<style>\n{}\n</style>\n", kept);
+        match inlined.to_ascii_lowercase().find("</head>") {
+            Some(index) => inlined.insert_str(index, &element),
+            None => inlined.insert_str(0, &element),
+        }
+    }
+    inlined
+}
+
+/// Resolve relative addresses in `href`, `src`, `srcset` and `background` attributes against `base`
+pub fn absolute_urls(html: &str, base: &Url) -> String {
+    rewrite_tags(html, |_, attributes| {
+        for (name, value) in attributes.iter_mut() {
+            let Some(value) = value else {
+                continue;
+            };
+            let name = name.to_ascii_lowercase();
+            if URL_ATTRIBUTES.contains(&name.as_str()) {
+                if let Some(absolute) = absolute_url(value, base) {
+                    *value = absolute;
+                }
+            } else if name == "srcset" {
+                *value = value
+                    .split(',')
+                    .map(|candidate| {
+                        let candidate = candidate.trim();
+                        let (url, descriptor) = candidate.split_once(char::is_whitespace).unwrap_or((candidate, ""));
+                        let url = absolute_url(url, base).unwrap_or_else(|| url.to_string());
+                        format!("{} {}", url, descriptor.trim()).trim_end().to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+            }
+        }
+    })
+}
+
+/// The absolute form of a relative address, or `None` to leave it as it is
+fn absolute_url(value: &str, base: &Url) -> Option<String> {
+    let value = value.trim();
+    // Merge tags and placeholders start with punctuation of their own
+    let is_relative = value
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphanumeric() || matches!(first, '/' | '.' | '?' | '#'));
+    if !is_relative || Url::parse(value).is_ok() {
+        return None;
+    }
+    base.join(value).ok().map(String::from)
+}
+
+type Attributes = Vec<(String, Option<String>)>;
+
+/// Rewrite the attributes of every start tag
+///
+/// `rewrite` gets the lowercase tag name and the attributes in order, with
+/// values as written, entities and all. Tags it leaves alone are kept
+/// byte for byte.
+fn rewrite_tags(html: &str, mut rewrite: impl FnMut(&str, &mut Attributes)) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    static ATTRIBUTE: OnceLock<Regex> = OnceLock::new();
+    let tag = TAG.get_or_init(|| Regex::new(r#"<([a-zA-Z][a-zA-Z0-9-]*)((?:[^<>"']|"[^"]*"|'[^']*')*?)(/?)>"#).expect("valid regex"));
+    let attribute = ATTRIBUTE.get_or_init(|| {
+        Regex::new(r#"([^\s"'=<>/]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+)))?"#).expect("valid regex")
+    });
+
+    tag.replace_all(html, |captures: &regex::Captures| {
+        let name = captures[1].to_ascii_lowercase();
+        let original: Attributes = attribute
+            .captures_iter(&captures[2])
+            .map(|attribute| {
+                let value = attribute.get(2).or(attribute.get(3)).or(attribute.get(4));
+                (attribute[1].to_string(), value.map(|value| value.as_str().to_string()))
+            })
+            .collect();
+
+        let mut attributes = original.clone();
+        rewrite(&name, &mut attributes);
+        if attributes == original {
+            return captures[0].to_string();
+        }
+
+        let mut tag = format!("<{}", &captures[1]);
+        for (name, value) in &attributes {
+            match value {
+                Some(value) => tag.push_str(&format!(" {}=\"{}\"", name, value.replace('"', "&quot;"))),
+                None => tag.push_str(&format!(" {}", name)),
+            }
+        }
+        if !captures[3].is_empty() {
+            tag.push_str(" /");
+        }
+        tag.push('>');
+        tag
+    })
+    .into_owned()
+}
+
+/// A rule that can be inlined
+struct Rule {
+    selector: Selector,
+    declarations: Vec<(String, String)>,
+}
+
+/// A type, class or id selector, or a combination of them
+struct Selector {
+    tag: Option<String>,
+    classes: Vec<String>,
+    id: Option<String>,
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Option<Self> {
+        static SIMPLE: OnceLock<Regex> = OnceLock::new();
+        let simple = SIMPLE
+            .get_or_init(|| Regex::new(r"^([a-zA-Z][a-zA-Z0-9-]*|\*)?((?:[.#][a-zA-Z_-][a-zA-Z0-9_-]*)*)$").expect("valid regex"));
+        let captures = simple.captures(selector.trim())?;
+        if captures[0].is_empty() {
+            return None;
+        }
+
+        let mut parsed = Selector {
+            tag: captures.get(1).map(|tag| tag.as_str().to_ascii_lowercase()).filter(|tag| tag != "*"),
+            classes: Vec::new(),
+            id: None,
+        };
+        let parts = &captures[2];
+        let mut starts: Vec<usize> = parts.match_indices(['.', '#']).map(|(index, _)| index).collect();
+        starts.push(parts.len());
+        for bounds in starts.windows(2) {
+            let part = &parts[bounds[0]..bounds[1]];
+            if let Some(class) = part.strip_prefix('.') {
+                parsed.classes.push(class.to_string());
+            } else if parsed.id.replace(part[1..].to_string()).is_some() {
+                return None;
+            }
+        }
+        Some(parsed)
+    }
+
+    fn matches(&self, tag: &str, attributes: &Attributes) -> bool {
+        let attribute = |wanted: &str| {
+            attributes
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(wanted))
+                .and_then(|(_, value)| value.as_deref())
+        };
+        self.tag.as_deref().is_none_or(|wanted| wanted == tag)
+            && self.id.as_deref().is_none_or(|wanted| attribute("id") == Some(wanted))
+            && self.classes.iter().all(|wanted| {
+                attribute("class").is_some_and(|classes| classes.split_whitespace().any(|class| class == wanted))
+            })
+    }
+
+    fn specificity(&self) -> (usize, usize, usize) {
+        (usize::from(self.id.is_some()), self.classes.len(), usize::from(self.tag.is_some()))
+    }
+}
+
+/// Split a stylesheet into the rules that can be inlined and the CSS that has to stay
+fn parse_stylesheet(css: &str) -> (Vec<Rule>, String) {
+    static COMMENT: OnceLock<Regex> = OnceLock::new();
+    let comment = COMMENT.get_or_init(|| Regex::new(r"(?s)/\*.*?\*/").expect("valid regex"));
+    let css = comment.replace_all(css, "");
+
+    let mut rules = Vec::new();
+    let mut kept = String::new();
+    let mut rest = css.trim();
+
+    while let Some(open) = rest.find('{') {
+        let prelude = rest[..open].trim();
+
+        // At-rules like @media hold blocks of their own, so find the matching brace
+        let mut depth = 0;
+        let mut close = rest.len();
+        for (index, char) in rest[open..].char_indices() {
+            match char {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = open + index;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let body = &rest[open + 1..close.min(rest.len())];
+
+        let selectors: Option<Vec<Selector>> = if prelude.starts_with('@') {
+            None
+        } else {
+            prelude.split(',').map(Selector::parse).collect()
+        };
+        match selectors {
+            Some(selectors) => {
+                let declarations = parse_declarations(body);
+                rules.extend(selectors.into_iter().map(|selector| Rule {
+                    selector,
+                    declarations: declarations.clone(),
+                }));
+            }
+            None => {
+                kept.push_str(&format!("{} {{{}}}\n", prelude, body));
+            }
+        }
+
+        rest = rest.get(close + 1..).unwrap_or_default().trim_start();
+    }
+
+    (rules, kept)
+}
+
+fn parse_declarations(css: &str) -> Vec<(String, String)> {
+    css.split(';')
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let (property, value) = (property.trim().to_ascii_lowercase(), value.trim());
+            (!property.is_empty() && !value.is_empty()).then(|| (property, value.to_string()))
+        })
+        .collect()
+}
+
+/// Add declarations, replacing earlier values of the same properties
+fn merge_declarations(declarations: &mut Vec<(String, String)>, more: &[(String, String)]) {
+    for (property, value) in more {
+        declarations.retain(|(existing, _)| existing != property);
+        declarations.push((property.clone(), value.clone()));
+    }
+}
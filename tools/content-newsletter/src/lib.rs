@@ -0,0 +1,385 @@
+//! # Content Newsletter
+//!
+//! Renders the most recent published articles as a newsletter, in an HTML
+//! and a plain-text version, so a newsletter can be run off the same
+//! content as the site.
+//!
+//! Articles are processed like the build does, shortcodes and all, then
+//! rendered with Handlebars templates: the ones set in the `newsletter`
+//! section of the configuration, or the built-in ones. The HTML version is
+//! made safe to send by email (see [`email`]) and every address in either
+//! version is absolute, against `publication.site_url`.
+//!
+//! A rendered newsletter can be pushed to a webhook, which hands it to the
+//! mailing provider (see [`webhook`]).
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::{write_file_atomic, FsContentRepository};
+use common_models::{Article, Config};
+use common_traits::ContentRepository;
+use content_build::{ShortcodeRegistry, SiteContext};
+use handlebars::Handlebars;
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+pub mod email;
+pub mod webhook;
+
+/// Template used when `newsletter.html_template` is not set
+pub const DEFAULT_HTML_TEMPLATE: &str = include_str!("../templates/newsletter.html.hbs");
+
+/// Template used when `newsletter.text_template` is not set
+pub const DEFAULT_TEXT_TEMPLATE: &str = include_str!("../templates/newsletter.txt.hbs");
+
+/// Which articles go in the newsletter, and how
+#[derive(Debug, Clone, Default)]
+pub struct NewsletterOptions {
+    /// Number of articles, `newsletter.articles` when not set
+    pub articles: Option<usize>,
+    /// Only articles in this topic
+    pub topic: Option<String>,
+    /// Subject line; made from the article titles when not set
+    pub subject: Option<String>,
+    /// Include each article in full rather than its summary and a link
+    pub full_content: bool,
+}
+
+/// An article as templates see it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewsletterArticle {
+    pub title: String,
+    pub topic: String,
+    pub slug: String,
+    /// Absolute address of the article
+    pub url: String,
+    /// Publication date, as `YYYY-MM-DD`
+    pub date: Option<String>,
+    pub summary: Option<String>,
+    pub tags: Vec<String>,
+    pub reading_time: Option<u32>,
+    /// The article body as email-safe HTML
+    pub html: String,
+    /// The article body as plain text
+    pub text: String,
+}
+
+/// What templates are rendered with
+#[derive(Debug, Clone, Serialize)]
+struct TemplateContext<'a> {
+    title: &'a str,
+    site_url: &'a str,
+    subject: &'a str,
+    full_content: bool,
+    articles: &'a [NewsletterArticle],
+}
+
+/// A rendered newsletter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Newsletter {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+    pub articles: Vec<NewsletterArticle>,
+}
+
+/// What was rendered, and where it went
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NewsletterReport {
+    pub subject: String,
+    /// Titles and addresses of the articles
+    pub articles: Vec<(String, String)>,
+    /// Files the newsletter was written to
+    pub files: Vec<PathBuf>,
+    /// Status code the webhook answered with, when the newsletter was pushed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pushed: Option<u16>,
+}
+
+impl NewsletterReport {
+    pub fn new(newsletter: &Newsletter) -> Self {
+        Self {
+            subject: newsletter.subject.clone(),
+            articles: newsletter.articles.iter().map(|article| (article.title.clone(), article.url.clone())).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl DisplayResult for NewsletterReport {
+    fn to_display(&self) -> String {
+        let mut lines = vec![format!("{} {}", "Subject:".bold(), self.subject)];
+        for (title, url) in &self.articles {
+            lines.push(format!("  - {} {}", title, url.dimmed()));
+        }
+        for file in &self.files {
+            lines.push(format!("{} Wrote {}", "✓".green(), file.display()));
+        }
+        if let Some(code) = self.pushed {
+            lines.push(format!("{} Pushed to the webhook ({})", "✓".green(), code));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Render the most recent published articles as a newsletter
+///
+/// # Errors
+///
+/// Returns an error if the site URL is not configured, there are no
+/// published articles, or the content or templates cannot be read or rendered
+pub fn render_newsletter(config: &Config, options: &NewsletterOptions) -> Result<Newsletter> {
+    let site_url = config
+        .publication
+        .site_url
+        .as_deref()
+        .context("Set publication.site_url; addresses in newsletters must be absolute")?;
+    let site_url = site_url.trim_end_matches('/');
+    let base = Url::parse(&format!("{}/", site_url)).with_context(|| format!("Invalid site URL: {}", site_url))?;
+
+    let count = options.articles.unwrap_or_else(|| config.newsletter.articles());
+    let articles = recent_articles(config, options.topic.as_deref(), count)?;
+    if articles.is_empty() {
+        return Err(anyhow::anyhow!("No published articles to send"));
+    }
+
+    let articles: Vec<NewsletterArticle> = articles
+        .iter()
+        .map(|article| newsletter_article(article, site_url, &base))
+        .collect::<Result<_>>()?;
+    let subject = options.subject.clone().unwrap_or_else(|| default_subject(&articles));
+    let context = TemplateContext {
+        title: &config.title,
+        site_url,
+        subject: &subject,
+        full_content: options.full_content,
+        articles: &articles,
+    };
+
+    let html_template = load_template(config.newsletter.html_template.as_deref(), DEFAULT_HTML_TEMPLATE)?;
+    let mut handlebars = Handlebars::new();
+    common_templates::helpers::register_helpers(&mut handlebars);
+    let html = handlebars
+        .render_template(&html_template, &context)
+        .context("Failed to render the HTML newsletter")?;
+
+    let text_template = load_template(config.newsletter.text_template.as_deref(), DEFAULT_TEXT_TEMPLATE)?;
+    handlebars.register_escape_fn(handlebars::no_escape);
+    let text = handlebars
+        .render_template(&text_template, &context)
+        .context("Failed to render the plain-text newsletter")?;
+
+    Ok(Newsletter {
+        subject,
+        html: email::email_safe(&html, &base),
+        text: tidy_text(&text),
+        articles,
+    })
+}
+
+/// Write a newsletter to `newsletter.html` and `newsletter.txt` in `output_dir`
+///
+/// # Errors
+///
+/// Returns an error if the files cannot be written
+pub fn write_newsletter(newsletter: &Newsletter, output_dir: &Path) -> Result<Vec<PathBuf>> {
+    common_fs::create_dir_all(output_dir)?;
+    let html_path = output_dir.join("newsletter.html");
+    let text_path = output_dir.join("newsletter.txt");
+    write_file_atomic(&html_path, &newsletter.html)
+        .with_context(|| format!("Failed to write {}", html_path.display()))?;
+    write_file_atomic(&text_path, &newsletter.text)
+        .with_context(|| format!("Failed to write {}", text_path.display()))?;
+    Ok(vec![html_path, text_path])
+}
+
+/// The `count` most recently published articles, newest first
+///
+/// Drafts, archived articles, articles scheduled for later and articles
+/// without a publication date, or dated in the future, are left out.
+///
+/// # Errors
+///
+/// Returns an error if the content or the shortcode partials cannot be read,
+/// or a published article cannot be processed
+pub fn recent_articles(config: &Config, topic: Option<&str>, count: usize) -> Result<Vec<Article>> {
+    let site = SiteContext {
+        site_url: config.publication.site_url.clone(),
+        shortcodes: ShortcodeRegistry::load(config, Path::new("templates"))?,
+        i18n: config.i18n.clone(),
+        typography: config.typography.clone(),
+        link_policy: config.link_policy.clone(),
+        ..Default::default()
+    };
+    let repository = FsContentRepository::new(config);
+    let now = Utc::now();
+
+    let mut articles = Vec::new();
+    for topic_key in repository.list_topics()? {
+        if topic.is_some_and(|topic| topic != topic_key) {
+            continue;
+        }
+        for location in repository.list_articles(&topic_key)? {
+            let content = repository.read_article(&location)?;
+            let (frontmatter, _) = common_markdown::extract_frontmatter_and_content(&content)
+                .with_context(|| format!("Failed to read the frontmatter of {}", location.content_file.display()))?;
+            let published = frontmatter.published_at.as_ref().and_then(|date| date.date());
+            let unpublished = frontmatter.is_draft.unwrap_or(false)
+                || frontmatter.is_archived()
+                || frontmatter.is_scheduled_after(now);
+            if unpublished || published.is_none_or(|published| published > now.date_naive()) {
+                continue;
+            }
+
+            let mut article = content_build::process_content_for_site(&location.dir, false, &site)
+                .with_context(|| format!("Failed to process {}", location.content_file.display()))?;
+            article.topic = location.topic;
+            article.slug = location.slug;
+            articles.push(article);
+        }
+    }
+
+    articles.sort_by(|a, b| b.frontmatter.published_at.cmp(&a.frontmatter.published_at));
+    articles.truncate(count);
+    Ok(articles)
+}
+
+fn newsletter_article(article: &Article, site_url: &str, base: &Url) -> Result<NewsletterArticle> {
+    let url = content_build::canonical_url(article, site_url);
+    // Relative links in an article are relative to the article's page
+    let page = base.join(&format!("{}/{}", article.topic, article.slug))?;
+    let html = match &article.html {
+        Some(html) => html.clone(),
+        None => common_markdown::markdown_to_html(&article.content),
+    };
+
+    Ok(NewsletterArticle {
+        title: article.frontmatter.title.clone(),
+        topic: article.topic.clone(),
+        slug: article.slug.clone(),
+        url,
+        date: article
+            .frontmatter
+            .published_at
+            .as_ref()
+            .and_then(|date| date.date())
+            .map(|date| date.format("%Y-%m-%d").to_string()),
+        summary: article.summary.clone(),
+        tags: article.frontmatter.tags.clone().unwrap_or_default(),
+        reading_time: article.reading_time,
+        html: email::email_safe(&html, &page),
+        text: markdown_to_text(&article.content, &page),
+    })
+}
+
+fn default_subject(articles: &[NewsletterArticle]) -> String {
+    match articles {
+        [] => String::new(),
+        [article] => article.title.clone(),
+        [article, rest @ ..] => format!("{} and {} more", article.title, rest.len()),
+    }
+}
+
+fn load_template(path: Option<&str>, default: &str) -> Result<String> {
+    match path {
+        Some(path) => {
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read newsletter template {}", path))
+        }
+        None => Ok(default.to_string()),
+    }
+}
+
+/// Render markdown as plain text, with the address of each link after its text
+///
+/// Relative addresses are resolved against `base`.
+pub fn markdown_to_text(markdown: &str, base: &Url) -> String {
+    let mut text = String::new();
+    let mut links: Vec<String> = Vec::new();
+    let mut lists: Vec<Option<u64>> = Vec::new();
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Item) => {
+                text.push_str(&"  ".repeat(lists.len().saturating_sub(1)));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        text.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => text.push_str("- "),
+                }
+            }
+            Event::Start(Tag::List(start)) => {
+                // Top-level lists follow a blank line, nested ones start on a line of their own
+                let needs_break = if lists.is_empty() {
+                    !text.is_empty() && !text.ends_with("\n\n")
+                } else {
+                    !text.ends_with('\n')
+                };
+                if needs_break {
+                    text.push('\n');
+                }
+                lists.push(start);
+            }
+            Event::End(Tag::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    text.push('\n');
+                }
+            }
+            Event::Start(Tag::Link(_, destination, _)) | Event::Start(Tag::Image(_, destination, _)) => {
+                let destination = destination.to_string();
+                links.push(match Url::parse(&destination) {
+                    Ok(_) => destination,
+                    Err(_) => base.join(&destination).map(String::from).unwrap_or(destination),
+                });
+            }
+            Event::End(Tag::Link(..)) | Event::End(Tag::Image(..)) => {
+                if let Some(link) = links.pop() {
+                    if !link.starts_with("mailto:") && !text.ends_with(&link) {
+                        text.push_str(&format!(" ({})", link));
+                    }
+                }
+            }
+            Event::Text(value) | Event::Code(value) => text.push_str(&value),
+            Event::SoftBreak => text.push(' '),
+            Event::HardBreak => text.push('\n'),
+            Event::Rule => text.push_str("* * *\n\n"),
+            Event::End(Tag::Item) if !text.ends_with('\n') => text.push('\n'),
+            Event::End(Tag::Paragraph) | Event::End(Tag::Heading(..)) | Event::End(Tag::CodeBlock(_)) => {
+                if lists.is_empty() {
+                    text.push_str("\n\n");
+                } else if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tidy_text(&text)
+}
+
+/// Collapse runs of blank lines and trailing spaces left by templates
+fn tidy_text(text: &str) -> String {
+    let mut tidy = String::new();
+    let mut blank = 0;
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank += 1;
+            if blank > 1 {
+                continue;
+            }
+        } else {
+            blank = 0;
+        }
+        tidy.push_str(line);
+        tidy.push('\n');
+    }
+    format!("{}\n", tidy.trim())
+}
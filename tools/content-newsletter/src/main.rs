@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use common_cli::OutputFormatArgs;
+use content_newsletter::{render_newsletter, webhook, write_newsletter, NewsletterOptions, NewsletterReport};
+use std::path::PathBuf;
+
+/// Render recent articles as an email newsletter
+#[derive(Parser)]
+#[command(author, version, about = "Render recent articles as an email newsletter")]
+struct Cli {
+    /// Number of recent articles to include (default: newsletter.articles, or 5)
+    #[arg(short, long)]
+    articles: Option<usize>,
+
+    /// Only include articles in this topic
+    #[arg(short, long)]
+    topic: Option<String>,
+
+    /// Subject line (default: made from the article titles)
+    #[arg(long)]
+    subject: Option<String>,
+
+    /// Include each article in full rather than its summary and a link
+    #[arg(long)]
+    full_content: bool,
+
+    /// Directory to write newsletter.html and newsletter.txt to
+    #[arg(short, long, default_value = "public")]
+    output_dir: PathBuf,
+
+    /// Push the newsletter to the webhook in newsletter.webhook
+    #[arg(long)]
+    send: bool,
+
+    #[command(flatten)]
+    format: OutputFormatArgs,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let formatter = cli.format.formatter();
+    let config = common_config::load_config()?;
+
+    // Check before rendering, so a missing webhook doesn't waste a run
+    let webhook = if cli.send {
+        Some(config.newsletter.webhook.as_ref().context("Set newsletter.webhook to send newsletters")?)
+    } else {
+        None
+    };
+
+    let options = NewsletterOptions {
+        articles: cli.articles,
+        topic: cli.topic,
+        subject: cli.subject,
+        full_content: cli.full_content,
+    };
+    let newsletter = render_newsletter(&config, &options)?;
+
+    let mut report = NewsletterReport::new(&newsletter);
+    report.files = write_newsletter(&newsletter, &cli.output_dir)?;
+    if let Some(webhook) = webhook {
+        report.pushed = Some(webhook::push(webhook, &newsletter)?);
+    }
+
+    formatter.print(&report)
+}
@@ -0,0 +1,80 @@
+//! # Webhook
+//!
+//! Pushes a rendered newsletter to a mailing provider. The newsletter is
+//! posted as JSON to the configured URL, with the token, when there is one,
+//! as a bearer token:
+//!
+//! ```json
+//! {
+//!   "subject": "...",
+//!   "html": "...",
+//!   "text": "...",
+//!   "articles": [{ "title": "...", "url": "..." }]
+//! }
+//! ```
+//!
+//! Most providers take a campaign in a different shape, so the webhook is
+//! usually a small adapter, such as a serverless function, in front of the
+//! provider's API.
+
+use crate::Newsletter;
+use anyhow::{Context, Result};
+use common_models::WebhookConfig;
+use reqwest::blocking::Client;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    subject: &'a str,
+    html: &'a str,
+    text: &'a str,
+    articles: Vec<PayloadArticle<'a>>,
+}
+
+#[derive(Serialize)]
+struct PayloadArticle<'a> {
+    title: &'a str,
+    url: &'a str,
+}
+
+/// Post a newsletter to the webhook, returning the status code it answered with
+///
+/// # Errors
+///
+/// Returns an error if the webhook cannot be reached or does not accept the newsletter
+pub fn push(webhook: &WebhookConfig, newsletter: &Newsletter) -> Result<u16> {
+    let payload = Payload {
+        subject: &newsletter.subject,
+        html: &newsletter.html,
+        text: &newsletter.text,
+        articles: newsletter
+            .articles
+            .iter()
+            .map(|article| PayloadArticle {
+                title: &article.title,
+                url: &article.url,
+            })
+            .collect(),
+    };
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(concat!("content-newsletter/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let mut request = client.post(&webhook.url).json(&payload);
+    if let Some(token) = webhook.token() {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .with_context(|| format!("Failed to reach {}", webhook.url))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        let body = response.text().unwrap_or_default();
+        Err(anyhow::anyhow!("{} answered {}: {}", webhook.url, status, body.trim()))
+    }
+}
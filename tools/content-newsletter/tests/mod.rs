@@ -0,0 +1,7 @@
+//! Test modules for content-newsletter
+
+// Unit tests
+#[path = "unit/email_tests.rs"]
+mod email_tests;
+#[path = "unit/newsletter_tests.rs"]
+mod newsletter_tests;
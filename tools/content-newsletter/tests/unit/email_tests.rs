@@ -0,0 +1,67 @@
+use content_newsletter::email::{absolute_urls, email_safe, inline_styles, strip_unsafe};
+use url::Url;
+
+#[cfg(test)]
+mod email_tests {
+    use super::*;
+
+    fn base() -> Url {
+        Url::parse("https://example.com/blog/hello").unwrap()
+    }
+
+    #[test]
+    fn test_strip_unsafe_removes_scripts_frames_and_handlers() {
+        let html = r#"<p onclick="steal()">Hi</p><script>alert(1)</script><iframe src="x"></iframe><a href="javascript:void(0)" title="t">link</a><formula>kept</formula>"#;
+
+        assert_eq!(
+            strip_unsafe(html),
+            r#"<p>Hi</p><a title="t">link</a><formula>kept</formula>"#
+        );
+    }
+
+    #[test]
+    fn test_strip_unsafe_keeps_non_ascii_attributes() {
+        let html = r#"<p aé="1" title="javascriptü" data-ñ="x" onmouseöver="steal()">Hi</p>"#;
+
+        assert_eq!(strip_unsafe(html), r#"<p aé="1" title="javascriptü" data-ñ="x">Hi</p>"#);
+    }
+
+    #[test]
+    fn test_inline_styles_applies_rules_by_specificity() {
+        let html = concat!(
+            "<html><head><style>\n",
+            "p { color: black; margin: 0 }\n",
+            "p.note, #lead { color: red }\n",
+            "a:hover { color: blue }\n",
+            "@media (max-width: 600px) { p { margin: 4px } }\n",
+            "</style></head><body>",
+            "<p class=\"note\" style=\"margin: 2px\">One</p><p>Two</p><br/>",
+            "</body></html>"
+        );
+
+        let inlined = inline_styles(html);
+
+        assert!(inlined.contains(r#"<p class="note" style="color: red; margin: 2px">One</p>"#));
+        assert!(inlined.contains(r#"<p style="color: black; margin: 0">Two</p><br/>"#));
+        // Rules that cannot be inlined stay in the head
+        assert!(inlined.contains("<style>\na:hover { color: blue }\n@media (max-width: 600px) { p { margin: 4px } }\n</style>\n</head>"));
+        assert_eq!(inlined.matches("<style>").count(), 1);
+    }
+
+    #[test]
+    fn test_absolute_urls_resolves_relative_addresses() {
+        let html = r#"<a href="other">Other</a><a href="/about">About</a><img src="https://cdn.example.com/a.png" srcset="a.png 1x, /b.png 2x"><a href="*|UNSUB|*">Unsubscribe</a><a href="mailto:me@example.com">Mail</a>"#;
+
+        assert_eq!(
+            absolute_urls(html, &base()),
+            r#"<a href="https://example.com/blog/other">Other</a><a href="https://example.com/about">About</a><img src="https://cdn.example.com/a.png" srcset="https://example.com/blog/a.png 1x, https://example.com/b.png 2x"><a href="*|UNSUB|*">Unsubscribe</a><a href="mailto:me@example.com">Mail</a>"#
+        );
+    }
+
+    #[test]
+    fn test_email_safe_leaves_plain_html_alone() {
+        let html = "<h1>Title</h1>\n<p>Text with <code>&lt;script&gt;</code></p>\n";
+
+        assert_eq!(email_safe(html, &base()), html);
+    }
+}
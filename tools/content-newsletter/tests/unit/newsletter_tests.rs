@@ -0,0 +1,167 @@
+use common_models::{Config, WebhookConfig};
+use common_test_utils::write_article;
+use content_newsletter::{markdown_to_text, render_newsletter, webhook, write_newsletter, NewsletterOptions};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::thread;
+use tempfile::TempDir;
+use url::Url;
+
+#[cfg(test)]
+mod newsletter_tests {
+    use super::*;
+
+    fn config(base_dir: &Path, site: Option<&str>) -> Config {
+        let mut config = common_test_utils::config_for(base_dir, &["blog", "notes"]);
+        config.title = "Test Site".to_string();
+        config.publication.site_url = site.map(str::to_string);
+        config
+    }
+
+    fn fixture() -> (TempDir, Config) {
+        let temp = TempDir::new().unwrap();
+        let base = temp.path().join("content");
+        write_article(
+            &base,
+            "blog",
+            "newest",
+            "---\ntitle: Newest\npublished: 2024-03-01\ndescription: The newest post.\n---\nSee [the other post](older) and ![a chart](chart.png).\n\n<script>track()</script>\n",
+        );
+        write_article(&base, "notes", "older", "---\ntitle: Older\npublished: 2024-02-01\ncanonical: https://notes.example.com/older\n---\nAn older note.\n");
+        write_article(&base, "blog", "oldest", "---\ntitle: Oldest\npublished: 2023-01-01\n---\nThe oldest post.\n");
+        write_article(&base, "blog", "draft", "---\ntitle: Draft\npublished: 2024-04-01\ndraft: true\n---\nNot yet.\n");
+        write_article(&base, "blog", "later", "---\ntitle: Later\npublished: 2099-01-01\n---\nScheduled.\n");
+        let config = config(&base, Some("https://example.com/"));
+        (temp, config)
+    }
+
+    #[test]
+    fn test_render_newsletter_with_recent_published_articles() {
+        let (temp, config) = fixture();
+        let options = NewsletterOptions {
+            articles: Some(2),
+            ..Default::default()
+        };
+
+        let newsletter = render_newsletter(&config, &options).unwrap();
+
+        let titles: Vec<&str> = newsletter.articles.iter().map(|article| article.title.as_str()).collect();
+        assert_eq!(titles, ["Newest", "Older"]);
+        assert_eq!(newsletter.subject, "Newest and 1 more");
+        assert_eq!(newsletter.articles[1].url, "https://notes.example.com/older");
+
+        // Summaries and links, with styles inlined and no scripts
+        assert!(newsletter.html.contains(r#"<a class="title" href="https://example.com/blog/newest" style="color: #222222; text-decoration: none">Newest</a>"#));
+        assert!(newsletter.html.contains("The newest post."));
+        assert!(newsletter.html.contains("March 1, 2024"));
+        assert!(!newsletter.html.contains("track()"));
+        assert!(!newsletter.html.contains("<style>"));
+
+        assert!(newsletter.text.starts_with("Test Site\nhttps://example.com\n\nNEWEST\nMarch 1, 2024\nThe newest post.\nRead more: https://example.com/blog/newest\n"));
+        assert!(!newsletter.text.contains("&"));
+
+        let files = write_newsletter(&newsletter, &temp.path().join("public")).unwrap();
+        assert_eq!(fs::read_to_string(&files[0]).unwrap(), newsletter.html);
+        assert_eq!(fs::read_to_string(&files[1]).unwrap(), newsletter.text);
+    }
+
+    #[test]
+    fn test_render_newsletter_with_full_content_and_custom_template() {
+        let (temp, mut config) = fixture();
+        let template = temp.path().join("newsletter.html.hbs");
+        fs::write(&template, "<style>p { margin: 0 }</style>{{#each articles}}<h2>{{title}}</h2>{{{html}}}{{/each}}").unwrap();
+        config.newsletter.html_template = Some(template.display().to_string());
+        let options = NewsletterOptions {
+            articles: Some(1),
+            topic: Some("blog".to_string()),
+            subject: Some("This week".to_string()),
+            full_content: true,
+        };
+
+        let newsletter = render_newsletter(&config, &options).unwrap();
+
+        assert_eq!(newsletter.subject, "This week");
+        assert!(newsletter.html.starts_with("<h2>Newest</h2><p style=\"margin: 0\">See <a href=\"https://example.com/blog/older\">the other post</a>"));
+        assert!(newsletter.html.contains(r#"src="https://example.com/images/blog/newest/chart.png""#));
+        assert!(newsletter.text.contains("See the other post (https://example.com/blog/older) and a chart (https://example.com/blog/chart.png)."));
+    }
+
+    #[test]
+    fn test_render_newsletter_reports_unreadable_articles() {
+        let (temp, config) = fixture();
+        write_article(&temp.path().join("content"), "blog", "broken", "---\ntitle: [Broken\n---\nBody.\n");
+
+        let err = render_newsletter(&config, &NewsletterOptions::default()).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("broken.md"), "{:#}", err);
+    }
+
+    #[test]
+    fn test_render_newsletter_requires_site_url() {
+        let (temp, _) = fixture();
+        let config = config(&temp.path().join("content"), None);
+
+        let err = render_newsletter(&config, &NewsletterOptions::default()).unwrap_err();
+
+        assert!(err.to_string().contains("publication.site_url"));
+    }
+
+    #[test]
+    fn test_markdown_to_text() {
+        let base = Url::parse("https://example.com/blog/post").unwrap();
+        let markdown = "# Heading\n\nSome *text* with [a link](/about) and [https://example.com](https://example.com).\n\n1. One\n2. Two\n   - Nested\n\n---\n\n```\ncode\n```\n";
+
+        assert_eq!(
+            markdown_to_text(markdown, &base),
+            "Heading\n\nSome text with a link (https://example.com/about) and https://example.com.\n\n1. One\n2. Two\n  - Nested\n\n* * *\n\ncode\n"
+        );
+    }
+
+    #[test]
+    fn test_push_posts_json_with_bearer_token() {
+        let (_temp, config) = fixture();
+        let newsletter = render_newsletter(&config, &NewsletterOptions::default()).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = Vec::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                head.push(line.trim().to_string());
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            write!(stream, "HTTP/1.1 201 Created\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").unwrap();
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let webhook_config = WebhookConfig {
+            url,
+            token: Some("secret".to_string()),
+        };
+        assert_eq!(webhook::push(&webhook_config, &newsletter).unwrap(), 201);
+
+        let (head, body) = server.join().unwrap();
+        assert_eq!(head[0], "POST /hook HTTP/1.1");
+        assert!(head.iter().any(|line| line.eq_ignore_ascii_case("authorization: Bearer secret")));
+        let payload: serde_yaml::Value = serde_yaml::from_str(&body).unwrap();
+        assert_eq!(payload["subject"].as_str(), Some(newsletter.subject.as_str()));
+        assert_eq!(payload["articles"][0]["url"].as_str(), Some("https://example.com/blog/newest"));
+        assert_eq!(payload["text"].as_str(), Some(newsletter.text.as_str()));
+    }
+}
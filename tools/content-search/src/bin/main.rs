@@ -1,7 +1,9 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
-use content_search::{SearchOptions, build_index, search_content, index_exists};
+use common_cli::OutputFormatArgs;
+use common_logging::LogArgs;
+use content_search::{HighlightOptions, ScoringOptions, SearchOptions, SearchReport, build_index, search_content, index_exists};
 use std::path::PathBuf;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
@@ -21,11 +23,34 @@ impl ContentType {
     }
 }
 
+/// Highlight options that render matches in bold yellow when the terminal supports color
+fn terminal_highlight_options() -> HighlightOptions {
+    if colored::control::SHOULD_COLORIZE.should_colorize() {
+        HighlightOptions {
+            pre_tag: "\u{1b}[1;33m".to_string(),
+            post_tag: "\u{1b}[0m".to_string(),
+            ..HighlightOptions::default()
+        }
+    } else {
+        HighlightOptions {
+            pre_tag: "[".to_string(),
+            post_tag: "]".to_string(),
+            ..HighlightOptions::default()
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(author, version, about = "Search content with full-text and metadata queries")]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    #[command(flatten)]
+    log: LogArgs,
+
+    #[command(flatten)]
+    output: OutputFormatArgs,
 }
 
 #[derive(Subcommand)]
@@ -45,7 +70,7 @@ enum Commands {
         content_type: Option<ContentType>,
         
         /// Limit search to content with these tags (comma-separated)
-        #[arg(short, long)]
+        #[arg(long)]
         tags: Option<String>,
         
         /// Maximum number of results to return
@@ -56,6 +81,10 @@ enum Commands {
         #[arg(long, default_value = "false")]
         include_drafts: bool,
         
+        /// Include archived content
+        #[arg(long, default_value = "false")]
+        include_archived: bool,
+        
         /// Search only in titles
         #[arg(long, default_value = "false")]
         title_only: bool,
@@ -83,6 +112,8 @@ enum Commands {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    cli.log.init()?;
+    let formatter = cli.output.formatter();
     
     match cli.command {
         Commands::Search {
@@ -92,6 +123,7 @@ fn main() -> Result<()> {
             tags,
             limit,
             include_drafts,
+            include_archived,
             title_only,
             index_path,
             rebuild,
@@ -115,10 +147,14 @@ fn main() -> Result<()> {
                 tags: tags_vec,
                 limit,
                 include_drafts,
+                include_archived,
                 title_only,
                 raw_query: false,
                 case_sensitive: false,
                 include_metadata: true,
+                // Structured output keeps the default markers rather than terminal escapes
+                highlight: if formatter.is_plain() { terminal_highlight_options() } else { HighlightOptions::default() },
+                scoring: ScoringOptions::default(),
             };
             
             // Get index path
@@ -126,77 +162,60 @@ fn main() -> Result<()> {
             
             // Check if we need to build/rebuild the index
             if rebuild || (index_path_ref.is_some() && !index_exists(index_path_ref.unwrap())) {
-                println!("{} search index...", "Building".green().bold());
+                if formatter.is_plain() {
+                    println!("{} search index...", "Building".green().bold());
+                }
                 build_index(index_path_ref, include_drafts)?;
             }
             
-            // Perform the search
-            println!("{} for: {}", "Searching".green().bold(), query.yellow());
-            
-            if let Some(topic) = &topic {
-                println!("  {} {}", "Topic:".cyan().bold(), topic);
-            }
-            
-            if let Some(ct) = &content_type {
-                println!("  {} {:?}", "Content type:".cyan().bold(), ct);
-            }
-            
-            if let Some(t) = &tags {
-                println!("  {} {}", "Tags:".cyan().bold(), t);
+            // Progress messages would corrupt structured output
+            if formatter.is_plain() {
+                println!("{} for: {}", "Searching".green().bold(), query.yellow());
+                
+                if let Some(topic) = &topic {
+                    println!("  {} {}", "Topic:".cyan().bold(), topic);
+                }
+                
+                if let Some(ct) = &content_type {
+                    println!("  {} {:?}", "Content type:".cyan().bold(), ct);
+                }
+                
+                if let Some(t) = &tags {
+                    println!("  {} {}", "Tags:".cyan().bold(), t);
+                }
             }
             
             // Search
             match search_content(&options) {
-                Ok(results) => {
-                    println!("\n{} {} results found", "Success:".green().bold(), results.len());
-                    
-                    for (i, result) in results.iter().enumerate() {
-                        println!("\n{}. {} ({})", 
-                            (i + 1).to_string().bold(), 
-                            result.title.bold(), 
-                            format!("score: {:.2}", result.score).cyan()
-                        );
-                        
-                        println!("   Topic: {}, Type: {}", result.topic, result.content_type);
-                        
-                        if !result.tags.is_empty() {
-                            println!("   Tags: {}", result.tags.join(", ").cyan());
-                        }
-                        
-                        if let Some(date) = &result.date {
-                            println!("   Date: {}", date);
-                        }
-                        
-                        println!("   {}", result.content);
-                        println!("   Path: {}", result.path.cyan());
-                    }
-                },
-                Err(e) => {
+                Ok(results) => formatter.print(&SearchReport::new(query, results))?,
+                Err(e) if formatter.is_plain() => {
                     println!("{} {}", "Error:".red().bold(), e);
                     
-                    if e.to_string().contains("No search results found") {
-                        println!("\nNo results found for the query '{}'.", query);
-                        println!("Try using different search terms, or removing filters.");
-                    } else if e.to_string().contains("Search index not found") {
+                    if e.to_string().contains("Search index not found") {
                         println!("\nThe search index was not found. Try building it first:");
                         println!("  writing search build");
                     }
                 }
+                Err(e) => return Err(e),
             }
         },
         
         Commands::Build { index_path, include_drafts } => {
-            println!("{} search index...", "Building".green().bold());
-            
-            let index_path_str = index_path.as_ref().map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|| "build/search_index".to_string());
+            if formatter.is_plain() {
+                println!("{} search index...", "Building".green().bold());
                 
-            println!("  {} {}", "Index path:".cyan().bold(), index_path_str);
-            println!("  {} {}", "Include drafts:".cyan().bold(), include_drafts);
+                let index_path_str = index_path.as_ref().map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "build/search_index".to_string());
+                    
+                println!("  {} {}", "Index path:".cyan().bold(), index_path_str);
+                println!("  {} {}", "Include drafts:".cyan().bold(), include_drafts);
+            }
             
             build_index(index_path.as_deref(), include_drafts)?;
             
-            println!("{} Search index built successfully", "Success:".green().bold());
+            if formatter.is_plain() {
+                println!("{} Search index built successfully", "Success:".green().bold());
+            }
         }
     }
     
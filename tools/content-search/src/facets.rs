@@ -0,0 +1,69 @@
+//! Facet counts for search results
+//!
+//! Facets summarise the full (unlimited) result set by topic, tag, year and
+//! month so that a caller can render drill-down filters from a single query.
+
+use crate::scoring::parse_date;
+use crate::SearchResult;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Result counts grouped by topic, tag and publication date
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchFacets {
+    /// Number of results per topic key
+    pub topics: BTreeMap<String, usize>,
+
+    /// Number of results per tag (lowercased)
+    pub tags: BTreeMap<String, usize>,
+
+    /// Number of results per publication year (`YYYY`)
+    pub years: BTreeMap<String, usize>,
+
+    /// Number of results per publication month (`YYYY-MM`)
+    pub months: BTreeMap<String, usize>,
+}
+
+impl SearchFacets {
+    /// Count the facets of a set of search results
+    ///
+    /// Results without a parseable date are not counted in the year and
+    /// month facets. Each tag is counted at most once per result.
+    pub fn from_results(results: &[SearchResult]) -> Self {
+        let mut facets = SearchFacets::default();
+
+        for result in results {
+            if !result.topic.is_empty() {
+                *facets.topics.entry(result.topic.clone()).or_insert(0) += 1;
+            }
+
+            let mut tags: Vec<String> = result.tags.iter().map(|tag| tag.to_lowercase()).collect();
+            tags.sort();
+            tags.dedup();
+            for tag in tags {
+                *facets.tags.entry(tag).or_insert(0) += 1;
+            }
+
+            if let Some(date) = result.date.as_deref().and_then(parse_date) {
+                *facets.years.entry(format!("{:04}", date.year())).or_insert(0) += 1;
+                *facets.months.entry(format!("{:04}-{:02}", date.year(), date.month())).or_insert(0) += 1;
+            }
+        }
+
+        facets
+    }
+}
+
+/// Search results together with facet counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacetedSearchResults {
+    /// Ranked results, truncated to the requested limit
+    pub results: Vec<SearchResult>,
+
+    /// Total number of matching documents before the limit was applied
+    pub total: usize,
+
+    /// Facet counts over all matching documents
+    pub facets: SearchFacets,
+}
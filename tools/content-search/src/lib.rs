@@ -1,12 +1,24 @@
 use anyhow::Result;
 use thiserror::Error;
 use common_config::load_config;
-use common_fs::read_file;
-use common_models::Config;
+use common_fs::FsContentRepository;
+use common_markdown::extract_frontmatter;
+use common_models::{Config, Frontmatter};
+use common_traits::{CancellationToken, ContentRepository};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use pulldown_cmark::{Parser, Event, Options};
+use regex::RegexBuilder;
+
+pub mod facets;
+pub mod report;
+pub mod scoring;
+
+pub use facets::{FacetedSearchResults, SearchFacets};
+pub use report::SearchReport;
+pub use scoring::ScoringOptions;
+use scoring::{CorpusStats, DocumentTerms};
 
 /// Error types for content search
 #[derive(Error, Debug)]
@@ -59,6 +71,49 @@ pub struct SearchResult {
     
     /// Search score
     pub score: f32,
+    
+    /// Every matched span in the document content
+    pub highlights: Vec<Highlight>,
+}
+
+/// A single matched span within a search result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Highlight {
+    /// Byte offset where the match starts in the document text
+    pub start: usize,
+    
+    /// Byte offset where the match ends in the document text
+    pub end: usize,
+    
+    /// Text surrounding the match, with the match wrapped in the configured markers
+    pub excerpt: String,
+}
+
+/// Options controlling how matches are highlighted
+#[derive(Debug, Clone)]
+pub struct HighlightOptions {
+    /// Marker inserted before each match
+    pub pre_tag: String,
+    
+    /// Marker inserted after each match
+    pub post_tag: String,
+    
+    /// Number of characters of context to include on each side of a match
+    pub context_size: usize,
+    
+    /// Maximum number of highlights to return per result (0 for no limit)
+    pub max_highlights: usize,
+}
+
+impl Default for HighlightOptions {
+    fn default() -> Self {
+        Self {
+            pre_tag: "<mark>".to_string(),
+            post_tag: "</mark>".to_string(),
+            context_size: 40,
+            max_highlights: 0,
+        }
+    }
 }
 
 /// Search options for content
@@ -82,6 +137,9 @@ pub struct SearchOptions {
     /// Whether to include draft content
     pub include_drafts: bool,
     
+    /// Whether to include archived content
+    pub include_archived: bool,
+    
     /// Search only in titles
     pub title_only: bool,
     
@@ -93,6 +151,12 @@ pub struct SearchOptions {
     
     /// Whether to include metadata in search
     pub include_metadata: bool,
+    
+    /// How matches are highlighted in results
+    pub highlight: HighlightOptions,
+    
+    /// How results are scored and ranked
+    pub scoring: ScoringOptions,
 }
 
 impl Default for SearchOptions {
@@ -104,10 +168,13 @@ impl Default for SearchOptions {
             tags: None,
             limit: 50,
             include_drafts: false,
+            include_archived: false,
             title_only: false,
             raw_query: false,
             case_sensitive: false,
             include_metadata: false,
+            highlight: HighlightOptions::default(),
+            scoring: ScoringOptions::default(),
         }
     }
 }
@@ -192,6 +259,100 @@ fn extract_metadata_and_content(content: &str) -> (String, HashMap<String, Strin
     (title, metadata, plain_text)
 }
 
+/// Frontmatter fields used to filter documents before text matching
+#[derive(Debug, Clone, Default)]
+struct DocumentFilterFields {
+    /// Parsed frontmatter, if the document has valid frontmatter
+    frontmatter: Option<Frontmatter>,
+    
+    /// The document's content type (`type` or `content_type` frontmatter key)
+    content_type: Option<String>,
+}
+
+impl DocumentFilterFields {
+    /// Parse the filterable fields from a document's frontmatter
+    fn parse(content: &str) -> Self {
+        let value = match extract_frontmatter(content) {
+            Ok((value, _)) => value,
+            Err(_) => return Self::default(),
+        };
+        
+        let content_type = ["type", "content_type"]
+            .iter()
+            .find_map(|key| value.get(*key).and_then(|v| v.as_str()))
+            .map(|s| s.to_string());
+        
+        Self {
+            frontmatter: serde_yaml::from_value(value).ok(),
+            content_type,
+        }
+    }
+    
+    /// The document's tags
+    fn tags(&self) -> Vec<String> {
+        self.frontmatter
+            .as_ref()
+            .and_then(|fm| fm.tags.clone())
+            .unwrap_or_default()
+    }
+    
+    /// Whether the document passes the draft, archive, content type and tag filters
+    fn matches(&self, options: &SearchOptions) -> bool {
+        let is_draft = self.frontmatter
+            .as_ref()
+            .and_then(|fm| fm.is_draft)
+            .unwrap_or(false);
+        if is_draft && !options.include_drafts {
+            return false;
+        }
+        
+        let is_archived = self.frontmatter
+            .as_ref()
+            .is_some_and(|fm| fm.is_archived());
+        if is_archived && !options.include_archived {
+            return false;
+        }
+        
+        if let Some(wanted) = &options.content_type {
+            match &self.content_type {
+                Some(content_type) if content_type.eq_ignore_ascii_case(wanted) => {}
+                _ => return false,
+            }
+        }
+        
+        if let Some(wanted_tags) = &options.tags {
+            let tags = self.tags();
+            let has_all = wanted_tags
+                .iter()
+                .all(|wanted| tags.iter().any(|tag| tag.eq_ignore_ascii_case(wanted)));
+            if !has_all {
+                return false;
+            }
+        }
+        
+        true
+    }
+}
+
+/// Check whether a document passes the draft, archive, content type and tag filters
+///
+/// Drafts are excluded unless `include_drafts` is set, archived content is
+/// excluded unless `include_archived` is set, `content_type` must
+/// match the document's `type` frontmatter field, and every requested tag
+/// must be present. Comparisons are case-insensitive.
+///
+/// # Parameters
+///
+/// * `content` - The raw document, including frontmatter
+/// * `options` - Search options providing the filters
+///
+/// # Returns
+///
+/// Returns `true` if the document should be searched
+pub fn matches_filters(content: &str, options: &SearchOptions) -> bool {
+    DocumentFilterFields::parse(content).matches(options)
+}
+
 /// Create an excerpt around the first occurrence of query terms
 fn create_excerpt(text: &str, query: &str, max_length: usize) -> String {
     // If query is empty, just return the beginning of the text
@@ -250,6 +411,104 @@ fn create_excerpt(text: &str, query: &str, max_length: usize) -> String {
     }
 }
 
+/// Find every occurrence of the query terms in text
+///
+/// Each query word is matched independently. Matches are returned in document
+/// order with byte offsets into `text` and an excerpt in which the match is
+/// wrapped in `options.pre_tag` and `options.post_tag`.
+///
+/// # Parameters
+///
+/// * `text` - The text to search
+/// * `query` - The search query
+/// * `case_sensitive` - Whether matching is case sensitive
+/// * `options` - Highlight options
+///
+/// # Returns
+///
+/// Returns the list of highlights, empty if nothing matched
+pub fn find_highlights(
+    text: &str,
+    query: &str,
+    case_sensitive: bool,
+    options: &HighlightOptions,
+) -> Vec<Highlight> {
+    let mut words: Vec<&str> = query.split_whitespace().collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+    
+    // Prefer longer words so overlapping terms highlight the widest match
+    words.sort_by(|a, b| b.len().cmp(&a.len()));
+    let pattern = words
+        .iter()
+        .map(|word| regex::escape(word))
+        .collect::<Vec<_>>()
+        .join("|");
+    
+    let re = match RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+    {
+        Ok(re) => re,
+        Err(_) => return Vec::new(),
+    };
+    
+    let mut highlights = Vec::new();
+    for m in re.find_iter(text) {
+        if options.max_highlights > 0 && highlights.len() >= options.max_highlights {
+            break;
+        }
+        
+        let start = context_start(text, m.start(), options.context_size);
+        let end = context_end(text, m.end(), options.context_size);
+        
+        let mut excerpt = String::new();
+        if start > 0 {
+            excerpt.push_str("...");
+        }
+        excerpt.push_str(&text[start..m.start()]);
+        excerpt.push_str(&options.pre_tag);
+        excerpt.push_str(m.as_str());
+        excerpt.push_str(&options.post_tag);
+        excerpt.push_str(&text[m.end()..end]);
+        if end < text.len() {
+            excerpt.push_str("...");
+        }
+        
+        highlights.push(Highlight {
+            start: m.start(),
+            end: m.end(),
+            excerpt,
+        });
+    }
+    
+    highlights
+}
+
+/// Byte offset `chars` characters before `pos`, on a character boundary
+fn context_start(text: &str, pos: usize, chars: usize) -> usize {
+    if chars == 0 {
+        return pos;
+    }
+    
+    text[..pos]
+        .char_indices()
+        .rev()
+        .nth(chars - 1)
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Byte offset `chars` characters after `pos`, on a character boundary
+fn context_end(text: &str, pos: usize, chars: usize) -> usize {
+    text[pos..]
+        .char_indices()
+        .nth(chars)
+        .map(|(i, _)| pos + i)
+        .unwrap_or(text.len())
+}
+
 /// Search for a query in text content
 fn search_in_text(text: &str, query: &str, case_sensitive: bool) -> bool {
     if case_sensitive {
@@ -270,30 +529,27 @@ fn search_in_metadata(metadata: &HashMap<String, String>, query: &str, case_sens
 }
 
 /// Find all content files in the workspace
-fn find_content_files(config: &Config, include_drafts: bool) -> Result<Vec<PathBuf>> {
+fn find_content_files(
+    config: &Config,
+    include_drafts: bool,
+    token: &CancellationToken,
+) -> Result<Vec<PathBuf>> {
+    let repository = FsContentRepository::new(config);
     let mut files = Vec::new();
     
-    for (_, topic_config) in &config.content.topics {
-        let topic_dir = PathBuf::from(format!("{}/{}", config.content.base_dir, topic_config.directory));
-        
-        // Find all markdown files in this topic
-        let markdown_files = common_fs::find_files_with_extension(&topic_dir, "md")?;
-        
-        for file in markdown_files {
-            // Skip files that aren't index.md (if we're using that convention)
-            if file.file_name().unwrap_or_default() != "index.md" {
-                continue;
-            }
-            
+    for topic_key in repository.list_topics()? {
+        token.check("search indexing")?;
+
+        for article in repository.list_articles(&topic_key)? {
             // Skip drafts if not including them
             if !include_drafts {
-                let content = read_file(&file)?;
+                let content = repository.read_article(&article)?;
                 if content.contains("draft: true") {
                     continue;
                 }
             }
             
-            files.push(file);
+            files.push(article.content_file);
         }
     }
     
@@ -307,10 +563,20 @@ pub fn index_exists(index_path: &Path) -> bool {
 
 /// Build a new search index for the content
 pub fn build_index(index_path: Option<&Path>, include_drafts: bool) -> Result<()> {
+    build_index_cancellable(index_path, include_drafts, &CancellationToken::new())
+}
+
+/// Build a new search index, stopping between topics if `token` is cancelled
+pub fn build_index_cancellable(
+    index_path: Option<&Path>,
+    include_drafts: bool,
+    token: &CancellationToken,
+) -> Result<()> {
+    let _span = tracing::info_span!("index").entered();
     let config = load_config()?;
     
     // Find all content files
-    let content_files = find_content_files(&config, include_drafts)?;
+    let content_files = find_content_files(&config, include_drafts, token)?;
     
     if content_files.is_empty() {
         return Err(anyhow::anyhow!("No content files found to index"));
@@ -336,34 +602,57 @@ pub fn build_index(index_path: Option<&Path>, include_drafts: bool) -> Result<()
 ///
 /// # Parameters
 ///
-/// * `topic_dir` - Path to the topic directory
+/// * `repository` - Content to search
+/// * `topic_key` - Key of the topic being searched
 /// * `query` - Search query
 /// * `options` - Search options
+/// * `corpus` - Corpus statistics, updated with every document scanned
 ///
 /// # Returns
 ///
-/// Returns a list of search results
+/// Returns a list of unscored search results with their term statistics
 ///
 /// # Errors
 ///
 /// Returns an error if the search fails
-fn search_topic(topic_dir: &Path, query: &str, options: &SearchOptions) -> Result<Vec<SearchResult>> {
+fn search_topic(
+    repository: &impl ContentRepository,
+    topic_key: &str,
+    query: &str,
+    options: &SearchOptions,
+    corpus: &mut CorpusStats,
+) -> Result<Vec<(SearchResult, DocumentTerms)>> {
     let mut results = Vec::new();
+    let query_terms = scoring::tokenize(query);
     
-    // Find all markdown files in the topic directory
-    let markdown_files = common_fs::find_files_with_extension(topic_dir, "md")?;
-    
-    for file_path in markdown_files {
-        // Only process index.md files
-        if file_path.file_name().unwrap_or_default() != "index.md" {
+    for article in repository.list_articles(topic_key)? {
+        // Read the file content
+        let content = repository.read_article(&article)?;
+        let file_path = article.content_file;
+        
+        // Apply draft, archive, content type and tag filters before any text matching
+        let fields = DocumentFilterFields::parse(&content);
+        if !fields.matches(options) {
             continue;
         }
         
-        // Read the file content
-        let content = common_fs::read_file(&file_path)?;
-        
         // Extract metadata and content
-        let (_title, metadata, content_text) = extract_metadata_and_content(&content);
+        let (title, metadata, content_text) = extract_metadata_and_content(&content);
+        let tags = match &fields.frontmatter {
+            Some(_) => fields.tags(),
+            None => metadata.get("tags")
+                .cloned()
+                .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default(),
+        };
+        let title = fields.frontmatter
+            .as_ref()
+            .map(|fm| fm.title.clone())
+            .unwrap_or(title);
+        
+        // Every scanned document contributes to the corpus statistics
+        let terms = DocumentTerms::new(&query_terms, &content_text, &title, &tags);
+        corpus.add_document(&terms);
         
         // Search in content
         let content_matches = search_in_text(&content_text, query, options.case_sensitive);
@@ -377,23 +666,70 @@ fn search_topic(topic_dir: &Path, query: &str, options: &SearchOptions) -> Resul
         
         // If we found matches, add to results
         if content_matches || metadata_matches {
-            results.push(SearchResult {
-                title: metadata.get("title").cloned().unwrap_or_default(),
-                slug: file_path.file_name().unwrap_or_default().to_string_lossy().to_string(),
-                topic: file_path.parent().unwrap().file_name().unwrap().to_string_lossy().to_string(),
+            results.push((SearchResult {
+                title,
+                slug: file_path
+                    .parent()
+                    .and_then(|dir| dir.file_name())
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                topic: topic_key.to_string(),
                 path: file_path.to_string_lossy().to_string(),
                 content: create_excerpt(&content_text, query, 160),
-                tags: metadata.get("tags")
-                    .cloned()
-                    .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
-                    .unwrap_or_default(),
-                content_type: metadata.get("type").cloned().unwrap_or_default(),
-                date: metadata.get("date").cloned(),
+                tags,
+                content_type: fields.content_type.clone().unwrap_or_default(),
+                date: fields.frontmatter
+                    .as_ref()
+                    .and_then(|fm| fm.published_at.as_ref().map(ToString::to_string))
+                    .or_else(|| metadata.get("date").cloned()),
                 score: 0.0,
-            });
+                highlights: find_highlights(&content_text, query, options.case_sensitive, &options.highlight),
+            }, terms));
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Find and rank every document matching the options, without applying the limit
+fn ranked_results(options: &SearchOptions) -> Result<Vec<SearchResult>> {
+    let config = load_config()?;
+    ranked_results_in(&FsContentRepository::new(&config), options)
+}
+
+/// Find and rank every document in `repository` matching the options
+fn ranked_results_in(repository: &impl ContentRepository, options: &SearchOptions) -> Result<Vec<SearchResult>> {
+    let mut candidates = Vec::new();
+    let mut corpus = CorpusStats::default();
+    
+    // If topic is provided, search only in that topic
+    if let Some(topic) = &options.topic {
+        if repository.list_topics()?.contains(topic) {
+            let topic_results = search_topic(repository, topic, &options.query, options, &mut corpus)?;
+            candidates.extend(topic_results);
+        } else {
+            return Err(SearchError::TopicNotFound(topic.clone()).into());
+        }
+    } else {
+        // Search in all topics
+        for topic_key in repository.list_topics()? {
+            let topic_results = search_topic(repository, &topic_key, &options.query, options, &mut corpus)?;
+            candidates.extend(topic_results);
         }
     }
     
+    // Score against the whole corpus and rank
+    let mut results: Vec<SearchResult> = candidates
+        .into_iter()
+        .map(|(mut result, terms)| {
+            let published = result.date.clone();
+            result.score = scoring::score_document(&terms, &corpus, published.as_deref(), &options.scoring);
+            result
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    
     Ok(results)
 }
 
@@ -413,26 +749,7 @@ fn search_topic(topic_dir: &Path, query: &str, options: &SearchOptions) -> Resul
 ///
 /// Returns an error if the search fails
 pub fn search_content(options: &SearchOptions) -> Result<Vec<SearchResult>> {
-    let config = load_config()?;
-    let mut results = Vec::new();
-    
-    // If topic is provided, search only in that topic
-    if let Some(topic) = &options.topic {
-        if let Some(topic_config) = config.content.topics.get(topic) {
-            let topic_dir = PathBuf::from(format!("{}/{}", config.content.base_dir, topic_config.directory));
-            let topic_results = search_topic(&topic_dir, &options.query, options)?;
-            results.extend(topic_results);
-        } else {
-            return Err(SearchError::TopicNotFound(topic.clone()).into());
-        }
-    } else {
-        // Search in all topics
-        for (_topic_key, topic_config) in &config.content.topics {
-            let topic_dir = PathBuf::from(format!("{}/{}", config.content.base_dir, topic_config.directory));
-            let topic_results = search_topic(&topic_dir, &options.query, options)?;
-            results.extend(topic_results);
-        }
-    }
+    let mut results = ranked_results(options)?;
     
     // Apply limit if provided
     if options.limit > 0 {
@@ -442,3 +759,47 @@ pub fn search_content(options: &SearchOptions) -> Result<Vec<SearchResult>> {
     Ok(results)
 }
 
+/// Search the content in `repository`
+///
+/// Works like [`search_content`], for callers that keep a repository
+/// between searches, such as a [`common_fs::CachedContentRepository`] that
+/// holds the content in memory.
+///
+/// # Errors
+///
+/// Returns an error if the topic is unknown or the content cannot be read
+pub fn search_repository(repository: &impl ContentRepository, options: &SearchOptions) -> Result<Vec<SearchResult>> {
+    let mut results = ranked_results_in(repository, options)?;
+    if options.limit > 0 {
+        results.truncate(options.limit);
+    }
+    Ok(results)
+}
+
+/// Search for content and count results per topic, tag, year and month
+///
+/// Facets are counted over every matching document, so they reflect the
+/// full result set even when `options.limit` truncates the returned results.
+///
+/// # Parameters
+///
+/// * `options` - Search options
+///
+/// # Returns
+///
+/// Returns the ranked results, the total match count and the facet counts
+///
+/// # Errors
+///
+/// Returns an error if the search fails
+pub fn search_with_facets(options: &SearchOptions) -> Result<FacetedSearchResults> {
+    let mut results = ranked_results(options)?;
+    let total = results.len();
+    let facets = SearchFacets::from_results(&results);
+    
+    if options.limit > 0 {
+        results.truncate(options.limit);
+    }
+    
+    Ok(FacetedSearchResults { results, total, facets })
+}
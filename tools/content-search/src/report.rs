@@ -0,0 +1,69 @@
+//! # Search Report
+//!
+//! The results of a search, printed as text for people or as JSON or YAML
+//! for scripts with `--format`.
+
+use colored::*;
+use common_cli::DisplayResult;
+use serde::Serialize;
+
+use crate::SearchResult;
+
+/// Most highlights shown for each result in plain output
+const PLAIN_HIGHLIGHTS: usize = 3;
+
+/// A query and the results it found, best first
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchReport {
+    pub query: String,
+    pub total: usize,
+    pub results: Vec<SearchResult>,
+}
+
+impl SearchReport {
+    pub fn new(query: impl Into<String>, results: Vec<SearchResult>) -> Self {
+        Self { query: query.into(), total: results.len(), results }
+    }
+}
+
+impl DisplayResult for SearchReport {
+    fn to_display(&self) -> String {
+        let mut lines = vec![format!("\n{} {} results found", "Success:".green().bold(), self.total)];
+
+        for (i, result) in self.results.iter().enumerate() {
+            lines.push(format!(
+                "\n{}. {} ({})",
+                (i + 1).to_string().bold(),
+                result.title.bold(),
+                format!("score: {:.2}", result.score).cyan()
+            ));
+            lines.push(format!("   Topic: {}, Type: {}", result.topic, result.content_type));
+
+            if !result.tags.is_empty() {
+                lines.push(format!("   Tags: {}", result.tags.join(", ").cyan()));
+            }
+            if let Some(date) = &result.date {
+                lines.push(format!("   Date: {}", date));
+            }
+
+            if result.highlights.is_empty() {
+                lines.push(format!("   {}", result.content));
+            } else {
+                for highlight in result.highlights.iter().take(PLAIN_HIGHLIGHTS) {
+                    lines.push(format!("   {}", highlight.excerpt));
+                }
+                if result.highlights.len() > PLAIN_HIGHLIGHTS {
+                    lines.push(format!("   ({} more matches)", result.highlights.len() - PLAIN_HIGHLIGHTS));
+                }
+            }
+            lines.push(format!("   Path: {}", result.path.cyan()));
+        }
+
+        if self.results.is_empty() {
+            lines.push(format!("\nNo results found for the query '{}'.", self.query));
+            lines.push("Try using different search terms, or removing filters.".to_string());
+        }
+
+        lines.join("\n")
+    }
+}
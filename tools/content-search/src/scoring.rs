@@ -0,0 +1,206 @@
+//! Relevance scoring for search results
+//!
+//! Results are scored with BM25 over the document body, with query terms found
+//! in the title and tags counted as boosted term occurrences, and an optional
+//! recency boost that decays with the age of the published date.
+
+use chrono::{NaiveDate, Utc};
+use std::collections::HashMap;
+
+/// Options controlling how search results are scored
+#[derive(Debug, Clone)]
+pub struct ScoringOptions {
+    /// BM25 term frequency saturation parameter
+    pub k1: f32,
+
+    /// BM25 document length normalization parameter (0.0 - 1.0)
+    pub b: f32,
+
+    /// Weight of a query term occurring in the title, relative to the body
+    pub title_boost: f32,
+
+    /// Weight of a query term matching a tag, relative to the body
+    pub tags_boost: f32,
+
+    /// Maximum relative boost given to a document published today (0.0 disables recency)
+    pub recency_weight: f32,
+
+    /// Number of days after which the recency boost is halved
+    pub recency_half_life_days: f32,
+}
+
+impl Default for ScoringOptions {
+    fn default() -> Self {
+        Self {
+            k1: 1.2,
+            b: 0.75,
+            title_boost: 3.0,
+            tags_boost: 2.0,
+            recency_weight: 0.2,
+            recency_half_life_days: 365.0,
+        }
+    }
+}
+
+/// Split text into lowercase search terms
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+/// Term statistics for a single document
+#[derive(Debug, Clone, Default)]
+pub struct DocumentTerms {
+    /// Number of terms in the body
+    pub length: usize,
+
+    /// Occurrences of each query term in the body
+    pub body: HashMap<String, usize>,
+
+    /// Occurrences of each query term in the title
+    pub title: HashMap<String, usize>,
+
+    /// Number of tags equal to each query term
+    pub tags: HashMap<String, usize>,
+}
+
+impl DocumentTerms {
+    /// Count occurrences of `query_terms` in a document's body, title and tags
+    pub fn new(query_terms: &[String], body: &str, title: &str, tags: &[String]) -> Self {
+        let body_terms = tokenize(body);
+        let title_terms = tokenize(title);
+        let tag_terms: Vec<String> = tags.iter().map(|tag| tag.to_lowercase()).collect();
+
+        let count = |terms: &[String], term: &String| terms.iter().filter(|t| *t == term).count();
+
+        let mut document = DocumentTerms {
+            length: body_terms.len(),
+            ..Default::default()
+        };
+        for term in query_terms {
+            document.body.insert(term.clone(), count(&body_terms, term));
+            document.title.insert(term.clone(), count(&title_terms, term));
+            document.tags.insert(term.clone(), count(&tag_terms, term));
+        }
+
+        document
+    }
+
+    /// Whether the document contains `term` in any field
+    fn contains(&self, term: &str) -> bool {
+        [&self.body, &self.title, &self.tags]
+            .iter()
+            .any(|field| field.get(term).copied().unwrap_or(0) > 0)
+    }
+}
+
+/// Corpus-wide statistics needed for BM25
+#[derive(Debug, Clone, Default)]
+pub struct CorpusStats {
+    /// Number of documents scanned
+    pub document_count: usize,
+
+    /// Sum of body lengths of all scanned documents
+    pub total_length: usize,
+
+    /// Number of documents containing each query term
+    pub document_frequency: HashMap<String, usize>,
+}
+
+impl CorpusStats {
+    /// Record a scanned document, whether or not it matched the query
+    pub fn add_document(&mut self, document: &DocumentTerms) {
+        self.document_count += 1;
+        self.total_length += document.length;
+
+        for term in document.body.keys() {
+            if document.contains(term) {
+                *self.document_frequency.entry(term.clone()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Average body length in terms
+    fn average_length(&self) -> f32 {
+        if self.document_count == 0 {
+            0.0
+        } else {
+            self.total_length as f32 / self.document_count as f32
+        }
+    }
+
+    /// Inverse document frequency of a term (BM25+ variant, always positive)
+    fn idf(&self, term: &str) -> f32 {
+        let n = self.document_count as f32;
+        let df = self.document_frequency.get(term).copied().unwrap_or(0) as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+}
+
+/// Score a document against the query terms
+///
+/// # Parameters
+///
+/// * `document` - Term statistics for the document
+/// * `corpus` - Statistics for the whole corpus
+/// * `published` - The document's published date, if any
+/// * `options` - Scoring options
+///
+/// # Returns
+///
+/// Returns the relevance score; higher is more relevant
+pub fn score_document(
+    document: &DocumentTerms,
+    corpus: &CorpusStats,
+    published: Option<&str>,
+    options: &ScoringOptions,
+) -> f32 {
+    let average_length = corpus.average_length();
+    let length_norm = if average_length > 0.0 {
+        1.0 - options.b + options.b * (document.length as f32 / average_length)
+    } else {
+        1.0
+    };
+
+    let mut score = 0.0;
+    for (term, body_tf) in &document.body {
+        let title_tf = document.title.get(term).copied().unwrap_or(0);
+        let tags_tf = document.tags.get(term).copied().unwrap_or(0);
+
+        let tf = *body_tf as f32
+            + options.title_boost * title_tf as f32
+            + options.tags_boost * tags_tf as f32;
+        if tf == 0.0 {
+            continue;
+        }
+
+        score += corpus.idf(term) * (tf * (options.k1 + 1.0)) / (tf + options.k1 * length_norm);
+    }
+
+    score * (1.0 + options.recency_weight * recency_decay(published, options.recency_half_life_days))
+}
+
+/// Exponential decay factor (1.0 for today, 0.5 after one half-life) for a published date
+fn recency_decay(published: Option<&str>, half_life_days: f32) -> f32 {
+    let date = match published.and_then(parse_date) {
+        Some(date) => date,
+        None => return 0.0,
+    };
+
+    if half_life_days <= 0.0 {
+        return 0.0;
+    }
+
+    let age_days = (Utc::now().date_naive() - date).num_days().max(0) as f32;
+    0.5_f32.powf(age_days / half_life_days)
+}
+
+/// Parse the date portion of a `YYYY-MM-DD` or RFC 3339 date string
+pub(crate) fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    value
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
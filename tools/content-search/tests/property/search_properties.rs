@@ -1,7 +1,7 @@
 //! Property-based tests for search functionality
 
 use anyhow::Result;
-use content_search::{SearchOptions, SearchResult};
+use content_search::{HighlightOptions, ScoringOptions, SearchOptions, SearchResult};
 use common_test_utils::fixtures::TestFixture;
 use common_test_utils::proptest::strategies::*;
 use proptest::prelude::*;
@@ -50,10 +50,13 @@ fn search_options_strategy() -> impl Strategy<Value = SearchOptions> {
             tags,
             limit,
             include_drafts,
+            include_archived: false,
             title_only,
             raw_query,
             case_sensitive,
             include_metadata,
+            highlight: HighlightOptions::default(),
+            scoring: ScoringOptions::default(),
         }
     })
 }
@@ -0,0 +1,61 @@
+//! Unit tests for search facet counts
+
+use content_search::{SearchFacets, SearchResult};
+
+fn result(topic: &str, tags: &[&str], date: Option<&str>) -> SearchResult {
+    SearchResult {
+        title: "Title".to_string(),
+        slug: "slug".to_string(),
+        topic: topic.to_string(),
+        path: "path".to_string(),
+        content: String::new(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        content_type: "article".to_string(),
+        date: date.map(|d| d.to_string()),
+        score: 1.0,
+        highlights: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod search_facets_tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_topics_and_tags() {
+        let results = vec![
+            result("blog", &["rust", "Async"], None),
+            result("blog", &["rust"], None),
+            result("notes", &["async", "ASYNC"], None),
+        ];
+        let facets = SearchFacets::from_results(&results);
+
+        assert_eq!(facets.topics.get("blog"), Some(&2));
+        assert_eq!(facets.topics.get("notes"), Some(&1));
+        assert_eq!(facets.tags.get("rust"), Some(&2));
+        assert_eq!(facets.tags.get("async"), Some(&2));
+    }
+
+    #[test]
+    fn test_counts_years_and_months() {
+        let results = vec![
+            result("blog", &[], Some("2024-01-15")),
+            result("blog", &[], Some("2024-01-30T10:00:00Z")),
+            result("blog", &[], Some("2023-12-01")),
+            result("blog", &[], Some("not a date")),
+            result("blog", &[], None),
+        ];
+        let facets = SearchFacets::from_results(&results);
+
+        assert_eq!(facets.years.get("2024"), Some(&2));
+        assert_eq!(facets.years.get("2023"), Some(&1));
+        assert_eq!(facets.months.get("2024-01"), Some(&2));
+        assert_eq!(facets.months.get("2023-12"), Some(&1));
+        assert_eq!(facets.months.values().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn test_empty_results() {
+        assert_eq!(SearchFacets::from_results(&[]), SearchFacets::default());
+    }
+}
@@ -0,0 +1,99 @@
+//! Unit tests for draft, archive, content type and tag filtering
+
+use content_search::{matches_filters, SearchOptions};
+
+const ARTICLE: &str = r#"---
+title: "Async Rust"
+published: "2024-01-15"
+tags:
+  - rust
+  - Async
+type: article
+---
+
+Body text about futures.
+"#;
+
+const DRAFT: &str = r#"---
+title: "Unfinished"
+draft: true
+tags:
+  - rust
+---
+
+Work in progress.
+"#;
+
+const ARCHIVED: &str = r#"---
+title: "Old News"
+archived: true
+---
+
+No longer relevant.
+"#;
+
+#[cfg(test)]
+mod matches_filters_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_filters_matches() {
+        assert!(matches_filters(ARTICLE, &SearchOptions::default()));
+    }
+
+    #[test]
+    fn test_drafts_excluded_unless_requested() {
+        let mut options = SearchOptions::default();
+        assert!(!matches_filters(DRAFT, &options));
+
+        options.include_drafts = true;
+        assert!(matches_filters(DRAFT, &options));
+    }
+
+    #[test]
+    fn test_archived_excluded_unless_requested() {
+        let mut options = SearchOptions::default();
+        assert!(!matches_filters(ARCHIVED, &options));
+
+        options.include_archived = true;
+        assert!(matches_filters(ARCHIVED, &options));
+    }
+
+    #[test]
+    fn test_content_type_must_match() {
+        let mut options = SearchOptions {
+            content_type: Some("Article".to_string()),
+            ..Default::default()
+        };
+        assert!(matches_filters(ARTICLE, &options));
+
+        options.content_type = Some("note".to_string());
+        assert!(!matches_filters(ARTICLE, &options));
+
+        options.include_drafts = true;
+        options.content_type = Some("article".to_string());
+        assert!(!matches_filters(DRAFT, &options));
+    }
+
+    #[test]
+    fn test_all_tags_required() {
+        let mut options = SearchOptions {
+            tags: Some(vec!["rust".to_string(), "async".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches_filters(ARTICLE, &options));
+
+        options.tags = Some(vec!["rust".to_string(), "web".to_string()]);
+        assert!(!matches_filters(ARTICLE, &options));
+    }
+
+    #[test]
+    fn test_documents_without_frontmatter_fail_tag_filters() {
+        let options = SearchOptions {
+            tags: Some(vec!["rust".to_string()]),
+            ..Default::default()
+        };
+        assert!(!matches_filters("No frontmatter here", &options));
+        assert!(matches_filters("No frontmatter here", &SearchOptions::default()));
+    }
+}
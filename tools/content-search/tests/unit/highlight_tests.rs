@@ -0,0 +1,79 @@
+//! Unit tests for search result highlighting
+
+use content_search::{find_highlights, HighlightOptions};
+
+#[cfg(test)]
+mod find_highlights_tests {
+    use super::*;
+
+    #[test]
+    fn test_returns_every_match() {
+        let text = "Rust is fast. Rust is safe. Writing rust is fun.";
+        let highlights = find_highlights(text, "rust", false, &HighlightOptions::default());
+
+        assert_eq!(highlights.len(), 3);
+        assert_eq!((highlights[0].start, highlights[0].end), (0, 4));
+        assert_eq!((highlights[1].start, highlights[1].end), (14, 18));
+        assert_eq!(&text[highlights[2].start..highlights[2].end], "rust");
+    }
+
+    #[test]
+    fn test_case_sensitive_matching() {
+        let text = "Rust and rust";
+        let highlights = find_highlights(text, "Rust", true, &HighlightOptions::default());
+
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].start, 0);
+    }
+
+    #[test]
+    fn test_matches_are_wrapped_in_markers() {
+        let options = HighlightOptions {
+            pre_tag: "**".to_string(),
+            post_tag: "**".to_string(),
+            context_size: 4,
+            max_highlights: 0,
+        };
+        let highlights = find_highlights("one two three four", "three", false, &options);
+
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].excerpt, "...two **three** fou...");
+    }
+
+    #[test]
+    fn test_multiple_query_words() {
+        let highlights = find_highlights("alpha beta gamma", "gamma alpha", false, &HighlightOptions::default());
+
+        assert_eq!(highlights.len(), 2);
+        assert!(highlights[0].excerpt.contains("<mark>alpha</mark>"));
+        assert!(highlights[1].excerpt.contains("<mark>gamma</mark>"));
+    }
+
+    #[test]
+    fn test_max_highlights_limits_results() {
+        let options = HighlightOptions {
+            max_highlights: 2,
+            ..HighlightOptions::default()
+        };
+        let highlights = find_highlights("a a a a", "a", false, &options);
+
+        assert_eq!(highlights.len(), 2);
+    }
+
+    #[test]
+    fn test_multibyte_context_stays_on_char_boundaries() {
+        let options = HighlightOptions {
+            context_size: 2,
+            ..HighlightOptions::default()
+        };
+        let highlights = find_highlights("héllo wörld café", "wörld", false, &options);
+
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].excerpt, "...o <mark>wörld</mark> c...");
+    }
+
+    #[test]
+    fn test_empty_query_has_no_highlights() {
+        assert!(find_highlights("text", "  ", false, &HighlightOptions::default()).is_empty());
+    }
+}
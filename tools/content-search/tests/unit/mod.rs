@@ -6,6 +6,10 @@ pub mod tests_tests;
 pub mod search_options_tests;
 pub mod search_functions_tests;
 pub mod search_integration_tests;
+pub mod highlight_tests;
+pub mod scoring_tests;
+pub mod filter_tests;
+pub mod facet_tests;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,94 @@
+//! Unit tests for search result scoring
+
+use content_search::scoring::{score_document, tokenize, CorpusStats, DocumentTerms};
+use content_search::ScoringOptions;
+
+fn terms(query: &str) -> Vec<String> {
+    tokenize(query)
+}
+
+fn corpus_of(documents: &[&DocumentTerms]) -> CorpusStats {
+    let mut corpus = CorpusStats::default();
+    for document in documents {
+        corpus.add_document(document);
+    }
+    corpus
+}
+
+#[cfg(test)]
+mod scoring_tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_lowercases_and_splits_on_punctuation() {
+        assert_eq!(tokenize("Hello, World! rust-lang"), vec!["hello", "world", "rust", "lang"]);
+    }
+
+    #[test]
+    fn test_more_occurrences_score_higher() {
+        let query = terms("rust");
+        let frequent = DocumentTerms::new(&query, "rust rust rust and more words", "", &[]);
+        let rare = DocumentTerms::new(&query, "rust and a few more words", "", &[]);
+        let corpus = corpus_of(&[&frequent, &rare]);
+        let options = ScoringOptions { recency_weight: 0.0, ..Default::default() };
+
+        assert!(
+            score_document(&frequent, &corpus, None, &options)
+                > score_document(&rare, &corpus, None, &options)
+        );
+    }
+
+    #[test]
+    fn test_title_and_tag_matches_are_boosted() {
+        let query = terms("rust");
+        let in_title = DocumentTerms::new(&query, "a post about rust", "Learning Rust", &[]);
+        let in_tags = DocumentTerms::new(&query, "a post about rust", "Learning", &["rust".to_string()]);
+        let body_only = DocumentTerms::new(&query, "a post about rust", "Learning", &[]);
+        let corpus = corpus_of(&[&in_title, &in_tags, &body_only]);
+        let options = ScoringOptions { recency_weight: 0.0, ..Default::default() };
+
+        let body_score = score_document(&body_only, &corpus, None, &options);
+        assert!(score_document(&in_title, &corpus, None, &options) > body_score);
+        assert!(score_document(&in_tags, &corpus, None, &options) > body_score);
+    }
+
+    #[test]
+    fn test_rare_terms_weigh_more() {
+        let query = terms("common rare");
+        let with_rare = DocumentTerms::new(&query, "common rare", "", &[]);
+        let with_common = DocumentTerms::new(&query, "common common", "", &[]);
+        let filler = DocumentTerms::new(&query, "common words", "", &[]);
+        let corpus = corpus_of(&[&with_rare, &with_common, &filler]);
+        let options = ScoringOptions { recency_weight: 0.0, ..Default::default() };
+
+        assert!(
+            score_document(&with_rare, &corpus, None, &options)
+                > score_document(&with_common, &corpus, None, &options)
+        );
+    }
+
+    #[test]
+    fn test_recent_documents_are_boosted() {
+        let query = terms("rust");
+        let document = DocumentTerms::new(&query, "rust", "", &[]);
+        let corpus = corpus_of(&[&document]);
+        let options = ScoringOptions::default();
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+        let recent = score_document(&document, &corpus, Some(&today), &options);
+        let old = score_document(&document, &corpus, Some("2000-01-01"), &options);
+        let undated = score_document(&document, &corpus, None, &options);
+
+        assert!(recent > old);
+        assert!(old >= undated);
+    }
+
+    #[test]
+    fn test_no_matches_scores_zero() {
+        let query = terms("missing");
+        let document = DocumentTerms::new(&query, "nothing relevant here", "Title", &[]);
+        let corpus = corpus_of(&[&document]);
+
+        assert_eq!(score_document(&document, &corpus, None, &ScoringOptions::default()), 0.0);
+    }
+}
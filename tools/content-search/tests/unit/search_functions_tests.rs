@@ -125,4 +125,46 @@ mod create_excerpt_tests {
 
         // If the function is not exposed, we'll need to test through behavior
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod search_repository_tests {
+    use common_fs::CachedContentRepository;
+    use common_models::{Config, TopicConfig};
+    use content_search::{search_repository, SearchOptions, SearchReport};
+
+    #[test]
+    fn test_search_repository_reads_through_the_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let article_dir = temp_dir.path().join("blog").join("rust-tips");
+        std::fs::create_dir_all(&article_dir).unwrap();
+        std::fs::write(
+            article_dir.join("index.md"),
+            "---\ntitle: \"Rust tips\"\ndraft: false\n---\nBorrowing explained.\n",
+        ).unwrap();
+
+        let mut config = Config::default();
+        config.content.base_dir = temp_dir.path().to_string_lossy().into_owned();
+        config.content.topics.insert("blog".to_string(), TopicConfig {
+            name: "Blog".to_string(),
+            description: String::new(),
+            directory: "blog".to_string(),
+        });
+        let repository = CachedContentRepository::new(&config);
+
+        let options = SearchOptions { query: "borrowing".to_string(), ..Default::default() };
+        let results = search_repository(&repository, &options).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Rust tips");
+        assert_eq!(repository.cached_files(), 1);
+
+        let options = SearchOptions { topic: Some("notes".to_string()), ..options };
+        assert!(search_repository(&repository, &options).is_err());
+
+        let report = SearchReport::new("borrowing", results);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["query"], "borrowing");
+        assert_eq!(json["total"], 1);
+        assert_eq!(json["results"][0]["title"], "Rust tips");
+    }
+}
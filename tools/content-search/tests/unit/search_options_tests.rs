@@ -1,6 +1,6 @@
 //! Unit tests for SearchOptions struct and its Default implementation
 
-use content_search::SearchOptions;
+use content_search::{HighlightOptions, ScoringOptions, SearchOptions};
 use anyhow::Result;
 
 #[cfg(test)]
@@ -35,10 +35,13 @@ mod search_options_tests {
             tags: Some(vec!["rust".to_string(), "programming".to_string()]),
             limit: 50,
             include_drafts: true,
+            include_archived: false,
             title_only: true,
             raw_query: true,
             case_sensitive: true,
             include_metadata: false,
+            highlight: HighlightOptions::default(),
+            scoring: ScoringOptions::default(),
         };
 
         // Verify custom values
@@ -0,0 +1,171 @@
+//! # Goals
+//!
+//! Measures the content against the writing goals in `goals.yaml`. A goal's
+//! actual value counts the articles published in the current week or month,
+//! and its streak counts the periods in a row that met the target.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use colored::*;
+use common_cli::DisplayResult;
+use common_config::{Goal, GoalMetric, GoalPeriod};
+use common_models::PublishDate;
+use serde::Serialize;
+
+use crate::ContentStats;
+
+/// Progress towards one goal in the current period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    /// Label of the current period, e.g. `2024-W07` or `2024-02`
+    pub period: String,
+    pub target: usize,
+    pub actual: usize,
+    /// Actual as a percentage of the target, which can exceed 100
+    pub percent: f64,
+    /// Whether the current period has met the target
+    pub met: bool,
+    /// Consecutive periods that met the target, ending with the current one
+    /// if it has been met, or the one before it if not
+    pub streak: usize,
+}
+
+/// Progress towards every configured goal
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GoalReport {
+    pub goals: Vec<GoalProgress>,
+}
+
+impl GoalReport {
+    /// Whether every goal has been met in the current period
+    pub fn all_met(&self) -> bool {
+        self.goals.iter().all(|progress| progress.met)
+    }
+}
+
+impl DisplayResult for GoalReport {
+    fn to_display(&self) -> String {
+        let mut lines = vec![
+            "Goals".yellow().bold().to_string(),
+            "=========================================".to_string(),
+        ];
+
+        if self.goals.is_empty() {
+            lines.push(format!("No goals configured in {}.", common_config::goals::GOALS_FILE));
+        }
+
+        for progress in &self.goals {
+            let metric = match progress.goal.metric {
+                GoalMetric::Words => "Words",
+                GoalMetric::Posts => "Posts",
+            };
+            let topic = progress.goal.topic.as_deref().map(|topic| format!(" in {}", topic)).unwrap_or_default();
+            let status = if progress.met { "met".green() } else { "in progress".yellow() };
+
+            lines.push(format!(
+                "{}{} ({}): {}/{} ({:.0}%, {}) - streak: {}",
+                metric.cyan(),
+                topic,
+                progress.period,
+                progress.actual,
+                progress.target,
+                progress.percent,
+                status,
+                progress.streak
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Measure the content against each goal as of `today`
+pub fn generate_goal_report(goals: &[Goal], stats: &[ContentStats], today: NaiveDate) -> GoalReport {
+    GoalReport {
+        goals: goals.iter().map(|goal| goal_progress(goal, stats, today)).collect(),
+    }
+}
+
+fn goal_progress(goal: &Goal, stats: &[ContentStats], today: NaiveDate) -> GoalProgress {
+    let current = period_start(goal.period, today);
+    let actual = measure(goal, stats, current);
+    let met = actual >= goal.target;
+
+    // A target of zero is always met, so the streak would never end
+    let mut streak = 0;
+    if goal.target > 0 {
+        let earliest = stats
+            .iter()
+            .filter_map(|stat| stat.published.as_ref().and_then(PublishDate::date))
+            .min()
+            .map(|date| period_start(goal.period, date));
+        let mut start = if met { Some(current) } else { previous_period(goal.period, current) };
+
+        while let Some(period) = start.filter(|period| earliest.is_some_and(|earliest| *period >= earliest)) {
+            if measure(goal, stats, period) < goal.target {
+                break;
+            }
+            streak += 1;
+            start = previous_period(goal.period, period);
+        }
+    }
+
+    let percent = if goal.target == 0 {
+        100.0
+    } else {
+        actual as f64 / goal.target as f64 * 100.0
+    };
+
+    GoalProgress {
+        goal: goal.clone(),
+        period: period_label(goal.period, current),
+        target: goal.target,
+        actual,
+        percent,
+        met,
+        streak,
+    }
+}
+
+/// Words or posts published in the period starting at `start`
+fn measure(goal: &Goal, stats: &[ContentStats], start: NaiveDate) -> usize {
+    let published = stats.iter().filter(|stat| {
+        !stat.is_draft
+            && goal.topic.as_ref().is_none_or(|topic| *topic == stat.topic)
+            && stat
+                .published
+                .as_ref()
+                .and_then(PublishDate::date)
+                .is_some_and(|date| period_start(goal.period, date) == start)
+    });
+
+    match goal.metric {
+        GoalMetric::Words => published.map(|stat| stat.word_count).sum(),
+        GoalMetric::Posts => published.count(),
+    }
+}
+
+/// First day of the period containing `date`
+fn period_start(period: GoalPeriod, date: NaiveDate) -> NaiveDate {
+    match period {
+        GoalPeriod::Week => date - Duration::days(i64::from(date.weekday().num_days_from_monday())),
+        GoalPeriod::Month => date.with_day(1).expect("every month has a first day"),
+    }
+}
+
+/// First day of the period before the one starting at `start`
+fn previous_period(period: GoalPeriod, start: NaiveDate) -> Option<NaiveDate> {
+    let day_before = start.pred_opt()?;
+    Some(period_start(period, day_before))
+}
+
+/// Label of the period starting at `start`
+fn period_label(period: GoalPeriod, start: NaiveDate) -> String {
+    match period {
+        GoalPeriod::Week => {
+            let week = start.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }
+        GoalPeriod::Month => start.format("%Y-%m").to_string(),
+    }
+}
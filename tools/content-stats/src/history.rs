@@ -0,0 +1,337 @@
+//! # Statistics History
+//!
+//! Each run of `content-stats --snapshot` records the totals in a JSON file
+//! under `.stats/` in the content base directory. Trend reports compare those
+//! snapshots to show how many articles were added and words written per week
+//! or month.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Utc};
+use common_cli::DisplayResult;
+use common_fs::normalize::join_paths;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::ContentStats;
+
+/// Name of the snapshot directory, relative to the content base directory
+pub const STATS_DIR: &str = ".stats";
+
+/// Totals for one topic in a snapshot
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicSnapshot {
+    pub articles: usize,
+    pub words: usize,
+}
+
+/// The content statistics at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub total_articles: usize,
+    pub total_words: usize,
+    pub total_drafts: usize,
+    pub total_published: usize,
+    /// Totals per topic key
+    pub topics: BTreeMap<String, TopicSnapshot>,
+    /// Word count per article, keyed by `topic/slug`
+    pub word_counts: BTreeMap<String, usize>,
+}
+
+impl StatsSnapshot {
+    /// Summarize article statistics as a snapshot taken at `timestamp`
+    pub fn from_stats(stats: &[ContentStats], timestamp: DateTime<Utc>) -> Self {
+        let mut topics: BTreeMap<String, TopicSnapshot> = BTreeMap::new();
+        let mut word_counts = BTreeMap::new();
+
+        for stat in stats {
+            let topic = topics.entry(stat.topic.clone()).or_default();
+            topic.articles += 1;
+            topic.words += stat.word_count;
+            word_counts.insert(format!("{}/{}", stat.topic, stat.slug), stat.word_count);
+        }
+
+        let total_drafts = stats.iter().filter(|stat| stat.is_draft).count();
+
+        Self {
+            timestamp,
+            total_articles: stats.len(),
+            total_words: stats.iter().map(|stat| stat.word_count).sum(),
+            total_drafts,
+            total_published: stats.len() - total_drafts,
+            topics,
+            word_counts,
+        }
+    }
+}
+
+/// Get the snapshot directory for the current configuration
+pub fn stats_dir() -> Result<PathBuf> {
+    let config = common_config::load_config()?;
+    Ok(join_paths(&config.content.base_dir, STATS_DIR))
+}
+
+/// Write a snapshot to `dir`, returning the path of the new file
+///
+/// # Errors
+///
+/// Returns an error if the directory can't be created or the file can't be written
+pub fn save_snapshot(dir: &Path, snapshot: &StatsSnapshot) -> Result<PathBuf> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create stats directory: {:?}", dir))?;
+
+    let path = dir.join(format!("{}.json", snapshot.timestamp.format("%Y%m%dT%H%M%SZ")));
+    let json = serde_json::to_string_pretty(snapshot).context("Failed to serialize stats snapshot")?;
+    fs::write(&path, json).with_context(|| format!("Failed to write stats snapshot: {:?}", path))?;
+
+    Ok(path)
+}
+
+/// Read every snapshot in `dir`, oldest first
+///
+/// A missing directory has no snapshots.
+///
+/// # Errors
+///
+/// Returns an error if a snapshot can't be read or parsed
+pub fn load_snapshots(dir: &Path) -> Result<Vec<StatsSnapshot>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read stats directory: {:?}", dir))? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+
+        let json = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read stats snapshot: {:?}", path))?;
+        let snapshot: StatsSnapshot = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse stats snapshot: {:?}", path))?;
+        snapshots.push(snapshot);
+    }
+
+    snapshots.sort_by_key(|snapshot| snapshot.timestamp);
+    Ok(snapshots)
+}
+
+/// Length of the periods a trend report is grouped into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendPeriod {
+    Week,
+    Month,
+}
+
+impl TrendPeriod {
+    /// Label of the period containing `timestamp`, e.g. `2024-W07` or `2024-02`
+    pub fn label(&self, timestamp: DateTime<Utc>) -> String {
+        match self {
+            TrendPeriod::Week => {
+                let week = timestamp.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            TrendPeriod::Month => timestamp.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+impl std::str::FromStr for TrendPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "week" | "weekly" => Ok(TrendPeriod::Week),
+            "month" | "monthly" => Ok(TrendPeriod::Month),
+            _ => Err(anyhow::anyhow!("Invalid trend period: {}. Use week or month", s)),
+        }
+    }
+}
+
+/// The snapshots a trend report covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrendRange {
+    /// Ignore snapshots before this time
+    pub since: Option<DateTime<Utc>>,
+    /// Ignore snapshots after this time
+    pub until: Option<DateTime<Utc>>,
+    pub period: TrendPeriod,
+}
+
+impl TrendRange {
+    /// Cover every snapshot, grouped by `period`
+    pub fn all(period: TrendPeriod) -> Self {
+        Self {
+            since: None,
+            until: None,
+            period,
+        }
+    }
+
+    fn contains(&self, timestamp: DateTime<Utc>) -> bool {
+        self.since.is_none_or(|since| timestamp >= since)
+            && self.until.is_none_or(|until| timestamp <= until)
+    }
+}
+
+/// Change over one period of a trend report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrendRow {
+    /// Period label, e.g. `2024-W07`
+    pub period: String,
+    /// Articles at the end of the period
+    pub total_articles: usize,
+    /// Words at the end of the period
+    pub total_words: usize,
+    /// Articles added since the previous period (negative if some were removed)
+    pub articles_added: i64,
+    /// Words written since the previous period (negative if some were removed)
+    pub words_written: i64,
+}
+
+/// Change in one topic across a trend report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicTrend {
+    pub articles_added: i64,
+    pub words_written: i64,
+}
+
+/// How the content changed across a range of snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrendReport {
+    pub period: TrendPeriod,
+    pub rows: Vec<TrendRow>,
+    pub articles_added: i64,
+    pub words_written: i64,
+    /// Change per topic key
+    pub topics: BTreeMap<String, TopicTrend>,
+}
+
+impl TrendReport {
+    /// Render the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize trend report")
+    }
+
+    /// Render the report as Markdown tables
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        let heading = match self.period {
+            TrendPeriod::Week => "Week",
+            TrendPeriod::Month => "Month",
+        };
+
+        let _ = writeln!(output, "| {} | Articles | Words | Articles added | Words written |", heading);
+        let _ = writeln!(output, "| --- | ---: | ---: | ---: | ---: |");
+        for row in &self.rows {
+            let _ = writeln!(
+                output,
+                "| {} | {} | {} | {:+} | {:+} |",
+                row.period, row.total_articles, row.total_words, row.articles_added, row.words_written
+            );
+        }
+        let _ = writeln!(
+            output,
+            "| **Total** | | | {:+} | {:+} |",
+            self.articles_added, self.words_written
+        );
+
+        if !self.topics.is_empty() {
+            let _ = writeln!(output, "\n| Topic | Articles added | Words written |");
+            let _ = writeln!(output, "| --- | ---: | ---: |");
+            for (topic, trend) in &self.topics {
+                let _ = writeln!(output, "| {} | {:+} | {:+} |", topic, trend.articles_added, trend.words_written);
+            }
+        }
+
+        output
+    }
+}
+
+impl DisplayResult for TrendReport {
+    fn to_display(&self) -> String {
+        self.to_markdown()
+    }
+}
+
+/// Compare snapshots to show how the content changed in each period
+///
+/// Each period is represented by its last snapshot and compared with the last
+/// snapshot before it, which may fall before the start of the range. The first
+/// snapshot ever taken has nothing to compare against, so it counts as the
+/// starting point rather than as content added.
+pub fn generate_trend_report(snapshots: &[StatsSnapshot], range: &TrendRange) -> TrendReport {
+    let mut sorted: Vec<&StatsSnapshot> = snapshots.iter().collect();
+    sorted.sort_by_key(|snapshot| snapshot.timestamp);
+
+    // The last snapshot of each period, keeping the one before the range as a baseline
+    let mut baseline = None;
+    let mut periods: Vec<(String, &StatsSnapshot)> = Vec::new();
+    for snapshot in sorted {
+        if !range.contains(snapshot.timestamp) {
+            if range.since.is_some_and(|since| snapshot.timestamp < since) {
+                baseline = Some(snapshot);
+            }
+            continue;
+        }
+
+        let label = range.period.label(snapshot.timestamp);
+        match periods.last_mut() {
+            Some((last_label, last)) if *last_label == label => *last = snapshot,
+            _ => periods.push((label, snapshot)),
+        }
+    }
+
+    let first = baseline.or_else(|| periods.first().map(|(_, snapshot)| *snapshot));
+    let mut previous = baseline;
+    let mut rows = Vec::new();
+    for (period, snapshot) in &periods {
+        let (articles_added, words_written) = match previous {
+            Some(previous) => (
+                delta(snapshot.total_articles, previous.total_articles),
+                delta(snapshot.total_words, previous.total_words),
+            ),
+            None => (0, 0),
+        };
+
+        rows.push(TrendRow {
+            period: period.clone(),
+            total_articles: snapshot.total_articles,
+            total_words: snapshot.total_words,
+            articles_added,
+            words_written,
+        });
+        previous = Some(snapshot);
+    }
+
+    let mut topics = BTreeMap::new();
+    if let (Some(first), Some((_, last))) = (first, periods.last()) {
+        let keys = first.topics.keys().chain(last.topics.keys());
+        for key in keys {
+            let before = first.topics.get(key).cloned().unwrap_or_default();
+            let after = last.topics.get(key).cloned().unwrap_or_default();
+            topics.insert(key.clone(), TopicTrend {
+                articles_added: delta(after.articles, before.articles),
+                words_written: delta(after.words, before.words),
+            });
+        }
+    }
+
+    TrendReport {
+        period: range.period,
+        articles_added: rows.iter().map(|row| row.articles_added).sum(),
+        words_written: rows.iter().map(|row| row.words_written).sum(),
+        rows,
+        topics,
+    }
+}
+
+/// Signed difference between two counts
+fn delta(after: usize, before: usize) -> i64 {
+    after as i64 - before as i64
+}
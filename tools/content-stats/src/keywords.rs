@@ -0,0 +1,211 @@
+//! # Keywords
+//!
+//! Vocabulary statistics for articles: the terms that set an article apart
+//! from the rest of the corpus (TF-IDF), how varied its vocabulary is
+//! (type/token ratio), and the words it leans on most.
+
+use colored::*;
+use common_cli::DisplayResult;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::ContentStats;
+
+/// Number of top terms kept for each article and for the corpus
+pub const TOP_TERMS: usize = 10;
+
+/// Number of overused words kept for each article
+pub const OVERUSED_WORDS: usize = 5;
+
+/// Times a word must appear before it can count as overused
+const OVERUSE_MIN_COUNT: usize = 3;
+
+/// Common English words that say nothing about what an article is about
+pub const STOP_WORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "also", "am", "an", "and", "any",
+    "are", "as", "at", "be", "because", "been", "before", "being", "below", "between", "both",
+    "but", "by", "can", "could", "did", "do", "does", "doing", "don't", "down", "during", "each",
+    "even", "few", "for", "from", "further", "get", "got", "had", "has", "have", "having", "he",
+    "her", "here", "hers", "him", "his", "how", "i", "i'm", "if", "in", "into", "is", "it",
+    "it's", "its", "just", "like", "make", "may", "me", "might", "more", "most", "much", "must",
+    "my", "no", "nor", "not", "now", "of", "off", "on", "once", "one", "only", "or", "other",
+    "our", "ours", "out", "over", "own", "really", "same", "she", "should", "so", "some", "such",
+    "than", "that", "that's", "the", "their", "theirs", "them", "then", "there", "these", "they",
+    "this", "those", "through", "to", "too", "under", "until", "up", "us", "use", "very", "was",
+    "we", "were", "what", "when", "where", "which", "while", "who", "whom", "why", "will", "with",
+    "would", "you", "your", "yours",
+];
+
+/// Whether a word is a stop word
+pub fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.contains(&word)
+}
+
+/// Split prose into lowercase words, keeping apostrophes inside words
+///
+/// Tokens without a letter, such as numbers, are skipped.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .map(|word| word.trim_matches(|c| c == '\'' || c == '’').replace('’', "'").to_lowercase())
+        .filter(|word| word.chars().any(char::is_alphabetic))
+        .collect()
+}
+
+/// Count the words that aren't stop words
+pub fn term_counts(tokens: &[String]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for token in tokens.iter().filter(|token| !is_stop_word(token)) {
+        *counts.entry(token.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Distinct words divided by total words, from 0 to 1
+///
+/// Longer texts naturally score lower, so compare articles of similar length.
+pub fn vocabulary_richness(tokens: &[String]) -> f64 {
+    if tokens.is_empty() {
+        return 0.0;
+    }
+
+    let distinct: HashSet<&String> = tokens.iter().collect();
+    distinct.len() as f64 / tokens.len() as f64
+}
+
+/// The words used most often, most frequent first
+///
+/// Only words used at least three times are included.
+pub fn overused_words(term_counts: &HashMap<String, usize>, limit: usize) -> Vec<(String, usize)> {
+    let mut words: Vec<(String, usize)> = term_counts
+        .iter()
+        .filter(|(_, count)| **count >= OVERUSE_MIN_COUNT)
+        .map(|(word, count)| (word.clone(), *count))
+        .collect();
+    words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    words.truncate(limit);
+    words
+}
+
+/// Score each article's terms by TF-IDF against all the articles
+///
+/// Replaces `top_terms` on every article. A term scores highly when it is
+/// frequent in the article but rare in the rest of the corpus.
+pub fn score_top_terms(stats: &mut [ContentStats], limit: usize) {
+    let document_frequency = document_frequency(stats);
+    let documents = stats.len();
+
+    for stat in stats.iter_mut() {
+        let total: usize = stat.term_counts.values().sum();
+        if total == 0 {
+            stat.top_terms.clear();
+            continue;
+        }
+
+        let scores = stat.term_counts.iter().map(|(term, count)| {
+            let tf = *count as f64 / total as f64;
+            (term.clone(), tf * idf(documents, document_frequency[term]))
+        });
+        stat.top_terms = top_scores(scores, limit);
+    }
+}
+
+/// Vocabulary statistics for a whole corpus
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct KeywordReport {
+    /// Articles in the corpus
+    pub articles: usize,
+    /// Distinct words, excluding stop words
+    pub vocabulary_size: usize,
+    /// Terms with the highest TF-IDF summed over the articles
+    pub top_terms: Vec<(String, f64)>,
+    /// Terms used in the most articles, with the number of articles
+    pub common_terms: Vec<(String, usize)>,
+    /// Average vocabulary richness of the articles
+    pub average_richness: f64,
+}
+
+impl DisplayResult for KeywordReport {
+    fn to_display(&self) -> String {
+        let mut lines = vec![
+            "Keywords".yellow().bold().to_string(),
+            "=========================================".to_string(),
+            format!("Articles: {}", self.articles),
+            format!("Vocabulary Size: {}", self.vocabulary_size),
+            format!("Average Vocabulary Richness: {:.2}", self.average_richness),
+        ];
+
+        lines.push(format!("\n{}", "Top Terms".yellow().bold()));
+        lines.push("------------------".to_string());
+        for (term, score) in &self.top_terms {
+            lines.push(format!("  {}: {:.3}", term.cyan(), score));
+        }
+
+        lines.push(format!("\n{}", "Most Common Terms".yellow().bold()));
+        lines.push("------------------".to_string());
+        for (term, articles) in &self.common_terms {
+            lines.push(format!("  {}: {} articles", term.cyan(), articles));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Summarize the vocabulary of a corpus
+pub fn keyword_report(stats: &[ContentStats], limit: usize) -> KeywordReport {
+    let document_frequency = document_frequency(stats);
+    let documents = stats.len();
+
+    let mut totals: HashMap<&str, f64> = HashMap::new();
+    for stat in stats {
+        let total: usize = stat.term_counts.values().sum();
+        for (term, count) in &stat.term_counts {
+            let tf = *count as f64 / total as f64;
+            *totals.entry(term.as_str()).or_insert(0.0) += tf * idf(documents, document_frequency[term]);
+        }
+    }
+
+    let mut common_terms: Vec<(String, usize)> = document_frequency
+        .iter()
+        .map(|(term, count)| (term.clone(), *count))
+        .collect();
+    common_terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    common_terms.truncate(limit);
+
+    let average_richness = if documents == 0 {
+        0.0
+    } else {
+        stats.iter().map(|stat| stat.vocabulary_richness).sum::<f64>() / documents as f64
+    };
+
+    KeywordReport {
+        articles: documents,
+        vocabulary_size: document_frequency.len(),
+        top_terms: top_scores(totals.into_iter().map(|(term, score)| (term.to_string(), score)), limit),
+        common_terms,
+        average_richness,
+    }
+}
+
+/// Number of articles each term appears in
+fn document_frequency(stats: &[ContentStats]) -> HashMap<String, usize> {
+    let mut frequency = HashMap::new();
+    for stat in stats {
+        for term in stat.term_counts.keys() {
+            *frequency.entry(term.clone()).or_insert(0) += 1;
+        }
+    }
+    frequency
+}
+
+/// Smoothed inverse document frequency, which is 1 for a term in every article
+fn idf(documents: usize, frequency: usize) -> f64 {
+    ((1 + documents) as f64 / (1 + frequency) as f64).ln() + 1.0
+}
+
+/// The highest scores, ties broken alphabetically
+fn top_scores(scores: impl Iterator<Item = (String, f64)>, limit: usize) -> Vec<(String, f64)> {
+    let mut scores: Vec<(String, f64)> = scores.collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    scores.truncate(limit);
+    scores
+}
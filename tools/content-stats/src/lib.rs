@@ -1,16 +1,32 @@
 use anyhow::Result;
 use chrono::NaiveDate;
-use common_models::{Config, Frontmatter, TopicConfig};
+use common_fs::FsContentRepository;
+use common_git::ChangedArticle;
+use common_models::{Config, Frontmatter, PublishDate, TopicConfig};
+use common_traits::{ArticleLocation, ContentRepository};
 use comrak::{markdown_to_html, ComrakOptions};
 use regex::Regex;
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
+
+pub mod goals;
+pub mod history;
+pub mod keywords;
+pub mod report;
+pub mod similarity;
+
+pub use goals::{generate_goal_report, GoalProgress, GoalReport};
+pub use history::{generate_trend_report, StatsSnapshot, TrendPeriod, TrendRange, TrendReport};
+pub use keywords::{keyword_report, KeywordReport};
+pub use report::{ArticleReport, DuplicateReport, StatsReport};
+pub use similarity::{find_similar, SimilarPair};
 
 /// Structure to hold content statistics for an article
 #[derive(Clone, Debug)]
 pub struct ContentStats {
     pub title: String,
-    pub published: String,
+    /// Publication date from the frontmatter, if it has one
+    pub published: Option<PublishDate>,
     pub word_count: usize,
     pub reading_time: usize, // in minutes
     pub character_count: usize,
@@ -20,6 +36,16 @@ pub struct ContentStats {
     pub slug: String,
     pub tags: Vec<String>,
     pub is_draft: bool,
+    /// Counts of the words that aren't stop words
+    pub term_counts: HashMap<String, usize>,
+    /// Terms that set the article apart from the corpus, by TF-IDF score
+    pub top_terms: Vec<(String, f64)>,
+    /// Distinct words divided by total words
+    pub vocabulary_richness: f64,
+    /// Words used most often, with their counts
+    pub overused_words: Vec<(String, usize)>,
+    /// MinHash signature of the prose, for finding near-duplicates
+    pub signature: Vec<u64>,
     // Add the fields needed for the overall stats
     pub total_articles: usize,
     pub total_words: usize,
@@ -44,8 +70,11 @@ pub struct StatsOptions {
     pub slug: Option<String>,
     pub topic: Option<String>,
     pub include_drafts: bool,
+    pub include_archived: bool,
     pub sort_by: String,
     pub detailed: bool,
+    /// Only include articles changed since this git reference
+    pub changed_since: Option<String>,
 }
 
 /// Type alias for stats generation result
@@ -72,7 +101,8 @@ pub fn calculate_stats(
     let re = Regex::new(r"<[^>]*>").unwrap();
     let text = re.replace_all(&html, "").to_string();
 
-    let word_count = common_markdown::calculate_word_count(&text);
+    // Count words in the prose only, skipping code, HTML and URLs
+    let word_count = common_markdown::analyze_words(content).word_count();
     let character_count = text.chars().count();
 
     // Count paragraphs (non-empty lines)
@@ -86,7 +116,17 @@ pub fn calculate_stats(
     let sentence_count = sentence_re.find_iter(&text).count();
 
     // Calculate reading time
-    let reading_time = common_markdown::calculate_reading_time(word_count) as usize;
+    let reading_time = common_markdown::calculate_reading_time_with_options(
+        content,
+        &common_markdown::ReadingTimeOptions::default(),
+    ) as usize;
+
+    // Vocabulary; top terms are rescored against the corpus by generate_stats
+    let tokens = keywords::tokenize(&common_markdown::prose_text(content));
+    let term_counts = keywords::term_counts(&tokens);
+    let vocabulary_richness = keywords::vocabulary_richness(&tokens);
+    let overused_words = keywords::overused_words(&term_counts, keywords::OVERUSED_WORDS);
+    let signature = similarity::minhash(&similarity::shingles(&tokens, similarity::SHINGLE_SIZE));
 
     // Extract tags
     let tags = frontmatter.tags.clone().unwrap_or_default();
@@ -96,13 +136,9 @@ pub fn calculate_stats(
         || frontmatter
             .published_at
             .as_ref()
-            .is_some_and(|p| p == "DRAFT");
+            .is_some_and(PublishDate::is_draft);
 
-    // Get published date or default
-    let published = frontmatter
-        .published_at
-        .clone()
-        .unwrap_or_else(|| "DRAFT".to_string());
+    let published = frontmatter.published_at.clone();
 
     ContentStats {
         title: frontmatter.title.clone(),
@@ -116,6 +152,11 @@ pub fn calculate_stats(
         slug: slug.to_string(),
         tags,
         is_draft,
+        term_counts,
+        top_terms: Vec::new(),
+        vocabulary_richness,
+        overused_words,
+        signature,
         total_articles: 0,
         total_words: 0,
         total_drafts: 0,
@@ -137,11 +178,31 @@ pub fn format_date(date_str: &str) -> String {
     }
 }
 
+/// Order statistics by published date, drafts first, then newest first
+///
+/// Articles without a date are ordered with the drafts. Drafts, and articles
+/// published on the same date, are ordered by title.
+pub fn compare_by_date(a: &ContentStats, b: &ContentStats) -> std::cmp::Ordering {
+    let date = |stats: &ContentStats| stats.published.clone().unwrap_or(PublishDate::Draft);
+    date(b).cmp(&date(a)).then_with(|| a.title.cmp(&b.title))
+}
+
 /// Generate statistics for content files based on the provided options
 pub fn generate_stats(options: &StatsOptions) -> Result<StatsResult> {
     // Read configuration
     let config = common_config::load_config()?;
+    generate_stats_in(&config, &FsContentRepository::new(&config), options)
+}
 
+/// Generate statistics for the articles in `repository`
+///
+/// `config` is used to check the requested topic and to find the articles
+/// changed since a git reference.
+pub fn generate_stats_in(
+    config: &Config,
+    repository: &impl ContentRepository,
+    options: &StatsOptions,
+) -> Result<StatsResult> {
     // Validate topic if provided
     if let Some(ref topic) = options.topic {
         if !config.content.topics.contains_key(topic) {
@@ -165,106 +226,68 @@ pub fn generate_stats(options: &StatsOptions) -> Result<StatsResult> {
     let mut total_drafts = 0;
     let mut tag_counts: HashMap<String, usize> = HashMap::new();
 
-    // Get the content base directory
-    let content_base_dir = PathBuf::from(&config.content.base_dir);
+    // Articles changed since the requested git reference, if any
+    let changed = match &options.changed_since {
+        Some(reference) => Some(common_git::changed_content_since(config, reference)?),
+        None => None,
+    };
 
-    // Process content for specific slug if provided
-    if let Some(ref slug) = options.slug {
-        // Find the article with the given slug in any topic
-        let mut found = false;
+    let is_wanted_topic = |topic_key: &str| options.topic.as_deref().is_none_or(|topic| topic == topic_key);
 
-        for (topic_key, topic_config) in &config.content.topics {
-            // Skip if a specific topic is requested and it's not this one
-            if options.topic.is_some() && options.topic.as_ref() != Some(topic_key) {
-                continue;
-            }
-
-            let topic_path = &topic_config.directory;
-            let article_path = content_base_dir.join(topic_path).join(slug);
-
-            if article_path.exists() {
-                let index_path = article_path.join("index.mdx");
-                if index_path.exists() {
-                    process_article(
-                        &index_path,
-                        topic_key,
-                        slug,
-                        options,
-                        &mut all_stats,
-                        &mut total_words,
-                        &mut total_articles,
-                        &mut total_drafts,
-                        &mut tag_counts,
-                    )?;
-                    found = true;
-                }
-            }
-        }
+    let articles = if let Some(ref slug) = options.slug {
+        // Find the article with the given slug in any topic
+        let articles: Vec<ArticleLocation> = repository
+            .find_articles(slug)?
+            .into_iter()
+            .filter(|article| is_wanted_topic(&article.topic))
+            .collect();
 
-        if !found {
+        if articles.is_empty() {
             return Err(anyhow::anyhow!("No article found with slug: {}", slug));
         }
+        articles
     } else {
         // Process all content
-        for (topic_key, topic_config) in &config.content.topics {
-            // Skip if a specific topic is requested and it's not this one
-            if options.topic.is_some() && options.topic.as_ref() != Some(topic_key) {
-                continue;
-            }
-
-            let topic_path = &topic_config.directory;
-            let topic_dir = content_base_dir.join(topic_path);
-
-            // Skip if the topic directory doesn't exist
-            if !topic_dir.exists() {
+        let mut articles = Vec::new();
+        for topic_key in repository.list_topics()? {
+            if !is_wanted_topic(&topic_key) {
                 continue;
             }
 
-            // Find all subdirectories in the topic directory (article directories)
-            let dirs = common_fs::find_dirs_with_depth(&topic_dir, 1, 1)?;
-
-            for article_dir in dirs {
-                let slug = article_dir
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .unwrap_or("");
-
-                let index_path = article_dir.join("index.mdx");
-                if index_path.exists() {
-                    process_article(
-                        &index_path,
-                        topic_key,
-                        slug,
-                        options,
-                        &mut all_stats,
-                        &mut total_words,
-                        &mut total_articles,
-                        &mut total_drafts,
-                        &mut tag_counts,
-                    )?;
-                }
-            }
+            articles.extend(repository.list_articles(&topic_key)?.into_iter().filter(|article| {
+                changed.as_ref().is_none_or(|changed| {
+                    changed.contains(&ChangedArticle {
+                        topic: article.topic.clone(),
+                        slug: article.slug.clone(),
+                    })
+                })
+            }));
         }
+        articles
+    };
+
+    for article in &articles {
+        process_article(
+            &repository.read_article(article)?,
+            &article.topic,
+            &article.slug,
+            options,
+            &mut all_stats,
+            &mut total_words,
+            &mut total_articles,
+            &mut total_drafts,
+            &mut tag_counts,
+        )?;
     }
 
+    // Score each article's terms against everything that was analyzed
+    keywords::score_top_terms(&mut all_stats, keywords::TOP_TERMS);
+
     // Sort the statistics
     match options.sort_by.as_str() {
         "date" => {
-            let sort_stats = |a: &ContentStats, b: &ContentStats| {
-                // Compare the published dates
-                if a.published == "DRAFT" && b.published == "DRAFT" {
-                    a.title.cmp(&b.title)
-                } else if a.published == "DRAFT" {
-                    std::cmp::Ordering::Less
-                } else if b.published == "DRAFT" {
-                    std::cmp::Ordering::Greater
-                } else {
-                    b.published.cmp(&a.published)
-                }
-            };
-
             let mut stats_vec = all_stats.iter().cloned().collect::<Vec<_>>();
-            stats_vec.sort_by(sort_stats);
+            stats_vec.sort_by(compare_by_date);
             return Ok((
                 stats_vec,
                 tag_counts,
@@ -295,21 +318,8 @@ pub fn generate_stats(options: &StatsOptions) -> Result<StatsResult> {
         }
         _ => {
             // Default sort by date
-            let sort_stats = |a: &ContentStats, b: &ContentStats| {
-                // Compare the published dates
-                if a.published == "DRAFT" && b.published == "DRAFT" {
-                    a.title.cmp(&b.title)
-                } else if a.published == "DRAFT" {
-                    std::cmp::Ordering::Less
-                } else if b.published == "DRAFT" {
-                    std::cmp::Ordering::Greater
-                } else {
-                    b.published.cmp(&a.published)
-                }
-            };
-
             let mut stats_vec = all_stats.iter().cloned().collect::<Vec<_>>();
-            stats_vec.sort_by(sort_stats);
+            stats_vec.sort_by(compare_by_date);
             return Ok((
                 stats_vec,
                 tag_counts,
@@ -321,9 +331,9 @@ pub fn generate_stats(options: &StatsOptions) -> Result<StatsResult> {
     }
 }
 
-/// Process a single article file and extract statistics
+/// Process a single article and extract statistics
 fn process_article(
-    #[allow(clippy::too_many_arguments)] index_path: &Path,
+    #[allow(clippy::too_many_arguments)] content: &str,
     topic_key: &str,
     slug: &str,
     options: &StatsOptions,
@@ -333,23 +343,25 @@ fn process_article(
     total_drafts: &mut usize,
     tag_counts: &mut HashMap<String, usize>,
 ) -> Result<()> {
-    // Read the content file
-    let content = common_fs::read_file(index_path)?;
-
     // Extract frontmatter and content
-    let (frontmatter, content_text) = common_markdown::extract_frontmatter_and_content(&content)?;
+    let (frontmatter, content_text) = common_markdown::extract_frontmatter_and_content(content)?;
 
     // Check if draft and skip if not including drafts
     let is_draft = frontmatter.is_draft.unwrap_or(false)
         || frontmatter
             .published_at
             .as_ref()
-            .is_some_and(|p| p == "DRAFT");
+            .is_some_and(PublishDate::is_draft);
 
     if is_draft && !options.include_drafts {
         return Ok(());
     }
 
+    // Skip archived content unless specifically included
+    if frontmatter.is_archived() && !options.include_archived {
+        return Ok(());
+    }
+
     // Calculate statistics
     let stats = calculate_stats(&content_text, &frontmatter, topic_key, slug);
 
@@ -381,6 +393,7 @@ fn process_article(
 /// * `config` - Application configuration
 /// * `topic_key` - Topic key
 /// * `topic_config` - Topic configuration
+/// * `include_archived` - Whether archived content counts towards the statistics
 ///
 /// # Returns
 ///
@@ -393,6 +406,7 @@ fn get_topic_stats(
     config: &Config,
     topic_key: &str,
     topic_config: &TopicConfig,
+    include_archived: bool,
 ) -> Result<TopicStats> {
     let mut stats = TopicStats {
         key: topic_key.to_string(),
@@ -420,19 +434,21 @@ fn get_topic_stats(
 
     // Process each article directory
     for article_dir in article_dirs {
-        // Check if the article has an index.md file
-        let index_file = article_dir.join("index.md");
-
-        if index_file.exists() {
-            // Read the index file
-            let content = common_fs::read_file(&index_file)?;
+        if let Some(content_file) = common_fs::resolve_content_dir(&article_dir) {
+            // Read the content file
+            let content = common_fs::read_file(&content_file)?;
 
             // Extract frontmatter
             if let Ok((frontmatter, content)) =
                 common_markdown::extract_frontmatter_and_content(&content)
             {
+                // Archived content only counts when asked for
+                if frontmatter.is_archived() && !include_archived {
+                    continue;
+                }
+
                 // Count words
-                let word_count = content.split_whitespace().count();
+                let word_count = common_markdown::analyze_words(&content).word_count();
 
                 // Update statistics
                 stats.article_count += 1;
@@ -469,7 +485,7 @@ pub fn get_content_stats(options: &StatsOptions) -> Result<ContentStats> {
     let config = common_config::load_config()?;
     let mut stats = ContentStats {
         title: "Content Statistics".to_string(),
-        published: "".to_string(),
+        published: None,
         word_count: 0,
         reading_time: 0,
         character_count: 0,
@@ -479,6 +495,11 @@ pub fn get_content_stats(options: &StatsOptions) -> Result<ContentStats> {
         slug: "".to_string(),
         tags: Vec::new(),
         is_draft: false,
+        term_counts: HashMap::new(),
+        top_terms: Vec::new(),
+        vocabulary_richness: 0.0,
+        overused_words: Vec::new(),
+        signature: Vec::new(),
         total_articles: 0,
         total_words: 0,
         total_drafts: 0,
@@ -489,7 +510,7 @@ pub fn get_content_stats(options: &StatsOptions) -> Result<ContentStats> {
     // If a specific topic is requested, only get stats for that topic
     if let Some(topic_key) = &options.topic {
         if let Some(topic_config) = config.content.topics.get(topic_key) {
-            let topic_stats = get_topic_stats(&config, topic_key, topic_config)?;
+            let topic_stats = get_topic_stats(&config, topic_key, topic_config, options.include_archived)?;
 
             // Update totals
             stats.total_articles += topic_stats.article_count;
@@ -505,7 +526,7 @@ pub fn get_content_stats(options: &StatsOptions) -> Result<ContentStats> {
     } else {
         // Get stats for all topics
         for (topic_key, topic_config) in &config.content.topics {
-            let topic_stats = get_topic_stats(&config, topic_key, topic_config)?;
+            let topic_stats = get_topic_stats(&config, topic_key, topic_config, options.include_archived)?;
 
             // Update totals
             stats.total_articles += topic_stats.article_count;
@@ -538,18 +559,7 @@ pub mod test_utils {
 
     /// Get the sort function for testing
     pub fn get_sort_function() -> Option<fn(&ContentStats, &ContentStats) -> Ordering> {
-        Some(|a, b| {
-            // Sort by published date (newest first)
-            if a.published == "DRAFT" && b.published == "DRAFT" {
-                a.title.cmp(&b.title)
-            } else if a.published == "DRAFT" {
-                Ordering::Less
-            } else if b.published == "DRAFT" {
-                Ordering::Greater
-            } else {
-                b.published.cmp(&a.published)
-            }
-        })
+        Some(compare_by_date)
     }
 
     /// Extract metadata and content from markdown for testing
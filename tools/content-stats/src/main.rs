@@ -1,7 +1,9 @@
 use anyhow::Result;
 use clap::Parser;
-use colored::*;
-use content_stats::{generate_stats, format_date, StatsOptions};
+use chrono::{Local, NaiveDate, Utc};
+use common_cli::{OutputFormat, OutputFormatArgs, OutputFormatter};
+use content_stats::history::{load_snapshots, save_snapshot, stats_dir};
+use content_stats::{generate_goal_report, generate_stats, generate_trend_report, find_similar, keyword_report, DuplicateReport, StatsOptions, StatsReport, StatsSnapshot, TrendPeriod, TrendRange};
 
 #[derive(Parser)]
 #[command(author, version, about = "Generate content statistics")]
@@ -18,104 +20,118 @@ struct Args {
     #[arg(short, long)]
     include_drafts: bool,
     
+    /// Include archived content
+    #[arg(long)]
+    include_archived: bool,
+    
     /// Sort by (date, words, reading_time)
-    #[arg(short, long, default_value = "date")]
+    #[arg(long, default_value = "date")]
     sort_by: String,
     
     /// Show detailed statistics
     #[arg(short, long)]
     detailed: bool,
+
+    /// Only include articles changed since this git commit, branch or tag
+    #[arg(long, value_name = "REF")]
+    changed_since: Option<String>,
+
+    /// Record a snapshot of the statistics in the .stats directory
+    #[arg(long, conflicts_with_all = ["slug", "topic", "changed_since"])]
+    snapshot: bool,
+
+    /// Report trends from recorded snapshots, grouped by week or month
+    #[arg(long, value_name = "PERIOD")]
+    trend: Option<TrendPeriod>,
+
+    /// Only include snapshots taken on or after this date (YYYY-MM-DD)
+    #[arg(long, requires = "trend")]
+    since: Option<NaiveDate>,
+
+    /// Print the trend report as JSON instead of Markdown (the same as --format json)
+    #[arg(long, requires = "trend")]
+    json: bool,
+
+    /// Show the terms and vocabulary of the whole corpus
+    #[arg(long)]
+    keywords: bool,
+
+    /// Report pairs of articles with near-duplicate text
+    #[arg(long)]
+    duplicates: bool,
+
+    /// Estimated similarity, from 0 to 1, at which articles count as near-duplicates
+    #[arg(long, default_value_t = content_stats::similarity::DEFAULT_THRESHOLD, requires = "duplicates")]
+    threshold: f64,
+
+    /// Show progress towards the goals in goals.yaml
+    #[arg(long, conflicts_with_all = ["slug", "topic", "changed_since"])]
+    goals: bool,
+
+    #[command(flatten)]
+    output: OutputFormatArgs,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let formatter = if args.json {
+        OutputFormatter::new(OutputFormat::Json)
+    } else {
+        args.output.formatter()
+    };
+    
+    if let Some(period) = args.trend {
+        let range = TrendRange {
+            since: args.since.map(|date| date.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc()),
+            ..TrendRange::all(period)
+        };
+        let report = generate_trend_report(&load_snapshots(&stats_dir()?)?, &range);
+        return formatter.print(&report);
+    }
     
     let options = StatsOptions {
         slug: args.slug,
         topic: args.topic,
         include_drafts: args.include_drafts,
+        include_archived: args.include_archived,
         sort_by: args.sort_by,
         detailed: args.detailed,
+        changed_since: args.changed_since,
     };
     
     let (stats, tag_counts, total_words, total_articles, total_drafts) = generate_stats(&options)?;
     
-    // Print statistics
-    if options.detailed {
-        println!("{}", "Content Statistics (Detailed)".yellow().bold());
-        println!("=========================================");
-        
-        if stats.is_empty() {
-            println!("No content found.");
-            return Ok(());
-        }
-        
-        for stat in &stats {
-            println!("\n{}", stat.title.green().bold());
-            println!("  {}: {}", "Topic".cyan(), stat.topic);
-            println!("  {}: {}", "Slug".cyan(), stat.slug);
-            println!("  {}: {}", "Published".cyan(), format_date(&stat.published));
-            println!("  {}: {} words", "Word Count".cyan(), stat.word_count);
-            println!("  {}: {} minutes", "Reading Time".cyan(), stat.reading_time);
-            println!("  {}: {}", "Character Count".cyan(), stat.character_count);
-            println!("  {}: {}", "Paragraph Count".cyan(), stat.paragraph_count);
-            println!("  {}: {}", "Sentence Count".cyan(), stat.sentence_count);
-            
-            if !stat.tags.is_empty() {
-                println!("  {}: {}", "Tags".cyan(), stat.tags.join(", "));
-            }
-            
-            if stat.is_draft {
-                println!("  {}: {}", "Draft".cyan(), "Yes".red());
-            }
-        }
-        
-        // Print tag counts
-        if !tag_counts.is_empty() {
-            println!("\n{}", "Tag Usage".yellow().bold());
-            println!("------------------");
-            
-            let mut tag_count_vec: Vec<(String, usize)> = tag_counts.into_iter().collect();
-            tag_count_vec.sort_by(|a, b| b.1.cmp(&a.1));
-            
-            for (tag, count) in tag_count_vec {
-                println!("  {}: {}", tag.cyan(), count);
-            }
-        }
-    } else {
-        // Print summary statistics
-        println!("{}", "Content Statistics".yellow().bold());
-        println!("=========================================");
-        
-        println!("Total Content: {}", total_articles);
-        println!("Published Articles: {}", total_articles - total_drafts);
-        println!("Drafts: {}", total_drafts);
-        println!("Total Words: {}", total_words);
-        
-        if total_articles > 0 {
-            println!("Average Words per Article: {}", total_words / total_articles);
-        }
-        
-        println!("\n{}", "Content List".yellow().bold());
-        println!("------------------");
-        
-        if stats.is_empty() {
-            println!("No content found.");
-            return Ok(());
-        }
-        
-        for stat in &stats {
-            let published_str = format_date(&stat.published);
-            let draft_indicator = if stat.is_draft { " [DRAFT]".red() } else { "".normal() };
-            
-            println!("{} - {}{} - {} words ({} min)", 
-                     published_str.cyan(),
-                     stat.title,
-                     draft_indicator,
-                     stat.word_count,
-                     stat.reading_time);
+    if args.goals {
+        let config = common_config::load_config()?;
+        let goals = common_config::load_goals(&common_config::goals_path(&config))?;
+        let report = generate_goal_report(&goals, &stats, Local::now().date_naive());
+        return formatter.print(&report);
+    }
+    
+    if args.keywords {
+        let report = keyword_report(&stats, content_stats::keywords::TOP_TERMS);
+        return formatter.print(&report);
+    }
+    
+    if args.duplicates {
+        let report = DuplicateReport {
+            threshold: args.threshold,
+            pairs: find_similar(&stats, args.threshold),
+        };
+        return formatter.print(&report);
+    }
+    
+    if args.snapshot {
+        let path = save_snapshot(&stats_dir()?, &StatsSnapshot::from_stats(&stats, Utc::now()))?;
+        // Keep standard output for the report in the structured formats
+        if formatter.is_plain() {
+            println!("Saved snapshot: {}", path.display());
+        } else {
+            eprintln!("Saved snapshot: {}", path.display());
         }
     }
     
-    Ok(())
-} 
\ No newline at end of file
+    let report = StatsReport::new(&stats, &tag_counts, total_words, total_articles, total_drafts)
+        .detailed(options.detailed);
+    formatter.print(&report)
+}
@@ -0,0 +1,236 @@
+//! # Reports
+//!
+//! The statistics in the shape `--format` prints them: as text for people,
+//! or as JSON or YAML for scripts. Per-article working data such as term
+//! counts and similarity signatures is left out.
+
+use colored::*;
+use common_cli::DisplayResult;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{format_date, ContentStats, SimilarPair};
+
+/// Statistics for one article
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArticleReport {
+    pub topic: String,
+    pub slug: String,
+    pub title: String,
+    /// Publication date as written in the frontmatter, if it has one
+    pub published: Option<String>,
+    pub word_count: usize,
+    /// In minutes
+    pub reading_time: usize,
+    pub character_count: usize,
+    pub paragraph_count: usize,
+    pub sentence_count: usize,
+    pub tags: Vec<String>,
+    pub is_draft: bool,
+    /// Terms that set the article apart from the corpus
+    pub top_terms: Vec<String>,
+    pub vocabulary_richness: f64,
+    /// Words used most often, with their counts
+    pub overused_words: Vec<(String, usize)>,
+}
+
+impl ArticleReport {
+    pub fn from_stats(stats: &ContentStats) -> Self {
+        Self {
+            topic: stats.topic.clone(),
+            slug: stats.slug.clone(),
+            title: stats.title.clone(),
+            published: stats.published.as_ref().map(ToString::to_string),
+            word_count: stats.word_count,
+            reading_time: stats.reading_time,
+            character_count: stats.character_count,
+            paragraph_count: stats.paragraph_count,
+            sentence_count: stats.sentence_count,
+            tags: stats.tags.clone(),
+            is_draft: stats.is_draft,
+            top_terms: stats.top_terms.iter().map(|(term, _)| term.clone()).collect(),
+            vocabulary_richness: stats.vocabulary_richness,
+            overused_words: stats.overused_words.clone(),
+        }
+    }
+}
+
+/// Totals for the content and statistics for each article
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsReport {
+    pub total_articles: usize,
+    pub published: usize,
+    pub drafts: usize,
+    pub total_words: usize,
+    pub average_words: usize,
+    /// Number of articles using each tag
+    pub tags: BTreeMap<String, usize>,
+    pub articles: Vec<ArticleReport>,
+    /// Show every statistic for each article in plain output
+    #[serde(skip)]
+    pub detailed: bool,
+}
+
+impl StatsReport {
+    /// Report on the output of [`crate::generate_stats`]
+    pub fn new(
+        stats: &[ContentStats],
+        tag_counts: &HashMap<String, usize>,
+        total_words: usize,
+        total_articles: usize,
+        total_drafts: usize,
+    ) -> Self {
+        Self {
+            total_articles,
+            published: total_articles - total_drafts,
+            drafts: total_drafts,
+            total_words,
+            average_words: total_words.checked_div(total_articles).unwrap_or(0),
+            tags: tag_counts.iter().map(|(tag, count)| (tag.clone(), *count)).collect(),
+            articles: stats.iter().map(ArticleReport::from_stats).collect(),
+            detailed: false,
+        }
+    }
+
+    /// Show every statistic for each article in plain output
+    pub fn detailed(mut self, detailed: bool) -> Self {
+        self.detailed = detailed;
+        self
+    }
+
+    fn detailed_display(&self) -> String {
+        let mut lines = vec![
+            "Content Statistics (Detailed)".yellow().bold().to_string(),
+            "=========================================".to_string(),
+        ];
+
+        if self.articles.is_empty() {
+            lines.push("No content found.".to_string());
+            return lines.join("\n");
+        }
+
+        for article in &self.articles {
+            lines.push(format!("\n{}", article.title.green().bold()));
+            lines.push(format!("  {}: {}", "Topic".cyan(), article.topic));
+            lines.push(format!("  {}: {}", "Slug".cyan(), article.slug));
+            lines.push(format!("  {}: {}", "Published".cyan(), published_label(article)));
+            lines.push(format!("  {}: {} words", "Word Count".cyan(), article.word_count));
+            lines.push(format!("  {}: {} minutes", "Reading Time".cyan(), article.reading_time));
+            lines.push(format!("  {}: {}", "Character Count".cyan(), article.character_count));
+            lines.push(format!("  {}: {}", "Paragraph Count".cyan(), article.paragraph_count));
+            lines.push(format!("  {}: {}", "Sentence Count".cyan(), article.sentence_count));
+
+            if !article.tags.is_empty() {
+                lines.push(format!("  {}: {}", "Tags".cyan(), article.tags.join(", ")));
+            }
+            if !article.top_terms.is_empty() {
+                lines.push(format!("  {}: {}", "Top Terms".cyan(), article.top_terms.join(", ")));
+            }
+            lines.push(format!("  {}: {:.2}", "Vocabulary Richness".cyan(), article.vocabulary_richness));
+            if !article.overused_words.is_empty() {
+                let words: Vec<String> =
+                    article.overused_words.iter().map(|(word, count)| format!("{} ({})", word, count)).collect();
+                lines.push(format!("  {}: {}", "Most Used Words".cyan(), words.join(", ")));
+            }
+            if article.is_draft {
+                lines.push(format!("  {}: {}", "Draft".cyan(), "Yes".red()));
+            }
+        }
+
+        if !self.tags.is_empty() {
+            lines.push(format!("\n{}", "Tag Usage".yellow().bold()));
+            lines.push("------------------".to_string());
+
+            let mut tags: Vec<(&String, &usize)> = self.tags.iter().collect();
+            tags.sort_by(|a, b| b.1.cmp(a.1));
+            for (tag, count) in tags {
+                lines.push(format!("  {}: {}", tag.cyan(), count));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    fn summary_display(&self) -> String {
+        let mut lines = vec![
+            "Content Statistics".yellow().bold().to_string(),
+            "=========================================".to_string(),
+            format!("Total Content: {}", self.total_articles),
+            format!("Published Articles: {}", self.published),
+            format!("Drafts: {}", self.drafts),
+            format!("Total Words: {}", self.total_words),
+        ];
+        if self.total_articles > 0 {
+            lines.push(format!("Average Words per Article: {}", self.average_words));
+        }
+
+        lines.push(format!("\n{}", "Content List".yellow().bold()));
+        lines.push("------------------".to_string());
+
+        if self.articles.is_empty() {
+            lines.push("No content found.".to_string());
+            return lines.join("\n");
+        }
+
+        for article in &self.articles {
+            let draft_indicator = if article.is_draft { " [DRAFT]".red() } else { "".normal() };
+            lines.push(format!(
+                "{} - {}{} - {} words ({} min)",
+                published_label(article).cyan(),
+                article.title,
+                draft_indicator,
+                article.word_count,
+                article.reading_time
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl DisplayResult for StatsReport {
+    fn to_display(&self) -> String {
+        if self.detailed {
+            self.detailed_display()
+        } else {
+            self.summary_display()
+        }
+    }
+}
+
+/// Pairs of articles with near-duplicate text
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateReport {
+    /// Similarity at which articles count as near-duplicates
+    pub threshold: f64,
+    /// Most similar first
+    pub pairs: Vec<SimilarPair>,
+}
+
+impl DisplayResult for DuplicateReport {
+    fn to_display(&self) -> String {
+        let mut lines = vec![
+            "Near-Duplicates".yellow().bold().to_string(),
+            "=========================================".to_string(),
+        ];
+
+        if self.pairs.is_empty() {
+            lines.push(format!("No articles are at least {:.0}% similar.", self.threshold * 100.0));
+        }
+        for pair in &self.pairs {
+            lines.push(format!(
+                "  {} and {}: {:.0}% similar",
+                pair.first.cyan(),
+                pair.second.cyan(),
+                pair.similarity * 100.0
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Publication date of an article for display
+fn published_label(article: &ArticleReport) -> String {
+    article.published.as_deref().map_or_else(|| "No date".to_string(), format_date)
+}
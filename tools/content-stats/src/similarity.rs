@@ -0,0 +1,118 @@
+//! # Similarity
+//!
+//! Near-duplicate detection for articles. Each article's prose is broken into
+//! overlapping runs of words (shingles), and a MinHash signature of those
+//! shingles estimates how much two articles overlap without comparing their
+//! text directly. Pairs above a threshold are likely duplicated drafts,
+//! reposts or large copy-pasted sections.
+
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use crate::ContentStats;
+
+/// Words in each shingle
+pub const SHINGLE_SIZE: usize = 5;
+
+/// Hash functions in each MinHash signature
+pub const SIGNATURE_SIZE: usize = 128;
+
+/// Estimated similarity at which two articles are reported
+pub const DEFAULT_THRESHOLD: f64 = 0.8;
+
+/// Overlapping runs of `size` words
+///
+/// A text shorter than `size` words is a single shingle.
+pub fn shingles(tokens: &[String], size: usize) -> HashSet<String> {
+    if tokens.is_empty() {
+        return HashSet::new();
+    }
+    if tokens.len() <= size {
+        return HashSet::from([tokens.join(" ")]);
+    }
+
+    tokens.windows(size.max(1)).map(|window| window.join(" ")).collect()
+}
+
+/// MinHash signature of a set of shingles, empty if there are none
+///
+/// Each slot holds the smallest hash of any shingle under a different seed.
+/// The fraction of slots two signatures share estimates the Jaccard
+/// similarity of their shingle sets.
+pub fn minhash(shingles: &HashSet<String>) -> Vec<u64> {
+    if shingles.is_empty() {
+        return Vec::new();
+    }
+
+    (0..SIGNATURE_SIZE as u64)
+        .map(|seed| {
+            shingles
+                .iter()
+                .map(|shingle| {
+                    // DefaultHasher::new uses fixed keys, so signatures are stable across runs
+                    let mut hasher = DefaultHasher::new();
+                    seed.hash(&mut hasher);
+                    shingle.hash(&mut hasher);
+                    hasher.finish()
+                })
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+/// Estimated Jaccard similarity of two signatures, from 0 to 1
+///
+/// Empty or mismatched signatures have nothing in common.
+pub fn estimate_similarity(a: &[u64], b: &[u64]) -> f64 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+
+    let matching = a.iter().zip(b).filter(|(a, b)| a == b).count();
+    matching as f64 / a.len() as f64
+}
+
+/// Two articles whose text is estimated to overlap
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarPair {
+    /// First article, as `topic/slug`
+    pub first: String,
+    /// Second article, as `topic/slug`
+    pub second: String,
+    /// Estimated Jaccard similarity of their shingles
+    pub similarity: f64,
+}
+
+/// Find the pairs of articles at or above `threshold` similarity, most similar first
+pub fn find_similar(stats: &[ContentStats], threshold: f64) -> Vec<SimilarPair> {
+    let mut pairs = Vec::new();
+
+    for (index, first) in stats.iter().enumerate() {
+        for second in &stats[index + 1..] {
+            let similarity = estimate_similarity(&first.signature, &second.signature);
+            if similarity > 0.0 && similarity >= threshold {
+                let mut names = [
+                    format!("{}/{}", first.topic, first.slug),
+                    format!("{}/{}", second.topic, second.slug),
+                ];
+                names.sort();
+                let [first, second] = names;
+                pairs.push(SimilarPair {
+                    first,
+                    second,
+                    similarity,
+                });
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| (&a.first, &a.second).cmp(&(&b.first, &b.second)))
+    });
+    pairs
+}
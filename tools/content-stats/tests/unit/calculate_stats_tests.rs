@@ -1,7 +1,7 @@
 //! Unit tests for the calculate_stats function
 
 use content_stats::{calculate_stats, ContentStats};
-use common_models::Frontmatter;
+use common_models::{Frontmatter, PublishDate};
 use anyhow::Result;
 
 #[cfg(test)]
@@ -12,13 +12,18 @@ mod calculate_stats_tests {
         Frontmatter {
             title: title.to_string(),
             description: Some("Test description".to_string()),
-            published_at: published_at.map(|s| s.to_string()),
+            published_at: published_at.map(|s| s.parse().unwrap()),
             updated_at: None,
             is_draft,
             tags: tags.map(|t| t.iter().map(|s| s.to_string()).collect()),
             topics: None,
             slug: None,
             featured_image_path: None,
+            publish_at: None,
+            series: None,
+            series_part: None,
+            archived: None,
+            extra: Default::default(),
         }
     }
 
@@ -54,7 +59,7 @@ And a final paragraph to round things out.
 
         // Assert results
         assert_eq!(stats.title, "Test Article");
-        assert_eq!(stats.published, "2023-01-01");
+        assert_eq!(stats.published, Some("2023-01-01".parse().unwrap()));
         assert!(stats.word_count > 0, "Word count should be greater than 0");
         assert!(stats.reading_time > 0, "Reading time should be greater than 0");
         assert!(stats.character_count > 0, "Character count should be greater than 0");
@@ -86,7 +91,7 @@ And a final paragraph to round things out.
 
         // Assert results
         assert_eq!(stats.title, "Draft Article");
-        assert_eq!(stats.published, "DRAFT");
+        assert_eq!(stats.published, Some(PublishDate::Draft));
         assert!(stats.is_draft, "Article should be marked as draft");
         assert_eq!(stats.tags, Vec::<String>::new(), "Tags should be empty");
     }
@@ -104,8 +104,10 @@ topics:
                 slug: None,
                 topic: None,
                 include_drafts: true,
+                include_archived: false,
                 sort_by: "date".to_string(),
                 detailed: true,
+                changed_since: None,
             };
 
             // Generate stats - this may fail if the function isn't yet implemented
@@ -138,8 +140,10 @@ topics:
                 slug: None,
                 topic: Some("blog".to_string()),
                 include_drafts: false,
+                include_archived: false,
                 sort_by: "date".to_string(),
                 detailed: true,
+                changed_since: None,
             };
 
             match generate_stats(&topic_options) {
@@ -160,8 +164,10 @@ topics:
                 slug: Some("test-article-1".to_string()),
                 topic: None,
                 include_drafts: true,
+                include_archived: false,
                 sort_by: "date".to_string(),
                 detailed: true,
+                changed_since: None,
             };
 
             match generate_stats(&slug_options) {
@@ -231,8 +237,10 @@ topics:
                 slug: None,
                 topic: Some("nonexistent".to_string()),
                 include_drafts: true,
+                include_archived: false,
                 sort_by: "date".to_string(),
                 detailed: true,
+                changed_since: None,
             };
 
             // Should return an error
@@ -300,8 +308,10 @@ topics:
                 slug: Some("nonexistent-article".to_string()),
                 topic: None,
                 include_drafts: true,
+                include_archived: false,
                 sort_by: "date".to_string(),
                 detailed: true,
+                changed_since: None,
             };
 
             // Should return an error
@@ -319,4 +329,56 @@ topics:
             Ok(())
         })
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_generate_stats_over_a_generated_repository() -> Result<()> {
+        let repo = common_test_utils::RepoBuilder::new()
+            .topics(2)
+            .articles_per_topic(3)
+            .drafts(1)
+            .build()?;
+        let _config = common_test_utils::use_config(&repo.config_path);
+        let options = |include_drafts| StatsOptions {
+            slug: None,
+            topic: None,
+            include_drafts,
+            include_archived: false,
+            sort_by: "date".to_string(),
+            detailed: false,
+            changed_since: None,
+        };
+
+        let (stats, _, _, _, _) = generate_stats(&options(false))?;
+        assert_eq!(stats.len(), repo.published().count());
+        assert!(stats.iter().all(|stat| !stat.is_draft));
+
+        let (stats, _, _, _, drafts) = generate_stats(&options(true))?;
+        assert_eq!(stats.len(), repo.articles().count());
+        assert_eq!(drafts, repo.drafts().count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_stats_reads_articles_through_the_repository() -> Result<()> {
+        let repository = common_test_utils::InMemoryContentRepository::new()
+            .with_article("blog", "first", "---\ntitle: First\npublished: 2024-01-01\ntags: [rust]\n---\n\nOne two three.\n")
+            .with_article("blog", "draft", "---\ntitle: Draft\ndraft: true\n---\n\nNot yet.\n");
+        let options = StatsOptions {
+            slug: None,
+            topic: None,
+            include_drafts: false,
+            include_archived: false,
+            sort_by: "date".to_string(),
+            detailed: false,
+            changed_since: None,
+        };
+
+        let (stats, tags, _, articles, _) = content_stats::generate_stats_in(&common_models::Config::default(), &repository, &options)?;
+        assert_eq!(articles, 1);
+        assert_eq!(stats[0].title, "First");
+        assert_eq!(tags["rust"], 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+//! Unit tests for goal progress
+
+use chrono::NaiveDate;
+use common_config::{Goal, GoalMetric, GoalPeriod};
+use content_stats::{generate_goal_report, ContentStats};
+
+#[cfg(test)]
+mod goals_tests {
+    use super::*;
+
+    fn stat(topic: &str, published: &str, word_count: usize) -> ContentStats {
+        ContentStats {
+            title: published.to_string(),
+            published: Some(published.parse().unwrap()),
+            word_count,
+            reading_time: word_count / 200,
+            character_count: word_count * 5,
+            paragraph_count: 1,
+            sentence_count: 1,
+            topic: topic.to_string(),
+            slug: format!("{}-{}", topic, published),
+            tags: vec![],
+            is_draft: false,
+            term_counts: Default::default(),
+            top_terms: Vec::new(),
+            vocabulary_richness: 0.0,
+            overused_words: Vec::new(),
+            signature: Vec::new(),
+            total_articles: 0,
+            total_words: 0,
+            total_drafts: 0,
+            total_published: 0,
+            topics: vec![],
+        }
+    }
+
+    fn goal(metric: GoalMetric, target: usize, period: GoalPeriod, topic: Option<&str>) -> Goal {
+        Goal {
+            metric,
+            target,
+            period,
+            topic: topic.map(str::to_string),
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_words_per_week_progress_and_streak() {
+        // Weeks starting 2024-01-01, 2024-01-08 and 2024-01-15
+        let stats = vec![
+            stat("blog", "2024-01-02", 600),
+            stat("blog", "2024-01-09", 400),
+            stat("notes", "2024-01-10", 200),
+            stat("blog", "2024-01-16", 300),
+        ];
+        let goals = vec![goal(GoalMetric::Words, 500, GoalPeriod::Week, None)];
+
+        let report = generate_goal_report(&goals, &stats, date("2024-01-17"));
+        let progress = &report.goals[0];
+
+        assert_eq!(progress.period, "2024-W03");
+        assert_eq!(progress.actual, 300);
+        assert_eq!(progress.percent, 60.0);
+        assert!(!progress.met);
+        // The current week isn't over, so the streak counts the two weeks before it
+        assert_eq!(progress.streak, 2);
+        assert!(!report.all_met());
+    }
+
+    #[test]
+    fn test_posts_per_month_for_a_topic() {
+        let stats = vec![
+            stat("blog", "2024-01-05", 100),
+            stat("blog", "2024-02-03", 100),
+            stat("blog", "2024-02-20", 100),
+            stat("notes", "2024-02-21", 100),
+        ];
+        let goals = vec![goal(GoalMetric::Posts, 2, GoalPeriod::Month, Some("blog"))];
+
+        let report = generate_goal_report(&goals, &stats, date("2024-02-25"));
+        let progress = &report.goals[0];
+
+        assert_eq!(progress.period, "2024-02");
+        assert_eq!(progress.actual, 2);
+        assert!(progress.met);
+        // January only had one post, so the streak starts in February
+        assert_eq!(progress.streak, 1);
+        assert!(report.all_met());
+    }
+
+    #[test]
+    fn test_drafts_do_not_count() {
+        let mut draft = stat("blog", "2024-03-04", 1000);
+        draft.is_draft = true;
+        let goals = vec![goal(GoalMetric::Words, 100, GoalPeriod::Week, None)];
+
+        let report = generate_goal_report(&goals, &[draft], date("2024-03-05"));
+
+        assert_eq!(report.goals[0].actual, 0);
+        assert_eq!(report.goals[0].streak, 0);
+    }
+}
@@ -0,0 +1,117 @@
+//! Unit tests for stats snapshots and trend reports
+
+use chrono::{DateTime, TimeZone, Utc};
+use content_stats::history::{load_snapshots, save_snapshot};
+use content_stats::{generate_trend_report, ContentStats, StatsSnapshot, TrendPeriod, TrendRange};
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+
+    fn stat(topic: &str, slug: &str, word_count: usize, is_draft: bool) -> ContentStats {
+        ContentStats {
+            title: slug.to_string(),
+            published: Some("2024-01-01".parse().unwrap()),
+            word_count,
+            reading_time: word_count / 200,
+            character_count: word_count * 5,
+            paragraph_count: 1,
+            sentence_count: 1,
+            topic: topic.to_string(),
+            slug: slug.to_string(),
+            tags: vec![],
+            is_draft,
+            term_counts: Default::default(),
+            top_terms: Vec::new(),
+            vocabulary_richness: 0.0,
+            overused_words: Vec::new(),
+            signature: Vec::new(),
+            total_articles: 0,
+            total_words: 0,
+            total_drafts: 0,
+            total_published: 0,
+            topics: vec![],
+        }
+    }
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_from_stats() {
+        let stats = vec![
+            stat("blog", "one", 100, false),
+            stat("blog", "two", 50, true),
+            stat("notes", "three", 10, false),
+        ];
+
+        let snapshot = StatsSnapshot::from_stats(&stats, at(2024, 1, 1));
+
+        assert_eq!(snapshot.total_articles, 3);
+        assert_eq!(snapshot.total_words, 160);
+        assert_eq!(snapshot.total_drafts, 1);
+        assert_eq!(snapshot.total_published, 2);
+        assert_eq!(snapshot.topics["blog"].articles, 2);
+        assert_eq!(snapshot.topics["blog"].words, 150);
+        assert_eq!(snapshot.word_counts["notes/three"], 10);
+    }
+
+    #[test]
+    fn test_save_and_load_snapshots_oldest_first() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join(".stats");
+        assert!(load_snapshots(&dir).unwrap().is_empty());
+
+        let later = StatsSnapshot::from_stats(&[stat("blog", "one", 100, false)], at(2024, 2, 1));
+        let earlier = StatsSnapshot::from_stats(&[], at(2024, 1, 1));
+        save_snapshot(&dir, &later).unwrap();
+        save_snapshot(&dir, &earlier).unwrap();
+
+        assert_eq!(load_snapshots(&dir).unwrap(), vec![earlier, later]);
+    }
+
+    #[test]
+    fn test_generate_trend_report_by_week() {
+        let snapshots = vec![
+            StatsSnapshot::from_stats(&[stat("blog", "one", 100, false)], at(2024, 1, 1)),
+            StatsSnapshot::from_stats(&[stat("blog", "one", 150, false)], at(2024, 1, 3)),
+            StatsSnapshot::from_stats(
+                &[stat("blog", "one", 150, false), stat("notes", "two", 300, false)],
+                at(2024, 1, 9),
+            ),
+        ];
+
+        let report = generate_trend_report(&snapshots, &TrendRange::all(TrendPeriod::Week));
+
+        let rows: Vec<(&str, i64, i64)> = report
+            .rows
+            .iter()
+            .map(|row| (row.period.as_str(), row.articles_added, row.words_written))
+            .collect();
+        assert_eq!(rows, vec![("2024-W01", 0, 0), ("2024-W02", 1, 300)]);
+        assert_eq!(report.articles_added, 1);
+        assert_eq!(report.words_written, 300);
+        assert_eq!(report.topics["blog"].words_written, 0);
+        assert_eq!(report.topics["notes"].articles_added, 1);
+
+        // Snapshots before the range are the baseline for its first period
+        let range = TrendRange {
+            since: Some(at(2024, 1, 2)),
+            ..TrendRange::all(TrendPeriod::Month)
+        };
+        let report = generate_trend_report(&snapshots, &range);
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0].period, "2024-01");
+        assert_eq!(report.rows[0].words_written, 350);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| Month | Articles | Words | Articles added | Words written |"));
+        assert!(markdown.contains("| 2024-01 | 2 | 450 | +1 | +350 |"));
+        assert!(markdown.contains("| notes | +1 | +300 |"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["period"], "month");
+        assert_eq!(json["rows"][0]["articles_added"], 1);
+    }
+}
@@ -0,0 +1,58 @@
+//! Unit tests for keyword extraction
+
+use common_models::Frontmatter;
+use content_stats::keywords::{overused_words, score_top_terms, term_counts, tokenize, vocabulary_richness};
+use content_stats::{calculate_stats, keyword_report};
+
+#[cfg(test)]
+mod keywords_tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_lowercases_and_keeps_apostrophes() {
+        assert_eq!(
+            tokenize("Rust's borrow-checker isn't 'scary' in 2024."),
+            vec!["rust's", "borrow", "checker", "isn't", "scary", "in"]
+        );
+    }
+
+    #[test]
+    fn test_term_counts_skip_stop_words() {
+        let counts = term_counts(&tokenize("The cat and the hat sat on the cat"));
+
+        assert_eq!(counts.get("cat"), Some(&2));
+        assert_eq!(counts.get("hat"), Some(&1));
+        assert!(!counts.contains_key("the"));
+        assert!(!counts.contains_key("and"));
+    }
+
+    #[test]
+    fn test_vocabulary_richness_and_overused_words() {
+        let tokens = tokenize("very good good good code code code code");
+        assert_eq!(vocabulary_richness(&tokens), 3.0 / 8.0);
+        assert_eq!(vocabulary_richness(&[]), 0.0);
+
+        let overused = overused_words(&term_counts(&tokens), 5);
+        assert_eq!(overused, vec![("code".to_string(), 4), ("good".to_string(), 3)]);
+    }
+
+    #[test]
+    fn test_top_terms_favour_terms_unique_to_an_article() {
+        let frontmatter = Frontmatter::default();
+        let mut stats = vec![
+            calculate_stats("Rust ownership and rust lifetimes keep writing safe.", &frontmatter, "blog", "rust"),
+            calculate_stats("Python typing makes writing scripts safe.", &frontmatter, "blog", "python"),
+        ];
+
+        score_top_terms(&mut stats, 3);
+
+        // "rust" appears twice and only in the first article
+        assert_eq!(stats[0].top_terms[0].0, "rust");
+        assert!(stats[1].top_terms.iter().all(|(term, _)| term != "writing"));
+
+        let report = keyword_report(&stats, 3);
+        assert_eq!(report.articles, 2);
+        assert_eq!(report.common_terms[0], ("safe".to_string(), 2));
+        assert!(report.average_richness > 0.0);
+    }
+}
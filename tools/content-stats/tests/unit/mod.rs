@@ -6,6 +6,11 @@ pub mod stats_options_tests;
 pub mod calculate_stats_tests;
 pub mod generate_stats_tests;
 pub mod date_format_tests;
+pub mod history_tests;
+pub mod goals_tests;
+pub mod keywords_tests;
+pub mod similarity_tests;
+pub mod report_tests;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,84 @@
+//! Unit tests for the reports printed with --format
+
+use common_cli::{OutputFormat, OutputFormatter};
+use content_stats::{ContentStats, DuplicateReport, SimilarPair, StatsReport};
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+
+    fn stat(slug: &str, word_count: usize, is_draft: bool) -> ContentStats {
+        ContentStats {
+            title: slug.to_string(),
+            published: Some("2024-01-01".parse().unwrap()),
+            word_count,
+            reading_time: word_count / 200,
+            character_count: word_count * 5,
+            paragraph_count: 1,
+            sentence_count: 1,
+            topic: "blog".to_string(),
+            slug: slug.to_string(),
+            tags: vec!["rust".to_string()],
+            is_draft,
+            term_counts: HashMap::from([("borrowing".to_string(), 3)]),
+            top_terms: vec![("borrowing".to_string(), 0.5)],
+            vocabulary_richness: 0.5,
+            overused_words: Vec::new(),
+            signature: vec![1, 2, 3],
+            total_articles: 0,
+            total_words: 0,
+            total_drafts: 0,
+            total_published: 0,
+            topics: vec![],
+        }
+    }
+
+    #[test]
+    fn test_stats_report_totals_and_articles() {
+        let stats = vec![stat("one", 300, false), stat("two", 100, true)];
+        let tags = HashMap::from([("rust".to_string(), 2)]);
+
+        let report = StatsReport::new(&stats, &tags, 400, 2, 1);
+        assert_eq!(report.published, 1);
+        assert_eq!(report.average_words, 200);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&OutputFormatter::new(OutputFormat::Json).render(&report).unwrap()).unwrap();
+        assert_eq!(json["total_words"], 400);
+        assert_eq!(json["tags"]["rust"], 2);
+        assert_eq!(json["articles"][1]["slug"], "two");
+        assert_eq!(json["articles"][1]["is_draft"], true);
+        assert_eq!(json["articles"][0]["top_terms"][0], "borrowing");
+        // Working data stays out of the report
+        assert!(json["articles"][0].get("signature").is_none());
+        assert!(json.get("detailed").is_none());
+
+        let plain = OutputFormatter::new(OutputFormat::Plain).render(&report).unwrap();
+        assert!(plain.contains("Total Words: 400"));
+        let detailed = OutputFormatter::new(OutputFormat::Plain).render(&report.detailed(true)).unwrap();
+        assert!(detailed.contains("Sentence Count"));
+    }
+
+    #[test]
+    fn test_empty_stats_report() {
+        let report = StatsReport::new(&[], &HashMap::new(), 0, 0, 0);
+        assert_eq!(report.average_words, 0);
+
+        let yaml = OutputFormatter::new(OutputFormat::Yaml).render(&report).unwrap();
+        assert!(yaml.contains("total_articles: 0"));
+        assert!(yaml.contains("articles: []"));
+    }
+
+    #[test]
+    fn test_duplicate_report() {
+        let report = DuplicateReport {
+            threshold: 0.8,
+            pairs: vec![SimilarPair { first: "blog/one".to_string(), second: "blog/two".to_string(), similarity: 0.9 }],
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["pairs"][0]["second"], "blog/two");
+        assert!(OutputFormatter::new(OutputFormat::Plain).render(&report).unwrap().contains("90% similar"));
+    }
+}
@@ -0,0 +1,61 @@
+//! Unit tests for near-duplicate detection
+
+use common_models::Frontmatter;
+use content_stats::calculate_stats;
+use content_stats::keywords::tokenize;
+use content_stats::similarity::{estimate_similarity, find_similar, minhash, shingles, SIGNATURE_SIZE};
+
+#[cfg(test)]
+mod similarity_tests {
+    use super::*;
+
+    const ARTICLE: &str = "Ownership is the feature that sets Rust apart from other languages. \
+        Every value has a single owner, and the value is dropped when its owner goes out of scope. \
+        Borrowing lets code use a value without taking ownership of it, and the borrow checker \
+        makes sure no reference outlives the value it points to.";
+
+    #[test]
+    fn test_shingles_overlap_by_word() {
+        let tokens = tokenize("one two three four");
+
+        let pairs = shingles(&tokens, 2);
+        assert_eq!(pairs.len(), 3);
+        assert!(pairs.contains("two three"));
+
+        assert_eq!(shingles(&tokens, 5).into_iter().collect::<Vec<_>>(), vec!["one two three four"]);
+        assert!(shingles(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_minhash_estimates_jaccard_similarity() {
+        let signature = minhash(&shingles(&tokenize(ARTICLE), 5));
+        assert_eq!(signature.len(), SIGNATURE_SIZE);
+        assert_eq!(signature, minhash(&shingles(&tokenize(ARTICLE), 5)));
+        assert_eq!(estimate_similarity(&signature, &signature), 1.0);
+
+        let unrelated = minhash(&shingles(&tokenize("A completely different note about sourdough bread and how long to proof it overnight."), 5));
+        assert!(estimate_similarity(&signature, &unrelated) < 0.1);
+
+        assert!(minhash(&shingles(&[], 5)).is_empty());
+        assert_eq!(estimate_similarity(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_find_similar_reports_near_duplicates() {
+        let frontmatter = Frontmatter::default();
+        let edited = ARTICLE.replace("the borrow checker", "the compiler's borrow checker");
+        let stats = vec![
+            calculate_stats(ARTICLE, &frontmatter, "blog", "ownership"),
+            calculate_stats("Sourdough needs a lively starter, patience and a hot oven. Proof it overnight in the fridge for a more sour loaf.", &frontmatter, "notes", "bread"),
+            calculate_stats(&edited, &frontmatter, "notes", "ownership-draft"),
+        ];
+
+        let pairs = find_similar(&stats, 0.5);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].first, "blog/ownership");
+        assert_eq!(pairs[0].second, "notes/ownership-draft");
+        assert!(pairs[0].similarity >= 0.5 && pairs[0].similarity < 1.0);
+
+        assert!(find_similar(&stats, 1.0).is_empty());
+    }
+}
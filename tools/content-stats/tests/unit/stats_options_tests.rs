@@ -13,8 +13,10 @@ mod stats_options_tests {
             slug: Some("test-article".to_string()),
             topic: Some("blog".to_string()),
             include_drafts: true,
+            include_archived: false,
             sort_by: "date".to_string(),
             detailed: true,
+            changed_since: None,
         };
 
         assert_eq!(options.slug, Some("test-article".to_string()));
@@ -31,8 +33,10 @@ mod stats_options_tests {
             slug: None,
             topic: None,
             include_drafts: false,
+            include_archived: false,
             sort_by: "word_count".to_string(),
             detailed: false,
+            changed_since: None,
         };
 
         assert_eq!(options.slug, None);
@@ -49,8 +53,10 @@ mod stats_options_tests {
             slug: Some("test-article".to_string()),
             topic: None,
             include_drafts: true,
+            include_archived: false,
             sort_by: "date".to_string(),
             detailed: false,
+            changed_since: None,
         };
 
         assert_eq!(options.slug, Some("test-article".to_string()));
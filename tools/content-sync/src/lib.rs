@@ -0,0 +1,533 @@
+//! # Content Sync
+//!
+//! Two-way sync between the content repository and an Obsidian vault, so
+//! articles can be drafted in Obsidian and published with these tools.
+//!
+//! Each topic is a folder in the vault, named after the topic's directory,
+//! and each article a note in it, named after the article's title. Notes
+//! and articles are converted as they're copied, see [`obsidian`]. Images
+//! and other files in article directories are not synced.
+//!
+//! The state of every synced pair is kept in the content repository (see
+//! [`state`]), so a sync copies each file that changed since the last one
+//! to the other side. A pair changed on both sides is a conflict and is
+//! left alone unless a side is preferred. New notes become new articles,
+//! and new articles new notes. Deleting either file is reported but never
+//! copied over; remove the other one by hand.
+//!
+//! Articles written by a sync are recorded in the journal, so
+//! `write undo` reverts the last sync's changes to the repository.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::{FsContentRepository, Journal};
+use common_models::Config;
+use common_traits::ContentRepository;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub mod obsidian;
+pub mod state;
+
+use obsidian::{LinkIndex, NoteLink};
+pub use state::{FileStamp, SyncEntry, SyncState, STATE_FILE};
+
+/// Which way changes may be copied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Both ways
+    #[default]
+    Both,
+    /// Only from the repository to the vault
+    Push,
+    /// Only from the vault to the repository
+    Pull,
+}
+
+/// One side of a sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Repository,
+    Vault,
+}
+
+/// Options for [`sync`]
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// The Obsidian vault directory
+    pub vault: PathBuf,
+    pub direction: Direction,
+    /// Side whose changes win a conflict, instead of leaving it
+    pub prefer: Option<Side>,
+    /// Only sync this topic
+    pub topic: Option<String>,
+    /// Report what would be copied without copying anything
+    pub dry_run: bool,
+}
+
+/// What a sync did with a pair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum SyncAction {
+    /// A note was written for a new article
+    CreatedNote,
+    /// A note was updated from its article
+    UpdatedNote,
+    /// An article was written for a new note
+    CreatedArticle,
+    /// An article was updated from its note
+    UpdatedArticle,
+    /// An existing article and note were found to match and paired
+    Linked,
+    /// Both sides changed
+    Conflict,
+    /// One side was deleted
+    Missing { side: Side },
+    /// A change that the direction doesn't allow copying
+    Skipped { reason: String },
+}
+
+/// A pair in a [`SyncReport`]
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncItem {
+    pub topic: String,
+    pub slug: String,
+    /// The note, relative to the vault
+    pub note: PathBuf,
+    #[serde(flatten)]
+    pub action: SyncAction,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// What a sync did
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncReport {
+    pub items: Vec<SyncItem>,
+    /// Number of pairs unchanged on both sides
+    pub unchanged: usize,
+    pub dry_run: bool,
+}
+
+impl SyncReport {
+    /// Number of conflicts left for the user
+    pub fn conflicts(&self) -> usize {
+        self.items.iter().filter(|item| item.action == SyncAction::Conflict).count()
+    }
+}
+
+impl DisplayResult for SyncReport {
+    fn to_display(&self) -> String {
+        let mut lines = Vec::new();
+        for item in &self.items {
+            let name = format!("{}/{}", item.topic, item.slug);
+            let note = item.note.display();
+            lines.push(match &item.action {
+                SyncAction::CreatedNote => format!("{} {} → new note {}", "✓".green(), name, note),
+                SyncAction::UpdatedNote => format!("{} {} → {}", "✓".green(), name, note),
+                SyncAction::CreatedArticle => format!("{} {} ← new note {}", "✓".green(), name, note),
+                SyncAction::UpdatedArticle => format!("{} {} ← {}", "✓".green(), name, note),
+                SyncAction::Linked => format!("{} {} = {}", "✓".green(), name, note),
+                SyncAction::Conflict => format!(
+                    "{} {} and {} both changed; use --prefer to choose",
+                    "✗".red(),
+                    name,
+                    note
+                ),
+                SyncAction::Missing { side: Side::Repository } => {
+                    format!("{} {} was removed from the repository; {} was kept", "!".yellow(), name, note)
+                }
+                SyncAction::Missing { side: Side::Vault } => {
+                    format!("{} {} was removed from the vault; {} was kept", "!".yellow(), note, name)
+                }
+                SyncAction::Skipped { reason } => format!("{} {}: {}", "-".dimmed(), name, reason),
+            });
+            for warning in &item.warnings {
+                lines.push(format!("    {} {}", "Warning:".yellow(), warning));
+            }
+        }
+
+        let verb = if self.dry_run { "Would sync" } else { "Synced" };
+        let changed = self
+            .items
+            .iter()
+            .filter(|item| !matches!(item.action, SyncAction::Conflict | SyncAction::Missing { .. } | SyncAction::Skipped { .. }))
+            .count();
+        lines.push(format!(
+            "{} {} pairs, {} unchanged, {} conflicts",
+            verb.bold(),
+            changed,
+            self.unchanged,
+            self.conflicts()
+        ));
+        lines.join("\n")
+    }
+}
+
+/// An article in the repository
+struct Article {
+    topic: String,
+    slug: String,
+    file: PathBuf,
+    title: String,
+}
+
+/// A note in the vault
+struct Note {
+    topic: String,
+    /// Relative to the vault
+    path: PathBuf,
+    /// Slug of the article the note becomes: its `slug` field or its name, slugified
+    slug: String,
+    /// Why the slug can't name an article directory, if it can't
+    invalid_slug: Option<String>,
+}
+
+fn note_stem(path: &Path) -> String {
+    path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// What to do with a pair
+enum Step {
+    Push { create: bool },
+    Pull { create: bool },
+    /// An article and a note that were never synced; linked if they match
+    Match,
+    Report(SyncAction),
+}
+
+/// Sync the content repository with an Obsidian vault
+///
+/// # Errors
+///
+/// Returns an error if the vault doesn't exist, or a file or the sync state
+/// cannot be read or written
+pub fn sync(config: &Config, options: &SyncOptions) -> Result<SyncReport> {
+    let base_dir = PathBuf::from(&config.content.base_dir);
+    let vault = options
+        .vault
+        .canonicalize()
+        .with_context(|| format!("Vault not found: {}", options.vault.display()))?;
+    let state_path = base_dir.join(STATE_FILE);
+    let mut state = SyncState::load(&state_path)?;
+    if state.vault != vault {
+        // Pairs recorded for another vault say nothing about this one
+        state = SyncState {
+            vault: vault.clone(),
+            entries: BTreeMap::new(),
+        };
+    }
+
+    let in_scope = |topic: &str| options.topic.is_none() || options.topic.as_deref() == Some(topic);
+    let articles = read_articles(config, &in_scope)?;
+    let notes = read_notes(config, &vault, &in_scope)?;
+
+    // Decide what happens to each pair, and the note each article goes with
+    let mut steps: Vec<(String, PathBuf, Step)> = Vec::new();
+    let mut claimed: HashSet<PathBuf> = HashSet::new();
+    let mut unchanged = 0;
+
+    let entries: Vec<(String, SyncEntry)> = state
+        .entries
+        .iter()
+        .filter(|(key, _)| key.split_once('/').is_some_and(|(topic, _)| in_scope(topic)))
+        .map(|(key, entry)| (key.clone(), entry.clone()))
+        .collect();
+    for (key, entry) in &entries {
+        claimed.insert(entry.note.clone());
+        let note_file = vault.join(&entry.note);
+        let step = match (articles.get(key), note_file.is_file()) {
+            (Some(article), true) => {
+                let article_changed = entry.article.is_changed(&article.file);
+                let note_changed = entry.vault.is_changed(&note_file);
+                match (article_changed, note_changed) {
+                    (false, false) => {
+                        unchanged += 1;
+                        continue;
+                    }
+                    (true, false) => Step::Push { create: false },
+                    (false, true) => Step::Pull { create: false },
+                    (true, true) => resolve_conflict(options.prefer),
+                }
+            }
+            (None, true) => Step::Report(SyncAction::Missing { side: Side::Repository }),
+            (Some(_), false) => Step::Report(SyncAction::Missing { side: Side::Vault }),
+            (None, false) => {
+                state.entries.remove(key);
+                continue;
+            }
+        };
+        steps.push((key.clone(), entry.note.clone(), step));
+    }
+
+    let mut note_paths: BTreeMap<String, PathBuf> =
+        entries.iter().map(|(key, entry)| (key.clone(), entry.note.clone())).collect();
+
+    for (key, article) in &articles {
+        if note_paths.contains_key(key) {
+            continue;
+        }
+        let folder = topic_folder(config, &article.topic);
+        let matching = notes
+            .iter()
+            .find(|note| note.topic == article.topic && note.slug == article.slug && !claimed.contains(&note.path));
+
+        let (path, step) = match matching {
+            Some(note) => (note.path.clone(), Step::Match),
+            None => {
+                let mut path = folder.join(format!("{}.md", obsidian::note_name(&article.title)));
+                if claimed.contains(&path) || vault.join(&path).exists() {
+                    path = folder.join(format!("{} ({}).md", obsidian::note_name(&article.title), article.slug));
+                }
+                (path, Step::Push { create: true })
+            }
+        };
+        claimed.insert(path.clone());
+        note_paths.insert(key.clone(), path.clone());
+        steps.push((key.clone(), path, step));
+    }
+
+    for note in &notes {
+        if claimed.contains(&note.path) {
+            continue;
+        }
+        let key = SyncState::key(&note.topic, &note.slug);
+        let step = if let Some(reason) = &note.invalid_slug {
+            // The slug becomes a path in the repository, so it's never used unchecked
+            Step::Report(SyncAction::Skipped { reason: reason.clone() })
+        } else if articles.contains_key(&key) || note_paths.contains_key(&key) {
+            Step::Report(SyncAction::Skipped {
+                reason: format!("{} is already paired with another note", key),
+            })
+        } else {
+            note_paths.insert(key.clone(), note.path.clone());
+            Step::Pull { create: true }
+        };
+        claimed.insert(note.path.clone());
+        steps.push((key, note.path.clone(), step));
+    }
+
+    // Wiki-links can point at any article or note, including ones created by this sync
+    let mut index = LinkIndex::new();
+    for (key, path) in &note_paths {
+        let Some((topic, slug)) = key.split_once('/') else { continue };
+        let title = articles.get(key).map_or_else(|| note_stem(path), |article| article.title.clone());
+        let link = NoteLink {
+            topic: topic.to_string(),
+            slug: slug.to_string(),
+            note: note_stem(path),
+        };
+        index.insert(link, &title);
+    }
+
+    let journal = Journal::for_base_dir(&base_dir);
+    let mut transaction = match options.dry_run {
+        true => None,
+        false => Some(journal.begin("sync", format!("Sync with {}", vault.display()))?),
+    };
+    let mut pulled = false;
+    let mut items = Vec::new();
+
+    for (key, note_path, step) in steps {
+        let (topic, slug) = key.split_once('/').map(|(topic, slug)| (topic.to_string(), slug.to_string())).unwrap_or_default();
+        let note_file = vault.join(&note_path);
+        let mut warnings = Vec::new();
+
+        let step = match step {
+            Step::Match => {
+                let converted = obsidian::from_note(&read(&note_file)?, &note_stem(&note_path), &index)?;
+                if converted.content == read(&articles[&key].file)? {
+                    if !options.dry_run {
+                        record(&mut state, &key, &note_path, &articles[&key].file, &note_file)?;
+                    }
+                    Step::Report(SyncAction::Linked)
+                } else {
+                    resolve_conflict(options.prefer)
+                }
+            }
+            step => step,
+        };
+
+        let action = match step {
+            Step::Push { .. } if options.direction == Direction::Pull => SyncAction::Skipped {
+                reason: "changed in the repository; push to copy it".to_string(),
+            },
+            Step::Pull { .. } if options.direction == Direction::Push => SyncAction::Skipped {
+                reason: "changed in the vault; pull to copy it".to_string(),
+            },
+            Step::Push { create } => {
+                let article = &articles[&key];
+                let converted = obsidian::to_note(&read(&article.file)?, &topic, &note_stem(&note_path), &index)?;
+                if !options.dry_run {
+                    write(&note_file, &converted.content)?;
+                    record(&mut state, &key, &note_path, &article.file, &note_file)?;
+                }
+                if create {
+                    SyncAction::CreatedNote
+                } else {
+                    SyncAction::UpdatedNote
+                }
+            }
+            Step::Pull { create } => {
+                let converted = obsidian::from_note(&read(&note_file)?, &note_stem(&note_path), &index)?;
+                warnings.extend(converted.unresolved.iter().map(|name| format!("No article for [[{}]]", name)));
+                let article_file = match articles.get(&key) {
+                    Some(article) => article.file.clone(),
+                    None => {
+                        base_dir.join(&config.content.topics[&topic].directory).join(&slug).join(format!("{}.md", slug))
+                    }
+                };
+                if let Some(transaction) = transaction.as_mut() {
+                    if create {
+                        transaction.created(article_file.parent().unwrap_or(&base_dir));
+                    } else {
+                        transaction.snapshot(&article_file)?;
+                    }
+                    write(&article_file, &converted.content)?;
+                    record(&mut state, &key, &note_path, &article_file, &note_file)?;
+                    pulled = true;
+                }
+                if create {
+                    SyncAction::CreatedArticle
+                } else {
+                    SyncAction::UpdatedArticle
+                }
+            }
+            Step::Match => unreachable!("matched above"),
+            Step::Report(action) => action,
+        };
+
+        items.push(SyncItem {
+            topic,
+            slug,
+            note: note_path,
+            action,
+            warnings,
+        });
+    }
+
+    if let Some(transaction) = transaction {
+        if pulled {
+            transaction.commit()?;
+        }
+        state.save(&state_path)?;
+    }
+
+    Ok(SyncReport {
+        items,
+        unchanged,
+        dry_run: options.dry_run,
+    })
+}
+
+fn resolve_conflict(prefer: Option<Side>) -> Step {
+    match prefer {
+        Some(Side::Repository) => Step::Push { create: false },
+        Some(Side::Vault) => Step::Pull { create: false },
+        None => Step::Report(SyncAction::Conflict),
+    }
+}
+
+/// Folder of a topic in the vault
+fn topic_folder(config: &Config, topic: &str) -> PathBuf {
+    PathBuf::from(config.content.topics.get(topic).map_or(topic, |topic| topic.directory.as_str()))
+}
+
+/// Every article in the topics in scope, by `{topic}/{slug}`
+fn read_articles(config: &Config, in_scope: &dyn Fn(&str) -> bool) -> Result<BTreeMap<String, Article>> {
+    let repository = FsContentRepository::new(config);
+    let mut articles = BTreeMap::new();
+    for topic in repository.list_topics()? {
+        if !in_scope(&topic) {
+            continue;
+        }
+        for location in repository.list_articles(&topic)? {
+            let content = repository.read_article(&location)?;
+            let title = common_markdown::extract_frontmatter(&content)
+                .ok()
+                .and_then(|(fields, _)| fields.get("title").and_then(Value::as_str).map(String::from))
+                .unwrap_or_else(|| location.slug.clone());
+            articles.insert(
+                SyncState::key(&topic, &location.slug),
+                Article {
+                    topic: topic.clone(),
+                    slug: location.slug,
+                    file: location.content_file,
+                    title,
+                },
+            );
+        }
+    }
+    Ok(articles)
+}
+
+/// Every note directly inside the folder of a topic in scope
+fn read_notes(config: &Config, vault: &Path, in_scope: &dyn Fn(&str) -> bool) -> Result<Vec<Note>> {
+    let mut topics: Vec<&String> = config.content.topics.keys().filter(|topic| in_scope(topic)).collect();
+    topics.sort();
+
+    let mut notes = Vec::new();
+    for topic in topics {
+        let folder = topic_folder(config, topic);
+        let dir = vault.join(&folder);
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "md"))
+            .collect();
+        files.sort();
+
+        for file in files {
+            let name = note_stem(&file);
+            let slug = common_markdown::extract_frontmatter(&read(&file)?)
+                .ok()
+                .and_then(|(fields, _)| fields.get("slug").and_then(Value::as_str).map(String::from))
+                .unwrap_or_else(|| slug::slugify(&name));
+            let invalid_slug = common_validation::validate_slug(&slug)
+                .err()
+                .map(|error| format!("invalid slug '{}': {}", slug, error));
+            notes.push(Note {
+                topic: topic.clone(),
+                path: folder.join(file.file_name().unwrap_or_default()),
+                slug,
+                invalid_slug,
+            });
+        }
+    }
+    Ok(notes)
+}
+
+/// Record a pair as it is now
+fn record(state: &mut SyncState, key: &str, note: &Path, article_file: &Path, note_file: &Path) -> Result<()> {
+    state.entries.insert(
+        key.to_string(),
+        SyncEntry {
+            note: note.to_path_buf(),
+            article: FileStamp::of(article_file)?,
+            vault: FileStamp::of(note_file)?,
+        },
+    );
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    common_fs::write_file_atomic(path, content)?;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use clap::Parser;
+use common_cli::OutputFormatArgs;
+use content_sync::{sync, Direction, Side, SyncOptions};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(author, version, about = "Sync content with an Obsidian vault")]
+struct Args {
+    /// The Obsidian vault directory
+    vault: PathBuf,
+
+    /// Which way to copy changes
+    #[arg(long, value_enum, default_value_t = Direction::Both)]
+    direction: Direction,
+
+    /// Side whose changes win when both changed
+    #[arg(long, value_enum)]
+    prefer: Option<Side>,
+
+    /// Only sync this topic
+    #[arg(short, long)]
+    topic: Option<String>,
+
+    /// Show what would be copied without copying anything
+    #[arg(long)]
+    dry_run: bool,
+
+    #[command(flatten)]
+    format: OutputFormatArgs,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let formatter = args.format.formatter();
+    let config = common_config::load_config()?;
+
+    let options = SyncOptions {
+        vault: args.vault,
+        direction: args.direction,
+        prefer: args.prefer,
+        topic: args.topic,
+        dry_run: args.dry_run,
+    };
+
+    formatter.print(&sync(&config, &options)?)
+}
@@ -0,0 +1,250 @@
+//! # Obsidian
+//!
+//! Converts between articles and Obsidian notes. A note is named after its
+//! article's title and links to other notes with wiki-links, which become
+//! site links to the article:
+//!
+//! | Obsidian                 | Article                           |
+//! |--------------------------|-----------------------------------|
+//! | `[[Note Name]]`          | `[Note Name](/{topic}/{slug})`    |
+//! | `[[Note Name\|text]]`    | `[text](/{topic}/{slug})`         |
+//! | `[[Note Name#Heading]]`  | `[Note Name](/{topic}/{slug}#heading)` |
+//! | `![[diagram.png]]`       | `![](diagram.png)`                |
+//! | `date`                   | `published`                       |
+//! | `tags: "#a #b"`          | `tags: [a, b]`                    |
+//!
+//! Notes only carry a `title` when it differs from their name. Links to
+//! headings, and wiki-links that match no article, are left as they are.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+
+static WIKI_LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(!?)\[\[([^\[\]|#]+)(#[^\[\]|]*)?(?:\|([^\[\]]*))?\]\]").expect("valid regex"));
+
+static SITE_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(^|[^!\]])\[([^\[\]]*)\]\((?:/([A-Za-z0-9_-]+)/([A-Za-z0-9_-]+)|\.\./([A-Za-z0-9_-]+))/?\)")
+        .expect("valid regex")
+});
+
+const IMAGE_EXTENSIONS: [&str; 8] = ["png", "jpg", "jpeg", "gif", "svg", "webp", "avif", "bmp"];
+
+/// An article that notes can link to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteLink {
+    pub topic: String,
+    pub slug: String,
+    /// Name of the article's note, without the `.md` extension
+    pub note: String,
+}
+
+/// Every article notes can link to, by the names a wiki-link may use
+#[derive(Debug, Clone, Default)]
+pub struct LinkIndex {
+    by_name: HashMap<String, NoteLink>,
+    by_article: HashMap<(String, String), NoteLink>,
+}
+
+impl LinkIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an article, reachable by its note name, title and slug
+    ///
+    /// Names already taken by an earlier article keep pointing at it.
+    pub fn insert(&mut self, link: NoteLink, title: &str) {
+        for name in [link.note.as_str(), title, link.slug.as_str()] {
+            self.by_name.entry(name.to_lowercase()).or_insert_with(|| link.clone());
+        }
+        self.by_article.insert((link.topic.clone(), link.slug.clone()), link);
+    }
+
+    /// The article a wiki-link target names, ignoring case
+    pub fn resolve(&self, name: &str) -> Option<&NoteLink> {
+        self.by_name.get(&name.trim().to_lowercase())
+    }
+
+    /// The note for an article
+    pub fn note_for(&self, topic: &str, slug: &str) -> Option<&NoteLink> {
+        self.by_article.get(&(topic.to_string(), slug.to_string()))
+    }
+}
+
+/// A note or article produced by a conversion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Converted {
+    pub content: String,
+    /// Wiki-link targets that match no article
+    pub unresolved: Vec<String>,
+}
+
+/// File name for a note, from an article title
+///
+/// Characters Obsidian doesn't allow in note names are replaced with `-`.
+pub fn note_name(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .map(|c| if "\\/:*?\"<>|#^[]".contains(c) { '-' } else { c })
+        .collect();
+    let name = name.trim().trim_start_matches('.').to_string();
+    if name.is_empty() {
+        "Untitled".to_string()
+    } else {
+        name
+    }
+}
+
+/// Convert an article in `topic` to the content of the note `note`
+///
+/// # Errors
+///
+/// Returns an error if the article's frontmatter cannot be parsed
+pub fn to_note(article: &str, topic: &str, note: &str, index: &LinkIndex) -> anyhow::Result<Converted> {
+    let (mut fields, body) = split(article)?;
+
+    if fields.get("title").and_then(Value::as_str) == Some(note) {
+        fields.remove("title");
+    }
+    rename(&mut fields, "published", "date");
+    if fields.get("date").and_then(Value::as_str).is_some_and(|date| date.eq_ignore_ascii_case("DRAFT")) {
+        fields.remove("date");
+        if !fields.contains_key("draft") {
+            fields.insert(Value::from("draft"), Value::from(true));
+        }
+    }
+
+    let body = SITE_LINK.replace_all(&body, |captures: &Captures| {
+        let (link_topic, slug) = match (captures.get(3), captures.get(4), captures.get(5)) {
+            (Some(link_topic), Some(slug), _) => (link_topic.as_str(), slug.as_str()),
+            (_, _, Some(slug)) => (topic, slug.as_str()),
+            _ => return captures[0].to_string(),
+        };
+        let Some(link) = index.note_for(link_topic, slug) else {
+            return captures[0].to_string();
+        };
+        let text = &captures[2];
+        let link = if text.is_empty() || text == link.note {
+            format!("[[{}]]", link.note)
+        } else {
+            format!("[[{}|{}]]", link.note, text)
+        };
+        format!("{}{}", &captures[1], link)
+    });
+
+    Ok(Converted {
+        content: join(&fields, &body)?,
+        unresolved: Vec::new(),
+    })
+}
+
+/// Convert the note `note`, in the folder of `topic`, to an article
+///
+/// # Errors
+///
+/// Returns an error if the note's frontmatter cannot be parsed
+pub fn from_note(note_content: &str, note: &str, index: &LinkIndex) -> anyhow::Result<Converted> {
+    let (fields, body) = split(note_content)?;
+
+    let mut article = Mapping::new();
+    let title = fields.get("title").cloned().unwrap_or_else(|| Value::from(note));
+    article.insert(Value::from("title"), title);
+    for (key, value) in fields {
+        let key = match key.as_str() {
+            Some("title") => continue,
+            Some("date") => Value::from("published"),
+            Some("tags") => {
+                article.insert(key, normalize_tags(value));
+                continue;
+            }
+            _ => key,
+        };
+        article.insert(key, value);
+    }
+
+    let mut unresolved = Vec::new();
+    let body = WIKI_LINK.replace_all(&body, |captures: &Captures| {
+        let target = captures[2].trim();
+        let heading = captures.get(3).map(|heading| heading.as_str().trim_start_matches('#'));
+        let alias = captures.get(4).map(|alias| alias.as_str());
+
+        if !captures[1].is_empty() {
+            let is_image = target
+                .rsplit_once('.')
+                .is_some_and(|(_, extension)| IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str()));
+            return if is_image {
+                format!("![{}]({})", alias.unwrap_or_default(), target.replace(' ', "%20"))
+            } else {
+                captures[0].to_string()
+            };
+        }
+
+        let Some(link) = index.resolve(target) else {
+            if !unresolved.iter().any(|name| name == target) {
+                unresolved.push(target.to_string());
+            }
+            return captures[0].to_string();
+        };
+        let anchor = heading.map(|heading| format!("#{}", slug::slugify(heading))).unwrap_or_default();
+        format!("[{}](/{}/{}{})", alias.unwrap_or(target), link.topic, link.slug, anchor)
+    });
+
+    Ok(Converted {
+        content: join(&article, &body)?,
+        unresolved,
+    })
+}
+
+/// Tags as a list without `#`, from a list or a string separated by spaces or commas
+fn normalize_tags(tags: Value) -> Value {
+    let tags: Vec<String> = match tags {
+        Value::Sequence(tags) => tags.iter().filter_map(Value::as_str).map(String::from).collect(),
+        Value::String(tags) => tags
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(String::from)
+            .collect(),
+        other => return other,
+    };
+    Value::Sequence(
+        tags.iter()
+            .map(|tag| tag.trim().trim_start_matches('#'))
+            .filter(|tag| !tag.is_empty())
+            .map(Value::from)
+            .collect(),
+    )
+}
+
+fn rename(fields: &mut Mapping, from: &str, to: &str) {
+    if fields.contains_key(to) {
+        return;
+    }
+    // Rebuild the mapping so the renamed field keeps its position
+    let renamed = std::mem::take(fields)
+        .into_iter()
+        .map(|(key, value)| if key.as_str() == Some(from) { (Value::from(to), value) } else { (key, value) })
+        .collect();
+    *fields = renamed;
+}
+
+/// Frontmatter fields and body of a markdown file; a file without
+/// frontmatter has no fields
+fn split(content: &str) -> anyhow::Result<(Mapping, String)> {
+    if !content.starts_with("---") {
+        return Ok((Mapping::new(), content.to_string()));
+    }
+    let (frontmatter, body) = common_markdown::extract_frontmatter(content)?;
+    match frontmatter {
+        Value::Mapping(fields) => Ok((fields, body)),
+        Value::Null => Ok((Mapping::new(), body)),
+        _ => anyhow::bail!("Frontmatter is not a mapping"),
+    }
+}
+
+fn join(fields: &Mapping, body: &str) -> anyhow::Result<String> {
+    if fields.is_empty() {
+        return Ok(body.to_string());
+    }
+    Ok(format!("---\n{}---\n{}", serde_yaml::to_string(fields)?, body))
+}
@@ -0,0 +1,112 @@
+//! # Sync State
+//!
+//! What each side looked like after the last sync, so the next one can tell
+//! which side changed. A file counts as unchanged when its modification
+//! time matches the recorded one, or, failing that, when its contents still
+//! hash the same.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Location of the state file, relative to the content base directory
+pub const STATE_FILE: &str = ".writing/obsidian-sync.yaml";
+
+/// A file as it was when last synced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    /// SHA-256 hash of the contents, as lowercase hex
+    pub hash: String,
+    /// Modification time in milliseconds since the Unix epoch
+    pub modified: u128,
+}
+
+impl FileStamp {
+    /// Stamp a file as it is now
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read
+    pub fn of(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self {
+            hash: hash(&data),
+            modified: modified(path),
+        })
+    }
+
+    /// Check whether the file at `path` differs from this stamp
+    pub fn is_changed(&self, path: &Path) -> bool {
+        if modified(path) == self.modified {
+            return false;
+        }
+        std::fs::read(path).map_or(true, |data| hash(&data) != self.hash)
+    }
+}
+
+fn hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+fn modified(path: &Path) -> u128 {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_millis())
+}
+
+/// An article paired with a note
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncEntry {
+    /// The note, relative to the vault
+    pub note: PathBuf,
+    pub article: FileStamp,
+    pub vault: FileStamp,
+}
+
+/// Pairs of articles and notes, keyed by `{topic}/{slug}`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    /// The vault the state belongs to
+    #[serde(default)]
+    pub vault: PathBuf,
+    #[serde(default)]
+    pub entries: BTreeMap<String, SyncEntry>,
+}
+
+impl SyncState {
+    /// Read the state from `path`, or start afresh if there is none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid sync state in {}", path.display()))
+    }
+
+    /// Write the state to `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        common_fs::write_file_atomic(path, &serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Key of an article's entry
+    pub fn key(topic: &str, slug: &str) -> String {
+        format!("{}/{}", topic, slug)
+    }
+}
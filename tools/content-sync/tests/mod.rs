@@ -0,0 +1,7 @@
+//! Test modules for content-sync
+
+// Unit tests
+#[path = "unit/obsidian_tests.rs"]
+mod obsidian_tests;
+#[path = "unit/sync_tests.rs"]
+mod sync_tests;
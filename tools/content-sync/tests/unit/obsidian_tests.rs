@@ -0,0 +1,72 @@
+use content_sync::obsidian::{from_note, note_name, to_note, LinkIndex, NoteLink};
+
+#[cfg(test)]
+mod obsidian_tests {
+    use super::*;
+
+    fn index() -> LinkIndex {
+        let mut index = LinkIndex::new();
+        index.insert(
+            NoteLink {
+                topic: "blog".to_string(),
+                slug: "hello-world".to_string(),
+                note: "Hello, World".to_string(),
+            },
+            "Hello, World",
+        );
+        index.insert(
+            NoteLink {
+                topic: "notes".to_string(),
+                slug: "why-rust".to_string(),
+                note: "Why Rust- A Retrospective".to_string(),
+            },
+            "Why Rust: A Retrospective",
+        );
+        index
+    }
+
+    #[test]
+    fn test_note_name_replaces_forbidden_characters() {
+        assert_eq!(note_name("Why Rust: A Retrospective"), "Why Rust- A Retrospective");
+        assert_eq!(note_name("C# [draft]"), "C- -draft-");
+        assert_eq!(note_name(" .hidden "), "hidden");
+        assert_eq!(note_name("///"), "---");
+        assert_eq!(note_name("  "), "Untitled");
+    }
+
+    #[test]
+    fn test_from_note_converts_wiki_links_and_frontmatter() {
+        let note = "---\ndate: 2024-05-01\ntags: \"#ideas, #rust\"\naliases: [Fresh]\n---\nSee [[Hello, World]], [[hello-world|the intro]] and [[Why Rust: A Retrospective#Getting Started]].\n\n![[diagram one.png]] ![[Other Note]] [[Nowhere]] [[Nowhere]]\n";
+        let converted = from_note(note, "Fresh Idea", &index()).unwrap();
+
+        assert_eq!(
+            converted.content,
+            "---\ntitle: Fresh Idea\npublished: 2024-05-01\ntags:\n- ideas\n- rust\naliases:\n- Fresh\n---\nSee [Hello, World](/blog/hello-world), [the intro](/blog/hello-world) and [Why Rust: A Retrospective](/notes/why-rust#getting-started).\n\n![](diagram%20one.png) ![[Other Note]] [[Nowhere]] [[Nowhere]]\n"
+        );
+        assert_eq!(converted.unresolved, vec!["Nowhere"]);
+    }
+
+    #[test]
+    fn test_from_note_without_frontmatter_uses_the_note_name() {
+        let converted = from_note("Just text.\n", "Quick Thought", &index()).unwrap();
+        assert_eq!(converted.content, "---\ntitle: Quick Thought\n---\nJust text.\n");
+    }
+
+    #[test]
+    fn test_to_note_converts_site_links_and_frontmatter() {
+        let article = "---\ntitle: 'Why Rust: A Retrospective'\npublished: 2024-01-31\ntags:\n- rust\n---\nRead [Hello, World](/blog/hello-world) first, then [this](../hello-world/).\n\n![A diagram](/blog/hello-world) [anchored](/blog/hello-world#intro) [unknown](/blog/unknown)\n";
+        let converted = to_note(article, "notes", "Why Rust- A Retrospective", &index()).unwrap();
+
+        assert_eq!(
+            converted.content,
+            "---\ntitle: 'Why Rust: A Retrospective'\ndate: 2024-01-31\ntags:\n- rust\n---\nRead [[Hello, World]] first, then [this](../hello-world/).\n\n![A diagram](/blog/hello-world) [anchored](/blog/hello-world#intro) [unknown](/blog/unknown)\n"
+        );
+    }
+
+    #[test]
+    fn test_to_note_drops_a_title_matching_the_note_and_marks_drafts() {
+        let article = "---\ntitle: Hello, World\npublished: DRAFT\n---\nSee [it](../hello-world).\n";
+        let converted = to_note(article, "blog", "Hello, World", &index()).unwrap();
+        assert_eq!(converted.content, "---\ndraft: true\n---\nSee [[Hello, World|it]].\n");
+    }
+}
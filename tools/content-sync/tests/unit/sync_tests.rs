@@ -0,0 +1,229 @@
+use common_fs::Journal;
+use common_models::Config;
+use content_sync::{sync, Direction, Side, SyncAction, SyncOptions, SyncReport, SyncState, STATE_FILE};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod sync_tests {
+    use super::*;
+
+    struct Fixture {
+        _temp: TempDir,
+        base: PathBuf,
+        vault: PathBuf,
+        config: Config,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let temp = TempDir::new().unwrap();
+            let base = temp.path().join("content");
+            let vault = temp.path().join("vault");
+            fs::create_dir_all(vault.join(".obsidian")).unwrap();
+
+            let config = common_test_utils::config_for(&base, &["blog", "notes"]);
+
+            let fixture = Self {
+                _temp: temp,
+                base,
+                vault,
+                config,
+            };
+            fixture.write_article(
+                "blog",
+                "hello",
+                "---\ntitle: Hello\npublished: 2024-01-01\n---\nHello! Next: [the second post](/blog/second).\n",
+            );
+            fixture.write_article("blog", "second", "---\ntitle: Second\npublished: 2024-02-01\n---\nMore.\n");
+            fixture.write_note("blog/Fresh Idea.md", "---\ntags: \"#ideas\"\n---\nBuilds on [[Hello]] and [[Nowhere]].\n");
+            fixture
+        }
+
+        fn article(&self, topic: &str, slug: &str) -> PathBuf {
+            self.base.join(topic).join(slug).join(format!("{}.md", slug))
+        }
+
+        fn write_article(&self, topic: &str, slug: &str, content: &str) {
+            touch(&self.article(topic, slug), content);
+        }
+
+        fn write_note(&self, path: &str, content: &str) {
+            touch(&self.vault.join(path), content);
+        }
+
+        fn sync(&self, direction: Direction, prefer: Option<Side>, dry_run: bool) -> SyncReport {
+            let options = SyncOptions {
+                vault: self.vault.clone(),
+                direction,
+                prefer,
+                topic: None,
+                dry_run,
+            };
+            sync(&self.config, &options).unwrap()
+        }
+    }
+
+    /// Write a file and move its modification time forward, so it reads as
+    /// changed however quickly it follows a sync
+    fn touch(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(60);
+        File::options().write(true).open(path).unwrap().set_modified(later).unwrap();
+    }
+
+    fn actions(report: &SyncReport) -> Vec<(String, SyncAction)> {
+        report
+            .items
+            .iter()
+            .map(|item| (format!("{}/{}", item.topic, item.slug), item.action.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_first_sync_copies_new_files_both_ways() {
+        let fixture = Fixture::new();
+
+        let report = fixture.sync(Direction::Both, None, false);
+        assert_eq!(
+            actions(&report),
+            vec![
+                ("blog/hello".to_string(), SyncAction::CreatedNote),
+                ("blog/second".to_string(), SyncAction::CreatedNote),
+                ("blog/fresh-idea".to_string(), SyncAction::CreatedArticle),
+            ]
+        );
+        assert_eq!(report.items[2].warnings, vec!["No article for [[Nowhere]]"]);
+
+        assert_eq!(
+            fs::read_to_string(fixture.vault.join("blog/Hello.md")).unwrap(),
+            "---\ndate: 2024-01-01\n---\nHello! Next: [[Second|the second post]].\n"
+        );
+        assert_eq!(
+            fs::read_to_string(fixture.article("blog", "fresh-idea")).unwrap(),
+            "---\ntitle: Fresh Idea\ntags:\n- ideas\n---\nBuilds on [Hello](/blog/hello) and [[Nowhere]].\n"
+        );
+
+        let state = SyncState::load(&fixture.base.join(STATE_FILE)).unwrap();
+        assert_eq!(state.entries.len(), 3);
+        assert_eq!(state.entries["blog/fresh-idea"].note, Path::new("blog/Fresh Idea.md"));
+
+        let again = fixture.sync(Direction::Both, None, false);
+        assert!(again.items.is_empty());
+        assert_eq!(again.unchanged, 3);
+    }
+
+    #[test]
+    fn test_changes_are_copied_to_the_other_side() {
+        let fixture = Fixture::new();
+        fixture.sync(Direction::Both, None, false);
+
+        fixture.write_note("blog/Second.md", "---\ndate: 2024-02-01\n---\nMore, edited in Obsidian.\n");
+        fixture.write_article("blog", "hello", "---\ntitle: Hello\npublished: 2024-01-01\n---\nHello again.\n");
+
+        let report = fixture.sync(Direction::Both, None, false);
+        assert_eq!(
+            actions(&report),
+            vec![
+                ("blog/hello".to_string(), SyncAction::UpdatedNote),
+                ("blog/second".to_string(), SyncAction::UpdatedArticle),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(fixture.article("blog", "second")).unwrap(),
+            "---\ntitle: Second\npublished: 2024-02-01\n---\nMore, edited in Obsidian.\n"
+        );
+        assert_eq!(
+            fs::read_to_string(fixture.vault.join("blog/Hello.md")).unwrap(),
+            "---\ndate: 2024-01-01\n---\nHello again.\n"
+        );
+
+        // The articles the sync wrote can be restored from the journal
+        Journal::for_base_dir(&fixture.base).undo_last().unwrap().unwrap();
+        assert_eq!(
+            fs::read_to_string(fixture.article("blog", "second")).unwrap(),
+            "---\ntitle: Second\npublished: 2024-02-01\n---\nMore.\n"
+        );
+    }
+
+    #[test]
+    fn test_changes_on_both_sides_conflict() {
+        let fixture = Fixture::new();
+        fixture.sync(Direction::Both, None, false);
+
+        fixture.write_note("blog/Hello.md", "From the vault.\n");
+        fixture.write_article("blog", "hello", "---\ntitle: Hello\n---\nFrom the repository.\n");
+
+        let report = fixture.sync(Direction::Both, None, false);
+        assert_eq!(actions(&report), vec![("blog/hello".to_string(), SyncAction::Conflict)]);
+        assert_eq!(report.conflicts(), 1);
+        assert_eq!(fs::read_to_string(fixture.vault.join("blog/Hello.md")).unwrap(), "From the vault.\n");
+
+        let report = fixture.sync(Direction::Both, Some(Side::Vault), false);
+        assert_eq!(actions(&report), vec![("blog/hello".to_string(), SyncAction::UpdatedArticle)]);
+        assert_eq!(
+            fs::read_to_string(fixture.article("blog", "hello")).unwrap(),
+            "---\ntitle: Hello\n---\nFrom the vault.\n"
+        );
+    }
+
+    #[test]
+    fn test_direction_and_dry_run_limit_what_is_written() {
+        let fixture = Fixture::new();
+
+        let report = fixture.sync(Direction::Both, None, true);
+        assert_eq!(report.items.len(), 3);
+        assert!(!fixture.vault.join("blog/Hello.md").exists());
+        assert!(!fixture.base.join(STATE_FILE).exists());
+
+        let report = fixture.sync(Direction::Push, None, false);
+        assert!(matches!(report.items[2].action, SyncAction::Skipped { .. }));
+        assert!(fixture.vault.join("blog/Hello.md").exists());
+        assert!(!fixture.article("blog", "fresh-idea").exists());
+    }
+
+    #[test]
+    fn test_deletions_are_reported_not_copied() {
+        let fixture = Fixture::new();
+        fixture.sync(Direction::Both, None, false);
+
+        fs::remove_file(fixture.vault.join("blog/Second.md")).unwrap();
+        let report = fixture.sync(Direction::Both, None, false);
+        assert_eq!(
+            actions(&report),
+            vec![("blog/second".to_string(), SyncAction::Missing { side: Side::Vault })]
+        );
+        assert!(fixture.article("blog", "second").exists());
+    }
+
+    #[test]
+    fn test_notes_with_invalid_slugs_are_skipped() {
+        let fixture = Fixture::new();
+        fixture.write_note("blog/Escape.md", "---\nslug: ../../outside\n---\nNope.\n");
+
+        let report = fixture.sync(Direction::Pull, None, false);
+        let skipped = report.items.iter().find(|item| item.note == Path::new("blog/Escape.md")).unwrap();
+        assert!(matches!(&skipped.action, SyncAction::Skipped { reason } if reason.starts_with("invalid slug")));
+        assert!(!fixture.base.parent().unwrap().join("outside").exists());
+        assert!(!fixture.base.join("outside").exists());
+    }
+
+    #[test]
+    fn test_existing_matching_files_are_linked() {
+        let fixture = Fixture::new();
+        fs::remove_file(fixture.vault.join("blog/Fresh Idea.md")).unwrap();
+        fixture.write_note("blog/Second.md", "---\ndate: 2024-02-01\n---\nMore.\n");
+
+        let report = fixture.sync(Direction::Both, None, false);
+        assert_eq!(
+            actions(&report),
+            vec![
+                ("blog/hello".to_string(), SyncAction::CreatedNote),
+                ("blog/second".to_string(), SyncAction::Linked),
+            ]
+        );
+    }
+}
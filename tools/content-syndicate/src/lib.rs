@@ -0,0 +1,423 @@
+//! # Content Syndicate
+//!
+//! Keeps track of the copies of articles posted elsewhere, and tells the
+//! pages articles link to about them:
+//!
+//! * `add` records the address of a copy in the article's `syndicated_to`
+//!   field, which the build turns into a `rel="syndication"` link
+//! * `list` shows the copies of each article
+//! * `webmentions` sends a Webmention to each page a published article links
+//!   to, when that page accepts them
+//!
+//! Webmentions are logged per article in the content repository (see
+//! [`log`]), so each link is mentioned once however often they are sent.
+//! Articles are published at `{site_url}/{topic}/{slug}`, which is the
+//! source of their mentions.
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::{FsContentRepository, Journal};
+use common_models::{Config, Frontmatter};
+use common_traits::{ArticleLocation, ContentRepository};
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+use std::path::PathBuf;
+use url::Url;
+
+pub mod log;
+pub mod webmention;
+
+pub use log::{Mention, MentionStatus, SendLog, LOG_FILE};
+
+/// Frontmatter field listing the addresses of an article's syndicated copies
+pub const SYNDICATED_TO_FIELD: &str = "syndicated_to";
+
+/// Copies of one article
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyndicatedArticle {
+    pub topic: String,
+    pub slug: String,
+    pub title: String,
+    pub urls: Vec<String>,
+    /// Addresses added by this command; empty when listing
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+}
+
+impl DisplayResult for SyndicatedArticle {
+    fn to_display(&self) -> String {
+        let mut lines = vec![format!("{}/{}", self.topic, self.slug).bold().to_string()];
+        for url in &self.urls {
+            let marker = if self.added.contains(url) { "+".green() } else { "-".normal() };
+            lines.push(format!("  {} {}", marker, url));
+        }
+        if self.added.is_empty() && !self.urls.is_empty() {
+            lines.push("Already recorded".dimmed().to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+/// Every article with syndicated copies
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SyndicationList {
+    pub articles: Vec<SyndicatedArticle>,
+}
+
+impl DisplayResult for SyndicationList {
+    fn to_display(&self) -> String {
+        if self.articles.is_empty() {
+            return "No articles have been syndicated".to_string();
+        }
+        self.articles
+            .iter()
+            .map(|article| {
+                let mut lines = vec![format!("{} ({}/{})", article.title.bold(), article.topic, article.slug)];
+                lines.extend(article.urls.iter().map(|url| format!("  {}", url)));
+                lines.join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Record the addresses of an article's syndicated copies in its frontmatter
+///
+/// Addresses already recorded are left as they are. The change is recorded
+/// in the journal, so `write undo` reverts it.
+///
+/// # Errors
+///
+/// Returns an error if an address is not an `http` or `https` URL, the
+/// article cannot be found, or its content cannot be read or written
+pub fn add_syndication(config: &Config, slug: &str, topic: Option<&str>, urls: &[String]) -> Result<SyndicatedArticle> {
+    for url in urls {
+        if !is_web_url(url) {
+            return Err(anyhow::anyhow!("'{}' is not an http or https URL", url));
+        }
+    }
+
+    let repository = FsContentRepository::new(config);
+    let location = find_article(&repository, slug, topic)?;
+    let content = repository.read_article(&location)?;
+    let (frontmatter, _) = common_markdown::extract_frontmatter_and_content(&content)?;
+
+    let mut recorded: Vec<String> = frontmatter.syndicated_to().into_iter().map(String::from).collect();
+    let mut added = Vec::new();
+    for url in urls.iter().map(|url| url.trim()) {
+        if !recorded.iter().any(|existing| existing == url) {
+            recorded.push(url.to_string());
+            added.push(url.to_string());
+        }
+    }
+
+    if !added.is_empty() {
+        let value = serde_yaml::Value::Sequence(recorded.iter().cloned().map(Into::into).collect());
+        let updated = content_edit::sections::set_frontmatter_field(&content, SYNDICATED_TO_FIELD, value)?;
+
+        let mut transaction = Journal::for_base_dir(&config.content.base_dir)
+            .begin("syndicate", format!("Record syndication of {}/{}", location.topic, location.slug))?;
+        transaction.snapshot(&location.content_file)?;
+        common_fs::write_file(&location.content_file, &updated)?;
+        transaction.commit()?;
+    }
+
+    Ok(SyndicatedArticle {
+        topic: location.topic,
+        slug: location.slug,
+        title: frontmatter.title,
+        urls: recorded,
+        added,
+    })
+}
+
+/// List the articles that have syndicated copies, optionally in one topic
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be read
+pub fn list_syndication(config: &Config, topic: Option<&str>) -> Result<SyndicationList> {
+    let mut articles = Vec::new();
+    for (location, frontmatter, _) in read_articles(config, topic, None)? {
+        let urls: Vec<String> = frontmatter.syndicated_to().into_iter().map(String::from).collect();
+        if !urls.is_empty() {
+            articles.push(SyndicatedArticle {
+                topic: location.topic,
+                slug: location.slug,
+                title: frontmatter.title,
+                urls,
+                added: Vec::new(),
+            });
+        }
+    }
+    Ok(SyndicationList { articles })
+}
+
+/// Which articles to send Webmentions for
+#[derive(Debug, Clone, Default)]
+pub struct WebmentionOptions {
+    pub topic: Option<String>,
+    pub slug: Option<String>,
+    /// Only articles published on or after this date
+    pub since: Option<NaiveDate>,
+    /// Report the links that would be mentioned without fetching or sending anything
+    pub dry_run: bool,
+}
+
+/// One link of a published article
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MentionItem {
+    pub topic: String,
+    pub slug: String,
+    /// Address of the article
+    pub source: String,
+    /// Address of the linked page
+    pub target: String,
+    /// What happened; not set in a dry run
+    #[serde(flatten)]
+    pub status: Option<MentionStatus>,
+}
+
+/// The Webmentions sent, or that would be sent
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WebmentionReport {
+    pub items: Vec<MentionItem>,
+    /// Links left alone because the log already settles them
+    pub logged: usize,
+    pub dry_run: bool,
+}
+
+impl WebmentionReport {
+    pub fn sent(&self) -> usize {
+        self.count(|status| matches!(status, MentionStatus::Sent { .. }))
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|status| matches!(status, MentionStatus::Failed { .. }))
+    }
+
+    fn count(&self, wanted: impl Fn(&MentionStatus) -> bool) -> usize {
+        self.items.iter().filter(|item| item.status.as_ref().is_some_and(&wanted)).count()
+    }
+}
+
+impl DisplayResult for WebmentionReport {
+    fn to_display(&self) -> String {
+        let mut lines = Vec::new();
+        let mut article = None;
+
+        for item in &self.items {
+            let name = format!("{}/{}", item.topic, item.slug);
+            if article.as_ref() != Some(&name) {
+                lines.push(name.bold().to_string());
+                article = Some(name);
+            }
+            lines.push(match &item.status {
+                None => format!("  {} {}", "→".cyan(), item.target),
+                Some(MentionStatus::Sent { code, .. }) => format!("  {} {} ({})", "✓".green(), item.target, code),
+                Some(MentionStatus::NoEndpoint) => {
+                    format!("  {} {} {}", "-".dimmed(), item.target, "(no endpoint)".dimmed())
+                }
+                Some(MentionStatus::Failed { error }) => format!("  {} {}: {}", "✗".red(), item.target, error),
+            });
+        }
+
+        if self.dry_run {
+            lines.push(format!(
+                "{} {} links, {} already logged",
+                "Would mention".bold(),
+                self.items.len(),
+                self.logged
+            ));
+        } else {
+            lines.push(format!(
+                "{} {} links, {} failed, {} without an endpoint, {} already logged",
+                "Mentioned".bold(),
+                self.sent(),
+                self.failed(),
+                self.items.len() - self.sent() - self.failed(),
+                self.logged
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Send Webmentions for the links of published articles
+///
+/// Each external link that the log does not already settle is mentioned,
+/// and the outcome logged after each article. Links to the site itself and
+/// to the article's own syndicated copies are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the site URL is not configured, or the content or
+/// log cannot be read or written
+pub fn send_webmentions(config: &Config, options: &WebmentionOptions) -> Result<WebmentionReport> {
+    let site_url = config
+        .publication
+        .site_url
+        .as_deref()
+        .context("Set publication.site_url; it is the source address of Webmentions")?;
+    let log_path = PathBuf::from(&config.content.base_dir).join(LOG_FILE);
+    let mut log = SendLog::load(&log_path)?;
+    let mut report = WebmentionReport {
+        dry_run: options.dry_run,
+        ..Default::default()
+    };
+    let now = Utc::now();
+
+    for (location, frontmatter, body) in read_articles(config, options.topic.as_deref(), options.slug.as_deref())? {
+        let published = frontmatter.published_at.as_ref().and_then(|date| date.date());
+        let is_published = published.is_some()
+            && !frontmatter.is_draft.unwrap_or(false)
+            && !frontmatter.is_archived()
+            && !frontmatter.is_scheduled_after(now);
+        if !is_published || options.since.is_some_and(|since| published < Some(since)) {
+            continue;
+        }
+
+        let key = SendLog::key(&location.topic, &location.slug);
+        let source = content_build::canonical_url_for(&frontmatter, &location.topic, &location.slug, site_url);
+        let copies = frontmatter.syndicated_to();
+        let mut changed = false;
+
+        for target in outbound_links(&body, site_url) {
+            if copies.contains(&target.as_str()) {
+                continue;
+            }
+            if log.get(&key, &target).is_some_and(|mention| mention.status.is_settled()) {
+                report.logged += 1;
+                continue;
+            }
+
+            let status = if options.dry_run {
+                None
+            } else {
+                let status = mention(&source, &target);
+                log.record(
+                    &key,
+                    Mention {
+                        target: target.clone(),
+                        status: status.clone(),
+                        at: Utc::now(),
+                    },
+                );
+                changed = true;
+                Some(status)
+            };
+            report.items.push(MentionItem {
+                topic: location.topic.clone(),
+                slug: location.slug.clone(),
+                source: source.clone(),
+                target,
+                status,
+            });
+        }
+
+        // Save as we go, so an interrupted run doesn't mention the same links again
+        if changed {
+            log.save(&log_path)?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Discover the endpoint of `target` and send it a mention from `source`
+fn mention(source: &str, target: &str) -> MentionStatus {
+    match webmention::discover(target) {
+        Ok(Some(endpoint)) => match webmention::send(&endpoint, source, target) {
+            Ok(code) => MentionStatus::Sent {
+                endpoint: endpoint.to_string(),
+                code,
+            },
+            Err(err) => MentionStatus::Failed {
+                error: format!("{:#}", err),
+            },
+        },
+        Ok(None) => MentionStatus::NoEndpoint,
+        Err(err) => MentionStatus::Failed {
+            error: format!("{:#}", err),
+        },
+    }
+}
+
+/// The `http` and `https` links in markdown that point away from the site,
+/// without fragments or duplicates
+pub fn outbound_links(markdown: &str, site_url: &str) -> Vec<String> {
+    let site_host = Url::parse(site_url).ok().and_then(|url| url.host_str().map(String::from));
+    let mut links: Vec<String> = Vec::new();
+
+    for event in Parser::new(markdown) {
+        let Event::Start(Tag::Link(_, destination, _)) = event else {
+            continue;
+        };
+        let Ok(mut url) = Url::parse(&destination) else {
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().map(String::from) == site_host {
+            continue;
+        }
+        url.set_fragment(None);
+        let link = url.to_string();
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+
+    links
+}
+
+fn is_web_url(url: &str) -> bool {
+    Url::parse(url.trim()).is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+}
+
+/// Find an article by slug, in `topic` when given
+fn find_article(repository: &FsContentRepository, slug: &str, topic: Option<&str>) -> Result<ArticleLocation> {
+    if let Some(topic) = topic {
+        return repository
+            .find_article(topic, slug)?
+            .with_context(|| format!("No article '{}' in topic '{}'", slug, topic));
+    }
+
+    let mut found = repository.find_articles(slug)?;
+    match found.len() {
+        0 => Err(anyhow::anyhow!("No article '{}'", slug)),
+        1 => Ok(found.remove(0)),
+        _ => Err(anyhow::anyhow!(
+            "'{}' is in more than one topic ({}); pass --topic",
+            slug,
+            found.iter().map(|location| location.topic.as_str()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Every article with readable frontmatter, optionally in one topic or with one slug
+fn read_articles(
+    config: &Config,
+    topic: Option<&str>,
+    slug: Option<&str>,
+) -> Result<Vec<(ArticleLocation, Frontmatter, String)>> {
+    let repository = FsContentRepository::new(config);
+    let mut articles = Vec::new();
+
+    for topic_key in repository.list_topics()? {
+        if topic.is_some_and(|topic| topic != topic_key) {
+            continue;
+        }
+        for location in repository.list_articles(&topic_key)? {
+            if slug.is_some_and(|slug| slug != location.slug) {
+                continue;
+            }
+            let content = repository.read_article(&location)?;
+            if let Ok((frontmatter, body)) = common_markdown::extract_frontmatter_and_content(&content) {
+                articles.push((location, frontmatter, body));
+            }
+        }
+    }
+
+    Ok(articles)
+}
@@ -0,0 +1,94 @@
+//! # Send Log
+//!
+//! Every Webmention sent for an article, kept per article so each link is
+//! mentioned once. Links whose pages have no endpoint are remembered too;
+//! failed sends are tried again on the next run.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Location of the log, relative to the content base directory
+pub const LOG_FILE: &str = ".writing/webmentions.yaml";
+
+/// The outcome of mentioning one link
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MentionStatus {
+    /// The endpoint accepted the mention with status `code`
+    Sent { endpoint: String, code: u16 },
+    /// The linked page does not accept Webmentions
+    NoEndpoint,
+    /// The page or its endpoint could not be reached, or refused the mention
+    Failed { error: String },
+}
+
+impl MentionStatus {
+    /// Whether the link needs no further mentions
+    pub fn is_settled(&self) -> bool {
+        !matches!(self, MentionStatus::Failed { .. })
+    }
+}
+
+/// A link an article was mentioned to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mention {
+    pub target: String,
+    #[serde(flatten)]
+    pub status: MentionStatus,
+    pub at: DateTime<Utc>,
+}
+
+/// Mentions by article, keyed by `{topic}/{slug}`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendLog {
+    #[serde(default)]
+    pub articles: BTreeMap<String, Vec<Mention>>,
+}
+
+impl SendLog {
+    /// Read the log from `path`, or start afresh if there is none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid Webmention log in {}", path.display()))
+    }
+
+    /// Write the log to `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        common_fs::write_file_atomic(path, &serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Key of an article's mentions
+    pub fn key(topic: &str, slug: &str) -> String {
+        format!("{}/{}", topic, slug)
+    }
+
+    /// The last mention of `target` by an article
+    pub fn get(&self, key: &str, target: &str) -> Option<&Mention> {
+        self.articles.get(key)?.iter().find(|mention| mention.target == target)
+    }
+
+    /// Record a mention, replacing any earlier one of the same target
+    pub fn record(&mut self, key: &str, mention: Mention) {
+        let mentions = self.articles.entry(key.to_string()).or_default();
+        mentions.retain(|earlier| earlier.target != mention.target);
+        mentions.push(mention);
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use colored::*;
+use common_cli::OutputFormatArgs;
+use content_syndicate::{add_syndication, list_syndication, send_webmentions, WebmentionOptions};
+
+#[derive(Parser)]
+#[command(author, version, about = "Track syndicated copies of articles and send Webmentions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    #[command(flatten)]
+    format: OutputFormatArgs,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Record where an article has been syndicated
+    Add {
+        /// Slug of the article
+        slug: String,
+
+        /// Addresses of the copies
+        #[arg(required = true)]
+        urls: Vec<String>,
+
+        /// Topic of the article, when the slug is used in more than one
+        #[arg(short, long)]
+        topic: Option<String>,
+    },
+
+    /// List the syndicated copies of each article
+    List {
+        /// Only list articles in this topic
+        #[arg(short, long)]
+        topic: Option<String>,
+    },
+
+    /// Send Webmentions to the pages published articles link to
+    Webmentions {
+        /// Only send for this article
+        slug: Option<String>,
+
+        /// Only send for articles in this topic
+        #[arg(short, long)]
+        topic: Option<String>,
+
+        /// Only send for articles published on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<NaiveDate>,
+
+        /// Show the links that would be mentioned without sending anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let formatter = cli.format.formatter();
+    let config = common_config::load_config()?;
+
+    match cli.command {
+        Commands::Add { slug, urls, topic } => {
+            formatter.print(&add_syndication(&config, &slug, topic.as_deref(), &urls)?)
+        }
+        Commands::List { topic } => formatter.print(&list_syndication(&config, topic.as_deref())?),
+        Commands::Webmentions {
+            slug,
+            topic,
+            since,
+            dry_run,
+        } => {
+            let options = WebmentionOptions {
+                topic,
+                slug,
+                since,
+                dry_run,
+            };
+
+            if formatter.is_plain() && !dry_run {
+                println!("{}", "Sending Webmentions...".green().bold());
+            }
+            formatter.print(&send_webmentions(&config, &options)?)
+        }
+    }
+}
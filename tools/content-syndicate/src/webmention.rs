@@ -0,0 +1,127 @@
+//! # Webmention
+//!
+//! Endpoint discovery and sending, following the W3C Webmention
+//! recommendation. A page advertises its endpoint in a `Link` header, or in
+//! the first `<link>` or `<a>` element with `rel="webmention"`, and the
+//! endpoint is resolved against the address the page was finally fetched
+//! from. A mention is a form `POST` of the `source` and `target` URLs.
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use reqwest::header::{CONTENT_TYPE, LINK};
+use scraper::{Html, Selector};
+use std::sync::OnceLock;
+use std::time::Duration;
+use url::Url;
+
+fn client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("content-syndicate/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// Find the Webmention endpoint of a page, or `None` if it has none
+///
+/// # Errors
+///
+/// Returns an error if the page cannot be fetched
+pub fn discover(target: &str) -> Result<Option<Url>> {
+    let response = client()
+        .get(target)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to fetch {}", target))?;
+    let base = response.url().clone();
+
+    let headers: Vec<String> = response
+        .headers()
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok().map(String::from))
+        .collect();
+    if let Some(endpoint) = endpoint_from_headers(headers.iter().map(String::as_str), &base) {
+        return Ok(Some(endpoint));
+    }
+
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("html"));
+    if !is_html {
+        return Ok(None);
+    }
+    let html = response.text().with_context(|| format!("Failed to read {}", target))?;
+    Ok(endpoint_from_html(&html, &base))
+}
+
+/// Send a Webmention, returning the status code the endpoint answered with
+///
+/// # Errors
+///
+/// Returns an error if the endpoint cannot be reached or does not accept the mention
+pub fn send(endpoint: &Url, source: &str, target: &str) -> Result<u16> {
+    let response = client()
+        .post(endpoint.clone())
+        .form(&[("source", source), ("target", target)])
+        .send()
+        .with_context(|| format!("Failed to reach {}", endpoint))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        Err(anyhow::anyhow!("{} answered {}", endpoint, status))
+    }
+}
+
+/// The endpoint advertised in `Link` header values, resolved against `base`
+pub fn endpoint_from_headers<'a>(values: impl IntoIterator<Item = &'a str>, base: &Url) -> Option<Url> {
+    for value in values {
+        let mut rest = value;
+        while let Some(start) = rest.find('<') {
+            let Some(length) = rest[start..].find('>') else {
+                break;
+            };
+            let href = &rest[start + 1..start + length];
+            let after = &rest[start + length + 1..];
+            let params_end = after.find('<').unwrap_or(after.len());
+
+            let is_webmention = after[..params_end].split(';').any(|param| {
+                let Some((name, value)) = param.split_once('=') else {
+                    return false;
+                };
+                let value = value.trim().trim_end_matches(',').trim().trim_matches('"');
+                name.trim().eq_ignore_ascii_case("rel") && has_webmention_rel(value)
+            });
+            if is_webmention {
+                if let Ok(endpoint) = base.join(href) {
+                    return Some(endpoint);
+                }
+            }
+            rest = &after[params_end..];
+        }
+    }
+
+    None
+}
+
+/// The endpoint advertised by the first `<link>` or `<a>` with `rel="webmention"`
+///
+/// An empty `href` is the page itself.
+pub fn endpoint_from_html(html: &str, base: &Url) -> Option<Url> {
+    let selector = Selector::parse("link[href], a[href]").expect("valid selector");
+    Html::parse_document(html)
+        .select(&selector)
+        .filter(|element| element.value().attr("rel").is_some_and(has_webmention_rel))
+        .find_map(|element| base.join(element.value().attr("href").unwrap_or_default().trim()).ok())
+}
+
+fn has_webmention_rel(rel: &str) -> bool {
+    rel.split_whitespace().any(|rel| rel.eq_ignore_ascii_case("webmention"))
+}
@@ -0,0 +1,7 @@
+//! Test modules for content-syndicate
+
+// Unit tests
+#[path = "unit/syndicate_tests.rs"]
+mod syndicate_tests;
+#[path = "unit/webmention_tests.rs"]
+mod webmention_tests;
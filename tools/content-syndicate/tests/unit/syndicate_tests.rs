@@ -0,0 +1,137 @@
+use common_fs::Journal;
+use common_models::Config;
+use common_test_utils::write_article;
+use content_syndicate::{
+    add_syndication, list_syndication, outbound_links, send_webmentions, Mention, MentionStatus, SendLog,
+    WebmentionOptions, LOG_FILE,
+};
+use chrono::{NaiveDate, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod syndicate_tests {
+    use super::*;
+
+    fn config(base_dir: &Path) -> Config {
+        let mut config = common_test_utils::config_for(base_dir, &["blog", "notes"]);
+        config.publication.site_url = Some("https://example.com".to_string());
+        config
+    }
+
+    fn fixture() -> (TempDir, PathBuf, Config) {
+        let temp = TempDir::new().unwrap();
+        let base = temp.path().join("content");
+        write_article(
+            &base,
+            "blog",
+            "hello",
+            "---\ntitle: Hello\npublished: 2024-03-01\n# Kept as written\ntags: [intro]\n---\nSee [a post](https://indieweb.org/Webmention#How), [it again](https://indieweb.org/Webmention), [home](https://example.com/about) and [the copy](https://dev.to/me/hello).\n",
+        );
+        write_article(&base, "blog", "old", "---\ntitle: Old\npublished: 2023-01-01\ncanonical: /archive/old\n---\n[Old link](https://old.example/page)\n");
+        write_article(&base, "notes", "draft", "---\ntitle: Draft\ndraft: true\npublished: 2024-03-02\n---\n[Link](https://draft.example/)\n");
+        let config = config(&base);
+        (temp, base, config)
+    }
+
+    #[test]
+    fn test_outbound_links_skip_the_site_and_duplicates() {
+        let markdown = "[a](https://indieweb.org/Webmention#How) [b](https://indieweb.org/Webmention) [c](/blog/x) [d](https://example.com/blog/x) [e](mailto:me@example.com) <https://other.dev/>";
+        assert_eq!(
+            outbound_links(markdown, "https://example.com"),
+            ["https://indieweb.org/Webmention", "https://other.dev/"]
+        );
+    }
+
+    #[test]
+    fn test_add_records_urls_and_can_be_undone() {
+        let (_temp, base, config) = fixture();
+        let file = base.join("blog/hello/hello.md");
+        let original = fs::read_to_string(&file).unwrap();
+
+        let article = add_syndication(&config, "hello", None, &["https://dev.to/me/hello".to_string()]).unwrap();
+        assert_eq!(article.added, ["https://dev.to/me/hello"]);
+        let article = add_syndication(
+            &config,
+            "hello",
+            Some("blog"),
+            &["https://dev.to/me/hello".to_string(), "https://medium.com/@me/hello".to_string()],
+        )
+        .unwrap();
+        assert_eq!(article.added, ["https://medium.com/@me/hello"]);
+        assert_eq!(article.urls.len(), 2);
+
+        let content = fs::read_to_string(&file).unwrap();
+        assert!(content.contains("# Kept as written\n"), "{}", content);
+        assert!(content.contains("syndicated_to:\n  - https://dev.to/me/hello\n  - https://medium.com/@me/hello\n"), "{}", content);
+
+        let list = list_syndication(&config, None).unwrap();
+        assert_eq!(list.articles.len(), 1);
+        assert_eq!(list.articles[0].slug, "hello");
+
+        assert!(add_syndication(&config, "hello", None, &["ftp://example.com/x".to_string()]).is_err());
+        assert!(add_syndication(&config, "missing", None, &["https://dev.to/x".to_string()]).is_err());
+
+        let journal = Journal::for_base_dir(&base);
+        journal.undo_last().unwrap().unwrap();
+        journal.undo_last().unwrap().unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), original);
+    }
+
+    #[test]
+    fn test_dry_run_lists_links_of_published_articles() {
+        let (_temp, base, config) = fixture();
+        add_syndication(&config, "hello", None, &["https://dev.to/me/hello".to_string()]).unwrap();
+
+        let options = WebmentionOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let report = send_webmentions(&config, &options).unwrap();
+        let targets: Vec<_> = report.items.iter().map(|item| item.target.as_str()).collect();
+        assert_eq!(targets, ["https://indieweb.org/Webmention", "https://old.example/page"]);
+        assert_eq!(report.items[0].source, "https://example.com/blog/hello");
+        assert_eq!(report.items[1].source, "https://example.com/archive/old");
+        assert!(report.items.iter().all(|item| item.status.is_none()));
+        assert!(!base.join(LOG_FILE).exists());
+
+        let options = WebmentionOptions {
+            since: NaiveDate::from_ymd_opt(2024, 1, 1),
+            ..options
+        };
+        assert_eq!(send_webmentions(&config, &options).unwrap().items.len(), 1);
+    }
+
+    #[test]
+    fn test_logged_links_are_not_mentioned_again() {
+        let (_temp, base, config) = fixture();
+        let log_path = base.join(LOG_FILE);
+        let mut log = SendLog::default();
+        let mention = |target: &str, status| Mention {
+            target: target.to_string(),
+            status,
+            at: Utc::now(),
+        };
+        log.record("blog/hello", mention("https://indieweb.org/Webmention", MentionStatus::NoEndpoint));
+        log.record(
+            "blog/old",
+            mention("https://old.example/page", MentionStatus::Failed { error: "timed out".to_string() }),
+        );
+        log.save(&log_path).unwrap();
+
+        let options = WebmentionOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let report = send_webmentions(&config, &options).unwrap();
+        // Failed mentions are tried again
+        let targets: Vec<_> = report.items.iter().map(|item| item.target.as_str()).collect();
+        assert_eq!(targets, ["https://dev.to/me/hello", "https://old.example/page"]);
+        assert_eq!(report.logged, 1);
+
+        let loaded = SendLog::load(&log_path).unwrap();
+        assert_eq!(loaded, log);
+        assert!(!loaded.get("blog/old", "https://old.example/page").unwrap().status.is_settled());
+    }
+}
@@ -0,0 +1,114 @@
+use content_syndicate::webmention::{discover, endpoint_from_headers, endpoint_from_html, send};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use url::Url;
+
+#[cfg(test)]
+mod webmention_tests {
+    use super::*;
+
+    /// Serve `connections` requests, one per connection, recording each as
+    /// `METHOD /path body`
+    fn serve(connections: usize) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        let base = address.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let mut parts = request_line.split_whitespace();
+                let (method, path) = (parts.next().unwrap(), parts.next().unwrap());
+                recorded.lock().unwrap().push(format!("{} {} {}", method, path, String::from_utf8(body).unwrap()));
+
+                let (status, headers, body) = match (method, path) {
+                    ("GET", "/post") => ("200 OK", String::new(), r#"<a href="/other">x</a><link rel="webmention" href="/endpoint">"#),
+                    ("GET", "/headed") => ("200 OK", format!("Link: <{}/endpoint>; rel=\"webmention\"\r\n", base), ""),
+                    ("GET", "/plain") => ("200 OK", String::new(), "<p>No endpoint</p>"),
+                    ("POST", "/endpoint") => ("202 Accepted", String::new(), ""),
+                    _ => ("404 Not Found", String::new(), ""),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+                    status,
+                    body.len(),
+                    headers,
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (address, requests)
+    }
+
+    #[test]
+    fn test_endpoint_from_headers() {
+        let base = Url::parse("https://example.com/posts/hello").unwrap();
+        let values = [
+            r#"<https://example.com/style.css>; rel="stylesheet""#,
+            r#"</about>; rel=author, <../mentions?a=1,b>; rel="other webmention""#,
+        ];
+        assert_eq!(
+            endpoint_from_headers(values, &base).unwrap().as_str(),
+            "https://example.com/mentions?a=1,b"
+        );
+        assert_eq!(endpoint_from_headers([r#"<https://x.dev/>; rel="webmentions""#], &base), None);
+    }
+
+    #[test]
+    fn test_endpoint_from_html_uses_the_first_in_document_order() {
+        let base = Url::parse("https://example.com/posts/hello").unwrap();
+        let html = r#"<html><head><link rel="stylesheet" href="/s.css"></head><body>
+            <a rel="nofollow webmention" href="https://mentions.example/a">first</a>
+            <link rel="webmention" href="/b"></body></html>"#;
+        assert_eq!(endpoint_from_html(html, &base).unwrap().as_str(), "https://mentions.example/a");
+
+        // An empty href is the page itself
+        assert_eq!(endpoint_from_html(r#"<link rel="webmention" href="">"#, &base), Some(base.clone()));
+        assert_eq!(endpoint_from_html("<p>Nothing</p>", &base), None);
+    }
+
+    #[test]
+    fn test_discover_and_send() {
+        let (address, requests) = serve(4);
+
+        let endpoint = discover(&format!("{}/post", address)).unwrap().unwrap();
+        assert_eq!(endpoint.as_str(), format!("{}/endpoint", address));
+        assert_eq!(
+            discover(&format!("{}/headed", address)).unwrap().unwrap().as_str(),
+            format!("{}/endpoint", address)
+        );
+        assert_eq!(discover(&format!("{}/plain", address)).unwrap(), None);
+
+        let code = send(&endpoint, "https://example.com/blog/hello", &format!("{}/post", address)).unwrap();
+        assert_eq!(code, 202);
+        let last = requests.lock().unwrap().last().cloned().unwrap();
+        assert!(last.starts_with("POST /endpoint source=https%3A%2F%2Fexample.com%2Fblog%2Fhello&target=http%3A%2F%2F127.0.0.1"), "{}", last);
+    }
+}
@@ -30,7 +30,7 @@ use anyhow::{Context, Result};
 use common_config::load_config;
 use common_fs::{read_file, write_file, create_dir_all};
 use common_models::Config;
-use common_templates::Template;
+use common_templates::{split_metadata, Template};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -221,10 +221,17 @@ pub fn list_templates() -> Result<Vec<Template>> {
         if path.extension().is_some_and(|ext| ext == "md") {
             let name = path.file_stem().unwrap().to_string_lossy().to_string();
 
-            // Read the file to determine content type
+            // Templates can describe themselves in a metadata block
             let content = read_file(&path)?;
+            let (metadata, _) = split_metadata(&content)
+                .with_context(|| format!("Invalid template file: {:?}", path))?;
 
-            // Simple content type extraction - a more robust implementation would use proper YAML parsing
+            if metadata.is_some() {
+                templates.push(Template::from_file(&path)?);
+                continue;
+            }
+
+            // Older templates only hint at their type in the content they produce
             let content_type = if content.contains("type: article") {
                 "article"
             } else if content.contains("type: note") {
@@ -0,0 +1,284 @@
+//! # Accessibility
+//!
+//! Checks the HTML that content-build writes, since that is what readers and
+//! their screen readers get. Each HTML file under the build directory is
+//! checked for:
+//!
+//! - headings that skip a level, such as an `<h4>` straight after an `<h2>`
+//! - tables without any `<th>` header cells
+//! - links whose text says nothing about where they go, such as "click here"
+//! - inline styles whose text and background colors contrast less than the
+//!   WCAG AA ratio of 4.5:1
+//!
+//! Findings are ordinary validation issues, so `severities.yaml` overrides
+//! and `--fail-on` apply to them as to every other check.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::{Severity, ValidationIssue, ValidationIssueType, ValidationResult};
+
+/// Link text that doesn't describe the link's target, compared lowercased
+const NON_DESCRIPTIVE_LINK_TEXT: &[&str] = &[
+    "click here",
+    "click",
+    "here",
+    "read more",
+    "more",
+    "learn more",
+    "link",
+    "this link",
+    "this",
+    "go",
+];
+
+/// Lowest contrast ratio WCAG AA allows for body text
+pub const MIN_CONTRAST_RATIO: f64 = 4.5;
+
+/// Check every HTML file under a build directory
+///
+/// Every file is returned, with or without issues, so the summary can count
+/// the files checked.
+///
+/// # Errors
+///
+/// Returns an error if the directory doesn't exist or a file can't be read
+pub fn validate_html_output(build_dir: &Path) -> Result<Vec<ValidationResult>> {
+    if !build_dir.is_dir() {
+        anyhow::bail!("Build directory not found: {}; run content-build first", build_dir.display());
+    }
+
+    let mut results = Vec::new();
+    for entry in WalkDir::new(build_dir).sort_by_file_name().into_iter().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let is_html = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("html"));
+        if !entry.file_type().is_file() || !is_html {
+            continue;
+        }
+
+        let html = std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+        let mut issues = Vec::new();
+        validate_accessibility(&html, &mut issues);
+        results.push(ValidationResult {
+            file_path: path.to_path_buf(),
+            issues,
+            suppressed: Default::default(),
+        });
+    }
+
+    Ok(results)
+}
+
+/// Report accessibility problems in an HTML document or fragment
+pub fn validate_accessibility(html: &str, issues: &mut Vec<ValidationIssue>) {
+    let line_at = |offset: usize| html[..offset].matches('\n').count() + 1;
+    let mut found = Vec::new();
+
+    check_heading_order(html, &mut found);
+    check_table_headers(html, &mut found);
+    check_link_text(html, &mut found);
+    check_contrast(html, &mut found);
+
+    found.sort_by_key(|(offset, _)| *offset);
+    for (offset, mut issue) in found {
+        issue.line = Some(line_at(offset));
+        issues.push(issue);
+    }
+}
+
+/// Headings may go down any number of levels but only up one at a time
+fn check_heading_order(html: &str, found: &mut Vec<(usize, ValidationIssue)>) {
+    let heading = Regex::new(r"(?i)<h([1-6])\b").expect("valid regex");
+
+    let mut previous: Option<u32> = None;
+    for captures in heading.captures_iter(html) {
+        let level: u32 = captures[1].parse().expect("heading level is a digit");
+        if let Some(previous) = previous {
+            if level > previous + 1 {
+                found.push((
+                    captures.get(0).map_or(0, |m| m.start()),
+                    issue(
+                        ValidationIssueType::HeadingOrder,
+                        format!("Heading level skipped: <h{}> follows <h{}>", level, previous),
+                        format!("Use <h{}> here, or add the missing heading levels", previous + 1),
+                    ),
+                ));
+            }
+        }
+        previous = Some(level);
+    }
+}
+
+/// Tables need header cells so screen readers can name each column or row
+fn check_table_headers(html: &str, found: &mut Vec<(usize, ValidationIssue)>) {
+    let table = Regex::new(r"(?is)<table\b.*?</table\s*>").expect("valid regex");
+    let header = Regex::new(r"(?i)<th\b").expect("valid regex");
+
+    for table in table.find_iter(html) {
+        if !header.is_match(table.as_str()) {
+            found.push((
+                table.start(),
+                issue(
+                    ValidationIssueType::MissingTableHeader,
+                    "Table has no header cells".to_string(),
+                    "Mark the header row with <th> cells, or a markdown table's first row".to_string(),
+                ),
+            ));
+        }
+    }
+}
+
+/// Links are often read out on their own, so their text must make sense out of context
+fn check_link_text(html: &str, found: &mut Vec<(usize, ValidationIssue)>) {
+    let link = Regex::new(r"(?is)<a\b([^>]*)>(.*?)</a\s*>").expect("valid regex");
+    let aria_label = Regex::new(r#"(?i)\saria-label(ledby)?\s*=\s*["'][^"']*\S"#).expect("valid regex");
+    let img_alt = Regex::new(r#"(?i)<img\b[^>]*\salt\s*=\s*["'][^"']*\S"#).expect("valid regex");
+    let tag = Regex::new(r"(?s)<[^>]*>").expect("valid regex");
+
+    for captures in link.captures_iter(html) {
+        if aria_label.is_match(&captures[1]) {
+            continue;
+        }
+
+        let text = tag.replace_all(&captures[2], " ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let normalized = text.trim_matches(|char: char| !char.is_alphanumeric()).to_lowercase();
+
+        let description = if normalized.is_empty() {
+            if img_alt.is_match(&captures[2]) {
+                continue;
+            }
+            "Link has no text".to_string()
+        } else if NON_DESCRIPTIVE_LINK_TEXT.contains(&normalized.as_str()) {
+            format!("Link text doesn't describe its target: \"{}\"", text)
+        } else {
+            continue;
+        };
+
+        found.push((
+            captures.get(0).map_or(0, |m| m.start()),
+            issue(
+                ValidationIssueType::NonDescriptiveLink,
+                description,
+                "Use link text that says where the link goes".to_string(),
+            ),
+        ));
+    }
+}
+
+/// Inline styles that set both colors must keep text readable
+///
+/// Styles that set only one of the colors are left alone, since the other
+/// comes from a stylesheet this check can't see.
+fn check_contrast(html: &str, found: &mut Vec<(usize, ValidationIssue)>) {
+    let style = Regex::new(r#"(?i)\sstyle\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid regex");
+    let declaration = Regex::new(r"(?i)(?:^|;)\s*(color|background-color|background)\s*:\s*([^;]+)").expect("valid regex");
+
+    for captures in style.captures_iter(html) {
+        let declarations = captures.get(1).or_else(|| captures.get(2)).map_or("", |m| m.as_str());
+
+        let mut foreground = None;
+        let mut background = None;
+        for declaration in declaration.captures_iter(declarations) {
+            let color = parse_color(declaration[2].trim());
+            if declaration[1].eq_ignore_ascii_case("color") {
+                foreground = color;
+            } else {
+                background = color;
+            }
+        }
+
+        let (Some(foreground), Some(background)) = (foreground, background) else {
+            continue;
+        };
+        let ratio = contrast_ratio(foreground, background);
+        if ratio < MIN_CONTRAST_RATIO {
+            found.push((
+                captures.get(0).map_or(0, |m| m.start()),
+                issue(
+                    ValidationIssueType::LowContrast,
+                    format!("Low color contrast in inline style: {:.2}:1", ratio),
+                    format!("Pick colors with a contrast ratio of at least {}:1", MIN_CONTRAST_RATIO),
+                ),
+            ));
+        }
+    }
+}
+
+fn issue(issue_type: ValidationIssueType, description: String, suggested_fix: String) -> ValidationIssue {
+    ValidationIssue {
+        issue_type,
+        severity: Severity::Warning,
+        line: None,
+        column: None,
+        description,
+        suggested_fix: Some(suggested_fix),
+    }
+}
+
+/// Parse a CSS color as red, green and blue, for hex, `rgb()` and a few names
+///
+/// Returns `None` for anything else, including colors with transparency.
+pub fn parse_color(value: &str) -> Option<(u8, u8, u8)> {
+    let value = value.trim_end_matches("!important").trim().to_lowercase();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|char| char.to_digit(16).map(|digit| digit as u8))
+            .collect::<Option<_>>()?;
+        return match digits.as_slice() {
+            [r, g, b] => Some((r * 17, g * 17, b * 17)),
+            [r1, r2, g1, g2, b1, b2] => Some((r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+            _ => None,
+        };
+    }
+
+    if let Some(arguments) = value.strip_prefix("rgb(").and_then(|rest| rest.strip_suffix(')')) {
+        let channels: Vec<u8> = arguments
+            .split([',', ' '])
+            .filter(|channel| !channel.is_empty())
+            .map(|channel| channel.parse().ok())
+            .collect::<Option<_>>()?;
+        return match channels.as_slice() {
+            [r, g, b] => Some((*r, *g, *b)),
+            _ => None,
+        };
+    }
+
+    match value.as_str() {
+        "black" => Some((0, 0, 0)),
+        "white" => Some((255, 255, 255)),
+        "gray" | "grey" => Some((128, 128, 128)),
+        "silver" => Some((192, 192, 192)),
+        "red" => Some((255, 0, 0)),
+        "green" => Some((0, 128, 0)),
+        "blue" => Some((0, 0, 255)),
+        "yellow" => Some((255, 255, 0)),
+        _ => None,
+    }
+}
+
+/// WCAG contrast ratio between two colors, from 1 for identical colors to 21 for black on white
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |value: u8| {
+        let value = f64::from(value) / 255.0;
+        if value <= 0.03928 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
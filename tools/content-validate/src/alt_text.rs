@@ -0,0 +1,228 @@
+//! # Alt Text
+//!
+//! Every image in published content needs alt text for screen readers. The
+//! check reports markdown images with an empty description and `<img>` tags
+//! without an `alt` attribute; `alt=""` on an `<img>` marks the image as
+//! decorative and is accepted. Missing alt text is a warning by default and
+//! can be raised to an error in `severities.yaml`:
+//!
+//! ```yaml
+//! missing_alt_text: error
+//! ```
+//!
+//! The alt text report lists every image still missing a description across
+//! the site, with a starting point for one taken from the image's file name.
+
+use anyhow::Result;
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::FsContentRepository;
+use common_models::Config;
+use common_traits::ContentRepository;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::{is_draft, Severity, ValidationIssue, ValidationIssueType};
+
+/// An image with no alt text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingAltText {
+    pub url: String,
+    pub line: usize,
+}
+
+/// Find the images in an article that have no alt text
+///
+/// Covers markdown images and `<img>` tags, local or remote.
+pub fn find_missing_alt_text(content: &str) -> Vec<MissingAltText> {
+    let line_at = |offset: usize| content[..offset].matches('\n').count() + 1;
+    let img_tag = Regex::new(r"(?is)<img\b[^>]*>").expect("valid regex");
+    let src = Regex::new(r#"(?i)\bsrc\s*=\s*["']([^"']*)["']"#).expect("valid regex");
+    let alt = Regex::new(r"(?i)\salt\b").expect("valid regex");
+
+    let mut missing = Vec::new();
+    let mut image: Option<(String, usize, String)> = None;
+
+    for (event, range) in Parser::new_ext(content, Options::all()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Image(_, url, _)) => {
+                image = Some((url.to_string(), line_at(range.start), String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, alt)) = image.as_mut() {
+                    alt.push_str(&text);
+                }
+            }
+            Event::End(Tag::Image(..)) => {
+                if let Some((url, line, alt)) = image.take() {
+                    if alt.trim().is_empty() {
+                        missing.push(MissingAltText { url, line });
+                    }
+                }
+            }
+            Event::Html(_) => {
+                for tag in img_tag.find_iter(&content[range.clone()]) {
+                    if alt.is_match(tag.as_str()) {
+                        continue;
+                    }
+                    let url = src
+                        .captures(tag.as_str())
+                        .map(|captures| captures[1].to_string())
+                        .unwrap_or_default();
+                    missing.push(MissingAltText {
+                        url,
+                        line: line_at(range.start + tag.start()),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    missing
+}
+
+/// Suggest alt text from an image's file name, like "Sales chart 2024" for `sales-chart-2024.png`
+///
+/// Only a starting point: a useful description says what the image shows.
+/// Returns `None` for names that carry no words, such as `IMG_1234.jpg`.
+pub fn suggest_alt_text(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let name = path.rsplit('/').next().unwrap_or_default();
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+
+    let words: Vec<&str> = stem
+        .split(|char: char| !char.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .filter(|word| !["img", "image", "dsc", "pxl", "screenshot"].contains(&word.to_lowercase().as_str()))
+        .collect();
+    if words.iter().all(|word| word.chars().all(|char| char.is_ascii_digit())) {
+        return None;
+    }
+
+    let sentence = words.join(" ").to_lowercase();
+    let mut chars = sentence.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect())
+}
+
+/// Report images without alt text in published content
+///
+/// Drafts aren't checked; they are reported once they are published.
+pub fn validate_alt_text(content: &str, issues: &mut Vec<ValidationIssue>) {
+    if is_draft(content) {
+        return;
+    }
+
+    for image in find_missing_alt_text(content) {
+        let suggested_fix = match suggest_alt_text(&image.url) {
+            Some(suggestion) => format!("Describe the image, e.g. ![{}]({})", suggestion, image.url),
+            None => "Describe what the image shows".to_string(),
+        };
+
+        issues.push(ValidationIssue {
+            issue_type: ValidationIssueType::MissingAltText,
+            severity: Severity::Warning,
+            line: Some(image.line),
+            column: None,
+            description: format!("Image has no alt text: {}", image.url),
+            suggested_fix: Some(suggested_fix),
+        });
+    }
+}
+
+/// An image without alt text, and where to find it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AltTextEntry {
+    pub topic: String,
+    pub article: String,
+    pub file_path: PathBuf,
+    pub line: usize,
+    pub image: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// Every image in published content that has no alt text
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AltTextReport {
+    /// Published articles that were checked
+    pub articles_checked: usize,
+    /// Images without alt text, by topic, article and line
+    pub images: Vec<AltTextEntry>,
+}
+
+/// List the images without alt text in the published articles of one topic or all of them
+///
+/// # Errors
+///
+/// Returns an error if the topic doesn't exist or an article can't be read
+pub fn alt_text_report(config: &Config, topic: Option<&str>) -> Result<AltTextReport> {
+    alt_text_report_in(&FsContentRepository::new(config), topic)
+}
+
+/// List the images without alt text in the published articles of `repository`
+///
+/// # Errors
+///
+/// Returns an error if the topic doesn't exist or an article can't be read
+pub fn alt_text_report_in(repository: &impl ContentRepository, topic: Option<&str>) -> Result<AltTextReport> {
+    let articles = match topic {
+        Some(topic) => repository.list_articles(topic)?,
+        None => repository.article_paths()?,
+    };
+
+    let mut report = AltTextReport::default();
+    for article in articles {
+        let content = repository.read_article(&article)?;
+        if is_draft(&content) {
+            continue;
+        }
+
+        report.articles_checked += 1;
+        for image in find_missing_alt_text(&content) {
+            report.images.push(AltTextEntry {
+                topic: article.topic.clone(),
+                article: article.slug.clone(),
+                file_path: article.content_file.clone(),
+                line: image.line,
+                suggestion: suggest_alt_text(&image.url),
+                image: image.url,
+            });
+        }
+    }
+
+    report.images.sort_by(|a, b| (&a.topic, &a.article, a.line).cmp(&(&b.topic, &b.article, b.line)));
+    Ok(report)
+}
+
+impl DisplayResult for AltTextReport {
+    fn to_display(&self) -> String {
+        let mut lines = Vec::new();
+        for entry in &self.images {
+            lines.push(format!(
+                "{}:{} {}",
+                entry.file_path.display(),
+                entry.line,
+                entry.image.bold()
+            ));
+            if let Some(suggestion) = &entry.suggestion {
+                lines.push(format!("    Suggestion: \"{}\"", suggestion));
+            }
+        }
+
+        let articles = self
+            .images
+            .iter()
+            .map(|entry| (&entry.topic, &entry.article))
+            .collect::<std::collections::BTreeSet<_>>()
+            .len();
+
+        lines.push(format!("\n{}", "=== Alt Text Report ===".green().bold()));
+        lines.push(format!("Articles checked: {}", self.articles_checked));
+        lines.push(format!("Articles with images missing alt text: {}", articles));
+        lines.push(format!("Images missing alt text: {}", self.images.len()));
+        lines.join("\n")
+    }
+}
@@ -1,9 +1,13 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
 use colored::*;
+use common_logging::LogArgs;
+use common_traits::CancellationToken;
+use common_cli::{OutputFormat, OutputFormatArgs, OutputFormatter};
 use content_validate::{
-    ValidationOptions, ValidationType, ValidationIssueType,
-    validate_content
+    alt_text_report, ArchiveFallback, DoctorOptions, PreCommitRule, Severity, ValidationOptions, ValidationReport, ValidationSummary, ValidationType,
+    github_comment, install_pre_commit_hook, load_base_totals, load_severity_overrides, run_doctor, seo_report, severities_path,
+    severity_label, validate_content_cancellable, validate_html_output, validate_staged, Linter, COMMENT_LIMIT
 };
 use std::path::PathBuf;
 
@@ -11,6 +15,9 @@ use std::path::PathBuf;
 enum ValidationTypeArg {
     Links,
     Markdown,
+    Assets,
+    PublishDate,
+    DuplicateSlugs,
     All,
 }
 
@@ -19,11 +26,63 @@ impl ValidationTypeArg {
         match self {
             ValidationTypeArg::Links => ValidationType::Links,
             ValidationTypeArg::Markdown => ValidationType::Markdown,
+            ValidationTypeArg::Assets => ValidationType::Assets,
+            ValidationTypeArg::PublishDate => ValidationType::PublishDate,
+            ValidationTypeArg::DuplicateSlugs => ValidationType::DuplicateSlugs,
             ValidationTypeArg::All => ValidationType::All,
         }
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum PreCommitRuleArg {
+    Frontmatter,
+    Slug,
+    Markdown,
+}
+
+impl PreCommitRuleArg {
+    fn to_rule(self) -> PreCommitRule {
+        match self {
+            PreCommitRuleArg::Frontmatter => PreCommitRule::Frontmatter,
+            PreCommitRuleArg::Slug => PreCommitRule::Slug,
+            PreCommitRuleArg::Markdown => PreCommitRule::Markdown,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum SeverityArg {
+    Error,
+    Warning,
+    Info,
+}
+
+impl SeverityArg {
+    fn to_severity(self) -> Severity {
+        match self {
+            SeverityArg::Error => Severity::Error,
+            SeverityArg::Warning => Severity::Warning,
+            SeverityArg::Info => Severity::Info,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum ArchiveFallbackArg {
+    Suggest,
+    Rewrite,
+}
+
+impl ArchiveFallbackArg {
+    fn to_archive_fallback(self) -> ArchiveFallback {
+        match self {
+            ArchiveFallbackArg::Suggest => ArchiveFallback::Suggest,
+            ArchiveFallbackArg::Rewrite => ArchiveFallback::Rewrite,
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(author, version, about = "Validate content for links, markdown formatting, and spelling")]
 struct Args {
@@ -36,7 +95,7 @@ struct Args {
     topic: Option<String>,
 
     /// Types of validation to perform
-    #[arg(short, long, value_enum, default_value = "all")]
+    #[arg(long, value_enum, default_value = "all")]
     validation_types: Vec<ValidationTypeArg>,
 
     /// Skip external link checking
@@ -47,7 +106,11 @@ struct Args {
     #[arg(long, default_value = "10")]
     external_link_timeout: u64,
 
-    /// Custom dictionary file path
+    /// Suggest, or rewrite links to, archived copies of external links that stay dead
+    #[arg(long, value_enum, conflicts_with = "skip_external_links")]
+    archive_dead_links: Option<ArchiveFallbackArg>,
+
+    /// Extra words to accept when spell checking, one per line
     #[arg(long)]
     dictionary: Option<PathBuf>,
 
@@ -55,13 +118,116 @@ struct Args {
     #[arg(long, default_value = "false")]
     include_drafts: bool,
 
-    /// Display verbose output
-    #[arg(short = 'b', long, default_value = "false")]
-    verbose: bool,
+    /// Directory of image-build output to look for images in
+    #[arg(long, default_value = "build/images")]
+    images_dir: PathBuf,
+
+    /// Only validate articles changed since this git commit, branch or tag
+    #[arg(long, value_name = "REF")]
+    changed_since: Option<String>,
+
+    /// Exit with an error status if any issue is at this severity or more serious
+    #[arg(long, value_enum)]
+    fail_on: Option<SeverityArg>,
+
+    /// Also write the report as a GitHub pull request comment to this file
+    #[arg(long, value_name = "FILE")]
+    github_comment: Option<PathBuf>,
+
+    /// JSON report of the base branch to compare the pull request comment with
+    #[arg(long, value_name = "FILE", requires = "github_comment")]
+    compare_to: Option<PathBuf>,
+
+    /// Run every check on all content and report the findings by severity
+    #[arg(long, conflicts_with_all = ["article", "topic"])]
+    doctor: bool,
+
+    /// Print the doctor report as JSON (the same as --format json)
+    #[arg(long, requires = "doctor")]
+    json: bool,
+
+    #[command(flatten)]
+    log: LogArgs,
+
+    #[command(flatten)]
+    output: OutputFormatArgs,
+
+    /// List every image in published content that has no alt text
+    #[arg(long, conflicts_with_all = ["doctor", "article", "staged"])]
+    alt_text_report: bool,
+
+    /// Check the HTML that content-build wrote for accessibility problems
+    #[arg(long, conflicts_with_all = ["doctor", "article", "topic", "staged", "alt_text_report"])]
+    a11y: bool,
+
+    /// Directory of content-build output to check with --a11y
+    #[arg(long, default_value = "public")]
+    build_dir: PathBuf,
+
+    /// Score published articles on SEO basics and list what to fix
+    #[arg(long, conflicts_with_all = ["doctor", "article", "staged", "alt_text_report", "a11y"])]
+    seo: bool,
+
+    /// Install a git pre-commit hook that validates staged content
+    #[arg(long, conflicts_with_all = ["doctor", "staged"])]
+    install_hooks: bool,
+
+    /// Replace an existing pre-commit hook that wasn't installed by this tool
+    #[arg(long, requires = "install_hooks")]
+    force: bool,
+
+    /// Validate only staged content files, without network checks, for use in a pre-commit hook
+    #[arg(long, conflicts_with_all = ["doctor", "article", "topic", "changed_since"])]
+    staged: bool,
+
+    /// Rules to run on staged content
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "frontmatter,slug,markdown")]
+    rules: Vec<PreCommitRuleArg>,
+
+    /// List the lint rules, marking those turned off in lint.yaml
+    #[arg(long, conflicts_with_all = ["doctor", "staged", "install_hooks"])]
+    list_rules: bool,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    args.log.init()?;
+
+    if args.doctor {
+        return doctor(&args);
+    }
+
+    if args.list_rules {
+        return list_rules();
+    }
+
+    if args.alt_text_report {
+        let config = common_config::load_config()?;
+        let report = alt_text_report(&config, args.topic.as_deref())?;
+        formatter(&args).print(&report)?;
+        return Ok(());
+    }
+
+    if args.a11y {
+        return a11y(&args);
+    }
+
+    if args.seo {
+        return seo(&args);
+    }
+
+    if args.install_hooks {
+        let rules = pre_commit_rules(&args);
+        let hook_path = install_pre_commit_hook(&std::env::current_dir()?, &rules, args.force)?;
+        println!("{} pre-commit hook: {}", "Installed".green().bold(), hook_path.display());
+        return Ok(());
+    }
+
+    if args.staged {
+        return staged(&args);
+    }
+
+    let formatter = formatter(&args);
 
     // Convert validation types
     let validation_types = args.validation_types.iter()
@@ -72,14 +238,110 @@ fn main() -> Result<()> {
     let options = ValidationOptions {
         article_slug: args.article.clone(),
         topic: args.topic.clone(),
-        validation_types: validation_types.clone(),
+        validation_types,
         check_external_links: !args.skip_external_links,
         timeout: Some(args.external_link_timeout),
         dictionary_path: args.dictionary.clone(),
         include_drafts: args.include_drafts,
+        images_dir: Some(args.images_dir.clone()),
+        changed_since: args.changed_since.clone(),
+        archive_fallback: args.archive_dead_links.map(ArchiveFallbackArg::to_archive_fallback).unwrap_or_default(),
     };
 
-    // Describe what we're doing
+    // Progress messages would corrupt structured output
+    if formatter.is_plain() {
+        describe(&options);
+    }
+
+    // Stop cleanly between articles and links when the user presses Ctrl-C
+    let token = CancellationToken::new();
+    token.cancel_on_ctrlc()?;
+
+    // Run validation
+    if formatter.is_plain() {
+        println!("\n{} content validation...", "Running".yellow().bold());
+    }
+    let results = validate_content_cancellable(&options, &token)?;
+
+    let config = common_config::load_config()?;
+    print_report(&args, ValidationReport::new(results, &config))
+}
+
+/// List the lint rules and whether each runs
+fn list_rules() -> Result<()> {
+    let config = common_config::load_config()?;
+    let linter = Linter::load(&config)?;
+
+    for rule in linter.registry().rules() {
+        let status = if linter.is_disabled(rule.id()) { " (disabled)".dimmed().to_string() } else { String::new() };
+        println!(
+            "{:<20} {:<8} {}{}",
+            rule.id().cyan().bold(),
+            rule.default_severity().to_string(),
+            rule.description(),
+            status
+        );
+    }
+
+    Ok(())
+}
+
+/// Check built HTML for accessibility problems
+fn a11y(args: &Args) -> Result<()> {
+    let config = common_config::load_config()?;
+    let overrides = load_severity_overrides(&severities_path(&config))?;
+
+    if formatter(args).is_plain() {
+        println!("{} built HTML in: {}", "Checking".green().bold(), args.build_dir.display());
+    }
+    let mut results = validate_html_output(&args.build_dir)?;
+    for result in &mut results {
+        overrides.apply(&mut result.issues);
+    }
+
+    print_report(args, ValidationReport::new(results, &config))
+}
+
+/// Audit published articles for SEO
+fn seo(args: &Args) -> Result<()> {
+    let config = common_config::load_config()?;
+    let overrides = load_severity_overrides(&severities_path(&config))?;
+
+    let report = seo_report(&config, args.topic.as_deref(), &overrides)?.verbose(args.log.verbosity().is_verbose());
+    formatter(args).print(&report)?;
+
+    if let Some(fail_on) = args.fail_on {
+        if report.issues_at_least(fail_on.to_severity()) > 0 {
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print a validation report, exiting with an error status if `--fail-on` is met
+fn print_report(args: &Args, report: ValidationReport) -> Result<()> {
+    let report = report.verbose(args.log.verbosity().is_verbose());
+    formatter(args).print(&report)?;
+
+    if let Some(comment_path) = &args.github_comment {
+        let base = args.compare_to.as_deref().map(load_base_totals).transpose()?;
+        std::fs::write(comment_path, github_comment(&report, base.as_ref(), COMMENT_LIMIT))
+            .with_context(|| format!("Failed to write {:?}", comment_path))?;
+    }
+
+    if let Some(fail_on) = args.fail_on {
+        let code = report.summary.exit_code(fail_on.to_severity());
+        if code != 0 {
+            std::process::exit(code);
+        }
+    }
+
+    Ok(())
+}
+
+/// Describe what is about to be validated
+fn describe(options: &ValidationOptions) {
     if let Some(article) = &options.article_slug {
         println!("{} article: {}", "Validating".green().bold(), article);
     } else if let Some(topic) = &options.topic {
@@ -98,6 +360,9 @@ fn main() -> Result<()> {
         println!("  {} {}", "Check external links:".cyan().bold(), options.check_external_links);
         if options.check_external_links {
             println!("  {} {} seconds", "External link timeout:".cyan().bold(), options.timeout.unwrap());
+            if options.archive_fallback != ArchiveFallback::Off {
+                println!("  {} {:?}", "Archive dead links:".cyan().bold(), options.archive_fallback);
+            }
         }
     }
 
@@ -107,84 +372,90 @@ fn main() -> Result<()> {
         }
     }
 
+    if options.validation_types.iter().any(|&vt| vt == ValidationType::Assets || vt == ValidationType::All) {
+        if let Some(images_dir) = &options.images_dir {
+            println!("  {} {}", "Image build directory:".cyan().bold(), images_dir.display());
+        }
+    }
+
     println!("  {} {}", "Include drafts:".cyan().bold(), options.include_drafts);
 
-    // Run validation
-    println!("\n{} content validation...", "Running".yellow().bold());
-    let results = validate_content(&options)?;
+    if let Some(reference) = &options.changed_since {
+        println!("  {} {}", "Changed since:".cyan().bold(), reference);
+    }
+}
 
-    if results.is_empty() {
-        println!("\n{} No content found to validate.", "Notice:".yellow().bold());
-        return Ok(());
+/// Formatter for the report, honouring the older `--json` flag
+fn formatter(args: &Args) -> OutputFormatter {
+    if args.json {
+        OutputFormatter::new(OutputFormat::Json)
+    } else {
+        args.output.formatter()
     }
+}
 
-    // Process results
-    let mut total_issues = 0;
-    let mut files_with_issues = 0;
+/// Rules selected on the command line, in the order they run
+fn pre_commit_rules(args: &Args) -> Vec<PreCommitRule> {
+    let mut rules: Vec<PreCommitRule> = args.rules.iter().map(|rule| rule.to_rule()).collect();
+    rules.sort();
+    rules.dedup();
+    rules
+}
 
-    // Count issues by type
-    let mut link_issues = 0;
-    let mut markdown_issues = 0;
+/// Validate staged content, exiting with an error status if it has errors
+///
+/// Only problems are printed, so a clean commit is silent.
+fn staged(args: &Args) -> Result<()> {
+    let config = common_config::load_config()?;
+    let results = validate_staged(&config, &pre_commit_rules(args))?;
 
     for result in &results {
-        if !result.issues.is_empty() {
-            files_with_issues += 1;
-            total_issues += result.issues.len();
-
-            // Display file path
-            println!("\n{} {}", "File:".blue().bold(), result.file_path.display());
-
-            // Show issues
-            for issue in &result.issues {
-                match issue.issue_type {
-                    ValidationIssueType::BrokenLink |
-                    ValidationIssueType::MissingInternalLink |
-                    ValidationIssueType::InvalidUrl => {
-                        link_issues += 1;
-                        println!("  {}: {}", "LINK".red().bold(), issue.description);
-                    },
-                    ValidationIssueType::MarkdownFormatting => {
-                        markdown_issues += 1;
-
-                        if let Some(line) = issue.line {
-                            println!("  {} (line {}): {}", "FORMAT".yellow().bold(), line, issue.description);
-                        } else {
-                            println!("  {}: {}", "FORMAT".yellow().bold(), issue.description);
-                        }
-                    },
-                }
-
-                // Show suggestion if available and verbose is enabled
-                if args.verbose {
-                    if let Some(suggestion) = &issue.suggested_fix {
-                        println!("    Suggestion: {}", suggestion);
-                    }
-                }
+        for issue in &result.issues {
+            let label = severity_label(issue.severity);
+            let location = match issue.line {
+                Some(line) => format!("{}:{}", result.file_path.display(), line),
+                None => result.file_path.display().to_string(),
+            };
+
+            println!("{} {}: {}", label, location, issue.description);
+            if let Some(suggestion) = &issue.suggested_fix {
+                println!("    Suggestion: {}", suggestion);
             }
+
         }
     }
 
-    // Summary
-    println!("\n{}", "=== Validation Summary ===".green().bold());
-    println!("Files checked: {}", results.len());
-    println!("Files with issues: {}", files_with_issues);
-    println!("Total issues: {}", total_issues);
-
-    if validation_types.contains(&ValidationType::Links) ||
-       validation_types.contains(&ValidationType::All) {
-        println!("Link issues: {}", link_issues);
+    let summary = ValidationSummary::from_results(&results, &config);
+    if summary.has_errors() {
+        eprintln!(
+            "\n{} {} error(s) in staged content; fix them or commit with --no-verify",
+            "Commit blocked:".red().bold(),
+            summary.totals.errors
+        );
+        std::process::exit(1);
     }
 
-    if validation_types.contains(&ValidationType::Markdown) ||
-       validation_types.contains(&ValidationType::All) {
-        println!("Markdown formatting issues: {}", markdown_issues);
-    }
+    Ok(())
+}
 
-    if total_issues > 0 {
-        println!("\n{} {} validation issues found", "Warning:".yellow().bold(), total_issues);
-    } else {
-        println!("\n{} No validation issues found!", "Success:".green().bold());
+/// Run the content doctor, exiting with an error status if it finds errors
+fn doctor(args: &Args) -> Result<()> {
+    let config = common_config::load_config()?;
+    let options = DoctorOptions {
+        check_external_links: !args.skip_external_links,
+        timeout: Some(args.external_link_timeout),
+        images_dir: Some(args.images_dir.clone()),
+    };
+
+    let token = CancellationToken::new();
+    token.cancel_on_ctrlc()?;
+
+    let report = run_doctor(&config, &options, &token)?.verbose(args.log.verbosity().is_verbose());
+    formatter(args).print(&report)?;
+
+    if report.has_errors() {
+        std::process::exit(1);
     }
 
     Ok(())
-}
\ No newline at end of file
+}
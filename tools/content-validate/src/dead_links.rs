@@ -0,0 +1,188 @@
+//! # Dead Link Quarantine
+//!
+//! External links fail for all sorts of passing reasons, so one failed check
+//! isn't enough to give up on a URL. Each failure is counted in a quarantine
+//! file, `.link-quarantine.yaml` in the content base directory, and a URL
+//! that responds again is let out. After [`QUARANTINE_AFTER`] failed runs in
+//! a row the URL counts as dead, and with `--archive-dead-links` the Wayback
+//! Machine is asked for its latest snapshot of it:
+//!
+//! - `suggest` adds the archived URL to the broken link issue
+//! - `rewrite` replaces the link in the article and reports the substitution
+//!
+//! Snapshots are remembered in the quarantine file, so each dead URL is
+//! looked up once.
+
+use anyhow::{Context, Result};
+use common_fs::write_file_atomic;
+use common_models::Config;
+use regex::Regex;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Name of the quarantine file in the content base directory
+pub const QUARANTINE_FILE: &str = ".link-quarantine.yaml";
+
+/// Failed runs in a row after which a link counts as dead
+pub const QUARANTINE_AFTER: u32 = 3;
+
+/// Wayback Machine availability API
+pub const WAYBACK_API: &str = "https://archive.org/wayback/available";
+
+/// What to do about dead external links
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArchiveFallback {
+    /// Report them as broken
+    #[default]
+    Off,
+    /// Report them with an archived copy to link to instead
+    Suggest,
+    /// Link to an archived copy instead, if there is one
+    Rewrite,
+}
+
+/// A link that has failed its checks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantinedLink {
+    /// Failed runs in a row
+    pub failures: u32,
+    /// Latest Wayback Machine snapshot, once looked up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_url: Option<String>,
+}
+
+/// Links that have failed their checks, by URL
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LinkQuarantine {
+    links: BTreeMap<String, QuarantinedLink>,
+    /// URLs already counted in this run, which may check a URL more than once
+    #[serde(skip)]
+    checked: BTreeSet<String>,
+}
+
+impl LinkQuarantine {
+    /// Load the quarantine file, returning an empty quarantine if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+        serde_yaml::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))
+    }
+
+    /// Write the quarantine file, removing it once no link is quarantined
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if self.links.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path).with_context(|| format!("Failed to remove {:?}", path))?;
+            }
+            return Ok(());
+        }
+
+        let yaml = serde_yaml::to_string(self).context("Failed to serialize link quarantine")?;
+        write_file_atomic(path, &yaml).with_context(|| format!("Failed to write {:?}", path))?;
+        Ok(())
+    }
+
+    /// Count a failed check, returning whether the link now counts as dead
+    pub fn record_failure(&mut self, url: &str) -> bool {
+        let link = self.links.entry(url.to_string()).or_default();
+        if self.checked.insert(url.to_string()) {
+            link.failures += 1;
+        }
+        link.failures >= QUARANTINE_AFTER
+    }
+
+    /// Let a link that responded out of quarantine
+    pub fn record_success(&mut self, url: &str) {
+        self.checked.insert(url.to_string());
+        self.links.remove(url);
+    }
+
+    /// A quarantined link, if the URL is in quarantine
+    pub fn get(&self, url: &str) -> Option<&QuarantinedLink> {
+        self.links.get(url)
+    }
+
+    /// The archived copy of a dead link, looking it up if it hasn't been yet
+    ///
+    /// A failed lookup is logged and tried again on the next run.
+    pub fn archived_url(&mut self, client: &Client, url: &str) -> Option<String> {
+        let link = self.links.get_mut(url)?;
+        if link.archived_url.is_none() {
+            match lookup_snapshot(client, url) {
+                Ok(snapshot) => link.archived_url = snapshot,
+                Err(err) => tracing::warn!(url, "Wayback Machine lookup failed: {:#}", err),
+            }
+        }
+        link.archived_url.clone()
+    }
+}
+
+/// Get the path of the quarantine file for a configuration
+pub fn quarantine_path(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(QUARANTINE_FILE)
+}
+
+/// Ask the Wayback Machine for its latest snapshot of a URL
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response can't be read
+pub fn lookup_snapshot(client: &Client, url: &str) -> Result<Option<String>> {
+    let body = client
+        .get(WAYBACK_API)
+        .query(&[("url", url)])
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .context("Wayback Machine request failed")?;
+    parse_snapshot(&body)
+}
+
+/// The snapshot URL in a Wayback Machine availability response, if it has one
+///
+/// # Errors
+///
+/// Returns an error if the response isn't JSON
+pub fn parse_snapshot(body: &str) -> Result<Option<String>> {
+    let response: serde_json::Value = serde_json::from_str(body).context("Invalid Wayback Machine response")?;
+    let closest = &response["archived_snapshots"]["closest"];
+    if closest["available"].as_bool() != Some(true) {
+        return Ok(None);
+    }
+
+    // Snapshots are listed over http; the archive serves them over https too
+    Ok(closest["url"]
+        .as_str()
+        .map(|snapshot| match snapshot.strip_prefix("http://") {
+            Some(rest) => format!("https://{}", rest),
+            None => snapshot.to_string(),
+        }))
+}
+
+/// Replace a link's URL in an article, returning whether anything changed
+///
+/// Only whole URLs are replaced, as a markdown link target, autolink or HTML
+/// attribute, so a longer URL containing the same text is left alone.
+///
+/// # Errors
+///
+/// Returns an error if the article can't be read or written
+pub fn rewrite_link(file_path: &Path, url: &str, replacement: &str) -> Result<bool> {
+    let content = std::fs::read_to_string(file_path).with_context(|| format!("Failed to read {:?}", file_path))?;
+    let pattern = Regex::new(&format!(r#"(^|[(<"'\s]){}([)"'>\s]|$)"#, regex::escape(url))).expect("valid regex");
+    let rewritten = pattern.replace_all(&content, |captures: &regex::Captures| {
+        format!("{}{}{}", &captures[1], replacement, &captures[2])
+    });
+
+    if rewritten == content {
+        return Ok(false);
+    }
+    write_file_atomic(file_path, &rewritten).with_context(|| format!("Failed to write {:?}", file_path))?;
+    Ok(true)
+}
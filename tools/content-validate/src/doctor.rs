@@ -0,0 +1,369 @@
+//! # Content Doctor
+//!
+//! Runs every check on the content in one pass: the configuration, the
+//! directory structure, frontmatter, links and images. The findings are
+//! collected into a single report ordered by severity, so the problems that
+//! break the build come first.
+
+use anyhow::{Context, Result};
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::{read_file, resolve_content_file};
+use common_markdown::extract_frontmatter_and_content;
+use common_models::Config;
+use common_traits::CancellationToken;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::report::severity_label;
+use crate::{
+    extract_image_references, extract_links, load_severity_overrides, severities_path, Link, Severity,
+    SeverityOverrides, ValidationIssue, ValidationOptions,
+};
+
+/// The kind of check that produced a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorCheck {
+    Config,
+    Structure,
+    Frontmatter,
+    Links,
+    Images,
+}
+
+/// A problem found by the doctor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorFinding {
+    pub severity: Severity,
+    pub check: DoctorCheck,
+    /// File or directory the finding is about, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl DoctorFinding {
+    fn new(severity: Severity, check: DoctorCheck, path: Option<&Path>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            check,
+            path: path.map(Path::to_path_buf),
+            line: None,
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    fn from_issue(check: DoctorCheck, path: &Path, issue: ValidationIssue) -> Self {
+        Self {
+            severity: issue.severity,
+            check,
+            path: Some(path.to_path_buf()),
+            line: issue.line,
+            message: issue.description,
+            suggestion: issue.suggested_fix,
+        }
+    }
+}
+
+/// Everything the doctor found, most serious first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DoctorReport {
+    /// Content files that were checked
+    pub files_checked: usize,
+    pub findings: Vec<DoctorFinding>,
+    /// Show suggested fixes in plain output
+    #[serde(skip)]
+    pub verbose: bool,
+}
+
+impl DoctorReport {
+    /// Number of findings at each severity
+    pub fn counts(&self) -> BTreeMap<Severity, usize> {
+        let mut counts = BTreeMap::new();
+        for finding in &self.findings {
+            *counts.entry(finding.severity).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Whether any finding is an error
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|finding| finding.severity == Severity::Error)
+    }
+
+    /// Render the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize doctor report")
+    }
+
+    /// Show suggested fixes in plain output
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+}
+
+impl DisplayResult for DoctorReport {
+    fn to_display(&self) -> String {
+        let mut lines = Vec::new();
+        for finding in &self.findings {
+            let location = match (&finding.path, finding.line) {
+                (Some(path), Some(line)) => format!(" {}:{}", path.display(), line),
+                (Some(path), None) => format!(" {}", path.display()),
+                (None, _) => String::new(),
+            };
+
+            lines.push(format!("{} [{:?}]{}: {}", severity_label(finding.severity), finding.check, location, finding.message));
+            if self.verbose {
+                if let Some(suggestion) = &finding.suggestion {
+                    lines.push(format!("    Suggestion: {}", suggestion));
+                }
+            }
+        }
+
+        let counts = self.counts();
+        lines.push(format!("\n{}", "=== Doctor Summary ===".green().bold()));
+        lines.push(format!("Files checked: {}", self.files_checked));
+        lines.push(format!("Errors: {}", counts.get(&Severity::Error).unwrap_or(&0)));
+        lines.push(format!("Warnings: {}", counts.get(&Severity::Warning).unwrap_or(&0)));
+        lines.push(format!("Info: {}", counts.get(&Severity::Info).unwrap_or(&0)));
+        lines.join("\n")
+    }
+}
+
+/// Options for a doctor run
+#[derive(Debug, Clone, Default)]
+pub struct DoctorOptions {
+    /// Check that external links respond
+    pub check_external_links: bool,
+    /// Timeout for external link checks (in seconds)
+    pub timeout: Option<u64>,
+    /// Directory of image-build output, checked for images the article directory lacks
+    pub images_dir: Option<PathBuf>,
+}
+
+/// Check the configuration and every article it covers
+///
+/// Problems with the content are findings rather than errors, so one bad
+/// article doesn't hide the rest.
+///
+/// # Errors
+///
+/// Returns an error if the run is cancelled or a topic directory can't be read
+pub fn run_doctor(config: &Config, options: &DoctorOptions, token: &CancellationToken) -> Result<DoctorReport> {
+    let mut report = DoctorReport::default();
+    let overrides = load_severity_overrides(&severities_path(config))?;
+
+    for issue in common_config::validate_config(config) {
+        report.findings.push(DoctorFinding::new(
+            Severity::Error,
+            DoctorCheck::Config,
+            None,
+            issue.to_string(),
+        ));
+    }
+
+    let validation_options = ValidationOptions {
+        article_slug: None,
+        topic: None,
+        validation_types: Vec::new(),
+        check_external_links: options.check_external_links,
+        timeout: options.timeout,
+        dictionary_path: None,
+        include_drafts: true,
+        images_dir: options.images_dir.clone(),
+        changed_since: None,
+        archive_fallback: Default::default(),
+    };
+    // The doctor only reports, so failures aren't counted towards quarantine
+    let mut quarantine = crate::LinkQuarantine::default();
+
+    let mut topics: Vec<_> = config.content.topics.iter().collect();
+    topics.sort_by_key(|(key, _)| *key);
+
+    for (topic_key, topic_config) in topics {
+        let topic_dir = Path::new(&config.content.base_dir).join(&topic_config.directory);
+        if !topic_dir.is_dir() {
+            // Reported by the configuration check
+            continue;
+        }
+
+        let mut article_dirs = Vec::new();
+        for entry in std::fs::read_dir(&topic_dir)
+            .with_context(|| format!("Failed to read topic directory: {:?}", topic_dir))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                article_dirs.push(path);
+            }
+        }
+        article_dirs.sort();
+
+        if article_dirs.is_empty() {
+            report.findings.push(DoctorFinding::new(
+                Severity::Info,
+                DoctorCheck::Structure,
+                Some(&topic_dir),
+                format!("Topic '{}' has no articles", topic_key),
+            ));
+        }
+
+        for article_dir in article_dirs {
+            token.check("content doctor")?;
+
+            let slug = article_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            let content_file = match resolve_content_file(&article_dir, &slug) {
+                Some(content_file) => content_file,
+                None => {
+                    report.findings.push(structure_finding(&article_dir, &slug));
+                    continue;
+                }
+            };
+
+            report.files_checked += 1;
+            let content = match read_file(&content_file) {
+                Ok(content) => content,
+                Err(err) => {
+                    report.findings.push(DoctorFinding::new(
+                        Severity::Error,
+                        DoctorCheck::Structure,
+                        Some(&content_file),
+                        format!("Failed to read content file: {}", err.message()),
+                    ));
+                    continue;
+                }
+            };
+
+            check_frontmatter(&content_file, &content, &overrides, &mut report.findings);
+
+            // Images are left to the image check, which also looks in the image-build output
+            let images: Vec<String> = extract_image_references(&content)
+                .into_iter()
+                .map(|reference| reference.url)
+                .collect();
+            let links: Vec<Link> = extract_links(&content)
+                .into_iter()
+                .filter(|link| !images.iter().any(|image| image == link.url()))
+                .collect();
+
+            let mut issues = Vec::new();
+            crate::check_links(&content_file, &links, &validation_options, token, &mut quarantine, &mut issues)?;
+            overrides.apply(&mut issues);
+            report.findings.extend(
+                issues
+                    .into_iter()
+                    .map(|issue| DoctorFinding::from_issue(DoctorCheck::Links, &content_file, issue)),
+            );
+
+            let mut issues = Vec::new();
+            crate::validate_assets(&content_file, &content, config, options.images_dir.as_deref(), &mut issues)?;
+            crate::validate_alt_text(&content, &mut issues);
+            overrides.apply(&mut issues);
+            report.findings.extend(
+                issues
+                    .into_iter()
+                    .map(|issue| DoctorFinding::from_issue(DoctorCheck::Images, &content_file, issue)),
+            );
+        }
+    }
+
+    // Sorting is stable, so findings keep their order within a file
+    report
+        .findings
+        .sort_by(|a, b| (a.severity, a.check, &a.path).cmp(&(b.severity, b.check, &b.path)));
+
+    Ok(report)
+}
+
+/// Describe an article directory without a content file
+fn structure_finding(article_dir: &Path, slug: &str) -> DoctorFinding {
+    let is_empty = std::fs::read_dir(article_dir).is_ok_and(|mut entries| entries.next().is_none());
+
+    let (severity, message, suggestion) = if is_empty {
+        (
+            Severity::Warning,
+            "Empty article directory",
+            "Delete the directory or add the article".to_string(),
+        )
+    } else {
+        (
+            Severity::Error,
+            "Article directory has no content file",
+            format!("Add one of: {}", common_fs::content_path::content_file_names(slug).join(", ")),
+        )
+    };
+
+    let mut finding = DoctorFinding::new(severity, DoctorCheck::Structure, Some(article_dir), message);
+    finding.suggestion = Some(suggestion);
+    finding
+}
+
+/// Check that the frontmatter parses and has the fields an article needs
+fn check_frontmatter(
+    file_path: &Path,
+    content: &str,
+    overrides: &SeverityOverrides,
+    findings: &mut Vec<DoctorFinding>,
+) {
+    let frontmatter = match extract_frontmatter_and_content(content) {
+        Ok((frontmatter, _)) => frontmatter,
+        Err(err) => {
+            let mut finding = DoctorFinding::new(
+                Severity::Error,
+                DoctorCheck::Frontmatter,
+                Some(file_path),
+                format!("Invalid frontmatter: {}", err.message()),
+            );
+            finding.suggestion = Some("Start the file with YAML frontmatter between --- lines".to_string());
+            findings.push(finding);
+            return;
+        }
+    };
+
+    if frontmatter.title.trim().is_empty() {
+        findings.push(DoctorFinding::new(
+            Severity::Error,
+            DoctorCheck::Frontmatter,
+            Some(file_path),
+            "Missing title",
+        ));
+    }
+
+    if !frontmatter.is_draft.unwrap_or(false) && frontmatter.published_at.is_none() {
+        findings.push(DoctorFinding::new(
+            Severity::Warning,
+            DoctorCheck::Frontmatter,
+            Some(file_path),
+            "Published article has no publish date",
+        ));
+    }
+
+    if frontmatter.description.as_deref().is_none_or(|description| description.trim().is_empty()) {
+        findings.push(DoctorFinding::new(
+            Severity::Info,
+            DoctorCheck::Frontmatter,
+            Some(file_path),
+            "Missing description",
+        ));
+    }
+
+    let mut issues = Vec::new();
+    crate::validate_publish_at(content, &mut issues);
+    overrides.apply(&mut issues);
+    findings.extend(
+        issues
+            .into_iter()
+            .map(|issue| DoctorFinding::from_issue(DoctorCheck::Frontmatter, file_path, issue)),
+    );
+}
@@ -0,0 +1,118 @@
+//! # Pull Request Comments
+//!
+//! Renders a validation report as GitHub-flavored markdown for a CI job to
+//! post on a pull request: a status line, the issue counts (compared with
+//! the base branch when its JSON report is given), and each file's issues in
+//! a collapsed section. Files with errors come first, and files that don't
+//! fit in GitHub's comment size limit are left out with a note saying so.
+
+use anyhow::{Context, Result};
+use common_cli::github::{details, fit_sections};
+use std::path::Path;
+
+use crate::{Severity, SeverityCounts, ValidationReport};
+
+pub use common_cli::github::COMMENT_LIMIT;
+
+/// Load the issue totals from a JSON validation report, such as one for the base branch
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or has no summary totals
+pub fn load_base_totals(path: &Path) -> Result<SeverityCounts> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let report: serde_json::Value =
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))?;
+    serde_json::from_value(report["summary"]["totals"].clone())
+        .with_context(|| format!("No summary totals in {:?}", path))
+}
+
+/// A validation report as a pull request comment of at most `limit` characters
+pub fn github_comment(report: &ValidationReport, base: Option<&SeverityCounts>, limit: usize) -> String {
+    let totals = &report.summary.totals;
+    let status = if totals.errors > 0 {
+        format!("## ❌ Content validation failed: {} {}", totals.errors, plural(totals.errors, "error"))
+    } else if totals.warnings > 0 {
+        format!("## ⚠️ Content validation passed with {} {}", totals.warnings, plural(totals.warnings, "warning"))
+    } else {
+        "## ✅ Content validation passed".to_string()
+    };
+
+    let mut head = vec![
+        status,
+        String::new(),
+        format!(
+            "Checked {} {}: {} with issues.",
+            report.summary.files_checked,
+            plural(report.summary.files_checked, "file"),
+            report.results.len()
+        ),
+        String::new(),
+        "| | Issues | Compared to base |".to_string(),
+        "|---|---:|---:|".to_string(),
+    ];
+    for (label, current, base) in [
+        ("❌ Errors", totals.errors, base.map(|base| base.errors)),
+        ("⚠️ Warnings", totals.warnings, base.map(|base| base.warnings)),
+        ("ℹ️ Info", totals.info, base.map(|base| base.info)),
+    ] {
+        head.push(format!("| {} | {} | {} |", label, current, change(current, base)));
+    }
+    let head = head.join("\n");
+
+    // Files with the most serious issues come first, so truncation drops the least
+    let mut results: Vec<_> = report.results.iter().collect();
+    results.sort_by_key(|result| {
+        let counts = report.summary.files.get(&result.file_path).copied().unwrap_or_default();
+        (std::cmp::Reverse(counts.errors), std::cmp::Reverse(counts.warnings))
+    });
+
+    let sections: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let summary = format!(
+                "<code>{}</code> ({} {})",
+                result.file_path.display(),
+                result.issues.len(),
+                plural(result.issues.len(), "issue")
+            );
+            let issues: Vec<String> = result
+                .issues
+                .iter()
+                .map(|issue| {
+                    let location = issue.line.map(|line| format!("line {}: ", line)).unwrap_or_default();
+                    format!("- {} {}{}", severity_emoji(issue.severity), location, issue.description)
+                })
+                .collect();
+            details(&summary, &issues.join("\n"))
+        })
+        .collect();
+
+    fit_sections(head, sections, limit, "file")
+}
+
+/// The change from a base count, such as `+2` or `-1`
+fn change(current: usize, base: Option<usize>) -> String {
+    match base {
+        None => "–".to_string(),
+        Some(base) if current > base => format!("🔺 +{}", current - base),
+        Some(base) if current < base => format!("🔻 -{}", base - current),
+        Some(_) => "no change".to_string(),
+    }
+}
+
+fn severity_emoji(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "❌",
+        Severity::Warning => "⚠️",
+        Severity::Info => "ℹ️",
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        noun.to_string()
+    } else {
+        format!("{}s", noun)
+    }
+}
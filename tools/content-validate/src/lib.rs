@@ -1,16 +1,67 @@
 use anyhow::Result;
 use common_config::load_config;
-use common_fs::{find_files_with_extension, read_file};
+use common_errors::{retry_with_backoff, RetryPolicy, WritingError};
+use common_fs::{discover_files, DiscoveryOptions, FileSystem, FsContentRepository, RealFileSystem};
+use common_git::ChangedArticle;
 use common_markdown::extract_frontmatter_and_content;
 use common_models::Config;
 use common_models::Frontmatter;
+use common_traits::{CancellationToken, ContentRepository};
 use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
 use reqwest::blocking::Client;
 use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use thiserror::Error;
 
+pub mod a11y;
+pub mod alt_text;
+pub mod dead_links;
+pub mod doctor;
+pub mod github;
+pub mod link_policy;
+pub mod lint;
+pub mod precommit;
+pub mod prose;
+pub mod report;
+pub mod seo;
+pub mod severity;
+pub mod spelling;
+pub mod summary;
+
+pub use a11y::{contrast_ratio, parse_color, validate_accessibility, validate_html_output, MIN_CONTRAST_RATIO};
+pub use alt_text::{
+    alt_text_report, alt_text_report_in, find_missing_alt_text, suggest_alt_text, validate_alt_text, AltTextEntry,
+    AltTextReport, MissingAltText,
+};
+pub use dead_links::{
+    lookup_snapshot, parse_snapshot, quarantine_path, rewrite_link, ArchiveFallback, LinkQuarantine, QuarantinedLink,
+    QUARANTINE_AFTER, QUARANTINE_FILE, WAYBACK_API,
+};
+pub use doctor::{run_doctor, DoctorCheck, DoctorFinding, DoctorOptions, DoctorReport};
+pub use github::{github_comment, load_base_totals, COMMENT_LIMIT};
+pub use link_policy::{is_tracking_param, validate_link_policy, without_tracking_params};
+pub use lint::{
+    lint_config_path, load_lint_config, LintConfig, LintContext, LintOutcome, LintRule, Linter, RuleRegistry, Suppressions,
+    LINT_FILE,
+};
+pub use precommit::{install_pre_commit_hook, validate_staged, PreCommitRule};
+pub use prose::{
+    find_banned_phrases, find_cliches, find_conjunction_starts, find_repeated_words, find_weasel_words,
+    load_banned_phrases, styles_dir, BannedPhrase, STYLES_DIR,
+};
+pub use report::{severity_label, CategoryCounts, IssueCategory, ValidationReport};
+pub use seo::{audit_article, seo_report, seo_report_in, SeoArticle, SeoAudit, SeoReport};
+pub use severity::{load_severity_overrides, severities_path, SeverityOverrides};
+pub use spelling::{
+    article_language, detect_language, dictionaries_dir, find_misspellings, load_dictionary, topic_of, Dictionary,
+    DICTIONARIES_DIR,
+};
+pub use summary::{SeverityCounts, ValidationSummary};
+
 /// Link kind
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum LocalLinkKind {
@@ -71,27 +122,26 @@ pub fn extract_links(content: &str) -> Vec<Link> {
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_TABLES);
 
-    let parser = Parser::new_ext(content, options);
+    let parser = Parser::new_ext(content, options).into_offset_iter();
 
-    for event in parser {
-        if let Event::Start(Tag::Link(_, ref url, _)) = event {
-            let kind = if url.starts_with("http://") || url.starts_with("https://") {
-                LocalLinkKind::External
-            } else {
-                LocalLinkKind::Internal
-            };
+    for (event, range) in parser {
+        let url = match &event {
+            Event::Start(Tag::Link(_, url, _)) | Event::Start(Tag::Image(_, url, _)) => url,
+            _ => continue,
+        };
 
-            links.push(Link::new(url.to_string(), kind, None, None));
-        }
-        if let Event::Start(Tag::Image(_, ref url, _)) = event {
-            let kind = if url.starts_with("http://") || url.starts_with("https://") {
-                LocalLinkKind::External
-            } else {
-                LocalLinkKind::Internal
-            };
+        let kind = if url.starts_with("http://") || url.starts_with("https://") {
+            LocalLinkKind::External
+        } else {
+            LocalLinkKind::Internal
+        };
 
-            links.push(Link::new(url.to_string(), kind, None, None));
-        }
+        // Issues need a line for suppression comments to apply
+        let line_start = content[..range.start].rfind('\n').map_or(0, |index| index + 1);
+        let line = prose::line_at(content, range.start);
+        let column = content[line_start..range.start].chars().count() + 1;
+
+        links.push(Link::new(url.to_string(), kind, Some(line), Some(column)));
     }
 
     links
@@ -124,6 +174,9 @@ pub enum ValidationError {
 pub enum ValidationType {
     Links,
     Markdown,
+    Assets,
+    PublishDate,
+    DuplicateSlugs,
     All,
 }
 
@@ -136,19 +189,30 @@ pub struct ValidationOptions {
     pub timeout: Option<u64>,
     pub dictionary_path: Option<PathBuf>,
     pub include_drafts: bool,
+    /// Directory of image-build output, checked for images the article directory lacks
+    pub images_dir: Option<PathBuf>,
+    /// Only validate articles changed since this git reference
+    pub changed_since: Option<String>,
+    /// What to do about external links that have been dead for several runs
+    pub archive_fallback: ArchiveFallback,
 }
 
 /// Validation result
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct ValidationResult {
     pub file_path: PathBuf,
     pub issues: Vec<ValidationIssue>,
+    /// Issues silenced by suppression comments, by rule ID
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub suppressed: BTreeMap<String, usize>,
 }
 
 /// Validation issue
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct ValidationIssue {
     pub issue_type: ValidationIssueType,
+    /// The issue type's default severity, unless overridden in `severities.yaml`
+    pub severity: Severity,
     pub line: Option<usize>,
     pub column: Option<usize>,
     pub description: String,
@@ -156,12 +220,107 @@ pub struct ValidationIssue {
 }
 
 /// Validation issue type
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ValidationIssueType {
     BrokenLink,
     MissingInternalLink,
     InvalidUrl,
     MarkdownFormatting,
+    InvalidPublishDate,
+    UnknownComponent,
+    DuplicateSlug,
+    MissingImage,
+    UnusedAsset,
+    InvalidFrontmatter,
+    InvalidSlug,
+    MissingAltText,
+    HeadingOrder,
+    MissingTableHeader,
+    NonDescriptiveLink,
+    LowContrast,
+    TitleLength,
+    MetaDescription,
+    MultipleH1,
+    MissingKeyword,
+    LowAltCoverage,
+    FewInternalLinks,
+    InvalidCanonical,
+    DeniedDomain,
+    UnlistedDomain,
+    InsecureLink,
+    TrackingParameter,
+    ArchivedLink,
+    WeaselWord,
+    Cliche,
+    RepeatedWord,
+    ConjunctionStart,
+    BannedPhrase,
+    Misspelling,
+}
+
+impl ValidationIssueType {
+    /// How serious an issue of this type is
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            ValidationIssueType::BrokenLink
+            | ValidationIssueType::InvalidUrl
+            | ValidationIssueType::InvalidPublishDate
+            | ValidationIssueType::DuplicateSlug
+            | ValidationIssueType::MissingImage
+            | ValidationIssueType::InvalidFrontmatter
+            | ValidationIssueType::InvalidSlug
+            | ValidationIssueType::DeniedDomain => Severity::Error,
+            ValidationIssueType::MissingInternalLink
+            | ValidationIssueType::MarkdownFormatting
+            | ValidationIssueType::UnknownComponent
+            | ValidationIssueType::MissingAltText
+            | ValidationIssueType::HeadingOrder
+            | ValidationIssueType::MissingTableHeader
+            | ValidationIssueType::NonDescriptiveLink
+            | ValidationIssueType::LowContrast
+            | ValidationIssueType::TitleLength
+            | ValidationIssueType::MetaDescription
+            | ValidationIssueType::MultipleH1
+            | ValidationIssueType::InvalidCanonical
+            | ValidationIssueType::UnlistedDomain
+            | ValidationIssueType::InsecureLink
+            | ValidationIssueType::TrackingParameter
+            | ValidationIssueType::RepeatedWord
+            | ValidationIssueType::BannedPhrase => Severity::Warning,
+            ValidationIssueType::UnusedAsset
+            | ValidationIssueType::ArchivedLink
+            | ValidationIssueType::MissingKeyword
+            | ValidationIssueType::LowAltCoverage
+            | ValidationIssueType::FewInternalLinks
+            | ValidationIssueType::WeaselWord
+            | ValidationIssueType::Cliche
+            | ValidationIssueType::ConjunctionStart
+            | ValidationIssueType::Misspelling => Severity::Info,
+        }
+    }
+}
+
+/// How serious a problem is, most serious first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Breaks the build or the published site
+    Error,
+    /// Likely a mistake, but the content still builds
+    Warning,
+    /// Worth knowing, such as files that could be cleaned up
+    Info,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
 }
 
 /// Validate content
@@ -180,348 +339,301 @@ pub enum ValidationIssueType {
 ///
 /// Returns an error if the validation fails
 pub fn validate_content(options: &ValidationOptions) -> Result<Vec<ValidationResult>> {
+    validate_content_cancellable(options, &CancellationToken::new())
+}
+
+/// Validate content, stopping between articles and links if `token` is cancelled
+///
+/// External link checks are the slowest part of validation, so the token is also
+/// checked before each outbound request. Rules turned off in `lint.yaml`
+/// are skipped, as are issues silenced by comments in the content.
+pub fn validate_content_cancellable(
+    options: &ValidationOptions,
+    token: &CancellationToken,
+) -> Result<Vec<ValidationResult>> {
     let config = load_config()?;
+    let overrides = load_severity_overrides(&severities_path(&config))?;
+    let linter = Linter::load(&config)?;
     let mut results = Vec::new();
 
-    // If a specific article is requested, only validate that article
-    if let Some(article_slug) = &options.article_slug {
-        if let Some(topic_key) = &options.topic {
-            // Validate a specific article in a specific topic
-            if let Some(topic_config) = config.content.topics.get(topic_key) {
-                let topic_dir =
-                    PathBuf::from(&config.content.base_dir).join(&topic_config.directory);
-                let article_dir = topic_dir.join(article_slug);
-
-                if !article_dir.exists() {
-                    return Err(ValidationError::ArticleNotFound(article_slug.clone()).into());
-                }
-
-                // Check for content file with matching name
-                let content_file_md = article_dir.join(format!("{}.md", article_slug));
-                let content_file_mdx = article_dir.join(format!("{}.mdx", article_slug));
-
-                // Determine which file to use
-                let content_file = if content_file_md.exists() {
-                    content_file_md
-                } else if content_file_mdx.exists() {
-                    content_file_mdx
-                } else {
-                    return Err(ValidationError::ArticleNotFound(article_slug.clone()).into());
-                };
-
-                let content = read_file(&content_file)?;
-
-                // Validate the article
-                let mut issues = Vec::new();
-
-                for validation_type in &options.validation_types {
-                    match validation_type {
-                        ValidationType::Links => {
-                            validate_links(&content_file, &content, &config, options, &mut issues)?;
-                        }
-                        ValidationType::Markdown => {
-                            validate_markdown(&content_file, &content, &mut issues)?;
-                        }
-                        ValidationType::All => {
-                            validate_links(&content_file, &content, &config, options, &mut issues)?;
-                            validate_markdown(&content_file, &content, &mut issues)?;
-                        }
-                    }
-                }
-
-                results.push(ValidationResult {
-                    file_path: content_file,
-                    issues,
-                });
-            } else {
-                return Err(ValidationError::TopicNotFound(topic_key.clone()).into());
-            }
-        } else {
-            // Validate a specific article in any topic
-            let mut found = false;
-
-            for (_topic_key, topic_config) in &config.content.topics {
-                let topic_dir =
-                    PathBuf::from(&config.content.base_dir).join(&topic_config.directory);
-                let article_dir = topic_dir.join(article_slug);
-
-                if article_dir.exists() {
-                    // Check for content file with matching name
-                    let content_file_md = article_dir.join(format!("{}.md", article_slug));
-                    let content_file_mdx = article_dir.join(format!("{}.mdx", article_slug));
-
-                    // Determine which file to use
-                    let content_file = if content_file_md.exists() {
-                        Some(content_file_md)
-                    } else if content_file_mdx.exists() {
-                        Some(content_file_mdx)
-                    } else {
-                        None
-                    };
-
-                    if let Some(content_file) = content_file {
-                        found = true;
-                        let content = read_file(&content_file)?;
-
-                        // Validate the article
-                        let mut issues = Vec::new();
-
-                        for validation_type in &options.validation_types {
-                            match validation_type {
-                                ValidationType::Links => {
-                                    validate_links(
-                                        &content_file,
-                                        &content,
-                                        &config,
-                                        options,
-                                        &mut issues,
-                                    )?;
-                                }
-                                ValidationType::Markdown => {
-                                    validate_markdown(&content_file, &content, &mut issues)?;
-                                }
-                                ValidationType::All => {
-                                    validate_links(
-                                        &content_file,
-                                        &content,
-                                        &config,
-                                        options,
-                                        &mut issues,
-                                    )?;
-                                    validate_markdown(&content_file, &content, &mut issues)?;
-                                }
-                            }
-                        }
-
-                        results.push(ValidationResult {
-                            file_path: content_file,
-                            issues,
-                        });
-                    }
-                }
+    // Articles changed since the requested git reference, if any
+    let changed = match &options.changed_since {
+        Some(reference) => Some(common_git::changed_content_since(&config, reference)?),
+        None => None,
+    };
+
+    // Links that failed earlier runs, kept up to date when external links are checked
+    let quarantine_file = quarantine_path(&config);
+    let mut quarantine = LinkQuarantine::load(&quarantine_file)?;
+
+    // Listed once for the run, not once per article
+    let slugs = slug_topics(&config);
+
+    let repository = FsContentRepository::new(&config);
+    let topic_dir = |topic_key: &String| {
+        repository
+            .topic_dir(topic_key)
+            .map_err(|_| ValidationError::TopicNotFound(topic_key.clone()))
+    };
+
+    // A requested article is validated even if it is a draft or unchanged
+    let (articles, requested) = if let Some(article_slug) = &options.article_slug {
+        let articles = match &options.topic {
+            Some(topic_key) => {
+                topic_dir(topic_key)?;
+                repository.find_article(topic_key, article_slug)?.into_iter().collect()
             }
+            None => repository.find_articles(article_slug)?,
+        };
 
-            if !found {
-                return Err(ValidationError::ArticleNotFound(article_slug.clone()).into());
-            }
+        if articles.is_empty() {
+            return Err(ValidationError::ArticleNotFound(article_slug.clone()).into());
         }
+        (articles, true)
     } else if let Some(topic_key) = &options.topic {
-        // Validate all articles in a specific topic
-        if let Some(topic_config) = config.content.topics.get(topic_key) {
-            let topic_dir = PathBuf::from(&config.content.base_dir).join(&topic_config.directory);
-
-            if !topic_dir.exists() {
-                return Err(ValidationError::TopicNotFound(topic_key.clone()).into());
-            }
-
-            // Find all content directories in the topic
-            let entries = std::fs::read_dir(&topic_dir)?;
-
-            for entry in entries {
-                let entry = entry?;
-                let path = entry.path();
-
-                if path.is_dir() {
-                    let slug = path
-                        .file_name()
-                        .and_then(|name| name.to_str())
-                        .unwrap_or("")
-                        .to_string();
-
-                    if slug.is_empty() {
-                        continue;
-                    }
-
-                    // Check for content files with matching name
-                    let content_file_md = path.join(format!("{}.md", slug));
-                    let content_file_mdx = path.join(format!("{}.mdx", slug));
-
-                    // Determine which file to use
-                    let content_file = if content_file_md.exists() {
-                        Some(content_file_md)
-                    } else if content_file_mdx.exists() {
-                        Some(content_file_mdx)
-                    } else {
-                        None
-                    };
-
-                    if let Some(content_file) = content_file {
-                        let content = read_file(&content_file)?;
-
-                        // Check if the content is a draft
-                        if !options.include_drafts && is_draft(&content) {
-                            continue;
-                        }
-
-                        // Validate the article
-                        let mut issues = Vec::new();
-
-                        for validation_type in &options.validation_types {
-                            match validation_type {
-                                ValidationType::Links => {
-                                    validate_links(
-                                        &content_file,
-                                        &content,
-                                        &config,
-                                        options,
-                                        &mut issues,
-                                    )?;
-                                }
-                                ValidationType::Markdown => {
-                                    validate_markdown(&content_file, &content, &mut issues)?;
-                                }
-                                ValidationType::All => {
-                                    validate_links(
-                                        &content_file,
-                                        &content,
-                                        &config,
-                                        options,
-                                        &mut issues,
-                                    )?;
-                                    validate_markdown(&content_file, &content, &mut issues)?;
-                                }
-                            }
-                        }
-
-                        results.push(ValidationResult {
-                            file_path: content_file,
-                            issues,
-                        });
-                    }
-                }
-            }
-        } else {
+        if !topic_dir(topic_key)?.exists() {
             return Err(ValidationError::TopicNotFound(topic_key.clone()).into());
         }
+        (repository.list_articles(topic_key)?, false)
     } else {
-        // Validate all articles in all topics
-        for (_topic_key, topic_config) in &config.content.topics {
-            let topic_dir = PathBuf::from(&config.content.base_dir).join(&topic_config.directory);
+        (repository.article_paths()?, false)
+    };
 
-            if !topic_dir.exists() {
-                continue;
-            }
+    for article in articles {
+        if !requested && !is_changed(&changed, &article.topic, &article.slug) {
+            continue;
+        }
 
-            // Find all content directories in the topic
-            let entries = std::fs::read_dir(&topic_dir)?;
+        token.check("content validation")?;
+        let content = repository.read_article(&article)?;
 
-            for entry in entries {
-                let entry = entry?;
-                let path = entry.path();
+        // Check if the content is a draft
+        if !requested && !options.include_drafts && is_draft(&content) {
+            continue;
+        }
 
-                if path.is_dir() {
-                    let slug = path
-                        .file_name()
-                        .and_then(|name| name.to_str())
-                        .unwrap_or("")
-                        .to_string();
+        let mut context = LintContext {
+            file_path: &article.content_file,
+            config: &config,
+            options,
+            token,
+            quarantine: &mut quarantine,
+            slug_topics: &slugs,
+        };
+        let outcome = linter.lint(&content, &mut context)?;
+
+        results.push(ValidationResult {
+            file_path: article.content_file,
+            issues: outcome.issues,
+            suppressed: outcome.suppressed,
+        });
+    }
 
-                    if slug.is_empty() {
-                        continue;
-                    }
+    if options.check_external_links {
+        quarantine.save(&quarantine_file)?;
+    }
 
-                    // Check for content files with matching name
-                    let content_file_md = path.join(format!("{}.md", slug));
-                    let content_file_mdx = path.join(format!("{}.mdx", slug));
-
-                    // Determine which file to use
-                    let content_file = if content_file_md.exists() {
-                        Some(content_file_md)
-                    } else if content_file_mdx.exists() {
-                        Some(content_file_mdx)
-                    } else {
-                        None
-                    };
+    for result in &mut results {
+        overrides.apply(&mut result.issues);
+    }
 
-                    if let Some(content_file) = content_file {
-                        let content = read_file(&content_file)?;
+    Ok(results)
+}
 
-                        // Check if the content is a draft
-                        if !options.include_drafts && is_draft(&content) {
-                            continue;
-                        }
+/// Validate the text of one content file, which need not be saved yet
+///
+/// Lets editor integrations check a buffer as it is typed. `file_path` is
+/// where the content lives, for resolving relative links and images; the
+/// file itself is not read. Severity overrides are applied as for
+/// [`validate_content`].
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be loaded or a validation fails to run
+pub fn validate_text(file_path: &Path, content: &str, options: &ValidationOptions) -> Result<Vec<ValidationIssue>> {
+    let config = load_config()?;
+    let overrides = load_severity_overrides(&severities_path(&config))?;
+
+    // The buffer may not be saved, so a fresh quarantine keeps dead links from being rewritten
+    let mut quarantine = LinkQuarantine::default();
+    let token = CancellationToken::new();
+    let slugs = slug_topics(&config);
+    let mut context = LintContext {
+        file_path,
+        config: &config,
+        options,
+        token: &token,
+        quarantine: &mut quarantine,
+        slug_topics: &slugs,
+    };
+    let mut issues = Linter::load(&config)?.lint(content, &mut context)?.issues;
+    overrides.apply(&mut issues);
+
+    Ok(issues)
+}
 
-                        // Validate the article
-                        let mut issues = Vec::new();
-
-                        for validation_type in &options.validation_types {
-                            match validation_type {
-                                ValidationType::Links => {
-                                    validate_links(
-                                        &content_file,
-                                        &content,
-                                        &config,
-                                        options,
-                                        &mut issues,
-                                    )?;
-                                }
-                                ValidationType::Markdown => {
-                                    validate_markdown(&content_file, &content, &mut issues)?;
-                                }
-                                ValidationType::All => {
-                                    validate_links(
-                                        &content_file,
-                                        &content,
-                                        &config,
-                                        options,
-                                        &mut issues,
-                                    )?;
-                                    validate_markdown(&content_file, &content, &mut issues)?;
-                                }
-                            }
-                        }
+/// Whether an article is among the changed articles, if only those are being validated
+fn is_changed(changed: &Option<BTreeSet<ChangedArticle>>, topic: &str, slug: &str) -> bool {
+    changed.as_ref().is_none_or(|changed| {
+        changed.contains(&ChangedArticle {
+            topic: topic.to_string(),
+            slug: slug.to_string(),
+        })
+    })
+}
 
-                        results.push(ValidationResult {
-                            file_path: content_file,
-                            issues,
-                        });
-                    }
-                }
-            }
+/// Frontmatter fields that set an article's canonical URL, in order of precedence
+pub const CANONICAL_FIELDS: &[&str] = &["canonical", "canonical_url"];
+
+/// Check the canonical URL set in an article's frontmatter
+///
+/// An absolute URL must be http or https. A path, or a URL on the site,
+/// must end in the `{topic}/{slug}` of an existing article, looked up in
+/// `slug_topics` (see [`slug_topics`]); canonical URLs on other sites aren't
+/// followed.
+pub fn validate_canonical(
+    content: &str,
+    config: &Config,
+    slug_topics: &BTreeMap<String, Vec<String>>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let frontmatter = match extract_frontmatter_and_content(content) {
+        Ok((frontmatter, _)) => frontmatter,
+        Err(_) => return,
+    };
+    let Some((field, value)) = CANONICAL_FIELDS
+        .iter()
+        .find_map(|field| frontmatter.get_string(field).map(|value| (*field, value.trim())))
+        .filter(|(_, value)| !value.is_empty())
+    else {
+        return;
+    };
+
+    let line = content
+        .lines()
+        .position(|line| line.trim_start().starts_with(&format!("{}:", field)))
+        .map(|index| index + 1);
+    let mut warn = |description: String, suggested_fix: &str| {
+        issues.push(ValidationIssue {
+            issue_type: ValidationIssueType::InvalidCanonical,
+            severity: Severity::Warning,
+            line,
+            column: None,
+            description,
+            suggested_fix: Some(suggested_fix.to_string()),
+        });
+    };
+
+    // Anything with a scheme is a URL, even a mistyped one like `htps:/`
+    let path = if value.contains(':') || value.starts_with("//") {
+        let valid = Url::parse(value).is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some());
+        if !valid {
+            warn(format!("Malformed canonical URL: {}", value), "Use an absolute http(s) URL or a site path like /blog/slug");
+            return;
         }
-    }
 
-    Ok(results)
+        let site_url = config.publication.site_url.as_deref().unwrap_or_default().trim_end_matches('/');
+        match value.strip_prefix(site_url).filter(|path| !site_url.is_empty() && (path.is_empty() || path.starts_with('/'))) {
+            Some(path) => path,
+            None => return,
+        }
+    } else {
+        value
+    };
+
+    let target = path.split(['?', '#']).next().unwrap_or_default();
+    let segments: Vec<&str> = target.split('/').filter(|segment| !segment.is_empty()).collect();
+    let exists = match segments.as_slice() {
+        [.., topic, slug] => slug_topics
+            .get(*slug)
+            .is_some_and(|topics| topics.iter().any(|key| key == topic)),
+        _ => false,
+    };
+    if !exists {
+        warn(
+            format!("Canonical URL doesn't point at an article: {}", value),
+            "Point it at an existing article as /{topic}/{slug}, or remove it",
+        );
+    }
 }
 
-/// Validate links in content
-fn validate_links(
-    _file_path: &Path,
-    content: &str,
-    _config: &Config,
+/// Check that each link resolves
+///
+/// Relative links are resolved against the directory of the content file.
+/// External links that fail are counted in `quarantine`, and dead ones are
+/// handled as `options.archive_fallback` says.
+fn check_links(
+    file_path: &Path,
+    links: &[Link],
     options: &ValidationOptions,
+    token: &CancellationToken,
+    quarantine: &mut LinkQuarantine,
     issues: &mut Vec<ValidationIssue>,
 ) -> Result<()> {
-    // Extract links from content
-    let links = extract_links(content);
+    let _span = tracing::debug_span!("link_check", file = %file_path.display()).entered();
 
-    // Check each link
-    for link in &links {
+    for link in links {
         // Check if the link is a URL
         if *link.kind() == LocalLinkKind::External {
             // Check if the URL is valid
             if let Ok(url) = Url::parse(link.url()) {
                 // Check if the URL is accessible
                 if options.check_external_links {
+                    token.check("link checking")?;
+                    tracing::debug!(url = %url, "Checking external link");
+
                     let client = Client::builder()
                         .timeout(Duration::from_secs(options.timeout.unwrap_or(10)))
                         .build()?;
 
-                    let response = client.head(url.clone()).send();
+                    // Timeouts and dropped connections often succeed on a second try
+                    let response = retry_with_backoff(
+                        || client.head(url.clone()).send().map_err(request_error),
+                        &RetryPolicy::default(),
+                    );
+
+                    let e = match response {
+                        Ok(_) => {
+                            quarantine.record_success(link.url());
+                            continue;
+                        }
+                        Err(e) => e,
+                    };
+
+                    let dead = quarantine.record_failure(link.url());
+                    let archived = match options.archive_fallback {
+                        ArchiveFallback::Off => None,
+                        _ if !dead => None,
+                        _ => quarantine.archived_url(&client, link.url()),
+                    };
 
-                    if let Err(e) = response {
-                        issues.push(ValidationIssue {
+                    match archived {
+                        Some(archived) if options.archive_fallback == ArchiveFallback::Rewrite => {
+                            if rewrite_link(file_path, link.url(), &archived)? {
+                                issues.push(ValidationIssue {
+                                    issue_type: ValidationIssueType::ArchivedLink,
+                                    severity: Severity::Info,
+                                    line: link.line(),
+                                    column: link.column(),
+                                    description: format!("Replaced dead link {} with {}", link.url(), archived),
+                                    suggested_fix: None,
+                                });
+                            }
+                        }
+                        archived => issues.push(ValidationIssue {
                             issue_type: ValidationIssueType::BrokenLink,
+                            severity: Severity::Error,
                             line: link.line(),
                             column: link.column(),
-                            description: format!("Broken link: {} ({})", link.url(), e),
-                            suggested_fix: None,
-                        });
+                            description: format!("Broken link: {} ({})", link.url(), e.message()),
+                            suggested_fix: archived.map(|archived| format!("Link to the archived copy: {}", archived)),
+                        }),
                     }
                 }
             } else {
                 issues.push(ValidationIssue {
                     issue_type: ValidationIssueType::InvalidUrl,
+                    severity: Severity::Error,
                     line: link.line(),
                     column: link.column(),
                     description: format!("Invalid URL: {}", link.url()),
@@ -529,12 +641,22 @@ fn validate_links(
                 });
             }
         } else {
-            // Check if the internal link exists
-            let target_path = PathBuf::from(link.url());
+            // Check if the internal link exists, ignoring any query or fragment
+            let target = link.url().split(['?', '#']).next().unwrap_or_default();
+            if target.is_empty() {
+                continue;
+            }
+
+            let target_path = if target.starts_with('/') {
+                PathBuf::from(target)
+            } else {
+                file_path.parent().unwrap_or(Path::new("")).join(target)
+            };
 
             if !target_path.exists() {
                 issues.push(ValidationIssue {
                     issue_type: ValidationIssueType::MissingInternalLink,
+                    severity: Severity::Warning,
                     line: link.line(),
                     column: link.column(),
                     description: format!("Missing internal link: {}", link.url()),
@@ -547,39 +669,309 @@ fn validate_links(
     Ok(())
 }
 
-/// Validate markdown in content
-fn validate_markdown(
-    _file_path: &Path,
-    _content: &str,
-    _issues: &mut Vec<ValidationIssue>,
-) -> Result<()> {
-    // TODO: Implement markdown validation
+/// Convert a failed request to an error, marking network failures as transient
+fn request_error(err: reqwest::Error) -> WritingError {
+    let error = WritingError::other(err.to_string());
+    if err.is_timeout() || err.is_connect() {
+        WritingError::transient(error)
+    } else {
+        error
+    }
+}
 
-    Ok(())
+/// Report MDX components that aren't registered in `shortcodes.yaml`
+///
+/// The build strips unknown components, so they would silently disappear
+/// from the published article.
+pub fn validate_components(content: &str, known: &[&str], issues: &mut Vec<ValidationIssue>) {
+    for component in common_markdown::find_components(content) {
+        if !known.contains(&component.name.as_str()) {
+            issues.push(ValidationIssue {
+                issue_type: ValidationIssueType::UnknownComponent,
+                severity: Severity::Warning,
+                line: Some(component.line),
+                column: None,
+                description: format!("Unknown component <{}>", component.name),
+                suggested_fix: Some(format!(
+                    "Add {} to {} or remove it",
+                    component.name,
+                    common_config::shortcodes::SHORTCODES_FILE
+                )),
+            });
+        }
+
+        // Check nested components, keeping line numbers relative to the whole file
+        if let Some(children) = &component.children {
+            let mut nested = Vec::new();
+            validate_components(children, known, &mut nested);
+
+            // Children are followed by the `</Name>` closing tag
+            let children_start = component.range.end - component.name.len() - 3 - children.len();
+            let line_offset = content[..children_start].matches('\n').count();
+            for mut issue in nested {
+                issue.line = issue.line.map(|line| line + line_offset);
+                issues.push(issue);
+            }
+        }
+    }
 }
 
-/// Validate resources in content
-#[allow(dead_code)]
-fn validate_resources(
+/// Map each article slug to the topics that contain it, sorted by topic key
+pub fn slug_topics(config: &Config) -> BTreeMap<String, Vec<String>> {
+    slug_topics_with_fs(&RealFileSystem, config)
+}
+
+/// Map each article slug to the topics that contain it, listing topics through `fs`
+pub fn slug_topics_with_fs(fs: &dyn FileSystem, config: &Config) -> BTreeMap<String, Vec<String>> {
+    let base_dir = PathBuf::from(&config.content.base_dir);
+    let mut slugs: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for (topic_key, topic_config) in &config.content.topics {
+        let article_dirs = match fs.list_dirs(&base_dir.join(&topic_config.directory)) {
+            Ok(article_dirs) => article_dirs,
+            Err(_) => continue,
+        };
+
+        for article_dir in article_dirs {
+            if let Some(slug) = article_dir.file_name() {
+                slugs
+                    .entry(slug.to_string_lossy().into_owned())
+                    .or_default()
+                    .push(topic_key.clone());
+            }
+        }
+    }
+
+    for topics in slugs.values_mut() {
+        topics.sort();
+    }
+
+    slugs
+}
+
+/// Report an article whose slug is also used in another topic
+///
+/// `slug_topics` maps each slug to the topics using it, as built by
+/// [`slug_topics`]. Build output and feed entries are named by slug, so
+/// articles sharing a slug overwrite each other unless the build is told to
+/// allow it.
+pub fn validate_slug_uniqueness(
     file_path: &Path,
-    _content: &str,
-    _config: &Config,
-    _issues: &mut Vec<ValidationIssue>,
+    slug_topics: &BTreeMap<String, Vec<String>>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let slug = match file_path.parent().and_then(Path::file_name) {
+        Some(slug) => slug.to_string_lossy(),
+        None => return,
+    };
+    let topics = match slug_topics.get(slug.as_ref()) {
+        Some(topics) if topics.len() > 1 => topics,
+        _ => return,
+    };
+
+    issues.push(ValidationIssue {
+        issue_type: ValidationIssueType::DuplicateSlug,
+        severity: Severity::Error,
+        line: None,
+        column: None,
+        description: format!("Slug '{}' is used in more than one topic: {}", slug, topics.join(", ")),
+        suggested_fix: Some(
+            "Rename one of the articles, or build with --allow-duplicate-slugs".to_string(),
+        ),
+    });
+}
+
+/// Check the date frontmatter fields: `published` and `updated` must be dates
+/// or `DRAFT`, and `publish_at` must parse as an RFC 3339 timestamp
+fn validate_publish_at(content: &str, issues: &mut Vec<ValidationIssue>) {
+    let frontmatter = match extract_frontmatter_and_content(content) {
+        Ok((frontmatter, _)) => frontmatter,
+        Err(_) => return,
+    };
+
+    for (field, date) in [("published", &frontmatter.published_at), ("updated", &frontmatter.updated_at)] {
+        if let Some(date) = date.as_ref().filter(|date| !date.is_valid()) {
+            let line = content
+                .lines()
+                .position(|line| line.trim_start().starts_with(&format!("{}:", field)))
+                .map(|index| index + 1);
+
+            issues.push(ValidationIssue {
+                issue_type: ValidationIssueType::InvalidPublishDate,
+                severity: Severity::Error,
+                line,
+                column: None,
+                description: format!("Invalid {} date '{}'", field, date),
+                suggested_fix: Some("Use YYYY-MM-DD, an RFC 3339 timestamp or DRAFT".to_string()),
+            });
+        }
+    }
+
+    if let Some(Err(err)) = frontmatter.publish_at_datetime() {
+        let line = content
+            .lines()
+            .position(|line| line.trim_start().starts_with("publish_at:"))
+            .map(|index| index + 1);
+
+        issues.push(ValidationIssue {
+            issue_type: ValidationIssueType::InvalidPublishDate,
+            severity: Severity::Error,
+            line,
+            column: None,
+            description: format!(
+                "Invalid publish_at '{}': {}",
+                frontmatter.publish_at.unwrap_or_default(),
+                err
+            ),
+            suggested_fix: Some("Use an RFC 3339 timestamp, e.g. 2024-06-01T09:00:00Z".to_string()),
+        });
+    }
+}
+
+/// Image file extensions, checked for files no article references
+pub const IMAGE_EXTENSIONS: &[&str] = &["avif", "gif", "jpeg", "jpg", "png", "svg", "webp"];
+
+/// Source image image-build turns into an article's responsive images
+pub const IMAGE_BUILD_SOURCE: &str = "index.jpg";
+
+/// URL prefix content-build gives the image-build output
+const IMAGES_URL: &str = "/images/";
+
+/// An image referenced from an article
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageReference {
+    pub url: String,
+    pub line: Option<usize>,
+}
+
+/// Find the local images an article references
+///
+/// Covers markdown images, `<img>` tags and the `featured_image` frontmatter
+/// field. Remote images, data URLs and site paths outside `/images/` aren't
+/// part of the article, so they are skipped.
+pub fn extract_image_references(content: &str) -> Vec<ImageReference> {
+    let line_at = |offset: usize| Some(content[..offset].matches('\n').count() + 1);
+    let img_tag = Regex::new(r#"<img\b[^>]*?\bsrc\s*=\s*["']([^"']+)["']"#).expect("valid regex");
+    let mut references = Vec::new();
+
+    if let Ok((frontmatter, _)) = extract_frontmatter_and_content(content) {
+        if let Some(url) = frontmatter.featured_image_path {
+            let line = content
+                .lines()
+                .position(|line| line.trim_start().starts_with("featured_image:"))
+                .map(|index| index + 1);
+            references.push(ImageReference { url, line });
+        }
+    }
+
+    for (event, range) in Parser::new_ext(content, Options::empty()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Image(_, url, _)) => references.push(ImageReference {
+                url: url.to_string(),
+                line: line_at(range.start),
+            }),
+            Event::Html(_) => {
+                for captures in img_tag.captures_iter(&content[range.clone()]) {
+                    let url = captures.get(1).expect("src group");
+                    references.push(ImageReference {
+                        url: url.as_str().to_string(),
+                        line: line_at(range.start + url.start()),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    references.retain(|reference| is_local_image(&reference.url));
+    references
+}
+
+/// Whether an image URL points at a file that belongs to the article
+fn is_local_image(url: &str) -> bool {
+    !(url.is_empty()
+        || url.starts_with('#')
+        || url.starts_with("data:")
+        || url.contains("://")
+        || url.starts_with("//")
+        || (url.starts_with('/') && !url.starts_with(IMAGES_URL)))
+}
+
+/// Report missing images and image files no article references
+///
+/// A relative image is found if it is in the article directory or in the
+/// article's image-build output under `images_dir`; an `/images/` URL is
+/// looked up in `images_dir` alone, and skipped without one. Image files in
+/// the article directory that are never referenced are dead weight, apart
+/// from the `index.jpg` image-build works from.
+///
+/// # Errors
+///
+/// Returns an error if the article directory can't be read
+pub fn validate_assets(
+    file_path: &Path,
+    content: &str,
+    config: &Config,
+    images_dir: Option<&Path>,
+    issues: &mut Vec<ValidationIssue>,
 ) -> Result<()> {
-    // Get the article directory
     let article_dir = file_path.parent().unwrap_or(Path::new(""));
 
-    // Get all files in the article directory
-    let _all_files = find_files_with_extension(&article_dir, "")?;
+    // image-build mirrors the content layout: `{images_dir}/{topic directory}/{slug}/`
+    let build_dir = images_dir.and_then(|images_dir| {
+        article_dir
+            .strip_prefix(&config.content.base_dir)
+            .ok()
+            .map(|relative| images_dir.join(relative))
+    });
 
-    // Check if there's a build directory
-    let build_dir = article_dir.join("build");
+    let mut referenced = Vec::new();
+    for reference in extract_image_references(content) {
+        let path = reference.url.split(['?', '#']).next().unwrap_or_default();
 
-    if build_dir.exists() {
-        // Get all files in the build directory
-        let _build_files = find_files_with_extension(&build_dir, "")?;
+        let found = if let Some(built) = path.strip_prefix(IMAGES_URL) {
+            images_dir.is_none_or(|images_dir| images_dir.join(built).is_file())
+        } else {
+            let relative = path.trim_start_matches("./");
+            referenced.push(PathBuf::from(relative));
+            article_dir.join(relative).is_file()
+                || build_dir.as_ref().is_some_and(|build_dir| build_dir.join(relative).is_file())
+        };
+
+        if !found {
+            issues.push(ValidationIssue {
+                issue_type: ValidationIssueType::MissingImage,
+                severity: Severity::Error,
+                line: reference.line,
+                column: None,
+                description: format!("Missing image: {}", reference.url),
+                suggested_fix: Some(format!(
+                    "Add the image to {} or run image-build",
+                    article_dir.display()
+                )),
+            });
+        }
+    }
+
+    // Images excluded by ignore files aren't published, so they are never reported
+    let discovery = DiscoveryOptions {
+        extensions: IMAGE_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+        ..Default::default()
+    };
+    for image in discover_files(article_dir, &discovery)? {
+        let relative = image.strip_prefix(article_dir).unwrap_or(&image);
+        if relative == Path::new(IMAGE_BUILD_SOURCE) || referenced.iter().any(|path| path == relative) {
+            continue;
+        }
 
-        // TODO: Validate resources
+        issues.push(ValidationIssue {
+            issue_type: ValidationIssueType::UnusedAsset,
+            severity: Severity::Info,
+            line: None,
+            column: None,
+            description: format!("Unused image: {}", relative.display()),
+            suggested_fix: Some("Reference the image in the article or delete it".to_string()),
+        });
     }
 
     Ok(())
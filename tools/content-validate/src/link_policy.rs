@@ -0,0 +1,147 @@
+//! # Link Policy
+//!
+//! Checks links to other sites against the `link_policy` section of the
+//! configuration. Each rule is off until it is configured:
+//!
+//! - `deny_domains`: links to these domains are errors
+//! - `allow_domains`: links to any other site are warnings
+//! - `require_https`: plain `http` links are warnings
+//! - `flag_tracking_params`: `utm_*` and click ID query parameters are warnings
+//!
+//! Links to the site's own host are never checked against the domain lists.
+//! The `nofollow_domains` rule is applied by content-build, which marks those
+//! links `rel="nofollow"` in the HTML it writes.
+
+use common_models::{Config, LinkPolicyConfig};
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
+use url::Url;
+
+use crate::{Severity, ValidationIssue, ValidationIssueType};
+
+/// Query parameters that only track where a visitor came from
+pub const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "igshid", "yclid"];
+
+/// Whether a query parameter only tracks where a visitor came from
+pub fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name.as_str())
+}
+
+/// Report links that break the configured link policy
+pub fn validate_link_policy(content: &str, config: &Config, issues: &mut Vec<ValidationIssue>) {
+    let policy = &config.link_policy;
+    if *policy == LinkPolicyConfig::default() {
+        return;
+    }
+
+    let site_host = config
+        .publication
+        .site_url
+        .as_deref()
+        .and_then(|site_url| Url::parse(site_url).ok())
+        .and_then(|site_url| site_url.host_str().map(str::to_lowercase));
+
+    for (url, line) in external_links(content) {
+        let Ok(parsed) = Url::parse(&url) else {
+            continue;
+        };
+        let Some(host) = parsed.host_str().map(str::to_lowercase) else {
+            continue;
+        };
+        let mut push = |issue_type: ValidationIssueType, description: String, suggested_fix: String| {
+            issues.push(ValidationIssue {
+                issue_type,
+                severity: issue_type.default_severity(),
+                line: Some(line),
+                column: None,
+                description,
+                suggested_fix: Some(suggested_fix),
+            });
+        };
+
+        if site_host.as_deref() != Some(host.as_str()) {
+            if LinkPolicyConfig::matches_domain(&host, &policy.deny_domains) {
+                push(
+                    ValidationIssueType::DeniedDomain,
+                    format!("Link to denied domain {}: {}", host, url),
+                    "Remove the link or point it somewhere else".to_string(),
+                );
+            } else if !policy.allow_domains.is_empty() && !LinkPolicyConfig::matches_domain(&host, &policy.allow_domains) {
+                push(
+                    ValidationIssueType::UnlistedDomain,
+                    format!("Link to a domain that isn't allowed, {}: {}", host, url),
+                    "Add the domain to link_policy.allow_domains or remove the link".to_string(),
+                );
+            }
+        }
+
+        if policy.require_https && parsed.scheme() == "http" {
+            push(
+                ValidationIssueType::InsecureLink,
+                format!("Link isn't https: {}", url),
+                format!("Use https://{}", url.trim_start_matches("http://")),
+            );
+        }
+
+        if policy.flag_tracking_params {
+            let tracking: Vec<String> = parsed
+                .query_pairs()
+                .map(|(name, _)| name.into_owned())
+                .filter(|name| is_tracking_param(name))
+                .collect();
+            if !tracking.is_empty() {
+                push(
+                    ValidationIssueType::TrackingParameter,
+                    format!("Link has tracking parameters ({}): {}", tracking.join(", "), url),
+                    format!("Link to {}", without_tracking_params(&parsed)),
+                );
+            }
+        }
+    }
+}
+
+/// A URL with its tracking query parameters removed
+pub fn without_tracking_params(url: &Url) -> String {
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !is_tracking_param(name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+
+    let mut clean = url.clone();
+    if kept.is_empty() {
+        clean.set_query(None);
+    } else {
+        clean.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    clean.to_string()
+}
+
+/// Absolute `http(s)` links in markdown links and `<a>` tags, with their line numbers
+fn external_links(content: &str) -> Vec<(String, usize)> {
+    let line_at = |offset: usize| content[..offset].matches('\n').count() + 1;
+    let anchor = Regex::new(r#"(?i)<a\b[^>]*?\bhref\s*=\s*["']([^"']+)["']"#).expect("valid regex");
+    let is_external = |url: &str| {
+        let url = url.to_ascii_lowercase();
+        url.starts_with("http://") || url.starts_with("https://")
+    };
+
+    let mut links = Vec::new();
+    for (event, range) in Parser::new_ext(content, Options::all()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(_, url, _)) if is_external(&url) => links.push((url.to_string(), line_at(range.start))),
+            Event::Html(_) => {
+                for captures in anchor.captures_iter(&content[range.clone()]) {
+                    let url = captures.get(1).expect("href group");
+                    if is_external(url.as_str()) {
+                        links.push((url.as_str().to_string(), line_at(range.start + url.start())));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    links
+}
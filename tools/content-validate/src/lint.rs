@@ -0,0 +1,624 @@
+//! # Lint Rules
+//!
+//! Each check run on a content file is a [`LintRule`] with a stable ID, so
+//! rules can be listed, turned off for a whole site in `lint.yaml`, or
+//! silenced in one article with a comment:
+//!
+//! ```markdown
+//! <!-- writing-disable duplicate-slug -->
+//! <!-- writing-disable-next-line links -->
+//! [Old docs](/docs/removed)
+//! <!-- writing-enable links, canonical -->
+//! ```
+//!
+//! A `writing-disable` comment with no IDs silences every rule. Issues
+//! without a line, such as a duplicate slug, are silenced when the rule is
+//! still disabled at the end of the file.
+//!
+//! ```yaml
+//! # lint.yaml in the content base directory
+//! disable:
+//!   - alt-text
+//! ```
+//!
+//! An article can turn rules off for itself in its frontmatter:
+//!
+//! ```yaml
+//! lint:
+//!   disable: [links]
+//! ```
+//!
+//! Rules an article turns off don't run on it at all. Issues silenced by
+//! comment are counted by rule so the report shows how much is being hidden.
+
+use anyhow::{bail, Context, Result};
+use common_markdown::extract_frontmatter_and_content;
+use common_models::Config;
+use common_traits::CancellationToken;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::prose::{BannedPhrasesRule, ClichesRule, ConjunctionStartsRule, RepeatedWordsRule, WeaselWordsRule};
+use crate::spelling::SpellingRule;
+use crate::{
+    check_links, extract_links, validate_alt_text, validate_assets, validate_canonical, validate_components,
+    validate_link_policy, validate_publish_at, validate_slug_uniqueness, LinkQuarantine, Severity, ValidationIssue,
+    ValidationIssueType, ValidationOptions, ValidationType,
+};
+
+/// Name of the lint settings file in the content base directory
+pub const LINT_FILE: &str = "lint.yaml";
+
+/// Frontmatter field holding an article's own lint settings
+pub const FRONTMATTER_FIELD: &str = "lint";
+
+/// What a rule needs to know about the file it checks
+pub struct LintContext<'a> {
+    /// Where the content lives, for resolving relative links and images
+    pub file_path: &'a Path,
+    /// Site configuration
+    pub config: &'a Config,
+    /// Options the validation was run with
+    pub options: &'a ValidationOptions,
+    /// Stops long-running checks, such as external links
+    pub token: &'a CancellationToken,
+    /// Failure counts for external links
+    pub quarantine: &'a mut LinkQuarantine,
+    /// Topics using each article slug, listed once per run with [`crate::slug_topics`]
+    pub slug_topics: &'a BTreeMap<String, Vec<String>>,
+}
+
+/// A check run on each content file
+pub trait LintRule: Send + Sync {
+    /// Stable kebab-case ID, used in `lint.yaml` and suppression comments
+    fn id(&self) -> &'static str;
+
+    /// What the rule checks, in a few words
+    fn description(&self) -> &'static str;
+
+    /// Severity of the most serious issue the rule reports
+    fn default_severity(&self) -> Severity;
+
+    /// The `--validation-types` group the rule runs in
+    fn validation_type(&self) -> ValidationType;
+
+    /// Check a content file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the check can't be run, not when it finds issues
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>>;
+}
+
+/// Checks the canonical URL in the frontmatter
+pub struct CanonicalRule;
+
+impl LintRule for CanonicalRule {
+    fn id(&self) -> &'static str {
+        "canonical"
+    }
+
+    fn description(&self) -> &'static str {
+        "Canonical URLs are well formed and point at an article"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Links
+    }
+
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        validate_canonical(content, context.config, context.slug_topics, &mut issues);
+        Ok(issues)
+    }
+}
+
+/// Checks links to other sites against the link policy
+pub struct LinkPolicyRule;
+
+impl LintRule for LinkPolicyRule {
+    fn id(&self) -> &'static str {
+        "link-policy"
+    }
+
+    fn description(&self) -> &'static str {
+        "External links follow the site's link policy"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Links
+    }
+
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        validate_link_policy(content, context.config, &mut issues);
+        Ok(issues)
+    }
+}
+
+/// Checks that links resolve
+pub struct LinksRule;
+
+impl LintRule for LinksRule {
+    fn id(&self) -> &'static str {
+        "links"
+    }
+
+    fn description(&self) -> &'static str {
+        "Internal links exist and external links respond"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Links
+    }
+
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        check_links(
+            context.file_path,
+            &extract_links(content),
+            context.options,
+            context.token,
+            context.quarantine,
+            &mut issues,
+        )?;
+        Ok(issues)
+    }
+}
+
+/// Checks the `published`, `updated` and `publish_at` frontmatter fields
+pub struct PublishDateRule;
+
+impl LintRule for PublishDateRule {
+    fn id(&self) -> &'static str {
+        "publish-date"
+    }
+
+    fn description(&self) -> &'static str {
+        "Publication dates are dates or DRAFT, and scheduled times RFC 3339 timestamps"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::PublishDate
+    }
+
+    fn check(&self, content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        validate_publish_at(content, &mut issues);
+        Ok(issues)
+    }
+}
+
+/// Checks MDX components against `shortcodes.yaml`
+pub struct ComponentsRule;
+
+impl LintRule for ComponentsRule {
+    fn id(&self) -> &'static str {
+        "components"
+    }
+
+    fn description(&self) -> &'static str {
+        "MDX components are registered shortcodes"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Markdown
+    }
+
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let shortcodes = common_config::load_shortcodes(&common_config::shortcodes_path(context.config))?;
+        let known: Vec<&str> = shortcodes.iter().map(|shortcode| shortcode.name.as_str()).collect();
+
+        let mut issues = Vec::new();
+        validate_components(content, &known, &mut issues);
+        Ok(issues)
+    }
+}
+
+/// Checks that no other topic uses the article's slug
+pub struct DuplicateSlugRule;
+
+impl LintRule for DuplicateSlugRule {
+    fn id(&self) -> &'static str {
+        "duplicate-slug"
+    }
+
+    fn description(&self) -> &'static str {
+        "Slugs are unique across topics"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::DuplicateSlugs
+    }
+
+    fn check(&self, _content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        validate_slug_uniqueness(context.file_path, context.slug_topics, &mut issues);
+        Ok(issues)
+    }
+}
+
+/// Checks referenced images exist and stored images are used
+pub struct AssetsRule;
+
+impl LintRule for AssetsRule {
+    fn id(&self) -> &'static str {
+        "assets"
+    }
+
+    fn description(&self) -> &'static str {
+        "Referenced images exist and stored images are referenced"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Assets
+    }
+
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        validate_assets(
+            context.file_path,
+            content,
+            context.config,
+            context.options.images_dir.as_deref(),
+            &mut issues,
+        )?;
+        Ok(issues)
+    }
+}
+
+/// Checks images have alt text
+pub struct AltTextRule;
+
+impl LintRule for AltTextRule {
+    fn id(&self) -> &'static str {
+        "alt-text"
+    }
+
+    fn description(&self) -> &'static str {
+        "Images in published content have alt text"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Assets
+    }
+
+    fn check(&self, content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        validate_alt_text(content, &mut issues);
+        Ok(issues)
+    }
+}
+
+/// The rules that can be run, in the order they run
+pub struct RuleRegistry {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl RuleRegistry {
+    /// Create a registry with no rules
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Create a registry with every built-in rule
+    pub fn builtin() -> Self {
+        let mut registry = Self::empty();
+        registry.register(CanonicalRule);
+        registry.register(LinkPolicyRule);
+        registry.register(LinksRule);
+        registry.register(PublishDateRule);
+        registry.register(ComponentsRule);
+        registry.register(DuplicateSlugRule);
+        registry.register(AssetsRule);
+        registry.register(AltTextRule);
+        registry.register(WeaselWordsRule);
+        registry.register(ClichesRule);
+        registry.register(RepeatedWordsRule);
+        registry.register(ConjunctionStartsRule);
+        registry.register(BannedPhrasesRule::default());
+        registry.register(SpellingRule);
+        registry
+    }
+
+    /// Add a rule, replacing any rule with the same ID
+    pub fn register(&mut self, rule: impl LintRule + 'static) {
+        match self.rules.iter().position(|existing| existing.id() == rule.id()) {
+            Some(index) => self.rules[index] = Box::new(rule),
+            None => self.rules.push(Box::new(rule)),
+        }
+    }
+
+    /// Get the rules, in the order they run
+    pub fn rules(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Find a rule by ID
+    pub fn get(&self, id: &str) -> Option<&dyn LintRule> {
+        self.rules().find(|rule| rule.id() == id)
+    }
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// Lint settings from `lint.yaml`, or from an article's `lint` frontmatter field
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// IDs of rules that never run
+    pub disable: BTreeSet<String>,
+}
+
+/// Get the path of the lint settings file for a configuration
+pub fn lint_config_path(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(LINT_FILE)
+}
+
+/// Load lint settings from a file, returning the defaults if it does not exist
+pub fn load_lint_config(path: &Path) -> Result<LintConfig> {
+    if !path.exists() {
+        return Ok(LintConfig::default());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read lint file: {}", path.display()))?;
+
+    if content.trim().is_empty() {
+        return Ok(LintConfig::default());
+    }
+
+    serde_yaml::from_str(&content).with_context(|| format!("Failed to parse lint file: {}", path.display()))
+}
+
+/// Runs the enabled rules of a registry on content files
+pub struct Linter {
+    registry: RuleRegistry,
+    settings: LintConfig,
+}
+
+impl Linter {
+    /// Create a linter, checking that every disabled rule exists
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings` disables a rule the registry doesn't have
+    pub fn new(registry: RuleRegistry, settings: LintConfig) -> Result<Self> {
+        if let Some(unknown) = settings.disable.iter().find(|id| registry.get(id).is_none()) {
+            let known: Vec<&str> = registry.rules().map(|rule| rule.id()).collect();
+            bail!("Unknown lint rule '{}' in {}; rules are {}", unknown, LINT_FILE, known.join(", "));
+        }
+
+        Ok(Self { registry, settings })
+    }
+
+    /// Create a linter with the built-in rules and the site's `lint.yaml`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lint.yaml` can't be read or names an unknown rule
+    pub fn load(config: &Config) -> Result<Self> {
+        Self::new(RuleRegistry::builtin(), load_lint_config(&lint_config_path(config))?)
+    }
+
+    /// Get the rules the linter runs from
+    pub fn registry(&self) -> &RuleRegistry {
+        &self.registry
+    }
+
+    /// Whether a rule is turned off in `lint.yaml`
+    pub fn is_disabled(&self, id: &str) -> bool {
+        self.settings.disable.contains(id)
+    }
+
+    /// Run the enabled rules in the requested validation types on a content file
+    ///
+    /// Rules turned off in the article's `lint` frontmatter are skipped, and
+    /// issues silenced by suppression comments are counted rather than
+    /// returned. Unknown rules named in the frontmatter are reported as
+    /// invalid frontmatter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a rule can't be run
+    pub fn lint(&self, content: &str, context: &mut LintContext<'_>) -> Result<LintOutcome> {
+        let suppressions = Suppressions::parse(content);
+        let types = &context.options.validation_types;
+        let mut outcome = LintOutcome::default();
+
+        let file_settings = match file_settings(content) {
+            Ok(settings) => settings,
+            Err(description) => {
+                outcome.issues.push(frontmatter_issue(content, description));
+                LintConfig::default()
+            }
+        };
+        for unknown in file_settings.disable.iter().filter(|id| self.registry.get(id).is_none()) {
+            outcome.issues.push(frontmatter_issue(content, format!("Unknown lint rule '{}' in frontmatter", unknown)));
+        }
+
+        for rule in self.registry.rules() {
+            let requested = types
+                .iter()
+                .any(|validation_type| matches!(validation_type, ValidationType::All) || *validation_type == rule.validation_type());
+            if !requested || self.is_disabled(rule.id()) || file_settings.disable.contains(rule.id()) {
+                continue;
+            }
+
+            let found = rule.check(content, context)?;
+            for issue in found {
+                if suppressions.suppresses(rule.id(), issue.line) {
+                    *outcome.suppressed.entry(rule.id().to_string()).or_default() += 1;
+                } else {
+                    outcome.issues.push(issue);
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// What linting one content file found
+#[derive(Debug, Default)]
+pub struct LintOutcome {
+    /// Issues to report
+    pub issues: Vec<ValidationIssue>,
+    /// Issues silenced by comments, by rule ID
+    pub suppressed: BTreeMap<String, usize>,
+}
+
+impl LintOutcome {
+    /// Number of issues silenced by any rule
+    pub fn suppressed_total(&self) -> usize {
+        self.suppressed.values().sum()
+    }
+}
+
+/// Read an article's `lint` frontmatter field, describing what's wrong if it can't be
+fn file_settings(content: &str) -> std::result::Result<LintConfig, String> {
+    let Ok((frontmatter, _)) = extract_frontmatter_and_content(content) else {
+        return Ok(LintConfig::default());
+    };
+
+    match frontmatter.extra.get(FRONTMATTER_FIELD) {
+        Some(value) => serde_yaml::from_value(value.clone())
+            .map_err(|e| format!("Invalid {} frontmatter: {}", FRONTMATTER_FIELD, e)),
+        None => Ok(LintConfig::default()),
+    }
+}
+
+/// An issue with the `lint` frontmatter field
+fn frontmatter_issue(content: &str, description: String) -> ValidationIssue {
+    let line = content
+        .lines()
+        .position(|line| line.starts_with(&format!("{}:", FRONTMATTER_FIELD)))
+        .map(|index| index + 1);
+
+    ValidationIssue {
+        issue_type: ValidationIssueType::InvalidFrontmatter,
+        severity: Severity::Error,
+        line,
+        column: None,
+        description,
+        suggested_fix: Some(format!("Use rule IDs listed by --list-rules under {}.disable", FRONTMATTER_FIELD)),
+    }
+}
+
+/// What a suppression comment does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    Disable,
+    Enable,
+    DisableNextLine,
+}
+
+/// A suppression comment
+#[derive(Debug, Clone)]
+struct Suppression {
+    directive: Directive,
+    /// Line of the comment (1-based)
+    line: usize,
+    /// Rules it applies to; every rule when empty
+    ids: Vec<String>,
+}
+
+impl Suppression {
+    fn applies_to(&self, id: &str) -> bool {
+        self.ids.is_empty() || self.ids.iter().any(|rule| rule == id)
+    }
+}
+
+/// The suppression comments in a content file
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions(Vec<Suppression>);
+
+impl Suppressions {
+    /// Find the suppression comments in content
+    pub fn parse(content: &str) -> Self {
+        let comment = Regex::new(r"<!--\s*writing-(disable-next-line|disable|enable)\b([^>]*?)\s*-->").expect("valid regex");
+
+        let mut suppressions = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            for captures in comment.captures_iter(line) {
+                let directive = match &captures[1] {
+                    "disable-next-line" => Directive::DisableNextLine,
+                    "disable" => Directive::Disable,
+                    _ => Directive::Enable,
+                };
+                let ids = captures[2]
+                    .split([',', ' '])
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect();
+
+                suppressions.push(Suppression { directive, line: index + 1, ids });
+            }
+        }
+
+        Self(suppressions)
+    }
+
+    /// Whether an issue a rule found on `line` is silenced
+    ///
+    /// An issue without a line is silenced if the rule is disabled at the end
+    /// of the file.
+    pub fn suppresses(&self, id: &str, line: Option<usize>) -> bool {
+        let target = line.unwrap_or(usize::MAX);
+        let mut disabled = false;
+
+        for suppression in self.0.iter().filter(|suppression| suppression.applies_to(id)) {
+            match suppression.directive {
+                Directive::DisableNextLine => {
+                    if line == Some(suppression.line + 1) {
+                        return true;
+                    }
+                }
+                Directive::Disable if suppression.line <= target => disabled = true,
+                Directive::Enable if suppression.line <= target => disabled = false,
+                _ => {}
+            }
+        }
+
+        disabled
+    }
+}
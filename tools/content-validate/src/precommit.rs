@@ -0,0 +1,260 @@
+//! # Pre-commit Validation
+//!
+//! Fast checks for a git pre-commit hook. Only the content files being
+//! committed are checked, as they are staged, and nothing touches the
+//! network, so the hook stays quick enough to run on every commit.
+
+use anyhow::Result;
+use common_fs::content_path::content_file_names;
+use common_markdown::extract_frontmatter_and_content;
+use common_models::Config;
+use pulldown_cmark::{Event, Parser, Tag};
+use std::path::{Component, Path, PathBuf};
+
+use crate::{
+    load_severity_overrides, severities_path, validate_components, validate_publish_at, Severity, ValidationIssue,
+    ValidationIssueType, ValidationResult,
+};
+
+/// A check the pre-commit hook can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreCommitRule {
+    /// Frontmatter parses, has a title and a valid `publish_at`
+    Frontmatter,
+    /// The article directory name is a valid slug
+    Slug,
+    /// Markdown structure and MDX components
+    Markdown,
+}
+
+impl PreCommitRule {
+    /// Every rule, in the order they run
+    pub const ALL: [PreCommitRule; 3] = [PreCommitRule::Frontmatter, PreCommitRule::Slug, PreCommitRule::Markdown];
+
+    /// Name of the rule on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            PreCommitRule::Frontmatter => "frontmatter",
+            PreCommitRule::Slug => "slug",
+            PreCommitRule::Markdown => "markdown",
+        }
+    }
+}
+
+/// Command the pre-commit hook runs for a set of rules
+pub fn hook_command(rules: &[PreCommitRule]) -> String {
+    let names: Vec<&str> = rules.iter().map(PreCommitRule::name).collect();
+    format!("exec content-validate --staged --rules {}", names.join(","))
+}
+
+/// Install a pre-commit hook that validates staged content with `rules`
+///
+/// Returns the path of the installed hook.
+///
+/// # Errors
+///
+/// Returns an error if `repo_path` isn't in a git repository, or another
+/// pre-commit hook exists and `force` isn't set
+pub fn install_pre_commit_hook(repo_path: &Path, rules: &[PreCommitRule], force: bool) -> Result<PathBuf> {
+    Ok(common_git::install_hook(repo_path, "pre-commit", &hook_command(rules), force)?)
+}
+
+/// Validate the staged version of every staged content file
+///
+/// Files that aren't article content files, such as images or files outside
+/// the topic directories, are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the content isn't in a git repository or a staged file
+/// can't be read
+pub fn validate_staged(config: &Config, rules: &[PreCommitRule]) -> Result<Vec<ValidationResult>> {
+    let base_dir = Path::new(&config.content.base_dir);
+    let base_dir = base_dir
+        .canonicalize()
+        .map_err(|_| common_errors::WritingError::directory_not_found(base_dir))?;
+
+    let shortcodes = common_config::load_shortcodes(&common_config::shortcodes_path(config))?;
+    let known: Vec<&str> = shortcodes.iter().map(|shortcode| shortcode.name.as_str()).collect();
+    let overrides = load_severity_overrides(&severities_path(config))?;
+
+    let mut results = Vec::new();
+    for file_path in common_git::staged_files(&base_dir)? {
+        if !is_content_file(config, &base_dir, &file_path) {
+            continue;
+        }
+
+        let content = common_git::read_staged(&file_path)?;
+        let mut issues = check_staged_file(&file_path, &content, rules, &known);
+        overrides.apply(&mut issues);
+        results.push(ValidationResult { file_path, issues, suppressed: Default::default() });
+    }
+
+    Ok(results)
+}
+
+/// Run the pre-commit rules on one content file
+///
+/// `known_components` are the MDX components registered in `shortcodes.yaml`.
+pub fn check_staged_file(
+    file_path: &Path,
+    content: &str,
+    rules: &[PreCommitRule],
+    known_components: &[&str],
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for rule in rules {
+        match rule {
+            PreCommitRule::Frontmatter => check_frontmatter(content, &mut issues),
+            PreCommitRule::Slug => check_slug(file_path, &mut issues),
+            PreCommitRule::Markdown => {
+                lint_markdown(content, &mut issues);
+                validate_components(content, known_components, &mut issues);
+            }
+        }
+    }
+
+    issues
+}
+
+/// Report markdown that renders badly: skipped heading levels and unclosed code fences
+pub fn lint_markdown(content: &str, issues: &mut Vec<ValidationIssue>) {
+    let body_start = frontmatter_end(content);
+    let body = &content[body_start..];
+    let line_offset = content[..body_start].matches('\n').count();
+
+    let mut previous_level = None;
+    for (event, range) in Parser::new(body).into_offset_iter() {
+        if let Event::Start(Tag::Heading(level, _, _)) = event {
+            let level = level as usize;
+            if let Some(previous) = previous_level {
+                if level > previous + 1 {
+                    issues.push(ValidationIssue {
+                        issue_type: ValidationIssueType::MarkdownFormatting,
+                        severity: Severity::Warning,
+                        line: Some(line_offset + body[..range.start].matches('\n').count() + 1),
+                        column: None,
+                        description: format!("Heading level {} follows level {}", level, previous),
+                        suggested_fix: Some(format!("Use a level {} heading", previous + 1)),
+                    });
+                }
+            }
+            previous_level = Some(level);
+        }
+    }
+
+    // An unclosed fence swallows the rest of the article into a code block
+    let mut open_fence = None;
+    for (index, line) in body.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let fence = if trimmed.starts_with("```") {
+            "```"
+        } else if trimmed.starts_with("~~~") {
+            "~~~"
+        } else {
+            continue;
+        };
+
+        match open_fence {
+            Some((open, _)) if open == fence => open_fence = None,
+            Some(_) => {}
+            None => open_fence = Some((fence, line_offset + index + 1)),
+        }
+    }
+
+    if let Some((_, line)) = open_fence {
+        issues.push(ValidationIssue {
+            issue_type: ValidationIssueType::MarkdownFormatting,
+            severity: Severity::Warning,
+            line: Some(line),
+            column: None,
+            description: "Code block is never closed".to_string(),
+            suggested_fix: Some("Close the code block with a matching fence".to_string()),
+        });
+    }
+}
+
+/// Check that the frontmatter parses and has a title
+fn check_frontmatter(content: &str, issues: &mut Vec<ValidationIssue>) {
+    match extract_frontmatter_and_content(content) {
+        Ok((frontmatter, _)) if frontmatter.title.trim().is_empty() => {
+            issues.push(ValidationIssue {
+                issue_type: ValidationIssueType::InvalidFrontmatter,
+                severity: Severity::Error,
+                line: None,
+                column: None,
+                description: "Missing title".to_string(),
+                suggested_fix: Some("Add a title to the frontmatter".to_string()),
+            });
+        }
+        Ok(_) => validate_publish_at(content, issues),
+        Err(err) => {
+            issues.push(ValidationIssue {
+                issue_type: ValidationIssueType::InvalidFrontmatter,
+                severity: Severity::Error,
+                line: None,
+                column: None,
+                description: format!("Invalid frontmatter: {}", err.message()),
+                suggested_fix: Some("Start the file with YAML frontmatter between --- lines".to_string()),
+            });
+        }
+    }
+}
+
+/// Check that the article directory name is a valid slug
+fn check_slug(file_path: &Path, issues: &mut Vec<ValidationIssue>) {
+    let slug = match file_path.parent().and_then(Path::file_name) {
+        Some(slug) => slug.to_string_lossy(),
+        None => return,
+    };
+
+    if let Err(err) = common_validation::validate_slug(&slug) {
+        issues.push(ValidationIssue {
+            issue_type: ValidationIssueType::InvalidSlug,
+            severity: Severity::Error,
+            line: None,
+            column: None,
+            description: format!("Invalid slug '{}': {}", slug, err.message()),
+            suggested_fix: Some(format!(
+                "Rename the directory to '{}'",
+                common_validation::slugify(&slug)
+            )),
+        });
+    }
+}
+
+/// Whether a file is the content file of an article in one of the topics
+fn is_content_file(config: &Config, base_dir: &Path, file_path: &Path) -> bool {
+    config.content.topics.values().any(|topic_config| {
+        let relative = match file_path.strip_prefix(base_dir.join(&topic_config.directory)) {
+            Ok(relative) => relative,
+            Err(_) => return false,
+        };
+
+        let components: Vec<Component> = relative.components().collect();
+        match components.as_slice() {
+            [Component::Normal(slug), Component::Normal(name)] => content_file_names(&slug.to_string_lossy())
+                .iter()
+                .any(|candidate| candidate.as_str() == name.to_string_lossy()),
+            _ => false,
+        }
+    })
+}
+
+/// Byte offset where the content after the frontmatter starts
+fn frontmatter_end(content: &str) -> usize {
+    if !content.starts_with("---") {
+        return 0;
+    }
+
+    let mut offset = 0;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        offset += line.len();
+        if index > 0 && line.trim_end() == "---" {
+            return offset;
+        }
+    }
+
+    0
+}
@@ -0,0 +1,553 @@
+//! # Prose Style
+//!
+//! Lint rules for house style, in the spirit of Vale and proselint. They
+//! check only the prose of an article: frontmatter, code, HTML and link
+//! targets are skipped.
+//!
+//! - `weasel-words`: vague intensifiers such as "very" and "basically"
+//! - `cliches`: stock phrases such as "at the end of the day"
+//! - `repeated-words`: the same word twice in a row, as in "the the"
+//! - `conjunction-starts`: too many sentences starting with "And" or "But"
+//! - `banned-phrases`: phrases listed in the `styles` directory
+//!
+//! Each YAML file in the `styles` directory of the content base directory is
+//! a list of phrases, optionally with a replacement:
+//!
+//! ```yaml
+//! # styles/house.yaml
+//! - going forward
+//! - phrase: utilize
+//!   suggestion: use
+//! ```
+
+use anyhow::{Context, Result};
+use common_markdown::extract_frontmatter_and_content;
+use common_models::Config;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
+use serde::Deserialize;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::lint::{LintContext, LintRule};
+use crate::{Severity, ValidationIssue, ValidationIssueType, ValidationType};
+
+/// Name of the directory of banned phrase lists in the content base directory
+pub const STYLES_DIR: &str = "styles";
+
+/// Words that hedge or intensify without adding meaning
+pub const WEASEL_WORDS: &[&str] = &[
+    "actually",
+    "arguably",
+    "basically",
+    "clearly",
+    "extremely",
+    "fairly",
+    "literally",
+    "obviously",
+    "quite",
+    "really",
+    "simply",
+    "somewhat",
+    "very",
+    "virtually",
+];
+
+/// Phrases worn out by overuse
+pub const CLICHES: &[&str] = &[
+    "at the end of the day",
+    "avoid like the plague",
+    "best of breed",
+    "few and far between",
+    "game changer",
+    "in this day and age",
+    "last but not least",
+    "low-hanging fruit",
+    "move the needle",
+    "needless to say",
+    "paradigm shift",
+    "the elephant in the room",
+    "think outside the box",
+    "tip of the iceberg",
+    "when all is said and done",
+];
+
+/// Conjunctions counted at the start of a sentence
+pub const CONJUNCTIONS: &[&str] = &["and", "but", "nor", "or", "so", "yet"];
+
+/// Share of sentences that may start with a conjunction
+pub const MAX_CONJUNCTION_SHARE: f64 = 0.1;
+
+/// Fewest conjunction starts worth reporting, however short the article
+pub const MIN_CONJUNCTION_STARTS: usize = 3;
+
+/// A phrase the house style doesn't allow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BannedPhrase {
+    /// Name of the style file the phrase is listed in
+    pub style: String,
+    pub phrase: String,
+    /// What to write instead
+    pub suggestion: Option<String>,
+}
+
+/// An entry in a style file, either a bare phrase or one with a replacement
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StyleEntry {
+    Phrase(String),
+    Detailed {
+        phrase: String,
+        #[serde(default)]
+        suggestion: Option<String>,
+    },
+}
+
+/// Get the directory of banned phrase lists for a configuration
+pub fn styles_dir(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(STYLES_DIR)
+}
+
+/// Load the banned phrases from every style file in a directory
+///
+/// Returns no phrases if the directory does not exist. Files are read in
+/// name order.
+pub fn load_banned_phrases(dir: &Path) -> Result<Vec<BannedPhrase>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read styles directory: {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("yaml" | "yml")))
+        .collect();
+    files.sort();
+
+    let mut phrases = Vec::new();
+    for path in files {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read style file: {}", path.display()))?;
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        let entries: Vec<StyleEntry> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse style file: {}", path.display()))?;
+        let style = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+
+        for entry in entries {
+            let (phrase, suggestion) = match entry {
+                StyleEntry::Phrase(phrase) => (phrase, None),
+                StyleEntry::Detailed { phrase, suggestion } => (phrase, suggestion),
+            };
+            if !phrase.trim().is_empty() {
+                phrases.push(BannedPhrase {
+                    style: style.clone(),
+                    phrase: phrase.trim().to_string(),
+                    suggestion,
+                });
+            }
+        }
+    }
+
+    Ok(phrases)
+}
+
+/// A run of prose text in a content file
+struct Prose {
+    /// Where the text is in the file
+    range: Range<usize>,
+    text: String,
+    /// Whether the text opens a paragraph, heading, list item or table cell
+    block_start: bool,
+}
+
+/// The prose of a content file, a run at a time
+fn prose(content: &str) -> Vec<Prose> {
+    let body_start = match extract_frontmatter_and_content(content) {
+        Ok((_, body)) => content.len() - body.len(),
+        Err(_) => 0,
+    };
+
+    let mut runs = Vec::new();
+    let mut in_code_block = false;
+    let mut block_start = false;
+
+    for (event, range) in Parser::new_ext(&content[body_start..], Options::all()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            Event::Start(Tag::Paragraph | Tag::Heading(..) | Tag::Item | Tag::TableCell) => block_start = true,
+            Event::Text(text) if !in_code_block => {
+                runs.push(Prose {
+                    range: body_start + range.start..body_start + range.end,
+                    text: text.to_string(),
+                    block_start,
+                });
+                block_start = false;
+            }
+            _ => {}
+        }
+    }
+
+    runs
+}
+
+/// The file with everything but prose blanked out, keeping offsets and line breaks
+///
+/// Blanked text becomes NUL rather than spaces, so words on either side of
+/// code or markup are never read as neighbours.
+fn masked(content: &str, runs: &[Prose]) -> String {
+    let mut bytes: Vec<u8> = content.bytes().map(|byte| if byte == b'\n' { b'\n' } else { 0 }).collect();
+    for run in runs {
+        bytes[run.range.clone()].copy_from_slice(&content.as_bytes()[run.range.clone()]);
+    }
+
+    String::from_utf8(bytes).expect("prose runs end on character boundaries")
+}
+
+/// The prose of a content file, with everything else blanked out
+pub(crate) fn prose_text(content: &str) -> String {
+    masked(content, &prose(content))
+}
+
+/// The 1-based line of a byte offset
+pub(crate) fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+/// A case-insensitive regex matching any of `phrases` as whole words
+///
+/// Spaces in a phrase match any run of whitespace, including a line break.
+fn phrase_regex(phrases: &[&str]) -> Regex {
+    let alternatives: Vec<String> = phrases
+        .iter()
+        .map(|phrase| {
+            let words: Vec<String> = phrase.split_whitespace().map(regex::escape).collect();
+            let start = if phrase.starts_with(|c: char| c.is_alphanumeric()) { r"\b" } else { "" };
+            let end = if phrase.ends_with(|c: char| c.is_alphanumeric()) { r"\b" } else { "" };
+            format!("{}{}{}", start, words.join(r"\s+"), end)
+        })
+        .collect();
+
+    Regex::new(&format!("(?i)(?:{})", alternatives.join("|"))).expect("valid regex")
+}
+
+/// Report every match of `regex` in the prose of a file
+fn phrase_issues(
+    content: &str,
+    regex: &Regex,
+    issue_type: ValidationIssueType,
+    describe: impl Fn(&str) -> (String, String),
+) -> Vec<ValidationIssue> {
+    let text = prose_text(content);
+
+    regex
+        .find_iter(&text)
+        .map(|found| {
+            let phrase = found.as_str().split_whitespace().collect::<Vec<_>>().join(" ");
+            let (description, suggested_fix) = describe(&phrase);
+            ValidationIssue {
+                issue_type,
+                severity: issue_type.default_severity(),
+                line: Some(line_at(content, found.start())),
+                column: None,
+                description,
+                suggested_fix: Some(suggested_fix),
+            }
+        })
+        .collect()
+}
+
+/// Find vague intensifiers and hedges
+pub fn find_weasel_words(content: &str) -> Vec<ValidationIssue> {
+    phrase_issues(content, &phrase_regex(WEASEL_WORDS), ValidationIssueType::WeaselWord, |word| {
+        (
+            format!("Weasel word: '{}'", word),
+            format!("Cut '{}' or say something more specific", word),
+        )
+    })
+}
+
+/// Find clichés
+pub fn find_cliches(content: &str) -> Vec<ValidationIssue> {
+    phrase_issues(content, &phrase_regex(CLICHES), ValidationIssueType::Cliche, |phrase| {
+        (format!("Cliché: '{}'", phrase), "Say it plainly in your own words".to_string())
+    })
+}
+
+/// Find words written twice in a row, such as "the the"
+pub fn find_repeated_words(content: &str) -> Vec<ValidationIssue> {
+    let text = prose_text(content);
+    let word = Regex::new(r"[\p{L}\p{N}']+").expect("valid regex");
+
+    let mut issues = Vec::new();
+    let mut previous: Option<regex::Match> = None;
+    for current in word.find_iter(&text) {
+        if let Some(previous) = previous {
+            let between = &text[previous.end()..current.start()];
+            let repeated = previous.as_str().to_lowercase() == current.as_str().to_lowercase();
+            let numeric = current.as_str().chars().all(|c| c.is_numeric());
+
+            if repeated && !numeric && between.chars().all(char::is_whitespace) {
+                issues.push(ValidationIssue {
+                    issue_type: ValidationIssueType::RepeatedWord,
+                    severity: ValidationIssueType::RepeatedWord.default_severity(),
+                    line: Some(line_at(content, current.start())),
+                    column: None,
+                    description: format!("Repeated word: '{} {}'", previous.as_str(), current.as_str()),
+                    suggested_fix: Some(format!("Remove one '{}'", current.as_str())),
+                });
+            }
+        }
+        previous = Some(current);
+    }
+
+    issues
+}
+
+/// The first word of each sentence and the line it is on
+fn sentence_starts(content: &str) -> Vec<(String, usize)> {
+    let boundary = Regex::new(r#"[.!?]+["')\]]*(\s+|$)"#).expect("valid regex");
+    let first_word = Regex::new(r"^\s*([\p{L}']+)").expect("valid regex");
+
+    let mut starts = Vec::new();
+    let mut pending = false;
+    for run in prose(content) {
+        let line = line_at(content, run.range.start);
+        let mut offsets = Vec::new();
+        if run.block_start || pending {
+            offsets.push(0);
+        }
+        pending = false;
+
+        for found in boundary.find_iter(&run.text) {
+            if found.end() == run.text.len() {
+                pending = true;
+            } else {
+                offsets.push(found.end());
+            }
+        }
+
+        for offset in offsets {
+            if let Some(captures) = first_word.captures(&run.text[offset..]) {
+                starts.push((captures[1].to_string(), line));
+            } else if offset == 0 && run.text.trim().is_empty() {
+                pending = true;
+            }
+        }
+    }
+
+    starts
+}
+
+/// Report an article where too many sentences start with a conjunction
+pub fn find_conjunction_starts(content: &str) -> Option<ValidationIssue> {
+    let starts = sentence_starts(content);
+    let lines: Vec<usize> = starts
+        .iter()
+        .filter(|(word, _)| CONJUNCTIONS.contains(&word.to_lowercase().as_str()))
+        .map(|(_, line)| *line)
+        .collect();
+
+    let share = lines.len() as f64 / starts.len().max(1) as f64;
+    if lines.len() < MIN_CONJUNCTION_STARTS || share <= MAX_CONJUNCTION_SHARE {
+        return None;
+    }
+
+    let mut listed: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    listed.dedup();
+    Some(ValidationIssue {
+        issue_type: ValidationIssueType::ConjunctionStart,
+        severity: ValidationIssueType::ConjunctionStart.default_severity(),
+        line: lines.first().copied(),
+        column: None,
+        description: format!(
+            "{} of {} sentences ({:.0}%) start with a conjunction, on lines {}",
+            lines.len(),
+            starts.len(),
+            share * 100.0,
+            listed.join(", ")
+        ),
+        suggested_fix: Some("Join some of these sentences to the one before, or start them differently".to_string()),
+    })
+}
+
+/// A banned phrase with the regex that finds it
+#[derive(Debug)]
+struct CompiledPhrase {
+    banned: BannedPhrase,
+    regex: Regex,
+}
+
+fn compile_phrases(phrases: &[BannedPhrase]) -> Vec<CompiledPhrase> {
+    phrases
+        .iter()
+        .map(|banned| CompiledPhrase {
+            banned: banned.clone(),
+            regex: phrase_regex(&[banned.phrase.as_str()]),
+        })
+        .collect()
+}
+
+/// Find phrases the house style doesn't allow
+pub fn find_banned_phrases(content: &str, phrases: &[BannedPhrase]) -> Vec<ValidationIssue> {
+    banned_phrase_issues(content, &compile_phrases(phrases))
+}
+
+fn banned_phrase_issues(content: &str, phrases: &[CompiledPhrase]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    for CompiledPhrase { banned, regex } in phrases {
+        issues.extend(phrase_issues(content, regex, ValidationIssueType::BannedPhrase, |found| {
+            let suggested_fix = match &banned.suggestion {
+                Some(suggestion) => format!("Use '{}' instead", suggestion),
+                None => format!("Rephrase without '{}'", found),
+            };
+            (format!("'{}' is banned by the {} style", found, banned.style), suggested_fix)
+        }));
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// Checks for vague intensifiers and hedges
+pub struct WeaselWordsRule;
+
+impl LintRule for WeaselWordsRule {
+    fn id(&self) -> &'static str {
+        "weasel-words"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prose avoids vague intensifiers such as \"very\""
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Markdown
+    }
+
+    fn check(&self, content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        Ok(find_weasel_words(content))
+    }
+}
+
+/// Checks for clichés
+pub struct ClichesRule;
+
+impl LintRule for ClichesRule {
+    fn id(&self) -> &'static str {
+        "cliches"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prose avoids clichés"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Markdown
+    }
+
+    fn check(&self, content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        Ok(find_cliches(content))
+    }
+}
+
+/// Checks for words written twice in a row
+pub struct RepeatedWordsRule;
+
+impl LintRule for RepeatedWordsRule {
+    fn id(&self) -> &'static str {
+        "repeated-words"
+    }
+
+    fn description(&self) -> &'static str {
+        "No word is written twice in a row"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Markdown
+    }
+
+    fn check(&self, content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        Ok(find_repeated_words(content))
+    }
+}
+
+/// Checks how many sentences start with a conjunction
+pub struct ConjunctionStartsRule;
+
+impl LintRule for ConjunctionStartsRule {
+    fn id(&self) -> &'static str {
+        "conjunction-starts"
+    }
+
+    fn description(&self) -> &'static str {
+        "Few sentences start with \"And\" or \"But\""
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Markdown
+    }
+
+    fn check(&self, content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        Ok(find_conjunction_starts(content).into_iter().collect())
+    }
+}
+
+/// Checks for phrases listed in the `styles` directory
+///
+/// The style files are read, and their phrases compiled, the first time the
+/// rule runs. A registry is built for each validation run, so edits to the
+/// styles are picked up by the next run.
+#[derive(Debug, Default)]
+pub struct BannedPhrasesRule {
+    phrases: OnceLock<Vec<CompiledPhrase>>,
+}
+
+impl LintRule for BannedPhrasesRule {
+    fn id(&self) -> &'static str {
+        "banned-phrases"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prose avoids phrases banned by the house style"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Markdown
+    }
+
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let phrases = match self.phrases.get() {
+            Some(phrases) => phrases,
+            None => {
+                let phrases = compile_phrases(&load_banned_phrases(&styles_dir(context.config))?);
+                self.phrases.get_or_init(|| phrases)
+            }
+        };
+        Ok(banned_phrase_issues(content, phrases))
+    }
+}
@@ -0,0 +1,210 @@
+//! # Validation Report
+//!
+//! Everything a validation run found, printed as text for people or as JSON
+//! or YAML for scripts and CI with `--format`.
+
+use colored::*;
+use common_cli::DisplayResult;
+use common_models::Config;
+use serde::Serialize;
+
+use crate::{Severity, ValidationIssueType, ValidationResult, ValidationSummary};
+
+/// The kind of check that finds an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueCategory {
+    Links,
+    Markdown,
+    Assets,
+    Accessibility,
+    Seo,
+    Style,
+}
+
+impl IssueCategory {
+    /// The check that finds issues of `issue_type`
+    pub fn of(issue_type: ValidationIssueType) -> Self {
+        match issue_type {
+            ValidationIssueType::BrokenLink
+            | ValidationIssueType::MissingInternalLink
+            | ValidationIssueType::InvalidUrl
+            | ValidationIssueType::InvalidCanonical
+            | ValidationIssueType::DeniedDomain
+            | ValidationIssueType::UnlistedDomain
+            | ValidationIssueType::InsecureLink
+            | ValidationIssueType::TrackingParameter
+            | ValidationIssueType::ArchivedLink => IssueCategory::Links,
+            ValidationIssueType::MarkdownFormatting
+            | ValidationIssueType::InvalidPublishDate
+            | ValidationIssueType::InvalidFrontmatter
+            | ValidationIssueType::InvalidSlug
+            | ValidationIssueType::UnknownComponent
+            | ValidationIssueType::DuplicateSlug => IssueCategory::Markdown,
+            ValidationIssueType::MissingImage
+            | ValidationIssueType::UnusedAsset
+            | ValidationIssueType::MissingAltText => IssueCategory::Assets,
+            ValidationIssueType::HeadingOrder
+            | ValidationIssueType::MissingTableHeader
+            | ValidationIssueType::NonDescriptiveLink
+            | ValidationIssueType::LowContrast => IssueCategory::Accessibility,
+            ValidationIssueType::TitleLength
+            | ValidationIssueType::MetaDescription
+            | ValidationIssueType::MultipleH1
+            | ValidationIssueType::MissingKeyword
+            | ValidationIssueType::LowAltCoverage
+            | ValidationIssueType::FewInternalLinks => IssueCategory::Seo,
+            ValidationIssueType::WeaselWord
+            | ValidationIssueType::Cliche
+            | ValidationIssueType::RepeatedWord
+            | ValidationIssueType::ConjunctionStart
+            | ValidationIssueType::BannedPhrase
+            | ValidationIssueType::Misspelling => IssueCategory::Style,
+        }
+    }
+}
+
+/// Number of issues found by each kind of check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CategoryCounts {
+    pub links: usize,
+    pub markdown: usize,
+    pub assets: usize,
+    pub accessibility: usize,
+    pub seo: usize,
+    pub style: usize,
+}
+
+impl CategoryCounts {
+    /// Count one issue under the check that finds it
+    pub fn add(&mut self, issue_type: ValidationIssueType) {
+        match IssueCategory::of(issue_type) {
+            IssueCategory::Links => self.links += 1,
+            IssueCategory::Markdown => self.markdown += 1,
+            IssueCategory::Assets => self.assets += 1,
+            IssueCategory::Accessibility => self.accessibility += 1,
+            IssueCategory::Seo => self.seo += 1,
+            IssueCategory::Style => self.style += 1,
+        }
+    }
+}
+
+/// The outcome of a validation run
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    /// Files with issues, in the order they were validated
+    pub results: Vec<ValidationResult>,
+    pub categories: CategoryCounts,
+    pub summary: ValidationSummary,
+    /// Show suggested fixes in plain output
+    #[serde(skip)]
+    pub verbose: bool,
+}
+
+impl ValidationReport {
+    /// Report on validation results, keeping only the files with issues
+    pub fn new(results: Vec<ValidationResult>, config: &Config) -> Self {
+        let summary = ValidationSummary::from_results(&results, config);
+        let results: Vec<ValidationResult> = results.into_iter().filter(|result| !result.issues.is_empty()).collect();
+
+        let mut categories = CategoryCounts::default();
+        for issue in results.iter().flat_map(|result| &result.issues) {
+            categories.add(issue.issue_type);
+        }
+
+        Self { results, categories, summary, verbose: false }
+    }
+
+    /// Show suggested fixes in plain output
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+}
+
+impl DisplayResult for ValidationReport {
+    fn to_display(&self) -> String {
+        if self.summary.files_checked == 0 {
+            return format!("{} No content found to validate.", "Notice:".yellow().bold());
+        }
+
+        let mut lines = Vec::new();
+        for result in &self.results {
+            lines.push(format!("\n{} {}", "File:".blue().bold(), result.file_path.display()));
+
+            for issue in &result.issues {
+                let label = match IssueCategory::of(issue.issue_type) {
+                    IssueCategory::Links => "LINK".red().bold(),
+                    IssueCategory::Markdown => "FORMAT".yellow().bold(),
+                    IssueCategory::Assets => "ASSET".magenta().bold(),
+                    IssueCategory::Accessibility => "A11Y".cyan().bold(),
+                    IssueCategory::Seo => "SEO".blue().bold(),
+                    IssueCategory::Style => "STYLE".green().bold(),
+                };
+
+                match issue.line {
+                    Some(line) => lines.push(format!("  {} (line {}): {}", label, line, issue.description)),
+                    None => lines.push(format!("  {}: {}", label, issue.description)),
+                }
+                if self.verbose {
+                    if let Some(suggestion) = &issue.suggested_fix {
+                        lines.push(format!("    Suggestion: {}", suggestion));
+                    }
+                }
+            }
+        }
+
+        let totals = &self.summary.totals;
+        lines.push(format!("\n{}", "=== Validation Summary ===".green().bold()));
+        lines.push(format!("Files checked: {}", self.summary.files_checked));
+        lines.push(format!("Files with issues: {}", self.results.len()));
+        lines.push(format!("Total issues: {}", totals.total()));
+        lines.push(format!("Link issues: {}", self.categories.links));
+        lines.push(format!("Markdown formatting issues: {}", self.categories.markdown));
+        lines.push(format!("Asset issues: {}", self.categories.assets));
+        lines.push(format!("Accessibility issues: {}", self.categories.accessibility));
+        lines.push(format!("SEO issues: {}", self.categories.seo));
+        lines.push(format!("Style issues: {}", self.categories.style));
+        lines.push(format!("Errors: {}", totals.errors));
+        lines.push(format!("Warnings: {}", totals.warnings));
+        lines.push(format!("Info: {}", totals.info));
+
+        // Suppressed issues are listed so that silencing a rule never goes unnoticed
+        if !self.summary.suppressed.is_empty() {
+            let by_rule: Vec<String> = self
+                .summary
+                .suppressed
+                .iter()
+                .map(|(rule, count)| format!("{}: {}", rule, count))
+                .collect();
+            lines.push(format!("Suppressed issues: {} ({})", self.summary.suppressed_total(), by_rule.join(", ")));
+        }
+
+        if self.summary.topics.len() > 1 {
+            lines.push(format!("\n{}", "Issues by topic:".cyan().bold()));
+            for (topic, counts) in &self.summary.topics {
+                lines.push(format!(
+                    "  {}: {} errors, {} warnings, {} info",
+                    topic, counts.errors, counts.warnings, counts.info
+                ));
+            }
+        }
+
+        if totals.total() > 0 {
+            lines.push(format!("\n{} {} validation issues found", "Warning:".yellow().bold(), totals.total()));
+        } else {
+            lines.push(format!("\n{} No validation issues found!", "Success:".green().bold()));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// A severity as a colored label for plain output
+pub fn severity_label(severity: Severity) -> ColoredString {
+    match severity {
+        Severity::Error => "ERROR".red().bold(),
+        Severity::Warning => "WARNING".yellow().bold(),
+        Severity::Info => "INFO".blue().bold(),
+    }
+}
@@ -0,0 +1,361 @@
+//! # SEO Audit
+//!
+//! Scores each published article on the basics search engines look at and
+//! lists what to fix, as validation issues so `severities.yaml` overrides
+//! apply. An article starts at 100 and loses the weight of each check it
+//! fails:
+//!
+//! | Check | Weight |
+//! |-------|--------|
+//! | Title between 30 and 60 characters | 20 |
+//! | Description between 70 and 160 characters | 20 |
+//! | At most one H1 in the body | 15 |
+//! | Focus keyword in the title and first paragraph | 15 |
+//! | Alt text on every image | 15 |
+//! | At least one internal link | 15 |
+//!
+//! The focus keyword is the `keyword` frontmatter field, or the first tag;
+//! articles with neither skip the keyword check. Images lose points in
+//! proportion to how many lack alt text.
+
+use anyhow::Result;
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::FsContentRepository;
+use common_markdown::extract_frontmatter_and_content;
+use common_models::Config;
+use common_traits::ContentRepository;
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag};
+use regex::Regex;
+use serde::Serialize;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use crate::{
+    find_missing_alt_text, is_draft, severity_label, Severity, SeverityOverrides, ValidationIssue, ValidationIssueType,
+};
+
+/// Title lengths, in characters, that show in full in search results
+pub const TITLE_LENGTH: RangeInclusive<usize> = 30..=60;
+
+/// Description lengths, in characters, that search results show without cutting off
+pub const DESCRIPTION_LENGTH: RangeInclusive<usize> = 70..=160;
+
+/// Frontmatter field naming the phrase an article should rank for
+pub const KEYWORD_FIELD: &str = "keyword";
+
+const TITLE_WEIGHT: f64 = 20.0;
+const DESCRIPTION_WEIGHT: f64 = 20.0;
+const H1_WEIGHT: f64 = 15.0;
+const KEYWORD_WEIGHT: f64 = 15.0;
+const ALT_TEXT_WEIGHT: f64 = 15.0;
+const INTERNAL_LINKS_WEIGHT: f64 = 15.0;
+
+/// The SEO score and issues of one article
+#[derive(Debug, Serialize)]
+pub struct SeoAudit {
+    /// From 0 to 100
+    pub score: u32,
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Score an article and list its SEO issues
+pub fn audit_article(content: &str) -> SeoAudit {
+    let (frontmatter, body) = extract_frontmatter_and_content(content).unwrap_or_default();
+    let body_offset = content.len().saturating_sub(body.len());
+    let line_at = |offset: usize| content[..(body_offset + offset).min(content.len())].matches('\n').count() + 1;
+    let field_line = |field: &str| {
+        content
+            .lines()
+            .position(|line| line.trim_start().starts_with(&format!("{}:", field)))
+            .map(|index| index + 1)
+    };
+
+    let mut issues = Vec::new();
+    let mut penalty = 0.0;
+
+    let title = frontmatter.title.trim();
+    let title_length = title.chars().count();
+    if !TITLE_LENGTH.contains(&title_length) {
+        penalty += TITLE_WEIGHT;
+        issues.push(issue(
+            ValidationIssueType::TitleLength,
+            field_line("title"),
+            format!("Title is {} characters long", title_length),
+            format!(
+                "Keep the title between {} and {} characters so search results show it in full",
+                TITLE_LENGTH.start(),
+                TITLE_LENGTH.end()
+            ),
+        ));
+    }
+
+    match frontmatter.description.as_deref().map(str::trim).filter(|description| !description.is_empty()) {
+        None => {
+            penalty += DESCRIPTION_WEIGHT;
+            issues.push(issue(
+                ValidationIssueType::MetaDescription,
+                None,
+                "No meta description".to_string(),
+                "Add a `description` to the frontmatter summarizing the article".to_string(),
+            ));
+        }
+        Some(description) => {
+            let length = description.chars().count();
+            if !DESCRIPTION_LENGTH.contains(&length) {
+                penalty += DESCRIPTION_WEIGHT;
+                issues.push(issue(
+                    ValidationIssueType::MetaDescription,
+                    field_line("description"),
+                    format!("Meta description is {} characters long", length),
+                    format!(
+                        "Keep the description between {} and {} characters",
+                        DESCRIPTION_LENGTH.start(),
+                        DESCRIPTION_LENGTH.end()
+                    ),
+                ));
+            }
+        }
+    }
+
+    let outline = outline(&body);
+
+    if let Some(&second) = outline.h1_offsets.get(1) {
+        penalty += H1_WEIGHT;
+        issues.push(issue(
+            ValidationIssueType::MultipleH1,
+            Some(line_at(second)),
+            format!("{} H1 headings in the body", outline.h1_offsets.len()),
+            "Use a single H1 and `##` headings for sections".to_string(),
+        ));
+    }
+
+    let keyword = frontmatter
+        .get_string(KEYWORD_FIELD)
+        .map(str::to_string)
+        .or_else(|| frontmatter.tags.as_ref().and_then(|tags| tags.first().cloned()));
+    if let Some(keyword) = keyword.map(|keyword| keyword.trim().to_lowercase()).filter(|keyword| !keyword.is_empty()) {
+        let mut missing_from = Vec::new();
+        if !title.to_lowercase().contains(&keyword) {
+            missing_from.push("title");
+        }
+        if !outline.first_paragraph.to_lowercase().contains(&keyword) {
+            missing_from.push("first paragraph");
+        }
+        if !missing_from.is_empty() {
+            penalty += KEYWORD_WEIGHT * missing_from.len() as f64 / 2.0;
+            issues.push(issue(
+                ValidationIssueType::MissingKeyword,
+                None,
+                format!("Keyword \"{}\" not in the {}", keyword, missing_from.join(" or ")),
+                format!("Mention \"{}\" in the {}", keyword, missing_from.join(" and ")),
+            ));
+        }
+    }
+
+    let missing_alt = find_missing_alt_text(&body);
+    if outline.images > 0 && !missing_alt.is_empty() {
+        penalty += ALT_TEXT_WEIGHT * missing_alt.len() as f64 / outline.images as f64;
+        issues.push(issue(
+            ValidationIssueType::LowAltCoverage,
+            Some(line_at(0) + missing_alt[0].line - 1),
+            format!("{} of {} images have no alt text", missing_alt.len(), outline.images),
+            "Describe each image; `content-validate --alt-text-report` lists them".to_string(),
+        ));
+    }
+
+    if outline.internal_links == 0 {
+        penalty += INTERNAL_LINKS_WEIGHT;
+        issues.push(issue(
+            ValidationIssueType::FewInternalLinks,
+            None,
+            "No links to other pages on the site".to_string(),
+            "Link to related articles".to_string(),
+        ));
+    }
+
+    SeoAudit {
+        score: (100.0 - penalty).round().max(0.0) as u32,
+        issues,
+    }
+}
+
+/// What the checks need to know about an article body
+#[derive(Default)]
+struct Outline {
+    /// Byte offsets of the H1 headings
+    h1_offsets: Vec<usize>,
+    first_paragraph: String,
+    images: usize,
+    internal_links: usize,
+}
+
+fn outline(body: &str) -> Outline {
+    let img_tag = Regex::new(r"(?i)<img\b").expect("valid regex");
+    let mut outline = Outline::default();
+    let mut in_first_paragraph = false;
+    let mut seen_paragraph = false;
+
+    for (event, range) in Parser::new_ext(body, Options::all()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading(HeadingLevel::H1, ..)) => outline.h1_offsets.push(range.start),
+            Event::Start(Tag::Paragraph) if !seen_paragraph => {
+                in_first_paragraph = true;
+                seen_paragraph = true;
+            }
+            Event::End(Tag::Paragraph) => in_first_paragraph = false,
+            Event::Text(text) | Event::Code(text) if in_first_paragraph => outline.first_paragraph.push_str(&text),
+            Event::SoftBreak | Event::HardBreak if in_first_paragraph => outline.first_paragraph.push(' '),
+            Event::Start(Tag::Image(..)) => outline.images += 1,
+            Event::Html(html) => outline.images += img_tag.find_iter(&html).count(),
+            Event::Start(Tag::Link(_, url, _)) if is_internal_link(&url) => outline.internal_links += 1,
+            _ => {}
+        }
+    }
+
+    outline
+}
+
+/// Whether a link goes to another page on the site, rather than elsewhere or within the page
+fn is_internal_link(url: &str) -> bool {
+    !(url.is_empty() || url.starts_with('#') || url.contains("://") || url.starts_with("//") || url.starts_with("mailto:"))
+}
+
+fn issue(issue_type: ValidationIssueType, line: Option<usize>, description: String, suggested_fix: String) -> ValidationIssue {
+    ValidationIssue {
+        issue_type,
+        severity: issue_type.default_severity(),
+        line,
+        column: None,
+        description,
+        suggested_fix: Some(suggested_fix),
+    }
+}
+
+/// The SEO audit of one article, and where to find it
+#[derive(Debug, Serialize)]
+pub struct SeoArticle {
+    pub topic: String,
+    pub article: String,
+    pub file_path: PathBuf,
+    pub score: u32,
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// SEO scores and issues for published articles, lowest score first
+#[derive(Debug, Default, Serialize)]
+pub struct SeoReport {
+    pub articles: Vec<SeoArticle>,
+    /// Mean score of the articles, or 100 if there are none
+    pub average_score: u32,
+    /// Show suggested fixes in plain output
+    #[serde(skip)]
+    pub verbose: bool,
+}
+
+impl SeoReport {
+    /// Show suggested fixes in plain output
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Issues at `severity` or more serious, across all articles
+    pub fn issues_at_least(&self, severity: Severity) -> usize {
+        self.articles
+            .iter()
+            .flat_map(|article| &article.issues)
+            .filter(|issue| issue.severity <= severity)
+            .count()
+    }
+}
+
+/// Audit the published articles of one topic or all of them
+///
+/// # Errors
+///
+/// Returns an error if the topic doesn't exist or an article can't be read
+pub fn seo_report(config: &Config, topic: Option<&str>, overrides: &SeverityOverrides) -> Result<SeoReport> {
+    seo_report_in(&FsContentRepository::new(config), topic, overrides)
+}
+
+/// Audit the published articles of `repository`
+///
+/// # Errors
+///
+/// Returns an error if the topic doesn't exist or an article can't be read
+pub fn seo_report_in(
+    repository: &impl ContentRepository,
+    topic: Option<&str>,
+    overrides: &SeverityOverrides,
+) -> Result<SeoReport> {
+    let articles = match topic {
+        Some(topic) => repository.list_articles(topic)?,
+        None => repository.article_paths()?,
+    };
+
+    let mut report = SeoReport::default();
+    for article in articles {
+        let content = repository.read_article(&article)?;
+        if is_draft(&content) {
+            continue;
+        }
+
+        let mut audit = audit_article(&content);
+        overrides.apply(&mut audit.issues);
+        report.articles.push(SeoArticle {
+            topic: article.topic,
+            article: article.slug,
+            file_path: article.content_file,
+            score: audit.score,
+            issues: audit.issues,
+        });
+    }
+
+    report.articles.sort_by(|a, b| a.score.cmp(&b.score).then_with(|| (&a.topic, &a.article).cmp(&(&b.topic, &b.article))));
+    report.average_score = match report.articles.len() {
+        0 => 100,
+        count => (report.articles.iter().map(|article| article.score).sum::<u32>() as f64 / count as f64).round() as u32,
+    };
+    Ok(report)
+}
+
+impl DisplayResult for SeoReport {
+    fn to_display(&self) -> String {
+        if self.articles.is_empty() {
+            return format!("{} No published content found to audit.", "Notice:".yellow().bold());
+        }
+
+        let mut lines = Vec::new();
+        for article in &self.articles {
+            let score = match article.score {
+                90.. => article.score.to_string().green(),
+                60..=89 => article.score.to_string().yellow(),
+                _ => article.score.to_string().red(),
+            };
+            lines.push(format!("\n{} {} ({}/{})", score.bold(), article.file_path.display(), article.topic, article.article));
+
+            for issue in &article.issues {
+                let label = severity_label(issue.severity);
+                match issue.line {
+                    Some(line) => lines.push(format!("  {} (line {}): {}", label, line, issue.description)),
+                    None => lines.push(format!("  {}: {}", label, issue.description)),
+                }
+                if self.verbose {
+                    if let Some(suggestion) = &issue.suggested_fix {
+                        lines.push(format!("    Suggestion: {}", suggestion));
+                    }
+                }
+            }
+        }
+
+        lines.push(format!("\n{}", "=== SEO Audit ===".green().bold()));
+        lines.push(format!("Articles audited: {}", self.articles.len()));
+        lines.push(format!(
+            "Articles with issues: {}",
+            self.articles.iter().filter(|article| !article.issues.is_empty()).count()
+        ));
+        lines.push(format!("Average score: {}", self.average_score));
+        lines.join("\n")
+    }
+}
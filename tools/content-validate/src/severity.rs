@@ -0,0 +1,72 @@
+//! # Severity Overrides
+//!
+//! Each issue type has a default severity. A `severities.yaml` file in the
+//! content base directory can raise or lower it, for example to fail CI on
+//! missing internal links or to quieten unused images.
+//!
+//! ```yaml
+//! missing_internal_link: error
+//! unused_asset: warning
+//! ```
+
+use anyhow::{Context, Result};
+use common_models::Config;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::{Severity, ValidationIssue, ValidationIssueType};
+
+/// Name of the severity overrides file in the content base directory
+pub const SEVERITIES_FILE: &str = "severities.yaml";
+
+/// Severities that replace the defaults for some issue types
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct SeverityOverrides(BTreeMap<ValidationIssueType, Severity>);
+
+impl SeverityOverrides {
+    /// Override the severity of one issue type
+    pub fn set(&mut self, issue_type: ValidationIssueType, severity: Severity) {
+        self.0.insert(issue_type, severity);
+    }
+
+    /// Severity of an issue type, after overrides
+    pub fn severity(&self, issue_type: ValidationIssueType) -> Severity {
+        self.0
+            .get(&issue_type)
+            .copied()
+            .unwrap_or_else(|| issue_type.default_severity())
+    }
+
+    /// Set the severity of each issue to its overridden value
+    pub fn apply(&self, issues: &mut [ValidationIssue]) {
+        for issue in issues {
+            if let Some(severity) = self.0.get(&issue.issue_type) {
+                issue.severity = *severity;
+            }
+        }
+    }
+}
+
+/// Get the path of the severity overrides file for a configuration
+pub fn severities_path(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(SEVERITIES_FILE)
+}
+
+/// Load severity overrides from a file, returning no overrides if it does not exist
+pub fn load_severity_overrides(path: &Path) -> Result<SeverityOverrides> {
+    if !path.exists() {
+        return Ok(SeverityOverrides::default());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read severities file: {}", path.display()))?;
+
+    if content.trim().is_empty() {
+        return Ok(SeverityOverrides::default());
+    }
+
+    serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse severities file: {}", path.display()))
+}
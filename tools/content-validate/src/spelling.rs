@@ -0,0 +1,314 @@
+//! # Spelling
+//!
+//! The `spelling` lint rule checks the prose of an article against the
+//! dictionary for its language. The language is the article's `lang`
+//! frontmatter field, the locale in a translation's file name, or is
+//! detected from common words in the text, falling back to the site's
+//! default locale.
+//!
+//! English is built in. Other languages, and words added for the whole site
+//! or a single topic, are word lists in `.writing/dictionaries` in the
+//! content base directory, one word per line, merged when an article is
+//! checked:
+//!
+//! ```text
+//! .writing/dictionaries/
+//!   de.txt       # German dictionary
+//!   en.txt       # words added to the built-in English dictionary
+//!   topics/
+//!     rust.txt   # project names and jargon for the rust topic
+//! ```
+//!
+//! A file passed with `--dictionary` adds words for every article. Articles
+//! in a language with no dictionary are not checked.
+
+use anyhow::{bail, Context, Result};
+use common_markdown::extract_frontmatter_and_content;
+use common_models::Config;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::lint::{LintContext, LintRule};
+use crate::prose::{line_at, prose_text};
+use crate::{Severity, ValidationIssue, ValidationIssueType, ValidationType};
+
+/// Location of the word lists, relative to the content base directory
+pub const DICTIONARIES_DIR: &str = ".writing/dictionaries";
+
+/// Directory of per-topic word lists, inside the dictionaries directory
+pub const TOPIC_DICTIONARIES_DIR: &str = "topics";
+
+/// Fewest common words needed to detect a language
+pub const MIN_DETECTION_HITS: usize = 5;
+
+/// The built-in English dictionary, one word and its frequency per line
+const BUILTIN_EN: &str = include_str!("../data/frequency_dictionary_en_82_765.txt");
+
+/// Common short words of each language, used to detect the language of an article
+const COMMON_WORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "is", "of", "to", "in", "that", "it", "with", "for", "this", "are"]),
+    ("de", &["der", "die", "und", "ist", "nicht", "das", "mit", "ein", "eine", "auf", "ich", "zu"]),
+    ("fr", &["le", "la", "les", "et", "est", "des", "une", "dans", "pour", "pas", "que", "avec"]),
+    ("es", &["el", "los", "las", "y", "es", "una", "por", "para", "con", "que", "del", "como"]),
+    ("it", &["il", "di", "che", "è", "per", "una", "con", "non", "sono", "della", "gli", "anche"]),
+    ("nl", &["de", "het", "een", "en", "is", "van", "niet", "dat", "met", "voor", "zijn", "ook"]),
+    ("pt", &["o", "os", "uma", "não", "para", "com", "que", "do", "da", "em", "é", "são"]),
+];
+
+/// Words of the built-in English dictionary, parsed on first use and shared for the run
+static BUILTIN_EN_WORDS: OnceLock<HashSet<String>> = OnceLock::new();
+
+/// Words known to be spelled correctly
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    /// Built-in words for the language, shared by every article's dictionary
+    builtin: Option<&'static HashSet<String>>,
+    /// Words added from word lists
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Get the built-in dictionary for a language, if there is one
+    pub fn builtin(lang: &str) -> Option<Self> {
+        match primary_subtag(lang).as_str() {
+            "en" => Some(Self {
+                builtin: Some(BUILTIN_EN_WORDS.get_or_init(|| list_words(BUILTIN_EN).collect())),
+                words: HashSet::new(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Add the words in a word list
+    ///
+    /// The first word on each line is added, so frequency lists can be used
+    /// as they are. Blank lines and lines starting with `#` are skipped.
+    pub fn add_words(&mut self, list: &str) {
+        let builtin = self.builtin;
+        let words = list_words(list).filter(|word| !builtin.is_some_and(|builtin| builtin.contains(word)));
+        self.words.extend(words);
+    }
+
+    /// Add the words in a word list file, returning whether the file exists
+    pub fn load_words(&mut self, path: &Path) -> Result<bool> {
+        if !path.is_file() {
+            return Ok(false);
+        }
+
+        let list = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read dictionary: {}", path.display()))?;
+        self.add_words(&list);
+        Ok(true)
+    }
+
+    /// Check whether a word is spelled correctly, ignoring case and a possessive `'s`
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.to_lowercase().replace('’', "'");
+        self.has_word(&word) || word.strip_suffix("'s").is_some_and(|stem| self.has_word(stem))
+    }
+
+    /// Number of words in the dictionary
+    pub fn len(&self) -> usize {
+        self.builtin.map_or(0, HashSet::len) + self.words.len()
+    }
+
+    /// Check whether the dictionary has no words
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn has_word(&self, word: &str) -> bool {
+        self.words.contains(word) || self.builtin.is_some_and(|builtin| builtin.contains(word))
+    }
+}
+
+/// The first word on each line of a word list, lowercased
+fn list_words(list: &str) -> impl Iterator<Item = String> + '_ {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_lowercase)
+}
+
+/// Get the directory of word lists for a configuration
+pub fn dictionaries_dir(config: &Config) -> PathBuf {
+    Path::new(&config.content.base_dir).join(DICTIONARIES_DIR)
+}
+
+/// Build the dictionary for an article
+///
+/// Starts from the built-in dictionary or `{lang}.txt` for the article's
+/// language, then adds the topic's word list and the `extra` file. Returns
+/// `None` if there is no dictionary for the language.
+pub fn load_dictionary(config: &Config, lang: &str, topic: Option<&str>, extra: Option<&Path>) -> Result<Option<Dictionary>> {
+    let dir = dictionaries_dir(config);
+    let primary = primary_subtag(lang);
+
+    let builtin = Dictionary::builtin(&primary);
+    let mut has_language = builtin.is_some();
+    let mut dictionary = builtin.unwrap_or_default();
+
+    has_language |= dictionary.load_words(&dir.join(format!("{}.txt", lang)))?;
+    if primary != lang {
+        has_language |= dictionary.load_words(&dir.join(format!("{}.txt", primary)))?;
+    }
+    if !has_language {
+        return Ok(None);
+    }
+
+    if let Some(topic) = topic {
+        dictionary.load_words(&dir.join(TOPIC_DICTIONARIES_DIR).join(format!("{}.txt", topic)))?;
+    }
+    if let Some(extra) = extra {
+        if !dictionary.load_words(extra)? {
+            bail!("Dictionary not found: {}", extra.display());
+        }
+    }
+
+    Ok(Some(dictionary))
+}
+
+/// The language part of a locale, such as `en` for `en-GB`
+fn primary_subtag(lang: &str) -> String {
+    lang.split(['-', '_']).next().unwrap_or(lang).to_lowercase()
+}
+
+/// Guess the language of some text from the common words in it
+///
+/// Returns `None` when there are too few common words to tell, or two
+/// languages match equally well.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let word = Regex::new(r"\p{L}+").expect("valid regex");
+    let words: Vec<String> = word.find_iter(text).map(|found| found.as_str().to_lowercase()).collect();
+
+    let mut scores: Vec<(usize, &'static str)> = COMMON_WORDS
+        .iter()
+        .map(|(lang, common)| (words.iter().filter(|word| common.contains(&word.as_str())).count(), *lang))
+        .collect();
+    scores.sort_by(|a, b| b.0.cmp(&a.0));
+
+    match scores.as_slice() {
+        [(best, lang), (second, _), ..] if *best >= MIN_DETECTION_HITS && best > second => Some(*lang),
+        _ => None,
+    }
+}
+
+/// Work out the language an article is written in
+///
+/// Uses the `lang` frontmatter field, then the locale of a translation file
+/// named `{slug}.{locale}.md`, then the language detected from the text, and
+/// finally the site's default locale.
+pub fn article_language(file_path: &Path, content: &str, config: &Config) -> String {
+    if let Ok((frontmatter, _)) = extract_frontmatter_and_content(content) {
+        if let Some(lang) = frontmatter.lang() {
+            return lang.to_string();
+        }
+    }
+
+    let file_locale = file_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit_once('.'))
+        .map(|(_, locale)| locale)
+        .filter(|locale| config.i18n.is_enabled() && config.i18n.is_available(locale));
+    if let Some(locale) = file_locale {
+        return locale.to_string();
+    }
+
+    detect_language(&prose_text(content))
+        .map(str::to_string)
+        .unwrap_or_else(|| config.i18n.default_locale.clone())
+}
+
+/// Find the key of the topic a content file belongs to
+pub fn topic_of(file_path: &Path, config: &Config) -> Option<String> {
+    let topic_dir = file_path.parent()?.parent()?.file_name()?.to_str()?;
+    config
+        .content
+        .topics
+        .iter()
+        .find(|(_, topic)| topic.directory == topic_dir)
+        .map(|(key, _)| key.clone())
+}
+
+/// Find words in the prose of an article that aren't in the dictionary
+///
+/// Each unknown word is reported once, at its first use. Acronyms and
+/// words with capitals after the first letter, such as `GitHub`, are
+/// skipped as names.
+pub fn find_misspellings(content: &str, dictionary: &Dictionary) -> Vec<ValidationIssue> {
+    let text = prose_text(content);
+    let word = Regex::new(r"\p{L}+(?:['’]\p{L}+)*").expect("valid regex");
+
+    let mut unknown: BTreeMap<String, (usize, usize, String)> = BTreeMap::new();
+    for found in word.find_iter(&text) {
+        let spelled = found.as_str();
+        let is_name = spelled.chars().skip(1).any(char::is_uppercase);
+        if spelled.chars().count() < 2 || is_name || dictionary.contains(spelled) {
+            continue;
+        }
+
+        let entry = unknown
+            .entry(spelled.to_lowercase())
+            .or_insert_with(|| (found.start(), 0, spelled.to_string()));
+        entry.1 += 1;
+    }
+
+    let mut unknown: Vec<(usize, usize, String)> = unknown.into_values().collect();
+    unknown.sort();
+
+    unknown
+        .into_iter()
+        .map(|(offset, count, spelled)| {
+            let times = if count > 1 { format!(" ({} times)", count) } else { String::new() };
+            ValidationIssue {
+                issue_type: ValidationIssueType::Misspelling,
+                severity: ValidationIssueType::Misspelling.default_severity(),
+                line: Some(line_at(content, offset)),
+                column: None,
+                description: format!("Unknown word: '{}'{}", spelled, times),
+                suggested_fix: Some(format!(
+                    "Fix the spelling, or add '{}' to a word list in {}",
+                    spelled, DICTIONARIES_DIR
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Checks spelling against the dictionary for the article's language
+pub struct SpellingRule;
+
+impl LintRule for SpellingRule {
+    fn id(&self) -> &'static str {
+        "spelling"
+    }
+
+    fn description(&self) -> &'static str {
+        "Words are in the dictionary for the article's language"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn validation_type(&self) -> ValidationType {
+        ValidationType::Markdown
+    }
+
+    fn check(&self, content: &str, context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+        let lang = article_language(context.file_path, content, context.config);
+        let topic = topic_of(context.file_path, context.config);
+        let dictionary = load_dictionary(
+            context.config,
+            &lang,
+            topic.as_deref(),
+            context.options.dictionary_path.as_deref(),
+        )?;
+
+        Ok(dictionary.map(|dictionary| find_misspellings(content, &dictionary)).unwrap_or_default())
+    }
+}
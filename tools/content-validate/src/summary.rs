@@ -0,0 +1,133 @@
+//! # Validation Summary
+//!
+//! Rolls validation issues up by severity for each file and topic, so a
+//! command line or CI job can report totals and decide whether to fail.
+
+use anyhow::{Context, Result};
+use common_models::Config;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::{Severity, ValidationResult};
+
+/// Number of issues at each severity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+impl SeverityCounts {
+    /// Count one issue
+    pub fn add(&mut self, severity: Severity) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Info => self.info += 1,
+        }
+    }
+
+    /// Issues at any severity
+    pub fn total(&self) -> usize {
+        self.errors + self.warnings + self.info
+    }
+
+    /// Issues at `severity` or more serious
+    pub fn at_least(&self, severity: Severity) -> usize {
+        match severity {
+            Severity::Error => self.errors,
+            Severity::Warning => self.errors + self.warnings,
+            Severity::Info => self.total(),
+        }
+    }
+}
+
+/// Issue counts by file, by topic and overall
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationSummary {
+    /// Files that were validated
+    pub files_checked: usize,
+    /// Counts for each file with issues
+    pub files: BTreeMap<PathBuf, SeverityCounts>,
+    /// Counts for each topic with issues, by topic key
+    pub topics: BTreeMap<String, SeverityCounts>,
+    pub totals: SeverityCounts,
+    /// Issues silenced by suppression comments, by rule ID
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub suppressed: BTreeMap<String, usize>,
+}
+
+impl ValidationSummary {
+    /// Summarize validation results
+    ///
+    /// Files are assigned to a topic by their directory. Files outside every
+    /// topic directory count towards the totals only.
+    pub fn from_results(results: &[ValidationResult], config: &Config) -> Self {
+        let topic_dirs: Vec<(&String, PathBuf)> = config
+            .content
+            .topics
+            .iter()
+            .map(|(key, topic)| (key, absolute(&Path::new(&config.content.base_dir).join(&topic.directory))))
+            .collect();
+
+        let mut summary = Self {
+            files_checked: results.len(),
+            ..Default::default()
+        };
+
+        for result in results {
+            for (rule, count) in &result.suppressed {
+                *summary.suppressed.entry(rule.clone()).or_default() += count;
+            }
+
+            if result.issues.is_empty() {
+                continue;
+            }
+
+            let file_path = absolute(&result.file_path);
+            let topic = topic_dirs
+                .iter()
+                .find(|(_, dir)| file_path.starts_with(dir))
+                .map(|(key, _)| (*key).clone());
+
+            for issue in &result.issues {
+                summary.totals.add(issue.severity);
+                summary.files.entry(result.file_path.clone()).or_default().add(issue.severity);
+                if let Some(topic) = &topic {
+                    summary.topics.entry(topic.clone()).or_default().add(issue.severity);
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Number of issues silenced by any rule
+    pub fn suppressed_total(&self) -> usize {
+        self.suppressed.values().sum()
+    }
+
+    /// Whether any issue is an error
+    pub fn has_errors(&self) -> bool {
+        self.totals.errors > 0
+    }
+
+    /// Process exit code for a run that fails on issues at `fail_on` or more serious
+    ///
+    /// Returns 1 if there are any such issues and 0 otherwise.
+    pub fn exit_code(&self, fail_on: Severity) -> i32 {
+        i32::from(self.totals.at_least(fail_on) > 0)
+    }
+
+    /// Render the summary as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize validation summary")
+    }
+}
+
+/// Resolve a path for comparison, leaving it as is if it doesn't exist
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
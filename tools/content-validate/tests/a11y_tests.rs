@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use content_validate::{
+        contrast_ratio, parse_color, validate_accessibility, validate_html_output, IssueCategory, ValidationIssueType,
+    };
+    use std::fs;
+
+    fn issues_in(html: &str) -> Vec<(ValidationIssueType, usize, String)> {
+        let mut issues = Vec::new();
+        validate_accessibility(html, &mut issues);
+        issues
+            .into_iter()
+            .map(|issue| (issue.issue_type, issue.line.unwrap(), issue.description))
+            .collect()
+    }
+
+    #[test]
+    fn test_heading_order_allows_going_back_up() {
+        let html = "<h1>Title</h1>\n<h2>Part</h2>\n<h3>Detail</h3>\n<h2>Next</h2>\n<h4>Skipped</h4>\n<hr>\n";
+        assert_eq!(issues_in(html), vec![(
+            ValidationIssueType::HeadingOrder,
+            5,
+            "Heading level skipped: <h4> follows <h2>".to_string(),
+        )]);
+    }
+
+    #[test]
+    fn test_tables_need_header_cells() {
+        let html = "<table><tr><th>Name</th></tr><tr><td>A</td></tr></table>\n<table>\n<tr><td>B</td></tr>\n</table>\n";
+        let issues = issues_in(html);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].0, ValidationIssueType::MissingTableHeader);
+        assert_eq!(issues[0].1, 2);
+    }
+
+    #[test]
+    fn test_link_text_must_describe_the_target() {
+        let html = concat!(
+            "<p>For details <a href=\"/docs\">click here</a>.</p>\n",
+            "<a href=\"/more\"><strong>Read more…</strong></a>\n",
+            "<a href=\"/guide\">The setup guide</a>\n",
+            "<a href=\"/x\" aria-label=\"Pricing\">here</a>\n",
+            "<a href=\"/home\"><img src=\"logo.svg\" alt=\"Home\"></a>\n",
+            "<a href=\"/y\"><img src=\"icon.svg\"></a>\n",
+        );
+        let issues: Vec<(usize, String)> = issues_in(html)
+            .into_iter()
+            .map(|(issue_type, line, description)| {
+                assert_eq!(issue_type, ValidationIssueType::NonDescriptiveLink);
+                (line, description)
+            })
+            .collect();
+        assert_eq!(issues, vec![
+            (1, "Link text doesn't describe its target: \"click here\"".to_string()),
+            (2, "Link text doesn't describe its target: \"Read more…\"".to_string()),
+            (6, "Link has no text".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_inline_style_contrast() {
+        let html = concat!(
+            "<span style=\"color: #777; background-color: #888\">faint</span>\n",
+            "<span style='color:black;background:white'>fine</span>\n",
+            "<span style=\"color: #777\">background unknown</span>\n",
+            "<span style=\"border-color: #eee; background: #fff\">no text color</span>\n",
+        );
+        let issues = issues_in(html);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].0, ValidationIssueType::LowContrast);
+        assert_eq!(issues[0].1, 1);
+
+        assert_eq!(parse_color("#FFF"), Some((255, 255, 255)));
+        assert_eq!(parse_color("rgb(255, 0, 10) !important"), Some((255, 0, 10)));
+        assert_eq!(parse_color("rgba(0, 0, 0, 0.5)"), None);
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 1e-9);
+        assert_eq!(IssueCategory::of(ValidationIssueType::LowContrast), IssueCategory::Accessibility);
+    }
+
+    #[test]
+    fn test_validate_html_output_checks_every_html_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let html_dir = temp_dir.path().join("html");
+        fs::create_dir_all(temp_dir.path().join("fr/html")).unwrap();
+        fs::create_dir_all(&html_dir).unwrap();
+        fs::write(html_dir.join("clean.html"), "<h1>Clean</h1>").unwrap();
+        fs::write(temp_dir.path().join("fr/html/sale.html"), "<h1>A</h1><h3>B</h3>").unwrap();
+        fs::write(temp_dir.path().join("rss.xml"), "<a>here</a>").unwrap();
+
+        let results = validate_html_output(temp_dir.path()).unwrap();
+        assert_eq!(results.len(), 2);
+        let with_issues: Vec<_> = results.iter().filter(|result| !result.issues.is_empty()).collect();
+        assert_eq!(with_issues.len(), 1);
+        assert!(with_issues[0].file_path.ends_with("fr/html/sale.html"));
+
+        assert!(validate_html_output(&temp_dir.path().join("missing")).is_err());
+    }
+}
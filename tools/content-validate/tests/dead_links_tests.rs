@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use content_validate::{parse_snapshot, rewrite_link, LinkQuarantine, QUARANTINE_AFTER};
+
+    const DEAD: &str = "https://gone.example.com/post";
+
+    #[test]
+    fn test_parse_snapshot() {
+        let available = r#"{"url": "gone.example.com/post", "archived_snapshots": {"closest": {
+            "status": "200", "available": true, "timestamp": "20200101000000",
+            "url": "http://web.archive.org/web/20200101000000/https://gone.example.com/post"}}}"#;
+        assert_eq!(
+            parse_snapshot(available).unwrap().as_deref(),
+            Some("https://web.archive.org/web/20200101000000/https://gone.example.com/post")
+        );
+
+        assert_eq!(parse_snapshot(r#"{"url": "gone.example.com/post", "archived_snapshots": {}}"#).unwrap(), None);
+        assert!(parse_snapshot("<html>Service Unavailable</html>").is_err());
+    }
+
+    #[test]
+    fn test_link_counts_as_dead_after_failing_several_runs() {
+        let mut quarantine = LinkQuarantine::default();
+        for run in 1..QUARANTINE_AFTER {
+            // Checking a URL twice in one run counts once
+            assert!(!quarantine.record_failure(DEAD));
+            assert!(!quarantine.record_failure(DEAD));
+            assert_eq!(quarantine.get(DEAD).unwrap().failures, run);
+
+            let yaml = serde_yaml::to_string(&quarantine).unwrap();
+            quarantine = serde_yaml::from_str(&yaml).unwrap();
+        }
+        assert!(quarantine.record_failure(DEAD));
+
+        quarantine.record_success(DEAD);
+        assert!(quarantine.get(DEAD).is_none());
+    }
+
+    #[test]
+    fn test_quarantine_file_is_removed_once_empty() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(".link-quarantine.yaml");
+
+        let mut quarantine = LinkQuarantine::default();
+        quarantine.record_failure(DEAD);
+        quarantine.save(&path).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("failures: 1"));
+
+        let mut quarantine = LinkQuarantine::load(&path).unwrap();
+        assert_eq!(quarantine.get(DEAD).unwrap().failures, 1);
+        quarantine.record_success(DEAD);
+        quarantine.save(&path).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_rewrite_link_replaces_whole_urls_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("article.md");
+        std::fs::write(
+            &path,
+            format!("[Post]({0}) <{0}> [Other]({0}s)\n<a href=\"{0}\">Post</a>\n", DEAD),
+        )
+        .unwrap();
+
+        let archived = "https://web.archive.org/web/2020/https://gone.example.com/post";
+        assert!(rewrite_link(&path, DEAD, archived).unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            format!("[Post]({0}) <{0}> [Other]({1}s)\n<a href=\"{0}\">Post</a>\n", archived, DEAD)
+        );
+        assert!(!rewrite_link(&path, DEAD, archived).unwrap());
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use common_test_utils::config_for;
+    use common_traits::CancellationToken;
+    use content_validate::{run_doctor, DoctorCheck, DoctorOptions, Severity};
+    use std::fs;
+
+    #[test]
+    fn test_doctor_reports_findings_by_severity() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let blog = temp_dir.path().join("blog");
+        fs::create_dir_all(blog.join("good")).unwrap();
+        fs::create_dir_all(blog.join("empty")).unwrap();
+        fs::create_dir_all(blog.join("no-content")).unwrap();
+        fs::create_dir_all(blog.join("broken")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("notes")).unwrap();
+
+        fs::write(
+            blog.join("good/good.md"),
+            "---\ntitle: Good\ndescription: A good article\npublished: 2024-01-02\n---\n\n![Chart](chart.png)\n",
+        )
+        .unwrap();
+        fs::write(blog.join("good/chart.png"), "").unwrap();
+        fs::write(blog.join("good/unused.png"), "").unwrap();
+        fs::write(blog.join("no-content/notes.txt"), "").unwrap();
+        fs::write(blog.join("broken/index.md"), "No frontmatter here\n\n![Gone](gone.png)\n").unwrap();
+
+        let report = run_doctor(&config_for(temp_dir.path(), &["blog", "notes"]), &DoctorOptions::default(), &CancellationToken::new()).unwrap();
+
+        let findings: Vec<(Severity, DoctorCheck, &str)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.severity, finding.check, finding.message.as_str()))
+            .collect();
+        assert_eq!(findings, vec![
+            (Severity::Error, DoctorCheck::Structure, "Article directory has no content file"),
+            (Severity::Error, DoctorCheck::Frontmatter, "Invalid frontmatter: No frontmatter found in content"),
+            (Severity::Error, DoctorCheck::Images, "Missing image: gone.png"),
+            (Severity::Warning, DoctorCheck::Structure, "Empty article directory"),
+            (Severity::Info, DoctorCheck::Structure, "Topic 'notes' has no articles"),
+            (Severity::Info, DoctorCheck::Images, "Unused image: unused.png"),
+        ]);
+        assert_eq!(report.findings[1].path, Some(blog.join("broken/index.md")));
+
+        assert_eq!(report.files_checked, 2);
+        assert!(report.has_errors());
+        assert_eq!(report.counts()[&Severity::Error], 3);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["findings"][0]["severity"], "error");
+        assert_eq!(json["findings"][0]["check"], "structure");
+    }
+
+    #[test]
+    fn test_doctor_reports_config_issues() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = config_for(temp_dir.path(), &["blog", "notes"]);
+        config.default_topic = Some("missing".to_string());
+
+        let report = run_doctor(&config, &DoctorOptions::default(), &CancellationToken::new()).unwrap();
+
+        assert!(report
+            .findings
+            .iter()
+            .all(|finding| finding.severity == Severity::Error && finding.check == DoctorCheck::Config));
+        assert!(report.findings.iter().any(|finding| finding.message.starts_with("default_topic")));
+        assert_eq!(report.files_checked, 0);
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use common_models::Config;
+    use content_validate::{
+        github_comment, load_base_totals, SeverityCounts, ValidationIssue, ValidationIssueType, ValidationReport,
+        ValidationResult, COMMENT_LIMIT,
+    };
+    use std::path::PathBuf;
+
+    fn issue(issue_type: ValidationIssueType, line: usize) -> ValidationIssue {
+        ValidationIssue {
+            issue_type,
+            severity: issue_type.default_severity(),
+            line: Some(line),
+            column: None,
+            description: format!("{:?}", issue_type),
+            suggested_fix: None,
+        }
+    }
+
+    fn report() -> ValidationReport {
+        let results = vec![
+            ValidationResult {
+                file_path: PathBuf::from("blog/a/a.md"),
+                issues: vec![issue(ValidationIssueType::MissingAltText, 4)],
+                suppressed: Default::default(),
+            },
+            ValidationResult {
+                file_path: PathBuf::from("blog/b/b.md"),
+                issues: vec![issue(ValidationIssueType::BrokenLink, 2), issue(ValidationIssueType::UnusedAsset, 9)],
+                suppressed: Default::default(),
+            },
+            ValidationResult {
+                file_path: PathBuf::from("blog/c/c.md"),
+                issues: Vec::new(),
+                suppressed: Default::default(),
+            },
+        ];
+        ValidationReport::new(results, &Config::default())
+    }
+
+    #[test]
+    fn test_comment_compares_with_base_and_puts_errors_first() {
+        let base = SeverityCounts { errors: 0, warnings: 1, info: 3 };
+        let comment = github_comment(&report(), Some(&base), COMMENT_LIMIT);
+
+        assert!(comment.starts_with("## ❌ Content validation failed: 1 error\n\nChecked 3 files: 2 with issues."));
+        assert!(comment.contains("| ❌ Errors | 1 | 🔺 +1 |"));
+        assert!(comment.contains("| ⚠️ Warnings | 1 | no change |"));
+        assert!(comment.contains("| ℹ️ Info | 1 | 🔻 -2 |"));
+        assert!(comment.contains("<summary><code>blog/b/b.md</code> (2 issues)</summary>\n\n- ❌ line 2: BrokenLink\n- ℹ️ line 9: UnusedAsset"));
+        assert!(comment.find("blog/b/b.md").unwrap() < comment.find("blog/a/a.md").unwrap());
+
+        let comment = github_comment(&report(), None, COMMENT_LIMIT);
+        assert!(comment.contains("| ❌ Errors | 1 | – |"));
+    }
+
+    #[test]
+    fn test_comment_is_truncated_to_the_limit() {
+        let full = github_comment(&report(), None, COMMENT_LIMIT);
+        let limit = full.find("<details>").unwrap() + 40;
+
+        let comment = github_comment(&report(), None, limit);
+        assert!(comment.chars().count() <= limit);
+        assert!(comment.ends_with("_2 more files not shown._"));
+    }
+
+    #[test]
+    fn test_load_base_totals_from_a_json_report() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("base.json");
+        std::fs::write(&path, r#"{"results": [], "summary": {"files_checked": 4, "totals": {"errors": 2, "warnings": 0, "info": 5}}}"#).unwrap();
+        assert_eq!(load_base_totals(&path).unwrap(), SeverityCounts { errors: 2, warnings: 0, info: 5 });
+
+        std::fs::write(&path, r#"{"summary": {}}"#).unwrap();
+        assert!(load_base_totals(&path).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use common_models::{Config, LinkPolicyConfig};
+    use content_validate::{validate_link_policy, without_tracking_params, Severity, ValidationIssueType};
+
+    const CONTENT: &str = "---\ntitle: Links\n---\n\n\
+                           [Spam](https://www.bit.ly/x) [Docs](http://docs.rs/url)\n\n\
+                           [Tracked](https://docs.rs/?utm_source=news&q=url&fbclid=abc)\n\n\
+                           <a href=\"https://elsewhere.org/\">Elsewhere</a> [Home](http://example.com/about)\n";
+
+    fn check(policy: LinkPolicyConfig) -> Vec<(ValidationIssueType, usize)> {
+        let mut config = Config::default();
+        config.publication.site_url = Some("https://example.com".to_string());
+        config.link_policy = policy;
+
+        let mut issues = Vec::new();
+        validate_link_policy(CONTENT, &config, &mut issues);
+        issues.into_iter().map(|issue| (issue.issue_type, issue.line.unwrap())).collect()
+    }
+
+    #[test]
+    fn test_no_policy_checks_nothing() {
+        assert!(check(LinkPolicyConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_domain_lists_cover_subdomains_but_not_the_site() {
+        let issues = check(LinkPolicyConfig {
+            deny_domains: vec!["bit.ly".to_string()],
+            allow_domains: vec!["docs.rs".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(issues, vec![
+            (ValidationIssueType::DeniedDomain, 5),
+            (ValidationIssueType::UnlistedDomain, 9),
+        ]);
+        assert_eq!(ValidationIssueType::DeniedDomain.default_severity(), Severity::Error);
+    }
+
+    #[test]
+    fn test_https_and_tracking_rules_toggle_independently() {
+        let https = check(LinkPolicyConfig {
+            require_https: true,
+            ..Default::default()
+        });
+        assert_eq!(https, vec![(ValidationIssueType::InsecureLink, 5), (ValidationIssueType::InsecureLink, 9)]);
+
+        let tracking = check(LinkPolicyConfig {
+            flag_tracking_params: true,
+            ..Default::default()
+        });
+        assert_eq!(tracking, vec![(ValidationIssueType::TrackingParameter, 7)]);
+
+        let url = url::Url::parse("https://docs.rs/?utm_source=news&q=url&fbclid=abc").unwrap();
+        assert_eq!(without_tracking_params(&url), "https://docs.rs/?q=url");
+        let url = url::Url::parse("https://docs.rs/page?UTM_medium=email#top").unwrap();
+        assert_eq!(without_tracking_params(&url), "https://docs.rs/page#top");
+    }
+
+    #[test]
+    fn test_link_policy_config_round_trips() {
+        let policy: LinkPolicyConfig =
+            serde_yaml::from_str("deny_domains: [bit.ly]\nrequire_https: true\nnofollow_domains: ['*.sponsor.example']\n").unwrap();
+        assert!(policy.require_https && !policy.flag_tracking_params);
+        assert!(policy.is_nofollow("sponsor.example"));
+        assert!(policy.is_nofollow("shop.sponsor.example"));
+        assert!(!policy.is_nofollow("notsponsor.example"));
+    }
+}
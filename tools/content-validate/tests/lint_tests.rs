@@ -0,0 +1,354 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use common_cli::DisplayResult;
+    use common_models::Config;
+    use common_traits::CancellationToken;
+    use content_validate::{
+        load_lint_config, ArchiveFallback, LinkQuarantine, LintConfig, LintContext, LintOutcome, LintRule, Linter,
+        RuleRegistry, Severity, Suppressions, ValidationIssue, ValidationIssueType, ValidationOptions, ValidationReport,
+        ValidationResult, ValidationType,
+    };
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    /// Reports every line containing TODO
+    struct TodoRule;
+
+    impl LintRule for TodoRule {
+        fn id(&self) -> &'static str {
+            "todo"
+        }
+
+        fn description(&self) -> &'static str {
+            "No TODO markers"
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::Warning
+        }
+
+        fn validation_type(&self) -> ValidationType {
+            ValidationType::Markdown
+        }
+
+        fn check(&self, content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+            Ok(content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| line.contains("TODO"))
+                .map(|(index, _)| ValidationIssue {
+                    issue_type: ValidationIssueType::MarkdownFormatting,
+                    severity: Severity::Warning,
+                    line: Some(index + 1),
+                    column: None,
+                    description: "TODO marker".to_string(),
+                    suggested_fix: None,
+                })
+                .collect())
+        }
+    }
+
+    fn options(validation_types: Vec<ValidationType>) -> ValidationOptions {
+        ValidationOptions {
+            article_slug: None,
+            topic: None,
+            validation_types,
+            check_external_links: false,
+            timeout: None,
+            dictionary_path: None,
+            include_drafts: false,
+            images_dir: None,
+            changed_since: None,
+            archive_fallback: ArchiveFallback::Off,
+        }
+    }
+
+    fn outcome(linter: &Linter, content: &str, validation_types: Vec<ValidationType>) -> LintOutcome {
+        let config = Config::default();
+        let options = options(validation_types);
+        let token = CancellationToken::new();
+        let mut quarantine = LinkQuarantine::default();
+        let slug_topics = BTreeMap::new();
+        let mut context = LintContext {
+            file_path: Path::new("content/blog/post/post.md"),
+            config: &config,
+            options: &options,
+            token: &token,
+            quarantine: &mut quarantine,
+            slug_topics: &slug_topics,
+        };
+
+        linter.lint(content, &mut context).unwrap()
+    }
+
+    fn lint(linter: &Linter, content: &str, validation_types: Vec<ValidationType>) -> Vec<usize> {
+        outcome(linter, content, validation_types)
+            .issues
+            .into_iter()
+            .filter_map(|issue| issue.line)
+            .collect()
+    }
+
+    /// Fails whenever it runs
+    struct BrokenRule;
+
+    impl LintRule for BrokenRule {
+        fn id(&self) -> &'static str {
+            "broken"
+        }
+
+        fn description(&self) -> &'static str {
+            "Always fails"
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::Error
+        }
+
+        fn validation_type(&self) -> ValidationType {
+            ValidationType::Markdown
+        }
+
+        fn check(&self, _content: &str, _context: &mut LintContext<'_>) -> Result<Vec<ValidationIssue>> {
+            anyhow::bail!("the broken rule ran")
+        }
+    }
+
+    fn todo_linter(settings: LintConfig) -> Result<Linter> {
+        let mut registry = RuleRegistry::empty();
+        registry.register(TodoRule);
+        Linter::new(registry, settings)
+    }
+
+    #[test]
+    fn test_builtin_rules_have_unique_ids() {
+        let registry = RuleRegistry::builtin();
+        let ids: Vec<&str> = registry.rules().map(|rule| rule.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "canonical",
+                "link-policy",
+                "links",
+                "publish-date",
+                "components",
+                "duplicate-slug",
+                "assets",
+                "alt-text",
+                "weasel-words",
+                "cliches",
+                "repeated-words",
+                "conjunction-starts",
+                "banned-phrases",
+                "spelling",
+            ]
+        );
+        assert_eq!(registry.get("links").unwrap().validation_type(), ValidationType::Links);
+        assert_eq!(registry.get("publish-date").unwrap().validation_type(), ValidationType::PublishDate);
+        assert_eq!(registry.get("alt-text").unwrap().default_severity(), Severity::Warning);
+        assert!(registry.get("missing").is_none());
+    }
+
+    #[test]
+    fn test_rules_run_only_for_their_validation_type() {
+        let linter = todo_linter(LintConfig::default()).unwrap();
+        let content = "TODO one\nfine\nTODO two\n";
+
+        assert_eq!(lint(&linter, content, vec![ValidationType::Markdown]), vec![1, 3]);
+        assert_eq!(lint(&linter, content, vec![ValidationType::All]), vec![1, 3]);
+        assert!(lint(&linter, content, vec![ValidationType::Links]).is_empty());
+    }
+
+    #[test]
+    fn test_lint_config_disables_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lint.yaml");
+        assert_eq!(load_lint_config(&path).unwrap(), LintConfig::default());
+
+        std::fs::write(&path, "disable:\n  - todo\n").unwrap();
+        let settings = load_lint_config(&path).unwrap();
+        let linter = todo_linter(settings).unwrap();
+        assert!(linter.is_disabled("todo"));
+        assert!(lint(&linter, "TODO\n", vec![ValidationType::All]).is_empty());
+
+        // Misspelled rules are caught rather than silently ignored
+        std::fs::write(&path, "disable:\n  - todos\n").unwrap();
+        let err = todo_linter(load_lint_config(&path).unwrap()).err().unwrap();
+        assert!(err.to_string().contains("Unknown lint rule 'todos'"));
+    }
+
+    #[test]
+    fn test_suppression_comments() {
+        let linter = todo_linter(LintConfig::default()).unwrap();
+        let content = "TODO 1\n\
+                       <!-- writing-disable-next-line todo -->\n\
+                       TODO 3\n\
+                       TODO 4\n\
+                       <!-- writing-disable links, todo -->\n\
+                       TODO 6\n\
+                       <!-- writing-enable todo -->\n\
+                       TODO 8\n\
+                       <!-- writing-disable-next-line other-rule -->\n\
+                       TODO 10\n";
+
+        assert_eq!(lint(&linter, content, vec![ValidationType::All]), vec![1, 4, 8, 10]);
+    }
+
+    #[test]
+    fn test_suppressions_without_a_line() {
+        // Disabled until the end of the file
+        let suppressions = Suppressions::parse("Intro\n<!-- writing-disable -->\nBody\n");
+        assert!(!suppressions.suppresses("duplicate-slug", Some(1)));
+        assert!(suppressions.suppresses("duplicate-slug", Some(3)));
+        assert!(suppressions.suppresses("duplicate-slug", None));
+
+        // Re-enabled before the end
+        let suppressions = Suppressions::parse("<!-- writing-disable duplicate-slug -->\nBody\n<!-- writing-enable -->\n");
+        assert!(suppressions.suppresses("duplicate-slug", Some(2)));
+        assert!(!suppressions.suppresses("duplicate-slug", None));
+        assert!(!suppressions.suppresses("links", Some(2)));
+    }
+
+    #[test]
+    fn test_suppressed_issues_are_counted() {
+        let linter = todo_linter(LintConfig::default()).unwrap();
+        let content = "<!-- writing-disable-next-line todo -->\nTODO 2\nTODO 3\n";
+
+        let outcome = outcome(&linter, content, vec![ValidationType::All]);
+        assert_eq!(outcome.issues.len(), 1);
+        assert_eq!(outcome.suppressed["todo"], 1);
+        assert_eq!(outcome.suppressed_total(), 1);
+    }
+
+    #[test]
+    fn test_suppression_comments_apply_to_links() {
+        let linter = Linter::new(RuleRegistry::builtin(), LintConfig::default()).unwrap();
+        let content = "Intro\n\
+                       <!-- writing-disable-next-line links -->\n\
+                       See [old docs](missing.md).\n\
+                       Also [gone](gone.md).\n";
+
+        let outcome = outcome(&linter, content, vec![ValidationType::Links]);
+        let issues: Vec<(ValidationIssueType, Option<usize>, Option<usize>)> = outcome
+            .issues
+            .iter()
+            .map(|issue| (issue.issue_type, issue.line, issue.column))
+            .collect();
+        assert_eq!(issues, vec![(ValidationIssueType::MissingInternalLink, Some(4), Some(6))]);
+        assert_eq!(outcome.suppressed["links"], 1);
+    }
+
+    #[test]
+    fn test_frontmatter_disables_rules_for_one_article() {
+        let linter = todo_linter(LintConfig::default()).unwrap();
+        let content = "---\ntitle: Post\nlint:\n  disable: [todo]\n---\nTODO 6\nTODO 7\n";
+
+        // The rule doesn't run, so there is nothing to count as suppressed
+        let outcome = outcome(&linter, content, vec![ValidationType::All]);
+        assert!(outcome.issues.is_empty());
+        assert!(outcome.suppressed.is_empty());
+
+        // Other articles are still checked
+        assert_eq!(lint(&linter, "---\ntitle: Other\n---\nTODO 4\n", vec![ValidationType::All]), vec![4]);
+    }
+
+    #[test]
+    fn test_rules_disabled_in_frontmatter_are_not_run() {
+        let mut registry = RuleRegistry::empty();
+        registry.register(BrokenRule);
+        registry.register(TodoRule);
+        let linter = Linter::new(registry, LintConfig::default()).unwrap();
+
+        let content = "---\ntitle: Post\nlint:\n  disable: [broken]\n---\nTODO 6\n";
+        assert_eq!(lint(&linter, content, vec![ValidationType::All]), vec![6]);
+    }
+
+    #[test]
+    fn test_unknown_frontmatter_rules_are_reported() {
+        let linter = todo_linter(LintConfig::default()).unwrap();
+        let content = "---\ntitle: Post\nlint:\n  disable: [todos]\n---\nTODO 6\n";
+
+        let outcome = outcome(&linter, content, vec![ValidationType::All]);
+        let issues: Vec<(ValidationIssueType, Option<usize>)> =
+            outcome.issues.iter().map(|issue| (issue.issue_type, issue.line)).collect();
+        assert_eq!(issues, vec![
+            (ValidationIssueType::InvalidFrontmatter, Some(3)),
+            (ValidationIssueType::MarkdownFormatting, Some(6)),
+        ]);
+        assert!(outcome.issues[0].description.contains("Unknown lint rule 'todos'"));
+    }
+
+    #[test]
+    fn test_report_shows_suppressed_counts() {
+        let mut suppressed = std::collections::BTreeMap::new();
+        suppressed.insert("links".to_string(), 2);
+        let results = vec![
+            ValidationResult {
+                file_path: PathBuf::from("blog/a/a.md"),
+                issues: Vec::new(),
+                suppressed: suppressed.clone(),
+            },
+            ValidationResult {
+                file_path: PathBuf::from("blog/b/b.md"),
+                issues: Vec::new(),
+                suppressed,
+            },
+        ];
+
+        let report = ValidationReport::new(results, &Config::default());
+        assert_eq!(report.summary.suppressed["links"], 4);
+        assert_eq!(report.summary.suppressed_total(), 4);
+        assert!(report.to_display().contains("Suppressed issues: 4 (links: 4)"));
+        assert_eq!(serde_json::to_value(&report.summary).unwrap()["suppressed"]["links"], 4);
+    }
+
+    #[test]
+    fn test_unreadable_publication_dates_are_reported() {
+        let linter = Linter::new(RuleRegistry::builtin(), LintConfig::default()).unwrap();
+        let content = "---\ntitle: Post\npublished: January 1, 2024\nupdated: ''\n---\nBody\n";
+
+        let outcome = outcome(&linter, content, vec![ValidationType::PublishDate]);
+        let issues: Vec<(ValidationIssueType, Option<usize>, &str)> = outcome
+            .issues
+            .iter()
+            .map(|issue| (issue.issue_type, issue.line, issue.description.as_str()))
+            .collect();
+        assert_eq!(issues, vec![(
+            ValidationIssueType::InvalidPublishDate,
+            Some(3),
+            "Invalid published date 'January 1, 2024'"
+        )]);
+    }
+
+    #[test]
+    fn test_duplicate_slugs_are_their_own_validation_type() {
+        let linter = Linter::new(RuleRegistry::builtin(), LintConfig::default()).unwrap();
+        let config = Config::default();
+        let token = CancellationToken::new();
+        let mut quarantine = LinkQuarantine::default();
+        let slug_topics = BTreeMap::from([("post".to_string(), vec!["blog".to_string(), "notes".to_string()])]);
+
+        let mut duplicates = |validation_type: ValidationType| {
+            let options = options(vec![validation_type]);
+            let mut context = LintContext {
+                file_path: Path::new("content/blog/post/post.md"),
+                config: &config,
+                options: &options,
+                token: &token,
+                quarantine: &mut quarantine,
+                slug_topics: &slug_topics,
+            };
+            linter
+                .lint("---\ntitle: Post\n---\nBody\n", &mut context)
+                .unwrap()
+                .issues
+                .into_iter()
+                .filter(|issue| issue.issue_type == ValidationIssueType::DuplicateSlug)
+                .count()
+        };
+
+        assert_eq!(duplicates(ValidationType::DuplicateSlugs), 1);
+        assert_eq!(duplicates(ValidationType::Markdown), 0);
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use common_test_utils::config_for;
+    use content_validate::precommit::{check_staged_file, hook_command};
+    use content_validate::{install_pre_commit_hook, validate_staged, PreCommitRule, ValidationIssueType};
+    use git2::Repository;
+    use std::fs;
+    use std::path::Path;
+
+    fn stage(repo: &Repository, path: &str) {
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+    }
+
+    #[test]
+    fn test_check_staged_file_runs_selected_rules() {
+        let content = "---\ntitle: \"\"\npublish_at: tomorrow\n---\n\n# Title\n\n### Skipped\n\n```rust\nfn main() {}\n";
+        let path = Path::new("content/blog/Bad_Slug/index.md");
+
+        let issues = check_staged_file(path, content, &PreCommitRule::ALL, &[]);
+        let found: Vec<(&ValidationIssueType, &str, Option<usize>)> = issues
+            .iter()
+            .map(|issue| (&issue.issue_type, issue.description.as_str(), issue.line))
+            .collect();
+        assert_eq!(found, vec![
+            (&ValidationIssueType::InvalidFrontmatter, "Missing title", None),
+            (
+                &ValidationIssueType::InvalidSlug,
+                "Invalid slug 'Bad_Slug': Slug can only contain lowercase letters, numbers, and hyphens",
+                None,
+            ),
+            (&ValidationIssueType::MarkdownFormatting, "Heading level 3 follows level 1", Some(8)),
+            (&ValidationIssueType::MarkdownFormatting, "Code block is never closed", Some(10)),
+        ]);
+
+        let issues = check_staged_file(path, content, &[PreCommitRule::Slug], &[]);
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_staged_checks_the_staged_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let repo = Repository::init(&root).unwrap();
+
+        fs::create_dir_all(root.join("content/blog/good")).unwrap();
+        fs::create_dir_all(root.join("content/blog/unstaged")).unwrap();
+        fs::write(root.join("content/blog/good/good.md"), "No frontmatter\n").unwrap();
+        fs::write(root.join("content/blog/good/chart.png"), "png").unwrap();
+        fs::write(root.join("content/blog/unstaged/unstaged.md"), "No frontmatter\n").unwrap();
+        stage(&repo, "content/blog/good/good.md");
+        stage(&repo, "content/blog/good/chart.png");
+
+        // Fixed in the working tree, but the broken version is what would be committed
+        fs::write(root.join("content/blog/good/good.md"), "---\ntitle: Good\n---\n\nFixed\n").unwrap();
+
+        let results = validate_staged(&config_for(&root.join("content"), &["blog"]), &PreCommitRule::ALL).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_path, root.join("content/blog/good/good.md"));
+        assert_eq!(results[0].issues.len(), 1);
+        assert_eq!(results[0].issues[0].issue_type, ValidationIssueType::InvalidFrontmatter);
+    }
+
+    #[test]
+    fn test_install_pre_commit_hook() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let hook = repo.path().join("hooks/pre-commit");
+
+        let path = install_pre_commit_hook(temp_dir.path(), &[PreCommitRule::Frontmatter], false).unwrap();
+        assert_eq!(path, hook);
+        let script = fs::read_to_string(&hook).unwrap();
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("exec content-validate --staged --rules frontmatter"));
+
+        // Reinstalling replaces our own hook, but not someone else's
+        install_pre_commit_hook(temp_dir.path(), &PreCommitRule::ALL, false).unwrap();
+        assert!(fs::read_to_string(&hook).unwrap().contains(&hook_command(&PreCommitRule::ALL)));
+
+        fs::write(&hook, "#!/bin/sh\nmake lint\n").unwrap();
+        assert!(install_pre_commit_hook(temp_dir.path(), &PreCommitRule::ALL, false).is_err());
+        install_pre_commit_hook(temp_dir.path(), &PreCommitRule::ALL, true).unwrap();
+        assert!(fs::read_to_string(&hook).unwrap().contains("--rules frontmatter,slug,markdown"));
+    }
+}
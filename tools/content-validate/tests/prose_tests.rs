@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use common_models::Config;
+    use common_traits::CancellationToken;
+    use content_validate::prose::BannedPhrasesRule;
+    use content_validate::{
+        find_banned_phrases, find_cliches, find_conjunction_starts, find_repeated_words, find_weasel_words,
+        load_banned_phrases, ArchiveFallback, LinkQuarantine, LintContext, LintRule, Severity, ValidationIssue,
+        ValidationIssueType, ValidationOptions, ValidationType, STYLES_DIR,
+    };
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    const CONTENT: &str = "---\ntitle: Very Simply\n---\n\n\
+                           This is very good and, at the end of\n\
+                           the day, it works.\n\n\
+                           ```\nvery basically\n```\n\n\
+                           Use `really` with [a link](https://example.com/very).\n";
+
+    fn lines(issues: &[ValidationIssue]) -> Vec<usize> {
+        issues.iter().map(|issue| issue.line.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_weasel_words_and_cliches_skip_everything_but_prose() {
+        let weasel = find_weasel_words(CONTENT);
+        assert_eq!(lines(&weasel), vec![5]);
+        assert_eq!(weasel[0].description, "Weasel word: 'very'");
+        assert_eq!(weasel[0].severity, Severity::Info);
+
+        // A phrase can run over a line break
+        let cliches = find_cliches(CONTENT);
+        assert_eq!(lines(&cliches), vec![5]);
+        assert_eq!(cliches[0].description, "Cliché: 'at the end of the day'");
+    }
+
+    #[test]
+    fn test_repeated_words() {
+        let content = "---\ntitle: Repeats\n---\n\
+                       It was the the best.\n\
+                       Ends with and\n\
+                       and starts the next. We had `had` had 2 2 days.\n";
+
+        let issues = find_repeated_words(content);
+        assert_eq!(lines(&issues), vec![4, 6]);
+        assert_eq!(issues[0].description, "Repeated word: 'the the'");
+        assert_eq!(issues[0].issue_type, ValidationIssueType::RepeatedWord);
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_conjunction_starts_are_reported_when_frequent() {
+        let content = "---\ntitle: Starts\n---\nAnd one. But two.\n\n- So three\n- Four here.\n";
+        let issue = find_conjunction_starts(content).unwrap();
+        assert_eq!(issue.line, Some(4));
+        assert_eq!(issue.description, "3 of 4 sentences (75%) start with a conjunction, on lines 4, 6");
+
+        // Too few to matter
+        assert!(find_conjunction_starts("And one. But two. Three.\n").is_none());
+
+        // Rare enough in a longer article
+        let long = format!("{}And x. But y. So z.\n", "A plain sentence. ".repeat(30));
+        assert!(find_conjunction_starts(&long).is_none());
+    }
+
+    #[test]
+    fn test_banned_phrases_are_loaded_from_style_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_banned_phrases(&dir.path().join("missing")).unwrap().is_empty());
+
+        std::fs::write(dir.path().join("house.yaml"), "- going forward\n- phrase: utilize\n  suggestion: use\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "- ignored\n").unwrap();
+        let phrases = load_banned_phrases(dir.path()).unwrap();
+        let listed: Vec<(&str, &str)> = phrases.iter().map(|phrase| (phrase.style.as_str(), phrase.phrase.as_str())).collect();
+        assert_eq!(listed, vec![("house", "going forward"), ("house", "utilize")]);
+
+        let content = "---\ntitle: Banned\n---\nWe utilize tools.\nGoing\nforward, less.\n";
+        let issues = find_banned_phrases(content, &phrases);
+        assert_eq!(lines(&issues), vec![4, 5]);
+        assert_eq!(issues[0].suggested_fix.as_deref(), Some("Use 'use' instead"));
+        assert_eq!(issues[1].description, "'Going forward' is banned by the house style");
+        assert_eq!(issues[1].suggested_fix.as_deref(), Some("Rephrase without 'Going forward'"));
+
+        std::fs::write(dir.path().join("broken.yml"), "phrase: [").unwrap();
+        assert!(load_banned_phrases(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_banned_phrases_rule_reads_the_styles_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(STYLES_DIR)).unwrap();
+        std::fs::write(dir.path().join(STYLES_DIR).join("house.yaml"), "- synergy\n").unwrap();
+
+        let mut config = Config::default();
+        config.content.base_dir = dir.path().to_string_lossy().to_string();
+        let options = ValidationOptions {
+            article_slug: None,
+            topic: None,
+            validation_types: vec![ValidationType::All],
+            check_external_links: false,
+            timeout: None,
+            dictionary_path: None,
+            include_drafts: false,
+            images_dir: None,
+            changed_since: None,
+            archive_fallback: ArchiveFallback::Off,
+        };
+        let token = CancellationToken::new();
+        let mut quarantine = LinkQuarantine::default();
+        let slug_topics = BTreeMap::new();
+        let mut context = LintContext {
+            file_path: Path::new("blog/post/post.md"),
+            config: &config,
+            options: &options,
+            token: &token,
+            quarantine: &mut quarantine,
+            slug_topics: &slug_topics,
+        };
+
+        let rule = BannedPhrasesRule::default();
+        let issues = rule.check("Pure synergy.\n", &mut context).unwrap();
+        assert_eq!(lines(&issues), vec![1]);
+        assert_eq!(issues[0].issue_type, ValidationIssueType::BannedPhrase);
+
+        // The styles are read once, when the rule first runs
+        std::fs::write(dir.path().join(STYLES_DIR).join("house.yaml"), "- purely\n").unwrap();
+        let issues = rule.check("Pure synergy.\n", &mut context).unwrap();
+        assert_eq!(lines(&issues), vec![1]);
+        assert!(BannedPhrasesRule::default().check("Pure synergy.\n", &mut context).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use common_models::{Config, TopicConfig};
+    use content_validate::{audit_article, seo_report, Severity, SeverityOverrides, ValidationIssueType};
+
+    const DESCRIPTION: &str = "A walk through how static site generators turn markdown into fast, cacheable pages.";
+
+    fn issue_types(content: &str) -> Vec<ValidationIssueType> {
+        audit_article(content).issues.iter().map(|issue| issue.issue_type).collect()
+    }
+
+    #[test]
+    fn test_well_formed_article_scores_full_marks() {
+        let content = format!(
+            "---\ntitle: How static site generators build pages\ndescription: {}\ntags:\n  - static site\n---\n\n\
+             A static site is built once and served as files.\n\n![Build pipeline](pipeline.png)\n\n\
+             See [the deploy guide](/blog/deploy) and [the spec](https://example.com).\n",
+            DESCRIPTION
+        );
+        let audit = audit_article(&content);
+        assert!(audit.issues.is_empty(), "{:?}", audit.issues);
+        assert_eq!(audit.score, 100);
+    }
+
+    #[test]
+    fn test_each_check_reports_an_issue() {
+        let content = "---\ntitle: Short\nkeyword: caching\n---\n\n# One\n\nNothing relevant here.\n\n\
+                       # Two\n\n![](a.png) ![Described](b.png)\n\n[Top](#one) [Mail](mailto:me@example.com)\n";
+        let audit = audit_article(content);
+        assert_eq!(issue_types(content), vec![
+            ValidationIssueType::TitleLength,
+            ValidationIssueType::MetaDescription,
+            ValidationIssueType::MultipleH1,
+            ValidationIssueType::MissingKeyword,
+            ValidationIssueType::LowAltCoverage,
+            ValidationIssueType::FewInternalLinks,
+        ]);
+        // Half the alt text weight is lost, for one of two images
+        assert_eq!(audit.score, 8);
+
+        let lines: Vec<Option<usize>> = audit.issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, vec![Some(2), None, Some(10), None, Some(12), None]);
+        assert_eq!(audit.issues[3].description, "Keyword \"caching\" not in the title or first paragraph");
+    }
+
+    #[test]
+    fn test_description_length_and_partial_keyword_match() {
+        let content = "---\ntitle: Caching static pages at the edge for speed\ndescription: Too short.\nkeyword: caching\n---\n\n\
+                       Edge servers keep copies close to readers. [More](/blog/edge)\n";
+        let audit = audit_article(content);
+        assert_eq!(issue_types(content), vec![ValidationIssueType::MetaDescription, ValidationIssueType::MissingKeyword]);
+        assert_eq!(audit.issues[0].description, "Meta description is 10 characters long");
+        assert_eq!(audit.issues[0].line, Some(3));
+        assert_eq!(audit.issues[1].severity, Severity::Info);
+        assert_eq!(audit.score, 73);
+    }
+
+    #[test]
+    fn test_seo_report_skips_drafts_and_sorts_by_score() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.content.base_dir = temp_dir.path().to_string_lossy().into_owned();
+        config.content.topics.clear();
+        config.content.topics.insert("blog".to_string(), TopicConfig {
+            name: "Blog".to_string(),
+            description: String::new(),
+            directory: "blog".to_string(),
+        });
+
+        let articles = [
+            ("good", format!("---\ntitle: How static site generators build pages\ndescription: {}\n---\n\nText. [Link](/blog/weak)\n", DESCRIPTION)),
+            ("weak", "---\ntitle: Weak\n---\n\nText.\n".to_string()),
+            ("draft", "---\ntitle: Draft\ndraft: true\n---\n\nText.\n".to_string()),
+        ];
+        for (slug, content) in &articles {
+            let article_dir = temp_dir.path().join("blog").join(slug);
+            std::fs::create_dir_all(&article_dir).unwrap();
+            std::fs::write(article_dir.join(format!("{}.md", slug)), content).unwrap();
+        }
+
+        let mut overrides = SeverityOverrides::default();
+        overrides.set(ValidationIssueType::TitleLength, Severity::Error);
+        let report = seo_report(&config, None, &overrides).unwrap();
+
+        let scores: Vec<(&str, u32)> = report.articles.iter().map(|article| (article.article.as_str(), article.score)).collect();
+        assert_eq!(scores, vec![("weak", 45), ("good", 100)]);
+        assert_eq!(report.average_score, 73);
+        assert_eq!(report.articles[0].issues[0].severity, Severity::Error);
+        assert_eq!(report.issues_at_least(Severity::Error), 1);
+        assert_eq!(report.issues_at_least(Severity::Info), 3);
+    }
+
+    #[test]
+    fn test_seo_report_reads_articles_through_the_repository() {
+        let repository = common_test_utils::InMemoryContentRepository::new()
+            .with_article("blog", "weak", "---\ntitle: Weak\n---\n\nText.\n")
+            .with_article("notes", "draft", "---\ntitle: Draft\ndraft: true\n---\n\nText.\n");
+
+        let report = content_validate::seo_report_in(&repository, None, &SeverityOverrides::default()).unwrap();
+        let scores: Vec<(&str, u32)> = report.articles.iter().map(|article| (article.article.as_str(), article.score)).collect();
+        assert_eq!(scores, vec![("weak", 45)]);
+        assert_eq!(report.articles[0].file_path, std::path::Path::new("blog/weak/index.md"));
+
+        let report = content_validate::seo_report_in(&repository, Some("notes"), &SeverityOverrides::default()).unwrap();
+        assert!(report.articles.is_empty());
+    }
+}
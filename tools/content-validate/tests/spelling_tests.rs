@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use common_models::{Config, TopicConfig};
+    use content_validate::{
+        article_language, detect_language, find_misspellings, load_dictionary, topic_of, Dictionary, ValidationIssueType,
+        DICTIONARIES_DIR,
+    };
+    use std::path::Path;
+
+    const ENGLISH: &str = "The cat is on the mat and it is happy with this.";
+    const GERMAN: &str = "Der Hund ist nicht das Problem, und die Katze ist mit ein Ball.";
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(detect_language(ENGLISH), Some("en"));
+        assert_eq!(detect_language(GERMAN), Some("de"));
+        assert_eq!(detect_language("Short text."), None);
+    }
+
+    #[test]
+    fn test_article_language() {
+        let mut config = Config::default();
+        let path = Path::new("content/blog/post/post.de.md");
+
+        // Frontmatter wins over everything else
+        let content = format!("---\ntitle: Post\nlang: fr\n---\n{}\n", GERMAN);
+        assert_eq!(article_language(path, &content, &config), "fr");
+
+        // Without i18n the file name says nothing, so the text is used
+        assert_eq!(article_language(path, &format!("{}\n", GERMAN), &config), "de");
+        assert_eq!(article_language(path, "Short text.\n", &config), "en");
+
+        config.i18n.locales = vec!["en".to_string(), "de".to_string()];
+        assert_eq!(article_language(path, "Short text.\n", &config), "de");
+
+        config.i18n.default_locale = "nl".to_string();
+        assert_eq!(article_language(Path::new("content/blog/post/post.md"), "Short text.\n", &config), "nl");
+    }
+
+    #[test]
+    fn test_dictionaries_are_merged_per_language_and_topic() {
+        let dir = tempfile::tempdir().unwrap();
+        let dictionaries = dir.path().join(DICTIONARIES_DIR);
+        std::fs::create_dir_all(dictionaries.join("topics")).unwrap();
+        std::fs::write(dictionaries.join("topics").join("blog.txt"), "# Projects\nKubernetes\n").unwrap();
+        std::fs::write(dictionaries.join("en.txt"), "wordz\n").unwrap();
+
+        let mut config = Config::default();
+        config.content.base_dir = dir.path().to_string_lossy().to_string();
+
+        let english = load_dictionary(&config, "en-GB", Some("blog"), None).unwrap().unwrap();
+        assert!(english.contains("the"));
+        assert!(english.contains("People's"));
+        assert!(english.contains("kubernetes"));
+        assert!(english.contains("wordz"));
+        assert!(!english.contains("projects"));
+
+        // No dictionary for the language, so it isn't checked
+        assert!(load_dictionary(&config, "fr", Some("blog"), None).unwrap().is_none());
+
+        std::fs::write(dictionaries.join("fr.txt"), "bonjour\n").unwrap();
+        let french = load_dictionary(&config, "fr", Some("blog"), None).unwrap().unwrap();
+        assert!(french.contains("Bonjour"));
+        assert!(french.contains("Kubernetes"));
+        assert!(!french.contains("the"));
+
+        assert!(load_dictionary(&config, "en", None, Some(&dir.path().join("missing.txt"))).is_err());
+    }
+
+    #[test]
+    fn test_builtin_dictionary_is_shared_and_extended_per_article() {
+        let builtin = Dictionary::builtin("en").unwrap();
+        assert!(!builtin.is_empty());
+
+        // Words added to one article's dictionary don't leak into the next
+        let mut extended = Dictionary::builtin("en-US").unwrap();
+        extended.add_words("the\nwordz\n");
+        assert_eq!(extended.len(), builtin.len() + 1);
+        assert!(extended.contains("wordz"));
+        assert!(!Dictionary::builtin("en").unwrap().contains("wordz"));
+        assert!(Dictionary::builtin("de").is_none());
+    }
+
+    #[test]
+    fn test_topic_of() {
+        let mut config = Config::default();
+        config.content.topics.insert(
+            "blog".to_string(),
+            TopicConfig {
+                name: "Blog".to_string(),
+                description: "Posts".to_string(),
+                directory: "posts".to_string(),
+            },
+        );
+
+        assert_eq!(topic_of(Path::new("content/posts/hello/hello.md"), &config), Some("blog".to_string()));
+        assert_eq!(topic_of(Path::new("content/notes/hello/hello.md"), &config), None);
+    }
+
+    #[test]
+    fn test_find_misspellings_reports_each_word_once() {
+        let content = "---\ntitle: Spelling\n---\n\
+                       This is the best site for people.\n\
+                       Teh news is good. Teh GitHub NASA `fooo` page.\n\
+                       Add wordz here.\n";
+
+        let issues = find_misspellings(content, &Dictionary::builtin("en").unwrap());
+        let found: Vec<(usize, &str)> =
+            issues.iter().map(|issue| (issue.line.unwrap(), issue.description.as_str())).collect();
+        assert_eq!(found, vec![(5, "Unknown word: 'Teh' (2 times)"), (6, "Unknown word: 'wordz'")]);
+        assert_eq!(issues[0].issue_type, ValidationIssueType::Misspelling);
+    }
+}
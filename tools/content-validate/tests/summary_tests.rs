@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use common_test_utils::config_for;
+    use content_validate::{
+        load_severity_overrides, CategoryCounts, Severity, SeverityCounts, SeverityOverrides, ValidationIssue,
+        ValidationIssueType, ValidationReport, ValidationResult, ValidationSummary,
+    };
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn issue(issue_type: ValidationIssueType) -> ValidationIssue {
+        ValidationIssue {
+            issue_type,
+            severity: issue_type.default_severity(),
+            line: None,
+            column: None,
+            description: format!("{:?}", issue_type),
+            suggested_fix: None,
+        }
+    }
+
+    #[test]
+    fn test_severity_overrides_replace_defaults() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("severities.yaml");
+        assert_eq!(load_severity_overrides(&path).unwrap(), SeverityOverrides::default());
+
+        fs::write(&path, "missing_internal_link: error\nunused_asset: warning\n").unwrap();
+        let overrides = load_severity_overrides(&path).unwrap();
+        assert_eq!(overrides.severity(ValidationIssueType::MissingInternalLink), Severity::Error);
+        assert_eq!(overrides.severity(ValidationIssueType::BrokenLink), Severity::Error);
+        assert_eq!(overrides.severity(ValidationIssueType::MarkdownFormatting), Severity::Warning);
+
+        let mut issues = vec![
+            issue(ValidationIssueType::MissingInternalLink),
+            issue(ValidationIssueType::UnknownComponent),
+        ];
+        overrides.apply(&mut issues);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[1].severity, Severity::Warning);
+
+        fs::write(&path, "broken_link: fatal\n").unwrap();
+        assert!(load_severity_overrides(&path).is_err());
+    }
+
+    #[test]
+    fn test_summary_rolls_up_by_file_and_topic() {
+        let base_dir = PathBuf::from("/content");
+        let results = vec![
+            ValidationResult {
+                file_path: base_dir.join("blog/first/first.md"),
+                issues: vec![
+                    issue(ValidationIssueType::BrokenLink),
+                    issue(ValidationIssueType::MarkdownFormatting),
+                ],
+                suppressed: Default::default(),
+            },
+            ValidationResult {
+                file_path: base_dir.join("blog/second/second.md"),
+                issues: vec![issue(ValidationIssueType::UnusedAsset)],
+                suppressed: Default::default(),
+            },
+            ValidationResult {
+                file_path: base_dir.join("notes/clean/clean.md"),
+                issues: Vec::new(),
+                suppressed: Default::default(),
+            },
+        ];
+
+        let summary = ValidationSummary::from_results(&results, &config_for(&base_dir, &["blog", "notes"]));
+        assert_eq!(summary.files_checked, 3);
+        assert_eq!(summary.totals, SeverityCounts { errors: 1, warnings: 1, info: 1 });
+        assert_eq!(summary.files.len(), 2);
+        assert_eq!(summary.files[&base_dir.join("blog/second/second.md")].info, 1);
+        assert_eq!(summary.topics.keys().collect::<Vec<_>>(), vec!["blog"]);
+        assert_eq!(summary.topics["blog"].total(), 3);
+
+        assert!(summary.has_errors());
+        assert_eq!(summary.exit_code(Severity::Error), 1);
+
+        let quiet = ValidationSummary::from_results(&results[1..], &config_for(&base_dir, &["blog", "notes"]));
+        assert_eq!(quiet.exit_code(Severity::Error), 0);
+        assert_eq!(quiet.exit_code(Severity::Warning), 0);
+        assert_eq!(quiet.exit_code(Severity::Info), 1);
+    }
+
+    #[test]
+    fn test_report_serializes_files_with_issues() {
+        let base_dir = PathBuf::from("/content");
+        let results = vec![
+            ValidationResult {
+                file_path: base_dir.join("blog/first/first.md"),
+                issues: vec![
+                    issue(ValidationIssueType::BrokenLink),
+                    issue(ValidationIssueType::MissingImage),
+                ],
+                suppressed: Default::default(),
+            },
+            ValidationResult {
+                file_path: base_dir.join("notes/clean/clean.md"),
+                issues: Vec::new(),
+                suppressed: Default::default(),
+            },
+        ];
+
+        let report = ValidationReport::new(results, &config_for(&base_dir, &["blog", "notes"]));
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.categories, CategoryCounts { links: 1, markdown: 0, assets: 1, accessibility: 0, seo: 0, style: 0 });
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["summary"]["files_checked"], 2);
+        assert_eq!(json["summary"]["totals"]["errors"], 2);
+        assert_eq!(json["results"][0]["issues"][0]["issue_type"], "broken_link");
+        assert_eq!(json["results"][0]["issues"][0]["severity"], "error");
+        assert!(json.get("verbose").is_none());
+    }
+}
@@ -1,7 +1,19 @@
 #[cfg(test)]
 mod tests {
     use content_validate::{
+        alt_text_report,
+        extract_image_references,
         extract_links,
+        find_missing_alt_text,
+        slug_topics,
+        slug_topics_with_fs,
+        suggest_alt_text,
+        validate_canonical,
+        validate_alt_text,
+        validate_assets,
+        validate_components,
+        validate_slug_uniqueness,
+        ValidationIssueType,
         LocalLinkKind,
         ValidationOptions,
         ValidationType,
@@ -28,6 +40,7 @@ mod tests {
 
         assert_eq!(links[0].url(), "https://example.com");
         assert_eq!(*links[0].kind(), LocalLinkKind::External);
+        assert_eq!((links[0].line(), links[0].column()), (Some(1), Some(11)));
 
         assert_eq!(links[1].url(), "/docs/example");
         assert_eq!(*links[1].kind(), LocalLinkKind::Internal);
@@ -46,6 +59,9 @@ mod tests {
             timeout: None,
             dictionary_path: None,
             include_drafts: false,
+            images_dir: None,
+            changed_since: None,
+            archive_fallback: Default::default(),
         };
 
         assert_eq!(options.article_slug, None);
@@ -63,6 +79,9 @@ mod tests {
             timeout: None,
             dictionary_path: None,
             include_drafts: false,
+            images_dir: None,
+            changed_since: None,
+            archive_fallback: Default::default(),
         };
 
         options.validation_types.push(ValidationType::Links);
@@ -72,4 +91,272 @@ mod tests {
         assert_eq!(options.validation_types[0], ValidationType::Links);
         assert_eq!(options.validation_types[1], ValidationType::Markdown);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_validate_components_reports_unknown_components() {
+        let content = "<YouTube id=\"abc\" />\n\n<Aside>\nText\n<Tweet id=\"1\" />\n</Aside>\n";
+        let mut issues = Vec::new();
+
+        validate_components(content, &["YouTube"], &mut issues);
+
+        let reported: Vec<(String, Option<usize>)> = issues
+            .iter()
+            .map(|issue| (issue.description.clone(), issue.line))
+            .collect();
+        assert_eq!(reported, vec![
+            ("Unknown component <Aside>".to_string(), Some(3)),
+            ("Unknown component <Tweet>".to_string(), Some(5)),
+        ]);
+        assert!(issues.iter().all(|issue| issue.issue_type == ValidationIssueType::UnknownComponent));
+    }
+
+    #[test]
+    fn test_validate_slug_uniqueness_reports_slugs_shared_across_topics() {
+        use common_models::{Config, TopicConfig};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.content.base_dir = temp_dir.path().to_string_lossy().into_owned();
+        for topic in ["blog", "notes"] {
+            config.content.topics.insert(topic.to_string(), TopicConfig {
+                name: topic.to_string(),
+                description: String::new(),
+                directory: topic.to_string(),
+            });
+        }
+        for dir in ["blog/shared", "notes/shared", "blog/unique"] {
+            std::fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+        }
+
+        let slugs = slug_topics(&config);
+        assert_eq!(slugs["shared"], vec!["blog".to_string(), "notes".to_string()]);
+        assert_eq!(slugs["unique"], vec!["blog".to_string()]);
+
+        let mut issues = Vec::new();
+        validate_slug_uniqueness(&temp_dir.path().join("blog/unique/unique.md"), &slugs, &mut issues);
+        assert!(issues.is_empty());
+
+        validate_slug_uniqueness(&temp_dir.path().join("notes/shared/shared.md"), &slugs, &mut issues);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, ValidationIssueType::DuplicateSlug);
+        assert!(issues[0].description.contains("blog, notes"));
+    }
+
+    #[test]
+    fn test_slug_topics_with_mock_filesystem() {
+        use common_models::{Config, TopicConfig};
+        use common_test_utils::mocks::MockFileSystem;
+        use common_errors::WritingError;
+        use std::path::{Path, PathBuf};
+
+        let mut config = Config::default();
+        config.content.base_dir = "content".to_string();
+        for topic in ["blog", "notes"] {
+            config.content.topics.insert(topic.to_string(), TopicConfig {
+                name: topic.to_string(),
+                description: String::new(),
+                directory: topic.to_string(),
+            });
+        }
+
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs.expect_list_dirs()
+            .returning(|path| {
+                if path == Path::new("content/blog") {
+                    Ok(vec![PathBuf::from("content/blog/shared"), PathBuf::from("content/blog/unique")])
+                } else {
+                    // A missing topic directory is skipped
+                    Err(WritingError::directory_not_found(path))
+                }
+            });
+
+        let slugs = slug_topics_with_fs(&mock_fs, &config);
+        assert_eq!(slugs.len(), 2);
+        assert_eq!(slugs["shared"], vec!["blog".to_string()]);
+        assert_eq!(slugs["unique"], vec!["blog".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_image_references_skips_remote_images() {
+        let content = "---\ntitle: Post\nfeatured_image: hero.png\n---\n\n![Chart](chart.png)\n\n<img src=\"photos/cat.jpg\" alt=\"Cat\">\n\n![Remote](https://example.com/a.png) ![Site](/favicon.png) ![Built](/images/blog/post/post-large.jpg)\n";
+
+        let references: Vec<(String, Option<usize>)> = extract_image_references(content)
+            .into_iter()
+            .map(|reference| (reference.url, reference.line))
+            .collect();
+        assert_eq!(references, vec![
+            ("hero.png".to_string(), Some(3)),
+            ("chart.png".to_string(), Some(6)),
+            ("photos/cat.jpg".to_string(), Some(8)),
+            ("/images/blog/post/post-large.jpg".to_string(), Some(10)),
+        ]);
+    }
+
+    #[test]
+    fn test_validate_assets_reports_missing_and_unused_images() {
+        use common_models::Config;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let content_dir = temp_dir.path().join("content");
+        let images_dir = temp_dir.path().join("build/images");
+        let article_dir = content_dir.join("blog/post");
+        std::fs::create_dir_all(article_dir.join("photos")).unwrap();
+        std::fs::create_dir_all(images_dir.join("blog/post")).unwrap();
+        for file in ["index.jpg", "chart.png", "photos/old.jpg", "notes.txt"] {
+            std::fs::write(article_dir.join(file), "").unwrap();
+        }
+        std::fs::write(images_dir.join("blog/post/post-large.jpg"), "").unwrap();
+
+        let mut config = Config::default();
+        config.content.base_dir = content_dir.to_string_lossy().into_owned();
+        let content = "# Post\n\n![Chart](./chart.png)\n![Large](post-large.jpg)\n![Gone](gone.png)\n![Built](/images/blog/post/post-small.jpg)\n";
+
+        let mut issues = Vec::new();
+        validate_assets(&article_dir.join("post.md"), content, &config, Some(&images_dir), &mut issues).unwrap();
+
+        let found: Vec<(&ValidationIssueType, &str, Option<usize>)> = issues
+            .iter()
+            .map(|issue| (&issue.issue_type, issue.description.as_str(), issue.line))
+            .collect();
+        assert_eq!(found, vec![
+            (&ValidationIssueType::MissingImage, "Missing image: gone.png", Some(5)),
+            (&ValidationIssueType::MissingImage, "Missing image: /images/blog/post/post-small.jpg", Some(6)),
+            (&ValidationIssueType::UnusedAsset, "Unused image: photos/old.jpg", None),
+        ]);
+
+        // Without image-build output, built images can only be found in the article directory
+        let mut issues = Vec::new();
+        validate_assets(&article_dir.join("post.md"), content, &config, None, &mut issues).unwrap();
+        let missing: Vec<&str> = issues
+            .iter()
+            .filter(|issue| issue.issue_type == ValidationIssueType::MissingImage)
+            .map(|issue| issue.description.as_str())
+            .collect();
+        assert_eq!(missing, vec!["Missing image: post-large.jpg", "Missing image: gone.png"]);
+    }
+
+    #[test]
+    fn test_find_missing_alt_text_in_markdown_and_html() {
+        let content = "---\ntitle: Post\n---\n\n![](sales-chart-2024.png) ![A cat](cat.jpg)\n\n![  ](https://example.com/IMG_1234.jpg)\n\n<img src=\"photos/dog.jpg\">\n<img src=\"divider.png\" alt=\"\">\n<img alt=\"Logo\" src=\"logo.svg\">\n\n```\n![](in-code.png)\n```\n";
+
+        let missing: Vec<(String, usize)> = find_missing_alt_text(content)
+            .into_iter()
+            .map(|image| (image.url, image.line))
+            .collect();
+        assert_eq!(missing, vec![
+            ("sales-chart-2024.png".to_string(), 5),
+            ("https://example.com/IMG_1234.jpg".to_string(), 7),
+            ("photos/dog.jpg".to_string(), 9),
+        ]);
+
+        assert_eq!(suggest_alt_text("sales-chart-2024.png").as_deref(), Some("Sales chart 2024"));
+        assert_eq!(suggest_alt_text("/images/blog/post/Team_Photo.webp?w=200").as_deref(), Some("Team photo"));
+        assert_eq!(suggest_alt_text("https://example.com/IMG_1234.jpg"), None);
+    }
+
+    #[test]
+    fn test_validate_alt_text_skips_drafts() {
+        let mut issues = Vec::new();
+        validate_alt_text("---\ntitle: Post\n---\n\n![](chart.png)\n", &mut issues);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, ValidationIssueType::MissingAltText);
+        assert_eq!(issues[0].severity, ValidationIssueType::MissingAltText.default_severity());
+        assert_eq!(issues[0].line, Some(5));
+        assert_eq!(issues[0].suggested_fix.as_deref(), Some("Describe the image, e.g. ![Chart](chart.png)"));
+
+        let mut issues = Vec::new();
+        validate_alt_text("---\ntitle: Post\ndraft: true\n---\n\n![](chart.png)\n", &mut issues);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_alt_text_report_lists_images_by_article_and_line() {
+        use common_models::{Config, TopicConfig};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.content.base_dir = temp_dir.path().to_string_lossy().into_owned();
+        for topic in ["blog", "notes"] {
+            config.content.topics.insert(topic.to_string(), TopicConfig {
+                name: topic.to_string(),
+                description: String::new(),
+                directory: topic.to_string(),
+            });
+        }
+        let articles = [
+            ("notes/first", "---\ntitle: First\n---\n\n![](a.png)\n"),
+            ("blog/second", "---\ntitle: Second\n---\n\n![Described](b.png)\n\n<img src=\"team-photo.png\">\n"),
+            ("blog/draft", "---\ntitle: Draft\ndraft: true\n---\n\n![](d.png)\n"),
+        ];
+        for (dir, content) in articles {
+            let article_dir = temp_dir.path().join(dir);
+            std::fs::create_dir_all(&article_dir).unwrap();
+            let slug = article_dir.file_name().unwrap().to_string_lossy().into_owned();
+            std::fs::write(article_dir.join(format!("{}.md", slug)), content).unwrap();
+        }
+
+        let report = alt_text_report(&config, None).unwrap();
+        assert_eq!(report.articles_checked, 2);
+        let images: Vec<(&str, &str, usize, &str)> = report
+            .images
+            .iter()
+            .map(|entry| (entry.topic.as_str(), entry.article.as_str(), entry.line, entry.image.as_str()))
+            .collect();
+        assert_eq!(images, vec![("blog", "second", 7, "team-photo.png"), ("notes", "first", 5, "a.png")]);
+        assert_eq!(report.images[0].suggestion.as_deref(), Some("Team photo"));
+
+        let report = alt_text_report(&config, Some("notes")).unwrap();
+        assert_eq!(report.images.len(), 1);
+    }
+
+    #[test]
+    fn test_alt_text_report_reads_articles_through_the_repository() {
+        let repository = common_test_utils::InMemoryContentRepository::new()
+            .with_article("blog", "first", "---\ntitle: First\n---\n\n![](a.png)\n")
+            .with_article("blog", "draft", "---\ntitle: Draft\ndraft: true\n---\n\n![](d.png)\n");
+
+        let report = content_validate::alt_text_report_in(&repository, None).unwrap();
+        assert_eq!(report.articles_checked, 1);
+        assert_eq!(report.images.len(), 1);
+        assert_eq!(report.images[0].file_path, std::path::Path::new("blog/first/index.md"));
+    }
+
+    #[test]
+    fn test_validate_canonical_flags_malformed_and_missing_targets() {
+        use common_models::{Config, TopicConfig};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.content.base_dir = temp_dir.path().to_string_lossy().into_owned();
+        config.publication.site_url = Some("https://example.com/".to_string());
+        config.content.topics.clear();
+        config.content.topics.insert("blog".to_string(), TopicConfig {
+            name: "Blog".to_string(),
+            description: String::new(),
+            directory: "posts".to_string(),
+        });
+        std::fs::create_dir_all(temp_dir.path().join("posts/hello")).unwrap();
+        let slugs = slug_topics(&config);
+
+        let check = |canonical: &str| {
+            let mut issues = Vec::new();
+            let content = format!("---\ntitle: Post\ncanonical: \"{}\"\n---\n\nBody\n", canonical);
+            validate_canonical(&content, &config, &slugs, &mut issues);
+            issues.into_iter().map(|issue| (issue.line, issue.description)).collect::<Vec<_>>()
+        };
+
+        assert!(check("/blog/hello").is_empty());
+        assert!(check("https://example.com/blog/hello/").is_empty());
+        assert!(check("https://elsewhere.dev/whatever").is_empty());
+        assert_eq!(check("htps:/example.com"), vec![(Some(3), "Malformed canonical URL: htps:/example.com".to_string())]);
+        assert_eq!(check("ftp://example.com/blog/hello"), vec![(
+            Some(3),
+            "Malformed canonical URL: ftp://example.com/blog/hello".to_string(),
+        )]);
+        assert_eq!(check("https://example.com/blog/gone"), vec![(
+            Some(3),
+            "Canonical URL doesn't point at an article: https://example.com/blog/gone".to_string(),
+        )]);
+        assert_eq!(check("/posts/hello").len(), 1);
+    }
+}
@@ -0,0 +1,613 @@
+//! Public API of each library
+//!
+//! Parses a library's module tree from its root file, following `mod`
+//! declarations to their files, then lists every path other crates can
+//! name each public item by: through `pub mod` chains, `pub use` of single
+//! items or whole modules, and `pub use module::*` globs. Re-exports of
+//! other workspace libraries are followed, so an item re-exported by a
+//! second crate gets a path in each.
+//!
+//! Items are functions, structs, enums, unions, traits, type aliases,
+//! constants, statics and `#[macro_export]` macros. Only `pub` items count;
+//! `pub(crate)` and narrower are internal, and `#[cfg(test)]` code is
+//! skipped.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use syn::{Attribute, Item, UseTree, Visibility};
+
+/// The kind of an exported item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Function,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    TypeAlias,
+    Const,
+    Static,
+    Macro,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ItemKind::Function => "fn",
+            ItemKind::Struct => "struct",
+            ItemKind::Enum => "enum",
+            ItemKind::Union => "union",
+            ItemKind::Trait => "trait",
+            ItemKind::TypeAlias => "type",
+            ItemKind::Const => "const",
+            ItemKind::Static => "static",
+            ItemKind::Macro => "macro",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A public item and every path it can be named by
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Export {
+    /// Path to the item where it is defined, such as `common_validation::slug::validate_slug`
+    pub canonical: String,
+    pub kind: ItemKind,
+    /// File the item is defined in
+    pub file: PathBuf,
+    pub line: usize,
+    /// Paths other crates can use, such as `common_validation::validate_slug`
+    pub paths: BTreeSet<String>,
+}
+
+/// Every public item in the workspace, and the paths that name them
+#[derive(Debug, Default)]
+pub struct ExportTable {
+    /// Exports by canonical path
+    pub exports: BTreeMap<String, Export>,
+    /// Canonical paths by public path
+    pub paths: HashMap<String, String>,
+    /// Files in each library's module tree, by library name
+    pub lib_files: HashMap<String, BTreeSet<PathBuf>>,
+}
+
+impl ExportTable {
+    /// Build the table for the given libraries, as `(library name, root file)` pairs
+    pub fn build(libraries: &[(String, PathBuf)]) -> Self {
+        let crates: HashMap<String, CrateModules> = libraries
+            .iter()
+            .map(|(name, root)| (name.clone(), CrateModules::parse(root)))
+            .collect();
+        let resolver = Resolver {
+            crates: &crates,
+            globs: RefCell::default(),
+            listing: RefCell::default(),
+            looking_up: RefCell::default(),
+        };
+
+        let mut table = ExportTable::default();
+        for (name, modules) in &crates {
+            table.lib_files.insert(name.clone(), modules.files.clone());
+        }
+
+        for (name, _) in libraries {
+            let mut stack = vec![(name.as_str(), 0)];
+            resolver.walk(name, 0, name, &mut stack, &mut table);
+        }
+
+        table
+    }
+
+    /// The export a public path names, or the one an associated path such as `Type::new` belongs to
+    pub fn resolve(&self, segments: &[String]) -> Option<(&str, &Export)> {
+        for len in (2..=segments.len()).rev() {
+            let path = segments[..len].join("::");
+            if let Some(canonical) = self.paths.get(&path) {
+                let (path, _) = self.paths.get_key_value(&path)?;
+                return self.exports.get(canonical).map(|export| (path.as_str(), export));
+            }
+        }
+        None
+    }
+
+    fn record(&mut self, public_path: String, item: &ItemRef) {
+        let export = self.exports.entry(item.canonical.clone()).or_insert_with(|| Export {
+            canonical: item.canonical.clone(),
+            kind: item.kind,
+            file: item.file.clone(),
+            line: item.line,
+            paths: BTreeSet::new(),
+        });
+        export.paths.insert(public_path.clone());
+        self.paths.insert(public_path, item.canonical.clone());
+    }
+}
+
+/// An item defined in a module
+#[derive(Debug, Clone)]
+struct LocalItem {
+    kind: ItemKind,
+    public: bool,
+    line: usize,
+}
+
+/// A `pub use` in a module
+#[derive(Debug, Clone)]
+struct PubUse {
+    target: Vec<String>,
+    /// Name it is re-exported as, or `None` for a glob
+    name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ModuleData {
+    path: Vec<String>,
+    file: PathBuf,
+    parent: Option<usize>,
+    items: BTreeMap<String, LocalItem>,
+    /// Child modules by name, with whether they are `pub`
+    children: BTreeMap<String, (bool, usize)>,
+    uses: Vec<PubUse>,
+}
+
+/// The module tree of one library
+#[derive(Debug, Default)]
+struct CrateModules {
+    modules: Vec<ModuleData>,
+    /// `#[macro_export]` macros, which are public at the crate root
+    macros: BTreeMap<String, (PathBuf, usize)>,
+    files: BTreeSet<PathBuf>,
+}
+
+impl CrateModules {
+    fn parse(root: &Path) -> Self {
+        let mut modules = CrateModules::default();
+        let dir = root.parent().unwrap_or(Path::new("")).to_path_buf();
+        if let Some(items) = parse_file(root) {
+            modules.files.insert(root.to_path_buf());
+            modules.add_module(Vec::new(), root, &dir, None, &items);
+        }
+        modules
+    }
+
+    fn add_module(&mut self, path: Vec<String>, file: &Path, dir: &Path, parent: Option<usize>, items: &[Item]) -> usize {
+        let index = self.modules.len();
+        self.modules.push(ModuleData {
+            path: path.clone(),
+            file: file.to_path_buf(),
+            parent,
+            items: BTreeMap::new(),
+            children: BTreeMap::new(),
+            uses: Vec::new(),
+        });
+
+        for item in items {
+            if is_cfg_test(item_attrs(item)) {
+                continue;
+            }
+
+            match item {
+                Item::Mod(module) => {
+                    let name = module.ident.to_string();
+                    let mut child_path = path.clone();
+                    child_path.push(name.clone());
+
+                    let child = match &module.content {
+                        Some((_, content)) => Some(self.add_module(child_path, file, &dir.join(&name), Some(index), content)),
+                        None => module_file(dir, &name, &module.attrs).and_then(|child_file| {
+                            let content = parse_file(&child_file)?;
+                            self.files.insert(child_file.clone());
+                            let child_dir = if child_file.file_name().is_some_and(|n| n == "mod.rs") {
+                                child_file.parent().unwrap_or(dir).to_path_buf()
+                            } else {
+                                dir.join(&name)
+                            };
+                            Some(self.add_module(child_path, &child_file, &child_dir, Some(index), &content))
+                        }),
+                    };
+                    if let Some(child) = child {
+                        let public = matches!(module.vis, Visibility::Public(_));
+                        self.modules[index].children.insert(name, (public, child));
+                    }
+                }
+                Item::Use(item_use) if matches!(item_use.vis, Visibility::Public(_)) => {
+                    let mut uses = Vec::new();
+                    flatten_use(&item_use.tree, Vec::new(), &mut uses);
+                    self.modules[index].uses.extend(uses);
+                }
+                Item::Macro(item_macro) => {
+                    let exported = item_macro.attrs.iter().any(|attr| attr.path().is_ident("macro_export"));
+                    if let (true, Some(ident)) = (exported, &item_macro.ident) {
+                        let line = ident.span().start().line;
+                        self.macros.insert(ident.to_string(), (file.to_path_buf(), line));
+                    }
+                }
+                _ => {
+                    if let Some((name, kind, vis, line)) = describe(item) {
+                        let public = matches!(vis, Visibility::Public(_));
+                        self.modules[index].items.insert(name, LocalItem { kind, public, line });
+                    }
+                }
+            }
+        }
+
+        index
+    }
+}
+
+/// A resolved path
+#[derive(Debug, Clone)]
+enum Target {
+    Item(ItemRef),
+    Module(String, usize),
+}
+
+impl Target {
+    /// The namespace the name lives in; one name can mean one thing in each
+    fn namespace(&self) -> Namespace {
+        match self {
+            Target::Module(..) => Namespace::Type,
+            Target::Item(item) => match item.kind {
+                ItemKind::Function | ItemKind::Const | ItemKind::Static => Namespace::Value,
+                ItemKind::Macro => Namespace::Macro,
+                _ => Namespace::Type,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Namespace {
+    Type,
+    Value,
+    Macro,
+}
+
+#[derive(Debug, Clone)]
+struct ItemRef {
+    canonical: String,
+    kind: ItemKind,
+    file: PathBuf,
+    line: usize,
+}
+
+/// A module, as its library name and index
+type ModuleKey = (String, usize);
+
+/// Resolves paths across libraries
+///
+/// Globs can import each other in a cycle, so each lookup and module
+/// listing notes that it is in progress, and one that comes back around to
+/// itself stops there.
+struct Resolver<'a> {
+    crates: &'a HashMap<String, CrateModules>,
+    /// Modules each module's globs import from, once resolved
+    globs: RefCell<HashMap<ModuleKey, Vec<ModuleKey>>>,
+    listing: RefCell<HashSet<ModuleKey>>,
+    looking_up: RefCell<HashSet<(String, usize, String)>>,
+}
+
+impl Resolver<'_> {
+    /// Record the public names of a module, and of the modules it makes public, under `prefix`
+    fn walk<'a>(&'a self, krate: &'a str, module: usize, prefix: &str, stack: &mut Vec<(&'a str, usize)>, table: &mut ExportTable) {
+        for ((name, _), target) in self.public_names(krate, module) {
+            let path = format!("{}::{}", prefix, name);
+            match target {
+                Target::Item(item) => table.record(path, &item),
+                Target::Module(child_crate, child) => {
+                    let Some((key, _)) = self.crates.get_key_value(&child_crate) else {
+                        continue;
+                    };
+                    if stack.contains(&(key.as_str(), child)) {
+                        continue;
+                    }
+                    stack.push((key.as_str(), child));
+                    self.walk(key, child, &path, stack, table);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    /// The names a module makes public: its `pub` items and modules, its
+    /// `pub use` names, then names from its globs that aren't taken
+    fn public_names(&self, krate: &str, module: usize) -> BTreeMap<(String, Namespace), Target> {
+        let mut names = BTreeMap::new();
+        let Some(modules) = self.crates.get(krate) else {
+            return names;
+        };
+        let data = &modules.modules[module];
+
+        for target in self.defined(krate, module, true) {
+            names.insert((self.target_name(&target), target.namespace()), target);
+        }
+
+        let key = (krate.to_string(), module);
+        if !self.listing.borrow_mut().insert(key.clone()) {
+            return names;
+        }
+
+        for pub_use in &data.uses {
+            if let Some(name) = &pub_use.name {
+                for target in self.resolve(krate, module, &pub_use.target) {
+                    names.insert((name.clone(), target.namespace()), target);
+                }
+            }
+        }
+        for (glob_crate, glob_module) in self.globs(krate, module) {
+            for (key, target) in self.public_names(&glob_crate, glob_module) {
+                names.entry(key).or_insert(target);
+            }
+        }
+
+        self.listing.borrow_mut().remove(&key);
+        names
+    }
+
+    /// Resolve a `use` path written in a module to everything its last segment names
+    fn resolve(&self, krate: &str, module: usize, segments: &[String]) -> Vec<Target> {
+        let Some(modules) = self.crates.get(krate) else {
+            return Vec::new();
+        };
+        let Some((first, rest)) = segments.split_first() else {
+            return Vec::new();
+        };
+
+        let mut current = match first.as_str() {
+            "crate" => vec![Target::Module(krate.to_string(), 0)],
+            "self" => vec![Target::Module(krate.to_string(), module)],
+            "super" => modules.modules[module].parent.map(|parent| Target::Module(krate.to_string(), parent)).into_iter().collect(),
+            name => {
+                let found = self.lookup(krate, module, name, false);
+                if found.is_empty() && self.crates.contains_key(name) {
+                    vec![Target::Module(name.to_string(), 0)]
+                } else {
+                    found
+                }
+            }
+        };
+
+        for segment in rest {
+            let Some((current_crate, current_module)) = current.iter().find_map(|target| match target {
+                Target::Module(current_crate, current_module) => Some((current_crate.clone(), *current_module)),
+                Target::Item(_) => None,
+            }) else {
+                return Vec::new();
+            };
+            current = match segment.as_str() {
+                "self" => vec![Target::Module(current_crate, current_module)],
+                "super" => self
+                    .crates
+                    .get(&current_crate)
+                    .and_then(|modules| modules.modules[current_module].parent)
+                    .map(|parent| Target::Module(current_crate.clone(), parent))
+                    .into_iter()
+                    .collect(),
+                name => self.lookup(&current_crate, current_module, name, false),
+            };
+        }
+
+        current
+    }
+
+    /// Find what a name means in a module, only counting public names when looking through a glob
+    fn lookup(&self, krate: &str, module: usize, name: &str, public_only: bool) -> Vec<Target> {
+        let Some(modules) = self.crates.get(krate) else {
+            return Vec::new();
+        };
+        let data = &modules.modules[module];
+
+        let mut found: Vec<Target> = self
+            .defined(krate, module, public_only)
+            .into_iter()
+            .filter(|target| self.target_name(target) == name)
+            .collect();
+        let key = (krate.to_string(), module, name.to_string());
+        if !self.looking_up.borrow_mut().insert(key.clone()) {
+            return found;
+        }
+
+        for pub_use in &data.uses {
+            if pub_use.name.as_deref() == Some(name) {
+                found.extend(self.resolve(krate, module, &pub_use.target));
+            }
+        }
+        for (glob_crate, glob_module) in self.globs(krate, module) {
+            for target in self.lookup(&glob_crate, glob_module, name, true) {
+                if !found.iter().any(|existing| existing.namespace() == target.namespace()) {
+                    found.push(target);
+                }
+            }
+        }
+
+        self.looking_up.borrow_mut().remove(&key);
+        found
+    }
+
+    /// The items, modules and exported macros a module defines itself
+    fn defined(&self, krate: &str, module: usize, public_only: bool) -> Vec<Target> {
+        let Some(modules) = self.crates.get(krate) else {
+            return Vec::new();
+        };
+        let data = &modules.modules[module];
+
+        let mut defined = Vec::new();
+        for (name, item) in &data.items {
+            if item.public || !public_only {
+                defined.push(Target::Item(item_ref(krate, data, name, item)));
+            }
+        }
+        for (public, child) in data.children.values() {
+            if *public || !public_only {
+                defined.push(Target::Module(krate.to_string(), *child));
+            }
+        }
+        if module == 0 {
+            for (name, (file, line)) in &modules.macros {
+                defined.push(Target::Item(ItemRef {
+                    canonical: format!("{}::{}", krate, name),
+                    kind: ItemKind::Macro,
+                    file: file.clone(),
+                    line: *line,
+                }));
+            }
+        }
+        defined
+    }
+
+    /// The modules a module's `pub use ...::*` globs import from
+    fn globs(&self, krate: &str, module: usize) -> Vec<ModuleKey> {
+        let key = (krate.to_string(), module);
+        if let Some(globs) = self.globs.borrow().get(&key) {
+            return globs.clone();
+        }
+        let Some(modules) = self.crates.get(krate) else {
+            return Vec::new();
+        };
+
+        // Globs that name each other would loop, so the module has none until they are resolved
+        self.globs.borrow_mut().insert(key.clone(), Vec::new());
+        let globs: Vec<ModuleKey> = modules.modules[module]
+            .uses
+            .iter()
+            .filter(|pub_use| pub_use.name.is_none())
+            .flat_map(|pub_use| self.resolve(krate, module, &pub_use.target))
+            .filter_map(|target| match target {
+                Target::Module(glob_crate, glob_module) => Some((glob_crate, glob_module)),
+                Target::Item(_) => None,
+            })
+            .collect();
+        self.globs.borrow_mut().insert(key, globs.clone());
+        globs
+    }
+
+    /// The name a target is defined with
+    fn target_name(&self, target: &Target) -> String {
+        match target {
+            Target::Item(item) => item.canonical.rsplit("::").next().unwrap_or_default().to_string(),
+            Target::Module(krate, module) => self
+                .crates
+                .get(krate)
+                .and_then(|modules| modules.modules[*module].path.last().cloned())
+                .unwrap_or_else(|| krate.clone()),
+        }
+    }
+}
+
+fn item_ref(krate: &str, module: &ModuleData, name: &str, item: &LocalItem) -> ItemRef {
+    let mut canonical = vec![krate.to_string()];
+    canonical.extend(module.path.iter().cloned());
+    canonical.push(name.to_string());
+    ItemRef {
+        canonical: canonical.join("::"),
+        kind: item.kind,
+        file: module.file.clone(),
+        line: item.line,
+    }
+}
+
+fn parse_file(path: &Path) -> Option<Vec<Item>> {
+    let source = fs::read_to_string(path).ok()?;
+    syn::parse_file(&source).ok().map(|file| file.items)
+}
+
+/// The file a `mod name;` declaration in a module with child directory `dir` loads
+fn module_file(dir: &Path, name: &str, attrs: &[Attribute]) -> Option<PathBuf> {
+    let custom = attrs.iter().find(|attr| attr.path().is_ident("path")).and_then(|attr| match &attr.meta {
+        syn::Meta::NameValue(meta) => match &meta.value {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(path), .. }) => Some(path.value()),
+            _ => None,
+        },
+        _ => None,
+    });
+    if let Some(custom) = custom {
+        return Some(dir.join(custom)).filter(|path| path.exists());
+    }
+
+    [dir.join(format!("{}.rs", name)), dir.join(name).join("mod.rs")]
+        .into_iter()
+        .find(|path| path.exists())
+}
+
+/// Flatten a `use` tree into the paths and names it imports
+fn flatten_use(tree: &UseTree, prefix: Vec<String>, uses: &mut Vec<PubUse>) {
+    match tree {
+        UseTree::Path(path) => {
+            let mut prefix = prefix;
+            prefix.push(path.ident.to_string());
+            flatten_use(&path.tree, prefix, uses);
+        }
+        UseTree::Name(name) if name.ident == "self" => {
+            if let Some(last) = prefix.last().cloned() {
+                uses.push(PubUse { target: prefix, name: Some(last) });
+            }
+        }
+        UseTree::Name(name) => {
+            let mut target = prefix;
+            target.push(name.ident.to_string());
+            uses.push(PubUse { target, name: Some(name.ident.to_string()) });
+        }
+        UseTree::Rename(rename) => {
+            let mut target = prefix;
+            if rename.ident != "self" {
+                target.push(rename.ident.to_string());
+            }
+            if rename.rename != "_" {
+                uses.push(PubUse { target, name: Some(rename.rename.to_string()) });
+            }
+        }
+        UseTree::Glob(_) => uses.push(PubUse { target: prefix, name: None }),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten_use(tree, prefix.clone(), uses);
+            }
+        }
+    }
+}
+
+fn describe(item: &Item) -> Option<(String, ItemKind, &Visibility, usize)> {
+    let (ident, kind, vis) = match item {
+        Item::Fn(item) => (&item.sig.ident, ItemKind::Function, &item.vis),
+        Item::Struct(item) => (&item.ident, ItemKind::Struct, &item.vis),
+        Item::Enum(item) => (&item.ident, ItemKind::Enum, &item.vis),
+        Item::Union(item) => (&item.ident, ItemKind::Union, &item.vis),
+        Item::Trait(item) => (&item.ident, ItemKind::Trait, &item.vis),
+        Item::Type(item) => (&item.ident, ItemKind::TypeAlias, &item.vis),
+        Item::Const(item) => (&item.ident, ItemKind::Const, &item.vis),
+        Item::Static(item) => (&item.ident, ItemKind::Static, &item.vis),
+        _ => return None,
+    };
+    Some((ident.to_string(), kind, vis, ident.span().start().line))
+}
+
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Const(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::Fn(item) => &item.attrs,
+        Item::Macro(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::Static(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Whether attributes include `#[cfg(test)]`
+pub(crate) fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|ident| ident == "test")
+    })
+}
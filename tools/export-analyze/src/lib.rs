@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+pub mod exports;
+pub mod usage;
+pub mod workspace;
+
+pub use exports::{Export, ExportTable, ItemKind};
+pub use usage::referenced_paths;
+pub use workspace::{load_member, load_members, Member};
+
+/// A public item no other crate uses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnusedExport {
+    pub canonical: String,
+    pub kind: ItemKind,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Paths it is exported by
+    pub paths: Vec<String>,
+}
+
+/// A public item exported by more than one path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateExport {
+    pub canonical: String,
+    pub kind: ItemKind,
+    pub paths: Vec<String>,
+    /// The paths other crates use it by
+    pub used_paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    /// Libraries analyzed
+    pub crates: Vec<String>,
+    pub files_scanned: usize,
+    /// Public items found
+    pub exports: usize,
+    pub unused: Vec<UnusedExport>,
+    pub duplicates: Vec<DuplicateExport>,
+}
+
+/// Analyze the public APIs of the workspace rooted at `root`
+///
+/// An export is used when a file outside its library's own module tree
+/// names it: another member's library, binaries, tests or examples, or its
+/// own package's binaries and tests, which see only the public API. With
+/// `ignore_tests`, uses in `tests` and `benches` directories, test files and
+/// `#[cfg(test)]` modules don't count.
+pub fn analyze_workspace(root: &Path, ignore_tests: bool) -> Result<Report> {
+    let members = load_members(root)?;
+    let libraries: Vec<(String, PathBuf)> = members
+        .iter()
+        .filter_map(|member| member.lib_root.clone().map(|lib_root| (member.lib_name.clone(), lib_root)))
+        .collect();
+    let table = ExportTable::build(&libraries);
+    let crates: BTreeSet<String> = libraries.iter().map(|(name, _)| name.clone()).collect();
+
+    // Public paths used from outside the library they start with
+    let mut used: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut files_scanned = 0;
+    for file in source_files(&members, ignore_tests) {
+        let Ok(source) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let Some(paths) = referenced_paths(&source, &crates, &table, ignore_tests) else {
+            continue;
+        };
+        files_scanned += 1;
+
+        for path in paths {
+            let in_own_library = table.lib_files.get(&path[0]).is_some_and(|files| files.contains(&file));
+            if in_own_library {
+                continue;
+            }
+            if let Some((public_path, export)) = table.resolve(&path) {
+                used.entry(export.canonical.clone()).or_default().insert(public_path.to_string());
+            }
+        }
+    }
+
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    let mut unused = Vec::new();
+    let mut duplicates = Vec::new();
+    for export in table.exports.values() {
+        let used_paths = used.get(&export.canonical);
+        if used_paths.is_none() {
+            unused.push(UnusedExport {
+                canonical: export.canonical.clone(),
+                kind: export.kind,
+                file: relative(&export.file),
+                line: export.line,
+                paths: export.paths.iter().cloned().collect(),
+            });
+        }
+        if export.paths.len() > 1 {
+            duplicates.push(DuplicateExport {
+                canonical: export.canonical.clone(),
+                kind: export.kind,
+                paths: export.paths.iter().cloned().collect(),
+                used_paths: used_paths.map(|paths| paths.iter().cloned().collect()).unwrap_or_default(),
+            });
+        }
+    }
+
+    Ok(Report {
+        crates: crates.into_iter().collect(),
+        files_scanned,
+        exports: table.exports.len(),
+        unused,
+        duplicates,
+    })
+}
+
+/// Rust files in each member, skipping `target` and hidden directories
+fn source_files(members: &[Member], ignore_tests: bool) -> Vec<PathBuf> {
+    let mut files = BTreeSet::new();
+    for member in members {
+        let walker = WalkDir::new(&member.dir).into_iter().filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            if entry.depth() == 0 {
+                return true;
+            }
+            let skipped_dir = entry.file_type().is_dir()
+                && (name == "target" || name.starts_with('.') || (ignore_tests && (name == "tests" || name == "benches")));
+            !skipped_dir
+        });
+        for entry in walker.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy();
+            let is_test_file = name == "tests.rs" || name.ends_with("_tests.rs") || name.ends_with("_test.rs");
+            if entry.file_type().is_file() && name.ends_with(".rs") && !(ignore_tests && is_test_file) {
+                files.insert(entry.into_path());
+            }
+        }
+    }
+    files.into_iter().collect()
+}
+
+pub fn output_json_report(report: &Report, path: &PathBuf) -> Result<()> {
+    let json = serde_json::to_string_pretty(report)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+pub fn output_markdown_report(report: &Report, path: &PathBuf) -> Result<()> {
+    fs::write(path, markdown_report(report))?;
+    Ok(())
+}
+
+pub fn markdown_report(report: &Report) -> String {
+    let mut markdown = String::new();
+
+    markdown.push_str("# Export Analysis Report\n\n");
+    markdown.push_str(&format!(
+        "Analyzed {} public items in {} libraries, and their uses in {} files.\n\n",
+        report.exports,
+        report.crates.len(),
+        report.files_scanned
+    ));
+
+    if !report.unused.is_empty() {
+        markdown.push_str("## Unused Exports\n\n");
+        markdown.push_str("| Item | Kind | Defined In | Exported As |\n");
+        markdown.push_str("|------|------|------------|-------------|\n");
+
+        for unused in &report.unused {
+            let paths: Vec<String> = unused.paths.iter().map(|path| format!("`{}`", path)).collect();
+            markdown.push_str(&format!(
+                "| `{}` | {} | {}:{} | {} |\n",
+                unused.canonical,
+                unused.kind,
+                unused.file.display(),
+                unused.line,
+                paths.join(", ")
+            ));
+        }
+        markdown.push('\n');
+    }
+
+    if !report.duplicates.is_empty() {
+        markdown.push_str("## Duplicate Export Paths\n\n");
+        markdown.push_str("| Item | Kind | Exported As | Used As |\n");
+        markdown.push_str("|------|------|-------------|---------|\n");
+
+        for duplicate in &report.duplicates {
+            let paths: Vec<String> = duplicate.paths.iter().map(|path| format!("`{}`", path)).collect();
+            let used: Vec<String> = duplicate.used_paths.iter().map(|path| format!("`{}`", path)).collect();
+            markdown.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                duplicate.canonical,
+                duplicate.kind,
+                paths.join(", "),
+                if used.is_empty() { "-".to_string() } else { used.join(", ") }
+            ));
+        }
+        markdown.push('\n');
+    }
+
+    if report.unused.is_empty() && report.duplicates.is_empty() {
+        markdown.push_str("No unused exports or duplicate export paths found.\n");
+    }
+
+    markdown
+}
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+use clap::Parser;
+use anyhow::Result;
+use export_analyze::{analyze_workspace, output_json_report, output_markdown_report};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Workspace directory, holding the workspace Cargo.toml
+    #[arg(short, long, default_value = ".")]
+    workspace: PathBuf,
+
+    /// Output report file
+    #[arg(short, long, default_value = "export_report.md")]
+    report: PathBuf,
+
+    /// Output JSON format, for tracking cleanup
+    #[arg(short, long)]
+    json: bool,
+
+    /// Don't count uses in tests and benchmarks
+    #[arg(long)]
+    ignore_tests: bool,
+
+    /// Exit with an error if any export is unused
+    #[arg(long)]
+    fail_on_unused: bool,
+
+    /// Enable verbose output
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let report = analyze_workspace(&args.workspace, args.ignore_tests)?;
+
+    if args.json {
+        output_json_report(&report, &args.report)?;
+    } else {
+        output_markdown_report(&report, &args.report)?;
+    }
+
+    if args.verbose {
+        println!(
+            "{} public items in {} libraries: {} unused, {} with duplicate paths",
+            report.exports,
+            report.crates.len(),
+            report.unused.len(),
+            report.duplicates.len()
+        );
+    }
+
+    if args.fail_on_unused && !report.unused.is_empty() {
+        anyhow::bail!("{} unused exports", report.unused.len());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+//! Paths a source file refers to
+//!
+//! Collects every path in a file that could name an item of a workspace
+//! library: `use` declarations, and paths in expressions, types, patterns,
+//! attributes and macro invocations. Names brought in by `use` are expanded,
+//! so after `use common_config as config;` the path `config::load_config`
+//! is reported as `common_config::load_config`. After a glob import such as
+//! `use common_validation::*`, each path is also tried under the glob's
+//! prefix, and kept if the library exports it.
+//!
+//! Macro arguments aren't parsed, so their tokens are scanned for runs of
+//! `name::name` instead.
+
+use std::collections::{BTreeSet, HashMap};
+use proc_macro2::{TokenStream, TokenTree};
+use syn::visit::{self, Visit};
+use syn::UseTree;
+
+use crate::exports::{is_cfg_test, ExportTable};
+
+/// The full paths a file refers to, such as `["common_config", "load_config"]`
+///
+/// `crates` are the workspace library names paths may start with, and
+/// `table` is used to keep only the glob-expanded paths that name an export.
+/// With `ignore_tests`, `#[cfg(test)]` modules are skipped. Returns `None`
+/// if the source can't be parsed.
+pub fn referenced_paths(
+    source: &str,
+    crates: &BTreeSet<String>,
+    table: &ExportTable,
+    ignore_tests: bool,
+) -> Option<BTreeSet<Vec<String>>> {
+    let file = syn::parse_file(source).ok()?;
+
+    let mut visitor = PathVisitor {
+        ignore_tests,
+        ..Default::default()
+    };
+    visitor.visit_file(&file);
+
+    let mut paths = BTreeSet::new();
+    for path in visitor.imports.iter().chain(&visitor.paths) {
+        let expanded = match visitor.bindings.get(&path[0]) {
+            Some(binding) if binding != path => {
+                let mut expanded = binding.clone();
+                expanded.extend(path[1..].iter().cloned());
+                expanded
+            }
+            _ => path.clone(),
+        };
+
+        if crates.contains(&expanded[0]) {
+            paths.insert(expanded);
+            continue;
+        }
+
+        for glob in &visitor.globs {
+            let mut candidate = glob.clone();
+            candidate.extend(path.iter().cloned());
+            if table.resolve(&candidate).is_some() {
+                paths.insert(candidate);
+            }
+        }
+    }
+
+    Some(paths)
+}
+
+#[derive(Default)]
+struct PathVisitor {
+    ignore_tests: bool,
+    /// Full paths of `use` declarations
+    imports: BTreeSet<Vec<String>>,
+    /// Names bound by `use` declarations, and the paths they stand for
+    bindings: HashMap<String, Vec<String>>,
+    /// Prefixes of glob imports
+    globs: BTreeSet<Vec<String>>,
+    /// Paths used in code
+    paths: BTreeSet<Vec<String>>,
+}
+
+impl PathVisitor {
+    fn add_use(&mut self, tree: &UseTree, prefix: Vec<String>) {
+        match tree {
+            UseTree::Path(path) => {
+                let mut prefix = prefix;
+                prefix.push(path.ident.to_string());
+                self.add_use(&path.tree, prefix);
+            }
+            UseTree::Name(name) if name.ident == "self" => {
+                if let Some(last) = prefix.last().cloned() {
+                    self.bindings.insert(last, prefix.clone());
+                    self.imports.insert(prefix);
+                }
+            }
+            UseTree::Name(name) => {
+                let mut path = prefix;
+                path.push(name.ident.to_string());
+                self.bindings.insert(name.ident.to_string(), path.clone());
+                self.imports.insert(path);
+            }
+            UseTree::Rename(rename) => {
+                let mut path = prefix;
+                if rename.ident != "self" {
+                    path.push(rename.ident.to_string());
+                }
+                if rename.rename != "_" {
+                    self.bindings.insert(rename.rename.to_string(), path.clone());
+                }
+                self.imports.insert(path);
+            }
+            UseTree::Glob(_) => {
+                if !prefix.is_empty() {
+                    self.globs.insert(prefix);
+                }
+            }
+            UseTree::Group(group) => {
+                for tree in &group.items {
+                    self.add_use(tree, prefix.clone());
+                }
+            }
+        }
+    }
+
+    fn add_tokens(&mut self, tokens: TokenStream) {
+        let mut current: Vec<String> = Vec::new();
+        let mut colons = 0;
+
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) if current.is_empty() || colons == 2 => {
+                    current.push(ident.to_string());
+                    colons = 0;
+                }
+                TokenTree::Punct(punct) if punct.as_char() == ':' && !current.is_empty() && colons < 2 => {
+                    colons += 1;
+                }
+                other => {
+                    self.take_token_path(&mut current);
+                    colons = 0;
+                    match other {
+                        TokenTree::Group(group) => self.add_tokens(group.stream()),
+                        TokenTree::Ident(ident) => current.push(ident.to_string()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        self.take_token_path(&mut current);
+    }
+
+    fn take_token_path(&mut self, current: &mut Vec<String>) {
+        if !current.is_empty() {
+            self.paths.insert(std::mem::take(current));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PathVisitor {
+    fn visit_item_use(&mut self, node: &'ast syn::ItemUse) {
+        self.add_use(&node.tree, Vec::new());
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if !(self.ignore_tests && is_cfg_test(&node.attrs)) {
+            visit::visit_item_mod(self, node);
+        }
+    }
+
+    fn visit_path(&mut self, node: &'ast syn::Path) {
+        let segments: Vec<String> = node.segments.iter().map(|segment| segment.ident.to_string()).collect();
+        if !segments.is_empty() {
+            self.paths.insert(segments);
+        }
+        visit::visit_path(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        self.add_tokens(node.tokens.clone());
+        visit::visit_macro(self, node);
+    }
+}
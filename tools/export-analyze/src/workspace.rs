@@ -0,0 +1,85 @@
+//! Workspace members
+//!
+//! Reads the member list from a workspace's `Cargo.toml`, and each member's
+//! package name and library target from its own manifest. Members listed
+//! without a manifest are skipped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+
+/// A package in the workspace
+#[derive(Debug, Clone)]
+pub struct Member {
+    /// Package name, such as `common-config`
+    pub name: String,
+    /// Name other crates use for its library, such as `common_config`
+    pub lib_name: String,
+    /// Directory holding its manifest
+    pub dir: PathBuf,
+    /// Root of its library target, if it has one
+    pub lib_root: Option<PathBuf>,
+}
+
+/// Load the members of the workspace rooted at `root`
+pub fn load_members(root: &Path) -> Result<Vec<Member>> {
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = read_manifest(&manifest_path)?;
+
+    let members: Vec<String> = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(|members| members.as_array())
+        .map(|members| members.iter().filter_map(|m| m.as_str().map(String::from)).collect())
+        .with_context(|| format!("No workspace members in {:?}", manifest_path))?;
+
+    let mut loaded = Vec::new();
+    for member in members {
+        let dir = root.join(&member);
+        let manifest_path = dir.join("Cargo.toml");
+        if !manifest_path.exists() {
+            continue;
+        }
+        loaded.push(load_member(&dir)?);
+    }
+
+    Ok(loaded)
+}
+
+/// Load one package from its directory
+pub fn load_member(dir: &Path) -> Result<Member> {
+    let manifest_path = dir.join("Cargo.toml");
+    let manifest = read_manifest(&manifest_path)?;
+
+    let name = manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+        .with_context(|| format!("No package name in {:?}", manifest_path))?
+        .to_string();
+
+    let lib = manifest.get("lib");
+    let lib_name = lib
+        .and_then(|lib| lib.get("name"))
+        .and_then(|name| name.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| name.replace('-', "_"));
+    let lib_root = lib
+        .and_then(|lib| lib.get("path"))
+        .and_then(|path| path.as_str())
+        .map(|path| dir.join(path))
+        .or_else(|| Some(dir.join("src").join("lib.rs")))
+        .filter(|path| path.exists());
+
+    Ok(Member {
+        name,
+        lib_name,
+        dir: dir.to_path_buf(),
+        lib_root,
+    })
+}
+
+fn read_manifest(path: &Path) -> Result<toml::Value> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))
+}
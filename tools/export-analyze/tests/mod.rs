@@ -0,0 +1,6 @@
+//! Tests for the export-analyze module
+//!
+//! This module contains tests for the export-analyze tool.
+
+// Unit tests
+pub mod unit;
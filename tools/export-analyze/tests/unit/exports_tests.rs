@@ -0,0 +1,104 @@
+//! Tests for public API discovery
+
+use export_analyze::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn write(root: &Path, path: &str, content: &str) -> PathBuf {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn paths(table: &ExportTable, canonical: &str) -> Vec<String> {
+    table.exports[canonical].paths.iter().cloned().collect()
+}
+
+#[test]
+fn test_module_tree_and_re_exports() {
+    let dir = tempdir().unwrap();
+    let lib = write(
+        dir.path(),
+        "common/validation/src/lib.rs",
+        "mod slug;\npub mod content;\nmod internal {\n    pub fn helper() {}\n}\n\npub use slug::*;\npub use internal::helper as run_helper;\n\npub fn validate() {}\nfn private() {}\npub(crate) fn crate_only() {}\n\n#[cfg(test)]\nmod tests {\n    pub fn in_tests() {}\n}\n",
+    );
+    write(dir.path(), "common/validation/src/slug.rs", "pub fn validate_slug() {}\npub struct Slug;\nfn hidden() {}\n");
+    write(dir.path(), "common/validation/src/content/mod.rs", "pub mod rules;\npub enum ContentKind { Article }\n");
+    write(dir.path(), "common/validation/src/content/rules.rs", "pub const MAX_LENGTH: usize = 80;\n");
+
+    let table = ExportTable::build(&[("common_validation".to_string(), lib)]);
+
+    let mut canonical: Vec<&str> = table.exports.keys().map(String::as_str).collect();
+    canonical.sort();
+    assert_eq!(
+        canonical,
+        vec![
+            "common_validation::content::ContentKind",
+            "common_validation::content::rules::MAX_LENGTH",
+            "common_validation::internal::helper",
+            "common_validation::slug::Slug",
+            "common_validation::slug::validate_slug",
+            "common_validation::validate",
+        ]
+    );
+
+    assert_eq!(paths(&table, "common_validation::slug::validate_slug"), vec!["common_validation::validate_slug"]);
+    assert_eq!(paths(&table, "common_validation::internal::helper"), vec!["common_validation::run_helper"]);
+    assert_eq!(
+        paths(&table, "common_validation::content::rules::MAX_LENGTH"),
+        vec!["common_validation::content::rules::MAX_LENGTH"]
+    );
+    assert_eq!(table.exports["common_validation::slug::Slug"].kind, ItemKind::Struct);
+    assert_eq!(table.exports["common_validation::slug::Slug"].line, 2);
+    assert_eq!(table.lib_files["common_validation"].len(), 4);
+}
+
+#[test]
+fn test_duplicate_paths_and_cross_crate_re_exports() {
+    let dir = tempdir().unwrap();
+    let models = write(
+        dir.path(),
+        "common/models/src/lib.rs",
+        "pub mod article;\npub use article::Article;\n\n#[macro_export]\nmacro_rules! article {\n    () => {};\n}\n",
+    );
+    write(dir.path(), "common/models/src/article.rs", "pub struct Article;\n");
+    let traits = write(dir.path(), "common/traits/src/lib.rs", "pub use common_models::*;\npub use common_models::article;\n");
+
+    let table = ExportTable::build(&[
+        ("common_models".to_string(), models),
+        ("common_traits".to_string(), traits),
+    ]);
+
+    assert_eq!(
+        paths(&table, "common_models::article::Article"),
+        vec![
+            "common_models::Article",
+            "common_models::article::Article",
+            "common_traits::Article",
+            "common_traits::article::Article",
+        ]
+    );
+    assert_eq!(table.exports["common_models::article"].kind, ItemKind::Macro);
+
+    // Associated paths resolve to their type
+    let segments: Vec<String> = ["common_traits", "Article", "new"].iter().map(|s| s.to_string()).collect();
+    let (path, export) = table.resolve(&segments).unwrap();
+    assert_eq!(path, "common_traits::Article");
+    assert_eq!(export.canonical, "common_models::article::Article");
+}
+
+#[test]
+fn test_glob_cycles_terminate() {
+    let dir = tempdir().unwrap();
+    let lib = write(
+        dir.path(),
+        "src/lib.rs",
+        "pub mod a {\n    pub use super::b::*;\n    pub fn in_a() {}\n}\npub mod b {\n    pub use super::a::*;\n    pub fn in_b() {}\n}\n",
+    );
+
+    let table = ExportTable::build(&[("cycle".to_string(), lib)]);
+    assert_eq!(paths(&table, "cycle::a::in_a"), vec!["cycle::a::in_a", "cycle::b::in_a"]);
+    assert_eq!(paths(&table, "cycle::b::in_b"), vec!["cycle::a::in_b", "cycle::b::in_b"]);
+}
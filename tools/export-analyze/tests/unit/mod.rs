@@ -0,0 +1,5 @@
+// Unit tests for export-analyze
+
+mod exports_tests;
+mod usage_tests;
+mod workspace_tests;
@@ -0,0 +1,57 @@
+//! Tests for finding the paths a file refers to
+
+use export_analyze::*;
+use std::collections::BTreeSet;
+use std::fs;
+use tempfile::tempdir;
+
+fn table() -> (ExportTable, BTreeSet<String>, tempfile::TempDir) {
+    let dir = tempdir().unwrap();
+    let lib = dir.path().join("src").join("lib.rs");
+    fs::create_dir_all(lib.parent().unwrap()).unwrap();
+    fs::write(&lib, "pub fn load_config() {}\npub struct Config;\npub fn helper() {}\n").unwrap();
+
+    let table = ExportTable::build(&[("common_config".to_string(), lib)]);
+    let crates = ["common_config".to_string()].into_iter().collect();
+    (table, crates, dir)
+}
+
+fn joined(paths: BTreeSet<Vec<String>>) -> Vec<String> {
+    paths.into_iter().map(|path| path.join("::")).collect()
+}
+
+#[test]
+fn test_use_aliases_and_full_paths() {
+    let (table, crates, _dir) = table();
+    let source = "use common_config as config;\nuse std::fs;\n\nfn main() {\n    let _ = config::load_config();\n    let _: common_config::Config = todo!();\n    fs::read(\"x\").ok();\n}\n";
+
+    let paths = joined(referenced_paths(source, &crates, &table, false).unwrap());
+    assert_eq!(paths, vec!["common_config", "common_config::Config", "common_config::load_config"]);
+}
+
+#[test]
+fn test_glob_imports_and_macro_arguments() {
+    let (table, crates, _dir) = table();
+    let source = "use common_config::*;\n\nfn main() {\n    println!(\"{:?}\", load_config());\n    assert!(matches!(Config, Config));\n}\n";
+
+    let paths = joined(referenced_paths(source, &crates, &table, false).unwrap());
+    assert_eq!(paths, vec!["common_config::Config", "common_config::load_config"]);
+}
+
+#[test]
+fn test_ignore_tests_skips_test_modules() {
+    let (table, crates, _dir) = table();
+    let source = "use common_config::load_config;\n\n#[cfg(test)]\nmod tests {\n    fn check() {\n        common_config::helper();\n    }\n}\n";
+
+    let with_tests = joined(referenced_paths(source, &crates, &table, false).unwrap());
+    assert!(with_tests.contains(&"common_config::helper".to_string()));
+
+    let without_tests = joined(referenced_paths(source, &crates, &table, true).unwrap());
+    assert_eq!(without_tests, vec!["common_config::load_config"]);
+}
+
+#[test]
+fn test_unparseable_source() {
+    let (table, crates, _dir) = table();
+    assert!(referenced_paths("fn broken( {", &crates, &table, false).is_none());
+}
@@ -0,0 +1,94 @@
+//! Tests for analyzing a whole workspace
+
+use export_analyze::*;
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// A workspace with a library, a tool that uses part of it, and a member without a manifest
+fn workspace(root: &Path) {
+    write(root, "Cargo.toml", "[workspace]\nmembers = [\"common/models\", \"content-new\", \"missing\"]\n");
+
+    write(root, "common/models/Cargo.toml", "[package]\nname = \"common-models\"\nversion = \"0.1.0\"\n");
+    write(
+        root,
+        "common/models/src/lib.rs",
+        "pub mod article;\npub use article::*;\n\npub fn unused_helper() {}\n",
+    );
+    write(
+        root,
+        "common/models/src/article.rs",
+        "pub struct Article;\npub struct Draft;\n\npub fn used_internally() -> Draft {\n    Draft\n}\n",
+    );
+    write(root, "common/models/tests/article_tests.rs", "use common_models::Draft;\n");
+
+    write(
+        root,
+        "content-new/Cargo.toml",
+        "[package]\nname = \"content-new\"\nversion = \"0.1.0\"\n\n[lib]\nname = \"content_new\"\npath = \"src/lib.rs\"\n",
+    );
+    write(root, "content-new/src/lib.rs", "use common_models::Article;\n\npub fn create(_: Article) {}\n");
+    write(root, "content-new/src/main.rs", "fn main() {\n    content_new::create(common_models::article::Article);\n}\n");
+}
+
+#[test]
+fn test_load_members() -> Result<()> {
+    let dir = tempdir()?;
+    workspace(dir.path());
+
+    let members = load_members(dir.path())?;
+    let names: Vec<(&str, &str)> = members.iter().map(|m| (m.name.as_str(), m.lib_name.as_str())).collect();
+    assert_eq!(names, vec![("common-models", "common_models"), ("content-new", "content_new")]);
+    assert!(members.iter().all(|member| member.lib_root.is_some()));
+
+    Ok(())
+}
+
+#[test]
+fn test_analyze_workspace() -> Result<()> {
+    let dir = tempdir()?;
+    workspace(dir.path());
+
+    let report = analyze_workspace(dir.path(), false)?;
+    assert_eq!(report.crates, vec!["common_models", "content_new"]);
+    assert_eq!(report.exports, 5);
+
+    // Uses inside the defining library don't count
+    let unused: Vec<&str> = report.unused.iter().map(|unused| unused.canonical.as_str()).collect();
+    assert_eq!(unused, vec!["common_models::article::used_internally", "common_models::unused_helper"]);
+    assert_eq!(report.unused[1].file, Path::new("common/models/src/lib.rs"));
+    assert_eq!(report.unused[1].line, 4);
+
+    let article = report
+        .duplicates
+        .iter()
+        .find(|duplicate| duplicate.canonical == "common_models::article::Article")
+        .unwrap();
+    assert_eq!(article.paths, vec!["common_models::Article", "common_models::article::Article"]);
+    assert_eq!(article.used_paths, vec!["common_models::Article", "common_models::article::Article"]);
+
+    let markdown = markdown_report(&report);
+    assert!(markdown.contains("## Unused Exports"));
+    assert!(markdown.contains("| `common_models::unused_helper` | fn | common/models/src/lib.rs:4 | `common_models::unused_helper` |"));
+
+    Ok(())
+}
+
+#[test]
+fn test_ignore_tests() -> Result<()> {
+    let dir = tempdir()?;
+    workspace(dir.path());
+
+    let report = analyze_workspace(dir.path(), true)?;
+    let unused: Vec<&str> = report.unused.iter().map(|unused| unused.canonical.as_str()).collect();
+    assert!(unused.contains(&"common_models::article::Draft"));
+
+    Ok(())
+}
@@ -0,0 +1,237 @@
+//! # Animated GIFs and Video
+//!
+//! Animated GIFs and short videos in an article directory are copied into
+//! the output with a poster: a JPEG of their first frame, shown until they
+//! play. GIF posters are decoded here. Video posters need image-build built
+//! with the `ffmpeg` feature and the `ffmpeg` program on the `PATH`, which
+//! also allow `--transcode-animations` to convert each animated GIF to MP4
+//! and animated WebP, a fraction of its size.
+//!
+//! Everything is recorded in the manifest: the copy under the size key
+//! [`ORIGINAL_SIZE`], the poster under [`POSTER_SIZE`] and the conversions
+//! under [`ANIMATED_SIZE`], so content-build can replace the GIF in the page.
+
+use anyhow::{Context, Result};
+use common_models::{ImageVariant, ANIMATED_SIZE, ORIGINAL_SIZE, POSTER_SIZE};
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, DynamicImage, ImageFormat};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Video formats copied with a poster
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "webm", "mov"];
+
+/// Formats animated GIFs are converted to, when ffmpeg is available
+pub const ANIMATED_FORMATS: &[&str] = &["mp4", "webp"];
+
+/// Find the animated GIFs and videos in an article directory and its subdirectories
+///
+/// GIFs with a single frame are left out, since they are ordinary images.
+pub fn find_animated_media(article_dir: &Path) -> Vec<PathBuf> {
+    let mut media: Vec<PathBuf> = WalkDir::new(article_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file())
+        .filter(|path| match extension(path).as_str() {
+            "gif" => is_animated_gif(path),
+            extension => VIDEO_EXTENSIONS.contains(&extension),
+        })
+        .collect();
+    media.sort();
+    media
+}
+
+/// Whether a GIF has more than one frame
+pub fn is_animated_gif(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let Ok(decoder) = GifDecoder::new(BufReader::new(file)) else {
+        return false;
+    };
+    decoder.into_frames().take(2).filter(|frame| frame.is_ok()).count() > 1
+}
+
+/// Whether ffmpeg can be used for video posters and GIF conversion
+pub fn ffmpeg_available() -> bool {
+    ffmpeg::is_available()
+}
+
+/// Copy an animated GIF or video into the output with its poster
+///
+/// When `transcode` is set and ffmpeg is available, an animated GIF is also
+/// converted to each of [`ANIMATED_FORMATS`]. A video's dimensions are those
+/// of its poster, or `0` when ffmpeg isn't available to extract one.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or decoded, or an output file can't be written
+pub fn process_animated_media(
+    source_path: &Path,
+    article_dir: &Path,
+    article_slug: &str,
+    topic_path: &str,
+    output_dir: &Path,
+    transcode: bool,
+) -> Result<Vec<ImageVariant>> {
+    let relative = source_path
+        .strip_prefix(article_dir)
+        .with_context(|| format!("File is outside the article directory: {}", source_path.display()))?;
+    let relative_url = relative.to_string_lossy().replace('\\', "/");
+    let format = extension(source_path);
+    let is_gif = format == "gif";
+
+    let article_output_dir = output_dir.join(topic_path).join(article_slug);
+    let output_path = article_output_dir.join(relative);
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create output directory: {}", parent.display()))?;
+    }
+    fs::copy(source_path, &output_path).with_context(|| format!("Failed to copy {}", source_path.display()))?;
+
+    let stem = relative.with_extension("").to_string_lossy().replace('\\', "/");
+    let poster_name = format!("{}-{}.jpg", stem, POSTER_SIZE);
+    let poster_path = article_output_dir.join(&poster_name);
+    let poster = if is_gif {
+        Some(gif_poster(source_path, &poster_path)?)
+    } else {
+        ffmpeg::video_poster(source_path, &poster_path)?
+    };
+
+    let (width, height) = poster.unwrap_or((0, 0));
+    let variant = |path: &str, size: &str, format: &str| ImageVariant {
+        path: format!("{}/{}/{}", topic_path, article_slug, path),
+        size: size.to_string(),
+        width,
+        height,
+        format: format.to_string(),
+    };
+
+    let mut variants = vec![variant(&relative_url, ORIGINAL_SIZE, &format)];
+    if poster.is_some() {
+        variants.push(variant(&poster_name, POSTER_SIZE, "jpg"));
+    }
+    if is_gif && transcode && ffmpeg_available() {
+        for target in ANIMATED_FORMATS {
+            let name = format!("{}.{}", stem, target);
+            ffmpeg::transcode_gif(source_path, &article_output_dir.join(&name), target)?;
+            variants.push(variant(&name, ANIMATED_SIZE, target));
+        }
+    }
+
+    Ok(variants)
+}
+
+/// Save the first frame of a GIF as a JPEG, returning its dimensions
+fn gif_poster(source_path: &Path, poster_path: &Path) -> Result<(u32, u32)> {
+    let file = File::open(source_path).with_context(|| format!("Failed to open GIF: {}", source_path.display()))?;
+    let decoder =
+        GifDecoder::new(BufReader::new(file)).with_context(|| format!("Failed to decode GIF: {}", source_path.display()))?;
+    let frame = decoder
+        .into_frames()
+        .next()
+        .with_context(|| format!("GIF has no frames: {}", source_path.display()))?
+        .with_context(|| format!("Failed to decode GIF: {}", source_path.display()))?;
+
+    let poster = DynamicImage::ImageRgba8(frame.into_buffer()).to_rgb8();
+    poster
+        .save_with_format(poster_path, ImageFormat::Jpeg)
+        .with_context(|| format!("Failed to save poster: {}", poster_path.display()))?;
+
+    Ok(poster.dimensions())
+}
+
+/// Lowercase extension of a path
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+#[cfg(feature = "ffmpeg")]
+mod ffmpeg {
+    use anyhow::{bail, Context, Result};
+    use std::path::Path;
+    use std::process::Command;
+    use std::sync::OnceLock;
+
+    /// Whether the `ffmpeg` program runs, checked once
+    pub fn is_available() -> bool {
+        static AVAILABLE: OnceLock<bool> = OnceLock::new();
+        *AVAILABLE.get_or_init(|| {
+            Command::new("ffmpeg")
+                .arg("-version")
+                .output()
+                .is_ok_and(|output| output.status.success())
+        })
+    }
+
+    /// Save the first frame of a video as a JPEG, returning its dimensions
+    pub fn video_poster(source_path: &Path, poster_path: &Path) -> Result<Option<(u32, u32)>> {
+        if !is_available() {
+            return Ok(None);
+        }
+
+        convert(source_path, &["-frames:v", "1"], poster_path)?;
+        let dimensions = image::image_dimensions(poster_path)
+            .with_context(|| format!("Failed to read poster: {}", poster_path.display()))?;
+        Ok(Some(dimensions))
+    }
+
+    /// Convert an animated GIF to a looping MP4 or animated WebP
+    pub fn transcode_gif(source_path: &Path, output_path: &Path, format: &str) -> Result<()> {
+        let args: &[&str] = match format {
+            // H.264 needs even dimensions and 4:2:0 chroma to play in browsers
+            "mp4" => &[
+                "-movflags",
+                "+faststart",
+                "-pix_fmt",
+                "yuv420p",
+                "-vf",
+                "scale=trunc(iw/2)*2:trunc(ih/2)*2",
+                "-an",
+            ],
+            "webp" => &["-c:v", "libwebp", "-loop", "0", "-quality", "75", "-an"],
+            format => bail!("Unsupported animation format: {}", format),
+        };
+        convert(source_path, args, output_path)
+    }
+
+    /// Run ffmpeg on one input and output, overwriting the output
+    fn convert(input: &Path, args: &[&str], output: &Path) -> Result<()> {
+        let status = Command::new("ffmpeg")
+            .args(["-y", "-loglevel", "error", "-i"])
+            .arg(input)
+            .args(args)
+            .arg(output)
+            .status()
+            .context("Failed to run ffmpeg")?;
+        if !status.success() {
+            bail!("ffmpeg failed to convert {} to {}", input.display(), output.display());
+        }
+
+        Ok(())
+    }
+}
+
+/// Stand-ins for when image-build is built without the `ffmpeg` feature
+#[cfg(not(feature = "ffmpeg"))]
+mod ffmpeg {
+    use anyhow::Result;
+    use std::path::Path;
+
+    pub fn is_available() -> bool {
+        false
+    }
+
+    pub fn video_poster(_source_path: &Path, _poster_path: &Path) -> Result<Option<(u32, u32)>> {
+        Ok(None)
+    }
+
+    pub fn transcode_gif(_source_path: &Path, _output_path: &Path, _format: &str) -> Result<()> {
+        Ok(())
+    }
+}
@@ -2,10 +2,20 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use std::collections::HashMap;
 use anyhow::{Result, Context};
-use common_models::{Config, ImageNaming};
+use common_models::{Config, ImageManifest, ImageNaming, ImageVariant, IMAGE_MANIFEST_FILE};
 use common_config::load_config;
+use common_traits::CancellationToken;
 use image::{ImageFormat, GenericImageView};
 
+pub mod animated;
+pub mod og_image;
+pub mod report;
+pub mod svg;
+pub use animated::{ffmpeg_available, find_animated_media, process_animated_media};
+pub use og_image::{CardRenderer, CARD_HEIGHT, CARD_WIDTH};
+pub use report::ImageBuildReport;
+pub use svg::{find_svg_images, process_svg_variants};
+
 /// Options for building responsive images
 #[derive(Debug, Clone)]
 pub struct BuildImagesOptions {
@@ -14,6 +24,10 @@ pub struct BuildImagesOptions {
     pub topic: Option<String>,
     pub article: Option<String>,
     pub force_rebuild: bool,
+    /// Render PNG fallbacks for SVG images at each configured size
+    pub rasterize_svg: bool,
+    /// Convert animated GIFs to MP4 and WebP, when built with the `ffmpeg` feature
+    pub transcode_animations: bool,
 }
 
 impl Default for BuildImagesOptions {
@@ -24,6 +38,8 @@ impl Default for BuildImagesOptions {
             topic: None,
             article: None,
             force_rebuild: false,
+            rasterize_svg: false,
+            transcode_animations: false,
         }
     }
 }
@@ -102,6 +118,18 @@ pub fn process_image(
     output_dir: &Path,
     config: &Config,
 ) -> Result<Vec<PathBuf>> {
+    let variants = process_image_variants(source_path, article_slug, topic_path, output_dir, config)?;
+    Ok(variants.iter().map(|variant| output_dir.join(&variant.path)).collect())
+}
+
+/// Process a single image, describing each generated file for the manifest
+pub fn process_image_variants(
+    source_path: &Path,
+    article_slug: &str,
+    topic_path: &str,
+    output_dir: &Path,
+    config: &Config,
+) -> Result<Vec<ImageVariant>> {
     // Create output directory for this article
     let article_output_dir = output_dir.join(topic_path).join(article_slug);
     fs::create_dir_all(&article_output_dir)
@@ -112,7 +140,7 @@ pub fn process_image(
         .context(format!("Failed to open image: {:?}", source_path))?;
 
     // Track all generated files
-    let mut generated_files = Vec::new();
+    let mut variants = Vec::new();
 
     // Process each image size
     for (size_key, size_config) in &config.images.sizes {
@@ -238,11 +266,17 @@ pub fn process_image(
                 }
             }
 
-            generated_files.push(output_path.clone());
+            variants.push(ImageVariant {
+                path: format!("{}/{}/{}", topic_path, article_slug, filename),
+                size: size_key.clone(),
+                width: processed_img.width(),
+                height: processed_img.height(),
+                format: format_name.to_string(),
+            });
         }
     }
 
-    Ok(generated_files)
+    Ok(variants)
 }
 
 /// Build images for a specific article
@@ -252,12 +286,23 @@ pub fn build_article_images(
     topic_key: &str,
     options: &BuildImagesOptions,
 ) -> Result<Vec<PathBuf>> {
+    let variants = build_article_image_variants(config, article_slug, topic_key, options)?;
+    Ok(variants.iter().map(|variant| options.output_dir.join(&variant.path)).collect())
+}
+
+/// Build images for a specific article, describing each generated file for the manifest
+pub fn build_article_image_variants(
+    config: &Config,
+    article_slug: &str,
+    topic_key: &str,
+    options: &BuildImagesOptions,
+) -> Result<Vec<ImageVariant>> {
     let topic_config = &config.content.topics[topic_key];
     let article_dir = get_article_dir(config, article_slug, topic_key)?;
 
     let source_path = article_dir.join("index.jpg");
     if source_path.exists() {
-        process_image(
+        process_image_variants(
             &source_path,
             article_slug,
             &topic_config.directory,
@@ -271,6 +316,16 @@ pub fn build_article_images(
 
 /// Main function to build images based on options
 pub fn build_images(options: &BuildImagesOptions) -> Result<(usize, usize, usize, usize)> {
+    build_images_cancellable(options, &CancellationToken::new())
+}
+
+/// Build images based on options, stopping between articles if `token` is cancelled
+///
+/// Images that were generated before cancellation are left in the output directory.
+pub fn build_images_cancellable(
+    options: &BuildImagesOptions,
+    token: &CancellationToken,
+) -> Result<(usize, usize, usize, usize)> {
     // Read configuration
     let config = load_config()?;
 
@@ -278,6 +333,13 @@ pub fn build_images(options: &BuildImagesOptions) -> Result<(usize, usize, usize
     fs::create_dir_all(&options.output_dir)
         .context(format!("Failed to create output directory: {:?}", options.output_dir))?;
 
+    // Images from earlier runs stay in the output directory, so they stay in the manifest
+    let manifest_path = options.output_dir.join(IMAGE_MANIFEST_FILE);
+    let mut manifest = ImageManifest::load(&manifest_path)?.unwrap_or_default();
+
+    // Social cards are rendered only when a font is configured
+    let cards = CardRenderer::load(&config.og_image)?;
+
     // Track statistics
     let mut total_articles = 0;
     let mut total_images = 0;
@@ -304,16 +366,68 @@ pub fn build_images(options: &BuildImagesOptions) -> Result<(usize, usize, usize
             find_topic_for_article(&config, article_slug)?
         };
 
+        let article_dir = get_article_dir(&config, article_slug, &topic_key)?;
+        let topic_path = &config.content.topics[&topic_key].directory;
+
+        if let Some(cards) = &cards {
+            match cards.render_article_card(&config, &article_dir, article_slug, topic_path, &options.output_dir) {
+                Ok(Some(card)) => manifest.insert(card),
+                Ok(None) => {}
+                Err(err) => tracing::warn!(article = %article_slug, "Skipping social card: {:#}", err),
+            }
+        }
+
+        // SVG diagrams are built whether or not the article has a source image
+        for svg_path in find_svg_images(&article_dir) {
+            total_images += 1;
+            match process_svg_variants(
+                &svg_path,
+                &article_dir,
+                article_slug,
+                topic_path,
+                &options.output_dir,
+                &config,
+                options.rasterize_svg,
+            ) {
+                Ok(variants) => {
+                    processed_images += 1;
+                    variants.into_iter().for_each(|variant| manifest.insert(variant));
+                },
+                Err(err) => tracing::warn!(path = %svg_path.display(), "Skipping SVG: {:#}", err),
+            }
+        }
+
+        for media_path in find_animated_media(&article_dir) {
+            total_images += 1;
+            match process_animated_media(
+                &media_path,
+                &article_dir,
+                article_slug,
+                topic_path,
+                &options.output_dir,
+                options.transcode_animations,
+            ) {
+                Ok(variants) => {
+                    processed_images += 1;
+                    variants.into_iter().for_each(|variant| manifest.insert(variant));
+                },
+                Err(err) => tracing::warn!(path = %media_path.display(), "Skipping animated media: {:#}", err),
+            }
+        }
+
         total_articles += 1;
         total_images += 1;
 
-        match build_article_images(&config, article_slug, &topic_key, options) {
-            Ok(_) => {
+        match build_article_image_variants(&config, article_slug, &topic_key, options) {
+            Ok(variants) => {
                 processed_images += 1;
+                variants.into_iter().for_each(|variant| manifest.insert(variant));
+                manifest.save(&manifest_path)?;
                 Ok((total_articles, total_images, processed_images, skipped_articles))
             },
             Err(e) => {
                 skipped_articles += 1;
+                manifest.save(&manifest_path)?;
                 // Return the error with context about the skipped article
                 Err(anyhow::anyhow!("Failed to process article {}: {}. Stats: {} total, {} processed, {} skipped",
                     article_slug, e, total_articles, processed_images, skipped_articles))
@@ -351,6 +465,12 @@ pub fn build_images(options: &BuildImagesOptions) -> Result<(usize, usize, usize
 
             // Find all article directories in this topic
             for entry in fs::read_dir(topic_dir)? {
+                if let Err(err) = token.check("image build") {
+                    // Keep a record of the images generated before stopping
+                    manifest.save(&manifest_path)?;
+                    return Err(err.into());
+                }
+
                 let entry = entry?;
                 let path = entry.path();
 
@@ -358,19 +478,65 @@ pub fn build_images(options: &BuildImagesOptions) -> Result<(usize, usize, usize
                     let article_slug = path.file_name().unwrap().to_string_lossy().to_string();
                     let source_path = path.join("index.jpg");
 
+                    if let Some(cards) = &cards {
+                        match cards.render_article_card(&config, &path, &article_slug, &topic_config.directory, &options.output_dir) {
+                            Ok(Some(card)) => manifest.insert(card),
+                            Ok(None) => {}
+                            Err(err) => tracing::warn!(article = %article_slug, "Skipping social card: {:#}", err),
+                        }
+                    }
+
+                    for svg_path in find_svg_images(&path) {
+                        total_images += 1;
+                        match process_svg_variants(
+                            &svg_path,
+                            &path,
+                            &article_slug,
+                            &topic_config.directory,
+                            &options.output_dir,
+                            &config,
+                            options.rasterize_svg,
+                        ) {
+                            Ok(variants) => {
+                                processed_images += 1;
+                                variants.into_iter().for_each(|variant| manifest.insert(variant));
+                            },
+                            Err(err) => tracing::warn!(path = %svg_path.display(), "Skipping SVG: {:#}", err),
+                        }
+                    }
+
+                    for media_path in find_animated_media(&path) {
+                        total_images += 1;
+                        match process_animated_media(
+                            &media_path,
+                            &path,
+                            &article_slug,
+                            &topic_config.directory,
+                            &options.output_dir,
+                            options.transcode_animations,
+                        ) {
+                            Ok(variants) => {
+                                processed_images += 1;
+                                variants.into_iter().for_each(|variant| manifest.insert(variant));
+                            },
+                            Err(err) => tracing::warn!(path = %media_path.display(), "Skipping animated media: {:#}", err),
+                        }
+                    }
+
                     if source_path.exists() {
                         total_articles += 1;
                         total_images += 1;
 
-                        match process_image(
+                        match process_image_variants(
                             &source_path,
                             &article_slug,
                             &topic_config.directory,
                             &options.output_dir,
                             &config,
                         ) {
-                            Ok(_) => {
+                            Ok(variants) => {
                                 processed_images += 1;
+                                variants.into_iter().for_each(|variant| manifest.insert(variant));
                             },
                             Err(_) => {
                                 skipped_articles += 1;
@@ -383,6 +549,7 @@ pub fn build_images(options: &BuildImagesOptions) -> Result<(usize, usize, usize
             }
         }
 
+        manifest.save(&manifest_path)?;
         Ok((total_articles, total_images, processed_images, skipped_articles))
     }
-}
\ No newline at end of file
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
 use clap::Parser;
 use colored::*;
-use image_build::{BuildImagesOptions, build_images};
+use common_cli::OutputFormatArgs;
+use common_traits::CancellationToken;
+use image_build::{BuildImagesOptions, ImageBuildReport, build_images_cancellable};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -26,10 +28,25 @@ struct Args {
     /// Specific topic to process (optional)
     #[arg(short, long)]
     topic: Option<String>,
+
+    /// Render PNG fallbacks for SVG images at each configured size
+    #[arg(long)]
+    rasterize_svg: bool,
+
+    /// Convert animated GIFs to MP4 and WebP (needs the `ffmpeg` feature and ffmpeg installed)
+    #[arg(long)]
+    transcode_animations: bool,
+
+    #[command(flatten)]
+    output: OutputFormatArgs,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let formatter = args.output.formatter();
+
+    // Warn about configuration problems before building
+    common_config::report_config_issues();
 
     // Convert args to options
     let options = BuildImagesOptions {
@@ -38,22 +55,32 @@ fn main() -> Result<()> {
         article: args.article,
         topic: args.topic,
         force_rebuild: false,
+        rasterize_svg: args.rasterize_svg,
+        transcode_animations: args.transcode_animations,
     };
 
-    println!("{}", "Scanning for source images...".yellow().bold());
+    if options.transcode_animations && !image_build::ffmpeg_available() {
+        eprintln!(
+            "{} ffmpeg is not available, so animated GIFs will not be converted",
+            "Warning:".yellow().bold()
+        );
+    }
+
+    // Stop cleanly between articles when the user presses Ctrl-C
+    let token = CancellationToken::new();
+    token.cancel_on_ctrlc()?;
+
+    if formatter.is_plain() {
+        println!("{}", "Scanning for source images...".yellow().bold());
+    }
 
     // Build images using the library function
-    match build_images(&options) {
-        Ok((total_articles, total_images, processed_images, skipped_articles)) => {
-            // Print summary
-            println!("\n{}", "Summary:".yellow().bold());
-            println!("  Total articles scanned: {}", total_articles.to_string().cyan().bold());
-            println!("  Total source images found: {}", total_images.to_string().cyan().bold());
-            println!("  Images processed: {}", processed_images.to_string().green().bold());
-            println!("  Articles skipped (no source image): {}", skipped_articles.to_string().red().bold());
-
-            println!("\n{}", "Image build complete!".green().bold());
-            Ok(())
+    match build_images_cancellable(&options, &token) {
+        Ok(counts) => {
+            if formatter.is_plain() {
+                println!();
+            }
+            formatter.print(&ImageBuildReport::from(counts))
         },
         Err(e) => {
             eprintln!("{} {}", "Error:".red().bold(), e);
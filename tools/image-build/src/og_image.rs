@@ -0,0 +1,262 @@
+//! # Social Cards
+//!
+//! With a font set in the `og_image` section of the configuration,
+//! image-build renders a 1200×630 card for each article, the size social
+//! sites show for `og:image`. The article's title is drawn over its
+//! `index.jpg`, darkened so the text stays readable, or over a plain
+//! background color. Long titles are set smaller and wrapped, and cut short
+//! after four lines.
+//!
+//! Cards are saved beside the article's other images with the size key
+//! `og`, and recorded in the manifest, so content-build can point the
+//! article's social metadata at them.
+
+use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+use anyhow::{anyhow, bail, Context, Result};
+use common_markdown::extract_frontmatter_and_content;
+use common_models::{Config, ImageVariant, OgImageConfig, SOCIAL_CARD_SIZE};
+use image::{imageops, DynamicImage, ImageFormat, Rgb, RgbImage};
+use std::fs;
+use std::path::Path;
+
+use crate::generate_image_filename;
+
+/// Width of a social card in pixels
+pub const CARD_WIDTH: u32 = 1200;
+
+/// Height of a social card in pixels
+pub const CARD_HEIGHT: u32 = 630;
+
+/// Space between the title and the edges of the card, in pixels
+const MARGIN: f32 = 80.0;
+
+/// Most lines of title shown
+const MAX_LINES: usize = 4;
+
+/// Title font sizes tried, largest first, in pixels
+const FONT_SIZES: &[f32] = &[72.0, 64.0, 56.0, 48.0, 40.0];
+
+/// Distance between baselines, as a multiple of the font size
+const LINE_SPACING: f32 = 1.2;
+
+/// How much darker the hero image is made behind the title
+const HERO_DARKENING: i32 = -90;
+
+/// Draws social cards in the configured font and colors
+pub struct CardRenderer {
+    font: FontVec,
+    background: Rgb<u8>,
+    text_color: Rgb<u8>,
+    use_hero_image: bool,
+}
+
+impl CardRenderer {
+    /// Create a renderer for a font and the configured colors
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a color isn't written as `#rrggbb`
+    pub fn new(font: FontVec, config: &OgImageConfig) -> Result<Self> {
+        Ok(Self {
+            font,
+            background: parse_color(&config.background)?,
+            text_color: parse_color(&config.text_color)?,
+            use_hero_image: config.use_hero_image,
+        })
+    }
+
+    /// Create a renderer from the configuration, returning `None` if no font is set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the font can't be read or parsed, or a color is invalid
+    pub fn load(config: &OgImageConfig) -> Result<Option<Self>> {
+        let Some(font_path) = &config.font else {
+            return Ok(None);
+        };
+
+        let data = fs::read(font_path).with_context(|| format!("Failed to read font: {}", font_path))?;
+        let font = FontVec::try_from_vec(data).map_err(|_| anyhow!("Failed to parse font: {}", font_path))?;
+        Self::new(font, config).map(Some)
+    }
+
+    /// Draw a card with a title, over the hero image if there is one
+    pub fn render(&self, title: &str, hero: Option<&DynamicImage>) -> RgbImage {
+        let mut card = match hero.filter(|_| self.use_hero_image) {
+            Some(hero) => {
+                let filled = hero
+                    .resize_to_fill(CARD_WIDTH, CARD_HEIGHT, imageops::FilterType::Lanczos3)
+                    .to_rgb8();
+                imageops::brighten(&filled, HERO_DARKENING)
+            }
+            None => RgbImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, self.background),
+        };
+
+        let (scale, lines) = self.layout(title);
+        let line_height = scale.y * LINE_SPACING;
+        let text_height = line_height * (lines.len() as f32 - 1.0) + scale.y;
+        let mut baseline = (CARD_HEIGHT as f32 - text_height) / 2.0 + self.font.as_scaled(scale).ascent();
+        for line in &lines {
+            self.draw_line(&mut card, line, scale, baseline);
+            baseline += line_height;
+        }
+
+        card
+    }
+
+    /// Render the card for an article and save it with the article's images
+    ///
+    /// Returns `None` if the article directory has no content file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content file or hero image can't be read, or the card can't be saved
+    pub fn render_article_card(
+        &self,
+        config: &Config,
+        article_dir: &Path,
+        article_slug: &str,
+        topic_path: &str,
+        output_dir: &Path,
+    ) -> Result<Option<ImageVariant>> {
+        let Some(content_path) = common_fs::resolve_content_file(article_dir, article_slug) else {
+            return Ok(None);
+        };
+        let content = fs::read_to_string(&content_path)
+            .with_context(|| format!("Failed to read content file: {}", content_path.display()))?;
+        let (frontmatter, _) = extract_frontmatter_and_content(&content)?;
+
+        let hero_path = article_dir.join("index.jpg");
+        let hero = if self.use_hero_image && hero_path.exists() {
+            Some(image::open(&hero_path).with_context(|| format!("Failed to open image: {}", hero_path.display()))?)
+        } else {
+            None
+        };
+
+        let card = self.render(&frontmatter.title, hero.as_ref());
+
+        let article_output_dir = output_dir.join(topic_path).join(article_slug);
+        fs::create_dir_all(&article_output_dir)
+            .with_context(|| format!("Failed to create output directory: {}", article_output_dir.display()))?;
+        let filename = generate_image_filename(config, article_slug, SOCIAL_CARD_SIZE, CARD_WIDTH, CARD_HEIGHT, "jpg");
+        let output_path = article_output_dir.join(&filename);
+        card.save_with_format(&output_path, ImageFormat::Jpeg)
+            .with_context(|| format!("Failed to save social card: {}", output_path.display()))?;
+
+        Ok(Some(ImageVariant {
+            path: format!("{}/{}/{}", topic_path, article_slug, filename),
+            size: SOCIAL_CARD_SIZE.to_string(),
+            width: CARD_WIDTH,
+            height: CARD_HEIGHT,
+            format: "jpg".to_string(),
+        }))
+    }
+
+    /// Choose the largest font size the title fits in, and wrap it to the card
+    ///
+    /// Titles still too long at the smallest size are cut short with an ellipsis.
+    pub fn layout(&self, title: &str) -> (PxScale, Vec<String>) {
+        let max_width = CARD_WIDTH as f32 - 2.0 * MARGIN;
+
+        for size in FONT_SIZES {
+            let scale = PxScale::from(*size);
+            let lines = self.wrap(title, scale, max_width);
+            if lines.len() <= MAX_LINES {
+                return (scale, lines);
+            }
+        }
+
+        // Still too long at the smallest size
+        let scale = PxScale::from(FONT_SIZES[FONT_SIZES.len() - 1]);
+        let mut lines = self.wrap(title, scale, max_width);
+        lines.truncate(MAX_LINES);
+        if let Some(last) = lines.last_mut() {
+            last.push('…');
+        }
+        (scale, lines)
+    }
+
+    /// Break text into lines no wider than `max_width`, keeping words whole
+    fn wrap(&self, text: &str, scale: PxScale, max_width: f32) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        let mut line = String::new();
+        for word in text.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if line.is_empty() || self.text_width(&candidate, scale) <= max_width {
+                line = candidate;
+            } else {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Width of a line of text in pixels
+    fn text_width(&self, text: &str, scale: PxScale) -> f32 {
+        let font = self.font.as_scaled(scale);
+        let mut width = 0.0;
+        let mut previous = None;
+        for c in text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                width += font.kern(previous, id);
+            }
+            width += font.h_advance(id);
+            previous = Some(id);
+        }
+
+        width
+    }
+
+    /// Draw a line of text at the left margin, blending it into the card
+    fn draw_line(&self, card: &mut RgbImage, text: &str, scale: PxScale, baseline: f32) {
+        let font = self.font.as_scaled(scale);
+        let mut x = MARGIN;
+        let mut previous = None;
+        for c in text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                x += font.kern(previous, id);
+            }
+            let glyph = id.with_scale_and_position(scale, point(x, baseline));
+            x += font.h_advance(id);
+            previous = Some(id);
+
+            let Some(outlined) = self.font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|glyph_x, glyph_y, coverage| {
+                let px = bounds.min.x as i32 + glyph_x as i32;
+                let py = bounds.min.y as i32 + glyph_y as i32;
+                if px < 0 || py < 0 || px as u32 >= card.width() || py as u32 >= card.height() {
+                    return;
+                }
+
+                let pixel = card.get_pixel_mut(px as u32, py as u32);
+                for (channel, text) in pixel.0.iter_mut().zip(self.text_color.0) {
+                    *channel = (*channel as f32 + (text as f32 - *channel as f32) * coverage.min(1.0)).round() as u8;
+                }
+            });
+        }
+    }
+}
+
+/// Parse a color written as `#rrggbb`
+///
+/// # Errors
+///
+/// Returns an error if the color isn't six hex digits
+pub fn parse_color(color: &str) -> Result<Rgb<u8>> {
+    let hex = color.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("Invalid color '{}', expected #rrggbb", color);
+    }
+
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).expect("hex digits");
+    Ok(Rgb([channel(0), channel(2), channel(4)]))
+}
@@ -0,0 +1,39 @@
+//! # Image Build Report
+//!
+//! Counts from an image build, printed as text or as JSON or YAML with
+//! `--format`.
+
+use colored::*;
+use common_cli::DisplayResult;
+use serde::Serialize;
+
+/// What an image build scanned and generated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ImageBuildReport {
+    pub total_articles: usize,
+    pub total_images: usize,
+    pub processed_images: usize,
+    /// Articles without a source image
+    pub skipped_articles: usize,
+}
+
+impl From<(usize, usize, usize, usize)> for ImageBuildReport {
+    /// Report on the counts returned by [`crate::build_images`]
+    fn from((total_articles, total_images, processed_images, skipped_articles): (usize, usize, usize, usize)) -> Self {
+        Self { total_articles, total_images, processed_images, skipped_articles }
+    }
+}
+
+impl DisplayResult for ImageBuildReport {
+    fn to_display(&self) -> String {
+        [
+            format!("{}", "Summary:".yellow().bold()),
+            format!("  Total articles scanned: {}", self.total_articles.to_string().cyan().bold()),
+            format!("  Total source images found: {}", self.total_images.to_string().cyan().bold()),
+            format!("  Images processed: {}", self.processed_images.to_string().green().bold()),
+            format!("  Articles skipped (no source image): {}", self.skipped_articles.to_string().red().bold()),
+            format!("\n{}", "Image build complete!".green().bold()),
+        ]
+        .join("\n")
+    }
+}
@@ -0,0 +1,115 @@
+//! # SVG Images
+//!
+//! SVG diagrams in an article directory are minified into the output,
+//! keeping their path within the article directory so links to them keep
+//! working. With `--rasterize-svg`, a PNG fallback is also rendered for
+//! each size in `images.sizes`, named `{name}-{size}.png`, for places that
+//! can't show SVG, such as email. Both are recorded in the manifest, with
+//! the SVG itself under the size key [`ORIGINAL_SIZE`].
+
+use anyhow::{anyhow, Context, Result};
+use common_models::{Config, ImageVariant, ORIGINAL_SIZE};
+use image_optimize::minify_svg;
+use resvg::{tiny_skia, usvg};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use walkdir::WalkDir;
+
+/// Find the SVG files in an article directory and its subdirectories
+pub fn find_svg_images(article_dir: &Path) -> Vec<PathBuf> {
+    let mut svgs: Vec<PathBuf> = WalkDir::new(article_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("svg")))
+        .collect();
+    svgs.sort();
+    svgs
+}
+
+/// Minify an SVG into the output, and render PNG fallbacks when `rasterize` is set
+///
+/// # Errors
+///
+/// Returns an error if the SVG can't be read or parsed, or an output file can't be written
+pub fn process_svg_variants(
+    source_path: &Path,
+    article_dir: &Path,
+    article_slug: &str,
+    topic_path: &str,
+    output_dir: &Path,
+    config: &Config,
+    rasterize: bool,
+) -> Result<Vec<ImageVariant>> {
+    let relative = source_path
+        .strip_prefix(article_dir)
+        .with_context(|| format!("SVG is outside the article directory: {}", source_path.display()))?;
+    let relative_url = relative.to_string_lossy().replace('\\', "/");
+
+    let svg = fs::read_to_string(source_path).with_context(|| format!("Failed to read SVG: {}", source_path.display()))?;
+    let minified = minify_svg(&svg);
+    let tree = usvg::Tree::from_str(&minified, &svg_options())
+        .map_err(|err| anyhow!("Failed to parse SVG {}: {}", source_path.display(), err))?;
+    let size = tree.size();
+
+    let article_output_dir = output_dir.join(topic_path).join(article_slug);
+    let output_path = article_output_dir.join(relative);
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create output directory: {}", parent.display()))?;
+    }
+    fs::write(&output_path, &minified).with_context(|| format!("Failed to write SVG: {}", output_path.display()))?;
+
+    let mut variants = vec![ImageVariant {
+        path: format!("{}/{}/{}", topic_path, article_slug, relative_url),
+        size: ORIGINAL_SIZE.to_string(),
+        width: size.width().round() as u32,
+        height: size.height().round() as u32,
+        format: "svg".to_string(),
+    }];
+    if !rasterize {
+        return Ok(variants);
+    }
+
+    let stem = relative.with_extension("").to_string_lossy().replace('\\', "/");
+    for (size_key, size_config) in &config.images.sizes {
+        let scale = size_config.width as f32 / size.width();
+        let width = size_config.width;
+        let height = ((size.height() * scale).round() as u32).max(1);
+
+        let mut pixmap =
+            tiny_skia::Pixmap::new(width, height).ok_or_else(|| anyhow!("Invalid PNG size: {}x{}", width, height))?;
+        resvg::render(&tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+
+        let filename = format!("{}-{}.png", stem, size_key.replace('_', "-"));
+        let png_path = article_output_dir.join(&filename);
+        pixmap
+            .save_png(&png_path)
+            .map_err(|err| anyhow!("Failed to save PNG {}: {}", png_path.display(), err))?;
+
+        variants.push(ImageVariant {
+            path: format!("{}/{}/{}", topic_path, article_slug, filename),
+            size: size_key.clone(),
+            width,
+            height,
+            format: "png".to_string(),
+        });
+    }
+
+    Ok(variants)
+}
+
+/// Parsing options with the system fonts, so text in diagrams is rendered
+fn svg_options() -> usvg::Options<'static> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    let fontdb = FONTS.get_or_init(|| {
+        let mut fontdb = usvg::fontdb::Database::new();
+        fontdb.load_system_fonts();
+        Arc::new(fontdb)
+    });
+
+    usvg::Options {
+        fontdb: Arc::clone(fontdb),
+        ..Default::default()
+    }
+}
@@ -61,6 +61,15 @@ fn create_test_config() -> Config {
             copyright: "Test Copyright".into(),
             site_url: Some("https://example.com".into()),
         },
+        hooks: Vec::new(),
+        i18n: Default::default(),
+        deploy: Default::default(),
+        export: Default::default(),
+        import: Default::default(),
+        newsletter: Default::default(),
+        typography: Default::default(),
+        link_policy: Default::default(),
+        og_image: Default::default(),
     }
 }
 
@@ -45,6 +45,8 @@ fn test_build_images_options_default() {
     assert_eq!(options.topic, None);
     assert_eq!(options.article, None);
     assert_eq!(options.force_rebuild, false);
+    assert_eq!(options.rasterize_svg, false);
+    assert_eq!(options.transcode_animations, false);
 }
 
 #[test]
@@ -656,4 +658,282 @@ fn test_build_images_with_different_quality_levels() -> Result<()> {
     assert!(low_size < high_size);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_process_image_variants_describe_generated_files() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source_path = temp_dir.path().join("index.jpg");
+    image::RgbImage::new(200, 100).save(&source_path)?;
+    let output_dir = temp_dir.path().join("images");
+
+    let config = common_models::Config::default();
+    let variants = image_build::process_image_variants(&source_path, "post", "blog", &output_dir, &config)?;
+    assert_eq!(variants.len(), image_build::process_image(&source_path, "post", "blog", &output_dir, &config)?.len());
+
+    for variant in &variants {
+        assert!(variant.path.starts_with("blog/post/"));
+        assert!(output_dir.join(&variant.path).is_file());
+        assert_eq!(variant.width, config.images.sizes[&variant.size].width);
+        assert_eq!(variant.height, variant.width / 2);
+    }
+    assert!(variants.iter().any(|variant| variant.format == "jpg"));
+
+    // The manifest round-trips and finds variants by path
+    let mut manifest = common_models::ImageManifest::default();
+    variants.iter().rev().cloned().for_each(|variant| manifest.insert(variant));
+    let manifest_path = output_dir.join(common_models::IMAGE_MANIFEST_FILE);
+    manifest.save(&manifest_path)?;
+
+    let loaded = common_models::ImageManifest::load(&manifest_path)?.unwrap();
+    assert_eq!(loaded, manifest);
+    assert_eq!(loaded.get(&variants[0].path), Some(&variants[0]));
+    assert!(loaded.get("blog/post/missing.jpg").is_none());
+    assert!(common_models::ImageManifest::load(&temp_dir.path().join("none.json"))?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_social_cards_need_a_font_and_valid_colors() -> Result<()> {
+    let mut config = common_models::OgImageConfig::default();
+    assert!(image_build::CardRenderer::load(&config)?.is_none());
+
+    config.font = Some("missing/font.ttf".to_string());
+    let err = image_build::CardRenderer::load(&config).err().unwrap();
+    assert!(err.to_string().contains("Failed to read font: missing/font.ttf"));
+
+    assert_eq!(image_build::og_image::parse_color("#1e293B")?, image::Rgb([0x1e, 0x29, 0x3b]));
+    assert_eq!(image_build::og_image::parse_color("ffffff")?, image::Rgb([255, 255, 255]));
+    assert!(image_build::og_image::parse_color("#fff").is_err());
+    assert!(image_build::og_image::parse_color("#gggggg").is_err());
+
+    Ok(())
+}
+
+/// Bundled font for rendering social cards in tests
+fn test_font_path() -> String {
+    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/DejaVuSansMono.ttf").to_string()
+}
+
+fn card_renderer() -> Result<image_build::CardRenderer> {
+    let config = common_models::OgImageConfig {
+        font: Some(test_font_path()),
+        background: "#000000".to_string(),
+        text_color: "#ffffff".to_string(),
+        use_hero_image: true,
+    };
+    Ok(image_build::CardRenderer::load(&config)?.expect("a font is configured"))
+}
+
+#[test]
+fn test_social_card_titles_are_wrapped_to_fit() -> Result<()> {
+    let cards = card_renderer()?;
+
+    let (scale, lines) = cards.layout("Short");
+    assert_eq!(scale.y, 72.0);
+    assert_eq!(lines, vec!["Short"]);
+
+    let (scale, lines) = cards.layout("A title that is long enough to wrap");
+    assert_eq!(scale.y, 72.0);
+    assert_eq!(lines, vec!["A title that is long", "enough to wrap"]);
+
+    // Too long even at the smallest size, so cut short after four lines
+    let (scale, lines) = cards.layout(&["word"; 200].join(" "));
+    assert_eq!(scale.y, 40.0);
+    assert_eq!(lines.len(), 4);
+    assert!(lines[3].ends_with('…'));
+
+    Ok(())
+}
+
+#[test]
+fn test_social_cards_draw_the_title_over_the_background_or_hero() -> Result<()> {
+    let cards = card_renderer()?;
+
+    let card = cards.render("Hello", None);
+    assert_eq!((card.width(), card.height()), (image_build::CARD_WIDTH, image_build::CARD_HEIGHT));
+    assert_eq!(*card.get_pixel(0, 0), image::Rgb([0, 0, 0]));
+    assert!(card.pixels().any(|pixel| *pixel == image::Rgb([255, 255, 255])));
+
+    // The hero image fills the card, darkened behind the title
+    let hero = image::DynamicImage::ImageRgb8(image::RgbImage::from_pixel(100, 50, image::Rgb([255, 0, 0])));
+    let card = cards.render("Hello", Some(&hero));
+    assert_eq!((card.width(), card.height()), (image_build::CARD_WIDTH, image_build::CARD_HEIGHT));
+    assert_eq!(*card.get_pixel(0, 0), image::Rgb([165, 0, 0]));
+
+    Ok(())
+}
+
+#[test]
+fn test_article_cards_are_saved_with_the_article_images() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let article_dir = fixture.path().join("content/blog/post");
+    let output_dir = fixture.path().join("output");
+    fs::create_dir_all(&article_dir)?;
+    let config = common_models::Config::default();
+    let cards = card_renderer()?;
+
+    assert!(cards.render_article_card(&config, &article_dir, "post", "blog", &output_dir)?.is_none());
+
+    fs::write(article_dir.join("index.md"), "---\ntitle: Social Cards\n---\n\nText.\n")?;
+    let card = cards.render_article_card(&config, &article_dir, "post", "blog", &output_dir)?.unwrap();
+    assert!(card.path.starts_with("blog/post/"));
+    assert_eq!(card.size, common_models::SOCIAL_CARD_SIZE);
+    assert_eq!((card.width, card.height), (image_build::CARD_WIDTH, image_build::CARD_HEIGHT));
+    let saved = image::open(output_dir.join(&card.path))?;
+    assert_eq!((saved.width(), saved.height()), (image_build::CARD_WIDTH, image_build::CARD_HEIGHT));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_failed_social_cards_are_skipped() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let mut config = common_test_utils::config_for(&fixture.path().join("content"), &["blog"]);
+    config.images.sizes.clear();
+    config.og_image.font = Some(test_font_path());
+    let config_path = common_test_utils::write_config(fixture.path(), &config);
+
+    // Unreadable frontmatter makes rendering the first card fail
+    for (slug, content) in [("broken", "---\ntitle: [unclosed\n---\n\nText.\n"), ("good", "---\ntitle: Good\n---\n\nText.\n")] {
+        let article_path = common_test_utils::write_article(&fixture.path().join("content"), "blog", slug, content);
+        image::RgbImage::new(40, 20).save(article_path.with_file_name("index.jpg"))?;
+    }
+    let broken_dir = fixture.path().join("content/blog/broken");
+    assert!(card_renderer()?.render_article_card(&config, &broken_dir, "broken", "blog", &fixture.path().join("scratch")).is_err());
+
+    let _config = common_test_utils::use_config(&config_path);
+    let output_dir = fixture.path().join("output");
+    let options = BuildImagesOptions {
+        output_dir: output_dir.clone(),
+        topic: Some("blog".to_string()),
+        ..BuildImagesOptions::default()
+    };
+
+    // The rest of the build goes on, in both the topic and single article paths
+    image_build::build_images(&options)?;
+    let options = BuildImagesOptions { article: Some("broken".to_string()), ..options };
+    image_build::build_images(&options)?;
+
+    let manifest = common_models::ImageManifest::load(&output_dir.join(common_models::IMAGE_MANIFEST_FILE))?.unwrap();
+    let cards: Vec<&str> = manifest
+        .variants
+        .iter()
+        .filter(|variant| variant.size == common_models::SOCIAL_CARD_SIZE)
+        .map(|variant| variant.path.as_str())
+        .collect();
+    assert_eq!(cards.len(), 1);
+    assert!(cards[0].starts_with("blog/good/"));
+
+    Ok(())
+}
+
+#[test]
+fn test_svg_images_are_minified_and_rasterized() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let article_dir = fixture.path().join("content/blog/post");
+    let output_dir = fixture.path().join("output");
+    fs::create_dir_all(article_dir.join("diagrams"))?;
+    let svg_path = article_dir.join("diagrams/flow.svg");
+    fs::write(
+        &svg_path,
+        "<!-- comment -->\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"100\">\n  <rect width=\"200\" height=\"100\" fill=\"#1e293b\" />\n</svg>\n",
+    )?;
+    fs::write(article_dir.join("index.jpg"), "")?;
+
+    assert_eq!(image_build::find_svg_images(&article_dir), vec![svg_path.clone()]);
+
+    let mut config = common_models::Config::default();
+    config.images.sizes.clear();
+    config.images.sizes.insert(
+        "small".to_string(),
+        common_models::ImageSize {
+            width: 100,
+            height: 50,
+            description: "Small".to_string(),
+        },
+    );
+
+    let variants = image_build::process_svg_variants(&svg_path, &article_dir, "post", "blog", &output_dir, &config, false)?;
+    assert_eq!(variants.len(), 1);
+    assert_eq!(variants[0].path, "blog/post/diagrams/flow.svg");
+    assert_eq!(variants[0].size, common_models::ORIGINAL_SIZE);
+    assert_eq!((variants[0].width, variants[0].height), (200, 100));
+    assert_eq!(variants[0].format, "svg");
+    let minified = fs::read_to_string(output_dir.join("blog/post/diagrams/flow.svg"))?;
+    assert!(!minified.contains("comment"));
+
+    let variants = image_build::process_svg_variants(&svg_path, &article_dir, "post", "blog", &output_dir, &config, true)?;
+    assert_eq!(variants.len(), 2);
+    assert_eq!(variants[1].path, "blog/post/diagrams/flow-small.png");
+    assert_eq!((variants[1].width, variants[1].height), (100, 50));
+    let png = image::open(output_dir.join("blog/post/diagrams/flow-small.png"))?;
+    assert_eq!((png.width(), png.height()), (100, 50));
+
+    Ok(())
+}
+
+#[test]
+fn test_animated_gifs_get_a_poster() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let article_dir = fixture.path().join("content/blog/post");
+    let output_dir = fixture.path().join("output");
+    fs::create_dir_all(&article_dir)?;
+
+    let gif_path = article_dir.join("deploy.gif");
+    let mut encoder = image::codecs::gif::GifEncoder::new(fs::File::create(&gif_path)?);
+    let frames = [image::Rgba([255, 0, 0, 255]), image::Rgba([0, 0, 255, 255])]
+        .map(|color| image::Frame::new(image::RgbaImage::from_pixel(40, 20, color)));
+    encoder.encode_frames(frames)?;
+    drop(encoder);
+    image::RgbaImage::new(40, 20).save(article_dir.join("still.gif"))?;
+
+    assert_eq!(image_build::find_animated_media(&article_dir), vec![gif_path.clone()]);
+
+    let variants = image_build::process_animated_media(&gif_path, &article_dir, "post", "blog", &output_dir, false)?;
+    let paths: Vec<(&str, &str)> = variants.iter().map(|variant| (variant.path.as_str(), variant.size.as_str())).collect();
+    assert_eq!(paths, vec![("blog/post/deploy.gif", "original"), ("blog/post/deploy-poster.jpg", "poster")]);
+    assert!(variants.iter().all(|variant| (variant.width, variant.height) == (40, 20)));
+
+    let poster = image::open(output_dir.join("blog/post/deploy-poster.jpg"))?.to_rgb8();
+    assert!(poster.get_pixel(20, 10)[0] > 200);
+    assert!(output_dir.join("blog/post/deploy.gif").exists());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_failed_animated_media_is_skipped() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let mut config = common_test_utils::config_for(&fixture.path().join("content"), &["blog"]);
+    config.images.sizes.clear();
+    let config_path = common_test_utils::write_config(fixture.path(), &config);
+
+    let article_dir = fixture.path().join("content/blog/post");
+    fs::create_dir_all(&article_dir)?;
+    image::RgbImage::new(40, 20).save(article_dir.join("index.jpg"))?;
+    let clip_path = article_dir.join("clip.mp4");
+    fs::write(&clip_path, "not a video")?;
+
+    // A directory in the way of the copy makes processing the video fail
+    let output_dir = fixture.path().join("output");
+    fs::create_dir_all(output_dir.join("blog/post/clip.mp4"))?;
+    assert!(image_build::process_animated_media(&clip_path, &article_dir, "post", "blog", &output_dir, false).is_err());
+
+    let _config = common_test_utils::use_config(&config_path);
+    let options = BuildImagesOptions {
+        output_dir,
+        topic: Some("blog".to_string()),
+        ..BuildImagesOptions::default()
+    };
+
+    // The rest of the build goes on, in both the topic and single article paths
+    assert_eq!(image_build::build_images(&options)?, (1, 2, 1, 0));
+    let options = BuildImagesOptions { article: Some("post".to_string()), ..options };
+    assert_eq!(image_build::build_images(&options)?, (1, 2, 1, 0));
+
+    Ok(())
+}
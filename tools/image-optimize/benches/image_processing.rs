@@ -27,6 +27,7 @@ fn bench_jpeg_processing(c: &mut Criterion) {
         ],
         quality: 85,
         preserve_metadata: false,
+        minify_svg: false,
         topic: Some("test-topic".into()),
     };
     
@@ -55,6 +56,7 @@ fn bench_webp_processing(c: &mut Criterion) {
         ],
         quality: 85,
         preserve_metadata: false,
+        minify_svg: false,
         topic: Some("test-topic".into()),
     };
     
@@ -83,6 +85,7 @@ fn bench_avif_processing(c: &mut Criterion) {
         ],
         quality: 85,
         preserve_metadata: false,
+        minify_svg: false,
         topic: Some("test-topic".into()),
     };
     
@@ -107,6 +110,7 @@ fn bench_single_size_processing(c: &mut Criterion) {
         sizes: vec![SizeVariant::Medium(800)],
         quality: 85,
         preserve_metadata: false,
+        minify_svg: false,
         topic: Some("test-topic".into()),
     };
     
@@ -126,6 +130,7 @@ fn bench_single_size_processing(c: &mut Criterion) {
             sizes: vec![SizeVariant::Medium(800)],
             quality: 85,
             preserve_metadata: false,
+            minify_svg: false,
             topic: Some("test-topic".into()),
         };
         
@@ -146,6 +151,7 @@ fn bench_single_size_processing(c: &mut Criterion) {
             sizes: vec![SizeVariant::Medium(800)],
             quality: 85,
             preserve_metadata: false,
+            minify_svg: false,
             topic: Some("test-topic".into()),
         };
         
@@ -177,6 +183,7 @@ fn bench_quality_impact(c: &mut Criterion) {
             sizes: vec![SizeVariant::Large(1200)],
             quality,
             preserve_metadata: false,
+            minify_svg: false,
             topic: Some("test-topic".into()),
         };
         
@@ -196,6 +203,7 @@ fn bench_quality_impact(c: &mut Criterion) {
                 sizes: vec![SizeVariant::Large(1200)],
                 quality,
                 preserve_metadata: false,
+                minify_svg: false,
                 topic: Some("test-topic".into()),
             };
             
@@ -216,6 +224,7 @@ fn bench_quality_impact(c: &mut Criterion) {
                 sizes: vec![SizeVariant::Large(1200)],
                 quality,
                 preserve_metadata: false,
+                minify_svg: false,
                 topic: Some("test-topic".into()),
             };
             
@@ -2,14 +2,21 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::{Write, BufWriter};
 use anyhow::Result;
+use colored::*;
+use common_cli::DisplayResult;
 use common_config::load_config;
 use common_errors::{WritingError, OptionValidationExt};
 use image::{GenericImageView, DynamicImage};
 use image::imageops::FilterType;
+use serde::Serialize;
 use thiserror::Error;
 
+pub mod svg;
+pub use svg::{minify_svg, minify_svg_file};
+
 /// Supported output formats for image optimization
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     Jpeg,
     #[cfg(feature = "webp")]
@@ -54,7 +61,8 @@ pub enum OptimizeError {
 }
 
 /// Image size variants for responsive images
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SizeVariant {
     Original,
     Large(u32),    // Default: 1200px width
@@ -102,6 +110,8 @@ pub struct OptimizeOptions {
     pub quality: u8,
     /// Whether to preserve original image metadata
     pub preserve_metadata: bool,
+    /// Whether [`optimize_images`] minifies SVG sources, which rewrites them in place
+    pub minify_svg: bool,
 }
 
 impl Default for OptimizeOptions {
@@ -114,6 +124,7 @@ impl Default for OptimizeOptions {
             sizes: vec![SizeVariant::Original],
             quality: 85,
             preserve_metadata: false,
+            minify_svg: false,
         }
     }
 }
@@ -225,21 +236,21 @@ fn save_in_format(img: &DynamicImage, path: &Path, format: OutputFormat, quality
 }
 
 /// Results of image optimization
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct OptimizationResult {
     pub original_size: u64,
     pub format_results: Vec<FormatResult>,
 }
 
 /// Results for a specific format
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct FormatResult {
     pub format: OutputFormat,
     pub size_results: Vec<SizeResult>,
 }
 
 /// Results for a specific size variant
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct SizeResult {
     pub variant: SizeVariant,
     pub dimensions: (u32, u32),
@@ -247,6 +258,39 @@ pub struct SizeResult {
     pub path: PathBuf,
 }
 
+impl DisplayResult for OptimizationResult {
+    fn to_display(&self) -> String {
+        let mut lines = vec![
+            format!("{} Image optimized successfully", "Success:".green().bold()),
+            format!("  {} {:.2} MB", "Original size:".cyan().bold(), self.original_size as f64 / 1_048_576.0),
+        ];
+
+        for format_result in &self.format_results {
+            lines.push(format!("\n  {} {:?}", "Format:".yellow().bold(), format_result.format));
+
+            for size_result in &format_result.size_results {
+                let ratio = if self.original_size > 0 {
+                    (size_result.file_size as f64 / self.original_size as f64) * 100.0
+                } else {
+                    0.0
+                };
+
+                lines.push(format!(
+                    "    {} {}: {}x{}, {:.2} MB ({:.1}%)",
+                    size_result.variant.name().cyan().bold(),
+                    size_result.path.display(),
+                    size_result.dimensions.0,
+                    size_result.dimensions.1,
+                    size_result.file_size as f64 / 1_048_576.0,
+                    ratio
+                ));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
 /// Main function to optimize an image for an article
 pub fn optimize_image(options: &OptimizeOptions) -> Result<OptimizationResult> {
     // Validate source image exists
@@ -432,6 +476,7 @@ pub fn optimize_images(options: &OptimizeOptions) -> Result<usize> {
                         max_width,
                         max_height,
                         format,
+                        options.minify_svg,
                     )?;
                 }
             } else {
@@ -454,6 +499,7 @@ pub fn optimize_images(options: &OptimizeOptions) -> Result<usize> {
                             max_width,
                             max_height,
                             format,
+                            options.minify_svg,
                         )?;
                     }
 
@@ -486,6 +532,7 @@ pub fn optimize_images(options: &OptimizeOptions) -> Result<usize> {
                             max_width,
                             max_height,
                             format,
+                            options.minify_svg,
                         )?;
                     }
                 }
@@ -518,6 +565,7 @@ pub fn optimize_images(options: &OptimizeOptions) -> Result<usize> {
                             max_width,
                             max_height,
                             format,
+                            options.minify_svg,
                         )?;
                     }
                 }
@@ -530,7 +578,9 @@ pub fn optimize_images(options: &OptimizeOptions) -> Result<usize> {
 
 /// Optimize images in a directory
 ///
-/// This function optimizes all images in a directory.
+/// This function optimizes all images in a directory. SVGs are minified in
+/// place rather than converted, and only when `minify_svg` is set, since that
+/// rewrites the source files.
 ///
 /// # Parameters
 ///
@@ -539,6 +589,7 @@ pub fn optimize_images(options: &OptimizeOptions) -> Result<usize> {
 /// * `max_width` - Maximum width
 /// * `max_height` - Maximum height
 /// * `format` - Output format (webp, jpeg, png)
+/// * `minify_svg` - Whether to minify SVG sources in place
 ///
 /// # Returns
 ///
@@ -553,6 +604,7 @@ fn optimize_images_in_dir(
     max_width: Option<u32>,
     max_height: Option<u32>,
     format: OutputFormat,
+    minify_svg: bool,
 ) -> Result<usize> {
     let mut optimized_count = 0;
 
@@ -566,7 +618,12 @@ fn optimize_images_in_dir(
                 .unwrap_or("")
                 .to_lowercase();
 
-            if ["jpg", "jpeg", "png", "gif", "webp"].contains(&extension.as_str()) {
+            if extension == "svg" {
+                if minify_svg {
+                    minify_svg_file(&path)?;
+                    optimized_count += 1;
+                }
+            } else if ["jpg", "jpeg", "png", "gif", "webp"].contains(&extension.as_str()) {
                 // Load the image
                 let img = image::open(&path)?;
 
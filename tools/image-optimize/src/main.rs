@@ -1,6 +1,7 @@
 use anyhow::Result;
 use clap::{Parser, ValueEnum};
 use colored::*;
+use common_cli::OutputFormatArgs;
 use image_optimize::{
     OptimizeOptions, OutputFormat, SizeVariant,
     optimize_image, default_formats, default_size_variants
@@ -79,6 +80,9 @@ struct Args {
     /// Preserve original image metadata
     #[arg(long, default_value = "false")]
     preserve_metadata: bool,
+
+    #[command(flatten)]
+    output: OutputFormatArgs,
 }
 
 fn main() -> Result<()> {
@@ -93,57 +97,42 @@ fn main() -> Result<()> {
         sizes: args.sizes.to_size_variants(),
         quality: args.quality,
         preserve_metadata: args.preserve_metadata,
+        minify_svg: false,
     };
     
-    println!("{} {}", "Optimizing image for article:".yellow().bold(), options.article.as_ref().unwrap_or(&"all".to_string()));
-    println!("  {} {}", "Source:".green().bold(), options.source.display());
-    
-    // Generate formats string for display
-    let formats_str = options.formats.iter()
-        .map(|f| format!("{:?}", f))
-        .collect::<Vec<_>>()
-        .join(", ");
-    println!("  {} {}", "Formats:".cyan().bold(), formats_str);
-    
-    // Generate sizes string for display
-    let sizes_str = options.sizes.iter()
-        .map(|s| s.name())
-        .collect::<Vec<_>>()
-        .join(", ");
-    println!("  {} {}", "Sizes:".cyan().bold(), sizes_str);
-    
-    println!("  {} {}%", "Quality:".cyan().bold(), options.quality);
+    let formatter = args.output.formatter();
+    if formatter.is_plain() {
+        println!("{} {}", "Optimizing image for article:".yellow().bold(), options.article.as_ref().unwrap_or(&"all".to_string()));
+        println!("  {} {}", "Source:".green().bold(), options.source.display());
+
+        // Generate formats string for display
+        let formats_str = options.formats.iter()
+            .map(|f| format!("{:?}", f))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("  {} {}", "Formats:".cyan().bold(), formats_str);
+
+        // Generate sizes string for display
+        let sizes_str = options.sizes.iter()
+            .map(|s| s.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("  {} {}", "Sizes:".cyan().bold(), sizes_str);
+
+        println!("  {} {}%", "Quality:".cyan().bold(), options.quality);
+    }
     
     // Optimize the image using the library function
     let result = optimize_image(&options)?;
     
-    // Print summary of results
-    println!("\n{} Image optimized successfully", "Success:".green().bold());
-    println!("  {} {:.2} MB", "Original size:".cyan().bold(), result.original_size as f64 / 1_048_576.0);
-    
-    // Display results for each format
-    for format_result in &result.format_results {
-        println!("\n  {} {:?}", "Format:".yellow().bold(), format_result.format);
-        
-        for size_result in &format_result.size_results {
-            let ratio = if result.original_size > 0 {
-                (size_result.file_size as f64 / result.original_size as f64) * 100.0
-            } else {
-                0.0
-            };
-            
-            println!("    {} {}: {}x{}, {:.2} MB ({:.1}%)",
-                size_result.variant.name().cyan().bold(),
-                size_result.path.display(),
-                size_result.dimensions.0,
-                size_result.dimensions.1,
-                size_result.file_size as f64 / 1_048_576.0,
-                ratio
-            );
-        }
+    if formatter.is_plain() {
+        println!();
+    }
+    formatter.print(&result)?;
+
+    if formatter.is_plain() {
+        println!("\nRun './writing images build --article={}' to generate HTML references for these images.", options.article.as_ref().unwrap_or(&"all".to_string()));
     }
-    
-    println!("\nRun './writing images build --article={}' to generate HTML references for these images.", options.article.as_ref().unwrap_or(&"all".to_string()));
     
     Ok(())
-} 
\ No newline at end of file
+}
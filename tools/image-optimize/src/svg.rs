@@ -0,0 +1,71 @@
+//! # SVG Minification
+//!
+//! SVG diagrams are text, so they are optimized by removing what doesn't
+//! change how they look: the XML declaration, comments, `<metadata>`, data
+//! kept by editors such as Inkscape and Sketch, and whitespace between tags.
+//! Whitespace inside `<text>` elements is collapsed but kept, since it
+//! separates words.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Namespace prefixes of editor data that doesn't affect rendering
+const EDITOR_PREFIXES: &[&str] = &["inkscape", "sodipodi", "sketch", "serif"];
+
+/// Remove comments, metadata, editor data and extra whitespace from an SVG
+pub fn minify_svg(svg: &str) -> String {
+    let prefixes = EDITOR_PREFIXES.join("|");
+    let removed = [
+        r"(?s)<\?xml.*?\?>".to_string(),
+        r"<!DOCTYPE[^>\[]*>".to_string(),
+        r"(?s)<!--.*?-->".to_string(),
+        r"(?s)<metadata\b[^>]*/>|<metadata\b.*?</metadata>".to_string(),
+        format!(r"(?s)<(?:{0}):[\w.-]+\b[^>]*/>|<(?:{0}):[\w.-]+\b.*?</(?:{0}):[\w.-]+>", prefixes),
+        format!(r#"\s(?:xmlns:(?:{0})|(?:{0}):[\w.-]+)="[^"]*""#, prefixes),
+    ];
+
+    let mut minified = svg.to_string();
+    for pattern in &removed {
+        let pattern = Regex::new(pattern).expect("valid regex");
+        minified = pattern.replace_all(&minified, "").into_owned();
+    }
+
+    // Whitespace between tags is dropped, except inside text, where it separates words
+    let text = Regex::new(r"(?s)<text\b.*?</text>").expect("valid regex");
+    let whitespace = Regex::new(r"\s+").expect("valid regex");
+    let between_tags = Regex::new(r">\s+<").expect("valid regex");
+
+    // Markup between text elements starts and ends at a tag, so it can be trimmed
+    let minify_markup = |markup: &str| {
+        let markup = whitespace.replace_all(markup, " ");
+        between_tags.replace_all(markup.trim(), "><").into_owned()
+    };
+
+    let mut output = String::with_capacity(minified.len());
+    let mut last = 0;
+    for found in text.find_iter(&minified) {
+        output.push_str(&minify_markup(&minified[last..found.start()]));
+        output.push_str(&whitespace.replace_all(found.as_str(), " "));
+        last = found.end();
+    }
+    output.push_str(&minify_markup(&minified[last..]));
+
+    output
+}
+
+/// Minify an SVG file in place, returning its size before and after
+///
+/// The file is only rewritten when minifying makes it smaller.
+pub fn minify_svg_file(path: &Path) -> Result<(u64, u64)> {
+    let svg = fs::read_to_string(path).with_context(|| format!("Failed to read SVG: {}", path.display()))?;
+    let minified = minify_svg(&svg);
+
+    if minified.len() < svg.len() {
+        fs::write(path, &minified).with_context(|| format!("Failed to write SVG: {}", path.display()))?;
+        Ok((svg.len() as u64, minified.len() as u64))
+    } else {
+        Ok((svg.len() as u64, svg.len() as u64))
+    }
+}
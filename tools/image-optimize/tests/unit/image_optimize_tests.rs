@@ -27,6 +27,7 @@ impl From<OptimizeTestOptions> for OptimizeOptions {
             sizes: options.sizes,
             quality: options.quality,
             preserve_metadata: options.preserve_metadata,
+            minify_svg: false,
         }
     }
 }
@@ -51,6 +52,7 @@ impl From<TestImageOptions> for OptimizeOptions {
             sizes: vec![SizeVariant::Original],
             quality: options.quality,
             preserve_metadata: false,
+            minify_svg: false,
         }
     }
 }
@@ -753,4 +755,62 @@ fn test_optimize_image_with_test_options_struct() -> Result<()> {
     assert!(!result.format_results.is_empty());
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_minify_svg_strips_metadata_and_whitespace() -> Result<()> {
+    let svg = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Created with Inkscape -->
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape" inkscape:version="1.3" width="100" height="50">
+  <metadata><rdf:RDF></rdf:RDF></metadata>
+  <sodipodi:namedview id="view" />
+  <rect   x="0" y="0"
+          width="100" height="50" />
+  <text x="10" y="30">Request   flow</text>
+</svg>
+"#;
+
+    assert_eq!(
+        image_optimize::minify_svg(svg),
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50"><rect x="0" y="0" width="100" height="50" /><text x="10" y="30">Request flow</text></svg>"#
+    );
+
+    let fixture = TestFixture::new()?;
+    let path = fixture.path().join("diagram.svg");
+    fs::write(&path, svg)?;
+    let (before, after) = image_optimize::minify_svg_file(&path)?;
+    assert_eq!(before, svg.len() as u64);
+    assert_eq!(after, fs::metadata(&path)?.len());
+    assert!(after < before);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_optimize_images_only_minifies_svgs_when_asked() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let config_path = common_test_utils::write_repository(fixture.path(), &["blog"]);
+    let images_dir = fixture.path().join("content/blog/post/images");
+    fs::create_dir_all(&images_dir)?;
+    let svg = "<!-- comment -->\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\" height=\"10\" />\n";
+    let path = images_dir.join("diagram.svg");
+    fs::write(&path, svg)?;
+
+    let _config = common_test_utils::use_config(&config_path);
+    let mut options = OptimizeOptions {
+        article: Some("post".to_string()),
+        topic: Some("blog".to_string()),
+        ..OptimizeOptions::default()
+    };
+
+    // Source SVGs are left alone by default
+    assert_eq!(image_optimize::optimize_images(&options)?, 0);
+    assert_eq!(fs::read_to_string(&path)?, svg);
+
+    options.minify_svg = true;
+    assert_eq!(image_optimize::optimize_images(&options)?, 1);
+    assert!(fs::read_to_string(&path)?.len() < svg.len());
+
+    Ok(())
+}
@@ -101,7 +101,7 @@ pub fn collect_articles(config: &Config, include_drafts: bool) -> Result<Vec<Art
                 slug: frontmatter.slug.clone().unwrap_or_default(),
                 topics: frontmatter.topics.clone().unwrap_or_default(),
                 tags: frontmatter.tags.clone().unwrap_or_default(),
-                published: frontmatter.published_at.clone().unwrap_or_default(),
+                published: frontmatter.published_at.as_ref().map(ToString::to_string).unwrap_or_default(),
                 path: relative_path.to_path_buf(),
                 content: markdown_content.clone(),
                 draft: frontmatter.is_draft.unwrap_or(false),
@@ -0,0 +1,262 @@
+//! # Topic Management
+//!
+//! Adds, renames and deletes topics, keeping `config.yaml` and the content
+//! directories in step. Renaming a topic moves its content with content-move,
+//! so frontmatter, links to the content and redirects are updated too.
+
+use anyhow::Result;
+use common_models::{Config, TopicConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for adding a topic
+#[derive(Debug, Clone, Default)]
+pub struct AddTopicOptions {
+    /// Topic key, used in URLs and frontmatter
+    pub key: String,
+    /// Display name
+    pub name: String,
+    /// Description
+    pub description: String,
+    /// Directory relative to the content base directory (defaults to the key)
+    pub directory: Option<String>,
+}
+
+/// Add a topic to the configuration and create its directory
+///
+/// # Errors
+///
+/// Returns an error if the key is invalid or already used, or another topic
+/// already uses the directory
+pub fn add_topic(options: &AddTopicOptions) -> Result<TopicConfig> {
+    let key = common_validation::validate_slug(&options.key)?;
+    let mut config = common_config::load_config()?;
+
+    if config.content.topics.contains_key(&key) {
+        return Err(anyhow::anyhow!("Topic already exists: {}", key));
+    }
+
+    let directory = options.directory.clone().unwrap_or_else(|| key.clone());
+    ensure_directory_unused(&config, &directory, None)?;
+
+    let topic = TopicConfig {
+        name: options.name.clone(),
+        description: options.description.clone(),
+        directory,
+    };
+
+    fs::create_dir_all(topic_path(&config, &topic))?;
+    config.content.topics.insert(key, topic.clone());
+    common_config::save_config(&config)?;
+
+    Ok(topic)
+}
+
+/// Rename a topic key and/or move it to a new directory
+///
+/// When the key changes, every article is moved to the new topic as a single
+/// batch with content-move, updating the `topics` frontmatter, rewriting links
+/// and recording redirects. If any move fails, the batch is rolled back and
+/// the configuration is left unchanged. When only the directory changes, the
+/// directory is renamed in place since URLs are unaffected.
+///
+/// # Parameters
+///
+/// * `key` - Current topic key
+/// * `new_key` - New topic key
+/// * `new_directory` - New directory (defaults to the current directory)
+///
+/// # Errors
+///
+/// Returns an error if the topic does not exist, the new key or directory is
+/// already in use, or the content cannot be moved
+pub fn rename_topic(key: &str, new_key: &str, new_directory: Option<&str>) -> Result<TopicConfig> {
+    let new_key = common_validation::validate_slug(new_key)?;
+    let mut config = common_config::load_config()?;
+
+    let topic = config
+        .content
+        .topics
+        .get(key)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Topic not found: {}", key))?;
+
+    if new_key != key && config.content.topics.contains_key(&new_key) {
+        return Err(anyhow::anyhow!("Topic already exists: {}", new_key));
+    }
+
+    let renamed = TopicConfig {
+        directory: new_directory.map(str::to_string).unwrap_or_else(|| topic.directory.clone()),
+        ..topic.clone()
+    };
+    if renamed.directory != topic.directory {
+        ensure_directory_unused(&config, &renamed.directory, Some(key))?;
+    }
+
+    if new_key == key {
+        if renamed.directory == topic.directory {
+            return Err(anyhow::anyhow!("No changes requested: {}", key));
+        }
+
+        let to = topic_path(&config, &renamed);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if topic_path(&config, &topic).exists() {
+            fs::rename(topic_path(&config, &topic), &to)?;
+        } else {
+            fs::create_dir_all(&to)?;
+        }
+
+        config.content.topics.insert(new_key, renamed.clone());
+        common_config::save_config(&config)?;
+        return Ok(renamed);
+    }
+
+    // Both topics must be configured while the content moves
+    let original = config.clone();
+    let new_path = topic_path(&config, &renamed);
+    let created = !new_path.exists();
+    fs::create_dir_all(&new_path)?;
+    config.content.topics.insert(new_key.clone(), renamed.clone());
+    common_config::save_config(&config)?;
+
+    // Put the configuration back and remove the directory made for the new
+    // topic, which is empty again once a failed batch has been rolled back
+    let restore = || -> Result<()> {
+        common_config::save_config(&original)?;
+        if created && new_path.exists() && is_empty_dir(&new_path)? {
+            fs::remove_dir(&new_path)?;
+        }
+        Ok(())
+    };
+
+    let moves: Vec<content_move::MoveOptions> = content_move::list_all_content()?
+        .into_iter()
+        .filter(|(topic_key, _, _)| topic_key == key)
+        .map(|(_, slug, _)| content_move::MoveOptions {
+            slug: Some(slug),
+            new_slug: None,
+            topic: Some(key.to_string()),
+            new_topic: Some(new_key.clone()),
+            update_frontmatter: true,
+            dry_run: false,
+        })
+        .collect();
+
+    let report = match content_move::move_many(moves) {
+        Ok(report) => report,
+        Err(err) => {
+            restore()?;
+            return Err(err);
+        }
+    };
+
+    if !report.is_success() {
+        restore()?;
+        let failures: Vec<String> = report
+            .items
+            .iter()
+            .filter_map(|item| match &item.status {
+                content_move::MoveItemStatus::Failed(reason) => {
+                    Some(format!("{}/{}: {}", item.plan.from_topic, item.plan.from_slug, reason))
+                }
+                _ => None,
+            })
+            .collect();
+        return Err(anyhow::anyhow!("Failed to move topic content: {}", failures.join("; ")));
+    }
+
+    // The old directory is empty now unless it is shared with the new topic
+    let old_path = topic_path(&config, &topic);
+    if renamed.directory != topic.directory && old_path.exists() && is_empty_dir(&old_path)? {
+        fs::remove_dir(&old_path)?;
+    }
+
+    config.content.topics.remove(key);
+    common_config::save_config(&config)?;
+
+    Ok(renamed)
+}
+
+/// Delete a topic that has no content
+///
+/// The topic is removed from the configuration and its directory is removed
+/// if it is empty.
+///
+/// # Errors
+///
+/// Returns an error if the topic does not exist or still contains content
+pub fn delete_topic(key: &str) -> Result<TopicConfig> {
+    let mut config = common_config::load_config()?;
+
+    let topic = config
+        .content
+        .topics
+        .get(key)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Topic not found: {}", key))?;
+
+    let path = topic_path(&config, &topic);
+    if path.exists() {
+        // Articles are directories, but loose Markdown files are content too
+        let content: Vec<String> = fs::read_dir(&path)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir() || is_markdown(&entry.path()))
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        if !content.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Topic '{}' still contains content: {}",
+                key,
+                content.join(", ")
+            ));
+        }
+
+        if is_empty_dir(&path)? {
+            fs::remove_dir(&path)?;
+        }
+    }
+
+    config.content.topics.remove(key);
+    common_config::save_config(&config)?;
+
+    Ok(topic)
+}
+
+/// Get the absolute directory of a topic
+fn topic_path(config: &Config, topic: &TopicConfig) -> PathBuf {
+    Path::new(&config.content.base_dir).join(&topic.directory)
+}
+
+/// Fail if another topic (other than `except`) already uses `directory`
+fn ensure_directory_unused(config: &Config, directory: &str, except: Option<&str>) -> Result<()> {
+    let used_by = config
+        .content
+        .topics
+        .iter()
+        .find(|(key, topic)| Some(key.as_str()) != except && topic.directory == directory);
+
+    match used_by {
+        Some((key, _)) => Err(anyhow::anyhow!(
+            "Directory '{}' is already used by topic '{}'",
+            directory,
+            key
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Check whether a directory has no entries
+fn is_empty_dir(path: &Path) -> Result<bool> {
+    Ok(fs::read_dir(path)?.next().is_none())
+}
+
+/// Check whether a path names a Markdown file
+fn is_markdown(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| matches!(extension.to_lowercase().as_str(), "md" | "mdx"))
+}
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use colored::*;
+use topic_manage::{add_topic, delete_topic, rename_topic, AddTopicOptions};
+
+#[derive(Parser)]
+#[command(author, version, about = "Add, rename and delete topics")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Add a topic and create its directory
+    Add {
+        /// Topic key
+        key: String,
+
+        /// Display name
+        #[arg(short, long)]
+        name: String,
+
+        /// Description
+        #[arg(short, long, default_value = "")]
+        description: String,
+
+        /// Directory (defaults to the key)
+        #[arg(long)]
+        directory: Option<String>,
+    },
+
+    /// Rename a topic, moving its content and updating frontmatter
+    Rename {
+        /// Current topic key
+        key: String,
+
+        /// New topic key
+        new_key: String,
+
+        /// New directory (defaults to the current directory)
+        #[arg(long)]
+        directory: Option<String>,
+    },
+
+    /// Delete a topic that has no content
+    Delete {
+        /// Topic key
+        key: String,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Add { key, name, description, directory } => {
+            let topic = add_topic(&AddTopicOptions { key: key.clone(), name, description, directory })?;
+            println!("{} Topic added: {} ({})", "SUCCESS:".green().bold(), key.yellow(), topic.directory);
+        }
+        Commands::Rename { key, new_key, directory } => {
+            let topic = rename_topic(&key, &new_key, directory.as_deref())?;
+            println!(
+                "{} Topic renamed: {} -> {} ({})",
+                "SUCCESS:".green().bold(),
+                key.yellow(),
+                new_key.yellow(),
+                topic.directory
+            );
+        }
+        Commands::Delete { key } => {
+            delete_topic(&key)?;
+            println!("{} Topic deleted: {}", "SUCCESS:".green().bold(), key.yellow());
+        }
+    }
+
+    Ok(())
+}
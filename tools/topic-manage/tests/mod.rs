@@ -0,0 +1,5 @@
+//! Test modules for topic-manage
+
+// Unit tests
+#[path = "unit/topic_manage_tests.rs"]
+mod topic_manage_tests;
@@ -0,0 +1,103 @@
+use common_test_utils::{use_config, write_article, write_repository};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use topic_manage::{add_topic, delete_topic, rename_topic, AddTopicOptions};
+
+#[cfg(test)]
+mod topic_manage_tests {
+    use super::*;
+
+    fn seed_repository(root: &Path) -> PathBuf {
+        let content = "---\ntitle: First Post\ntopics:\n  - blog\n---\nBody\n";
+        write_article(&root.join("content"), "blog", "first-post", content);
+        write_repository(root, &["blog"])
+    }
+
+    #[test]
+    fn test_add_and_delete_topic() {
+        let temp_dir = TempDir::new().unwrap();
+        let _config = use_config(&seed_repository(temp_dir.path()));
+
+        let options = AddTopicOptions {
+            key: "notes".to_string(),
+            name: "Notes".to_string(),
+            description: "Short notes".to_string(),
+            directory: None,
+        };
+        add_topic(&options).unwrap();
+        assert!(temp_dir.path().join("content/notes").is_dir());
+        assert!(common_config::load_config().unwrap().content.topics.contains_key("notes"));
+
+        let err = add_topic(&options).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        let err = add_topic(&AddTopicOptions {
+            key: "other".to_string(),
+            directory: Some("blog".to_string()),
+            ..options.clone()
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("already used"));
+
+        let err = delete_topic("blog").unwrap_err();
+        assert!(err.to_string().contains("still contains content"));
+        assert!(temp_dir.path().join("content/blog/first-post").exists());
+
+        delete_topic("notes").unwrap();
+        assert!(!temp_dir.path().join("content/notes").exists());
+        assert!(!common_config::load_config().unwrap().content.topics.contains_key("notes"));
+    }
+
+    #[test]
+    fn test_rename_topic_moves_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let _config = use_config(&seed_repository(temp_dir.path()));
+
+        rename_topic("blog", "articles", Some("articles")).unwrap();
+
+        let config = common_config::load_config().unwrap();
+        assert!(!config.content.topics.contains_key("blog"));
+        assert_eq!(config.content.topics["articles"].directory, "articles");
+
+        let moved = temp_dir.path().join("content/articles/first-post/first-post.md");
+        let content = fs::read_to_string(moved).unwrap();
+        assert!(content.contains("articles"));
+        assert!(!content.contains("- blog"));
+        assert!(!temp_dir.path().join("content/blog").exists());
+
+        assert!(rename_topic("blog", "posts", None).is_err());
+    }
+
+    #[test]
+    fn test_delete_topic_with_loose_markdown_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let _config = use_config(&seed_repository(temp_dir.path()));
+        add_topic(&AddTopicOptions {
+            key: "notes".to_string(),
+            name: "Notes".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        fs::write(temp_dir.path().join("content/notes/loose.md"), "---\ntitle: Loose\n---\n").unwrap();
+
+        let err = delete_topic("notes").unwrap_err();
+        assert!(err.to_string().contains("loose.md"));
+        assert!(common_config::load_config().unwrap().content.topics.contains_key("notes"));
+    }
+
+    #[test]
+    fn test_failed_rename_removes_new_topic_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let _config = use_config(&seed_repository(temp_dir.path()));
+        write_article(&temp_dir.path().join("content"), "blog", "broken", "---\ntitle: [unclosed\n---\nBody\n");
+
+        assert!(rename_topic("blog", "articles", Some("articles")).is_err());
+
+        assert!(!temp_dir.path().join("content/articles").exists());
+        assert!(temp_dir.path().join("content/blog/first-post/first-post.md").exists());
+        let config = common_config::load_config().unwrap();
+        assert!(config.content.topics.contains_key("blog"));
+        assert!(!config.content.topics.contains_key("articles"));
+    }
+}
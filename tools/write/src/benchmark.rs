@@ -1,3 +1,8 @@
+//! # Benchmark Analysis
+//!
+//! `write build benchmark` compares benchmark results against a baseline
+//! with benchmark-analyze and writes the report.
+
 use std::path::PathBuf;
 use anyhow::Result;
 use benchmark_analyze::{collect_results, generate_report, output_json_report, output_markdown_report};
@@ -21,7 +26,7 @@ pub fn analyze_benchmarks(
     // Generate report
     let report_data = generate_report(
         &current_results,
-        baseline_results.as_ref().map(|v| &**v),
+        baseline_results.as_deref(),
         threshold,
     );
 
@@ -1,78 +1,60 @@
 //! # CLI Module
 //!
-//! This module defines the CLI interface for the application.
+//! This module defines the CLI interface for the application: one `write`
+//! binary whose subcommands dispatch into the tool libraries, with the
+//! flags every command shares defined once in [`GlobalArgs`].
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use common_cli::OutputFormatArgs;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(
+    name = "write",
     author,
     version,
     about = "Content Management CLI",
     long_about = "A tool for managing writing content, topics, images, and build processes."
 )]
 pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
-#[derive(Subcommand, Debug)]
-pub enum Commands {
-    /// Content management commands
-    #[command(subcommand)]
-    Content(ContentCommands),
-
-    /// Topic management commands
-    #[command(subcommand)]
-    Topic(TopicCommands),
-
-    /// Image management commands
-    #[command(subcommand)]
-    Image(ImageCommands),
-
-    /// Build management commands
-    #[command(subcommand)]
-    Build(BuildCommands),
-
-    /// Content migration commands
-    #[command(subcommand)]
-    Migrate(MigrateCommands),
-
-    /// Generate statistics about content
-    Stats {
-        /// Generate statistics for a specific content slug
-        #[arg(short, long)]
-        slug: Option<String>,
-
-        /// Generate statistics for a specific topic
-        #[arg(short, long)]
-        topic: Option<String>,
+/// Flags accepted by every command
+#[derive(Args, Debug, Clone)]
+pub struct GlobalArgs {
+    /// Configuration file to use instead of ./config.yaml
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
 
-        /// Include draft content in statistics
-        #[arg(short, long)]
-        include_drafts: bool,
+    /// Topic to work in
+    #[arg(short, long, global = true)]
+    pub topic: Option<String>,
 
-        /// Sort by field (date, title, words, reading_time)
-        #[arg(short, long, default_value = "date")]
-        sort_by: String,
+    #[command(flatten)]
+    pub output: OutputFormatArgs,
 
-        /// Show detailed statistics (per content)
-        #[arg(short, long)]
-        detailed: bool,
-    },
+    /// Show more detail
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
 }
 
 #[derive(Subcommand, Debug)]
-pub enum ContentCommands {
+pub enum Commands {
     /// Create new content
     New {
         /// Title of the content
         #[arg(short = 'T', long)]
         title: String,
 
-        /// Topic to create the content in
+        /// Slug for the content (derived from the title if omitted)
         #[arg(short, long)]
-        topic: String,
+        slug: Option<String>,
 
         /// Description for the content
         #[arg(short = 'g', long)]
@@ -95,125 +77,263 @@ pub enum ContentCommands {
         edit: bool,
     },
 
-    /// Edit content
+    /// Edit content in $EDITOR, or set a single frontmatter field
     Edit {
         /// Slug of the content to edit
-        #[arg(short, long)]
-        slug: Option<String>,
-
-        /// Topic of the content
-        #[arg(short, long)]
-        topic: Option<String>,
+        slug: String,
 
         /// Field to update (for single field updates)
-        #[arg(short, long)]
+        #[arg(short, long, requires = "value")]
         field: Option<String>,
 
         /// Value to set (for single field updates)
-        #[arg(short, long)]
+        #[arg(long, requires = "field")]
         value: Option<String>,
-
-        /// Open in editor
-        #[arg(short, long)]
-        editor: bool,
     },
 
-    /// Move content to a different location
+    /// Move content to a different topic or slug
     Move {
         /// Slug of the content to move
-        #[arg(short, long)]
         slug: String,
 
-        /// Topic to move from
-        #[arg(short, long)]
-        from: Option<String>,
-
-        /// Topic to move to
-        #[arg(short, long)]
+        /// Topic to move the content to
+        #[arg(long)]
         to: Option<String>,
+
+        /// New slug for the content
+        #[arg(long)]
+        new_slug: Option<String>,
+
+        /// Show what would change without moving anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Delete content
     Delete {
         /// Slug of the content to delete
-        #[arg(short, long)]
         slug: String,
 
-        /// Topic of the content
-        #[arg(short, long)]
-        topic: Option<String>,
-
-        /// Force deletion without confirmation
+        /// Delete without confirmation
         #[arg(short, long)]
         force: bool,
+
+        /// Move the content to the trash instead of deleting it
+        #[arg(long)]
+        trash: bool,
     },
 
     /// Validate content
     Validate {
-        /// Slug of the content to validate
+        /// Slug of the content to validate (all content if omitted)
         #[arg(short, long)]
         slug: Option<String>,
 
-        /// Topic of the content
-        #[arg(short, long)]
-        topic: Option<String>,
+        /// Checks to run
+        #[arg(long = "type", value_enum, default_value = "all")]
+        types: Vec<ValidationCheck>,
 
-        /// Validate all content
-        #[arg(short, long)]
-        all: bool,
+        /// Check that external links resolve
+        #[arg(long)]
+        check_external_links: bool,
 
-        /// Fix validation issues
-        #[arg(short, long)]
-        fix: bool,
+        /// Include draft content
+        #[arg(long)]
+        include_drafts: bool,
     },
 
-    /// List content
-    List {
-        /// Topic to list content for
+    /// Build content (generate HTML, JSON, RSS and sitemap), or run another build task
+    #[command(args_conflicts_with_subcommands = true)]
+    Build {
+        #[command(subcommand)]
+        task: Option<BuildCommands>,
+
+        /// Slug of the content to build (all content if omitted)
         #[arg(short, long)]
-        topic: Option<String>,
+        slug: Option<String>,
+
+        /// Output directory
+        #[arg(short, long)]
+        output_dir: Option<String>,
 
         /// Include draft content
+        #[arg(long)]
+        include_drafts: bool,
+
+        /// Minify rendered HTML and write compact JSON
+        #[arg(long)]
+        minify: bool,
+    },
+
+    /// Generate statistics about content
+    Stats {
+        /// Generate statistics for a specific content slug
         #[arg(short, long)]
-        drafts: bool,
+        slug: Option<String>,
 
-        /// Output format (table, json, yaml)
-        #[arg(short, long, default_value = "table")]
-        format: String,
+        /// Include draft content in statistics
+        #[arg(long)]
+        include_drafts: bool,
+
+        /// Sort by field (date, title, words, reading_time)
+        #[arg(long, default_value = "date")]
+        sort_by: String,
     },
 
     /// Search content
     Search {
         /// Search query
-        #[arg(short, long)]
-        query: Option<String>,
+        query: String,
 
-        /// Topic to search in
-        #[arg(short, long)]
-        topic: Option<String>,
+        /// Limit results to content with these tags (comma-separated)
+        #[arg(long)]
+        tags: Option<String>,
+
+        /// Maximum number of results
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Include draft content
+        #[arg(long)]
+        include_drafts: bool,
+
+        /// Rebuild the search index before searching
+        #[arg(long)]
+        rebuild: bool,
+    },
+
+    /// List content and manage content templates
+    #[command(subcommand)]
+    Content(ContentCommands),
+
+    /// Image management commands
+    #[command(subcommand)]
+    Images(ImageCommands),
+
+    /// Topic management commands
+    #[command(subcommand)]
+    Topic(TopicCommands),
+
+    /// Content migration commands
+    #[command(subcommand)]
+    Migrate(MigrateCommands),
+
+    /// Undo the last move, delete or bulk frontmatter edit
+    Undo,
+
+    /// List the operations that can be undone, most recent first
+    History,
+
+    /// Browse topics and articles in an interactive dashboard
+    Tui,
+
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// Checks `write validate` can run
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ValidationCheck {
+    Links,
+    Markdown,
+    Assets,
+    PublishDate,
+    DuplicateSlugs,
+    All,
+}
 
+#[derive(Subcommand, Debug)]
+pub enum ContentCommands {
+    /// List content
+    List {
         /// Include draft content
         #[arg(short, long)]
         drafts: bool,
-
-        /// Output format (table, json, yaml)
-        #[arg(short, long, default_value = "table")]
-        format: String,
     },
 
     /// Content template management
     Template {
-        /// Template to create or use
-        #[arg(short, long)]
+        /// Template to create
+        #[arg(long, required_unless_present = "list")]
         template: Option<String>,
 
         /// List available templates
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "template")]
         list: bool,
 
-        /// Output path for new template
+        /// Content type of the new template (article, note, tutorial or review)
+        #[arg(long = "type", default_value = "article")]
+        content_type: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BuildCommands {
+    /// Generate the table of contents for the collection
+    Toc {
+        /// File to write the table of contents to
+        #[arg(short, long, default_value = "index.md")]
+        output: PathBuf,
+    },
+
+    /// Analyze benchmark results
+    Benchmark {
+        /// Directory containing baseline benchmark results
+        #[arg(short, long)]
+        baseline: Option<PathBuf>,
+
+        /// Directory containing current benchmark results
+        #[arg(short, long)]
+        current: PathBuf,
+
+        /// Regression threshold percentage
+        #[arg(long, default_value = "10")]
+        threshold: f64,
+
+        /// Output report file
+        #[arg(short, long, default_value = "benchmark_report.md")]
+        report: PathBuf,
+
+        /// Write the report as JSON instead of Markdown
+        #[arg(short, long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ImageCommands {
+    /// Build responsive images from each article's source image
+    Build {
+        /// Article to build images for (all articles if omitted)
+        #[arg(short, long)]
+        article: Option<String>,
+
+        /// Output directory for optimized images
+        #[arg(short, long, default_value = "build/images")]
+        output_dir: PathBuf,
+
+        /// Source directory containing content
+        #[arg(short, long, default_value = "content")]
+        source_dir: PathBuf,
+    },
+
+    /// Optimize a source image for an article
+    Optimize {
+        /// Source image path
+        #[arg(short, long)]
+        source: PathBuf,
+
+        /// Article slug
         #[arg(short, long)]
-        output: Option<String>,
+        article: String,
+
+        /// Quality level (0-100)
+        #[arg(short, long, default_value = "85")]
+        quality: u8,
     },
 }
 
@@ -253,7 +373,7 @@ pub enum TopicCommands {
         description: Option<String>,
 
         /// New directory for the topic
-        #[arg(short, long)]
+        #[arg(short = 'r', long)]
         directory: Option<String>,
     },
 
@@ -264,7 +384,7 @@ pub enum TopicCommands {
         from: String,
 
         /// New key/slug for the topic
-        #[arg(short, long)]
+        #[arg(long)]
         to: String,
     },
 
@@ -280,104 +400,22 @@ pub enum TopicCommands {
     },
 
     /// List topics
-    List {
-        /// Output format (table, json, yaml)
-        #[arg(short, long, default_value = "table")]
-        format: String,
-    },
-}
-
-#[derive(Subcommand, Debug)]
-pub enum ImageCommands {
-    /// Build images
-    Build {
-        /// Topic to build images for
-        #[arg(short, long)]
-        topic: Option<String>,
-
-        /// Force rebuild all images
-        #[arg(short, long)]
-        rebuild: bool,
-    },
-
-    /// Optimize images
-    Optimize {
-        /// Topic to optimize images for
-        #[arg(short, long)]
-        topic: Option<String>,
-
-        /// Re-optimize already optimized images
-        #[arg(short, long)]
-        reoptimize: bool,
-    },
-}
-
-#[derive(Subcommand, Debug)]
-pub enum BuildCommands {
-    /// Build content (generate HTML, JSON)
-    Content {
-        /// Topic to build content for (optional)
-        #[arg(short, long)]
-        topic: Option<String>,
-
-        /// Force rebuild all content
-        #[arg(short, long)]
-        rebuild: bool,
-    },
-
-    /// Generate table of contents
-    Toc {
-        /// Topic to generate TOC for (optional)
-        #[arg(short, long)]
-        topic: Option<String>,
-    },
-
-    /// Analyze benchmark results
-    Benchmark {
-        /// Directory containing baseline benchmark results
-        #[arg(short, long)]
-        baseline: Option<String>,
-
-        /// Directory containing current benchmark results
-        #[arg(short, long)]
-        current: String,
-
-        /// Regression threshold percentage
-        #[arg(short, long, default_value = "10")]
-        threshold: f64,
-
-        /// Output report file
-        #[arg(short, long, default_value = "benchmark_report.md")]
-        report: String,
-
-        /// Output JSON format
-        #[arg(short, long)]
-        json: bool,
-
-        /// Enable verbose output
-        #[arg(short, long)]
-        verbose: bool,
-    },
+    List,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum MigrateCommands {
     /// Migrate content from old index.md/index.mdx structure to the new structure
+    ///
+    /// Migrates only the topic given with `--topic`, if there is one, and
+    /// lists each file it migrates with `--verbose`.
     Content {
-        /// Topic to migrate (if not specified, all topics will be migrated)
-        #[arg(short, long)]
-        topic: Option<String>,
-
         /// Dry run (don't actually modify files)
         #[arg(short = 'd', long)]
         dry_run: bool,
 
         /// Delete old index files after migration
-        #[arg(short, long)]
+        #[arg(long)]
         delete_old: bool,
-
-        /// Verbose output
-        #[arg(short, long)]
-        verbose: bool,
     },
 }
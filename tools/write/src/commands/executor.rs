@@ -1,163 +1,327 @@
 //! # Command Executor
 //!
-//! This module provides functions for executing CLI commands.
+//! This module runs parsed CLI commands by calling into the tool libraries,
+//! so `write new` does exactly what `content-new` does.
 
 use crate::cli::{
-    BuildCommands, Commands, ContentCommands, ImageCommands, MigrateCommands, TopicCommands,
+    BuildCommands, Cli, Commands, ContentCommands, GlobalArgs, ImageCommands, MigrateCommands, TopicCommands,
+    ValidationCheck,
 };
-use crate::tools::{build, content, image, topic};
+use crate::commands::report::{
+    ContentChange, ContentList, GeneratedFile, JournalHistory, TemplateList, TopicList, UndoReport,
+};
+use crate::tools::topic;
 use anyhow::Result;
+use clap::CommandFactory;
+use colored::*;
+use common_cli::OutputFormatter;
+use dialoguer::Confirm;
+use std::path::PathBuf;
 
-/// Execute a command
-pub fn execute_command(command: Commands) -> Result<()> {
-    match command {
-        Commands::Content(cmd) => execute_content_command(cmd),
-        Commands::Topic(cmd) => execute_topic_command(cmd),
-        Commands::Image(cmd) => execute_image_command(cmd),
-        Commands::Build(cmd) => execute_build_command(cmd),
-        Commands::Migrate(cmd) => execute_migrate_command(cmd),
-        Commands::Stats {
-            slug,
-            topic,
-            include_drafts,
-            sort_by,
-            detailed,
-        } => {
-            // Implementing a simple stats command
-            println!("Content Statistics:");
-            println!("- Slug filter: {}", slug.as_deref().unwrap_or("None"));
-            println!("- Topic filter: {}", topic.as_deref().unwrap_or("None"));
-            println!("- Include drafts: {}", include_drafts);
-            println!("- Sort by: {}", sort_by);
-            println!("- Detailed: {}", detailed);
-
-            // In a real implementation, we would call a function to generate stats
-            // content::generate_content_stats(slug, topic, include_drafts, sort_by.to_string(), detailed)
-            Ok(())
+/// Use the configuration file from `--config`, if one was given
+///
+/// The tool libraries load their configuration themselves, so the path is
+/// passed to them through `CONFIG_PATH`.
+pub fn apply_config(global: &GlobalArgs) -> Result<()> {
+    if let Some(path) = &global.config {
+        if !path.exists() {
+            return Err(anyhow::anyhow!("Configuration file not found: {}", path.display()));
         }
+        std::env::set_var("CONFIG_PATH", path);
+        common_config::clear_config_cache();
     }
+    Ok(())
 }
 
-/// Execute a content command
-pub fn execute_content_command(command: ContentCommands) -> Result<()> {
-    // Initialize the ContentTools singleton
-    crate::tools::content::lazy_content_tools()?;
+/// Execute a command
+pub fn execute_command(command: Commands, global: &GlobalArgs) -> Result<()> {
+    let formatter = global.output.formatter();
+    let topic = global.topic.clone();
 
     match command {
-        ContentCommands::New {
+        Commands::New {
             title,
-            topic,
+            slug,
             description,
             tags,
             draft,
             template,
             edit,
         } => {
-            let content = title.clone();
-            let topic_clone = topic.clone();
-
-            // Create the content
-            content::create_content(
-                Some(title),
-                Some(topic),
+            let options = content_new::NewOptions {
+                slug: Some(slug.unwrap_or_else(|| slug::slugify(&title))),
+                title: Some(title),
+                topic: topic.clone(),
                 description,
-                tags,
-                None,
-                draft,
                 template,
-                None,
-            )?;
+                tags: tags.map(|tags| split_list(&tags)),
+                draft: Some(draft),
+            };
+            let path = content_new::create_content(&options)?;
+            let slug = options.slug.unwrap_or_default();
 
-            // Open the editor if requested
             if edit {
-                content::edit_content(Some(content), Some(topic_clone), true, true)?;
+                open_editor(&path)?;
             }
 
-            Ok(())
+            formatter.print(&ContentChange::new("created", &slug, topic.as_deref(), path))
         }
-        ContentCommands::Edit {
-            slug,
-            topic,
-            field,
-            value,
-            editor,
-        } => {
-            if let (Some(field), Some(value)) = (field.as_deref(), value.as_deref()) {
-                // Update a specific field
-                content::update_frontmatter_field(
-                    &slug.unwrap_or_default(),
-                    topic.as_deref(),
-                    field,
-                    value,
-                )
+        Commands::Edit { slug, field, value } => {
+            let path = content_edit::find_content_path(&slug, topic.as_deref())?;
+
+            if let (Some(field), Some(value)) = (field, value) {
+                content_edit::update_frontmatter_field(&slug, topic.as_deref(), &field, &value)?;
             } else {
-                // Edit the whole content
-                content::edit_content(slug, topic, true, editor)
+                open_editor(&path)?;
             }
+
+            formatter.print(&ContentChange::new("edited", &slug, topic.as_deref(), path))
+        }
+        Commands::Move { slug, to, new_slug, dry_run } => {
+            let options = content_move::MoveOptions {
+                slug: Some(slug),
+                new_slug,
+                topic,
+                new_topic: to,
+                update_frontmatter: true,
+                dry_run,
+            };
+            let plan = content_move::move_content(&options)?;
+            if let Some(conflict) = plan.conflicts.first() {
+                return Err(anyhow::anyhow!("{}", conflict));
+            }
+
+            let change = ContentChange::new("moved", &plan.to_slug, Some(&plan.to_topic), plan.destination);
+            formatter.print(&change.dry_run(dry_run))
+        }
+        Commands::Delete { slug, force, trash } => {
+            if !force {
+                let prompt = format!("Delete content '{}'?", slug);
+                if !Confirm::new().with_prompt(prompt).interact()? {
+                    if formatter.is_plain() {
+                        println!("Operation cancelled");
+                    }
+                    return Ok(());
+                }
+            }
+
+            let options = content_delete::DeleteOptions {
+                slug: Some(slug.clone()),
+                topic: topic.clone(),
+                force,
+                trash,
+            };
+            let path = content_delete::delete_content(&options)?;
+
+            formatter.print(&ContentChange::new("deleted", &slug, topic.as_deref(), PathBuf::from(path)))
         }
-        ContentCommands::Move { slug, from, to } => {
-            content::move_content(Some(slug), None, from, to)
+        Commands::Validate {
+            slug,
+            types,
+            check_external_links,
+            include_drafts,
+        } => {
+            let options = content_validate::ValidationOptions {
+                article_slug: slug,
+                topic,
+                validation_types: types.iter().map(|check| validation_type(*check)).collect(),
+                check_external_links,
+                timeout: None,
+                dictionary_path: None,
+                include_drafts,
+                images_dir: None,
+                changed_since: None,
+                archive_fallback: Default::default(),
+            };
+            let results = content_validate::validate_content(&options)?;
+            let config = common_config::load_config()?;
+            let report = content_validate::ValidationReport::new(results, &config).verbose(global.verbose);
+            formatter.print(&report)
         }
-        ContentCommands::Delete { slug, topic, force } => {
-            content::delete_content(Some(slug), topic, force)
+        Commands::Build { task: Some(task), .. } => execute_build_command(task, &formatter),
+        Commands::Build {
+            task: None,
+            slug,
+            output_dir,
+            include_drafts,
+            minify,
+        } => {
+            let options = content_build::BuildOptions {
+                output_dir,
+                slug,
+                topic,
+                include_drafts,
+                verbose: global.verbose && formatter.is_plain(),
+                minify,
+                ..Default::default()
+            };
+            formatter.print(&content_build::build_content(&options)?)
         }
-        ContentCommands::Validate {
+        Commands::Stats {
             slug,
-            topic,
-            all: _,
-            fix,
+            include_drafts,
+            sort_by,
         } => {
-            let validation_types = None;
-            content::validate_content(
+            let options = content_stats::StatsOptions {
                 slug,
                 topic,
-                validation_types,
-                false, // Don't check external links by default
-                None,
-                None,
-                false, // Don't include drafts by default
-                !fix,  // Verbose if not fixing
-            )
+                include_drafts,
+                include_archived: false,
+                sort_by,
+                detailed: global.verbose,
+                changed_since: None,
+            };
+            let (stats, tag_counts, total_words, total_articles, total_drafts) =
+                content_stats::generate_stats(&options)?;
+            let report = content_stats::StatsReport::new(&stats, &tag_counts, total_words, total_articles, total_drafts)
+                .detailed(global.verbose);
+            formatter.print(&report)
         }
-        ContentCommands::List {
-            topic,
-            drafts,
-            format,
-        } => content::list_content_with_options(topic, drafts, &format),
-        ContentCommands::Search {
+        Commands::Search {
             query,
-            topic,
-            drafts,
-            format: _,
-        } => content::search_content(
-            query.unwrap_or_default(),
-            topic,
-            None,
-            None,
-            None,
-            drafts,
-            false,
-            None,
-            false,
-        ),
+            tags,
+            limit,
+            include_drafts,
+            rebuild,
+        } => {
+            if rebuild {
+                if formatter.is_plain() {
+                    println!("{} search index...", "Building".green().bold());
+                }
+                content_search::build_index(None, include_drafts)?;
+            }
+
+            let options = content_search::SearchOptions {
+                query: query.clone(),
+                topic,
+                tags: tags.map(|tags| split_list(&tags)),
+                limit,
+                include_drafts,
+                ..Default::default()
+            };
+            let results = content_search::search_content(&options)?;
+            formatter.print(&content_search::SearchReport::new(query, results))
+        }
+        Commands::Content(cmd) => execute_content_command(cmd, global, &formatter),
+        Commands::Images(cmd) => execute_image_command(cmd, global, &formatter),
+        Commands::Topic(cmd) => execute_topic_command(cmd, &formatter),
+        Commands::Migrate(cmd) => execute_migrate_command(cmd, global),
+        Commands::Undo => {
+            let undone = journal()?.undo_last()?;
+            formatter.print(&UndoReport { undone })
+        }
+        Commands::History => formatter.print(&JournalHistory {
+            transactions: journal()?.history()?,
+        }),
+        Commands::Tui => crate::tui::run(),
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "write", &mut std::io::stdout());
+            Ok(())
+        }
+    }
+}
+
+/// Execute a build task other than building the content
+pub fn execute_build_command(command: BuildCommands, formatter: &OutputFormatter) -> Result<()> {
+    match command {
+        BuildCommands::Toc { output } => {
+            let options = toc_generate::TocOptions {
+                output,
+                ..Default::default()
+            };
+            let path = toc_generate::generate_toc(&options)?;
+            formatter.print(&GeneratedFile::new("table of contents", path))
+        }
+        BuildCommands::Benchmark {
+            baseline,
+            current,
+            threshold,
+            report,
+            json,
+        } => {
+            crate::benchmark::analyze_benchmarks(baseline, current, threshold, report.clone(), json, false)?;
+            formatter.print(&GeneratedFile::new("benchmark report", report))
+        }
+    }
+}
+
+/// Execute a content listing or template command
+pub fn execute_content_command(command: ContentCommands, global: &GlobalArgs, formatter: &OutputFormatter) -> Result<()> {
+    match command {
+        ContentCommands::List { drafts } => {
+            let options = content_stats::StatsOptions {
+                slug: None,
+                topic: global.topic.clone(),
+                include_drafts: drafts,
+                include_archived: false,
+                sort_by: "date".to_string(),
+                detailed: false,
+                changed_since: None,
+            };
+            let (stats, ..) = content_stats::generate_stats(&options)?;
+            formatter.print(&ContentList::from_stats(&stats))
+        }
+        ContentCommands::Template { list: true, .. } => {
+            formatter.print(&TemplateList::new(content_template::list_templates()?))
+        }
         ContentCommands::Template {
             template,
-            list,
-            output,
+            content_type,
+            ..
         } => {
-            if list {
-                content::list_templates()
-            } else if let Some(template_name) = template {
-                content::create_template(Some(template_name), output)
-            } else {
-                content::create_template(None, output)
-            }
+            let options = content_template::CreateTemplateOptions {
+                name: template.unwrap_or_default(),
+                content_type,
+                content: None,
+            };
+            let template = content_template::create_template(options)?;
+            formatter.print(&GeneratedFile::new(&format!("template '{}'", template.name), template.path))
+        }
+    }
+}
+
+/// Execute an image command
+pub fn execute_image_command(command: ImageCommands, global: &GlobalArgs, formatter: &OutputFormatter) -> Result<()> {
+    match command {
+        ImageCommands::Build {
+            article,
+            output_dir,
+            source_dir,
+        } => {
+            let options = image_build::BuildImagesOptions {
+                output_dir,
+                source_dir,
+                topic: global.topic.clone(),
+                article,
+                force_rebuild: false,
+                rasterize_svg: false,
+                transcode_animations: false,
+            };
+            let counts = image_build::build_images(&options)?;
+            formatter.print(&image_build::ImageBuildReport::from(counts))
+        }
+        ImageCommands::Optimize {
+            source,
+            article,
+            quality,
+        } => {
+            let options = image_optimize::OptimizeOptions {
+                source,
+                article: Some(article),
+                topic: global.topic.clone(),
+                formats: image_optimize::default_formats(),
+                sizes: image_optimize::default_size_variants(),
+                quality,
+                preserve_metadata: false,
+                minify_svg: false,
+            };
+            formatter.print(&image_optimize::optimize_image(&options)?)
         }
     }
 }
 
 /// Execute a topic command
-pub fn execute_topic_command(command: TopicCommands) -> Result<()> {
+pub fn execute_topic_command(command: TopicCommands, formatter: &OutputFormatter) -> Result<()> {
     match command {
         TopicCommands::Add {
             key,
@@ -173,70 +337,21 @@ pub fn execute_topic_command(command: TopicCommands) -> Result<()> {
         } => topic::edit_topic_with_directory(Some(key), name, description, directory),
         TopicCommands::Rename { from, to } => topic::rename_topic(Some(from), Some(to), None, None),
         TopicCommands::Delete { key, force } => topic::delete_topic(Some(key), None, force),
-        TopicCommands::List { format } => topic::list_topics_with_format(&format),
-    }
-}
-
-/// Execute an image command
-pub fn execute_image_command(command: ImageCommands) -> Result<()> {
-    match command {
-        ImageCommands::Build { topic, rebuild } => {
-            if rebuild {
-                // Force rebuilding all images
-                image::build_images(None, topic, None)
-            } else {
-                // Only build new images
-                image::build_images(None, topic, None)
-            }
-        }
-        ImageCommands::Optimize { topic, reoptimize } => image::optimize_images(topic, reoptimize),
-    }
-}
-
-/// Execute a build command
-pub fn execute_build_command(command: BuildCommands) -> Result<()> {
-    // Initialize the lazy build cache for better performance
-    let _cache = build::lazy_build_cache();
-
-    match command {
-        BuildCommands::Content { topic, rebuild } => {
-            build::build_content(
-                None, None, topic, false,   // Don't include drafts by default
-                false,   // Don't skip HTML
-                false,   // Don't skip JSON
-                false,   // Don't skip RSS
-                false,   // Don't skip sitemap
-                rebuild, // Pass the rebuild flag as force_rebuild
-                true,    // Verbose output
-            )
-        }
-        BuildCommands::Toc { topic: _ } => {
-            // Topic is ignored for now, as the TOC is generated for all content
-            build::generate_toc(None)
-        }
-        BuildCommands::Benchmark { .. } => {
-            // TODO: Implement benchmark functionality
-            Ok(())
-        }
+        TopicCommands::List => formatter.print(&TopicList::from_config(&common_config::load_config()?)),
     }
 }
 
 /// Execute a migrate command
-pub fn execute_migrate_command(command: MigrateCommands) -> Result<()> {
+pub fn execute_migrate_command(command: MigrateCommands, global: &GlobalArgs) -> Result<()> {
     match command {
-        MigrateCommands::Content {
-            topic,
-            dry_run,
-            delete_old,
-            verbose,
-        } => {
+        MigrateCommands::Content { dry_run, delete_old } => {
             // Use the content-migrate binary to perform the migration
             let mut cmd = std::process::Command::new("cargo");
             cmd.args(["run", "--bin", "content-migrate", "--"]);
 
             // Add arguments from the user's command
-            if let Some(topic) = topic {
-                cmd.args(["--topic", &topic]);
+            if let Some(topic) = &global.topic {
+                cmd.args(["--topic", topic]);
             }
 
             if dry_run {
@@ -247,7 +362,7 @@ pub fn execute_migrate_command(command: MigrateCommands) -> Result<()> {
                 cmd.arg("--delete-old");
             }
 
-            if verbose {
+            if global.verbose {
                 cmd.arg("--verbose");
             }
 
@@ -274,3 +389,41 @@ pub fn execute_migrate_command(command: MigrateCommands) -> Result<()> {
         }
     }
 }
+
+/// Open a file in `$EDITOR`, falling back to vim
+pub fn open_editor(path: &std::path::Path) -> Result<()> {
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
+    let status = std::process::Command::new(&editor)
+        .arg(path)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to open editor '{}': {}", editor, e))?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!("Editor exited with non-zero status: {}", status));
+    }
+    Ok(())
+}
+
+/// The journal of the configured content repository
+fn journal() -> Result<common_fs::Journal> {
+    let config = common_config::load_config()?;
+    Ok(common_fs::Journal::for_base_dir(&config.content.base_dir))
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn validation_type(check: ValidationCheck) -> content_validate::ValidationType {
+    match check {
+        ValidationCheck::Links => content_validate::ValidationType::Links,
+        ValidationCheck::Markdown => content_validate::ValidationType::Markdown,
+        ValidationCheck::Assets => content_validate::ValidationType::Assets,
+        ValidationCheck::PublishDate => content_validate::ValidationType::PublishDate,
+        ValidationCheck::DuplicateSlugs => content_validate::ValidationType::DuplicateSlugs,
+        ValidationCheck::All => content_validate::ValidationType::All,
+    }
+}
@@ -3,4 +3,5 @@
 //! This module contains command implementations for the CLI.
 
 pub mod build;
-pub mod executor;
\ No newline at end of file
+pub mod executor;
+pub mod report;
@@ -0,0 +1,280 @@
+//! # Command Reports
+//!
+//! Reports for the commands that have no report type in their tool library,
+//! printed with `--format` like the rest.
+
+use colored::*;
+use common_cli::DisplayResult;
+use common_fs::TransactionRecord;
+use common_models::Config;
+use common_templates::Template;
+use content_stats::ContentStats;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Content created, edited, moved or deleted by a command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContentChange {
+    /// What happened: `created`, `edited`, `moved` or `deleted`
+    pub action: String,
+    pub slug: String,
+    pub topic: Option<String>,
+    /// Where the content is after the change, or was before a delete
+    pub path: PathBuf,
+    /// The change was only planned, not made
+    pub dry_run: bool,
+}
+
+impl ContentChange {
+    pub fn new(action: &str, slug: &str, topic: Option<&str>, path: PathBuf) -> Self {
+        Self {
+            action: action.to_string(),
+            slug: slug.to_string(),
+            topic: topic.map(String::from),
+            path,
+            dry_run: false,
+        }
+    }
+
+    /// Mark the change as planned but not made
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+impl DisplayResult for ContentChange {
+    fn to_display(&self) -> String {
+        let name = match &self.topic {
+            Some(topic) => format!("{}/{}", topic, self.slug),
+            None => self.slug.clone(),
+        };
+
+        if self.dry_run {
+            format!("{} {} would be {} ({})", "Dry run:".yellow().bold(), name, self.action, self.path.display())
+        } else {
+            format!("{} {} {} ({})", "✓".green(), capitalize(&self.action), name, self.path.display())
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A file written by a build task or template command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GeneratedFile {
+    /// What the file is, such as `table of contents`
+    pub kind: String,
+    pub path: PathBuf,
+}
+
+impl GeneratedFile {
+    pub fn new(kind: &str, path: PathBuf) -> Self {
+        Self {
+            kind: kind.to_string(),
+            path,
+        }
+    }
+}
+
+impl DisplayResult for GeneratedFile {
+    fn to_display(&self) -> String {
+        format!("{} Wrote {} to {}", "✓".green(), self.kind, self.path.display())
+    }
+}
+
+/// An article listed by `write content list`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContentSummary {
+    pub topic: String,
+    pub slug: String,
+    pub title: String,
+    pub draft: bool,
+    pub word_count: usize,
+}
+
+/// Articles in the order content-stats sorted them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContentList {
+    pub content: Vec<ContentSummary>,
+}
+
+impl ContentList {
+    pub fn from_stats(stats: &[ContentStats]) -> Self {
+        let content = stats
+            .iter()
+            .map(|article| ContentSummary {
+                topic: article.topic.clone(),
+                slug: article.slug.clone(),
+                title: article.title.clone(),
+                draft: article.is_draft,
+                word_count: article.word_count,
+            })
+            .collect();
+
+        Self { content }
+    }
+}
+
+impl DisplayResult for ContentList {
+    fn to_display(&self) -> String {
+        if self.content.is_empty() {
+            return "No content found.".to_string();
+        }
+
+        self.content
+            .iter()
+            .map(|article| {
+                let draft = if article.draft { " (draft)".yellow().to_string() } else { String::new() };
+                format!(
+                    "{} - {}{} ({} words)",
+                    format!("{}/{}", article.topic, article.slug).cyan().bold(),
+                    article.title,
+                    draft,
+                    article.word_count
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A content template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateSummary {
+    pub name: String,
+    pub content_type: String,
+    pub description: String,
+}
+
+/// The templates in the templates directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateList {
+    pub templates: Vec<TemplateSummary>,
+}
+
+impl TemplateList {
+    pub fn new(templates: Vec<Template>) -> Self {
+        let templates = templates
+            .into_iter()
+            .map(|template| TemplateSummary {
+                name: template.name,
+                content_type: template.content_type,
+                description: template.description,
+            })
+            .collect();
+
+        Self { templates }
+    }
+}
+
+impl DisplayResult for TemplateList {
+    fn to_display(&self) -> String {
+        if self.templates.is_empty() {
+            return "No templates found.".to_string();
+        }
+
+        self.templates
+            .iter()
+            .map(|template| format!("{} ({})", template.name.cyan().bold(), template.content_type))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A topic from the configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopicSummary {
+    pub key: String,
+    pub name: String,
+    pub description: String,
+    pub directory: String,
+}
+
+/// The topics in the configuration, ordered by key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopicList {
+    pub topics: Vec<TopicSummary>,
+}
+
+impl TopicList {
+    pub fn from_config(config: &Config) -> Self {
+        let mut topics: Vec<TopicSummary> = config
+            .content
+            .topics
+            .iter()
+            .map(|(key, topic)| TopicSummary {
+                key: key.clone(),
+                name: topic.name.clone(),
+                description: topic.description.clone(),
+                directory: topic.directory.clone(),
+            })
+            .collect();
+        topics.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Self { topics }
+    }
+}
+
+impl DisplayResult for TopicList {
+    fn to_display(&self) -> String {
+        if self.topics.is_empty() {
+            return "No topics configured.".to_string();
+        }
+
+        self.topics
+            .iter()
+            .map(|topic| format!("{} - {} ({})", topic.key.cyan().bold(), topic.name, topic.directory))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Operations recorded in the journal, most recent first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JournalHistory {
+    pub transactions: Vec<TransactionRecord>,
+}
+
+impl DisplayResult for JournalHistory {
+    fn to_display(&self) -> String {
+        if self.transactions.is_empty() {
+            return "Nothing to undo.".to_string();
+        }
+
+        self.transactions
+            .iter()
+            .map(|transaction| {
+                format!(
+                    "{} {} ({} paths)",
+                    transaction.recorded_at.format("%Y-%m-%d %H:%M:%S").to_string().dimmed(),
+                    transaction.description,
+                    transaction.changes.len()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The operation rolled back by `write undo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UndoReport {
+    /// `None` if the journal was empty
+    pub undone: Option<TransactionRecord>,
+}
+
+impl DisplayResult for UndoReport {
+    fn to_display(&self) -> String {
+        match &self.undone {
+            Some(transaction) => format!("{} Undid: {}", "✓".green(), transaction.description),
+            None => "Nothing to undo.".to_string(),
+        }
+    }
+}
@@ -8,8 +8,10 @@
 #![allow(dead_code)]
 
 // Re-export public modules
+pub mod benchmark;
 pub mod cli;
 pub mod commands;
 pub mod tools;
 pub mod ui;
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod tui;
\ No newline at end of file
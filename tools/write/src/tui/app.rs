@@ -0,0 +1,218 @@
+//! # Dashboard State
+//!
+//! What the dashboard shows and which row is selected, kept apart from the
+//! terminal so key handling can be tested without one. Keys that need to
+//! touch the content repository come back as an [`Action`] for the event
+//! loop to perform.
+
+use ratatui::crossterm::event::KeyCode;
+use std::path::PathBuf;
+
+/// One article in the dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArticleRow {
+    pub topic: String,
+    pub slug: String,
+    pub title: String,
+    pub is_draft: bool,
+    pub word_count: usize,
+    /// Validation issues found in the article
+    pub issues: usize,
+    /// The article's content file, if it could be found
+    pub path: Option<PathBuf>,
+}
+
+/// The pane that arrow keys move through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Topics,
+    Articles,
+}
+
+/// What keys currently do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Navigating topics and articles
+    Browse,
+    /// Typing the topic to move the selected article to
+    Move(String),
+}
+
+/// Work the event loop does for the dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Quit,
+    /// Open a content file in `$EDITOR`
+    Edit(PathBuf),
+    /// Move an article to another topic
+    Move { slug: String, topic: String, new_topic: String },
+    /// Publish an article
+    Publish { slug: String, topic: String },
+    /// Reload statistics and validation results
+    Reload,
+}
+
+/// The dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct App {
+    /// Topic keys, in the order they are listed
+    pub topics: Vec<String>,
+    pub articles: Vec<ArticleRow>,
+    pub topic_index: usize,
+    /// Selected row among the selected topic's articles
+    pub article_index: usize,
+    pub focus: Focus,
+    pub mode: Mode,
+    /// Message shown at the bottom of the screen
+    pub status: String,
+}
+
+impl App {
+    pub fn new(topics: Vec<String>, articles: Vec<ArticleRow>) -> Self {
+        Self {
+            topics,
+            articles,
+            topic_index: 0,
+            article_index: 0,
+            focus: Focus::Topics,
+            mode: Mode::Browse,
+            status: "Press ? for help".to_string(),
+        }
+    }
+
+    /// Replace the content, keeping the selection where it still exists
+    pub fn replace(&mut self, topics: Vec<String>, articles: Vec<ArticleRow>) {
+        self.topics = topics;
+        self.articles = articles;
+        self.topic_index = self.topic_index.min(self.topics.len().saturating_sub(1));
+        self.article_index = self.article_index.min(self.visible_articles().len().saturating_sub(1));
+    }
+
+    /// The selected topic's key
+    pub fn selected_topic(&self) -> Option<&str> {
+        self.topics.get(self.topic_index).map(String::as_str)
+    }
+
+    /// Articles in the selected topic
+    pub fn visible_articles(&self) -> Vec<&ArticleRow> {
+        match self.selected_topic() {
+            Some(topic) => self.articles.iter().filter(|article| article.topic == topic).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn selected_article(&self) -> Option<&ArticleRow> {
+        self.visible_articles().get(self.article_index).copied()
+    }
+
+    /// Number of articles and drafts in a topic
+    pub fn topic_counts(&self, topic: &str) -> (usize, usize) {
+        let articles = self.articles.iter().filter(|article| article.topic == topic);
+        articles.fold((0, 0), |(total, drafts), article| (total + 1, drafts + usize::from(article.is_draft)))
+    }
+
+    /// Respond to a key press
+    pub fn handle_key(&mut self, key: KeyCode) -> Action {
+        match &mut self.mode {
+            Mode::Move(input) => match key {
+                KeyCode::Esc => {
+                    self.mode = Mode::Browse;
+                    self.status = "Move cancelled".to_string();
+                    Action::None
+                }
+                KeyCode::Enter => {
+                    let new_topic = input.trim().to_string();
+                    self.mode = Mode::Browse;
+                    match self.selected_article() {
+                        Some(article) if !new_topic.is_empty() => Action::Move {
+                            slug: article.slug.clone(),
+                            topic: article.topic.clone(),
+                            new_topic,
+                        },
+                        _ => Action::None,
+                    }
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                    Action::None
+                }
+                KeyCode::Char(c) => {
+                    input.push(c);
+                    Action::None
+                }
+                _ => Action::None,
+            },
+            Mode::Browse => self.handle_browse_key(key),
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: KeyCode) -> Action {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Tab | KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {
+                self.focus = match self.focus {
+                    Focus::Topics => Focus::Articles,
+                    Focus::Articles => Focus::Topics,
+                };
+                Action::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.step(1);
+                Action::None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.step(-1);
+                Action::None
+            }
+            KeyCode::Char('r') => Action::Reload,
+            KeyCode::Char('?') => {
+                self.status = "↑↓ select  ⇥ switch pane  e edit  m move  p publish  r reload  q quit".to_string();
+                Action::None
+            }
+            KeyCode::Enter | KeyCode::Char('e') => match self.selected_article() {
+                Some(ArticleRow { path: Some(path), .. }) => Action::Edit(path.clone()),
+                Some(article) => {
+                    self.status = format!("Could not find the file for {}", article.slug);
+                    Action::None
+                }
+                None => Action::None,
+            },
+            KeyCode::Char('m') if self.selected_article().is_some() => {
+                self.mode = Mode::Move(String::new());
+                Action::None
+            }
+            KeyCode::Char('p') => match self.selected_article() {
+                Some(article) if article.is_draft => Action::Publish {
+                    slug: article.slug.clone(),
+                    topic: article.topic.clone(),
+                },
+                Some(article) => {
+                    self.status = format!("{} is already published", article.slug);
+                    Action::None
+                }
+                None => Action::None,
+            },
+            _ => Action::None,
+        }
+    }
+
+    /// Move the selection in the focused pane, staying within it
+    fn step(&mut self, delta: isize) {
+        let (index, len) = match self.focus {
+            Focus::Topics => (&mut self.topic_index, self.topics.len()),
+            Focus::Articles => {
+                let len = self.visible_articles().len();
+                (&mut self.article_index, len)
+            }
+        };
+        if len == 0 {
+            return;
+        }
+        *index = index.saturating_add_signed(delta).min(len - 1);
+
+        if self.focus == Focus::Topics {
+            self.article_index = 0;
+        }
+    }
+}
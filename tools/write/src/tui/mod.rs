@@ -0,0 +1,142 @@
+//! # Dashboard
+//!
+//! `write tui` shows every topic with its articles, their draft status,
+//! word counts from content-stats and issue counts from content-validate,
+//! and can open, move or publish the selected article.
+
+pub mod app;
+pub mod view;
+
+use anyhow::Result;
+use common_models::ContentState;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub use app::{Action, App, ArticleRow, Focus, Mode};
+
+/// Load topics and articles from the content repository
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be loaded, or statistics or
+/// validation fail
+pub fn load() -> Result<(Vec<String>, Vec<ArticleRow>)> {
+    let config = common_config::load_config()?;
+    let mut topics: Vec<String> = config.content.topics.keys().cloned().collect();
+    topics.sort();
+
+    let stats_options = content_stats::StatsOptions {
+        slug: None,
+        topic: None,
+        include_drafts: true,
+        include_archived: false,
+        sort_by: "date".to_string(),
+        detailed: false,
+        changed_since: None,
+    };
+    let (stats, ..) = content_stats::generate_stats(&stats_options)?;
+
+    let validation_options = content_validate::ValidationOptions {
+        article_slug: None,
+        topic: None,
+        validation_types: vec![content_validate::ValidationType::All],
+        check_external_links: false,
+        timeout: None,
+        dictionary_path: None,
+        include_drafts: true,
+        images_dir: None,
+        changed_since: None,
+        archive_fallback: Default::default(),
+    };
+    let issues: HashMap<PathBuf, usize> = content_validate::validate_content(&validation_options)?
+        .into_iter()
+        .map(|result| (canonical(result.file_path), result.issues.len()))
+        .collect();
+
+    let articles = stats
+        .into_iter()
+        .map(|stats| {
+            let path = content_edit::find_content_path(&stats.slug, Some(&stats.topic)).ok();
+            let issues = path.clone().map(canonical).and_then(|path| issues.get(&path).copied()).unwrap_or(0);
+            ArticleRow {
+                topic: stats.topic,
+                slug: stats.slug,
+                title: stats.title,
+                is_draft: stats.is_draft,
+                word_count: stats.word_count,
+                issues,
+                path,
+            }
+        })
+        .collect();
+
+    Ok((topics, articles))
+}
+
+fn canonical(path: PathBuf) -> PathBuf {
+    path.canonicalize().unwrap_or(path)
+}
+
+/// Run the dashboard until the user quits
+///
+/// # Errors
+///
+/// Returns an error if the content cannot be loaded or the terminal fails
+pub fn run() -> Result<()> {
+    let (topics, articles) = load()?;
+    let mut app = App::new(topics, articles);
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    loop {
+        terminal.draw(|frame| view::draw(frame, app))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        let outcome = match app.handle_key(key.code) {
+            Action::None => continue,
+            Action::Quit => return Ok(()),
+            Action::Edit(path) => {
+                ratatui::restore();
+                let edited = crate::commands::executor::open_editor(&path);
+                *terminal = ratatui::init();
+                edited.map(|_| format!("Saved {}", path.display()))
+            }
+            Action::Move { slug, topic, new_topic } => {
+                let options = content_move::MoveOptions {
+                    slug: Some(slug.clone()),
+                    new_slug: None,
+                    topic: Some(topic),
+                    new_topic: Some(new_topic.clone()),
+                    update_frontmatter: true,
+                    dry_run: false,
+                };
+                content_move::move_content(&options).map(|_| format!("Moved {} to {}", slug, new_topic))
+            }
+            Action::Publish { slug, topic } => content_edit::transition(&slug, Some(&topic), ContentState::Published)
+                .map(|_| format!("Published {}", slug))
+                .map_err(anyhow::Error::from),
+            Action::Reload => Ok("Reloaded".to_string()),
+        };
+
+        app.status = match outcome.and_then(|message| load().map(|content| (message, content))) {
+            Ok((message, (topics, articles))) => {
+                app.replace(topics, articles);
+                message
+            }
+            Err(e) => format!("Error: {}", e),
+        };
+    }
+}
@@ -0,0 +1,103 @@
+//! # Dashboard Rendering
+//!
+//! Draws the topic list, the selected topic's articles and the status line.
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+
+use super::app::{App, Focus, Mode};
+
+/// Draw the whole dashboard
+pub fn draw(frame: &mut Frame, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(frame.area());
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(25), Constraint::Percentage(75)])
+        .split(rows[0]);
+
+    draw_topics(frame, app, panes[0]);
+    draw_articles(frame, app, panes[1]);
+    draw_status(frame, app, rows[1]);
+}
+
+fn pane(title: &str, focused: bool) -> Block<'_> {
+    let style = if focused { Style::default().fg(Color::Yellow) } else { Style::default() };
+    Block::default().borders(Borders::ALL).title(title).border_style(style)
+}
+
+fn highlight() -> Style {
+    Style::default().add_modifier(Modifier::REVERSED)
+}
+
+fn draw_topics(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .topics
+        .iter()
+        .map(|topic| {
+            let (total, drafts) = app.topic_counts(topic);
+            ListItem::new(format!("{} ({}, {} drafts)", topic, total, drafts))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(pane("Topics", app.focus == Focus::Topics))
+        .highlight_style(highlight());
+    let mut state = ListState::default().with_selected(Some(app.topic_index));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_articles(frame: &mut Frame, app: &App, area: Rect) {
+    let rows: Vec<Row> = app
+        .visible_articles()
+        .into_iter()
+        .map(|article| {
+            let status = if article.is_draft {
+                Span::styled("draft", Style::default().fg(Color::Red))
+            } else {
+                Span::styled("published", Style::default().fg(Color::Green))
+            };
+            let issues = if article.issues > 0 {
+                Span::styled(article.issues.to_string(), Style::default().fg(Color::Yellow))
+            } else {
+                Span::raw("0")
+            };
+
+            Row::new(vec![
+                Line::from(article.title.clone()),
+                Line::from(status),
+                Line::from(article.word_count.to_string()),
+                Line::from(issues),
+            ])
+        })
+        .collect();
+
+    let title = match app.selected_topic() {
+        Some(topic) => format!("Articles in {}", topic),
+        None => "Articles".to_string(),
+    };
+    let table = Table::new(
+        rows,
+        [Constraint::Min(20), Constraint::Length(10), Constraint::Length(7), Constraint::Length(7)],
+    )
+    .header(Row::new(vec!["Title", "Status", "Words", "Issues"]).style(Style::default().add_modifier(Modifier::BOLD)))
+    .block(pane(&title, app.focus == Focus::Articles))
+    .row_highlight_style(highlight());
+
+    let selected = if app.focus == Focus::Articles { Some(app.article_index) } else { None };
+    let mut state = TableState::default().with_selected(selected);
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let text = match &app.mode {
+        Mode::Move(input) => format!("Move to topic: {}▏ (Enter to move, Esc to cancel)", input),
+        Mode::Browse => app.status.clone(),
+    };
+    frame.render_widget(Paragraph::new(text).block(Block::default().borders(Borders::ALL)), area);
+}
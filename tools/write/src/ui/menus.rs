@@ -5,7 +5,8 @@
 use anyhow::Result;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 
-use crate::cli::{Commands, ContentCommands, TopicCommands, ImageCommands, BuildCommands};
+use crate::cli::{Commands, TopicCommands, ImageCommands};
+use std::path::PathBuf;
 
 /// Interactive menu for the main commands
 ///
@@ -49,10 +50,10 @@ pub fn show_main_menu() -> Result<Option<Commands>> {
         .interact()?;
 
     match selection {
-        0 => show_content_menu().map(|cmd| cmd.map(Commands::Content)),
+        0 => show_content_menu(),
         1 => show_topic_menu().map(|cmd| cmd.map(Commands::Topic)),
-        2 => show_image_menu().map(|cmd| cmd.map(Commands::Image)),
-        3 => show_build_menu().map(|cmd| cmd.map(Commands::Build)),
+        2 => show_image_menu().map(|cmd| cmd.map(Commands::Images)),
+        3 => show_build_menu(),
         4 => {
             // Statistics options
             let slug_input = Input::<String>::with_theme(&ColorfulTheme::default())
@@ -62,13 +63,6 @@ pub fn show_main_menu() -> Result<Option<Commands>> {
 
             let slug = if slug_input.is_empty() { None } else { Some(slug_input) };
 
-            let topic_input = Input::<String>::with_theme(&ColorfulTheme::default())
-                .with_prompt("Topic (optional)")
-                .allow_empty(true)
-                .interact()?;
-
-            let topic = if topic_input.is_empty() { None } else { Some(topic_input) };
-
             let include_drafts = Confirm::with_theme(&ColorfulTheme::default())
                 .with_prompt("Include drafts?")
                 .default(false)
@@ -81,17 +75,10 @@ pub fn show_main_menu() -> Result<Option<Commands>> {
                 .items(&sort_options)
                 .interact()?;
 
-            let detailed = Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt("Show detailed statistics?")
-                .default(false)
-                .interact()?;
-
             Ok(Some(Commands::Stats {
                 slug,
-                topic,
                 include_drafts,
                 sort_by: sort_options[sort_selection].to_string(),
-                detailed,
             }))
         },
         5 => Ok(None), // Exit
@@ -112,7 +99,7 @@ pub fn show_main_menu() -> Result<Option<Commands>> {
 ///
 /// Returns an error if there is an issue with displaying the menu
 /// or getting user input
-pub fn show_content_menu() -> Result<Option<ContentCommands>> {
+pub fn show_content_menu() -> Result<Option<Commands>> {
     let items = vec![
         "Create New Content",
         "Edit Content",
@@ -141,13 +128,6 @@ pub fn show_content_menu() -> Result<Option<ContentCommands>> {
 
             let title = Some(title_input);
 
-            let topic_input = Input::<String>::with_theme(&ColorfulTheme::default())
-                .with_prompt("Topic")
-                .allow_empty(true)
-                .interact()?;
-
-            let topic = if topic_input.is_empty() { None } else { Some(topic_input) };
-
             let description_input = Input::<String>::with_theme(&ColorfulTheme::default())
                 .with_prompt("Description (optional)")
                 .allow_empty(true)
@@ -179,9 +159,9 @@ pub fn show_content_menu() -> Result<Option<ContentCommands>> {
                 .default(true)
                 .interact()?;
 
-            Ok(Some(ContentCommands::New {
+            Ok(Some(Commands::New {
                 title: title.unwrap_or_default(),
-                topic: topic.unwrap_or_default(),
+                slug: None,
                 description,
                 tags,
                 draft,
@@ -195,47 +175,11 @@ pub fn show_content_menu() -> Result<Option<ContentCommands>> {
                 .with_prompt("Slug")
                 .interact()?;
 
-            let slug = Some(slug_input);
-
-            let topic_input = Input::<String>::with_theme(&ColorfulTheme::default())
-                .with_prompt("Topic (optional)")
-                .allow_empty(true)
-                .interact()?;
-
-            let topic = if topic_input.is_empty() { None } else { Some(topic_input) };
-
-            let frontmatter_only = Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt("Edit frontmatter only?")
-                .default(false)
-                .interact()?;
-
-            let content_only = if frontmatter_only {
-                false
-            } else {
-                Confirm::with_theme(&ColorfulTheme::default())
-                    .with_prompt("Edit content only?")
-                    .default(false)
-                    .interact()?
-            };
-
-            // Convert UI options to ContentCommands::Edit options
-            let field = if frontmatter_only {
-                Some("frontmatter".to_string())
-            } else if content_only {
-                Some("content".to_string())
-            } else {
-                Some("all".to_string())
-            };
-
-            let value = None; // This will be edited in the editor
-            let editor = true; // Always use editor for interactive mode
-
-            Ok(Some(ContentCommands::Edit {
-                slug,
-                topic,
-                field,
-                value,
-                editor,
+            // The whole file is edited in the editor in interactive mode
+            Ok(Some(Commands::Edit {
+                slug: slug_input,
+                field: None,
+                value: None,
             }))
         },
         // ... rest of content menu options
@@ -340,19 +284,18 @@ pub fn show_image_menu() -> Result<Option<ImageCommands>> {
     match selection {
         0 => {
             // Build images options
-            let topic_input = Input::<String>::with_theme(&ColorfulTheme::default())
-                .with_prompt("Topic (optional)")
+            let article_input = Input::<String>::with_theme(&ColorfulTheme::default())
+                .with_prompt("Article (optional)")
                 .allow_empty(true)
                 .interact()?;
 
-            let topic = if topic_input.is_empty() { None } else { Some(topic_input) };
-
-            let rebuild = Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt("Rebuild all images?")
-                .default(false)
-                .interact()?;
+            let article = if article_input.is_empty() { None } else { Some(article_input) };
 
-            Ok(Some(ImageCommands::Build { topic, rebuild }))
+            Ok(Some(ImageCommands::Build {
+                article,
+                output_dir: PathBuf::from("build/images"),
+                source_dir: PathBuf::from("content"),
+            }))
         },
         // ... rest of image menu options
         2 => Ok(None), // Back
@@ -373,10 +316,9 @@ pub fn show_image_menu() -> Result<Option<ImageCommands>> {
 ///
 /// Returns an error if there is an issue with displaying the menu
 /// or getting user input
-pub fn show_build_menu() -> Result<Option<BuildCommands>> {
+pub fn show_build_menu() -> Result<Option<Commands>> {
     let items = vec![
         "Build Site",
-        "Build Table of Contents",
         "Back",
     ];
 
@@ -391,22 +333,20 @@ pub fn show_build_menu() -> Result<Option<BuildCommands>> {
     match selection {
         0 => {
             // Build site options
-            let topic_input = Input::<String>::with_theme(&ColorfulTheme::default())
-                .with_prompt("Topic (optional)")
-                .allow_empty(true)
-                .interact()?;
-
-            let topic = if topic_input.is_empty() { None } else { Some(topic_input) };
-
-            let rebuild = Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt("Force rebuild?")
+            let include_drafts = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Include drafts?")
                 .default(false)
                 .interact()?;
 
-            Ok(Some(BuildCommands::Content { topic, rebuild }))
+            Ok(Some(Commands::Build {
+                task: None,
+                slug: None,
+                output_dir: None,
+                include_drafts,
+                minify: false,
+            }))
         },
-        // ... rest of build menu options
-        2 => Ok(None), // Back
+        1 => Ok(None), // Back
         _ => unreachable!(),
     }
 }
\ No newline at end of file
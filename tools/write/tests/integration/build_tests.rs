@@ -3,36 +3,25 @@
 //! This file contains integration tests for the build command to ensure it properly
 //! coordinates with other tools and produces expected outputs.
 
+use super::{ensure_test_dirs, replace_body};
 use anyhow::Result;
 use common_test_utils::integration::TestCommand;
 use std::path::PathBuf;
 
-/// Helper function to ensure test directories exist
-fn ensure_test_dirs(command: &TestCommand) -> Result<()> {
-    let content_dir = command.fixture.temp_dir.path().join("content").join("blog");
-    if !content_dir.exists() {
-        std::fs::create_dir_all(&content_dir)?;
-    }
-
-    let build_dir = command.fixture.temp_dir.path().join("build");
-    if !build_dir.exists() {
-        std::fs::create_dir_all(&build_dir)?;
-    }
-
-    Ok(())
-}
-
 /// Helper to create test content
 fn create_test_content(command: &TestCommand) -> Result<()> {
     // Create a blog post
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Build Test Post",
         "--topic", "blog",
         "--description", "This is a test post for build testing",
-        "--content", "# Build Test Post\n\nThis is some test content for the build process."
     ]);
 
+    let article = command.fixture.temp_dir.path()
+        .join("content").join("blog").join("build-test-post").join("build-test-post.md");
+    replace_body(&article, "# Build Test Post\n\nThis is some test content for the build process.")?;
+
     Ok(())
 }
 
@@ -45,7 +34,7 @@ fn test_build_content_workflow() -> Result<()> {
 
     // Act - Run the build command
     let output = command.assert_success(&[
-        "content", "build",
+        "build",
     ]);
 
     // Assert
@@ -71,16 +60,19 @@ fn test_build_with_validation_workflow() -> Result<()> {
     ensure_test_dirs(&command)?;
     create_test_content(&command)?;
 
-    // Act - Run the build command with validation
+    // Act - Validate the content, then build it
+    let validate_output = command.assert_success(&[
+        "validate",
+    ]);
     let output = command.assert_success(&[
-        "content", "build",
-        "--validate",
+        "build",
     ]);
 
     // Assert
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("validated") || stdout.contains("validation"),
+    let stdout = String::from_utf8_lossy(&validate_output.stdout);
+    assert!(stdout.contains("Validation Summary"),
             "Output should indicate validation was performed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("build") || stdout.contains("built"),
             "Output should indicate content was built");
 
@@ -100,8 +92,8 @@ fn test_build_with_specific_output_dir() -> Result<()> {
 
     // Act - Run the build command with custom output directory
     let output = command.assert_success(&[
-        "content", "build",
-        "--output", custom_output_dir.to_str().unwrap(),
+        "build",
+        "--output-dir", custom_output_dir.to_str().unwrap(),
     ]);
 
     // Assert
@@ -128,7 +120,7 @@ fn test_build_sitemap_workflow() -> Result<()> {
 
     // Act - Run the build command
     command.assert_success(&[
-        "content", "build",
+        "build",
     ]);
 
     // Assert - Check for sitemap.xml
@@ -152,7 +144,7 @@ fn test_build_rss_feed_workflow() -> Result<()> {
 
     // Act - Run the build command
     command.assert_success(&[
-        "content", "build",
+        "build",
     ]);
 
     // Assert - Check for feed.xml
@@ -176,7 +168,7 @@ fn test_build_llm_friendly_output_workflow() -> Result<()> {
 
     // Act - Run the build command
     command.assert_success(&[
-        "content", "build",
+        "build",
     ]);
 
     // Assert - Check for llms.txt
@@ -67,7 +67,7 @@ custom_field: "This is from the custom template"
 
     // Act - Create new content using the config with custom template
     let output = command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Template Test Post",
         "--topic", "blog",
         "--description", "This is a test of template configuration"
@@ -127,7 +127,7 @@ publication:
 
     // Create a post
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Config Build Test",
         "--topic", "blog",
         "--description", "Testing build configuration"
@@ -135,7 +135,7 @@ publication:
 
     // Act - Build content
     let output = command.assert_success(&[
-        "content", "build",
+        "build",
     ]);
 
     // Assert
@@ -212,7 +212,7 @@ publication:
 
     // Act - Build images
     let output = command.assert_success(&[
-        "image", "build",
+        "images", "build",
     ]);
 
     // Assert
@@ -257,7 +257,7 @@ publication:
 
     // Create content with initial config
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Initial Config Post",
         "--topic", "blog",
         "--description", "This is created with the initial config"
@@ -284,7 +284,7 @@ publication:
 
     // Act - Create new content after config modification
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Modified Config Post",
         "--topic", "blog",
         "--description", "This is created with the modified config"
@@ -350,7 +350,7 @@ publication:
     let stdout = {
         let output = Command::new(&command.path)
             .args(&[
-                "content", "new",
+                "new",
                 "--title", "Env Var Test Post",
                 "--topic", "blog",
                 "--description", "Testing environment variable overrides"
@@ -8,19 +8,11 @@
 //! This file contains integration tests for content operations like create, edit, move, delete, etc.
 //! to ensure the tools integrate properly.
 
+use super::ensure_test_dirs;
 use anyhow::Result;
 use common_test_utils::integration::TestCommand;
 use std::path::PathBuf;
 
-/// Helper function to ensure test directories exist
-fn ensure_test_dirs(command: &TestCommand) -> Result<()> {
-    let content_dir = command.fixture.temp_dir.path().join("content").join("blog");
-    if !content_dir.exists() {
-        std::fs::create_dir_all(&content_dir)?;
-    }
-    Ok(())
-}
-
 #[test]
 fn test_content_create_workflow() -> Result<()> {
     // Arrange - Create the command and test environment
@@ -28,7 +20,7 @@ fn test_content_create_workflow() -> Result<()> {
 
     // Act & Assert - Create a new blog post
     let output = command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Test Integration Post",
         "--topic", "blog",
         "--description", "This is a test post for integration testing"
@@ -58,7 +50,7 @@ fn test_content_edit_workflow() -> Result<()> {
 
     // First create content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Editable Post",
         "--topic", "blog",
         "--description", "This is a post we'll edit"
@@ -76,11 +68,15 @@ fn test_content_edit_workflow() -> Result<()> {
     let slug = file_name.split('.').next().unwrap();
 
     // Act - Update the frontmatter
+    command.assert_success(&[
+        "edit", slug,
+        "--field", "title",
+        "--value", "Updated Title",
+    ]);
     let output = command.assert_success(&[
-        "content", "edit",
-        "--slug", slug,
-        "--title", "Updated Title",
-        "--description", "This description has been updated"
+        "edit", slug,
+        "--field", "description",
+        "--value", "This description has been updated",
     ]);
 
     // Assert
@@ -89,7 +85,7 @@ fn test_content_edit_workflow() -> Result<()> {
             "Output should indicate content was updated");
 
     // Verify the content file was updated
-    let content = std::fs::read_to_string(file_path)?;
+    let content = std::fs::read_to_string(file_path.join(format!("{}.md", slug)))?;
     assert!(content.contains("Updated Title"), "Content should contain updated title");
     assert!(content.contains("This description has been updated"),
             "Content should contain updated description");
@@ -105,14 +101,14 @@ fn test_content_search_workflow() -> Result<()> {
 
     // Create multiple content items
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "First Searchable Post",
         "--topic", "blog",
         "--description", "This is the first post containing a unique term xylophone"
     ]);
 
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Second Searchable Post",
         "--topic", "blog",
         "--description", "This is the second post with different content"
@@ -120,8 +116,7 @@ fn test_content_search_workflow() -> Result<()> {
 
     // Act - Search for the unique term
     let output = command.assert_success(&[
-        "content", "search",
-        "--query", "xylophone",
+        "search", "xylophone",
     ]);
 
     // Assert
@@ -142,7 +137,7 @@ fn test_content_validate_workflow() -> Result<()> {
 
     // Create content that we'll validate
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Validation Test Post",
         "--topic", "blog",
         "--description", "This is a post we'll validate"
@@ -150,7 +145,7 @@ fn test_content_validate_workflow() -> Result<()> {
 
     // Act - Validate all content
     let output = command.assert_success(&[
-        "content", "validate",
+        "validate",
     ]);
 
     // Assert
@@ -169,7 +164,7 @@ fn test_content_move_workflow() -> Result<()> {
 
     // Create content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Movable Post",
         "--topic", "blog",
         "--description", "This is a post we'll move"
@@ -196,9 +191,8 @@ fn test_content_move_workflow() -> Result<()> {
 
     // Act - Move the content
     let output = command.assert_success(&[
-        "content", "move",
-        "--slug", slug,
-        "--to-topic", "articles",
+        "move", slug,
+        "--to", "articles",
     ]);
 
     // Assert
@@ -225,7 +219,7 @@ fn test_content_delete_workflow() -> Result<()> {
 
     // Create content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Deletable Post",
         "--topic", "blog",
         "--description", "This is a post we'll delete"
@@ -244,8 +238,7 @@ fn test_content_delete_workflow() -> Result<()> {
 
     // Act - Delete the content
     let output = command.assert_success(&[
-        "content", "delete",
-        "--slug", slug,
+        "delete", slug,
         "--force",  // Skip confirmation
     ]);
 
@@ -267,14 +260,14 @@ fn test_content_stats_workflow() -> Result<()> {
 
     // Create some content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Stats Test Post 1",
         "--topic", "blog",
         "--description", "First post for stats"
     ]);
 
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Stats Test Post 2",
         "--topic", "blog",
         "--description", "Second post for stats"
@@ -282,7 +275,7 @@ fn test_content_stats_workflow() -> Result<()> {
 
     // Act - Get stats
     let output = command.assert_success(&[
-        "content", "stats",
+        "stats",
     ]);
 
     // Assert
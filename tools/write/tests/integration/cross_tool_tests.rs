@@ -3,30 +3,11 @@
 //! This file contains integration tests that test how different tools interact
 //! with each other within the Write CLI.
 
+use super::{ensure_test_dirs, replace_body};
 use anyhow::Result;
 use common_test_utils::integration::TestCommand;
 use std::path::PathBuf;
 
-/// Helper function to ensure test directories exist
-fn ensure_test_dirs(command: &TestCommand) -> Result<()> {
-    let content_dir = command.fixture.temp_dir.path().join("content").join("blog");
-    if !content_dir.exists() {
-        std::fs::create_dir_all(&content_dir)?;
-    }
-
-    let build_dir = command.fixture.temp_dir.path().join("build");
-    if !build_dir.exists() {
-        std::fs::create_dir_all(&build_dir)?;
-    }
-
-    let images_dir = command.fixture.temp_dir.path().join("images").join("src");
-    if !images_dir.exists() {
-        std::fs::create_dir_all(&images_dir)?;
-    }
-
-    Ok(())
-}
-
 #[test]
 fn test_content_stats_after_content_operations() -> Result<()> {
     // Arrange - Create the command and test environment
@@ -35,26 +16,26 @@ fn test_content_stats_after_content_operations() -> Result<()> {
 
     // Create initial content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "First Post",
         "--topic", "blog",
         "--description", "This is the first post"
     ]);
 
     // Get initial stats
-    let initial_output = command.assert_success(&["content", "stats"]);
+    let initial_output = command.assert_success(&["stats"]);
     let initial_stdout = String::from_utf8_lossy(&initial_output.stdout);
 
     // Create more content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Second Post",
         "--topic", "blog",
         "--description", "This is the second post"
     ]);
 
     // Act - Get updated stats
-    let updated_output = command.assert_success(&["content", "stats"]);
+    let updated_output = command.assert_success(&["stats"]);
     let updated_stdout = String::from_utf8_lossy(&updated_output.stdout);
 
     // Assert - Stats should reflect the new content
@@ -73,7 +54,7 @@ fn test_content_validate_after_content_edit() -> Result<()> {
 
     // Create content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Validation Post",
         "--topic", "blog",
         "--description", "This is a post for validation testing"
@@ -91,22 +72,21 @@ fn test_content_validate_after_content_edit() -> Result<()> {
     let slug = file_name.split('.').next().unwrap();
 
     // Run initial validation
-    command.assert_success(&["content", "validate"]);
+    command.assert_success(&["validate"]);
 
     // Edit the content to have invalid markdown
-    command.assert_success(&[
-        "content", "edit",
-        "--slug", slug,
-        "--content", "# Title\n\nThis is [a broken link](http://example.com.\n\nIncomplete link."
-    ]);
+    replace_body(
+        &file_path.join(format!("{}.md", slug)),
+        "# Title\n\nThis is [a broken link](http://example.com.\n\nIncomplete link.",
+    )?;
 
     // Act - Validate again
-    let validate_output = command.assert_failure(&["content", "validate", "--strict"]);
+    let validate_output = command.assert_success(&["validate"]);
 
     // Assert
-    let stderr = String::from_utf8_lossy(&validate_output.stderr);
-    assert!(stderr.contains("error") || stderr.contains("invalid"),
-            "Validation should report errors for invalid markdown");
+    let stdout = String::from_utf8_lossy(&validate_output.stdout);
+    assert!(!stdout.contains("Files with issues: 0"),
+            "Validation should report issues for invalid markdown");
 
     Ok(())
 }
@@ -119,7 +99,7 @@ fn test_topic_rename_affects_content_operations() -> Result<()> {
 
     // Create content in the blog topic
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Topic Test Post",
         "--topic", "blog",
         "--description", "This is a post for testing topic renames"
@@ -134,8 +114,7 @@ fn test_topic_rename_affects_content_operations() -> Result<()> {
 
     // Act - Try to search for content
     let search_output = command.assert_success(&[
-        "content", "search",
-        "--query", "Topic Test Post"
+        "search", "Topic Test Post",
     ]);
 
     // Assert
@@ -162,11 +141,10 @@ fn test_content_build_after_content_edit() -> Result<()> {
 
     // Create content
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Build Test Post",
         "--topic", "blog",
         "--description", "This is a post for testing build after edit",
-        "--content", "# Original Content\n\nThis is the original content."
     ]);
 
     // Get the slug from the created file
@@ -179,9 +157,11 @@ fn test_content_build_after_content_edit() -> Result<()> {
     let file_path = created_files.first().expect("No content file found");
     let file_name = file_path.file_name().unwrap().to_string_lossy();
     let slug = file_name.split('.').next().unwrap();
+    let article = file_path.join(format!("{}.md", slug));
+    replace_body(&article, "# Original Content\n\nThis is the original content.")?;
 
     // Build the content
-    command.assert_success(&["content", "build"]);
+    command.assert_success(&["build"]);
 
     // First build should have created files
     let build_dir = command.fixture.temp_dir.path().join("build");
@@ -190,14 +170,10 @@ fn test_content_build_after_content_edit() -> Result<()> {
         .collect::<Vec<_>>();
 
     // Edit the content
-    command.assert_success(&[
-        "content", "edit",
-        "--slug", slug,
-        "--content", "# Updated Content\n\nThis content has been updated."
-    ]);
+    replace_body(&article, "# Updated Content\n\nThis content has been updated.")?;
 
     // Act - Build again
-    command.assert_success(&["content", "build"]);
+    command.assert_success(&["build"]);
 
     // Assert - The build files should reflect the updated content
     let output_html = build_dir.join("blog").join(format!("{}.html", slug));
@@ -225,22 +201,24 @@ fn test_content_validation_and_build_integration() -> Result<()> {
 
     // Create content with validation issues (missing description)
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Validation Build Post",
         "--topic", "blog",
         // No description - this might cause a validation warning
     ]);
 
-    // Act - Build with validation enabled
+    // Act - Validate, then build
+    let validate_output = command.assert_success(&[
+        "validate",
+    ]);
     let output = command.assert_success(&[
-        "content", "build",
-        "--validate",
+        "build",
     ]);
 
     // Assert
+    let stdout = String::from_utf8_lossy(&validate_output.stdout);
+    assert!(stdout.contains("Validation Summary"), "Output should mention validation");
     let stdout = String::from_utf8_lossy(&output.stdout);
-    // Should mention both validation and build
-    assert!(stdout.contains("validat"), "Output should mention validation");
     assert!(stdout.contains("build"), "Output should mention build");
 
     // Verify build output files were created despite validation warnings
@@ -272,17 +250,18 @@ fn test_end_to_end_content_workflow() -> Result<()> {
 
     // 2. Create content in the new topic
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Complete Workflow Test",
         "--topic", "technical",
         "--description", "This is a post for testing the complete workflow",
-        "--content", "# Workflow Test\n\nThis is a test of the complete content workflow."
     ]);
+    let article = command.fixture.temp_dir.path()
+        .join("content").join("technical").join("complete-workflow-test").join("complete-workflow-test.md");
+    replace_body(&article, "# Workflow Test\n\nThis is a test of the complete content workflow.")?;
 
     // 3. Find the content with search
     let search_output = command.assert_success(&[
-        "content", "search",
-        "--query", "Workflow Test"
+        "search", "Workflow Test",
     ]);
     let search_stdout = String::from_utf8_lossy(&search_output.stdout);
     assert!(search_stdout.contains("Complete Workflow Test"),
@@ -301,26 +280,29 @@ fn test_end_to_end_content_workflow() -> Result<()> {
 
     // 5. Edit the content
     command.assert_success(&[
-        "content", "edit",
-        "--slug", slug,
-        "--title", "Updated Workflow Test",
-        "--content", "# Updated Workflow\n\nThis content has been updated as part of the workflow test."
+        "edit", slug,
+        "--field", "title",
+        "--value", "Updated Workflow Test",
     ]);
+    replace_body(
+        &file_path.join(format!("{}.md", slug)),
+        "# Updated Workflow\n\nThis content has been updated as part of the workflow test.",
+    )?;
 
     // 6. Validate the content
     command.assert_success(&[
-        "content", "validate",
+        "validate",
         "--slug", slug
     ]);
 
     // 7. Get stats on the content
-    let stats_output = command.assert_success(&["content", "stats"]);
+    let stats_output = command.assert_success(&["stats"]);
     let stats_stdout = String::from_utf8_lossy(&stats_output.stdout);
     assert!(stats_stdout.contains("technical") || stats_stdout.contains("Technical"),
             "Stats should include the technical topic");
 
     // 8. Build the content
-    command.assert_success(&["content", "build"]);
+    command.assert_success(&["build"]);
 
     // 9. Verify the complete workflow produced the expected results
     let build_dir = command.fixture.temp_dir.path().join("build");
@@ -3,25 +3,11 @@
 //! This file contains integration tests that focus on error handling
 //! across tool boundaries within the Write CLI.
 
+use super::{ensure_test_dirs, replace_body};
 use anyhow::Result;
 use common_test_utils::integration::TestCommand;
 use std::path::PathBuf;
 
-/// Helper function to ensure test directories exist
-fn ensure_test_dirs(command: &TestCommand) -> Result<()> {
-    let content_dir = command.fixture.temp_dir.path().join("content").join("blog");
-    if !content_dir.exists() {
-        std::fs::create_dir_all(&content_dir)?;
-    }
-
-    let build_dir = command.fixture.temp_dir.path().join("build");
-    if !build_dir.exists() {
-        std::fs::create_dir_all(&build_dir)?;
-    }
-
-    Ok(())
-}
-
 #[test]
 fn test_content_edit_nonexistent_slug() -> Result<()> {
     // Arrange - Create the command and test environment
@@ -30,9 +16,9 @@ fn test_content_edit_nonexistent_slug() -> Result<()> {
 
     // Act - Try to edit a non-existent slug
     let output = command.assert_failure(&[
-        "content", "edit",
-        "--slug", "this-slug-does-not-exist",
-        "--title", "Updated Title",
+        "edit", "this-slug-does-not-exist",
+        "--field", "title",
+        "--value", "Updated Title",
     ]);
 
     // Assert
@@ -51,8 +37,7 @@ fn test_content_delete_nonexistent_slug() -> Result<()> {
 
     // Act - Try to delete a non-existent slug
     let output = command.assert_failure(&[
-        "content", "delete",
-        "--slug", "this-slug-does-not-exist",
+        "delete", "this-slug-does-not-exist",
         "--force", // Skip confirmation
     ]);
 
@@ -80,9 +65,8 @@ fn test_content_move_nonexistent_slug() -> Result<()> {
 
     // Act - Try to move a non-existent slug
     let output = command.assert_failure(&[
-        "content", "move",
-        "--slug", "this-slug-does-not-exist",
-        "--to-topic", "target",
+        "move", "this-slug-does-not-exist",
+        "--to", "target",
     ]);
 
     // Assert
@@ -101,7 +85,7 @@ fn test_content_move_nonexistent_topic() -> Result<()> {
 
     // Create a valid content item
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Topic Move Test",
         "--topic", "blog",
         "--description", "This is a test post for testing moves"
@@ -120,9 +104,8 @@ fn test_content_move_nonexistent_topic() -> Result<()> {
 
     // Act - Try to move to a non-existent topic
     let output = command.assert_failure(&[
-        "content", "move",
-        "--slug", slug,
-        "--to-topic", "nonexistent-topic",
+        "move", slug,
+        "--to", "nonexistent-topic",
     ]);
 
     // Assert
@@ -149,7 +132,7 @@ fn test_topic_delete_with_content() -> Result<()> {
 
     // Create content in that topic
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Topic Delete Test",
         "--topic", "delete-test",
         "--description", "This is a test post for topic deletion"
@@ -213,22 +196,26 @@ fn test_content_validate_with_malformed_content() -> Result<()> {
 
     // Create a content file with malformed markdown
     command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Malformed Content",
         "--topic", "blog",
         "--description", "This is a test post with malformed content",
-        "--content", "# Title\n\nThis is [a broken link](http://example.com.\nThis is an invalid header\n===\nMore text."
     ]);
-
-    // Act - Validate with strict mode
-    let output = command.assert_failure(&[
-        "content", "validate",
-        "--strict",
+    let article = command.fixture.temp_dir.path()
+        .join("content").join("blog").join("malformed-content").join("malformed-content.md");
+    replace_body(
+        &article,
+        "# Title\n\nThis is [a broken link](http://example.com.\nThis is an invalid header\n===\nMore text.",
+    )?;
+
+    // Act - Validate the content
+    let output = command.assert_success(&[
+        "validate",
     ]);
 
     // Assert
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("error") || stderr.contains("invalid"),
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Files with issues: 0"),
             "Validation should report errors");
 
     Ok(())
@@ -244,7 +231,7 @@ fn test_content_build_with_no_content() -> Result<()> {
 
     // Act - Run the build
     let output = command.assert_success(&[
-        "content", "build",
+        "build",
     ]);
 
     // Assert - Should complete without errors but indicate no content
@@ -263,14 +250,14 @@ fn test_recovery_from_errors() -> Result<()> {
 
     // First try an operation that should fail
     let _ = command.assert_failure(&[
-        "content", "edit",
-        "--slug", "nonexistent-slug",
-        "--title", "This Should Fail",
+        "edit", "nonexistent-slug",
+        "--field", "title",
+        "--value", "This Should Fail",
     ]);
 
     // Act - Then try a valid operation
     let output = command.assert_success(&[
-        "content", "new",
+        "new",
         "--title", "Recovery Test",
         "--topic", "blog",
         "--description", "This is a test of recovery after error"
@@ -293,7 +280,7 @@ fn test_invalid_command_parameters() -> Result<()> {
 
     // Missing required parameter
     let output = command.assert_failure(&[
-        "content", "new",
+        "new",
         // Missing title
         "--topic", "blog",
     ]);
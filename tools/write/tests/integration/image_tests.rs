@@ -8,32 +8,12 @@
 //! This file contains integration tests for image operations to ensure the tools
 //! integrate properly with the Write CLI.
 
+use super::ensure_test_dirs;
 use anyhow::Result;
 use common_test_utils::integration::TestCommand;
 use std::path::PathBuf;
 use std::io::Write;
 
-/// Helper function to ensure test directories exist
-fn ensure_test_dirs(command: &TestCommand) -> Result<()> {
-    // Create image test directories
-    let images_dir = command.fixture.temp_dir.path().join("images");
-    if !images_dir.exists() {
-        std::fs::create_dir_all(&images_dir)?;
-    }
-
-    let images_src_dir = command.fixture.temp_dir.path().join("images").join("src");
-    if !images_src_dir.exists() {
-        std::fs::create_dir_all(&images_src_dir)?;
-    }
-
-    let images_build_dir = command.fixture.temp_dir.path().join("images").join("build");
-    if !images_build_dir.exists() {
-        std::fs::create_dir_all(&images_build_dir)?;
-    }
-
-    Ok(())
-}
-
 /// Helper to create a test image
 fn create_test_image(command: &TestCommand) -> Result<PathBuf> {
     // Create a simple test image
@@ -69,8 +49,9 @@ fn test_image_optimize_workflow() -> Result<()> {
 
     // Act - Run the image optimize command
     let output = command.assert_success(&[
-        "image", "optimize",
-        "--path", test_image_path.to_str().unwrap(),
+        "images", "optimize",
+        "--source", test_image_path.to_str().unwrap(),
+        "--article", "test-image",
     ]);
 
     // Assert
@@ -99,7 +80,7 @@ fn test_image_build_workflow() -> Result<()> {
 
     // Act - Run the image build command to process all images
     let output = command.assert_success(&[
-        "image", "build",
+        "images", "build",
     ]);
 
     // Assert
@@ -119,16 +100,17 @@ fn test_image_build_workflow() -> Result<()> {
 }
 
 #[test]
-fn test_image_build_with_format_options() -> Result<()> {
+fn test_image_build_with_output_dir() -> Result<()> {
     // Arrange - Create the command and test environment
     let command = TestCommand::new("write")?;
     ensure_test_dirs(&command)?;
     let _test_image_path = create_test_image(&command)?;
 
-    // Act - Run the image build command with specific format options
+    // Act - Run the image build command with a specific output directory
+    let image_build_dir = command.fixture.temp_dir.path().join("images").join("build");
     let output = command.assert_success(&[
-        "image", "build",
-        "--formats", "webp,avif",
+        "images", "build",
+        "--output-dir", image_build_dir.to_str().unwrap(),
     ]);
 
     // Assert
@@ -136,37 +118,28 @@ fn test_image_build_with_format_options() -> Result<()> {
     assert!(stdout.contains("build") || stdout.contains("built") || stdout.contains("processed"),
             "Output should indicate images were built/processed");
 
-    // Verify the image was processed in requested formats
-    let image_build_dir = command.fixture.temp_dir.path().join("images").join("build");
+    // Verify the build wrote to the requested directory
     let built_files = std::fs::read_dir(image_build_dir)?
         .filter_map(|e| e.ok())
-        .map(|e| e.path())
         .collect::<Vec<_>>();
 
-    // Check for webp format file (even if the actual conversion might not work in tests)
-    let has_requested_formats = built_files.iter()
-        .any(|path| {
-            let ext = path.extension().unwrap_or_default().to_string_lossy();
-            ext == "webp" || ext == "avif"
-        });
-
-    assert!(has_requested_formats || !built_files.is_empty(),
-            "Build should have attempted to create requested formats");
+    assert!(!built_files.is_empty(), "Build should have produced output files in the output directory");
 
     Ok(())
 }
 
 #[test]
-fn test_image_build_with_size_options() -> Result<()> {
+fn test_image_build_with_source_dir() -> Result<()> {
     // Arrange - Create the command and test environment
     let command = TestCommand::new("write")?;
     ensure_test_dirs(&command)?;
     let _test_image_path = create_test_image(&command)?;
 
-    // Act - Run the image build command with specific size options
+    // Act - Run the image build command with a specific source directory
+    let images_src_dir = command.fixture.temp_dir.path().join("images").join("src");
     let output = command.assert_success(&[
-        "image", "build",
-        "--sizes", "100,200,300",
+        "images", "build",
+        "--source-dir", images_src_dir.to_str().unwrap(),
     ]);
 
     // Assert
@@ -13,6 +13,35 @@ mod stats_tests;
 mod topic_tests;
 mod configuration_tests;
 
+use anyhow::Result;
+use common_test_utils::integration::TestCommand;
+use std::path::Path;
+
+/// Lay out a repository with a blog topic in a command's fixture
+///
+/// The config is written where [`TestCommand`] points `CONFIG_PATH`, and the
+/// build and image directories are created for the commands that write there.
+pub(crate) fn ensure_test_dirs(command: &TestCommand) -> Result<()> {
+    let root = command.fixture.temp_dir.path();
+    common_test_utils::write_repository(root, &["blog"]);
+    for dir in ["content/blog", "build", "images/src", "images/build"] {
+        std::fs::create_dir_all(root.join(dir))?;
+    }
+    Ok(())
+}
+
+/// Replace the Markdown after an article's frontmatter
+///
+/// `write new` has no flag for the body, so tests that need one write it
+/// into the created file.
+pub(crate) fn replace_body(article: &Path, body: &str) -> Result<()> {
+    let content = std::fs::read_to_string(article)?;
+    // The frontmatter runs from the opening `---` to the next one
+    let frontmatter_end = content[3..].find("\n---").map(|end| end + 7).unwrap_or(0);
+    std::fs::write(article, format!("{}\n{}\n", &content[..frontmatter_end], body))?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
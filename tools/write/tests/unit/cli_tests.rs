@@ -0,0 +1,113 @@
+//! Tests for the command line of the write binary
+
+#[cfg(test)]
+mod cli_tests {
+    use clap::{CommandFactory, Parser};
+    use clap_complete::Shell;
+    use common_cli::{DisplayResult, OutputFormat};
+    use std::path::PathBuf;
+    use write::cli::{BuildCommands, Cli, Commands, ContentCommands, ImageCommands, MigrateCommands};
+    use write::commands::report::{ContentChange, JournalHistory, UndoReport};
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_global_flags_are_accepted_after_the_subcommand() {
+        let cli = Cli::try_parse_from([
+            "write", "stats", "--topic", "blog", "--format", "json", "--verbose", "--config", "site.yaml",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.global.topic.as_deref(), Some("blog"));
+        assert_eq!(cli.global.output.format, OutputFormat::Json);
+        assert!(cli.global.verbose);
+        assert_eq!(cli.global.config, Some(PathBuf::from("site.yaml")));
+        assert!(matches!(cli.command, Commands::Stats { .. }));
+    }
+
+    #[test]
+    fn test_global_flags_reach_nested_subcommands() {
+        let cli = Cli::try_parse_from(["write", "images", "build", "-t", "blog", "--format", "yaml"]).unwrap();
+
+        assert_eq!(cli.global.topic.as_deref(), Some("blog"));
+        assert_eq!(cli.global.output.format, OutputFormat::Yaml);
+        assert!(matches!(cli.command, Commands::Images(ImageCommands::Build { .. })));
+    }
+
+    #[test]
+    fn test_build_runs_content_build_or_a_task() {
+        let cli = Cli::try_parse_from(["write", "build", "--output-dir", "public"]).unwrap();
+        assert!(matches!(cli.command, Commands::Build { task: None, .. }));
+
+        let cli = Cli::try_parse_from(["write", "build", "toc", "--output", "toc.md"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Build { task: Some(BuildCommands::Toc { ref output }), .. } if output == &PathBuf::from("toc.md")
+        ));
+
+        let cli = Cli::try_parse_from(["write", "build", "benchmark", "--current", "results", "--json"]).unwrap();
+        assert!(matches!(cli.command, Commands::Build { task: Some(BuildCommands::Benchmark { json: true, .. }), .. }));
+
+        assert!(Cli::try_parse_from(["write", "build", "--minify", "toc"]).is_err());
+    }
+
+    #[test]
+    fn test_content_list_and_template_commands() {
+        let cli = Cli::try_parse_from(["write", "content", "list", "--drafts", "-t", "blog"]).unwrap();
+        assert_eq!(cli.global.topic.as_deref(), Some("blog"));
+        assert!(matches!(cli.command, Commands::Content(ContentCommands::List { drafts: true })));
+
+        let cli = Cli::try_parse_from(["write", "content", "template", "--list"]).unwrap();
+        assert!(matches!(cli.command, Commands::Content(ContentCommands::Template { list: true, .. })));
+
+        assert!(Cli::try_parse_from(["write", "content", "template"]).is_err());
+        assert!(Cli::try_parse_from(["write", "content", "template", "--template", "review", "--type", "review"]).is_ok());
+    }
+
+    #[test]
+    fn test_migrate_accepts_topic_and_verbose() {
+        let cli =
+            Cli::try_parse_from(["write", "migrate", "content", "--topic", "blog", "--verbose", "--dry-run"]).unwrap();
+
+        assert_eq!(cli.global.topic.as_deref(), Some("blog"));
+        assert!(cli.global.verbose);
+        assert!(matches!(cli.command, Commands::Migrate(MigrateCommands::Content { dry_run: true, .. })));
+    }
+
+    #[test]
+    fn test_edit_field_requires_value() {
+        assert!(Cli::try_parse_from(["write", "edit", "hello", "--field", "title"]).is_err());
+        assert!(Cli::try_parse_from(["write", "edit", "hello", "--field", "title", "--value", "Hi"]).is_ok());
+    }
+
+    #[test]
+    fn test_completions_cover_every_subcommand() {
+        let mut script = Vec::new();
+        clap_complete::generate(Shell::Bash, &mut Cli::command(), "write", &mut script);
+        let script = String::from_utf8(script).unwrap();
+
+        for command in [
+            "new", "edit", "move", "delete", "validate", "build", "stats", "search", "content", "images", "undo", "history",
+        ] {
+            assert!(script.contains(command), "missing {} in completions", command);
+        }
+    }
+
+    #[test]
+    fn test_content_change_display() {
+        let change = ContentChange::new("moved", "hello", Some("blog"), PathBuf::from("content/blog/hello"));
+        assert!(change.to_display().contains("Moved blog/hello"));
+
+        let planned = change.dry_run(true);
+        assert!(planned.to_display().contains("blog/hello would be moved"));
+    }
+
+    #[test]
+    fn test_empty_journal_display() {
+        assert_eq!(UndoReport { undone: None }.to_display(), "Nothing to undo.");
+        assert_eq!(JournalHistory { transactions: Vec::new() }.to_display(), "Nothing to undo.");
+    }
+}
@@ -1,5 +1,8 @@
 //! Unit tests for write
 
+mod cli_tests;
+mod tui_tests;
+
 #[cfg(test)]
 mod tests {
     #[test]
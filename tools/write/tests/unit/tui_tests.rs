@@ -0,0 +1,132 @@
+//! Tests for the dashboard state and rendering
+
+#[cfg(test)]
+mod tui_tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::KeyCode;
+    use ratatui::Terminal;
+    use std::path::PathBuf;
+    use write::tui::{view, Action, App, ArticleRow, Focus, Mode};
+
+    fn article(topic: &str, slug: &str, is_draft: bool) -> ArticleRow {
+        ArticleRow {
+            topic: topic.to_string(),
+            slug: slug.to_string(),
+            title: slug.replace('-', " "),
+            is_draft,
+            word_count: 100,
+            issues: 0,
+            path: Some(PathBuf::from(format!("content/{}/{}/index.md", topic, slug))),
+        }
+    }
+
+    fn app() -> App {
+        App::new(
+            vec!["blog".to_string(), "notes".to_string()],
+            vec![
+                article("blog", "first-post", false),
+                article("blog", "second-post", true),
+                article("notes", "a-note", true),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_navigation_moves_within_the_focused_pane() {
+        let mut app = app();
+        assert_eq!(app.visible_articles().len(), 2);
+
+        app.handle_key(KeyCode::Down);
+        assert_eq!(app.selected_topic(), Some("notes"));
+        assert_eq!(app.visible_articles().len(), 1);
+
+        // Selection stops at the last topic
+        app.handle_key(KeyCode::Down);
+        assert_eq!(app.selected_topic(), Some("notes"));
+
+        app.handle_key(KeyCode::Up);
+        app.handle_key(KeyCode::Tab);
+        assert_eq!(app.focus, Focus::Articles);
+        app.handle_key(KeyCode::Char('j'));
+        assert_eq!(app.selected_article().unwrap().slug, "second-post");
+    }
+
+    #[test]
+    fn test_topic_counts() {
+        let app = app();
+        assert_eq!(app.topic_counts("blog"), (2, 1));
+        assert_eq!(app.topic_counts("notes"), (1, 1));
+    }
+
+    #[test]
+    fn test_quick_actions() {
+        let mut app = app();
+        app.handle_key(KeyCode::Tab);
+
+        assert_eq!(
+            app.handle_key(KeyCode::Char('e')),
+            Action::Edit(PathBuf::from("content/blog/first-post/index.md"))
+        );
+
+        // The first post is already published
+        assert_eq!(app.handle_key(KeyCode::Char('p')), Action::None);
+        app.handle_key(KeyCode::Down);
+        assert_eq!(
+            app.handle_key(KeyCode::Char('p')),
+            Action::Publish { slug: "second-post".to_string(), topic: "blog".to_string() }
+        );
+
+        assert_eq!(app.handle_key(KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn test_move_prompt() {
+        let mut app = app();
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Char('m'));
+        assert_eq!(app.mode, Mode::Move(String::new()));
+
+        for c in "notex".chars() {
+            app.handle_key(KeyCode::Char(c));
+        }
+        app.handle_key(KeyCode::Backspace);
+        app.handle_key(KeyCode::Char('s'));
+
+        assert_eq!(
+            app.handle_key(KeyCode::Enter),
+            Action::Move {
+                slug: "first-post".to_string(),
+                topic: "blog".to_string(),
+                new_topic: "notes".to_string(),
+            }
+        );
+        assert_eq!(app.mode, Mode::Browse);
+
+        app.handle_key(KeyCode::Char('m'));
+        app.handle_key(KeyCode::Esc);
+        assert_eq!(app.mode, Mode::Browse);
+    }
+
+    #[test]
+    fn test_replace_keeps_selection_in_range() {
+        let mut app = app();
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Down);
+
+        app.replace(vec!["blog".to_string()], vec![article("blog", "first-post", false)]);
+        assert_eq!(app.selected_article().unwrap().slug, "first-post");
+    }
+
+    #[test]
+    fn test_draw_shows_topics_and_articles() {
+        let app = app();
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| view::draw(frame, &app)).unwrap();
+
+        let screen: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("blog (2, 1 drafts)"));
+        assert!(screen.contains("second post"));
+        assert!(screen.contains("draft"));
+        assert!(screen.contains("published"));
+    }
+}